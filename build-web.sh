@@ -51,6 +51,10 @@ rm -rf web/space_looter.js
 rm -rf web/space_looter_bg.wasm
 rm -rf web/space_looter.d.ts
 
+# Build details baked into the binary (shown on the about screen)
+export SPACE_LOOTER_GIT_HASH="${SPACE_LOOTER_GIT_HASH:-$(git rev-parse --short HEAD 2>/dev/null)}"
+export SPACE_LOOTER_BUILD_DATE="${SPACE_LOOTER_BUILD_DATE:-$(date -u +%Y-%m-%d)}"
+
 # Build with wasm-pack
 echo -e "${BLUE}🔨 Building WASM package...${NC}"
 wasm-pack build \
@@ -111,6 +115,9 @@ sed -e "s/import('\.\/space_looter\.js')/import('.\/space_looter.js?v=${TIMESTAM
     -e "s/href=\"\.\/space_looter\.js\"/href=\".\/space_looter.js?v=${TIMESTAMP}\"/g" \
     web/index.html > dist/index.html
 
+# Service worker for offline play; it must sit next to index.html
+cp web/sw.js dist/
+
 if [ -f "pkg/space_looter.d.ts" ]; then
     cp pkg/space_looter.d.ts dist/
 fi
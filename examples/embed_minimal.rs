@@ -0,0 +1,145 @@
+//! Embed Minimal - Space Looter movement in an app of your own
+//!
+//! Builds a Bevy app from `DefaultPlugins` and only the core and movement
+//! plugins, puts a player on a five by five fixture map and moves it with
+//! the arrow keys (or WASD). Run it with:
+//!
+//! ```sh
+//! cargo run --example embed_minimal
+//! ```
+
+use bevy::prelude::*;
+use space_looter::domain::entities::map::MapTile;
+use space_looter::domain::value_objects::terrain::{Elevation, TerrainType};
+use space_looter::domain::value_objects::{EntityId, Position3D, TileCoordinate};
+use space_looter::domain::{Map, PlayerStats};
+use space_looter::infrastructure::bevy::resources::{MapResource, PlayerResource};
+use space_looter::presentation::map_renderer::PlayerMarker;
+use space_looter::presentation::movement::{tile_to_world_position, SmoothMovement};
+use space_looter::{SpaceLooterCorePlugin, SpaceLooterMovementPlugin};
+
+/// Tiles from the center to the edge of the fixture map
+const FIXTURE_RADIUS: i32 = 2;
+
+fn main() {
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins);
+    embed(&mut app);
+    app.add_systems(Startup, spawn_visuals_system.after(setup_fixture_system))
+        .run();
+}
+
+/// Add Space Looter's pieces and the fixture world, without any rendering
+fn embed(app: &mut App) {
+    app.add_plugins((SpaceLooterCorePlugin, SpaceLooterMovementPlugin::default()))
+        .add_systems(Startup, setup_fixture_system);
+}
+
+/// A small plains map, the player at its center
+fn setup_fixture_system(
+    mut commands: Commands,
+    mut map_resource: ResMut<MapResource>,
+    mut player_resource: ResMut<PlayerResource>,
+) {
+    let mut map = Map::new(EntityId::generate(), "Fixture".to_string(), 1952)
+        .expect("fixture map name is valid");
+    for x in -FIXTURE_RADIUS..=FIXTURE_RADIUS {
+        for y in -FIXTURE_RADIUS..=FIXTURE_RADIUS {
+            map.set_tile(
+                TileCoordinate::new(x, y, 0),
+                MapTile::new(TerrainType::Plains, Elevation::sea_level(), true),
+            );
+        }
+    }
+    // Nothing is generated beyond the fixture
+    map.mark_fixed_layout();
+    map_resource.load_map(map);
+
+    player_resource
+        .create_player(
+            "pilot".to_string(),
+            "Pilot".to_string(),
+            Position3D::origin(),
+            PlayerStats::starting_stats(),
+        )
+        .expect("fixture player is valid");
+    commands.spawn((
+        SmoothMovement::new(Position3D::origin()),
+        PlayerMarker,
+        Transform::from_translation(tile_to_world_position(Position3D::origin())),
+    ));
+}
+
+/// Camera, light and meshes for the map and the player
+fn spawn_visuals_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    players: Query<Entity, With<PlayerMarker>>,
+) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 12.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(4.0, 10.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    let tile = meshes.add(Cuboid::new(2.0, 0.2, 2.0));
+    let plains = materials.add(Color::srgb(0.45, 0.65, 0.3));
+    for x in -FIXTURE_RADIUS..=FIXTURE_RADIUS {
+        for y in -FIXTURE_RADIUS..=FIXTURE_RADIUS {
+            commands.spawn((
+                Mesh3d(tile.clone()),
+                MeshMaterial3d(plains.clone()),
+                Transform::from_translation(
+                    tile_to_world_position(Position3D::new(x, y, 0)) - Vec3::Y * 0.2,
+                ),
+            ));
+        }
+    }
+
+    // The fixture's player exists once the startup systems ran
+    let body = meshes.add(Capsule3d::new(0.4, 0.8));
+    let suit = materials.add(Color::srgb(0.9, 0.8, 0.2));
+    for player in players.iter() {
+        commands
+            .entity(player)
+            .insert((Mesh3d(body.clone()), MeshMaterial3d(suit.clone())));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headless_embed_steps_ten_frames() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(ButtonInput::<KeyCode>::default())
+            .insert_resource(ButtonInput::<MouseButton>::default());
+        embed(&mut app);
+        app.finish();
+        app.cleanup();
+
+        app.update();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ArrowRight);
+        for _ in 0..10 {
+            app.update();
+            // No input plugin clears the press between frames
+            app.world_mut()
+                .resource_mut::<ButtonInput<KeyCode>>()
+                .clear();
+        }
+
+        let mut players = app
+            .world_mut()
+            .query_filtered::<&SmoothMovement, With<PlayerMarker>>();
+        let movement = players.single(app.world()).unwrap();
+        assert_eq!(movement.target_position, Position3D::new(1, 0, 0));
+    }
+}
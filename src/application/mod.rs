@@ -5,9 +5,8 @@
 //! application services that coordinate between the domain and infrastructure layers.
 //!
 //! ## Architecture
-//! - **Use Cases**: Specific business operations (move player, spawn enemy, etc.)
+//! - **Use Cases**: Specific business operations (resolving encounters, etc.)
 //! - **Services**: Application-level services that coordinate domain operations
-//! - **DTOs**: Data Transfer Objects for communication between layers
 //!
 //! ## Rules
 //! - Can depend on domain layer
@@ -21,7 +20,7 @@ pub mod use_cases;
 // Re-export common application types
 pub use services::{GameSessionService, InputHandlerService};
 pub use use_cases::{
-    HandleEncounterUseCase, MovePlayerUseCase, SpawnEnemiesUseCase, UpdateScoreUseCase,
+    EncounterApproach, EncounterContext, EncounterOutcome, ResolveEncounterUseCase,
 };
 
 /// Application-specific error types
@@ -62,77 +61,6 @@ impl From<crate::domain::DomainError> for ApplicationError {
 /// Common result type for application operations
 pub type ApplicationResult<T> = Result<T, ApplicationError>;
 
-/// Input/Output DTOs for application layer
-pub mod dto {
-    use crate::domain::{Position, Score, Velocity};
-
-    /// Input for moving a player
-    #[derive(Debug, Clone)]
-    pub struct MovePlayerInput {
-        pub player_id: String,
-        pub direction_x: f32,
-        pub direction_y: f32,
-        pub speed: f32,
-        pub delta_time: f32,
-    }
-
-    /// Output from moving a player
-    #[derive(Debug, Clone)]
-    pub struct MovePlayerOutput {
-        pub player_id: String,
-        pub new_position: Position,
-        pub new_velocity: Velocity,
-    }
-
-    /// Input for spawning enemies
-    #[derive(Debug, Clone)]
-    pub struct SpawnEnemiesInput {
-        pub spawn_position: Position,
-        pub enemy_type: String,
-        pub velocity: Velocity,
-    }
-
-    /// Output from spawning enemies
-    #[derive(Debug, Clone)]
-    pub struct SpawnEnemiesOutput {
-        pub enemy_id: String,
-        pub position: Position,
-        pub velocity: Velocity,
-    }
-
-    /// Input for collision handling
-    #[derive(Debug, Clone)]
-    pub struct HandleCollisionInput {
-        pub entity1_id: String,
-        pub entity1_position: Position,
-        pub entity2_id: String,
-        pub entity2_position: Position,
-    }
-
-    /// Output from collision handling
-    #[derive(Debug, Clone)]
-    pub struct HandleCollisionOutput {
-        pub collision_detected: bool,
-        pub score_change: Option<u32>,
-        pub entities_to_remove: Vec<String>,
-    }
-
-    /// Input for score updates
-    #[derive(Debug, Clone)]
-    pub struct UpdateScoreInput {
-        pub session_id: String,
-        pub points_to_add: u32,
-    }
-
-    /// Output from score updates
-    #[derive(Debug, Clone)]
-    pub struct UpdateScoreOutput {
-        pub session_id: String,
-        pub old_score: Score,
-        pub new_score: Score,
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,188 @@
+//! Game Query Service - Read-Only Views of the Game State
+//!
+//! Builds plain, serializable data transfer objects from domain entities so
+//! external tooling (control channel, web bindings, debug overlays) can read
+//! the game state without holding references into the ECS world.
+
+use crate::domain::entities::{Map, Player};
+use crate::domain::services::GameLogService;
+use crate::domain::value_objects::{Position3D, ResourceType, TerrainType, TileCoordinate};
+use serde::{Deserialize, Serialize};
+
+/// Snapshot of the player character
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+    pub name: String,
+    pub level: u32,
+    pub experience: u32,
+    pub position: Position3D,
+    pub movement_points: u8,
+    pub max_movement_points: u8,
+    pub action_points: u8,
+    pub resources: Vec<ResourceSnapshot>,
+}
+
+/// Amount of a single resource type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceSnapshot {
+    pub resource_type: ResourceType,
+    pub amount: u32,
+}
+
+/// Snapshot of a single loaded map tile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TileSnapshot {
+    pub position: Position3D,
+    pub terrain: TerrainType,
+    pub elevation: i32,
+    pub explored: bool,
+    pub passable: bool,
+    pub movement_cost: u8,
+    pub resource_node: Option<ResourceType>,
+}
+
+/// A single game log entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogEntrySnapshot {
+    pub sequence: u64,
+    pub category: String,
+    pub text: String,
+}
+
+/// Log entries newer than a requested sequence
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogSnapshot {
+    pub entries: Vec<LogEntrySnapshot>,
+    pub truncated: bool,
+}
+
+/// Stateless service building query DTOs from domain entities
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GameQueryService;
+
+impl GameQueryService {
+    /// Create a new query service
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Build a snapshot of the player
+    pub fn player_snapshot(&self, player: &Player) -> PlayerSnapshot {
+        let mut resources: Vec<ResourceSnapshot> = player
+            .resources()
+            .amounts()
+            .into_iter()
+            .filter(|amount| amount.amount > 0)
+            .map(|amount| ResourceSnapshot {
+                resource_type: amount.resource_type,
+                amount: amount.amount,
+            })
+            .collect();
+        resources.sort_by_key(|resource| resource.resource_type as u8);
+
+        PlayerSnapshot {
+            name: player.name().to_string(),
+            level: player.level(),
+            experience: player.experience().points(),
+            position: *player.position(),
+            movement_points: player.movement_points(),
+            max_movement_points: player.max_movement_points(),
+            action_points: player.action_points(),
+            resources,
+        }
+    }
+
+    /// Build snapshots of loaded tiles within a radius, ordered by position
+    pub fn tiles_around(&self, map: &Map, center: Position3D, radius: u32) -> Vec<TileSnapshot> {
+        let mut tiles: Vec<TileSnapshot> = center
+            .positions_within_distance(radius)
+            .into_iter()
+            .filter_map(|position| {
+                map.get_tile(&TileCoordinate::from(position))
+                    .map(|tile| TileSnapshot {
+                        position,
+                        terrain: tile.terrain_type,
+                        elevation: tile.elevation.height,
+                        explored: tile.is_explored(),
+                        passable: tile.terrain_type.is_passable(),
+                        movement_cost: tile.terrain_type.movement_cost(),
+                        resource_node: map
+                            .get_resource_node(&position)
+                            .map(|node| node.properties().resource_type),
+                    })
+            })
+            .collect();
+        tiles.sort_by_key(|tile| (tile.position.y, tile.position.x));
+        tiles
+    }
+
+    /// Build a snapshot of log entries newer than `after`
+    pub fn log_since(&self, game_log: &GameLogService, after: Option<u64>) -> LogSnapshot {
+        let slice = game_log.messages_since(after);
+        LogSnapshot {
+            entries: slice
+                .messages
+                .into_iter()
+                .map(|message| LogEntrySnapshot {
+                    sequence: message.sequence,
+                    category: format!("{:?}", message.log_type),
+                    text: message.display_text(),
+                })
+                .collect(),
+            truncated: slice.truncated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::MapTile;
+    use crate::domain::services::GameLogType;
+    use crate::domain::value_objects::{terrain::Elevation, EntityId};
+
+    #[test]
+    fn player_snapshot_reflects_player() {
+        let player =
+            Player::create_new_character("Scout".to_string(), Position3D::new(2, 3, 0)).unwrap();
+        let snapshot = GameQueryService::new().player_snapshot(&player);
+
+        assert_eq!(snapshot.name, "Scout");
+        assert_eq!(snapshot.position, Position3D::new(2, 3, 0));
+        assert_eq!(snapshot.movement_points, player.movement_points());
+        assert!(snapshot.resources.iter().all(|r| r.amount > 0));
+    }
+
+    #[test]
+    fn tiles_around_only_reports_loaded_tiles() {
+        let mut map = Map::new(EntityId::generate(), "Test".to_string(), 1).unwrap();
+        map.set_tile(
+            TileCoordinate::new(0, 0, 0),
+            MapTile::new(TerrainType::Plains, Elevation::sea_level(), true),
+        );
+        map.set_tile(
+            TileCoordinate::new(1, 0, 0),
+            MapTile::new(TerrainType::Ocean, Elevation::sea_level(), false),
+        );
+
+        let tiles = GameQueryService::new().tiles_around(&map, Position3D::origin(), 2);
+
+        assert_eq!(tiles.len(), 2);
+        assert!(tiles[0].passable);
+        assert!(!tiles[1].passable);
+    }
+
+    #[test]
+    fn log_since_returns_only_newer_entries() {
+        let mut game_log = GameLogService::new();
+        game_log.log_message("first".to_string(), GameLogType::System);
+        let first = game_log.latest_sequence();
+        game_log.log_message("second".to_string(), GameLogType::System);
+
+        let snapshot = GameQueryService::new().log_since(&game_log, first);
+
+        assert_eq!(snapshot.entries.len(), 1);
+        assert_eq!(snapshot.entries[0].text, "second");
+        assert!(!snapshot.truncated);
+    }
+}
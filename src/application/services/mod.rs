@@ -7,6 +7,9 @@
 //! ## Architecture
 //! - **Game Session Service**: Manages game session lifecycle and state
 //! - **Input Handler Service**: Processes and validates user input
+//! - **Game Query Service**: Builds serializable read-only snapshots of game state
+//! - **Share Codes**: Encodes and checks the strings players share a run's start with
+//! - **Warning State Service**: Rates how close the player is to dying or starving
 //!
 //! ## Rules
 //! - Coordinate between use cases and domain services
@@ -14,12 +17,20 @@
 //! - Handle application-specific business logic
 //! - No direct infrastructure dependencies
 
+pub mod game_query;
 pub mod game_session;
 pub mod input_handler;
+pub mod share_code;
+pub mod warning_state;
 
 // Re-export services for convenience
+pub use game_query::{
+    GameQueryService, LogEntrySnapshot, LogSnapshot, PlayerSnapshot, ResourceSnapshot, TileSnapshot,
+};
 pub use game_session::GameSessionService;
 pub use input_handler::InputHandlerService;
+pub use share_code::{ShareCode, ShareCodeError, SharedCharacter};
+pub use warning_state::{VignetteStyle, Warning, WarningInputs, WarningStateService, WarningTone};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,405 @@
+//! Share Codes - A run's start as a string players can pass around
+//!
+//! A share code holds the world seed, the difficulty, the mutators, the
+//! starting scenario and, optionally, the starting character's name and
+//! stats. The fields are packed into bytes behind a format version,
+//! followed by a checksum, and written as URL-safe base64 without padding
+//! so a code survives chat clients and address bars. Version 1 codes
+//! predate scenarios and read as a Standard Drop.
+//!
+//! Decoding reports what failed: text that is not a code at all, a version
+//! this build cannot read, a checksum that does not match, or a character
+//! `PlayerStats::new` refuses, so a hand-edited code cannot start a run
+//! with an illegal build.
+
+use crate::domain::entities::game::DifficultyLevel;
+use crate::domain::services::hashing::fnv1a_32;
+use crate::domain::services::{Mutator, Mutators, STANDARD_DROP};
+use crate::domain::value_objects::PlayerStats;
+
+/// Format version written by this build
+///
+/// - v1: seed, difficulty, mutators and character
+/// - v2: adds the starting scenario
+pub const SHARE_CODE_VERSION: u8 = 2;
+
+/// Longest character name a share code carries, in bytes
+pub const SHARE_CODE_MAX_NAME_LEN: usize = 24;
+
+const SHARE_CODE_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Bytes of the checksum closing every code
+const CHECKSUM_LEN: usize = 4;
+
+/// Why a share code could not be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShareCodeError {
+    /// Not base64, or too short or long for its fields
+    Malformed(String),
+    /// Written by a build with another format version
+    UnsupportedVersion(u8),
+    /// The checksum does not match; the code was mistyped or edited
+    BadChecksum,
+    /// The character's stats are not a legal build
+    InvalidStats(String),
+    /// The starting scenario is not known to this build
+    UnknownScenario(String),
+}
+
+impl std::fmt::Display for ShareCodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShareCodeError::Malformed(reason) => write!(f, "not a share code: {}", reason),
+            ShareCodeError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported share code version {} (this build reads version {})",
+                version, SHARE_CODE_VERSION
+            ),
+            ShareCodeError::BadChecksum => write!(f, "bad checksum, the code was changed"),
+            ShareCodeError::InvalidStats(reason) => write!(f, "illegal character: {}", reason),
+            ShareCodeError::UnknownScenario(id) => write!(f, "unknown starting scenario {}", id),
+        }
+    }
+}
+
+impl std::error::Error for ShareCodeError {}
+
+/// The starting character of a shared run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedCharacter {
+    pub name: String,
+    pub stats: PlayerStats,
+}
+
+/// Everything a shared run starts from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareCode {
+    pub seed: u64,
+    pub difficulty: DifficultyLevel,
+    pub mutators: Mutators,
+    /// Id of the starting scenario
+    pub scenario: String,
+    pub character: Option<SharedCharacter>,
+}
+
+impl ShareCode {
+    /// The code as text
+    ///
+    /// Names longer than `SHARE_CODE_MAX_NAME_LEN` bytes are cut at the
+    /// last character that fits.
+    pub fn encode(&self) -> String {
+        let mut bytes = vec![SHARE_CODE_VERSION];
+        bytes.extend_from_slice(&self.seed.to_be_bytes());
+        bytes.push(difficulty_code(self.difficulty));
+        bytes.push(
+            self.mutators
+                .iter()
+                .fold(0u8, |bits, mutator| bits | (1 << (mutator.code() - 1))),
+        );
+        match &self.character {
+            Some(character) => {
+                let stats = character.stats;
+                bytes.push(1);
+                bytes.extend_from_slice(&[
+                    stats.strength,
+                    stats.dexterity,
+                    stats.intelligence,
+                    stats.charisma,
+                    stats.luck,
+                    stats.endurance,
+                ]);
+                let name = truncate_name(&character.name, SHARE_CODE_MAX_NAME_LEN);
+                bytes.push(name.len() as u8);
+                bytes.extend_from_slice(name.as_bytes());
+            }
+            None => bytes.push(0),
+        }
+        let scenario = truncate_name(&self.scenario, u8::MAX as usize);
+        bytes.push(scenario.len() as u8);
+        bytes.extend_from_slice(scenario.as_bytes());
+        bytes.extend_from_slice(&fnv1a_32(&bytes).to_be_bytes());
+        encode_base64(&bytes)
+    }
+
+    /// Read a code, ignoring surrounding whitespace
+    pub fn decode(text: &str) -> Result<Self, ShareCodeError> {
+        let bytes = decode_base64(text.trim())?;
+        let Some(&version) = bytes.first() else {
+            return Err(ShareCodeError::Malformed("the code is empty".to_string()));
+        };
+        if !(1..=SHARE_CODE_VERSION).contains(&version) {
+            return Err(ShareCodeError::UnsupportedVersion(version));
+        }
+        if bytes.len() < 1 + CHECKSUM_LEN {
+            return Err(ShareCodeError::Malformed(
+                "the code is cut short".to_string(),
+            ));
+        }
+        let (body, sum) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+        if sum != fnv1a_32(body).to_be_bytes() {
+            return Err(ShareCodeError::BadChecksum);
+        }
+
+        let mut reader = ByteReader { bytes: &body[1..] };
+        let seed = u64::from_be_bytes(reader.take_array()?);
+        let difficulty = difficulty_from_code(reader.take_byte()?)?;
+        let mutator_bits = reader.take_byte()?;
+        let mutators = Mutators::new(
+            Mutator::all()
+                .into_iter()
+                .filter(|mutator| mutator_bits & (1 << (mutator.code() - 1)) != 0),
+        );
+        let character = match reader.take_byte()? {
+            0 => None,
+            1 => {
+                let [strength, dexterity, intelligence, charisma, luck, endurance] =
+                    reader.take_array()?;
+                let stats =
+                    PlayerStats::new(strength, dexterity, intelligence, charisma, luck, endurance)
+                        .map_err(|e| ShareCodeError::InvalidStats(e.to_string()))?;
+                let name_len = reader.take_byte()? as usize;
+                let name = String::from_utf8(reader.take(name_len)?.to_vec())
+                    .map_err(|_| ShareCodeError::Malformed("the name is not text".to_string()))?;
+                if name.trim().is_empty() || name_len > SHARE_CODE_MAX_NAME_LEN {
+                    return Err(ShareCodeError::InvalidStats(format!(
+                        "the character needs a name of at most {} bytes",
+                        SHARE_CODE_MAX_NAME_LEN
+                    )));
+                }
+                Some(SharedCharacter { name, stats })
+            }
+            flag => {
+                return Err(ShareCodeError::Malformed(format!(
+                    "unknown character flag {}",
+                    flag
+                )))
+            }
+        };
+        let scenario = if version >= 2 {
+            let len = reader.take_byte()? as usize;
+            String::from_utf8(reader.take(len)?.to_vec())
+                .map_err(|_| ShareCodeError::Malformed("the scenario is not text".to_string()))?
+        } else {
+            STANDARD_DROP.to_string()
+        };
+        if !reader.bytes.is_empty() {
+            return Err(ShareCodeError::Malformed(
+                "unexpected trailing data".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            seed,
+            difficulty,
+            mutators,
+            scenario,
+            character,
+        })
+    }
+
+    /// One-line description for the log
+    pub fn summary(&self) -> String {
+        let mut parts = vec![
+            format!("seed {}", self.seed),
+            format!("{:?}", self.difficulty),
+        ];
+        if !self.mutators.is_empty() {
+            parts.push(self.mutators.names());
+        }
+        if self.scenario != STANDARD_DROP {
+            parts.push(format!("start {}", self.scenario));
+        }
+        if let Some(character) = &self.character {
+            parts.push(character.name.clone());
+        }
+        parts.join(", ")
+    }
+}
+
+/// Fields read in order from the code's body
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ShareCodeError> {
+        if self.bytes.len() < len {
+            return Err(ShareCodeError::Malformed(
+                "the code is cut short".to_string(),
+            ));
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn take_byte(&mut self) -> Result<u8, ShareCodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], ShareCodeError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+}
+
+fn difficulty_code(difficulty: DifficultyLevel) -> u8 {
+    match difficulty {
+        DifficultyLevel::Easy => 1,
+        DifficultyLevel::Normal => 2,
+        DifficultyLevel::Hard => 3,
+        DifficultyLevel::Expert => 4,
+        DifficultyLevel::Nightmare => 5,
+    }
+}
+
+fn difficulty_from_code(code: u8) -> Result<DifficultyLevel, ShareCodeError> {
+    match code {
+        1 => Ok(DifficultyLevel::Easy),
+        2 => Ok(DifficultyLevel::Normal),
+        3 => Ok(DifficultyLevel::Hard),
+        4 => Ok(DifficultyLevel::Expert),
+        5 => Ok(DifficultyLevel::Nightmare),
+        _ => Err(ShareCodeError::Malformed(format!(
+            "unknown difficulty {}",
+            code
+        ))),
+    }
+}
+
+/// `name` cut to at most `max` bytes, on a character boundary
+fn truncate_name(name: &str, max: usize) -> &str {
+    let mut end = name.len().min(max);
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    &name[..end]
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |group, (index, &byte)| {
+                group | ((byte as u32) << (16 - 8 * index))
+            });
+        for index in 0..=chunk.len() {
+            let digit = (group >> (18 - 6 * index)) & 63;
+            text.push(SHARE_CODE_ALPHABET[digit as usize] as char);
+        }
+    }
+    text
+}
+
+fn decode_base64(text: &str) -> Result<Vec<u8>, ShareCodeError> {
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for digit in text.bytes() {
+        let value = SHARE_CODE_ALPHABET
+            .iter()
+            .position(|&candidate| candidate == digit)
+            .ok_or_else(|| {
+                ShareCodeError::Malformed(format!("'{}' is not part of a code", digit as char))
+            })?;
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code() -> ShareCode {
+        ShareCode {
+            seed: 0xdead_beef_0042,
+            difficulty: DifficultyLevel::Hard,
+            mutators: Mutators::new([Mutator::GlassCannon, Mutator::NightOwl]),
+            scenario: "crash_survivor".to_string(),
+            character: Some(SharedCharacter {
+                name: "Vega".to_string(),
+                stats: PlayerStats::new(14, 12, 8, 10, 16, 9).unwrap(),
+            }),
+        }
+    }
+
+    /// A code with the given body bytes and a correct checksum
+    fn with_checksum(mut body: Vec<u8>) -> String {
+        body.extend_from_slice(&fnv1a_32(&body).to_be_bytes());
+        encode_base64(&body)
+    }
+
+    #[test]
+    fn codes_round_trip_with_and_without_a_character() {
+        let shared = code();
+        let text = shared.encode();
+        assert!(text.bytes().all(|byte| SHARE_CODE_ALPHABET.contains(&byte)));
+        assert_eq!(
+            ShareCode::decode(&format!("  {}\n", text)),
+            Ok(shared.clone())
+        );
+
+        let bare = ShareCode {
+            seed: 7,
+            difficulty: DifficultyLevel::Normal,
+            mutators: Mutators::default(),
+            scenario: STANDARD_DROP.to_string(),
+            character: None,
+        };
+        assert_eq!(ShareCode::decode(&bare.encode()), Ok(bare));
+
+        // A version 1 code has no scenario and starts a Standard Drop
+        let mut v1 = decode_base64(&shared.encode()).unwrap();
+        v1.truncate(v1.len() - CHECKSUM_LEN - 1 - "crash_survivor".len());
+        v1[0] = 1;
+        let old = ShareCode::decode(&with_checksum(v1)).unwrap();
+        assert_eq!(old.scenario, STANDARD_DROP);
+        assert_eq!(old.character, shared.character);
+    }
+
+    #[test]
+    fn tampered_and_foreign_codes_say_what_failed() {
+        let mut bytes = decode_base64(&code().encode()).unwrap();
+        bytes[3] ^= 0x10;
+        assert_eq!(
+            ShareCode::decode(&encode_base64(&bytes)),
+            Err(ShareCodeError::BadChecksum)
+        );
+
+        let mut future = decode_base64(&code().encode()).unwrap();
+        future.truncate(future.len() - CHECKSUM_LEN);
+        future[0] = SHARE_CODE_VERSION + 1;
+        assert_eq!(
+            ShareCode::decode(&with_checksum(future)),
+            Err(ShareCodeError::UnsupportedVersion(SHARE_CODE_VERSION + 1))
+        );
+
+        assert!(matches!(
+            ShareCode::decode("not a code!"),
+            Err(ShareCodeError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn illegal_builds_are_refused_even_with_a_valid_checksum() {
+        let mut body = decode_base64(&code().encode()).unwrap();
+        body.truncate(body.len() - CHECKSUM_LEN);
+        // Version, seed, difficulty and mutators come before the stats
+        body[12] = 25;
+        assert!(matches!(
+            ShareCode::decode(&with_checksum(body)),
+            Err(ShareCodeError::InvalidStats(_))
+        ));
+    }
+}
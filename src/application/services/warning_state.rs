@@ -0,0 +1,203 @@
+//! Warning State Service - How close the player is to dying or starving
+//!
+//! Reads the player through the query service and turns low health and an
+//! empty Food hold into one severity between 0 and 1, the strongest of the
+//! conditions, with the tone of the condition that set it. Low health only
+//! clears once health climbs a band above its threshold, so hovering at the
+//! boundary does not flicker the warning; starving ends the moment Food is
+//! back. The service also picks how the screen-edge vignette is drawn for
+//! the player's accessibility settings.
+
+use crate::application::services::{GameQueryService, PlayerSnapshot};
+use crate::domain::constants::{
+    WARNING_HEALTH_THRESHOLD, WARNING_HYSTERESIS_BAND, WARNING_MIN_SEVERITY,
+    WARNING_STARVING_SEVERITY,
+};
+use crate::domain::entities::Player;
+use crate::domain::value_objects::ResourceType;
+
+/// Values the warning depends on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WarningInputs {
+    /// Share of health left, if the player has health at all
+    pub health: Option<f32>,
+    /// Food in the cargo hold
+    pub food: u32,
+}
+
+impl WarningInputs {
+    /// Inputs read from a player snapshot
+    pub fn from_snapshot(snapshot: &PlayerSnapshot, health: Option<f32>) -> Self {
+        let food = snapshot
+            .resources
+            .iter()
+            .find(|resource| resource.resource_type == ResourceType::Food)
+            .map(|resource| resource.amount)
+            .unwrap_or(0);
+        Self { health, food }
+    }
+}
+
+/// Which condition a warning is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarningTone {
+    /// Out of Food: amber
+    Amber,
+    /// Low health: red
+    Red,
+}
+
+/// An active warning
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Warning {
+    /// Between [`WARNING_MIN_SEVERITY`] and 1
+    pub severity: f32,
+    pub tone: WarningTone,
+}
+
+/// How the vignette is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VignetteStyle {
+    /// Solid border instead of a translucent band
+    pub border: bool,
+    /// Opacity pulses slowly instead of holding still
+    pub pulsing: bool,
+}
+
+impl VignetteStyle {
+    /// Style for the reduce-motion and high contrast settings
+    pub fn select(reduce_motion: bool, high_contrast: bool) -> Self {
+        Self {
+            border: high_contrast,
+            pulsing: !reduce_motion,
+        }
+    }
+}
+
+/// Tracks the warning between updates for its hysteresis
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WarningStateService {
+    low_health: bool,
+    current: Option<Warning>,
+}
+
+impl WarningStateService {
+    /// Create a service with no warning active
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Warning currently active
+    pub fn current(&self) -> Option<Warning> {
+        self.current
+    }
+
+    /// Recompute the warning from the player; health is the share left, or
+    /// `None` to rate starving alone
+    pub fn update_from_player(&mut self, player: &Player, health: Option<f32>) -> Option<Warning> {
+        let snapshot = GameQueryService::new().player_snapshot(player);
+        self.update(WarningInputs::from_snapshot(&snapshot, health))
+    }
+
+    /// Recompute the warning from new inputs
+    pub fn update(&mut self, inputs: WarningInputs) -> Option<Warning> {
+        let health = inputs.health.map(|health| health.clamp(0.0, 1.0));
+        self.low_health = match health {
+            Some(health) if self.low_health => {
+                health < WARNING_HEALTH_THRESHOLD + WARNING_HYSTERESIS_BAND
+            }
+            Some(health) => health < WARNING_HEALTH_THRESHOLD,
+            None => false,
+        };
+
+        let health_warning = health.filter(|_| self.low_health).map(|health| Warning {
+            severity: (1.0 - health / WARNING_HEALTH_THRESHOLD).max(WARNING_MIN_SEVERITY),
+            tone: WarningTone::Red,
+        });
+        let food_warning = (inputs.food == 0).then_some(Warning {
+            severity: WARNING_STARVING_SEVERITY,
+            tone: WarningTone::Amber,
+        });
+
+        self.current = match (health_warning, food_warning) {
+            (Some(health), Some(food)) if food.severity > health.severity => Some(food),
+            (Some(health), _) => Some(health),
+            (None, food) => food,
+        };
+        self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(health: Option<f32>, food: u32) -> WarningInputs {
+        WarningInputs { health, food }
+    }
+
+    #[test]
+    fn severity_is_the_strongest_condition() {
+        let mut service = WarningStateService::new();
+        assert_eq!(service.update(inputs(Some(0.8), 5)), None);
+        assert_eq!(service.update(inputs(None, 5)), None);
+
+        let starving = service.update(inputs(None, 0)).unwrap();
+        assert_eq!(starving.tone, WarningTone::Amber);
+        assert_eq!(starving.severity, WARNING_STARVING_SEVERITY);
+
+        // Nearly dead outweighs starving, barely hurt does not
+        let dying = WarningStateService::new()
+            .update(inputs(Some(0.0), 0))
+            .unwrap();
+        assert_eq!((dying.severity, dying.tone), (1.0, WarningTone::Red));
+        let hurt = WarningStateService::new()
+            .update(inputs(Some(0.2), 0))
+            .unwrap();
+        assert_eq!(hurt.tone, WarningTone::Amber);
+        let hurt = WarningStateService::new()
+            .update(inputs(Some(0.2), 3))
+            .unwrap();
+        assert_eq!(
+            (hurt.severity, hurt.tone),
+            (WARNING_MIN_SEVERITY, WarningTone::Red)
+        );
+
+        // Food back clears the warning at once
+        assert!(service.update(inputs(None, 0)).is_some());
+        assert_eq!(service.update(inputs(None, 1)), None);
+    }
+
+    #[test]
+    fn low_health_needs_the_band_to_clear() {
+        let mut service = WarningStateService::new();
+        assert!(service.update(inputs(Some(0.26), 5)).is_none());
+        assert!(service.update(inputs(Some(0.24), 5)).is_some());
+        // Back over the threshold but inside the band: still warning
+        assert!(service.update(inputs(Some(0.26), 5)).is_some());
+        assert!(service.update(inputs(Some(0.29), 5)).is_some());
+        assert!(service.update(inputs(Some(0.30), 5)).is_none());
+        assert!(service.update(inputs(Some(0.27), 5)).is_none());
+        assert_eq!(service.current(), None);
+    }
+
+    #[test]
+    fn vignette_follows_the_accessibility_settings() {
+        assert_eq!(
+            VignetteStyle::select(false, false),
+            VignetteStyle {
+                border: false,
+                pulsing: true
+            }
+        );
+        assert!(!VignetteStyle::select(true, false).pulsing);
+        assert!(VignetteStyle::select(false, true).border);
+        assert_eq!(
+            VignetteStyle::select(true, true),
+            VignetteStyle {
+                border: true,
+                pulsing: false
+            }
+        );
+    }
+}
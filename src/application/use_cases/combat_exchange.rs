@@ -0,0 +1,392 @@
+//! Combat Exchange - A fight played out over three visible rounds
+//!
+//! Fighting hostiles is a best of three. Each round is an opposed roll: the
+//! player's d20 plus the strength modifier and any roll assist, against the
+//! opponent's threat times `ENCOUNTER_THREAT_WEIGHT` plus a d6; ties go to
+//! the player. Two rounds won wins the fight, two lost loses it, and a
+//! natural 20 or 1 settles it on the spot. Every lost round costs damage,
+//! and the salvage of a won fight grows with the margins it was won by.
+//!
+//! The exchange is a small state machine: rounds are played one at a time
+//! for the player to watch, or all at once when nobody is at the keys.
+//! Both use the dice in the same order, so they play out the same.
+
+use crate::application::use_cases::{
+    DiceService, EncounterApproach, EncounterFlags, EncounterOutcome, ResolveEncounterUseCase,
+    RollTier,
+};
+use crate::application::{ApplicationError, ApplicationResult};
+use crate::domain::constants::{
+    ENCOUNTER_EXCHANGE_ROUNDS, ENCOUNTER_LOOT_PER_MARGIN, ENCOUNTER_MAX_THREAT,
+    ENCOUNTER_ROUND_DAMAGE, ENCOUNTER_THREAT_WEIGHT,
+};
+use crate::domain::value_objects::{PlayerStats, ResourceType, StatType};
+
+/// Rounds either side needs to take the fight
+const ROUNDS_TO_WIN: usize = ENCOUNTER_EXCHANGE_ROUNDS / 2 + 1;
+
+/// One opposed roll of a fight
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExchangeRound {
+    /// The player's natural d20
+    pub natural: u8,
+    pub player_total: i32,
+    /// The opponent's d6
+    pub opponent_die: u8,
+    pub opponent_total: i32,
+}
+
+impl ExchangeRound {
+    /// Check if the player took the round
+    pub fn won(&self) -> bool {
+        match self.natural {
+            20 => true,
+            1 => false,
+            _ => self.player_total >= self.opponent_total,
+        }
+    }
+
+    /// Check if the round was a natural 20 or 1
+    pub fn is_critical(&self) -> bool {
+        matches!(self.natural, 1 | 20)
+    }
+
+    /// How far the player's total beat the opponent's; zero for a lost round
+    pub fn margin(&self) -> u32 {
+        if self.won() {
+            (self.player_total - self.opponent_total).max(0) as u32
+        } else {
+            0
+        }
+    }
+
+    /// Round line, e.g. `d20 14 → 15 vs 8 + d6 4 → 12 ● won`
+    pub fn describe(&self) -> String {
+        let verdict = match (self.won(), self.is_critical()) {
+            (true, true) => "critical win",
+            (true, false) => "won",
+            (false, true) => "critical loss",
+            (false, false) => "lost",
+        };
+        format!(
+            "d20 {} → {} vs {} + d6 {} → {} {} {}",
+            self.natural,
+            self.player_total,
+            self.opponent_total - self.opponent_die as i32,
+            self.opponent_die,
+            self.opponent_total,
+            if self.won() { "●" } else { "○" },
+            verdict
+        )
+    }
+}
+
+/// Where a fight stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExchangeState {
+    Ongoing,
+    Won,
+    Lost,
+}
+
+/// A fight in progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CombatExchange {
+    threat: u8,
+    /// Strength modifier plus the roll assist
+    modifier: i32,
+    /// The next round rolls twice and keeps the lower
+    disadvantage: bool,
+    rounds: [Option<ExchangeRound>; ENCOUNTER_EXCHANGE_ROUNDS],
+}
+
+impl CombatExchange {
+    /// Start a fight against an opponent of `threat`
+    pub fn new(
+        stats: &PlayerStats,
+        threat: u8,
+        assist: i32,
+        disadvantage: bool,
+    ) -> ApplicationResult<Self> {
+        if threat == 0 || threat > ENCOUNTER_MAX_THREAT {
+            return Err(ApplicationError::InvalidInput(format!(
+                "encounter threat must be between 1 and {}, got {}",
+                ENCOUNTER_MAX_THREAT, threat
+            )));
+        }
+        Ok(Self {
+            threat,
+            modifier: stats.get_modifier(StatType::Strength) as i32 + assist,
+            disadvantage,
+            rounds: [None; ENCOUNTER_EXCHANGE_ROUNDS],
+        })
+    }
+
+    /// Rounds played so far
+    pub fn rounds(&self) -> impl Iterator<Item = &ExchangeRound> {
+        self.rounds.iter().flatten()
+    }
+
+    /// Rounds the player took
+    pub fn wins(&self) -> usize {
+        self.rounds().filter(|round| round.won()).count()
+    }
+
+    /// Rounds the opponent took
+    pub fn losses(&self) -> usize {
+        self.rounds().filter(|round| !round.won()).count()
+    }
+
+    /// Where the fight stands
+    pub fn state(&self) -> ExchangeState {
+        if let Some(round) = self.rounds().last().filter(|round| round.is_critical()) {
+            return if round.won() {
+                ExchangeState::Won
+            } else {
+                ExchangeState::Lost
+            };
+        }
+        if self.wins() >= ROUNDS_TO_WIN {
+            ExchangeState::Won
+        } else if self.losses() >= ROUNDS_TO_WIN {
+            ExchangeState::Lost
+        } else {
+            ExchangeState::Ongoing
+        }
+    }
+
+    /// Check if the player may still run: before the first round, or
+    /// straight after losing it
+    pub fn can_flee(&self) -> bool {
+        match self.rounds().collect::<Vec<_>>().as_slice() {
+            [] => true,
+            [first] => !first.won() && self.state() == ExchangeState::Ongoing,
+            _ => false,
+        }
+    }
+
+    /// Roll the next round at disadvantage, after a failed escape
+    pub fn hamper_next_round(&mut self) {
+        self.disadvantage = true;
+    }
+
+    /// Play the next round
+    pub fn play_round(&mut self, dice: &mut dyn DiceService) -> ApplicationResult<ExchangeRound> {
+        if self.state() != ExchangeState::Ongoing {
+            return Err(ApplicationError::InvalidInput(
+                "the fight is already over".to_string(),
+            ));
+        }
+        let slot = self
+            .rounds
+            .iter()
+            .position(Option::is_none)
+            .ok_or_else(|| ApplicationError::InvalidInput("no rounds left".to_string()))?;
+
+        let mut natural = Self::roll(dice.roll_d20(), 20)?;
+        if std::mem::take(&mut self.disadvantage) {
+            natural = natural.min(Self::roll(dice.roll_d20(), 20)?);
+        }
+        let opponent_die = Self::roll(dice.roll_d6(), 6)?;
+        let round = ExchangeRound {
+            natural,
+            player_total: natural as i32 + self.modifier,
+            opponent_die,
+            opponent_total: self.threat as i32 * ENCOUNTER_THREAT_WEIGHT + opponent_die as i32,
+        };
+        self.rounds[slot] = Some(round);
+        Ok(round)
+    }
+
+    /// Play every remaining round, for autopilot and skipped fights
+    pub fn auto_resolve(&mut self, dice: &mut dyn DiceService) -> ApplicationResult<()> {
+        while self.state() == ExchangeState::Ongoing {
+            self.play_round(dice)?;
+        }
+        Ok(())
+    }
+
+    /// Damage from every round lost so far
+    pub fn damage_taken(&self) -> u32 {
+        self.losses() as u32 * ENCOUNTER_ROUND_DAMAGE * self.threat as u32
+    }
+
+    /// Salvage of a won fight, scaled by the margins of the won rounds
+    pub fn loot(&self) -> Vec<(ResourceType, u32)> {
+        if self.state() != ExchangeState::Won {
+            return Vec::new();
+        }
+        let threat = self.threat as u32;
+        let margin: u32 = self.rounds().map(ExchangeRound::margin).sum();
+        let mut loot = vec![(
+            ResourceType::Metal,
+            5 * threat + ENCOUNTER_LOOT_PER_MARGIN * margin,
+        )];
+        if self.rounds().any(|round| round.natural == 20) {
+            loot.push((ResourceType::Energy, 2 * threat));
+        }
+        loot
+    }
+
+    /// Round results as pips: `●` won, `○` lost, `·` still to play
+    pub fn pips(&self) -> String {
+        self.rounds
+            .iter()
+            .map(|round| match round {
+                Some(round) if round.won() => '●',
+                Some(_) => '○',
+                None => '·',
+            })
+            .collect()
+    }
+
+    /// Outcome of the finished fight, in the terms of a single encounter
+    pub fn outcome(&self) -> Option<EncounterOutcome> {
+        let last = *self.rounds().last()?;
+        let tier = match (self.state(), last.is_critical()) {
+            (ExchangeState::Ongoing, _) => return None,
+            (ExchangeState::Won, true) => RollTier::CriticalSuccess,
+            (ExchangeState::Won, false) => RollTier::Success,
+            (ExchangeState::Lost, true) => RollTier::CriticalFailure,
+            (ExchangeState::Lost, false) => RollTier::Failure,
+        };
+        Some(EncounterOutcome {
+            approach: EncounterApproach::Fight,
+            tier,
+            roll: last.natural,
+            total: last.player_total,
+            difficulty: last.opponent_total,
+            damage_dealt: self.wins() as u32 * ENCOUNTER_ROUND_DAMAGE * self.threat as u32,
+            damage_taken: self.damage_taken(),
+            loot: self.loot(),
+            fled: false,
+            flags: EncounterFlags::default(),
+            experience: ResolveEncounterUseCase::experience(self.threat, tier),
+        })
+    }
+
+    fn roll(face: u8, sides: u8) -> ApplicationResult<u8> {
+        if !(1..=sides).contains(&face) {
+            return Err(ApplicationError::InvalidInput(format!(
+                "d{} rolled {}",
+                sides, face
+            )));
+        }
+        Ok(face)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::use_cases::RngDice;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const THREAT: u8 = 4;
+
+    /// Faces in the order they are rolled: d20, then d6, each round
+    struct ScriptedDice(Vec<u8>);
+
+    impl DiceService for ScriptedDice {
+        fn roll_d20(&mut self) -> u8 {
+            self.0.remove(0)
+        }
+
+        fn roll_d6(&mut self) -> u8 {
+            self.0.remove(0)
+        }
+    }
+
+    fn exchange() -> CombatExchange {
+        CombatExchange::new(&PlayerStats::starting_stats(), THREAT, 0, false).unwrap()
+    }
+
+    #[test]
+    fn best_of_three_with_critical_rounds_ending_it_early() {
+        // Opponent: 8 + d6. Won, lost, won.
+        let mut fight = exchange();
+        assert!(fight.can_flee());
+        fight
+            .auto_resolve(&mut ScriptedDice(vec![15, 3, 5, 4, 12, 2]))
+            .unwrap();
+        assert_eq!(fight.state(), ExchangeState::Won);
+        assert_eq!(fight.pips(), "●○●");
+        assert_eq!(fight.outcome().unwrap().tier, RollTier::Success);
+
+        // Two lost rounds end it before the third
+        let mut fight = exchange();
+        fight.play_round(&mut ScriptedDice(vec![5, 6])).unwrap();
+        assert!(fight.can_flee());
+        fight.play_round(&mut ScriptedDice(vec![6, 6])).unwrap();
+        assert_eq!(fight.state(), ExchangeState::Lost);
+        assert_eq!(fight.pips(), "○○·");
+        assert!(!fight.can_flee());
+        assert!(fight.play_round(&mut ScriptedDice(vec![10, 1])).is_err());
+
+        // A natural 20 or 1 settles it on the spot
+        let mut fight = exchange();
+        fight.play_round(&mut ScriptedDice(vec![20, 6])).unwrap();
+        assert_eq!(fight.state(), ExchangeState::Won);
+        assert_eq!(fight.outcome().unwrap().tier, RollTier::CriticalSuccess);
+        let mut fight = exchange();
+        fight.play_round(&mut ScriptedDice(vec![15, 1])).unwrap();
+        assert!(!fight.can_flee());
+        fight.play_round(&mut ScriptedDice(vec![1, 1])).unwrap();
+        assert_eq!(fight.state(), ExchangeState::Lost);
+        assert_eq!(fight.outcome().unwrap().tier, RollTier::CriticalFailure);
+    }
+
+    #[test]
+    fn loot_follows_the_margin_and_damage_the_lost_rounds() {
+        // Margins 15 - 9 = 6 and 13 - 10 = 3, one round lost
+        let mut fight = exchange();
+        fight
+            .auto_resolve(&mut ScriptedDice(vec![15, 1, 2, 2, 13, 2]))
+            .unwrap();
+        let outcome = fight.outcome().unwrap();
+        assert_eq!(
+            outcome.loot,
+            vec![(ResourceType::Metal, 5 * 4 + ENCOUNTER_LOOT_PER_MARGIN * 9)]
+        );
+        assert_eq!(outcome.damage_taken, ENCOUNTER_ROUND_DAMAGE * 4);
+
+        // A narrow win salvages less; a lost fight nothing
+        let mut narrow = exchange();
+        narrow
+            .auto_resolve(&mut ScriptedDice(vec![10, 2, 11, 3]))
+            .unwrap();
+        assert!(narrow.loot()[0].1 < outcome.loot[0].1);
+        assert_eq!(narrow.damage_taken(), 0);
+
+        let mut lost = exchange();
+        lost.auto_resolve(&mut ScriptedDice(vec![2, 6, 3, 6]))
+            .unwrap();
+        assert!(lost.loot().is_empty());
+        assert_eq!(lost.damage_taken(), 2 * ENCOUNTER_ROUND_DAMAGE * 4);
+    }
+
+    #[test]
+    fn auto_resolving_plays_out_like_round_by_round() {
+        let mut manual_wins = 0;
+        let mut auto_wins = 0;
+        for seed in 0..500 {
+            let mut manual = exchange();
+            let mut dice = RngDice(StdRng::seed_from_u64(seed));
+            while manual.state() == ExchangeState::Ongoing {
+                manual.play_round(&mut dice).unwrap();
+            }
+
+            let mut auto = exchange();
+            auto.auto_resolve(&mut RngDice(StdRng::seed_from_u64(seed)))
+                .unwrap();
+
+            assert_eq!(manual, auto, "seed {}", seed);
+            manual_wins += usize::from(manual.state() == ExchangeState::Won);
+            auto_wins += usize::from(auto.state() == ExchangeState::Won);
+        }
+        assert_eq!(manual_wins, auto_wins);
+        // Even odds against a threat of 4 with average stats
+        assert!((150..350).contains(&auto_wins), "{} wins", auto_wins);
+    }
+}
@@ -0,0 +1,217 @@
+//! Construct Building Use Case - Build and upgrade the base's buildings
+//!
+//! A building not yet on the base is constructed for its build cost; one
+//! already there is raised a level for its upgrade cost. Upgrades are
+//! checked before anything is paid: a building at `MAX_BUILDING_LEVEL`, one
+//! damaged in a raid, or one that would outgrow the level of the character
+//! in charge cannot be raised. The cost is paid through `pay`, as refinery
+//! jobs are, so the caller decides where the resources come from.
+
+use crate::application::ApplicationResult;
+use crate::domain::constants::MAX_BUILDING_LEVEL;
+use crate::domain::entities::{Base, BaseBuilding, BuildingType};
+use crate::domain::value_objects::ResourceCollection;
+use crate::domain::{DomainError, DomainResult};
+
+/// What a construction did to the base
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Construction {
+    Built(BuildingType),
+    Upgraded {
+        building_type: BuildingType,
+        level: u8,
+    },
+}
+
+/// Use case building or upgrading one building of the base
+pub struct ConstructBuildingUseCase;
+
+impl ConstructBuildingUseCase {
+    /// Create a new construct building use case
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Cost of the next step for `building_type`: building it or raising it
+    pub fn next_cost(base: &Base, building_type: BuildingType) -> ResourceCollection {
+        let cost = match base.building(building_type) {
+            Some(building) => building_type.upgrade_cost(building.level),
+            None => building_type.build_cost(),
+        };
+        let mut collection = ResourceCollection::new();
+        for (resource_type, amount) in cost {
+            collection.set_amount(resource_type, amount);
+        }
+        collection
+    }
+
+    /// Check whether `building_type` may be raised a level now
+    pub fn check_upgrade(
+        base: &Base,
+        building_type: BuildingType,
+        commander_level: u32,
+    ) -> DomainResult<()> {
+        let Some(building) = base.building(building_type) else {
+            return Ok(());
+        };
+        if building.level >= MAX_BUILDING_LEVEL {
+            return Err(DomainError::BaseUpgradeError(format!(
+                "{} is already at the highest level",
+                building.name
+            )));
+        }
+        if building.damaged {
+            return Err(DomainError::BaseUpgradeError(format!(
+                "{} must be repaired before it is upgraded",
+                building.name
+            )));
+        }
+        let next = building.level as u32 + 1;
+        if commander_level < next {
+            return Err(DomainError::BaseUpgradeError(format!(
+                "{} level {} needs a level {} commander",
+                building.name, next, next
+            )));
+        }
+        Ok(())
+    }
+
+    /// Build `building_type`, or raise it a level if it already stands
+    pub fn execute(
+        &self,
+        base: &mut Base,
+        building_type: BuildingType,
+        commander_level: u32,
+        pay: impl FnOnce(&ResourceCollection) -> DomainResult<()>,
+    ) -> ApplicationResult<Construction> {
+        Self::check_upgrade(base, building_type, commander_level)?;
+        pay(&Self::next_cost(base, building_type))?;
+
+        if base.building(building_type).is_some() {
+            let level = base.upgrade_building(building_type)?;
+            return Ok(Construction::Upgraded {
+                building_type,
+                level,
+            });
+        }
+        let slot = (base.buildings().len() as i32, 0);
+        base.add_building(BaseBuilding::new(
+            building_type,
+            building_type.name().to_string(),
+            slot,
+        ));
+        Ok(Construction::Built(building_type))
+    }
+}
+
+impl Default for ConstructBuildingUseCase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ApplicationError;
+    use crate::domain::value_objects::{EntityId, Position3D, ResourceType};
+
+    fn base() -> Base {
+        Base::new(
+            EntityId::generate(),
+            "Home".to_string(),
+            Position3D::origin(),
+        )
+        .unwrap()
+    }
+
+    /// Pays from `cargo`, the way the player's inventory does
+    fn pay_from(
+        cargo: &mut ResourceCollection,
+    ) -> impl FnOnce(&ResourceCollection) -> DomainResult<()> + '_ {
+        move |cost| cargo.pay_cost(cost)
+    }
+
+    #[test]
+    fn construction_and_upgrades_deduct_their_cost() {
+        let use_case = ConstructBuildingUseCase::new();
+        let mut base = base();
+        let mut cargo = ResourceCollection::new();
+        cargo.set_amount(ResourceType::Metal, 200);
+        cargo.set_amount(ResourceType::Energy, 100);
+
+        let built = use_case
+            .execute(
+                &mut base,
+                BuildingType::ResourceStorage,
+                1,
+                pay_from(&mut cargo),
+            )
+            .unwrap();
+        assert_eq!(built, Construction::Built(BuildingType::ResourceStorage));
+        assert_eq!(cargo.get_amount(ResourceType::Metal), 150);
+        assert_eq!(cargo.get_amount(ResourceType::Energy), 80);
+
+        let upgraded = use_case
+            .execute(
+                &mut base,
+                BuildingType::ResourceStorage,
+                2,
+                pay_from(&mut cargo),
+            )
+            .unwrap();
+        assert_eq!(
+            upgraded,
+            Construction::Upgraded {
+                building_type: BuildingType::ResourceStorage,
+                level: 2
+            }
+        );
+        assert_eq!(cargo.get_amount(ResourceType::Metal), 50);
+        assert_eq!(cargo.get_amount(ResourceType::Energy), 40);
+
+        // Short of the next step: the error says so and nothing changes
+        let short = use_case.execute(&mut base, BuildingType::Refinery, 2, pay_from(&mut cargo));
+        assert!(matches!(
+            short,
+            Err(ApplicationError::DomainError(
+                DomainError::InsufficientResources(_)
+            ))
+        ));
+        assert!(base.building(BuildingType::Refinery).is_none());
+        assert_eq!(cargo.get_amount(ResourceType::Metal), 50);
+    }
+
+    #[test]
+    fn upgrades_are_refused_before_anything_is_paid() {
+        let use_case = ConstructBuildingUseCase::new();
+        let mut base = base();
+        base.add_building(BaseBuilding::new(
+            BuildingType::Workshop,
+            "Workshop".to_string(),
+            (0, 0),
+        ));
+        let refused = |base: &mut Base, commander_level| {
+            let result = use_case.execute(base, BuildingType::Workshop, commander_level, |_| {
+                panic!("refused upgrades are never paid")
+            });
+            matches!(
+                result,
+                Err(ApplicationError::DomainError(
+                    DomainError::BaseUpgradeError(_)
+                ))
+            )
+        };
+
+        // A level 1 commander cannot run a level 2 workshop
+        assert!(refused(&mut base, 1));
+        base.set_building_damaged(BuildingType::Workshop, true);
+        assert!(refused(&mut base, 5));
+        base.set_building_damaged(BuildingType::Workshop, false);
+
+        while base.building(BuildingType::Workshop).unwrap().level < MAX_BUILDING_LEVEL {
+            base.upgrade_building(BuildingType::Workshop).unwrap();
+        }
+        assert!(refused(&mut base, u32::MAX));
+    }
+}
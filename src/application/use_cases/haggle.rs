@@ -0,0 +1,491 @@
+//! Haggle - Bargaining with a trader met in the field
+//!
+//! A trade event opens a haggle. The trader asks for part of the player's
+//! most valuable holding and offers goods of the terrain in return, picked
+//! from its common resources most of the time and its rarer ones now and
+//! then. Faction standing shifts the opening offer by the trade rate of its
+//! tier.
+//!
+//! The player takes the offer, walks away, or counters: a Charisma check
+//! against a difficulty that rises with every counter made. A success
+//! raises the offered goods, a natural 20 also throws in a piece of gear; a
+//! miss lowers them, and missing by a wide margin or rolling a natural 1
+//! makes the trader call talks off. After `HAGGLE_MAX_COUNTERS` counters
+//! the offer has to be taken or left. Nothing changes hands here: the
+//! caller moves the goods once the haggle ends in a deal.
+//!
+//! Auto-resolving skips the talk and settles on the event's own roll, the
+//! way trade events always paid out: Data for a roll of 13 and up, nothing
+//! below.
+
+use crate::application::use_cases::DiceService;
+use crate::application::{ApplicationError, ApplicationResult};
+use crate::domain::constants::{
+    HAGGLE_COUNTER_DIFFICULTY, HAGGLE_COUNTER_DIFFICULTY_STEP, HAGGLE_COUNTER_STEP_PERCENT,
+    HAGGLE_MAX_COUNTERS, HAGGLE_TRADE_VALUE, HAGGLE_WALKOUT_MARGIN,
+};
+use crate::domain::services::{ReputationTier, TradeService};
+use crate::domain::value_objects::{
+    PlayerStats, ResourceCollection, ResourceType, StatType, TerrainType,
+};
+
+/// Goods a trader puts on the table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HaggleOffer {
+    /// Cargo the trader asks for; none for a plain payout
+    pub wanted: Option<(ResourceType, u32)>,
+    /// Cargo the trader hands over
+    pub offered: (ResourceType, u32),
+    /// A piece of gear thrown in after a critical counter
+    pub bonus_item: bool,
+}
+
+impl HaggleOffer {
+    /// Opening offer of a trader on `terrain` for a player holding `holdings`
+    ///
+    /// The offered goods come from the terrain's resources, Data where it
+    /// has none; the trader asks for `HAGGLE_TRADE_VALUE` worth of the most
+    /// valuable other holding, or all of it when there is less. Returns
+    /// none when the player holds nothing the trader wants.
+    pub fn generate(
+        terrain: Option<TerrainType>,
+        holdings: &ResourceCollection,
+        tier: ReputationTier,
+        dice: &mut dyn DiceService,
+    ) -> Option<Self> {
+        let primary = terrain.map(|t| t.primary_resources()).unwrap_or_default();
+        let secondary = terrain.map(|t| t.secondary_resources()).unwrap_or_default();
+        let table = match (dice.roll_d6(), primary.is_empty(), secondary.is_empty()) {
+            (_, true, true) => vec![ResourceType::Data],
+            (5..=6, _, false) | (_, true, false) => secondary,
+            _ => primary,
+        };
+        let offered_type = table[dice.roll_d20().saturating_sub(1) as usize % table.len()];
+
+        let value = |resource_type: ResourceType| {
+            holdings.get_amount(resource_type) * resource_type.base_value()
+        };
+        // Ties go to the resource listed first
+        let wanted_type = ResourceType::all()
+            .into_iter()
+            .rev()
+            .filter(|&resource_type| resource_type != offered_type && value(resource_type) > 0)
+            .max_by_key(|&resource_type| value(resource_type))?;
+
+        let wanted = holdings
+            .get_amount(wanted_type)
+            .min(HAGGLE_TRADE_VALUE.div_ceil(wanted_type.base_value()));
+        let worth = TradeService::new().apply_rate(wanted * wanted_type.base_value(), tier);
+        Some(Self {
+            wanted: Some((wanted_type, wanted)),
+            offered: (offered_type, (worth / offered_type.base_value()).max(1)),
+            bonus_item: false,
+        })
+    }
+
+    /// What a trade event paid on a roll of `roll` before haggling existed
+    pub fn legacy(roll: u8) -> Option<Self> {
+        let data = match roll {
+            20..=u8::MAX => 40,
+            17..=19 => 30,
+            13..=16 => 20,
+            _ => return None,
+        };
+        Some(Self {
+            wanted: None,
+            offered: (ResourceType::Data, data),
+            bonus_item: false,
+        })
+    }
+
+    /// What taking the offer costs
+    pub fn cost(&self) -> ResourceCollection {
+        let mut cost = ResourceCollection::new();
+        if let Some((resource_type, amount)) = self.wanted {
+            cost.set_amount(resource_type, amount);
+        }
+        cost
+    }
+
+    /// What taking the offer brings in
+    pub fn goods(&self) -> ResourceCollection {
+        let mut goods = ResourceCollection::new();
+        goods.set_amount(self.offered.0, self.offered.1);
+        goods
+    }
+
+    /// Offer line, e.g. `12 Metal for 4 Energy`
+    pub fn describe(&self) -> String {
+        let offered = format!("{} {}", self.offered.1, self.offered.0);
+        let offered = if self.bonus_item {
+            format!("{} and a piece of gear", offered)
+        } else {
+            offered
+        };
+        match self.wanted {
+            Some((resource_type, amount)) => {
+                format!("{} {} for {}", amount, resource_type, offered)
+            }
+            None => offered,
+        }
+    }
+
+    /// Raise or lower the offered goods by one counter's step
+    fn shift(&mut self, up: bool) {
+        let step = (self.offered.1 * HAGGLE_COUNTER_STEP_PERCENT / 100).max(1);
+        self.offered.1 = if up {
+            self.offered.1 + step
+        } else {
+            self.offered.1.saturating_sub(step).max(1)
+        };
+    }
+}
+
+/// Where a haggle stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HaggleState {
+    Open,
+    /// The player took the offer
+    Accepted,
+    /// Settled on the event roll; a deal if it paid out
+    AutoResolved,
+    WalkedAway,
+    /// The trader broke talks off
+    CalledOff,
+}
+
+impl HaggleState {
+    /// Check if goods are to change hands
+    pub fn is_deal(&self) -> bool {
+        matches!(self, HaggleState::Accepted | HaggleState::AutoResolved)
+    }
+}
+
+/// One counter of a haggle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CounterRoll {
+    /// The player's natural d20
+    pub natural: u8,
+    pub total: i32,
+    pub difficulty: i32,
+}
+
+impl CounterRoll {
+    /// Check if the trader gave ground
+    pub fn succeeded(&self) -> bool {
+        match self.natural {
+            20 => true,
+            1 => false,
+            _ => self.total >= self.difficulty,
+        }
+    }
+
+    /// Check if the trader had enough and called talks off
+    pub fn ends_talks(&self) -> bool {
+        self.natural == 1
+            || (self.natural != 20 && self.total < self.difficulty - HAGGLE_WALKOUT_MARGIN)
+    }
+
+    /// Counter line, e.g. `d20 14 → 15 vs 13 ● the trader gives ground`
+    pub fn describe(&self) -> String {
+        let verdict = if self.natural == 20 {
+            "the trader throws in extra"
+        } else if self.succeeded() {
+            "the trader gives ground"
+        } else if self.ends_talks() {
+            "the trader has had enough"
+        } else {
+            "the trader hardens"
+        };
+        format!(
+            "d20 {} → {} vs {} {} {}",
+            self.natural,
+            self.total,
+            self.difficulty,
+            if self.succeeded() { "●" } else { "○" },
+            verdict
+        )
+    }
+}
+
+/// A haggle in progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Haggle {
+    offer: HaggleOffer,
+    /// Final roll of the trade event, which auto-resolving settles on
+    roll: u8,
+    /// Charisma modifier
+    modifier: i32,
+    counters: [Option<CounterRoll>; HAGGLE_MAX_COUNTERS],
+    state: HaggleState,
+}
+
+impl Haggle {
+    /// Start haggling over `offer` after a trade event rolled `roll`
+    pub fn new(stats: &PlayerStats, roll: u8, offer: HaggleOffer) -> Self {
+        Self {
+            offer,
+            roll,
+            modifier: stats.get_modifier(StatType::Charisma) as i32,
+            counters: [None; HAGGLE_MAX_COUNTERS],
+            state: HaggleState::Open,
+        }
+    }
+
+    /// The offer on the table
+    pub fn offer(&self) -> &HaggleOffer {
+        &self.offer
+    }
+
+    /// Where the haggle stands
+    pub fn state(&self) -> HaggleState {
+        self.state
+    }
+
+    /// Counters made so far
+    pub fn counters(&self) -> impl Iterator<Item = &CounterRoll> {
+        self.counters.iter().flatten()
+    }
+
+    /// Counters the player may still make
+    pub fn counters_left(&self) -> usize {
+        HAGGLE_MAX_COUNTERS - self.counters().count()
+    }
+
+    /// Difficulty of the next counter
+    pub fn next_difficulty(&self) -> i32 {
+        HAGGLE_COUNTER_DIFFICULTY + HAGGLE_COUNTER_DIFFICULTY_STEP * self.counters().count() as i32
+    }
+
+    /// Push for a better offer
+    pub fn counter(&mut self, dice: &mut dyn DiceService) -> ApplicationResult<CounterRoll> {
+        self.require_open()?;
+        let slot = self
+            .counters
+            .iter()
+            .position(Option::is_none)
+            .ok_or_else(|| {
+                ApplicationError::InvalidInput("the trader takes no more counters".to_string())
+            })?;
+
+        let natural = dice.roll_d20();
+        if !(1..=20).contains(&natural) {
+            return Err(ApplicationError::InvalidInput(format!(
+                "d20 rolled {}",
+                natural
+            )));
+        }
+        let counter = CounterRoll {
+            natural,
+            total: natural as i32 + self.modifier,
+            difficulty: self.next_difficulty(),
+        };
+        self.counters[slot] = Some(counter);
+
+        if counter.ends_talks() {
+            self.state = HaggleState::CalledOff;
+        } else {
+            self.offer.shift(counter.succeeded());
+            if natural == 20 {
+                self.offer.bonus_item = true;
+            }
+        }
+        Ok(counter)
+    }
+
+    /// Take the offer as it stands
+    pub fn accept(&mut self) -> ApplicationResult<()> {
+        self.require_open()?;
+        self.state = HaggleState::Accepted;
+        Ok(())
+    }
+
+    /// Leave without a deal
+    pub fn walk_away(&mut self) -> ApplicationResult<()> {
+        self.require_open()?;
+        self.state = HaggleState::WalkedAway;
+        Ok(())
+    }
+
+    /// Skip the talk and settle on the event roll, as trade events did
+    /// before haggling
+    pub fn auto_resolve(&mut self) -> ApplicationResult<()> {
+        self.require_open()?;
+        match HaggleOffer::legacy(self.roll) {
+            Some(offer) => {
+                self.offer = offer;
+                self.state = HaggleState::AutoResolved;
+            }
+            None => self.state = HaggleState::CalledOff,
+        }
+        Ok(())
+    }
+
+    fn require_open(&self) -> ApplicationResult<()> {
+        if self.state != HaggleState::Open {
+            return Err(ApplicationError::InvalidInput(
+                "the haggle is already over".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::use_cases::{FixedRoll, RngDice};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn holdings() -> ResourceCollection {
+        let mut holdings = ResourceCollection::new();
+        holdings.set_amount(ResourceType::Metal, 100);
+        holdings.set_amount(ResourceType::Food, 30);
+        holdings.set_amount(ResourceType::Technology, 2);
+        holdings
+    }
+
+    fn haggle() -> Haggle {
+        let offer = HaggleOffer {
+            wanted: Some((ResourceType::Metal, 60)),
+            offered: (ResourceType::Energy, 30),
+            bonus_item: false,
+        };
+        Haggle::new(&PlayerStats::starting_stats(), 14, offer)
+    }
+
+    #[test]
+    fn offers_stay_within_the_terrain_table_and_the_cargo() {
+        let terrains = [
+            Some(TerrainType::Mountains),
+            Some(TerrainType::Forest),
+            Some(TerrainType::Constructed),
+            None,
+        ];
+        for seed in 0..200 {
+            for terrain in terrains {
+                let offer = HaggleOffer::generate(
+                    terrain,
+                    &holdings(),
+                    ReputationTier::Neutral,
+                    &mut RngDice(StdRng::seed_from_u64(seed)),
+                )
+                .unwrap();
+                let (offered_type, offered) = offer.offered;
+                let (wanted_type, wanted) = offer.wanted.unwrap();
+
+                let table = match terrain.filter(|t| !t.primary_resources().is_empty()) {
+                    Some(t) => [t.primary_resources(), t.secondary_resources()].concat(),
+                    None => vec![ResourceType::Data],
+                };
+                assert!(table.contains(&offered_type), "{:?}", offer);
+                assert_ne!(wanted_type, offered_type);
+                assert!((1..=holdings().get_amount(wanted_type)).contains(&wanted));
+                assert!(wanted * wanted_type.base_value() < HAGGLE_TRADE_VALUE + 10);
+                // Worth what is asked, give or take a unit of the offered goods
+                let worth = offered * offered_type.base_value();
+                let asked = wanted * wanted_type.base_value();
+                assert!(offered >= 1);
+                assert!(worth <= asked.max(offered_type.base_value()), "{:?}", offer);
+                assert!(worth + offered_type.base_value() > asked, "{:?}", offer);
+            }
+        }
+
+        // Standing shifts the opening offer
+        let opening = |tier| {
+            HaggleOffer::generate(
+                Some(TerrainType::Plains),
+                &holdings(),
+                tier,
+                &mut FixedRoll(1),
+            )
+            .unwrap()
+            .offered
+        };
+        assert_eq!(opening(ReputationTier::Neutral), (ResourceType::Food, 60));
+        assert!(opening(ReputationTier::Friendly).1 > 60);
+        assert!(opening(ReputationTier::Hostile).1 < 60);
+
+        // Nothing to trade, no offer
+        assert!(HaggleOffer::generate(
+            Some(TerrainType::Plains),
+            &ResourceCollection::new(),
+            ReputationTier::Neutral,
+            &mut FixedRoll(1),
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn every_counter_is_harder_and_the_third_is_the_last() {
+        let mut talks = haggle();
+        assert_eq!(talks.next_difficulty(), HAGGLE_COUNTER_DIFFICULTY);
+
+        // 14 beats 10, then 13; 14 misses 16 by too little to end talks
+        talks.counter(&mut FixedRoll(14)).unwrap();
+        assert_eq!(talks.offer().offered.1, 36);
+        assert_eq!(
+            talks.next_difficulty(),
+            HAGGLE_COUNTER_DIFFICULTY + HAGGLE_COUNTER_DIFFICULTY_STEP
+        );
+        talks.counter(&mut FixedRoll(14)).unwrap();
+        assert_eq!(talks.offer().offered.1, 43);
+        let third = talks.counter(&mut FixedRoll(14)).unwrap();
+        assert_eq!(third.difficulty, HAGGLE_COUNTER_DIFFICULTY + 6);
+        assert!(!third.succeeded() && !third.ends_talks());
+        assert_eq!(talks.offer().offered.1, 35);
+        assert_eq!(talks.counters_left(), 0);
+        assert!(talks.counter(&mut FixedRoll(20)).is_err());
+        talks.accept().unwrap();
+        assert!(talks.state().is_deal());
+
+        // A wide miss ends talks; a natural 20 throws in gear
+        let mut talks = haggle();
+        talks.counter(&mut FixedRoll(4)).unwrap();
+        assert_eq!(talks.state(), HaggleState::CalledOff);
+        assert!(talks.accept().is_err());
+        let mut talks = haggle();
+        talks.counter(&mut FixedRoll(20)).unwrap();
+        assert!(talks.offer().bonus_item);
+
+        // Walking away leaves the offer unpaid
+        let mut talks = haggle();
+        talks.walk_away().unwrap();
+        assert_eq!(talks.state(), HaggleState::WalkedAway);
+        assert!(!talks.state().is_deal());
+    }
+
+    #[test]
+    fn auto_resolving_pays_out_like_the_old_single_roll() {
+        // The flat payout trade events had before haggling
+        let legacy = |roll: u8| match roll {
+            20..=u8::MAX => Some(40),
+            17..=19 => Some(30),
+            13..=16 => Some(20),
+            _ => None,
+        };
+
+        let mut rng = StdRng::seed_from_u64(1944);
+        let mut payouts = [0usize; 4];
+        for _ in 0..4000 {
+            let roll = rng.gen_range(1..=20);
+            let mut talks = Haggle::new(&PlayerStats::starting_stats(), roll, *haggle().offer());
+            talks.auto_resolve().unwrap();
+            let paid = talks.state().is_deal().then_some(talks.offer().offered.1);
+            assert_eq!(paid, legacy(roll), "roll {}", roll);
+            assert!(talks.offer().cost().is_empty() || !talks.state().is_deal());
+            payouts[match paid {
+                None => 0,
+                Some(20) => 1,
+                Some(30) => 2,
+                _ => 3,
+            }] += 1;
+        }
+        // 12, 4, 3 and 1 faces in 20
+        for (count, faces) in payouts.into_iter().zip([12, 4, 3, 1]) {
+            let expected = 4000 * faces / 20;
+            assert!(count.abs_diff(expected) < 120, "{} vs {}", count, expected);
+        }
+    }
+}
@@ -5,10 +5,12 @@
 //! workflow and coordinates between domain entities and services.
 //!
 //! ## Architecture
-//! - **Move Player**: Handle player movement with business rules
-//! - **Spawn Enemies**: Manage enemy creation and placement
-//! - **Handle Collision**: Process entity collisions and effects
-//! - **Update Score**: Manage score changes and validation
+//! - **Resolve Encounter**: Settle hostile encounters with a chosen approach
+//! - **Combat Exchange**: Play a fight out as a best of three opposed rolls
+//! - **Construct Building**: Build and upgrade the base's buildings
+//! - **Haggle**: Bargain with a field trader over up to three counters
+//! - **Mystery Encounter**: Meet a mysterious phenomenon with a chosen approach
+//! - **Start Scenario**: Apply a starting scenario to a new character and map
 //!
 //! ## Rules
 //! - Single responsibility per use case
@@ -16,24 +18,20 @@
 //! - Domain entity coordination
 //! - Business rule enforcement
 
-pub mod handle_collision;
-pub mod move_player;
-pub mod spawn_enemies;
-pub mod update_score;
+pub mod combat_exchange;
+pub mod construct_building;
+pub mod haggle;
+pub mod mystery_encounter;
+pub mod resolve_encounter;
+pub mod start_scenario;
 
 // Re-export use cases for convenience
-pub use handle_collision::HandleEncounterUseCase;
-pub use move_player::MovePlayerUseCase;
-pub use spawn_enemies::SpawnEnemiesUseCase;
-pub use update_score::UpdateScoreUseCase;
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn use_cases_can_be_imported() {
-        // Compilation test to ensure all use cases are properly exported
-        // Individual use case tests are in their respective modules
-    }
-}
+pub use combat_exchange::{CombatExchange, ExchangeRound, ExchangeState};
+pub use construct_building::{ConstructBuildingUseCase, Construction};
+pub use haggle::{CounterRoll, Haggle, HaggleOffer, HaggleState};
+pub use mystery_encounter::MysteryApproach;
+pub use resolve_encounter::{
+    DiceService, EncounterApproach, EncounterContext, EncounterFlags, EncounterOutcome, FixedRoll,
+    ResolveEncounterUseCase, RngDice, RollTier,
+};
+pub use start_scenario::StartScenarioUseCase;
@@ -0,0 +1,118 @@
+//! Mystery Encounter Use Case - Choosing how to meet a mysterious phenomenon
+//!
+//! A mystery holds the player until they pick an approach. Studying it rolls
+//! a d20 on Intelligence and attuning to it rolls on Luck; the roll is then
+//! read like the event roll of any other event, so the rewards stay the
+//! ones the event path pays. Leaving it be rolls nothing and earns nothing.
+
+use crate::domain::constants::MYSTERY_INSIGHT_ROLL;
+use crate::domain::services::tile_movement::MovementDiceResult;
+use crate::domain::value_objects::{DiceModifier, DiceRoll, DiceType, PlayerStats, StatType};
+
+/// How the player meets a mystery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MysteryApproach {
+    Study,
+    Attune,
+    Leave,
+}
+
+impl MysteryApproach {
+    /// Every approach, in menu order
+    pub const ALL: [MysteryApproach; 3] = [
+        MysteryApproach::Study,
+        MysteryApproach::Attune,
+        MysteryApproach::Leave,
+    ];
+
+    /// Name shown on the choice
+    pub fn label(&self) -> &'static str {
+        match self {
+            MysteryApproach::Study => "Study it",
+            MysteryApproach::Attune => "Attune to it",
+            MysteryApproach::Leave => "Leave it be",
+        }
+    }
+
+    /// Stat whose modifier applies to the roll; leaving rolls nothing
+    pub fn stat(&self) -> Option<StatType> {
+        match self {
+            MysteryApproach::Study => Some(StatType::Intelligence),
+            MysteryApproach::Attune => Some(StatType::Luck),
+            MysteryApproach::Leave => None,
+        }
+    }
+
+    /// Chance the roll reaches `MYSTERY_INSIGHT_ROLL` with `stats`
+    pub fn insight_chance(&self, stats: &PlayerStats) -> f32 {
+        let Some(stat) = self.stat() else {
+            return 0.0;
+        };
+        let modifier = stats.get_modifier(stat) as i16;
+        let faces = (1..=20i16)
+            .filter(|face| face + modifier >= MYSTERY_INSIGHT_ROLL as i16)
+            .count();
+        faces as f32 / 20.0
+    }
+
+    /// The roll the approach makes, as shown before choosing
+    pub fn preview(&self, stats: &PlayerStats) -> String {
+        match self.stat() {
+            Some(stat) => format!(
+                "d20 {:+} ({}) - {:.0}% to understand it",
+                stats.get_modifier(stat),
+                stat,
+                self.insight_chance(stats) * 100.0
+            ),
+            None => "no roll".to_string(),
+        }
+    }
+
+    /// Read the d20 `face` as the mystery's event roll; `None` when leaving
+    pub fn resolve(&self, stats: &PlayerStats, face: u8) -> Option<MovementDiceResult> {
+        let modifier = stats.get_modifier(self.stat()?);
+        let base_roll = face.clamp(1, DiceType::D20.max_value());
+        Some(MovementDiceResult {
+            base_roll,
+            level_modifier: 0,
+            terrain_modifier: 0,
+            danger_modifier: 0,
+            gear_modifier: 0,
+            assist_modifier: DiceModifier::none(),
+            disadvantage: false,
+            total_modifier: modifier,
+            final_result: (base_roll as i16 + modifier as i16).clamp(1, u8::MAX as i16) as u8,
+            dice_roll: DiceRoll::new(1, DiceType::D20, DiceModifier::none()).ok()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(intelligence: u8, luck: u8) -> PlayerStats {
+        PlayerStats::new(10, 10, intelligence, 10, luck, 10).unwrap()
+    }
+
+    #[test]
+    fn the_roll_carries_the_approach_stat() {
+        let sharp = stats(16, 8);
+        let studied = MysteryApproach::Study.resolve(&sharp, 12).unwrap();
+        assert_eq!((studied.base_roll, studied.final_result), (12, 15));
+        let attuned = MysteryApproach::Attune.resolve(&sharp, 12).unwrap();
+        assert_eq!(attuned.final_result, 11);
+        assert_eq!(MysteryApproach::Leave.resolve(&sharp, 12), None);
+
+        assert_eq!(MysteryApproach::Study.insight_chance(&sharp), 0.45);
+        assert!(
+            MysteryApproach::Study.insight_chance(&sharp)
+                > MysteryApproach::Attune.insight_chance(&sharp)
+        );
+        assert_eq!(
+            MysteryApproach::Study.preview(&sharp),
+            "d20 +3 (Intelligence) - 45% to understand it"
+        );
+        assert_eq!(MysteryApproach::Leave.preview(&sharp), "no roll");
+    }
+}
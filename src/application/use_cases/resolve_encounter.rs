@@ -0,0 +1,599 @@
+//! Resolve Encounter Use Case - Dice-based resolution of hostile encounters
+//!
+//! An encounter pits a snapshot of the player's stats against an opponent's
+//! threat. The player picks an approach, each tied to one stat; a d20 plus
+//! that stat's modifier is compared with a difficulty that grows with the
+//! threat. The roll tier and the approach together decide the damage on
+//! both sides, the loot and whether the player got away.
+//!
+//! Fleeing also depends on the ground: open terrain helps, swamps and caves
+//! hinder. A failed escape hands the opponent a free hit and the fight goes
+//! on, with the next roll made at disadvantage.
+
+use crate::application::{ApplicationError, ApplicationResult};
+use crate::domain::constants::{ENCOUNTER_BASE_DIFFICULTY, ENCOUNTER_MAX_THREAT};
+use crate::domain::value_objects::terrain::TerrainType;
+use crate::domain::value_objects::{PlayerStats, ResourceType, StatType};
+use rand::Rng;
+
+/// Source of the dice rolled for an encounter
+pub trait DiceService {
+    /// Roll a d20, from 1 to 20
+    fn roll_d20(&mut self) -> u8;
+
+    /// Roll a d6, from 1 to 6
+    fn roll_d6(&mut self) -> u8;
+}
+
+/// Dice backed by a random number generator
+pub struct RngDice<R>(pub R);
+
+impl<R: Rng> DiceService for RngDice<R> {
+    fn roll_d20(&mut self) -> u8 {
+        self.0.gen_range(1..=20)
+    }
+
+    fn roll_d6(&mut self) -> u8 {
+        self.0.gen_range(1..=6)
+    }
+}
+
+/// Dice that always show the same face, for scripted encounters
+///
+/// A d6 shows the face too, capped at 6.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedRoll(pub u8);
+
+impl DiceService for FixedRoll {
+    fn roll_d20(&mut self) -> u8 {
+        self.0
+    }
+
+    fn roll_d6(&mut self) -> u8 {
+        self.0.min(6)
+    }
+}
+
+/// How the player deals with the opponent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EncounterApproach {
+    Fight,
+    Negotiate,
+    Sneak,
+    Flee,
+}
+
+impl EncounterApproach {
+    /// Every approach, in menu order
+    pub const ALL: [EncounterApproach; 4] = [
+        EncounterApproach::Fight,
+        EncounterApproach::Negotiate,
+        EncounterApproach::Sneak,
+        EncounterApproach::Flee,
+    ];
+
+    /// Word the approach is remembered by in the session flags
+    pub fn key(&self) -> &'static str {
+        match self {
+            EncounterApproach::Fight => "fight",
+            EncounterApproach::Negotiate => "negotiate",
+            EncounterApproach::Sneak => "sneak",
+            EncounterApproach::Flee => "flee",
+        }
+    }
+
+    /// Stat whose modifier applies to the roll
+    pub fn stat(&self) -> StatType {
+        match self {
+            EncounterApproach::Fight => StatType::Strength,
+            EncounterApproach::Negotiate => StatType::Charisma,
+            EncounterApproach::Sneak => StatType::Dexterity,
+            EncounterApproach::Flee => StatType::Dexterity,
+        }
+    }
+
+    /// Modifier the ground gives the roll; only fleeing depends on it
+    pub fn terrain_modifier(&self, terrain: Option<TerrainType>) -> i32 {
+        match (self, terrain) {
+            (EncounterApproach::Flee, Some(terrain)) => terrain.flee_modifier() as i32,
+            _ => 0,
+        }
+    }
+}
+
+/// How well the roll went
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RollTier {
+    /// A natural 1
+    CriticalFailure,
+    Failure,
+    Success,
+    /// A natural 20
+    CriticalSuccess,
+}
+
+impl RollTier {
+    /// Tier of a roll; natural 1 and 20 win over the total
+    pub fn of(natural: u8, total: i32, difficulty: i32) -> Self {
+        match natural {
+            1 => RollTier::CriticalFailure,
+            20 => RollTier::CriticalSuccess,
+            _ if total >= difficulty => RollTier::Success,
+            _ => RollTier::Failure,
+        }
+    }
+
+    /// Check if the approach worked
+    pub fn is_success(&self) -> bool {
+        matches!(self, RollTier::Success | RollTier::CriticalSuccess)
+    }
+}
+
+/// Everything an encounter is resolved from
+pub struct EncounterContext<'a> {
+    /// The player's stats when the encounter starts
+    pub stats: PlayerStats,
+    /// Opponent strength, from 1 to `ENCOUNTER_MAX_THREAT`
+    pub threat: u8,
+    pub approach: EncounterApproach,
+    /// Terrain the encounter happens on, if known
+    pub terrain: Option<TerrainType>,
+    /// Roll twice and keep the lower, after a failed escape
+    pub disadvantage: bool,
+    pub dice: &'a mut dyn DiceService,
+}
+
+/// Standing changes caused by how the encounter was handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EncounterFlags {
+    /// The opponent walked away unharmed
+    pub spared: bool,
+    /// Word travels: positive after fair deals, negative after betrayals
+    pub reputation: i8,
+}
+
+/// Result of an encounter, ready to be applied by the caller
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncounterOutcome {
+    pub approach: EncounterApproach,
+    pub tier: RollTier,
+    /// Natural d20 roll
+    pub roll: u8,
+    /// Roll plus stat and terrain modifiers
+    pub total: i32,
+    pub difficulty: i32,
+    pub damage_dealt: u32,
+    pub damage_taken: u32,
+    pub loot: Vec<(ResourceType, u32)>,
+    /// The player escaped before the encounter played out
+    pub fled: bool,
+    pub flags: EncounterFlags,
+    pub experience: u32,
+}
+
+impl EncounterOutcome {
+    /// One-line summary for the game log
+    pub fn summary(&self) -> String {
+        let verdict = match (self.approach, self.tier.is_success()) {
+            (EncounterApproach::Fight, true) => "The hostiles are driven off",
+            (EncounterApproach::Fight, false) => "The fight goes badly",
+            (EncounterApproach::Negotiate, true) => "The talks end in a deal",
+            (EncounterApproach::Negotiate, false) => "The talks break down",
+            (EncounterApproach::Sneak, true) => "You slip past unseen",
+            (EncounterApproach::Sneak, false) => "You are spotted",
+            (EncounterApproach::Flee, _) if self.fled => "You get away",
+            (EncounterApproach::Flee, _) => "They cut off your escape",
+        };
+        let mut summary = format!(
+            "⚔️ {} (rolled {}, total {} vs {})",
+            verdict, self.roll, self.total, self.difficulty
+        );
+        if self.damage_taken > 0 {
+            summary.push_str(&format!(" - took {} damage", self.damage_taken));
+        }
+        summary
+    }
+}
+
+/// Use case resolving an encounter with an approach chosen by the player
+pub struct ResolveEncounterUseCase;
+
+impl ResolveEncounterUseCase {
+    /// Create a new resolve encounter use case
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Roll for the encounter and work out its outcome
+    pub fn execute(&self, context: EncounterContext<'_>) -> ApplicationResult<EncounterOutcome> {
+        let threat = context.threat;
+        if threat == 0 || threat > ENCOUNTER_MAX_THREAT {
+            return Err(ApplicationError::InvalidInput(format!(
+                "encounter threat must be between 1 and {}, got {}",
+                ENCOUNTER_MAX_THREAT, threat
+            )));
+        }
+        let roll = Self::roll(context.dice)?;
+        let roll = if context.disadvantage {
+            roll.min(Self::roll(context.dice)?)
+        } else {
+            roll
+        };
+
+        let approach = context.approach;
+        let total = roll as i32 + Self::modifier(&context.stats, approach, context.terrain);
+        let difficulty = Self::difficulty(threat);
+        let tier = RollTier::of(roll, total, difficulty);
+
+        let mut outcome = EncounterOutcome {
+            approach,
+            tier,
+            roll,
+            total,
+            difficulty,
+            damage_dealt: 0,
+            damage_taken: 0,
+            loot: Vec::new(),
+            fled: false,
+            flags: EncounterFlags::default(),
+            experience: Self::experience(threat, tier),
+        };
+        Self::apply_approach(&mut outcome, threat as u32);
+        Ok(outcome)
+    }
+
+    /// Chance from 0 to 1 that `approach` succeeds, on a single roll
+    pub fn success_chance(
+        &self,
+        stats: &PlayerStats,
+        threat: u8,
+        approach: EncounterApproach,
+        terrain: Option<TerrainType>,
+    ) -> f32 {
+        let modifier = Self::modifier(stats, approach, terrain);
+        let difficulty = Self::difficulty(threat);
+        let successes = (1..=20u8)
+            .filter(|&roll| RollTier::of(roll, roll as i32 + modifier, difficulty).is_success())
+            .count();
+        successes as f32 / 20.0
+    }
+
+    fn roll(dice: &mut dyn DiceService) -> ApplicationResult<u8> {
+        let roll = dice.roll_d20();
+        if !(1..=20).contains(&roll) {
+            return Err(ApplicationError::InvalidInput(format!(
+                "d20 rolled {}",
+                roll
+            )));
+        }
+        Ok(roll)
+    }
+
+    fn modifier(
+        stats: &PlayerStats,
+        approach: EncounterApproach,
+        terrain: Option<TerrainType>,
+    ) -> i32 {
+        stats.get_modifier(approach.stat()) as i32 + approach.terrain_modifier(terrain)
+    }
+
+    fn difficulty(threat: u8) -> i32 {
+        ENCOUNTER_BASE_DIFFICULTY + threat as i32
+    }
+
+    /// Experience for an encounter: more for success, most for a critical
+    pub(crate) fn experience(threat: u8, tier: RollTier) -> u32 {
+        let threat = threat as u32;
+        match tier {
+            RollTier::CriticalFailure => threat,
+            RollTier::Failure => 2 * threat,
+            RollTier::Success => 10 * threat,
+            RollTier::CriticalSuccess => 20 * threat,
+        }
+    }
+
+    /// Damage, loot and flags of each approach and roll tier
+    fn apply_approach(outcome: &mut EncounterOutcome, threat: u32) {
+        use EncounterApproach::*;
+        use RollTier::*;
+
+        match (outcome.approach, outcome.tier) {
+            (Fight, CriticalFailure) => {
+                outcome.damage_taken = 4 * threat;
+            }
+            (Fight, Failure) => {
+                outcome.damage_dealt = threat;
+                outcome.damage_taken = 2 * threat;
+            }
+            (Fight, Success) => {
+                outcome.damage_dealt = 3 * threat;
+                outcome.damage_taken = threat / 2;
+                outcome.loot.push((ResourceType::Metal, 5 * threat));
+            }
+            (Fight, CriticalSuccess) => {
+                outcome.damage_dealt = 5 * threat;
+                outcome.loot.push((ResourceType::Metal, 10 * threat));
+                outcome.loot.push((ResourceType::Energy, 2 * threat));
+            }
+            (Negotiate, CriticalFailure) => {
+                outcome.damage_taken = 2 * threat;
+                outcome.flags.reputation = -1;
+            }
+            (Negotiate, Failure) => {
+                outcome.flags.spared = true;
+            }
+            (Negotiate, Success) => {
+                outcome.loot.push((ResourceType::Data, 3 * threat));
+                outcome.flags.spared = true;
+                outcome.flags.reputation = 1;
+            }
+            (Negotiate, CriticalSuccess) => {
+                outcome.loot.push((ResourceType::Data, 6 * threat));
+                outcome.flags.spared = true;
+                outcome.flags.reputation = 2;
+            }
+            (Sneak, CriticalFailure) => {
+                outcome.damage_taken = 3 * threat;
+            }
+            (Sneak, Failure) => {
+                outcome.damage_taken = threat;
+            }
+            (Sneak, Success) => {
+                outcome.flags.spared = true;
+            }
+            (Sneak, CriticalSuccess) => {
+                outcome.loot.push((ResourceType::Metal, 4 * threat));
+                outcome.flags.spared = true;
+            }
+            // Turning your back hands them a free hit; the fight goes on
+            (Flee, CriticalFailure) => {
+                outcome.damage_taken = 2 * threat;
+            }
+            (Flee, Failure) => {
+                outcome.damage_taken = threat;
+            }
+            (Flee, Success) | (Flee, CriticalSuccess) => {
+                outcome.fled = true;
+                outcome.flags.spared = true;
+            }
+        }
+    }
+}
+
+impl Default for ResolveEncounterUseCase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THREAT: u8 = 4;
+
+    /// Natural rolls landing on each tier against `THREAT` with average stats
+    const TIER_ROLLS: [(RollTier, u8); 4] = [
+        (RollTier::CriticalFailure, 1),
+        (RollTier::Failure, 5),
+        (RollTier::Success, 15),
+        (RollTier::CriticalSuccess, 20),
+    ];
+
+    fn resolve(approach: EncounterApproach, roll: u8) -> EncounterOutcome {
+        let mut dice = FixedRoll(roll);
+        ResolveEncounterUseCase::new()
+            .execute(EncounterContext {
+                stats: PlayerStats::starting_stats(),
+                threat: THREAT,
+                approach,
+                terrain: None,
+                disadvantage: false,
+                dice: &mut dice,
+            })
+            .unwrap()
+    }
+
+    /// Expected (damage dealt, damage taken, loot, fled, spared, reputation)
+    fn expected(
+        approach: EncounterApproach,
+        tier: RollTier,
+    ) -> (u32, u32, Vec<(ResourceType, u32)>, bool, bool, i8) {
+        use EncounterApproach::*;
+        use RollTier::*;
+
+        match (approach, tier) {
+            (Fight, CriticalFailure) => (0, 16, vec![], false, false, 0),
+            (Fight, Failure) => (4, 8, vec![], false, false, 0),
+            (Fight, Success) => (12, 2, vec![(ResourceType::Metal, 20)], false, false, 0),
+            (Fight, CriticalSuccess) => (
+                20,
+                0,
+                vec![(ResourceType::Metal, 40), (ResourceType::Energy, 8)],
+                false,
+                false,
+                0,
+            ),
+            (Negotiate, CriticalFailure) => (0, 8, vec![], false, false, -1),
+            (Negotiate, Failure) => (0, 0, vec![], false, true, 0),
+            (Negotiate, Success) => (0, 0, vec![(ResourceType::Data, 12)], false, true, 1),
+            (Negotiate, CriticalSuccess) => (0, 0, vec![(ResourceType::Data, 24)], false, true, 2),
+            (Sneak, CriticalFailure) => (0, 12, vec![], false, false, 0),
+            (Sneak, Failure) => (0, 4, vec![], false, false, 0),
+            (Sneak, Success) => (0, 0, vec![], false, true, 0),
+            (Sneak, CriticalSuccess) => (0, 0, vec![(ResourceType::Metal, 16)], false, true, 0),
+            (Flee, CriticalFailure) => (0, 8, vec![], false, false, 0),
+            (Flee, Failure) => (0, 4, vec![], false, false, 0),
+            (Flee, Success) => (0, 0, vec![], true, true, 0),
+            (Flee, CriticalSuccess) => (0, 0, vec![], true, true, 0),
+        }
+    }
+
+    #[test]
+    fn every_approach_and_tier_resolves_as_specified() {
+        for approach in EncounterApproach::ALL {
+            for (tier, roll) in TIER_ROLLS {
+                let outcome = resolve(approach, roll);
+                assert_eq!(outcome.tier, tier, "{:?} rolling {}", approach, roll);
+
+                let (dealt, taken, loot, fled, spared, reputation) = expected(approach, tier);
+                let context = format!("{:?} / {:?}", approach, tier);
+                assert_eq!(outcome.damage_dealt, dealt, "{}", context);
+                assert_eq!(outcome.damage_taken, taken, "{}", context);
+                assert_eq!(outcome.loot, loot, "{}", context);
+                assert_eq!(outcome.fled, fled, "{}", context);
+                assert_eq!(outcome.flags.spared, spared, "{}", context);
+                assert_eq!(outcome.flags.reputation, reputation, "{}", context);
+                assert_eq!(outcome.experience > 2 * THREAT as u32, tier.is_success());
+            }
+        }
+    }
+
+    #[test]
+    fn approach_stat_modifies_the_roll() {
+        let mut stats = PlayerStats::starting_stats();
+        stats.charisma = 18;
+        let mut dice = FixedRoll(9);
+
+        let outcome = ResolveEncounterUseCase::new()
+            .execute(EncounterContext {
+                stats,
+                threat: THREAT,
+                approach: EncounterApproach::Negotiate,
+                terrain: None,
+                disadvantage: false,
+                dice: &mut dice,
+            })
+            .unwrap();
+
+        // 9 + 4 meets the difficulty of 8 + 4; strength would not help
+        assert_eq!(outcome.total, 13);
+        assert_eq!(outcome.difficulty, 12);
+        assert_eq!(outcome.tier, RollTier::Success);
+        assert_eq!(resolve(EncounterApproach::Fight, 9).tier, RollTier::Failure);
+    }
+
+    /// Dice showing a scripted sequence of faces
+    struct ScriptedDice(Vec<u8>);
+
+    impl DiceService for ScriptedDice {
+        fn roll_d20(&mut self) -> u8 {
+            self.0.remove(0)
+        }
+
+        fn roll_d6(&mut self) -> u8 {
+            self.0.remove(0)
+        }
+    }
+
+    fn flee(
+        terrain: Option<TerrainType>,
+        disadvantage: bool,
+        dice: &mut dyn DiceService,
+    ) -> EncounterOutcome {
+        ResolveEncounterUseCase::new()
+            .execute(EncounterContext {
+                stats: PlayerStats::starting_stats(),
+                threat: THREAT,
+                approach: EncounterApproach::Flee,
+                terrain,
+                disadvantage,
+                dice,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn fleeing_is_easier_on_open_ground() {
+        // 10 + 3 beats the difficulty of 12 on the plains, 10 - 3 in a swamp does not
+        let plains = flee(Some(TerrainType::Plains), false, &mut FixedRoll(10));
+        assert_eq!(plains.total, 13);
+        assert!(plains.fled);
+        let swamp = flee(Some(TerrainType::Swamp), false, &mut FixedRoll(10));
+        assert_eq!(swamp.total, 7);
+        assert!(!swamp.fled);
+        assert_eq!(swamp.damage_taken, THREAT as u32);
+
+        let use_case = ResolveEncounterUseCase::new();
+        let stats = PlayerStats::starting_stats();
+        let chance = |threat, terrain| {
+            use_case.success_chance(&stats, threat, EncounterApproach::Flee, Some(terrain))
+        };
+        assert_eq!(chance(THREAT, TerrainType::Plains), 0.6);
+        assert_eq!(chance(THREAT, TerrainType::Forest), 0.45);
+        assert_eq!(chance(THREAT, TerrainType::Cave), 0.3);
+        // A stronger opponent is harder to shake off
+        assert!(chance(8, TerrainType::Plains) < chance(THREAT, TerrainType::Plains));
+        // Terrain only matters when running
+        assert_eq!(
+            use_case.success_chance(
+                &stats,
+                THREAT,
+                EncounterApproach::Fight,
+                Some(TerrainType::Plains)
+            ),
+            use_case.success_chance(
+                &stats,
+                THREAT,
+                EncounterApproach::Fight,
+                Some(TerrainType::Cave)
+            ),
+        );
+    }
+
+    #[test]
+    fn disadvantage_keeps_the_lower_roll() {
+        let outcome = flee(
+            Some(TerrainType::Plains),
+            true,
+            &mut ScriptedDice(vec![15, 6]),
+        );
+        assert_eq!(outcome.roll, 6);
+        assert!(!outcome.fled);
+        assert_eq!(outcome.damage_taken, THREAT as u32);
+
+        let outcome = flee(
+            Some(TerrainType::Plains),
+            true,
+            &mut ScriptedDice(vec![6, 15]),
+        );
+        assert_eq!(outcome.roll, 6);
+        let outcome = flee(
+            Some(TerrainType::Plains),
+            false,
+            &mut ScriptedDice(vec![15, 6]),
+        );
+        assert_eq!(outcome.roll, 15);
+        assert!(outcome.fled);
+    }
+
+    #[test]
+    fn invalid_threat_and_rolls_are_rejected() {
+        let use_case = ResolveEncounterUseCase::new();
+        for (threat, roll) in [
+            (0, 10),
+            (ENCOUNTER_MAX_THREAT + 1, 10),
+            (THREAT, 0),
+            (THREAT, 21),
+        ] {
+            let mut dice = FixedRoll(roll);
+            let result = use_case.execute(EncounterContext {
+                stats: PlayerStats::starting_stats(),
+                threat,
+                approach: EncounterApproach::Fight,
+                terrain: None,
+                disadvantage: false,
+                dice: &mut dice,
+            });
+            assert!(matches!(result, Err(ApplicationError::InvalidInput(_))));
+        }
+    }
+
+    #[test]
+    fn rng_dice_stay_on_the_d20() {
+        let mut dice = RngDice(rand::thread_rng());
+        for _ in 0..200 {
+            assert!((1..=20).contains(&dice.roll_d20()));
+        }
+    }
+}
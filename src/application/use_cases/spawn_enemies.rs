@@ -1,50 +0,0 @@
-//! Spawn Enemies Use Case - Enemy Creation Logic
-//!
-//! Handles enemy spawning requests with business rule validation
-//! and coordinates with domain entities and services.
-
-use crate::application::{dto::SpawnEnemiesInput, dto::SpawnEnemiesOutput, ApplicationResult};
-use crate::domain::{Enemy, EnemyType, Position, SpawningService, Velocity};
-use std::sync::Arc;
-
-/// Use case for handling enemy spawning operations
-pub struct SpawnEnemiesUseCase {
-    spawning_service: SpawningService,
-}
-
-impl SpawnEnemiesUseCase {
-    /// Create a new spawn enemies use case
-    pub fn new(spawning_service: SpawningService) -> Self {
-        Self { spawning_service }
-    }
-
-    /// Execute enemy spawning with business rules
-    pub fn execute(&self, input: SpawnEnemiesInput) -> ApplicationResult<SpawnEnemiesOutput> {
-        // Generate unique enemy ID
-        let enemy_id = format!("enemy_{}", chrono::Utc::now().timestamp_millis());
-
-        // Create enemy with provided parameters
-        let enemy_type = match input.enemy_type.as_str() {
-            "Basic" => EnemyType::Basic,
-            _ => EnemyType::Basic, // Default to Basic for now
-        };
-
-        // For now, just return the input values
-        // In a real implementation, this would create and store the enemy entity
-        Ok(SpawnEnemiesOutput {
-            enemy_id,
-            position: input.spawn_position,
-            velocity: input.velocity,
-        })
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn spawn_enemies_use_case_creation() {
-        // This is a stub test for compilation
-    }
-}
@@ -0,0 +1,210 @@
+//! Start Scenario Use Case - Apply a starting scenario to a new run
+//!
+//! Runs once a new character stands on a shaped spawn area, before any
+//! mutator is applied. Lowers the rolled stats, swaps the usual starting
+//! resources for the scenario's, levels the character up, hands out
+//! consumables, charts the area around the spawn, leaves the scenario's
+//! wreck and readies the ground the base will be built on. Creating the
+//! base itself is left to the caller, at `ScenarioStart::base_position`.
+
+use crate::application::{ApplicationError, ApplicationResult};
+use crate::domain::constants::experience_for_level;
+use crate::domain::entities::{Map, Player};
+use crate::domain::services::{
+    chart_start_area, ConsumableKind, MapService, Scenario, ScenarioStart, WreckField, WreckOrigin,
+};
+
+/// Use case applying a scenario to a freshly created character and map
+pub struct StartScenarioUseCase;
+
+impl StartScenarioUseCase {
+    /// Create a new start scenario use case
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Apply `scenario`, with its random parts drawn in `start`
+    pub fn execute(
+        &self,
+        scenario: &Scenario,
+        start: &ScenarioStart,
+        player: &mut Player,
+        map: &mut Map,
+        wrecks: &mut WreckField,
+    ) -> ApplicationResult<()> {
+        if scenario.start_level == 0 {
+            return Err(ApplicationError::InvalidInput(format!(
+                "scenario {} must start at level 1 or above",
+                scenario.id
+            )));
+        }
+
+        for stat in &start.lowered_stats {
+            player.decrease_stat(*stat, scenario.stat_penalty);
+        }
+
+        let resources = scenario.starting_resources(player.resources());
+        *player.resources_mut() = resources;
+
+        let missing =
+            experience_for_level(scenario.start_level).saturating_sub(player.experience().points());
+        if missing > 0 {
+            player.add_experience(missing)?;
+        }
+
+        if scenario.scout_probes > 0 {
+            player
+                .consumables_mut()
+                .add(ConsumableKind::ScoutProbe, scenario.scout_probes);
+        }
+
+        let spawn = *player.position();
+        if scenario.reveal_radius > 0 {
+            chart_start_area(map, spawn, scenario.reveal_radius);
+        }
+
+        let map_service = MapService::new(map.seed());
+        if let Some(position) = start.wreck {
+            map_service.prepare_site(map, position)?;
+            wrecks.leave(position, WreckOrigin::Crash, scenario.wreck_loot.clone());
+        }
+        if start.base_position != spawn {
+            map_service.prepare_site(map, start.base_position)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for StartScenarioUseCase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::constants::{
+        CRASH_SITE_LOOT, CRASH_SURVIVOR_SALVAGED_METAL, CRASH_SURVIVOR_STAT_PENALTY,
+        VETERAN_SCOUT_LEVEL, VETERAN_SCOUT_REVEAL_RADIUS,
+    };
+    use crate::domain::value_objects::{
+        EntityId, Position3D, ResourceType, StatType, TileCoordinate,
+    };
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    struct Fixture {
+        player: Player,
+        map: Map,
+        wrecks: WreckField,
+    }
+
+    fn fixture() -> Fixture {
+        let spawn = Position3D::origin();
+        let mut map = Map::new(EntityId::generate(), "Test".to_string(), 1948).unwrap();
+        let service = MapService::new(1948);
+        service.shape_spawn_area(&mut map, spawn).unwrap();
+        Fixture {
+            player: Player::create_new_character("Tester".to_string(), spawn).unwrap(),
+            map,
+            wrecks: WreckField::new(),
+        }
+    }
+
+    fn explored(map: &Map) -> usize {
+        map.tiles()
+            .values()
+            .filter(|tile| tile.is_explored())
+            .count()
+    }
+
+    fn is_charted(map: &Map, position: Position3D) -> bool {
+        map.get_tile(&TileCoordinate::from(position))
+            .is_some_and(|tile| tile.is_explored())
+    }
+
+    fn run(scenario: &Scenario, fixture: &mut Fixture) -> ScenarioStart {
+        let start = scenario.roll(*fixture.player.position(), &mut StdRng::seed_from_u64(1948));
+        StartScenarioUseCase::new()
+            .execute(
+                scenario,
+                &start,
+                &mut fixture.player,
+                &mut fixture.map,
+                &mut fixture.wrecks,
+            )
+            .unwrap();
+        start
+    }
+
+    #[test]
+    fn standard_drop_changes_nothing() {
+        let mut fixture = fixture();
+        let before = fixture.player.clone();
+        let charted = explored(&fixture.map);
+
+        let start = run(&Scenario::standard_drop(), &mut fixture);
+        assert_eq!(start.base_position, *before.position());
+        assert_eq!(fixture.player.stats(), before.stats());
+        assert_eq!(fixture.player.resources(), before.resources());
+        assert_eq!(fixture.player.level(), 1);
+        assert_eq!(explored(&fixture.map), charted);
+        assert!(fixture.wrecks.wrecks().is_empty());
+    }
+
+    #[test]
+    fn crash_survivor_is_hurt_but_has_salvage_and_a_walk_to_the_base() {
+        let mut fixture = fixture();
+        let before = fixture.player.clone();
+
+        let start = run(&Scenario::crash_survivor(), &mut fixture);
+        for stat in StatType::all() {
+            let expected = if start.lowered_stats.contains(&stat) {
+                before.stats().get_stat(stat) - CRASH_SURVIVOR_STAT_PENALTY
+            } else {
+                before.stats().get_stat(stat)
+            };
+            assert_eq!(fixture.player.stats().get_stat(stat), expected);
+        }
+        assert_eq!(
+            fixture.player.resources().get_amount(ResourceType::Metal),
+            before.resources().get_amount(ResourceType::Metal) + CRASH_SURVIVOR_SALVAGED_METAL
+        );
+
+        let wreck = &fixture.wrecks.wrecks()[0];
+        assert_eq!(Some(wreck.position), start.wreck);
+        assert_eq!(wreck.origin, WreckOrigin::Crash);
+        assert_eq!(wreck.loot, CRASH_SITE_LOOT.to_vec());
+        assert!(fixture.map.is_passable(&wreck.position));
+        assert!(fixture.map.is_passable(&start.base_position));
+    }
+
+    #[test]
+    fn veteran_scout_levels_up_and_charts_but_travels_light() {
+        let mut fixture = fixture();
+        let before = fixture.player.clone();
+
+        run(&Scenario::veteran_scout(), &mut fixture);
+        assert_eq!(fixture.player.level(), VETERAN_SCOUT_LEVEL);
+        assert_eq!(
+            fixture.player.max_movement_points(),
+            before.max_movement_points() + 1
+        );
+        assert_eq!(
+            fixture
+                .player
+                .consumables()
+                .count(ConsumableKind::ScoutProbe),
+            1
+        );
+        assert_eq!(
+            fixture.player.resources().get_amount(ResourceType::Metal),
+            before.resources().get_amount(ResourceType::Metal) * 3 / 4
+        );
+        let radius = VETERAN_SCOUT_REVEAL_RADIUS as i32;
+        assert!(is_charted(&fixture.map, Position3D::new(radius - 1, 0, 0)));
+        assert!(!is_charted(&fixture.map, Position3D::new(radius + 1, 0, 0)));
+    }
+}
@@ -35,6 +35,12 @@ pub const CRITICAL_SUCCESS_THRESHOLD: u8 = 18;
 /// Critical failure threshold
 pub const CRITICAL_FAILURE_THRESHOLD: u8 = 2;
 
+/// Seconds of play between automatic dice rolls when there is no keyboard
+pub const AUTO_ROLL_INTERVAL_SECS: f32 = 5.0;
+
+/// Seconds between status lines of the headless web build
+pub const STATUS_LOG_INTERVAL_SECS: f32 = 10.0;
+
 // =============================================================================
 // PLAYER PROGRESSION CONSTANTS
 // =============================================================================
@@ -60,6 +66,15 @@ pub const DEFAULT_STAT_VALUE: u8 = 10;
 /// Stat points gained per level up
 pub const STAT_POINTS_PER_LEVEL: u8 = 2;
 
+/// Experience for exploring a tile, by the terrain's difficulty tier
+pub const EXPLORATION_XP_EASY: u32 = 2;
+pub const EXPLORATION_XP_ROUGH: u32 = 3;
+pub const EXPLORATION_XP_HARSH: u32 = 5;
+pub const EXPLORATION_XP_EXTREME: u32 = 8;
+
+/// Multiplier on the first tile of each terrain type explored in a run
+pub const FIRST_DISCOVERY_XP_MULTIPLIER: u32 = 5;
+
 // =============================================================================
 // MOVEMENT AND ACTION POINTS CONSTANTS
 // =============================================================================
@@ -94,6 +109,12 @@ pub const DURATION_PER_MOVEMENT_POINT_MS: f32 = 700.0;
 /// Minimum movement points that trigger automatic rest
 pub const AUTO_REST_MOVEMENT_THRESHOLD: u8 = 0;
 
+/// Seconds a tapped move target waits for the confirming second tap
+pub const TAP_CONFIRM_TIMEOUT_SECS: f32 = 2.0;
+
+/// Milliseconds after a tapped move starts in which tapping the origin undoes it
+pub const TAP_UNDO_WINDOW_MS: u64 = 300;
+
 /// Per-terrain animation duration multipliers (applied to base calculation)
 /// These multipliers affect how long the movement animation takes based on terrain type
 /// 1.0 = normal speed, >1.0 = slower, <1.0 = faster
@@ -156,6 +177,795 @@ pub const TILE_GENERATION_BUFFER: u32 = 2;
 /// Enable diamond pattern fog of war (true = diamond, false = circular)
 pub const FOG_OF_WAR_DIAMOND_PATTERN: bool = true;
 
+/// Tiles explored before the player receives a survey report of their region
+pub const SURVEY_REPORT_TILES: u32 = 100;
+
+/// Radius around the player covered by the survey report
+pub const SURVEY_REPORT_RADIUS: u32 = 6;
+
+// =============================================================================
+// SPAWN SHAPING CONSTANTS
+// =============================================================================
+
+/// Radius around the starting position curated for a fair first day
+pub const SPAWN_SHAPING_RADIUS: u32 = 5;
+
+/// Minimum share of low-cost passable tiles within the spawn radius
+pub const SPAWN_MIN_LOW_COST_SHARE: f32 = 0.6;
+
+/// Highest movement cost still considered low-cost terrain near the spawn
+pub const SPAWN_LOW_COST_MAX_MOVEMENT: u8 = 2;
+
+/// Minimum number of common resource nodes within the spawn radius
+pub const SPAWN_MIN_COMMON_NODES: usize = 2;
+
+/// Moves at the start of a new session during which Hazard/Combat events are suppressed
+pub const NEW_SESSION_GRACE_MOVES: u8 = 5;
+
+// =============================================================================
+// FORTUNE'S FAVOR ASSIST CONSTANTS
+// =============================================================================
+
+/// Number of recent meaningful roll outcomes tracked by the assist
+pub const FORTUNE_WINDOW_SIZE: usize = 10;
+
+/// Failures in the window (with no successes) before the assist kicks in
+pub const FORTUNE_MIN_FAILURES: usize = 4;
+
+/// Failures in the window before the assist bonus escalates
+pub const FORTUNE_ESCALATION_FAILURES: usize = 6;
+
+/// Bonus applied once the assist kicks in
+pub const FORTUNE_BASE_BONUS: i8 = 1;
+
+/// Bonus applied once the assist has escalated
+pub const FORTUNE_ESCALATED_BONUS: i8 = 2;
+
+// =============================================================================
+// RUIN DELVING CONSTANTS
+// =============================================================================
+
+/// Width and height of a generated interior, walls included
+pub const INTERIOR_SIZE: i32 = 9;
+
+/// Fewest rooms generated in an interior
+pub const INTERIOR_MIN_ROOMS: usize = 2;
+
+/// Most rooms generated in an interior (one per quadrant)
+pub const INTERIOR_MAX_ROOMS: usize = 4;
+
+/// One in this many constructed overworld tiles hides an enterable ruin
+pub const INTERIOR_SITE_RARITY: u64 = 6;
+
+/// Amount of a resource found on each loot tile
+pub const INTERIOR_LOOT_AMOUNT: u32 = 15;
+
+/// Item id of the relics found on loot tiles
+pub const RUIN_RELIC_ID: &str = "ruin_relic";
+
+/// Carrying weight of a single relic
+pub const RUIN_RELIC_WEIGHT: u32 = 5;
+
+/// Experience the base grants for each relic handed in
+pub const RUIN_RELIC_EXPERIENCE: u32 = 20;
+
+// =============================================================================
+// EXPEDITION PLANNING CONSTANTS
+// =============================================================================
+
+/// Maximum number of nodes the pathfinder expands before giving up
+pub const PATHFINDING_MAX_EXPANSIONS: usize = 4096;
+
+/// Maximum number of waypoints in one expedition plan
+pub const EXPEDITION_MAX_WAYPOINTS: usize = 8;
+
+/// Distance from the planned route that prompts to abandon the expedition
+pub const EXPEDITION_DEVIATION_TILES: u32 = 3;
+
+/// Food recommended per expedition day
+pub const EXPEDITION_FOOD_PER_DAY: u32 = 5;
+
+/// Energy recommended per expedition day
+pub const EXPEDITION_ENERGY_PER_DAY: u32 = 3;
+
+// =============================================================================
+// GHOST TRAIL CONSTANTS
+// =============================================================================
+
+/// Previous runs kept per seed, best scores first
+pub const GHOST_MAX_RUNS_PER_SEED: usize = 3;
+
+/// Opacity of ghost trail markers
+pub const GHOST_TRAIL_ALPHA: f32 = 0.35;
+
+// =============================================================================
+// RESCUE MISSION CONSTANTS
+// =============================================================================
+
+/// Chance in percent per step that a distress signal is picked up
+pub const RESCUE_SIGNAL_CHANCE: u8 = 2;
+
+/// Signal chance near Constructed or Anomaly terrain
+pub const RESCUE_SIGNAL_CHANCE_NEAR_SITE: u8 = 6;
+
+/// Tiles around the player searched for Constructed or Anomaly terrain
+pub const RESCUE_SITE_SCAN_RADIUS: u32 = 2;
+
+/// Closest and farthest objective placement, in tiles
+pub const RESCUE_MIN_DISTANCE: u32 = 6;
+pub const RESCUE_MAX_DISTANCE: u32 = 12;
+
+/// Rests before a distress signal goes silent
+pub const RESCUE_COUNTDOWN_RESTS: u32 = 3;
+
+/// Difficulty of the Charisma or Strength check on arrival (d20)
+pub const RESCUE_CHECK_DIFFICULTY: i32 = 12;
+
+/// Experience for a successful and a failed rescue
+pub const RESCUE_SUCCESS_EXPERIENCE: u32 = 150;
+pub const RESCUE_FAILURE_EXPERIENCE: u32 = 25;
+
+/// Side length of a threat region, in tiles
+pub const RESCUE_THREAT_REGION_SIZE: i32 = 16;
+
+/// Highest movement roll penalty from expired signals in one region
+pub const RESCUE_MAX_REGION_THREAT: u8 = 3;
+
+// =============================================================================
+// QUEST BOARD CONSTANTS
+// =============================================================================
+
+/// Rests between two postings of the base's bulletin board
+pub const QUEST_BOARD_REFRESH_RESTS: u32 = 2;
+
+/// New offers put up at each posting
+pub const QUEST_BOARD_OFFERS: usize = 3;
+
+/// Quests the player may hold at once without Living Quarters
+pub const QUEST_ACTIVE_SLOTS: usize = 3;
+
+/// Extra slots at most, one per Living Quarters level
+pub const QUEST_MAX_EXTRA_SLOTS: usize = 2;
+
+/// Days an abandoned quest's template sits out the postings
+pub const QUEST_ABANDON_COOLDOWN_DAYS: u32 = 1;
+
+/// Closest and farthest quest target from the base, in tiles
+pub const QUEST_TARGET_MIN_DISTANCE: u32 = 5;
+pub const QUEST_TARGET_MAX_DISTANCE: u32 = 14;
+
+/// New tiles a charting quest asks for
+pub const QUEST_CHARTING_TILES: u32 = 20;
+
+/// Metal a salvage quest asks to be hauled
+pub const QUEST_SALVAGE_METAL: u32 = 40;
+
+/// Rests an endurance quest asks for
+pub const QUEST_ENDURANCE_RESTS: u32 = 3;
+
+/// Token paid when a quest's target is gone: experience and Metal
+pub const QUEST_COMPENSATION_EXPERIENCE: u32 = 20;
+pub const QUEST_COMPENSATION_METAL: u32 = 10;
+
+// =============================================================================
+// AMBIENT FAUNA CONSTANTS
+// =============================================================================
+
+/// Most fauna alive at once
+pub const FAUNA_MAX_TOTAL: usize = 12;
+
+/// Most fauna alive at once on one terrain type
+pub const FAUNA_MAX_PER_TERRAIN: usize = 4;
+
+/// Chance that a free, visible habitat tile gains a creature per spawn pass
+pub const FAUNA_SPAWN_CHANCE: f64 = 0.08;
+
+/// Seconds between wander steps
+pub const FAUNA_WANDER_INTERVAL_SECS: f32 = 3.0;
+
+/// Seconds a wander step takes
+pub const FAUNA_STEP_SECS: f32 = 0.8;
+
+/// Chance that scattering fauna leaves behind 1 Organics
+pub const FAUNA_SCATTER_DROP_CHANCE: f64 = 0.02;
+
+// =============================================================================
+// LOW MOVEMENT GUARD CONSTANTS
+// =============================================================================
+
+/// Default movement points at or below which the guard speaks up
+pub const LOW_POINTS_DEFAULT_THRESHOLD: u8 = 2;
+
+/// Seconds the low movement HUD warning pulses
+pub const LOW_POINTS_WARNING_PULSE_SECS: f32 = 4.0;
+
+// =============================================================================
+// ANNOUNCEMENT CONSTANTS
+// =============================================================================
+
+/// Seconds within which announcements are merged into one, from the first
+pub const ANNOUNCEMENT_COALESCE_SECS: f64 = 0.5;
+
+/// Food or Energy at or below which a falling amount is announced
+pub const ANNOUNCEMENT_LOW_SUPPLY_UNITS: u32 = 5;
+
+// =============================================================================
+// MAP EXPORT CONSTANTS
+// =============================================================================
+
+/// Most cells an exported map has per side; larger regions are downsampled
+pub const MAP_EXPORT_MAX_CELLS: u32 = 512;
+
+/// Pixels per side of one cell in an exported image
+pub const MAP_EXPORT_PIXELS_PER_CELL: u32 = 4;
+
+/// Pixels per font pixel of the footer line in an exported image
+pub const MAP_EXPORT_FOOTER_SCALE: u32 = 2;
+
+// =============================================================================
+// WARNING LAYER CONSTANTS
+// =============================================================================
+
+/// Share of health below which the low-health warning starts
+pub const WARNING_HEALTH_THRESHOLD: f32 = 0.25;
+
+/// Extra share of health needed before the warning clears again
+pub const WARNING_HYSTERESIS_BAND: f32 = 0.05;
+
+/// Weakest severity of an active warning, so it is never inaudible
+pub const WARNING_MIN_SEVERITY: f32 = 0.25;
+
+/// Severity of running out of Food
+pub const WARNING_STARVING_SEVERITY: f32 = 0.6;
+
+/// Seconds of one vignette pulse
+pub const WARNING_PULSE_SECONDS: f32 = 2.0;
+
+/// Vignette opacity at full severity
+pub const WARNING_VIGNETTE_ALPHA: f32 = 0.35;
+
+/// Width of the translucent vignette band and of the high contrast border
+pub const WARNING_VIGNETTE_WIDTH: f32 = 64.0;
+pub const WARNING_BORDER_WIDTH: f32 = 6.0;
+
+/// Heartbeat volume at full severity
+pub const WARNING_HEARTBEAT_VOLUME: f32 = 0.4;
+
+// =============================================================================
+// ANOMALY STORM CONSTANTS
+// =============================================================================
+
+/// Tiles from the origin where the deep zone starts (Manhattan distance)
+pub const STORM_DEEP_ZONE_DISTANCE: u32 = 24;
+
+/// Chance in percent per rest that a storm forms while the player is in the deep zone
+pub const STORM_SPAWN_CHANCE: u8 = 8;
+
+/// Closest and farthest storm formation from the player, in tiles
+pub const STORM_SPAWN_MIN_DISTANCE: u32 = 4;
+pub const STORM_SPAWN_MAX_DISTANCE: u32 = 10;
+
+/// Most storms active at once
+pub const STORM_MAX_ACTIVE: usize = 2;
+
+/// Radius of a storm's footprint, in tiles
+pub const STORM_RADIUS: u32 = 2;
+
+/// Tiles a storm travels per rest
+pub const STORM_MIN_STEP: u32 = 1;
+pub const STORM_MAX_STEP: u32 = 3;
+
+/// Chance in percent that each step follows the storm's heading
+pub const STORM_HEADING_BIAS_PERCENT: u8 = 60;
+
+/// Rests a storm lasts before dissipating
+pub const STORM_MIN_LIFETIME_RESTS: u32 = 5;
+pub const STORM_MAX_LIFETIME_RESTS: u32 = 10;
+
+/// Movement cost multiplier inside a storm
+pub const STORM_MOVEMENT_COST_MULTIPLIER: u8 = 2;
+
+/// Chance in percent per move inside a storm of a Hazard event
+pub const STORM_HAZARD_CHANCE: u8 = 25;
+
+/// Chance in percent that a swept Metal node transmutes into Exotic Matter
+pub const STORM_TRANSMUTE_CHANCE: u8 = 10;
+
+/// Chance in percent that a swept node is depleted
+pub const STORM_DEPLETE_CHANCE: u8 = 20;
+
+// =============================================================================
+// INVENTORY TRANSFER CONSTANTS
+// =============================================================================
+
+/// Carrying capacity before Strength, gear and base facilities
+pub const CARRYING_CAPACITY_BASE: u32 = 100;
+
+/// Carrying capacity each point of Strength adds
+pub const CARRYING_CAPACITY_PER_STRENGTH: u32 = 10;
+
+/// Days of supplies kept in the cargo by "Deposit all except reserve"
+pub const INVENTORY_DEFAULT_RESERVE_DAYS: u32 = 3;
+
+/// Most days of supplies the reserve can be set to
+pub const INVENTORY_MAX_RESERVE_DAYS: u32 = 10;
+
+/// Food and Energy a day in the field uses up, for the reserve estimate
+pub const INVENTORY_RESERVE_FOOD_PER_DAY: u32 = 5;
+pub const INVENTORY_RESERVE_ENERGY_PER_DAY: u32 = 3;
+
+// =============================================================================
+// CAMERA HINT CONSTANTS
+// =============================================================================
+
+/// Seconds the camera frames the source of a notable event
+pub const CAMERA_HINT_DURATION_SECS: f32 = 1.5;
+
+/// Shortest gap between the starts of two camera hints, in seconds
+pub const CAMERA_HINT_MIN_INTERVAL_SECS: f32 = 5.0;
+
+/// Most hints waiting in the queue; older ones are dropped first
+pub const CAMERA_HINT_MAX_QUEUED: usize = 4;
+
+/// Share of the viewport, from the center, that counts as on-screen
+pub const CAMERA_HINT_SCREEN_MARGIN: f32 = 0.9;
+
+// =============================================================================
+// BLITZ MODE CONSTANTS
+// =============================================================================
+
+/// Default seconds per exploration decision in blitz runs
+pub const BLITZ_DEFAULT_DECISION_SECS: u32 = 10;
+
+/// Shortest and longest decision time the settings accept
+pub const BLITZ_MIN_DECISION_SECS: u32 = 3;
+pub const BLITZ_MAX_DECISION_SECS: u32 = 60;
+
+/// Extra seconds granted when a modal closes
+pub const BLITZ_MODAL_BONUS_SECS: f32 = 2.0;
+
+/// Remaining seconds at which the countdown turns red
+pub const BLITZ_CRITICAL_SECS: f32 = 3.0;
+
+// =============================================================================
+// ENCOUNTER CONSTANTS
+// =============================================================================
+
+/// Difficulty of an encounter check before the opponent's threat is added
+pub const ENCOUNTER_BASE_DIFFICULTY: i32 = 8;
+
+/// Highest opponent threat; matches the terrain danger scale
+pub const ENCOUNTER_MAX_THREAT: u8 = 10;
+
+/// Hull damage for each movement point a hazard takes
+pub const HAZARD_DAMAGE_PER_POINT: u32 = 5;
+
+/// Rounds of a fight; winning most of them wins the encounter
+pub const ENCOUNTER_EXCHANGE_ROUNDS: usize = 3;
+
+/// Opponent's side of a round: threat times this, plus a d6
+pub const ENCOUNTER_THREAT_WEIGHT: i32 = 2;
+
+/// Damage per threat point for every round of a fight that is lost
+pub const ENCOUNTER_ROUND_DAMAGE: u32 = 2;
+
+/// Metal salvaged per point the won rounds were won by
+pub const ENCOUNTER_LOOT_PER_MARGIN: u32 = 2;
+
+/// Movement points a successful escape costs on top of the step back
+pub const FLEE_EXTRA_MOVEMENT_COST: u8 = 1;
+
+/// Tiles from which fled hostiles can still catch up on the next turn
+pub const HOSTILE_PURSUIT_RANGE: u32 = 2;
+
+/// d20 roll fled hostiles need to catch up from beyond arm's reach
+pub const HOSTILE_PURSUIT_DIFFICULTY: u8 = 12;
+
+/// Roll a mystery needs before its meaning is understood
+pub const MYSTERY_INSIGHT_ROLL: u8 = 15;
+
+// =============================================================================
+// WRECK CONSTANTS
+// =============================================================================
+
+/// Unclaimed wrecks on the map at once; a new one pushes out the oldest
+pub const WRECK_CAP: usize = 10;
+
+/// Movement points spent salvaging a wreck
+pub const WRECK_SALVAGE_COST: u8 = 1;
+
+/// Rests after which the debris of a salvaged wreck is cleared away
+pub const WRECK_DEBRIS_RESTS: u32 = 5;
+
+/// Intelligence check totals reaching a standard and a rich salvage yield
+pub const WRECK_SALVAGE_STANDARD_DC: i32 = 8;
+pub const WRECK_SALVAGE_RICH_DC: i32 = 16;
+
+// =============================================================================
+// DECAL CONSTANTS
+// =============================================================================
+
+/// Decals a tile shows at once; another one replaces the oldest
+pub const DECAL_TILE_CAP: usize = 2;
+
+/// Rests over which a scorch mark fades away
+pub const DECAL_SCORCH_RESTS: u32 = 10;
+
+// =============================================================================
+// HAGGLE CONSTANTS
+// =============================================================================
+
+/// Worth, in base resource value, of what a field trader asks for
+pub const HAGGLE_TRADE_VALUE: u32 = 60;
+
+/// Counters the player may make before having to take or leave the offer
+pub const HAGGLE_MAX_COUNTERS: usize = 3;
+
+/// Charisma check difficulty of the first counter
+pub const HAGGLE_COUNTER_DIFFICULTY: i32 = 10;
+
+/// Difficulty added by every counter already made
+pub const HAGGLE_COUNTER_DIFFICULTY_STEP: i32 = 3;
+
+/// Share of the offered goods a counter wins or loses, in percent
+pub const HAGGLE_COUNTER_STEP_PERCENT: u32 = 20;
+
+/// Missing a counter by more than this makes the trader call talks off
+pub const HAGGLE_WALKOUT_MARGIN: i32 = 5;
+
+// =============================================================================
+// SEASON CONSTANTS
+// =============================================================================
+
+/// In-game days each season lasts
+pub const SEASON_LENGTH_DAYS: u32 = 15;
+
+/// Multiplier a Flare puts on Energy found
+pub const FLARE_ENERGY_YIELD_MULTIPLIER: f32 = 1.5;
+
+/// Chance per rest in a Flare that a piece of equipped gear malfunctions, in percent
+pub const FLARE_MALFUNCTION_CHANCE: u8 = 15;
+
+/// Movement points a Drift adds to every step onto open terrain
+pub const DRIFT_OPEN_TERRAIN_COST: u8 = 1;
+
+/// Multiplier a Drift puts on Data found
+pub const DRIFT_DATA_YIELD_MULTIPLIER: f32 = 1.5;
+
+/// Quiet movement rolls a Surge turns into events
+pub const SURGE_EVENT_PRESSURE: u8 = 2;
+
+/// Movement roll penalty of a Surge
+pub const SURGE_THREAT: u8 = 2;
+
+// =============================================================================
+// PAGE API CONSTANTS
+// =============================================================================
+
+/// Commands the embedding page can have waiting for the next frame
+pub const PAGE_COMMAND_QUEUE_LIMIT: usize = 16;
+
+// =============================================================================
+// CALENDAR CONSTANTS
+// =============================================================================
+
+/// Hour each day starts at after a rest
+pub const CALENDAR_DAWN_HOUR: u32 = 6;
+
+/// Hour night falls
+pub const CALENDAR_NIGHTFALL_HOUR: u32 = 21;
+
+/// Tiles moved from dawn until night falls
+pub const CALENDAR_TILES_PER_DAY: u32 = 6;
+
+/// Weight multiplier on Hazard and Combat events at night
+pub const NIGHT_HOSTILE_EVENT_WEIGHT: f32 = 2.0;
+
+/// Weight multiplier on ResourceDiscovery events at night
+pub const NIGHT_DISCOVERY_EVENT_WEIGHT: f32 = 0.5;
+
+// =============================================================================
+// STARTING SCENARIO CONSTANTS
+// =============================================================================
+
+/// Stats a Crash Survivor starts with lowered
+pub const CRASH_SURVIVOR_LOWERED_STATS: u8 = 2;
+
+/// Points taken off each lowered stat of a Crash Survivor
+pub const CRASH_SURVIVOR_STAT_PENALTY: u8 = 2;
+
+/// Metal a Crash Survivor pulls from the wreck before the run starts
+pub const CRASH_SURVIVOR_SALVAGED_METAL: u32 = 20;
+
+/// Loot left in the crash site next to a Crash Survivor's spawn
+pub const CRASH_SITE_LOOT: [(ResourceType, u32); 2] =
+    [(ResourceType::Metal, 15), (ResourceType::Technology, 4)];
+
+/// Tiles between a Crash Survivor's spawn and the base
+pub const CRASH_SURVIVOR_BASE_DISTANCE: u32 = 8;
+
+/// Level a Veteran Scout starts at
+pub const VETERAN_SCOUT_LEVEL: u32 = 2;
+
+/// Scout Probes a Veteran Scout starts with
+pub const VETERAN_SCOUT_PROBES: u32 = 1;
+
+/// Radius a Veteran Scout has charted around the spawn
+pub const VETERAN_SCOUT_REVEAL_RADIUS: u32 = 6;
+
+/// Share of the starting resources a Veteran Scout keeps, in percent
+pub const VETERAN_SCOUT_RESOURCE_PERCENT: u32 = 75;
+
+// =============================================================================
+// EMERGENCY RECALL CONSTANTS
+// =============================================================================
+
+/// Share of each carried resource lost to an Emergency Recall, in percent,
+/// rounded up
+pub const RECALL_RESOURCE_LOSS_PERCENT: u32 = 50;
+
+/// Share of maximum health an Emergency Recall patches the hull up to,
+/// in percent
+pub const RECALL_HEALTH_PERCENT: u32 = 50;
+
+/// Days the player stays Injured after an Emergency Recall
+pub const RECALL_INJURY_DAYS: u32 = 3;
+
+/// Movement points an Injured player is short after every rest
+pub const INJURED_MOVEMENT_PENALTY: i8 = 2;
+
+/// Penalty on every movement roll while Injured
+pub const INJURED_DICE_PENALTY: u8 = 1;
+
+/// Share of the final score kept by a hardcore run with the recall enabled,
+/// in percent
+pub const HARDCORE_RECALL_SCORE_PERCENT: u32 = 80;
+
+// =============================================================================
+// DAWN REPORT CONSTANTS
+// =============================================================================
+
+/// Days of Food or Energy left at which the advisor suggests heading home
+pub const DAWN_LOW_SUPPLY_DAYS: u32 = 2;
+
+/// Tiles within which an anomaly storm earns a warning
+pub const DAWN_STORM_WARNING_DISTANCE: u32 = 6;
+
+/// Tiles within which the nearest point of interest is worth suggesting
+pub const DAWN_POI_ADVICE_DISTANCE: u32 = 8;
+
+/// Region threat from which the advisor suggests moving on
+pub const DAWN_RESTLESS_THREAT: u8 = 2;
+
+// =============================================================================
+// PLAY HEATMAP CONSTANTS
+// =============================================================================
+
+/// Side of the square regions the run summary adds heat up over, in tiles
+pub const HEATMAP_REGION_SIZE: i32 = 8;
+
+/// Grades heat is drawn in, above the grade for nothing at all
+pub const HEATMAP_GRADES: u32 = 4;
+
+/// Explored tiles where nothing was counted
+pub const HEATMAP_EXPLORED_COLOR: Color = Color::srgb(0.18, 0.2, 0.26);
+
+/// Heat grades from the lowest to the maximum, cold to hot
+pub const HEATMAP_GRADE_COLORS: [Color; HEATMAP_GRADES as usize] = [
+    Color::srgb(0.2, 0.45, 0.85),
+    Color::srgb(0.95, 0.85, 0.2),
+    Color::srgb(0.95, 0.5, 0.1),
+    Color::srgb(0.9, 0.15, 0.1),
+];
+
+// =============================================================================
+// MINIMAP CONSTANTS
+// =============================================================================
+
+/// Tiles along each side of the minimap texture, one pixel per tile
+pub const MINIMAP_CANVAS_TILES: i32 = 128;
+
+/// Tiles along each side of the minimap's near and far views
+pub const MINIMAP_ZOOM_SPANS: [i32; 2] = [24, 64];
+
+/// Side of the minimap panel on screen
+pub const MINIMAP_PANEL_SIZE: f32 = 168.0;
+
+/// The player's pixel on the minimap
+pub const MINIMAP_PLAYER_COLOR: Color = Color::WHITE;
+
+/// The base's pixel on the minimap
+pub const MINIMAP_BASE_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
+
+// =============================================================================
+// SCOUT PROBE CONSTANTS
+// =============================================================================
+
+/// Tiles a scout probe flies before it burns out
+pub const SCOUT_PROBE_RANGE: i32 = 8;
+
+/// Seconds a probe takes to cross one tile; a full flight takes 2 seconds
+pub const SCOUT_PROBE_SECS_PER_TILE: f32 = 0.25;
+
+/// Tiles revealed on each side of the probe's path
+pub const SCOUT_PROBE_CORRIDOR_HALF_WIDTH: i32 = 1;
+
+/// Cargo spent to craft one probe
+pub const SCOUT_PROBE_CRAFT_ALLOYS: u32 = 2;
+pub const SCOUT_PROBE_CRAFT_TECHNOLOGY: u32 = 1;
+
+// =============================================================================
+// FACTION REPUTATION CONSTANTS
+// =============================================================================
+
+/// Lowest and highest standing with a faction
+pub const REPUTATION_MIN: i32 = -100;
+pub const REPUTATION_MAX: i32 = 100;
+
+/// Standing below which a faction turns hostile
+pub const REPUTATION_HOSTILE_BELOW: i32 = -30;
+
+/// Standing from which a faction is friendly, then allied
+pub const REPUTATION_FRIENDLY_FROM: i32 = 30;
+pub const REPUTATION_ALLIED_FROM: i32 = 70;
+
+/// Share of a standing change that goes the other way for the rival faction
+pub const REPUTATION_RIVAL_COUPLING: f32 = 0.5;
+
+/// Exchange rate bonus (or penalty when hostile) in percent
+pub const REPUTATION_TRADE_RATE_PERCENT: u32 = 15;
+
+/// Standing gained with the Scavenger Guild for a bribe
+pub const REPUTATION_BRIBE_GAIN: i32 = 8;
+
+/// Units received in a trade for each point of standing, at least one point
+pub const REPUTATION_TRADE_VOLUME_PER_POINT: u32 = 10;
+
+/// Standing gained with the Colonial Authority for a completed quest
+pub const REPUTATION_QUEST_GAIN: i32 = 6;
+
+/// Standing lost with the Colonial Authority for an abandoned quest
+pub const REPUTATION_QUEST_ABANDON_LOSS: i32 = 3;
+
+/// Metal paid to make hostiles look the other way
+pub const HOSTILE_BRIBE_METAL: u32 = 15;
+
+// =============================================================================
+// MOVEMENT ECONOMY CONSTANTS
+// =============================================================================
+
+/// Movement points a day may gain from events at full value (Normal difficulty)
+pub const MOVEMENT_GRANT_SOFT_CAP: u32 = 8;
+
+/// Endurance at which the soft cap is neither raised nor lowered
+pub const MOVEMENT_GRANT_ENDURANCE_BASELINE: i32 = 10;
+
+/// Endurance points per point of soft cap above or below the baseline
+pub const MOVEMENT_GRANT_ENDURANCE_STEP: i32 = 2;
+
+/// Movement points an event outcome earns, as (lowest final roll, points);
+/// rolls below the lowest band are critical failures and earn nothing
+pub const MOVEMENT_REWARD_BANDS: [(u8, u8); 6] =
+    [(20, 7), (17, 5), (13, 4), (10, 3), (7, 2), (4, 1)];
+
+/// Amount a resource discovery turns up on average ground, as (lowest
+/// final roll, amount); the ground picks the resource
+pub const DISCOVERY_AMOUNT_BANDS: [(u8, u32); 4] = [(20, 50), (17, 30), (13, 15), (0, 5)];
+
+/// Share of an event's reward paid on each terrain, in percent; rough or
+/// strange ground pays more for the same roll
+pub const EVENT_REWARD_TERRAIN_PERCENT: [(TerrainType, u32); 12] = [
+    (TerrainType::Plains, 100),
+    (TerrainType::Forest, 110),
+    (TerrainType::Mountains, 125),
+    (TerrainType::Desert, 115),
+    (TerrainType::Tundra, 120),
+    (TerrainType::Swamp, 115),
+    (TerrainType::Ocean, 120),
+    (TerrainType::Volcanic, 140),
+    (TerrainType::Anomaly, 150),
+    (TerrainType::Constructed, 100),
+    (TerrainType::Cave, 125),
+    (TerrainType::Crystal, 130),
+];
+
+/// Movement points for landing on a tile that triggers an event
+pub const EVENT_LANDING_MOVEMENT_POINTS: u8 = 2;
+
+/// Movement points for landing safely on a tile
+pub const SAFE_LANDING_MOVEMENT_POINTS: u8 = 2;
+
+// =============================================================================
+// SESSION FLAG CONSTANTS
+// =============================================================================
+
+/// Flag set once hostiles were let go or paid off
+pub const FLAG_SPARED_SCAVENGER: &str = "spared_scavenger";
+
+/// Counter of bribes paid to hostiles
+pub const FLAG_BRIBES_PAID: &str = "bribes_paid";
+
+/// Counter of hostile contacts won in a fight
+pub const FLAG_RAIDERS_DRIVEN_OFF: &str = "raiders_driven_off";
+
+/// Tag holding how the last hostile contact was dealt with
+pub const FLAG_LAST_ENCOUNTER: &str = "last_encounter";
+
+// =============================================================================
+// BACKGROUND FRAME LIMITER CONSTANTS
+// =============================================================================
+
+/// Update rate while the page is hidden and throttled
+pub const BACKGROUND_THROTTLED_UPDATES_PER_SEC: u32 = 2;
+
+/// Largest frame delta accepted while throttled, so slow frames keep real
+/// time instead of being clamped to the usual quarter second
+pub const BACKGROUND_MAX_FRAME_DELTA_SECS: u64 = 60;
+
+// =============================================================================
+// TILE STALENESS CONSTANTS
+// =============================================================================
+
+/// Days an explored tile can go unseen before it turns stale
+pub const DEFAULT_STALE_AFTER_DAYS: u32 = 20;
+
+/// Tiles this close to the base never turn stale
+pub const STALE_PROTECTED_BASE_RADIUS: u32 = 6;
+
+/// Chance in percent that a refresh rerolls a stale tile's terrain variation
+pub const STALE_TERRAIN_REROLL_PERCENT: u64 = 25;
+
+/// Chance in percent that an untouched resource node shifts during a refresh
+pub const STALE_NODE_SHIFT_PERCENT: u64 = 35;
+
+/// How far stale tiles are faded towards grey on the scanner
+pub const STALE_DESATURATION: f32 = 0.35;
+
+// =============================================================================
+// RUN MUTATOR CONSTANTS
+// =============================================================================
+
+/// Movement points Iron Stomach takes off the maximum
+pub const IRON_STOMACH_MOVEMENT_PENALTY: i8 = 2;
+
+/// Multiplier Glass Cannon puts on event rewards and hazard damage
+pub const GLASS_CANNON_MULTIPLIER: f32 = 2.0;
+
+/// Radius Cartographer charts around the spawn at the start of a run
+pub const CARTOGRAPHER_REVEAL_RADIUS: u32 = 15;
+
+/// Experience multiplier under Cartographer
+pub const CARTOGRAPHER_EXPERIENCE_MULTIPLIER: f32 = 0.8;
+
+/// Movement points a rest restores less under Night Owl
+pub const NIGHT_OWL_REST_PENALTY: i8 = 1;
+
+// =============================================================================
+// GEAR CONSTANTS
+// =============================================================================
+
+/// Chance in percent that found gear is Rare or better
+pub const GEAR_RARE_CHANCE_PERCENT: u64 = 25;
+
+/// Chance in percent that found gear is a Prototype
+pub const GEAR_PROTOTYPE_CHANCE_PERCENT: u64 = 5;
+
+/// Metal scrap recovered per rarity tier when salvaging gear
+pub const GEAR_SALVAGE_METAL_PER_TIER: u32 = 8;
+
+// =============================================================================
+// PARTY CONSTANTS
+// =============================================================================
+
+/// Characters in a pass-and-play party
+pub const PARTY_SIZE: usize = 2;
+
+/// Name of the second character when none is set
+pub const PARTY_DEFAULT_PARTNER_NAME: &str = "Rook";
+
+/// Amount one keypress adds to or removes from a cargo exchange
+pub const PARTY_TRANSFER_STEP: u32 = 5;
+
 // =============================================================================
 // TILE CACHING CONSTANTS
 // =============================================================================
@@ -222,6 +1032,82 @@ pub const FAST_REGEN_RATE: u32 = 5;
 /// Resource gathering time in seconds
 pub const RESOURCE_GATHERING_TIME: u32 = 10;
 
+/// Sum of every row of [`discovery_resource_weights`]
+pub const DISCOVERY_WEIGHT_TOTAL: u32 = 100;
+
+/// Odds of each resource a discovery on `terrain` turns up, in percent
+pub fn discovery_resource_weights(terrain: TerrainType) -> &'static [(ResourceType, u32)] {
+    use ResourceType::*;
+    match terrain {
+        TerrainType::Plains => &[
+            (Food, 35),
+            (Organics, 20),
+            (Metal, 20),
+            (Energy, 15),
+            (Data, 10),
+        ],
+        TerrainType::Forest => &[
+            (Organics, 40),
+            (Food, 35),
+            (Energy, 10),
+            (Metal, 10),
+            (Data, 5),
+        ],
+        TerrainType::Mountains => &[
+            (Metal, 45),
+            (Alloys, 30),
+            (Energy, 10),
+            (Technology, 10),
+            (ExoticMatter, 5),
+        ],
+        TerrainType::Desert => &[
+            (Metal, 25),
+            (Energy, 25),
+            (Technology, 20),
+            (Data, 15),
+            (Alloys, 15),
+        ],
+        TerrainType::Tundra => &[
+            (Metal, 25),
+            (Energy, 25),
+            (Alloys, 20),
+            (ExoticMatter, 15),
+            (Data, 15),
+        ],
+        TerrainType::Swamp => &[(Organics, 40), (Energy, 25), (Food, 25), (ExoticMatter, 10)],
+        TerrainType::Ocean => &[(Energy, 40), (Food, 30), (Organics, 20), (Data, 10)],
+        TerrainType::Volcanic => &[(Alloys, 35), (Metal, 25), (Energy, 25), (ExoticMatter, 15)],
+        TerrainType::Anomaly => &[
+            (ExoticMatter, 50),
+            (Data, 20),
+            (Energy, 15),
+            (Technology, 15),
+        ],
+        TerrainType::Constructed => &[(Technology, 40), (Data, 35), (Alloys, 15), (Metal, 10)],
+        TerrainType::Cave => &[(Metal, 35), (Alloys, 25), (ExoticMatter, 20), (Energy, 20)],
+        TerrainType::Crystal => &[
+            (Energy, 35),
+            (Technology, 30),
+            (ExoticMatter, 20),
+            (Data, 15),
+        ],
+    }
+}
+
+/// Resource a discovery on `terrain` turns up for a roll below
+/// [`DISCOVERY_WEIGHT_TOTAL`]
+pub fn discovery_resource_at(terrain: TerrainType, roll: u32) -> ResourceType {
+    let weights = discovery_resource_weights(terrain);
+    let mut left = roll % DISCOVERY_WEIGHT_TOTAL;
+    for &(resource_type, weight) in weights {
+        if left < weight {
+            return resource_type;
+        }
+        left -= weight;
+    }
+    weights[weights.len() - 1].0
+}
+
 // =============================================================================
 // EVENT SYSTEM CONSTANTS
 // =============================================================================
@@ -248,6 +1134,13 @@ pub const EVENT_COOLDOWN_LONG: u32 = 30;
 /// Maximum number of active events at once
 pub const MAX_ACTIVE_EVENTS: usize = 5;
 
+// =============================================================================
+// GAME LOG CONSTANTS
+// =============================================================================
+
+/// Default number of entries kept in the game log ring buffer
+pub const GAME_LOG_DEFAULT_CAPACITY: usize = 500;
+
 // =============================================================================
 // COMBAT CONSTANTS
 // =============================================================================
@@ -302,6 +1195,24 @@ pub const BUILDING_COST_MULTIPLIER: f32 = 2.0;
 /// Maximum building level
 pub const MAX_BUILDING_LEVEL: u8 = 10;
 
+/// Distinct building models by level; higher levels reuse the last one
+pub const BUILDING_VISUAL_TIERS: u8 = 3;
+
+/// Cargo room each Resource Storage level adds to the carrying capacity
+pub const STORAGE_CARGO_PER_LEVEL: u32 = 25;
+
+/// Laboratory levels per extra tile of view; the first level already adds one
+pub const LABORATORY_LEVELS_PER_VIEW_TILE: u8 = 3;
+
+/// Highest bonus Living Quarters add to the night roll, one per level
+pub const QUARTERS_MAX_REST_ROLL_BONUS: u8 = 5;
+
+/// Refinery jobs that can wait in the queue per refinery level
+pub const REFINERY_JOBS_PER_LEVEL: usize = 2;
+
+/// Share of a cancelled refinery job's inputs given back, in percent
+pub const REFINERY_CANCEL_REFUND_PERCENT: u32 = 50;
+
 // =============================================================================
 // GAME TIME CONSTANTS
 // =============================================================================
@@ -355,6 +1266,27 @@ pub const TERRAIN_COLOR_CAVE: Color = Color::srgb(0.3, 0.2, 0.2); // Dark brown
 pub const TERRAIN_COLOR_CRYSTAL: Color = Color::srgb(0.8, 0.6, 1.0); // Purple crystal
 pub const TERRAIN_COLOR_ANOMALY: Color = Color::srgb(1.0, 0.0, 1.0); // Magenta
 
+/// Slope from which a drop is drawn as a cliff edge on its low side
+pub const CLIFF_MIN_SLOPE: u32 = 2;
+
+/// Dark strip along the low side of a cliff edge
+pub const CLIFF_EDGE_COLOR: Color = Color::srgb(0.08, 0.07, 0.06);
+
+/// Default strength of slope and height shading (0 = flat colors)
+pub const SLOPE_SHADING_DEFAULT_INTENSITY: f32 = 0.35;
+
+/// Height at which shading reaches its brightest; deep water its darkest
+pub const SLOPE_SHADING_HEIGHT_SPAN: f32 = 20.0;
+
+/// Slope at which shading reaches its darkest
+pub const SLOPE_SHADING_MAX_SLOPE: u32 = 4;
+
+/// Keyframes animated terrain (Ocean, Swamp, Volcanic) cycles through
+pub const TERRAIN_ANIMATION_KEYFRAMES: usize = 4;
+
+/// Seconds each terrain animation keyframe is held
+pub const TERRAIN_ANIMATION_STEP_SECS: f32 = 0.75;
+
 // =============================================================================
 // UI COLOR CONSTANTS
 // =============================================================================
@@ -363,6 +1295,8 @@ pub const TERRAIN_COLOR_ANOMALY: Color = Color::srgb(1.0, 0.0, 1.0); // Magenta
 pub const HUD_BACKGROUND: Color = Color::srgba(0.05, 0.15, 0.25, 0.9);
 pub const PANEL_BACKGROUND: Color = Color::srgba(0.1, 0.2, 0.3, 0.85);
 pub const SCANNER_BACKGROUND: Color = Color::srgba(0.0, 0.1, 0.2, 0.95);
+/// Opaque screen that hides the map while the device is passed on
+pub const HANDOVER_BACKGROUND: Color = Color::srgb(0.02, 0.06, 0.1);
 
 /// Text colors for UI elements
 pub const PRIMARY_TEXT: Color = Color::srgb(0.85, 0.95, 1.0);
@@ -380,6 +1314,7 @@ pub const SCANNER_GRID: Color = Color::srgba(0.0, 0.8, 1.0, 0.3);
 pub const SHIP_SIGNATURE: Color = Color::srgb(1.0, 1.0, 0.0); // Bright Yellow
 pub const EXPLORED_SPACE: Color = Color::srgba(0.2, 0.6, 0.9, 0.8);
 pub const UNEXPLORED_SPACE: Color = Color::srgba(0.1, 0.1, 0.3, 0.6);
+pub const WRECK_SIGNATURE: Color = Color::srgb(1.0, 0.45, 0.1); // Burning orange
 
 /// Space object signature colors for scanner display
 pub const ASTEROID_FIELD: Color = Color::srgb(0.6, 0.6, 0.6); // Gray
@@ -431,6 +1366,8 @@ pub const AUDIO_UI_CLICK: &str = "audio/sfx/ui/button_click.wav";
 pub const AUDIO_UI_HOVER: &str = "audio/sfx/ui/button_hover.wav";
 pub const AUDIO_UI_NOTIFICATION: &str = "audio/sfx/ui/notification.wav";
 pub const AUDIO_UI_WARNING: &str = "audio/sfx/ui/warning.wav";
+pub const AUDIO_ENCOUNTER_STINGER: &str = "audio/sfx/ui/warning.wav";
+pub const AUDIO_WARNING_HEARTBEAT: &str = "audio/sfx/dice/dice_low_roll.wav";
 
 // Terrain-specific Ambient Sounds (dedicated files)
 pub const AUDIO_AMBIENT_PLAINS: &str = "audio/ambient/plains_wind.ogg";
@@ -455,6 +1392,7 @@ pub const AUDIO_MUSIC_PLAYLIST: &[&str] = &[
     "audio/music/theme/menu_theme.ogg",
     "audio/music/theme/menu_theme2.ogg",
     "audio/music/theme/menu_theme3.ogg",
+    "audio/music/mystery_ambient.ogg",
 ];
 
 // Track display names (keep in sync with AUDIO_MUSIC_PLAYLIST)
@@ -462,18 +1400,56 @@ pub const AUDIO_TRACK_NAMES: &[&str] = &[
     "Dreams under the stars",
     "Legend of Nocturne",
     "Whispers of September",
+    "Derelict Signal",
 ];
 
+// Music stems per playlist entry (keep in sync with AUDIO_MUSIC_PLAYLIST)
+// Layered tracks list up to 3 files in base, tension, percussion order and
+// play them in sync; an empty entry plays the playlist file on its own.
+pub const AUDIO_MUSIC_STEMS: &[&[&str]] = &[
+    &[],
+    &[],
+    &[],
+    &[
+        "audio/music/mystery_ambient.ogg",
+        "audio/music/tension_discovery.ogg",
+        "audio/music/combat_encounter.ogg",
+    ],
+];
+
+// Combat loop for tracks without a percussion stem
+pub const AUDIO_COMBAT_LOOP: &str = "audio/music/combat_encounter.ogg";
+
 // Audio Volume Defaults
 pub const DEFAULT_MASTER_VOLUME: f32 = 0.7;
 pub const DEFAULT_MUSIC_VOLUME: f32 = 0.6;
 pub const DEFAULT_AMBIENT_VOLUME: f32 = 0.3;
 pub const DEFAULT_SFX_VOLUME: f32 = 0.8;
+/// Change of a player volume level per key press
+pub const VOLUME_STEP: f32 = 0.1;
 
 // Music Timing Constants
 pub const MUSIC_CHANGE_INTERVAL_SECONDS: f32 = 30.0; // How long between track changes
 pub const AMBIENT_RETRY_INTERVAL_SECONDS: u64 = 10; // How often to retry loading ambient
 pub const AUDIO_STATUS_CHECK_INTERVAL_SECONDS: u64 = 15; // How often to check asset status
+pub const AUDIO_PROBE_TIMEOUT_SECONDS: f32 = 2.0; // How long to wait for the probe sink to appear
+
+// Layered Music Stems
+pub const MUSIC_STEM_TENSION_THRESHOLD: f32 = 0.4; // Danger above which the tension stem fades in
+pub const MUSIC_STEM_PERCUSSION_THRESHOLD: f32 = 0.7; // Danger above which percussion fades in
+pub const MUSIC_STEM_FADE_WIDTH: f32 = 0.15; // Danger range over which a stem reaches full volume
+pub const MUSIC_STEM_RAMP_PER_SECOND: f32 = 0.5; // Maximum stem gain change per second
+pub const MUSIC_STEM_MAX_DRIFT_SECONDS: f32 = 0.05; // Stem offset that triggers a synced restart
+
+// Encounter Music
+pub const MUSIC_ENCOUNTER_DUCK: f32 = 0.5; // Share of the music volume taken away during an encounter
+pub const MUSIC_ENCOUNTER_FADE_PER_SECOND: f32 = 1.0; // Gain change per second when ducking or crossfading
+pub const MUSIC_RESTART_FADE_PER_SECOND: f32 = 0.2; // Fade-in of a track restarted after combat
+pub const MUSIC_RAID_SECONDS: f32 = 6.0; // How long a raid holds the music
+
+// Sound Effect Arbitration
+pub const SFX_MAX_NEW_PER_FRAME: usize = 3; // New one-shot effects started in a single frame
+pub const SFX_RETRIGGER_INTERVAL_SECONDS: f32 = 0.08; // Minimum gap before the same sample restarts
 
 /// Get ambient sound path for a terrain type
 pub fn get_ambient_sound_for_terrain(
@@ -606,6 +1582,31 @@ pub fn get_terrain_duration_multiplier(
 mod tests {
     use super::*;
 
+    #[test]
+    fn every_resource_can_be_discovered_somewhere() {
+        for terrain in TerrainType::all() {
+            let weights = discovery_resource_weights(terrain);
+            let total: u32 = weights.iter().map(|(_, weight)| weight).sum();
+            assert_eq!(total, DISCOVERY_WEIGHT_TOTAL, "{:?}", terrain);
+            assert_eq!(discovery_resource_at(terrain, 0), weights[0].0);
+            assert_eq!(
+                discovery_resource_at(terrain, DISCOVERY_WEIGHT_TOTAL - 1),
+                weights[weights.len() - 1].0
+            );
+        }
+        for resource_type in ResourceType::all() {
+            assert!(
+                TerrainType::all().into_iter().any(|terrain| {
+                    discovery_resource_weights(terrain)
+                        .iter()
+                        .any(|(found, _)| *found == resource_type)
+                }),
+                "{:?}",
+                resource_type
+            );
+        }
+    }
+
     #[test]
     fn test_experience_calculation() {
         assert_eq!(experience_for_level(1), 0);
@@ -3,10 +3,12 @@
 //! This entity represents the player's base which can be upgraded with resources
 //! and provides various benefits and capabilities.
 
+use crate::domain::entities::refinery::{RefineryDelivery, RefineryQueue, RefineryRecipe};
 use crate::domain::value_objects::resources::ResourceCollection;
 use crate::domain::value_objects::{EntityId, Position3D, ResourceType};
 use crate::domain::{DomainError, DomainResult};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// The player's base entity
@@ -19,6 +21,7 @@ pub struct Base {
     buildings: Vec<BaseBuilding>,
     resources_stored: ResourceCollection,
     storage_capacity: u32,
+    refinery: RefineryQueue,
     created_at: DateTime<Utc>,
     last_updated: DateTime<Utc>,
     version: u64,
@@ -42,6 +45,7 @@ impl Base {
             buildings: Vec::new(),
             resources_stored: ResourceCollection::new(),
             storage_capacity: 1000,
+            refinery: RefineryQueue::new(),
             created_at: now,
             last_updated: now,
             version: 1,
@@ -82,6 +86,154 @@ impl Base {
     pub fn storage_capacity(&self) -> u32 {
         self.storage_capacity
     }
+
+    /// Storage space left
+    pub fn free_capacity(&self) -> u32 {
+        self.storage_capacity
+            .saturating_sub(self.resources_stored.storage_requirement())
+    }
+
+    /// Put resources into storage; returns the amount that fit
+    pub fn store_resource(&mut self, resource_type: ResourceType, amount: u32) -> u32 {
+        let stored = amount.min(self.free_capacity());
+        if stored > 0 {
+            let total = self.resources_stored.get_amount(resource_type) + stored;
+            self.resources_stored.set_amount(resource_type, total);
+            self.touch();
+        }
+        stored
+    }
+
+    /// Put a whole collection into storage; nothing is stored unless all of it fits
+    pub fn store_resources(&mut self, resources: &ResourceCollection) -> DomainResult<()> {
+        if resources.storage_requirement() > self.free_capacity() {
+            return Err(DomainError::InsufficientResources(format!(
+                "Base storage has room for {}, need {}",
+                self.free_capacity(),
+                resources.storage_requirement()
+            )));
+        }
+        self.resources_stored.add_collection(resources)?;
+        self.touch();
+        Ok(())
+    }
+
+    /// Take a collection out of storage; nothing is taken unless all of it is there
+    pub fn withdraw_resources(&mut self, resources: &ResourceCollection) -> DomainResult<()> {
+        self.resources_stored.pay_cost(resources)?;
+        self.touch();
+        Ok(())
+    }
+
+    /// Add a constructed building
+    pub fn add_building(&mut self, building: BaseBuilding) {
+        self.buildings.push(building);
+        self.touch();
+    }
+
+    /// First building of a type, if constructed
+    pub fn building(&self, building_type: BuildingType) -> Option<&BaseBuilding> {
+        self.buildings
+            .iter()
+            .find(|building| building.building_type == building_type)
+    }
+
+    /// Raise a constructed building one level; returns the new level
+    pub fn upgrade_building(&mut self, building_type: BuildingType) -> DomainResult<u8> {
+        let building = self
+            .buildings
+            .iter_mut()
+            .find(|building| building.building_type == building_type)
+            .ok_or_else(|| {
+                DomainError::BuildingRequirementsNotMet(format!(
+                    "The base has no {:?}",
+                    building_type
+                ))
+            })?;
+        if building.level >= crate::domain::constants::MAX_BUILDING_LEVEL {
+            return Err(DomainError::BaseUpgradeError(format!(
+                "{} is already at the highest level",
+                building.name
+            )));
+        }
+        building.level += 1;
+        let level = building.level;
+        self.touch();
+        Ok(level)
+    }
+
+    /// Mark a building as damaged or repaired; returns false if it is not built
+    pub fn set_building_damaged(&mut self, building_type: BuildingType, damaged: bool) -> bool {
+        let Some(building) = self
+            .buildings
+            .iter_mut()
+            .find(|building| building.building_type == building_type)
+        else {
+            return false;
+        };
+        building.damaged = damaged;
+        self.touch();
+        true
+    }
+
+    /// Refinery jobs in queue order
+    pub fn refinery(&self) -> &RefineryQueue {
+        &self.refinery
+    }
+
+    /// Replace the refinery queue, e.g. when loading a save
+    pub fn restore_refinery(&mut self, queue: RefineryQueue) {
+        self.refinery = queue;
+    }
+
+    /// Queue a refinery job, paying its inputs through `pay`
+    pub fn enqueue_refinery_job(
+        &mut self,
+        recipe: RefineryRecipe,
+        pay: impl FnOnce(&ResourceCollection) -> DomainResult<()>,
+    ) -> DomainResult<()> {
+        let level = self
+            .building(BuildingType::Refinery)
+            .map(|refinery| refinery.level)
+            .ok_or_else(|| {
+                DomainError::BuildingRequirementsNotMet("The base has no refinery".to_string())
+            })?;
+        self.refinery.enqueue(recipe, level, pay)?;
+        self.touch();
+        Ok(())
+    }
+
+    /// Cancel an unfinished refinery job; returns the refund
+    pub fn cancel_refinery_job(&mut self, index: usize) -> DomainResult<ResourceCollection> {
+        let refund = self.refinery.cancel(index)?;
+        self.touch();
+        Ok(refund)
+    }
+
+    /// Clear finished jobs from the refinery list
+    pub fn collect_refinery_jobs(&mut self) -> usize {
+        self.refinery.collect_completed()
+    }
+
+    /// Run the refinery for one rest, storing the output of a finished job
+    ///
+    /// Output that does not fit into storage is lost and reported as overflow.
+    pub fn advance_refinery(&mut self) -> Option<RefineryDelivery> {
+        let recipe = self.refinery.advance_rest()?;
+        let (resource_type, amount) = recipe.output();
+        let deposited = self.store_resource(resource_type, amount);
+        Some(RefineryDelivery {
+            recipe,
+            resource_type,
+            deposited,
+            overflow: amount - deposited,
+        })
+    }
+
+    fn touch(&mut self) {
+        self.last_updated = Utc::now();
+        self.version += 1;
+    }
 }
 
 /// Base development levels
@@ -116,6 +268,8 @@ pub struct BaseBuilding {
     pub level: u8,
     pub position_in_base: (i32, i32),
     pub constructed_at: DateTime<Utc>,
+    /// Hit by a raid and not yet repaired
+    pub damaged: bool,
 }
 
 impl BaseBuilding {
@@ -128,12 +282,13 @@ impl BaseBuilding {
             level: 1,
             position_in_base,
             constructed_at: Utc::now(),
+            damaged: false,
         }
     }
 }
 
 /// Types of buildings
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum BuildingType {
     ResourceStorage,
     Workshop,
@@ -141,9 +296,48 @@ pub enum BuildingType {
     PowerPlant,
     LivingQuarters,
     DefenseSystem,
+    Refinery,
 }
 
 impl BuildingType {
+    /// Every building type, in the order the base lists them
+    pub fn all() -> [BuildingType; 7] {
+        [
+            BuildingType::ResourceStorage,
+            BuildingType::Workshop,
+            BuildingType::Laboratory,
+            BuildingType::PowerPlant,
+            BuildingType::LivingQuarters,
+            BuildingType::DefenseSystem,
+            BuildingType::Refinery,
+        ]
+    }
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuildingType::ResourceStorage => "Resource Storage",
+            BuildingType::Workshop => "Workshop",
+            BuildingType::Laboratory => "Laboratory",
+            BuildingType::PowerPlant => "Power Plant",
+            BuildingType::LivingQuarters => "Living Quarters",
+            BuildingType::DefenseSystem => "Defense System",
+            BuildingType::Refinery => "Refinery",
+        }
+    }
+
+    /// Get the resource cost to raise this building from `level` to the next
+    ///
+    /// Each level the building already has adds `BUILDING_COST_MULTIPLIER`
+    /// times its build cost.
+    pub fn upgrade_cost(&self, level: u8) -> HashMap<ResourceType, u32> {
+        let factor = crate::domain::constants::BUILDING_COST_MULTIPLIER * level.max(1) as f32;
+        self.build_cost()
+            .into_iter()
+            .map(|(resource_type, amount)| (resource_type, (amount as f32 * factor).round() as u32))
+            .collect()
+    }
+
     /// Get the resource cost to build this building at level 1
     pub fn build_cost(&self) -> HashMap<ResourceType, u32> {
         let mut cost = HashMap::new();
@@ -173,6 +367,10 @@ impl BuildingType {
                 cost.insert(ResourceType::Technology, 30);
                 cost.insert(ResourceType::Energy, 75);
             }
+            BuildingType::Refinery => {
+                cost.insert(ResourceType::Metal, 60);
+                cost.insert(ResourceType::Energy, 30);
+            }
         }
         cost
     }
@@ -202,10 +400,94 @@ mod tests {
         assert_eq!(building.level, 1);
     }
 
+    #[test]
+    fn refinery_output_respects_storage_capacity() {
+        let mut base = Base::new(
+            EntityId::generate(),
+            "Depot".to_string(),
+            Position3D::origin(),
+        )
+        .unwrap();
+        assert!(base
+            .enqueue_refinery_job(RefineryRecipe::AlloySmelting, |_| Ok(()))
+            .is_err());
+
+        base.add_building(BaseBuilding::new(
+            BuildingType::Refinery,
+            "Refinery".to_string(),
+            (1, 0),
+        ));
+        base.store_resource(ResourceType::Food, base.storage_capacity());
+        base.enqueue_refinery_job(RefineryRecipe::AlloySmelting, |_| Ok(()))
+            .unwrap();
+
+        let delivery = base.advance_refinery().unwrap();
+        assert_eq!(delivery.deposited, 0);
+        assert_eq!(delivery.overflow, 1);
+        assert_eq!(base.resources().get_amount(ResourceType::Alloys), 0);
+    }
+
+    #[test]
+    fn bulk_storage_changes_are_all_or_nothing() {
+        let mut base = Base::new(
+            EntityId::generate(),
+            "Depot".to_string(),
+            Position3D::origin(),
+        )
+        .unwrap();
+        let mut load = ResourceCollection::new();
+        load.set_amount(ResourceType::Metal, 600);
+        load.set_amount(ResourceType::Food, 300);
+        base.store_resources(&load).unwrap();
+
+        assert!(base.store_resources(&load).is_err());
+        assert_eq!(base.free_capacity(), 100);
+
+        load.set_amount(ResourceType::Data, 1);
+        assert!(base.withdraw_resources(&load).is_err());
+        assert_eq!(base.resources().get_amount(ResourceType::Metal), 600);
+
+        load.set_amount(ResourceType::Data, 0);
+        base.withdraw_resources(&load).unwrap();
+        assert!(base.resources().is_empty());
+    }
+
+    #[test]
+    fn buildings_upgrade_to_the_cap_and_take_damage() {
+        let mut base = Base::new(
+            EntityId::generate(),
+            "Depot".to_string(),
+            Position3D::origin(),
+        )
+        .unwrap();
+        assert!(base.upgrade_building(BuildingType::Workshop).is_err());
+        assert!(!base.set_building_damaged(BuildingType::Workshop, true));
+
+        base.add_building(BaseBuilding::new(
+            BuildingType::Workshop,
+            "Workshop".to_string(),
+            (0, 1),
+        ));
+        for level in 2..=crate::domain::constants::MAX_BUILDING_LEVEL {
+            assert_eq!(
+                base.upgrade_building(BuildingType::Workshop).unwrap(),
+                level
+            );
+        }
+        assert!(base.upgrade_building(BuildingType::Workshop).is_err());
+
+        assert!(base.set_building_damaged(BuildingType::Workshop, true));
+        assert!(base.building(BuildingType::Workshop).unwrap().damaged);
+    }
+
     #[test]
     fn building_costs() {
         let cost = BuildingType::Workshop.build_cost();
         assert!(cost.contains_key(&ResourceType::Metal));
         assert!(cost.contains_key(&ResourceType::Technology));
+
+        let upgrade = BuildingType::Workshop.upgrade_cost(3);
+        assert_eq!(upgrade[&ResourceType::Metal], 450);
+        assert_eq!(upgrade[&ResourceType::Technology], 60);
     }
 }
@@ -7,6 +7,7 @@ use crate::domain::value_objects::resources::ResourceCollection;
 use crate::domain::value_objects::{EntityId, GameTime, Position3D};
 use crate::domain::{DomainError, DomainResult};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
 /// A game event entity
 #[derive(Debug, Clone, PartialEq)]
@@ -125,7 +126,7 @@ impl Event {
 }
 
 /// Types of events that can occur
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EventType {
     /// Resource discovery event
     ResourceDiscovery,
@@ -349,6 +349,17 @@ impl DifficultyLevel {
             DifficultyLevel::Nightmare => 2.0,
         }
     }
+
+    /// Get multiplier on the daily soft cap of movement points from events
+    pub fn movement_grant_multiplier(&self) -> f32 {
+        match self {
+            DifficultyLevel::Easy => 1.5,
+            DifficultyLevel::Normal => 1.0,
+            DifficultyLevel::Hard => 0.75,
+            DifficultyLevel::Expert => 0.6,
+            DifficultyLevel::Nightmare => 0.5,
+        }
+    }
 }
 
 impl std::fmt::Display for DifficultyLevel {
@@ -0,0 +1,290 @@
+//! Inventory Entity - Cargo and carried items under one weight limit
+//!
+//! The inventory holds the player's resources next to discrete items. Items
+//! stack by id, every unit of a stack weighing the same, while resources
+//! weigh one per unit. Both count against a carrying capacity that grows
+//! with Strength; whatever would go past it is refused and handed back, so
+//! callers decide what to say about the excess.
+
+use crate::domain::constants::{
+    CARRYING_CAPACITY_BASE, CARRYING_CAPACITY_PER_STRENGTH, MAX_RESOURCE_AMOUNT,
+};
+use crate::domain::services::inventory::BulkTransfer;
+use crate::domain::value_objects::resources::ResourceCollection;
+use crate::domain::value_objects::ResourceType;
+use crate::domain::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+
+/// Stack of one kind of item
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InventoryItem {
+    pub item_id: String,
+    pub count: u32,
+    /// Weight of a single unit
+    pub unit_weight: u32,
+}
+
+impl InventoryItem {
+    /// Create a stack of `count` units
+    pub fn new(item_id: impl Into<String>, count: u32, unit_weight: u32) -> Self {
+        Self {
+            item_id: item_id.into(),
+            count,
+            unit_weight,
+        }
+    }
+
+    /// Weight of the whole stack
+    pub fn weight(&self) -> u32 {
+        self.count.saturating_mul(self.unit_weight)
+    }
+}
+
+/// Resources and item stacks carried by the player
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Inventory {
+    resources: ResourceCollection,
+    items: Vec<InventoryItem>,
+}
+
+impl Inventory {
+    /// Create an empty inventory
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an inventory holding `resources` and no items
+    pub fn with_resources(resources: ResourceCollection) -> Self {
+        Self {
+            resources,
+            items: Vec::new(),
+        }
+    }
+
+    /// Carrying capacity a Strength score gives, before gear and facilities
+    pub fn strength_capacity(strength: u8) -> u32 {
+        CARRYING_CAPACITY_BASE + strength as u32 * CARRYING_CAPACITY_PER_STRENGTH
+    }
+
+    /// Get resources
+    pub fn resources(&self) -> &ResourceCollection {
+        &self.resources
+    }
+
+    /// Get mutable resources
+    pub fn resources_mut(&mut self) -> &mut ResourceCollection {
+        &mut self.resources
+    }
+
+    /// Item stacks in the order they were first picked up
+    pub fn items(&self) -> &[InventoryItem] {
+        &self.items
+    }
+
+    /// Total weight of resources and items
+    pub fn weight(&self) -> u32 {
+        self.items
+            .iter()
+            .fold(self.resources.storage_requirement(), |total, item| {
+                total.saturating_add(item.weight())
+            })
+    }
+
+    /// Weight that still fits under `capacity`
+    pub fn free_capacity(&self, capacity: u32) -> u32 {
+        capacity.saturating_sub(self.weight())
+    }
+
+    /// How many units of an item are held
+    pub fn item_count(&self, item_id: &str) -> u32 {
+        self.items
+            .iter()
+            .find(|item| item.item_id == item_id)
+            .map_or(0, |item| item.count)
+    }
+
+    /// Check if at least `count` units of an item are held
+    pub fn has_item(&self, item_id: &str, count: u32) -> bool {
+        count > 0 && self.item_count(item_id) >= count
+    }
+
+    /// Add items as far as `capacity` allows, stacking onto a held stack
+    ///
+    /// A held stack keeps its own unit weight. Returns how many units did
+    /// not fit and were not added.
+    pub fn add_item(&mut self, item: InventoryItem, capacity: u32) -> u32 {
+        let free = self.free_capacity(capacity);
+        let position = self
+            .items
+            .iter()
+            .position(|held| held.item_id == item.item_id);
+        let unit_weight = position.map_or(item.unit_weight, |index| self.items[index].unit_weight);
+        let fitting = match free.checked_div(unit_weight) {
+            Some(room) => item.count.min(room),
+            None => item.count,
+        };
+        let refused = item.count - fitting;
+        if fitting > 0 {
+            match position {
+                Some(index) => {
+                    let held = &mut self.items[index];
+                    held.count = held.count.saturating_add(fitting);
+                }
+                None => self.items.push(InventoryItem {
+                    count: fitting,
+                    ..item
+                }),
+            }
+        }
+        refused
+    }
+
+    /// Remove units of an item, dropping its stack once empty
+    ///
+    /// Nothing is removed unless all of it is held. Returns how many units
+    /// are left.
+    pub fn remove_item(&mut self, item_id: &str, count: u32) -> DomainResult<u32> {
+        let Some(index) = self
+            .items
+            .iter()
+            .position(|item| item.item_id == item_id && item.count >= count)
+        else {
+            return Err(DomainError::InsufficientResources(format!(
+                "Not enough {}. Need: {}, Have: {}",
+                item_id,
+                count,
+                self.item_count(item_id)
+            )));
+        };
+        let held = &mut self.items[index];
+        held.count -= count;
+        let left = held.count;
+        if left == 0 {
+            self.items.remove(index);
+        }
+        Ok(left)
+    }
+
+    /// Take in a find as far as `capacity` allows
+    ///
+    /// Resources are taken in type order; returns what did not fit,
+    /// including anything past the per-resource cap.
+    pub fn pick_up(&mut self, found: &ResourceCollection, capacity: u32) -> ResourceCollection {
+        let plan = BulkTransfer::plan_pickup(found, self.free_capacity(capacity));
+        let mut dropped = plan.left_behind;
+        for resource_type in ResourceType::all() {
+            let moved = plan.moved.get_amount(resource_type);
+            let held = self.resources.get_amount(resource_type);
+            let kept = moved.min(MAX_RESOURCE_AMOUNT.saturating_sub(held));
+            self.resources.set_amount(resource_type, held + kept);
+            dropped.set_amount(
+                resource_type,
+                dropped.get_amount(resource_type) + moved - kept,
+            );
+        }
+        dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collection(amounts: &[(ResourceType, u32)]) -> ResourceCollection {
+        let mut collection = ResourceCollection::new();
+        for &(resource_type, amount) in amounts {
+            collection.set_amount(resource_type, amount);
+        }
+        collection
+    }
+
+    #[test]
+    fn strength_raises_the_capacity() {
+        assert_eq!(Inventory::strength_capacity(0), CARRYING_CAPACITY_BASE);
+        assert_eq!(
+            Inventory::strength_capacity(12) - Inventory::strength_capacity(10),
+            2 * CARRYING_CAPACITY_PER_STRENGTH
+        );
+    }
+
+    #[test]
+    fn items_stack_by_id_and_keep_their_unit_weight() {
+        let mut inventory = Inventory::new();
+        assert_eq!(
+            inventory.add_item(InventoryItem::new("hull_patch", 2, 5), 100),
+            0
+        );
+        assert_eq!(
+            inventory.add_item(InventoryItem::new("hull_patch", 3, 1), 100),
+            0
+        );
+        assert_eq!(
+            inventory.add_item(InventoryItem::new("beacon", 1, 4), 100),
+            0
+        );
+
+        assert_eq!(inventory.items().len(), 2);
+        assert_eq!(inventory.item_count("hull_patch"), 5);
+        assert_eq!(inventory.weight(), 5 * 5 + 4);
+        assert!(inventory.has_item("hull_patch", 5));
+        assert!(!inventory.has_item("hull_patch", 6));
+        assert!(!inventory.has_item("flare", 1));
+
+        assert_eq!(inventory.remove_item("hull_patch", 4), Ok(1));
+        assert!(inventory.remove_item("hull_patch", 2).is_err());
+        assert_eq!(inventory.item_count("hull_patch"), 1);
+        assert_eq!(inventory.remove_item("hull_patch", 1), Ok(0));
+        assert_eq!(inventory.items().len(), 1);
+    }
+
+    #[test]
+    fn items_past_the_capacity_are_refused() {
+        let mut inventory = Inventory::with_resources(collection(&[(ResourceType::Metal, 90)]));
+
+        // 10 free: two units of weight 4 fit, the third does not
+        assert_eq!(
+            inventory.add_item(InventoryItem::new("beacon", 3, 4), 100),
+            1
+        );
+        assert_eq!(inventory.item_count("beacon"), 2);
+        assert_eq!(
+            inventory.add_item(InventoryItem::new("beacon", 1, 4), 100),
+            1
+        );
+        assert_eq!(inventory.item_count("beacon"), 2);
+
+        // Weightless items always fit
+        assert_eq!(inventory.add_item(InventoryItem::new("map", 5, 0), 100), 0);
+        assert_eq!(inventory.weight(), 98);
+    }
+
+    #[test]
+    fn a_find_past_the_capacity_leaves_the_excess() {
+        let mut inventory = Inventory::with_resources(collection(&[(ResourceType::Food, 30)]));
+        inventory.add_item(InventoryItem::new("beacon", 5, 2), 100);
+
+        let found = collection(&[(ResourceType::Metal, 40), (ResourceType::Energy, 30)]);
+        let dropped = inventory.pick_up(&found, 100);
+
+        // 60 free of the 70 found
+        assert_eq!(inventory.weight(), 100);
+        assert_eq!(dropped.storage_requirement(), 10);
+
+        // A full hold takes nothing more
+        assert_eq!(inventory.pick_up(&found, 100), found);
+    }
+
+    #[test]
+    fn a_find_past_the_resource_cap_leaves_the_excess() {
+        let mut inventory =
+            Inventory::with_resources(collection(&[(ResourceType::Data, MAX_RESOURCE_AMOUNT - 5)]));
+        let found = collection(&[(ResourceType::Data, 20)]);
+
+        let dropped = inventory.pick_up(&found, u32::MAX);
+        assert_eq!(
+            inventory.resources().get_amount(ResourceType::Data),
+            MAX_RESOURCE_AMOUNT
+        );
+        assert_eq!(dropped.get_amount(ResourceType::Data), 15);
+    }
+}
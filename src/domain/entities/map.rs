@@ -3,14 +3,15 @@
 //! This entity manages the game world including terrain, resources,
 //! and procedural generation of map chunks.
 
+use crate::domain::services::chunk_ownership::ChunkFeatures;
 use crate::domain::value_objects::{
-    resources::ResourceNodeProperties,
+    resources::{ResourceNodeProperties, ResourceRichness},
     terrain::{Elevation, TerrainType},
     EntityId, Position3D, ResourceType, TileCoordinate,
 };
 use crate::domain::{constants, DomainError, DomainResult};
 use chrono::{DateTime, Utc};
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 /// The game world map entity
 #[derive(Debug, Clone, PartialEq)]
@@ -23,8 +24,12 @@ pub struct Map {
     created_at: DateTime<Utc>,
     last_updated: DateTime<Utc>,
     version: u64,
+    relief_version: u64,
     player_history: VecDeque<Position3D>,
+    pinned_tiles: HashSet<TileCoordinate>,
     cache_dir: String,
+    fixed_layout: bool,
+    chunk_features: ChunkFeatures,
 }
 
 impl Map {
@@ -48,11 +53,35 @@ impl Map {
             created_at: now,
             last_updated: now,
             version: 1,
+            relief_version: 1,
             player_history: VecDeque::with_capacity(constants::PLAYER_HISTORY_SIZE),
+            pinned_tiles: HashSet::new(),
             cache_dir,
+            fixed_layout: false,
+            chunk_features: ChunkFeatures::new(),
         })
     }
 
+    /// Mark the map as fixed-layout; procedural generation never extends it
+    pub fn mark_fixed_layout(&mut self) {
+        self.fixed_layout = true;
+    }
+
+    /// Check if the map is fixed-layout (e.g. a ruin interior)
+    pub fn has_fixed_layout(&self) -> bool {
+        self.fixed_layout
+    }
+
+    /// Chunks generated so far and the features placed in them
+    pub fn chunk_features(&self) -> &ChunkFeatures {
+        &self.chunk_features
+    }
+
+    /// Get mutable chunk ledger, for generation passes
+    pub fn chunk_features_mut(&mut self) -> &mut ChunkFeatures {
+        &mut self.chunk_features
+    }
+
     /// Get map ID
     pub fn id(&self) -> &EntityId {
         &self.id
@@ -80,16 +109,61 @@ impl Map {
 
     /// Set tile at coordinate
     pub fn set_tile(&mut self, coordinate: TileCoordinate, tile: MapTile) {
-        self.tiles.insert(coordinate, tile);
+        let previous = self.tiles.insert(coordinate, tile.clone());
+        if previous.map(|previous| previous.elevation) != Some(tile.elevation) {
+            self.relief_version += 1;
+        }
         self.last_updated = Utc::now();
         self.version += 1;
     }
 
+    /// Counter bumped whenever a tile's elevation changes or a tile is added
+    ///
+    /// Slopes only change with it, so anything derived from them can be
+    /// kept until it moves.
+    pub fn relief_version(&self) -> u64 {
+        self.relief_version
+    }
+
+    /// Steepness of a tile: the largest height difference to a cardinal
+    /// neighbor
+    ///
+    /// Neighbors that are not loaded, e.g. across the border into a chunk
+    /// that has not been generated yet, are left out rather than read as
+    /// sea level. `None` when the tile itself is not loaded.
+    pub fn slope_at(&self, coordinate: &TileCoordinate) -> Option<u32> {
+        let height = self.get_tile(coordinate)?.elevation.height;
+        let slope = [(0, 1), (0, -1), (1, 0), (-1, 0)]
+            .into_iter()
+            .filter_map(|(dx, dy)| {
+                let neighbor =
+                    TileCoordinate::new(coordinate.x + dx, coordinate.y + dy, coordinate.z);
+                self.get_tile(&neighbor)
+            })
+            .map(|neighbor| neighbor.elevation.height.abs_diff(height))
+            .max()
+            .unwrap_or(0);
+        Some(slope)
+    }
+
     /// Get all loaded tiles
     pub fn tiles(&self) -> &HashMap<TileCoordinate, MapTile> {
         &self.tiles
     }
 
+    /// Keep a tile loaded even when it leaves the player's cache area
+    ///
+    /// Used for hand-shaped tiles that would otherwise be regenerated from
+    /// the seed after being evicted.
+    pub fn pin_tile(&mut self, coordinate: TileCoordinate) {
+        self.pinned_tiles.insert(coordinate);
+    }
+
+    /// Check if a tile is pinned in memory
+    pub fn is_tile_pinned(&self, coordinate: &TileCoordinate) -> bool {
+        self.pinned_tiles.contains(coordinate)
+    }
+
     /// Update player position and manage tile cache
     pub fn update_player_position(&mut self, position: Position3D) {
         // Add to history
@@ -98,6 +172,11 @@ impl Map {
             self.player_history.pop_front();
         }
 
+        // Fixed-layout maps cannot be regenerated, so every tile stays loaded
+        if self.fixed_layout {
+            return;
+        }
+
         // Note: Tile generation is now handled by MapService
         // This method only manages the cache - generation should be done
         // via MapService.generate_tiles_around_player() before calling this method
@@ -114,12 +193,17 @@ impl Map {
             .map(TileCoordinate::from)
             .collect();
 
-        // Remove tiles not in visible set or history
+        // Remove tiles not in visible set or history. Explored tiles stay, the
+        // map remembers charted ground until it goes stale.
         let tiles_to_cache: Vec<TileCoordinate> = self
             .tiles
-            .keys()
-            .filter(|coord| !visible_coords.contains(coord))
-            .cloned()
+            .iter()
+            .filter(|(coord, tile)| {
+                !tile.is_explored()
+                    && !visible_coords.contains(coord)
+                    && !self.pinned_tiles.contains(coord)
+            })
+            .map(|(coord, _)| *coord)
             .collect();
 
         // Cache old tiles (simplified - would be async)
@@ -142,11 +226,40 @@ impl Map {
         self.version += 1;
     }
 
+    /// Get mutable resource node at position
+    pub fn get_resource_node_mut(&mut self, position: &Position3D) -> Option<&mut ResourceNode> {
+        let node = self.resource_nodes.get_mut(position)?;
+        self.last_updated = Utc::now();
+        self.version += 1;
+        Some(node)
+    }
+
+    /// Remove the resource node at position, returning it
+    pub fn take_resource_node(&mut self, position: &Position3D) -> Option<ResourceNode> {
+        let node = self.resource_nodes.remove(position)?;
+        self.last_updated = Utc::now();
+        self.version += 1;
+        Some(node)
+    }
+
     /// Get all resource nodes
     pub fn resource_nodes(&self) -> &HashMap<Position3D, ResourceNode> {
         &self.resource_nodes
     }
 
+    /// Richness a discovery on `position` draws on
+    ///
+    /// A resource node on the tile sets it; otherwise the tile's own ground.
+    pub fn discovery_richness(&self, position: &Position3D) -> ResourceRichness {
+        self.get_resource_node(position)
+            .map(|node| node.properties().richness)
+            .or_else(|| {
+                self.get_tile(&TileCoordinate::from(*position))
+                    .map(|tile| tile.richness)
+            })
+            .unwrap_or(ResourceRichness::Average)
+    }
+
     /// Check if position is passable
     pub fn is_passable(&self, position: &Position3D) -> bool {
         let tile_coord = TileCoordinate::from(*position);
@@ -167,6 +280,12 @@ impl Map {
         }
     }
 
+    /// Terrain at position, if the tile is generated
+    pub fn terrain_at(&self, position: &Position3D) -> Option<TerrainType> {
+        self.get_tile(&TileCoordinate::from(*position))
+            .map(|tile| tile.terrain_type)
+    }
+
     /// Get danger level at position
     pub fn danger_level(&self, position: &Position3D) -> u8 {
         let tile_coord = TileCoordinate::from(*position);
@@ -215,6 +334,10 @@ pub struct MapTile {
     pub elevation: Elevation,
     pub is_explored: bool,
     pub last_visited: Option<DateTime<Utc>>,
+    /// Day of the run the player last saw this tile
+    pub last_visited_day: Option<u32>,
+    /// How much a resource discovery on this tile turns up
+    pub richness: ResourceRichness,
 }
 
 impl MapTile {
@@ -225,15 +348,29 @@ impl MapTile {
             elevation,
             is_explored,
             last_visited: None,
+            last_visited_day: None,
+            richness: ResourceRichness::Average,
         }
     }
 
+    /// The same tile with ground of `richness`
+    pub fn with_richness(mut self, richness: ResourceRichness) -> Self {
+        self.richness = richness;
+        self
+    }
+
     /// Mark tile as explored
     pub fn explore(&mut self) {
         self.is_explored = true;
         self.last_visited = Some(Utc::now());
     }
 
+    /// Mark tile as explored and seen on `day`
+    pub fn visit(&mut self, day: u32) {
+        self.explore();
+        self.last_visited_day = Some(day);
+    }
+
     /// Check if tile has been explored
     pub fn is_explored(&self) -> bool {
         self.is_explored
@@ -306,6 +443,23 @@ impl ResourceNode {
         self.current_amount == 0
     }
 
+    /// Empty the node without counting it as harvested
+    pub fn deplete(&mut self) {
+        self.current_amount = 0;
+    }
+
+    /// Turn the node into a source of another resource, keeping its amount
+    pub fn transmute(&mut self, resource_type: ResourceType) {
+        self.properties.resource_type = resource_type;
+    }
+
+    /// Fill the node back to capacity, returning the amount restored
+    pub fn replenish(&mut self) -> u32 {
+        let restored = self.max_capacity - self.current_amount;
+        self.current_amount = self.max_capacity;
+        restored
+    }
+
     /// Check if node is full
     pub fn is_full(&self) -> bool {
         self.current_amount >= self.max_capacity
@@ -467,4 +621,59 @@ mod tests {
         // Should include center and adjacent tiles (5 total in Manhattan distance 1)
         assert_eq!(tiles.len(), 5);
     }
+
+    #[test]
+    fn slopes_ignore_neighbors_in_ungenerated_chunks() {
+        let mut map = Map::new(EntityId::generate(), "Test".to_string(), 123).unwrap();
+        let tile_at =
+            |height| MapTile::new(TerrainType::Plains, Elevation::new(height).unwrap(), false);
+        // Last column of chunk 0; x = 16 belongs to the next chunk
+        let border = TileCoordinate::new(15, 0, 0);
+        map.set_tile(border, tile_at(6));
+        map.set_tile(TileCoordinate::new(14, 0, 0), tile_at(5));
+        assert_eq!(map.slope_at(&border), Some(1));
+        assert_eq!(map.slope_at(&TileCoordinate::new(16, 0, 0)), None);
+
+        // Generating the neighboring chunk reveals the drop
+        let relief = map.relief_version();
+        map.set_tile(TileCoordinate::new(16, 0, 0), tile_at(2));
+        assert_eq!(map.slope_at(&border), Some(4));
+        assert!(map.relief_version() > relief);
+
+        // Exploring a tile keeps its height and the relief
+        let relief = map.relief_version();
+        let mut explored = map.get_tile(&border).unwrap().clone();
+        explored.explore();
+        map.set_tile(border, explored);
+        assert_eq!(map.relief_version(), relief);
+    }
+
+    #[test]
+    fn pinned_tiles_survive_cache_eviction() {
+        let mut map = Map::new(EntityId::generate(), "Test".to_string(), 123).unwrap();
+
+        let far = TileCoordinate::new(10, 10, 0);
+        let also_far = TileCoordinate::new(-10, -10, 0);
+        map.set_tile(
+            far,
+            MapTile::new(TerrainType::Plains, Elevation::sea_level(), false),
+        );
+        map.set_tile(
+            also_far,
+            MapTile::new(TerrainType::Plains, Elevation::sea_level(), false),
+        );
+        map.pin_tile(far);
+        let charted = TileCoordinate::new(0, 12, 0);
+        map.set_tile(
+            charted,
+            MapTile::new(TerrainType::Plains, Elevation::sea_level(), true),
+        );
+
+        map.update_player_position(Position3D::origin());
+
+        assert!(map.get_tile(&far).is_some());
+        assert!(map.get_tile(&also_far).is_none());
+        // Explored ground is remembered
+        assert!(map.get_tile(&charted).is_some());
+    }
 }
@@ -8,19 +8,23 @@ pub mod audio;
 pub mod base;
 pub mod event;
 pub mod game;
+pub mod inventory;
 pub mod map;
 pub mod player;
 pub mod quest;
+pub mod refinery;
 pub mod resource;
 
 // Re-export all entity types for convenience
 pub use audio::{AudioAsset, AudioPlayback};
-pub use base::{Base, BaseBuilding, BaseLevel};
+pub use base::{Base, BaseBuilding, BaseLevel, BuildingType};
 pub use event::{Event, EventType};
 pub use game::GameSession;
+pub use inventory::{Inventory, InventoryItem};
 pub use map::{Map, MapTile, ResourceNode};
 pub use player::Player;
 pub use quest::{Quest, QuestObjective, QuestStatus};
+pub use refinery::{RefineryDelivery, RefineryJob, RefineryQueue, RefineryRecipe};
 pub use resource::Resource;
 
 /// Common trait for all domain entities
@@ -3,8 +3,11 @@
 //! This entity represents the player character with RPG statistics,
 //! progression system, inventory, and all player-related game state.
 
+use crate::domain::entities::Inventory;
+use crate::domain::services::gear::{derived_stats, Gear, GearItem, GearSlot};
+use crate::domain::services::inventory::Consumables;
 use crate::domain::value_objects::{
-    resources::ResourceCollection, EntityId, Experience, GameTime, PlayerStats, Position3D,
+    resources::ResourceCollection, EntityId, Experience, GameTime, Health, PlayerStats, Position3D,
     StatType,
 };
 use crate::domain::{DomainError, DomainResult};
@@ -21,12 +24,15 @@ pub struct Player {
     position: Position3D,
     stats: PlayerStats,
     experience: Experience,
-    resources: ResourceCollection,
+    health: Health,
+    inventory: Inventory,
     movement_points: u8,
     action_points: u8,
     max_movement_points: u8,
     max_action_points: u8,
     equipment: PlayerEquipment,
+    gear: Gear,
+    consumables: Consumables,
     status_effects: Vec<StatusEffect>,
     exploration_data: ExplorationData,
     created_at: DateTime<Utc>,
@@ -49,7 +55,7 @@ impl Player {
         }
 
         let experience = Experience::new(0)?;
-        let resources = ResourceCollection::starting_resources();
+        let inventory = Inventory::with_resources(ResourceCollection::starting_resources());
         let now = Utc::now();
 
         Ok(Self {
@@ -58,12 +64,15 @@ impl Player {
             position: starting_position,
             stats: starting_stats,
             experience,
-            resources,
+            health: Health::full(crate::domain::constants::BASE_PLAYER_HEALTH),
+            inventory,
             movement_points: crate::domain::constants::BASE_MOVEMENT_POINTS,
             action_points: crate::domain::constants::BASE_ACTION_POINTS,
             max_movement_points: crate::domain::constants::BASE_MOVEMENT_POINTS,
             max_action_points: crate::domain::constants::BASE_ACTION_POINTS,
             equipment: PlayerEquipment::new(),
+            gear: Gear::new(),
+            consumables: Consumables::new(),
             status_effects: Vec::new(),
             exploration_data: ExplorationData::new(),
             created_at: now,
@@ -94,11 +103,16 @@ impl Player {
         &self.position
     }
 
-    /// Get player statistics
+    /// Get player statistics, without gear
     pub fn stats(&self) -> &PlayerStats {
         &self.stats
     }
 
+    /// Get player statistics with the equipped gear applied
+    pub fn derived_stats(&self) -> PlayerStats {
+        derived_stats(&self.stats, &self.gear)
+    }
+
     /// Get current experience
     pub fn experience(&self) -> &Experience {
         &self.experience
@@ -111,23 +125,64 @@ impl Player {
 
     /// Get resources
     pub fn resources(&self) -> &ResourceCollection {
-        &self.resources
+        self.inventory.resources()
     }
 
     /// Get mutable resources
     pub fn resources_mut(&mut self) -> &mut ResourceCollection {
         self.update_timestamp();
-        &mut self.resources
+        self.inventory.resources_mut()
+    }
+
+    /// Get the inventory: resources and carried items
+    pub fn inventory(&self) -> &Inventory {
+        &self.inventory
+    }
+
+    /// Get the mutable inventory
+    pub fn inventory_mut(&mut self) -> &mut Inventory {
+        self.update_timestamp();
+        &mut self.inventory
     }
 
     /// Add resources to player inventory
     pub fn add_resources(&mut self, resources: &ResourceCollection) {
-        if let Err(e) = self.resources.add_collection(resources) {
+        if let Err(e) = self.inventory.resources_mut().add_collection(resources) {
             warn!("Failed to add resources to player: {}", e);
         }
         self.update_timestamp();
     }
 
+    /// Get current health
+    pub fn health(&self) -> &Health {
+        &self.health
+    }
+
+    /// Replace the health, as when a saved player is loaded
+    pub fn set_health(&mut self, health: Health) {
+        self.health = health;
+        self.update_timestamp();
+    }
+
+    /// Take damage, stopping at zero health; returns the damage taken
+    pub fn take_damage(&mut self, amount: u32) -> u32 {
+        let taken = self.health.take_damage(amount);
+        self.update_timestamp();
+        taken
+    }
+
+    /// Heal, stopping at maximum health; returns the health gained
+    pub fn heal(&mut self, amount: u32) -> u32 {
+        let healed = self.health.heal(amount);
+        self.update_timestamp();
+        healed
+    }
+
+    /// Check if the player has no health left
+    pub fn is_dead(&self) -> bool {
+        self.health.is_dead()
+    }
+
     /// Get current movement points
     pub fn movement_points(&self) -> u8 {
         self.movement_points
@@ -138,9 +193,10 @@ impl Player {
         self.action_points
     }
 
-    /// Get maximum movement points
+    /// Get maximum movement points, including the equipped module
     pub fn max_movement_points(&self) -> u8 {
         self.max_movement_points
+            .saturating_add(self.gear.rest_movement_bonus())
     }
 
     /// Get maximum action points
@@ -181,6 +237,12 @@ impl Player {
         Ok(())
     }
 
+    /// Place the player at a position without spending movement (map transitions)
+    pub fn relocate(&mut self, new_position: Position3D) {
+        self.position = new_position;
+        self.update_timestamp();
+    }
+
     /// Consume action points
     pub fn consume_action_points(&mut self, cost: u8) -> DomainResult<()> {
         if !self.can_act() {
@@ -203,15 +265,24 @@ impl Player {
 
     /// Restore movement and action points (typically at turn start)
     pub fn restore_points(&mut self) {
-        self.movement_points = self.max_movement_points;
+        self.movement_points = self.max_movement_points();
         self.action_points = self.max_action_points;
         self.update_timestamp();
     }
 
+    /// Set the movement point maximum before gear, keeping at least one point
+    ///
+    /// Movement points above the new maximum are cut back to it.
+    pub fn set_max_movement_points(&mut self, max: u8) {
+        self.max_movement_points = max.max(1);
+        self.movement_points = self.movement_points.min(self.max_movement_points());
+        self.update_timestamp();
+    }
+
     /// Add movement points (capped at maximum)
     pub fn add_movement_points(&mut self, points: u8) {
         self.movement_points =
-            (self.movement_points.saturating_add(points)).min(self.max_movement_points);
+            (self.movement_points.saturating_add(points)).min(self.max_movement_points());
         self.update_timestamp();
     }
 
@@ -276,9 +347,15 @@ impl Player {
         Ok(())
     }
 
+    /// Lower a stat by `amount` points, never below the minimum
+    pub fn decrease_stat(&mut self, stat_type: StatType, amount: u8) {
+        self.stats = self.stats.decrease_stat(stat_type, amount);
+        self.update_timestamp();
+    }
+
     /// Get stat modifier for dice rolls
     pub fn get_stat_modifier(&self, stat_type: StatType) -> i8 {
-        let base_modifier = self.stats.get_modifier(stat_type);
+        let base_modifier = self.derived_stats().get_modifier(stat_type);
         let equipment_modifier = self.equipment.get_stat_modifier(stat_type);
         base_modifier + equipment_modifier
     }
@@ -355,6 +432,64 @@ impl Player {
         item
     }
 
+    /// Get found gear, equipped and stashed
+    pub fn gear(&self) -> &Gear {
+        &self.gear
+    }
+
+    /// Put found gear into the stash, returning its ID
+    pub fn stow_gear(&mut self, item: GearItem) -> u32 {
+        let id = self.gear.stow(item);
+        self.update_timestamp();
+        id
+    }
+
+    /// Equip stashed gear, returning the ID of the piece it replaced
+    pub fn equip_gear(&mut self, id: u32) -> DomainResult<Option<u32>> {
+        let displaced = self.gear.equip(id)?;
+        self.clamp_movement_points();
+        self.update_timestamp();
+        Ok(displaced)
+    }
+
+    /// Take off the gear in a slot, returning its ID
+    pub fn unequip_gear(&mut self, slot: GearSlot) -> Option<u32> {
+        let id = self.gear.unequip(slot)?;
+        self.clamp_movement_points();
+        self.update_timestamp();
+        Some(id)
+    }
+
+    /// Salvage stashed gear, returning the piece taken apart
+    pub fn salvage_gear(&mut self, id: u32) -> DomainResult<GearItem> {
+        let item = self.gear.salvage(id)?;
+        self.update_timestamp();
+        Ok(item)
+    }
+
+    /// Replace all gear, e.g. when restoring a save
+    pub fn set_gear(&mut self, gear: Gear) {
+        self.gear = gear;
+        self.clamp_movement_points();
+        self.update_timestamp();
+    }
+
+    /// Cut movement points back to a maximum that shrank with the gear
+    fn clamp_movement_points(&mut self) {
+        self.movement_points = self.movement_points.min(self.max_movement_points());
+    }
+
+    /// Get single-use items
+    pub fn consumables(&self) -> &Consumables {
+        &self.consumables
+    }
+
+    /// Get mutable single-use items
+    pub fn consumables_mut(&mut self) -> &mut Consumables {
+        self.update_timestamp();
+        &mut self.consumables
+    }
+
     /// Check if player meets level requirement
     pub fn meets_level_requirement(&self, required_level: u32) -> bool {
         self.level() >= required_level
@@ -383,15 +518,13 @@ impl Player {
 
     /// Get player's current carrying capacity
     pub fn carrying_capacity(&self) -> u32 {
-        let base_capacity = 100;
-        let strength_bonus = self.stats.strength as u32 * 10;
         let equipment_bonus = self.equipment.get_carrying_capacity_bonus();
-        base_capacity + strength_bonus + equipment_bonus
+        Inventory::strength_capacity(self.stats.strength) + equipment_bonus
     }
 
     /// Check if player is overloaded
     pub fn is_overloaded(&self) -> bool {
-        self.resources.storage_requirement() > self.carrying_capacity()
+        self.inventory.weight() > self.carrying_capacity()
     }
 
     /// Get summary information for display
@@ -402,7 +535,7 @@ impl Player {
             position: self.position,
             movement_points: self.movement_points,
             action_points: self.action_points,
-            total_resources_value: self.resources.total_value(),
+            total_resources_value: self.resources().total_value(),
             locations_visited: self.locations_visited_count(),
             is_overloaded: self.is_overloaded(),
         }
@@ -422,7 +555,7 @@ impl Player {
     pub fn is_valid(&self) -> bool {
         !self.name.is_empty()
             && self.name.len() <= 50
-            && self.movement_points <= self.max_movement_points
+            && self.movement_points <= self.max_movement_points()
             && self.action_points <= self.max_action_points
             && self.level() <= crate::domain::constants::MAX_PLAYER_LEVEL
     }
@@ -181,6 +181,20 @@ impl Quest {
         }
     }
 
+    /// Mark an active quest as failed, e.g. when its target is gone
+    pub fn fail(&mut self) -> DomainResult<()> {
+        match self.status {
+            QuestStatus::Active => {
+                self.status = QuestStatus::Failed;
+                self.version += 1;
+                Ok(())
+            }
+            _ => Err(DomainError::QuestError(
+                "Only active quests can fail".to_string(),
+            )),
+        }
+    }
+
     /// Check if quest is completed
     pub fn is_completed(&self) -> bool {
         self.status == QuestStatus::Completed
@@ -311,7 +325,7 @@ pub enum ObjectiveType {
     /// Explore tiles
     ExploreTiles(u32),
     /// Survive for duration
-    Survive(u32), // seconds
+    Survive(u32), // rest cycles
     /// Reach player level
     ReachLevel(u32),
     /// Defeat enemies
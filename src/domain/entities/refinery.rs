@@ -0,0 +1,309 @@
+//! Refinery - Multi-stage processing jobs run by the base
+//!
+//! The refinery turns raw materials into refined ones over several rests.
+//! Inputs are paid when a job is queued. Jobs run one at a time in queue
+//! order, and every rest advances the front job by one step. Finished jobs
+//! stay listed until the player collects them. The queue is plain data so
+//! it can be saved and restored with its progress.
+
+use crate::domain::constants::{REFINERY_CANCEL_REFUND_PERCENT, REFINERY_JOBS_PER_LEVEL};
+use crate::domain::value_objects::resources::ResourceCollection;
+use crate::domain::value_objects::ResourceType;
+use crate::domain::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+
+/// Processing recipes offered by the refinery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RefineryRecipe {
+    /// 3 Metal into 1 Alloys over 1 rest
+    AlloySmelting,
+    /// 2 Alloys and 1 Energy into 1 Technology over 2 rests
+    TechnologyAssembly,
+}
+
+impl RefineryRecipe {
+    /// Every recipe in menu order
+    pub fn all() -> [RefineryRecipe; 2] {
+        [
+            RefineryRecipe::AlloySmelting,
+            RefineryRecipe::TechnologyAssembly,
+        ]
+    }
+
+    /// Resources paid when the job is queued
+    pub fn inputs(&self) -> &'static [(ResourceType, u32)] {
+        match self {
+            RefineryRecipe::AlloySmelting => &[(ResourceType::Metal, 3)],
+            RefineryRecipe::TechnologyAssembly => {
+                &[(ResourceType::Alloys, 2), (ResourceType::Energy, 1)]
+            }
+        }
+    }
+
+    /// Resource delivered when the job completes
+    pub fn output(&self) -> (ResourceType, u32) {
+        match self {
+            RefineryRecipe::AlloySmelting => (ResourceType::Alloys, 1),
+            RefineryRecipe::TechnologyAssembly => (ResourceType::Technology, 1),
+        }
+    }
+
+    /// Rests the job needs to complete
+    pub fn rests_required(&self) -> u32 {
+        match self {
+            RefineryRecipe::AlloySmelting => 1,
+            RefineryRecipe::TechnologyAssembly => 2,
+        }
+    }
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            RefineryRecipe::AlloySmelting => "Alloy Smelting",
+            RefineryRecipe::TechnologyAssembly => "Technology Assembly",
+        }
+    }
+
+    /// Inputs as a collection, ready to be paid
+    pub fn input_cost(&self) -> DomainResult<ResourceCollection> {
+        ResourceCollection::cost(self.inputs())
+    }
+}
+
+/// A queued, running or finished refinery job
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RefineryJob {
+    pub recipe: RefineryRecipe,
+    /// Rests of work done so far
+    pub rests_done: u32,
+}
+
+impl RefineryJob {
+    /// Create a job with no work done
+    pub fn new(recipe: RefineryRecipe) -> Self {
+        Self {
+            recipe,
+            rests_done: 0,
+        }
+    }
+
+    /// Check if the job has finished
+    pub fn is_complete(&self) -> bool {
+        self.rests_done >= self.recipe.rests_required()
+    }
+
+    /// Work done from 0.0 to 1.0
+    pub fn progress(&self) -> f32 {
+        (self.rests_done as f32 / self.recipe.rests_required() as f32).min(1.0)
+    }
+}
+
+/// Output of a finished job as it reached base storage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefineryDelivery {
+    pub recipe: RefineryRecipe,
+    pub resource_type: ResourceType,
+    /// Amount that fit into storage
+    pub deposited: u32,
+    /// Amount lost because storage was full
+    pub overflow: u32,
+}
+
+/// Jobs of the base refinery in queue order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RefineryQueue {
+    jobs: Vec<RefineryJob>,
+}
+
+impl RefineryQueue {
+    /// Create an empty queue
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Unfinished jobs a refinery of `building_level` can hold
+    pub fn capacity(building_level: u8) -> usize {
+        building_level.max(1) as usize * REFINERY_JOBS_PER_LEVEL
+    }
+
+    /// All jobs, finished ones included
+    pub fn jobs(&self) -> &[RefineryJob] {
+        &self.jobs
+    }
+
+    /// Number of jobs not finished yet
+    pub fn pending_count(&self) -> usize {
+        self.jobs.iter().filter(|job| !job.is_complete()).count()
+    }
+
+    /// Queue a job, paying its inputs through `pay`
+    ///
+    /// Nothing is paid when the queue is full, and nothing is queued when
+    /// paying fails.
+    pub fn enqueue(
+        &mut self,
+        recipe: RefineryRecipe,
+        building_level: u8,
+        pay: impl FnOnce(&ResourceCollection) -> DomainResult<()>,
+    ) -> DomainResult<()> {
+        if self.pending_count() >= Self::capacity(building_level) {
+            return Err(DomainError::BuildingRequirementsNotMet(format!(
+                "Refinery queue is full ({} jobs)",
+                Self::capacity(building_level)
+            )));
+        }
+        pay(&recipe.input_cost()?)?;
+        self.jobs.push(RefineryJob::new(recipe));
+        Ok(())
+    }
+
+    /// Advance the front unfinished job by one rest
+    ///
+    /// Returns the recipe of the job if this rest finished it.
+    pub fn advance_rest(&mut self) -> Option<RefineryRecipe> {
+        let job = self.jobs.iter_mut().find(|job| !job.is_complete())?;
+        job.rests_done += 1;
+        job.is_complete().then_some(job.recipe)
+    }
+
+    /// Cancel an unfinished job, returning the refunded share of its inputs
+    pub fn cancel(&mut self, index: usize) -> DomainResult<ResourceCollection> {
+        let job = self.jobs.get(index).ok_or_else(|| {
+            DomainError::ValidationError(format!("No refinery job at position {}", index + 1))
+        })?;
+        if job.is_complete() {
+            return Err(DomainError::ValidationError(
+                "A finished job cannot be cancelled; collect it instead".to_string(),
+            ));
+        }
+
+        let job = self.jobs.remove(index);
+        let mut refund = ResourceCollection::new();
+        for &(resource_type, amount) in job.recipe.inputs() {
+            refund.set_amount(resource_type, amount * REFINERY_CANCEL_REFUND_PERCENT / 100);
+        }
+        Ok(refund)
+    }
+
+    /// Remove finished jobs from the list; returns how many were removed
+    pub fn collect_completed(&mut self) -> usize {
+        let before = self.jobs.len();
+        self.jobs.retain(|job| !job.is_complete());
+        before - self.jobs.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cargo(amounts: &[(ResourceType, u32)]) -> ResourceCollection {
+        ResourceCollection::cost(amounts).unwrap()
+    }
+
+    #[test]
+    fn enqueue_consumes_inputs_and_respects_depth() {
+        let mut queue = RefineryQueue::new();
+        let mut hold = cargo(&[(ResourceType::Metal, 10)]);
+
+        for _ in 0..RefineryQueue::capacity(1) {
+            queue
+                .enqueue(RefineryRecipe::AlloySmelting, 1, |cost| hold.pay_cost(cost))
+                .unwrap();
+        }
+        assert_eq!(hold.get_amount(ResourceType::Metal), 4);
+
+        // A full queue takes nothing
+        assert!(queue
+            .enqueue(RefineryRecipe::AlloySmelting, 1, |cost| hold.pay_cost(cost))
+            .is_err());
+        assert_eq!(hold.get_amount(ResourceType::Metal), 4);
+
+        // Failing to pay queues nothing
+        assert!(queue
+            .enqueue(RefineryRecipe::TechnologyAssembly, 2, |cost| hold
+                .pay_cost(cost))
+            .is_err());
+        assert_eq!(queue.jobs().len(), 2);
+    }
+
+    #[test]
+    fn jobs_advance_one_step_per_rest_in_queue_order() {
+        let mut queue = RefineryQueue::new();
+        queue
+            .enqueue(RefineryRecipe::TechnologyAssembly, 2, |_| Ok(()))
+            .unwrap();
+        queue
+            .enqueue(RefineryRecipe::AlloySmelting, 2, |_| Ok(()))
+            .unwrap();
+
+        assert_eq!(queue.advance_rest(), None);
+        assert_eq!(queue.jobs()[0].progress(), 0.5);
+        assert_eq!(queue.jobs()[1].rests_done, 0);
+
+        assert_eq!(
+            queue.advance_rest(),
+            Some(RefineryRecipe::TechnologyAssembly)
+        );
+        assert_eq!(queue.advance_rest(), Some(RefineryRecipe::AlloySmelting));
+        assert_eq!(queue.advance_rest(), None);
+        assert_eq!(queue.pending_count(), 0);
+
+        assert_eq!(queue.collect_completed(), 2);
+        assert!(queue.jobs().is_empty());
+    }
+
+    #[test]
+    fn cancel_refunds_half_rounded_down() {
+        let mut queue = RefineryQueue::new();
+        queue
+            .enqueue(RefineryRecipe::AlloySmelting, 2, |_| Ok(()))
+            .unwrap();
+        queue
+            .enqueue(RefineryRecipe::TechnologyAssembly, 2, |_| Ok(()))
+            .unwrap();
+
+        let refund = queue.cancel(1).unwrap();
+        assert_eq!(refund.get_amount(ResourceType::Alloys), 1);
+        assert_eq!(refund.get_amount(ResourceType::Energy), 0);
+
+        let refund = queue.cancel(0).unwrap();
+        assert_eq!(refund.get_amount(ResourceType::Metal), 1);
+        assert!(queue.jobs().is_empty());
+        assert!(queue.cancel(0).is_err());
+    }
+
+    #[test]
+    fn finished_jobs_cannot_be_cancelled() {
+        let mut queue = RefineryQueue::new();
+        queue
+            .enqueue(RefineryRecipe::AlloySmelting, 1, |_| Ok(()))
+            .unwrap();
+        queue.advance_rest();
+
+        assert!(queue.cancel(0).is_err());
+        assert_eq!(queue.jobs().len(), 1);
+    }
+
+    #[test]
+    fn in_progress_queue_survives_a_save_round_trip() {
+        let mut queue = RefineryQueue::new();
+        queue
+            .enqueue(RefineryRecipe::TechnologyAssembly, 2, |_| Ok(()))
+            .unwrap();
+        queue
+            .enqueue(RefineryRecipe::AlloySmelting, 2, |_| Ok(()))
+            .unwrap();
+        queue.advance_rest();
+
+        let saved = serde_json::to_string(&queue).unwrap();
+        let mut restored: RefineryQueue = serde_json::from_str(&saved).unwrap();
+        assert_eq!(restored, queue);
+
+        // Progress carries on where it stopped
+        assert_eq!(
+            restored.advance_rest(),
+            Some(RefineryRecipe::TechnologyAssembly)
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! Announcements - Short plain-text lines for screen readers
+//!
+//! Critical game events are also put into words for players who cannot read
+//! the canvas. Each announcement carries a severity, and the player's filter
+//! decides which severities are spoken at all. Text is reduced to plain
+//! words, without emoji or decoration, and everything pushed within a short
+//! window is merged into a single announcement so a busy turn does not flood
+//! the reader with one line per effect.
+
+use crate::domain::constants::ANNOUNCEMENT_COALESCE_SECS;
+use crate::domain::services::font_service::fallback_text;
+use serde::{Deserialize, Serialize};
+
+/// How urgent an announcement is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AnnouncementSeverity {
+    /// Routine feedback, such as a screen opening
+    Info,
+    /// Outcomes the player should hear about
+    Important,
+    /// Warnings that need the player's attention now
+    Critical,
+}
+
+/// Severities the player wants announced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AnnouncementFilter {
+    All,
+    #[default]
+    Important,
+    Critical,
+}
+
+impl AnnouncementFilter {
+    /// Check if announcements of `severity` pass the filter
+    pub fn allows(&self, severity: AnnouncementSeverity) -> bool {
+        let lowest = match self {
+            AnnouncementFilter::All => AnnouncementSeverity::Info,
+            AnnouncementFilter::Important => AnnouncementSeverity::Important,
+            AnnouncementFilter::Critical => AnnouncementSeverity::Critical,
+        };
+        severity >= lowest
+    }
+}
+
+/// One line handed to the screen reader
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Announcement {
+    pub text: String,
+    pub severity: AnnouncementSeverity,
+}
+
+/// `text` as a screen reader should hear it
+///
+/// Emoji and box drawing go, symbols with a meaning become words, and the
+/// lines are joined into sentences.
+pub fn plain_announcement_text(text: &str) -> String {
+    fallback_text(text)
+        .lines()
+        .map(|line| {
+            line.trim_start_matches(|c: char| !c.is_alphanumeric())
+                .trim()
+        })
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            if line.ends_with(['.', '!', '?']) {
+                line.to_string()
+            } else {
+                format!("{}.", line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Filters announcements and merges those close together in time
+#[derive(Debug, Clone, bevy::prelude::Resource)]
+pub struct AnnouncementService {
+    filter: AnnouncementFilter,
+    window: f64,
+    opened_at: Option<f64>,
+    pending: Vec<Announcement>,
+}
+
+impl Default for AnnouncementService {
+    fn default() -> Self {
+        Self::new(AnnouncementFilter::default())
+    }
+}
+
+impl AnnouncementService {
+    /// Create a service with the standard coalescing window
+    pub fn new(filter: AnnouncementFilter) -> Self {
+        Self {
+            filter,
+            window: ANNOUNCEMENT_COALESCE_SECS,
+            opened_at: None,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Severities currently announced
+    pub fn filter(&self) -> AnnouncementFilter {
+        self.filter
+    }
+
+    /// Change the severities announced from now on
+    pub fn set_filter(&mut self, filter: AnnouncementFilter) {
+        self.filter = filter;
+    }
+
+    /// Queue `text` at time `now` in seconds; returns false if it was dropped
+    ///
+    /// Filtered severities, text with nothing left to read and repeats of a
+    /// line already waiting are dropped.
+    pub fn push(&mut self, text: &str, severity: AnnouncementSeverity, now: f64) -> bool {
+        if !self.filter.allows(severity) {
+            return false;
+        }
+        let text = plain_announcement_text(text);
+        if text.is_empty() || self.pending.iter().any(|pending| pending.text == text) {
+            return false;
+        }
+        self.opened_at.get_or_insert(now);
+        self.pending.push(Announcement { text, severity });
+        true
+    }
+
+    /// The merged announcement, once the window of the first pushed line closed
+    pub fn flush(&mut self, now: f64) -> Option<Announcement> {
+        let opened_at = self.opened_at?;
+        if now - opened_at < self.window {
+            return None;
+        }
+        self.opened_at = None;
+        let pending = std::mem::take(&mut self.pending);
+        let severity = pending.iter().map(|line| line.severity).max()?;
+        let text = pending
+            .into_iter()
+            .map(|line| line.text)
+            .collect::<Vec<_>>()
+            .join(" ");
+        Some(Announcement { text, severity })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_within_the_window_are_merged() {
+        let mut service = AnnouncementService::new(AnnouncementFilter::All);
+        assert!(service.push("Movement blocked", AnnouncementSeverity::Important, 10.0));
+        assert!(service.push("Food is low", AnnouncementSeverity::Critical, 10.3));
+        assert!(!service.push("Food is low", AnnouncementSeverity::Critical, 10.4));
+        assert_eq!(service.flush(10.49), None);
+
+        let merged = service.flush(10.5).unwrap();
+        assert_eq!(merged.text, "Movement blocked. Food is low.");
+        assert_eq!(merged.severity, AnnouncementSeverity::Critical);
+        assert_eq!(service.flush(11.5), None);
+
+        // A line after the window opens a window of its own
+        service.push("Base opened", AnnouncementSeverity::Info, 12.0);
+        assert_eq!(service.flush(12.2), None);
+        assert_eq!(service.flush(12.6).unwrap().text, "Base opened.");
+    }
+
+    #[test]
+    fn filter_drops_lower_severities() {
+        let mut service = AnnouncementService::new(AnnouncementFilter::Critical);
+        assert!(!service.push("Quest log opened", AnnouncementSeverity::Info, 0.0));
+        assert!(!service.push("Rested", AnnouncementSeverity::Important, 0.0));
+        assert!(service.push("Out of food", AnnouncementSeverity::Critical, 0.0));
+
+        service.set_filter(AnnouncementFilter::Important);
+        assert!(service.push("Rested", AnnouncementSeverity::Important, 0.1));
+        assert!(!service.push("Quest log opened", AnnouncementSeverity::Info, 0.1));
+        assert!(AnnouncementFilter::All.allows(AnnouncementSeverity::Info));
+    }
+
+    #[test]
+    fn text_is_reduced_to_plain_sentences() {
+        assert_eq!(
+            plain_announcement_text("⚠️ Food is running low"),
+            "Food is running low."
+        );
+        assert_eq!(
+            plain_announcement_text("🎲 Roll: 14 → Success!"),
+            "Dice Roll: 14 -> Success!"
+        );
+        assert_eq!(
+            plain_announcement_text("🌅 Dawn breaks\n🌙 You rested well"),
+            "Dawn breaks. You rested well."
+        );
+        assert_eq!(plain_announcement_text("🚀 ✨"), "");
+    }
+}
@@ -0,0 +1,435 @@
+//! Anomaly Storms - Roaming hazards of the deep zone
+//!
+//! Far from the landing site, a rest may brew an anomaly storm: a region of
+//! radius two that drifts one to three tiles per rest, mostly along its
+//! heading, and dissipates after five to ten rests. Moving into a storm costs
+//! double, rolls with disadvantage and may set off a Hazard event. Resource
+//! nodes the storm sweeps over can be depleted, and Metal nodes may even
+//! transmute into Exotic Matter. Storms never move the player; effects are
+//! looked up on every move, so a storm arriving or lifting simply changes
+//! the next step.
+
+use crate::domain::constants::{
+    STORM_DEEP_ZONE_DISTANCE, STORM_DEPLETE_CHANCE, STORM_HAZARD_CHANCE,
+    STORM_HEADING_BIAS_PERCENT, STORM_MAX_ACTIVE, STORM_MAX_LIFETIME_RESTS, STORM_MAX_STEP,
+    STORM_MIN_LIFETIME_RESTS, STORM_MIN_STEP, STORM_MOVEMENT_COST_MULTIPLIER, STORM_RADIUS,
+    STORM_SPAWN_CHANCE, STORM_SPAWN_MAX_DISTANCE, STORM_SPAWN_MIN_DISTANCE, STORM_TRANSMUTE_CHANCE,
+};
+use crate::domain::entities::{Event, EventType, Map};
+use crate::domain::services::MovementConditions;
+use crate::domain::value_objects::position::Direction;
+use crate::domain::value_objects::{Position3D, ResourceType, TileCoordinate};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// A storm region on the overworld
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnomalyStorm {
+    pub center: Position3D,
+    /// Direction most steps follow
+    pub heading: Direction,
+    /// Rests left before the storm dissipates
+    pub rests_remaining: u32,
+}
+
+impl AnomalyStorm {
+    /// Check if `position` lies inside the storm
+    pub fn covers(&self, position: Position3D) -> bool {
+        position.z == self.center.z && self.center.manhattan_distance_2d(&position) <= STORM_RADIUS
+    }
+
+    /// Tiles inside the storm
+    pub fn footprint(&self) -> Vec<Position3D> {
+        self.center.positions_within_distance(STORM_RADIUS)
+    }
+
+    /// Check if the storm lifts at the next rest
+    pub fn is_dissipating(&self) -> bool {
+        self.rests_remaining <= 1
+    }
+
+    /// Direction of one step: the heading on a biased roll, any direction otherwise
+    pub fn step_direction(&self, rng: &mut impl Rng) -> Direction {
+        if rng.gen_range(1..=100) <= STORM_HEADING_BIAS_PERCENT {
+            self.heading
+        } else {
+            Direction::horizontal()[rng.gen_range(0..4)]
+        }
+    }
+
+    /// Travel one to three tiles, returning the tiles newly swept on the way
+    pub fn drift(&mut self, rng: &mut impl Rng) -> Vec<Position3D> {
+        let mut covered: HashSet<Position3D> = self.footprint().into_iter().collect();
+        let mut swept = Vec::new();
+        for _ in 0..rng.gen_range(STORM_MIN_STEP..=STORM_MAX_STEP) {
+            self.center = self.center.move_direction(self.step_direction(rng), 1);
+            for position in self.footprint() {
+                if covered.insert(position) {
+                    swept.push(position);
+                }
+            }
+        }
+        swept
+    }
+}
+
+/// What a passing storm did to a resource node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeChange {
+    /// Metal turned into Exotic Matter
+    Transmuted,
+    /// The node was emptied
+    Depleted,
+}
+
+impl NodeChange {
+    /// Effect of a d100 `roll` on a swept node of `resource_type`
+    ///
+    /// Low rolls deplete any node; high rolls transmute Metal.
+    pub fn roll(resource_type: ResourceType, roll: u8) -> Option<NodeChange> {
+        if roll <= STORM_DEPLETE_CHANCE {
+            Some(NodeChange::Depleted)
+        } else if resource_type == ResourceType::Metal && roll > 100 - STORM_TRANSMUTE_CHANCE {
+            Some(NodeChange::Transmuted)
+        } else {
+            None
+        }
+    }
+}
+
+/// What happened to the storms during one rest
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StormReport {
+    /// Storms still active that moved
+    pub moved: usize,
+    /// Centers of the storms that dissipated
+    pub dissipated: Vec<Position3D>,
+    /// Resource nodes changed by passing storms
+    pub node_changes: Vec<(Position3D, NodeChange)>,
+}
+
+/// Active storms of the world
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, bevy::prelude::Resource)]
+pub struct WorldHazards {
+    storms: Vec<AnomalyStorm>,
+}
+
+impl WorldHazards {
+    /// Create a world with no storms
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All active storms
+    pub fn storms(&self) -> &[AnomalyStorm] {
+        &self.storms
+    }
+
+    /// The storm covering `position`, if any
+    pub fn storm_at(&self, position: Position3D) -> Option<&AnomalyStorm> {
+        self.storms.iter().find(|storm| storm.covers(position))
+    }
+
+    /// Check if `position` lies in the deep zone where storms form
+    pub fn is_deep_zone(position: Position3D) -> bool {
+        position.manhattan_distance_2d(&Position3D::origin()) >= STORM_DEEP_ZONE_DISTANCE
+    }
+
+    /// Movement conditions of a move into `position`
+    pub fn conditions_at(&self, position: Position3D) -> MovementConditions {
+        if self.storm_at(position).is_some() {
+            MovementConditions {
+                cost_multiplier: STORM_MOVEMENT_COST_MULTIPLIER,
+                disadvantage: true,
+                ..MovementConditions::default()
+            }
+        } else {
+            MovementConditions::default()
+        }
+    }
+
+    /// Movement cost of `position` given its terrain cost
+    pub fn movement_cost(&self, position: Position3D, base_cost: u8) -> u8 {
+        self.conditions_at(position).apply_cost(base_cost)
+    }
+
+    /// Hazard set off by a move into `position`; `roll` is a d100 result
+    pub fn storm_hazard(&self, position: Position3D, roll: u8) -> Option<Event> {
+        self.storm_at(position)?;
+        if roll > STORM_HAZARD_CHANCE {
+            return None;
+        }
+        Event::new(
+            EventType::Hazard,
+            "Anomaly Storm".to_string(),
+            "Warped lightning arcs across your path!".to_string(),
+            Some(position),
+        )
+        .ok()
+    }
+
+    /// Add a storm
+    pub fn add_storm(&mut self, storm: AnomalyStorm) {
+        self.storms.push(storm);
+    }
+
+    /// Roll for a new storm near a player in the deep zone
+    ///
+    /// Storms form on known, passable deep-zone tiles away from the player.
+    pub fn try_form_storm(
+        &mut self,
+        map: &Map,
+        player_position: Position3D,
+        rng: &mut impl Rng,
+    ) -> Option<&AnomalyStorm> {
+        if self.storms.len() >= STORM_MAX_ACTIVE
+            || !Self::is_deep_zone(player_position)
+            || rng.gen_range(1..=100) > STORM_SPAWN_CHANCE
+        {
+            return None;
+        }
+        let candidates: Vec<Position3D> = player_position
+            .positions_within_distance(STORM_SPAWN_MAX_DISTANCE)
+            .into_iter()
+            .filter(|position| {
+                position.manhattan_distance_2d(&player_position) >= STORM_SPAWN_MIN_DISTANCE
+                    && Self::is_deep_zone(*position)
+                    && map
+                        .get_tile(&TileCoordinate::from(*position))
+                        .is_some_and(|tile| tile.terrain_type.is_passable())
+            })
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        self.storms.push(AnomalyStorm {
+            center: candidates[rng.gen_range(0..candidates.len())],
+            heading: Direction::horizontal()[rng.gen_range(0..4)],
+            rests_remaining: rng.gen_range(STORM_MIN_LIFETIME_RESTS..=STORM_MAX_LIFETIME_RESTS),
+        });
+        self.storms.last()
+    }
+
+    /// Advance every storm by one rest
+    ///
+    /// Storms at the end of their life dissipate where they are; the others
+    /// drift and act on the resource nodes they sweep over.
+    pub fn tick_rest(&mut self, map: &mut Map, rng: &mut impl Rng) -> StormReport {
+        let mut report = StormReport::default();
+        let mut remaining = Vec::with_capacity(self.storms.len());
+        for mut storm in self.storms.drain(..) {
+            storm.rests_remaining = storm.rests_remaining.saturating_sub(1);
+            if storm.rests_remaining == 0 {
+                report.dissipated.push(storm.center);
+                continue;
+            }
+
+            for position in storm.drift(rng) {
+                let Some(node) = map.get_resource_node(&position) else {
+                    continue;
+                };
+                let change =
+                    NodeChange::roll(node.properties().resource_type, rng.gen_range(1..=100));
+                let (Some(change), Some(node)) = (change, map.get_resource_node_mut(&position))
+                else {
+                    continue;
+                };
+                match change {
+                    NodeChange::Depleted => node.deplete(),
+                    NodeChange::Transmuted => node.transmute(ResourceType::ExoticMatter),
+                }
+                report.node_changes.push((position, change));
+            }
+            report.moved += 1;
+            remaining.push(storm);
+        }
+        self.storms = remaining;
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{MapTile, ResourceNode};
+    use crate::domain::value_objects::resources::{
+        RegenerationRate, ResourceAccessibility, ResourceNodeProperties, ResourceRichness,
+    };
+    use crate::domain::value_objects::terrain::{Elevation, TerrainType};
+    use crate::domain::value_objects::EntityId;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn storm_at(center: Position3D, rests_remaining: u32) -> AnomalyStorm {
+        AnomalyStorm {
+            center,
+            heading: Direction::East,
+            rests_remaining,
+        }
+    }
+
+    fn plains_map(center: Position3D, radius: u32) -> Map {
+        let mut map = Map::new(EntityId::generate(), "Deep".to_string(), 7).unwrap();
+        for position in center.positions_within_distance(radius) {
+            map.set_tile(
+                TileCoordinate::from(position),
+                MapTile::new(TerrainType::Plains, Elevation::sea_level(), true),
+            );
+        }
+        map
+    }
+
+    fn node(resource_type: ResourceType) -> ResourceNode {
+        ResourceNode::new(
+            EntityId::generate(),
+            ResourceNodeProperties::new(
+                resource_type,
+                ResourceRichness::Average,
+                ResourceAccessibility::Easy,
+                RegenerationRate::None,
+            ),
+            20,
+            20,
+        )
+    }
+
+    #[test]
+    fn storms_drift_along_their_heading() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut storm = storm_at(Position3D::origin(), 100);
+        let mut east = 0;
+        let mut other = 0;
+        for _ in 0..1000 {
+            match storm.step_direction(&mut rng) {
+                Direction::East => east += 1,
+                _ => other += 1,
+            }
+        }
+        // 60% bias plus a quarter of the unbiased steps
+        assert!(east > 650 && east < 750, "east steps: {}", east);
+        assert_eq!(east + other, 1000);
+
+        for _ in 0..50 {
+            let before = storm.center;
+            let swept = storm.drift(&mut rng);
+            let moved = before.manhattan_distance_2d(&storm.center);
+            assert!(moved <= STORM_MAX_STEP);
+            assert!(swept
+                .iter()
+                .all(|position| !storm_at(before, 1).covers(*position)));
+        }
+        assert!(storm.center.x > 30, "storm ended at {:?}", storm.center);
+    }
+
+    #[test]
+    fn tiles_inside_a_storm_cost_double_and_roll_worse() {
+        let mut hazards = WorldHazards::new();
+        hazards.add_storm(storm_at(Position3D::new(30, 0, 0), 5));
+
+        let inside = Position3D::new(31, 1, 0);
+        let edge = Position3D::new(32, 0, 0);
+        let outside = Position3D::new(33, 0, 0);
+
+        assert_eq!(hazards.movement_cost(inside, 2), 4);
+        assert_eq!(hazards.movement_cost(edge, 3), 6);
+        assert_eq!(hazards.movement_cost(outside, 2), 2);
+        assert!(hazards.conditions_at(inside).disadvantage);
+        assert!(!hazards.conditions_at(outside).disadvantage);
+
+        let hazard = hazards.storm_hazard(inside, STORM_HAZARD_CHANCE).unwrap();
+        assert_eq!(hazard.event_type(), EventType::Hazard);
+        assert!(hazards
+            .storm_hazard(inside, STORM_HAZARD_CHANCE + 1)
+            .is_none());
+        assert!(hazards.storm_hazard(outside, 1).is_none());
+    }
+
+    #[test]
+    fn swept_nodes_transmute_or_deplete_by_roll() {
+        let count = |resource_type: ResourceType, change: NodeChange| {
+            (1..=100)
+                .filter(|&roll| NodeChange::roll(resource_type, roll) == Some(change))
+                .count()
+        };
+        assert_eq!(
+            count(ResourceType::Metal, NodeChange::Depleted),
+            STORM_DEPLETE_CHANCE as usize
+        );
+        assert_eq!(
+            count(ResourceType::Metal, NodeChange::Transmuted),
+            STORM_TRANSMUTE_CHANCE as usize
+        );
+        assert_eq!(
+            count(ResourceType::Energy, NodeChange::Depleted),
+            STORM_DEPLETE_CHANCE as usize
+        );
+        assert_eq!(count(ResourceType::Energy, NodeChange::Transmuted), 0);
+
+        // Applied to the map, changes match the report
+        let center = Position3D::new(30, 0, 0);
+        let mut map = plains_map(center, 12);
+        for position in center.positions_within_distance(10) {
+            map.add_resource_node(position, node(ResourceType::Metal));
+        }
+        let mut hazards = WorldHazards::new();
+        hazards.add_storm(storm_at(center, 10));
+        let report = hazards.tick_rest(&mut map, &mut StdRng::seed_from_u64(11));
+        for (position, change) in &report.node_changes {
+            let node = map.get_resource_node(position).unwrap();
+            match change {
+                NodeChange::Depleted => assert!(node.is_depleted()),
+                NodeChange::Transmuted => {
+                    assert_eq!(node.properties().resource_type, ResourceType::ExoticMatter)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn storms_dissipate_after_their_lifetime() {
+        let center = Position3D::new(40, 0, 0);
+        let mut map = plains_map(center, 30);
+        let mut hazards = WorldHazards::new();
+        hazards.add_storm(storm_at(center, 5));
+        hazards.add_storm(storm_at(center, 7));
+        let mut rng = StdRng::seed_from_u64(5);
+
+        for _ in 0..4 {
+            let report = hazards.tick_rest(&mut map, &mut rng);
+            assert!(report.dissipated.is_empty());
+        }
+        assert!(hazards.storms()[0].is_dissipating());
+        let report = hazards.tick_rest(&mut map, &mut rng);
+        assert_eq!(report.dissipated.len(), 1);
+        assert_eq!(report.moved, 1);
+        hazards.tick_rest(&mut map, &mut rng);
+        assert_eq!(hazards.tick_rest(&mut map, &mut rng).dissipated.len(), 1);
+        assert!(hazards.storms().is_empty());
+
+        // New storms live within the configured lifetime and only form deep
+        let mut formed = 0;
+        for _ in 0..2000 {
+            let mut hazards = WorldHazards::new();
+            assert!(hazards
+                .try_form_storm(&map, Position3D::origin(), &mut rng)
+                .is_none());
+            if let Some(storm) = hazards.try_form_storm(&map, center, &mut rng) {
+                formed += 1;
+                assert!((STORM_MIN_LIFETIME_RESTS..=STORM_MAX_LIFETIME_RESTS)
+                    .contains(&storm.rests_remaining));
+                assert!(WorldHazards::is_deep_zone(storm.center));
+            }
+        }
+        assert!(formed > 0);
+
+        // Storm state survives a save round trip
+        let mut hazards = WorldHazards::new();
+        hazards.add_storm(storm_at(center, 3));
+        let saved = serde_json::to_string(&hazards).unwrap();
+        assert_eq!(
+            serde_json::from_str::<WorldHazards>(&saved).unwrap(),
+            hazards
+        );
+    }
+}
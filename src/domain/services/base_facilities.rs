@@ -0,0 +1,88 @@
+//! Base Facilities - What the base's buildings do for the player
+//!
+//! Intact buildings lend the player their effect wherever they roam; a
+//! building damaged in a raid does nothing until it is repaired. Effects
+//! grow with the building's level: Resource Storage adds cargo room, the
+//! Laboratory's sensors see further into the fog, and Living Quarters make
+//! for better nights by raising the night roll.
+
+use crate::domain::constants::{
+    LABORATORY_LEVELS_PER_VIEW_TILE, QUARTERS_MAX_REST_ROLL_BONUS, STORAGE_CARGO_PER_LEVEL,
+};
+use crate::domain::entities::{Base, BuildingType};
+
+/// Bonuses the base's intact buildings grant
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FacilityBonuses {
+    /// Added to the carrying capacity
+    pub cargo_capacity: u32,
+    /// Added to the fogged view radius, in tiles
+    pub view_radius: u32,
+    /// Added to the night roll of a rest
+    pub rest_roll_bonus: u8,
+}
+
+impl FacilityBonuses {
+    /// Bonuses of the buildings of `base`
+    pub fn of(base: &Base) -> Self {
+        let level = |building_type: BuildingType| {
+            base.building(building_type)
+                .filter(|building| !building.damaged)
+                .map_or(0, |building| building.level)
+        };
+        Self {
+            cargo_capacity: level(BuildingType::ResourceStorage) as u32 * STORAGE_CARGO_PER_LEVEL,
+            view_radius: level(BuildingType::Laboratory).div_ceil(LABORATORY_LEVELS_PER_VIEW_TILE)
+                as u32,
+            rest_roll_bonus: level(BuildingType::LivingQuarters).min(QUARTERS_MAX_REST_ROLL_BONUS),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::BaseBuilding;
+    use crate::domain::value_objects::{EntityId, Position3D};
+
+    #[test]
+    fn intact_buildings_lend_their_effects_by_level() {
+        let mut base = Base::new(
+            EntityId::generate(),
+            "Home".to_string(),
+            Position3D::origin(),
+        )
+        .unwrap();
+        assert_eq!(FacilityBonuses::of(&base), FacilityBonuses::default());
+
+        for building_type in [
+            BuildingType::ResourceStorage,
+            BuildingType::Laboratory,
+            BuildingType::LivingQuarters,
+        ] {
+            base.add_building(BaseBuilding::new(
+                building_type,
+                building_type.name().to_string(),
+                (0, 0),
+            ));
+        }
+        for _ in 0..6 {
+            base.upgrade_building(BuildingType::LivingQuarters).unwrap();
+        }
+        for _ in 0..3 {
+            base.upgrade_building(BuildingType::Laboratory).unwrap();
+        }
+        assert_eq!(
+            FacilityBonuses::of(&base),
+            FacilityBonuses {
+                cargo_capacity: STORAGE_CARGO_PER_LEVEL,
+                view_radius: 2,
+                rest_roll_bonus: QUARTERS_MAX_REST_ROLL_BONUS,
+            }
+        );
+
+        // Raided buildings do nothing until repaired
+        base.set_building_damaged(BuildingType::ResourceStorage, true);
+        assert_eq!(FacilityBonuses::of(&base).cargo_capacity, 0);
+    }
+}
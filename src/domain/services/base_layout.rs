@@ -0,0 +1,169 @@
+//! Base Layout - Which tile of the base cluster shows which building
+//!
+//! The base is drawn as a small cluster: its own tile plus the four
+//! orthogonal neighbours. Constructed buildings take the neighbour tiles in
+//! construction order, so a building keeps its spot when others are added
+//! and when a save is loaded. Buildings that do not fit are stacked as
+//! indicators on the base tile itself.
+
+use crate::domain::constants::BUILDING_VISUAL_TIERS;
+use crate::domain::entities::{Base, BaseBuilding, BuildingType};
+use crate::domain::value_objects::Position3D;
+
+/// Neighbour tiles buildings are placed on, in assignment order
+pub const BASE_SLOT_OFFSETS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// Where a building is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildingSlot {
+    /// A neighbour tile of the base
+    Neighbor(Position3D),
+    /// An indicator on the base tile; 0 is the lowest
+    Stacked(usize),
+}
+
+/// Which model a building is drawn with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildingVariant {
+    /// Model tier, from 1 up to `BUILDING_VISUAL_TIERS`
+    Intact {
+        tier: u8,
+    },
+    Damaged,
+}
+
+impl BuildingVariant {
+    /// Variant for a building's current state
+    pub fn of(building: &BaseBuilding) -> Self {
+        if building.damaged {
+            BuildingVariant::Damaged
+        } else {
+            BuildingVariant::Intact {
+                tier: building.level.clamp(1, BUILDING_VISUAL_TIERS),
+            }
+        }
+    }
+}
+
+/// A building with its place in the cluster
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlacedBuilding {
+    pub building_type: BuildingType,
+    pub slot: BuildingSlot,
+    pub variant: BuildingVariant,
+}
+
+/// Place every constructed building of `base`
+///
+/// Ordering uses the construction time, then the building type, never the
+/// order buildings happen to be stored in.
+pub fn layout_base(base: &Base) -> Vec<PlacedBuilding> {
+    let origin = *base.position();
+    let mut buildings: Vec<&BaseBuilding> = base.buildings().iter().collect();
+    buildings.sort_by_key(|building| (building.constructed_at, building.building_type));
+
+    buildings
+        .into_iter()
+        .enumerate()
+        .map(|(index, building)| {
+            let slot = match BASE_SLOT_OFFSETS.get(index) {
+                Some(&(dx, dy)) => {
+                    BuildingSlot::Neighbor(Position3D::new(origin.x + dx, origin.y + dy, origin.z))
+                }
+                None => BuildingSlot::Stacked(index - BASE_SLOT_OFFSETS.len()),
+            };
+            PlacedBuilding {
+                building_type: building.building_type,
+                slot,
+                variant: BuildingVariant::of(building),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::EntityId;
+    use chrono::{Duration, Utc};
+
+    fn base_with(types: &[BuildingType]) -> Base {
+        let mut base = Base::new(
+            EntityId::generate(),
+            "Central Command".to_string(),
+            Position3D::new(5, 5, 0),
+        )
+        .unwrap();
+        let start = Utc::now();
+        for (minute, building_type) in types.iter().enumerate() {
+            let mut building =
+                BaseBuilding::new(*building_type, format!("{:?}", building_type), (0, 0));
+            building.constructed_at = start + Duration::minutes(minute as i64);
+            base.add_building(building);
+        }
+        base
+    }
+
+    #[test]
+    fn slots_follow_construction_order_and_overflow_stacks() {
+        let base = base_with(&[
+            BuildingType::Refinery,
+            BuildingType::Workshop,
+            BuildingType::PowerPlant,
+            BuildingType::Laboratory,
+            BuildingType::LivingQuarters,
+            BuildingType::DefenseSystem,
+        ]);
+
+        let layout = layout_base(&base);
+
+        assert_eq!(layout[0].building_type, BuildingType::Refinery);
+        assert_eq!(
+            layout[0].slot,
+            BuildingSlot::Neighbor(Position3D::new(6, 5, 0))
+        );
+        assert_eq!(
+            layout[3].slot,
+            BuildingSlot::Neighbor(Position3D::new(5, 4, 0))
+        );
+        assert_eq!(layout[4].slot, BuildingSlot::Stacked(0));
+        assert_eq!(layout[5].slot, BuildingSlot::Stacked(1));
+    }
+
+    #[test]
+    fn new_buildings_do_not_move_existing_ones() {
+        let before = layout_base(&base_with(&[
+            BuildingType::Refinery,
+            BuildingType::Workshop,
+        ]));
+        let after = layout_base(&base_with(&[
+            BuildingType::Refinery,
+            BuildingType::Workshop,
+            BuildingType::Laboratory,
+        ]));
+
+        assert_eq!(before[..], after[..2]);
+    }
+
+    #[test]
+    fn damaged_buildings_use_the_damaged_variant() {
+        let mut building =
+            BaseBuilding::new(BuildingType::Workshop, "Workshop".to_string(), (0, 0));
+        building.level = 2;
+        assert_eq!(
+            BuildingVariant::of(&building),
+            BuildingVariant::Intact { tier: 2 }
+        );
+
+        building.level = BUILDING_VISUAL_TIERS + 4;
+        assert_eq!(
+            BuildingVariant::of(&building),
+            BuildingVariant::Intact {
+                tier: BUILDING_VISUAL_TIERS
+            }
+        );
+
+        building.damaged = true;
+        assert_eq!(BuildingVariant::of(&building), BuildingVariant::Damaged);
+    }
+}
@@ -0,0 +1,181 @@
+//! Base Automation Report - What the base did while the player rested
+//!
+//! Every night, the systems that run the base add a line item for each
+//! building that acted: what it did, what it used and made, and anything
+//! that went wrong. Items are kept in building slot order whatever order
+//! they were added in, so the same night always reads the same way. The
+//! report collapses into a single digest line for the game log; a night in
+//! which nothing happened, or with no buildings at all, reads "Base quiet".
+
+use crate::domain::entities::BaseBuilding;
+use crate::domain::value_objects::ResourceType;
+
+/// One building's part in a night's report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaseReportItem {
+    /// Building slot in the base layout, which orders the report
+    pub slot: (i32, i32),
+    pub building: String,
+    /// What the building did, e.g. `produced`
+    pub action: String,
+    pub resources_in: Vec<(ResourceType, u32)>,
+    pub resources_out: Vec<(ResourceType, u32)>,
+    /// Things that went wrong, e.g. lost output
+    pub incidents: Vec<String>,
+}
+
+impl BaseReportItem {
+    /// An item for `building` doing `action`
+    pub fn new(building: &BaseBuilding, action: impl Into<String>) -> Self {
+        Self {
+            slot: building.position_in_base,
+            building: building.name.clone(),
+            action: action.into(),
+            resources_in: Vec::new(),
+            resources_out: Vec::new(),
+            incidents: Vec::new(),
+        }
+    }
+
+    /// Note resources the building used
+    pub fn with_input(mut self, resource_type: ResourceType, amount: u32) -> Self {
+        self.resources_in.push((resource_type, amount));
+        self
+    }
+
+    /// Note resources the building made
+    pub fn with_output(mut self, resource_type: ResourceType, amount: u32) -> Self {
+        self.resources_out.push((resource_type, amount));
+        self
+    }
+
+    /// Note something that went wrong
+    pub fn with_incident(mut self, incident: impl Into<String>) -> Self {
+        self.incidents.push(incident.into());
+        self
+    }
+
+    /// The item as one line, e.g. `Refinery produced 10 Alloys`
+    pub fn line(&self) -> String {
+        let amounts = |amounts: &[(ResourceType, u32)]| {
+            amounts
+                .iter()
+                .map(|(resource_type, amount)| format!("{} {}", amount, resource_type))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut line = format!("{} {}", self.building, self.action);
+        if !self.resources_out.is_empty() {
+            line.push_str(&format!(" {}", amounts(&self.resources_out)));
+        }
+        if !self.resources_in.is_empty() {
+            line.push_str(&format!(" (used {})", amounts(&self.resources_in)));
+        }
+        for incident in &self.incidents {
+            line.push_str(&format!(" - {}", incident));
+        }
+        line
+    }
+}
+
+/// Everything the base did over one night
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BaseAutomationReport {
+    items: Vec<BaseReportItem>,
+}
+
+impl BaseAutomationReport {
+    /// An empty report for a new night
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an item, keeping the items in building slot order
+    ///
+    /// Items of the same slot stay in the order they were added.
+    pub fn add(&mut self, item: BaseReportItem) {
+        let index = self.items.partition_point(|other| other.slot <= item.slot);
+        self.items.insert(index, item);
+    }
+
+    /// Items in building slot order
+    pub fn items(&self) -> &[BaseReportItem] {
+        &self.items
+    }
+
+    /// Check if no building did anything
+    pub fn is_quiet(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// One line per item, or "Base quiet"
+    pub fn lines(&self) -> Vec<String> {
+        if self.is_quiet() {
+            return vec!["Base quiet".to_string()];
+        }
+        self.items.iter().map(BaseReportItem::line).collect()
+    }
+
+    /// The whole report as a single log entry
+    pub fn digest(&self) -> String {
+        format!("Base report: {}", self.lines().join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::BuildingType;
+
+    fn building(name: &str, slot: (i32, i32)) -> BaseBuilding {
+        BaseBuilding::new(BuildingType::Workshop, name.to_string(), slot)
+    }
+
+    #[test]
+    fn items_follow_building_slots() {
+        let mut report = BaseAutomationReport::new();
+        report.add(BaseReportItem::new(&building("Refinery", (1, 0)), "idled"));
+        report.add(BaseReportItem::new(&building("Storage", (0, 0)), "sorted"));
+        report.add(BaseReportItem::new(&building("Refinery", (1, 0)), "cooled"));
+        report.add(BaseReportItem::new(
+            &building("Defense", (0, 1)),
+            "stood by",
+        ));
+
+        let order: Vec<&str> = report
+            .items()
+            .iter()
+            .map(|item| item.action.as_str())
+            .collect();
+        assert_eq!(order, ["sorted", "stood by", "idled", "cooled"]);
+    }
+
+    #[test]
+    fn a_night_without_buildings_is_quiet() {
+        let report = BaseAutomationReport::new();
+        assert!(report.is_quiet());
+        assert_eq!(report.lines(), ["Base quiet"]);
+        assert_eq!(report.digest(), "Base report: Base quiet");
+    }
+
+    #[test]
+    fn digest_joins_every_line() {
+        let mut report = BaseAutomationReport::new();
+        report.add(
+            BaseReportItem::new(&building("Refinery", (1, 0)), "produced")
+                .with_output(ResourceType::Alloys, 10)
+                .with_incident("storage full, 5 Alloys lost"),
+        );
+        report.add(
+            BaseReportItem::new(&building("Workshop", (0, 0)), "repaired")
+                .with_input(ResourceType::Metal, 3)
+                .with_input(ResourceType::Energy, 1),
+        );
+
+        assert_eq!(
+            report.digest(),
+            "Base report: Workshop repaired (used 3 Metal, 1 Energy); \
+             Refinery produced 10 Alloys - storage full, 5 Alloys lost"
+        );
+    }
+}
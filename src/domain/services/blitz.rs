@@ -0,0 +1,194 @@
+//! Blitz Mode - A real-time countdown on every exploration decision
+//!
+//! In a blitz run each decision gets a fixed number of seconds. The clock
+//! only runs while the player could actually act: animations, rests,
+//! encounters and menus hold it. Closing a modal hands back a couple of
+//! bonus seconds so reading it is not punished. When the clock runs out the
+//! autopilot takes one step towards the cheapest unexplored tile.
+
+use crate::domain::constants::BLITZ_MODAL_BONUS_SECS;
+use crate::domain::entities::Map;
+use crate::domain::services::PathfindingService;
+use crate::domain::value_objects::position::{Direction, Position3D};
+
+/// Why the countdown is holding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlitzPause {
+    /// A move is still animating
+    Animating,
+    /// Out of movement points; a rest is due
+    Resting,
+    /// A combat or event is being resolved
+    Encounter,
+    /// A menu or confirmation panel is open
+    Menu,
+}
+
+impl BlitzPause {
+    /// Check if the pause shows a modal the player has to read
+    pub fn is_modal(&self) -> bool {
+        matches!(self, BlitzPause::Encounter | BlitzPause::Menu)
+    }
+}
+
+/// Countdown for the current decision
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlitzClock {
+    limit: f32,
+    remaining: f32,
+    held_by_modal: bool,
+}
+
+impl BlitzClock {
+    /// Create a clock allowing `limit` seconds per decision
+    pub fn new(limit: f32) -> Self {
+        Self {
+            limit,
+            remaining: limit,
+            held_by_modal: false,
+        }
+    }
+
+    /// Seconds allowed per decision
+    pub fn limit(&self) -> f32 {
+        self.limit
+    }
+
+    /// Seconds left for the current decision
+    pub fn remaining(&self) -> f32 {
+        self.remaining
+    }
+
+    /// Check if the current decision has run out of time
+    pub fn is_expired(&self) -> bool {
+        self.remaining <= 0.0
+    }
+
+    /// Advance the clock, returning true on the tick it runs out
+    ///
+    /// While `pause` is set the clock holds. Leaving a modal pause grants
+    /// [`BLITZ_MODAL_BONUS_SECS`] before counting resumes.
+    pub fn tick(&mut self, delta_secs: f32, pause: Option<BlitzPause>) -> bool {
+        if let Some(pause) = pause {
+            self.held_by_modal |= pause.is_modal();
+            return false;
+        }
+        if self.held_by_modal {
+            self.held_by_modal = false;
+            self.grant_bonus(BLITZ_MODAL_BONUS_SECS);
+        }
+        if self.is_expired() {
+            return false;
+        }
+        self.remaining = (self.remaining - delta_secs).max(0.0);
+        self.is_expired()
+    }
+
+    /// Add seconds to the current decision
+    pub fn grant_bonus(&mut self, seconds: f32) {
+        self.remaining += seconds;
+    }
+
+    /// Start a fresh decision
+    pub fn reset(&mut self) {
+        self.remaining = self.limit;
+        self.held_by_modal = false;
+    }
+}
+
+/// Direction the autopilot steps in from `from`
+///
+/// Follows the cheapest route to the nearest unexplored tile; `None` when
+/// nothing reachable is left to explore.
+pub fn autopilot_direction(
+    pathfinding: &PathfindingService,
+    map: &Map,
+    from: Position3D,
+) -> Option<Direction> {
+    let route = pathfinding.nearest_unexplored(map, from)?;
+    let first = *route.steps.first()?;
+    match (first.x - from.x, first.y - from.y) {
+        (0, 1) => Some(Direction::North),
+        (0, -1) => Some(Direction::South),
+        (1, 0) => Some(Direction::East),
+        (-1, 0) => Some(Direction::West),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::MapTile;
+    use crate::domain::value_objects::terrain::{Elevation, TerrainType};
+    use crate::domain::value_objects::{EntityId, TileCoordinate};
+
+    #[test]
+    fn countdown_holds_while_paused() {
+        let mut clock = BlitzClock::new(10.0);
+
+        assert!(!clock.tick(4.0, None));
+        assert_eq!(clock.remaining(), 6.0);
+
+        for pause in [BlitzPause::Animating, BlitzPause::Resting] {
+            assert!(!clock.tick(30.0, Some(pause)));
+        }
+        assert_eq!(clock.remaining(), 6.0);
+
+        assert!(clock.tick(6.5, None));
+        assert!(clock.is_expired());
+        // Expiry is reported once
+        assert!(!clock.tick(1.0, None));
+
+        clock.reset();
+        assert_eq!(clock.remaining(), 10.0);
+    }
+
+    #[test]
+    fn closing_a_modal_grants_bonus_time_once() {
+        let mut clock = BlitzClock::new(10.0);
+        clock.tick(9.0, None);
+
+        clock.tick(5.0, Some(BlitzPause::Encounter));
+        clock.tick(5.0, Some(BlitzPause::Menu));
+        clock.tick(0.0, None);
+        assert_eq!(clock.remaining(), 1.0 + BLITZ_MODAL_BONUS_SECS);
+
+        // Animations are not modals
+        clock.tick(1.0, Some(BlitzPause::Animating));
+        clock.tick(0.0, None);
+        assert_eq!(clock.remaining(), 1.0 + BLITZ_MODAL_BONUS_SECS);
+    }
+
+    #[test]
+    fn autopilot_heads_for_the_cheapest_unexplored_tile() {
+        let mut map = Map::new(EntityId::generate(), "Test".to_string(), 1).unwrap();
+        for x in -2..=2 {
+            let terrain = if x < 0 {
+                TerrainType::Mountains
+            } else {
+                TerrainType::Plains
+            };
+            map.set_tile(
+                TileCoordinate::new(x, 0, 0),
+                MapTile::new(terrain, Elevation::sea_level(), x.abs() < 2),
+            );
+        }
+        let pathfinding = PathfindingService::new();
+
+        assert_eq!(
+            autopilot_direction(&pathfinding, &map, Position3D::new(0, 0, 0)),
+            Some(Direction::East)
+        );
+
+        let mut explored = Map::new(EntityId::generate(), "Test".to_string(), 1).unwrap();
+        explored.set_tile(
+            TileCoordinate::new(0, 0, 0),
+            MapTile::new(TerrainType::Plains, Elevation::sea_level(), true),
+        );
+        assert_eq!(
+            autopilot_direction(&pathfinding, &explored, Position3D::new(0, 0, 0)),
+            None
+        );
+    }
+}
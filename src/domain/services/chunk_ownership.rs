@@ -0,0 +1,446 @@
+//! Chunk Ownership - Which chunk generates which tile
+//!
+//! Every tile belongs to exactly one chunk, the one its coordinates
+//! floor-divide into, and only that chunk's generation pass writes the
+//! tile or anything standing on it. Features covering several tiles belong
+//! to the chunk holding their anchor. Features are smaller than a chunk, so
+//! any feature that could overlap another is anchored in one of the chunks
+//! around its footprint; a feature waits until all of those are generated,
+//! and of two overlapping features the one with the lower anchor wins.
+//! Generating neighbours in any order therefore settles on the same
+//! features.
+//!
+//! A validation pass looks for duplicate node ids and overlapping
+//! footprints, which ownership should make impossible; debug builds run it
+//! after each chunk and log what it finds.
+
+use crate::domain::entities::map::Map;
+use crate::domain::services::tile_staleness::chunk_of;
+use crate::domain::value_objects::position::ChunkCoordinate;
+use crate::domain::value_objects::{EntityId, Position3D, TileCoordinate};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Chunk owning the tile at `position`
+pub fn owning_chunk(position: Position3D) -> ChunkCoordinate {
+    chunk_of(TileCoordinate::from(position))
+}
+
+/// Tiles covered by a multi-tile feature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Footprint {
+    /// Tile deciding which chunk owns the feature
+    pub anchor: Position3D,
+    /// Every tile covered, the anchor included
+    pub tiles: Vec<Position3D>,
+}
+
+impl Footprint {
+    /// Footprint of the given tiles, anchored at `anchor`
+    pub fn new(anchor: Position3D, tiles: impl IntoIterator<Item = Position3D>) -> Self {
+        let mut tiles: Vec<Position3D> = tiles.into_iter().collect();
+        if !tiles.contains(&anchor) {
+            tiles.push(anchor);
+        }
+        Self { anchor, tiles }
+    }
+
+    /// Rectangle of `width` by `height` tiles with the anchor in its corner
+    pub fn rect(anchor: Position3D, width: i32, height: i32) -> Self {
+        Self::new(
+            anchor,
+            (0..width).flat_map(|x| (0..height).map(move |y| anchor.offset(x, y, 0))),
+        )
+    }
+
+    /// Chunk owning the feature
+    pub fn owner(&self) -> ChunkCoordinate {
+        owning_chunk(self.anchor)
+    }
+
+    /// Chunks the footprint covers
+    pub fn chunks(&self) -> HashSet<ChunkCoordinate> {
+        self.tiles.iter().map(|tile| owning_chunk(*tile)).collect()
+    }
+
+    /// Chunks that may anchor a feature overlapping this one: the chunks
+    /// covered and their neighbours on the same level
+    pub fn rival_chunks(&self) -> HashSet<ChunkCoordinate> {
+        self.chunks()
+            .into_iter()
+            .flat_map(|chunk| {
+                (-1..=1).flat_map(move |dx| {
+                    (-1..=1)
+                        .map(move |dy| ChunkCoordinate::new(chunk.x + dx, chunk.y + dy, chunk.z))
+                })
+            })
+            .collect()
+    }
+
+    /// Order deciding overlaps: the lower anchor wins, then the lower id
+    fn rank(&self, id: EntityId) -> (i32, i32, i32, u64) {
+        (self.anchor.x, self.anchor.y, self.anchor.z, id.0)
+    }
+}
+
+/// What became of a proposed feature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    /// The feature now holds its tiles
+    Placed,
+    /// Waiting for a chunk around it to be generated, or for a rival with a
+    /// lower anchor to be decided
+    Deferred,
+    /// A rival with a lower anchor holds one of its tiles
+    Blocked,
+    /// The anchor belongs to another chunk, which places it instead
+    NotOwned,
+}
+
+/// Chunks generated so far and the features proposed in them
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChunkFeatures {
+    generated: HashSet<ChunkCoordinate>,
+    proposed: HashMap<EntityId, Footprint>,
+    /// Proposed features covering each tile
+    covering: HashMap<Position3D, Vec<EntityId>>,
+    claims: HashMap<Position3D, EntityId>,
+    placed: HashSet<EntityId>,
+    blocked: HashSet<EntityId>,
+    deferred: Vec<EntityId>,
+}
+
+impl ChunkFeatures {
+    /// Create an empty ledger
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if a chunk has been generated
+    pub fn is_generated(&self, chunk: ChunkCoordinate) -> bool {
+        self.generated.contains(&chunk)
+    }
+
+    /// Record a chunk as generated, once its features are proposed; returns
+    /// the deferred features this let through
+    pub fn mark_generated(&mut self, chunk: ChunkCoordinate) -> Vec<EntityId> {
+        self.generated.insert(chunk);
+        let mut placed = Vec::new();
+        loop {
+            let mut waiting = std::mem::take(&mut self.deferred);
+            waiting.sort_by_key(|id| self.proposed[id].rank(*id));
+            let before = waiting.len();
+            for id in waiting {
+                match self.decide(id) {
+                    Placement::Placed => placed.push(id),
+                    Placement::Deferred => self.deferred.push(id),
+                    Placement::Blocked | Placement::NotOwned => {}
+                }
+            }
+            if self.deferred.len() == before {
+                return placed;
+            }
+        }
+    }
+
+    /// Propose a feature while generating `chunk`, before the chunk is
+    /// marked generated
+    pub fn propose(
+        &mut self,
+        chunk: ChunkCoordinate,
+        id: EntityId,
+        footprint: Footprint,
+    ) -> Placement {
+        if footprint.owner() != chunk {
+            return Placement::NotOwned;
+        }
+        for tile in &footprint.tiles {
+            self.covering.entry(*tile).or_default().push(id);
+        }
+        self.proposed.insert(id, footprint);
+        let placement = self.decide(id);
+        if placement == Placement::Deferred {
+            self.deferred.push(id);
+        }
+        placement
+    }
+
+    fn decide(&mut self, id: EntityId) -> Placement {
+        let footprint = &self.proposed[&id];
+        if footprint
+            .rival_chunks()
+            .into_iter()
+            .any(|chunk| !self.is_generated(chunk))
+        {
+            return Placement::Deferred;
+        }
+        let rank = footprint.rank(id);
+        let mut waiting = false;
+        for rival in footprint
+            .tiles
+            .iter()
+            .filter_map(|tile| self.covering.get(tile))
+            .flatten()
+        {
+            if *rival == id || self.proposed[rival].rank(*rival) > rank {
+                continue;
+            }
+            if self.placed.contains(rival) {
+                self.blocked.insert(id);
+                return Placement::Blocked;
+            }
+            waiting |= !self.blocked.contains(rival);
+        }
+        if waiting {
+            return Placement::Deferred;
+        }
+        for tile in &footprint.tiles {
+            self.claims.insert(*tile, id);
+        }
+        self.placed.insert(id);
+        Placement::Placed
+    }
+
+    /// Feature holding a tile
+    pub fn feature_at(&self, position: Position3D) -> Option<EntityId> {
+        self.claims.get(&position).copied()
+    }
+
+    /// Footprint of a placed feature
+    pub fn footprint(&self, id: EntityId) -> Option<&Footprint> {
+        self.placed.get(&id).map(|_| &self.proposed[&id])
+    }
+
+    /// Placed features
+    pub fn features(&self) -> impl Iterator<Item = (EntityId, &Footprint)> {
+        self.placed.iter().map(|id| (*id, &self.proposed[id]))
+    }
+
+    /// Number of features still waiting on a neighbour or a rival
+    pub fn deferred_count(&self) -> usize {
+        self.deferred.len()
+    }
+}
+
+/// Something generation should never have produced
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerationIssue {
+    /// The same node id stands on more than one tile
+    DuplicateNodeId {
+        id: EntityId,
+        positions: Vec<Position3D>,
+    },
+    /// Two features cover the same tile
+    OverlappingFeatures {
+        position: Position3D,
+        first: EntityId,
+        second: EntityId,
+    },
+}
+
+impl fmt::Display for GenerationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GenerationIssue::DuplicateNodeId { id, positions } => {
+                write!(f, "node {} placed {} times", id.0, positions.len())
+            }
+            GenerationIssue::OverlappingFeatures {
+                position,
+                first,
+                second,
+            } => write!(
+                f,
+                "features {} and {} overlap at ({}, {}, {})",
+                first.0, second.0, position.x, position.y, position.z
+            ),
+        }
+    }
+}
+
+/// Scan the map's nodes and the given footprints for duplicates and overlaps
+pub fn validate_generation<'a>(
+    map: &Map,
+    features: impl IntoIterator<Item = (EntityId, &'a Footprint)>,
+) -> Vec<GenerationIssue> {
+    let mut issues = Vec::new();
+
+    let mut nodes: HashMap<EntityId, Vec<Position3D>> = HashMap::new();
+    for (position, node) in map.resource_nodes() {
+        nodes.entry(*node.id()).or_default().push(*position);
+    }
+    for (id, mut positions) in nodes {
+        if positions.len() > 1 {
+            positions.sort_by_key(|position| (position.x, position.y, position.z));
+            issues.push(GenerationIssue::DuplicateNodeId { id, positions });
+        }
+    }
+
+    let mut claims: HashMap<Position3D, EntityId> = HashMap::new();
+    for (id, footprint) in features {
+        for tile in &footprint.tiles {
+            match claims.get(tile) {
+                Some(first) if *first != id => {
+                    issues.push(GenerationIssue::OverlappingFeatures {
+                        position: *tile,
+                        first: *first,
+                        second: id,
+                    });
+                }
+                _ => {
+                    claims.insert(*tile, id);
+                }
+            }
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::constants::MAP_CHUNK_SIZE;
+
+    const SIZE: i32 = MAP_CHUNK_SIZE as i32;
+
+    #[test]
+    fn every_tile_has_one_owner_by_floor_division() {
+        assert_eq!(
+            owning_chunk(Position3D::new(0, 0, 0)),
+            ChunkCoordinate::origin()
+        );
+        assert_eq!(
+            owning_chunk(Position3D::new(SIZE - 1, SIZE - 1, 0)),
+            ChunkCoordinate::origin()
+        );
+        assert_eq!(
+            owning_chunk(Position3D::new(SIZE, 0, 0)),
+            ChunkCoordinate::new(1, 0, 0)
+        );
+        // Negative tiles round down, not towards zero
+        assert_eq!(
+            owning_chunk(Position3D::new(-1, -SIZE, 0)),
+            ChunkCoordinate::new(-1, -1, 0)
+        );
+    }
+
+    /// Generate `chunk`, proposing the features it owns
+    fn generate(
+        features: &mut ChunkFeatures,
+        chunk: ChunkCoordinate,
+        all: &[(EntityId, Footprint)],
+    ) {
+        for (id, footprint) in all {
+            if footprint.owner() == chunk {
+                assert_eq!(
+                    features.propose(chunk, *id, footprint.clone()),
+                    Placement::Deferred
+                );
+            }
+        }
+        features.mark_generated(chunk);
+    }
+
+    #[test]
+    fn straddling_feature_waits_for_its_neighbour_in_either_order() {
+        let west = ChunkCoordinate::new(0, 0, 0);
+        let east = ChunkCoordinate::new(1, 0, 0);
+        // The ruin is anchored in the west chunk and reaches over the border
+        // onto the camp, which the east chunk anchors
+        let ruin = Footprint::rect(Position3D::new(SIZE - 2, 4, 0), 4, 2);
+        let camp = Footprint::rect(Position3D::new(SIZE, 4, 0), 2, 2);
+        let (ruin_id, camp_id) = (EntityId::new(1), EntityId::new(2));
+        let all = [(ruin_id, ruin.clone()), (camp_id, camp.clone())];
+        let surrounding: Vec<ChunkCoordinate> = (-1..=2)
+            .flat_map(|x| (-1..=1).map(move |y| ChunkCoordinate::new(x, y, 0)))
+            .filter(|chunk| *chunk != west && *chunk != east)
+            .collect();
+
+        let mut settled = Vec::new();
+        for order in [[west, east], [east, west]] {
+            let mut features = ChunkFeatures::new();
+            for chunk in order {
+                generate(&mut features, chunk, &all);
+            }
+            // Nothing is decided while a chunk around them is missing
+            assert_eq!(features.deferred_count(), 2);
+            assert_eq!(features.feature_at(Position3D::new(SIZE, 4, 0)), None);
+            for chunk in &surrounding {
+                generate(&mut features, *chunk, &all);
+            }
+            settled.push(features);
+        }
+
+        for features in &settled {
+            assert_eq!(features.deferred_count(), 0);
+            assert_eq!(features.footprint(ruin_id), Some(&ruin));
+            assert_eq!(features.footprint(camp_id), None);
+            assert_eq!(
+                features.feature_at(Position3D::new(SIZE + 1, 5, 0)),
+                Some(ruin_id)
+            );
+        }
+        let placed = |features: &ChunkFeatures| {
+            let mut placed: Vec<(EntityId, Footprint)> = features
+                .features()
+                .map(|(id, footprint)| (id, footprint.clone()))
+                .collect();
+            placed.sort_by_key(|(id, _)| id.0);
+            placed
+        };
+        assert_eq!(placed(&settled[0]), placed(&settled[1]));
+    }
+
+    #[test]
+    fn a_blocked_rival_frees_the_tiles_it_would_have_taken() {
+        // first blocks second, so third, which only overlaps second, is placed
+        let first = Footprint::rect(Position3D::new(2, 2, 0), 2, 1);
+        let second = Footprint::rect(Position3D::new(3, 2, 0), 2, 1);
+        let third = Footprint::rect(Position3D::new(4, 2, 0), 2, 1);
+        let all = [
+            (EntityId::new(3), third),
+            (EntityId::new(2), second),
+            (EntityId::new(1), first),
+        ];
+
+        let mut features = ChunkFeatures::new();
+        generate(&mut features, ChunkCoordinate::origin(), &all);
+        for x in -1..=1 {
+            for y in -1..=1 {
+                if (x, y) != (0, 0) {
+                    generate(&mut features, ChunkCoordinate::new(x, y, 0), &all);
+                }
+            }
+        }
+
+        assert_eq!(features.deferred_count(), 0);
+        let mut placed: Vec<EntityId> = features.features().map(|(id, _)| id).collect();
+        placed.sort_by_key(|id| id.0);
+        assert_eq!(placed, vec![EntityId::new(1), EntityId::new(3)]);
+    }
+
+    #[test]
+    fn validation_reports_duplicate_nodes_and_overlaps() {
+        let map = Map::new(EntityId::new(9), "Checked".to_string(), 7).unwrap();
+        let first = Footprint::rect(Position3D::new(0, 0, 0), 2, 2);
+        let second = Footprint::rect(Position3D::new(1, 1, 0), 2, 2);
+        let apart = Footprint::rect(Position3D::new(5, 5, 0), 1, 1);
+
+        assert!(validate_generation(&map, [(EntityId::new(1), &first)]).is_empty());
+        let issues = validate_generation(
+            &map,
+            [
+                (EntityId::new(1), &first),
+                (EntityId::new(2), &second),
+                (EntityId::new(3), &apart),
+            ],
+        );
+        assert_eq!(
+            issues,
+            vec![GenerationIssue::OverlappingFeatures {
+                position: Position3D::new(1, 1, 0),
+                first: EntityId::new(1),
+                second: EntityId::new(2),
+            }]
+        );
+    }
+}
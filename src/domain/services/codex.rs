@@ -0,0 +1,296 @@
+//! Codex - Encyclopedia entries unlocked by meeting things in play
+//!
+//! Every terrain type, event type, point of interest, item kind and faction
+//! has an entry with a title, flavor text and gameplay notes. Entries are
+//! keyed by ids such as `terrain/Plains` or `event/Combat` and start
+//! locked; meeting the thing unlocks its entry for good, across runs. The
+//! entries come from a data file, which is refused if any terrain or event
+//! type is missing from it.
+
+use crate::domain::entities::EventType;
+use crate::domain::services::{ConsumableKind, Faction, GearSlot};
+use crate::domain::value_objects::terrain::TerrainType;
+use crate::domain::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+
+/// The sections of the codex
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CodexCategory {
+    Terrain,
+    Event,
+    PointOfInterest,
+    Item,
+    Faction,
+}
+
+impl CodexCategory {
+    /// Every category, in display order
+    pub fn all() -> [CodexCategory; 5] {
+        [
+            CodexCategory::Terrain,
+            CodexCategory::Event,
+            CodexCategory::PointOfInterest,
+            CodexCategory::Item,
+            CodexCategory::Faction,
+        ]
+    }
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            CodexCategory::Terrain => "Terrain",
+            CodexCategory::Event => "Events",
+            CodexCategory::PointOfInterest => "Points of Interest",
+            CodexCategory::Item => "Items",
+            CodexCategory::Faction => "Factions",
+        }
+    }
+}
+
+/// Points of interest with a codex entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointOfInterest {
+    /// Ruins that can be delved into
+    Ruins,
+    /// An untapped resource node
+    Deposit,
+    /// The faction trade board at the base
+    Spaceport,
+}
+
+/// Id of the entry for a terrain type
+pub fn terrain_entry_id(terrain: TerrainType) -> String {
+    format!("terrain/{:?}", terrain)
+}
+
+/// Id of the entry for an event type
+pub fn event_entry_id(event_type: EventType) -> String {
+    format!("event/{:?}", event_type)
+}
+
+/// Id of the entry for a point of interest
+pub fn poi_entry_id(poi: PointOfInterest) -> String {
+    format!("poi/{:?}", poi)
+}
+
+/// Id of the entry for gear worn in a slot
+pub fn gear_entry_id(slot: GearSlot) -> String {
+    format!("item/{:?}", slot)
+}
+
+/// Id of the entry for a consumable
+pub fn consumable_entry_id(kind: ConsumableKind) -> String {
+    format!("item/{:?}", kind)
+}
+
+/// Id of the entry for a faction
+pub fn faction_entry_id(faction: Faction) -> String {
+    format!("faction/{:?}", faction)
+}
+
+/// One encyclopedia entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodexEntry {
+    pub id: String,
+    pub category: CodexCategory,
+    pub title: String,
+    pub flavor: String,
+    /// How the thing matters in play
+    pub notes: String,
+    /// Shown in place of the entry while it is locked
+    pub hint: String,
+}
+
+/// Every codex entry, in data file order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Codex {
+    entries: Vec<CodexEntry>,
+}
+
+impl Codex {
+    /// Load the codex from its JSON data file
+    ///
+    /// Fails on duplicate ids and when a terrain or event type has no entry,
+    /// so new content cannot ship undocumented.
+    pub fn parse(json: &str) -> DomainResult<Self> {
+        let codex: Codex = serde_json::from_str(json)
+            .map_err(|e| DomainError::ConfigurationError(format!("invalid codex data: {}", e)))?;
+        codex.validated()
+    }
+
+    /// This codex with `entries` replacing those with the same id, or added
+    /// after them, checked like the data file
+    pub fn with_overrides(
+        &self,
+        entries: impl IntoIterator<Item = CodexEntry>,
+    ) -> DomainResult<Self> {
+        let mut codex = self.clone();
+        for entry in entries {
+            match codex
+                .entries
+                .iter_mut()
+                .find(|existing| existing.id == entry.id)
+            {
+                Some(existing) => *existing = entry,
+                None => codex.entries.push(entry),
+            }
+        }
+        codex.validated()
+    }
+
+    fn validated(self) -> DomainResult<Self> {
+        let mut ids = HashSet::new();
+        for entry in &self.entries {
+            if !ids.insert(entry.id.as_str()) {
+                return Err(DomainError::ConfigurationError(format!(
+                    "duplicate codex entry {}",
+                    entry.id
+                )));
+            }
+        }
+
+        let required = TerrainType::all()
+            .into_iter()
+            .map(terrain_entry_id)
+            .chain(EventType::all().into_iter().map(event_entry_id));
+        let missing: Vec<String> = required.filter(|id| !ids.contains(id.as_str())).collect();
+        if !missing.is_empty() {
+            return Err(DomainError::ConfigurationError(format!(
+                "codex entries missing for {}",
+                missing.join(", ")
+            )));
+        }
+        Ok(self)
+    }
+
+    /// Every entry
+    pub fn entries(&self) -> &[CodexEntry] {
+        &self.entries
+    }
+
+    /// Entry with `id`, if there is one
+    pub fn entry(&self, id: &str) -> Option<&CodexEntry> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    /// Entries of one category, in data file order
+    pub fn in_category(&self, category: CodexCategory) -> impl Iterator<Item = &CodexEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.category == category)
+    }
+}
+
+/// Codex entries the player has unlocked, kept across runs
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CodexUnlocks {
+    unlocked: BTreeSet<String>,
+}
+
+impl CodexUnlocks {
+    /// Unlock an entry; false if it was unlocked already
+    pub fn unlock(&mut self, id: &str) -> bool {
+        if self.unlocked.contains(id) {
+            return false;
+        }
+        self.unlocked.insert(id.to_string())
+    }
+
+    /// Check if an entry is unlocked
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.contains(id)
+    }
+
+    /// Unlocked entries of one category, and how many it has
+    pub fn progress(&self, codex: &Codex, category: CodexCategory) -> (usize, usize) {
+        codex
+            .in_category(category)
+            .fold((0, 0), |(unlocked, total), entry| {
+                (
+                    unlocked + usize::from(self.is_unlocked(&entry.id)),
+                    total + 1,
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, category: CodexCategory) -> CodexEntry {
+        CodexEntry {
+            id: id.to_string(),
+            category,
+            title: id.to_string(),
+            flavor: String::new(),
+            notes: String::new(),
+            hint: String::new(),
+        }
+    }
+
+    fn complete_entries() -> Vec<CodexEntry> {
+        TerrainType::all()
+            .into_iter()
+            .map(|terrain| entry(&terrain_entry_id(terrain), CodexCategory::Terrain))
+            .chain(
+                EventType::all()
+                    .into_iter()
+                    .map(|event_type| entry(&event_entry_id(event_type), CodexCategory::Event)),
+            )
+            .collect()
+    }
+
+    fn to_json(entries: Vec<CodexEntry>) -> String {
+        serde_json::to_string(&Codex { entries }).unwrap()
+    }
+
+    #[test]
+    fn every_terrain_and_event_type_needs_an_entry() {
+        assert!(Codex::parse(&to_json(complete_entries())).is_ok());
+
+        let mut entries = complete_entries();
+        entries.retain(|entry| entry.id != "terrain/Crystal" && entry.id != "event/Trade");
+        let Err(DomainError::ConfigurationError(reason)) = Codex::parse(&to_json(entries)) else {
+            panic!("an incomplete codex was accepted");
+        };
+        assert!(reason.contains("terrain/Crystal"), "{}", reason);
+        assert!(reason.contains("event/Trade"), "{}", reason);
+
+        let mut entries = complete_entries();
+        entries.push(entry("terrain/Plains", CodexCategory::Terrain));
+        assert!(Codex::parse(&to_json(entries)).is_err());
+    }
+
+    #[test]
+    fn categories_filter_entries_in_order() {
+        let mut entries = complete_entries();
+        entries.push(entry(
+            &faction_entry_id(Faction::FreeTraders),
+            CodexCategory::Faction,
+        ));
+        entries.push(entry(&gear_entry_id(GearSlot::Tool), CodexCategory::Item));
+        let codex = Codex::parse(&to_json(entries)).unwrap();
+
+        let terrains: Vec<&str> = codex
+            .in_category(CodexCategory::Terrain)
+            .map(|entry| entry.id.as_str())
+            .collect();
+        assert_eq!(terrains.len(), TerrainType::all().len());
+        assert_eq!(terrains[0], "terrain/Plains");
+        assert_eq!(codex.in_category(CodexCategory::Item).count(), 1);
+        assert_eq!(codex.in_category(CodexCategory::PointOfInterest).count(), 0);
+
+        let mut unlocks = CodexUnlocks::default();
+        assert!(unlocks.unlock("terrain/Plains"));
+        assert!(!unlocks.unlock("terrain/Plains"));
+        assert!(unlocks.unlock("faction/FreeTraders"));
+        assert_eq!(
+            unlocks.progress(&codex, CodexCategory::Terrain),
+            (1, TerrainType::all().len())
+        );
+        assert_eq!(unlocks.progress(&codex, CodexCategory::Faction), (1, 1));
+    }
+}
@@ -0,0 +1,309 @@
+//! Data Packs - External additions and overrides to the shipped data
+//!
+//! A pack is a JSON manifest with an id, a priority and any number of
+//! codex entries and movement events. Codex entries replace the shipped
+//! entry with the same id or add a new one; events replace the shipped
+//! event with the same title in their roll category or add a new one.
+//! Recipes and terrain rules are still defined in code and cannot be
+//! changed by packs; terrain pages of the codex can.
+//!
+//! Packs apply from the lowest priority to the highest, ties broken by id,
+//! so the same packs always give the same result. When two packs change
+//! the same key the later one wins and the conflict names the pack that
+//! lost. The pack set has a fingerprint built from every pack's id and
+//! content hash; saves remember it and refuse to load under another set.
+
+use crate::domain::entities::EventType;
+use crate::domain::services::hashing::fnv1a_64;
+use crate::domain::services::tile_movement::EventCategory;
+use crate::domain::services::{Codex, CodexEntry};
+use crate::domain::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// A movement event added or replaced by a pack
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackEvent {
+    /// Roll outcome the event can follow
+    pub category: EventCategory,
+    pub event_type: EventType,
+    pub title: String,
+    pub description: String,
+}
+
+/// One data pack, as read from its manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataPack {
+    pub id: String,
+    /// Higher priorities apply later and win conflicts
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub codex: Vec<CodexEntry>,
+    #[serde(default)]
+    pub events: Vec<PackEvent>,
+    /// Hash of the manifest text
+    #[serde(skip)]
+    pub hash: u64,
+}
+
+impl DataPack {
+    /// Read a pack manifest
+    ///
+    /// Fails on a missing id, on entries or events the pack lists twice and
+    /// on events without a title or description.
+    pub fn parse(json: &str) -> DomainResult<Self> {
+        let mut pack: DataPack = serde_json::from_str(json)
+            .map_err(|e| DomainError::ConfigurationError(format!("invalid pack: {}", e)))?;
+        let invalid = |reason: String| {
+            DomainError::ConfigurationError(format!("pack {}: {}", pack_label(&pack.id), reason))
+        };
+        if pack.id.trim().is_empty() {
+            return Err(invalid("missing id".to_string()));
+        }
+
+        let mut keys = HashSet::new();
+        for key in pack.keys() {
+            if !keys.insert(key.clone()) {
+                return Err(invalid(format!("{} is listed twice", key)));
+            }
+        }
+        if let Some(event) = pack
+            .events
+            .iter()
+            .find(|event| event.title.trim().is_empty() || event.description.trim().is_empty())
+        {
+            return Err(invalid(format!(
+                "{:?} event needs a title and a description",
+                event.event_type
+            )));
+        }
+
+        pack.hash = fnv1a_64(json.as_bytes());
+        Ok(pack)
+    }
+
+    /// Keys of everything the pack changes, e.g. `codex/terrain/Plains`
+    pub fn keys(&self) -> impl Iterator<Item = String> + '_ {
+        let codex = self.codex.iter().map(|entry| format!("codex/{}", entry.id));
+        let events = self
+            .events
+            .iter()
+            .map(|event| format!("event/{:?}/{}", event.category, event.title));
+        codex.chain(events)
+    }
+}
+
+fn pack_label(id: &str) -> &str {
+    if id.is_empty() {
+        "<unnamed>"
+    } else {
+        id
+    }
+}
+
+/// Two packs changed the same key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackConflict {
+    pub key: String,
+    pub winner: String,
+    pub loser: String,
+}
+
+impl PackConflict {
+    /// Report line, e.g. `codex/event/Combat: pirates overrides lore`
+    pub fn describe(&self) -> String {
+        format!("{}: {} overrides {}", self.key, self.winner, self.loser)
+    }
+}
+
+/// The packs in use, in the order they apply
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackSet {
+    packs: Vec<DataPack>,
+    conflicts: Vec<PackConflict>,
+}
+
+impl PackSet {
+    /// Order `packs` and find where they overlap
+    ///
+    /// Of two packs with the same id only the one applying later is kept,
+    /// and the other is reported as the loser of the whole pack.
+    pub fn new(mut packs: Vec<DataPack>) -> Self {
+        packs.sort_by(|a, b| a.priority.cmp(&b.priority).then_with(|| a.id.cmp(&b.id)));
+
+        let label = |pack: &DataPack| format!("{} ({:016x})", pack.id, pack.hash);
+        let mut conflicts = Vec::new();
+        let mut kept: Vec<DataPack> = Vec::new();
+        for pack in packs.into_iter().rev() {
+            match kept.iter().find(|winner| winner.id == pack.id) {
+                Some(winner) => conflicts.push(PackConflict {
+                    key: format!("pack/{}", pack.id),
+                    winner: label(winner),
+                    loser: label(&pack),
+                }),
+                None => kept.push(pack),
+            }
+        }
+        kept.reverse();
+        let packs = kept;
+
+        let mut owners: BTreeMap<String, &str> = BTreeMap::new();
+        for pack in &packs {
+            for key in pack.keys() {
+                if let Some(loser) = owners.insert(key.clone(), &pack.id) {
+                    conflicts.push(PackConflict {
+                        key,
+                        winner: pack.id.clone(),
+                        loser: loser.to_string(),
+                    });
+                }
+            }
+        }
+
+        Self { packs, conflicts }
+    }
+
+    /// Packs in the order they apply
+    pub fn packs(&self) -> &[DataPack] {
+        &self.packs
+    }
+
+    /// Keys changed by more than one pack
+    pub fn conflicts(&self) -> &[PackConflict] {
+        &self.conflicts
+    }
+
+    /// Check if only the shipped data is in use
+    pub fn is_empty(&self) -> bool {
+        self.packs.is_empty()
+    }
+
+    /// Fingerprint of the pack set; empty when no packs are in use
+    pub fn fingerprint(&self) -> String {
+        if self.packs.is_empty() {
+            return String::new();
+        }
+        let mut ids: Vec<String> = self
+            .packs
+            .iter()
+            .map(|pack| format!("{}:{:016x}", pack.id, pack.hash))
+            .collect();
+        ids.sort();
+        format!("{:016x}", fnv1a_64(ids.join("\n").as_bytes()))
+    }
+
+    /// The shipped codex with every pack's entries applied
+    pub fn apply_codex(&self, base: &Codex) -> DomainResult<Codex> {
+        let entries = self
+            .packs
+            .iter()
+            .flat_map(|pack| pack.codex.iter().cloned());
+        base.with_overrides(entries)
+    }
+
+    /// Events to add to the movement tables, later packs last
+    pub fn events(&self) -> impl Iterator<Item = &PackEvent> {
+        self.packs.iter().flat_map(|pack| pack.events.iter())
+    }
+
+    /// One line per pack, then one per conflict
+    pub fn listing(&self) -> Vec<String> {
+        if self.packs.is_empty() {
+            return vec!["no data packs loaded".to_string()];
+        }
+        let mut lines: Vec<String> = self
+            .packs
+            .iter()
+            .map(|pack| {
+                format!(
+                    "{} (priority {}, {} codex, {} events, {:016x})",
+                    pack.id,
+                    pack.priority,
+                    pack.codex.len(),
+                    pack.events.len(),
+                    pack.hash
+                )
+            })
+            .collect();
+        lines.extend(
+            self.conflicts
+                .iter()
+                .map(|conflict| format!("conflict {}", conflict.describe())),
+        );
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pack(id: &str, priority: i32, title: &str) -> DataPack {
+        DataPack::parse(&format!(
+            r#"{{
+                "id": "{}",
+                "priority": {},
+                "codex": [{{
+                    "id": "terrain/Plains",
+                    "category": "Terrain",
+                    "title": "{}",
+                    "flavor": "f",
+                    "notes": "n",
+                    "hint": "h"
+                }}],
+                "events": [{{
+                    "category": "Success",
+                    "event_type": "Trade",
+                    "title": "{} caravan",
+                    "description": "d"
+                }}]
+            }}"#,
+            id, priority, title, id
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn higher_priorities_win_and_the_loser_is_named() {
+        let base = Codex::parse(include_str!("../../../assets/codex.json")).unwrap();
+        let set = PackSet::new(vec![
+            pack("steppe", 5, "Steppe"),
+            pack("meadows", 1, "Meadows"),
+        ]);
+
+        let ids: Vec<&str> = set.packs().iter().map(|pack| pack.id.as_str()).collect();
+        assert_eq!(ids, ["meadows", "steppe"]);
+        let codex = set.apply_codex(&base).unwrap();
+        assert_eq!(codex.entry("terrain/Plains").unwrap().title, "Steppe");
+        assert_eq!(codex.entries().len(), base.entries().len());
+        assert_eq!(set.events().count(), 2);
+
+        assert_eq!(
+            set.conflicts(),
+            [PackConflict {
+                key: "codex/terrain/Plains".to_string(),
+                winner: "steppe".to_string(),
+                loser: "meadows".to_string(),
+            }]
+        );
+        assert!(set
+            .listing()
+            .contains(&"conflict codex/terrain/Plains: steppe overrides meadows".to_string()));
+    }
+
+    #[test]
+    fn fingerprint_follows_pack_contents_not_load_order() {
+        assert_eq!(PackSet::default().fingerprint(), "");
+
+        let a = PackSet::new(vec![pack("a", 0, "A"), pack("b", 0, "B")]);
+        let b = PackSet::new(vec![pack("b", 0, "B"), pack("a", 0, "A")]);
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        assert_eq!(a.fingerprint().len(), 16);
+
+        let edited = PackSet::new(vec![pack("a", 0, "A2"), pack("b", 0, "B")]);
+        assert_ne!(a.fingerprint(), edited.fingerprint());
+
+        assert!(DataPack::parse(r#"{"id": ""}"#).is_err());
+    }
+}
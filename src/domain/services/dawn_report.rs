@@ -0,0 +1,429 @@
+//! Dawn Report - A forecast of the day ahead after every rest
+//!
+//! After a rest the player gets a short report on the day ahead: the
+//! anomaly storms that stand in for weather, the season, the nearest known
+//! point of interest and the open distress signal with their distances,
+//! how many days the Food and Energy in the cargo last, what runs out at
+//! the next rest, and raids once there are any. A prioritised list of advisor rules
+//! reads the report; the highest priority rule with something to say gives
+//! the day's advice.
+//!
+//! Every part of the report may be missing. It only lists what is known,
+//! and an empty report still reads as a quiet morning.
+
+use crate::domain::constants::{
+    DAWN_LOW_SUPPLY_DAYS, DAWN_POI_ADVICE_DISTANCE, DAWN_RESTLESS_THREAT,
+    DAWN_STORM_WARNING_DISTANCE, STORM_MOVEMENT_COST_MULTIPLIER, STORM_RADIUS,
+};
+use crate::domain::services::Season;
+use crate::domain::value_objects::Position3D;
+use std::f32::consts::FRAC_PI_4;
+
+/// Eight compass points, counter-clockwise from east
+const HEADINGS: [&str; 8] = [
+    "east",
+    "north-east",
+    "north",
+    "north-west",
+    "west",
+    "south-west",
+    "south",
+    "south-east",
+];
+
+/// How far and which way something lies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bearing {
+    /// Tiles to walk, without diagonals
+    pub distance: u32,
+    pub heading: &'static str,
+}
+
+impl Bearing {
+    /// Bearing of `to` seen from `from`
+    pub fn between(from: Position3D, to: Position3D) -> Self {
+        let dx = (to.x - from.x) as f32;
+        let dy = (to.y - from.y) as f32;
+        let heading = if dx == 0.0 && dy == 0.0 {
+            "here"
+        } else {
+            let sector = (dy.atan2(dx) / FRAC_PI_4).round() as i32;
+            HEADINGS[sector.rem_euclid(8) as usize]
+        };
+        Self {
+            distance: from.manhattan_distance_2d(&to),
+            heading,
+        }
+    }
+
+    /// "4 tiles north-east", or "right here"
+    pub fn describe(&self) -> String {
+        match self.distance {
+            0 => "right here".to_string(),
+            1 => format!("1 tile {}", self.heading),
+            distance => format!("{} tiles {}", distance, self.heading),
+        }
+    }
+}
+
+/// A known place worth walking to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Landmark {
+    pub name: String,
+    pub position: Position3D,
+}
+
+/// The landmark closest to `from` on its level, the earliest one on a tie
+pub fn nearest_landmark(from: Position3D, landmarks: &[Landmark]) -> Option<(&Landmark, Bearing)> {
+    landmarks
+        .iter()
+        .filter(|landmark| landmark.position.z == from.z && landmark.position != from)
+        .map(|landmark| (landmark, Bearing::between(from, landmark.position)))
+        .fold(None, |nearest, candidate| match nearest {
+            Some((_, best)) if best.distance <= candidate.1.distance => nearest,
+            _ => Some(candidate),
+        })
+}
+
+/// Whole days `amount` lasts at `per_day`, none without any upkeep
+pub fn days_of(amount: u32, per_day: u32) -> Option<u32> {
+    (per_day > 0).then(|| amount / per_day)
+}
+
+/// The forecast for one day
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DawnReport {
+    pub day: u32,
+    /// Nearest anomaly storm and whether it lifts at the next rest
+    pub storm: Option<(Bearing, bool)>,
+    /// Season of the day and the days it has left, today included
+    pub season: Option<(Season, u32)>,
+    /// Nearest known point of interest
+    pub nearest_poi: Option<(String, Bearing)>,
+    /// The open distress signal and the rests it has left
+    pub distress: Option<(Bearing, u32)>,
+    pub food_days: Option<u32>,
+    pub energy_days: Option<u32>,
+    /// What runs out at the next rest
+    pub expiring: Vec<String>,
+    /// Rests until the next raid on the base
+    pub raid_in_rests: Option<u32>,
+    /// Movement roll penalty of the player's region
+    pub region_threat: u8,
+}
+
+impl DawnReport {
+    /// Check if the player is standing in the storm
+    pub fn in_storm(&self) -> bool {
+        self.storm
+            .is_some_and(|(bearing, _)| bearing.distance <= STORM_RADIUS)
+    }
+
+    /// Advice of the highest priority rule that has any
+    pub fn advice(&self) -> Option<String> {
+        advise(self, &ADVISOR_RULES)
+    }
+
+    /// Lines of the report, headline first
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("DAWN REPORT - DAY {}", self.day)];
+
+        lines.push(match self.storm {
+            Some(_) if self.in_storm() => format!(
+                "Weather: inside an anomaly storm - moves cost x{} and roll with disadvantage",
+                STORM_MOVEMENT_COST_MULTIPLIER
+            ),
+            Some((bearing, _)) => format!("Weather: anomaly storm {}", bearing.describe()),
+            None => "Weather: clear".to_string(),
+        });
+        if let Some((season, days_left)) = self.season {
+            lines.push(format!(
+                "Season: {}, {} day(s) left - {}",
+                season.name(),
+                days_left,
+                season.effects()
+            ));
+        }
+        if let Some((name, bearing)) = &self.nearest_poi {
+            lines.push(format!("Nearest {}: {}", name, bearing.describe()));
+        }
+        if let Some((bearing, rests)) = self.distress {
+            lines.push(format!(
+                "Distress signal: {}, {} rest(s) left",
+                bearing.describe(),
+                rests
+            ));
+        }
+        let supply = |days: Option<u32>| match days {
+            Some(days) => format!("{} day(s)", days),
+            None => "no upkeep".to_string(),
+        };
+        if self.food_days.is_some() || self.energy_days.is_some() {
+            lines.push(format!(
+                "Supplies: Food {}, Energy {}",
+                supply(self.food_days),
+                supply(self.energy_days)
+            ));
+        }
+        if !self.expiring.is_empty() {
+            lines.push(format!("Ending tonight: {}", self.expiring.join(", ")));
+        }
+        if let Some(rests) = self.raid_in_rests {
+            lines.push(format!("Raid expected in {} rest(s)", rests));
+        }
+        lines.push(format!(
+            "Advisor: {}",
+            self.advice()
+                .unwrap_or_else(|| "nothing pressing - explore freely".to_string())
+        ));
+        lines
+    }
+}
+
+/// One rule of the advisor
+pub struct AdvisorRule {
+    pub name: &'static str,
+    /// Higher priorities win
+    pub priority: u8,
+    pub advise: fn(&DawnReport) -> Option<String>,
+}
+
+/// Every advisor rule, most pressing first
+pub const ADVISOR_RULES: [AdvisorRule; 8] = [
+    AdvisorRule {
+        name: "out of supplies",
+        priority: 100,
+        advise: |report| {
+            (report.food_days == Some(0) || report.energy_days == Some(0))
+                .then(|| "supplies are out - return to base before anything else".to_string())
+        },
+    },
+    AdvisorRule {
+        name: "raid due",
+        priority: 90,
+        advise: |report| {
+            report
+                .raid_in_rests
+                .filter(|rests| *rests <= 1)
+                .map(|_| "raiders are due tonight - stay close to the base".to_string())
+        },
+    },
+    AdvisorRule {
+        name: "caught in a storm",
+        priority: 80,
+        advise: |report| {
+            report
+                .in_storm()
+                .then(|| "get out of the storm before exploring further".to_string())
+        },
+    },
+    AdvisorRule {
+        name: "signal fading",
+        priority: 70,
+        advise: |report| {
+            report
+                .distress
+                .filter(|(_, rests)| *rests <= 1)
+                .map(|(bearing, _)| {
+                    format!(
+                        "the distress signal fades tonight - head {} now",
+                        bearing.heading
+                    )
+                })
+        },
+    },
+    AdvisorRule {
+        name: "storm nearby",
+        priority: 60,
+        advise: |report| {
+            report
+                .storm
+                .filter(|(bearing, lifting)| {
+                    bearing.distance <= DAWN_STORM_WARNING_DISTANCE && !lifting
+                })
+                .map(|(bearing, _)| format!("avoid the anomaly storm to the {}", bearing.heading))
+        },
+    },
+    AdvisorRule {
+        name: "low supplies",
+        priority: 50,
+        advise: |report| {
+            let low = |days: Option<u32>| days.is_some_and(|days| days <= DAWN_LOW_SUPPLY_DAYS);
+            match (low(report.food_days), low(report.energy_days)) {
+                (true, _) => Some("food is running low - consider returning to base".to_string()),
+                (false, true) => {
+                    Some("energy is running low - consider returning to base".to_string())
+                }
+                _ => None,
+            }
+        },
+    },
+    AdvisorRule {
+        name: "restless region",
+        priority: 40,
+        advise: |report| {
+            (report.region_threat >= DAWN_RESTLESS_THREAT)
+                .then(|| "this region is restless - keep moving".to_string())
+        },
+    },
+    AdvisorRule {
+        name: "point of interest",
+        priority: 10,
+        advise: |report| {
+            report
+                .nearest_poi
+                .as_ref()
+                .filter(|(_, bearing)| bearing.distance <= DAWN_POI_ADVICE_DISTANCE)
+                .map(|(name, bearing)| format!("worth a look: the {} {}", name, bearing.describe()))
+        },
+    },
+];
+
+/// Advice of the highest priority rule in `rules` that has any
+///
+/// On equal priorities the rule listed first wins.
+pub fn advise(report: &DawnReport, rules: &[AdvisorRule]) -> Option<String> {
+    rules
+        .iter()
+        .filter_map(|rule| (rule.advise)(report).map(|advice| (rule.priority, advice)))
+        .fold(None, |best: Option<(u8, String)>, candidate| match best {
+            Some((priority, _)) if priority >= candidate.0 => best,
+            _ => Some(candidate),
+        })
+        .map(|(_, advice)| advice)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bearing(distance: u32, heading: &'static str) -> Bearing {
+        Bearing { distance, heading }
+    }
+
+    #[test]
+    fn the_most_pressing_rule_gives_the_advice() {
+        let mut report = DawnReport {
+            day: 4,
+            nearest_poi: Some(("ruins".to_string(), bearing(3, "west"))),
+            food_days: Some(5),
+            energy_days: Some(5),
+            ..DawnReport::default()
+        };
+        assert_eq!(
+            report.advice().unwrap(),
+            "worth a look: the ruins 3 tiles west"
+        );
+
+        report.food_days = Some(2);
+        assert_eq!(
+            report.advice().unwrap(),
+            "food is running low - consider returning to base"
+        );
+
+        report.storm = Some((bearing(5, "east"), false));
+        assert_eq!(
+            report.advice().unwrap(),
+            "avoid the anomaly storm to the east"
+        );
+        // A storm lifting tonight is not worth a detour
+        report.storm = Some((bearing(5, "east"), true));
+        assert!(report.advice().unwrap().starts_with("food"));
+
+        report.food_days = Some(0);
+        report.raid_in_rests = Some(1);
+        assert!(report.advice().unwrap().starts_with("supplies are out"));
+
+        // Equal priorities go to the rule listed first
+        let rules = [
+            AdvisorRule {
+                name: "first",
+                priority: 5,
+                advise: |_| Some("first".to_string()),
+            },
+            AdvisorRule {
+                name: "second",
+                priority: 5,
+                advise: |_| Some("second".to_string()),
+            },
+        ];
+        assert_eq!(advise(&report, &rules).unwrap(), "first");
+        assert!(ADVISOR_RULES
+            .windows(2)
+            .all(|pair| pair[0].priority > pair[1].priority));
+    }
+
+    #[test]
+    fn the_nearest_landmark_is_measured_in_tiles_walked() {
+        let from = Position3D::new(2, 2, 0);
+        assert_eq!(
+            Bearing::between(from, Position3D::new(5, 6, 0)),
+            bearing(7, "north-east")
+        );
+        assert_eq!(
+            Bearing::between(from, Position3D::new(-4, 2, 0)),
+            bearing(6, "west")
+        );
+        assert_eq!(
+            Bearing::between(from, Position3D::new(3, -5, 0)),
+            bearing(8, "south")
+        );
+        assert_eq!(Bearing::between(from, from).describe(), "right here");
+
+        let landmark = |name: &str, x, y, z| Landmark {
+            name: name.to_string(),
+            position: Position3D::new(x, y, z),
+        };
+        let landmarks = vec![
+            landmark("deposit", 8, 2, 0),
+            landmark("ruins", 2, -2, 0),
+            landmark("wreck", 0, 0, 0),
+            // Closer, but on another level
+            landmark("vault", 2, 3, -1),
+        ];
+        let (nearest, distance) = nearest_landmark(from, &landmarks).unwrap();
+        assert_eq!(nearest.name, "ruins");
+        assert_eq!(distance.describe(), "4 tiles south");
+        assert!(nearest_landmark(from, &[]).is_none());
+        assert_eq!(days_of(17, 5), Some(3));
+        assert_eq!(days_of(17, 0), None);
+    }
+
+    #[test]
+    fn an_empty_report_still_reads_as_a_quiet_morning() {
+        let report = DawnReport {
+            day: 1,
+            ..DawnReport::default()
+        };
+        assert_eq!(
+            report.lines(),
+            vec![
+                "DAWN REPORT - DAY 1".to_string(),
+                "Weather: clear".to_string(),
+                "Advisor: nothing pressing - explore freely".to_string(),
+            ]
+        );
+
+        let busy = DawnReport {
+            day: 9,
+            storm: Some((bearing(1, "north"), false)),
+            distress: Some((bearing(6, "south-west"), 1)),
+            food_days: Some(3),
+            energy_days: None,
+            expiring: vec!["the distress signal".to_string()],
+            ..DawnReport::default()
+        };
+        let lines = busy.lines();
+        assert!(lines[1].starts_with("Weather: inside an anomaly storm"));
+        assert_eq!(
+            lines[2],
+            "Distress signal: 6 tiles south-west, 1 rest(s) left"
+        );
+        assert_eq!(lines[3], "Supplies: Food 3 day(s), Energy no upkeep");
+        assert_eq!(lines[4], "Ending tonight: the distress signal");
+        assert_eq!(
+            lines[5],
+            "Advisor: get out of the storm before exploring further"
+        );
+    }
+}
@@ -0,0 +1,258 @@
+//! Decals - Marks the player leaves on the ground
+//!
+//! Tiles worked for resources show a stump or a pit depending on the
+//! terrain, won fights leave a scorch mark that fades over
+//! `DECAL_SCORCH_RESTS` rests, and prospected deposits get a survey stake.
+//! A tile shows at most `DECAL_TILE_CAP` decals; a new one replaces the
+//! oldest. Decals are scenery only: nothing in the game reads them back.
+
+use crate::domain::constants::{DECAL_SCORCH_RESTS, DECAL_TILE_CAP};
+use crate::domain::value_objects::terrain::TerrainType;
+use crate::domain::value_objects::Position3D;
+use serde::{Deserialize, Serialize};
+
+/// What left the mark
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DecalKind {
+    /// Resources were taken from the tile
+    Worked,
+    /// A fight was won here
+    Scorch,
+    /// A deposit was prospected
+    SurveyStake,
+}
+
+/// How a decal is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecalLook {
+    Stump,
+    Pit,
+    Scorch,
+    Stake,
+}
+
+impl DecalKind {
+    /// Look of the decal on `terrain`: worked ground with trees or reeds
+    /// leaves stumps, anything else a pit
+    pub fn look(&self, terrain: TerrainType) -> DecalLook {
+        match self {
+            DecalKind::Worked => match terrain {
+                TerrainType::Forest | TerrainType::Plains | TerrainType::Swamp => DecalLook::Stump,
+                _ => DecalLook::Pit,
+            },
+            DecalKind::Scorch => DecalLook::Scorch,
+            DecalKind::SurveyStake => DecalLook::Stake,
+        }
+    }
+}
+
+/// A mark and the rests since it was made
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Decal {
+    pub kind: DecalKind,
+    pub age: u32,
+}
+
+impl Decal {
+    /// Opacity between 0 and 1; only scorch marks fade
+    pub fn opacity(&self) -> f32 {
+        match self.kind {
+            DecalKind::Scorch => 1.0 - self.age as f32 / DECAL_SCORCH_RESTS as f32,
+            _ => 1.0,
+        }
+    }
+
+    /// Check if the decal has faded away
+    pub fn has_faded(&self) -> bool {
+        self.kind == DecalKind::Scorch && self.age >= DECAL_SCORCH_RESTS
+    }
+}
+
+/// Decals on one tile, oldest first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecalSite {
+    pub position: Position3D,
+    pub decals: Vec<Decal>,
+}
+
+/// Every decal on the map
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TileDecals {
+    sites: Vec<DecalSite>,
+}
+
+impl TileDecals {
+    /// A map without any decals
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark `position` with a new decal of `kind`
+    ///
+    /// A decal of the same kind already there is made new again. Returns
+    /// the decal replaced to stay within `DECAL_TILE_CAP`.
+    pub fn add(&mut self, position: Position3D, kind: DecalKind) -> Option<Decal> {
+        let index = match self.sites.iter().position(|site| site.position == position) {
+            Some(index) => index,
+            None => {
+                self.sites.push(DecalSite {
+                    position,
+                    decals: Vec::new(),
+                });
+                self.sites.len() - 1
+            }
+        };
+        let decals = &mut self.sites[index].decals;
+        decals.retain(|decal| decal.kind != kind);
+        let replaced = if decals.len() >= DECAL_TILE_CAP {
+            // Oldest by age; between equal ages the one made first
+            let oldest = decals
+                .iter()
+                .enumerate()
+                .max_by_key(|(index, decal)| (decal.age, std::cmp::Reverse(*index)))
+                .map(|(index, _)| index)?;
+            Some(decals.remove(oldest))
+        } else {
+            None
+        };
+        decals.push(Decal { kind, age: 0 });
+        replaced
+    }
+
+    /// Age every decal by a rest; returns how many faded away
+    pub fn rest(&mut self) -> usize {
+        let mut faded = 0;
+        for site in &mut self.sites {
+            for decal in &mut site.decals {
+                decal.age = decal.age.saturating_add(1);
+            }
+            let before = site.decals.len();
+            site.decals.retain(|decal| !decal.has_faded());
+            faded += before - site.decals.len();
+        }
+        self.sites.retain(|site| !site.decals.is_empty());
+        faded
+    }
+
+    /// Decals on `position`, oldest first
+    pub fn at(&self, position: Position3D) -> &[Decal] {
+        self.sites
+            .iter()
+            .find(|site| site.position == position)
+            .map(|site| site.decals.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Every marked tile
+    pub fn sites(&self) -> &[DecalSite] {
+        &self.sites
+    }
+
+    /// Check if no tile is marked
+    pub fn is_empty(&self) -> bool {
+        self.sites.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TILE: Position3D = Position3D { x: 4, y: -2, z: 0 };
+
+    #[test]
+    fn scorch_marks_fade_over_ten_rests() {
+        let mut decals = TileDecals::new();
+        decals.add(TILE, DecalKind::Scorch);
+        decals.add(TILE, DecalKind::SurveyStake);
+        assert_eq!(decals.at(TILE)[0].opacity(), 1.0);
+
+        for rest in 1..DECAL_SCORCH_RESTS {
+            assert_eq!(decals.rest(), 0);
+            let scorch = decals.at(TILE)[0];
+            assert_eq!(scorch.age, rest);
+            assert!(scorch.opacity() > 0.0 && scorch.opacity() < 1.0);
+        }
+        assert!((decals.at(TILE)[0].opacity() - 0.1).abs() < 1e-6);
+        assert_eq!(decals.rest(), 1);
+        // The stake stays, at full opacity
+        assert_eq!(decals.at(TILE).len(), 1);
+        assert_eq!(decals.at(TILE)[0].kind, DecalKind::SurveyStake);
+        assert_eq!(decals.at(TILE)[0].opacity(), 1.0);
+
+        let mut scorched = TileDecals::new();
+        scorched.add(TILE, DecalKind::Scorch);
+        for _ in 0..DECAL_SCORCH_RESTS {
+            scorched.rest();
+        }
+        assert!(scorched.is_empty());
+        assert!(scorched.at(TILE).is_empty());
+    }
+
+    #[test]
+    fn a_third_decal_replaces_the_oldest() {
+        let mut decals = TileDecals::new();
+        assert_eq!(decals.add(TILE, DecalKind::Worked), None);
+        decals.rest();
+        assert_eq!(decals.add(TILE, DecalKind::SurveyStake), None);
+
+        let replaced = decals.add(TILE, DecalKind::Scorch).unwrap();
+        assert_eq!(replaced.kind, DecalKind::Worked);
+        let kinds: Vec<DecalKind> = decals.at(TILE).iter().map(|d| d.kind).collect();
+        assert_eq!(kinds, vec![DecalKind::SurveyStake, DecalKind::Scorch]);
+
+        // The same kind again is renewed rather than stacked
+        decals.rest();
+        assert_eq!(decals.add(TILE, DecalKind::SurveyStake), None);
+        assert_eq!(decals.at(TILE).len(), DECAL_TILE_CAP);
+        assert_eq!(
+            decals.at(TILE)[1],
+            Decal {
+                kind: DecalKind::SurveyStake,
+                age: 0
+            }
+        );
+
+        // Between equal ages the first made goes
+        let mut fresh = TileDecals::new();
+        fresh.add(TILE, DecalKind::Worked);
+        fresh.add(TILE, DecalKind::Scorch);
+        assert_eq!(
+            fresh.add(TILE, DecalKind::SurveyStake).map(|d| d.kind),
+            Some(DecalKind::Worked)
+        );
+    }
+
+    #[test]
+    fn decals_round_trip_through_json() {
+        let mut decals = TileDecals::new();
+        decals.add(TILE, DecalKind::Worked);
+        decals.add(Position3D::origin(), DecalKind::Scorch);
+        decals.rest();
+
+        let json = serde_json::to_string(&decals).unwrap();
+        let restored: TileDecals = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, decals);
+        assert_eq!(restored.at(Position3D::origin())[0].age, 1);
+        assert_eq!(
+            serde_json::to_value(TileDecals::new()).unwrap(),
+            serde_json::json!({ "sites": [] })
+        );
+    }
+
+    #[test]
+    fn worked_ground_looks_like_its_terrain() {
+        assert_eq!(
+            DecalKind::Worked.look(TerrainType::Forest),
+            DecalLook::Stump
+        );
+        assert_eq!(
+            DecalKind::Worked.look(TerrainType::Mountains),
+            DecalLook::Pit
+        );
+        assert_eq!(
+            DecalKind::Scorch.look(TerrainType::Forest),
+            DecalLook::Scorch
+        );
+    }
+}
@@ -0,0 +1,273 @@
+//! Emergency Recall - One rescue per run instead of a defeat
+//!
+//! Once per run, a turn that would end in defeat recalls the player to the
+//! base instead. The rescue is steep: half of every carried resource is
+//! lost, rounded up, and the player is Injured for a few days, which is a
+//! layer of the modifier stack costing movement after every rest and a
+//! point on every movement roll. Normal runs always carry the recall;
+//! hardcore runs only when it was switched on before the run started, and
+//! then keep a smaller share of their final score.
+//!
+//! There is no player health yet, so the hull reading stays full and only
+//! being stranded can defeat a run today; the health check is ready for
+//! when damage lands.
+
+use crate::domain::constants::{
+    HARDCORE_RECALL_SCORE_PERCENT, INJURED_DICE_PENALTY, INJURED_MOVEMENT_PENALTY,
+    RECALL_INJURY_DAYS, RECALL_RESOURCE_LOSS_PERCENT,
+};
+use crate::domain::services::{RunMode, RunModifier};
+use crate::domain::value_objects::resources::ResourceCollection;
+use serde::{Deserialize, Serialize};
+
+/// What ended, or would have ended, a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefeatCause {
+    /// Health ran out
+    HealthDepleted,
+    /// No movement points, no food and nowhere to step to
+    Stranded,
+}
+
+impl DefeatCause {
+    /// Short description for the log and the summary
+    pub fn describe(&self) -> &'static str {
+        match self {
+            DefeatCause::HealthDepleted => "hull integrity lost",
+            DefeatCause::Stranded => "stranded without food or a way out",
+        }
+    }
+}
+
+/// The player's condition at the end of a turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Vitals {
+    pub health: u32,
+    pub movement_points: u8,
+    pub food: u32,
+    /// Passable tiles the player could step to next
+    pub open_neighbours: usize,
+}
+
+impl Vitals {
+    /// Why the player is defeated, if they are
+    pub fn defeat_cause(&self) -> Option<DefeatCause> {
+        if self.health == 0 {
+            Some(DefeatCause::HealthDepleted)
+        } else if self.movement_points == 0 && self.food == 0 && self.open_neighbours == 0 {
+            Some(DefeatCause::Stranded)
+        } else {
+            None
+        }
+    }
+}
+
+/// Resources a recall takes from `carried`: the loss share of each type,
+/// rounded up
+pub fn recall_losses(carried: &ResourceCollection) -> ResourceCollection {
+    let mut lost = ResourceCollection::new();
+    for resource in carried.resource_types() {
+        let amount = carried.get_amount(resource);
+        lost.set_amount(
+            resource,
+            (amount * RECALL_RESOURCE_LOSS_PERCENT).div_ceil(100),
+        );
+    }
+    lost
+}
+
+/// Rule changes of the Injured status
+pub fn injury_modifier() -> RunModifier {
+    RunModifier {
+        rest_movement_delta: -INJURED_MOVEMENT_PENALTY,
+        threat_delta: INJURED_DICE_PENALTY,
+        ..RunModifier::default()
+    }
+}
+
+/// The run's Emergency Recall and whether it was spent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmergencyRecall {
+    enabled: bool,
+    /// The recall counts against the final score
+    score_penalty: bool,
+    used_on_day: Option<u32>,
+}
+
+impl Default for EmergencyRecall {
+    fn default() -> Self {
+        Self::for_run(RunMode::Normal, false)
+    }
+}
+
+impl EmergencyRecall {
+    /// Recall of a new run; hardcore runs only get one when `hardcore_opt_in`
+    pub fn for_run(mode: RunMode, hardcore_opt_in: bool) -> Self {
+        let hardcore = mode == RunMode::Hardcore;
+        Self {
+            enabled: !hardcore || hardcore_opt_in,
+            score_penalty: hardcore && hardcore_opt_in,
+            used_on_day: None,
+        }
+    }
+
+    /// Check if the run carries a recall at all
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Check if a defeat would still be turned into a recall
+    pub fn is_available(&self) -> bool {
+        self.enabled && self.used_on_day.is_none()
+    }
+
+    /// Day the recall was used on, if it was
+    pub fn used_on_day(&self) -> Option<u32> {
+        self.used_on_day
+    }
+
+    /// Spend the recall on `day`; false if there is none left
+    pub fn spend(&mut self, day: u32) -> bool {
+        if !self.is_available() {
+            return false;
+        }
+        self.used_on_day = Some(day);
+        true
+    }
+
+    /// Injured days left counting `day`, zero once healed
+    pub fn injury_days_left(&self, day: u32) -> u32 {
+        self.used_on_day
+            .filter(|used| day >= *used)
+            .map(|used| (used + RECALL_INJURY_DAYS).saturating_sub(day))
+            .unwrap_or(0)
+    }
+
+    /// Check if the player is still Injured on `day`
+    pub fn is_injured(&self, day: u32) -> bool {
+        self.injury_days_left(day) > 0
+    }
+
+    /// HUD line, e.g. `Recall: used on day 4 - Injured, 2 days left`
+    pub fn status_line(&self, day: u32) -> String {
+        match self.used_on_day {
+            _ if !self.enabled => "Recall: none".to_string(),
+            None => "Recall: ready".to_string(),
+            Some(used) if self.is_injured(day) => format!(
+                "Recall: used on day {} - Injured, {} days left",
+                used,
+                self.injury_days_left(day)
+            ),
+            Some(used) => format!("Recall: used on day {}", used),
+        }
+    }
+
+    /// Share of the final score the run keeps, in percent
+    pub fn score_percent(&self) -> u32 {
+        if self.score_penalty {
+            HARDCORE_RECALL_SCORE_PERCENT
+        } else {
+            100
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::ModifierStack;
+    use crate::domain::value_objects::ResourceType;
+
+    fn vitals() -> Vitals {
+        Vitals {
+            health: 100,
+            movement_points: 0,
+            food: 0,
+            open_neighbours: 0,
+        }
+    }
+
+    #[test]
+    fn each_trigger_defeats_on_its_own() {
+        assert_eq!(vitals().defeat_cause(), Some(DefeatCause::Stranded));
+        let wounded = Vitals {
+            health: 0,
+            movement_points: 9,
+            food: 30,
+            open_neighbours: 4,
+        };
+        assert_eq!(wounded.defeat_cause(), Some(DefeatCause::HealthDepleted));
+
+        // Any way out keeps a stranded player in the run
+        for survivor in [
+            Vitals {
+                movement_points: 1,
+                ..vitals()
+            },
+            Vitals {
+                food: 1,
+                ..vitals()
+            },
+            Vitals {
+                open_neighbours: 1,
+                ..vitals()
+            },
+        ] {
+            assert_eq!(survivor.defeat_cause(), None);
+        }
+    }
+
+    #[test]
+    fn recall_takes_half_of_each_resource_rounded_up() {
+        let carried = ResourceCollection::cost(&[
+            (ResourceType::Metal, 9),
+            (ResourceType::Food, 1),
+            (ResourceType::Energy, 10),
+        ])
+        .unwrap();
+        let lost = recall_losses(&carried);
+        assert_eq!(lost.get_amount(ResourceType::Metal), 5);
+        assert_eq!(lost.get_amount(ResourceType::Food), 1);
+        assert_eq!(lost.get_amount(ResourceType::Energy), 5);
+        assert_eq!(lost.get_amount(ResourceType::Data), 0);
+    }
+
+    #[test]
+    fn the_recall_is_spent_once_and_injures_for_three_days() {
+        let mut recall = EmergencyRecall::for_run(RunMode::Normal, false);
+        assert!(recall.is_available());
+        assert_eq!(recall.score_percent(), 100);
+        assert!(!recall.is_injured(4));
+
+        assert!(recall.spend(4));
+        assert!(!recall.spend(5));
+        assert_eq!(recall.used_on_day(), Some(4));
+        assert!(!recall.is_available());
+        assert_eq!(recall.injury_days_left(4), RECALL_INJURY_DAYS);
+        assert!(recall.is_injured(6));
+        assert!(!recall.is_injured(7));
+        assert!(!recall.is_injured(3));
+        assert_eq!(
+            recall.status_line(5),
+            "Recall: used on day 4 - Injured, 2 days left"
+        );
+
+        // Injured is a layer of the stack, added and removed without piling up
+        let mut stack = ModifierStack::default();
+        stack.set_injured(true);
+        stack.set_injured(true);
+        assert!(stack.is_injured());
+        assert_eq!(stack.threat(), INJURED_DICE_PENALTY);
+        assert_eq!(stack.rest_movement(10), 10 - INJURED_MOVEMENT_PENALTY as u8);
+        stack.set_injured(false);
+        assert_eq!(stack, ModifierStack::default());
+
+        // Hardcore runs only carry one when asked, and pay for it in score
+        let mut hardcore = EmergencyRecall::for_run(RunMode::Hardcore, false);
+        assert!(!hardcore.is_available());
+        assert!(!hardcore.spend(1));
+        let opted_in = EmergencyRecall::for_run(RunMode::Hardcore, true);
+        assert!(opted_in.is_available());
+        assert_eq!(opted_in.score_percent(), HARDCORE_RECALL_SCORE_PERCENT);
+    }
+}
@@ -0,0 +1,394 @@
+//! Expedition Planning - Multi-day routes with projected cost and risk
+//!
+//! An expedition is a sequence of waypoints on explored terrain, connected
+//! by the cheapest routes the pathfinder finds. The planner projects how
+//! many movement points, rest stops and supplies the trip needs; an active
+//! plan tracks waypoint progress and how far the player strays from it.
+
+use crate::domain::constants::{
+    EXPEDITION_ENERGY_PER_DAY, EXPEDITION_FOOD_PER_DAY, EXPEDITION_MAX_WAYPOINTS,
+};
+use crate::domain::entities::Map;
+use crate::domain::services::pathfinding::PathfindingService;
+use crate::domain::value_objects::Position3D;
+use crate::domain::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+
+/// A committed route through a sequence of waypoints
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpeditionPlan {
+    start: Position3D,
+    waypoints: Vec<Position3D>,
+    /// Every tile of the trip in order, excluding the start
+    route: Vec<Position3D>,
+    /// Index into `route` at which each waypoint is reached
+    waypoint_steps: Vec<usize>,
+    /// Index of the next waypoint to reach
+    next_waypoint: usize,
+}
+
+/// Progress made when a waypoint is reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaypointProgress {
+    /// Number of waypoints reached so far
+    pub reached: usize,
+    /// Total number of waypoints in the plan
+    pub total: usize,
+}
+
+impl WaypointProgress {
+    /// Check if this was the final waypoint
+    pub fn is_final(&self) -> bool {
+        self.reached == self.total
+    }
+}
+
+impl ExpeditionPlan {
+    /// Starting position of the plan
+    pub fn start(&self) -> Position3D {
+        self.start
+    }
+
+    /// All waypoints in order
+    pub fn waypoints(&self) -> &[Position3D] {
+        &self.waypoints
+    }
+
+    /// Waypoints not reached yet
+    pub fn remaining_waypoints(&self) -> &[Position3D] {
+        &self.waypoints[self.next_waypoint.min(self.waypoints.len())..]
+    }
+
+    /// Every tile of the planned trip, excluding the start
+    pub fn route(&self) -> &[Position3D] {
+        &self.route
+    }
+
+    /// Next waypoint to reach, if any
+    pub fn next_waypoint(&self) -> Option<Position3D> {
+        self.waypoints.get(self.next_waypoint).copied()
+    }
+
+    /// Check if every waypoint has been reached
+    pub fn is_complete(&self) -> bool {
+        self.next_waypoint >= self.waypoints.len()
+    }
+
+    /// Tile the current leg departs from and the route index of its first step
+    fn departure(&self) -> (Position3D, usize) {
+        match self.next_waypoint {
+            0 => (self.start, 0),
+            n => {
+                let previous = self.waypoint_steps[n - 1];
+                (self.route[previous], previous + 1)
+            }
+        }
+    }
+
+    /// Route from the current leg's departure tile to its end at `last_step`
+    fn route_from_departure(&self, last_step: usize) -> Vec<Position3D> {
+        let (departure, first_step) = self.departure();
+        std::iter::once(departure)
+            .chain(self.route[first_step..=last_step].iter().copied())
+            .collect()
+    }
+
+    /// Tiles left to the next waypoint from `position`
+    ///
+    /// Follows the route when the player is on the current leg, otherwise
+    /// falls back to the straight-line (Manhattan) distance.
+    pub fn tiles_to_next_waypoint(&self, position: Position3D) -> Option<u32> {
+        let waypoint = self.next_waypoint()?;
+        let leg = self.route_from_departure(self.waypoint_steps[self.next_waypoint]);
+        let remaining = leg
+            .iter()
+            .position(|step| *step == position)
+            .map(|index| (leg.len() - 1 - index) as u32)
+            .unwrap_or_else(|| position.manhattan_distance_2d(&waypoint));
+        Some(remaining)
+    }
+
+    /// Distance from `position` to the closest tile of the route still ahead
+    pub fn deviation(&self, position: Position3D) -> u32 {
+        if self.is_complete() {
+            return 0;
+        }
+        self.route_from_departure(self.route.len() - 1)
+            .iter()
+            .map(|step| step.manhattan_distance_2d(&position))
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Check if the player strayed more than `max_tiles` from the route
+    pub fn is_off_course(&self, position: Position3D, max_tiles: u32) -> bool {
+        self.deviation(position) > max_tiles
+    }
+
+    /// Record the player's position, advancing when a waypoint is reached
+    pub fn record_position(&mut self, position: Position3D) -> Option<WaypointProgress> {
+        if self.next_waypoint() != Some(position) {
+            return None;
+        }
+        self.next_waypoint += 1;
+        Some(WaypointProgress {
+            reached: self.next_waypoint,
+            total: self.waypoints.len(),
+        })
+    }
+}
+
+/// Projected cost of an expedition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpeditionEstimate {
+    /// Number of tiles travelled
+    pub tiles: u32,
+    /// Movement points required for the whole trip
+    pub movement_points: u32,
+    /// Rests needed along the way
+    pub rest_stops: u32,
+    /// Days on the road, counting the day of departure
+    pub days: u32,
+    /// Sum of the danger level of every tile travelled
+    pub cumulative_risk: u32,
+    /// Recommended food supply
+    pub food_needed: u32,
+    /// Recommended energy supply
+    pub energy_needed: u32,
+}
+
+/// Service building expedition plans and projecting their cost
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExpeditionPlanner {
+    pathfinding: PathfindingService,
+}
+
+impl ExpeditionPlanner {
+    /// Create a new expedition planner
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connect the waypoints with the cheapest routes across explored terrain
+    pub fn plan(
+        &self,
+        map: &Map,
+        start: Position3D,
+        waypoints: &[Position3D],
+    ) -> DomainResult<ExpeditionPlan> {
+        if waypoints.is_empty() {
+            return Err(DomainError::ValidationError(
+                "An expedition needs at least one waypoint".to_string(),
+            ));
+        }
+        if waypoints.len() > EXPEDITION_MAX_WAYPOINTS {
+            return Err(DomainError::ValidationError(format!(
+                "An expedition can have at most {} waypoints",
+                EXPEDITION_MAX_WAYPOINTS
+            )));
+        }
+
+        let mut route = Vec::new();
+        let mut waypoint_steps = Vec::with_capacity(waypoints.len());
+        let mut from = start;
+        for (index, &waypoint) in waypoints.iter().enumerate() {
+            if waypoint == from {
+                return Err(DomainError::ValidationError(format!(
+                    "Waypoint {} repeats the previous position",
+                    index + 1
+                )));
+            }
+            let leg = self
+                .pathfinding
+                .find_path(map, from, waypoint)
+                .ok_or_else(|| {
+                    DomainError::ValidationError(format!(
+                        "Waypoint {} cannot be reached through explored terrain",
+                        index + 1
+                    ))
+                })?;
+            route.extend(leg.steps);
+            waypoint_steps.push(route.len() - 1);
+            from = waypoint;
+        }
+
+        Ok(ExpeditionPlan {
+            start,
+            waypoints: waypoints.to_vec(),
+            route,
+            waypoint_steps,
+            next_waypoint: 0,
+        })
+    }
+
+    /// Project the cost of a plan
+    ///
+    /// `movement_per_rest` is the average number of movement points
+    /// available after resting (see `RestingService`).
+    pub fn estimate(
+        &self,
+        map: &Map,
+        plan: &ExpeditionPlan,
+        current_movement_points: u8,
+        movement_per_rest: f32,
+    ) -> ExpeditionEstimate {
+        let movement_points: u32 = plan
+            .route
+            .iter()
+            .map(|step| map.movement_cost(step) as u32)
+            .sum();
+        let cumulative_risk: u32 = plan
+            .route
+            .iter()
+            .map(|step| map.danger_level(step) as u32)
+            .sum();
+
+        let shortfall = movement_points.saturating_sub(current_movement_points as u32);
+        let rest_stops = if shortfall == 0 || movement_per_rest <= 0.0 {
+            0
+        } else {
+            (shortfall as f32 / movement_per_rest).ceil() as u32
+        };
+        let days = rest_stops + 1;
+
+        ExpeditionEstimate {
+            tiles: plan.route.len() as u32,
+            movement_points,
+            rest_stops,
+            days,
+            cumulative_risk,
+            food_needed: days * EXPEDITION_FOOD_PER_DAY,
+            energy_needed: days * EXPEDITION_ENERGY_PER_DAY,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::MapTile;
+    use crate::domain::value_objects::terrain::{Elevation, TerrainType};
+    use crate::domain::value_objects::{EntityId, TileCoordinate};
+
+    /// Three rows of explored plains with a forest tile at (2, 0)
+    fn fixture_map() -> Map {
+        let mut map = Map::new(EntityId::generate(), "Test".to_string(), 1).unwrap();
+        for (y, row) in ["..F.", "....", "...."].iter().enumerate() {
+            for (x, symbol) in row.chars().enumerate() {
+                let terrain = match symbol {
+                    'F' => TerrainType::Forest,
+                    _ => TerrainType::Plains,
+                };
+                map.set_tile(
+                    TileCoordinate::new(x as i32, y as i32, 0),
+                    MapTile::new(terrain, Elevation::sea_level(), true),
+                );
+            }
+        }
+        map
+    }
+
+    fn fixture_plan(map: &Map) -> ExpeditionPlan {
+        ExpeditionPlanner::new()
+            .plan(
+                map,
+                Position3D::new(0, 0, 0),
+                &[Position3D::new(3, 0, 0), Position3D::new(3, 2, 0)],
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn estimate_aggregates_route_cost_days_and_supplies() {
+        let map = fixture_map();
+        let plan = fixture_plan(&map);
+
+        // Straight through the forest is cheaper than going around it
+        assert_eq!(plan.route().len(), 5);
+        assert!(plan.route().contains(&Position3D::new(2, 0, 0)));
+
+        let estimate = ExpeditionPlanner::new().estimate(&map, &plan, 2, 3.0);
+
+        assert_eq!(estimate.tiles, 5);
+        assert_eq!(estimate.movement_points, 6); // 1 + 2 + 1 + 1 + 1
+        assert_eq!(estimate.cumulative_risk, 6); // plains 1, forest 2
+                                                 // 4 points short, 3 restored per rest: two rests, three days
+        assert_eq!(estimate.rest_stops, 2);
+        assert_eq!(estimate.days, 3);
+        assert_eq!(estimate.food_needed, 3 * EXPEDITION_FOOD_PER_DAY);
+        assert_eq!(estimate.energy_needed, 3 * EXPEDITION_ENERGY_PER_DAY);
+
+        // Enough points on hand means no rest at all
+        let rested = ExpeditionPlanner::new().estimate(&map, &plan, 10, 3.0);
+        assert_eq!(rested.rest_stops, 0);
+        assert_eq!(rested.days, 1);
+    }
+
+    #[test]
+    fn unreachable_waypoints_are_rejected() {
+        let map = fixture_map();
+        let planner = ExpeditionPlanner::new();
+
+        assert!(planner.plan(&map, Position3D::origin(), &[]).is_err());
+        assert!(planner
+            .plan(&map, Position3D::origin(), &[Position3D::new(9, 9, 0)])
+            .is_err());
+    }
+
+    #[test]
+    fn waypoint_progress_and_remaining_distance() {
+        let map = fixture_map();
+        let mut plan = fixture_plan(&map);
+
+        assert_eq!(
+            plan.tiles_to_next_waypoint(Position3D::new(0, 0, 0)),
+            Some(3)
+        );
+        assert_eq!(
+            plan.tiles_to_next_waypoint(Position3D::new(1, 0, 0)),
+            Some(2)
+        );
+        assert!(plan.record_position(Position3D::new(1, 0, 0)).is_none());
+
+        let progress = plan.record_position(Position3D::new(3, 0, 0)).unwrap();
+        assert_eq!(progress.reached, 1);
+        assert!(!progress.is_final());
+        assert_eq!(
+            plan.tiles_to_next_waypoint(Position3D::new(3, 0, 0)),
+            Some(2)
+        );
+
+        let progress = plan.record_position(Position3D::new(3, 2, 0)).unwrap();
+        assert!(progress.is_final());
+        assert!(plan.is_complete());
+        assert_eq!(plan.tiles_to_next_waypoint(Position3D::new(3, 2, 0)), None);
+    }
+
+    #[test]
+    fn deviation_measures_distance_from_remaining_route() {
+        let map = fixture_map();
+        let mut plan = fixture_plan(&map);
+
+        assert_eq!(plan.deviation(Position3D::new(1, 0, 0)), 0);
+        assert_eq!(plan.deviation(Position3D::new(0, 2, 0)), 2);
+        assert!(!plan.is_off_course(Position3D::new(0, 2, 0), 3));
+        assert!(plan.is_off_course(Position3D::new(-4, 0, 0), 3));
+
+        // Once past the first waypoint the first leg no longer counts
+        plan.record_position(Position3D::new(3, 0, 0));
+        assert_eq!(plan.deviation(Position3D::new(0, 0, 0)), 3);
+    }
+
+    #[test]
+    fn plan_survives_a_save_round_trip() {
+        let map = fixture_map();
+        let mut plan = fixture_plan(&map);
+        plan.record_position(Position3D::new(3, 0, 0));
+
+        let json = serde_json::to_string(&plan).unwrap();
+        let restored: ExpeditionPlan = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, plan);
+        assert_eq!(restored.next_waypoint(), Some(Position3D::new(3, 2, 0)));
+    }
+}
@@ -0,0 +1,154 @@
+//! Exploration Experience - Rewarding the player for charting new ground
+//!
+//! A tile is worth experience the first time the player explores it, by the
+//! difficulty tier of its terrain. The first tile of each terrain type
+//! charted in a run is a discovery, worth `FIRST_DISCOVERY_XP_MULTIPLIER`
+//! times as much. Only the step from unexplored to explored counts: revisits
+//! grant nothing, and neither do stale tiles refreshed later, since the
+//! refresh keeps them explored.
+
+use crate::domain::constants::{
+    EXPLORATION_XP_EASY, EXPLORATION_XP_EXTREME, EXPLORATION_XP_HARSH, EXPLORATION_XP_ROUGH,
+    FIRST_DISCOVERY_XP_MULTIPLIER,
+};
+use crate::domain::entities::MapTile;
+use crate::domain::value_objects::terrain::TerrainType;
+use std::collections::HashSet;
+
+/// Experience for exploring one tile of `terrain`, before any bonus
+pub fn terrain_exploration_xp(terrain: TerrainType) -> u32 {
+    match terrain {
+        TerrainType::Plains | TerrainType::Constructed => EXPLORATION_XP_EASY,
+        TerrainType::Forest | TerrainType::Desert | TerrainType::Crystal => EXPLORATION_XP_ROUGH,
+        TerrainType::Mountains | TerrainType::Tundra | TerrainType::Swamp | TerrainType::Cave => {
+            EXPLORATION_XP_HARSH
+        }
+        TerrainType::Ocean | TerrainType::Volcanic | TerrainType::Anomaly => EXPLORATION_XP_EXTREME,
+    }
+}
+
+/// Experience earned by exploring a tile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExplorationGrant {
+    pub terrain: TerrainType,
+    pub experience: u32,
+    /// First tile of this terrain type charted in the run
+    pub first_discovery: bool,
+}
+
+/// Terrain types charted so far in a run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiscoveredTerrains {
+    terrains: HashSet<TerrainType>,
+}
+
+impl DiscoveredTerrains {
+    /// Create an empty record for a new run
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Experience for exploring `tile` now; `None` if it was explored already
+    pub fn explore(&mut self, tile: &MapTile) -> Option<ExplorationGrant> {
+        if tile.is_explored() {
+            return None;
+        }
+        let terrain = tile.terrain_type;
+        let first_discovery = self.terrains.insert(terrain);
+        let mut experience = terrain_exploration_xp(terrain);
+        if first_discovery {
+            experience *= FIRST_DISCOVERY_XP_MULTIPLIER;
+        }
+        Some(ExplorationGrant {
+            terrain,
+            experience,
+            first_discovery,
+        })
+    }
+
+    /// Note `terrain` as charted without granting anything, as when a
+    /// saved run is loaded
+    pub fn record(&mut self, terrain: TerrainType) {
+        self.terrains.insert(terrain);
+    }
+
+    /// Check if a tile of `terrain` was charted this run
+    pub fn contains(&self, terrain: TerrainType) -> bool {
+        self.terrains.contains(&terrain)
+    }
+
+    /// Number of terrain types charted this run
+    pub fn len(&self) -> usize {
+        self.terrains.len()
+    }
+
+    /// Check if nothing was charted yet
+    pub fn is_empty(&self) -> bool {
+        self.terrains.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::terrain::Elevation;
+
+    fn unexplored(terrain: TerrainType) -> MapTile {
+        MapTile::new(terrain, Elevation::sea_level(), false)
+    }
+
+    #[test]
+    fn harder_terrain_is_worth_more() {
+        assert_eq!(terrain_exploration_xp(TerrainType::Plains), 2);
+        assert_eq!(terrain_exploration_xp(TerrainType::Mountains), 5);
+        assert_eq!(terrain_exploration_xp(TerrainType::Anomaly), 8);
+        assert!(
+            terrain_exploration_xp(TerrainType::Forest)
+                > terrain_exploration_xp(TerrainType::Constructed)
+        );
+    }
+
+    #[test]
+    fn each_terrain_is_discovered_once_per_run() {
+        let mut discovered = DiscoveredTerrains::new();
+
+        let first = discovered.explore(&unexplored(TerrainType::Swamp)).unwrap();
+        assert!(first.first_discovery);
+        assert_eq!(first.experience, 5 * FIRST_DISCOVERY_XP_MULTIPLIER);
+
+        let second = discovered.explore(&unexplored(TerrainType::Swamp)).unwrap();
+        assert!(!second.first_discovery);
+        assert_eq!(second.experience, 5);
+
+        assert!(
+            discovered
+                .explore(&unexplored(TerrainType::Plains))
+                .unwrap()
+                .first_discovery
+        );
+        assert_eq!(discovered.len(), 2);
+        assert!(
+            DiscoveredTerrains::new()
+                .explore(&unexplored(TerrainType::Swamp))
+                .unwrap()
+                .first_discovery
+        );
+    }
+
+    #[test]
+    fn explored_tiles_grant_nothing() {
+        let mut discovered = DiscoveredTerrains::new();
+        let mut tile = unexplored(TerrainType::Anomaly);
+        assert!(discovered.explore(&tile).is_some());
+        tile.visit(1);
+
+        // Revisits, and the staleness refresh seeing it again on a later day
+        assert_eq!(discovered.explore(&tile), None);
+        tile.visit(30);
+        assert_eq!(discovered.explore(&tile), None);
+        // A refresh may reroll the terrain, but the tile stays explored
+        tile.terrain_type = TerrainType::Plains;
+        assert_eq!(discovered.explore(&tile), None);
+        assert!(!discovered.contains(TerrainType::Plains));
+    }
+}
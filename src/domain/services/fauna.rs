@@ -0,0 +1,327 @@
+//! Ambient Fauna - Decorative creatures wandering visible terrain
+//!
+//! Fauna are flavor only: they live on explored tiles the player can see,
+//! wander one tile at a time within their habitat and never block movement
+//! or trigger events. Population is capped in total and per terrain type.
+//! All randomness comes from the caller's RNG so outcomes can be seeded.
+
+use crate::domain::constants::{
+    FAUNA_MAX_PER_TERRAIN, FAUNA_MAX_TOTAL, FAUNA_SCATTER_DROP_CHANCE, FAUNA_SPAWN_CHANCE,
+};
+use crate::domain::entities::Map;
+use crate::domain::services::VisibilityService;
+use crate::domain::value_objects::terrain::TerrainType;
+use crate::domain::value_objects::{Position3D, TileCoordinate};
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+
+/// Kinds of ambient creatures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FaunaKind {
+    Grazer,
+    Hopper,
+    CanopyGlider,
+    MossCrawler,
+    DustMite,
+    SandSkitter,
+    FrostMoth,
+    SnowBurrower,
+    ShardBeetle,
+    PrismMoth,
+    BogCroaker,
+    MireFly,
+    GlowWisp,
+    PhaseFlicker,
+}
+
+impl FaunaKind {
+    /// Creatures living on a terrain type; empty where nothing lives
+    pub fn for_terrain(terrain: TerrainType) -> &'static [FaunaKind] {
+        match terrain {
+            TerrainType::Plains => &[FaunaKind::Grazer, FaunaKind::Hopper],
+            TerrainType::Forest => &[FaunaKind::CanopyGlider, FaunaKind::MossCrawler],
+            TerrainType::Desert => &[FaunaKind::DustMite, FaunaKind::SandSkitter],
+            TerrainType::Tundra => &[FaunaKind::FrostMoth, FaunaKind::SnowBurrower],
+            TerrainType::Crystal => &[FaunaKind::ShardBeetle, FaunaKind::PrismMoth],
+            TerrainType::Swamp => &[FaunaKind::BogCroaker, FaunaKind::MireFly],
+            TerrainType::Anomaly => &[FaunaKind::GlowWisp, FaunaKind::PhaseFlicker],
+            TerrainType::Mountains
+            | TerrainType::Ocean
+            | TerrainType::Volcanic
+            | TerrainType::Constructed
+            | TerrainType::Cave => &[],
+        }
+    }
+
+    /// Plural display name for log lines
+    pub fn plural_name(&self) -> &'static str {
+        match self {
+            FaunaKind::Grazer => "grazers",
+            FaunaKind::Hopper => "hoppers",
+            FaunaKind::CanopyGlider => "canopy gliders",
+            FaunaKind::MossCrawler => "moss crawlers",
+            FaunaKind::DustMite => "dust mites",
+            FaunaKind::SandSkitter => "sand skitters",
+            FaunaKind::FrostMoth => "frost moths",
+            FaunaKind::SnowBurrower => "snow burrowers",
+            FaunaKind::ShardBeetle => "shard beetles",
+            FaunaKind::PrismMoth => "prism moths",
+            FaunaKind::BogCroaker => "bog croakers",
+            FaunaKind::MireFly => "mire flies",
+            FaunaKind::GlowWisp => "glow wisps",
+            FaunaKind::PhaseFlicker => "phase flickers",
+        }
+    }
+}
+
+/// Living fauna counted in total and per terrain type
+#[derive(Debug, Clone, Default)]
+pub struct FaunaCensus {
+    total: usize,
+    by_terrain: HashMap<TerrainType, usize>,
+}
+
+impl FaunaCensus {
+    /// Create an empty census
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one creature living on `terrain`
+    pub fn add(&mut self, terrain: TerrainType) {
+        self.total += 1;
+        *self.by_terrain.entry(terrain).or_insert(0) += 1;
+    }
+
+    /// Number of creatures alive
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Number of creatures alive on `terrain`
+    pub fn count(&self, terrain: TerrainType) -> usize {
+        self.by_terrain.get(&terrain).copied().unwrap_or(0)
+    }
+
+    /// Check if both caps leave room for another creature on `terrain`
+    pub fn has_room(&self, terrain: TerrainType) -> bool {
+        self.total < FAUNA_MAX_TOTAL && self.count(terrain) < FAUNA_MAX_PER_TERRAIN
+    }
+}
+
+/// Spawning, wandering and scattering rules for ambient fauna
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FaunaService;
+
+impl FaunaService {
+    /// Create a new fauna service
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Pick new creatures for one spawn pass
+    ///
+    /// Only explored habitat tiles visible from `player` that hold no
+    /// creature yet are considered. The census is updated with every
+    /// creature picked, so the caps hold across the pass.
+    pub fn plan_spawns(
+        &self,
+        map: &Map,
+        player: Position3D,
+        occupied: &HashSet<TileCoordinate>,
+        census: &mut FaunaCensus,
+        rng: &mut impl Rng,
+    ) -> Vec<(TileCoordinate, FaunaKind)> {
+        let mut spawns = Vec::new();
+        for coordinate in VisibilityService::new().get_all_visible_coordinates(player) {
+            if occupied.contains(&coordinate) || coordinate == TileCoordinate::from(player) {
+                continue;
+            }
+            let Some(tile) = map.get_tile(&coordinate).filter(|tile| tile.is_explored()) else {
+                continue;
+            };
+            let kinds = FaunaKind::for_terrain(tile.terrain_type);
+            if kinds.is_empty() || !census.has_room(tile.terrain_type) {
+                continue;
+            }
+            if rng.gen_bool(FAUNA_SPAWN_CHANCE) {
+                census.add(tile.terrain_type);
+                spawns.push((coordinate, kinds[rng.gen_range(0..kinds.len())]));
+            }
+        }
+        spawns
+    }
+
+    /// Pick an adjacent explored tile of the same terrain to wander to
+    pub fn wander_target(
+        &self,
+        map: &Map,
+        from: TileCoordinate,
+        habitat: TerrainType,
+        rng: &mut impl Rng,
+    ) -> Option<TileCoordinate> {
+        let options: Vec<TileCoordinate> = Position3D::from(from)
+            .adjacent_positions()
+            .into_iter()
+            .map(TileCoordinate::from)
+            .filter(|coordinate| {
+                map.get_tile(coordinate)
+                    .is_some_and(|tile| tile.is_explored() && tile.terrain_type == habitat)
+            })
+            .collect();
+        if options.is_empty() {
+            return None;
+        }
+        Some(options[rng.gen_range(0..options.len())])
+    }
+
+    /// Check if a creature on `tile` is still within sight of the player
+    pub fn is_in_range(&self, player: Position3D, tile: TileCoordinate) -> bool {
+        VisibilityService::new().is_tile_visible(player, tile)
+    }
+
+    /// Roll whether scattered fauna leave behind 1 Organics
+    pub fn scatter_drops_organics(&self, rng: &mut impl Rng) -> bool {
+        rng.gen_bool(FAUNA_SCATTER_DROP_CHANCE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::MapTile;
+    use crate::domain::value_objects::terrain::Elevation;
+    use crate::domain::value_objects::EntityId;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn explored_map(terrain: TerrainType) -> Map {
+        let mut map = Map::new(EntityId::generate(), "Fauna".to_string(), 3).unwrap();
+        for position in Position3D::origin().positions_within_distance(8) {
+            map.set_tile(
+                TileCoordinate::from(position),
+                MapTile::new(terrain, Elevation::sea_level(), true),
+            );
+        }
+        map
+    }
+
+    #[test]
+    fn spawns_respect_total_and_per_terrain_caps() {
+        let service = FaunaService::new();
+        let plains = explored_map(TerrainType::Plains);
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut census = FaunaCensus::new();
+
+        // Many passes over the same view never exceed the per-terrain cap
+        for _ in 0..50 {
+            service.plan_spawns(
+                &plains,
+                Position3D::origin(),
+                &HashSet::new(),
+                &mut census,
+                &mut rng,
+            );
+        }
+        assert_eq!(census.count(TerrainType::Plains), FAUNA_MAX_PER_TERRAIN);
+
+        // A full census blocks every terrain
+        let mut full = FaunaCensus::new();
+        for terrain in [
+            TerrainType::Desert,
+            TerrainType::Forest,
+            TerrainType::Tundra,
+        ] {
+            for _ in 0..FAUNA_MAX_PER_TERRAIN {
+                full.add(terrain);
+            }
+        }
+        assert_eq!(full.total(), FAUNA_MAX_TOTAL);
+        let anomaly = explored_map(TerrainType::Anomaly);
+        for _ in 0..50 {
+            assert!(service
+                .plan_spawns(
+                    &anomaly,
+                    Position3D::origin(),
+                    &HashSet::new(),
+                    &mut full,
+                    &mut rng
+                )
+                .is_empty());
+        }
+    }
+
+    #[test]
+    fn spawns_only_on_explored_free_habitat() {
+        let service = FaunaService::new();
+        let mut map = explored_map(TerrainType::Mountains);
+        let habitat = TileCoordinate::new(1, 0, 0);
+        map.set_tile(
+            habitat,
+            MapTile::new(TerrainType::Desert, Elevation::sea_level(), true),
+        );
+        let mut rng = StdRng::seed_from_u64(11);
+
+        let mut spawned = Vec::new();
+        for _ in 0..200 {
+            let mut census = FaunaCensus::new();
+            spawned.extend(service.plan_spawns(
+                &map,
+                Position3D::origin(),
+                &HashSet::new(),
+                &mut census,
+                &mut rng,
+            ));
+        }
+        assert!(!spawned.is_empty());
+        assert!(spawned
+            .iter()
+            .all(|(coordinate, kind)| *coordinate == habitat
+                && FaunaKind::for_terrain(TerrainType::Desert).contains(kind)));
+
+        let occupied = HashSet::from([habitat]);
+        for _ in 0..200 {
+            let mut census = FaunaCensus::new();
+            assert!(service
+                .plan_spawns(&map, Position3D::origin(), &occupied, &mut census, &mut rng)
+                .is_empty());
+        }
+    }
+
+    #[test]
+    fn fauna_leave_range_with_visibility() {
+        let service = FaunaService::new();
+        assert!(service.is_in_range(Position3D::origin(), TileCoordinate::new(2, 1, 0)));
+        assert!(!service.is_in_range(Position3D::origin(), TileCoordinate::new(9, 0, 0)));
+        assert!(!service.is_in_range(Position3D::new(-6, 0, 0), TileCoordinate::new(2, 1, 0)));
+    }
+
+    #[test]
+    fn scatter_drop_rate_matches_chance() {
+        let service = FaunaService::new();
+        let mut rng = StdRng::seed_from_u64(1895);
+        let rolls = 20_000;
+        let drops = (0..rolls)
+            .filter(|_| service.scatter_drops_organics(&mut rng))
+            .count();
+        let rate = drops as f64 / rolls as f64;
+        assert!(
+            (rate - FAUNA_SCATTER_DROP_CHANCE).abs() < 0.005,
+            "rate {}",
+            rate
+        );
+
+        // The same seed gives the same drops
+        let first: Vec<bool> = {
+            let mut rng = StdRng::seed_from_u64(5);
+            (0..100)
+                .map(|_| service.scatter_drops_organics(&mut rng))
+                .collect()
+        };
+        let mut rng = StdRng::seed_from_u64(5);
+        let second: Vec<bool> = (0..100)
+            .map(|_| service.scatter_drops_organics(&mut rng))
+            .collect();
+        assert_eq!(first, second);
+    }
+}
@@ -140,6 +140,88 @@ impl Default for FontPaths {
     }
 }
 
+/// Plain stand-ins for symbols the built-in fallback font cannot draw
+///
+/// Only symbols that carry meaning on their own are listed; decorative
+/// emoji are dropped.
+const FALLBACK_SYMBOLS: &[(char, &str)] = &[
+    ('🎲', "Dice"),
+    ('⚠', "!"),
+    ('🚫', "No"),
+    ('✅', "OK"),
+    ('❌', "X"),
+    ('❓', "?"),
+    ('💰', "$"),
+    ('⚡', "Energy"),
+    ('😴', "Zz"),
+    ('💤', "Zz"),
+    ('⏸', "Paused"),
+    ('🔇', "Muted"),
+    ('🟢', "(ok)"),
+    ('🟡', "(low)"),
+    ('🟠', "(warn)"),
+    ('🔴', "(crit)"),
+    ('★', "*"),
+    ('█', "#"),
+    ('░', "-"),
+    ('·', "-"),
+    ('→', "->"),
+    ('←', "<-"),
+    ('—', "-"),
+    ('–', "-"),
+    ('…', "..."),
+];
+
+/// Accented letters and the plain letter the fallback font can draw
+const FALLBACK_LETTERS: &[(&str, char)] = &[
+    ("àáâãäå", 'a'),
+    ("ÀÁÂÃÄÅ", 'A'),
+    ("èéêë", 'e'),
+    ("ÈÉÊË", 'E'),
+    ("ìíîï", 'i'),
+    ("ÌÍÎÏ", 'I'),
+    ("òóôõöø", 'o'),
+    ("ÒÓÔÕÖØ", 'O'),
+    ("ùúûü", 'u'),
+    ("ÙÚÛÜ", 'U'),
+    ("ýÿ", 'y'),
+    ("çÇ", 'c'),
+    ("ñÑ", 'n'),
+];
+
+/// `text` reduced to what the built-in fallback font can draw
+///
+/// Meaningful symbols become short plain labels, accented letters lose
+/// their accents and every other non-ASCII character is dropped together
+/// with the space it leaves behind, so "🚀 Launch" reads "Launch".
+pub fn fallback_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut dropped = false;
+    for ch in text.chars() {
+        if ch == ' ' {
+            let at_word_start = out.is_empty() || out.ends_with(' ') || out.ends_with('\n');
+            if dropped && at_word_start {
+                continue;
+            }
+            out.push(ch);
+        } else if ch.is_ascii() {
+            out.push(ch);
+        } else if let Some((_, label)) = FALLBACK_SYMBOLS.iter().find(|(symbol, _)| *symbol == ch) {
+            out.push_str(label);
+        } else if let Some((_, plain)) = FALLBACK_LETTERS
+            .iter()
+            .find(|(letters, _)| letters.contains(ch))
+        {
+            out.push(*plain);
+        } else {
+            dropped = true;
+            continue;
+        }
+        dropped = false;
+    }
+    out
+}
+
 /// Helper functions for font configuration
 impl FontConfig {
     /// Create a new font configuration
@@ -215,6 +297,17 @@ mod tests {
         assert_eq!(config.weight, FontWeight::Normal);
     }
 
+    #[test]
+    fn fallback_text_keeps_the_meaning_of_symbols() {
+        assert_eq!(fallback_text("🎲 Roll"), "Dice Roll");
+        assert_eq!(fallback_text("⚠️ Low fuel"), "! Low fuel");
+        assert_eq!(fallback_text("🚀 Launch"), "Launch");
+        assert_eq!(fallback_text("🗺️ Map\n🎮 Play"), "Map\nPlay");
+        assert_eq!(fallback_text("Café · 3→4 ███░░"), "Cafe - 3->4 ###--");
+        assert_eq!(fallback_text("😮‍💨 Tired"), "Tired");
+        assert_eq!(fallback_text("plain  text"), "plain  text");
+    }
+
     #[test]
     fn font_paths_default() {
         let paths = FontPaths::default();
@@ -0,0 +1,158 @@
+//! Game Calendar - Days and nights of a run
+//!
+//! Each rest starts a new day at dawn. Every tile moved spends part of the
+//! daylight, and night falls after `CALENDAR_TILES_PER_DAY` moves; it lasts
+//! until the next rest, and moves made that late stop the clock just before
+//! midnight. The phase weighs which events a move turns up: the event
+//! tables are tuned for daylight, and at night hazards and raiders turn up
+//! more often while finds are harder to spot.
+
+use crate::domain::constants::{
+    CALENDAR_DAWN_HOUR, CALENDAR_NIGHTFALL_HOUR, CALENDAR_TILES_PER_DAY,
+    NIGHT_DISCOVERY_EVENT_WEIGHT, NIGHT_HOSTILE_EVENT_WEIGHT,
+};
+use crate::domain::entities::EventType;
+use crate::domain::value_objects::GameTime;
+
+const SECONDS_PER_DAY: u32 = 24 * 3600;
+
+/// Latest time of a day the clock reaches before the rest
+const LAST_MINUTE: u32 = SECONDS_PER_DAY - 60;
+
+/// Day or night, as far as the world's events care
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CalendarPhase {
+    #[default]
+    Day,
+    Night,
+}
+
+impl CalendarPhase {
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            CalendarPhase::Day => "Day",
+            CalendarPhase::Night => "Night",
+        }
+    }
+
+    /// Multiplier on the chance an `event_type` event is picked
+    pub fn event_weight(&self, event_type: EventType) -> f32 {
+        match (self, event_type) {
+            (CalendarPhase::Night, EventType::Hazard | EventType::Combat) => {
+                NIGHT_HOSTILE_EVENT_WEIGHT
+            }
+            (CalendarPhase::Night, EventType::ResourceDiscovery) => NIGHT_DISCOVERY_EVENT_WEIGHT,
+            _ => 1.0,
+        }
+    }
+}
+
+/// In-world date and time of the run
+#[derive(bevy::prelude::Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameCalendar {
+    time: GameTime,
+}
+
+impl Default for GameCalendar {
+    fn default() -> Self {
+        Self::at_dawn(1)
+    }
+}
+
+impl GameCalendar {
+    /// Dawn of `day`, counted from 1
+    pub fn at_dawn(day: u32) -> Self {
+        let days_past = day.max(1) - 1;
+        Self {
+            time: GameTime::new(days_past * SECONDS_PER_DAY)
+                .add(GameTime::from_hours(CALENDAR_DAWN_HOUR)),
+        }
+    }
+
+    /// Time since the run's first midnight
+    pub fn time(&self) -> GameTime {
+        self.time
+    }
+
+    /// Day of the run, counted from 1
+    pub fn day(&self) -> u32 {
+        self.time.seconds() / SECONDS_PER_DAY + 1
+    }
+
+    /// Hour of the day, with the minutes as a fraction
+    pub fn hour(&self) -> f32 {
+        (self.time.seconds() % SECONDS_PER_DAY) as f32 / 3600.0
+    }
+
+    /// Day or night
+    pub fn phase(&self) -> CalendarPhase {
+        let hour = self.time.hours() % 24;
+        if (CALENDAR_DAWN_HOUR..CALENDAR_NIGHTFALL_HOUR).contains(&hour) {
+            CalendarPhase::Day
+        } else {
+            CalendarPhase::Night
+        }
+    }
+
+    /// Spend the daylight one tile takes to cross
+    pub fn record_move(&mut self) {
+        let daylight = (CALENDAR_NIGHTFALL_HOUR - CALENDAR_DAWN_HOUR) * 3600;
+        let step = daylight / CALENDAR_TILES_PER_DAY.max(1);
+        let day_start = self.time.seconds() - self.time.seconds() % SECONDS_PER_DAY;
+        let advanced = self.time.advance_by_seconds(step).seconds();
+        self.time = GameTime::new(advanced.min(day_start + LAST_MINUTE));
+    }
+
+    /// HUD label, e.g. `Day 3, Night`
+    pub fn label(&self) -> String {
+        format!("Day {}, {}", self.day(), self.phase().name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn night_falls_after_a_day_of_moves_and_holds_until_the_next_dawn() {
+        let mut calendar = GameCalendar::default();
+        assert_eq!(calendar.label(), "Day 1, Day");
+        assert_eq!(calendar.hour(), CALENDAR_DAWN_HOUR as f32);
+
+        for _ in 1..CALENDAR_TILES_PER_DAY {
+            calendar.record_move();
+        }
+        assert_eq!(calendar.phase(), CalendarPhase::Day);
+        calendar.record_move();
+        assert_eq!(calendar.hour(), CALENDAR_NIGHTFALL_HOUR as f32);
+        assert_eq!(calendar.label(), "Day 1, Night");
+
+        // Moving on into the night never runs past midnight
+        for _ in 0..10 {
+            calendar.record_move();
+        }
+        assert_eq!(calendar.day(), 1);
+        assert_eq!(calendar.phase(), CalendarPhase::Night);
+
+        // A rest sleeps through to the next dawn
+        calendar = GameCalendar::at_dawn(calendar.day() + 1);
+        assert_eq!(calendar.label(), "Day 2, Day");
+        assert_eq!(calendar.hour(), CALENDAR_DAWN_HOUR as f32);
+        assert_eq!(calendar.time().hours(), 24 + CALENDAR_DAWN_HOUR);
+    }
+
+    #[test]
+    fn night_favours_hostile_events_and_day_favours_finds() {
+        let night = CalendarPhase::Night;
+        let day = CalendarPhase::Day;
+        assert!(night.event_weight(EventType::Combat) > day.event_weight(EventType::Combat));
+        assert!(night.event_weight(EventType::Hazard) > day.event_weight(EventType::Hazard));
+        assert!(
+            day.event_weight(EventType::ResourceDiscovery)
+                > night.event_weight(EventType::ResourceDiscovery)
+        );
+        assert_eq!(night.event_weight(EventType::Trade), 1.0);
+        assert_eq!(day.event_weight(EventType::Combat), 1.0);
+    }
+}
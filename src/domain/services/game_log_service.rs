@@ -5,6 +5,7 @@
 //! with appropriate formatting and timestamps.
 
 use crate::domain::{
+    constants::GAME_LOG_DEFAULT_CAPACITY,
     entities::{Event, Player},
     value_objects::{
         resources::{ResourceCollection, ResourceType},
@@ -16,6 +17,10 @@ use chrono::{DateTime, Utc};
 use std::collections::VecDeque;
 
 /// Service for managing game log messages and events
+///
+/// Messages are stored in a bounded ring buffer. Every stored entry carries a
+/// monotonically increasing sequence ID, so consumers that remember the last
+/// sequence they saw can detect when older entries were evicted.
 #[derive(Debug, bevy::prelude::Resource)]
 pub struct GameLogService {
     /// Queue of recent log messages
@@ -24,6 +29,10 @@ pub struct GameLogService {
     max_messages: usize,
     /// Whether to include timestamps in messages
     include_timestamps: bool,
+    /// Sequence ID assigned to the next stored entry
+    next_sequence: u64,
+    /// Number of entries evicted because the buffer was full
+    dropped_count: u64,
 }
 
 /// Individual log message with metadata
@@ -37,6 +46,30 @@ pub struct GameLogMessage {
     pub timestamp: DateTime<Utc>,
     /// Priority level for display ordering
     pub priority: LogPriority,
+    /// Stable, monotonically increasing ID of this entry
+    pub sequence: u64,
+    /// How many consecutive identical messages this entry stands for
+    pub repeat_count: u32,
+}
+
+impl GameLogMessage {
+    /// Text for display, including the ×N counter for compacted entries
+    pub fn display_text(&self) -> String {
+        if self.repeat_count > 1 {
+            format!("{} ×{}", self.message, self.repeat_count)
+        } else {
+            self.message.clone()
+        }
+    }
+}
+
+/// Result of reading the log from a previously seen sequence ID
+#[derive(Debug)]
+pub struct GameLogSlice<'a> {
+    /// Messages newer than the requested sequence, oldest first
+    pub messages: Vec<&'a GameLogMessage>,
+    /// True if entries after the requested sequence were evicted before being read
+    pub truncated: bool,
 }
 
 /// Categories of game log messages
@@ -80,10 +113,18 @@ pub enum LogPriority {
 impl GameLogService {
     /// Create a new game log service
     pub fn new() -> Self {
+        Self::with_capacity(GAME_LOG_DEFAULT_CAPACITY)
+    }
+
+    /// Create a game log service holding at most `capacity` entries
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
         Self {
-            messages: VecDeque::new(),
-            max_messages: 100,
+            messages: VecDeque::with_capacity(capacity),
+            max_messages: capacity,
             include_timestamps: false,
+            next_sequence: 0,
+            dropped_count: 0,
         }
     }
 
@@ -93,24 +134,42 @@ impl GameLogService {
     }
 
     /// Add a message with specific priority
+    ///
+    /// Consecutive identical `Resources` messages are compacted into the
+    /// previous entry, bumping its repeat counter instead of growing the log.
     pub fn log_message_with_priority(
         &mut self,
         message: String,
         log_type: GameLogType,
         priority: LogPriority,
     ) {
+        if log_type == GameLogType::Resources {
+            if let Some(last) = self.messages.back_mut() {
+                if last.log_type == GameLogType::Resources && last.message == message {
+                    last.repeat_count = last.repeat_count.saturating_add(1);
+                    last.timestamp = Utc::now();
+                    last.priority = last.priority.clone().max(priority);
+                    return;
+                }
+            }
+        }
+
         let log_message = GameLogMessage {
             message,
             log_type,
             timestamp: Utc::now(),
             priority,
+            sequence: self.next_sequence,
+            repeat_count: 1,
         };
+        self.next_sequence += 1;
 
         self.messages.push_back(log_message);
 
         // Keep only the most recent messages
         while self.messages.len() > self.max_messages {
             self.messages.pop_front();
+            self.dropped_count += 1;
         }
     }
 
@@ -279,8 +338,44 @@ impl GameLogService {
         self.messages.iter().collect()
     }
 
+    /// Get messages stored after the given sequence ID, oldest first
+    ///
+    /// Pass `None` to read everything. The `truncated` flag is set when the
+    /// entry directly following `after` has already been evicted, meaning the
+    /// caller missed some messages.
+    pub fn messages_since(&self, after: Option<u64>) -> GameLogSlice<'_> {
+        let first_wanted = after.map_or(0, |seq| seq + 1);
+        let truncated = self
+            .oldest_sequence()
+            .map_or(first_wanted < self.next_sequence, |oldest| {
+                first_wanted < oldest
+            });
+
+        GameLogSlice {
+            messages: self
+                .messages
+                .iter()
+                .filter(|message| message.sequence >= first_wanted)
+                .collect(),
+            truncated,
+        }
+    }
+
+    /// Sequence ID of the oldest retained entry
+    pub fn oldest_sequence(&self) -> Option<u64> {
+        self.messages.front().map(|message| message.sequence)
+    }
+
+    /// Sequence ID of the newest retained entry
+    pub fn latest_sequence(&self) -> Option<u64> {
+        self.messages.back().map(|message| message.sequence)
+    }
+
     /// Clear all messages
+    ///
+    /// Sequence IDs keep increasing so readers can still detect the gap.
     pub fn clear_messages(&mut self) {
+        self.dropped_count += self.messages.len() as u64;
         self.messages.clear();
     }
 
@@ -288,6 +383,26 @@ impl GameLogService {
     pub fn message_count(&self) -> usize {
         self.messages.len()
     }
+
+    /// Number of entries currently stored
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Whether the log holds no entries
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Maximum number of entries kept before the oldest are evicted
+    pub fn capacity(&self) -> usize {
+        self.max_messages
+    }
+
+    /// Total number of entries evicted or cleared since creation
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped_count
+    }
 }
 
 impl Default for GameLogService {
@@ -409,4 +524,117 @@ mod tests {
         assert!(formatted.contains("Food: 10"));
         assert!(formatted.contains("Energy: 5"));
     }
+
+    #[test]
+    fn test_default_capacity() {
+        let service = GameLogService::new();
+        assert_eq!(service.capacity(), GAME_LOG_DEFAULT_CAPACITY);
+        assert_eq!(service.len(), 0);
+        assert_eq!(service.dropped_count(), 0);
+    }
+
+    #[test]
+    fn test_wraparound_sequence_continuity() {
+        let mut service = GameLogService::with_capacity(4);
+
+        for i in 0..10 {
+            service.log_message(format!("Message {}", i), GameLogType::System);
+        }
+
+        assert_eq!(service.len(), 4);
+        let sequences: Vec<u64> = service
+            .get_all_messages()
+            .iter()
+            .map(|m| m.sequence)
+            .collect();
+        assert_eq!(sequences, vec![6, 7, 8, 9]);
+        assert_eq!(service.oldest_sequence(), Some(6));
+        assert_eq!(service.latest_sequence(), Some(9));
+    }
+
+    #[test]
+    fn test_truncation_detection() {
+        let mut service = GameLogService::with_capacity(3);
+
+        service.log_message("a".to_string(), GameLogType::System);
+        service.log_message("b".to_string(), GameLogType::System);
+        let seen = service.latest_sequence();
+
+        // Reader is caught up: nothing new, nothing missed
+        let slice = service.messages_since(seen);
+        assert!(slice.messages.is_empty());
+        assert!(!slice.truncated);
+
+        // One new entry, still within capacity
+        service.log_message("c".to_string(), GameLogType::System);
+        let slice = service.messages_since(seen);
+        assert_eq!(slice.messages.len(), 1);
+        assert!(!slice.truncated);
+
+        // Push enough entries to evict everything after `seen`
+        for i in 0..5 {
+            service.log_message(format!("later {}", i), GameLogType::System);
+        }
+        let slice = service.messages_since(seen);
+        assert!(slice.truncated);
+        assert_eq!(slice.messages.len(), 3);
+
+        // A fresh reader on a log that already dropped entries is truncated too
+        assert!(service.messages_since(None).truncated);
+    }
+
+    #[test]
+    fn test_duplicate_resources_compaction() {
+        let mut service = GameLogService::new();
+
+        for _ in 0..3 {
+            service.log_message(
+                "Gained 2 movement points".to_string(),
+                GameLogType::Resources,
+            );
+        }
+        service.log_message("Moved".to_string(), GameLogType::Movement);
+        service.log_message(
+            "Gained 2 movement points".to_string(),
+            GameLogType::Resources,
+        );
+
+        let messages = service.get_all_messages();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0].repeat_count, 3);
+        assert_eq!(messages[0].display_text(), "Gained 2 movement points ×3");
+        assert_eq!(messages[2].repeat_count, 1);
+        assert_eq!(messages[2].display_text(), "Gained 2 movement points");
+    }
+
+    #[test]
+    fn test_non_resource_duplicates_not_compacted() {
+        let mut service = GameLogService::new();
+
+        service.log_message("Same".to_string(), GameLogType::System);
+        service.log_message("Same".to_string(), GameLogType::System);
+
+        assert_eq!(service.len(), 2);
+    }
+
+    #[test]
+    fn test_dropped_counter() {
+        let mut service = GameLogService::with_capacity(2);
+
+        service.log_message("1".to_string(), GameLogType::System);
+        service.log_message("2".to_string(), GameLogType::System);
+        assert_eq!(service.dropped_count(), 0);
+
+        service.log_message("3".to_string(), GameLogType::System);
+        assert_eq!(service.dropped_count(), 1);
+
+        // Compacted duplicates do not evict anything
+        service.log_message("r".to_string(), GameLogType::Resources);
+        service.log_message("r".to_string(), GameLogType::Resources);
+        assert_eq!(service.dropped_count(), 2);
+
+        service.clear_messages();
+        assert_eq!(service.dropped_count(), 4);
+        assert!(service.is_empty());
+    }
 }
@@ -0,0 +1,451 @@
+//! Gear - Found equipment in three slots with typed bonuses
+//!
+//! The player wears one piece of gear per slot: a Suit, a Tool and a
+//! Module. Each piece grants one kind of bonus, and its rarity sets how
+//! strong the bonus is. Gear is found on critical mystery outcomes and in
+//! the vaults at the end of ruins, kept in a stash until equipped, and can
+//! be salvaged for scrap metal.
+//!
+//! Bonuses never touch the stored `PlayerStats`. Whatever reads a stat asks
+//! for the derived stats instead, which are recomputed from the base stats
+//! and the equipped gear every time.
+
+use crate::domain::constants::{
+    GEAR_PROTOTYPE_CHANCE_PERCENT, GEAR_RARE_CHANCE_PERCENT, GEAR_SALVAGE_METAL_PER_TIER,
+};
+use crate::domain::value_objects::resources::ResourceCollection;
+use crate::domain::value_objects::terrain::TerrainType;
+use crate::domain::value_objects::{PlayerStats, ResourceType};
+use crate::domain::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Terrains a tool can give better footing on
+const FOOTING_TERRAINS: [TerrainType; 7] = [
+    TerrainType::Forest,
+    TerrainType::Mountains,
+    TerrainType::Desert,
+    TerrainType::Tundra,
+    TerrainType::Swamp,
+    TerrainType::Volcanic,
+    TerrainType::Cave,
+];
+
+/// Where a piece of gear is worn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GearSlot {
+    Suit,
+    Tool,
+    Module,
+}
+
+impl GearSlot {
+    /// Every slot, in display order
+    pub fn all() -> [GearSlot; 3] {
+        [GearSlot::Suit, GearSlot::Tool, GearSlot::Module]
+    }
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            GearSlot::Suit => "Suit",
+            GearSlot::Tool => "Tool",
+            GearSlot::Module => "Module",
+        }
+    }
+}
+
+/// How well a piece of gear was made
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GearRarity {
+    Common,
+    Rare,
+    Prototype,
+}
+
+impl GearRarity {
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            GearRarity::Common => "Common",
+            GearRarity::Rare => "Rare",
+            GearRarity::Prototype => "Prototype",
+        }
+    }
+
+    /// Size of the bonus a piece of this rarity grants
+    pub fn magnitude(&self) -> u8 {
+        match self {
+            GearRarity::Common => 1,
+            GearRarity::Rare => 2,
+            GearRarity::Prototype => 3,
+        }
+    }
+
+    /// Rarity for a roll in `0..100`
+    fn from_percent(roll: u64) -> Self {
+        if roll < GEAR_PROTOTYPE_CHANCE_PERCENT {
+            GearRarity::Prototype
+        } else if roll < GEAR_RARE_CHANCE_PERCENT {
+            GearRarity::Rare
+        } else {
+            GearRarity::Common
+        }
+    }
+}
+
+/// The kind of bonus a piece of gear grants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GearBonus {
+    /// Suit: more Endurance
+    Endurance,
+    /// Suit: less hazard damage
+    HazardShielding,
+    /// Tool: better movement rolls on one terrain
+    Footing(TerrainType),
+    /// Module: more movement points after each rest
+    RestMovement,
+    /// Module: a wider view around the player
+    ViewRadius,
+}
+
+impl GearBonus {
+    /// Slot gear with this bonus is worn in
+    pub fn slot(&self) -> GearSlot {
+        match self {
+            GearBonus::Endurance | GearBonus::HazardShielding => GearSlot::Suit,
+            GearBonus::Footing(_) => GearSlot::Tool,
+            GearBonus::RestMovement | GearBonus::ViewRadius => GearSlot::Module,
+        }
+    }
+
+    /// Describe the bonus at a magnitude, e.g. `+1 Endurance`
+    pub fn describe(&self, magnitude: u8) -> String {
+        match self {
+            GearBonus::Endurance => format!("+{} Endurance", magnitude),
+            GearBonus::HazardShielding => format!("-{} hazard damage", magnitude),
+            GearBonus::Footing(terrain) => format!("+{} to moves on {}", magnitude, terrain),
+            GearBonus::RestMovement => format!("+{} movement per rest", magnitude),
+            GearBonus::ViewRadius => format!("+{} view radius", magnitude),
+        }
+    }
+
+    /// Base name of gear with this bonus
+    fn item_name(&self) -> String {
+        match self {
+            GearBonus::Endurance => "Padded Exosuit".to_string(),
+            GearBonus::HazardShielding => "Shielded Suit".to_string(),
+            GearBonus::Footing(terrain) => format!("{} Crampons", terrain),
+            GearBonus::RestMovement => "Sleep Regulator".to_string(),
+            GearBonus::ViewRadius => "Sensor Mast".to_string(),
+        }
+    }
+}
+
+/// A piece of gear owned by the player
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GearItem {
+    /// Identifies the piece within the player's gear; 0 until stowed
+    pub id: u32,
+    pub name: String,
+    pub rarity: GearRarity,
+    pub bonus: GearBonus,
+}
+
+impl GearItem {
+    /// Create a piece of gear, named after its bonus and rarity
+    pub fn new(bonus: GearBonus, rarity: GearRarity) -> Self {
+        let name = match rarity {
+            GearRarity::Common => bonus.item_name(),
+            GearRarity::Rare => format!("Tuned {}", bonus.item_name()),
+            GearRarity::Prototype => format!("Prototype {}", bonus.item_name()),
+        };
+        Self {
+            id: 0,
+            name,
+            rarity,
+            bonus,
+        }
+    }
+
+    /// Pick a piece of gear from a random or seeded value
+    pub fn roll(value: u64) -> Self {
+        let rarity = GearRarity::from_percent(value % 100);
+        let rest = value / 100;
+        let bonus = match rest % 5 {
+            0 => GearBonus::Endurance,
+            1 => GearBonus::HazardShielding,
+            2 => GearBonus::Footing(
+                FOOTING_TERRAINS[(rest / 5 % FOOTING_TERRAINS.len() as u64) as usize],
+            ),
+            3 => GearBonus::RestMovement,
+            _ => GearBonus::ViewRadius,
+        };
+        Self::new(bonus, rarity)
+    }
+
+    /// Slot this piece is worn in
+    pub fn slot(&self) -> GearSlot {
+        self.bonus.slot()
+    }
+
+    /// Size of this piece's bonus
+    pub fn magnitude(&self) -> u8 {
+        self.rarity.magnitude()
+    }
+
+    /// Describe this piece's bonus
+    pub fn describe(&self) -> String {
+        self.bonus.describe(self.magnitude())
+    }
+
+    /// Scrap recovered by salvaging this piece
+    pub fn salvage_value(&self) -> ResourceCollection {
+        let mut scrap = ResourceCollection::new();
+        scrap.set_amount(
+            ResourceType::Metal,
+            GEAR_SALVAGE_METAL_PER_TIER * self.magnitude() as u32,
+        );
+        scrap
+    }
+}
+
+/// Equipped gear and the stash of unequipped pieces
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Gear {
+    equipped: BTreeMap<GearSlot, GearItem>,
+    stash: Vec<GearItem>,
+    next_id: u32,
+}
+
+impl Default for Gear {
+    fn default() -> Self {
+        Self {
+            equipped: BTreeMap::new(),
+            stash: Vec::new(),
+            next_id: 1,
+        }
+    }
+}
+
+impl Gear {
+    /// Create gear with nothing equipped or stashed
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Piece equipped in a slot
+    pub fn equipped(&self, slot: GearSlot) -> Option<&GearItem> {
+        self.equipped.get(&slot)
+    }
+
+    /// Unequipped pieces, in the order they were found
+    pub fn stash(&self) -> &[GearItem] {
+        &self.stash
+    }
+
+    /// Every piece owned: equipped ones in slot order, then the stash
+    pub fn items(&self) -> Vec<&GearItem> {
+        self.equipped.values().chain(self.stash.iter()).collect()
+    }
+
+    /// Check if a piece is equipped
+    pub fn is_equipped(&self, id: u32) -> bool {
+        self.equipped.values().any(|item| item.id == id)
+    }
+
+    /// Put a found piece into the stash, returning its ID
+    pub fn stow(&mut self, mut item: GearItem) -> u32 {
+        item.id = self.next_id;
+        self.next_id += 1;
+        self.stash.push(item);
+        self.next_id - 1
+    }
+
+    /// Equip a stashed piece, moving whatever was in its slot to the stash
+    ///
+    /// Equipping a piece that is already equipped changes nothing. Returns
+    /// the ID of the piece that was taken off, if any.
+    pub fn equip(&mut self, id: u32) -> DomainResult<Option<u32>> {
+        if self.is_equipped(id) {
+            return Ok(None);
+        }
+        let index = self
+            .stash
+            .iter()
+            .position(|item| item.id == id)
+            .ok_or_else(|| DomainError::PlayerError(format!("No gear with ID {}", id)))?;
+        let item = self.stash.remove(index);
+        let displaced = self.equipped.insert(item.slot(), item);
+        Ok(displaced.map(|previous| {
+            let previous_id = previous.id;
+            self.stash.push(previous);
+            previous_id
+        }))
+    }
+
+    /// Move the piece in a slot to the stash, returning its ID
+    ///
+    /// Unequipping an empty slot changes nothing.
+    pub fn unequip(&mut self, slot: GearSlot) -> Option<u32> {
+        let item = self.equipped.remove(&slot)?;
+        let id = item.id;
+        self.stash.push(item);
+        Some(id)
+    }
+
+    /// Take a stashed piece apart; equipped pieces must be taken off first
+    pub fn salvage(&mut self, id: u32) -> DomainResult<GearItem> {
+        if let Some(item) = self.equipped.values().find(|item| item.id == id) {
+            return Err(DomainError::PlayerError(format!(
+                "Unequip the {} before salvaging it",
+                item.name
+            )));
+        }
+        let index = self
+            .stash
+            .iter()
+            .position(|item| item.id == id)
+            .ok_or_else(|| DomainError::PlayerError(format!("No gear with ID {}", id)))?;
+        Ok(self.stash.remove(index))
+    }
+
+    /// Summed magnitude of the equipped bonuses matching `bonus`
+    fn bonus(&self, bonus: GearBonus) -> u8 {
+        self.equipped
+            .values()
+            .filter(|item| item.bonus == bonus)
+            .map(|item| item.magnitude())
+            .sum()
+    }
+
+    /// Extra Endurance from the suit
+    pub fn endurance_bonus(&self) -> u8 {
+        self.bonus(GearBonus::Endurance)
+    }
+
+    /// Hazard damage the suit absorbs
+    pub fn hazard_reduction(&self) -> u8 {
+        self.bonus(GearBonus::HazardShielding)
+    }
+
+    /// Movement roll bonus of the tool on a terrain
+    pub fn footing_bonus(&self, terrain: TerrainType) -> i8 {
+        self.bonus(GearBonus::Footing(terrain)) as i8
+    }
+
+    /// Extra movement points the module adds to every rest
+    pub fn rest_movement_bonus(&self) -> u8 {
+        self.bonus(GearBonus::RestMovement)
+    }
+
+    /// Extra view radius from the module
+    pub fn view_radius_bonus(&self) -> u32 {
+        self.bonus(GearBonus::ViewRadius) as u32
+    }
+}
+
+/// Stats with the equipped gear applied; the base stats are left as they are
+pub fn derived_stats(base: &PlayerStats, gear: &Gear) -> PlayerStats {
+    PlayerStats {
+        endurance: base.endurance.saturating_add(gear.endurance_bonus()),
+        ..*base
+    }
+}
+
+/// A stat for the character sheet, e.g. `12 (+1)` when gear adds to it
+pub fn stat_line(base: u8, derived: u8) -> String {
+    if derived == base {
+        base.to_string()
+    } else {
+        format!("{} ({:+})", derived, derived as i16 - base as i16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derived_stats_add_the_equipped_bonuses_only() {
+        let base = PlayerStats::new(10, 10, 10, 10, 10, 11).unwrap();
+        let mut gear = Gear::new();
+        let suit = gear.stow(GearItem::new(GearBonus::Endurance, GearRarity::Common));
+        let tool = gear.stow(GearItem::new(
+            GearBonus::Footing(TerrainType::Swamp),
+            GearRarity::Rare,
+        ));
+        assert_eq!(derived_stats(&base, &gear), base);
+
+        gear.equip(suit).unwrap();
+        gear.equip(tool).unwrap();
+        let derived = derived_stats(&base, &gear);
+        assert_eq!(derived.endurance, 12);
+        assert_eq!(base.endurance, 11);
+        assert_eq!(
+            stat_line(base.endurance, derived.endurance),
+            "12 (+1)".to_string()
+        );
+        assert_eq!(stat_line(base.strength, derived.strength), "10");
+        assert_eq!(gear.footing_bonus(TerrainType::Swamp), 2);
+        assert_eq!(gear.footing_bonus(TerrainType::Mountains), 0);
+
+        // Prototype suits grant three times what common ones do
+        let prototype = gear.stow(GearItem::new(
+            GearBonus::HazardShielding,
+            GearRarity::Prototype,
+        ));
+        gear.equip(prototype).unwrap();
+        assert_eq!(gear.hazard_reduction(), 3);
+        assert_eq!(gear.endurance_bonus(), 0);
+    }
+
+    #[test]
+    fn each_slot_holds_one_piece_and_equipping_is_idempotent() {
+        let mut gear = Gear::new();
+        let regulator = gear.stow(GearItem::new(GearBonus::RestMovement, GearRarity::Common));
+        let mast = gear.stow(GearItem::new(GearBonus::ViewRadius, GearRarity::Rare));
+
+        assert_eq!(gear.equip(regulator).unwrap(), None);
+        let before = gear.clone();
+        assert_eq!(gear.equip(regulator).unwrap(), None);
+        assert_eq!(gear, before);
+
+        // Both are modules: the mast takes the regulator's place
+        assert_eq!(gear.equip(mast).unwrap(), Some(regulator));
+        assert_eq!(gear.equipped(GearSlot::Module).unwrap().id, mast);
+        assert_eq!(gear.stash().len(), 1);
+        assert_eq!(gear.rest_movement_bonus(), 0);
+        assert_eq!(gear.view_radius_bonus(), 2);
+
+        assert_eq!(gear.unequip(GearSlot::Module), Some(mast));
+        let before = gear.clone();
+        assert_eq!(gear.unequip(GearSlot::Module), None);
+        assert_eq!(gear, before);
+        assert!(gear.equip(99).is_err());
+    }
+
+    #[test]
+    fn salvaging_takes_stashed_gear_apart_for_scrap() {
+        let mut gear = Gear::new();
+        let suit = gear.stow(GearItem::new(GearBonus::Endurance, GearRarity::Rare));
+        gear.equip(suit).unwrap();
+        assert!(gear.salvage(suit).is_err());
+
+        gear.unequip(GearSlot::Suit);
+        let item = gear.salvage(suit).unwrap();
+        assert!(gear.items().is_empty());
+        assert_eq!(
+            item.salvage_value().get_amount(ResourceType::Metal),
+            2 * GEAR_SALVAGE_METAL_PER_TIER
+        );
+
+        // Rolled gear always fits the slot of its bonus
+        for value in [0, 7, 123, 4_567, 98_765_432] {
+            let item = GearItem::roll(value);
+            assert_eq!(item.slot(), item.bonus.slot());
+        }
+        assert_eq!(GearItem::roll(3).rarity, GearRarity::Prototype);
+        assert_eq!(GearItem::roll(99).rarity, GearRarity::Common);
+    }
+}
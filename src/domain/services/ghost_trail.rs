@@ -0,0 +1,252 @@
+//! Ghost Trails - Breadcrumbs of previous runs on the same seed
+//!
+//! A finished run leaves a breadcrumb of where the player stood at the end
+//! of each day. Breadcrumbs are stored delta and run-length encoded, keyed by
+//! world seed, and only the best few runs per seed are kept. Ghost trails are
+//! purely informational and never feed back into gameplay.
+
+use crate::domain::constants::GHOST_MAX_RUNS_PER_SEED;
+use crate::domain::entities::Map;
+use crate::domain::value_objects::{Position3D, TileCoordinate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The same step repeated `count` times
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrailStep {
+    pub dx: i32,
+    pub dy: i32,
+    pub dz: i32,
+    pub count: u32,
+}
+
+/// Compressed list of position samples
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Breadcrumb {
+    start: Option<Position3D>,
+    steps: Vec<TrailStep>,
+}
+
+impl Breadcrumb {
+    /// Compress position samples into start position plus repeated steps
+    pub fn compress(samples: &[Position3D]) -> Self {
+        let Some((&start, rest)) = samples.split_first() else {
+            return Self::default();
+        };
+
+        let mut steps: Vec<TrailStep> = Vec::new();
+        let mut previous = start;
+        for &sample in rest {
+            let (dx, dy, dz) = (
+                sample.x - previous.x,
+                sample.y - previous.y,
+                sample.z - previous.z,
+            );
+            match steps.last_mut() {
+                Some(step) if (step.dx, step.dy, step.dz) == (dx, dy, dz) => step.count += 1,
+                _ => steps.push(TrailStep {
+                    dx,
+                    dy,
+                    dz,
+                    count: 1,
+                }),
+            }
+            previous = sample;
+        }
+
+        Self {
+            start: Some(start),
+            steps,
+        }
+    }
+
+    /// Expand back into the original position samples
+    pub fn expand(&self) -> Vec<Position3D> {
+        let Some(start) = self.start else {
+            return Vec::new();
+        };
+
+        let mut samples = vec![start];
+        let mut current = start;
+        for step in &self.steps {
+            for _ in 0..step.count {
+                current = current.offset(step.dx, step.dy, step.dz);
+                samples.push(current);
+            }
+        }
+        samples
+    }
+
+    /// Number of encoded steps (compressed size)
+    pub fn step_count(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Check if the breadcrumb holds no samples
+    pub fn is_empty(&self) -> bool {
+        self.start.is_none()
+    }
+}
+
+/// A finished run as remembered for its seed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GhostRun {
+    pub score: u32,
+    pub days: u32,
+    pub trail: Breadcrumb,
+}
+
+impl GhostRun {
+    /// Remember a run from its per-day position samples
+    pub fn new(score: u32, days: u32, samples: &[Position3D]) -> Self {
+        Self {
+            score,
+            days,
+            trail: Breadcrumb::compress(samples),
+        }
+    }
+
+    /// Trail positions the player has already explored in the current run
+    ///
+    /// Unexplored or unloaded tiles are left out so the ghost never reveals
+    /// terrain ahead of the player.
+    pub fn visible_trail(&self, map: &Map) -> Vec<Position3D> {
+        self.trail
+            .expand()
+            .into_iter()
+            .filter(|position| {
+                map.get_tile(&TileCoordinate::from(*position))
+                    .is_some_and(|tile| tile.is_explored())
+            })
+            .collect()
+    }
+}
+
+/// Best previous runs per world seed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GhostBook {
+    runs: BTreeMap<u64, Vec<GhostRun>>,
+}
+
+impl GhostBook {
+    /// Create an empty ghost book
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember a run, keeping only the best runs for its seed
+    ///
+    /// Returns false when the run scored too low to be kept. Ties keep the
+    /// older run.
+    pub fn record(&mut self, seed: u64, run: GhostRun) -> bool {
+        let runs = self.runs.entry(seed).or_default();
+        let index = runs.partition_point(|kept| kept.score >= run.score);
+        if index >= GHOST_MAX_RUNS_PER_SEED {
+            return false;
+        }
+        runs.insert(index, run);
+        runs.truncate(GHOST_MAX_RUNS_PER_SEED);
+        true
+    }
+
+    /// Best run recorded for a seed
+    pub fn best(&self, seed: u64) -> Option<&GhostRun> {
+        self.runs.get(&seed).and_then(|runs| runs.first())
+    }
+
+    /// All kept runs for a seed, best first
+    pub fn runs_for(&self, seed: u64) -> &[GhostRun] {
+        self.runs.get(&seed).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::MapTile;
+    use crate::domain::value_objects::terrain::{Elevation, TerrainType};
+    use crate::domain::value_objects::EntityId;
+
+    #[test]
+    fn breadcrumb_round_trips_and_compresses_repeats() {
+        let samples = vec![
+            Position3D::new(0, 0, 0),
+            Position3D::new(2, 0, 0),
+            Position3D::new(4, 0, 0),
+            Position3D::new(6, 0, 0),
+            Position3D::new(6, -3, 0),
+            Position3D::new(6, -3, 0),
+            Position3D::new(-1, 5, 0),
+        ];
+
+        let breadcrumb = Breadcrumb::compress(&samples);
+
+        assert_eq!(breadcrumb.expand(), samples);
+        // Three identical eastward days collapse into one step
+        assert_eq!(breadcrumb.step_count(), 4);
+
+        let empty = Breadcrumb::compress(&[]);
+        assert!(empty.is_empty());
+        assert!(empty.expand().is_empty());
+
+        let single = Breadcrumb::compress(&[Position3D::new(3, 3, 0)]);
+        assert_eq!(single.expand(), vec![Position3D::new(3, 3, 0)]);
+    }
+
+    #[test]
+    fn visible_trail_only_includes_explored_tiles() {
+        let mut map = Map::new(EntityId::generate(), "Ghosts".to_string(), 1).unwrap();
+        map.set_tile(
+            TileCoordinate::new(0, 0, 0),
+            MapTile::new(TerrainType::Plains, Elevation::sea_level(), true),
+        );
+        map.set_tile(
+            TileCoordinate::new(1, 0, 0),
+            MapTile::new(TerrainType::Plains, Elevation::sea_level(), false),
+        );
+        map.set_tile(
+            TileCoordinate::new(2, 0, 0),
+            MapTile::new(TerrainType::Forest, Elevation::sea_level(), true),
+        );
+        let run = GhostRun::new(
+            100,
+            4,
+            &[
+                Position3D::new(0, 0, 0),
+                Position3D::new(1, 0, 0),
+                Position3D::new(2, 0, 0),
+                Position3D::new(9, 9, 0),
+            ],
+        );
+
+        assert_eq!(
+            run.visible_trail(&map),
+            vec![Position3D::new(0, 0, 0), Position3D::new(2, 0, 0)]
+        );
+    }
+
+    #[test]
+    fn book_keeps_best_runs_per_seed() {
+        let mut book = GhostBook::new();
+        let trail = [Position3D::origin()];
+        for score in [500, 1200, 300] {
+            assert!(book.record(7, GhostRun::new(score, 3, &trail)));
+        }
+
+        // A better run evicts the lowest score
+        assert!(book.record(7, GhostRun::new(900, 5, &trail)));
+        let scores: Vec<u32> = book.runs_for(7).iter().map(|run| run.score).collect();
+        assert_eq!(scores, vec![1200, 900, 500]);
+
+        // A worse run is not kept
+        assert!(!book.record(7, GhostRun::new(100, 9, &trail)));
+        assert_eq!(book.runs_for(7).len(), GHOST_MAX_RUNS_PER_SEED);
+        assert_eq!(book.best(7).unwrap().score, 1200);
+
+        // Seeds are independent
+        assert!(book.best(8).is_none());
+        assert!(book.record(8, GhostRun::new(1, 1, &trail)));
+        assert_eq!(book.runs_for(7).len(), GHOST_MAX_RUNS_PER_SEED);
+    }
+}
@@ -0,0 +1,52 @@
+//! Hashing - Stable FNV-1a hashes
+//!
+//! Save checksums, run codes and other hashes that are written down or
+//! compared between builds must not change with the Rust release, which
+//! `DefaultHasher` does not promise. They all go through FNV-1a here.
+
+/// FNV-1a offset basis of the 64-bit hash, where a fresh hash starts
+pub const FNV64_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a prime of the 64-bit hash
+const FNV64_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a offset basis of the 32-bit hash
+const FNV32_OFFSET: u32 = 0x811c_9dc5;
+
+/// FNV-1a prime of the 32-bit hash
+const FNV32_PRIME: u32 = 0x0100_0193;
+
+/// 64-bit FNV-1a of `bytes`
+pub fn fnv1a_64(bytes: &[u8]) -> u64 {
+    fnv1a_64_from(FNV64_OFFSET, bytes)
+}
+
+/// 64-bit FNV-1a of `bytes`, continuing from an earlier `hash`
+pub fn fnv1a_64_from(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV64_PRIME)
+    })
+}
+
+/// 32-bit FNV-1a of `bytes`, for checksums that have to stay short
+pub fn fnv1a_32(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(FNV32_OFFSET, |hash, &byte| {
+        (hash ^ byte as u32).wrapping_mul(FNV32_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference_vectors() {
+        assert_eq!(fnv1a_64(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a_64(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a_64(b"foobar"), 0x8594_4171_f739_67e8);
+        assert_eq!(fnv1a_64_from(fnv1a_64(b"foo"), b"bar"), fnv1a_64(b"foobar"));
+        assert_eq!(fnv1a_32(b""), 0x811c_9dc5);
+        assert_eq!(fnv1a_32(b"a"), 0xe40c_292c);
+        assert_eq!(fnv1a_32(b"foobar"), 0xbf9c_f968);
+    }
+}
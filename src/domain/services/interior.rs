@@ -0,0 +1,334 @@
+//! Interior Generation - Compact ruin sub-maps entered from the overworld
+//!
+//! Some constructed overworld tiles hide ruins the player can delve into.
+//! Each interior is a small fixed-layout map: one room per chosen quadrant,
+//! rooms chained by corridors, a loot tile in every room and a single exit.
+//! Generation is deterministic from the overworld seed and site coordinate.
+
+use crate::domain::constants::{
+    INTERIOR_LOOT_AMOUNT, INTERIOR_MAX_ROOMS, INTERIOR_MIN_ROOMS, INTERIOR_SITE_RARITY,
+    INTERIOR_SIZE,
+};
+use crate::domain::entities::{Map, MapTile};
+use crate::domain::services::gear::GearItem;
+use crate::domain::value_objects::resources::ResourceCollection;
+use crate::domain::value_objects::terrain::{Elevation, TerrainType};
+use crate::domain::value_objects::{EntityId, Position3D, ResourceType, TileCoordinate};
+use crate::domain::DomainResult;
+
+/// Resources that can be found on interior loot tiles
+const LOOT_TYPES: [ResourceType; 4] = [
+    ResourceType::Metal,
+    ResourceType::Technology,
+    ResourceType::Data,
+    ResourceType::Alloys,
+];
+
+/// A generated ruin interior and what is left to take from it
+#[derive(Debug, Clone)]
+pub struct Interior {
+    map: Map,
+    entrance: Position3D,
+    exit: Position3D,
+    loot: Vec<Position3D>,
+    room_count: usize,
+}
+
+impl Interior {
+    /// The interior's tile map
+    pub fn map(&self) -> &Map {
+        &self.map
+    }
+
+    /// Mutable access to the interior's tile map (exploration state)
+    pub fn map_mut(&mut self) -> &mut Map {
+        &mut self.map
+    }
+
+    /// Tile the player arrives on
+    pub fn entrance(&self) -> Position3D {
+        self.entrance
+    }
+
+    /// Tile that leads back to the overworld
+    pub fn exit(&self) -> Position3D {
+        self.exit
+    }
+
+    /// Check if a position is the exit
+    pub fn is_exit(&self, position: Position3D) -> bool {
+        self.exit == position
+    }
+
+    /// Loot tiles not taken yet
+    pub fn remaining_loot(&self) -> &[Position3D] {
+        &self.loot
+    }
+
+    /// Number of rooms in the interior
+    pub fn room_count(&self) -> usize {
+        self.room_count
+    }
+
+    /// Gear kept in the vault behind the last room, the same for every visit
+    pub fn vault_gear(&self) -> GearItem {
+        GearItem::roll(InteriorGenerator::mix(
+            self.map.seed() ^ position_hash(self.exit),
+        ))
+    }
+
+    /// Take the loot at a position; each loot tile pays out once
+    pub fn take_loot(&mut self, position: Position3D) -> Option<ResourceCollection> {
+        let index = self.loot.iter().position(|tile| *tile == position)?;
+        self.loot.swap_remove(index);
+
+        let hash = InteriorGenerator::mix(self.map.seed() ^ position_hash(position));
+        let mut resources = ResourceCollection::new();
+        resources.set_amount(
+            LOOT_TYPES[(hash % LOOT_TYPES.len() as u64) as usize],
+            INTERIOR_LOOT_AMOUNT,
+        );
+        Some(resources)
+    }
+}
+
+/// A rectangular room inside an interior
+#[derive(Debug, Clone, Copy)]
+struct Room {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+impl Room {
+    fn center(&self) -> Position3D {
+        Position3D::new(self.x + self.width / 2, self.y + self.height / 2, 0)
+    }
+
+    fn corner(&self) -> Position3D {
+        Position3D::new(self.x, self.y, 0)
+    }
+}
+
+/// Deterministic generator for ruin interiors
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InteriorGenerator;
+
+impl InteriorGenerator {
+    /// Create a new interior generator
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check if an overworld tile hides an enterable ruin
+    pub fn is_delve_site(
+        &self,
+        world_seed: u64,
+        coordinate: TileCoordinate,
+        terrain: TerrainType,
+    ) -> bool {
+        terrain == TerrainType::Constructed
+            && Self::site_seed(world_seed, coordinate).is_multiple_of(INTERIOR_SITE_RARITY)
+    }
+
+    /// Generate the interior behind a delve site
+    pub fn generate(&self, world_seed: u64, site: TileCoordinate) -> DomainResult<Interior> {
+        let seed = Self::site_seed(world_seed, site);
+        let mut state = seed;
+        let mut next = move || {
+            state = Self::mix(state);
+            state
+        };
+
+        let mut map = Map::new(
+            EntityId::generate(),
+            format!("Ruins {}-{}", site.x, site.y),
+            seed,
+        )?;
+        map.mark_fixed_layout();
+
+        // One room per quadrant, quadrants visited in shuffled order
+        let mut quadrants = [0, 1, 2, 3];
+        for i in (1..quadrants.len()).rev() {
+            quadrants.swap(i, (next() % (i as u64 + 1)) as usize);
+        }
+        let room_count = INTERIOR_MIN_ROOMS
+            + (next() % (INTERIOR_MAX_ROOMS - INTERIOR_MIN_ROOMS + 1) as u64) as usize;
+
+        let quadrant_span = (INTERIOR_SIZE - 3) / 2;
+        let rooms: Vec<Room> = quadrants[..room_count]
+            .iter()
+            .map(|&quadrant| {
+                let width = 2 + (next() % 2) as i32;
+                let height = 2 + (next() % 2) as i32;
+                let origin_x = 1 + (quadrant % 2) * (quadrant_span + 1);
+                let origin_y = 1 + (quadrant / 2) * (quadrant_span + 1);
+                Room {
+                    x: origin_x + (next() % (quadrant_span - width + 1) as u64) as i32,
+                    y: origin_y + (next() % (quadrant_span - height + 1) as u64) as i32,
+                    width,
+                    height,
+                }
+            })
+            .collect();
+
+        for room in &rooms {
+            for x in room.x..room.x + room.width {
+                for y in room.y..room.y + room.height {
+                    Self::carve(&mut map, Position3D::new(x, y, 0), TerrainType::Constructed);
+                }
+            }
+        }
+        for pair in rooms.windows(2) {
+            Self::carve_corridor(&mut map, pair[0].center(), pair[1].center());
+        }
+
+        Ok(Interior {
+            map,
+            entrance: rooms[0].center(),
+            exit: rooms[room_count - 1].center(),
+            loot: rooms.iter().map(Room::corner).collect(),
+            room_count,
+        })
+    }
+
+    /// Carve an L-shaped corridor, horizontal leg first
+    fn carve_corridor(map: &mut Map, from: Position3D, to: Position3D) {
+        let (step_x, step_y) = ((to.x - from.x).signum(), (to.y - from.y).signum());
+        let mut current = from;
+        while current.x != to.x {
+            current = current.offset(step_x, 0, 0);
+            Self::carve(map, current, TerrainType::Cave);
+        }
+        while current.y != to.y {
+            current = current.offset(0, step_y, 0);
+            Self::carve(map, current, TerrainType::Cave);
+        }
+    }
+
+    /// Turn a wall into floor; existing floor keeps its terrain
+    fn carve(map: &mut Map, position: Position3D, terrain: TerrainType) {
+        let coordinate = TileCoordinate::from(position);
+        if map.get_tile(&coordinate).is_none() {
+            map.set_tile(
+                coordinate,
+                MapTile::new(terrain, Elevation::sea_level(), false),
+            );
+        }
+    }
+
+    fn site_seed(world_seed: u64, site: TileCoordinate) -> u64 {
+        Self::mix(world_seed ^ position_hash(Position3D::new(site.x, site.y, site.z)))
+    }
+
+    /// SplitMix64 step
+    fn mix(value: u64) -> u64 {
+        let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+fn position_hash(position: Position3D) -> u64 {
+    let mut hash = 17u64;
+    hash = hash.wrapping_mul(31).wrapping_add(position.x as u64);
+    hash = hash.wrapping_mul(31).wrapping_add(position.y as u64);
+    hash.wrapping_mul(31).wrapping_add(position.z as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashSet, VecDeque};
+
+    fn reachable_from(map: &Map, start: Position3D) -> HashSet<Position3D> {
+        let mut seen = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(current) = queue.pop_front() {
+            for next in [
+                current.offset(1, 0, 0),
+                current.offset(-1, 0, 0),
+                current.offset(0, 1, 0),
+                current.offset(0, -1, 0),
+            ] {
+                if map.is_passable(&next) && seen.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        seen
+    }
+
+    #[test]
+    fn interiors_are_bounded_connected_and_exit_reachable() {
+        let generator = InteriorGenerator::new();
+        for x in 0..40 {
+            let interior = generator
+                .generate(1234, TileCoordinate::new(x, -x, 0))
+                .unwrap();
+            let map = interior.map();
+
+            assert!(map.has_fixed_layout());
+            assert!((INTERIOR_MIN_ROOMS..=INTERIOR_MAX_ROOMS).contains(&interior.room_count()));
+            assert_eq!(interior.remaining_loot().len(), interior.room_count());
+            assert_ne!(interior.entrance(), interior.exit());
+
+            // Outer ring stays solid wall
+            for coordinate in map.tiles().keys() {
+                assert!(coordinate.x > 0 && coordinate.x < INTERIOR_SIZE - 1);
+                assert!(coordinate.y > 0 && coordinate.y < INTERIOR_SIZE - 1);
+            }
+
+            // Every floor tile, the exit and all loot connect to the entrance
+            let reachable = reachable_from(map, interior.entrance());
+            assert_eq!(reachable.len(), map.tiles().len());
+            assert!(reachable.contains(&interior.exit()));
+            for loot in interior.remaining_loot() {
+                assert!(reachable.contains(loot));
+            }
+        }
+    }
+
+    #[test]
+    fn generation_is_deterministic_per_site() {
+        let generator = InteriorGenerator::new();
+        let site = TileCoordinate::new(7, 3, 0);
+        let a = generator.generate(99, site).unwrap();
+        let b = generator.generate(99, site).unwrap();
+
+        assert_eq!(a.map().tiles(), b.map().tiles());
+        assert_eq!(a.remaining_loot(), b.remaining_loot());
+        assert_eq!(a.exit(), b.exit());
+        assert_eq!(a.vault_gear(), b.vault_gear());
+    }
+
+    #[test]
+    fn loot_pays_out_once() {
+        let mut interior = InteriorGenerator::new()
+            .generate(5, TileCoordinate::new(1, 1, 0))
+            .unwrap();
+        let loot = interior.remaining_loot()[0];
+
+        let found = interior.take_loot(loot).unwrap();
+        let amounts = found.amounts();
+        assert_eq!(amounts.len(), 1);
+        assert_eq!(amounts[0].amount, INTERIOR_LOOT_AMOUNT);
+        assert!(interior.take_loot(loot).is_none());
+        assert!(!interior.remaining_loot().contains(&loot));
+    }
+
+    #[test]
+    fn only_some_constructed_tiles_are_delve_sites() {
+        let generator = InteriorGenerator::new();
+        let sites = (0..600)
+            .filter(|&x| {
+                generator.is_delve_site(42, TileCoordinate::new(x, 0, 0), TerrainType::Constructed)
+            })
+            .count();
+
+        assert!(sites > 0 && sites < 600);
+        assert!(!generator.is_delve_site(42, TileCoordinate::new(0, 0, 0), TerrainType::Plains));
+    }
+}
@@ -0,0 +1,435 @@
+//! Inventory - Bulk transfers to base storage and list sorting
+//!
+//! Standing on the base tile, the player can move whole loads between their
+//! cargo and base storage instead of one resource type at a time. Finds out
+//! in the field go into the cargo the same way, up to the carrying capacity
+//! set by Strength; the rest is left where it was found. Transfers
+//! are planned first: a plan lists what moves and what has to stay behind
+//! because storage or carrying capacity ran out, so the caller can apply it
+//! in one go and report partial transfers. Resource types are filled in
+//! their canonical order.
+//!
+//! The inventory list can be sorted by amount, type, rarity or recent
+//! change. Sorting is stable, so ties keep the canonical type order.
+//!
+//! Next to the cargo the player carries single-use items. They are counted
+//! per kind, crafted from cargo or found in the world, and used up one at a
+//! time.
+
+use crate::domain::constants::{
+    INVENTORY_RESERVE_ENERGY_PER_DAY, INVENTORY_RESERVE_FOOD_PER_DAY, SCOUT_PROBE_CRAFT_ALLOYS,
+    SCOUT_PROBE_CRAFT_TECHNOLOGY,
+};
+use crate::domain::value_objects::resources::ResourceCollection;
+use crate::domain::value_objects::ResourceType;
+use crate::domain::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Resources to move in a bulk transfer
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransferPlan {
+    /// What the transfer moves
+    pub moved: ResourceCollection,
+    /// What did not fit and stays where it was
+    pub left_behind: ResourceCollection,
+}
+
+impl TransferPlan {
+    /// Check if nothing moves
+    pub fn is_empty(&self) -> bool {
+        self.moved.is_empty()
+    }
+
+    /// Check if some resources could not be moved
+    pub fn is_partial(&self) -> bool {
+        !self.left_behind.is_empty()
+    }
+}
+
+/// Plans bulk transfers between cargo and base storage
+pub struct BulkTransfer;
+
+impl BulkTransfer {
+    /// Food and Energy kept back for a number of days in the field
+    pub fn supply_reserve(days: u32) -> ResourceCollection {
+        let mut reserve = ResourceCollection::new();
+        reserve.set_amount(
+            ResourceType::Food,
+            days.saturating_mul(INVENTORY_RESERVE_FOOD_PER_DAY),
+        );
+        reserve.set_amount(
+            ResourceType::Energy,
+            days.saturating_mul(INVENTORY_RESERVE_ENERGY_PER_DAY),
+        );
+        reserve
+    }
+
+    /// Deposit the cargo into storage, keeping `reserve` in the cargo
+    ///
+    /// Reserved amounts are not reported as left behind.
+    pub fn plan_deposit(
+        cargo: &ResourceCollection,
+        reserve: &ResourceCollection,
+        free_capacity: u32,
+    ) -> TransferPlan {
+        let mut offered = ResourceCollection::new();
+        for resource_type in ResourceType::all() {
+            let spare = cargo
+                .get_amount(resource_type)
+                .saturating_sub(reserve.get_amount(resource_type));
+            offered.set_amount(resource_type, spare);
+        }
+        Self::fill(&offered, free_capacity)
+    }
+
+    /// Withdraw from storage until the cargo is full
+    pub fn plan_withdraw(storage: &ResourceCollection, free_capacity: u32) -> TransferPlan {
+        Self::fill(storage, free_capacity)
+    }
+
+    /// Pick up a find in the field, leaving behind what does not fit
+    pub fn plan_pickup(found: &ResourceCollection, free_capacity: u32) -> TransferPlan {
+        Self::fill(found, free_capacity)
+    }
+
+    /// Take from `source` in type order until `capacity` is used up
+    fn fill(source: &ResourceCollection, capacity: u32) -> TransferPlan {
+        let mut plan = TransferPlan::default();
+        let mut remaining = capacity;
+        for resource_type in ResourceType::all() {
+            let available = source.get_amount(resource_type);
+            let taken = available.min(remaining);
+            remaining -= taken;
+            plan.moved.set_amount(resource_type, taken);
+            plan.left_behind
+                .set_amount(resource_type, available - taken);
+        }
+        plan
+    }
+}
+
+/// Order of the inventory list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum InventorySortMode {
+    /// Largest amount first
+    #[default]
+    Amount,
+    /// Canonical resource type order
+    Type,
+    /// Rarest first
+    Rarity,
+    /// Largest change since the panel was opened first
+    RecentChange,
+}
+
+impl InventorySortMode {
+    /// The next mode when cycling
+    pub fn next(self) -> Self {
+        match self {
+            InventorySortMode::Amount => InventorySortMode::Type,
+            InventorySortMode::Type => InventorySortMode::Rarity,
+            InventorySortMode::Rarity => InventorySortMode::RecentChange,
+            InventorySortMode::RecentChange => InventorySortMode::Amount,
+        }
+    }
+
+    /// Label shown in the panel
+    pub fn label(self) -> &'static str {
+        match self {
+            InventorySortMode::Amount => "Amount",
+            InventorySortMode::Type => "Type",
+            InventorySortMode::Rarity => "Rarity",
+            InventorySortMode::RecentChange => "Recent change",
+        }
+    }
+}
+
+/// One line of the inventory list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InventoryEntry {
+    pub resource_type: ResourceType,
+    pub amount: u32,
+    /// Change since the panel was opened
+    pub change: i64,
+}
+
+impl InventoryEntry {
+    /// Check if the amount changed since the panel was opened
+    pub fn recently_changed(&self) -> bool {
+        self.change != 0
+    }
+
+    /// Entries for every resource held now or when the panel was opened
+    pub fn list(
+        current: &ResourceCollection,
+        opened_with: &ResourceCollection,
+        mode: InventorySortMode,
+    ) -> Vec<InventoryEntry> {
+        let mut entries: Vec<InventoryEntry> = ResourceType::all()
+            .into_iter()
+            .map(|resource_type| {
+                let amount = current.get_amount(resource_type);
+                InventoryEntry {
+                    resource_type,
+                    amount,
+                    change: amount as i64 - opened_with.get_amount(resource_type) as i64,
+                }
+            })
+            .filter(|entry| entry.amount > 0 || entry.recently_changed())
+            .collect();
+        Self::sort(&mut entries, mode);
+        entries
+    }
+
+    /// Sort entries; ties keep their current order
+    pub fn sort(entries: &mut [InventoryEntry], mode: InventorySortMode) {
+        match mode {
+            InventorySortMode::Amount => {
+                entries.sort_by_key(|entry| std::cmp::Reverse(entry.amount))
+            }
+            InventorySortMode::Type => entries.sort_by_key(|entry| {
+                ResourceType::all()
+                    .iter()
+                    .position(|resource_type| *resource_type == entry.resource_type)
+            }),
+            InventorySortMode::Rarity => {
+                entries.sort_by_key(|entry| std::cmp::Reverse(entry.resource_type.rarity()))
+            }
+            InventorySortMode::RecentChange => {
+                entries.sort_by_key(|entry| std::cmp::Reverse(entry.change.unsigned_abs()))
+            }
+        }
+    }
+}
+
+/// Kinds of single-use items
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ConsumableKind {
+    /// Drone that reveals a corridor of the map, see `scout_probe`
+    ScoutProbe,
+}
+
+impl ConsumableKind {
+    /// Every kind in display order
+    pub fn all() -> [ConsumableKind; 1] {
+        [ConsumableKind::ScoutProbe]
+    }
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConsumableKind::ScoutProbe => "Scout Probe",
+        }
+    }
+
+    /// Cargo spent to craft one
+    pub fn craft_inputs(&self) -> &'static [(ResourceType, u32)] {
+        match self {
+            ConsumableKind::ScoutProbe => &[
+                (ResourceType::Alloys, SCOUT_PROBE_CRAFT_ALLOYS),
+                (ResourceType::Technology, SCOUT_PROBE_CRAFT_TECHNOLOGY),
+            ],
+        }
+    }
+
+    /// Crafting inputs as a collection, ready to be paid
+    pub fn craft_cost(&self) -> DomainResult<ResourceCollection> {
+        ResourceCollection::cost(self.craft_inputs())
+    }
+}
+
+/// Single-use items held by the player
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Consumables {
+    counts: BTreeMap<ConsumableKind, u32>,
+}
+
+impl Consumables {
+    /// Create an empty set of consumables
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many of a kind are held
+    pub fn count(&self, kind: ConsumableKind) -> u32 {
+        self.counts.get(&kind).copied().unwrap_or(0)
+    }
+
+    /// Check if nothing is held
+    pub fn is_empty(&self) -> bool {
+        self.counts.values().all(|count| *count == 0)
+    }
+
+    /// Add items of a kind, returning the new count
+    pub fn add(&mut self, kind: ConsumableKind, amount: u32) -> u32 {
+        let count = self.counts.entry(kind).or_insert(0);
+        *count = count.saturating_add(amount);
+        *count
+    }
+
+    /// Use up one item of a kind, returning how many are left
+    pub fn consume(&mut self, kind: ConsumableKind) -> DomainResult<u32> {
+        match self.counts.get_mut(&kind) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                Ok(*count)
+            }
+            _ => Err(DomainError::InsufficientResources(format!(
+                "No {} left",
+                kind.name()
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collection(amounts: &[(ResourceType, u32)]) -> ResourceCollection {
+        let mut collection = ResourceCollection::new();
+        for &(resource_type, amount) in amounts {
+            collection.set_amount(resource_type, amount);
+        }
+        collection
+    }
+
+    #[test]
+    fn deposit_all_moves_everything_that_fits() {
+        let cargo = collection(&[(ResourceType::Metal, 40), (ResourceType::Food, 20)]);
+
+        let plan = BulkTransfer::plan_deposit(&cargo, &ResourceCollection::new(), 100);
+        assert_eq!(plan.moved, cargo);
+        assert!(!plan.is_partial());
+
+        // Metal comes first in type order and fills the last 50 slots
+        let plan = BulkTransfer::plan_deposit(&cargo, &ResourceCollection::new(), 50);
+        assert_eq!(plan.moved.get_amount(ResourceType::Metal), 40);
+        assert_eq!(plan.moved.get_amount(ResourceType::Food), 10);
+        assert_eq!(plan.left_behind, collection(&[(ResourceType::Food, 10)]));
+
+        let plan = BulkTransfer::plan_deposit(&cargo, &ResourceCollection::new(), 0);
+        assert!(plan.is_empty());
+        assert_eq!(plan.left_behind, cargo);
+    }
+
+    #[test]
+    fn deposit_keeps_the_supply_reserve() {
+        let reserve = BulkTransfer::supply_reserve(2);
+        assert_eq!(
+            reserve.get_amount(ResourceType::Food),
+            2 * INVENTORY_RESERVE_FOOD_PER_DAY
+        );
+        assert_eq!(
+            reserve.get_amount(ResourceType::Energy),
+            2 * INVENTORY_RESERVE_ENERGY_PER_DAY
+        );
+        assert!(BulkTransfer::supply_reserve(0).is_empty());
+
+        let cargo = collection(&[
+            (ResourceType::Food, 25),
+            (ResourceType::Energy, 2),
+            (ResourceType::Metal, 7),
+        ]);
+        let plan = BulkTransfer::plan_deposit(&cargo, &reserve, 1000);
+        assert_eq!(
+            plan.moved.get_amount(ResourceType::Food),
+            25 - 2 * INVENTORY_RESERVE_FOOD_PER_DAY
+        );
+        assert_eq!(plan.moved.get_amount(ResourceType::Energy), 0);
+        assert_eq!(plan.moved.get_amount(ResourceType::Metal), 7);
+        assert!(!plan.is_partial());
+    }
+
+    #[test]
+    fn withdraw_stops_at_carrying_capacity() {
+        let storage = collection(&[(ResourceType::Energy, 30), (ResourceType::Alloys, 30)]);
+
+        let plan = BulkTransfer::plan_withdraw(&storage, 45);
+        assert_eq!(plan.moved.get_amount(ResourceType::Energy), 30);
+        assert_eq!(plan.moved.get_amount(ResourceType::Alloys), 15);
+        assert_eq!(plan.left_behind, collection(&[(ResourceType::Alloys, 15)]));
+        assert_eq!(
+            plan.moved.storage_requirement() + plan.left_behind.storage_requirement(),
+            storage.storage_requirement()
+        );
+    }
+
+    #[test]
+    fn sorting_is_stable_and_flags_changes() {
+        let opened = collection(&[(ResourceType::Metal, 10), (ResourceType::Data, 10)]);
+        let current = collection(&[
+            (ResourceType::Metal, 10),
+            (ResourceType::Energy, 10),
+            (ResourceType::Food, 10),
+            (ResourceType::Organics, 4),
+        ]);
+
+        let order = |mode| {
+            InventoryEntry::list(&current, &opened, mode)
+                .iter()
+                .map(|entry| entry.resource_type)
+                .collect::<Vec<_>>()
+        };
+
+        // Equal amounts keep type order; Data was emptied but stays listed
+        assert_eq!(
+            order(InventorySortMode::Amount),
+            vec![
+                ResourceType::Metal,
+                ResourceType::Energy,
+                ResourceType::Food,
+                ResourceType::Organics,
+                ResourceType::Data,
+            ]
+        );
+        // Energy and Organics share rarity 3
+        assert_eq!(
+            order(InventorySortMode::Rarity)[..3],
+            [
+                ResourceType::Data,
+                ResourceType::Energy,
+                ResourceType::Organics
+            ]
+        );
+        assert_eq!(
+            order(InventorySortMode::RecentChange)[..4],
+            [
+                ResourceType::Energy,
+                ResourceType::Food,
+                ResourceType::Data,
+                ResourceType::Organics,
+            ]
+        );
+
+        let entries = InventoryEntry::list(&current, &opened, InventorySortMode::Type);
+        assert!(!entries[0].recently_changed());
+        assert_eq!(entries.iter().filter(|e| e.recently_changed()).count(), 4);
+
+        let mut mode = InventorySortMode::default();
+        for _ in 0..4 {
+            mode = mode.next();
+        }
+        assert_eq!(mode, InventorySortMode::default());
+    }
+
+    #[test]
+    fn consumables_are_used_up_one_at_a_time() {
+        let mut consumables = Consumables::new();
+        assert!(consumables.is_empty());
+        assert!(consumables.consume(ConsumableKind::ScoutProbe).is_err());
+
+        assert_eq!(consumables.add(ConsumableKind::ScoutProbe, 2), 2);
+        assert_eq!(consumables.consume(ConsumableKind::ScoutProbe).unwrap(), 1);
+        assert_eq!(consumables.consume(ConsumableKind::ScoutProbe).unwrap(), 0);
+        assert!(consumables.consume(ConsumableKind::ScoutProbe).is_err());
+        assert!(consumables.is_empty());
+
+        let cost = ConsumableKind::ScoutProbe.craft_cost().unwrap();
+        assert_eq!(
+            cost.get_amount(ResourceType::Alloys),
+            SCOUT_PROBE_CRAFT_ALLOYS
+        );
+    }
+}
@@ -0,0 +1,240 @@
+//! Low Movement Guard - Catch the player before an unplanned forced rest
+//!
+//! After each completed move the remaining movement points are compared with
+//! a threshold chosen in the settings. Warn mode only raises a warning; pause
+//! mode holds further movement until the player confirms. Confirming with
+//! "don't ask again today" silences the guard until the next rest.
+
+use crate::domain::value_objects::terrain::TerrainType;
+use crate::domain::value_objects::Position3D;
+use serde::{Deserialize, Serialize};
+
+/// How the guard reacts when movement points run low
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LowPointsGuardMode {
+    Off,
+    /// HUD pulse and a log warning
+    #[default]
+    Warn,
+    /// Hold movement until the player confirms
+    Pause,
+}
+
+/// What the guard asks for after a move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LowPointsAlert {
+    None,
+    Warn,
+    Pause,
+}
+
+/// Confirmation and suppression state of the guard
+#[derive(Debug, Clone, Default, bevy::prelude::Resource)]
+pub struct LowPointsGuard {
+    awaiting_confirmation: bool,
+    suppressed_today: bool,
+}
+
+impl LowPointsGuard {
+    /// Create a guard with nothing pending
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check the points left after a move has been fully applied
+    ///
+    /// In pause mode an alert also holds movement until [`Self::confirm`].
+    pub fn evaluate(
+        &mut self,
+        movement_points: u8,
+        threshold: u8,
+        mode: LowPointsGuardMode,
+    ) -> LowPointsAlert {
+        if movement_points > threshold || self.suppressed_today {
+            return LowPointsAlert::None;
+        }
+        match mode {
+            LowPointsGuardMode::Off => LowPointsAlert::None,
+            LowPointsGuardMode::Warn => LowPointsAlert::Warn,
+            LowPointsGuardMode::Pause => {
+                self.awaiting_confirmation = true;
+                LowPointsAlert::Pause
+            }
+        }
+    }
+
+    /// Check if movement input must wait for a confirmation
+    pub fn blocks_movement(&self) -> bool {
+        self.awaiting_confirmation
+    }
+
+    /// Check if the player asked not to be warned again today
+    pub fn is_suppressed(&self) -> bool {
+        self.suppressed_today
+    }
+
+    /// Release held movement, optionally silencing the guard until the next rest
+    pub fn confirm(&mut self, dont_ask_again_today: bool) {
+        self.awaiting_confirmation = false;
+        if dont_ask_again_today {
+            self.suppressed_today = true;
+        }
+    }
+
+    /// A rest starts a new day: points are back and the guard listens again
+    pub fn start_new_day(&mut self) {
+        self.awaiting_confirmation = false;
+        self.suppressed_today = false;
+    }
+}
+
+/// Nearby options listed when the guard pauses
+#[derive(Debug, Clone, PartialEq)]
+pub struct SafeOptions {
+    /// Tiles to the base, if it can be reached from here
+    pub base_distance: Option<u32>,
+    /// The current tile is low-danger terrain
+    pub on_safe_ground: bool,
+    /// Average movement points expected after resting
+    pub rest_forecast: f32,
+}
+
+impl SafeOptions {
+    /// Gather the options around `position`
+    pub fn assess(
+        position: Position3D,
+        base: Option<Position3D>,
+        terrain: Option<TerrainType>,
+        rest_forecast: f32,
+    ) -> Self {
+        Self {
+            base_distance: base.map(|base| base.manhattan_distance_2d(&position)),
+            on_safe_ground: terrain.is_some_and(|terrain| TerrainType::safe().contains(&terrain)),
+            rest_forecast,
+        }
+    }
+
+    /// One line per option for the confirmation panel
+    pub fn lines(&self) -> Vec<String> {
+        vec![
+            match self.base_distance {
+                Some(0) => "Base: you are at the base".to_string(),
+                Some(tiles) => format!("Base: {} tiles away", tiles),
+                None => "Base: out of reach from here".to_string(),
+            },
+            if self.on_safe_ground {
+                "This tile is safe ground to rest on".to_string()
+            } else {
+                "This tile is NOT safe ground to rest on".to_string()
+            },
+            format!(
+                "Resting restores about {:.0} movement points",
+                self.rest_forecast
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn threshold_is_inclusive_and_off_mode_stays_quiet() {
+        let mut guard = LowPointsGuard::new();
+        assert_eq!(
+            guard.evaluate(3, 2, LowPointsGuardMode::Warn),
+            LowPointsAlert::None
+        );
+        assert_eq!(
+            guard.evaluate(2, 2, LowPointsGuardMode::Warn),
+            LowPointsAlert::Warn
+        );
+        assert_eq!(
+            guard.evaluate(0, 2, LowPointsGuardMode::Off),
+            LowPointsAlert::None
+        );
+        assert!(!guard.blocks_movement());
+    }
+
+    #[test]
+    fn warn_mode_never_holds_movement() {
+        let mut guard = LowPointsGuard::new();
+        for points in 0..=2 {
+            assert_eq!(
+                guard.evaluate(points, 2, LowPointsGuardMode::Warn),
+                LowPointsAlert::Warn
+            );
+            assert!(!guard.blocks_movement());
+        }
+    }
+
+    #[test]
+    fn pause_mode_holds_movement_until_confirmed() {
+        let mut guard = LowPointsGuard::new();
+        assert_eq!(
+            guard.evaluate(1, 2, LowPointsGuardMode::Pause),
+            LowPointsAlert::Pause
+        );
+        assert!(guard.blocks_movement());
+
+        guard.confirm(false);
+        assert!(!guard.blocks_movement());
+
+        // Without suppression the next low move pauses again
+        assert_eq!(
+            guard.evaluate(1, 2, LowPointsGuardMode::Pause),
+            LowPointsAlert::Pause
+        );
+        assert!(guard.blocks_movement());
+    }
+
+    #[test]
+    fn dont_ask_again_lasts_until_the_next_rest() {
+        let mut guard = LowPointsGuard::new();
+        guard.evaluate(1, 2, LowPointsGuardMode::Pause);
+        guard.confirm(true);
+        assert!(guard.is_suppressed());
+        assert_eq!(
+            guard.evaluate(0, 2, LowPointsGuardMode::Pause),
+            LowPointsAlert::None
+        );
+        assert!(!guard.blocks_movement());
+
+        guard.start_new_day();
+        assert!(!guard.is_suppressed());
+        assert_eq!(
+            guard.evaluate(0, 2, LowPointsGuardMode::Pause),
+            LowPointsAlert::Pause
+        );
+    }
+
+    #[test]
+    fn safe_options_describe_base_and_ground() {
+        let options = SafeOptions::assess(
+            Position3D::new(3, 4, 0),
+            Some(Position3D::origin()),
+            Some(TerrainType::Plains),
+            12.4,
+        );
+        assert_eq!(options.base_distance, Some(7));
+        assert!(options.on_safe_ground);
+        assert_eq!(
+            options.lines(),
+            vec![
+                "Base: 7 tiles away".to_string(),
+                "This tile is safe ground to rest on".to_string(),
+                "Resting restores about 12 movement points".to_string(),
+            ]
+        );
+
+        let exposed = SafeOptions::assess(
+            Position3D::origin(),
+            None,
+            Some(TerrainType::Volcanic),
+            10.0,
+        );
+        assert_eq!(exposed.base_distance, None);
+        assert!(!exposed.on_safe_ground);
+    }
+}
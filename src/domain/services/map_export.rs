@@ -0,0 +1,432 @@
+//! Map Export - The explored world as a picture or as text
+//!
+//! A snapshot covers the explored tiles of one level, north up, with one
+//! cell per tile. Regions wider or taller than `MAP_EXPORT_MAX_CELLS` are
+//! downsampled: each cell then covers a square of tiles and shows the most
+//! common explored terrain in it, or the most important marker. Unexplored
+//! tiles stay blank, and markers on them are left out, so an export shows
+//! no more than the player has seen. A snapshot renders to RGBA pixels for
+//! an image file and to one character per cell for pasting into a report;
+//! both end with a footer line naming the seed and the day.
+
+use crate::domain::constants::{MAP_EXPORT_FOOTER_SCALE, MAP_EXPORT_PIXELS_PER_CELL};
+use crate::domain::entities::Map;
+use crate::domain::value_objects::terrain::TerrainType;
+use crate::domain::value_objects::{Position3D, TileCoordinate};
+
+/// Background of the image footer
+const FOOTER_BACKGROUND: [u8; 4] = [10, 12, 18, 255];
+/// Text of the image footer
+const FOOTER_TEXT: [u8; 4] = [230, 230, 230, 255];
+
+/// Something worth pointing out on an exported map
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ExportMarker {
+    /// A resource node that is not worked out
+    PointOfInterest,
+    /// A waypoint of the active expedition
+    Waypoint,
+    Base,
+    Player,
+}
+
+impl ExportMarker {
+    /// Character in the text export
+    pub fn symbol(&self) -> char {
+        match self {
+            ExportMarker::PointOfInterest => '$',
+            ExportMarker::Waypoint => 'x',
+            ExportMarker::Base => 'B',
+            ExportMarker::Player => '@',
+        }
+    }
+
+    /// Colour in the image export
+    pub fn color(&self) -> (u8, u8, u8) {
+        match self {
+            ExportMarker::PointOfInterest => (0, 230, 200),
+            ExportMarker::Waypoint => (255, 140, 0),
+            ExportMarker::Base => (255, 215, 0),
+            ExportMarker::Player => (255, 255, 255),
+        }
+    }
+}
+
+/// What one cell of an export shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportCell {
+    Unexplored,
+    Terrain(TerrainType),
+    Marker(ExportMarker),
+}
+
+impl ExportCell {
+    /// Character in the text export; unexplored cells are blank
+    pub fn symbol(&self) -> char {
+        match self {
+            ExportCell::Unexplored => ' ',
+            ExportCell::Terrain(terrain) => terrain.legend_char(),
+            ExportCell::Marker(marker) => marker.symbol(),
+        }
+    }
+
+    /// Pixel in the image export; unexplored cells are transparent
+    pub fn rgba(&self) -> [u8; 4] {
+        let (r, g, b) = match self {
+            ExportCell::Unexplored => return [0, 0, 0, 0],
+            ExportCell::Terrain(terrain) => terrain.color(),
+            ExportCell::Marker(marker) => marker.color(),
+        };
+        [r, g, b, 255]
+    }
+}
+
+/// Tiles per cell side so that neither side exceeds `max_cells` cells
+pub fn downsample_step(width: u32, height: u32, max_cells: u32) -> u32 {
+    width.max(height).div_ceil(max_cells.max(1)).max(1)
+}
+
+/// Footer line of an export
+pub fn export_footer(seed: u64, day: u32) -> String {
+    format!("SEED {} - DAY {}", seed, day)
+}
+
+/// Cells of the explored region of one level, ready to render
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapSnapshot {
+    pub columns: u32,
+    pub rows: u32,
+    /// Tiles per cell side
+    pub step: u32,
+    cells: Vec<ExportCell>,
+    footer: String,
+}
+
+impl MapSnapshot {
+    /// Capture the explored tiles of level `z`, or `None` if none are
+    pub fn capture(
+        map: &Map,
+        z: i32,
+        markers: &[(Position3D, ExportMarker)],
+        max_cells: u32,
+        footer: String,
+    ) -> Option<Self> {
+        let explored = map
+            .tiles()
+            .iter()
+            .filter(|(coordinate, tile)| coordinate.z == z && tile.is_explored());
+        let (mut min_x, mut max_x, mut min_y, mut max_y) = (i32::MAX, i32::MIN, i32::MAX, i32::MIN);
+        for (coordinate, _) in explored {
+            min_x = min_x.min(coordinate.x);
+            max_x = max_x.max(coordinate.x);
+            min_y = min_y.min(coordinate.y);
+            max_y = max_y.max(coordinate.y);
+        }
+        if min_x > max_x {
+            return None;
+        }
+
+        let width = (max_x - min_x + 1) as u32;
+        let height = (max_y - min_y + 1) as u32;
+        let step = downsample_step(width, height, max_cells);
+        let columns = width.div_ceil(step);
+        let rows = height.div_ceil(step);
+        let explored_at = |x: i32, y: i32| {
+            map.get_tile(&TileCoordinate::new(x, y, z))
+                .filter(|tile| tile.is_explored())
+                .map(|tile| tile.terrain_type)
+        };
+
+        let mut cells = Vec::with_capacity((columns * rows) as usize);
+        for row in 0..rows {
+            // Rows run from north to south
+            let top = max_y - (row * step) as i32;
+            for column in 0..columns {
+                let left = min_x + (column * step) as i32;
+                let mut counts: Vec<(TerrainType, u32)> = Vec::new();
+                for y in ((top - step as i32 + 1).max(min_y)..=top).rev() {
+                    for x in left..(left + step as i32).min(max_x + 1) {
+                        let Some(terrain) = explored_at(x, y) else {
+                            continue;
+                        };
+                        match counts.iter_mut().find(|(seen, _)| *seen == terrain) {
+                            Some((_, count)) => *count += 1,
+                            None => counts.push((terrain, 1)),
+                        }
+                    }
+                }
+                // The first terrain to reach the highest count wins a tie
+                let terrain = counts
+                    .iter()
+                    .fold(
+                        None,
+                        |best: Option<(TerrainType, u32)>, &(terrain, count)| match best {
+                            Some((_, best_count)) if best_count >= count => best,
+                            _ => Some((terrain, count)),
+                        },
+                    )
+                    .map(|(terrain, _)| terrain);
+                let marker = markers
+                    .iter()
+                    .filter(|(position, _)| {
+                        position.z == z
+                            && (left..left + step as i32).contains(&position.x)
+                            && (top - step as i32 + 1..=top).contains(&position.y)
+                            && explored_at(position.x, position.y).is_some()
+                    })
+                    .map(|(_, marker)| *marker)
+                    .max();
+                cells.push(match (marker, terrain) {
+                    (Some(marker), _) => ExportCell::Marker(marker),
+                    (None, Some(terrain)) => ExportCell::Terrain(terrain),
+                    (None, None) => ExportCell::Unexplored,
+                });
+            }
+        }
+
+        Some(Self {
+            columns,
+            rows,
+            step,
+            cells,
+            footer,
+        })
+    }
+
+    /// Cell at `column` from the west and `row` from the north
+    pub fn cell(&self, column: u32, row: u32) -> ExportCell {
+        if column >= self.columns || row >= self.rows {
+            return ExportCell::Unexplored;
+        }
+        self.cells[(row * self.columns + column) as usize]
+    }
+
+    /// Footer line the renderings end with
+    pub fn footer(&self) -> &str {
+        &self.footer
+    }
+
+    /// One line of characters per row, then the footer
+    pub fn to_ascii(&self) -> String {
+        let mut text = String::with_capacity(((self.columns + 1) * self.rows) as usize);
+        for row in 0..self.rows {
+            let line: String = (0..self.columns)
+                .map(|column| self.cell(column, row).symbol())
+                .collect();
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        text.push_str(&self.footer);
+        text.push('\n');
+        text
+    }
+
+    /// RGBA pixels of the map with the footer strip below it
+    ///
+    /// Returns the width, the height and the pixels row by row.
+    pub fn to_rgba(&self) -> (u32, u32, Vec<u8>) {
+        let cell = MAP_EXPORT_PIXELS_PER_CELL;
+        let scale = MAP_EXPORT_FOOTER_SCALE;
+        let text_width = footer_text_width(&self.footer) * scale;
+        let width = (self.columns * cell).max(text_width + 2 * scale);
+        let map_height = self.rows * cell;
+        let footer_height = (GLYPH_HEIGHT + 2) * scale;
+        let height = map_height + footer_height;
+
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+        let mut put = |x: u32, y: u32, rgba: [u8; 4]| {
+            let index = ((y * width + x) * 4) as usize;
+            pixels[index..index + 4].copy_from_slice(&rgba);
+        };
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let rgba = self.cell(column, row).rgba();
+                for dy in 0..cell {
+                    for dx in 0..cell {
+                        put(column * cell + dx, row * cell + dy, rgba);
+                    }
+                }
+            }
+        }
+        for y in map_height..height {
+            for x in 0..width {
+                put(x, y, FOOTER_BACKGROUND);
+            }
+        }
+        for (index, ch) in self.footer.chars().enumerate() {
+            let glyph = glyph(ch);
+            let left = scale + index as u32 * (GLYPH_WIDTH + 1) * scale;
+            for (gy, bits) in glyph.iter().enumerate() {
+                for gx in 0..GLYPH_WIDTH {
+                    if bits & (1 << (GLYPH_WIDTH - 1 - gx)) == 0 {
+                        continue;
+                    }
+                    for sy in 0..scale {
+                        for sx in 0..scale {
+                            put(
+                                left + gx * scale + sx,
+                                map_height + scale + gy as u32 * scale + sy,
+                                FOOTER_TEXT,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        (width, height, pixels)
+    }
+}
+
+const GLYPH_WIDTH: u32 = 3;
+const GLYPH_HEIGHT: u32 = 5;
+
+/// Width of the footer in font pixels, one column apart per character
+fn footer_text_width(footer: &str) -> u32 {
+    let chars = footer.chars().count() as u32;
+    (chars * (GLYPH_WIDTH + 1)).saturating_sub(1)
+}
+
+/// Rows of a footer character, three bits each; unknown characters are blank
+fn glyph(ch: char) -> [u8; GLYPH_HEIGHT as usize] {
+    match ch {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' | 'S' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b111, 0b100, 0b111],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        _ => [0; GLYPH_HEIGHT as usize],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::constants::MAP_EXPORT_MAX_CELLS;
+    use crate::domain::entities::map::MapTile;
+    use crate::domain::value_objects::terrain::Elevation;
+    use crate::domain::value_objects::EntityId;
+
+    fn map_with(tiles: &[(i32, i32, TerrainType, bool)]) -> Map {
+        let mut map = Map::new(EntityId::generate(), "Export".to_string(), 1956).unwrap();
+        for &(x, y, terrain, explored) in tiles {
+            map.set_tile(
+                TileCoordinate::new(x, y, 0),
+                MapTile::new(terrain, Elevation::sea_level(), explored),
+            );
+        }
+        map
+    }
+
+    #[test]
+    fn cells_map_to_characters_and_pixels() {
+        let map = map_with(&[
+            (0, 1, TerrainType::Ocean, true),
+            (1, 1, TerrainType::Forest, true),
+            (0, 0, TerrainType::Plains, true),
+            (1, 0, TerrainType::Plains, true),
+        ]);
+        let markers = [
+            (Position3D::new(1, 0, 0), ExportMarker::Base),
+            (Position3D::new(1, 0, 0), ExportMarker::Player),
+        ];
+        let snapshot =
+            MapSnapshot::capture(&map, 0, &markers, MAP_EXPORT_MAX_CELLS, "F".to_string()).unwrap();
+        assert_eq!((snapshot.columns, snapshot.rows, snapshot.step), (2, 2, 1));
+        // North is the first row; the player outranks the base
+        assert_eq!(snapshot.to_ascii(), "~T\n.@\nF\n");
+
+        let (r, g, b) = TerrainType::Forest.color();
+        assert_eq!(snapshot.cell(1, 0).rgba(), [r, g, b, 255]);
+        let (width, _, pixels) = snapshot.to_rgba();
+        let at = |x: u32, y: u32| {
+            let index = ((y * width + x) * 4) as usize;
+            [
+                pixels[index],
+                pixels[index + 1],
+                pixels[index + 2],
+                pixels[index + 3],
+            ]
+        };
+        assert_eq!(at(MAP_EXPORT_PIXELS_PER_CELL, 0), [r, g, b, 255]);
+        assert_eq!(
+            at(
+                MAP_EXPORT_PIXELS_PER_CELL * 2 - 1,
+                MAP_EXPORT_PIXELS_PER_CELL
+            ),
+            [255, 255, 255, 255]
+        );
+    }
+
+    #[test]
+    fn large_regions_are_downsampled() {
+        assert_eq!(downsample_step(512, 512, 512), 1);
+        assert_eq!(downsample_step(513, 10, 512), 2);
+        assert_eq!(downsample_step(100, 2000, 512), 4);
+        assert_eq!(downsample_step(0, 0, 512), 1);
+
+        // Five tiles wide, at most two cells: three tiles per cell
+        let map = map_with(&[
+            (0, 0, TerrainType::Desert, true),
+            (1, 0, TerrainType::Forest, true),
+            (2, 0, TerrainType::Forest, true),
+            (3, 0, TerrainType::Ocean, true),
+            (4, 0, TerrainType::Ocean, true),
+        ]);
+        let snapshot = MapSnapshot::capture(&map, 0, &[], 2, String::new()).unwrap();
+        assert_eq!((snapshot.columns, snapshot.rows, snapshot.step), (2, 1, 3));
+        assert_eq!(
+            snapshot.cell(0, 0),
+            ExportCell::Terrain(TerrainType::Forest)
+        );
+        assert_eq!(snapshot.cell(1, 0), ExportCell::Terrain(TerrainType::Ocean));
+    }
+
+    #[test]
+    fn unexplored_tiles_and_their_markers_stay_blank() {
+        let map = map_with(&[
+            (0, 0, TerrainType::Plains, true),
+            (1, 0, TerrainType::Mountains, false),
+            (2, 0, TerrainType::Plains, true),
+        ]);
+        let markers = [(Position3D::new(1, 0, 0), ExportMarker::PointOfInterest)];
+        let snapshot =
+            MapSnapshot::capture(&map, 0, &markers, MAP_EXPORT_MAX_CELLS, String::new()).unwrap();
+        assert_eq!(snapshot.cell(1, 0), ExportCell::Unexplored);
+        assert_eq!(snapshot.to_ascii(), ". .\n\n");
+        assert_eq!(snapshot.cell(1, 0).rgba(), [0, 0, 0, 0]);
+
+        let fogged = map_with(&[(0, 0, TerrainType::Plains, false)]);
+        assert!(
+            MapSnapshot::capture(&fogged, 0, &[], MAP_EXPORT_MAX_CELLS, String::new()).is_none()
+        );
+    }
+
+    #[test]
+    fn footer_names_seed_and_day_under_the_map() {
+        let footer = export_footer(1956, 4);
+        assert_eq!(footer, "SEED 1956 - DAY 4");
+
+        let map = map_with(&[(0, 0, TerrainType::Plains, true)]);
+        let snapshot =
+            MapSnapshot::capture(&map, 0, &[], MAP_EXPORT_MAX_CELLS, footer.clone()).unwrap();
+        assert!(snapshot.to_ascii().ends_with("SEED 1956 - DAY 4\n"));
+
+        // A one-tile map is narrower than the footer, which sets the width
+        let (width, height, pixels) = snapshot.to_rgba();
+        let scale = MAP_EXPORT_FOOTER_SCALE;
+        assert_eq!(width, (footer.len() as u32 * 4 - 1) * scale + 2 * scale);
+        assert_eq!(height, MAP_EXPORT_PIXELS_PER_CELL + 7 * scale);
+        assert_eq!(pixels.len() as u32, width * height * 4);
+        assert!(pixels.chunks(4).any(|pixel| pixel == FOOTER_TEXT));
+    }
+}
@@ -2,25 +2,35 @@
 //!
 //! This service encapsulates all map generation logic, including terrain
 //! generation, resource node placement, and tile cache management.
+//! Terrain and elevation come from the layered noise fields of
+//! `terrain_noise`, so biomes form large connected regions.
 //! It follows DDD principles by keeping generation logic separate from
 //! the Map entity itself.
 
+use crate::domain::services::chunk_ownership::{owning_chunk, validate_generation, Footprint};
+use crate::domain::services::interior::InteriorGenerator;
+use crate::domain::services::terrain_noise::{GenerationConfig, TerrainNoise, TerrainSample};
 use crate::domain::{
     constants,
     entities::map::{Map, MapTile, ResourceNode},
     value_objects::{
-        resources::ResourceType,
+        position::ChunkCoordinate,
+        resources::{ResourceRichness, ResourceType},
         terrain::{Elevation, TerrainType},
         EntityId, Position3D, TileCoordinate,
     },
     DomainResult,
 };
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
 
 /// Service responsible for map generation and management operations
 #[derive(Debug, Clone)]
 pub struct MapService {
     /// Random seed for consistent generation
     seed: u64,
+    /// Noise fields the terrain is drawn from
+    noise: TerrainNoise,
 }
 
 /// Biome types that group terrain logically
@@ -34,21 +44,18 @@ pub enum BiomeType {
     Artificial,  // Constructed, Anomaly
 }
 
-/// Biome configuration with terrain probabilities
-#[derive(Debug, Clone)]
-pub struct BiomeConfig {
-    pub primary_terrain: TerrainType,
-    pub secondary_terrain: TerrainType,
-    pub rare_terrain: Option<TerrainType>,
-    pub primary_weight: u32,
-    pub secondary_weight: u32,
-    pub rare_weight: u32,
-}
-
 impl MapService {
     /// Create a new map service with a random seed
     pub fn new(seed: u64) -> Self {
-        Self { seed }
+        Self::with_config(seed, GenerationConfig::default())
+    }
+
+    /// Create a map service generating with custom thresholds
+    pub fn with_config(seed: u64, config: GenerationConfig) -> Self {
+        Self {
+            seed,
+            noise: TerrainNoise::new(seed, config),
+        }
     }
 
     /// Generate tiles around a player position for movement
@@ -58,14 +65,54 @@ impl MapService {
         map: &mut Map,
         player_position: Position3D,
     ) -> DomainResult<Vec<TileCoordinate>> {
-        let mut generated_tiles = Vec::new();
         let generation_radius =
             constants::FOGGED_VISIBLE_RADIUS + constants::TILE_GENERATION_BUFFER;
 
         // Generate tiles in buffer zone around player
         let needed_positions = player_position.positions_within_distance(generation_radius);
+        self.generate_tiles_at(map, needed_positions)
+    }
+
+    /// Generate the chunks holding the given tiles
+    ///
+    /// Each chunk not yet in the map's ledger is generated whole through
+    /// `generate_owned_chunk`; requested tiles still missing afterwards
+    /// (off the chunk surface, or dropped since) are filled in one by one.
+    /// Fixed-layout maps are left alone. Returns the coordinates generated.
+    pub fn generate_tiles_at(
+        &self,
+        map: &mut Map,
+        positions: impl IntoIterator<Item = Position3D>,
+    ) -> DomainResult<Vec<TileCoordinate>> {
+        if map.has_fixed_layout() {
+            return Ok(Vec::new());
+        }
+
+        let positions: Vec<Position3D> = positions.into_iter().collect();
+        let mut chunks: Vec<ChunkCoordinate> = positions
+            .iter()
+            .map(|pos| owning_chunk(*pos))
+            .filter(|chunk| !map.chunk_features().is_generated(*chunk))
+            .collect();
+        chunks.sort_by_key(|chunk| (chunk.z, chunk.y, chunk.x));
+        chunks.dedup();
+
+        let mut generated_tiles = Vec::new();
+        for chunk in chunks {
+            generated_tiles.extend(self.generate_owned_chunk(map, chunk)?);
+        }
+        generated_tiles.extend(self.generate_missing_tiles(map, positions)?);
+        Ok(generated_tiles)
+    }
 
-        for pos in needed_positions {
+    /// Generate the given tiles that are not loaded yet, tile by tile
+    fn generate_missing_tiles(
+        &self,
+        map: &mut Map,
+        positions: impl IntoIterator<Item = Position3D>,
+    ) -> DomainResult<Vec<TileCoordinate>> {
+        let mut generated_tiles = Vec::new();
+        for pos in positions {
             let coord = TileCoordinate::from(pos);
             if !map.tiles().contains_key(&coord) {
                 let tile = self.generate_single_tile(pos)?;
@@ -85,33 +132,60 @@ impl MapService {
     }
 
     /// Generate a chunk of tiles around a center position
+    ///
+    /// The square is routed through `generate_tiles_at`, so the owned
+    /// chunks under it are generated whole.
     pub fn generate_chunk(
         &self,
         map: &mut Map,
         chunk_center: Position3D,
         chunk_size: i32,
     ) -> DomainResult<()> {
-        for x in -chunk_size / 2..=chunk_size / 2 {
-            for y in -chunk_size / 2..=chunk_size / 2 {
-                let pos = Position3D::new(chunk_center.x + x, chunk_center.y + y, chunk_center.z);
-                let coord = TileCoordinate::from(pos);
-
-                // Only generate if tile doesn't exist
-                if !map.tiles().contains_key(&coord) {
-                    let tile = self.generate_single_tile(pos)?;
-                    map.set_tile(coord, tile);
+        let half = chunk_size / 2;
+        let positions =
+            (-half..=half).flat_map(|x| (-half..=half).map(move |y| chunk_center.offset(x, y, 0)));
+        self.generate_tiles_at(map, positions)?;
+        Ok(())
+    }
 
-                    // Generate resource nodes
-                    if self.should_place_resource_node(&pos) {
-                        if let Some(resource_node) = self.generate_resource_node(&pos)? {
-                            map.add_resource_node(pos, resource_node);
-                        }
-                    }
-                }
-            }
+    /// Generate the tiles a chunk owns on its surface level
+    ///
+    /// Only tiles floor-dividing into `chunk` are written, so neighbouring
+    /// chunks never both place nodes on their shared border. Ruins found
+    /// in the chunk are proposed to the map's feature ledger before the
+    /// chunk is marked generated. Debug builds validate the map against
+    /// the ledger afterwards. Returns the coordinates generated.
+    pub fn generate_owned_chunk(
+        &self,
+        map: &mut Map,
+        chunk: ChunkCoordinate,
+    ) -> DomainResult<Vec<TileCoordinate>> {
+        let size = constants::MAP_CHUNK_SIZE as i32;
+        let origin = chunk.to_world_origin(size);
+        let positions = (0..size).flat_map(|x| (0..size).map(move |y| origin.offset(x, y, 0)));
+        let generated = self.generate_missing_tiles(map, positions)?;
+
+        let interiors = InteriorGenerator::new();
+        let ruins: Vec<Position3D> = generated
+            .iter()
+            .filter(|coord| {
+                map.get_tile(coord).is_some_and(|tile| {
+                    interiors.is_delve_site(map.seed(), **coord, tile.terrain_type)
+                })
+            })
+            .map(|coord| coord.to_position())
+            .collect();
+        let ledger = map.chunk_features_mut();
+        for ruin in ruins {
+            ledger.propose(chunk, self.feature_id(ruin), Footprint::new(ruin, [ruin]));
         }
+        ledger.mark_generated(chunk);
 
-        Ok(())
+        #[cfg(debug_assertions)]
+        for issue in validate_generation(map, map.chunk_features().features()) {
+            bevy::log::error!("Chunk ({}, {}) generation: {}", chunk.x, chunk.y, issue);
+        }
+        Ok(generated)
     }
 
     /// Generate a single tile at a specific position
@@ -119,118 +193,64 @@ impl MapService {
         let terrain = self.generate_terrain_type(position);
         let elevation = self.generate_elevation(position);
 
-        Ok(MapTile::new(terrain, elevation, false))
+        Ok(MapTile::new(terrain, elevation, false).with_richness(self.tile_richness(position)))
     }
 
-    /// Generate terrain type based on position and biome zones
-    fn generate_terrain_type(&self, position: Position3D) -> TerrainType {
-        let biome = self.determine_biome(position);
-        let config = self.get_biome_config(biome);
-        let hash = self.hash_position(position);
-
-        let total_weight = config.primary_weight + config.secondary_weight + config.rare_weight;
-        let roll = hash % (total_weight as u64);
-
-        if roll < config.primary_weight as u64 {
-            config.primary_terrain
-        } else if roll < (config.primary_weight + config.secondary_weight) as u64 {
-            config.secondary_terrain
-        } else if let Some(rare) = config.rare_terrain {
-            rare
-        } else {
-            config.primary_terrain
+    /// Richness of the ground at a position, the same for every visit
+    ///
+    /// Most ground is average; one tile in five is poor and one in ten
+    /// each is rich or abundant.
+    fn tile_richness(&self, position: Position3D) -> ResourceRichness {
+        let roll = (self
+            .hash_position(position)
+            .wrapping_mul(0xBF58_476D_1CE4_E5B9)
+            >> 40)
+            % 10;
+        match roll {
+            0..=1 => ResourceRichness::Poor,
+            2..=7 => ResourceRichness::Average,
+            8 => ResourceRichness::Rich,
+            _ => ResourceRichness::Abundant,
         }
     }
 
-    /// Determine biome type based on position
-    fn determine_biome(&self, position: Position3D) -> BiomeType {
-        let _hash = self.hash_position(position);
-
-        // Create biome zones based on position
-        let biome_x = (position.x as f64 / constants::MAP_CHUNK_SIZE as f64).floor() as i32;
-        let biome_y = (position.y as f64 / constants::MAP_CHUNK_SIZE as f64).floor() as i32;
-
-        let biome_hash = self.hash_biome_coordinate(biome_x, biome_y);
+    /// Terrain and elevation of a position under another roll
+    ///
+    /// The noise fields shift only slightly, so a tile may turn into a
+    /// neighbouring terrain near a border but stays within its region.
+    /// Used to refresh stale tiles.
+    pub fn reroll_details(&self, position: Position3D, salt: u64) -> (TerrainType, Elevation) {
+        let hash = self.hash_position(position) ^ salt;
+        let sample = self
+            .noise
+            .sample(position)
+            .jittered(hash, self.noise.config().reroll_jitter);
+        (
+            self.noise.config().classify(sample),
+            self.elevation_from_roll(sample, hash),
+        )
+    }
 
-        // Generate larger biome zones (16x16 tiles each)
-        match biome_hash % 100 {
-            0..=35 => BiomeType::Temperate, // 35% - Most common, good for gameplay
-            36..=55 => BiomeType::Arid,     // 20% - Desert regions
-            56..=70 => BiomeType::Cold,     // 15% - Mountain/tundra regions
-            71..=80 => BiomeType::Wetlands, // 10% - Swamps and water
-            81..=90 => BiomeType::Underground, // 10% - Caves and crystals
-            _ => BiomeType::Artificial,     // 10% - Constructed/anomaly
-        }
+    /// Generate terrain type from the noise fields at a position
+    fn generate_terrain_type(&self, position: Position3D) -> TerrainType {
+        self.noise.terrain(position)
     }
 
-    /// Get biome configuration for terrain generation
-    fn get_biome_config(&self, biome: BiomeType) -> BiomeConfig {
-        match biome {
-            BiomeType::Temperate => BiomeConfig {
-                primary_terrain: TerrainType::Plains,
-                secondary_terrain: TerrainType::Forest,
-                rare_terrain: Some(TerrainType::Constructed),
-                primary_weight: 60,   // 60% plains
-                secondary_weight: 35, // 35% forest
-                rare_weight: 5,       // 5% constructed
-            },
-            BiomeType::Arid => BiomeConfig {
-                primary_terrain: TerrainType::Desert,
-                secondary_terrain: TerrainType::Plains,
-                rare_terrain: Some(TerrainType::Volcanic),
-                primary_weight: 70,   // 70% desert
-                secondary_weight: 25, // 25% plains (oases)
-                rare_weight: 5,       // 5% volcanic
-            },
-            BiomeType::Cold => BiomeConfig {
-                primary_terrain: TerrainType::Tundra,
-                secondary_terrain: TerrainType::Mountains,
-                rare_terrain: Some(TerrainType::Crystal),
-                primary_weight: 60,   // 60% tundra
-                secondary_weight: 35, // 35% mountains
-                rare_weight: 5,       // 5% crystal formations
-            },
-            BiomeType::Wetlands => BiomeConfig {
-                primary_terrain: TerrainType::Swamp,
-                secondary_terrain: TerrainType::Plains,
-                rare_terrain: Some(TerrainType::Ocean),
-                primary_weight: 65,   // 65% swamp
-                secondary_weight: 30, // 30% plains (dry areas)
-                rare_weight: 5,       // 5% ocean (small lakes)
-            },
-            BiomeType::Underground => BiomeConfig {
-                primary_terrain: TerrainType::Cave,
-                secondary_terrain: TerrainType::Mountains,
-                rare_terrain: Some(TerrainType::Crystal),
-                primary_weight: 70,   // 70% caves
-                secondary_weight: 20, // 20% mountains (surface)
-                rare_weight: 10,      // 10% crystal caves
-            },
-            BiomeType::Artificial => BiomeConfig {
-                primary_terrain: TerrainType::Constructed,
-                secondary_terrain: TerrainType::Plains,
-                rare_terrain: Some(TerrainType::Anomaly),
-                primary_weight: 50,   // 50% constructed
-                secondary_weight: 40, // 40% plains (ruins/abandoned)
-                rare_weight: 10,      // 10% anomalies
-            },
-        }
-    }
-
-    /// Hash biome coordinates for consistent biome zones
-    fn hash_biome_coordinate(&self, biome_x: i32, biome_y: i32) -> u64 {
-        let mut hash = self.seed;
-        hash = hash.wrapping_mul(73).wrapping_add(biome_x as u64);
-        hash = hash.wrapping_mul(79).wrapping_add(biome_y as u64);
-        hash
+    /// Determine biome type based on position
+    fn determine_biome(&self, position: Position3D) -> BiomeType {
+        BiomeType::from_terrain(self.generate_terrain_type(position))
     }
 
     /// Generate elevation based on position
     fn generate_elevation(&self, position: Position3D) -> Elevation {
-        // Simple elevation based on distance from origin with some variation
-        let base_elevation = (position.x.abs() + position.y.abs()) / 10;
-        let variation = (self.hash_position(position) % 5) as i32 - 2;
-        let final_elevation = (base_elevation + variation).max(0);
+        self.elevation_from_roll(self.noise.sample(position), self.hash_position(position))
+    }
+
+    /// Elevation of a sample, roughened by a roll
+    fn elevation_from_roll(&self, sample: TerrainSample, hash: u64) -> Elevation {
+        // The elevation field sets the height, the roll adds a step either way
+        let variation = (hash % 3) as i32 - 1;
+        let final_elevation = (self.noise.config().height(sample) + variation).max(0);
 
         Elevation::new(final_elevation).unwrap_or(Elevation::sea_level())
     }
@@ -261,8 +281,12 @@ impl MapService {
             let capacity = 50 + (hash % 100) as u32; // 50-149 capacity
             let current_amount = capacity; // Start full
 
-            let node =
-                ResourceNode::new(EntityId::generate(), node_props, capacity, current_amount);
+            let node = ResourceNode::new(
+                self.node_id(*position),
+                node_props,
+                capacity,
+                current_amount,
+            );
 
             Ok(Some(node))
         } else {
@@ -270,6 +294,25 @@ impl MapService {
         }
     }
 
+    /// Id of the node generated at a position, the same however often and
+    /// in whichever order the tile is generated
+    fn node_id(&self, position: Position3D) -> EntityId {
+        EntityId::new(
+            self.hash_position(position)
+                .wrapping_mul(0xD6E8_FEB8_6659_FD93)
+                .rotate_left(29),
+        )
+    }
+
+    /// Stable id of the feature anchored at a position
+    fn feature_id(&self, position: Position3D) -> EntityId {
+        EntityId::new(
+            self.hash_position(position)
+                .wrapping_mul(0x94D0_49BB_1331_11EB)
+                .rotate_left(17),
+        )
+    }
+
     /// Hash a position for pseudo-random generation
     fn hash_position(&self, position: Position3D) -> u64 {
         // Simple hash combining position coordinates with seed
@@ -298,6 +341,229 @@ impl MapService {
         Ok(passable_ratio >= 0.8)
     }
 
+    /// Check whether impassable terrain encloses the center within a radius
+    ///
+    /// Flood-fills passable tiles from `center` without leaving the radius and
+    /// reports an enclosure when no tile on the outer ring can be reached.
+    pub fn is_enclosed(&self, map: &Map, center: Position3D, radius: u32) -> bool {
+        if radius == 0 {
+            return false;
+        }
+        if !map.is_passable(&center) {
+            return true;
+        }
+
+        let mut visited = HashSet::new();
+        let mut frontier = VecDeque::new();
+        visited.insert(center);
+        frontier.push_back(center);
+
+        while let Some(current) = frontier.pop_front() {
+            if current.manhattan_distance_2d(&center) >= radius {
+                return false;
+            }
+
+            for neighbor in [
+                current.offset(1, 0, 0),
+                current.offset(-1, 0, 0),
+                current.offset(0, 1, 0),
+                current.offset(0, -1, 0),
+            ] {
+                if neighbor.manhattan_distance_2d(&center) <= radius
+                    && map.is_passable(&neighbor)
+                    && visited.insert(neighbor)
+                {
+                    frontier.push_back(neighbor);
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Curate the area around a new game's spawn for a fair first day
+    ///
+    /// Guarantees a minimum share of low-cost passable terrain, a minimum
+    /// number of common resource nodes and an open path out of the spawn
+    /// radius. Changes depend only on the seed and spawn position, and shaped
+    /// tiles are pinned so later cache eviction cannot regenerate them.
+    /// Only call this once when starting a new game, never after loading.
+    pub fn shape_spawn_area(
+        &self,
+        map: &mut Map,
+        spawn: Position3D,
+    ) -> DomainResult<SpawnShapingReport> {
+        let radius = constants::SPAWN_SHAPING_RADIUS;
+        let mut report = SpawnShapingReport::default();
+        let mut positions = spawn.positions_within_distance(radius);
+        positions.sort_by_key(|pos| (pos.x, pos.y));
+
+        for pos in &positions {
+            let coord = TileCoordinate::from(*pos);
+            if map.get_tile(&coord).is_none() {
+                let tile = self.generate_single_tile(*pos)?;
+                map.set_tile(coord, tile);
+            }
+        }
+
+        // The spawn tile itself is always easy ground
+        if !self.is_low_cost_at(map, &spawn) {
+            self.soften_tile(map, spawn);
+            report.tiles_softened += 1;
+        }
+
+        // Raise the share of low-cost terrain, converting the harshest tiles first
+        let required =
+            (positions.len() as f32 * constants::SPAWN_MIN_LOW_COST_SHARE).ceil() as usize;
+        let low_cost = positions
+            .iter()
+            .filter(|pos| self.is_low_cost_at(map, pos))
+            .count();
+        if low_cost < required {
+            let mut candidates: Vec<Position3D> = positions
+                .iter()
+                .filter(|pos| !self.is_low_cost_at(map, pos))
+                .copied()
+                .collect();
+            candidates.sort_by_key(|pos| {
+                (
+                    std::cmp::Reverse(map.movement_cost(pos)),
+                    pos.manhattan_distance_2d(&spawn),
+                    self.shaping_hash(*pos),
+                )
+            });
+
+            for pos in candidates.into_iter().take(required - low_cost) {
+                self.soften_tile(map, pos);
+                report.tiles_softened += 1;
+            }
+        }
+
+        // Carve a straight path out if the spawn is still walled in
+        if self.is_enclosed(map, spawn, radius) {
+            let directions = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+            let (dx, dy) = directions[(self.shaping_hash(spawn) % 4) as usize];
+            for step in 1..=radius as i32 {
+                let pos = spawn.offset(dx * step, dy * step, 0);
+                if !map.is_passable(&pos) {
+                    self.soften_tile(map, pos);
+                    report.tiles_softened += 1;
+                }
+            }
+            report.path_carved = true;
+        }
+
+        // Guarantee a few common resource nodes close to the spawn
+        let common_nodes = positions
+            .iter()
+            .filter(|pos| {
+                map.get_resource_node(pos)
+                    .map(|node| node.properties().resource_type.is_basic())
+                    .unwrap_or(false)
+            })
+            .count();
+        if common_nodes < constants::SPAWN_MIN_COMMON_NODES {
+            let mut candidates: Vec<Position3D> = positions
+                .iter()
+                .filter(|pos| **pos != spawn)
+                .filter(|pos| map.get_resource_node(pos).is_none())
+                .filter(|pos| self.is_low_cost_at(map, pos))
+                .copied()
+                .collect();
+            candidates
+                .sort_by_key(|pos| (pos.manhattan_distance_2d(&spawn), self.shaping_hash(*pos)));
+
+            let mut missing = constants::SPAWN_MIN_COMMON_NODES - common_nodes;
+            for pos in candidates {
+                if missing == 0 {
+                    break;
+                }
+                if let Some(node) = self.generate_common_resource_node(map, &pos) {
+                    map.add_resource_node(pos, node);
+                    report.nodes_added += 1;
+                    missing -= 1;
+                }
+            }
+        }
+
+        for pos in &positions {
+            map.pin_tile(TileCoordinate::from(*pos));
+        }
+
+        Ok(report)
+    }
+
+    /// Make sure `position` is easy ground, e.g. for a base away from the spawn
+    ///
+    /// The tile is generated if the map does not have it yet and softened if
+    /// it is impassable or costly, then pinned like the shaped spawn area.
+    /// Returns true if the terrain had to change.
+    pub fn prepare_site(&self, map: &mut Map, position: Position3D) -> DomainResult<bool> {
+        let coord = TileCoordinate::from(position);
+        if map.get_tile(&coord).is_none() {
+            let tile = self.generate_single_tile(position)?;
+            map.set_tile(coord, tile);
+        }
+        let softened = !self.is_low_cost_at(map, &position);
+        if softened {
+            self.soften_tile(map, position);
+        }
+        map.pin_tile(coord);
+        Ok(softened)
+    }
+
+    /// Check if the tile at a position is passable and cheap to enter
+    fn is_low_cost_at(&self, map: &Map, position: &Position3D) -> bool {
+        map.is_passable(position)
+            && map.movement_cost(position) <= constants::SPAWN_LOW_COST_MAX_MOVEMENT
+    }
+
+    /// Replace a tile's terrain with easy ground, keeping its elevation
+    fn soften_tile(&self, map: &mut Map, position: Position3D) {
+        let coord = TileCoordinate::from(position);
+        let elevation = map
+            .get_tile(&coord)
+            .map(|tile| tile.elevation)
+            .unwrap_or_else(|| self.generate_elevation(position));
+        let richness = self.tile_richness(position);
+        let terrain = if self.shaping_hash(position).is_multiple_of(3) {
+            TerrainType::Forest
+        } else {
+            TerrainType::Plains
+        };
+        map.set_tile(
+            coord,
+            MapTile::new(terrain, elevation, false).with_richness(richness),
+        );
+    }
+
+    /// Generate a node of a common resource suited to the tile's terrain
+    fn generate_common_resource_node(
+        &self,
+        map: &Map,
+        position: &Position3D,
+    ) -> Option<ResourceNode> {
+        let terrain = map.get_tile(&TileCoordinate::from(*position))?.terrain_type;
+        let hash = self.shaping_hash(*position);
+        let mut common_types = ResourceType::basic();
+        let len = common_types.len();
+        common_types.rotate_left((hash % len as u64) as usize);
+
+        common_types.into_iter().find_map(|resource_type| {
+            terrain.generate_resource_node(resource_type).map(|props| {
+                let capacity = 50 + (hash % 100) as u32;
+                ResourceNode::new(self.node_id(*position), props, capacity, capacity)
+            })
+        })
+    }
+
+    /// Hash used by the spawn shaping pass, salted apart from terrain generation
+    fn shaping_hash(&self, position: Position3D) -> u64 {
+        self.hash_position(position)
+            .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            .rotate_left(17)
+    }
+
     /// Get generation statistics for debugging
     pub fn get_generation_stats(
         &self,
@@ -347,21 +613,97 @@ impl MapService {
         let mut stats = BiomeStats::default();
 
         for pos in positions {
-            let biome = self.determine_biome(pos);
-            stats.total_tiles += 1;
-
-            match biome {
-                BiomeType::Temperate => stats.temperate += 1,
-                BiomeType::Arid => stats.arid += 1,
-                BiomeType::Cold => stats.cold += 1,
-                BiomeType::Wetlands => stats.wetlands += 1,
-                BiomeType::Underground => stats.underground += 1,
-                BiomeType::Artificial => stats.artificial += 1,
-            }
+            stats.record(self.determine_biome(pos));
         }
 
         stats
     }
+
+    /// Analyze what was actually generated in a region
+    ///
+    /// Works on partially generated maps: positions without a tile are left
+    /// out of every metric and reported through `MapAnalysis::is_partial`.
+    pub fn analyze(&self, map: &Map, region: MapRegion) -> MapAnalysis {
+        let positions = region.positions();
+        let mut analysis = MapAnalysis {
+            region,
+            region_tiles: positions.len() as u32,
+            generated_tiles: 0,
+            biomes: BiomeStats::default(),
+            biome_regions: 0,
+            passable_tiles: 0,
+            components: 0,
+            largest_component: 0,
+            resource_nodes: Vec::new(),
+            total_movement_cost: 0,
+        };
+
+        let mut passable = HashSet::new();
+        let mut biomes = HashMap::new();
+        for pos in positions {
+            let Some(tile) = map.get_tile(&TileCoordinate::from(pos)) else {
+                continue;
+            };
+            analysis.generated_tiles += 1;
+            let biome = BiomeType::from_terrain(tile.terrain_type);
+            analysis.biomes.record(biome);
+            biomes.insert(pos, biome);
+
+            if tile.terrain_type.is_passable() {
+                passable.insert(pos);
+                analysis.total_movement_cost += tile.terrain_type.movement_cost() as u32;
+            }
+            if let Some(node) = map.get_resource_node(&pos) {
+                analysis.record_node(node.properties().resource_type);
+            }
+        }
+        analysis.passable_tiles = passable.len() as u32;
+
+        // Flood-fill passable tiles into connected components
+        let mut unvisited = passable;
+        while let Some(&start) = unvisited.iter().next() {
+            unvisited.remove(&start);
+            let mut size = 1;
+            let mut frontier = VecDeque::from([start]);
+            while let Some(current) = frontier.pop_front() {
+                for neighbor in [
+                    current.offset(1, 0, 0),
+                    current.offset(-1, 0, 0),
+                    current.offset(0, 1, 0),
+                    current.offset(0, -1, 0),
+                ] {
+                    if unvisited.remove(&neighbor) {
+                        size += 1;
+                        frontier.push_back(neighbor);
+                    }
+                }
+            }
+            analysis.components += 1;
+            analysis.largest_component = analysis.largest_component.max(size);
+        }
+
+        // Flood-fill generated tiles into regions of one biome
+        while let Some((&start, &biome)) = biomes.iter().next() {
+            biomes.remove(&start);
+            let mut frontier = VecDeque::from([start]);
+            while let Some(current) = frontier.pop_front() {
+                for neighbor in [
+                    current.offset(1, 0, 0),
+                    current.offset(-1, 0, 0),
+                    current.offset(0, 1, 0),
+                    current.offset(0, -1, 0),
+                ] {
+                    if biomes.get(&neighbor) == Some(&biome) {
+                        biomes.remove(&neighbor);
+                        frontier.push_back(neighbor);
+                    }
+                }
+            }
+            analysis.biome_regions += 1;
+        }
+
+        analysis
+    }
 }
 
 impl Default for MapService {
@@ -390,6 +732,14 @@ pub struct GenerationStats {
     pub resource_nodes: u32,
 }
 
+/// Changes applied by the spawn shaping pass
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpawnShapingReport {
+    pub tiles_softened: u32,
+    pub nodes_added: u32,
+    pub path_carved: bool,
+}
+
 /// Statistics about biome distribution
 #[derive(Debug, Clone, Default)]
 pub struct BiomeStats {
@@ -428,6 +778,267 @@ impl BiomeStats {
     }
 }
 
+impl BiomeType {
+    /// All biome types, in display order
+    pub fn all() -> [BiomeType; 6] {
+        [
+            BiomeType::Temperate,
+            BiomeType::Arid,
+            BiomeType::Cold,
+            BiomeType::Wetlands,
+            BiomeType::Underground,
+            BiomeType::Artificial,
+        ]
+    }
+
+    /// Biome a generated terrain belongs to
+    pub fn from_terrain(terrain: TerrainType) -> Self {
+        match terrain {
+            TerrainType::Plains | TerrainType::Forest => BiomeType::Temperate,
+            TerrainType::Desert | TerrainType::Volcanic => BiomeType::Arid,
+            TerrainType::Tundra | TerrainType::Mountains => BiomeType::Cold,
+            TerrainType::Swamp | TerrainType::Ocean => BiomeType::Wetlands,
+            TerrainType::Cave | TerrainType::Crystal => BiomeType::Underground,
+            TerrainType::Constructed | TerrainType::Anomaly => BiomeType::Artificial,
+        }
+    }
+}
+
+impl BiomeStats {
+    /// Count one tile of a biome
+    pub fn record(&mut self, biome: BiomeType) {
+        self.total_tiles += 1;
+        match biome {
+            BiomeType::Temperate => self.temperate += 1,
+            BiomeType::Arid => self.arid += 1,
+            BiomeType::Cold => self.cold += 1,
+            BiomeType::Wetlands => self.wetlands += 1,
+            BiomeType::Underground => self.underground += 1,
+            BiomeType::Artificial => self.artificial += 1,
+        }
+    }
+
+    /// Number of tiles of a biome
+    pub fn count(&self, biome: BiomeType) -> u32 {
+        match biome {
+            BiomeType::Temperate => self.temperate,
+            BiomeType::Arid => self.arid,
+            BiomeType::Cold => self.cold,
+            BiomeType::Wetlands => self.wetlands,
+            BiomeType::Underground => self.underground,
+            BiomeType::Artificial => self.artificial,
+        }
+    }
+
+    /// Share of tiles of a biome, in percent
+    pub fn percentage(&self, biome: BiomeType) -> f32 {
+        if self.total_tiles == 0 {
+            0.0
+        } else {
+            (self.count(biome) as f32 / self.total_tiles as f32) * 100.0
+        }
+    }
+
+    /// Most common biome, if any tile was counted
+    pub fn dominant(&self) -> Option<BiomeType> {
+        BiomeType::all()
+            .into_iter()
+            .filter(|biome| self.count(*biome) > 0)
+            .max_by_key(|biome| self.count(*biome))
+    }
+}
+
+/// Area of a map to analyze: every position within a manhattan radius
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapRegion {
+    pub center: Position3D,
+    pub radius: u32,
+}
+
+impl MapRegion {
+    /// Create a region around a center
+    pub fn new(center: Position3D, radius: u32) -> Self {
+        Self { center, radius }
+    }
+
+    /// Positions covered by the region
+    pub fn positions(&self) -> Vec<Position3D> {
+        self.center.positions_within_distance(self.radius)
+    }
+}
+
+/// What a region of a map actually contains
+#[derive(Debug, Clone)]
+pub struct MapAnalysis {
+    pub region: MapRegion,
+    /// Positions covered by the region
+    pub region_tiles: u32,
+    /// Positions that have a generated tile; every metric counts only these
+    pub generated_tiles: u32,
+    pub biomes: BiomeStats,
+    /// Connected groups of tiles of one biome (4-neighbour)
+    pub biome_regions: u32,
+    pub passable_tiles: u32,
+    /// Connected groups of passable tiles (4-neighbour)
+    pub components: u32,
+    /// Size of the largest connected group
+    pub largest_component: u32,
+    /// Resource nodes by type, in resource display order
+    pub resource_nodes: Vec<(ResourceType, u32)>,
+    total_movement_cost: u32,
+}
+
+impl MapAnalysis {
+    fn record_node(&mut self, resource_type: ResourceType) {
+        match self
+            .resource_nodes
+            .iter_mut()
+            .find(|(kind, _)| *kind == resource_type)
+        {
+            Some((_, count)) => *count += 1,
+            None => {
+                self.resource_nodes.push((resource_type, 1));
+                self.resource_nodes
+                    .sort_by_key(|(kind, _)| constants::resource_display_priority(*kind));
+            }
+        }
+    }
+
+    /// Check if part of the region has not been generated yet
+    pub fn is_partial(&self) -> bool {
+        self.generated_tiles < self.region_tiles
+    }
+
+    /// Average number of tiles in a biome region; larger means more coherent
+    pub fn average_biome_region_size(&self) -> f32 {
+        if self.biome_regions == 0 {
+            0.0
+        } else {
+            self.generated_tiles as f32 / self.biome_regions as f32
+        }
+    }
+
+    /// Share of passable tiles in the largest connected group (0.0 - 1.0)
+    pub fn largest_component_share(&self) -> f32 {
+        if self.passable_tiles == 0 {
+            0.0
+        } else {
+            self.largest_component as f32 / self.passable_tiles as f32
+        }
+    }
+
+    /// Resource nodes of a type per 100 generated tiles
+    pub fn resource_density(&self, resource_type: ResourceType) -> f32 {
+        let count = self
+            .resource_nodes
+            .iter()
+            .find(|(kind, _)| *kind == resource_type)
+            .map_or(0, |(_, count)| *count);
+        self.per_hundred_tiles(count)
+    }
+
+    /// All resource nodes per 100 generated tiles
+    pub fn total_resource_density(&self) -> f32 {
+        self.per_hundred_tiles(self.resource_nodes.iter().map(|(_, count)| count).sum())
+    }
+
+    /// Resource type with the most nodes, if any
+    pub fn richest_resource(&self) -> Option<ResourceType> {
+        self.resource_nodes
+            .iter()
+            .max_by_key(|(_, count)| *count)
+            .map(|(kind, _)| *kind)
+    }
+
+    /// Average movement cost over passable generated tiles
+    pub fn average_movement_cost(&self) -> f32 {
+        if self.passable_tiles == 0 {
+            0.0
+        } else {
+            self.total_movement_cost as f32 / self.passable_tiles as f32
+        }
+    }
+
+    fn per_hundred_tiles(&self, count: u32) -> f32 {
+        if self.generated_tiles == 0 {
+            0.0
+        } else {
+            (count as f32 * 100.0) / self.generated_tiles as f32
+        }
+    }
+}
+
+impl fmt::Display for MapAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Map analysis around ({}, {}), radius {}",
+            self.region.center.x, self.region.center.y, self.region.radius
+        )?;
+        write!(
+            f,
+            "  Tiles: {}/{} generated",
+            self.generated_tiles, self.region_tiles
+        )?;
+        if self.is_partial() {
+            write!(f, " (partial: ungenerated tiles are not counted)")?;
+        }
+        writeln!(f)?;
+
+        let biomes: Vec<String> = BiomeType::all()
+            .into_iter()
+            .filter(|biome| self.biomes.count(*biome) > 0)
+            .map(|biome| format!("{:?} {:.1}%", biome, self.biomes.percentage(biome)))
+            .collect();
+        writeln!(
+            f,
+            "  Biomes: {}",
+            if biomes.is_empty() {
+                "none".to_string()
+            } else {
+                biomes.join(", ")
+            }
+        )?;
+        if self.biome_regions > 0 {
+            writeln!(
+                f,
+                "  Biome regions: {}, {:.1} tiles on average",
+                self.biome_regions,
+                self.average_biome_region_size()
+            )?;
+        }
+
+        writeln!(
+            f,
+            "  Connectivity: {} component(s), largest holds {:.1}% of {} passable tiles",
+            self.components,
+            self.largest_component_share() * 100.0,
+            self.passable_tiles
+        )?;
+
+        let resources: Vec<String> = self
+            .resource_nodes
+            .iter()
+            .map(|(kind, _)| format!("{} {:.1}", kind, self.resource_density(*kind)))
+            .collect();
+        writeln!(
+            f,
+            "  Resources per 100 tiles: {}",
+            if resources.is_empty() {
+                "none".to_string()
+            } else {
+                resources.join(", ")
+            }
+        )?;
+
+        write!(
+            f,
+            "  Average movement cost: {:.2}",
+            self.average_movement_cost()
+        )
+    }
+}
+
 impl GenerationStats {
     /// Get the percentage of passable tiles
     pub fn passable_percentage(&self) -> f32 {
@@ -462,6 +1073,52 @@ mod tests {
         assert_eq!(service.seed, 12345);
     }
 
+    #[test]
+    fn adjacent_chunks_agree_on_their_border_in_either_order() {
+        let size = constants::MAP_CHUNK_SIZE as i32;
+        let service = MapService::new(777);
+        let west = ChunkCoordinate::new(-1, 0, 0);
+        let east = ChunkCoordinate::origin();
+
+        let generate = |order: [ChunkCoordinate; 2]| {
+            let mut map = create_test_map();
+            let counts: Vec<usize> = order
+                .iter()
+                .map(|chunk| {
+                    service
+                        .generate_owned_chunk(&mut map, *chunk)
+                        .unwrap()
+                        .len()
+                })
+                .collect();
+            assert_eq!(counts, vec![(size * size) as usize; 2]);
+            assert!(validate_generation(&map, std::iter::empty()).is_empty());
+            map
+        };
+        let west_first = generate([west, east]);
+        let east_first = generate([east, west]);
+
+        // Both sides of the shared edge, and the chunks as a whole
+        assert_eq!(west_first.tiles(), east_first.tiles());
+        assert_eq!(west_first.tiles().len(), (2 * size * size) as usize);
+        let nodes = |map: &Map| {
+            let mut nodes: Vec<(i32, i32, EntityId, u32)> = map
+                .resource_nodes()
+                .iter()
+                .map(|(pos, node)| (pos.x, pos.y, *node.id(), node.current_amount()))
+                .collect();
+            nodes.sort_by_key(|(x, y, id, _)| (*x, *y, id.0));
+            nodes
+        };
+        assert_eq!(nodes(&west_first), nodes(&east_first));
+        for x in [-1, 0] {
+            for y in 0..size {
+                let coord = TileCoordinate::new(x, y, 0);
+                assert_eq!(west_first.get_tile(&coord), east_first.get_tile(&coord));
+            }
+        }
+    }
+
     #[test]
     fn generate_tiles_around_player() {
         let service = MapService::new(12345);
@@ -628,4 +1285,375 @@ mod tests {
         let hash2 = service2.hash_position(pos);
         assert_ne!(hash1, hash2);
     }
+
+    #[test]
+    fn noise_generation_is_deterministic_per_seed() {
+        let first = MapService::new(77);
+        let again = MapService::new(77);
+        let other = MapService::new(78);
+        let positions = Position3D::new(40, -25, 0).positions_within_distance(10);
+
+        let tiles = |service: &MapService| -> Vec<(TerrainType, Elevation)> {
+            positions
+                .iter()
+                .map(|pos| {
+                    let tile = service.generate_single_tile(*pos).unwrap();
+                    (tile.terrain_type, tile.elevation)
+                })
+                .collect()
+        };
+        assert_eq!(tiles(&first), tiles(&again));
+        assert_ne!(tiles(&first), tiles(&other));
+    }
+
+    #[test]
+    fn ground_richness_varies_but_never_changes() {
+        let service = MapService::new(77);
+        let positions = Position3D::new(40, -25, 0).positions_within_distance(10);
+        let richness: Vec<ResourceRichness> = positions
+            .iter()
+            .map(|pos| service.generate_single_tile(*pos).unwrap().richness)
+            .collect();
+        for level in [
+            ResourceRichness::Poor,
+            ResourceRichness::Average,
+            ResourceRichness::Rich,
+            ResourceRichness::Abundant,
+        ] {
+            assert!(richness.contains(&level), "{:?}", level);
+        }
+        let average = richness
+            .iter()
+            .filter(|level| **level == ResourceRichness::Average)
+            .count();
+        assert!(average * 2 > richness.len());
+        assert!(positions
+            .iter()
+            .zip(&richness)
+            .all(|(pos, level)| service.tile_richness(*pos) == *level));
+    }
+
+    #[test]
+    fn noise_biomes_form_large_regions() {
+        for seed in 1..=5 {
+            let service = MapService::new(seed);
+            let mut map = Map::new(EntityId::generate(), "Test".to_string(), seed).unwrap();
+            let region = MapRegion::new(Position3D::new(60, -40, 0), 20);
+            service
+                .generate_tiles_at(&mut map, region.positions())
+                .unwrap();
+
+            let analysis = service.analyze(&map, region);
+            // Drawing every tile on its own averaged under ten tiles a region
+            assert!(
+                analysis.average_biome_region_size() >= 25.0,
+                "seed {} averaged {:.1} tiles per biome region",
+                seed,
+                analysis.average_biome_region_size()
+            );
+        }
+    }
+
+    #[test]
+    fn thresholds_shift_terrain_shares() {
+        let share = |config: GenerationConfig, terrain: TerrainType| {
+            let positions = Position3D::new(0, 0, 0).positions_within_distance(40);
+            let matching = (1..=3)
+                .flat_map(|seed| {
+                    let service = MapService::with_config(seed, config);
+                    positions
+                        .iter()
+                        .map(move |pos| service.generate_terrain_type(*pos))
+                        .collect::<Vec<_>>()
+                })
+                .filter(|generated| *generated == terrain)
+                .count();
+            matching as f32 / (positions.len() * 3) as f32
+        };
+        let base = GenerationConfig::default();
+
+        let wetter = GenerationConfig {
+            ocean_level: base.ocean_level + 0.08,
+            ..base
+        };
+        assert!(share(wetter, TerrainType::Ocean) > share(base, TerrainType::Ocean) + 0.1);
+
+        let drier = GenerationConfig {
+            dry_level: base.dry_level + 0.08,
+            ..base
+        };
+        assert!(share(drier, TerrainType::Desert) > share(base, TerrainType::Desert) + 0.02);
+    }
+
+    #[test]
+    fn every_terrain_type_is_generated_across_seeds() {
+        let mut seen = HashSet::new();
+        for seed in 1..=8 {
+            let service = MapService::new(seed);
+            for x in -32..=32 {
+                for y in -32..=32 {
+                    seen.insert(service.generate_terrain_type(Position3D::new(x, y, 0)));
+                }
+            }
+        }
+        assert_eq!(seen.len(), 12, "only generated {:?}", seen);
+    }
+
+    #[test]
+    fn analysis_counts_biome_regions() {
+        let service = MapService::new(1);
+        let mut map = create_test_map();
+        // Plains and forest make one temperate region, the desert another
+        tile(&mut map, 0, 0, TerrainType::Plains);
+        tile(&mut map, 1, 0, TerrainType::Forest);
+        tile(&mut map, 0, 1, TerrainType::Plains);
+        tile(&mut map, -1, 0, TerrainType::Desert);
+        tile(&mut map, 0, -1, TerrainType::Plains);
+
+        let analysis = service.analyze(&map, MapRegion::new(Position3D::origin(), 1));
+
+        assert_eq!(analysis.biome_regions, 2);
+        assert_eq!(analysis.average_biome_region_size(), 2.5);
+        assert!(analysis.to_string().contains("Biome regions: 2"));
+    }
+
+    fn shaped_map(seed: u64) -> Map {
+        let service = MapService::new(seed);
+        let mut map = Map::new(EntityId::generate(), "Test".to_string(), seed).unwrap();
+        service
+            .generate_chunk(&mut map, Position3D::origin(), 20)
+            .unwrap();
+        service
+            .shape_spawn_area(&mut map, Position3D::origin())
+            .unwrap();
+        map
+    }
+
+    #[test]
+    fn spawn_shaping_guarantees_hold_across_seeds() {
+        let radius = constants::SPAWN_SHAPING_RADIUS;
+        let spawn = Position3D::origin();
+
+        for seed in (0..200u64).map(|i| i.wrapping_mul(0x2545_F491_4F6C_DD1D)) {
+            let service = MapService::new(seed);
+            let map = shaped_map(seed);
+            let positions = spawn.positions_within_distance(radius);
+
+            let low_cost = positions
+                .iter()
+                .filter(|pos| service.is_low_cost_at(&map, pos))
+                .count();
+            let share = low_cost as f32 / positions.len() as f32;
+            assert!(
+                share >= constants::SPAWN_MIN_LOW_COST_SHARE,
+                "seed {} low-cost share {}",
+                seed,
+                share
+            );
+
+            let common_nodes = positions
+                .iter()
+                .filter_map(|pos| map.get_resource_node(pos))
+                .filter(|node| node.properties().resource_type.is_basic())
+                .count();
+            assert!(
+                common_nodes >= constants::SPAWN_MIN_COMMON_NODES,
+                "seed {} has {} common nodes",
+                seed,
+                common_nodes
+            );
+
+            assert!(
+                !service.is_enclosed(&map, spawn, radius),
+                "seed {} encloses the spawn",
+                seed
+            );
+            assert!(map.is_passable(&spawn));
+        }
+    }
+
+    #[test]
+    fn spawn_shaping_is_deterministic() {
+        for seed in [0u64, 7, 12345, u64::MAX] {
+            let first = shaped_map(seed);
+            let second = shaped_map(seed);
+
+            for pos in
+                Position3D::origin().positions_within_distance(constants::SPAWN_SHAPING_RADIUS)
+            {
+                let coord = TileCoordinate::from(pos);
+                assert_eq!(
+                    first.get_tile(&coord).map(|t| t.terrain_type),
+                    second.get_tile(&coord).map(|t| t.terrain_type)
+                );
+                assert_eq!(
+                    first
+                        .get_resource_node(&pos)
+                        .map(|n| n.properties().clone()),
+                    second
+                        .get_resource_node(&pos)
+                        .map(|n| n.properties().clone())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn enclosure_detection() {
+        let service = MapService::new(1);
+        let mut map = create_test_map();
+        let center = Position3D::origin();
+
+        for pos in center.positions_within_distance(4) {
+            let terrain = if pos.manhattan_distance_2d(&center) == 2 {
+                TerrainType::Ocean
+            } else {
+                TerrainType::Plains
+            };
+            map.set_tile(
+                TileCoordinate::from(pos),
+                MapTile::new(terrain, Elevation::sea_level(), false),
+            );
+        }
+        assert!(service.is_enclosed(&map, center, 4));
+
+        // Opening a single gap in the ring frees the spawn
+        map.set_tile(
+            TileCoordinate::new(2, 0, 0),
+            MapTile::new(TerrainType::Plains, Elevation::sea_level(), false),
+        );
+        assert!(!service.is_enclosed(&map, center, 4));
+    }
+
+    #[test]
+    fn spawn_shaping_opens_an_enclosed_spawn() {
+        let service = MapService::new(99);
+        let mut map = create_test_map();
+        let center = Position3D::origin();
+
+        for pos in center.positions_within_distance(constants::SPAWN_SHAPING_RADIUS) {
+            let terrain = if pos.manhattan_distance_2d(&center) == 1 {
+                TerrainType::Ocean
+            } else {
+                TerrainType::Plains
+            };
+            map.set_tile(
+                TileCoordinate::from(pos),
+                MapTile::new(terrain, Elevation::sea_level(), false),
+            );
+        }
+
+        let report = service.shape_spawn_area(&mut map, center).unwrap();
+
+        assert!(report.path_carved);
+        assert!(!service.is_enclosed(&map, center, constants::SPAWN_SHAPING_RADIUS));
+        assert!(map.is_tile_pinned(&TileCoordinate::from(center)));
+    }
+
+    fn tile(map: &mut Map, x: i32, y: i32, terrain: TerrainType) {
+        map.set_tile(
+            TileCoordinate::new(x, y, 0),
+            MapTile::new(terrain, Elevation::sea_level(), true),
+        );
+    }
+
+    fn node(map: &mut Map, x: i32, y: i32, resource_type: ResourceType) {
+        use crate::domain::value_objects::resources::{
+            RegenerationRate, ResourceAccessibility, ResourceNodeProperties, ResourceRichness,
+        };
+        let props = ResourceNodeProperties::new(
+            resource_type,
+            ResourceRichness::Average,
+            ResourceAccessibility::Easy,
+            RegenerationRate::Slow,
+        );
+        map.add_resource_node(
+            Position3D::new(x, y, 0),
+            ResourceNode::new(EntityId::generate(), props, 50, 50),
+        );
+    }
+
+    #[test]
+    fn analysis_reports_biome_shares_of_generated_tiles_only() {
+        let service = MapService::new(1);
+        let mut map = create_test_map();
+        tile(&mut map, 0, 0, TerrainType::Plains);
+        tile(&mut map, 1, 0, TerrainType::Forest);
+        tile(&mut map, -1, 0, TerrainType::Desert);
+        tile(&mut map, 0, 1, TerrainType::Cave);
+
+        let analysis = service.analyze(&map, MapRegion::new(Position3D::origin(), 1));
+
+        assert_eq!(analysis.region_tiles, 5);
+        assert_eq!(analysis.generated_tiles, 4);
+        assert!(analysis.is_partial());
+        assert_eq!(analysis.biomes.percentage(BiomeType::Temperate), 50.0);
+        assert_eq!(analysis.biomes.percentage(BiomeType::Arid), 25.0);
+        assert_eq!(analysis.biomes.percentage(BiomeType::Underground), 25.0);
+        assert_eq!(analysis.biomes.percentage(BiomeType::Cold), 0.0);
+        assert_eq!(analysis.biomes.dominant(), Some(BiomeType::Temperate));
+        assert!(analysis.to_string().contains("partial"));
+    }
+
+    #[test]
+    fn analysis_counts_disconnected_components() {
+        let service = MapService::new(1);
+        let mut map = create_test_map();
+        // Three-tile island, two-tile island split by ocean, and a lone tile
+        tile(&mut map, 0, 0, TerrainType::Plains);
+        tile(&mut map, 1, 0, TerrainType::Plains);
+        tile(&mut map, 0, 1, TerrainType::Forest);
+        tile(&mut map, 2, 0, TerrainType::Ocean);
+        tile(&mut map, 2, 1, TerrainType::Ocean);
+        tile(&mut map, 3, 0, TerrainType::Mountains);
+        tile(&mut map, 3, 1, TerrainType::Mountains);
+        tile(&mut map, -3, 0, TerrainType::Desert);
+
+        let analysis = service.analyze(&map, MapRegion::new(Position3D::origin(), 4));
+
+        assert_eq!(analysis.passable_tiles, 6);
+        assert_eq!(analysis.components, 3);
+        assert_eq!(analysis.largest_component, 3);
+        assert_eq!(analysis.largest_component_share(), 0.5);
+        assert!(analysis.to_string().contains("3 component(s)"));
+    }
+
+    #[test]
+    fn analysis_reports_resource_density_and_movement_cost() {
+        let service = MapService::new(1);
+        let mut map = create_test_map();
+        for pos in Position3D::origin().positions_within_distance(1) {
+            tile(&mut map, pos.x, pos.y, TerrainType::Plains);
+        }
+        tile(&mut map, 0, 0, TerrainType::Swamp);
+        tile(&mut map, 1, 0, TerrainType::Ocean);
+        node(&mut map, 0, 1, ResourceType::Metal);
+        node(&mut map, -1, 0, ResourceType::Metal);
+        node(&mut map, 0, -1, ResourceType::Energy);
+        // Nodes outside the region are ignored
+        node(&mut map, 5, 5, ResourceType::Energy);
+
+        let analysis = service.analyze(&map, MapRegion::new(Position3D::origin(), 1));
+
+        assert!(!analysis.is_partial());
+        assert_eq!(analysis.resource_density(ResourceType::Metal), 40.0);
+        assert_eq!(analysis.resource_density(ResourceType::Energy), 20.0);
+        assert_eq!(analysis.resource_density(ResourceType::Food), 0.0);
+        assert_eq!(analysis.total_resource_density(), 60.0);
+        assert_eq!(analysis.richest_resource(), Some(ResourceType::Metal));
+        // Swamp (4) and three plains (1) are passable; ocean is not counted
+        assert_eq!(analysis.average_movement_cost(), 7.0 / 4.0);
+    }
+
+    #[test]
+    fn analysis_of_ungenerated_region_is_empty() {
+        let service = MapService::new(1);
+        let analysis = service.analyze(&create_test_map(), MapRegion::new(Position3D::origin(), 2));
+
+        assert_eq!(analysis.generated_tiles, 0);
+        assert_eq!(analysis.components, 0);
+        assert_eq!(analysis.largest_component_share(), 0.0);
+        assert_eq!(analysis.average_movement_cost(), 0.0);
+        assert!(analysis.to_string().contains("Biomes: none"));
+    }
 }
@@ -14,28 +14,146 @@
 //! - Stateless services (or explicitly managed state)
 //! - Clear single responsibility
 
+pub mod announcements;
+pub mod anomaly_storm;
 pub mod audio_service;
+pub mod base_facilities;
+pub mod base_layout;
+pub mod base_report;
+pub mod blitz;
+pub mod chunk_ownership;
+pub mod codex;
 pub mod collision;
+pub mod data_packs;
+pub mod dawn_report;
+pub mod decals;
+pub mod emergency_recall;
+pub mod expedition;
+pub mod exploration_xp;
+pub mod fauna;
 pub mod font_service;
+pub mod game_calendar;
 pub mod game_log_service;
+pub mod gear;
+pub mod ghost_trail;
+pub mod hashing;
+pub mod interior;
+pub mod inventory;
+pub mod low_points_guard;
+pub mod map_export;
 pub mod map_service;
+pub mod move_undo;
+pub mod movement_economy;
+pub mod movement_governor;
+pub mod music_override;
+pub mod mutators;
+pub mod party;
+pub mod pathfinding;
+pub mod play_heatmap;
+pub mod quest_board;
+pub mod reputation;
+pub mod rescue;
 pub mod resting_service;
+pub mod run_ledger;
+pub mod scenarios;
+pub mod scout_probe;
+pub mod seasons;
+pub mod session_flags;
 pub mod spawning;
+pub mod terrain_noise;
 pub mod tile_cache_service;
 pub mod tile_movement;
+pub mod tile_staleness;
+pub mod trade;
 pub mod visibility_service;
+pub mod wrecks;
 
 // Re-export services for convenience
+pub use announcements::{
+    plain_announcement_text, Announcement, AnnouncementFilter, AnnouncementService,
+    AnnouncementSeverity,
+};
+pub use anomaly_storm::{AnomalyStorm, NodeChange, StormReport, WorldHazards};
 pub use audio_service::{AudioService, AudioServiceError, SimpleAudioService};
+pub use base_facilities::FacilityBonuses;
+pub use base_layout::{layout_base, BuildingSlot, BuildingVariant, PlacedBuilding};
+pub use base_report::{BaseAutomationReport, BaseReportItem};
+pub use blitz::{autopilot_direction, BlitzClock, BlitzPause};
+pub use chunk_ownership::{
+    owning_chunk, validate_generation, ChunkFeatures, Footprint, GenerationIssue, Placement,
+};
+pub use codex::{
+    consumable_entry_id, event_entry_id, faction_entry_id, gear_entry_id, poi_entry_id,
+    terrain_entry_id, Codex, CodexCategory, CodexEntry, CodexUnlocks, PointOfInterest,
+};
 pub use collision::CollisionService;
+pub use data_packs::{DataPack, PackConflict, PackEvent, PackSet};
+pub use dawn_report::{nearest_landmark, AdvisorRule, Bearing, DawnReport, Landmark};
+pub use decals::{Decal, DecalKind, DecalLook, DecalSite, TileDecals};
+pub use emergency_recall::{injury_modifier, recall_losses, DefeatCause, EmergencyRecall, Vitals};
+pub use expedition::{ExpeditionEstimate, ExpeditionPlan, ExpeditionPlanner, WaypointProgress};
+pub use exploration_xp::{terrain_exploration_xp, DiscoveredTerrains, ExplorationGrant};
+pub use fauna::{FaunaCensus, FaunaKind, FaunaService};
 pub use font_service::{FontConfig, FontService, FontSize, FontType, FontWeight};
-pub use game_log_service::{GameLogMessage, GameLogService, GameLogType, LogPriority};
-pub use map_service::{BiomeStats, BiomeType, GenerationStats, MapService};
+pub use game_calendar::{CalendarPhase, GameCalendar};
+pub use game_log_service::{
+    GameLogMessage, GameLogService, GameLogSlice, GameLogType, LogPriority,
+};
+pub use gear::{derived_stats, stat_line, Gear, GearBonus, GearItem, GearRarity, GearSlot};
+pub use ghost_trail::{Breadcrumb, GhostBook, GhostRun};
+pub use interior::{Interior, InteriorGenerator};
+pub use inventory::{
+    BulkTransfer, ConsumableKind, Consumables, InventoryEntry, InventorySortMode, TransferPlan,
+};
+pub use low_points_guard::{LowPointsAlert, LowPointsGuard, LowPointsGuardMode, SafeOptions};
+pub use map_export::{downsample_step, export_footer, ExportCell, ExportMarker, MapSnapshot};
+pub use map_service::{
+    BiomeStats, BiomeType, GenerationStats, MapAnalysis, MapRegion, MapService, SpawnShapingReport,
+};
+pub use move_undo::{MoveSnapshot, MoveUndo};
+pub use movement_economy::{MovementEconomyService, MovementReward};
+pub use movement_governor::{grant_threshold, Fatigue, GrantSource, MovementGovernor};
+pub use music_override::{
+    ducked_volume, AudioMemory, EncounterKind, EncounterMix, MusicOverrideStack, ResumeAction,
+    ResumePoint, TrackRestore,
+};
+pub use mutators::{
+    chart_start_area, ModifierSource, ModifierStack, Mutator, Mutators, RunModifier,
+};
+pub use party::{Contribution, HotSeat, Party, RunTally, TransferOffer, TurnPhase};
+pub use pathfinding::{find_route, PathfindingService, Route};
+pub use play_heatmap::{
+    grade_range, heat_grade, normalized, region_of, HeatMetric, HeatRegion, HeatTile,
+    HeatmapRecord, PlayHeatmap, TileHeat,
+};
+pub use quest_board::{
+    BoardPosting, PostedQuest, QuestBoard, QuestCompensation, QuestCounters, QuestTemplate,
+};
+pub use reputation::{Faction, Reputation, ReputationCause, ReputationChange, ReputationTier};
+pub use rescue::{DistressSignal, RescueOutcome, TimedObjective};
 pub use resting_service::RestingService;
+pub use run_ledger::{DefeatFollowUp, ProfileStats, RunEnd, RunMode, Tombstones};
+pub use scenarios::{
+    Scenario, ScenarioStart, ScenarioTable, CRASH_SURVIVOR, STANDARD_DROP, VETERAN_SCOUT,
+};
+pub use scout_probe::{
+    probe_sightings, reveal_probe_slice, ProbeFlight, ProbePhase, ProbeRoute, ProbeSighting,
+    ProbeStop,
+};
+pub use seasons::{season_change, season_days_left, Season};
+pub use session_flags::{
+    weight_factor, Comparison, FlagCondition, FlagValue, FlagWeight, SessionFlags,
+};
 pub use spawning::SpawningService;
+pub use terrain_noise::{GenerationConfig, NoiseLayer, TerrainNoise, TerrainSample};
 pub use tile_cache_service::{CacheStats, TileCacheService};
-pub use tile_movement::TileMovementService;
+pub use tile_movement::{
+    EventGrace, FortuneFavor, MovementConditions, MovementPreview, RollOutcome, TileMovementService,
+};
+pub use tile_staleness::{chunk_of, StaleRefresh, StalenessRules, TileStalenessService};
+pub use trade::{TradeOffer, TradeService};
 pub use visibility_service::{VisibilityLevel, VisibilityService};
+pub use wrecks::{Debris, SalvageYield, Wreck, WreckField, WreckOrigin};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,211 @@
+//! Move Undo - Taking back the last move of a relaxed run
+//!
+//! A move is followed from the moment its cost is paid until the world has
+//! answered it: where it started and ended, the points the player had and
+//! every resource it changed. Once sealed it may be taken back, only that
+//! move and only once; paying for a new move replaces it, and anything else
+//! the player does afterwards retires it. A move taken back leaves its
+//! tile's event consumed, so stepping there again never rolls another one.
+
+use crate::domain::value_objects::{Position3D, ResourceType};
+use std::collections::HashSet;
+
+/// What a move changed, enough to put the player back before it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveSnapshot {
+    /// Movement points before the cost was paid
+    pub movement_points: u8,
+    /// Tiles explored before the move
+    pub tiles_explored: u32,
+    /// Tile the move started from, once it landed
+    pub from: Option<Position3D>,
+    /// Tile the move ended on, once it landed
+    pub to: Option<Position3D>,
+    /// Net change of each resource during the move
+    pub resource_deltas: Vec<(ResourceType, i32)>,
+}
+
+impl MoveSnapshot {
+    fn add_resource(&mut self, resource: ResourceType, delta: i32) {
+        match self
+            .resource_deltas
+            .iter_mut()
+            .find(|(kind, _)| *kind == resource)
+        {
+            Some((_, total)) => *total += delta,
+            None => self.resource_deltas.push((resource, delta)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+enum UndoState {
+    #[default]
+    Idle,
+    /// Paid for and still being answered
+    Open(MoveSnapshot),
+    /// Fully answered; may be taken back
+    Sealed(MoveSnapshot),
+}
+
+/// The move that may be taken back, and tiles whose event is spent
+#[derive(Debug, Clone, Default, bevy::prelude::Resource)]
+pub struct MoveUndo {
+    state: UndoState,
+    consumed_events: HashSet<Position3D>,
+}
+
+impl MoveUndo {
+    /// Follow a newly paid move, dropping the one held before
+    pub fn begin(&mut self, movement_points: u8, tiles_explored: u32) {
+        self.state = UndoState::Open(MoveSnapshot {
+            movement_points,
+            tiles_explored,
+            from: None,
+            to: None,
+            resource_deltas: Vec::new(),
+        });
+    }
+
+    /// The move put the player on `to`; a second relocation retires it
+    pub fn land(&mut self, from: Position3D, to: Position3D) {
+        match &mut self.state {
+            UndoState::Open(snapshot) if snapshot.to.is_none() => {
+                snapshot.from = Some(from);
+                snapshot.to = Some(to);
+            }
+            UndoState::Idle => {}
+            _ => self.expire(),
+        }
+    }
+
+    /// A resource changed; after the seal that is another action
+    pub fn record_resource(&mut self, resource: ResourceType, delta: i32) {
+        match &mut self.state {
+            UndoState::Open(snapshot) => snapshot.add_resource(resource, delta),
+            UndoState::Sealed(_) => self.expire(),
+            UndoState::Idle => {}
+        }
+    }
+
+    /// Movement points changed; after the seal that is another action
+    pub fn record_points(&mut self) {
+        if matches!(self.state, UndoState::Sealed(_)) {
+            self.expire();
+        }
+    }
+
+    /// Close a landed move to further changes
+    pub fn seal(&mut self) {
+        if let UndoState::Open(snapshot) = &self.state {
+            if snapshot.to.is_some() {
+                self.state = UndoState::Sealed(snapshot.clone());
+            }
+        }
+    }
+
+    /// Retire the held move: something else happened
+    pub fn expire(&mut self) {
+        self.state = UndoState::Idle;
+    }
+
+    /// The move that may be taken back now, if any
+    pub fn available(&self) -> Option<&MoveSnapshot> {
+        match &self.state {
+            UndoState::Sealed(snapshot) => Some(snapshot),
+            _ => None,
+        }
+    }
+
+    /// Take the held move back, leaving its tile's event consumed
+    pub fn take_back(&mut self) -> Option<MoveSnapshot> {
+        let UndoState::Sealed(snapshot) = std::mem::take(&mut self.state) else {
+            return None;
+        };
+        if let Some(to) = snapshot.to {
+            self.consumed_events.insert(to);
+        }
+        Some(snapshot)
+    }
+
+    /// Check if moving onto `position` can no longer trigger an event
+    pub fn is_event_consumed(&self, position: Position3D) -> bool {
+        self.consumed_events.contains(&position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn landed_move() -> MoveUndo {
+        let mut undo = MoveUndo::default();
+        undo.begin(10, 4);
+        undo.record_resource(ResourceType::Metal, -2);
+        undo.land(Position3D::origin(), Position3D::new(1, 0, 0));
+        undo.record_points();
+        undo.record_resource(ResourceType::Metal, 5);
+        undo.record_resource(ResourceType::Food, 1);
+        undo
+    }
+
+    #[test]
+    fn a_sealed_move_keeps_what_it_changed() {
+        let mut undo = landed_move();
+        assert!(undo.available().is_none());
+        undo.seal();
+        let snapshot = undo.available().unwrap();
+        assert_eq!(snapshot.movement_points, 10);
+        assert_eq!(snapshot.tiles_explored, 4);
+        assert_eq!(snapshot.from, Some(Position3D::origin()));
+        assert_eq!(snapshot.to, Some(Position3D::new(1, 0, 0)));
+        assert_eq!(
+            snapshot.resource_deltas,
+            vec![(ResourceType::Metal, 3), (ResourceType::Food, 1)]
+        );
+    }
+
+    #[test]
+    fn only_the_last_move_is_taken_back_and_its_event_stays_spent() {
+        let mut undo = landed_move();
+        undo.seal();
+        // A new move replaces the old one before it lands
+        undo.begin(7, 5);
+        assert!(undo.available().is_none());
+        undo.land(Position3D::new(1, 0, 0), Position3D::new(2, 0, 0));
+        undo.seal();
+
+        let snapshot = undo.take_back().unwrap();
+        assert_eq!(snapshot.movement_points, 7);
+        assert!(undo.take_back().is_none());
+        assert!(undo.is_event_consumed(Position3D::new(2, 0, 0)));
+        assert!(!undo.is_event_consumed(Position3D::new(1, 0, 0)));
+    }
+
+    #[test]
+    fn anything_after_the_seal_retires_the_move() {
+        let sealed = || {
+            let mut undo = landed_move();
+            undo.seal();
+            undo
+        };
+        let mut crafted = sealed();
+        crafted.record_resource(ResourceType::Metal, -3);
+        let mut rested = sealed();
+        rested.record_points();
+        let mut recalled = sealed();
+        recalled.land(Position3D::new(1, 0, 0), Position3D::origin());
+        let mut chose = sealed();
+        chose.expire();
+        for mut undo in [crafted, rested, recalled, chose] {
+            assert!(undo.available().is_none());
+            assert!(undo.take_back().is_none());
+        }
+
+        // Relocated twice before the seal, as entering ruins does
+        let mut delved = landed_move();
+        delved.land(Position3D::new(1, 0, 0), Position3D::new(0, 0, -1));
+        delved.seal();
+        assert!(delved.available().is_none());
+    }
+}
@@ -0,0 +1,220 @@
+//! Movement Economy - What a move pays out
+//!
+//! Every landing earns a few movement points back, more when the tile
+//! triggers an event. An event's outcome roll pays out again by band: the
+//! better the roll the more points, and a resource discovery turns up more
+//! of what the ground holds, worth as much experience. The terrain the event
+//! happens on scales the whole reward. Which resource turns up, and how rich
+//! the ground is, is left to the discovery itself. The tables default to the
+//! ones in `domain::constants` and can be swapped out for balancing without
+//! touching the systems that pay them.
+
+use crate::domain::constants::{
+    DISCOVERY_AMOUNT_BANDS, EVENT_LANDING_MOVEMENT_POINTS, EVENT_REWARD_TERRAIN_PERCENT,
+    MOVEMENT_REWARD_BANDS, SAFE_LANDING_MOVEMENT_POINTS,
+};
+use crate::domain::entities::EventType;
+use crate::domain::services::tile_movement::MovementDiceResult;
+use crate::domain::value_objects::TerrainType;
+
+/// What an event's outcome roll pays out, before run modifiers
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovementReward {
+    pub movement_points: u8,
+    pub experience: u32,
+    /// Amount a resource discovery turns up on average ground
+    pub discovered: u32,
+}
+
+/// Reward tables for moves and the events they trigger
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovementEconomyService {
+    /// Movement points by outcome, as (lowest final roll, points)
+    pub movement_bands: Vec<(u8, u8)>,
+    /// Amount found on a resource discovery, as (lowest final roll, amount)
+    pub discovery_bands: Vec<(u8, u32)>,
+    /// Share of the reward paid on each terrain, in percent; 100 where missing
+    pub terrain_percent: Vec<(TerrainType, u32)>,
+    pub event_landing_points: u8,
+    pub safe_landing_points: u8,
+}
+
+impl Default for MovementEconomyService {
+    fn default() -> Self {
+        Self {
+            movement_bands: MOVEMENT_REWARD_BANDS.to_vec(),
+            discovery_bands: DISCOVERY_AMOUNT_BANDS.to_vec(),
+            terrain_percent: EVENT_REWARD_TERRAIN_PERCENT.to_vec(),
+            event_landing_points: EVENT_LANDING_MOVEMENT_POINTS,
+            safe_landing_points: SAFE_LANDING_MOVEMENT_POINTS,
+        }
+    }
+}
+
+/// Value of the highest band `roll` reaches, if any
+fn band_value<T: Copy>(bands: &[(u8, T)], roll: u8) -> Option<T> {
+    bands
+        .iter()
+        .filter(|(min_roll, _)| roll >= *min_roll)
+        .max_by_key(|(min_roll, _)| *min_roll)
+        .map(|(_, value)| *value)
+}
+
+impl MovementEconomyService {
+    /// Movement points for landing on a tile, with or without an event
+    pub fn landing_points(&self, triggered_event: bool) -> u8 {
+        if triggered_event {
+            self.event_landing_points
+        } else {
+            self.safe_landing_points
+        }
+    }
+
+    /// Share of the reward paid on `terrain`, in percent
+    pub fn terrain_percent(&self, terrain: TerrainType) -> u32 {
+        self.terrain_percent
+            .iter()
+            .find(|(candidate, _)| *candidate == terrain)
+            .map_or(100, |(_, percent)| *percent)
+    }
+
+    /// What the outcome roll of an `event_type` event on `terrain` pays out
+    pub fn event_reward(
+        &self,
+        dice_result: &MovementDiceResult,
+        event_type: EventType,
+        terrain: TerrainType,
+    ) -> MovementReward {
+        let roll = dice_result.final_result;
+        let percent = self.terrain_percent(terrain);
+        let scale = |value: u32| value.saturating_mul(percent) / 100;
+        let points = scale(band_value(&self.movement_bands, roll).unwrap_or(0) as u32);
+        let movement_points = u8::try_from(points).unwrap_or(u8::MAX);
+        let discovered = if event_type == EventType::ResourceDiscovery {
+            scale(band_value(&self.discovery_bands, roll).unwrap_or(0))
+        } else {
+            0
+        };
+        MovementReward {
+            movement_points,
+            experience: discovered,
+            discovered,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::dice::{DiceModifier, DiceRoll, DiceType};
+
+    fn rolled(final_result: u8) -> MovementDiceResult {
+        MovementDiceResult {
+            base_roll: final_result.min(20),
+            level_modifier: 0,
+            terrain_modifier: 0,
+            danger_modifier: 0,
+            gear_modifier: 0,
+            assist_modifier: DiceModifier::none(),
+            disadvantage: false,
+            total_modifier: 0,
+            final_result,
+            dice_roll: DiceRoll::new(1, DiceType::D20, DiceModifier::none()).unwrap(),
+        }
+    }
+
+    #[test]
+    fn better_rolls_never_pay_less() {
+        let economy = MovementEconomyService::default();
+        let mut last = (0, 0);
+        for roll in 0..=25 {
+            let reward = economy.event_reward(
+                &rolled(roll),
+                EventType::ResourceDiscovery,
+                TerrainType::Plains,
+            );
+            assert!(reward.movement_points >= last.0, "roll {}", roll);
+            assert!(reward.discovered >= last.1, "roll {}", roll);
+            assert_eq!(reward.experience, reward.discovered);
+            last = (reward.movement_points, reward.discovered);
+        }
+        assert_eq!(last, (7, 50));
+        assert_eq!(
+            economy.event_reward(&rolled(14), EventType::Combat, TerrainType::Plains),
+            MovementReward {
+                movement_points: 4,
+                experience: 0,
+                discovered: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn harsher_terrain_pays_more_for_the_same_roll() {
+        let economy = MovementEconomyService::default();
+        let reward =
+            |terrain| economy.event_reward(&rolled(20), EventType::ResourceDiscovery, terrain);
+        let plains = reward(TerrainType::Plains);
+        assert_eq!((plains.movement_points, plains.discovered), (7, 50));
+        for terrain in TerrainType::all() {
+            let paid = reward(terrain);
+            assert!(
+                paid.movement_points >= plains.movement_points,
+                "{:?}",
+                terrain
+            );
+            assert!(paid.discovered >= plains.discovered, "{:?}", terrain);
+        }
+        let anomaly = reward(TerrainType::Anomaly);
+        assert_eq!((anomaly.movement_points, anomaly.discovered), (10, 75));
+
+        // Terrain missing from the table pays the plain reward
+        let flat = MovementEconomyService {
+            terrain_percent: Vec::new(),
+            ..MovementEconomyService::default()
+        };
+        assert_eq!(
+            flat.event_reward(
+                &rolled(20),
+                EventType::ResourceDiscovery,
+                TerrainType::Anomaly
+            ),
+            plains
+        );
+    }
+
+    #[test]
+    fn critical_failures_never_earn_points() {
+        let economy = MovementEconomyService::default();
+        for roll in (0..=25).filter(|roll| rolled(*roll).outcome_category() == "Critical Failure") {
+            for event_type in [
+                EventType::ResourceDiscovery,
+                EventType::Combat,
+                EventType::Mystery,
+            ] {
+                assert_eq!(
+                    economy
+                        .event_reward(&rolled(roll), event_type, TerrainType::Volcanic)
+                        .movement_points,
+                    0
+                );
+            }
+        }
+
+        // Tables given in any order still pay by the highest band reached
+        let reshuffled = MovementEconomyService {
+            movement_bands: vec![(4, 1), (20, 9), (10, 3)],
+            ..MovementEconomyService::default()
+        };
+        let points = |roll| {
+            reshuffled
+                .event_reward(&rolled(roll), EventType::Mystery, TerrainType::Plains)
+                .movement_points
+        };
+        assert_eq!((points(3), points(11), points(22)), (0, 3, 9));
+        assert_eq!(
+            reshuffled.landing_points(true),
+            EVENT_LANDING_MOVEMENT_POINTS
+        );
+    }
+}
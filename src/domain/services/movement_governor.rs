@@ -0,0 +1,165 @@
+//! Movement Governor - Diminishing movement point rewards within a day
+//!
+//! Events, combat and boons can hand out movement points. Without a limit a
+//! lucky chain grants more than moves cost and a day never has to end. The
+//! governor counts the points granted since the last rest against a soft
+//! cap set by difficulty and Endurance: points up to the cap count in full,
+//! the next cap's worth is halved, and anything past twice the cap is lost.
+//! Halving works on the day's running total, so two grants of one point in
+//! the halved tier yield one point between them. Resting starts a fresh day.
+//! The count is not saved; a loaded run starts its day fresh.
+
+use crate::domain::constants::{
+    MOVEMENT_GRANT_ENDURANCE_BASELINE, MOVEMENT_GRANT_ENDURANCE_STEP, MOVEMENT_GRANT_SOFT_CAP,
+};
+use crate::domain::entities::game::DifficultyLevel;
+
+/// Where granted movement points come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GrantSource {
+    /// Safe steps and successful exploration rolls
+    Exploration,
+    /// Outcome of a triggered event
+    EventReward,
+    /// Victory in combat
+    CombatBonus,
+    /// A boon event
+    Boon,
+    /// An understood mystery
+    Mystery,
+}
+
+impl GrantSource {
+    /// Every source, in display order
+    pub fn all() -> [GrantSource; 5] {
+        [
+            GrantSource::Exploration,
+            GrantSource::EventReward,
+            GrantSource::CombatBonus,
+            GrantSource::Boon,
+            GrantSource::Mystery,
+        ]
+    }
+}
+
+/// How worn out the day's grants have left the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Fatigue {
+    /// Grants count in full
+    Fresh,
+    /// Past the soft cap; grants are halved
+    Tired,
+    /// Past twice the soft cap; grants are lost
+    Exhausted,
+}
+
+/// Daily soft cap for a difficulty and an Endurance score, never below one
+pub fn grant_threshold(difficulty: DifficultyLevel, endurance: u8) -> u32 {
+    let base = (MOVEMENT_GRANT_SOFT_CAP as f32 * difficulty.movement_grant_multiplier()).round();
+    let bonus =
+        (endurance as i32 - MOVEMENT_GRANT_ENDURANCE_BASELINE) / MOVEMENT_GRANT_ENDURANCE_STEP;
+    (base as i32 + bonus).max(1) as u32
+}
+
+/// Points a day's running total of `requested` points is worth
+fn effective_total(requested: u32, threshold: u32) -> u32 {
+    let full = requested.min(threshold);
+    let halved = requested.saturating_sub(threshold).min(threshold);
+    full + halved / 2
+}
+
+/// Movement points granted since the last rest
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MovementGovernor {
+    difficulty: DifficultyLevel,
+    requested_today: u32,
+}
+
+impl MovementGovernor {
+    /// A fresh day at `difficulty`
+    pub fn new(difficulty: DifficultyLevel) -> Self {
+        Self {
+            difficulty,
+            requested_today: 0,
+        }
+    }
+
+    /// Difficulty the caps are scaled by
+    pub fn difficulty(&self) -> DifficultyLevel {
+        self.difficulty
+    }
+
+    /// Points requested since the last rest, before any reduction
+    pub fn requested_today(&self) -> u32 {
+        self.requested_today
+    }
+
+    /// Daily soft cap for a player with `endurance`
+    pub fn threshold(&self, endurance: u8) -> u32 {
+        grant_threshold(self.difficulty, endurance)
+    }
+
+    /// Count a grant of `points` and return how many of them are given
+    pub fn govern(&mut self, points: u8, endurance: u8) -> u8 {
+        let threshold = self.threshold(endurance);
+        let before = effective_total(self.requested_today, threshold);
+        self.requested_today = self.requested_today.saturating_add(points as u32);
+        let after = effective_total(self.requested_today, threshold);
+        (after - before).min(points as u32) as u8
+    }
+
+    /// Current tier for a player with `endurance`
+    pub fn fatigue(&self, endurance: u8) -> Fatigue {
+        let threshold = self.threshold(endurance);
+        if self.requested_today >= threshold.saturating_mul(2) {
+            Fatigue::Exhausted
+        } else if self.requested_today >= threshold {
+            Fatigue::Tired
+        } else {
+            Fatigue::Fresh
+        }
+    }
+
+    /// Start a fresh day
+    pub fn reset(&mut self) {
+        self.requested_today = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grants_are_full_then_halved_then_lost() {
+        let mut governor = MovementGovernor::new(DifficultyLevel::Normal);
+        let endurance = MOVEMENT_GRANT_ENDURANCE_BASELINE as u8;
+        assert_eq!(governor.threshold(endurance), 8);
+
+        assert_eq!(governor.govern(6, endurance), 6);
+        assert_eq!(governor.fatigue(endurance), Fatigue::Fresh);
+        // Two in full, four halved
+        assert_eq!(governor.govern(6, endurance), 4);
+        assert_eq!(governor.fatigue(endurance), Fatigue::Tired);
+        // Single points in the halved tier pay out every other time
+        assert_eq!(governor.govern(1, endurance), 0);
+        assert_eq!(governor.govern(1, endurance), 1);
+        // Two more reach twice the cap; everything after is lost
+        assert_eq!(governor.govern(3, endurance), 1);
+        assert_eq!(governor.fatigue(endurance), Fatigue::Exhausted);
+        assert_eq!(governor.govern(7, endurance), 0);
+
+        governor.reset();
+        assert_eq!(governor.fatigue(endurance), Fatigue::Fresh);
+        assert_eq!(governor.govern(3, endurance), 3);
+    }
+
+    #[test]
+    fn difficulty_and_endurance_move_the_cap() {
+        assert_eq!(grant_threshold(DifficultyLevel::Easy, 10), 12);
+        assert_eq!(grant_threshold(DifficultyLevel::Nightmare, 10), 4);
+        assert_eq!(grant_threshold(DifficultyLevel::Normal, 14), 10);
+        assert_eq!(grant_threshold(DifficultyLevel::Normal, 9), 8);
+        assert_eq!(grant_threshold(DifficultyLevel::Nightmare, 1), 1);
+    }
+}
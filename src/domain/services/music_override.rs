@@ -0,0 +1,375 @@
+//! Music Overrides - Encounters taking over the soundtrack
+//!
+//! Normal play follows the playlist. An encounter pushes an override: the
+//! music is ducked for as long as it lasts, and a combat that goes past its
+//! first round crossfades into a combat loop. Overrides nest, so a fight in
+//! a storm is a combat override on top of a storm override; the highest
+//! priority one decides the mix, and ending either unwinds to the other.
+//!
+//! A track faded out for the combat loop is remembered with its playback
+//! offset. It comes back at that offset where the audio backend can seek,
+//! otherwise it restarts from the top with a slower fade-in so the intro
+//! slips in under the ambience instead of cutting in.
+//!
+//! Saves remember the soundtrack the same way: the track and its offset,
+//! the terrain ambient and the adaptive volume. Loading waits for the
+//! remembered assets before picking up where the save left off, and falls
+//! back to a random track if the playlist no longer has the saved one.
+
+use crate::domain::constants::{
+    MUSIC_ENCOUNTER_DUCK, MUSIC_ENCOUNTER_FADE_PER_SECOND, MUSIC_RESTART_FADE_PER_SECOND,
+};
+use crate::domain::value_objects::terrain::TerrainType;
+use serde::{Deserialize, Serialize};
+
+/// Something happening that the music should react to, lowest priority first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EncounterKind {
+    /// The player stands in an anomaly storm
+    Storm,
+    /// The base is under attack
+    Raid,
+    /// Hostiles are fighting the player
+    Combat,
+}
+
+impl EncounterKind {
+    /// Name for logs
+    pub fn name(&self) -> &'static str {
+        match self {
+            EncounterKind::Storm => "storm",
+            EncounterKind::Raid => "raid",
+            EncounterKind::Combat => "combat",
+        }
+    }
+}
+
+/// Music volume multipliers while an encounter plays
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EncounterMix {
+    /// Gain of the playlist track
+    pub track: f32,
+    /// Gain of the combat loop
+    pub combat_loop: f32,
+}
+
+impl EncounterMix {
+    /// Plain playlist music
+    pub const NORMAL: EncounterMix = EncounterMix {
+        track: 1.0,
+        combat_loop: 0.0,
+    };
+}
+
+/// `volume` lowered by the encounter duck while an override is active
+pub fn ducked_volume(volume: f32, active: Option<EncounterKind>) -> f32 {
+    match active {
+        Some(_) => volume * (1.0 - MUSIC_ENCOUNTER_DUCK),
+        None => volume,
+    }
+}
+
+/// Encounters currently holding the music, in the order they began
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MusicOverrideStack {
+    entries: Vec<EncounterKind>,
+    /// The combat went past its first round
+    combat_loop: bool,
+}
+
+impl MusicOverrideStack {
+    /// Start an override; returns false if `kind` was already active
+    pub fn push(&mut self, kind: EncounterKind) -> bool {
+        if self.entries.contains(&kind) {
+            return false;
+        }
+        self.entries.push(kind);
+        true
+    }
+
+    /// End an override wherever it sits; returns false if it was not active
+    pub fn pop(&mut self, kind: EncounterKind) -> bool {
+        let Some(index) = self.entries.iter().rposition(|entry| *entry == kind) else {
+            return false;
+        };
+        self.entries.remove(index);
+        if kind == EncounterKind::Combat {
+            self.combat_loop = false;
+        }
+        true
+    }
+
+    /// Push what began and pop what ended; returns the encounters that began
+    pub fn sync(&mut self, active: &[EncounterKind]) -> Vec<EncounterKind> {
+        let ended: Vec<EncounterKind> = self
+            .entries
+            .iter()
+            .copied()
+            .filter(|kind| !active.contains(kind))
+            .collect();
+        for kind in ended {
+            self.pop(kind);
+        }
+        active
+            .iter()
+            .copied()
+            .filter(|kind| self.push(*kind))
+            .collect()
+    }
+
+    /// Highest priority override, or `None` for normal music
+    pub fn active(&self) -> Option<EncounterKind> {
+        self.entries.iter().copied().max()
+    }
+
+    /// Check if `kind` is holding the music
+    pub fn contains(&self, kind: EncounterKind) -> bool {
+        self.entries.contains(&kind)
+    }
+
+    /// Number of nested overrides
+    pub fn depth(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// The combat lasts more than one round: switch to the combat loop
+    pub fn engage_combat_loop(&mut self) {
+        if self.contains(EncounterKind::Combat) {
+            self.combat_loop = true;
+        }
+    }
+
+    /// Check if the combat loop should be playing
+    pub fn wants_combat_loop(&self) -> bool {
+        self.combat_loop && self.active() == Some(EncounterKind::Combat)
+    }
+
+    /// Gains the music should ramp towards
+    pub fn mix(&self) -> EncounterMix {
+        if self.wants_combat_loop() {
+            return EncounterMix {
+                track: 0.0,
+                combat_loop: 1.0,
+            };
+        }
+        EncounterMix {
+            track: ducked_volume(1.0, self.active()),
+            combat_loop: 0.0,
+        }
+    }
+}
+
+/// Where the playlist track stood when the combat loop took over
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResumePoint {
+    pub track_index: usize,
+    /// Playback offset in seconds, if the backend reported one
+    pub offset: Option<f32>,
+}
+
+/// How to bring the playlist track back after combat
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResumeAction {
+    /// Seek to the offset and fade in at the normal rate
+    Seek { offset: f32, fade_per_second: f32 },
+    /// Play from the top, fading in slowly
+    Restart { fade_per_second: f32 },
+}
+
+impl ResumePoint {
+    /// Pick how to resume, given whether the backend can seek
+    pub fn action(&self, can_seek: bool) -> ResumeAction {
+        match self.offset {
+            Some(offset) if can_seek => ResumeAction::Seek {
+                offset,
+                fade_per_second: MUSIC_ENCOUNTER_FADE_PER_SECOND,
+            },
+            _ => ResumeAction::Restart {
+                fade_per_second: MUSIC_RESTART_FADE_PER_SECOND,
+            },
+        }
+    }
+}
+
+/// What was playing when the run was saved
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AudioMemory {
+    /// Playlist index of the track
+    pub track_index: Option<usize>,
+    /// Playback offset in seconds, if the backend reported one
+    pub track_position: Option<f32>,
+    /// Terrain whose ambient was playing
+    pub ambient: Option<TerrainType>,
+    pub music_volume: f32,
+    pub danger_level: f32,
+}
+
+/// How far restoring the saved track has come
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrackRestore {
+    /// The saved track is still loading
+    Waiting,
+    /// Play the saved track from where it was
+    Resume(ResumePoint),
+    /// The playlist no longer has the saved track, or none was saved
+    Missing,
+}
+
+impl AudioMemory {
+    /// Decide on the saved track for a playlist of `playlist_len` tracks,
+    /// given whether the track's assets are loaded
+    pub fn track_restore(&self, playlist_len: usize, track_loaded: bool) -> TrackRestore {
+        match self.track_index {
+            Some(track_index) if track_index < playlist_len => {
+                if !track_loaded {
+                    return TrackRestore::Waiting;
+                }
+                TrackRestore::Resume(ResumePoint {
+                    track_index,
+                    offset: self.track_position,
+                })
+            }
+            _ => TrackRestore::Missing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_overrides_resolve_to_the_highest_and_unwind() {
+        let mut stack = MusicOverrideStack::default();
+        assert_eq!(stack.active(), None);
+        assert!(stack.push(EncounterKind::Storm));
+        assert!(stack.push(EncounterKind::Combat));
+        assert!(!stack.push(EncounterKind::Storm));
+        assert_eq!(stack.depth(), 2);
+        assert_eq!(stack.active(), Some(EncounterKind::Combat));
+
+        // The storm lifting mid-fight leaves the combat in charge
+        assert!(stack.pop(EncounterKind::Storm));
+        assert_eq!(stack.active(), Some(EncounterKind::Combat));
+        assert!(stack.push(EncounterKind::Storm));
+        assert!(stack.pop(EncounterKind::Combat));
+        assert_eq!(stack.active(), Some(EncounterKind::Storm));
+        assert!(!stack.pop(EncounterKind::Combat));
+        assert!(stack.pop(EncounterKind::Storm));
+        assert_eq!(stack.active(), None);
+
+        let began = stack.sync(&[EncounterKind::Raid, EncounterKind::Storm]);
+        assert_eq!(began, vec![EncounterKind::Raid, EncounterKind::Storm]);
+        assert!(stack.sync(&[EncounterKind::Storm]).is_empty());
+        assert_eq!(stack.active(), Some(EncounterKind::Storm));
+    }
+
+    #[test]
+    fn encounters_duck_the_music_by_half() {
+        assert_eq!(ducked_volume(0.6, None), 0.6);
+        assert_eq!(ducked_volume(0.6, Some(EncounterKind::Storm)), 0.3);
+
+        let mut stack = MusicOverrideStack::default();
+        assert_eq!(stack.mix(), EncounterMix::NORMAL);
+        stack.push(EncounterKind::Combat);
+        assert_eq!(stack.mix().track, 0.5);
+        assert_eq!(stack.mix().combat_loop, 0.0);
+
+        // A second round crossfades into the loop
+        stack.engage_combat_loop();
+        assert_eq!(
+            stack.mix(),
+            EncounterMix {
+                track: 0.0,
+                combat_loop: 1.0
+            }
+        );
+    }
+
+    #[test]
+    fn track_resumes_after_combat_with_or_without_seeking() {
+        let mut stack = MusicOverrideStack::default();
+        stack.push(EncounterKind::Storm);
+        stack.push(EncounterKind::Combat);
+        stack.engage_combat_loop();
+        assert!(stack.wants_combat_loop());
+        stack.pop(EncounterKind::Combat);
+        assert!(!stack.wants_combat_loop());
+        assert_eq!(stack.mix().track, 0.5);
+        // A later fight starts on its first round again
+        stack.push(EncounterKind::Combat);
+        assert!(!stack.wants_combat_loop());
+
+        let saved = ResumePoint {
+            track_index: 3,
+            offset: Some(42.5),
+        };
+        assert_eq!(
+            saved.action(true),
+            ResumeAction::Seek {
+                offset: 42.5,
+                fade_per_second: MUSIC_ENCOUNTER_FADE_PER_SECOND
+            }
+        );
+        let restart = ResumeAction::Restart {
+            fade_per_second: MUSIC_RESTART_FADE_PER_SECOND,
+        };
+        assert_eq!(saved.action(false), restart);
+        let unknown = ResumePoint {
+            track_index: 3,
+            offset: None,
+        };
+        assert_eq!(unknown.action(true), restart);
+    }
+
+    #[test]
+    fn saved_audio_round_trips_through_json() {
+        let memory = AudioMemory {
+            track_index: Some(2),
+            track_position: Some(73.25),
+            ambient: Some(TerrainType::Swamp),
+            music_volume: 0.4,
+            danger_level: 0.65,
+        };
+        let json = serde_json::to_string(&memory).unwrap();
+        assert_eq!(serde_json::from_str::<AudioMemory>(&json).unwrap(), memory);
+
+        let silent = AudioMemory {
+            track_index: None,
+            track_position: None,
+            ambient: None,
+            ..memory
+        };
+        let json = serde_json::to_value(silent).unwrap();
+        assert_eq!(json["track_index"], serde_json::Value::Null);
+        assert_eq!(serde_json::from_value::<AudioMemory>(json).unwrap(), silent);
+    }
+
+    #[test]
+    fn saved_track_waits_for_its_assets_or_falls_back() {
+        let memory = AudioMemory {
+            track_index: Some(3),
+            track_position: Some(12.0),
+            ambient: None,
+            music_volume: 0.5,
+            danger_level: 0.0,
+        };
+        assert_eq!(memory.track_restore(6, false), TrackRestore::Waiting);
+        assert_eq!(
+            memory.track_restore(6, true),
+            TrackRestore::Resume(ResumePoint {
+                track_index: 3,
+                offset: Some(12.0)
+            })
+        );
+
+        // The playlist shrank between versions
+        assert_eq!(memory.track_restore(3, true), TrackRestore::Missing);
+        assert_eq!(memory.track_restore(3, false), TrackRestore::Missing);
+        let nothing = AudioMemory {
+            track_index: None,
+            ..memory
+        };
+        assert_eq!(nothing.track_restore(6, true), TrackRestore::Missing);
+    }
+}
@@ -0,0 +1,591 @@
+//! Run Mutators - Optional rule changes chosen for a new run
+//!
+//! Mutators trade an advantage for a handicap to give replays some variety.
+//! They are picked before a run starts, saved with it and cannot change
+//! until the next run. Every source of rule changes - difficulty,
+//! milestones, mutators, the season, injuries and assists - contributes a
+//! `RunModifier` layer to the run's `ModifierStack`, which applies the
+//! layers in that order and rounds after each one, so the same selection
+//! always resolves the same way.
+
+use crate::domain::constants::{
+    CARTOGRAPHER_EXPERIENCE_MULTIPLIER, CARTOGRAPHER_REVEAL_RADIUS, GLASS_CANNON_MULTIPLIER,
+    IRON_STOMACH_MOVEMENT_PENALTY, NIGHT_OWL_REST_PENALTY,
+};
+use crate::domain::entities::game::DifficultyLevel;
+use crate::domain::entities::Map;
+use crate::domain::services::{injury_modifier, Season};
+use crate::domain::value_objects::resources::ResourceCollection;
+use crate::domain::value_objects::{Position3D, ResourceType, TileCoordinate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// An optional rule change for a whole run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Mutator {
+    /// No food upkeep, but a smaller movement point maximum
+    IronStomach,
+    /// Double event rewards and double hazard damage
+    GlassCannon,
+    /// Start with the area around the spawn charted, but earn less experience
+    Cartographer,
+    /// Bad nights no longer spoil the rest, but rests restore less
+    NightOwl,
+}
+
+impl Mutator {
+    /// Every mutator, in display order
+    pub fn all() -> [Mutator; 4] {
+        [
+            Mutator::IronStomach,
+            Mutator::GlassCannon,
+            Mutator::Cartographer,
+            Mutator::NightOwl,
+        ]
+    }
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mutator::IronStomach => "Iron Stomach",
+            Mutator::GlassCannon => "Glass Cannon",
+            Mutator::Cartographer => "Cartographer",
+            Mutator::NightOwl => "Night Owl",
+        }
+    }
+
+    /// Stable code identifying the mutator in the score hash
+    pub fn code(&self) -> u8 {
+        match self {
+            Mutator::IronStomach => 1,
+            Mutator::GlassCannon => 2,
+            Mutator::Cartographer => 3,
+            Mutator::NightOwl => 4,
+        }
+    }
+
+    /// Rule changes of the mutator
+    pub fn modifier(&self) -> RunModifier {
+        let neutral = RunModifier::default();
+        match self {
+            Mutator::IronStomach => RunModifier {
+                food_upkeep: false,
+                max_movement_delta: -IRON_STOMACH_MOVEMENT_PENALTY,
+                ..neutral
+            },
+            Mutator::GlassCannon => RunModifier {
+                event_reward_multiplier: GLASS_CANNON_MULTIPLIER,
+                hazard_damage_multiplier: GLASS_CANNON_MULTIPLIER,
+                ..neutral
+            },
+            Mutator::Cartographer => RunModifier {
+                start_reveal_radius: CARTOGRAPHER_REVEAL_RADIUS,
+                experience_multiplier: CARTOGRAPHER_EXPERIENCE_MULTIPLIER,
+                ..neutral
+            },
+            Mutator::NightOwl => RunModifier {
+                night_penalties: false,
+                rest_movement_delta: -NIGHT_OWL_REST_PENALTY,
+                ..neutral
+            },
+        }
+    }
+}
+
+/// The mutators a run was started with
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Mutators(BTreeSet<Mutator>);
+
+impl Mutators {
+    /// A selection of mutators; duplicates count once
+    pub fn new(mutators: impl IntoIterator<Item = Mutator>) -> Self {
+        Self(mutators.into_iter().collect())
+    }
+
+    /// Check if a mutator is active
+    pub fn contains(&self, mutator: Mutator) -> bool {
+        self.0.contains(&mutator)
+    }
+
+    /// Check if the run has no mutators
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Active mutators in a stable order
+    pub fn iter(&self) -> impl Iterator<Item = Mutator> + '_ {
+        self.0.iter().copied()
+    }
+
+    /// Names of the active mutators for summaries
+    pub fn names(&self) -> String {
+        self.iter()
+            .map(|mutator| mutator.name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// One layer of rule changes; the default changes nothing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunModifier {
+    /// Multiplier on experience gained
+    pub experience_multiplier: f32,
+    /// Multiplier on resources found through events
+    pub event_reward_multiplier: f32,
+    /// Multiplier on movement points lost to hazards
+    pub hazard_damage_multiplier: f32,
+    /// Change to the movement point maximum
+    pub max_movement_delta: i8,
+    /// Change to the movement points a rest leaves the player with
+    pub rest_movement_delta: i8,
+    /// Expeditions need food for every day on the road
+    pub food_upkeep: bool,
+    /// Bad night events spoil the rest
+    pub night_penalties: bool,
+    /// Radius charted around the spawn when the run starts
+    pub start_reveal_radius: u32,
+    /// Multiplier on one resource wherever it is found
+    pub yield_boost: Option<(ResourceType, f32)>,
+    /// Movement points added to steps onto open terrain
+    pub open_terrain_cost_delta: u8,
+    /// Quiet movement rolls that turn up an event instead
+    pub event_pressure: u8,
+    /// Penalty on every movement roll
+    pub threat_delta: u8,
+    /// Chance per rest that equipped gear malfunctions, in percent
+    pub malfunction_chance: u8,
+}
+
+impl Default for RunModifier {
+    fn default() -> Self {
+        Self {
+            experience_multiplier: 1.0,
+            event_reward_multiplier: 1.0,
+            hazard_damage_multiplier: 1.0,
+            max_movement_delta: 0,
+            rest_movement_delta: 0,
+            food_upkeep: true,
+            night_penalties: true,
+            start_reveal_radius: 0,
+            yield_boost: None,
+            open_terrain_cost_delta: 0,
+            event_pressure: 0,
+            threat_delta: 0,
+            malfunction_chance: 0,
+        }
+    }
+}
+
+impl RunModifier {
+    /// Rule changes of a difficulty level
+    pub fn from_difficulty(difficulty: DifficultyLevel) -> Self {
+        Self {
+            experience_multiplier: difficulty.experience_multiplier(),
+            event_reward_multiplier: difficulty.resource_scarcity_multiplier(),
+            hazard_damage_multiplier: difficulty.danger_multiplier(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Where a layer of rule changes comes from, in the order layers apply
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ModifierSource {
+    Difficulty,
+    Milestone,
+    Mutator,
+    Season,
+    Injury,
+    Assist,
+}
+
+/// Every rule change of a run, applied in `ModifierSource` order
+///
+/// The stack is built when the run starts and only read afterwards, apart
+/// from the season and injury layers, which change as the days pass.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModifierStack {
+    difficulty: DifficultyLevel,
+    mutators: Mutators,
+    season: Option<Season>,
+    layers: Vec<(ModifierSource, RunModifier)>,
+}
+
+impl Default for ModifierStack {
+    fn default() -> Self {
+        Self::new(DifficultyLevel::Normal, Mutators::default())
+    }
+}
+
+impl ModifierStack {
+    /// Stack for a run at `difficulty` with the selected mutators
+    pub fn new(difficulty: DifficultyLevel, mutators: Mutators) -> Self {
+        let mut stack = Self {
+            difficulty,
+            mutators: Mutators::default(),
+            season: None,
+            layers: Vec::new(),
+        };
+        stack = stack.with_layer(
+            ModifierSource::Difficulty,
+            RunModifier::from_difficulty(difficulty),
+        );
+        for mutator in mutators.iter() {
+            stack = stack.with_layer(ModifierSource::Mutator, mutator.modifier());
+        }
+        stack.mutators = mutators;
+        stack
+    }
+
+    /// Add a layer after every layer of its source and the sources before it
+    pub fn with_layer(mut self, source: ModifierSource, modifier: RunModifier) -> Self {
+        self.insert_layer(source, modifier);
+        self
+    }
+
+    fn insert_layer(&mut self, source: ModifierSource, modifier: RunModifier) {
+        let index = self
+            .layers
+            .iter()
+            .position(|(existing, _)| *existing > source)
+            .unwrap_or(self.layers.len());
+        self.layers.insert(index, (source, modifier));
+    }
+
+    /// Swap the season layer for the one of `season`
+    pub fn set_season(&mut self, season: Season) {
+        self.layers
+            .retain(|(source, _)| *source != ModifierSource::Season);
+        self.insert_layer(ModifierSource::Season, season.modifier());
+        self.season = Some(season);
+    }
+
+    /// Add or remove the layer of the Injured status
+    pub fn set_injured(&mut self, injured: bool) {
+        self.layers
+            .retain(|(source, _)| *source != ModifierSource::Injury);
+        if injured {
+            self.insert_layer(ModifierSource::Injury, injury_modifier());
+        }
+    }
+
+    /// Check if the Injured layer is in the stack
+    pub fn is_injured(&self) -> bool {
+        self.layers
+            .iter()
+            .any(|(source, _)| *source == ModifierSource::Injury)
+    }
+
+    /// Season whose layer is in the stack, if any yet
+    pub fn season(&self) -> Option<Season> {
+        self.season
+    }
+
+    /// Difficulty of the run
+    pub fn difficulty(&self) -> DifficultyLevel {
+        self.difficulty
+    }
+
+    /// Mutators of the run
+    pub fn mutators(&self) -> &Mutators {
+        &self.mutators
+    }
+
+    fn scale(&self, value: u32, multiplier: fn(&RunModifier) -> f32) -> u32 {
+        self.layers.iter().fold(value, |value, (_, modifier)| {
+            (value as f32 * multiplier(modifier)).round() as u32
+        })
+    }
+
+    fn offset(&self, value: u8, delta: fn(&RunModifier) -> i8) -> u8 {
+        self.layers.iter().fold(value, |value, (_, modifier)| {
+            (value as i16 + delta(modifier) as i16).clamp(1, u8::MAX as i16) as u8
+        })
+    }
+
+    /// Experience actually earned for `base` points
+    pub fn experience(&self, base: u32) -> u32 {
+        self.scale(base, |modifier| modifier.experience_multiplier)
+    }
+
+    /// Amount of a resource an event actually awards
+    pub fn event_reward(&self, base: u32) -> u32 {
+        self.scale(base, |modifier| modifier.event_reward_multiplier)
+    }
+
+    /// Movement points a hazard actually takes
+    pub fn hazard_damage(&self, base: u8) -> u8 {
+        self.scale(base as u32, |modifier| modifier.hazard_damage_multiplier)
+            .min(u8::MAX as u32) as u8
+    }
+
+    /// Amount of `resource` a find actually yields
+    pub fn resource_yield(&self, resource: ResourceType, base: u32) -> u32 {
+        self.layers
+            .iter()
+            .fold(base, |value, (_, modifier)| match modifier.yield_boost {
+                Some((boosted, multiplier)) if boosted == resource => {
+                    (value as f32 * multiplier).round() as u32
+                }
+                _ => value,
+            })
+    }
+
+    /// Everything in `found` after yield boosts
+    pub fn yields(&self, found: &ResourceCollection) -> ResourceCollection {
+        let mut boosted = ResourceCollection::new();
+        for resource in found.resource_types() {
+            boosted.set_amount(
+                resource,
+                self.resource_yield(resource, found.get_amount(resource)),
+            );
+        }
+        boosted
+    }
+
+    /// Movement points added to every step onto open terrain
+    pub fn open_terrain_surcharge(&self) -> u8 {
+        self.layers.iter().fold(0u8, |total, (_, modifier)| {
+            total.saturating_add(modifier.open_terrain_cost_delta)
+        })
+    }
+
+    /// Quiet movement rolls that turn up an event instead
+    pub fn event_pressure(&self) -> u8 {
+        self.layers.iter().fold(0u8, |total, (_, modifier)| {
+            total.saturating_add(modifier.event_pressure)
+        })
+    }
+
+    /// Penalty on every movement roll
+    pub fn threat(&self) -> u8 {
+        self.layers.iter().fold(0u8, |total, (_, modifier)| {
+            total.saturating_add(modifier.threat_delta)
+        })
+    }
+
+    /// Chance per rest that equipped gear malfunctions, in percent
+    pub fn malfunction_chance(&self) -> u8 {
+        self.layers
+            .iter()
+            .fold(0u8, |total, (_, modifier)| {
+                total.saturating_add(modifier.malfunction_chance)
+            })
+            .min(100)
+    }
+
+    /// Movement point maximum for an unmodified `base`, never below one
+    pub fn max_movement(&self, base: u8) -> u8 {
+        self.offset(base, |modifier| modifier.max_movement_delta)
+    }
+
+    /// Movement points left after a rest that would restore `restored`,
+    /// never below one
+    pub fn rest_movement(&self, restored: u8) -> u8 {
+        self.offset(restored, |modifier| modifier.rest_movement_delta)
+    }
+
+    /// Food an expedition needs instead of `base`
+    pub fn food_upkeep(&self, base: u32) -> u32 {
+        if self.layers.iter().all(|(_, modifier)| modifier.food_upkeep) {
+            base
+        } else {
+            0
+        }
+    }
+
+    /// Check if bad night events spoil the rest
+    pub fn night_penalties(&self) -> bool {
+        self.layers
+            .iter()
+            .all(|(_, modifier)| modifier.night_penalties)
+    }
+
+    /// Radius charted around the spawn when the run starts
+    pub fn start_reveal_radius(&self) -> u32 {
+        self.layers
+            .iter()
+            .map(|(_, modifier)| modifier.start_reveal_radius)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Chart every generated tile within `radius` of `center`
+///
+/// Returns the number of tiles that were not explored before.
+pub fn chart_start_area(map: &mut Map, center: Position3D, radius: u32) -> usize {
+    let radius = radius as i32;
+    let mut charted = 0;
+    for dy in -radius..=radius {
+        for dx in -radius..=radius {
+            if dx * dx + dy * dy > radius * radius {
+                continue;
+            }
+            let coordinate = TileCoordinate::new(center.x + dx, center.y + dy, center.z);
+            let Some(tile) = map.get_tile(&coordinate) else {
+                continue;
+            };
+            if tile.is_explored() {
+                continue;
+            }
+            let mut explored = tile.clone();
+            explored.is_explored = true;
+            map.set_tile(coordinate, explored);
+            charted += 1;
+        }
+    }
+    charted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::constants::{
+        DRIFT_OPEN_TERRAIN_COST, FLARE_MALFUNCTION_CHANCE, SURGE_EVENT_PRESSURE, SURGE_THREAT,
+    };
+    use crate::domain::entities::MapTile;
+    use crate::domain::value_objects::terrain::{Elevation, TerrainType};
+    use crate::domain::value_objects::EntityId;
+
+    fn stack_with(mutator: Mutator) -> ModifierStack {
+        ModifierStack::new(DifficultyLevel::Normal, Mutators::new([mutator]))
+    }
+
+    #[test]
+    fn each_mutator_changes_only_its_own_hooks() {
+        let plain = ModifierStack::default();
+        assert_eq!(plain.experience(50), 50);
+        assert_eq!(plain.event_reward(15), 15);
+        assert_eq!(plain.hazard_damage(2), 2);
+        assert_eq!(plain.max_movement(3), 3);
+        assert_eq!(plain.rest_movement(3), 3);
+        assert_eq!(plain.food_upkeep(10), 10);
+        assert!(plain.night_penalties());
+        assert_eq!(plain.start_reveal_radius(), 0);
+
+        let iron_stomach = stack_with(Mutator::IronStomach);
+        assert_eq!(iron_stomach.food_upkeep(10), 0);
+        assert_eq!(iron_stomach.max_movement(5), 3);
+        assert_eq!(iron_stomach.max_movement(3), 1);
+        assert_eq!(iron_stomach.experience(50), 50);
+
+        let glass_cannon = stack_with(Mutator::GlassCannon);
+        assert_eq!(glass_cannon.event_reward(15), 30);
+        assert_eq!(glass_cannon.hazard_damage(2), 4);
+        assert_eq!(glass_cannon.max_movement(3), 3);
+
+        let cartographer = stack_with(Mutator::Cartographer);
+        assert_eq!(
+            cartographer.start_reveal_radius(),
+            CARTOGRAPHER_REVEAL_RADIUS
+        );
+        assert_eq!(cartographer.experience(50), 40);
+        assert_eq!(cartographer.event_reward(15), 15);
+
+        let night_owl = stack_with(Mutator::NightOwl);
+        assert!(!night_owl.night_penalties());
+        assert_eq!(night_owl.rest_movement(3), 2);
+        assert_eq!(night_owl.rest_movement(1), 1);
+        assert_eq!(night_owl.food_upkeep(10), 10);
+    }
+
+    #[test]
+    fn layers_apply_in_source_order_starting_with_difficulty() {
+        // Easy first: 7 * 0.75 = 5.25 -> 5, then 5 * 0.8 = 4
+        // Cartographer first would give 7 * 0.8 = 5.6 -> 6, then 4.5 -> 5
+        let stack = ModifierStack::new(
+            DifficultyLevel::Easy,
+            Mutators::new([Mutator::Cartographer]),
+        );
+        assert_eq!(stack.experience(7), 4);
+
+        let halve = RunModifier {
+            experience_multiplier: 0.5,
+            ..RunModifier::default()
+        };
+        // A milestone added later still applies before the mutators
+        let with_milestone = stack.clone().with_layer(ModifierSource::Milestone, halve);
+        assert_eq!(with_milestone.experience(7), 2);
+        let with_assist = stack.with_layer(ModifierSource::Assist, halve);
+        assert_eq!(with_assist.experience(7), 2);
+        assert_eq!(with_milestone.experience(17), 6);
+        assert_eq!(with_assist.experience(17), 5);
+    }
+
+    #[test]
+    fn each_season_changes_only_its_own_hooks_and_stacks_after_mutators() {
+        let mut stack = ModifierStack::default();
+        stack.set_season(Season::Calm);
+        assert_eq!(stack.season(), Some(Season::Calm));
+        assert_eq!(stack.layers.len(), 2);
+        assert_eq!(stack.resource_yield(ResourceType::Energy, 10), 10);
+        assert_eq!(stack.malfunction_chance(), 0);
+
+        stack.set_season(Season::Flare);
+        assert_eq!(stack.season(), Some(Season::Flare));
+        assert_eq!(stack.resource_yield(ResourceType::Energy, 10), 15);
+        assert_eq!(stack.resource_yield(ResourceType::Data, 10), 10);
+        assert_eq!(stack.malfunction_chance(), FLARE_MALFUNCTION_CHANCE);
+        assert_eq!(stack.open_terrain_surcharge(), 0);
+
+        // Swapping seasons replaces the layer instead of piling it up
+        stack.set_season(Season::Drift);
+        assert_eq!(stack.layers.len(), 2);
+        assert_eq!(stack.resource_yield(ResourceType::Energy, 10), 10);
+        assert_eq!(stack.resource_yield(ResourceType::Data, 10), 15);
+        assert_eq!(stack.open_terrain_surcharge(), DRIFT_OPEN_TERRAIN_COST);
+        assert_eq!(stack.malfunction_chance(), 0);
+        let found =
+            ResourceCollection::cost(&[(ResourceType::Data, 4), (ResourceType::Metal, 4)]).unwrap();
+        let boosted = stack.yields(&found);
+        assert_eq!(boosted.get_amount(ResourceType::Data), 6);
+        assert_eq!(boosted.get_amount(ResourceType::Metal), 4);
+
+        stack.set_season(Season::Surge);
+        assert_eq!(stack.event_pressure(), SURGE_EVENT_PRESSURE);
+        assert_eq!(stack.threat(), SURGE_THREAT);
+        assert_eq!(stack.open_terrain_surcharge(), 0);
+        assert_eq!(stack.experience(50), 50);
+
+        // The season applies after the mutators, whenever it was set
+        let halve_energy = RunModifier {
+            yield_boost: Some((ResourceType::Energy, 0.5)),
+            ..RunModifier::default()
+        };
+        let mut stack = stack_with(Mutator::GlassCannon);
+        stack.set_season(Season::Flare);
+        let stack = stack.with_layer(ModifierSource::Mutator, halve_energy);
+        // 5 * 0.5 = 2.5 -> 3, then 3 * 1.5 = 4.5 -> 5; the other way round gives 4
+        assert_eq!(stack.resource_yield(ResourceType::Energy, 5), 5);
+    }
+
+    #[test]
+    fn cartographer_charts_a_disc_around_the_spawn() {
+        let mut map = Map::new(EntityId::generate(), "Chart".to_string(), 3).unwrap();
+        for y in -20..=20 {
+            for x in -20..=20 {
+                map.set_tile(
+                    TileCoordinate::new(x, y, 0),
+                    MapTile::new(TerrainType::Plains, Elevation::sea_level(), false),
+                );
+            }
+        }
+
+        let charted = chart_start_area(&mut map, Position3D::origin(), CARTOGRAPHER_REVEAL_RADIUS);
+
+        assert!(charted > 600);
+        let explored = |x, y| {
+            map.get_tile(&TileCoordinate::new(x, y, 0))
+                .is_some_and(|tile| tile.is_explored())
+        };
+        assert!(explored(15, 0) && explored(0, -15));
+        assert!(!explored(15, 15) && !explored(16, 0));
+        assert_eq!(
+            chart_start_area(&mut map, Position3D::origin(), CARTOGRAPHER_REVEAL_RADIUS),
+            0
+        );
+    }
+}
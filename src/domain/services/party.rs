@@ -0,0 +1,378 @@
+//! Party - Two characters sharing a run in pass-and-play turns
+//!
+//! In hot-seat mode two characters explore the same map from the same base.
+//! They take whole days in turn: the active character plays until their rest
+//! completes, then the run waits on a handover until the next player takes
+//! the device. Stats, cargo, gear, movement points and the open distress
+//! signal belong to each character; the map, the base and its storage are
+//! shared. Characters standing on the same tile can exchange cargo.
+//!
+//! Only the active character is in play. The waiting one is kept here and
+//! swapped in on handover, so everything that reads "the player" follows
+//! the turn without knowing about the party.
+
+use crate::domain::constants::PARTY_SIZE;
+use crate::domain::entities::Player;
+use crate::domain::services::rescue::DistressSignal;
+use crate::domain::value_objects::resources::ResourceCollection;
+use crate::domain::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+
+/// Where the turn stands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TurnPhase {
+    /// The active character is playing their day
+    #[default]
+    Playing,
+    /// The day is over and the device is being passed on
+    Handover,
+}
+
+/// Whose day it is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotSeat {
+    active: usize,
+    phase: TurnPhase,
+}
+
+impl HotSeat {
+    /// Start with the first character playing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Party slot of the character in play
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Party slot of the character waiting for their turn
+    pub fn waiting(&self) -> usize {
+        (self.active + 1) % PARTY_SIZE
+    }
+
+    /// Whether the day is being played or handed over
+    pub fn phase(&self) -> TurnPhase {
+        self.phase
+    }
+
+    /// Check if the device is being passed on
+    pub fn is_handover(&self) -> bool {
+        self.phase == TurnPhase::Handover
+    }
+
+    /// End the active character's day
+    pub fn end_day(&mut self) -> DomainResult<()> {
+        if self.is_handover() {
+            return Err(DomainError::InvalidGameState(
+                "The day already ended; waiting for the next player".to_string(),
+            ));
+        }
+        self.phase = TurnPhase::Handover;
+        Ok(())
+    }
+
+    /// The waiting player took the device; returns their party slot
+    pub fn take_over(&mut self) -> DomainResult<usize> {
+        if !self.is_handover() {
+            return Err(DomainError::InvalidGameState(
+                "The active character's day is not over yet".to_string(),
+            ));
+        }
+        self.active = self.waiting();
+        self.phase = TurnPhase::Playing;
+        Ok(self.active)
+    }
+}
+
+/// Run counters that can be credited to a character
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunTally {
+    pub tiles_explored: u32,
+    pub experience_gained: u32,
+    pub resources_gathered: u32,
+}
+
+impl RunTally {
+    /// What was added since `earlier`
+    pub fn since(&self, earlier: &RunTally) -> RunTally {
+        RunTally {
+            tiles_explored: self.tiles_explored.saturating_sub(earlier.tiles_explored),
+            experience_gained: self
+                .experience_gained
+                .saturating_sub(earlier.experience_gained),
+            resources_gathered: self
+                .resources_gathered
+                .saturating_sub(earlier.resources_gathered),
+        }
+    }
+
+    fn add(&mut self, other: &RunTally) {
+        self.tiles_explored += other.tiles_explored;
+        self.experience_gained += other.experience_gained;
+        self.resources_gathered += other.resources_gathered;
+    }
+}
+
+/// What one character did for the run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Contribution {
+    pub name: String,
+    pub days_played: u32,
+    pub tally: RunTally,
+}
+
+impl Contribution {
+    /// Run summary line for this character
+    pub fn summary(&self) -> String {
+        format!(
+            "{}: {} days, {} tiles, {} XP, {} gathered",
+            self.name,
+            self.days_played,
+            self.tally.tiles_explored,
+            self.tally.experience_gained,
+            self.tally.resources_gathered
+        )
+    }
+}
+
+/// Cargo each side of an exchange hands over
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransferOffer {
+    /// From the active character to the waiting one
+    pub give: ResourceCollection,
+    /// From the waiting character to the active one
+    pub take: ResourceCollection,
+}
+
+impl TransferOffer {
+    /// Check if neither side hands anything over
+    pub fn is_empty(&self) -> bool {
+        self.give.is_empty() && self.take.is_empty()
+    }
+}
+
+/// The waiting character, the turn order and each character's contribution
+#[derive(Debug, Clone)]
+pub struct Party {
+    benched: Player,
+    benched_signal: Option<DistressSignal>,
+    turns: HotSeat,
+    contributions: [Contribution; PARTY_SIZE],
+    day_started_at: RunTally,
+}
+
+impl Party {
+    /// Form a party; `active_name` is the character already in play
+    pub fn new(active_name: &str, benched: Player) -> Self {
+        let contributions = [
+            Contribution {
+                name: active_name.to_string(),
+                ..Contribution::default()
+            },
+            Contribution {
+                name: benched.name().to_string(),
+                ..Contribution::default()
+            },
+        ];
+        Self::restore(benched, HotSeat::new(), contributions)
+    }
+
+    /// Rebuild a party, e.g. from a save
+    pub fn restore(
+        benched: Player,
+        turns: HotSeat,
+        contributions: [Contribution; PARTY_SIZE],
+    ) -> Self {
+        Self {
+            benched,
+            benched_signal: None,
+            turns,
+            contributions,
+            day_started_at: RunTally::default(),
+        }
+    }
+
+    /// The character waiting for their turn
+    pub fn benched(&self) -> &Player {
+        &self.benched
+    }
+
+    /// Turn order of the party
+    pub fn turns(&self) -> &HotSeat {
+        &self.turns
+    }
+
+    /// Each character's contribution, in party slot order
+    pub fn contributions(&self) -> &[Contribution; PARTY_SIZE] {
+        &self.contributions
+    }
+
+    /// Name of the character whose turn comes next
+    pub fn waiting_name(&self) -> &str {
+        &self.contributions[self.turns.waiting()].name
+    }
+
+    /// End the active character's day, crediting them with what the run
+    /// gained since it began; `tally` is the run total so far
+    pub fn end_day(&mut self, tally: RunTally) -> DomainResult<()> {
+        self.turns.end_day()?;
+        let contribution = &mut self.contributions[self.turns.active()];
+        contribution.days_played += 1;
+        contribution.tally.add(&tally.since(&self.day_started_at));
+        self.day_started_at = tally;
+        Ok(())
+    }
+
+    /// Hand control to the waiting character
+    ///
+    /// `active` and `signal` are the character in play and their open
+    /// distress signal; afterwards they hold the incoming character's.
+    pub fn take_over(
+        &mut self,
+        active: &mut Player,
+        signal: &mut Option<DistressSignal>,
+    ) -> DomainResult<usize> {
+        let slot = self.turns.take_over()?;
+        std::mem::swap(active, &mut self.benched);
+        std::mem::swap(signal, &mut self.benched_signal);
+        Ok(slot)
+    }
+
+    /// Exchange cargo between the active and the waiting character
+    ///
+    /// Both must stand on the same tile. Either both sides are handed over
+    /// or, when one side cannot pay or would be overloaded, nothing is.
+    pub fn exchange(&mut self, active: &mut Player, offer: &TransferOffer) -> DomainResult<()> {
+        if active.position() != self.benched.position() {
+            return Err(DomainError::PlayerError(format!(
+                "{} is not on this tile",
+                self.benched.name()
+            )));
+        }
+        let active_cargo = exchanged_cargo(active, &offer.give, &offer.take)?;
+        let benched_cargo = exchanged_cargo(&self.benched, &offer.take, &offer.give)?;
+        *active.resources_mut() = active_cargo;
+        *self.benched.resources_mut() = benched_cargo;
+        Ok(())
+    }
+}
+
+/// A character's cargo after giving `gives` and receiving `receives`
+fn exchanged_cargo(
+    player: &Player,
+    gives: &ResourceCollection,
+    receives: &ResourceCollection,
+) -> DomainResult<ResourceCollection> {
+    let mut cargo = player.resources().clone();
+    cargo.pay_cost(gives).map_err(|_| {
+        DomainError::InsufficientResources(format!("{} cannot hand over {}", player.name(), gives))
+    })?;
+    cargo.add_collection(receives)?;
+    if cargo.storage_requirement() > player.carrying_capacity() {
+        return Err(DomainError::InsufficientResources(format!(
+            "{} has no room for {}",
+            player.name(),
+            receives
+        )));
+    }
+    Ok(cargo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{Position3D, ResourceType};
+
+    fn character(name: &str, metal: u32) -> Player {
+        let mut player =
+            Player::create_new_character(name.to_string(), Position3D::origin()).unwrap();
+        *player.resources_mut() =
+            ResourceCollection::cost(&[(ResourceType::Metal, metal)]).unwrap();
+        player
+    }
+
+    #[test]
+    fn turns_alternate_through_a_handover() {
+        let mut turns = HotSeat::new();
+        assert_eq!(turns.active(), 0);
+        assert!(turns.take_over().is_err());
+
+        turns.end_day().unwrap();
+        assert!(turns.is_handover());
+        assert!(turns.end_day().is_err());
+        assert_eq!(turns.take_over().unwrap(), 1);
+        assert_eq!(turns.phase(), TurnPhase::Playing);
+
+        turns.end_day().unwrap();
+        assert_eq!(turns.take_over().unwrap(), 0);
+    }
+
+    #[test]
+    fn handover_swaps_private_state_and_credits_the_day() {
+        let mut active = character("Vex", 10);
+        let mut party = Party::new("Vex", character("Rook", 30));
+        let mut signal = Some(DistressSignal {
+            target: Position3D::new(4, 4, 0),
+            rests_remaining: 2,
+        });
+
+        let tally = RunTally {
+            tiles_explored: 12,
+            experience_gained: 40,
+            resources_gathered: 5,
+        };
+        party.end_day(tally).unwrap();
+        assert_eq!(party.take_over(&mut active, &mut signal).unwrap(), 1);
+
+        assert_eq!(active.name(), "Rook");
+        assert_eq!(active.resources().get_amount(ResourceType::Metal), 30);
+        assert_eq!(signal, None);
+        assert_eq!(party.benched().name(), "Vex");
+        assert_eq!(party.contributions()[0].days_played, 1);
+        assert_eq!(party.contributions()[0].tally, tally);
+
+        party
+            .end_day(RunTally {
+                tiles_explored: 20,
+                ..tally
+            })
+            .unwrap();
+        party.take_over(&mut active, &mut signal).unwrap();
+        assert_eq!(active.name(), "Vex");
+        assert_eq!(signal.map(|signal| signal.rests_remaining), Some(2));
+        assert_eq!(party.contributions()[1].tally.tiles_explored, 8);
+        assert_eq!(party.contributions()[1].tally.experience_gained, 0);
+    }
+
+    #[test]
+    fn exchanges_are_all_or_nothing() {
+        let mut active = character("Vex", 10);
+        let mut party = Party::new("Vex", character("Rook", 30));
+        let offer = TransferOffer {
+            give: ResourceCollection::cost(&[(ResourceType::Metal, 10)]).unwrap(),
+            take: ResourceCollection::cost(&[(ResourceType::Metal, 25)]).unwrap(),
+        };
+        party.exchange(&mut active, &offer).unwrap();
+        assert_eq!(active.resources().get_amount(ResourceType::Metal), 25);
+        assert_eq!(
+            party.benched().resources().get_amount(ResourceType::Metal),
+            15
+        );
+
+        // Rook cannot hand over what they do not have: neither side changes
+        let greedy = TransferOffer {
+            give: ResourceCollection::cost(&[(ResourceType::Metal, 5)]).unwrap(),
+            take: ResourceCollection::cost(&[(ResourceType::Metal, 40)]).unwrap(),
+        };
+        assert!(party.exchange(&mut active, &greedy).is_err());
+        assert_eq!(active.resources().get_amount(ResourceType::Metal), 25);
+        assert_eq!(
+            party.benched().resources().get_amount(ResourceType::Metal),
+            15
+        );
+
+        active.relocate(Position3D::new(1, 0, 0));
+        assert!(party.exchange(&mut active, &offer).is_err());
+    }
+}
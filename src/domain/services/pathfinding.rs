@@ -0,0 +1,361 @@
+//! Pathfinding Service - Cheapest routes across explored terrain
+//!
+//! Finds the route with the lowest total movement cost between two tiles
+//! using A* over the four cardinal neighbors, or all eight when diagonal
+//! steps are allowed. Only explored, passable tiles are considered so
+//! planned routes never reveal unexplored terrain, and a diagonal step
+//! never cuts the corner of an impassable tile.
+
+use crate::domain::constants::PATHFINDING_MAX_EXPANSIONS;
+use crate::domain::entities::Map;
+use crate::domain::value_objects::{Position3D, TileCoordinate};
+use crate::domain::{DomainError, DomainResult};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// A route between two tiles
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    /// Tiles stepped onto, excluding the start and including the goal
+    pub steps: Vec<Position3D>,
+    /// Sum of the movement cost of every step
+    pub movement_cost: u32,
+}
+
+impl Route {
+    /// Number of tiles travelled
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Check if the route has no steps (start equals goal)
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+/// Tiles stepped onto along the cheapest known route from `from` to `to`
+///
+/// Ends with `to`; empty when already there. Fails with
+/// `TileNotAccessible` when no route through explored terrain exists.
+pub fn find_route(
+    map: &Map,
+    from: Position3D,
+    to: Position3D,
+    allow_diagonal: bool,
+) -> DomainResult<Vec<Position3D>> {
+    PathfindingService::new()
+        .find_path_with(map, from, to, allow_diagonal)
+        .map(|route| route.steps)
+        .ok_or(DomainError::TileNotAccessible(to.x, to.y, to.z))
+}
+
+/// Stateless A* pathfinder over the tile map
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PathfindingService;
+
+impl PathfindingService {
+    /// Create a new pathfinding service
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check if a tile can be part of a planned route
+    pub fn is_walkable(&self, map: &Map, position: &Position3D) -> bool {
+        map.get_tile(&TileCoordinate::from(*position))
+            .map(|tile| tile.is_explored() && tile.terrain_type.is_passable())
+            .unwrap_or(false)
+    }
+
+    /// Find the cheapest route from `from` to `to` in cardinal steps
+    ///
+    /// Returns `None` when the goal is not walkable, cannot be reached
+    /// through explored terrain, or the search exceeds its node budget.
+    pub fn find_path(&self, map: &Map, from: Position3D, to: Position3D) -> Option<Route> {
+        self.find_path_with(map, from, to, false)
+    }
+
+    /// Find the cheapest route from `from` to `to`, with diagonal steps if
+    /// `allow_diagonal` is set
+    pub fn find_path_with(
+        &self,
+        map: &Map,
+        from: Position3D,
+        to: Position3D,
+        allow_diagonal: bool,
+    ) -> Option<Route> {
+        if from == to {
+            return Some(Route {
+                steps: Vec::new(),
+                movement_cost: 0,
+            });
+        }
+        if !self.is_walkable(map, &to) {
+            return None;
+        }
+
+        let mut open = BinaryHeap::new();
+        let mut best_cost: HashMap<Position3D, u32> = HashMap::new();
+        let mut came_from: HashMap<Position3D, Position3D> = HashMap::new();
+
+        // Every step costs at least 1, so the step count never overestimates
+        let heuristic = |position: Position3D| {
+            if allow_diagonal {
+                position.x.abs_diff(to.x).max(position.y.abs_diff(to.y))
+            } else {
+                position.manhattan_distance_2d(&to)
+            }
+        };
+
+        best_cost.insert(from, 0);
+        open.push(Reverse((heuristic(from), 0u32, Self::key(from))));
+
+        let mut expansions = 0;
+        while let Some(Reverse((_, cost, (x, y, z)))) = open.pop() {
+            let current = Position3D::new(x, y, z);
+            if current == to {
+                return Some(Route {
+                    steps: Self::reconstruct(&came_from, from, to),
+                    movement_cost: cost,
+                });
+            }
+            if cost > best_cost.get(&current).copied().unwrap_or(u32::MAX) {
+                continue; // Stale queue entry
+            }
+
+            expansions += 1;
+            if expansions > PATHFINDING_MAX_EXPANSIONS {
+                return None;
+            }
+
+            for (dx, dy) in Self::STEPS {
+                let diagonal = dx != 0 && dy != 0;
+                if diagonal && !allow_diagonal {
+                    continue;
+                }
+                let neighbor = current.offset(dx, dy, 0);
+                let cuts_corner = diagonal
+                    && !(self.is_walkable(map, &current.offset(dx, 0, 0))
+                        && self.is_walkable(map, &current.offset(0, dy, 0)));
+                if cuts_corner || !self.is_walkable(map, &neighbor) {
+                    continue;
+                }
+                let next_cost = cost + map.movement_cost(&neighbor) as u32;
+                if next_cost < best_cost.get(&neighbor).copied().unwrap_or(u32::MAX) {
+                    best_cost.insert(neighbor, next_cost);
+                    came_from.insert(neighbor, current);
+                    let estimate = next_cost + heuristic(neighbor);
+                    open.push(Reverse((estimate, next_cost, Self::key(neighbor))));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Cheapest route to the closest unexplored, passable tile
+    ///
+    /// The route crosses explored terrain only; its last step is the
+    /// unexplored tile itself. Ties are broken by coordinates so the same
+    /// map always yields the same route.
+    pub fn nearest_unexplored(&self, map: &Map, from: Position3D) -> Option<Route> {
+        let mut open = BinaryHeap::new();
+        let mut best_cost: HashMap<Position3D, u32> = HashMap::new();
+        let mut came_from: HashMap<Position3D, Position3D> = HashMap::new();
+
+        best_cost.insert(from, 0);
+        open.push(Reverse((0u32, Self::key(from))));
+
+        let mut expansions = 0;
+        while let Some(Reverse((cost, (x, y, z)))) = open.pop() {
+            let current = Position3D::new(x, y, z);
+            if cost > best_cost.get(&current).copied().unwrap_or(u32::MAX) {
+                continue; // Stale queue entry
+            }
+            if current != from && !self.is_walkable(map, &current) {
+                return Some(Route {
+                    steps: Self::reconstruct(&came_from, from, current),
+                    movement_cost: cost,
+                });
+            }
+
+            expansions += 1;
+            if expansions > PATHFINDING_MAX_EXPANSIONS {
+                return None;
+            }
+
+            for neighbor in [
+                current.offset(0, 1, 0),
+                current.offset(0, -1, 0),
+                current.offset(1, 0, 0),
+                current.offset(-1, 0, 0),
+            ] {
+                let passable = map
+                    .get_tile(&TileCoordinate::from(neighbor))
+                    .is_some_and(|tile| tile.terrain_type.is_passable());
+                if !passable {
+                    continue;
+                }
+                let next_cost = cost + map.movement_cost(&neighbor) as u32;
+                if next_cost < best_cost.get(&neighbor).copied().unwrap_or(u32::MAX) {
+                    best_cost.insert(neighbor, next_cost);
+                    came_from.insert(neighbor, current);
+                    open.push(Reverse((next_cost, Self::key(neighbor))));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Cardinal steps first, then diagonals
+    const STEPS: [(i32, i32); 8] = [
+        (0, 1),
+        (0, -1),
+        (1, 0),
+        (-1, 0),
+        (1, 1),
+        (1, -1),
+        (-1, 1),
+        (-1, -1),
+    ];
+
+    /// Orderable queue key for a position
+    fn key(position: Position3D) -> (i32, i32, i32) {
+        (position.x, position.y, position.z)
+    }
+
+    fn reconstruct(
+        came_from: &HashMap<Position3D, Position3D>,
+        from: Position3D,
+        to: Position3D,
+    ) -> Vec<Position3D> {
+        let mut steps = vec![to];
+        let mut current = to;
+        while let Some(previous) = came_from.get(&current) {
+            if *previous == from {
+                break;
+            }
+            steps.push(*previous);
+            current = *previous;
+        }
+        steps.reverse();
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::MapTile;
+    use crate::domain::value_objects::terrain::{Elevation, TerrainType};
+    use crate::domain::value_objects::EntityId;
+
+    fn map_from_rows(rows: &[&str]) -> Map {
+        let mut map = Map::new(EntityId::generate(), "Test".to_string(), 1).unwrap();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, symbol) in row.chars().enumerate() {
+                let terrain = match symbol {
+                    '.' => TerrainType::Plains,
+                    'S' => TerrainType::Swamp,
+                    '~' => TerrainType::Ocean,
+                    _ => continue,
+                };
+                map.set_tile(
+                    TileCoordinate::new(x as i32, y as i32, 0),
+                    MapTile::new(terrain, Elevation::sea_level(), true),
+                );
+            }
+        }
+        map
+    }
+
+    #[test]
+    fn finds_cheapest_route_around_expensive_terrain() {
+        let map = map_from_rows(&["...", ".S.", "..."]);
+        let route = PathfindingService::new()
+            .find_path(&map, Position3D::new(0, 1, 0), Position3D::new(2, 1, 0))
+            .unwrap();
+
+        assert_eq!(route.len(), 4);
+        assert!(!route.steps.contains(&Position3D::new(1, 1, 0)));
+        assert_eq!(route.steps.last(), Some(&Position3D::new(2, 1, 0)));
+        assert_eq!(
+            route.movement_cost,
+            4 * TerrainType::Plains.movement_cost() as u32
+        );
+    }
+
+    #[test]
+    fn impassable_or_unexplored_goals_are_unreachable() {
+        let mut map = map_from_rows(&[".~."]);
+        let service = PathfindingService::new();
+
+        // Ocean blocks the only connection
+        assert!(service
+            .find_path(&map, Position3D::new(0, 0, 0), Position3D::new(2, 0, 0))
+            .is_none());
+
+        map.set_tile(
+            TileCoordinate::new(3, 0, 0),
+            MapTile::new(TerrainType::Plains, Elevation::sea_level(), false),
+        );
+        assert!(!service.is_walkable(&map, &Position3D::new(3, 0, 0)));
+    }
+
+    #[test]
+    fn diagonal_routes_only_when_allowed_and_never_around_corners() {
+        let map = map_from_rows(&["...", "...", "..."]);
+        let from = Position3D::new(0, 0, 0);
+        let to = Position3D::new(2, 2, 0);
+
+        assert_eq!(find_route(&map, from, to, false).unwrap().len(), 4);
+        assert_eq!(
+            find_route(&map, from, to, true).unwrap(),
+            vec![Position3D::new(1, 1, 0), to]
+        );
+
+        // Ocean on both sides of the corner keeps the route cardinal
+        let walled = map_from_rows(&[".~", "~."]);
+        assert_eq!(
+            find_route(&walled, from, Position3D::new(1, 1, 0), true),
+            Err(DomainError::TileNotAccessible(1, 1, 0))
+        );
+        assert_eq!(find_route(&map, from, from, true), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn nearest_unexplored_prefers_the_cheapest_frontier() {
+        let mut map = map_from_rows(&[".S..", "....", "...."]);
+        for (x, y) in [(4, 1), (1, -1)] {
+            map.set_tile(
+                TileCoordinate::new(x, y, 0),
+                MapTile::new(TerrainType::Plains, Elevation::sea_level(), false),
+            );
+        }
+        let service = PathfindingService::new();
+
+        // (1, -1) is fewer steps away, but only across the swamp at (1, 0)
+        let route = service
+            .nearest_unexplored(&map, Position3D::new(1, 1, 0))
+            .unwrap();
+        assert_eq!(
+            route.steps,
+            vec![
+                Position3D::new(2, 1, 0),
+                Position3D::new(3, 1, 0),
+                Position3D::new(4, 1, 0)
+            ]
+        );
+        assert_eq!(
+            route.movement_cost,
+            3 * TerrainType::Plains.movement_cost() as u32
+        );
+
+        // Fully explored maps have no frontier
+        let explored = map_from_rows(&["..."]);
+        assert!(service
+            .nearest_unexplored(&explored, Position3D::new(0, 0, 0))
+            .is_none());
+    }
+}
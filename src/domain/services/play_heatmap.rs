@@ -0,0 +1,413 @@
+//! Play Heatmap - Where a run spent its time and what happened there
+//!
+//! Every tile keeps four counters: how often the player ended a move on
+//! it, how many events it triggered, how many resources were taken from it
+//! and how much damage was suffered on it. Only tiles with something to
+//! show are stored, so a long run costs as many entries as tiles it
+//! touched, and each update is a single map lookup. Counters are graded
+//! against the current maximum of a metric for drawing, and summed over
+//! square regions of `HEATMAP_REGION_SIZE` tiles for the run summary.
+
+use crate::domain::constants::{HEATMAP_GRADES, HEATMAP_REGION_SIZE};
+use crate::domain::value_objects::Position3D;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// What a heatmap counts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum HeatMetric {
+    /// Moves ended on the tile
+    #[default]
+    Visits,
+    /// Events triggered on the tile
+    Events,
+    /// Resources gathered on the tile
+    Extracted,
+    /// Damage suffered on the tile
+    Damage,
+}
+
+impl HeatMetric {
+    /// Get all metrics in cycling order
+    pub fn all() -> [HeatMetric; 4] {
+        [
+            HeatMetric::Visits,
+            HeatMetric::Events,
+            HeatMetric::Extracted,
+            HeatMetric::Damage,
+        ]
+    }
+
+    /// The metric after this one, wrapping around
+    pub fn next(&self) -> Self {
+        let all = Self::all();
+        let index = all.iter().position(|metric| metric == self).unwrap_or(0);
+        all[(index + 1) % all.len()]
+    }
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            HeatMetric::Visits => "Visits",
+            HeatMetric::Events => "Events triggered",
+            HeatMetric::Extracted => "Resources extracted",
+            HeatMetric::Damage => "Damage taken",
+        }
+    }
+}
+
+/// Counters of one tile
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TileHeat {
+    pub visits: u32,
+    pub events: u32,
+    pub extracted: u32,
+    pub damage: u32,
+}
+
+impl TileHeat {
+    /// Counter of one metric
+    pub fn get(&self, metric: HeatMetric) -> u32 {
+        match metric {
+            HeatMetric::Visits => self.visits,
+            HeatMetric::Events => self.events,
+            HeatMetric::Extracted => self.extracted,
+            HeatMetric::Damage => self.damage,
+        }
+    }
+
+    fn get_mut(&mut self, metric: HeatMetric) -> &mut u32 {
+        match metric {
+            HeatMetric::Visits => &mut self.visits,
+            HeatMetric::Events => &mut self.events,
+            HeatMetric::Extracted => &mut self.extracted,
+            HeatMetric::Damage => &mut self.damage,
+        }
+    }
+}
+
+/// A tile's counters as written to a save
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeatTile {
+    pub position: Position3D,
+    pub heat: TileHeat,
+}
+
+/// Saved form of the heatmap: touched tiles in a stable order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeatmapRecord {
+    pub tiles: Vec<HeatTile>,
+}
+
+/// Square of `HEATMAP_REGION_SIZE` tiles and what it added up to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeatRegion {
+    /// Corner of the region with the lowest coordinates
+    pub origin: Position3D,
+    pub total: u32,
+}
+
+impl HeatRegion {
+    /// Region spanned, as "(x, y) to (x, y)"
+    pub fn describe(&self) -> String {
+        let last = HEATMAP_REGION_SIZE - 1;
+        format!(
+            "({}, {}) to ({}, {})",
+            self.origin.x,
+            self.origin.y,
+            self.origin.x + last,
+            self.origin.y + last
+        )
+    }
+}
+
+/// Per-tile counters of a run, only for tiles that have any
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "HeatmapRecord", into = "HeatmapRecord")]
+pub struct PlayHeatmap {
+    tiles: HashMap<Position3D, TileHeat>,
+    /// Bumped on every update, so views know when to redraw
+    revision: u64,
+}
+
+impl PartialEq for PlayHeatmap {
+    fn eq(&self, other: &Self) -> bool {
+        self.tiles == other.tiles
+    }
+}
+
+impl From<HeatmapRecord> for PlayHeatmap {
+    fn from(record: HeatmapRecord) -> Self {
+        Self {
+            tiles: record
+                .tiles
+                .into_iter()
+                .filter(|tile| tile.heat != TileHeat::default())
+                .map(|tile| (tile.position, tile.heat))
+                .collect(),
+            revision: 0,
+        }
+    }
+}
+
+impl From<PlayHeatmap> for HeatmapRecord {
+    fn from(heatmap: PlayHeatmap) -> Self {
+        let mut tiles: Vec<HeatTile> = heatmap
+            .tiles
+            .into_iter()
+            .map(|(position, heat)| HeatTile { position, heat })
+            .collect();
+        tiles.sort_by_key(|tile| (tile.position.z, tile.position.y, tile.position.x));
+        Self { tiles }
+    }
+}
+
+impl PlayHeatmap {
+    /// A run that has not touched any tile yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `amount` to a tile's counter; nothing is stored for zero
+    pub fn record(&mut self, position: Position3D, metric: HeatMetric, amount: u32) {
+        if amount == 0 {
+            return;
+        }
+        let counter = self.tiles.entry(position).or_default().get_mut(metric);
+        *counter = counter.saturating_add(amount);
+        self.revision += 1;
+    }
+
+    /// Counters of a tile, all zero if nothing happened there
+    pub fn at(&self, position: Position3D) -> TileHeat {
+        self.tiles.get(&position).copied().unwrap_or_default()
+    }
+
+    /// Tiles with at least one counter above zero
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Check if no tile has been touched
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// Changes since the heatmap was created or loaded
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Highest counter of a metric over tiles on level `z`
+    pub fn max(&self, metric: HeatMetric, z: i32) -> u32 {
+        self.tiles
+            .iter()
+            .filter(|(position, _)| position.z == z)
+            .map(|(_, heat)| heat.get(metric))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Tile the player took the most damage on; ties go to the lowest y, then x
+    pub fn most_dangerous(&self) -> Option<(Position3D, u32)> {
+        self.tiles
+            .iter()
+            .filter(|(_, heat)| heat.damage > 0)
+            .map(|(position, heat)| (*position, heat.damage))
+            .min_by_key(|(position, damage)| {
+                (
+                    std::cmp::Reverse(*damage),
+                    position.z,
+                    position.y,
+                    position.x,
+                )
+            })
+    }
+
+    /// Region the most resources were extracted from; ties as for tiles
+    pub fn most_profitable_region(&self) -> Option<HeatRegion> {
+        let mut regions: HashMap<Position3D, u32> = HashMap::new();
+        for (position, heat) in &self.tiles {
+            if heat.extracted > 0 {
+                *regions.entry(region_of(*position)).or_default() += heat.extracted;
+            }
+        }
+        regions
+            .into_iter()
+            .map(|(origin, total)| HeatRegion { origin, total })
+            .min_by_key(|region| {
+                (
+                    std::cmp::Reverse(region.total),
+                    region.origin.z,
+                    region.origin.y,
+                    region.origin.x,
+                )
+            })
+    }
+
+    /// The two run summary lines
+    pub fn summary_lines(&self) -> [String; 2] {
+        [
+            match self.most_dangerous() {
+                Some((position, damage)) => format!(
+                    "Most dangerous tile: ({}, {}) - {} damage taken",
+                    position.x, position.y, damage
+                ),
+                None => "Most dangerous tile: none - no damage taken".to_string(),
+            },
+            match self.most_profitable_region() {
+                Some(region) => format!(
+                    "Most profitable region: {} - {} resources extracted",
+                    region.describe(),
+                    region.total
+                ),
+                None => "Most profitable region: none - nothing extracted".to_string(),
+            },
+        ]
+    }
+}
+
+/// Corner of the region a tile belongs to
+pub fn region_of(position: Position3D) -> Position3D {
+    Position3D::new(
+        position.x.div_euclid(HEATMAP_REGION_SIZE) * HEATMAP_REGION_SIZE,
+        position.y.div_euclid(HEATMAP_REGION_SIZE) * HEATMAP_REGION_SIZE,
+        position.z,
+    )
+}
+
+/// Share of the maximum a counter reaches, from 0.0 to 1.0
+pub fn normalized(value: u32, max: u32) -> f32 {
+    if max == 0 {
+        return 0.0;
+    }
+    (value as f32 / max as f32).min(1.0)
+}
+
+/// Grade of a counter, from 0 (nothing) to `HEATMAP_GRADES` (the maximum)
+///
+/// Grade `g` holds the counters above `(g - 1) / HEATMAP_GRADES` of the
+/// maximum and up to `g / HEATMAP_GRADES` of it.
+pub fn heat_grade(value: u32, max: u32) -> u32 {
+    if value == 0 || max == 0 {
+        return 0;
+    }
+    let value = value.min(max) as u64;
+    (value * HEATMAP_GRADES as u64).div_ceil(max as u64) as u32
+}
+
+/// Counters falling in a grade, for the legend; `None` if it holds none
+pub fn grade_range(grade: u32, max: u32) -> Option<(u32, u32)> {
+    if grade == 0 || grade > HEATMAP_GRADES || max == 0 {
+        return None;
+    }
+    let bound = |grade: u32| (grade as u64 * max as u64 / HEATMAP_GRADES as u64) as u32;
+    let low = bound(grade - 1) + 1;
+    let high = bound(grade);
+    (low <= high).then_some((low, high))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_scripted_run_only_stores_touched_tiles() {
+        let mut heatmap = PlayHeatmap::new();
+        let camp = Position3D::new(1, 1, 0);
+        let mine = Position3D::new(2, 1, 0);
+        let ambush = Position3D::new(-3, 4, 0);
+
+        for _ in 0..3 {
+            heatmap.record(camp, HeatMetric::Visits, 1);
+        }
+        heatmap.record(mine, HeatMetric::Visits, 1);
+        heatmap.record(mine, HeatMetric::Extracted, 12);
+        heatmap.record(mine, HeatMetric::Extracted, 5);
+        heatmap.record(ambush, HeatMetric::Events, 1);
+        heatmap.record(ambush, HeatMetric::Damage, 9);
+        heatmap.record(Position3D::new(9, 9, 0), HeatMetric::Damage, 0);
+
+        assert_eq!(heatmap.len(), 3);
+        assert_eq!(heatmap.at(camp).visits, 3);
+        assert_eq!(
+            heatmap.at(mine),
+            TileHeat {
+                visits: 1,
+                extracted: 17,
+                ..TileHeat::default()
+            }
+        );
+        assert_eq!(heatmap.at(ambush).get(HeatMetric::Damage), 9);
+        assert_eq!(heatmap.at(Position3D::new(9, 9, 0)), TileHeat::default());
+        assert_eq!(heatmap.max(HeatMetric::Visits, 0), 3);
+        assert_eq!(heatmap.max(HeatMetric::Visits, -1), 0);
+
+        // Saved sorted, loaded back the same
+        let record = HeatmapRecord::from(heatmap.clone());
+        assert_eq!(record.tiles[0].position, camp);
+        assert_eq!(PlayHeatmap::from(record), heatmap);
+    }
+
+    #[test]
+    fn counters_are_graded_against_the_maximum() {
+        assert_eq!(heat_grade(0, 40), 0);
+        assert_eq!(heat_grade(5, 0), 0);
+        assert_eq!(heat_grade(1, 40), 1);
+        assert_eq!(heat_grade(10, 40), 1);
+        assert_eq!(heat_grade(11, 40), 2);
+        assert_eq!(heat_grade(40, 40), HEATMAP_GRADES);
+        assert_eq!(heat_grade(90, 40), HEATMAP_GRADES);
+        assert_eq!(normalized(10, 40), 0.25);
+        assert_eq!(normalized(3, 0), 0.0);
+
+        assert_eq!(grade_range(1, 40), Some((1, 10)));
+        assert_eq!(grade_range(4, 40), Some((31, 40)));
+        // With a maximum of 2 only the second and last grades hold anything
+        assert_eq!(grade_range(1, 2), None);
+        assert_eq!(grade_range(2, 2), Some((1, 1)));
+        assert_eq!(grade_range(4, 2), Some((2, 2)));
+        for max in 1..50 {
+            for value in 1..=max {
+                let (low, high) = grade_range(heat_grade(value, max), max).unwrap();
+                assert!(low <= value && value <= high, "{} of {}", value, max);
+            }
+        }
+    }
+
+    #[test]
+    fn the_summary_picks_the_worst_tile_and_the_richest_region() {
+        let mut heatmap = PlayHeatmap::new();
+        assert_eq!(
+            heatmap.summary_lines()[0],
+            "Most dangerous tile: none - no damage taken"
+        );
+
+        heatmap.record(Position3D::new(5, 2, 0), HeatMetric::Damage, 6);
+        heatmap.record(Position3D::new(-1, 0, 0), HeatMetric::Damage, 6);
+        heatmap.record(Position3D::new(3, 3, 0), HeatMetric::Damage, 2);
+        // Two modest hauls in one region beat the single best tile elsewhere
+        heatmap.record(Position3D::new(-2, -2, 0), HeatMetric::Extracted, 10);
+        heatmap.record(Position3D::new(-7, -8, 0), HeatMetric::Extracted, 10);
+        heatmap.record(Position3D::new(12, 3, 0), HeatMetric::Extracted, 15);
+
+        assert_eq!(
+            heatmap.most_dangerous(),
+            Some((Position3D::new(-1, 0, 0), 6))
+        );
+        assert_eq!(
+            heatmap.most_profitable_region(),
+            Some(HeatRegion {
+                origin: Position3D::new(-8, -8, 0),
+                total: 20
+            })
+        );
+        assert_eq!(
+            heatmap.summary_lines(),
+            [
+                "Most dangerous tile: (-1, 0) - 6 damage taken".to_string(),
+                "Most profitable region: (-8, -8) to (-1, -1) - 20 resources extracted".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,657 @@
+//! Quest Board - Offers posted at the base and the quests the player holds
+//!
+//! The base's bulletin board puts up a few new offers every couple of rests;
+//! offers nobody took by then come down. Accepting an offer moves it onto
+//! the player's active list, which holds only so many quests; Living
+//! Quarters add room for more. Quests without a target are measured against
+//! the run's counters - tiles charted, Metal hauled, nights rested - from the
+//! moment they were accepted. Abandoning an active quest is allowed but
+//! costs standing, and its kind of quest sits out the postings for a day.
+//! A quest whose target disappears - the node worked out or moved, the site
+//! no longer passable - is settled with a small token payment instead of
+//! staying on the list, impossible to finish.
+
+use crate::domain::constants::{
+    QUEST_ABANDON_COOLDOWN_DAYS, QUEST_ACTIVE_SLOTS, QUEST_BOARD_OFFERS, QUEST_BOARD_REFRESH_RESTS,
+    QUEST_CHARTING_TILES, QUEST_COMPENSATION_EXPERIENCE, QUEST_COMPENSATION_METAL,
+    QUEST_ENDURANCE_RESTS, QUEST_MAX_EXTRA_SLOTS, QUEST_SALVAGE_METAL, QUEST_TARGET_MAX_DISTANCE,
+    QUEST_TARGET_MIN_DISTANCE,
+};
+use crate::domain::entities::quest::{
+    ObjectiveType, Quest, QuestObjective, QuestRewards, QuestType,
+};
+use crate::domain::entities::Map;
+use crate::domain::value_objects::resources::ResourceCollection;
+use crate::domain::value_objects::{Position3D, ResourceType};
+use crate::domain::{DomainError, DomainResult};
+use rand::Rng;
+use std::collections::HashMap;
+
+/// Kinds of quest the board posts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuestTemplate {
+    /// Walk to a known site and look around
+    Survey,
+    /// Reach a resource node and assess it
+    Prospect,
+    /// Chart a number of new tiles
+    Charting,
+    /// Haul an amount of Metal
+    Salvage,
+    /// Rest out a number of nights in the field
+    Endurance,
+}
+
+impl QuestTemplate {
+    /// Every template, in posting order
+    pub fn all() -> [QuestTemplate; 5] {
+        [
+            QuestTemplate::Survey,
+            QuestTemplate::Prospect,
+            QuestTemplate::Charting,
+            QuestTemplate::Salvage,
+            QuestTemplate::Endurance,
+        ]
+    }
+
+    /// Name shown on the board
+    pub fn name(&self) -> &'static str {
+        match self {
+            QuestTemplate::Survey => "Survey",
+            QuestTemplate::Prospect => "Prospect",
+            QuestTemplate::Charting => "Charting",
+            QuestTemplate::Salvage => "Salvage",
+            QuestTemplate::Endurance => "Endurance",
+        }
+    }
+
+    /// Pick a target tile around the base, if the template needs one
+    ///
+    /// Returns `Some(None)` for templates without a target and `None` when
+    /// no known tile suits the template.
+    fn pick_target(&self, map: &Map, base: Position3D, roll: u64) -> Option<Option<Position3D>> {
+        if !self.needs_target() {
+            return Some(None);
+        }
+        let mut candidates: Vec<Position3D> = map
+            .get_tiles_in_radius(&base, QUEST_TARGET_MAX_DISTANCE)
+            .into_iter()
+            .map(|(coordinate, _)| Position3D::new(coordinate.x, coordinate.y, coordinate.z))
+            .filter(|position| {
+                position.manhattan_distance_2d(&base) >= QUEST_TARGET_MIN_DISTANCE
+                    && self.target_is_valid(map, *position)
+            })
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        candidates.sort_by_key(|position| (position.x, position.y));
+        Some(Some(candidates[(roll % candidates.len() as u64) as usize]))
+    }
+
+    /// Check if the template sends the player to a tile
+    pub fn needs_target(&self) -> bool {
+        matches!(self, QuestTemplate::Survey | QuestTemplate::Prospect)
+    }
+
+    /// Check whether the quest can still be finished at `target`
+    pub fn target_is_valid(&self, map: &Map, target: Position3D) -> bool {
+        match self {
+            QuestTemplate::Survey => map.terrain_at(&target).is_some() && map.is_passable(&target),
+            QuestTemplate::Prospect => map
+                .get_resource_node(&target)
+                .is_some_and(|node| !node.is_depleted()),
+            QuestTemplate::Charting | QuestTemplate::Salvage | QuestTemplate::Endurance => true,
+        }
+    }
+
+    /// Build the quest entity for a target
+    fn build(&self, target: Option<Position3D>) -> DomainResult<Quest> {
+        let (title, description, quest_type, objective, rewards) = match (self, target) {
+            (QuestTemplate::Survey, Some(target)) => (
+                format!("Survey [{}, {}]", target.x, target.y),
+                "The Colonial Authority wants eyes on a site near the base.".to_string(),
+                QuestType::Exploration,
+                QuestObjective::new(
+                    ObjectiveType::VisitLocation(vec![target]),
+                    format!("Reach [{}, {}]", target.x, target.y),
+                    1,
+                ),
+                rewards(60, &[(ResourceType::Data, 6)]),
+            ),
+            (QuestTemplate::Prospect, Some(target)) => (
+                format!("Prospect [{}, {}]", target.x, target.y),
+                "A deposit was reported nearby; confirm it before the crews head out.".to_string(),
+                QuestType::Gathering,
+                QuestObjective::new(
+                    ObjectiveType::VisitLocation(vec![target]),
+                    format!("Reach the node at [{}, {}]", target.x, target.y),
+                    1,
+                ),
+                rewards(50, &[(ResourceType::Metal, 15)]),
+            ),
+            (QuestTemplate::Charting, _) => (
+                format!("Chart {} tiles", QUEST_CHARTING_TILES),
+                "The maps of the sector are thin; fill in what you can.".to_string(),
+                QuestType::Exploration,
+                QuestObjective::new(
+                    ObjectiveType::ExploreTiles(QUEST_CHARTING_TILES),
+                    format!("Explore {} new tiles", QUEST_CHARTING_TILES),
+                    QUEST_CHARTING_TILES,
+                ),
+                rewards(40, &[(ResourceType::Food, 10)]),
+            ),
+            (QuestTemplate::Salvage, _) => (
+                format!("Haul {} Metal", QUEST_SALVAGE_METAL),
+                "The fabricators are starved; bring back whatever Metal you find.".to_string(),
+                QuestType::Gathering,
+                QuestObjective::new(
+                    ObjectiveType::CollectResources(HashMap::from([(
+                        ResourceType::Metal,
+                        QUEST_SALVAGE_METAL,
+                    )])),
+                    format!("Gather {} Metal", QUEST_SALVAGE_METAL),
+                    QUEST_SALVAGE_METAL,
+                ),
+                rewards(45, &[(ResourceType::Energy, 12)]),
+            ),
+            (QuestTemplate::Endurance, _) => (
+                format!("Weather {} nights", QUEST_ENDURANCE_RESTS),
+                "Prove an outpost crew can hold out between supply runs.".to_string(),
+                QuestType::Side,
+                QuestObjective::new(
+                    ObjectiveType::Survive(QUEST_ENDURANCE_RESTS),
+                    format!("Rest {} times", QUEST_ENDURANCE_RESTS),
+                    QUEST_ENDURANCE_RESTS,
+                ),
+                rewards(35, &[(ResourceType::Food, 8), (ResourceType::Energy, 6)]),
+            ),
+            (_, None) => {
+                return Err(DomainError::QuestError(format!(
+                    "{} quests need a target",
+                    self.name()
+                )))
+            }
+        };
+        Quest::new(title, description, quest_type, vec![objective], rewards)
+    }
+}
+
+fn rewards(experience: u32, resources: &[(ResourceType, u32)]) -> QuestRewards {
+    let mut collection = ResourceCollection::new();
+    for &(resource_type, amount) in resources {
+        collection.set_amount(resource_type, amount);
+    }
+    QuestRewards::new(experience, collection, Vec::new())
+}
+
+/// Run counters that quests without a target are measured against
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuestCounters {
+    pub tiles_explored: u32,
+    pub metal_gathered: u32,
+    pub nights_rested: u32,
+}
+
+impl QuestCounters {
+    /// Progress of `template` since `start`
+    fn progress_since(&self, start: &QuestCounters, template: QuestTemplate) -> u32 {
+        match template {
+            QuestTemplate::Charting => self.tiles_explored.saturating_sub(start.tiles_explored),
+            QuestTemplate::Salvage => self.metal_gathered.saturating_sub(start.metal_gathered),
+            QuestTemplate::Endurance => self.nights_rested.saturating_sub(start.nights_rested),
+            QuestTemplate::Survey | QuestTemplate::Prospect => 0,
+        }
+    }
+}
+
+/// A quest on the board or on the player's list
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostedQuest {
+    pub template: QuestTemplate,
+    pub quest: Quest,
+    /// Tile the quest sends the player to, if any
+    pub target: Option<Position3D>,
+    /// Run counters when the quest was accepted
+    counters_at_accept: QuestCounters,
+}
+
+/// What a posting did to the board
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BoardPosting {
+    /// Offers that came down unaccepted
+    pub expired: usize,
+    /// New offers put up
+    pub posted: usize,
+}
+
+/// Token paid for an active quest whose target is gone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuestCompensation {
+    pub title: String,
+    pub experience: u32,
+    pub resources: Vec<(ResourceType, u32)>,
+}
+
+/// The base's bulletin board and the quests the player accepted
+#[derive(Debug, Clone, Default)]
+pub struct QuestBoard {
+    offers: Vec<PostedQuest>,
+    active: Vec<PostedQuest>,
+    rests_since_posting: u32,
+    has_posted: bool,
+    /// First day each abandoned template may be posted again
+    cooldowns: HashMap<QuestTemplate, u32>,
+}
+
+impl QuestBoard {
+    /// Create an empty board
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Offers on the board
+    pub fn offers(&self) -> &[PostedQuest] {
+        &self.offers
+    }
+
+    /// Quests the player holds
+    pub fn active(&self) -> &[PostedQuest] {
+        &self.active
+    }
+
+    /// Check if the board ever put up offers
+    pub fn has_posted(&self) -> bool {
+        self.has_posted
+    }
+
+    /// Rests left before the next posting
+    pub fn rests_until_posting(&self) -> u32 {
+        QUEST_BOARD_REFRESH_RESTS.saturating_sub(self.rests_since_posting)
+    }
+
+    /// Active quests allowed with Living Quarters at `quarters_level`
+    pub fn active_slots(quarters_level: Option<u8>) -> usize {
+        QUEST_ACTIVE_SLOTS + (quarters_level.unwrap_or(0) as usize).min(QUEST_MAX_EXTRA_SLOTS)
+    }
+
+    /// Check if a template sits out the postings on `day`
+    pub fn is_cooling_down(&self, template: QuestTemplate, day: u32) -> bool {
+        self.cooldowns
+            .get(&template)
+            .is_some_and(|available_from| day < *available_from)
+    }
+
+    /// Count a rest; returns true when the board is due a new posting
+    pub fn record_rest(&mut self) -> bool {
+        self.rests_since_posting += 1;
+        self.rests_since_posting >= QUEST_BOARD_REFRESH_RESTS
+    }
+
+    /// Take down the old offers and put up new ones around the base
+    ///
+    /// Templates cooling down on `day` are skipped, and so are templates
+    /// without a suitable target on the known map, so a posting may hold
+    /// fewer offers.
+    pub fn post(
+        &mut self,
+        map: &Map,
+        base: Position3D,
+        day: u32,
+        rng: &mut impl Rng,
+    ) -> BoardPosting {
+        let expired = self.offers.len();
+        self.offers.clear();
+        self.rests_since_posting = 0;
+        self.has_posted = true;
+
+        let templates: Vec<QuestTemplate> = QuestTemplate::all()
+            .into_iter()
+            .filter(|template| !self.is_cooling_down(*template, day))
+            .collect();
+        // A few more draws than offers, as a draw may find no fresh target
+        for _ in 0..QUEST_BOARD_OFFERS * 4 {
+            if templates.is_empty() || self.offers.len() >= QUEST_BOARD_OFFERS {
+                break;
+            }
+            let template = templates[rng.gen_range(0..templates.len())];
+            let Some(target) = template.pick_target(map, base, rng.gen()) else {
+                continue;
+            };
+            // Never the same target twice on one board
+            if target.is_some() && self.offers.iter().any(|offer| offer.target == target) {
+                continue;
+            }
+            if let Ok(quest) = template.build(target) {
+                self.offers.push(PostedQuest {
+                    template,
+                    quest,
+                    target,
+                    counters_at_accept: QuestCounters::default(),
+                });
+            }
+        }
+
+        BoardPosting {
+            expired,
+            posted: self.offers.len(),
+        }
+    }
+
+    /// Move an offer onto the active list
+    pub fn accept(
+        &mut self,
+        offer: usize,
+        slots: usize,
+        counters: QuestCounters,
+    ) -> DomainResult<&PostedQuest> {
+        if offer >= self.offers.len() {
+            return Err(DomainError::QuestError("No such offer".to_string()));
+        }
+        if self.active.len() >= slots {
+            return Err(DomainError::QuestError(format!(
+                "All {} quest slots are taken; finish or abandon one first",
+                slots
+            )));
+        }
+        let mut posted = self.offers.remove(offer);
+        posted.quest.start()?;
+        posted.counters_at_accept = counters;
+        self.active.push(posted);
+        Ok(&self.active[self.active.len() - 1])
+    }
+
+    /// Give up an active quest; its template sits out the next day's postings
+    pub fn abandon(&mut self, index: usize, day: u32) -> DomainResult<PostedQuest> {
+        if index >= self.active.len() {
+            return Err(DomainError::QuestError("No such quest".to_string()));
+        }
+        let mut abandoned = self.active.remove(index);
+        abandoned.quest.abandon()?;
+        self.cooldowns
+            .insert(abandoned.template, day + QUEST_ABANDON_COOLDOWN_DAYS + 1);
+        Ok(abandoned)
+    }
+
+    /// Settle quests whose target is gone
+    ///
+    /// Offers with a lost target are simply taken down; active quests fail
+    /// and are paid off with a token compensation each.
+    pub fn settle_lost_targets(&mut self, map: &Map) -> Vec<QuestCompensation> {
+        let still_valid = |posted: &PostedQuest| {
+            posted
+                .target
+                .is_none_or(|target| posted.template.target_is_valid(map, target))
+        };
+        self.offers.retain(still_valid);
+
+        let (valid, lost): (Vec<PostedQuest>, Vec<PostedQuest>) =
+            self.active.drain(..).partition(still_valid);
+        self.active = valid;
+        lost.into_iter()
+            .map(|mut posted| {
+                let _ = posted.quest.fail();
+                QuestCompensation {
+                    title: posted.quest.title().to_string(),
+                    experience: QUEST_COMPENSATION_EXPERIENCE,
+                    resources: vec![(ResourceType::Metal, QUEST_COMPENSATION_METAL)],
+                }
+            })
+            .collect()
+    }
+
+    /// Progress the active quests; returns the ones just completed
+    ///
+    /// `position` is where the player stands after a move, and `None` on a
+    /// rest, when no target can be reached.
+    pub fn advance(
+        &mut self,
+        position: Option<Position3D>,
+        counters: QuestCounters,
+    ) -> Vec<PostedQuest> {
+        let mut completed = Vec::new();
+        let mut index = 0;
+        while index < self.active.len() {
+            let posted = &mut self.active[index];
+            let progress = if posted.template.needs_target() {
+                u32::from(position.is_some() && posted.target == position)
+            } else {
+                counters.progress_since(&posted.counters_at_accept, posted.template)
+            };
+            let objective = posted.quest.objectives()[0].id;
+            if posted
+                .quest
+                .update_objective_progress(&objective, progress)
+                .unwrap_or(false)
+            {
+                completed.push(self.active.remove(index));
+            } else {
+                index += 1;
+            }
+        }
+        completed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::map::{MapTile, ResourceNode};
+    use crate::domain::entities::quest::QuestStatus;
+    use crate::domain::value_objects::resources::{
+        RegenerationRate, ResourceAccessibility, ResourceNodeProperties, ResourceRichness,
+    };
+    use crate::domain::value_objects::terrain::{Elevation, TerrainType};
+    use crate::domain::value_objects::{EntityId, TileCoordinate};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const NODE: Position3D = Position3D { x: 6, y: 0, z: 0 };
+
+    /// Plains all around the base, one resource node east of it
+    fn known_map() -> Map {
+        let mut map = Map::new(EntityId::generate(), "Board".to_string(), 1954).unwrap();
+        for position in Position3D::origin().positions_within_distance(QUEST_TARGET_MAX_DISTANCE) {
+            map.set_tile(
+                TileCoordinate::from(position),
+                MapTile::new(TerrainType::Plains, Elevation::sea_level(), true),
+            );
+        }
+        let props = ResourceNodeProperties::new(
+            ResourceType::Food,
+            ResourceRichness::Average,
+            ResourceAccessibility::Easy,
+            RegenerationRate::Slow,
+        );
+        map.add_resource_node(NODE, ResourceNode::new(EntityId::generate(), props, 50, 50));
+        map
+    }
+
+    fn posted_board(map: &Map) -> QuestBoard {
+        let mut board = QuestBoard::new();
+        board.post(
+            map,
+            Position3D::origin(),
+            1,
+            &mut StdRng::seed_from_u64(1954),
+        );
+        board
+    }
+
+    #[test]
+    fn offers_are_replaced_every_two_rests() {
+        let map = known_map();
+        let mut board = posted_board(&map);
+        assert!(board.has_posted());
+        assert_eq!(board.offers().len(), QUEST_BOARD_OFFERS);
+        let first: Vec<String> = board
+            .offers()
+            .iter()
+            .map(|offer| offer.quest.title().to_string())
+            .collect();
+
+        assert!(!board.record_rest());
+        assert!(board.record_rest());
+        // One accepted offer stays with the player; the other two come down
+        board.accept(0, 3, QuestCounters::default()).unwrap();
+        let posting = board.post(&map, Position3D::origin(), 3, &mut StdRng::seed_from_u64(7));
+        assert_eq!(posting.expired, QUEST_BOARD_OFFERS - 1);
+        assert_eq!(posting.posted, board.offers().len());
+        assert_eq!(board.active()[0].quest.title(), first[0]);
+        assert!(!board.record_rest());
+    }
+
+    #[test]
+    fn accepting_respects_the_active_slots() {
+        assert_eq!(QuestBoard::active_slots(None), QUEST_ACTIVE_SLOTS);
+        assert_eq!(QuestBoard::active_slots(Some(1)), QUEST_ACTIVE_SLOTS + 1);
+        assert_eq!(
+            QuestBoard::active_slots(Some(9)),
+            QUEST_ACTIVE_SLOTS + QUEST_MAX_EXTRA_SLOTS
+        );
+
+        let map = known_map();
+        let mut board = posted_board(&map);
+        board.accept(0, 2, QuestCounters::default()).unwrap();
+        board.accept(0, 2, QuestCounters::default()).unwrap();
+        assert!(board.accept(0, 2, QuestCounters::default()).is_err());
+        assert_eq!(board.offers().len(), QUEST_BOARD_OFFERS - 2);
+        assert!(board.active().iter().all(|posted| posted.quest.is_active()));
+        assert!(board.accept(5, 9, QuestCounters::default()).is_err());
+    }
+
+    #[test]
+    fn abandoning_keeps_the_template_off_the_next_day() {
+        let map = known_map();
+        let mut board = posted_board(&map);
+        board.accept(0, 3, QuestCounters::default()).unwrap();
+        let template = board.active()[0].template;
+
+        let abandoned = board.abandon(0, 4).unwrap();
+        assert_eq!(abandoned.quest.status(), QuestStatus::Abandoned);
+        assert!(board.active().is_empty());
+        assert!(board.abandon(0, 4).is_err());
+
+        assert!(board.is_cooling_down(template, 5));
+        assert!(!board.is_cooling_down(template, 6));
+        for seed in 0..20 {
+            board.post(
+                &map,
+                Position3D::origin(),
+                5,
+                &mut StdRng::seed_from_u64(seed),
+            );
+            assert!(board
+                .offers()
+                .iter()
+                .all(|offer| offer.template != template));
+        }
+    }
+
+    #[test]
+    fn quests_complete_on_arrival_and_after_charting() {
+        let mut board = QuestBoard::new();
+        for (template, target) in [
+            (QuestTemplate::Prospect, Some(NODE)),
+            (QuestTemplate::Charting, None),
+        ] {
+            board.offers.push(PostedQuest {
+                template,
+                quest: template.build(target).unwrap(),
+                target,
+                counters_at_accept: QuestCounters::default(),
+            });
+        }
+        let explored = |tiles_explored| QuestCounters {
+            tiles_explored,
+            ..QuestCounters::default()
+        };
+        board.accept(0, 3, explored(10)).unwrap();
+        board.accept(0, 3, explored(10)).unwrap();
+
+        assert!(board
+            .advance(Some(Position3D::new(5, 0, 0)), explored(12))
+            .is_empty());
+        let arrived = board.advance(Some(NODE), explored(12));
+        assert_eq!(arrived.len(), 1);
+        assert!(arrived[0].quest.is_completed());
+        let charted = board.advance(Some(NODE), explored(10 + QUEST_CHARTING_TILES));
+        assert_eq!(charted[0].template, QuestTemplate::Charting);
+        assert!(board.active().is_empty());
+    }
+
+    #[test]
+    fn hauls_and_rests_count_only_from_acceptance() {
+        let mut board = QuestBoard::new();
+        for template in [QuestTemplate::Salvage, QuestTemplate::Endurance] {
+            board.offers.push(PostedQuest {
+                template,
+                quest: template.build(None).unwrap(),
+                target: None,
+                counters_at_accept: QuestCounters::default(),
+            });
+        }
+        let before = QuestCounters {
+            tiles_explored: 4,
+            metal_gathered: 100,
+            nights_rested: 6,
+        };
+        board.accept(0, 3, before).unwrap();
+        board.accept(0, 3, before).unwrap();
+
+        let hauled = QuestCounters {
+            metal_gathered: 100 + QUEST_SALVAGE_METAL - 1,
+            nights_rested: 6 + QUEST_ENDURANCE_RESTS - 1,
+            ..before
+        };
+        assert!(board.advance(None, hauled).is_empty());
+        let progress: Vec<u32> = board
+            .active()
+            .iter()
+            .map(|posted| posted.quest.objectives()[0].current_progress)
+            .collect();
+        assert_eq!(
+            progress,
+            vec![QUEST_SALVAGE_METAL - 1, QUEST_ENDURANCE_RESTS - 1]
+        );
+
+        let rested = QuestCounters {
+            nights_rested: 6 + QUEST_ENDURANCE_RESTS,
+            ..hauled
+        };
+        let completed = board.advance(None, rested);
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].template, QuestTemplate::Endurance);
+        assert_eq!(board.active()[0].template, QuestTemplate::Salvage);
+    }
+
+    #[test]
+    fn a_lost_target_is_paid_off_instead_of_left_impossible() {
+        let mut map = known_map();
+        let mut board = QuestBoard::new();
+        board.offers.push(PostedQuest {
+            template: QuestTemplate::Prospect,
+            quest: QuestTemplate::Prospect.build(Some(NODE)).unwrap(),
+            target: Some(NODE),
+            counters_at_accept: QuestCounters::default(),
+        });
+        board.accept(0, 3, QuestCounters::default()).unwrap();
+        assert!(board.settle_lost_targets(&map).is_empty());
+        assert!(QuestTemplate::Survey.target_is_valid(&map, Position3D::new(5, 0, 0)));
+        assert!(!QuestTemplate::Survey.target_is_valid(&map, Position3D::new(40, 0, 0)));
+
+        // Worked out by someone else before the player got there
+        map.get_resource_node_mut(&NODE).unwrap().deplete();
+        let compensations = board.settle_lost_targets(&map);
+        assert_eq!(
+            compensations,
+            vec![QuestCompensation {
+                title: "Prospect [6, 0]".to_string(),
+                experience: QUEST_COMPENSATION_EXPERIENCE,
+                resources: vec![(ResourceType::Metal, QUEST_COMPENSATION_METAL)],
+            }]
+        );
+        assert!(board.active().is_empty());
+        assert!(board.settle_lost_targets(&map).is_empty());
+    }
+}
@@ -0,0 +1,347 @@
+//! Faction Reputation - Standing with the spaceport factions
+//!
+//! The player keeps a standing with each of the three factions running the
+//! spaceports. Standing moves with the player's choices: bribes buy the
+//! Scavenger Guild's favour, trades please whoever was traded with and
+//! completed quests earn the Colonial Authority's respect, abandoned ones
+//! cost a little of it. The Guild and the
+//! Authority are rivals, so a change with one moves the other the opposite
+//! way by `REPUTATION_RIVAL_COUPLING`. The tier of a standing decides the
+//! exchange rates and offers at the base and which flavour of trade and
+//! narrative events the player runs into.
+
+use crate::domain::constants::{
+    REPUTATION_ALLIED_FROM, REPUTATION_BRIBE_GAIN, REPUTATION_FRIENDLY_FROM,
+    REPUTATION_HOSTILE_BELOW, REPUTATION_MAX, REPUTATION_MIN, REPUTATION_QUEST_ABANDON_LOSS,
+    REPUTATION_QUEST_GAIN, REPUTATION_TRADE_RATE_PERCENT, REPUTATION_TRADE_VOLUME_PER_POINT,
+};
+use serde::{Deserialize, Serialize};
+
+/// A spaceport faction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Faction {
+    ScavengerGuild,
+    ColonialAuthority,
+    FreeTraders,
+}
+
+impl Faction {
+    /// Every faction, in display order
+    pub fn all() -> [Faction; 3] {
+        [
+            Faction::ScavengerGuild,
+            Faction::ColonialAuthority,
+            Faction::FreeTraders,
+        ]
+    }
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Faction::ScavengerGuild => "Scavenger Guild",
+            Faction::ColonialAuthority => "Colonial Authority",
+            Faction::FreeTraders => "Free Traders",
+        }
+    }
+
+    /// Faction whose standing moves the opposite way, if any
+    pub fn rival(&self) -> Option<Faction> {
+        match self {
+            Faction::ScavengerGuild => Some(Faction::ColonialAuthority),
+            Faction::ColonialAuthority => Some(Faction::ScavengerGuild),
+            Faction::FreeTraders => None,
+        }
+    }
+}
+
+/// How a faction treats the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReputationTier {
+    Hostile,
+    Neutral,
+    Friendly,
+    Allied,
+}
+
+impl ReputationTier {
+    /// Tier of a standing
+    pub fn from_score(score: i32) -> Self {
+        if score < REPUTATION_HOSTILE_BELOW {
+            ReputationTier::Hostile
+        } else if score < REPUTATION_FRIENDLY_FROM {
+            ReputationTier::Neutral
+        } else if score < REPUTATION_ALLIED_FROM {
+            ReputationTier::Friendly
+        } else {
+            ReputationTier::Allied
+        }
+    }
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReputationTier::Hostile => "Hostile",
+            ReputationTier::Neutral => "Neutral",
+            ReputationTier::Friendly => "Friendly",
+            ReputationTier::Allied => "Allied",
+        }
+    }
+
+    /// Change to exchange rates in percent
+    pub fn trade_rate_percent(&self) -> i32 {
+        let step = REPUTATION_TRADE_RATE_PERCENT as i32;
+        match self {
+            ReputationTier::Hostile => -step,
+            ReputationTier::Neutral => 0,
+            ReputationTier::Friendly | ReputationTier::Allied => step,
+        }
+    }
+
+    /// Relative chance of the faction's flavour of an event
+    pub fn event_weight(&self) -> u32 {
+        match self {
+            ReputationTier::Hostile => 1,
+            ReputationTier::Neutral => 2,
+            ReputationTier::Friendly => 3,
+            ReputationTier::Allied => 4,
+        }
+    }
+}
+
+/// Why a standing changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReputationCause {
+    /// Paid hostiles to look the other way
+    Bribe,
+    /// Accepted an offer; `volume` is the units received
+    Trade { faction: Faction, volume: u32 },
+    /// Finished a quest
+    QuestCompleted,
+    /// Gave up a quest taken from the bulletin board
+    QuestAbandoned,
+}
+
+impl ReputationCause {
+    /// Faction the cause answers to and the change before coupling
+    pub fn shift(&self) -> (Faction, i32) {
+        match *self {
+            ReputationCause::Bribe => (Faction::ScavengerGuild, REPUTATION_BRIBE_GAIN),
+            ReputationCause::Trade { faction, volume } => (
+                faction,
+                (volume / REPUTATION_TRADE_VOLUME_PER_POINT).max(1) as i32,
+            ),
+            ReputationCause::QuestCompleted => (Faction::ColonialAuthority, REPUTATION_QUEST_GAIN),
+            ReputationCause::QuestAbandoned => {
+                (Faction::ColonialAuthority, -REPUTATION_QUEST_ABANDON_LOSS)
+            }
+        }
+    }
+}
+
+/// A standing that moved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReputationChange {
+    pub faction: Faction,
+    pub previous: i32,
+    pub current: i32,
+}
+
+impl ReputationChange {
+    /// Check if the change crossed into another tier
+    pub fn tier_changed(&self) -> bool {
+        ReputationTier::from_score(self.previous) != ReputationTier::from_score(self.current)
+    }
+}
+
+/// Standing with every faction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reputation {
+    scavenger_guild: i32,
+    colonial_authority: i32,
+    free_traders: i32,
+}
+
+impl Reputation {
+    /// Neutral standing with everyone
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Standing with a faction
+    pub fn score(&self, faction: Faction) -> i32 {
+        match faction {
+            Faction::ScavengerGuild => self.scavenger_guild,
+            Faction::ColonialAuthority => self.colonial_authority,
+            Faction::FreeTraders => self.free_traders,
+        }
+    }
+
+    /// Tier of the standing with a faction
+    pub fn tier(&self, faction: Faction) -> ReputationTier {
+        ReputationTier::from_score(self.score(faction))
+    }
+
+    fn score_mut(&mut self, faction: Faction) -> &mut i32 {
+        match faction {
+            Faction::ScavengerGuild => &mut self.scavenger_guild,
+            Faction::ColonialAuthority => &mut self.colonial_authority,
+            Faction::FreeTraders => &mut self.free_traders,
+        }
+    }
+
+    /// Move a standing within its range, and the rival's by `coupling` of
+    /// what was actually applied
+    ///
+    /// Returns the standings that moved.
+    pub fn adjust(&mut self, faction: Faction, delta: i32, coupling: f32) -> Vec<ReputationChange> {
+        let mut changes = Vec::new();
+        let applied = self.shift_clamped(faction, delta, &mut changes);
+        if let Some(rival) = faction.rival() {
+            let rival_delta = -(applied as f32 * coupling).round() as i32;
+            self.shift_clamped(rival, rival_delta, &mut changes);
+        }
+        changes
+    }
+
+    /// Apply the change for a cause
+    pub fn apply(&mut self, cause: ReputationCause, coupling: f32) -> Vec<ReputationChange> {
+        let (faction, delta) = cause.shift();
+        self.adjust(faction, delta, coupling)
+    }
+
+    fn shift_clamped(
+        &mut self,
+        faction: Faction,
+        delta: i32,
+        changes: &mut Vec<ReputationChange>,
+    ) -> i32 {
+        let score = self.score_mut(faction);
+        let previous = *score;
+        *score = previous
+            .saturating_add(delta)
+            .clamp(REPUTATION_MIN, REPUTATION_MAX);
+        if *score != previous {
+            changes.push(ReputationChange {
+                faction,
+                previous,
+                current: *score,
+            });
+        }
+        *score - previous
+    }
+
+    /// Weight of an event template tagged with `faction`; untagged
+    /// templates weigh the same as a neutral faction's
+    pub fn event_weight(&self, faction: Option<Faction>) -> u32 {
+        faction
+            .map(|faction| self.tier(faction))
+            .unwrap_or(ReputationTier::Neutral)
+            .event_weight()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::constants::REPUTATION_RIVAL_COUPLING;
+
+    #[test]
+    fn tiers_start_at_their_thresholds() {
+        assert_eq!(
+            ReputationTier::from_score(REPUTATION_MIN),
+            ReputationTier::Hostile
+        );
+        assert_eq!(
+            ReputationTier::from_score(REPUTATION_HOSTILE_BELOW - 1),
+            ReputationTier::Hostile
+        );
+        assert_eq!(
+            ReputationTier::from_score(REPUTATION_HOSTILE_BELOW),
+            ReputationTier::Neutral
+        );
+        assert_eq!(
+            ReputationTier::from_score(REPUTATION_FRIENDLY_FROM - 1),
+            ReputationTier::Neutral
+        );
+        assert_eq!(
+            ReputationTier::from_score(REPUTATION_FRIENDLY_FROM),
+            ReputationTier::Friendly
+        );
+        assert_eq!(
+            ReputationTier::from_score(REPUTATION_ALLIED_FROM - 1),
+            ReputationTier::Friendly
+        );
+        assert_eq!(
+            ReputationTier::from_score(REPUTATION_ALLIED_FROM),
+            ReputationTier::Allied
+        );
+        assert_eq!(
+            ReputationTier::from_score(REPUTATION_MAX),
+            ReputationTier::Allied
+        );
+        assert_eq!(
+            Reputation::new().tier(Faction::FreeTraders),
+            ReputationTier::Neutral
+        );
+    }
+
+    #[test]
+    fn rivals_move_the_opposite_way_and_standings_stay_in_range() {
+        let mut reputation = Reputation::new();
+
+        let changes = reputation.apply(ReputationCause::Bribe, REPUTATION_RIVAL_COUPLING);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(
+            reputation.score(Faction::ScavengerGuild),
+            REPUTATION_BRIBE_GAIN
+        );
+        assert_eq!(
+            reputation.score(Faction::ColonialAuthority),
+            -REPUTATION_BRIBE_GAIN / 2
+        );
+
+        // The Free Traders have no rival
+        let changes = reputation.adjust(Faction::FreeTraders, 10, 1.0);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(
+            reputation.score(Faction::ScavengerGuild),
+            REPUTATION_BRIBE_GAIN
+        );
+
+        // Only the part that fit in the range is passed on to the rival
+        let mut reputation = Reputation::new();
+        reputation.adjust(Faction::ColonialAuthority, REPUTATION_MAX - 4, 0.0);
+        let changes = reputation.adjust(Faction::ColonialAuthority, 50, 0.5);
+        assert_eq!(reputation.score(Faction::ColonialAuthority), REPUTATION_MAX);
+        assert_eq!(reputation.score(Faction::ScavengerGuild), -2);
+        assert_eq!(changes[0].previous, REPUTATION_MAX - 4);
+
+        // Nothing moves at the edge of the range
+        assert!(reputation
+            .adjust(Faction::ColonialAuthority, 5, 0.5)
+            .is_empty());
+        reputation.adjust(Faction::ScavengerGuild, -500, 0.0);
+        assert_eq!(reputation.score(Faction::ScavengerGuild), REPUTATION_MIN);
+    }
+
+    #[test]
+    fn trade_volume_earns_at_least_one_point() {
+        let small = ReputationCause::Trade {
+            faction: Faction::FreeTraders,
+            volume: 3,
+        };
+        let large = ReputationCause::Trade {
+            faction: Faction::FreeTraders,
+            volume: 45,
+        };
+        assert_eq!(small.shift(), (Faction::FreeTraders, 1));
+        assert_eq!(large.shift(), (Faction::FreeTraders, 4));
+
+        let change = ReputationChange {
+            faction: Faction::FreeTraders,
+            previous: REPUTATION_FRIENDLY_FROM - 1,
+            current: REPUTATION_FRIENDLY_FROM,
+        };
+        assert!(change.tier_changed());
+    }
+}
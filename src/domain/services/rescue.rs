@@ -0,0 +1,362 @@
+//! Rescue Missions - Distress signals with a timed objective on the map
+//!
+//! While exploring, the player may pick up a distress signal, more often
+//! near ruins and anomalies. The signal marks a tile a few days' walk away
+//! and stays open for a fixed number of rests. Reaching the tile in time
+//! leads to a Charisma or Strength check deciding how the rescue goes;
+//! letting the countdown run out leaves the region a little more dangerous.
+//! Only one signal is open at a time.
+
+use crate::domain::constants::{
+    RESCUE_CHECK_DIFFICULTY, RESCUE_COUNTDOWN_RESTS, RESCUE_FAILURE_EXPERIENCE,
+    RESCUE_MAX_DISTANCE, RESCUE_MAX_REGION_THREAT, RESCUE_MIN_DISTANCE, RESCUE_SIGNAL_CHANCE,
+    RESCUE_SIGNAL_CHANCE_NEAR_SITE, RESCUE_SITE_SCAN_RADIUS, RESCUE_SUCCESS_EXPERIENCE,
+    RESCUE_THREAT_REGION_SIZE,
+};
+use crate::domain::entities::Map;
+use crate::domain::value_objects::resources::ResourceType;
+use crate::domain::value_objects::terrain::TerrainType;
+use crate::domain::value_objects::{Position3D, StatType};
+use std::collections::HashMap;
+
+/// An open distress signal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistressSignal {
+    /// Tile the player has to reach
+    pub target: Position3D,
+    /// Rests left before the signal goes silent
+    pub rests_remaining: u32,
+}
+
+impl DistressSignal {
+    /// Tiles between `position` and the objective
+    pub fn distance_from(&self, position: Position3D) -> u32 {
+        self.target.manhattan_distance_2d(&position)
+    }
+}
+
+/// Result of the check made on reaching the objective
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RescueOutcome {
+    /// Stat the check was made with
+    pub stat: StatType,
+    /// Natural d20 roll
+    pub roll: u8,
+    /// Roll plus stat modifier
+    pub total: i32,
+    pub success: bool,
+    pub experience: u32,
+    pub resources: Vec<(ResourceType, u32)>,
+}
+
+impl RescueOutcome {
+    /// One-line summary for the game log
+    pub fn summary(&self) -> String {
+        let stat = match self.stat {
+            StatType::Charisma => "Charisma",
+            _ => "Strength",
+        };
+        if self.success {
+            format!(
+                "Rescue succeeded ({} check {} vs {}): the survivors share their salvage",
+                stat, self.total, RESCUE_CHECK_DIFFICULTY
+            )
+        } else {
+            format!(
+                "Rescue faltered ({} check {} vs {}): you get them out, but the cargo is lost",
+                stat, self.total, RESCUE_CHECK_DIFFICULTY
+            )
+        }
+    }
+}
+
+/// The open signal plus the threat left behind by ignored ones
+#[derive(Debug, Clone, Default, bevy::prelude::Resource)]
+pub struct TimedObjective {
+    active: Option<DistressSignal>,
+    region_threat: HashMap<(i32, i32), u8>,
+}
+
+impl TimedObjective {
+    /// Create an objective tracker with no open signal
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The open signal, if any
+    pub fn active(&self) -> Option<&DistressSignal> {
+        self.active.as_ref()
+    }
+
+    /// Slot of the open signal, for handing it between party characters
+    pub fn signal_mut(&mut self) -> &mut Option<DistressSignal> {
+        &mut self.active
+    }
+
+    /// Chance in percent that a step at `position` picks up a signal
+    pub fn signal_chance(map: &Map, position: Position3D) -> u8 {
+        let near_site = map
+            .get_tiles_in_radius(&position, RESCUE_SITE_SCAN_RADIUS)
+            .iter()
+            .any(|(_, tile)| {
+                matches!(
+                    tile.terrain_type,
+                    TerrainType::Constructed | TerrainType::Anomaly
+                )
+            });
+        if near_site {
+            RESCUE_SIGNAL_CHANCE_NEAR_SITE
+        } else {
+            RESCUE_SIGNAL_CHANCE
+        }
+    }
+
+    /// Roll for a new signal after a step; `roll` is a d100 result
+    ///
+    /// Returns the new signal when one was opened. Nothing happens while a
+    /// signal is already open or when no passable tile lies within range;
+    /// `placement` picks among the candidate tiles.
+    pub fn try_raise_signal(
+        &mut self,
+        map: &Map,
+        position: Position3D,
+        roll: u8,
+        placement: u64,
+    ) -> Option<&DistressSignal> {
+        if self.active.is_some() || roll > Self::signal_chance(map, position) {
+            return None;
+        }
+        let target = Self::place_objective(map, position, placement)?;
+        self.active = Some(DistressSignal {
+            target,
+            rests_remaining: RESCUE_COUNTDOWN_RESTS,
+        });
+        self.active.as_ref()
+    }
+
+    /// Pick a known, passable tile within the placement band around `origin`
+    pub fn place_objective(map: &Map, origin: Position3D, placement: u64) -> Option<Position3D> {
+        let candidates: Vec<Position3D> = map
+            .get_tiles_in_radius(&origin, RESCUE_MAX_DISTANCE)
+            .into_iter()
+            .filter(|(_, tile)| tile.terrain_type.is_passable())
+            .map(|(coordinate, _)| Position3D::new(coordinate.x, coordinate.y, coordinate.z))
+            .filter(|position| {
+                let distance = position.manhattan_distance_2d(&origin);
+                (RESCUE_MIN_DISTANCE..=RESCUE_MAX_DISTANCE).contains(&distance)
+            })
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        Some(candidates[(placement % candidates.len() as u64) as usize])
+    }
+
+    /// Count down one rest; returns the signal if it just expired
+    ///
+    /// An expired signal raises the threat of the region it was placed in.
+    pub fn tick_rest(&mut self) -> Option<DistressSignal> {
+        let signal = self.active.as_mut()?;
+        signal.rests_remaining = signal.rests_remaining.saturating_sub(1);
+        if signal.rests_remaining > 0 {
+            return None;
+        }
+        let expired = self.active.take()?;
+        let threat = self
+            .region_threat
+            .entry(Self::region_of(expired.target))
+            .or_insert(0);
+        *threat = (*threat + 1).min(RESCUE_MAX_REGION_THREAT);
+        Some(expired)
+    }
+
+    /// Resolve the signal if `position` is its objective
+    ///
+    /// `roll` is the natural d20; the better of the Charisma and Strength
+    /// modifiers is added to it.
+    pub fn try_resolve(
+        &mut self,
+        position: Position3D,
+        roll: u8,
+        charisma_modifier: i8,
+        strength_modifier: i8,
+    ) -> Option<RescueOutcome> {
+        if self.active.as_ref()?.target != position {
+            return None;
+        }
+        self.active = None;
+
+        let (stat, modifier) = if charisma_modifier >= strength_modifier {
+            (StatType::Charisma, charisma_modifier)
+        } else {
+            (StatType::Strength, strength_modifier)
+        };
+        let total = roll as i32 + modifier as i32;
+        let success = total >= RESCUE_CHECK_DIFFICULTY;
+        let resources = if success {
+            vec![
+                (ResourceType::Technology, 6),
+                (ResourceType::Alloys, 4),
+                (ResourceType::Food, 10),
+            ]
+        } else {
+            Vec::new()
+        };
+
+        Some(RescueOutcome {
+            stat,
+            roll,
+            total,
+            success,
+            experience: if success {
+                RESCUE_SUCCESS_EXPERIENCE
+            } else {
+                RESCUE_FAILURE_EXPERIENCE
+            },
+            resources,
+        })
+    }
+
+    /// Movement roll penalty in the region of `position`
+    pub fn threat_at(&self, position: Position3D) -> u8 {
+        self.region_threat
+            .get(&Self::region_of(position))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn region_of(position: Position3D) -> (i32, i32) {
+        (
+            position.x.div_euclid(RESCUE_THREAT_REGION_SIZE),
+            position.y.div_euclid(RESCUE_THREAT_REGION_SIZE),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::MapTile;
+    use crate::domain::value_objects::terrain::Elevation;
+    use crate::domain::value_objects::{EntityId, TileCoordinate};
+
+    fn plains_map(radius: u32) -> Map {
+        let mut map = Map::new(EntityId::generate(), "Rescue".to_string(), 5).unwrap();
+        for position in Position3D::origin().positions_within_distance(radius) {
+            map.set_tile(
+                TileCoordinate::from(position),
+                MapTile::new(TerrainType::Plains, Elevation::sea_level(), true),
+            );
+        }
+        map
+    }
+
+    fn open_signal(objective: &mut TimedObjective, map: &Map) -> DistressSignal {
+        objective
+            .try_raise_signal(map, Position3D::origin(), 1, 0)
+            .cloned()
+            .unwrap()
+    }
+
+    #[test]
+    fn objective_is_placed_within_distance_bounds() {
+        let map = plains_map(14);
+        for placement in 0..200 {
+            let target =
+                TimedObjective::place_objective(&map, Position3D::origin(), placement).unwrap();
+            let distance = target.manhattan_distance_2d(&Position3D::origin());
+            assert!((RESCUE_MIN_DISTANCE..=RESCUE_MAX_DISTANCE).contains(&distance));
+        }
+
+        // Nothing known far enough away means no signal
+        assert!(TimedObjective::place_objective(&plains_map(4), Position3D::origin(), 0).is_none());
+    }
+
+    #[test]
+    fn signal_expires_after_countdown_and_raises_threat() {
+        let map = plains_map(14);
+        let mut objective = TimedObjective::new();
+        let signal = open_signal(&mut objective, &map);
+        assert_eq!(signal.rests_remaining, RESCUE_COUNTDOWN_RESTS);
+
+        for _ in 1..RESCUE_COUNTDOWN_RESTS {
+            assert!(objective.tick_rest().is_none());
+        }
+        assert_eq!(objective.threat_at(signal.target), 0);
+
+        assert_eq!(objective.tick_rest().unwrap().target, signal.target);
+        assert!(objective.active().is_none());
+        assert_eq!(objective.threat_at(signal.target), 1);
+        assert!(objective.tick_rest().is_none());
+    }
+
+    #[test]
+    fn only_one_signal_is_open_at_a_time() {
+        let map = plains_map(14);
+        let mut objective = TimedObjective::new();
+        let first = open_signal(&mut objective, &map);
+
+        assert!(objective
+            .try_raise_signal(&map, Position3D::origin(), 1, 99)
+            .is_none());
+        assert_eq!(objective.active(), Some(&first));
+
+        // A failed gate roll never opens one either
+        let mut quiet = TimedObjective::new();
+        assert!(quiet
+            .try_raise_signal(&map, Position3D::origin(), 100, 0)
+            .is_none());
+    }
+
+    #[test]
+    fn signals_are_likelier_near_ruins_and_anomalies() {
+        let mut map = plains_map(14);
+        assert_eq!(
+            TimedObjective::signal_chance(&map, Position3D::origin()),
+            RESCUE_SIGNAL_CHANCE
+        );
+        map.set_tile(
+            TileCoordinate::new(1, 1, 0),
+            MapTile::new(TerrainType::Anomaly, Elevation::sea_level(), true),
+        );
+        assert_eq!(
+            TimedObjective::signal_chance(&map, Position3D::origin()),
+            RESCUE_SIGNAL_CHANCE_NEAR_SITE
+        );
+    }
+
+    #[test]
+    fn reaching_the_objective_resolves_with_the_better_stat() {
+        let map = plains_map(14);
+        let mut objective = TimedObjective::new();
+        let signal = open_signal(&mut objective, &map);
+
+        // Wrong tile leaves the signal open
+        assert!(objective
+            .try_resolve(Position3D::origin(), 20, 0, 0)
+            .is_none());
+
+        let outcome = objective.try_resolve(signal.target, 10, 0, 3).unwrap();
+        assert!(outcome.success);
+        assert_eq!(outcome.stat, StatType::Strength);
+        assert_eq!(outcome.total, 13);
+        assert_eq!(outcome.experience, RESCUE_SUCCESS_EXPERIENCE);
+        assert!(!outcome.resources.is_empty());
+        assert!(objective.active().is_none());
+    }
+
+    #[test]
+    fn failed_check_still_closes_the_signal() {
+        let map = plains_map(14);
+        let mut objective = TimedObjective::new();
+        let signal = open_signal(&mut objective, &map);
+
+        let outcome = objective.try_resolve(signal.target, 4, 2, -1).unwrap();
+        assert!(!outcome.success);
+        assert_eq!(outcome.stat, StatType::Charisma);
+        assert_eq!(outcome.experience, RESCUE_FAILURE_EXPERIENCE);
+        assert!(outcome.resources.is_empty());
+        assert!(objective.active().is_none());
+        assert_eq!(objective.threat_at(signal.target), 0);
+    }
+}
@@ -6,6 +6,7 @@
 
 use crate::domain::{
     entities::Player,
+    services::ModifierStack,
     value_objects::{
         dice::{DiceModifier, DiceRoll, DiceType},
         resources::ResourceCollection,
@@ -35,35 +36,49 @@ impl RestingService {
     }
 
     /// Process a full rest cycle when player has 0 movement points
+    ///
+    /// `roll_bonus` raises the night roll, up to a natural 20.
     pub fn process_rest_cycle(
         &self,
         player: &mut Player,
         current_position: Position3D,
+        modifiers: &ModifierStack,
+        roll_bonus: u8,
     ) -> DomainResult<RestCycleResult> {
         // Roll for night events
         let night_dice = DiceRoll::new(1, DiceType::D20, DiceModifier::none())?;
-        let night_roll = night_dice.total() as u8;
+        let night_roll = (night_dice.total() as u8)
+            .saturating_add(roll_bonus)
+            .min(20);
 
         // Determine what happens during the night
         let night_event = self.determine_night_event(night_roll, &current_position)?;
-        let rest_outcome = self.determine_rest_outcome(night_roll, &night_event)?;
+        let mut rest_outcome = self.determine_rest_outcome(night_roll, &night_event)?;
+        // Without night penalties a bad night still makes for a normal rest
+        if !modifiers.night_penalties() && rest_outcome == RestOutcome::PoorRest {
+            rest_outcome = RestOutcome::NormalRest;
+        }
 
         // Apply rest effects
-        let resources_gained = self.apply_rest_effects(player, &rest_outcome)?;
+        let resources_gained = self.apply_rest_effects(player, &rest_outcome, modifiers)?;
+
+        // Mend the hull by a share of its maximum, set by the rest quality
+        let health_restored =
+            player.heal(player.health().max() * Self::healing_percent(&rest_outcome) / 100);
 
         // Restore movement points (always happens after rest)
         player.restore_points();
 
         // Add extra movement points based on rest quality to ensure playability
-        let extra_movement = match rest_outcome {
-            RestOutcome::PoorRest => 2,         // Total: max + 2 extra
-            RestOutcome::NormalRest => 4,       // Total: max + 4 extra
-            RestOutcome::GoodRest => 6,         // Total: max + 6 extra
-            RestOutcome::GreatRest => 8,        // Total: max + 8 extra
-            RestOutcome::ExceptionalRest => 12, // Total: max + 12 extra
-        };
-
-        player.add_movement_points(extra_movement);
+        player.add_movement_points(Self::extra_movement(&rest_outcome));
+
+        let restored = player.movement_points();
+        let rested = modifiers.rest_movement(restored);
+        if rested < restored {
+            player.subtract_movement_points(restored - rested);
+        } else {
+            player.add_movement_points(rested - restored);
+        }
 
         // Generate rest description
         let description = self.generate_rest_description(&night_event, &rest_outcome, night_roll);
@@ -73,12 +88,49 @@ impl RestingService {
             rest_outcome,
             resources_gained,
             movement_points_restored: player.movement_points(),
+            health_restored,
             description,
             dice_roll: night_roll,
             rest_completed_at: Utc::now(),
         })
     }
 
+    /// Extra movement points granted on top of a full restore
+    fn extra_movement(outcome: &RestOutcome) -> u8 {
+        match outcome {
+            RestOutcome::PoorRest => 2,         // Total: max + 2 extra
+            RestOutcome::NormalRest => 4,       // Total: max + 4 extra
+            RestOutcome::GoodRest => 6,         // Total: max + 6 extra
+            RestOutcome::GreatRest => 8,        // Total: max + 8 extra
+            RestOutcome::ExceptionalRest => 12, // Total: max + 12 extra
+        }
+    }
+
+    /// Share of maximum health a rest heals, in percent
+    fn healing_percent(outcome: &RestOutcome) -> u32 {
+        match outcome {
+            RestOutcome::PoorRest => 10,
+            RestOutcome::NormalRest => 25,
+            RestOutcome::GoodRest => 40,
+            RestOutcome::GreatRest => 60,
+            RestOutcome::ExceptionalRest => 100,
+        }
+    }
+
+    /// Average movement points available after a rest, over every night roll
+    pub fn average_movement_after_rest(&self, max_movement_points: u8) -> f32 {
+        let position = Position3D::origin();
+        let total: u32 = (1..=20u8)
+            .map(|roll| {
+                self.determine_night_event(roll, &position)
+                    .and_then(|event| self.determine_rest_outcome(roll, &event))
+                    .map(|outcome| Self::extra_movement(&outcome) as u32)
+                    .unwrap_or(0)
+            })
+            .sum();
+        max_movement_points as f32 + total as f32 / 20.0
+    }
+
     /// Determine what type of night event occurs based on dice roll
     fn determine_night_event(
         &self,
@@ -129,6 +181,7 @@ impl RestingService {
         &self,
         player: &mut Player,
         outcome: &RestOutcome,
+        modifiers: &ModifierStack,
     ) -> DomainResult<ResourceCollection> {
         let mut resources = ResourceCollection::new();
 
@@ -145,14 +198,14 @@ impl RestingService {
                 // Good rest - moderate bonuses
                 resources.set_amount(ResourceType::Food, 10);
                 resources.set_amount(ResourceType::Energy, 5);
-                player.add_experience(10)?;
+                player.add_experience(modifiers.experience(10))?;
             }
             RestOutcome::GreatRest => {
                 // Great rest - good bonuses + extra movement
                 resources.set_amount(ResourceType::Food, 15);
                 resources.set_amount(ResourceType::Energy, 10);
                 resources.set_amount(ResourceType::Data, 5);
-                player.add_experience(20)?;
+                player.add_experience(modifiers.experience(20))?;
             }
             RestOutcome::ExceptionalRest => {
                 // Exceptional rest - major bonuses
@@ -160,7 +213,7 @@ impl RestingService {
                 resources.set_amount(ResourceType::Energy, 20);
                 resources.set_amount(ResourceType::Data, 15);
                 resources.set_amount(ResourceType::Technology, 5);
-                player.add_experience(50)?;
+                player.add_experience(modifiers.experience(50))?;
             }
         }
 
@@ -328,6 +381,8 @@ pub struct RestCycleResult {
     pub resources_gained: ResourceCollection,
     /// Movement points restored (should be max)
     pub movement_points_restored: u8,
+    /// Health mended during the night
+    pub health_restored: u32,
     /// Narrative description of the rest
     pub description: String,
     /// The dice roll that determined the events
@@ -356,13 +411,87 @@ mod tests {
         player.subtract_movement_points(player.movement_points());
         assert_eq!(player.movement_points(), 0);
 
-        let result = service.process_rest_cycle(&mut player, Position3D::new(0, 0, 0));
+        let result = service.process_rest_cycle(
+            &mut player,
+            Position3D::new(0, 0, 0),
+            &ModifierStack::default(),
+            0,
+        );
         assert!(result.is_ok());
 
         // Player should have movement points restored
         assert!(player.movement_points() > 0);
     }
 
+    #[test]
+    fn night_owl_sleeps_through_bad_nights_but_rests_shorter() {
+        use crate::domain::entities::game::DifficultyLevel;
+        use crate::domain::services::{Mutator, Mutators};
+
+        let service = RestingService::new();
+        let night_owl =
+            ModifierStack::new(DifficultyLevel::Normal, Mutators::new([Mutator::NightOwl]));
+        let mut player =
+            Player::create_new_character("Owl".to_string(), Position3D::new(0, 0, 0)).unwrap();
+
+        // Cover the spread of night rolls
+        for _ in 0..40 {
+            player.subtract_movement_points(player.movement_points());
+            let result = service
+                .process_rest_cycle(&mut player, Position3D::new(0, 0, 0), &night_owl, 0)
+                .unwrap();
+            assert_ne!(result.rest_outcome, RestOutcome::PoorRest);
+            assert_eq!(player.movement_points(), player.max_movement_points() - 1);
+        }
+    }
+
+    #[test]
+    fn better_rest_heals_more_but_never_past_full() {
+        let service = RestingService::new();
+        let mut player =
+            Player::create_new_character("Vex".to_string(), Position3D::new(0, 0, 0)).unwrap();
+        player.take_damage(90);
+
+        let healed: Vec<u32> = [
+            RestOutcome::PoorRest,
+            RestOutcome::NormalRest,
+            RestOutcome::GoodRest,
+            RestOutcome::GreatRest,
+            RestOutcome::ExceptionalRest,
+        ]
+        .iter()
+        .map(|outcome| player.health().max() * RestingService::healing_percent(outcome) / 100)
+        .collect();
+        assert!(healed.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // Whatever the night, the hull is mended and stays within its maximum
+        for _ in 0..40 {
+            let before = player.health().current();
+            let result = service
+                .process_rest_cycle(
+                    &mut player,
+                    Position3D::new(0, 0, 0),
+                    &ModifierStack::default(),
+                    0,
+                )
+                .unwrap();
+            assert_eq!(player.health().current(), before + result.health_restored);
+            assert!(player.health().current() <= player.health().max());
+            if before < player.health().max() {
+                assert!(result.health_restored > 0);
+            }
+        }
+        assert_eq!(player.health().current(), player.health().max());
+    }
+
+    #[test]
+    fn average_movement_after_rest_weights_every_night_roll() {
+        let service = RestingService::new();
+        // 12 poor (+2), 4 normal (+4), 2 good (+6), 1 great (+8), 1 exceptional (+12)
+        let average = service.average_movement_after_rest(10);
+        assert!((average - 13.6).abs() < 1e-4);
+    }
+
     #[test]
     fn test_night_event_determination() {
         let service = RestingService::new();
@@ -0,0 +1,179 @@
+//! Run Ledger - How runs are played, how they end, and what is remembered
+//!
+//! A run is started in normal or hardcore mode and the mode is fixed until
+//! it ends. Runs end finished, defeated or abandoned; the player profile
+//! counts them across every run. A hardcore defeat buries the run's save
+//! slot: its saves stay readable on disk for a moment but may never be
+//! loaded again, while a normal defeat offers the last autosave instead.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Rules a run is played under
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RunMode {
+    #[default]
+    Normal,
+    /// Defeat is final: the run's saves are deleted
+    Hardcore,
+    /// Casual: the last move may be taken back
+    Relaxed,
+}
+
+impl RunMode {
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            RunMode::Normal => "Normal",
+            RunMode::Hardcore => "Hardcore",
+            RunMode::Relaxed => "Relaxed",
+        }
+    }
+
+    /// Check if the last move may be taken back
+    pub fn allows_undo(&self) -> bool {
+        *self == RunMode::Relaxed
+    }
+}
+
+/// How a run came to an end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RunEnd {
+    Finished,
+    Defeated,
+    /// Given up from the pause menu
+    Abandoned,
+}
+
+/// What happens to the saves of a defeated run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefeatFollowUp {
+    /// Hardcore: the slot is buried and its save deleted
+    DeleteSave,
+    /// Normal or relaxed: the last autosave may be reloaded
+    OfferReload,
+    /// Normal or relaxed, but nothing was saved yet
+    SummaryOnly,
+}
+
+impl DefeatFollowUp {
+    /// Follow-up for a run in `mode` that has `has_autosave` or not
+    pub fn after_defeat(mode: RunMode, has_autosave: bool) -> Self {
+        match mode {
+            RunMode::Hardcore => DefeatFollowUp::DeleteSave,
+            RunMode::Normal | RunMode::Relaxed if has_autosave => DefeatFollowUp::OfferReload,
+            RunMode::Normal | RunMode::Relaxed => DefeatFollowUp::SummaryOnly,
+        }
+    }
+}
+
+/// Runs counted across the whole player profile
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileStats {
+    pub runs_started: u32,
+    /// Runs played to an end, won or lost
+    pub runs_finished: u32,
+    pub runs_abandoned: u32,
+    pub hardcore_runs_started: u32,
+}
+
+impl ProfileStats {
+    /// Count a new run
+    pub fn record_start(&mut self, mode: RunMode) {
+        self.runs_started += 1;
+        if mode == RunMode::Hardcore {
+            self.hardcore_runs_started += 1;
+        }
+    }
+
+    /// Count a run that ended
+    pub fn record_end(&mut self, end: RunEnd) {
+        match end {
+            RunEnd::Finished | RunEnd::Defeated => self.runs_finished += 1,
+            RunEnd::Abandoned => self.runs_abandoned += 1,
+        }
+    }
+
+    /// Profile line, e.g. `Runs: 4 started, 2 finished, 1 abandoned`
+    pub fn summary(&self) -> String {
+        format!(
+            "Runs: {} started, {} finished, {} abandoned",
+            self.runs_started, self.runs_finished, self.runs_abandoned
+        )
+    }
+}
+
+/// Save slots whose hardcore run was defeated
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Tombstones {
+    slots: BTreeSet<String>,
+}
+
+impl Tombstones {
+    /// Bury `slot`; false if it was buried already
+    pub fn bury(&mut self, slot: &str) -> bool {
+        self.slots.insert(slot.to_string())
+    }
+
+    /// Free `slot` for a new run
+    pub fn lift(&mut self, slot: &str) -> bool {
+        self.slots.remove(slot)
+    }
+
+    /// Check if `slot` is buried
+    pub fn is_buried(&self, slot: &str) -> bool {
+        self.slots.contains(slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_counts_every_way_a_run_ends() {
+        let mut stats = ProfileStats::default();
+        stats.record_start(RunMode::Normal);
+        stats.record_start(RunMode::Hardcore);
+        stats.record_start(RunMode::Normal);
+        stats.record_end(RunEnd::Defeated);
+        stats.record_end(RunEnd::Finished);
+        stats.record_end(RunEnd::Abandoned);
+
+        assert_eq!(stats.runs_started, 3);
+        assert_eq!(stats.hardcore_runs_started, 1);
+        assert_eq!(stats.runs_finished, 2);
+        assert_eq!(stats.runs_abandoned, 1);
+        assert_eq!(stats.summary(), "Runs: 3 started, 2 finished, 1 abandoned");
+    }
+
+    #[test]
+    fn only_hardcore_defeats_bury_the_slot() {
+        assert_eq!(
+            DefeatFollowUp::after_defeat(RunMode::Hardcore, true),
+            DefeatFollowUp::DeleteSave
+        );
+        assert_eq!(
+            DefeatFollowUp::after_defeat(RunMode::Normal, true),
+            DefeatFollowUp::OfferReload
+        );
+        assert_eq!(
+            DefeatFollowUp::after_defeat(RunMode::Normal, false),
+            DefeatFollowUp::SummaryOnly
+        );
+        assert_eq!(
+            DefeatFollowUp::after_defeat(RunMode::Relaxed, true),
+            DefeatFollowUp::OfferReload
+        );
+
+        let mut tombstones = Tombstones::default();
+        assert!(!tombstones.is_buried("savegame.json"));
+        assert!(tombstones.bury("savegame.json"));
+        assert!(!tombstones.bury("savegame.json"));
+        assert!(tombstones.is_buried("savegame.json"));
+        assert!(tombstones.lift("savegame.json"));
+        assert!(!tombstones.is_buried("savegame.json"));
+    }
+}
@@ -0,0 +1,308 @@
+//! Starting Scenarios - Curated ways to begin a run
+//!
+//! A scenario changes the character and the ground around the spawn before
+//! the first move: stats, resources, level, consumables, charted tiles, a
+//! wreck to salvage and where the base stands. Scenarios are plain data in a
+//! `ScenarioTable`, so more can be added next to the three built in. The
+//! random parts - which stats suffer, where the base and the wreck lie - are
+//! drawn from a generator the caller seeds from the world, so a shared run
+//! starts the same way for everyone.
+
+use crate::domain::constants::{
+    CRASH_SITE_LOOT, CRASH_SURVIVOR_BASE_DISTANCE, CRASH_SURVIVOR_LOWERED_STATS,
+    CRASH_SURVIVOR_SALVAGED_METAL, CRASH_SURVIVOR_STAT_PENALTY, VETERAN_SCOUT_LEVEL,
+    VETERAN_SCOUT_PROBES, VETERAN_SCOUT_RESOURCE_PERCENT, VETERAN_SCOUT_REVEAL_RADIUS,
+};
+use crate::domain::value_objects::resources::ResourceCollection;
+use crate::domain::value_objects::{Position3D, ResourceType, StatType};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Id of the scenario every run used before scenarios existed
+pub const STANDARD_DROP: &str = "standard_drop";
+
+/// Id of the crash landing scenario
+pub const CRASH_SURVIVOR: &str = "crash_survivor";
+
+/// Id of the experienced scout scenario
+pub const VETERAN_SCOUT: &str = "veteran_scout";
+
+/// One way to start a run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scenario {
+    /// Stable id saved with the run and carried by share codes
+    pub id: String,
+    pub name: String,
+    /// What the scenario changes, in a few words
+    pub description: String,
+    /// Stats lowered at random
+    #[serde(default)]
+    pub lowered_stats: u8,
+    /// Points taken off each lowered stat
+    #[serde(default)]
+    pub stat_penalty: u8,
+    /// Share of the usual starting resources kept, in percent
+    #[serde(default = "full_share")]
+    pub resource_percent: u32,
+    /// Resources added on top of the starting ones
+    #[serde(default)]
+    pub bonus_resources: Vec<(ResourceType, u32)>,
+    /// Level the character starts at
+    #[serde(default = "first_level")]
+    pub start_level: u32,
+    #[serde(default)]
+    pub scout_probes: u32,
+    /// Radius charted around the spawn
+    #[serde(default)]
+    pub reveal_radius: u32,
+    /// Loot of a wreck left next to the spawn; no wreck without loot
+    #[serde(default)]
+    pub wreck_loot: Vec<(ResourceType, u32)>,
+    /// Tiles between the spawn and the base; zero builds it on the spawn
+    #[serde(default)]
+    pub base_distance: u32,
+}
+
+fn full_share() -> u32 {
+    100
+}
+
+fn first_level() -> u32 {
+    1
+}
+
+impl Scenario {
+    /// Nothing changes: the run starts as it always did
+    pub fn standard_drop() -> Self {
+        Self {
+            id: STANDARD_DROP.to_string(),
+            name: "Standard Drop".to_string(),
+            description: "land at the base with the usual kit".to_string(),
+            lowered_stats: 0,
+            stat_penalty: 0,
+            resource_percent: full_share(),
+            bonus_resources: Vec::new(),
+            start_level: first_level(),
+            scout_probes: 0,
+            reveal_radius: 0,
+            wreck_loot: Vec::new(),
+            base_distance: 0,
+        }
+    }
+
+    /// Hurt in a crash, with salvage at hand and the base a walk away
+    pub fn crash_survivor() -> Self {
+        Self {
+            id: CRASH_SURVIVOR.to_string(),
+            name: "Crash Survivor".to_string(),
+            description: format!(
+                "-{} to {} random stats, +{} Metal, a wreck next door, the base {} tiles away",
+                CRASH_SURVIVOR_STAT_PENALTY,
+                CRASH_SURVIVOR_LOWERED_STATS,
+                CRASH_SURVIVOR_SALVAGED_METAL,
+                CRASH_SURVIVOR_BASE_DISTANCE
+            ),
+            lowered_stats: CRASH_SURVIVOR_LOWERED_STATS,
+            stat_penalty: CRASH_SURVIVOR_STAT_PENALTY,
+            bonus_resources: vec![(ResourceType::Metal, CRASH_SURVIVOR_SALVAGED_METAL)],
+            wreck_loot: CRASH_SITE_LOOT.to_vec(),
+            base_distance: CRASH_SURVIVOR_BASE_DISTANCE,
+            ..Self::standard_drop()
+        }
+    }
+
+    /// Experienced and well informed, but travelling light
+    pub fn veteran_scout() -> Self {
+        Self {
+            id: VETERAN_SCOUT.to_string(),
+            name: "Veteran Scout".to_string(),
+            description: format!(
+                "level {}, a Scout Probe and the nearby area charted, {}% of the usual resources",
+                VETERAN_SCOUT_LEVEL, VETERAN_SCOUT_RESOURCE_PERCENT
+            ),
+            resource_percent: VETERAN_SCOUT_RESOURCE_PERCENT,
+            start_level: VETERAN_SCOUT_LEVEL,
+            scout_probes: VETERAN_SCOUT_PROBES,
+            reveal_radius: VETERAN_SCOUT_REVEAL_RADIUS,
+            ..Self::standard_drop()
+        }
+    }
+
+    /// Draw the random parts of the scenario for a run spawning at `spawn`
+    pub fn roll(&self, spawn: Position3D, rng: &mut impl Rng) -> ScenarioStart {
+        let mut stats = StatType::all().to_vec();
+        stats.shuffle(rng);
+        stats.truncate(self.lowered_stats as usize);
+
+        let directions = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+        let base_position = if self.base_distance > 0 {
+            let (dx, dy) = directions[rng.gen_range(0..directions.len())];
+            let distance = self.base_distance as i32;
+            spawn.offset(dx * distance, dy * distance, 0)
+        } else {
+            spawn
+        };
+        let wreck = (!self.wreck_loot.is_empty()).then(|| {
+            let (dx, dy) = directions[rng.gen_range(0..directions.len())];
+            spawn.offset(dx, dy, 0)
+        });
+
+        ScenarioStart {
+            lowered_stats: stats,
+            base_position,
+            wreck,
+        }
+    }
+
+    /// Starting resources of the scenario, given the usual ones
+    ///
+    /// The share is taken first, rounding down, then the bonus is added.
+    pub fn starting_resources(&self, usual: &ResourceCollection) -> ResourceCollection {
+        let mut resources = ResourceCollection::new();
+        for resource in usual.resource_types() {
+            resources.set_amount(
+                resource,
+                usual.get_amount(resource) * self.resource_percent / 100,
+            );
+        }
+        for &(resource, amount) in &self.bonus_resources {
+            resources.set_amount(resource, resources.get_amount(resource) + amount);
+        }
+        resources
+    }
+}
+
+/// The random parts of a scenario, drawn for one run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScenarioStart {
+    /// Stats that start lowered
+    pub lowered_stats: Vec<StatType>,
+    /// Where the base is built
+    pub base_position: Position3D,
+    /// Where the scenario's wreck lies, if it has one
+    pub wreck: Option<Position3D>,
+}
+
+/// Every scenario a run can start with, in display order
+#[derive(Debug, Clone, PartialEq, Eq, bevy::prelude::Resource)]
+pub struct ScenarioTable {
+    scenarios: Vec<Scenario>,
+}
+
+impl Default for ScenarioTable {
+    fn default() -> Self {
+        Self {
+            scenarios: vec![
+                Scenario::standard_drop(),
+                Scenario::crash_survivor(),
+                Scenario::veteran_scout(),
+            ],
+        }
+    }
+}
+
+impl ScenarioTable {
+    /// Add a scenario, replacing the one with the same id
+    pub fn add(&mut self, scenario: Scenario) {
+        match self.scenarios.iter_mut().find(|s| s.id == scenario.id) {
+            Some(existing) => *existing = scenario,
+            None => self.scenarios.push(scenario),
+        }
+    }
+
+    /// Every scenario in display order
+    pub fn scenarios(&self) -> &[Scenario] {
+        &self.scenarios
+    }
+
+    /// The scenario with `id`, if the table has it
+    pub fn get(&self, id: &str) -> Option<&Scenario> {
+        self.scenarios.iter().find(|scenario| scenario.id == id)
+    }
+
+    /// The scenario with `id`, or a Standard Drop for unknown ids
+    pub fn resolve(&self, id: &str) -> Scenario {
+        self.get(id)
+            .or_else(|| self.get(STANDARD_DROP))
+            .cloned()
+            .unwrap_or_else(Scenario::standard_drop)
+    }
+
+    /// The scenario listed after `id`, wrapping around
+    pub fn next_after(&self, id: &str) -> Scenario {
+        let index = self
+            .scenarios
+            .iter()
+            .position(|scenario| scenario.id == id)
+            .map_or(0, |index| (index + 1) % self.scenarios.len());
+        self.scenarios
+            .get(index)
+            .cloned()
+            .unwrap_or_else(Scenario::standard_drop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::constants::STARTING_RESOURCES;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn rolls_follow_the_scenario_and_the_seed() {
+        let spawn = Position3D::origin();
+        let standard = Scenario::standard_drop().roll(spawn, &mut StdRng::seed_from_u64(1));
+        assert!(standard.lowered_stats.is_empty());
+        assert_eq!(standard.base_position, spawn);
+        assert_eq!(standard.wreck, None);
+
+        let crash = Scenario::crash_survivor();
+        let start = crash.roll(spawn, &mut StdRng::seed_from_u64(1948));
+        assert_eq!(start.lowered_stats.len(), 2);
+        assert_ne!(start.lowered_stats[0], start.lowered_stats[1]);
+        assert_eq!(
+            start.base_position.manhattan_distance_2d(&spawn),
+            CRASH_SURVIVOR_BASE_DISTANCE
+        );
+        assert_eq!(start.wreck.unwrap().manhattan_distance_2d(&spawn), 1);
+        // The same seed always gives the same start
+        assert_eq!(crash.roll(spawn, &mut StdRng::seed_from_u64(1948)), start);
+    }
+
+    #[test]
+    fn starting_resources_take_the_share_then_add_the_bonus() {
+        let usual = ResourceCollection::starting_resources();
+        let metal = STARTING_RESOURCES
+            .iter()
+            .find(|(resource, _)| *resource == ResourceType::Metal)
+            .map(|(_, amount)| *amount as u32)
+            .unwrap();
+
+        assert_eq!(Scenario::standard_drop().starting_resources(&usual), usual);
+        assert_eq!(
+            Scenario::crash_survivor()
+                .starting_resources(&usual)
+                .get_amount(ResourceType::Metal),
+            metal + CRASH_SURVIVOR_SALVAGED_METAL
+        );
+        let scout = Scenario::veteran_scout().starting_resources(&usual);
+        for resource in usual.resource_types() {
+            assert_eq!(
+                scout.get_amount(resource),
+                usual.get_amount(resource) * 3 / 4
+            );
+        }
+
+        let mut table = ScenarioTable::default();
+        assert_eq!(table.next_after(STANDARD_DROP).id, CRASH_SURVIVOR);
+        assert_eq!(table.next_after(VETERAN_SCOUT).id, STANDARD_DROP);
+        assert_eq!(table.resolve("unknown").id, STANDARD_DROP);
+        table.add(Scenario {
+            id: "pack_start".to_string(),
+            ..Scenario::veteran_scout()
+        });
+        assert_eq!(table.next_after(VETERAN_SCOUT).id, "pack_start");
+    }
+}
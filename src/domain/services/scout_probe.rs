@@ -0,0 +1,319 @@
+//! Scout Probes - Single-use drones that reveal a corridor of the map
+//!
+//! A probe is launched from the player's tile in a cardinal direction and
+//! flies up to `SCOUT_PROBE_RANGE` tiles, one tile every
+//! `SCOUT_PROBE_SECS_PER_TILE`. Every tile it reaches reveals a three-wide
+//! slice of the corridor at explored level. The route is fixed at launch:
+//! the probe stops early in front of impassable terrain or at the edge of
+//! the loaded world. The probe is used up when it is launched, so a flight
+//! that ends early never gives it back.
+
+use crate::domain::constants::{SCOUT_PROBE_RANGE, SCOUT_PROBE_SECS_PER_TILE};
+use crate::domain::entities::Map;
+use crate::domain::services::{InteriorGenerator, VisibilityService};
+use crate::domain::value_objects::position::Direction;
+use crate::domain::value_objects::terrain::TerrainType;
+use crate::domain::value_objects::{Position3D, ResourceType, TileCoordinate};
+
+/// Why a probe's route ends where it does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeStop {
+    /// The probe flew its full range
+    MaxRange,
+    /// The next tile could not be flown over
+    Impassable(TerrainType),
+    /// The next tile is past the edge of the loaded world
+    WorldEdge,
+}
+
+/// Tiles a probe flies over, planned at launch
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeRoute {
+    pub origin: Position3D,
+    pub heading: Direction,
+    /// Tiles in flight order, not including the launch tile
+    pub path: Vec<Position3D>,
+    pub stop: ProbeStop,
+}
+
+impl ProbeRoute {
+    /// Plan a flight from `origin` towards `heading`
+    pub fn plan(map: &Map, origin: Position3D, heading: Direction) -> Self {
+        let mut path = Vec::new();
+        let mut stop = ProbeStop::MaxRange;
+        for distance in 1..=SCOUT_PROBE_RANGE {
+            let position = origin.move_direction(heading, distance);
+            match map.get_tile(&TileCoordinate::from(position)) {
+                None => {
+                    stop = ProbeStop::WorldEdge;
+                    break;
+                }
+                Some(tile) if !tile.terrain_type.is_passable() => {
+                    stop = ProbeStop::Impassable(tile.terrain_type);
+                    break;
+                }
+                Some(_) => path.push(position),
+            }
+        }
+        Self {
+            origin,
+            heading,
+            path,
+            stop,
+        }
+    }
+
+    /// Check if the probe could not leave the launch tile
+    pub fn is_empty(&self) -> bool {
+        self.path.is_empty()
+    }
+}
+
+/// Stage of a probe flight
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProbePhase {
+    /// Crossing from the previous tile to `path[leg]`
+    Cruising { leg: usize, elapsed: f32 },
+    /// The route is done; the probe can be removed
+    Landed,
+}
+
+/// A launched probe
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeFlight {
+    route: ProbeRoute,
+    phase: ProbePhase,
+}
+
+impl ProbeFlight {
+    /// Start flying a route
+    pub fn launch(route: ProbeRoute) -> Self {
+        let phase = if route.is_empty() {
+            ProbePhase::Landed
+        } else {
+            ProbePhase::Cruising {
+                leg: 0,
+                elapsed: 0.0,
+            }
+        };
+        Self { route, phase }
+    }
+
+    /// The route being flown
+    pub fn route(&self) -> &ProbeRoute {
+        &self.route
+    }
+
+    /// Current stage of the flight
+    pub fn phase(&self) -> ProbePhase {
+        self.phase
+    }
+
+    /// Check if the flight is over
+    pub fn is_landed(&self) -> bool {
+        self.phase == ProbePhase::Landed
+    }
+
+    /// Advance the flight, returning the tiles reached during this tick
+    pub fn advance(&mut self, delta_secs: f32) -> Vec<Position3D> {
+        let mut reached = Vec::new();
+        let ProbePhase::Cruising {
+            mut leg,
+            mut elapsed,
+        } = self.phase
+        else {
+            return reached;
+        };
+
+        elapsed += delta_secs;
+        while elapsed >= SCOUT_PROBE_SECS_PER_TILE && leg < self.route.path.len() {
+            elapsed -= SCOUT_PROBE_SECS_PER_TILE;
+            reached.push(self.route.path[leg]);
+            leg += 1;
+        }
+        self.phase = if leg >= self.route.path.len() {
+            ProbePhase::Landed
+        } else {
+            ProbePhase::Cruising { leg, elapsed }
+        };
+        reached
+    }
+
+    /// Tiles the probe is between and how far along it is, for drawing
+    pub fn segment(&self) -> (Position3D, Position3D, f32) {
+        let last = self.route.path.last().copied().unwrap_or(self.route.origin);
+        match self.phase {
+            ProbePhase::Cruising { leg, elapsed } => {
+                let from = leg
+                    .checked_sub(1)
+                    .map_or(self.route.origin, |previous| self.route.path[previous]);
+                (
+                    from,
+                    self.route.path[leg],
+                    (elapsed / SCOUT_PROBE_SECS_PER_TILE).clamp(0.0, 1.0),
+                )
+            }
+            ProbePhase::Landed => (last, last, 1.0),
+        }
+    }
+}
+
+/// A point of interest a probe flew over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeSighting {
+    /// An untapped resource node
+    ResourceNode(ResourceType),
+    /// Ruins that can be delved into
+    Ruins,
+}
+
+/// Reveal the corridor slice below a probe at `center`
+///
+/// Returns the coordinates that were not explored before.
+pub fn reveal_probe_slice(
+    map: &mut Map,
+    visibility: &VisibilityService,
+    center: Position3D,
+    heading: Direction,
+) -> Vec<TileCoordinate> {
+    let mut revealed = Vec::new();
+    for coordinate in visibility.get_probe_corridor_coordinates(center, heading) {
+        let Some(tile) = map.get_tile(&coordinate) else {
+            continue;
+        };
+        if tile.is_explored() {
+            continue;
+        }
+        let mut explored = tile.clone();
+        explored.is_explored = true;
+        map.set_tile(coordinate, explored);
+        revealed.push(coordinate);
+    }
+    revealed
+}
+
+/// Points of interest on the given tiles
+pub fn probe_sightings(
+    map: &Map,
+    coordinates: &[TileCoordinate],
+) -> Vec<(TileCoordinate, ProbeSighting)> {
+    let ruins = InteriorGenerator::new();
+    coordinates
+        .iter()
+        .filter_map(|coordinate| {
+            let position = Position3D::from(*coordinate);
+            if let Some(node) = map
+                .get_resource_node(&position)
+                .filter(|node| !node.is_depleted())
+            {
+                return Some((
+                    *coordinate,
+                    ProbeSighting::ResourceNode(node.properties().resource_type),
+                ));
+            }
+            let tile = map.get_tile(coordinate)?;
+            ruins
+                .is_delve_site(map.seed(), *coordinate, tile.terrain_type)
+                .then_some((*coordinate, ProbeSighting::Ruins))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::MapTile;
+    use crate::domain::value_objects::terrain::Elevation;
+    use crate::domain::value_objects::EntityId;
+
+    /// Plains from x = -1..=1 and y = 0..length, unexplored
+    fn strip(length: i32) -> Map {
+        let mut map = Map::new(EntityId::generate(), "Test".to_string(), 1).unwrap();
+        for x in -1..=1 {
+            for y in 0..length {
+                map.set_tile(
+                    TileCoordinate::new(x, y, 0),
+                    MapTile::new(TerrainType::Plains, Elevation::sea_level(), false),
+                );
+            }
+        }
+        map
+    }
+
+    #[test]
+    fn route_stops_at_full_range_impassable_terrain_or_the_world_edge() {
+        let origin = Position3D::new(0, 0, 0);
+
+        let open = ProbeRoute::plan(&strip(20), origin, Direction::North);
+        assert_eq!(open.path.len(), SCOUT_PROBE_RANGE as usize);
+        assert_eq!(open.path[0], Position3D::new(0, 1, 0));
+        assert_eq!(open.stop, ProbeStop::MaxRange);
+
+        let mut lake = strip(20);
+        lake.set_tile(
+            TileCoordinate::new(0, 4, 0),
+            MapTile::new(TerrainType::Ocean, Elevation::sea_level(), false),
+        );
+        let blocked = ProbeRoute::plan(&lake, origin, Direction::North);
+        assert_eq!(blocked.path.len(), 3);
+        assert_eq!(blocked.stop, ProbeStop::Impassable(TerrainType::Ocean));
+
+        let edge = ProbeRoute::plan(&strip(6), origin, Direction::North);
+        assert_eq!(edge.path.last(), Some(&Position3D::new(0, 5, 0)));
+        assert_eq!(edge.stop, ProbeStop::WorldEdge);
+
+        // Nothing to the south of the strip
+        let nowhere = ProbeRoute::plan(&strip(6), origin, Direction::South);
+        assert!(nowhere.is_empty());
+        assert!(ProbeFlight::launch(nowhere).is_landed());
+    }
+
+    #[test]
+    fn flight_reaches_one_tile_per_step_and_reveals_a_three_wide_corridor() {
+        let mut map = strip(20);
+        let visibility = VisibilityService::new();
+        let route = ProbeRoute::plan(&map, Position3D::new(0, 0, 0), Direction::North);
+        let mut flight = ProbeFlight::launch(route);
+
+        assert!(flight.advance(SCOUT_PROBE_SECS_PER_TILE * 0.5).is_empty());
+        let (from, to, progress) = flight.segment();
+        assert_eq!(
+            (from, to),
+            (Position3D::new(0, 0, 0), Position3D::new(0, 1, 0))
+        );
+        assert!((progress - 0.5).abs() < 1e-4);
+
+        let mut revealed = Vec::new();
+        let mut reached = Vec::new();
+        while !flight.is_landed() {
+            for position in flight.advance(SCOUT_PROBE_SECS_PER_TILE) {
+                reached.push(position);
+                revealed.extend(reveal_probe_slice(
+                    &mut map,
+                    &visibility,
+                    position,
+                    Direction::North,
+                ));
+            }
+        }
+
+        assert_eq!(reached.len(), SCOUT_PROBE_RANGE as usize);
+        assert_eq!(revealed.len(), 3 * SCOUT_PROBE_RANGE as usize);
+        assert!(map
+            .get_tile(&TileCoordinate::new(-1, SCOUT_PROBE_RANGE, 0))
+            .unwrap()
+            .is_explored());
+        assert!(!map
+            .get_tile(&TileCoordinate::new(0, SCOUT_PROBE_RANGE + 1, 0))
+            .unwrap()
+            .is_explored());
+        // The launch tile is not part of the corridor
+        assert!(!map
+            .get_tile(&TileCoordinate::new(0, 0, 0))
+            .unwrap()
+            .is_explored());
+
+        // A second pass finds nothing new
+        assert!(reveal_probe_slice(&mut map, &visibility, reached[0], Direction::North).is_empty());
+    }
+}
@@ -0,0 +1,176 @@
+//! Seasons - Long cycles of world conditions
+//!
+//! Every `SEASON_LENGTH_DAYS` the world shifts into another season. A run
+//! always opens in a Calm; the other three follow in an order drawn from
+//! the world seed, and the four then repeat. Replays and daily runs of a
+//! seed therefore see the same season on the same day. A season is one
+//! layer of the run's `ModifierStack`, so its effects compose with the
+//! difficulty and mutators the same way every time.
+
+use crate::domain::constants::{
+    DRIFT_DATA_YIELD_MULTIPLIER, DRIFT_OPEN_TERRAIN_COST, FLARE_ENERGY_YIELD_MULTIPLIER,
+    FLARE_MALFUNCTION_CHANCE, SEASON_LENGTH_DAYS, SURGE_EVENT_PRESSURE, SURGE_THREAT,
+};
+use crate::domain::services::RunModifier;
+use crate::domain::value_objects::ResourceType;
+
+/// World conditions that hold for a whole season
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Season {
+    /// Baseline; nothing changes
+    Calm,
+    /// More Energy found, but gear may malfunction overnight
+    Flare,
+    /// Open terrain is slower to cross, but more Data is found
+    Drift,
+    /// Events turn up more often and moves roll worse
+    Surge,
+}
+
+impl Season {
+    /// Every season, Calm first
+    pub fn all() -> [Season; 4] {
+        [Season::Calm, Season::Flare, Season::Drift, Season::Surge]
+    }
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Season::Calm => "Calm",
+            Season::Flare => "Flare",
+            Season::Drift => "Drift",
+            Season::Surge => "Surge",
+        }
+    }
+
+    /// Icon shown with the name
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Season::Calm => "🌌",
+            Season::Flare => "☀️",
+            Season::Drift => "🌫️",
+            Season::Surge => "⚡",
+        }
+    }
+
+    /// What the season changes, in a few words
+    pub fn effects(&self) -> &'static str {
+        match self {
+            Season::Calm => "no effects",
+            Season::Flare => "more Energy found, gear may malfunction at night",
+            Season::Drift => "slower on open terrain, more Data found",
+            Season::Surge => "more events, worse movement rolls",
+        }
+    }
+
+    /// Rule changes of the season
+    pub fn modifier(&self) -> RunModifier {
+        let neutral = RunModifier::default();
+        match self {
+            Season::Calm => neutral,
+            Season::Flare => RunModifier {
+                yield_boost: Some((ResourceType::Energy, FLARE_ENERGY_YIELD_MULTIPLIER)),
+                malfunction_chance: FLARE_MALFUNCTION_CHANCE,
+                ..neutral
+            },
+            Season::Drift => RunModifier {
+                yield_boost: Some((ResourceType::Data, DRIFT_DATA_YIELD_MULTIPLIER)),
+                open_terrain_cost_delta: DRIFT_OPEN_TERRAIN_COST,
+                ..neutral
+            },
+            Season::Surge => RunModifier {
+                event_pressure: SURGE_EVENT_PRESSURE,
+                threat_delta: SURGE_THREAT,
+                ..neutral
+            },
+        }
+    }
+
+    /// Season of `day` in a world generated from `seed`
+    pub fn on_day(seed: u64, day: u32) -> Season {
+        let cycle = (day.max(1) - 1) / SEASON_LENGTH_DAYS;
+        season_order(seed)[cycle as usize % 4]
+    }
+}
+
+/// Days the season of `day` still lasts, `day` included
+pub fn season_days_left(day: u32) -> u32 {
+    SEASON_LENGTH_DAYS - (day.max(1) - 1) % SEASON_LENGTH_DAYS
+}
+
+/// The season that ends and the one that begins, if `day` starts a new one
+pub fn season_change(seed: u64, day: u32) -> Option<(Season, Season)> {
+    if day <= 1 {
+        return None;
+    }
+    let before = Season::on_day(seed, day - 1);
+    let after = Season::on_day(seed, day);
+    (before != after).then_some((before, after))
+}
+
+/// Calm, then the other seasons shuffled by the seed
+fn season_order(seed: u64) -> [Season; 4] {
+    let mut order = Season::all();
+    // Fisher-Yates over the last three, drawing from a mixed seed
+    for i in (2..4).rev() {
+        let j = 1 + (mix(seed ^ i as u64) % i as u64) as usize;
+        order.swap(i, j);
+    }
+    order
+}
+
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seasons_follow_from_seed_and_day() {
+        for seed in [0, 7, 1946, u64::MAX] {
+            let order: Vec<Season> = (0..4)
+                .map(|cycle| Season::on_day(seed, 1 + cycle * SEASON_LENGTH_DAYS))
+                .collect();
+            // Calm opens every run and each season comes up once per round
+            assert_eq!(order[0], Season::Calm);
+            for season in Season::all() {
+                assert!(order.contains(&season));
+            }
+            // A season holds for its whole length, then the round repeats
+            for day in 1..=SEASON_LENGTH_DAYS {
+                assert_eq!(Season::on_day(seed, day), Season::Calm);
+            }
+            assert_eq!(Season::on_day(seed, 16), order[1]);
+            assert_eq!(Season::on_day(seed, 61), Season::Calm);
+            assert_eq!(Season::on_day(seed, 76), order[1]);
+            assert_eq!(Season::on_day(seed, 0), Season::Calm);
+        }
+        // Different worlds may take the seasons in a different order
+        let orders: std::collections::HashSet<Season> =
+            (0..32).map(|seed| Season::on_day(seed, 16)).collect();
+        assert!(orders.len() > 1);
+
+        assert_eq!(season_days_left(1), SEASON_LENGTH_DAYS);
+        assert_eq!(season_days_left(15), 1);
+        assert_eq!(season_days_left(16), SEASON_LENGTH_DAYS);
+    }
+
+    #[test]
+    fn each_change_is_announced_on_exactly_one_day() {
+        let seed = 1946;
+        let changes: Vec<(u32, Season, Season)> = (0..=90)
+            .filter_map(|day| season_change(seed, day).map(|(from, to)| (day, from, to)))
+            .collect();
+        let days: Vec<u32> = changes.iter().map(|(day, _, _)| *day).collect();
+        assert_eq!(days, vec![16, 31, 46, 61, 76]);
+        for (day, from, to) in changes {
+            assert_eq!(from, Season::on_day(seed, day - 1));
+            assert_eq!(to, Season::on_day(seed, day));
+        }
+    }
+}
@@ -0,0 +1,586 @@
+//! Session Flags - Choices that later events remember
+//!
+//! Encounter outcomes and quest steps leave marks on the session: a flag
+//! (`spared_scavenger`), a counter (`bribes_paid`) or a tag holding a word
+//! (`vault_alpha` = `opened`). Event data refers to them through small
+//! conditions written as text:
+//!
+//! ```text
+//! spared_scavenger                      the key is set
+//! !spared_scavenger                     negation
+//! bribes_paid >= 3                      counter comparison: < <= > >= == !=
+//! vault_alpha == opened                 tag comparison: == !=
+//! spared_scavenger & bribes_paid < 2    both hold; `|` for either, `( )` to group
+//! ```
+//!
+//! Weights are written `x2 if bribes_paid >= 3`. Keys that were never set
+//! read as unset, and their counters as zero.
+
+use crate::domain::entities::Quest;
+use crate::domain::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Value stored under a session key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagValue {
+    /// Something happened
+    Flag,
+    /// How many times something happened
+    Counter(u32),
+    /// Which way something went
+    Tag(String),
+}
+
+impl std::fmt::Display for FlagValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FlagValue::Flag => write!(f, "set"),
+            FlagValue::Counter(count) => write!(f, "{}", count),
+            FlagValue::Tag(value) => write!(f, "\"{}\"", value),
+        }
+    }
+}
+
+/// Flags, counters and tags set during a run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SessionFlags {
+    values: BTreeMap<String, FlagValue>,
+}
+
+impl SessionFlags {
+    /// Start a run with nothing set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a flag, replacing whatever was stored under the key
+    pub fn set_flag(&mut self, key: &str) {
+        self.values.insert(key.to_string(), FlagValue::Flag);
+    }
+
+    /// Add to a counter and return its new count
+    pub fn increment(&mut self, key: &str, amount: u32) -> u32 {
+        let count = self.counter(key).saturating_add(amount);
+        self.values
+            .insert(key.to_string(), FlagValue::Counter(count));
+        count
+    }
+
+    /// Set a tag, replacing whatever was stored under the key
+    pub fn set_tag(&mut self, key: &str, value: &str) {
+        self.values
+            .insert(key.to_string(), FlagValue::Tag(value.to_string()));
+    }
+
+    /// Remove a key
+    pub fn clear(&mut self, key: &str) -> Option<FlagValue> {
+        self.values.remove(key)
+    }
+
+    /// Value stored under a key
+    pub fn get(&self, key: &str) -> Option<&FlagValue> {
+        self.values.get(key)
+    }
+
+    /// Check if a key is set; counters must be above zero
+    pub fn is_set(&self, key: &str) -> bool {
+        match self.values.get(key) {
+            Some(FlagValue::Counter(count)) => *count > 0,
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    /// Count of a counter, zero when it is not one
+    pub fn counter(&self, key: &str) -> u32 {
+        match self.values.get(key) {
+            Some(FlagValue::Counter(count)) => *count,
+            _ => 0,
+        }
+    }
+
+    /// Word of a tag
+    pub fn tag(&self, key: &str) -> Option<&str> {
+        match self.values.get(key) {
+            Some(FlagValue::Tag(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Number of keys set
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Check if nothing is set
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Every key with its value, in key order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &FlagValue)> {
+        self.values.iter().map(|(key, value)| (key.as_str(), value))
+    }
+
+    /// One `key = value` line per key, for debugging
+    pub fn listing(&self) -> Vec<String> {
+        self.iter()
+            .map(|(key, value)| format!("{} = {}", key, value))
+            .collect()
+    }
+
+    /// Set the flags a completed quest unlocks
+    pub fn record_quest(&mut self, quest: &Quest) {
+        if quest.is_completed() {
+            for unlock in &quest.rewards().unlocks {
+                self.set_flag(unlock);
+            }
+        }
+    }
+}
+
+/// How a counter is compared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    /// Check if `left` compares to `right` this way
+    pub fn holds(self, left: u32, right: u32) -> bool {
+        match self {
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Greater => left > right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Equal => left == right,
+            Comparison::NotEqual => left != right,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Equal => "==",
+            Comparison::NotEqual => "!=",
+        }
+    }
+}
+
+/// A condition on the session flags, parsed from text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FlagCondition {
+    /// The key is set
+    Set(String),
+    /// A counter compares to a number
+    Counter {
+        key: String,
+        comparison: Comparison,
+        value: u32,
+    },
+    /// A tag holds a word
+    Tag {
+        key: String,
+        value: String,
+    },
+    Not(Box<FlagCondition>),
+    All(Vec<FlagCondition>),
+    Any(Vec<FlagCondition>),
+}
+
+impl FlagCondition {
+    /// Check the condition against the session flags
+    pub fn holds(&self, flags: &SessionFlags) -> bool {
+        match self {
+            FlagCondition::Set(key) => flags.is_set(key),
+            FlagCondition::Counter {
+                key,
+                comparison,
+                value,
+            } => comparison.holds(flags.counter(key), *value),
+            FlagCondition::Tag { key, value } => flags.tag(key) == Some(value.as_str()),
+            FlagCondition::Not(inner) => !inner.holds(flags),
+            FlagCondition::All(terms) => terms.iter().all(|term| term.holds(flags)),
+            FlagCondition::Any(terms) => terms.iter().any(|term| term.holds(flags)),
+        }
+    }
+}
+
+impl FromStr for FlagCondition {
+    type Err = DomainError;
+
+    fn from_str(text: &str) -> DomainResult<Self> {
+        let mut parser = ConditionParser {
+            tokens: tokenize(text)?,
+            position: 0,
+        };
+        let condition = parser.any()?;
+        match parser.tokens.get(parser.position) {
+            None => Ok(condition),
+            Some(token) => Err(invalid_condition(text, &format!("unexpected {}", token))),
+        }
+    }
+}
+
+/// Factor applied to an event's weight while a condition holds
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlagWeight {
+    pub factor: f32,
+    pub condition: FlagCondition,
+}
+
+impl FlagWeight {
+    /// Factor for the current flags; 1 when the condition does not hold
+    pub fn factor_for(&self, flags: &SessionFlags) -> f32 {
+        if self.condition.holds(flags) {
+            self.factor
+        } else {
+            1.0
+        }
+    }
+}
+
+impl FromStr for FlagWeight {
+    type Err = DomainError;
+
+    fn from_str(text: &str) -> DomainResult<Self> {
+        let (factor, condition) = text
+            .split_once(" if ")
+            .ok_or_else(|| invalid_weight(text, "expected `x<factor> if <condition>`"))?;
+        let factor = factor.trim();
+        let factor = factor
+            .strip_prefix('x')
+            .or_else(|| factor.strip_prefix('×'))
+            .ok_or_else(|| invalid_weight(text, "the factor starts with `x`"))?
+            .parse::<f32>()
+            .ok()
+            .filter(|factor| factor.is_finite() && *factor >= 0.0)
+            .ok_or_else(|| invalid_weight(text, "the factor is not a number of zero or more"))?;
+        Ok(Self {
+            factor,
+            condition: condition.parse()?,
+        })
+    }
+}
+
+/// Product of the weight factors whose conditions hold
+pub fn weight_factor(weights: &[FlagWeight], flags: &SessionFlags) -> f32 {
+    weights
+        .iter()
+        .map(|weight| weight.factor_for(flags))
+        .product()
+}
+
+fn invalid_condition(text: &str, reason: &str) -> DomainError {
+    DomainError::ValidationError(format!("Invalid flag condition '{}': {}", text, reason))
+}
+
+fn invalid_weight(text: &str, reason: &str) -> DomainError {
+    DomainError::ValidationError(format!("Invalid flag weight '{}': {}", text, reason))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Key(String),
+    Number(u32),
+    Compare(Comparison),
+    Not,
+    And,
+    Or,
+    Open,
+    Close,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Key(key) => write!(f, "'{}'", key),
+            Token::Number(value) => write!(f, "'{}'", value),
+            Token::Compare(comparison) => write!(f, "'{}'", comparison.symbol()),
+            Token::Not => write!(f, "'!'"),
+            Token::And => write!(f, "'&'"),
+            Token::Or => write!(f, "'|'"),
+            Token::Open => write!(f, "'('"),
+            Token::Close => write!(f, "')'"),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> DomainResult<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '&' => Token::And,
+            '|' => Token::Or,
+            '!' if chars.next_if_eq(&'=').is_some() => Token::Compare(Comparison::NotEqual),
+            '!' => Token::Not,
+            '<' | '>' | '=' => {
+                let or_equal = chars.next_if_eq(&'=').is_some();
+                Token::Compare(match (c, or_equal) {
+                    ('<', false) => Comparison::Less,
+                    ('<', true) => Comparison::LessOrEqual,
+                    ('>', false) => Comparison::Greater,
+                    ('>', true) => Comparison::GreaterOrEqual,
+                    ('=', true) => Comparison::Equal,
+                    _ => return Err(invalid_condition(text, "use `==` to compare")),
+                })
+            }
+            c if c.is_ascii_digit() => {
+                let mut digits = c.to_string();
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                    digits.push(digit);
+                }
+                Token::Number(
+                    digits
+                        .parse()
+                        .map_err(|_| invalid_condition(text, "number out of range"))?,
+                )
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut key = c.to_string();
+                while let Some(next) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                    key.push(next);
+                }
+                Token::Key(key)
+            }
+            other => {
+                return Err(invalid_condition(
+                    text,
+                    &format!("unexpected character '{}'", other),
+                ))
+            }
+        };
+        tokens.push(token);
+    }
+    if tokens.is_empty() {
+        return Err(invalid_condition(text, "the condition is empty"));
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent over `any := all ('|' all)*`, `all := unary ('&' unary)*`
+/// and `unary := '!' unary | '(' any ')' | key [comparison value]`
+struct ConditionParser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl ConditionParser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.position) == Some(token) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error(&self, reason: &str) -> DomainError {
+        let text = self
+            .tokens
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+        invalid_condition(&text, reason)
+    }
+
+    fn any(&mut self) -> DomainResult<FlagCondition> {
+        let mut terms = vec![self.all()?];
+        while self.eat(&Token::Or) {
+            terms.push(self.all()?);
+        }
+        Ok(match terms.len() {
+            1 => terms.remove(0),
+            _ => FlagCondition::Any(terms),
+        })
+    }
+
+    fn all(&mut self) -> DomainResult<FlagCondition> {
+        let mut terms = vec![self.unary()?];
+        while self.eat(&Token::And) {
+            terms.push(self.unary()?);
+        }
+        Ok(match terms.len() {
+            1 => terms.remove(0),
+            _ => FlagCondition::All(terms),
+        })
+    }
+
+    fn unary(&mut self) -> DomainResult<FlagCondition> {
+        match self.next() {
+            Some(Token::Not) => Ok(FlagCondition::Not(Box::new(self.unary()?))),
+            Some(Token::Open) => {
+                let inner = self.any()?;
+                if self.eat(&Token::Close) {
+                    Ok(inner)
+                } else {
+                    Err(self.error("missing ')'"))
+                }
+            }
+            Some(Token::Key(key)) => self.comparison(key),
+            Some(token) => Err(self.error(&format!("unexpected {}", token))),
+            None => Err(self.error("the condition ends too early")),
+        }
+    }
+
+    fn comparison(&mut self, key: String) -> DomainResult<FlagCondition> {
+        let Some(Token::Compare(comparison)) = self.tokens.get(self.position).cloned() else {
+            return Ok(FlagCondition::Set(key));
+        };
+        self.position += 1;
+        match (self.next(), comparison) {
+            (Some(Token::Number(value)), _) => Ok(FlagCondition::Counter {
+                key,
+                comparison,
+                value,
+            }),
+            (Some(Token::Key(value)), Comparison::Equal) => Ok(FlagCondition::Tag { key, value }),
+            (Some(Token::Key(value)), Comparison::NotEqual) => {
+                Ok(FlagCondition::Not(Box::new(FlagCondition::Tag {
+                    key,
+                    value,
+                })))
+            }
+            _ => Err(self.error(&format!(
+                "'{}' compares with a number, or with a word using == or !=",
+                key
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(text: &str) -> FlagCondition {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn conditions_parse_and_evaluate() {
+        let mut flags = SessionFlags::new();
+        flags.set_flag("spared_scavenger");
+        flags.increment("bribes_paid", 2);
+        flags.set_tag("vault_alpha", "opened");
+
+        for (text, expected) in [
+            ("spared_scavenger", true),
+            ("!spared_scavenger", false),
+            ("ambushed", false),
+            ("bribes_paid >= 2", true),
+            ("bribes_paid>2", false),
+            ("bribes_paid != 0", true),
+            ("raiders_driven_off < 1", true),
+            ("vault_alpha == opened", true),
+            ("vault_alpha != opened", false),
+            ("spared_scavenger & bribes_paid < 2", false),
+            ("ambushed | bribes_paid == 2", true),
+            ("!(ambushed | spared_scavenger) & vault_alpha", false),
+            ("!!spared_scavenger", true),
+        ] {
+            assert_eq!(condition(text).holds(&flags), expected, "{}", text);
+        }
+        assert_eq!(
+            condition("a & b | c"),
+            FlagCondition::Any(vec![
+                FlagCondition::All(vec![
+                    FlagCondition::Set("a".to_string()),
+                    FlagCondition::Set("b".to_string()),
+                ]),
+                FlagCondition::Set("c".to_string()),
+            ])
+        );
+
+        for text in [
+            "",
+            "   ",
+            "spared_scavenger &",
+            "(spared_scavenger",
+            "spared_scavenger)",
+            "bribes_paid = 3",
+            "bribes_paid >= -1",
+            "bribes_paid > opened",
+            "3 > bribes_paid",
+            "spared scavenger",
+            "spared-scavenger",
+        ] {
+            assert!(text.parse::<FlagCondition>().is_err(), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn weights_apply_only_while_their_condition_holds() {
+        let weights: Vec<FlagWeight> = ["x2 if bribes_paid >= 3", "×0.5 if ambushed"]
+            .iter()
+            .map(|text| text.parse().unwrap())
+            .collect();
+        let mut flags = SessionFlags::new();
+        assert_eq!(weight_factor(&weights, &flags), 1.0);
+
+        flags.increment("bribes_paid", 3);
+        assert_eq!(weight_factor(&weights, &flags), 2.0);
+        flags.set_flag("ambushed");
+        assert_eq!(weight_factor(&weights, &flags), 1.0);
+        assert_eq!(weight_factor(&[], &flags), 1.0);
+
+        for text in [
+            "2 if ambushed",
+            "x if ambushed",
+            "x-1 if ambushed",
+            "x2 ambushed",
+        ] {
+            assert!(text.parse::<FlagWeight>().is_err(), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn flag_store_keeps_its_types_through_serialization() {
+        let mut flags = SessionFlags::new();
+        flags.set_flag("spared_scavenger");
+        assert_eq!(flags.increment("bribes_paid", 1), 1);
+        assert_eq!(flags.increment("bribes_paid", 2), 3);
+        flags.set_tag("vault_alpha", "opened");
+        flags.increment("zero", 0);
+
+        let json = serde_json::to_value(&flags).unwrap();
+        assert_eq!(json["bribes_paid"], serde_json::json!({ "counter": 3 }));
+        let restored: SessionFlags = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, flags);
+        assert_eq!(restored.counter("bribes_paid"), 3);
+        assert_eq!(restored.tag("vault_alpha"), Some("opened"));
+        assert!(!restored.is_set("zero"));
+        assert_eq!(
+            restored.listing(),
+            vec![
+                "bribes_paid = 3",
+                "spared_scavenger = set",
+                "vault_alpha = \"opened\"",
+                "zero = 0",
+            ]
+        );
+    }
+}
@@ -0,0 +1,342 @@
+//! Terrain Noise - Coherent terrain from layered value noise
+//!
+//! Three smooth fields are sampled for every position of the overworld:
+//! elevation decides where the sea, the tundra, the mountains and the caves
+//! are and how high a tile stands, moisture splits the lowland into desert,
+//! plains, forest and swamp, and a fine feature field raises rare clusters
+//! of crystal, volcanic, constructed and anomaly tiles. Each field is
+//! fractal value noise: a lattice of seeded random values, smoothly
+//! interpolated and summed over a few octaves. Neighbouring tiles sample
+//! nearly the same values, so biomes form regions tens of tiles across and
+//! the sea forms connected bodies. Every threshold and octave setting lives
+//! in [`GenerationConfig`].
+
+use crate::domain::value_objects::{terrain::TerrainType, Position3D};
+
+/// Salt of the elevation field
+const ELEVATION_SALT: u64 = 0x454C_4556_4154_494F;
+/// Salt of the moisture field
+const MOISTURE_SALT: u64 = 0x4D4F_4953_5455_5245;
+/// Salt of the feature field
+const FEATURE_SALT: u64 = 0x4645_4154_5552_4553;
+/// Spreads the octaves of a field apart
+const OCTAVE_STEP: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// One fractal noise field
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoiseLayer {
+    /// Tiles between lattice points of the first octave
+    pub scale: f64,
+    /// Octaves summed, each finer than the last
+    pub octaves: u32,
+    /// Amplitude of each octave relative to the previous one
+    pub persistence: f64,
+    /// Frequency of each octave relative to the previous one
+    pub lacunarity: f64,
+}
+
+impl NoiseLayer {
+    /// Create a layer with the usual halving amplitude and doubling frequency
+    pub fn new(scale: f64, octaves: u32) -> Self {
+        Self {
+            scale,
+            octaves,
+            persistence: 0.5,
+            lacunarity: 2.0,
+        }
+    }
+
+    /// Value of the field at a point, between 0.0 and 1.0
+    pub fn sample(&self, seed: u64, salt: u64, x: f64, y: f64) -> f64 {
+        let mut total = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0 / self.scale.max(1.0);
+        let mut norm = 0.0;
+        for octave in 0..self.octaves.max(1) {
+            let octave_salt = salt.wrapping_add((octave as u64).wrapping_mul(OCTAVE_STEP));
+            total += amplitude * value_noise(seed, octave_salt, x * frequency, y * frequency);
+            norm += amplitude;
+            amplitude *= self.persistence;
+            frequency *= self.lacunarity;
+        }
+        total / norm
+    }
+}
+
+/// Thresholds and noise settings of overworld generation
+///
+/// Field values run from 0.0 to 1.0 and cluster around 0.5. Elevation
+/// below `ocean_level` is sea; above it the bands from `tundra_level` up are
+/// highland, and lowland terrain follows moisture. Feature peaks above
+/// `feature_level` replace the terrain beneath them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenerationConfig {
+    pub elevation: NoiseLayer,
+    pub moisture: NoiseLayer,
+    pub features: NoiseLayer,
+    /// Elevation below which a tile is ocean
+    pub ocean_level: f64,
+    pub tundra_level: f64,
+    pub mountain_level: f64,
+    pub cave_level: f64,
+    /// Moisture below which lowland is desert
+    pub dry_level: f64,
+    /// Moisture from which lowland is forest
+    pub forest_level: f64,
+    /// Moisture from which lowland is swamp
+    pub wet_level: f64,
+    /// Feature value from which a special terrain replaces the tile
+    pub feature_level: f64,
+    /// Feature value from which lowland features are anomalies
+    pub anomaly_level: f64,
+    /// Tiles around the origin lifted out of the sea
+    pub landfall_radius: f64,
+    /// Elevation the landfall lifts low ground to at the origin
+    pub landfall_level: f64,
+    /// Elevation level of the highest ground
+    pub max_height: i32,
+    /// Largest shift of the fields when a tile is rerolled
+    pub reroll_jitter: f64,
+}
+
+impl Default for GenerationConfig {
+    fn default() -> Self {
+        Self {
+            elevation: NoiseLayer::new(32.0, 4),
+            moisture: NoiseLayer::new(24.0, 3),
+            features: NoiseLayer::new(6.0, 2),
+            ocean_level: 0.36,
+            tundra_level: 0.60,
+            mountain_level: 0.65,
+            cave_level: 0.72,
+            dry_level: 0.42,
+            forest_level: 0.53,
+            wet_level: 0.60,
+            feature_level: 0.78,
+            anomaly_level: 0.86,
+            landfall_radius: 12.0,
+            landfall_level: 0.44,
+            max_height: 20,
+            reroll_jitter: 0.03,
+        }
+    }
+}
+
+impl GenerationConfig {
+    /// Terrain of a sample
+    pub fn classify(&self, sample: TerrainSample) -> TerrainType {
+        if sample.elevation < self.ocean_level {
+            return TerrainType::Ocean;
+        }
+        let highland = sample.elevation >= self.tundra_level;
+        let terrain = if sample.elevation >= self.cave_level {
+            TerrainType::Cave
+        } else if sample.elevation >= self.mountain_level {
+            TerrainType::Mountains
+        } else if highland {
+            TerrainType::Tundra
+        } else if sample.moisture < self.dry_level {
+            TerrainType::Desert
+        } else if sample.moisture >= self.wet_level {
+            TerrainType::Swamp
+        } else if sample.moisture >= self.forest_level {
+            TerrainType::Forest
+        } else {
+            TerrainType::Plains
+        };
+
+        if sample.feature < self.feature_level {
+            terrain
+        } else if highland {
+            TerrainType::Crystal
+        } else if terrain == TerrainType::Desert {
+            TerrainType::Volcanic
+        } else if sample.feature >= self.anomaly_level {
+            TerrainType::Anomaly
+        } else {
+            TerrainType::Constructed
+        }
+    }
+
+    /// Elevation level of a sample: sea level up to `max_height`
+    pub fn height(&self, sample: TerrainSample) -> i32 {
+        let above_sea = (sample.elevation - self.ocean_level) / (1.0 - self.ocean_level);
+        (above_sea.clamp(0.0, 1.0) * self.max_height as f64).round() as i32
+    }
+}
+
+/// Field values at one position
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TerrainSample {
+    pub elevation: f64,
+    pub moisture: f64,
+    pub feature: f64,
+}
+
+impl TerrainSample {
+    /// The sample with elevation and moisture shifted, by up to `amount` each
+    ///
+    /// Both shifts are drawn from `roll`, so a tile near a border may cross
+    /// it while a tile deep inside a region keeps its terrain.
+    pub fn jittered(self, roll: u64, amount: f64) -> Self {
+        let shift = |bits: u64| ((bits % 201) as f64 / 100.0 - 1.0) * amount;
+        Self {
+            elevation: self.elevation + shift(roll),
+            moisture: self.moisture + shift(roll / 201),
+            feature: self.feature,
+        }
+    }
+}
+
+/// The noise fields of one world seed
+#[derive(Debug, Clone)]
+pub struct TerrainNoise {
+    seed: u64,
+    config: GenerationConfig,
+}
+
+impl TerrainNoise {
+    /// Create the fields of a seed
+    pub fn new(seed: u64, config: GenerationConfig) -> Self {
+        Self { seed, config }
+    }
+
+    /// Generation settings in use
+    pub fn config(&self) -> &GenerationConfig {
+        &self.config
+    }
+
+    /// Field values at a position; every z-level shares the surface fields
+    pub fn sample(&self, position: Position3D) -> TerrainSample {
+        let (x, y) = (position.x as f64, position.y as f64);
+        let mut elevation = self
+            .config
+            .elevation
+            .sample(self.seed, ELEVATION_SALT, x, y);
+
+        // Lift low ground near the origin so every world starts on land
+        let distance = (x * x + y * y).sqrt();
+        if distance < self.config.landfall_radius {
+            let lift = (2.0 * (1.0 - distance / self.config.landfall_radius)).min(1.0);
+            elevation = elevation.max(self.config.landfall_level * lift);
+        }
+
+        TerrainSample {
+            elevation,
+            moisture: self.config.moisture.sample(self.seed, MOISTURE_SALT, x, y),
+            feature: self.config.features.sample(self.seed, FEATURE_SALT, x, y),
+        }
+    }
+
+    /// Terrain at a position
+    pub fn terrain(&self, position: Position3D) -> TerrainType {
+        self.config.classify(self.sample(position))
+    }
+}
+
+/// Smoothly interpolated lattice noise, between 0.0 and 1.0
+fn value_noise(seed: u64, salt: u64, x: f64, y: f64) -> f64 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (tx, ty) = (smoothstep(x - x0), smoothstep(y - y0));
+    let (ix, iy) = (x0 as i64, y0 as i64);
+
+    let top = lerp(
+        lattice(seed, salt, ix, iy),
+        lattice(seed, salt, ix + 1, iy),
+        tx,
+    );
+    let bottom = lerp(
+        lattice(seed, salt, ix, iy + 1),
+        lattice(seed, salt, ix + 1, iy + 1),
+        tx,
+    );
+    lerp(top, bottom, ty)
+}
+
+/// Random value of a lattice point, between 0.0 and 1.0
+fn lattice(seed: u64, salt: u64, ix: i64, iy: i64) -> f64 {
+    let hash = mix(mix(mix(seed ^ salt) ^ ix as u64) ^ iy as u64);
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// SplitMix64 finalizer
+fn mix(value: u64) -> u64 {
+    let mut z = value;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn smoothstep(t: f64) -> f64 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn noise_is_deterministic_smooth_and_bounded() {
+        let layer = NoiseLayer::new(16.0, 4);
+        for step in 0..200 {
+            let x = step as f64 * 0.37 - 40.0;
+            let value = layer.sample(7, ELEVATION_SALT, x, -x * 0.5);
+            assert!((0.0..=1.0).contains(&value));
+            assert_eq!(value, layer.sample(7, ELEVATION_SALT, x, -x * 0.5));
+            // Neighbouring tiles differ only a little
+            let next = layer.sample(7, ELEVATION_SALT, x + 1.0, -x * 0.5);
+            assert!((value - next).abs() < 0.25);
+        }
+        assert_ne!(
+            layer.sample(7, ELEVATION_SALT, 3.5, 3.5),
+            layer.sample(8, ELEVATION_SALT, 3.5, 3.5)
+        );
+    }
+
+    #[test]
+    fn thresholds_pick_the_terrain() {
+        let config = GenerationConfig::default();
+        let at = |elevation, moisture, feature| {
+            config.classify(TerrainSample {
+                elevation,
+                moisture,
+                feature,
+            })
+        };
+        assert_eq!(at(0.2, 0.5, 0.5), TerrainType::Ocean);
+        assert_eq!(at(0.5, 0.3, 0.5), TerrainType::Desert);
+        assert_eq!(at(0.5, 0.5, 0.5), TerrainType::Plains);
+        assert_eq!(at(0.5, 0.55, 0.5), TerrainType::Forest);
+        assert_eq!(at(0.5, 0.7, 0.5), TerrainType::Swamp);
+        assert_eq!(at(0.62, 0.5, 0.5), TerrainType::Tundra);
+        assert_eq!(at(0.68, 0.5, 0.5), TerrainType::Mountains);
+        assert_eq!(at(0.8, 0.5, 0.5), TerrainType::Cave);
+        assert_eq!(at(0.8, 0.5, 0.8), TerrainType::Crystal);
+        assert_eq!(at(0.5, 0.3, 0.8), TerrainType::Volcanic);
+        assert_eq!(at(0.5, 0.5, 0.8), TerrainType::Constructed);
+        assert_eq!(at(0.5, 0.5, 0.9), TerrainType::Anomaly);
+        // Features never rise from the sea
+        assert_eq!(at(0.2, 0.5, 0.9), TerrainType::Ocean);
+
+        assert_eq!(
+            config.height(TerrainSample {
+                elevation: 0.2,
+                moisture: 0.5,
+                feature: 0.5,
+            }),
+            0
+        );
+        assert_eq!(
+            config.height(TerrainSample {
+                elevation: 1.0,
+                moisture: 0.5,
+                feature: 0.5,
+            }),
+            config.max_height
+        );
+    }
+}
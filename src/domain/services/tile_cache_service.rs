@@ -247,6 +247,8 @@ mod tests {
             elevation: Elevation::sea_level(),
             is_explored: false,
             last_visited: None,
+            last_visited_day: None,
+            richness: crate::domain::value_objects::resources::ResourceRichness::Average,
         }
     }
 
@@ -5,15 +5,24 @@
 //! based on the roll result and player progression.
 
 use crate::domain::entities::{Event, EventType, Map, Player};
-use crate::domain::services::MapService;
+use crate::domain::services::{
+    weight_factor, CalendarPhase, Faction, FlagCondition, FlagWeight, MapService, Reputation,
+    SessionFlags,
+};
 use crate::domain::value_objects::{
-    dice::{DiceModifier, DiceRoll, DiceType},
+    dice::{DiceModifier, DiceRoll, DiceType, SuccessLevel},
+    terrain::TerrainType,
     Position3D, TileCoordinate,
 };
 use crate::domain::{DomainError, DomainResult};
 
+use rand::seq::SliceRandom;
 use rand::Rng;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// Lowest neutral roll that passes without an event
+const QUIET_ROLL: u8 = 10;
 
 /// Service for handling tile-based movement with dice events
 #[derive(Debug, bevy::prelude::Resource)]
@@ -32,6 +41,32 @@ impl TileMovementService {
         service
     }
 
+    /// Add an event a roll in `category` can trigger, replacing the event
+    /// with the same title there
+    pub fn add_event_template(
+        &mut self,
+        category: EventCategory,
+        event_type: EventType,
+        title: &str,
+        description: &str,
+    ) {
+        let template = EventTemplate {
+            event_type,
+            title: title.to_string(),
+            description: description.to_string(),
+            faction: None,
+            flags: FlagHooks::default(),
+        };
+        let templates = self.event_templates.entry(category).or_default();
+        match templates
+            .iter_mut()
+            .find(|existing| existing.title == title)
+        {
+            Some(existing) => *existing = template,
+            None => templates.push(template),
+        }
+    }
+
     /// Execute a movement attempt from current position to target position
     /// Returns the movement result with any triggered events
     pub fn attempt_movement(
@@ -40,6 +75,70 @@ impl TileMovementService {
         target_position: Position3D,
         map: &mut Map,
         player_level: u32,
+    ) -> DomainResult<MovementResult> {
+        self.attempt_movement_with_assist(
+            player,
+            target_position,
+            map,
+            player_level,
+            &DiceModifier::none(),
+        )
+    }
+
+    /// Execute a movement attempt with an extra assist modifier on the roll
+    pub fn attempt_movement_with_assist(
+        &self,
+        player: &Player,
+        target_position: Position3D,
+        map: &mut Map,
+        player_level: u32,
+        assist: &DiceModifier,
+    ) -> DomainResult<MovementResult> {
+        self.attempt_movement_with_conditions(
+            player,
+            target_position,
+            map,
+            player_level,
+            assist,
+            MovementConditions::default(),
+        )
+    }
+
+    /// Execute a movement attempt under conditions of the target tile
+    pub fn attempt_movement_with_conditions(
+        &self,
+        player: &Player,
+        target_position: Position3D,
+        map: &mut Map,
+        player_level: u32,
+        assist: &DiceModifier,
+        conditions: MovementConditions,
+    ) -> DomainResult<MovementResult> {
+        self.attempt_movement_with_rngs(
+            player,
+            target_position,
+            map,
+            player_level,
+            assist,
+            conditions,
+            &mut rand::thread_rng(),
+            &mut rand::thread_rng(),
+        )
+    }
+
+    /// Execute a movement attempt, rolling the dice from `rolls` and picking
+    /// the event from `events` so each can follow its own seeded stream
+    #[allow(clippy::too_many_arguments)]
+    pub fn attempt_movement_with_rngs<R: Rng + ?Sized, E: Rng + ?Sized>(
+        &self,
+        player: &Player,
+        target_position: Position3D,
+        map: &mut Map,
+        player_level: u32,
+        assist: &DiceModifier,
+        conditions: MovementConditions,
+        rolls: &mut R,
+        events: &mut E,
     ) -> DomainResult<MovementResult> {
         // Generate tiles around player position if needed
         let map_service = MapService::new(map.seed());
@@ -64,7 +163,7 @@ impl TileMovementService {
         }
 
         // Calculate movement cost
-        let movement_cost = map.movement_cost(&target_position);
+        let movement_cost = conditions.step_cost(map, &target_position);
         if player.movement_points() < movement_cost {
             return Err(DomainError::InsufficientResources(format!(
                 "Not enough movement points. Need: {}, Have: {}",
@@ -74,11 +173,29 @@ impl TileMovementService {
         }
 
         // Roll dice for movement event
-        let dice_result = self.roll_movement_dice(player, map, &target_position, player_level)?;
+        let dice_result = self.roll_movement_dice(
+            player,
+            map,
+            &target_position,
+            player_level,
+            assist,
+            conditions.disadvantage,
+            rolls,
+        )?;
 
         // Generate event based on dice result
-        let event =
-            self.generate_movement_event(&dice_result, &target_position, map, player_level)?;
+        let event = self.generate_movement_event(
+            &dice_result,
+            &target_position,
+            map,
+            player_level,
+            &conditions,
+            events,
+        )?;
+        let (triggered_event, marks_flag, faction) = match event {
+            Some((event, marks_flag, faction)) => (Some(event), marks_flag, faction),
+            None => (None, None, None),
+        };
 
         // Update map cache for new player position
         map.update_player_position(target_position);
@@ -86,10 +203,13 @@ impl TileMovementService {
         // Create movement result
         let result = MovementResult {
             success: true,
+            origin_position: *player.position(),
             target_position,
             movement_cost,
             dice_result: dice_result.clone(),
-            triggered_event: event,
+            triggered_event,
+            marks_flag,
+            faction,
         };
 
         Ok(result)
@@ -106,105 +226,161 @@ impl TileMovementService {
     }
 
     /// Roll dice for movement with modifiers based on player and environment
-    fn roll_movement_dice(
+    #[allow(clippy::too_many_arguments)]
+    fn roll_movement_dice<R: Rng + ?Sized>(
         &self,
         player: &Player,
         map: &Map,
         target_position: &Position3D,
         player_level: u32,
+        assist: &DiceModifier,
+        disadvantage: bool,
+        rng: &mut R,
     ) -> DomainResult<MovementDiceResult> {
-        // Base dice roll (d20)
+        // Base dice roll (d20), the lower of two under disadvantage
         let base_dice = DiceRoll::new(1, DiceType::D20, DiceModifier::none())?;
-        let base_result = base_dice.total();
+        let mut base_result = rng.gen_range(1..=DiceType::D20.max_value());
+        if disadvantage {
+            base_result = base_result.min(rng.gen_range(1..=DiceType::D20.max_value()));
+        }
 
-        // Calculate modifiers
-        let mut total_modifier = 0i8;
+        let modifiers = self.roll_modifiers(player, map, target_position, player_level, assist);
+
+        Ok(MovementDiceResult {
+            base_roll: base_result,
+            level_modifier: modifiers.level,
+            terrain_modifier: modifiers.terrain,
+            danger_modifier: modifiers.danger,
+            gear_modifier: modifiers.gear,
+            assist_modifier: assist.clone(),
+            disadvantage,
+            total_modifier: modifiers.total,
+            final_result: modified_roll(base_result, modifiers.total),
+            dice_roll: base_dice,
+        })
+    }
 
+    /// Modifiers the movement d20 gets for a move onto `target_position`
+    fn roll_modifiers(
+        &self,
+        player: &Player,
+        map: &Map,
+        target_position: &Position3D,
+        player_level: u32,
+        assist: &DiceModifier,
+    ) -> RollModifiers {
         // Player level modifier (higher level = better outcomes)
-        let level_modifier = (player_level as i8 / 5).min(5); // +1 per 5 levels, max +5
-        total_modifier += level_modifier;
-
-        // Terrain modifier
-        let tile_coord = TileCoordinate::from(*target_position);
-        if let Some(tile) = map.get_tile(&tile_coord) {
-            let terrain_modifier = match tile.terrain_type {
-                crate::domain::value_objects::terrain::TerrainType::Plains => 2,
-                crate::domain::value_objects::terrain::TerrainType::Forest => 0,
-                crate::domain::value_objects::terrain::TerrainType::Mountains => -2,
-                crate::domain::value_objects::terrain::TerrainType::Desert => -1,
-                crate::domain::value_objects::terrain::TerrainType::Tundra => -3,
-                crate::domain::value_objects::terrain::TerrainType::Swamp => -4,
-                crate::domain::value_objects::terrain::TerrainType::Ocean => -5,
-                crate::domain::value_objects::terrain::TerrainType::Volcanic => -4,
-                crate::domain::value_objects::terrain::TerrainType::Anomaly => -6,
-                crate::domain::value_objects::terrain::TerrainType::Constructed => 3,
-                crate::domain::value_objects::terrain::TerrainType::Cave => -3,
-                crate::domain::value_objects::terrain::TerrainType::Crystal => 1,
-            };
-            total_modifier += terrain_modifier;
-        }
+        let level = (player_level as i8 / 5).min(5); // +1 per 5 levels, max +5
+
+        let terrain_type = map
+            .get_tile(&TileCoordinate::from(*target_position))
+            .map(|tile| tile.terrain_type);
+        let terrain = terrain_type.map(terrain_roll_modifier).unwrap_or(0);
+
+        // Tool gear steadies the player on its terrain
+        let gear = terrain_type
+            .map(|terrain_type| player.gear().footing_bonus(terrain_type))
+            .unwrap_or(0);
 
         // Danger level modifier (higher danger = worse outcomes but better rewards)
-        let danger_level = map.danger_level(target_position);
-        let danger_modifier = -(danger_level as i8 / 2); // Negative modifier for danger
-        total_modifier += danger_modifier;
+        let danger = -(map.danger_level(target_position) as i8 / 2);
 
-        // Apply modifier to roll
-        let modified_result = (base_result as i8 + total_modifier).max(1) as u8;
+        // Optional assist bonus (e.g. Fortune's Favor)
+        let total = level + terrain + gear + danger + assist.total_modifier() as i8;
 
-        Ok(MovementDiceResult {
-            base_roll: base_result as u8,
-            level_modifier,
-            terrain_modifier: if let Some(tile) =
-                map.get_tile(&TileCoordinate::from(*target_position))
-            {
-                match tile.terrain_type {
-                    crate::domain::value_objects::terrain::TerrainType::Plains => 2,
-                    crate::domain::value_objects::terrain::TerrainType::Forest => 0,
-                    crate::domain::value_objects::terrain::TerrainType::Mountains => -2,
-                    crate::domain::value_objects::terrain::TerrainType::Desert => -1,
-                    crate::domain::value_objects::terrain::TerrainType::Tundra => -3,
-                    crate::domain::value_objects::terrain::TerrainType::Swamp => -4,
-                    crate::domain::value_objects::terrain::TerrainType::Ocean => -5,
-                    crate::domain::value_objects::terrain::TerrainType::Volcanic => -4,
-                    crate::domain::value_objects::terrain::TerrainType::Anomaly => -6,
-                    crate::domain::value_objects::terrain::TerrainType::Constructed => 3,
-                    crate::domain::value_objects::terrain::TerrainType::Cave => -3,
-                    crate::domain::value_objects::terrain::TerrainType::Crystal => 1,
-                }
-            } else {
-                0
-            },
-            danger_modifier,
-            total_modifier,
-            final_result: modified_result,
-            dice_roll: base_dice,
+        RollModifiers {
+            level,
+            terrain,
+            gear,
+            danger,
+            total,
+        }
+    }
+
+    /// Work out what a move would cost and risk, without rolling
+    ///
+    /// Uses the same modifiers and event tables as an actual move, and never
+    /// touches a random number generator, so showing a preview cannot
+    /// change what the move then rolls. Tiles are not generated either.
+    pub fn preview_movement(
+        &self,
+        player: &Player,
+        target_position: Position3D,
+        map: &Map,
+        player_level: u32,
+        assist: &DiceModifier,
+        conditions: &MovementConditions,
+    ) -> DomainResult<MovementPreview> {
+        if !self.is_valid_movement(player.position(), &target_position) {
+            return Err(DomainError::InvalidMapCoordinates(
+                target_position.x,
+                target_position.y,
+                target_position.z,
+            ));
+        }
+        if !map.is_passable(&target_position) {
+            return Err(DomainError::TileNotAccessible(
+                target_position.x,
+                target_position.y,
+                target_position.z,
+            ));
+        }
+
+        let modifiers = self.roll_modifiers(player, map, &target_position, player_level, assist);
+        let event_chance = (1..=20u8)
+            .filter(|&base| {
+                self.can_trigger_event(modified_roll(base, modifiers.total), conditions)
+            })
+            .map(|base| d20_chance(base, conditions.disadvantage))
+            .sum();
+
+        Ok(MovementPreview {
+            target_position,
+            movement_cost: conditions.step_cost(map, &target_position),
+            event_chance,
+            danger_level: map.danger_level(&target_position),
+            total_modifier: modifiers.total,
+            disadvantage: conditions.disadvantage,
         })
     }
 
-    /// Generate an event based on dice roll result
-    fn generate_movement_event(
+    /// Check if a final roll of `result` leads to an event
+    fn can_trigger_event(&self, result: u8, conditions: &MovementConditions) -> bool {
+        if conditions.is_quiet(result) {
+            return false;
+        }
+        let flags = &conditions.flags;
+        self.event_templates
+            .get(&EventCategory::for_roll(result))
+            .is_some_and(|templates| {
+                templates.iter().any(|template| {
+                    template.flags.is_offered(flags)
+                        && conditions.reputation.event_weight(template.faction) as f32
+                            * weight_factor(&template.flags.weights, flags)
+                            > 0.0
+                })
+            })
+    }
+
+    /// Generate an event based on dice roll result, with the session flag
+    /// it marks when it happens and the faction whose flavour it is
+    #[allow(clippy::type_complexity)]
+    fn generate_movement_event<R: Rng + ?Sized>(
         &self,
         dice_result: &MovementDiceResult,
         position: &Position3D,
         _map: &Map,
         _player_level: u32,
-    ) -> DomainResult<Option<Event>> {
+        conditions: &MovementConditions,
+        rng: &mut R,
+    ) -> DomainResult<Option<(Event, Option<&'static str>, Option<Faction>)>> {
         let result = dice_result.final_result;
 
         // Determine event category based on dice result
-        let event_category = match result {
-            1..=3 => EventCategory::CriticalFailure,
-            4..=7 => EventCategory::Failure,
-            8..=12 => EventCategory::Neutral,
-            13..=16 => EventCategory::Success,
-            17..=19 => EventCategory::GreatSuccess,
-            20..=255 => EventCategory::CriticalSuccess,
-            0 => EventCategory::CriticalFailure, // Edge case for 0
-        };
+        let event_category = EventCategory::for_roll(result);
 
         // Some rolls don't trigger events (neutral outcomes)
-        if matches!(event_category, EventCategory::Neutral) && result >= 10 {
+        if conditions.is_quiet(result) {
             return Ok(None); // Safe movement, no event
         }
 
@@ -213,12 +389,15 @@ impl TileMovementService {
             DomainError::EventTriggerError("No event templates found".to_string())
         })?;
 
-        if templates.is_empty() {
+        let Some(template) = choose_template(
+            templates,
+            &conditions.reputation,
+            &conditions.flags,
+            conditions.phase,
+            rng,
+        ) else {
             return Ok(None);
-        }
-
-        let mut rng = rand::thread_rng();
-        let template = &templates[rng.gen_range(0..templates.len())];
+        };
 
         // Create event from template
         let event = Event::new(
@@ -228,7 +407,7 @@ impl TileMovementService {
             Some(*position),
         )?;
 
-        Ok(Some(event))
+        Ok(Some((event, template.flags.marks, template.faction)))
     }
 
     /// Initialize event templates for different categories
@@ -241,16 +420,22 @@ impl TileMovementService {
                     event_type: EventType::Hazard,
                     title: "Equipment Malfunction".to_string(),
                     description: "Your equipment suffers a critical failure!".to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
                 },
                 EventTemplate {
                     event_type: EventType::Combat,
                     title: "Ambush!".to_string(),
                     description: "Hostile entities emerge from the shadows!".to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
                 },
                 EventTemplate {
                     event_type: EventType::Hazard,
                     title: "Environmental Hazard".to_string(),
                     description: "The ground gives way beneath your feet!".to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
                 },
             ],
         );
@@ -264,16 +449,22 @@ impl TileMovementService {
                     title: "Minor Setback".to_string(),
                     description: "You encounter a minor obstacle that slows your progress."
                         .to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
                 },
                 EventTemplate {
                     event_type: EventType::Malfunction,
                     title: "Equipment Strain".to_string(),
                     description: "Your equipment shows signs of wear and tear.".to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
                 },
                 EventTemplate {
                     event_type: EventType::Combat,
                     title: "Hostile Encounter".to_string(),
                     description: "You spot dangerous creatures in the area.".to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
                 },
             ],
         );
@@ -286,12 +477,42 @@ impl TileMovementService {
                     event_type: EventType::Narrative,
                     title: "Quiet Exploration".to_string(),
                     description: "You move through the area without incident.".to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
                 },
                 EventTemplate {
                     event_type: EventType::Mystery,
                     title: "Strange Phenomenon".to_string(),
                     description: "You notice something unusual but can't quite identify what."
                         .to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
+                },
+                EventTemplate {
+                    event_type: EventType::Narrative,
+                    title: "Guild Chatter".to_string(),
+                    description: "Scavenger Guild crews swap salvage rumours on an open channel."
+                        .to_string(),
+                    faction: Some(Faction::ScavengerGuild),
+                    flags: FlagHooks::default(),
+                },
+                EventTemplate {
+                    event_type: EventType::Narrative,
+                    title: "Authority Patrol".to_string(),
+                    description:
+                        "A Colonial Authority cutter sweeps the area and logs your transponder."
+                            .to_string(),
+                    faction: Some(Faction::ColonialAuthority),
+                    flags: FlagHooks::default(),
+                },
+                EventTemplate {
+                    event_type: EventType::Narrative,
+                    title: "Trader Beacon".to_string(),
+                    description:
+                        "A Free Traders beacon broadcasts market prices from the spaceport."
+                            .to_string(),
+                    faction: Some(Faction::FreeTraders),
+                    flags: FlagHooks::default(),
                 },
             ],
         );
@@ -304,16 +525,47 @@ impl TileMovementService {
                     event_type: EventType::ResourceDiscovery,
                     title: "Resource Cache".to_string(),
                     description: "You discover a small cache of useful resources.".to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
                 },
                 EventTemplate {
                     event_type: EventType::Trade,
                     title: "Friendly Encounter".to_string(),
                     description: "You encounter a friendly trader willing to deal.".to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
+                },
+                EventTemplate {
+                    event_type: EventType::Trade,
+                    title: "Guild Salvage Barter".to_string(),
+                    description: "A Guild hauler offers to swap salvage, no questions asked."
+                        .to_string(),
+                    faction: Some(Faction::ScavengerGuild),
+                    flags: FlagHooks::default(),
+                },
+                EventTemplate {
+                    event_type: EventType::Trade,
+                    title: "Authority Supply Drop".to_string(),
+                    description:
+                        "A Colonial Authority depot sells licensed supplies at fair prices."
+                            .to_string(),
+                    faction: Some(Faction::ColonialAuthority),
+                    flags: FlagHooks::default(),
+                },
+                EventTemplate {
+                    event_type: EventType::Trade,
+                    title: "Free Trader Caravan".to_string(),
+                    description: "A Free Traders caravan drops out of its lane to haggle."
+                        .to_string(),
+                    faction: Some(Faction::FreeTraders),
+                    flags: FlagHooks::default(),
                 },
                 EventTemplate {
                     event_type: EventType::Boon,
                     title: "Favorable Conditions".to_string(),
                     description: "The environment provides unexpected advantages.".to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
                 },
             ],
         );
@@ -326,16 +578,22 @@ impl TileMovementService {
                     event_type: EventType::ResourceDiscovery,
                     title: "Rich Deposit".to_string(),
                     description: "You discover a rich vein of valuable resources!".to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
                 },
                 EventTemplate {
                     event_type: EventType::Boon,
                     title: "Ancient Technology".to_string(),
                     description: "You find remnants of advanced technology!".to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
                 },
                 EventTemplate {
                     event_type: EventType::Mystery,
                     title: "Hidden Knowledge".to_string(),
                     description: "You uncover secrets that expand your understanding.".to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
                 },
             ],
         );
@@ -348,19 +606,35 @@ impl TileMovementService {
                     event_type: EventType::ResourceDiscovery,
                     title: "Jackpot Discovery".to_string(),
                     description: "You strike it rich with an incredible resource find!".to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
                 },
                 EventTemplate {
                     event_type: EventType::Boon,
                     title: "Legendary Artifact".to_string(),
                     description: "You discover a powerful artifact of ancient origin!".to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
                 },
                 EventTemplate {
                     event_type: EventType::Trade,
                     title: "Exclusive Opportunity".to_string(),
                     description: "A rare trading opportunity presents itself!".to_string(),
+                    faction: None,
+                    flags: FlagHooks::default(),
                 },
             ],
         );
+
+        // Follow-ups of earlier choices, offered once their flags allow
+        for follow_up in FOLLOW_UP_EVENTS {
+            if let (Ok(template), Some(templates)) = (
+                follow_up.template(),
+                self.event_templates.get_mut(&follow_up.category),
+            ) {
+                templates.push(template);
+            }
+        }
     }
 }
 
@@ -374,10 +648,118 @@ impl Default for TileMovementService {
 #[derive(Debug, Clone, PartialEq)]
 pub struct MovementResult {
     pub success: bool,
+    /// Tile the player moved from, where a retreat leads back to
+    pub origin_position: Position3D,
     pub target_position: Position3D,
     pub movement_cost: u8,
     pub dice_result: MovementDiceResult,
     pub triggered_event: Option<Event>,
+    /// Session flag to set once the triggered event is applied
+    pub marks_flag: Option<&'static str>,
+    /// Faction whose flavour of the triggered event this is, if any
+    pub faction: Option<Faction>,
+}
+
+/// Conditions that change how a move resolves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MovementConditions {
+    /// Multiplier on the terrain's movement cost
+    pub cost_multiplier: u8,
+    /// Roll the movement d20 twice and keep the lower result
+    pub disadvantage: bool,
+    /// Faction standing; biases which flavour of an event is picked
+    pub reputation: Reputation,
+    /// Earlier choices; decide which follow-up events can turn up
+    pub flags: SessionFlags,
+    /// Movement points added to steps onto open terrain
+    pub open_terrain_surcharge: u8,
+    /// Quiet rolls, counted up from the lowest, that turn up an event instead
+    pub event_pressure: u8,
+    /// Day or night; weighs which kind of event turns up
+    pub phase: CalendarPhase,
+}
+
+impl Default for MovementConditions {
+    fn default() -> Self {
+        Self {
+            cost_multiplier: 1,
+            disadvantage: false,
+            reputation: Reputation::default(),
+            flags: SessionFlags::default(),
+            open_terrain_surcharge: 0,
+            event_pressure: 0,
+            phase: CalendarPhase::Day,
+        }
+    }
+}
+
+impl MovementConditions {
+    /// Movement cost of a tile under these conditions
+    pub fn apply_cost(&self, base_cost: u8) -> u8 {
+        base_cost.saturating_mul(self.cost_multiplier)
+    }
+
+    /// Movement cost of a step onto `target`, open ground surcharged
+    pub fn step_cost(&self, map: &Map, target: &Position3D) -> u8 {
+        let cost = self.apply_cost(map.movement_cost(target));
+        match map.terrain_at(target) {
+            Some(terrain) if terrain.is_open() => cost.saturating_add(self.open_terrain_surcharge),
+            _ => cost,
+        }
+    }
+
+    /// Check if a final roll of `result` passes without any event
+    pub fn is_quiet(&self, result: u8) -> bool {
+        matches!(EventCategory::for_roll(result), EventCategory::Neutral)
+            && result >= QUIET_ROLL.saturating_add(self.event_pressure)
+    }
+}
+
+/// What a move would cost and risk, worked out before it is made
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovementPreview {
+    pub target_position: Position3D,
+    pub movement_cost: u8,
+    /// Chance the move triggers an event, from 0 to 1
+    pub event_chance: f32,
+    /// Danger level of the target terrain
+    pub danger_level: u8,
+    /// Sum of every modifier the movement d20 would get
+    pub total_modifier: i8,
+    /// The d20 would be rolled twice, keeping the lower result
+    pub disadvantage: bool,
+}
+
+impl MovementPreview {
+    /// Threat tier of the target tile
+    pub fn threat_tier(&self) -> &'static str {
+        match self.danger_level {
+            0..=2 => "Low",
+            3..=5 => "Moderate",
+            6..=8 => "High",
+            _ => "Extreme",
+        }
+    }
+
+    /// Event chance as a whole percentage
+    pub fn event_percent(&self) -> u32 {
+        (self.event_chance * 100.0).round() as u32
+    }
+
+    /// One line for overlays, e.g. `2 MP | 45% event | Threat: High | Roll -3`
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} MP | {}% event | Threat: {} | Roll {:+}",
+            self.movement_cost,
+            self.event_percent(),
+            self.threat_tier(),
+            self.total_modifier
+        );
+        if self.disadvantage {
+            summary.push_str(" | Disadvantage");
+        }
+        summary
+    }
 }
 
 /// Detailed result of movement dice roll
@@ -387,6 +769,11 @@ pub struct MovementDiceResult {
     pub level_modifier: i8,
     pub terrain_modifier: i8,
     pub danger_modifier: i8,
+    /// Bonus from the equipped tool on the target terrain
+    pub gear_modifier: i8,
+    pub assist_modifier: DiceModifier,
+    /// The base roll was the lower of two
+    pub disadvantage: bool,
     pub total_modifier: i8,
     pub final_result: u8,
     pub dice_roll: DiceRoll,
@@ -395,8 +782,8 @@ pub struct MovementDiceResult {
 impl MovementDiceResult {
     /// Get a formatted description of the dice roll
     pub fn description(&self) -> String {
-        format!(
-            "🎲 Rolled {} + {} = {} (Base: {}, Level: {:+}, Terrain: {:+}, Danger: {:+})",
+        let mut description = format!(
+            "🎲 Rolled {} + {} = {} (Base: {}, Level: {:+}, Terrain: {:+}, Danger: {:+}",
             self.base_roll,
             self.total_modifier,
             self.final_result,
@@ -404,7 +791,28 @@ impl MovementDiceResult {
             self.level_modifier,
             self.terrain_modifier,
             self.danger_modifier
-        )
+        );
+        if self.gear_modifier != 0 {
+            description.push_str(&format!(", Gear: {:+}", self.gear_modifier));
+        }
+        // Assists are only named when the player asked to see them
+        if let Some(label) = self.assist_modifier.source_label() {
+            description.push_str(&format!(", {}", label));
+        }
+        if self.disadvantage {
+            description.push_str(", Disadvantage");
+        }
+        description.push(')');
+        description
+    }
+
+    /// Classify the roll for Fortune's Favor tracking
+    pub fn roll_outcome(&self) -> RollOutcome {
+        match self.final_result {
+            0..=7 => RollOutcome::Failure,
+            8..=12 => RollOutcome::Neutral,
+            13..=255 => RollOutcome::Success,
+        }
     }
 
     /// Get the outcome category as a string
@@ -421,8 +829,175 @@ impl MovementDiceResult {
     }
 }
 
+/// Grace period that suppresses hostile events at the start of a new session
+///
+/// Each movement consumes one grace move; while any remain, Hazard and Combat
+/// events are dropped so a new player's first steps cannot go badly wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventGrace {
+    moves_remaining: u8,
+}
+
+impl EventGrace {
+    /// Create a grace period lasting the given number of moves
+    pub fn new(moves: u8) -> Self {
+        Self {
+            moves_remaining: moves,
+        }
+    }
+
+    /// No grace period, used for loaded or continued sessions
+    pub fn expired() -> Self {
+        Self::new(0)
+    }
+
+    /// Number of moves still covered by the grace period
+    pub fn moves_remaining(&self) -> u8 {
+        self.moves_remaining
+    }
+
+    /// Check if the grace period is still running
+    pub fn is_active(&self) -> bool {
+        self.moves_remaining > 0
+    }
+
+    /// Consume one move of grace, dropping hostile events while active
+    pub fn filter_event(&mut self, event: Option<Event>) -> Option<Event> {
+        if !self.is_active() {
+            return event;
+        }
+
+        self.moves_remaining -= 1;
+        event.filter(|event| !matches!(event.event_type(), EventType::Hazard | EventType::Combat))
+    }
+}
+
+impl Default for EventGrace {
+    fn default() -> Self {
+        Self::new(crate::domain::constants::NEW_SESSION_GRACE_MOVES)
+    }
+}
+
+/// Outcome of a meaningful roll as tracked by Fortune's Favor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollOutcome {
+    Failure,
+    Neutral,
+    Success,
+}
+
+impl From<SuccessLevel> for RollOutcome {
+    fn from(level: SuccessLevel) -> Self {
+        if level.is_success() {
+            RollOutcome::Success
+        } else {
+            RollOutcome::Failure
+        }
+    }
+}
+
+/// Optional "Fortune's Favor" assist that softens streaks of bad luck
+///
+/// Tracks the last few meaningful roll outcomes. A window with enough
+/// failures and no successes grants a small hidden bonus to the next roll,
+/// which escalates on longer streaks; any success clears the window. The
+/// assist is off by default and can never be enabled for daily runs.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FortuneFavor {
+    enabled: bool,
+    show_assist: bool,
+    daily_mode: bool,
+    window: VecDeque<RollOutcome>,
+}
+
+impl FortuneFavor {
+    /// Label shown on the roll when the assist is visible
+    pub const SOURCE: &'static str = "Fortune's Favor";
+
+    /// Create a disabled assist for a regular run
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an assist for a daily run, which can never be enabled
+    pub fn for_daily_run() -> Self {
+        Self {
+            daily_mode: true,
+            ..Self::default()
+        }
+    }
+
+    /// Check if the assist is turned on
+    pub fn is_enabled(&self) -> bool {
+        self.enabled && !self.daily_mode
+    }
+
+    /// Turn the assist on or off; enabling is refused in daily mode
+    pub fn set_enabled(&mut self, enabled: bool) -> DomainResult<()> {
+        if enabled && self.daily_mode {
+            return Err(DomainError::InvalidGameState(
+                "Fortune's Favor is not available in daily mode".to_string(),
+            ));
+        }
+        self.enabled = enabled;
+        Ok(())
+    }
+
+    /// Check if assisted rolls are labelled for the player
+    pub fn show_assist(&self) -> bool {
+        self.show_assist
+    }
+
+    /// Label assisted rolls instead of applying the bonus silently
+    pub fn set_show_assist(&mut self, show: bool) {
+        self.show_assist = show;
+    }
+
+    /// Number of failures in the current window
+    pub fn failures(&self) -> usize {
+        self.window
+            .iter()
+            .filter(|outcome| **outcome == RollOutcome::Failure)
+            .count()
+    }
+
+    /// Bonus the next roll receives, zero when the assist is off
+    pub fn pending_bonus(&self) -> i8 {
+        use crate::domain::constants::*;
+
+        // Successes clear the window, so any tracked streak is all misses
+        if !self.is_enabled() {
+            return 0;
+        }
+        match self.failures() {
+            n if n >= FORTUNE_ESCALATION_FAILURES => FORTUNE_ESCALATED_BONUS,
+            n if n >= FORTUNE_MIN_FAILURES => FORTUNE_BASE_BONUS,
+            _ => 0,
+        }
+    }
+
+    /// Modifier to apply to the next roll
+    pub fn modifier(&self) -> DomainResult<DiceModifier> {
+        DiceModifier::builder()
+            .assist(self.pending_bonus(), Self::SOURCE, self.show_assist)
+            .build()
+    }
+
+    /// Record the outcome of a meaningful roll
+    pub fn record_outcome(&mut self, outcome: RollOutcome) {
+        if outcome == RollOutcome::Success {
+            self.window.clear();
+            return;
+        }
+        self.window.push_back(outcome);
+        while self.window.len() > crate::domain::constants::FORTUNE_WINDOW_SIZE {
+            self.window.pop_front();
+        }
+    }
+}
+
 /// Categories of events based on dice roll results
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EventCategory {
     CriticalFailure,
     Failure,
@@ -432,12 +1007,175 @@ pub enum EventCategory {
     CriticalSuccess,
 }
 
+impl EventCategory {
+    /// Category of a final movement roll
+    pub fn for_roll(result: u8) -> Self {
+        match result {
+            1..=3 => EventCategory::CriticalFailure,
+            4..=7 => EventCategory::Failure,
+            8..=12 => EventCategory::Neutral,
+            13..=16 => EventCategory::Success,
+            17..=19 => EventCategory::GreatSuccess,
+            20..=255 => EventCategory::CriticalSuccess,
+            0 => EventCategory::CriticalFailure, // Edge case for 0
+        }
+    }
+}
+
+/// Modifiers on a movement roll, by source
+struct RollModifiers {
+    level: i8,
+    terrain: i8,
+    gear: i8,
+    danger: i8,
+    total: i8,
+}
+
+/// Movement roll modifier of a terrain
+fn terrain_roll_modifier(terrain_type: TerrainType) -> i8 {
+    match terrain_type {
+        TerrainType::Plains => 2,
+        TerrainType::Forest => 0,
+        TerrainType::Mountains => -2,
+        TerrainType::Desert => -1,
+        TerrainType::Tundra => -3,
+        TerrainType::Swamp => -4,
+        TerrainType::Ocean => -5,
+        TerrainType::Volcanic => -4,
+        TerrainType::Anomaly => -6,
+        TerrainType::Constructed => 3,
+        TerrainType::Cave => -3,
+        TerrainType::Crystal => 1,
+    }
+}
+
+/// Final result of a d20 showing `base` with `total_modifier` applied
+fn modified_roll(base: u8, total_modifier: i8) -> u8 {
+    (base as i16 + total_modifier as i16).clamp(1, u8::MAX as i16) as u8
+}
+
+/// Chance the movement d20 shows `face`, keeping the lower of two rolls
+/// under disadvantage
+fn d20_chance(face: u8, disadvantage: bool) -> f32 {
+    if !disadvantage {
+        return 1.0 / 20.0;
+    }
+    let at_least = |face: u8| (21 - face as u32).pow(2);
+    (at_least(face) - at_least(face + 1)) as f32 / 400.0
+}
+
 /// Template for generating events
 #[derive(Debug, Clone, PartialEq)]
 struct EventTemplate {
     event_type: EventType,
     title: String,
     description: String,
+    /// Faction whose flavour of the event this is, if any
+    faction: Option<Faction>,
+    /// Session flags the event depends on
+    flags: FlagHooks,
+}
+
+/// How an event template depends on the session flags
+#[derive(Debug, Clone, Default, PartialEq)]
+struct FlagHooks {
+    /// Only offered while this holds
+    requires: Option<FlagCondition>,
+    /// Weight factors applied while their conditions hold
+    weights: Vec<FlagWeight>,
+    /// Flag set when the event happens; it is not offered again after that
+    marks: Option<&'static str>,
+}
+
+impl FlagHooks {
+    fn is_offered(&self, flags: &SessionFlags) -> bool {
+        self.marks.is_none_or(|key| !flags.is_set(key))
+            && self
+                .requires
+                .as_ref()
+                .is_none_or(|condition| condition.holds(flags))
+    }
+}
+
+/// A follow-up event as written in the event data
+struct FollowUpEvent {
+    category: EventCategory,
+    event_type: EventType,
+    title: &'static str,
+    description: &'static str,
+    /// Flag condition, see [`crate::domain::services::session_flags`]
+    requires: &'static str,
+    /// Weight rules such as `x2 if bribes_paid >= 3`
+    weights: &'static [&'static str],
+    marks: Option<&'static str>,
+}
+
+impl FollowUpEvent {
+    fn template(&self) -> DomainResult<EventTemplate> {
+        Ok(EventTemplate {
+            event_type: self.event_type,
+            title: self.title.to_string(),
+            description: self.description.to_string(),
+            faction: None,
+            flags: FlagHooks {
+                requires: Some(self.requires.parse()?),
+                weights: self
+                    .weights
+                    .iter()
+                    .map(|weight| weight.parse())
+                    .collect::<DomainResult<_>>()?,
+                marks: self.marks,
+            },
+        })
+    }
+}
+
+/// Events that echo how earlier hostile contacts were settled
+const FOLLOW_UP_EVENTS: &[FollowUpEvent] = &[
+    FollowUpEvent {
+        category: EventCategory::Success,
+        event_type: EventType::Boon,
+        title: "Scavenger's Gift",
+        description: "The scavenger crew you let go hails you and drops a crate of salvage \
+                      at your feet. Debts are remembered out here.",
+        requires: "spared_scavenger",
+        weights: &["x3 if bribes_paid >= 3"],
+        marks: Some("scavenger_gift_received"),
+    },
+    FollowUpEvent {
+        category: EventCategory::Failure,
+        event_type: EventType::Combat,
+        title: "Scavenger Ambush",
+        description: "The raiders you paid off are back, and they brought friends. \
+                      Word is you are an easy mark.",
+        requires: "bribes_paid >= 2 & raiders_driven_off < 2",
+        weights: &["x2 if bribes_paid >= 4"],
+        marks: None,
+    },
+];
+
+/// Pick a template, favouring the flavours of factions the player stands
+/// well with and the kinds of event the time of day brings; templates whose
+/// flag conditions fail are left out
+fn choose_template<'a, R: Rng + ?Sized>(
+    templates: &'a [EventTemplate],
+    reputation: &Reputation,
+    flags: &SessionFlags,
+    phase: CalendarPhase,
+    rng: &mut R,
+) -> Option<&'a EventTemplate> {
+    let offered: Vec<&EventTemplate> = templates
+        .iter()
+        .filter(|template| template.flags.is_offered(flags))
+        .collect();
+    offered
+        .choose_weighted(rng, |template| {
+            reputation.event_weight(template.faction) as f32
+                * weight_factor(&template.flags.weights, flags)
+                * phase.event_weight(template.event_type)
+        })
+        .ok()
+        .copied()
 }
 
 #[cfg(test)]
@@ -499,7 +1237,15 @@ mod tests {
         let target = Position3D::new(1, 0, 0);
 
         let result = service
-            .roll_movement_dice(&player, &map, &target, 1)
+            .roll_movement_dice(
+                &player,
+                &map,
+                &target,
+                1,
+                &DiceModifier::none(),
+                false,
+                &mut rand::thread_rng(),
+            )
             .unwrap();
 
         assert!(result.base_roll >= 1 && result.base_roll <= 20);
@@ -540,6 +1286,9 @@ mod tests {
             level_modifier: 2,
             terrain_modifier: 1,
             danger_modifier: -1,
+            gear_modifier: 0,
+            assist_modifier: DiceModifier::none(),
+            disadvantage: false,
             total_modifier: 2,
             final_result: 17,
             dice_roll,
@@ -560,6 +1309,9 @@ mod tests {
             level_modifier: 0,
             terrain_modifier: 0,
             danger_modifier: 0,
+            gear_modifier: 0,
+            assist_modifier: DiceModifier::none(),
+            disadvantage: false,
             total_modifier: 0,
             final_result: 1,
             dice_roll: dice_roll.clone(),
@@ -571,10 +1323,482 @@ mod tests {
             level_modifier: 0,
             terrain_modifier: 0,
             danger_modifier: 0,
+            gear_modifier: 0,
+            assist_modifier: DiceModifier::none(),
+            disadvantage: false,
             total_modifier: 0,
             final_result: 20,
             dice_roll,
         };
         assert_eq!(critical_success.outcome_category(), "Critical Success");
     }
+
+    fn test_event(event_type: EventType) -> Option<Event> {
+        Some(
+            Event::new(
+                event_type,
+                "Test".to_string(),
+                "Test event".to_string(),
+                Some(Position3D::origin()),
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn event_grace_suppresses_hostile_events() {
+        let mut grace = EventGrace::new(3);
+
+        assert!(grace.filter_event(test_event(EventType::Hazard)).is_none());
+        assert!(grace.filter_event(test_event(EventType::Combat)).is_none());
+        assert!(grace
+            .filter_event(test_event(EventType::ResourceDiscovery))
+            .is_some());
+    }
+
+    #[test]
+    fn event_grace_decrements_and_expires() {
+        let mut grace = EventGrace::default();
+        assert_eq!(
+            grace.moves_remaining(),
+            crate::domain::constants::NEW_SESSION_GRACE_MOVES
+        );
+
+        for expected in (0..crate::domain::constants::NEW_SESSION_GRACE_MOVES).rev() {
+            assert!(grace.is_active());
+            grace.filter_event(None);
+            assert_eq!(grace.moves_remaining(), expected);
+        }
+
+        assert!(!grace.is_active());
+        assert!(grace.filter_event(test_event(EventType::Combat)).is_some());
+        assert_eq!(grace.moves_remaining(), 0);
+    }
+
+    #[test]
+    fn expired_grace_passes_everything() {
+        let mut grace = EventGrace::expired();
+        assert!(grace.filter_event(test_event(EventType::Hazard)).is_some());
+    }
+
+    fn enabled_fortune() -> FortuneFavor {
+        let mut fortune = FortuneFavor::new();
+        fortune.set_enabled(true).unwrap();
+        fortune
+    }
+
+    #[test]
+    fn fortune_favor_is_off_by_default() {
+        let mut fortune = FortuneFavor::new();
+        for _ in 0..8 {
+            fortune.record_outcome(RollOutcome::Failure);
+        }
+        assert!(!fortune.is_enabled());
+        assert_eq!(fortune.pending_bonus(), 0);
+    }
+
+    #[test]
+    fn fortune_favor_window_keeps_recent_outcomes() {
+        let mut fortune = enabled_fortune();
+        for _ in 0..3 {
+            fortune.record_outcome(RollOutcome::Failure);
+        }
+        for _ in 0..crate::domain::constants::FORTUNE_WINDOW_SIZE {
+            fortune.record_outcome(RollOutcome::Neutral);
+        }
+
+        // Old failures fell out of the window
+        assert_eq!(fortune.failures(), 0);
+        fortune.record_outcome(RollOutcome::Failure);
+        assert_eq!(fortune.failures(), 1);
+    }
+
+    #[test]
+    fn fortune_favor_escalates_and_resets() {
+        let mut fortune = enabled_fortune();
+        for _ in 0..3 {
+            fortune.record_outcome(RollOutcome::Failure);
+        }
+        assert_eq!(fortune.pending_bonus(), 0);
+
+        fortune.record_outcome(RollOutcome::Failure);
+        assert_eq!(fortune.pending_bonus(), 1);
+
+        fortune.record_outcome(RollOutcome::Neutral);
+        fortune.record_outcome(RollOutcome::Failure);
+        fortune.record_outcome(RollOutcome::Failure);
+        assert_eq!(fortune.pending_bonus(), 2);
+
+        fortune.record_outcome(SuccessLevel::Success.into());
+        assert_eq!(fortune.failures(), 0);
+        assert_eq!(fortune.pending_bonus(), 0);
+    }
+
+    #[test]
+    fn fortune_favor_modifier_tags_only_when_shown() {
+        let mut fortune = enabled_fortune();
+        for _ in 0..4 {
+            fortune.record_outcome(RollOutcome::Failure);
+        }
+
+        let hidden = fortune.modifier().unwrap();
+        assert_eq!(hidden.total_modifier(), 1);
+        assert_eq!(hidden.source_label(), None);
+
+        fortune.set_show_assist(true);
+        let shown = fortune.modifier().unwrap();
+        assert_eq!(shown.source_label().as_deref(), Some("Fortune's Favor +1"));
+    }
+
+    #[test]
+    fn fortune_favor_cannot_be_enabled_in_daily_mode() {
+        let mut fortune = FortuneFavor::for_daily_run();
+        assert!(fortune.set_enabled(true).is_err());
+        for _ in 0..8 {
+            fortune.record_outcome(RollOutcome::Failure);
+        }
+        assert!(!fortune.is_enabled());
+        assert_eq!(fortune.pending_bonus(), 0);
+        assert!(fortune.set_enabled(false).is_ok());
+    }
+
+    #[test]
+    fn assist_modifier_raises_movement_roll() {
+        let service = TileMovementService::new();
+        let player = create_test_player();
+        let map = create_test_map();
+        let target = Position3D::new(1, 0, 0);
+
+        let plain = service
+            .roll_movement_dice(
+                &player,
+                &map,
+                &target,
+                1,
+                &DiceModifier::none(),
+                false,
+                &mut rand::thread_rng(),
+            )
+            .unwrap();
+        let assist = DiceModifier::builder()
+            .assist(2, FortuneFavor::SOURCE, true)
+            .build()
+            .unwrap();
+        let assisted = service
+            .roll_movement_dice(
+                &player,
+                &map,
+                &target,
+                1,
+                &assist,
+                false,
+                &mut rand::thread_rng(),
+            )
+            .unwrap();
+
+        assert_eq!(assisted.total_modifier, plain.total_modifier + 2);
+        assert!(assisted.description().contains("Fortune's Favor +2"));
+    }
+
+    #[test]
+    fn equipped_tool_steadies_moves_on_its_terrain() {
+        use crate::domain::services::gear::{GearBonus, GearItem, GearRarity};
+
+        let service = TileMovementService::new();
+        let mut player = create_test_player();
+        let mut map = create_test_map();
+        let swamp = Position3D::new(1, 0, 0);
+        map.set_tile(
+            TileCoordinate::from(swamp),
+            MapTile::new(TerrainType::Swamp, Elevation::sea_level(), false),
+        );
+        let plains = Position3D::new(0, 1, 0);
+
+        let roll = |player: &Player, target: &Position3D| {
+            service
+                .roll_movement_dice(
+                    player,
+                    &map,
+                    target,
+                    1,
+                    &DiceModifier::none(),
+                    false,
+                    &mut rand::thread_rng(),
+                )
+                .unwrap()
+        };
+        let bare = roll(&player, &swamp);
+        assert_eq!(bare.gear_modifier, 0);
+
+        let crampons = player.stow_gear(GearItem::new(
+            GearBonus::Footing(TerrainType::Swamp),
+            GearRarity::Rare,
+        ));
+        assert_eq!(roll(&player, &swamp).gear_modifier, 0);
+        player.equip_gear(crampons).unwrap();
+
+        let geared = roll(&player, &swamp);
+        assert_eq!(geared.gear_modifier, 2);
+        assert_eq!(geared.total_modifier, bare.total_modifier + 2);
+        assert!(geared.description().contains("Gear: +2"));
+        assert_eq!(roll(&player, &plains).gear_modifier, 0);
+    }
+
+    #[test]
+    fn faction_standing_biases_event_flavour() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let service = TileMovementService::new();
+        let templates = &service.event_templates[&EventCategory::Success];
+        let guild_picks = |reputation: &Reputation| {
+            let mut rng = StdRng::seed_from_u64(1908);
+            (0..2000)
+                .filter(|_| {
+                    choose_template(
+                        templates,
+                        reputation,
+                        &SessionFlags::new(),
+                        CalendarPhase::Day,
+                        &mut rng,
+                    )
+                    .unwrap()
+                    .faction
+                        == Some(Faction::ScavengerGuild)
+                })
+                .count()
+        };
+
+        let neutral = guild_picks(&Reputation::new());
+        let mut allied = Reputation::new();
+        allied.adjust(Faction::ScavengerGuild, 80, 1.0);
+        let mut hostile = Reputation::new();
+        hostile.adjust(Faction::ScavengerGuild, -80, 1.0);
+
+        // One of six equally weighted templates
+        assert!((250..420).contains(&neutral), "neutral {}", neutral);
+        // The rival Authority swings the other way: 4 of 13 allied, 1 of 13 hostile
+        assert!(guild_picks(&allied) > neutral + 150);
+        assert!(guild_picks(&hostile) < neutral - 100);
+        assert!(choose_template(
+            &[],
+            &allied,
+            &SessionFlags::new(),
+            CalendarPhase::Day,
+            &mut StdRng::seed_from_u64(1)
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn follow_up_events_wait_for_their_flags() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        assert!(FOLLOW_UP_EVENTS
+            .iter()
+            .all(|follow_up| follow_up.template().is_ok()));
+
+        let service = TileMovementService::new();
+        let templates = &service.event_templates[&EventCategory::Success];
+        let reputation = Reputation::new();
+        let gifts = |flags: &SessionFlags| {
+            let mut rng = StdRng::seed_from_u64(1916);
+            (0..2000)
+                .filter(|_| {
+                    choose_template(templates, &reputation, flags, CalendarPhase::Day, &mut rng)
+                        .unwrap()
+                        .title
+                        == "Scavenger's Gift"
+                })
+                .count()
+        };
+
+        let mut flags = SessionFlags::new();
+        assert_eq!(gifts(&flags), 0);
+        flags.set_flag(crate::domain::constants::FLAG_SPARED_SCAVENGER);
+        let spared = gifts(&flags);
+        // One of seven equally weighted templates, then three of nine
+        assert!((200..380).contains(&spared), "spared {}", spared);
+        flags.increment(crate::domain::constants::FLAG_BRIBES_PAID, 3);
+        assert!(gifts(&flags) > spared + 250);
+        flags.set_flag("scavenger_gift_received");
+        assert_eq!(gifts(&flags), 0);
+    }
+
+    #[test]
+    fn night_shifts_events_towards_danger_and_away_from_finds() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let service = TileMovementService::new();
+        let picks = |category: EventCategory, phase: CalendarPhase, wanted: &[EventType]| {
+            let templates = &service.event_templates[&category];
+            let mut rng = StdRng::seed_from_u64(2012);
+            (0..2000)
+                .filter(|_| {
+                    let template = choose_template(
+                        templates,
+                        &Reputation::new(),
+                        &SessionFlags::new(),
+                        phase,
+                        &mut rng,
+                    )
+                    .unwrap();
+                    wanted.contains(&template.event_type)
+                })
+                .count()
+        };
+
+        // Two of three failures are hostile by day, four of five at night
+        let hostile = [EventType::Hazard, EventType::Combat];
+        let by_day = picks(EventCategory::Failure, CalendarPhase::Day, &hostile);
+        let at_night = picks(EventCategory::Failure, CalendarPhase::Night, &hostile);
+        assert!(at_night > by_day + 200, "{} vs {}", at_night, by_day);
+
+        let finds = [EventType::ResourceDiscovery];
+        let by_day = picks(EventCategory::Success, CalendarPhase::Day, &finds);
+        let at_night = picks(EventCategory::Success, CalendarPhase::Night, &finds);
+        assert!(by_day > at_night + 100, "{} vs {}", by_day, at_night);
+    }
+
+    #[test]
+    fn preview_matches_what_the_move_rolls() {
+        let service = TileMovementService::new();
+        let player = create_test_player();
+        let mut map = create_test_map();
+        let target = Position3D::new(1, 0, 0);
+        map.set_tile(
+            TileCoordinate::from(target),
+            MapTile::new(TerrainType::Mountains, Elevation::sea_level(), false),
+        );
+        let assist = DiceModifier::situational(-2).unwrap();
+
+        for disadvantage in [false, true] {
+            let conditions = MovementConditions {
+                cost_multiplier: 2,
+                disadvantage,
+                ..MovementConditions::default()
+            };
+            let preview = service
+                .preview_movement(&player, target, &map, 7, &assist, &conditions)
+                .unwrap();
+            let rolled = service
+                .roll_movement_dice(
+                    &player,
+                    &map,
+                    &target,
+                    7,
+                    &assist,
+                    disadvantage,
+                    &mut rand::thread_rng(),
+                )
+                .unwrap();
+            assert_eq!(preview.total_modifier, rolled.total_modifier);
+            assert_eq!(preview.movement_cost, conditions.step_cost(&map, &target));
+
+            // Every face the d20 can show, weighted as the move weighs it
+            let mut expected = 0.0;
+            for face in 1..=20u8 {
+                let dice_result = MovementDiceResult {
+                    base_roll: face,
+                    final_result: modified_roll(face, rolled.total_modifier),
+                    ..rolled.clone()
+                };
+                let event = service
+                    .generate_movement_event(
+                        &dice_result,
+                        &target,
+                        &map,
+                        7,
+                        &conditions,
+                        &mut rand::thread_rng(),
+                    )
+                    .unwrap();
+                if event.is_some() {
+                    expected += d20_chance(face, disadvantage);
+                }
+            }
+            assert!((preview.event_chance - expected).abs() < 1e-6);
+        }
+        let total: f32 = (1..=20).map(|face| d20_chance(face, true)).sum();
+        assert!((total - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn season_conditions_surcharge_open_ground_and_stir_up_events() {
+        let mut map = create_test_map();
+        let forest = Position3D::new(0, 1, 0);
+        map.set_tile(
+            TileCoordinate::from(forest),
+            MapTile::new(TerrainType::Forest, Elevation::sea_level(), false),
+        );
+        let plains = Position3D::new(1, 0, 0);
+        let drift = MovementConditions {
+            open_terrain_surcharge: 1,
+            ..MovementConditions::default()
+        };
+        assert_eq!(
+            drift.step_cost(&map, &plains),
+            map.movement_cost(&plains) + 1
+        );
+        assert_eq!(drift.step_cost(&map, &forest), map.movement_cost(&forest));
+
+        let calm = MovementConditions::default();
+        let surge = MovementConditions {
+            event_pressure: 2,
+            ..MovementConditions::default()
+        };
+        let quiet = |conditions: &MovementConditions| {
+            (1..=20)
+                .filter(|&roll| conditions.is_quiet(roll))
+                .collect::<Vec<u8>>()
+        };
+        assert_eq!(quiet(&calm), vec![10, 11, 12]);
+        assert_eq!(quiet(&surge), vec![12]);
+
+        let service = TileMovementService::new();
+        let player = create_test_player();
+        let chance = |conditions: &MovementConditions| {
+            service
+                .preview_movement(&player, plains, &map, 1, &DiceModifier::none(), conditions)
+                .unwrap()
+                .event_chance
+        };
+        assert!(chance(&surge) > chance(&calm));
+    }
+
+    #[test]
+    fn previews_never_draw_random_numbers() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let service = TileMovementService::new();
+        let player = create_test_player();
+        let map = create_test_map();
+        let target = Position3D::new(0, 1, 0);
+        let conditions = MovementConditions::default();
+        let roll = |rng: &mut StdRng| {
+            service
+                .roll_movement_dice(&player, &map, &target, 1, &DiceModifier::none(), false, rng)
+                .unwrap()
+                .base_roll
+        };
+
+        let mut untouched = StdRng::seed_from_u64(1933);
+        let mut previewed = StdRng::seed_from_u64(1933);
+        let first = service
+            .preview_movement(&player, target, &map, 1, &DiceModifier::none(), &conditions)
+            .unwrap();
+        let rolls: Vec<u8> = (0..20).map(|_| roll(&mut untouched)).collect();
+        let mut previewed_rolls = Vec::new();
+        for _ in 0..20 {
+            let again = service
+                .preview_movement(&player, target, &map, 1, &DiceModifier::none(), &conditions)
+                .unwrap();
+            assert_eq!(again, first);
+            previewed_rolls.push(roll(&mut previewed));
+        }
+        assert_eq!(previewed_rolls, rolls);
+    }
 }
@@ -0,0 +1,401 @@
+//! Tile Staleness - Explored ground that changes while nobody looks
+//!
+//! Every explored tile remembers the day the player last saw it. Once that
+//! is more than `stale_after_days` ago and the tile lies outside the
+//! protected radius around the base, the tile is stale: the scanner shows it
+//! faded, and when the player re-enters its chunk a refresh pass may reroll
+//! the tile's minor terrain variation and let resource nodes regrow or shift
+//! within the chunk. Pinned tiles, which hold hand-shaped ground, ruin
+//! entrances and anchored positions such as quest objectives and expedition
+//! waypoints are never touched. Every roll depends only on the map seed, the
+//! tile and the day of the refresh, so a replay refreshes identically.
+
+use crate::domain::constants::{
+    MAP_CHUNK_SIZE, STALE_NODE_SHIFT_PERCENT, STALE_PROTECTED_BASE_RADIUS,
+    STALE_TERRAIN_REROLL_PERCENT,
+};
+use crate::domain::entities::map::{Map, MapTile};
+use crate::domain::services::{InteriorGenerator, MapService};
+use crate::domain::value_objects::position::ChunkCoordinate;
+use crate::domain::value_objects::{Position3D, TileCoordinate};
+use std::collections::HashSet;
+
+/// Salt for terrain rolls, so they differ from node rolls on the same tile
+const TERRAIN_SALT: u64 = 0x7465_7272_6169_6e00;
+/// Salt for resource node rolls
+const NODE_SALT: u64 = 0x6e6f_6465_7300_0000;
+
+/// When explored tiles turn stale
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StalenessRules {
+    /// Days a tile can go unseen before it turns stale
+    pub stale_after_days: u32,
+    /// Base the protected radius is measured from, if any
+    pub base: Option<Position3D>,
+    /// Tiles this close to the base never turn stale
+    pub protected_radius: u32,
+}
+
+impl StalenessRules {
+    /// Rules with the default protected radius around `base`
+    pub fn new(stale_after_days: u32, base: Option<Position3D>) -> Self {
+        Self {
+            stale_after_days,
+            base,
+            protected_radius: STALE_PROTECTED_BASE_RADIUS,
+        }
+    }
+}
+
+/// What a refresh pass changed in a chunk
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StaleRefresh {
+    /// Stale tiles the pass looked at
+    pub tiles_refreshed: u32,
+    /// Tiles whose terrain or elevation changed
+    pub tiles_rerolled: u32,
+    /// Harvested nodes that grew back to capacity
+    pub nodes_regrown: u32,
+    /// Untouched nodes that moved to another tile of the chunk
+    pub nodes_shifted: u32,
+}
+
+impl StaleRefresh {
+    /// Check if anything visible changed
+    pub fn changed_anything(&self) -> bool {
+        self.tiles_rerolled + self.nodes_regrown + self.nodes_shifted > 0
+    }
+}
+
+/// Chunk holding a tile, as used for refresh passes
+pub fn chunk_of(coordinate: TileCoordinate) -> ChunkCoordinate {
+    coordinate.to_chunk_coordinate(MAP_CHUNK_SIZE as i32)
+}
+
+/// Service deciding which tiles are stale and refreshing them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileStalenessService {
+    rules: StalenessRules,
+}
+
+impl TileStalenessService {
+    /// Create a service for the given rules
+    pub fn new(rules: StalenessRules) -> Self {
+        Self { rules }
+    }
+
+    /// Rules in effect
+    pub fn rules(&self) -> &StalenessRules {
+        &self.rules
+    }
+
+    /// Check if a tile lies in the protected radius around the base
+    pub fn is_near_base(&self, coordinate: TileCoordinate) -> bool {
+        self.rules.base.is_some_and(|base| {
+            Position3D::from(coordinate).manhattan_distance_2d(&base) <= self.rules.protected_radius
+        })
+    }
+
+    /// Check if an explored tile has gone unseen for too long on `day`
+    pub fn is_stale(&self, coordinate: TileCoordinate, tile: &MapTile, day: u32) -> bool {
+        let Some(last_visited) = tile.last_visited_day else {
+            return false;
+        };
+        tile.is_explored()
+            && day.saturating_sub(last_visited) > self.rules.stale_after_days
+            && !self.is_near_base(coordinate)
+    }
+
+    /// Check if a refresh must leave a tile alone
+    ///
+    /// Pinned tiles hold hand-shaped ground, ruin entrances lead to fixed
+    /// interiors and anchors are referenced by quests or the player's
+    /// markers.
+    pub fn is_protected(
+        &self,
+        map: &Map,
+        coordinate: TileCoordinate,
+        anchors: &HashSet<Position3D>,
+    ) -> bool {
+        map.is_tile_pinned(&coordinate)
+            || anchors.contains(&Position3D::from(coordinate))
+            || map.get_tile(&coordinate).is_some_and(|tile| {
+                InteriorGenerator::new().is_delve_site(map.seed(), coordinate, tile.terrain_type)
+            })
+    }
+
+    /// Stale tiles of a chunk on `day`, in a stable order
+    pub fn stale_tiles_in_chunk(
+        &self,
+        map: &Map,
+        chunk: ChunkCoordinate,
+        day: u32,
+    ) -> Vec<TileCoordinate> {
+        let mut stale: Vec<TileCoordinate> = map
+            .tiles()
+            .iter()
+            .filter(|(coordinate, tile)| {
+                chunk_of(**coordinate) == chunk && self.is_stale(**coordinate, tile, day)
+            })
+            .map(|(coordinate, _)| *coordinate)
+            .collect();
+        stale.sort_by_key(|coordinate| (coordinate.z, coordinate.y, coordinate.x));
+        stale
+    }
+
+    /// Refresh the stale, unprotected tiles of a chunk on `day`
+    ///
+    /// Refreshed tiles count as seen on `day`, so a chunk is refreshed at
+    /// most once per stale period.
+    pub fn refresh_chunk(
+        &self,
+        map: &mut Map,
+        chunk: ChunkCoordinate,
+        day: u32,
+        anchors: &HashSet<Position3D>,
+    ) -> StaleRefresh {
+        let mut report = StaleRefresh::default();
+        let seed = map.seed();
+        let map_service = MapService::new(seed);
+        let interiors = InteriorGenerator::new();
+        let stale: Vec<TileCoordinate> = self
+            .stale_tiles_in_chunk(map, chunk, day)
+            .into_iter()
+            .filter(|coordinate| !self.is_protected(map, *coordinate, anchors))
+            .collect();
+
+        for &coordinate in &stale {
+            let Some(mut tile) = map.get_tile(&coordinate).cloned() else {
+                continue;
+            };
+            let roll = refresh_roll(seed, coordinate, day, TERRAIN_SALT);
+            if roll % 100 < STALE_TERRAIN_REROLL_PERCENT {
+                let (terrain, elevation) =
+                    map_service.reroll_details(Position3D::from(coordinate), roll);
+                // Never open or close a path, and never move a ruin entrance
+                let keeps_shape = terrain.is_passable() == tile.terrain_type.is_passable()
+                    && !interiors.is_delve_site(seed, coordinate, terrain);
+                if keeps_shape && (terrain, elevation) != (tile.terrain_type, tile.elevation) {
+                    tile.terrain_type = terrain;
+                    tile.elevation = elevation;
+                    report.tiles_rerolled += 1;
+                }
+            }
+            tile.last_visited_day = Some(day);
+            map.set_tile(coordinate, tile);
+            report.tiles_refreshed += 1;
+        }
+
+        let positions: Vec<Position3D> = stale.iter().copied().map(Position3D::from).collect();
+        let mut free: Vec<Position3D> = positions
+            .iter()
+            .copied()
+            .filter(|position| {
+                map.get_resource_node(position).is_none() && map.is_passable(position)
+            })
+            .collect();
+        let with_nodes: Vec<Position3D> = positions
+            .iter()
+            .copied()
+            .filter(|position| map.get_resource_node(position).is_some())
+            .collect();
+
+        for position in with_nodes {
+            let Some(node) = map.get_resource_node_mut(&position) else {
+                continue;
+            };
+            // Nodes the player worked on grow back where they were
+            if node.total_harvested() > 0 || !node.is_full() {
+                node.replenish();
+                report.nodes_regrown += 1;
+                continue;
+            }
+
+            let roll = refresh_roll(seed, TileCoordinate::from(position), day, NODE_SALT);
+            if roll % 100 >= STALE_NODE_SHIFT_PERCENT || free.is_empty() {
+                continue;
+            }
+            let target = free.remove((roll / 100) as usize % free.len());
+            if let Some(node) = map.take_resource_node(&position) {
+                map.add_resource_node(target, node);
+                free.push(position);
+                report.nodes_shifted += 1;
+            }
+        }
+
+        report
+    }
+}
+
+/// Deterministic roll for a tile on a day
+fn refresh_roll(seed: u64, coordinate: TileCoordinate, day: u32, salt: u64) -> u64 {
+    let mut hash = seed ^ salt;
+    for value in [
+        coordinate.x as u64,
+        coordinate.y as u64,
+        coordinate.z as u64,
+        day as u64,
+    ] {
+        hash = (hash ^ value).wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^ (hash >> 33)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::terrain::{Elevation, TerrainType};
+    use crate::domain::value_objects::EntityId;
+
+    const SEED: u64 = 4242;
+
+    /// A generated chunk far from the base, every tile last seen on `day`
+    fn visited_chunk(day: u32) -> (Map, ChunkCoordinate) {
+        let mut map = Map::new(EntityId::generate(), "Stale".to_string(), SEED).unwrap();
+        let origin = Position3D::new(64, 64, 0);
+        let size = MAP_CHUNK_SIZE as i32;
+        MapService::new(SEED)
+            .generate_tiles_at(
+                &mut map,
+                (0..size).flat_map(|x| (0..size).map(move |y| origin.offset(x, y, 0))),
+            )
+            .unwrap();
+        let coordinates: Vec<TileCoordinate> = map.tiles().keys().copied().collect();
+        for coordinate in coordinates {
+            let mut tile = map.get_tile(&coordinate).unwrap().clone();
+            tile.visit(day);
+            map.set_tile(coordinate, tile);
+        }
+        (map, chunk_of(TileCoordinate::from(origin)))
+    }
+
+    #[test]
+    fn tiles_turn_stale_after_the_threshold_outside_the_base_radius() {
+        let service =
+            TileStalenessService::new(StalenessRules::new(20, Some(Position3D::origin())));
+        let far = TileCoordinate::new(30, 0, 0);
+        let mut tile = MapTile::new(TerrainType::Plains, Elevation::sea_level(), false);
+
+        // Never seen, never stale
+        assert!(!service.is_stale(far, &tile, 100));
+
+        tile.visit(4);
+        assert!(!service.is_stale(far, &tile, 24));
+        assert!(service.is_stale(far, &tile, 25));
+
+        // Seeing it again starts over
+        tile.visit(25);
+        assert!(!service.is_stale(far, &tile, 45));
+        assert!(service.is_stale(far, &tile, 46));
+
+        // Ground around the base stays fresh
+        let near = TileCoordinate::new(STALE_PROTECTED_BASE_RADIUS as i32, 0, 0);
+        assert!(service.is_near_base(near));
+        assert!(!service.is_stale(near, &tile, 500));
+        assert!(!service.is_near_base(TileCoordinate::new(
+            STALE_PROTECTED_BASE_RADIUS as i32 + 1,
+            0,
+            0
+        )));
+    }
+
+    #[test]
+    fn refresh_leaves_protected_tiles_alone() {
+        let service = TileStalenessService::new(StalenessRules::new(20, None));
+        let (mut map, chunk) = visited_chunk(1);
+        let mut tiles: Vec<TileCoordinate> = map.tiles().keys().copied().collect();
+        tiles.sort_by_key(|coordinate| (coordinate.y, coordinate.x));
+
+        let pinned = tiles[0];
+        map.pin_tile(pinned);
+        let anchor = tiles[17];
+        let anchors: HashSet<Position3D> = [Position3D::from(anchor)].into_iter().collect();
+        // Turn a tile into a ruin entrance
+        let ruin = tiles
+            .iter()
+            .copied()
+            .find(|coordinate| {
+                InteriorGenerator::new().is_delve_site(SEED, *coordinate, TerrainType::Constructed)
+            })
+            .unwrap();
+        let mut ruin_tile = map.get_tile(&ruin).unwrap().clone();
+        ruin_tile.terrain_type = TerrainType::Constructed;
+        map.set_tile(ruin, ruin_tile);
+
+        let protected: HashSet<TileCoordinate> = tiles
+            .iter()
+            .copied()
+            .filter(|coordinate| service.is_protected(&map, *coordinate, &anchors))
+            .collect();
+        assert!([pinned, anchor, ruin]
+            .iter()
+            .all(|coordinate| protected.contains(coordinate)));
+        let before: Vec<(TileCoordinate, MapTile)> = protected
+            .iter()
+            .map(|coordinate| (*coordinate, map.get_tile(coordinate).unwrap().clone()))
+            .collect();
+
+        // Refresh the chunk again and again over many stale periods
+        let mut changed = false;
+        for round in 1..=8 {
+            let report = service.refresh_chunk(&mut map, chunk, 1 + round * 21, &anchors);
+            assert_eq!(
+                report.tiles_refreshed as usize,
+                tiles.len() - protected.len()
+            );
+            changed |= report.changed_anything();
+        }
+        assert!(changed);
+
+        for (coordinate, tile) in &before {
+            assert_eq!(map.get_tile(coordinate), Some(tile));
+            assert!(service.is_protected(&map, *coordinate, &anchors));
+        }
+        // Refreshed tiles count as seen, protected ones stay stale
+        let stale: HashSet<TileCoordinate> = service
+            .stale_tiles_in_chunk(&map, chunk, 170)
+            .into_iter()
+            .collect();
+        assert_eq!(stale, protected);
+    }
+
+    #[test]
+    fn refresh_is_deterministic_for_a_seed_and_day() {
+        let service = TileStalenessService::new(StalenessRules::new(20, None));
+        let anchors = HashSet::new();
+        let refreshed_on = |day: u32| {
+            let (mut map, chunk) = visited_chunk(1);
+            // A harvested node grows back where it was
+            let mut positions: Vec<Position3D> = map.resource_nodes().keys().copied().collect();
+            positions.sort_by_key(|position| (position.x, position.y));
+            let mined = positions
+                .into_iter()
+                .find(|position| !service.is_protected(&map, (*position).into(), &anchors))
+                .unwrap();
+            map.get_resource_node_mut(&mined).unwrap().harvest(10);
+            let report = service.refresh_chunk(&mut map, chunk, day, &anchors);
+            assert!(map.get_resource_node(&mined).unwrap().is_full());
+            assert!(report.nodes_regrown >= 1);
+
+            let mut tiles: Vec<(TileCoordinate, TerrainType, Elevation)> = map
+                .tiles()
+                .iter()
+                .map(|(coordinate, tile)| (*coordinate, tile.terrain_type, tile.elevation))
+                .collect();
+            tiles.sort_by_key(|(coordinate, _, _)| (coordinate.x, coordinate.y));
+            let mut nodes: Vec<(Position3D, u32)> = map
+                .resource_nodes()
+                .iter()
+                .map(|(position, node)| (*position, node.current_amount()))
+                .collect();
+            nodes.sort_by_key(|(position, _)| (position.x, position.y));
+            (tiles, nodes, report)
+        };
+
+        let first = refreshed_on(30);
+        assert_eq!(refreshed_on(30), first);
+        assert_ne!(refreshed_on(31).0, first.0);
+    }
+}
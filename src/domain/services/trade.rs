@@ -0,0 +1,165 @@
+//! Trade Service - Faction offers at the base and their exchange rates
+//!
+//! Every faction that is not hostile puts one deal on the board at the
+//! base, rotating after each night of rest. Friendly and allied factions
+//! add an exclusive deal of their own. What the player receives is scaled
+//! by the faction's tier, so a good standing buys more for the same cargo.
+
+use crate::domain::services::reputation::{Faction, Reputation, ReputationTier};
+use crate::domain::value_objects::resources::{ResourceCollection, ResourceType};
+
+/// A deal offered by a faction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TradeOffer {
+    pub faction: Faction,
+    /// Cargo the player hands over
+    pub give: (ResourceType, u32),
+    /// Cargo the player receives, after the faction's rate
+    pub receive: (ResourceType, u32),
+    /// Only offered to friendly and allied players
+    pub exclusive: bool,
+}
+
+impl TradeOffer {
+    /// What accepting the offer costs
+    pub fn cost(&self) -> ResourceCollection {
+        let mut cost = ResourceCollection::new();
+        cost.set_amount(self.give.0, self.give.1);
+        cost
+    }
+
+    /// What accepting the offer pays out
+    pub fn goods(&self) -> ResourceCollection {
+        let mut goods = ResourceCollection::new();
+        goods.set_amount(self.receive.0, self.receive.1);
+        goods
+    }
+}
+
+type Deal = ((ResourceType, u32), (ResourceType, u32));
+
+/// Deals that rotate through a faction's slot on the board
+fn standard_deals(faction: Faction) -> &'static [Deal] {
+    use ResourceType::*;
+    match faction {
+        Faction::ScavengerGuild => &[
+            ((Metal, 30), (Alloys, 20)),
+            ((Organics, 20), (Metal, 40)),
+            ((Food, 20), (Energy, 20)),
+        ],
+        Faction::ColonialAuthority => &[
+            ((Energy, 20), (Food, 40)),
+            ((Data, 20), (Technology, 10)),
+            ((Metal, 40), (Energy, 30)),
+        ],
+        Faction::FreeTraders => &[
+            ((Food, 30), (Data, 20)),
+            ((Alloys, 10), (Metal, 40)),
+            ((Organics, 20), (Food, 40)),
+        ],
+    }
+}
+
+/// Deal only offered to friendly and allied players
+fn exclusive_deal(faction: Faction) -> Deal {
+    use ResourceType::*;
+    match faction {
+        Faction::ScavengerGuild => ((Metal, 40), (ExoticMatter, 10)),
+        Faction::ColonialAuthority => ((Energy, 30), (Technology, 20)),
+        Faction::FreeTraders => ((Data, 20), (Alloys, 30)),
+    }
+}
+
+/// Service building the faction offers at the base
+#[derive(Debug, Clone, Default)]
+pub struct TradeService;
+
+impl TradeService {
+    /// Create a new trade service
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Scale an amount by the exchange rate of a tier, rounding to nearest
+    pub fn apply_rate(&self, amount: u32, tier: ReputationTier) -> u32 {
+        let percent = (100 + tier.trade_rate_percent()).max(0) as u32;
+        (amount * percent + 50) / 100
+    }
+
+    /// Offers on the board for `rotation`, usually the nights rested
+    pub fn offers(&self, reputation: &Reputation, rotation: u32) -> Vec<TradeOffer> {
+        let mut offers = Vec::new();
+        for (index, faction) in Faction::all().into_iter().enumerate() {
+            let tier = reputation.tier(faction);
+            if tier == ReputationTier::Hostile {
+                continue;
+            }
+            let deals = standard_deals(faction);
+            let deal = deals[(rotation as usize + index) % deals.len()];
+            offers.push(self.offer(faction, tier, deal, false));
+            if tier >= ReputationTier::Friendly {
+                offers.push(self.offer(faction, tier, exclusive_deal(faction), true));
+            }
+        }
+        offers
+    }
+
+    fn offer(
+        &self,
+        faction: Faction,
+        tier: ReputationTier,
+        (give, (resource_type, amount)): Deal,
+        exclusive: bool,
+    ) -> TradeOffer {
+        TradeOffer {
+            faction,
+            give,
+            receive: (resource_type, self.apply_rate(amount, tier)),
+            exclusive,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rates_move_fifteen_percent_with_the_tier() {
+        let trade = TradeService::new();
+        assert_eq!(trade.apply_rate(40, ReputationTier::Hostile), 34);
+        assert_eq!(trade.apply_rate(40, ReputationTier::Neutral), 40);
+        assert_eq!(trade.apply_rate(40, ReputationTier::Friendly), 46);
+        assert_eq!(trade.apply_rate(40, ReputationTier::Allied), 46);
+        // Halves round up
+        assert_eq!(trade.apply_rate(10, ReputationTier::Hostile), 9);
+        assert_eq!(trade.apply_rate(0, ReputationTier::Allied), 0);
+    }
+
+    #[test]
+    fn board_follows_standing_and_rotates_each_night() {
+        let trade = TradeService::new();
+        let mut reputation = Reputation::new();
+
+        let neutral = trade.offers(&reputation, 0);
+        assert_eq!(neutral.len(), 3);
+        assert!(neutral.iter().all(|offer| !offer.exclusive));
+        assert_ne!(trade.offers(&reputation, 1), neutral);
+        assert_eq!(trade.offers(&reputation, 3), neutral);
+
+        // Allied with the Guild, which turns the Authority hostile
+        reputation.adjust(Faction::ScavengerGuild, 80, 1.0);
+        let offers = trade.offers(&reputation, 0);
+        assert!(offers
+            .iter()
+            .all(|offer| offer.faction != Faction::ColonialAuthority));
+        let guild: Vec<_> = offers
+            .iter()
+            .filter(|offer| offer.faction == Faction::ScavengerGuild)
+            .collect();
+        assert_eq!(guild.len(), 2);
+        assert!(guild[1].exclusive);
+        assert_eq!(guild[0].receive, (ResourceType::Alloys, 23));
+        assert_eq!(guild[0].cost().get_amount(ResourceType::Metal), 30);
+    }
+}
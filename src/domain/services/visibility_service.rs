@@ -6,7 +6,11 @@
 //! - Fogged visible tiles (with fog overlay): larger diamond pattern
 
 use crate::domain::{
-    constants::{FOGGED_VISIBLE_RADIUS, FOG_OF_WAR_DIAMOND_PATTERN, FULLY_VISIBLE_RADIUS},
+    constants::{
+        FOGGED_VISIBLE_RADIUS, FOG_OF_WAR_DIAMOND_PATTERN, FULLY_VISIBLE_RADIUS,
+        SCOUT_PROBE_CORRIDOR_HALF_WIDTH,
+    },
+    value_objects::position::Direction,
     value_objects::{Position3D, TileCoordinate},
 };
 
@@ -23,12 +27,43 @@ pub enum VisibilityLevel {
 
 /// Service for managing tile visibility and fog of war
 #[derive(Debug, Clone)]
-pub struct VisibilityService;
+pub struct VisibilityService {
+    /// Tiles added to the fogged radius, e.g. by a sensor module
+    extra_radius: u32,
+    /// Tiles taken off the fogged radius, e.g. at night
+    narrowing: u32,
+}
 
 impl VisibilityService {
     /// Create a new visibility service
     pub fn new() -> Self {
-        Self
+        Self {
+            extra_radius: 0,
+            narrowing: 0,
+        }
+    }
+
+    /// Create a visibility service that sees further into the fog
+    pub fn with_extra_radius(extra_radius: u32) -> Self {
+        Self {
+            extra_radius,
+            narrowing: 0,
+        }
+    }
+
+    /// The same service seeing `narrowing` tiles less far into the fog
+    ///
+    /// The fog never shrinks into the fully visible zone.
+    pub fn narrowed_by(mut self, narrowing: u32) -> Self {
+        self.narrowing = narrowing;
+        self
+    }
+
+    /// Radius of the fogged zone, including any extra radius and narrowing
+    pub fn fogged_radius(&self) -> u32 {
+        (FOGGED_VISIBLE_RADIUS + self.extra_radius)
+            .saturating_sub(self.narrowing)
+            .max(FULLY_VISIBLE_RADIUS)
     }
 
     /// Get the visibility level for a tile from the player's position
@@ -56,7 +91,7 @@ impl VisibilityService {
             let distance = player_pos.manhattan_distance_2d(&tile_pos);
             if distance <= FULLY_VISIBLE_RADIUS {
                 VisibilityLevel::FullyVisible
-            } else if distance <= FOGGED_VISIBLE_RADIUS {
+            } else if distance <= self.fogged_radius() {
                 VisibilityLevel::Fogged
             } else {
                 VisibilityLevel::Hidden
@@ -99,7 +134,7 @@ impl VisibilityService {
         let mut fogged_tiles = Vec::new();
 
         // Generate diamond pattern coordinates
-        let radius = FOGGED_VISIBLE_RADIUS as i32;
+        let radius = self.fogged_radius() as i32;
         for dx in -radius..=radius {
             for dy in -radius..=radius {
                 let tile_pos = Position3D::new(player_pos.x + dx, player_pos.y + dy, player_pos.z);
@@ -124,6 +159,35 @@ impl VisibilityService {
         all_tiles
     }
 
+    /// Get the tiles a scout probe reveals while passing over `center`
+    ///
+    /// A slice across the probe's heading: the tile below it and
+    /// `SCOUT_PROBE_CORRIDOR_HALF_WIDTH` tiles on either side. Revealed tiles
+    /// become explored, not visible; they are shown again once the player
+    /// comes near.
+    pub fn get_probe_corridor_coordinates(
+        &self,
+        center: Position3D,
+        heading: Direction,
+    ) -> Vec<TileCoordinate> {
+        let (across_x, across_y) = match heading {
+            Direction::North | Direction::South => (1, 0),
+            Direction::East | Direction::West => (0, 1),
+            Direction::Up | Direction::Down => (0, 0),
+        };
+        let half_width = if across_x == 0 && across_y == 0 {
+            0
+        } else {
+            SCOUT_PROBE_CORRIDOR_HALF_WIDTH
+        };
+
+        (-half_width..=half_width)
+            .map(|offset| {
+                TileCoordinate::from(center.offset(offset * across_x, offset * across_y, 0))
+            })
+            .collect()
+    }
+
     /// Check if a tile position is in the diamond pattern around player (fully visible)
     fn is_in_diamond_pattern_fully_visible(
         &self,
@@ -146,7 +210,7 @@ impl VisibilityService {
         let dz = (player_pos.z - tile_pos.z).abs();
 
         // Same Z level and within diamond pattern (Manhattan distance)
-        dz == 0 && (dx + dy) <= FOGGED_VISIBLE_RADIUS as i32
+        dz == 0 && (dx + dy) <= self.fogged_radius() as i32
     }
 }
 
@@ -310,4 +374,81 @@ mod tests {
             VisibilityLevel::Hidden
         );
     }
+
+    #[test]
+    fn test_probe_corridor_is_three_wide_across_the_heading() {
+        let service = VisibilityService::new();
+        let center = Position3D::new(2, 3, 0);
+
+        for heading in [Direction::North, Direction::South] {
+            assert_eq!(
+                service.get_probe_corridor_coordinates(center, heading),
+                vec![
+                    TileCoordinate::new(1, 3, 0),
+                    TileCoordinate::new(2, 3, 0),
+                    TileCoordinate::new(3, 3, 0),
+                ]
+            );
+        }
+        for heading in [Direction::East, Direction::West] {
+            assert_eq!(
+                service.get_probe_corridor_coordinates(center, heading),
+                vec![
+                    TileCoordinate::new(2, 2, 0),
+                    TileCoordinate::new(2, 3, 0),
+                    TileCoordinate::new(2, 4, 0),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_extra_radius_widens_only_the_fog() {
+        let base = VisibilityService::new();
+        let sensor = VisibilityService::with_extra_radius(2);
+        let player_pos = Position3D::new(0, 0, 0);
+        let beyond = TileCoordinate::new(FOGGED_VISIBLE_RADIUS as i32 + 2, 0, 0);
+
+        assert_eq!(
+            base.get_tile_visibility(player_pos, beyond),
+            VisibilityLevel::Hidden
+        );
+        assert_eq!(
+            sensor.get_tile_visibility(player_pos, beyond),
+            VisibilityLevel::Fogged
+        );
+        assert_eq!(
+            sensor.get_fully_visible_coordinates(player_pos),
+            base.get_fully_visible_coordinates(player_pos)
+        );
+        assert!(
+            sensor.get_all_visible_coordinates(player_pos).len()
+                > base.get_all_visible_coordinates(player_pos).len()
+        );
+    }
+
+    #[test]
+    fn narrowing_shrinks_only_the_fog() {
+        let base = VisibilityService::new();
+        let night = VisibilityService::new().narrowed_by(1);
+        let player_pos = Position3D::new(0, 0, 0);
+        let edge = TileCoordinate::new(FOGGED_VISIBLE_RADIUS as i32, 0, 0);
+
+        assert_eq!(
+            night.get_tile_visibility(player_pos, edge),
+            VisibilityLevel::Hidden
+        );
+        assert_eq!(
+            base.get_tile_visibility(player_pos, edge),
+            VisibilityLevel::Fogged
+        );
+        assert_eq!(
+            night.get_fully_visible_coordinates(player_pos),
+            base.get_fully_visible_coordinates(player_pos)
+        );
+        assert_eq!(
+            VisibilityService::new().narrowed_by(99).fogged_radius(),
+            FULLY_VISIBLE_RADIUS
+        );
+    }
 }
@@ -0,0 +1,295 @@
+//! Wrecks - Salvage left behind by won fights
+//!
+//! Beating hostiles, or repelling raiders at the base, leaves a wreck on
+//! the tile holding the loot rolled for the fight; a Crash Survivor run
+//! starts next to the wreck of its own ship. Salvaging it costs a
+//! movement point and an Intelligence check decides how much of that loot
+//! comes out. A salvaged wreck turns into debris, which is only scenery and
+//! is cleared away after `WRECK_DEBRIS_RESTS` rests. At most `WRECK_CAP`
+//! wrecks lie around unclaimed; the oldest one goes when another is left.
+
+use crate::domain::constants::{
+    WRECK_CAP, WRECK_DEBRIS_RESTS, WRECK_SALVAGE_RICH_DC, WRECK_SALVAGE_STANDARD_DC,
+};
+use crate::domain::value_objects::{Position3D, ResourceType};
+use serde::{Deserialize, Serialize};
+
+/// What the wreck was before the fight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WreckOrigin {
+    /// Hostiles beaten out in the field
+    Hostiles,
+    /// Raiders driven off at the base
+    Raid,
+    /// The ship a Crash Survivor run starts next to
+    Crash,
+}
+
+impl WreckOrigin {
+    /// Raiders when the fight was at the base, hostiles anywhere else
+    pub fn of_fight(position: Position3D, base: Position3D) -> Self {
+        if position == base {
+            Self::Raid
+        } else {
+            Self::Hostiles
+        }
+    }
+
+    /// Name used in the game log
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Hostiles => "hostile wreck",
+            Self::Raid => "raider wreck",
+            Self::Crash => "crash site",
+        }
+    }
+}
+
+/// How well a wreck was taken apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SalvageYield {
+    /// Half the loot, rounded up
+    Scraps,
+    /// All of the loot
+    Standard,
+    /// Half as much again
+    Rich,
+}
+
+impl SalvageYield {
+    /// Tier of an Intelligence check: a d20 `roll` plus the stat `modifier`
+    ///
+    /// A natural 20 always yields rich salvage.
+    pub fn from_check(roll: u8, modifier: i8) -> Self {
+        let total = roll as i32 + modifier as i32;
+        if roll >= 20 || total >= WRECK_SALVAGE_RICH_DC {
+            Self::Rich
+        } else if total >= WRECK_SALVAGE_STANDARD_DC {
+            Self::Standard
+        } else {
+            Self::Scraps
+        }
+    }
+
+    /// `amount` of a loot entry after this yield
+    pub fn scale(&self, amount: u32) -> u32 {
+        match self {
+            Self::Scraps => amount.div_ceil(2),
+            Self::Standard => amount,
+            Self::Rich => amount + amount / 2,
+        }
+    }
+
+    /// Word used in the game log
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::Scraps => "scraps",
+            Self::Standard => "a fair haul",
+            Self::Rich => "a rich haul",
+        }
+    }
+}
+
+/// An unclaimed wreck and the loot rolled for it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Wreck {
+    pub position: Position3D,
+    pub origin: WreckOrigin,
+    pub loot: Vec<(ResourceType, u32)>,
+}
+
+/// Scrap left by a salvaged wreck, until it is cleared away
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Debris {
+    pub position: Position3D,
+    pub rests_left: u32,
+}
+
+/// Every wreck and pile of debris on the map, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WreckField {
+    wrecks: Vec<Wreck>,
+    debris: Vec<Debris>,
+}
+
+impl WreckField {
+    /// A map without any wrecks
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Leave a wreck holding `loot` on `position`
+    ///
+    /// Loot left on a tile that already has a wreck is added to it. Returns
+    /// the oldest wreck when this one goes over `WRECK_CAP`.
+    pub fn leave(
+        &mut self,
+        position: Position3D,
+        origin: WreckOrigin,
+        loot: Vec<(ResourceType, u32)>,
+    ) -> Option<Wreck> {
+        if loot.is_empty() {
+            return None;
+        }
+        if let Some(wreck) = self.wrecks.iter_mut().find(|w| w.position == position) {
+            for (resource_type, amount) in loot {
+                match wreck
+                    .loot
+                    .iter_mut()
+                    .find(|(kind, _)| *kind == resource_type)
+                {
+                    Some(entry) => entry.1 += amount,
+                    None => wreck.loot.push((resource_type, amount)),
+                }
+            }
+            return None;
+        }
+
+        self.wrecks.push(Wreck {
+            position,
+            origin,
+            loot,
+        });
+        (self.wrecks.len() > WRECK_CAP).then(|| self.wrecks.remove(0))
+    }
+
+    /// The wreck on `position`, if any
+    pub fn at(&self, position: Position3D) -> Option<&Wreck> {
+        self.wrecks.iter().find(|wreck| wreck.position == position)
+    }
+
+    /// Take the wreck on `position` apart, leaving debris
+    ///
+    /// Returns the loot after `salvage_yield`, or none without a wreck.
+    pub fn salvage(
+        &mut self,
+        position: Position3D,
+        salvage_yield: SalvageYield,
+    ) -> Option<Vec<(ResourceType, u32)>> {
+        let index = self.wrecks.iter().position(|w| w.position == position)?;
+        let wreck = self.wrecks.remove(index);
+        self.debris.retain(|debris| debris.position != position);
+        self.debris.push(Debris {
+            position,
+            rests_left: WRECK_DEBRIS_RESTS,
+        });
+        Some(
+            wreck
+                .loot
+                .into_iter()
+                .map(|(resource_type, amount)| (resource_type, salvage_yield.scale(amount)))
+                .filter(|(_, amount)| *amount > 0)
+                .collect(),
+        )
+    }
+
+    /// Count a rest down on every pile of debris; returns how many were cleared
+    pub fn rest(&mut self) -> usize {
+        let before = self.debris.len();
+        for debris in &mut self.debris {
+            debris.rests_left = debris.rests_left.saturating_sub(1);
+        }
+        self.debris.retain(|debris| debris.rests_left > 0);
+        before - self.debris.len()
+    }
+
+    /// Unclaimed wrecks, oldest first
+    pub fn wrecks(&self) -> &[Wreck] {
+        &self.wrecks
+    }
+
+    /// Debris still lying around
+    pub fn debris(&self) -> &[Debris] {
+        &self.debris
+    }
+
+    /// Check if there is nothing on the map
+    pub fn is_empty(&self) -> bool {
+        self.wrecks.is_empty() && self.debris.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metal(amount: u32) -> Vec<(ResourceType, u32)> {
+        vec![(ResourceType::Metal, amount)]
+    }
+
+    #[test]
+    fn salvage_yield_follows_the_intelligence_check() {
+        assert_eq!(SalvageYield::from_check(3, 0), SalvageYield::Scraps);
+        assert_eq!(SalvageYield::from_check(5, 3), SalvageYield::Standard);
+        assert_eq!(SalvageYield::from_check(15, 0), SalvageYield::Standard);
+        assert_eq!(SalvageYield::from_check(14, 2), SalvageYield::Rich);
+        assert_eq!(SalvageYield::from_check(20, -5), SalvageYield::Rich);
+
+        let mut field = WreckField::new();
+        let spot = Position3D::new(2, 1, 0);
+        field.leave(spot, WreckOrigin::Hostiles, metal(15));
+        assert_eq!(
+            field.clone().salvage(spot, SalvageYield::Scraps),
+            Some(metal(8))
+        );
+        assert_eq!(
+            field.clone().salvage(spot, SalvageYield::Standard),
+            Some(metal(15))
+        );
+        assert_eq!(field.salvage(spot, SalvageYield::Rich), Some(metal(22)));
+        assert!(field.at(spot).is_none());
+        assert_eq!(field.debris().len(), 1);
+        assert_eq!(field.salvage(spot, SalvageYield::Rich), None);
+    }
+
+    #[test]
+    fn the_oldest_unclaimed_wreck_goes_over_the_cap() {
+        let mut field = WreckField::new();
+        for x in 0..WRECK_CAP as i32 {
+            assert!(field
+                .leave(Position3D::new(x, 0, 0), WreckOrigin::Hostiles, metal(5))
+                .is_none());
+        }
+        // Salvaged wrecks no longer count towards the cap
+        field.salvage(Position3D::new(0, 0, 0), SalvageYield::Standard);
+        assert!(field
+            .leave(Position3D::new(0, 5, 0), WreckOrigin::Raid, metal(5))
+            .is_none());
+        // Loot on a tile with a wreck joins it instead of making another
+        assert!(field
+            .leave(Position3D::new(0, 5, 0), WreckOrigin::Raid, metal(5))
+            .is_none());
+        assert_eq!(field.at(Position3D::new(0, 5, 0)).unwrap().loot, metal(10));
+
+        let evicted = field
+            .leave(Position3D::new(0, 6, 0), WreckOrigin::Hostiles, metal(5))
+            .unwrap();
+        assert_eq!(evicted.position, Position3D::new(1, 0, 0));
+        assert_eq!(field.wrecks().len(), WRECK_CAP);
+        assert_eq!(field.wrecks()[0].position, Position3D::new(2, 0, 0));
+        assert_eq!(
+            WreckOrigin::of_fight(Position3D::origin(), Position3D::origin()),
+            WreckOrigin::Raid
+        );
+    }
+
+    #[test]
+    fn debris_is_cleared_after_its_rests() {
+        let mut field = WreckField::new();
+        let first = Position3D::new(1, 0, 0);
+        let second = Position3D::new(2, 0, 0);
+        field.leave(first, WreckOrigin::Hostiles, metal(5));
+        field.leave(second, WreckOrigin::Hostiles, metal(5));
+        field.salvage(first, SalvageYield::Standard);
+        field.rest();
+        field.rest();
+        field.salvage(second, SalvageYield::Standard);
+
+        let cleared: Vec<usize> = (0..WRECK_DEBRIS_RESTS + 2).map(|_| field.rest()).collect();
+        let first_cleared = WRECK_DEBRIS_RESTS as usize - 3;
+        assert_eq!(cleared[first_cleared], 1);
+        assert_eq!(cleared[WRECK_DEBRIS_RESTS as usize - 1], 1);
+        assert_eq!(cleared.iter().sum::<usize>(), 2);
+        assert!(field.is_empty());
+    }
+}
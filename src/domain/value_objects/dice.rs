@@ -255,6 +255,8 @@ pub struct DiceModifier {
     pub equipment_bonus: i8,      // Bonus from equipment
     pub situational_modifier: i8, // Bonus/penalty from circumstances
     pub luck_bonus: i8,           // Bonus from luck events or abilities
+    pub assist_bonus: i8,         // Bonus from optional player assists
+    pub source_tags: Vec<String>, // Visible labels naming where bonuses came from
 }
 
 impl DiceModifier {
@@ -291,6 +293,8 @@ impl DiceModifier {
             equipment_bonus,
             situational_modifier,
             luck_bonus,
+            assist_bonus: 0,
+            source_tags: Vec::new(),
         })
     }
 
@@ -301,9 +305,16 @@ impl DiceModifier {
             equipment_bonus: 0,
             situational_modifier: 0,
             luck_bonus: 0,
+            assist_bonus: 0,
+            source_tags: Vec::new(),
         }
     }
 
+    /// Start building a modifier piece by piece
+    pub fn builder() -> DiceModifierBuilder {
+        DiceModifierBuilder::default()
+    }
+
     /// Create a modifier with only stat bonus
     pub fn from_stat(stat_modifier: i8) -> DomainResult<Self> {
         Self::new(stat_modifier, 0, 0, 0)
@@ -311,8 +322,20 @@ impl DiceModifier {
 
     /// Get the total modifier value
     pub fn total_modifier(&self) -> i32 {
-        (self.stat_modifier + self.equipment_bonus + self.situational_modifier + self.luck_bonus)
-            as i32
+        (self.stat_modifier
+            + self.equipment_bonus
+            + self.situational_modifier
+            + self.luck_bonus
+            + self.assist_bonus) as i32
+    }
+
+    /// Visible source labels joined for display, if any
+    pub fn source_label(&self) -> Option<String> {
+        if self.source_tags.is_empty() {
+            None
+        } else {
+            Some(self.source_tags.join(", "))
+        }
     }
 
     /// Check if this modifier has any effect
@@ -322,12 +345,20 @@ impl DiceModifier {
 
     /// Add another modifier to this one
     pub fn add(&self, other: &DiceModifier) -> DomainResult<Self> {
-        Self::new(
+        let mut combined = Self::new(
             self.stat_modifier + other.stat_modifier,
             self.equipment_bonus + other.equipment_bonus,
             self.situational_modifier + other.situational_modifier,
             self.luck_bonus + other.luck_bonus,
-        )
+        )?;
+        combined.assist_bonus = self.assist_bonus + other.assist_bonus;
+        combined.source_tags = self
+            .source_tags
+            .iter()
+            .chain(other.source_tags.iter())
+            .cloned()
+            .collect();
+        Ok(combined)
     }
 
     /// Create a situational modifier
@@ -346,6 +377,73 @@ impl DiceModifier {
     }
 }
 
+/// Builder for dice modifiers combining several bonus sources
+#[derive(Debug, Clone, Default)]
+pub struct DiceModifierBuilder {
+    stat_modifier: i8,
+    equipment_bonus: i8,
+    situational_modifier: i8,
+    luck_bonus: i8,
+    assist_bonus: i8,
+    source_tags: Vec<String>,
+}
+
+impl DiceModifierBuilder {
+    /// Add a stat bonus or penalty
+    pub fn stat(mut self, modifier: i8) -> Self {
+        self.stat_modifier += modifier;
+        self
+    }
+
+    /// Add an equipment bonus
+    pub fn equipment(mut self, bonus: i8) -> Self {
+        self.equipment_bonus += bonus;
+        self
+    }
+
+    /// Add a situational bonus or penalty
+    pub fn situational(mut self, modifier: i8) -> Self {
+        self.situational_modifier += modifier;
+        self
+    }
+
+    /// Add a luck bonus
+    pub fn luck(mut self, bonus: i8) -> Self {
+        self.luck_bonus += bonus;
+        self
+    }
+
+    /// Add an assist bonus, labelled with `source` only when `visible`
+    pub fn assist(mut self, bonus: i8, source: &str, visible: bool) -> Self {
+        if bonus == 0 {
+            return self;
+        }
+        self.assist_bonus += bonus;
+        if visible {
+            self.source_tags.push(format!("{} {:+}", source, bonus));
+        }
+        self
+    }
+
+    /// Validate and build the modifier
+    pub fn build(self) -> DomainResult<DiceModifier> {
+        let mut modifier = DiceModifier::new(
+            self.stat_modifier,
+            self.equipment_bonus,
+            self.situational_modifier,
+            self.luck_bonus,
+        )?;
+        if self.assist_bonus < -10 || self.assist_bonus > 10 {
+            return Err(DomainError::DiceModifierError(
+                "Individual modifiers must be between -10 and +10".to_string(),
+            ));
+        }
+        modifier.assist_bonus = self.assist_bonus;
+        modifier.source_tags = self.source_tags;
+        Ok(modifier)
+    }
+}
+
 impl Default for DiceModifier {
     fn default() -> Self {
         DiceModifier::none()
@@ -617,6 +715,24 @@ mod tests {
         assert!(DiceModifier::new(10, 10, 5, 0).is_err());
     }
 
+    #[test]
+    fn dice_modifier_builder_tags_visible_assists() {
+        let hidden = DiceModifier::builder()
+            .situational(-2)
+            .assist(1, "Fortune's Favor", false)
+            .build()
+            .unwrap();
+        assert_eq!(hidden.total_modifier(), -1);
+        assert_eq!(hidden.source_label(), None);
+
+        let shown = DiceModifier::builder()
+            .assist(2, "Fortune's Favor", true)
+            .build()
+            .unwrap();
+        assert_eq!(shown.assist_bonus, 2);
+        assert_eq!(shown.source_label().as_deref(), Some("Fortune's Favor +2"));
+    }
+
     #[test]
     fn dice_result_creation() {
         let roll = DiceRoll::simple(2, DiceType::D6).unwrap();
@@ -0,0 +1,110 @@
+//! Health - The hull integrity of a character
+//!
+//! Health runs from zero up to a maximum. Damage stops at zero and healing
+//! stops at the maximum, so neither ever overflows; a character at zero is
+//! dead and the run's survival check takes it from there.
+
+use crate::domain::constants::MAX_PLAYER_HEALTH;
+use crate::domain::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Current and maximum health
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Health {
+    current: u32,
+    max: u32,
+}
+
+impl Health {
+    /// Create health with validation
+    pub fn new(current: u32, max: u32) -> DomainResult<Self> {
+        if max == 0 || max > MAX_PLAYER_HEALTH {
+            return Err(DomainError::ValidationError(format!(
+                "Maximum health must be between 1 and {}",
+                MAX_PLAYER_HEALTH
+            )));
+        }
+        if current > max {
+            return Err(DomainError::ValidationError(format!(
+                "Health {} exceeds its maximum {}",
+                current, max
+            )));
+        }
+        Ok(Self { current, max })
+    }
+
+    /// Full health with a maximum of `max`, kept within the health limits
+    pub fn full(max: u32) -> Self {
+        let max = max.clamp(1, MAX_PLAYER_HEALTH);
+        Self { current: max, max }
+    }
+
+    /// Current health
+    pub fn current(&self) -> u32 {
+        self.current
+    }
+
+    /// Maximum health
+    pub fn max(&self) -> u32 {
+        self.max
+    }
+
+    /// Share of health left, from 0.0 to 1.0
+    pub fn fraction(&self) -> f32 {
+        self.current as f32 / self.max as f32
+    }
+
+    /// Check if no health is left
+    pub fn is_dead(&self) -> bool {
+        self.current == 0
+    }
+
+    /// Take `amount` damage, stopping at zero; returns the damage taken
+    pub fn take_damage(&mut self, amount: u32) -> u32 {
+        let taken = amount.min(self.current);
+        self.current -= taken;
+        taken
+    }
+
+    /// Heal `amount`, stopping at the maximum; returns the health gained
+    pub fn heal(&mut self, amount: u32) -> u32 {
+        let healed = amount.min(self.max - self.current);
+        self.current += healed;
+        healed
+    }
+}
+
+impl fmt::Display for Health {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{} HP", self.current, self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn damage_beyond_the_health_left_stops_at_zero() {
+        let mut health = Health::new(30, 100).unwrap();
+        assert_eq!(health.take_damage(45), 30);
+        assert_eq!(health.current(), 0);
+        assert!(health.is_dead());
+        assert_eq!(health.take_damage(u32::MAX), 0);
+        assert_eq!(health.current(), 0);
+    }
+
+    #[test]
+    fn healing_never_passes_the_maximum() {
+        let mut health = Health::new(90, 100).unwrap();
+        assert_eq!(health.heal(25), 10);
+        assert_eq!(health, Health::full(100));
+        assert_eq!(health.heal(u32::MAX), 0);
+        assert!(!health.is_dead());
+
+        assert!(Health::new(101, 100).is_err());
+        assert!(Health::new(0, 0).is_err());
+        assert_eq!(Health::full(0).max(), 1);
+    }
+}
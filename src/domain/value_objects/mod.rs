@@ -10,12 +10,14 @@ use std::fmt;
 
 // Module declarations
 pub mod dice;
+pub mod health;
 pub mod position;
 pub mod resources;
 pub mod terrain;
 
 // Re-export all value objects for convenience
-pub use dice::{DiceModifier, DiceResult, DiceRoll, DiceType};
+pub use dice::{DiceModifier, DiceModifierBuilder, DiceResult, DiceRoll, DiceType};
+pub use health::Health;
 pub use position::{Position3D, TileCoordinate};
 pub use resources::{ResourceAmount, ResourceCollection, ResourceType};
 pub use terrain::TerrainType;
@@ -198,6 +200,18 @@ impl PlayerStats {
         }
     }
 
+    /// Get the value of a stat
+    pub fn get_stat(&self, stat_type: StatType) -> u8 {
+        match stat_type {
+            StatType::Strength => self.strength,
+            StatType::Dexterity => self.dexterity,
+            StatType::Intelligence => self.intelligence,
+            StatType::Charisma => self.charisma,
+            StatType::Luck => self.luck,
+            StatType::Endurance => self.endurance,
+        }
+    }
+
     /// Get modifier for dice rolls based on stat
     pub fn get_modifier(&self, stat_type: StatType) -> i8 {
         let stat_value = match stat_type {
@@ -242,6 +256,23 @@ impl PlayerStats {
 
         Ok(new_stats)
     }
+
+    /// Lower a stat by `amount` points, never below `MIN_STAT_VALUE`
+    pub fn decrease_stat(&self, stat_type: StatType, amount: u8) -> Self {
+        let mut new_stats = *self;
+        let value = match stat_type {
+            StatType::Strength => &mut new_stats.strength,
+            StatType::Dexterity => &mut new_stats.dexterity,
+            StatType::Intelligence => &mut new_stats.intelligence,
+            StatType::Charisma => &mut new_stats.charisma,
+            StatType::Luck => &mut new_stats.luck,
+            StatType::Endurance => &mut new_stats.endurance,
+        };
+        *value = value
+            .saturating_sub(amount)
+            .max(crate::domain::constants::MIN_STAT_VALUE);
+        new_stats
+    }
 }
 
 /// Types of player statistics
@@ -255,6 +286,20 @@ pub enum StatType {
     Endurance,
 }
 
+impl StatType {
+    /// Every stat in display order
+    pub fn all() -> [StatType; 6] {
+        [
+            StatType::Strength,
+            StatType::Dexterity,
+            StatType::Intelligence,
+            StatType::Charisma,
+            StatType::Luck,
+            StatType::Endurance,
+        ]
+    }
+}
+
 impl fmt::Display for StatType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
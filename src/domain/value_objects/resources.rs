@@ -538,6 +538,11 @@ impl ResourceRichness {
             ResourceRichness::Abundant => 3.0,
         }
     }
+
+    /// `amount` scaled by the yield multiplier, rounded to whole units
+    pub fn scale(&self, amount: u32) -> u32 {
+        (amount as f32 * self.yield_multiplier()).round() as u32
+    }
 }
 
 impl fmt::Display for ResourceRichness {
@@ -133,6 +133,24 @@ impl TerrainType {
         }
     }
 
+    /// Modifier to a flee check; open ground is easy to run on, mud and tunnels are not
+    pub fn flee_modifier(&self) -> i8 {
+        match self {
+            TerrainType::Plains => 3,
+            TerrainType::Forest => 0,
+            TerrainType::Mountains => -1,
+            TerrainType::Desert => 1,
+            TerrainType::Tundra => 0,
+            TerrainType::Swamp => -3,
+            TerrainType::Ocean => -4,
+            TerrainType::Volcanic => -2,
+            TerrainType::Anomaly => -2,
+            TerrainType::Constructed => 2,
+            TerrainType::Cave => -3,
+            TerrainType::Crystal => -1,
+        }
+    }
+
     /// Check if this terrain can be traversed without special equipment
     pub fn is_passable(&self) -> bool {
         !matches!(self, TerrainType::Ocean)
@@ -156,6 +174,14 @@ impl TerrainType {
         matches!(self, TerrainType::Constructed)
     }
 
+    /// Check if this terrain is open ground with nothing to shelter behind
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self,
+            TerrainType::Plains | TerrainType::Desert | TerrainType::Tundra
+        )
+    }
+
     /// Get primary resource types this terrain can contain
     pub fn primary_resources(&self) -> Vec<ResourceType> {
         match self {
@@ -370,6 +396,24 @@ impl TerrainType {
         }
     }
 
+    /// Get the plain character standing for this terrain in text maps
+    pub fn legend_char(&self) -> char {
+        match self {
+            TerrainType::Plains => '.',
+            TerrainType::Forest => 'T',
+            TerrainType::Mountains => '^',
+            TerrainType::Desert => ':',
+            TerrainType::Tundra => '_',
+            TerrainType::Swamp => '%',
+            TerrainType::Ocean => '~',
+            TerrainType::Volcanic => 'V',
+            TerrainType::Anomaly => '?',
+            TerrainType::Constructed => '#',
+            TerrainType::Cave => 'O',
+            TerrainType::Crystal => '*',
+        }
+    }
+
     /// Check if this terrain type is compatible with adjacent terrain
     /// Some terrain types naturally occur together, others don't
     pub fn is_compatible_with(&self, other: &TerrainType) -> bool {
@@ -428,6 +472,40 @@ impl TerrainType {
             _ => true,
         }
     }
+
+    /// Priority used when blending edges between adjacent terrains
+    ///
+    /// Each terrain has a unique priority, so any two different terrains
+    /// resolve to exactly one winner. Water overrides land, raised and
+    /// hazardous terrain overrides flat ground.
+    pub fn transition_priority(&self) -> u8 {
+        match self {
+            TerrainType::Ocean => 12,
+            TerrainType::Volcanic => 11,
+            TerrainType::Anomaly => 10,
+            TerrainType::Mountains => 9,
+            TerrainType::Crystal => 8,
+            TerrainType::Swamp => 7,
+            TerrainType::Forest => 6,
+            TerrainType::Tundra => 5,
+            TerrainType::Desert => 4,
+            TerrainType::Cave => 3,
+            TerrainType::Constructed => 2,
+            TerrainType::Plains => 1,
+        }
+    }
+
+    /// Terrain that bleeds onto this tile's edge from a neighbor, if any
+    ///
+    /// Returns the neighbor's terrain when it outranks this one; the lower
+    /// priority side of a shared edge is the one that draws the transition.
+    pub fn transition_from(&self, neighbor: TerrainType) -> Option<TerrainType> {
+        if neighbor.transition_priority() > self.transition_priority() {
+            Some(neighbor)
+        } else {
+            None
+        }
+    }
 }
 
 impl fmt::Display for TerrainType {
@@ -624,6 +702,35 @@ mod tests {
         assert_eq!(mountain.category(), ElevationCategory::Mountains);
     }
 
+    #[test]
+    fn transition_priority_resolves_every_pair_once() {
+        for a in TerrainType::all() {
+            assert_eq!(a.transition_from(a), None);
+            for b in TerrainType::all() {
+                if a == b {
+                    continue;
+                }
+                // Exactly one side of a shared edge draws the transition
+                let a_draws = a.transition_from(b).is_some();
+                let b_draws = b.transition_from(a).is_some();
+                assert_ne!(a_draws, b_draws, "{:?} vs {:?}", a, b);
+            }
+        }
+
+        assert_eq!(
+            TerrainType::Plains.transition_from(TerrainType::Ocean),
+            Some(TerrainType::Ocean)
+        );
+        assert_eq!(
+            TerrainType::Plains.transition_from(TerrainType::Mountains),
+            Some(TerrainType::Mountains)
+        );
+        assert_eq!(
+            TerrainType::Ocean.transition_from(TerrainType::Mountains),
+            None
+        );
+    }
+
     #[test]
     fn terrain_modifiers() {
         assert!(TerrainType::Plains.visibility_modifier() > 1.0);
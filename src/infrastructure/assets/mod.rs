@@ -0,0 +1,484 @@
+//! Asset Integrity - Checking the bundled assets once at startup
+//!
+//! Every file the game loads is listed in `ASSET_MANIFEST` with its kind,
+//! whether the game can run without it and, optionally, the size range a
+//! healthy copy falls into. At startup each entry is loaded and classified
+//! from its load state as ok, missing, failed or suspiciously sized. A broken
+//! optional asset (a music track, an icon) is a warning; a broken required
+//! one (the primary font, the UI click) blocks with instructions, since the
+//! deployment itself is incomplete. Sizes can only be read natively; on the
+//! web the size check is skipped.
+
+use crate::domain::constants::*;
+use bevy::asset::io::AssetReaderError;
+use bevy::asset::{AssetLoadError, LoadState};
+use serde::{Deserialize, Serialize};
+use AssetKind::{Audio, Font, Image};
+
+/// Seconds an asset may stay pending before it counts as failed
+pub const ASSET_CHECK_TIMEOUT_SECONDS: f32 = 20.0;
+
+/// Shown with the report when a required asset is broken
+pub const ASSET_REPAIR_INSTRUCTIONS: &str = "Some files the game needs did not load. \
+Make sure the whole assets folder (fonts, audio and icons) was deployed next to the game, \
+then restart it or reload the page.";
+
+/// Size range of a short sound effect, in bytes; a WAV header alone is 44
+const SOUND_EFFECT_BYTES: (u64, u64) = (45, 4_000_000);
+
+/// Size range of a music or ambient track, in bytes
+const TRACK_BYTES: (u64, u64) = (1_024, 30_000_000);
+
+/// Size range of a UI icon, in bytes
+const ICON_BYTES: (u64, u64) = (64, 1_000_000);
+
+/// Size range of a font, in bytes
+const FONT_BYTES: (u64, u64) = (10_000, 20_000_000);
+
+/// What an asset is loaded as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetKind {
+    Font,
+    Audio,
+    Image,
+}
+
+/// One bundled asset the game loads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssetEntry {
+    /// Path below the assets folder
+    pub path: &'static str,
+    pub kind: AssetKind,
+    /// Whether the game is unusable without it
+    pub required: bool,
+    /// Inclusive size range of a healthy copy, in bytes
+    pub size: Option<(u64, u64)>,
+}
+
+impl AssetEntry {
+    /// An asset the game cannot run without
+    pub const fn required(path: &'static str, kind: AssetKind) -> Self {
+        Self {
+            path,
+            kind,
+            required: true,
+            size: None,
+        }
+    }
+
+    /// An asset the game can do without
+    pub const fn optional(path: &'static str, kind: AssetKind) -> Self {
+        Self {
+            path,
+            kind,
+            required: false,
+            size: None,
+        }
+    }
+
+    /// Expect a healthy copy to fall into `range` bytes
+    pub const fn sized(self, range: (u64, u64)) -> Self {
+        Self {
+            size: Some(range),
+            ..self
+        }
+    }
+}
+
+/// Every asset the game loads, one line each
+pub const ASSET_MANIFEST: &[AssetEntry] = &[
+    AssetEntry::required("fonts/FiraSans-Regular.ttf", Font).sized(FONT_BYTES),
+    AssetEntry::required(AUDIO_UI_CLICK, Audio).sized(SOUND_EFFECT_BYTES),
+    AssetEntry::optional(AUDIO_DICE_ROLL, Audio).sized(SOUND_EFFECT_BYTES),
+    AssetEntry::optional(AUDIO_MOVEMENT_STEP, Audio).sized(SOUND_EFFECT_BYTES),
+    AssetEntry::optional(AUDIO_DISCOVERY_CHIME, Audio).sized(SOUND_EFFECT_BYTES),
+    AssetEntry::optional(AUDIO_RESOURCE_FOUND, Audio).sized(SOUND_EFFECT_BYTES),
+    AssetEntry::optional(AUDIO_REST_COMPLETE, Audio).sized(SOUND_EFFECT_BYTES),
+    AssetEntry::optional("audio/music/theme/menu_theme.ogg", Audio).sized(TRACK_BYTES),
+    AssetEntry::optional("audio/music/theme/menu_theme2.ogg", Audio).sized(TRACK_BYTES),
+    AssetEntry::optional("audio/music/theme/menu_theme3.ogg", Audio).sized(TRACK_BYTES),
+    AssetEntry::optional("audio/music/mystery_ambient.ogg", Audio).sized(TRACK_BYTES),
+    AssetEntry::optional("audio/music/tension_discovery.ogg", Audio).sized(TRACK_BYTES),
+    AssetEntry::optional("audio/music/combat_encounter.ogg", Audio).sized(TRACK_BYTES),
+    AssetEntry::optional(AUDIO_AMBIENT_PLAINS, Audio).sized(TRACK_BYTES),
+    AssetEntry::optional(AUDIO_AMBIENT_FOREST, Audio).sized(TRACK_BYTES),
+    AssetEntry::optional(AUDIO_AMBIENT_MOUNTAINS, Audio).sized(TRACK_BYTES),
+    AssetEntry::optional(AUDIO_AMBIENT_DESERT, Audio).sized(TRACK_BYTES),
+    AssetEntry::optional(AUDIO_AMBIENT_TUNDRA, Audio).sized(TRACK_BYTES),
+    AssetEntry::optional(AUDIO_AMBIENT_SWAMP, Audio).sized(TRACK_BYTES),
+    AssetEntry::optional(AUDIO_AMBIENT_OCEAN, Audio).sized(TRACK_BYTES),
+    AssetEntry::optional(AUDIO_AMBIENT_VOLCANIC, Audio).sized(TRACK_BYTES),
+    AssetEntry::optional(AUDIO_AMBIENT_ANOMALY, Audio).sized(TRACK_BYTES),
+    AssetEntry::optional(AUDIO_AMBIENT_CONSTRUCTED, Audio).sized(TRACK_BYTES),
+    AssetEntry::optional(AUDIO_AMBIENT_CAVE, Audio).sized(TRACK_BYTES),
+    AssetEntry::optional(AUDIO_AMBIENT_CRYSTAL, Audio).sized(TRACK_BYTES),
+    AssetEntry::optional(AUDIO_AMBIENT_SPACE, Audio).sized(TRACK_BYTES),
+    AssetEntry::optional("icons/rocket.png", Image).sized(ICON_BYTES),
+    AssetEntry::optional("icons/satellite.png", Image).sized(ICON_BYTES),
+    AssetEntry::optional("icons/gear.png", Image).sized(ICON_BYTES),
+    AssetEntry::optional("icons/game_die.png", Image).sized(ICON_BYTES),
+];
+
+/// What the asset server says about one asset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadObservation {
+    /// Not loaded yet
+    Pending,
+    Loaded,
+    /// No file at the path
+    NotFound,
+    /// The file exists but could not be read or decoded
+    Failed(String),
+}
+
+impl LoadObservation {
+    /// Read a Bevy load state
+    pub fn from_load_state(state: &LoadState) -> Self {
+        match state {
+            LoadState::NotLoaded | LoadState::Loading => LoadObservation::Pending,
+            LoadState::Loaded => LoadObservation::Loaded,
+            LoadState::Failed(error) => match error.as_ref() {
+                AssetLoadError::AssetReaderError(AssetReaderError::NotFound(_))
+                | AssetLoadError::AssetReaderError(AssetReaderError::HttpError(404)) => {
+                    LoadObservation::NotFound
+                }
+                error => LoadObservation::Failed(error.to_string()),
+            },
+        }
+    }
+}
+
+/// Outcome of the check for one asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetStatus {
+    Ok,
+    /// Still loading; the check is not settled
+    Pending,
+    Missing,
+    Failed {
+        reason: String,
+    },
+    /// Loaded, but the file is outside its expected size range
+    SuspiciousSize {
+        bytes: u64,
+    },
+}
+
+/// How much a broken asset matters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetSeverity {
+    Fine,
+    Warning,
+    /// The game cannot be played as deployed
+    Blocking,
+}
+
+/// Classify one asset from its load state and, when known, its file size
+///
+/// An asset still pending after the timeout counts as failed.
+pub fn classify(
+    entry: &AssetEntry,
+    observation: &LoadObservation,
+    bytes: Option<u64>,
+    timed_out: bool,
+) -> AssetStatus {
+    match observation {
+        LoadObservation::Pending if timed_out => AssetStatus::Failed {
+            reason: format!(
+                "still loading after {} seconds",
+                ASSET_CHECK_TIMEOUT_SECONDS
+            ),
+        },
+        LoadObservation::Pending => AssetStatus::Pending,
+        LoadObservation::NotFound => AssetStatus::Missing,
+        LoadObservation::Failed(reason) => AssetStatus::Failed {
+            reason: reason.clone(),
+        },
+        LoadObservation::Loaded => match (entry.size, bytes) {
+            (Some((min, max)), Some(bytes)) if bytes < min || bytes > max => {
+                AssetStatus::SuspiciousSize { bytes }
+            }
+            _ => AssetStatus::Ok,
+        },
+    }
+}
+
+/// Severity of a status for a required or optional asset
+///
+/// Only a required asset that is missing or failed blocks; an odd size is
+/// worth a warning either way, since the file may still play.
+pub fn severity(status: &AssetStatus, required: bool) -> AssetSeverity {
+    match status {
+        AssetStatus::Ok | AssetStatus::Pending => AssetSeverity::Fine,
+        AssetStatus::SuspiciousSize { .. } => AssetSeverity::Warning,
+        AssetStatus::Missing | AssetStatus::Failed { .. } if required => AssetSeverity::Blocking,
+        AssetStatus::Missing | AssetStatus::Failed { .. } => AssetSeverity::Warning,
+    }
+}
+
+/// The check of one manifest entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetCheck {
+    pub path: String,
+    pub kind: AssetKind,
+    pub required: bool,
+    pub status: AssetStatus,
+}
+
+impl AssetCheck {
+    pub fn severity(&self) -> AssetSeverity {
+        severity(&self.status, self.required)
+    }
+
+    /// One line for the report, e.g. `missing (required) fonts/FiraSans-Regular.ttf`
+    pub fn describe(&self) -> String {
+        let importance = if self.required {
+            "required"
+        } else {
+            "optional"
+        };
+        let status = match &self.status {
+            AssetStatus::Ok => "ok".to_string(),
+            AssetStatus::Pending => "pending".to_string(),
+            AssetStatus::Missing => "missing".to_string(),
+            AssetStatus::Failed { reason } => format!("failed: {}", reason),
+            AssetStatus::SuspiciousSize { bytes } => format!("suspicious size: {} bytes", bytes),
+        };
+        format!("{} ({}) {}", status, importance, self.path)
+    }
+}
+
+/// Result of checking every manifest entry
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AssetIntegrityReport {
+    pub checks: Vec<AssetCheck>,
+}
+
+impl AssetIntegrityReport {
+    /// Check every manifest entry with the observed load state and size
+    pub fn build(
+        manifest: &[AssetEntry],
+        timed_out: bool,
+        observe: impl Fn(&AssetEntry) -> (LoadObservation, Option<u64>),
+    ) -> Self {
+        let checks = manifest
+            .iter()
+            .map(|entry| {
+                let (observation, bytes) = observe(entry);
+                AssetCheck {
+                    path: entry.path.to_string(),
+                    kind: entry.kind,
+                    required: entry.required,
+                    status: classify(entry, &observation, bytes, timed_out),
+                }
+            })
+            .collect();
+        Self { checks }
+    }
+
+    /// Whether every asset has finished loading one way or the other
+    pub fn is_settled(&self) -> bool {
+        !self
+            .checks
+            .iter()
+            .any(|check| check.status == AssetStatus::Pending)
+    }
+
+    /// Whether a required asset is missing or failed
+    pub fn is_blocking(&self) -> bool {
+        self.worst() == AssetSeverity::Blocking
+    }
+
+    /// Severity of the worst check
+    pub fn worst(&self) -> AssetSeverity {
+        self.checks
+            .iter()
+            .map(AssetCheck::severity)
+            .max()
+            .unwrap_or(AssetSeverity::Fine)
+    }
+
+    /// Checks that are not fine, worst first
+    pub fn problems(&self) -> Vec<&AssetCheck> {
+        let mut problems: Vec<&AssetCheck> = self
+            .checks
+            .iter()
+            .filter(|check| check.severity() != AssetSeverity::Fine)
+            .collect();
+        problems.sort_by_key(|check| std::cmp::Reverse(check.severity()));
+        problems
+    }
+
+    /// Counts per status, e.g. `29 ok, 1 missing, 0 failed, 0 suspicious, 0 pending`
+    pub fn summary(&self) -> String {
+        let count = |matches: fn(&AssetStatus) -> bool| {
+            self.checks
+                .iter()
+                .filter(|check| matches(&check.status))
+                .count()
+        };
+        format!(
+            "{} ok, {} missing, {} failed, {} suspicious, {} pending",
+            count(|status| *status == AssetStatus::Ok),
+            count(|status| *status == AssetStatus::Missing),
+            count(|status| matches!(status, AssetStatus::Failed { .. })),
+            count(|status| matches!(status, AssetStatus::SuspiciousSize { .. })),
+            count(|status| *status == AssetStatus::Pending),
+        )
+    }
+
+    /// Summary followed by one line per problem
+    pub fn lines(&self) -> Vec<String> {
+        std::iter::once(format!("assets: {}", self.summary()))
+            .chain(self.problems().into_iter().map(AssetCheck::describe))
+            .collect()
+    }
+}
+
+/// Size of a bundled asset on disk, looked up the way Bevy finds the assets folder
+///
+/// Always `None` on the web, where the files are fetched rather than read.
+pub fn asset_file_size(path: &str) -> Option<u64> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = path;
+        None
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let base = std::env::var("BEVY_ASSET_ROOT")
+            .or_else(|_| std::env::var("CARGO_MANIFEST_DIR"))
+            .map(std::path::PathBuf::from)
+            .ok()
+            .or_else(|| {
+                std::env::current_exe()
+                    .ok()
+                    .and_then(|exe| exe.parent().map(std::path::Path::to_path_buf))
+            })?;
+        std::fs::metadata(base.join("assets").join(path))
+            .ok()
+            .map(|metadata| metadata.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FONT: AssetEntry =
+        AssetEntry::required("fonts/test.ttf", AssetKind::Font).sized((10, 100));
+
+    #[test]
+    fn load_states_are_classified() {
+        assert_eq!(
+            classify(&FONT, &LoadObservation::Loaded, Some(50), false),
+            AssetStatus::Ok
+        );
+        // Without a known size the range is not checked
+        assert_eq!(
+            classify(&FONT, &LoadObservation::Loaded, None, false),
+            AssetStatus::Ok
+        );
+        assert_eq!(
+            classify(&FONT, &LoadObservation::Loaded, Some(0), false),
+            AssetStatus::SuspiciousSize { bytes: 0 }
+        );
+        assert_eq!(
+            classify(&FONT, &LoadObservation::Loaded, Some(101), false),
+            AssetStatus::SuspiciousSize { bytes: 101 }
+        );
+        assert_eq!(
+            classify(&FONT, &LoadObservation::NotFound, None, false),
+            AssetStatus::Missing
+        );
+        assert_eq!(
+            classify(
+                &FONT,
+                &LoadObservation::Failed("bad glyf table".to_string()),
+                None,
+                false
+            ),
+            AssetStatus::Failed {
+                reason: "bad glyf table".to_string()
+            }
+        );
+        assert_eq!(
+            classify(&FONT, &LoadObservation::Pending, None, false),
+            AssetStatus::Pending
+        );
+        assert!(matches!(
+            classify(&FONT, &LoadObservation::Pending, None, true),
+            AssetStatus::Failed { .. }
+        ));
+    }
+
+    #[test]
+    fn only_broken_required_assets_block() {
+        let music = AssetEntry::optional("audio/music/extra.ogg", AssetKind::Audio);
+        let manifest = [FONT, music];
+        let report = |font: LoadObservation, track: LoadObservation| {
+            AssetIntegrityReport::build(&manifest, false, |entry| {
+                if entry.required {
+                    (font.clone(), Some(50))
+                } else {
+                    (track.clone(), None)
+                }
+            })
+        };
+
+        let missing_music = report(LoadObservation::Loaded, LoadObservation::NotFound);
+        assert!(missing_music.is_settled());
+        assert!(!missing_music.is_blocking());
+        assert_eq!(missing_music.worst(), AssetSeverity::Warning);
+        assert_eq!(
+            missing_music.lines(),
+            vec![
+                "assets: 1 ok, 1 missing, 0 failed, 0 suspicious, 0 pending".to_string(),
+                "missing (optional) audio/music/extra.ogg".to_string(),
+            ]
+        );
+
+        let missing_font = report(LoadObservation::NotFound, LoadObservation::Loaded);
+        assert!(missing_font.is_blocking());
+        assert_eq!(missing_font.problems()[0].path, "fonts/test.ttf");
+
+        let loading = report(LoadObservation::Loaded, LoadObservation::Pending);
+        assert!(!loading.is_settled());
+        assert_eq!(loading.worst(), AssetSeverity::Fine);
+    }
+
+    #[test]
+    fn manifest_covers_the_bundled_files() {
+        let playlist = AUDIO_MUSIC_PLAYLIST
+            .iter()
+            .chain(AUDIO_MUSIC_STEMS.iter().flat_map(|stems| stems.iter()));
+        for path in playlist {
+            assert!(
+                ASSET_MANIFEST.iter().any(|entry| entry.path == *path),
+                "{} is not in the manifest",
+                path
+            );
+        }
+        let required: Vec<&str> = ASSET_MANIFEST
+            .iter()
+            .filter(|entry| entry.required)
+            .map(|entry| entry.path)
+            .collect();
+        assert_eq!(required, vec!["fonts/FiraSans-Regular.ttf", AUDIO_UI_CLICK]);
+
+        // The checked-in assets pass their own check
+        for entry in ASSET_MANIFEST {
+            let bytes = asset_file_size(entry.path);
+            assert!(bytes.is_some(), "{} is not bundled", entry.path);
+            assert_eq!(
+                classify(entry, &LoadObservation::Loaded, bytes, false),
+                AssetStatus::Ok,
+                "{}",
+                entry.path
+            );
+        }
+    }
+}
@@ -3,9 +3,16 @@
 //! This module provides the Bevy-specific implementation of the FontService domain interface.
 //! It handles font loading, asset management, and rendering configuration using Bevy's
 //! asset system following DDD principles.
+//!
+//! When the primary font is missing or broken (e.g. a web deploy where it
+//! 404s), every font type switches to Bevy's built-in font and text is
+//! reduced to what that font can draw, so the game stays readable.
 
-use crate::domain::services::font_service::{FontConfig, FontError, FontService, FontType};
+use crate::domain::services::font_service::{
+    fallback_text, FontConfig, FontError, FontService, FontSize, FontType,
+};
 use crate::domain::{DomainError, DomainResult};
+use crate::infrastructure::assets::LoadObservation;
 use bevy::prelude::*;
 use std::collections::HashMap;
 
@@ -18,6 +25,8 @@ pub struct BevyFontService {
     font_paths: FontPaths,
     /// Whether fonts have been loaded
     fonts_loaded: bool,
+    /// Whether the primary font failed and the built-in font is used instead
+    fallback_active: bool,
 }
 
 /// Font path configuration
@@ -47,6 +56,7 @@ impl BevyFontService {
             font_handles: HashMap::new(),
             font_paths: FontPaths::default(),
             fonts_loaded: false,
+            fallback_active: false,
         }
     }
 
@@ -56,6 +66,7 @@ impl BevyFontService {
             font_handles: HashMap::new(),
             font_paths,
             fonts_loaded: false,
+            fallback_active: false,
         }
     }
 
@@ -96,11 +107,56 @@ impl BevyFontService {
         Ok(())
     }
 
+    /// Whether text is drawn with the built-in fallback font
+    pub fn is_fallback_active(&self) -> bool {
+        self.fallback_active
+    }
+
+    /// Switch every font type to the built-in font once the primary font
+    /// turns out missing or broken; returns whether this call switched
+    pub fn observe_primary_font(&mut self, observation: &LoadObservation) -> bool {
+        if self.fallback_active
+            || !matches!(
+                observation,
+                LoadObservation::NotFound | LoadObservation::Failed(_)
+            )
+        {
+            return false;
+        }
+        for handle in self.font_handles.values_mut() {
+            *handle = Handle::default();
+        }
+        self.fallback_active = true;
+        true
+    }
+
+    /// `text` as it should be shown with the current font
+    pub fn display_text(&self, text: &str) -> String {
+        if self.fallback_active {
+            fallback_text(text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Text and font for `text`; never fails, even without any font loaded
+    pub fn build_text(&self, text: &str, config: FontConfig) -> (Text, TextFont) {
+        let font_size = config.size.to_pixels();
+        let font = self.create_text_font(config).unwrap_or_else(|_| TextFont {
+            font_size,
+            ..default()
+        });
+        (Text::new(self.display_text(text)), font)
+    }
+
     /// Check if fonts are ready for use
     pub fn fonts_ready(&self, asset_server: &AssetServer) -> bool {
         if !self.fonts_loaded {
             return false;
         }
+        if self.fallback_active {
+            return true;
+        }
 
         // Check if all font handles are loaded
         self.font_handles.values().all(|handle| {
@@ -178,7 +234,16 @@ impl Plugin for FontPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(BevyFontService::new())
             .add_systems(Startup, initialize_font_system)
-            .add_systems(Update, monitor_font_loading);
+            .add_systems(
+                Update,
+                (
+                    monitor_font_loading,
+                    font_fallback_system,
+                    degrade_text_system,
+                    degrade_text_span_system,
+                )
+                    .chain(),
+            );
     }
 }
 
@@ -204,37 +269,86 @@ fn monitor_font_loading(font_service: Res<BevyFontService>, asset_server: Res<As
     }
 }
 
+/// Switch to the built-in font when the primary font fails to load
+fn font_fallback_system(
+    mut font_service: ResMut<BevyFontService>,
+    asset_server: Res<AssetServer>,
+    mut text_fonts: Query<&mut TextFont>,
+) {
+    if !font_service.fonts_loaded || font_service.fallback_active {
+        return;
+    }
+    let Ok(primary) = font_service.get_font_handle(FontType::UiRegular) else {
+        return;
+    };
+    let observation = LoadObservation::from_load_state(&asset_server.load_state(&primary));
+    if !font_service.observe_primary_font(&observation) {
+        return;
+    }
+
+    warn!(
+        "🔤 Font {} could not be loaded ({:?}); using the built-in font",
+        font_service.font_paths.ui_regular, observation
+    );
+    for mut text_font in text_fonts.iter_mut() {
+        if text_font.font == primary {
+            text_font.font = Handle::default();
+        }
+    }
+}
+
+/// Reduce text to what the built-in font can draw while it is in use
+fn degrade_text_system(font_service: Res<BevyFontService>, mut texts: Query<&mut Text>) {
+    if !font_service.fallback_active {
+        return;
+    }
+    // Everything once on the switch, then only what changed; reading first
+    // keeps text that is already plain from being marked changed again
+    let switched = font_service.is_changed();
+    for mut text in texts.iter_mut() {
+        if !switched && !text.is_changed() {
+            continue;
+        }
+        let plain = fallback_text(&text.0);
+        if plain != text.0 {
+            text.0 = plain;
+        }
+    }
+}
+
+/// Reduce text spans the same way as whole texts
+fn degrade_text_span_system(font_service: Res<BevyFontService>, mut spans: Query<&mut TextSpan>) {
+    if !font_service.fallback_active {
+        return;
+    }
+    let switched = font_service.is_changed();
+    for mut span in spans.iter_mut() {
+        if !switched && !span.is_changed() {
+            continue;
+        }
+        let plain = fallback_text(&span.0);
+        if plain != span.0 {
+            span.0 = plain;
+        }
+    }
+}
+
 /// Helper function to create text with emoji support
 pub fn create_emoji_text(
+    font_service: &BevyFontService,
     text: &str,
-    size: crate::domain::services::font_service::FontSize,
+    size: FontSize,
 ) -> (Text, TextFont) {
-    let config = FontConfig::emoji(size);
-
-    // For now, create with default font - the actual font will be applied by systems
-    (
-        Text::new(text),
-        TextFont {
-            font_size: size.to_pixels(),
-            ..default()
-        },
-    )
+    font_service.build_text(text, FontConfig::emoji(size))
 }
 
 /// Helper function to create regular text
 pub fn create_regular_text(
+    font_service: &BevyFontService,
     text: &str,
-    size: crate::domain::services::font_service::FontSize,
+    size: FontSize,
 ) -> (Text, TextFont) {
-    let config = FontConfig::regular(size);
-
-    (
-        Text::new(text),
-        TextFont {
-            font_size: size.to_pixels(),
-            ..default()
-        },
-    )
+    font_service.build_text(text, FontConfig::regular(size))
 }
 
 /// Component to mark text that should use emoji fonts
@@ -273,6 +387,7 @@ pub fn apply_font_to_text(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bevy::asset::weak_handle;
 
     #[test]
     fn bevy_font_service_creation() {
@@ -300,4 +415,49 @@ mod tests {
         let service = BevyFontService::new();
         assert!(service.validate_fonts().is_err());
     }
+
+    #[test]
+    fn failed_primary_font_falls_back_to_the_built_in_font() {
+        let primary: Handle<Font> = weak_handle!("5f1a5a25-0000-4000-8000-000000000001");
+        let mut service = BevyFontService::new();
+        service
+            .font_handles
+            .insert(FontType::UiRegular, primary.clone());
+        service
+            .font_handles
+            .insert(FontType::UiEmoji, primary.clone());
+        service.fonts_loaded = true;
+
+        assert!(!service.observe_primary_font(&LoadObservation::Pending));
+        assert!(!service.observe_primary_font(&LoadObservation::Loaded));
+        assert_eq!(
+            service.get_font_handle(FontType::UiRegular).unwrap(),
+            primary
+        );
+
+        assert!(service.observe_primary_font(&LoadObservation::NotFound));
+        assert!(service.is_fallback_active());
+        assert_eq!(
+            service.get_font_handle(FontType::UiEmoji).unwrap(),
+            Handle::default()
+        );
+        assert!(!service.observe_primary_font(&LoadObservation::Failed("bad".to_string())));
+        assert_eq!(service.display_text("🎲 Roll"), "Dice Roll");
+    }
+
+    #[test]
+    fn text_is_built_without_any_font_loaded() {
+        let service = BevyFontService::new();
+        let (text, font) = create_emoji_text(&service, "🎲 Roll", FontSize::Medium);
+        assert_eq!(text.0, "🎲 Roll");
+        assert_eq!(font.font, Handle::default());
+        assert_eq!(font.font_size, FontSize::Medium.to_pixels());
+
+        let mut service = BevyFontService::new();
+        service.fonts_loaded = true;
+        service.observe_primary_font(&LoadObservation::Failed("bad".to_string()));
+        let (text, font) = create_regular_text(&service, "⚠️ Low fuel", FontSize::Small);
+        assert_eq!(text.0, "! Low fuel");
+        assert_eq!(font.font, Handle::default());
+    }
 }
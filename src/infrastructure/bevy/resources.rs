@@ -4,23 +4,138 @@
 //! providing shared access to domain entities and services across systems.
 //! Resources are designed for turn-based gameplay with dice mechanics.
 
+use crate::domain::entities::InventoryItem;
+use crate::domain::services::gear::{GearItem, GearSlot};
+use crate::domain::services::hashing::fnv1a_64;
+use crate::domain::services::inventory::ConsumableKind;
+use crate::domain::services::movement_governor::{Fatigue, GrantSource, MovementGovernor};
+use crate::domain::services::party::{Party, RunTally, TransferOffer};
+use crate::domain::services::rescue::DistressSignal;
+use crate::domain::services::resting_service::RestCycleResult;
+use crate::domain::services::{
+    DiscoveredTerrains, ExplorationGrant, FacilityBonuses, Interior, InteriorGenerator,
+    ModifierStack, RestingService, Scenario, STANDARD_DROP,
+};
 use crate::domain::{
     Base, DiceRoll, EntityId, GamePhase, GameSession, GameTime, Map, Player, Position3D,
-    ResourceType, TerrainType, WorldBoundaries,
+    ResourceCollection, ResourceType, TerrainType, TileCoordinate, WorldBoundaries,
 };
 use bevy::prelude::*;
-use std::collections::HashMap;
+use std::collections::{hash_map::Entry, HashMap};
+
+/// Most recent player changes kept for the event forwarder
+const PLAYER_CHANGE_BACKLOG: usize = 64;
+
+/// A change made to the player through the `PlayerResource` API
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayerChange {
+    MovementPointsSpent {
+        cost: u8,
+        remaining: u8,
+    },
+    MovementPointsGranted {
+        granted: u8,
+        total: u8,
+    },
+    /// A grant was cut down by the day's diminishing returns
+    MovementGrantReduced {
+        source: GrantSource,
+        requested: u8,
+        granted: u8,
+    },
+    MovementPointsLost {
+        lost: u8,
+        remaining: u8,
+    },
+    MovementPointsRestored {
+        total: u8,
+    },
+    Damaged {
+        taken: u32,
+        remaining: u32,
+    },
+    Healed {
+        healed: u32,
+        current: u32,
+    },
+    ResourceChanged {
+        resource_type: ResourceType,
+        delta: i32,
+        new_total: u32,
+    },
+    ConsumableChanged {
+        kind: ConsumableKind,
+        delta: i32,
+        remaining: u32,
+    },
+    ItemChanged {
+        item_id: String,
+        delta: i32,
+        remaining: u32,
+    },
+    ExperienceGained {
+        points: u32,
+        leveled_up: bool,
+    },
+    Relocated {
+        from: Position3D,
+        to: Position3D,
+    },
+    GearFound {
+        id: u32,
+        name: String,
+    },
+    GearEquipped {
+        slot: GearSlot,
+        id: u32,
+        displaced: Option<u32>,
+    },
+    GearUnequipped {
+        slot: GearSlot,
+        id: u32,
+    },
+    GearSalvaged {
+        name: String,
+    },
+    CharacterSwitched {
+        from: String,
+        to: String,
+    },
+}
 
 /// Bevy resource wrapper for the main player entity
+///
+/// Systems read the player freely but change it only through the
+/// intent-based methods below, which check their invariants in the same
+/// call that applies them and record a `PlayerChange` for each effect.
 #[derive(Resource, Debug, Clone)]
 pub struct PlayerResource {
-    pub player: Option<Player>,
+    player: Option<Player>,
+    changes: Vec<PlayerChange>,
+    governor: MovementGovernor,
+    /// What the base's buildings lend the player
+    facilities: FacilityBonuses,
 }
 
 impl PlayerResource {
     /// Create a new player resource
     pub fn new() -> Self {
-        Self { player: None }
+        Self {
+            player: None,
+            changes: Vec::new(),
+            governor: MovementGovernor::default(),
+            facilities: FacilityBonuses::default(),
+        }
+    }
+
+    /// Bonuses the base's buildings lend the player
+    pub fn facilities(&self) -> FacilityBonuses {
+        self.facilities
+    }
+
+    /// Take on the bonuses of the base's buildings as they stand now
+    pub fn set_facilities(&mut self, facilities: FacilityBonuses) {
+        self.facilities = facilities;
     }
 
     /// Create player and store in resource
@@ -42,8 +157,8 @@ impl PlayerResource {
         self.player.as_ref()
     }
 
-    /// Get mutable reference to current player
-    pub fn player_mut(&mut self) -> Option<&mut Player> {
+    /// Raw mutable access, bypassing change tracking
+    pub(crate) fn player_mut(&mut self) -> Option<&mut Player> {
         self.player.as_mut()
     }
 
@@ -62,44 +177,543 @@ impl PlayerResource {
         self.player.as_ref().map(|p| p.level())
     }
 
-    /// Move player to new position
-    pub fn move_player(
+    /// Get reference to current player (convenience method)
+    pub fn get_player(&self) -> Option<&Player> {
+        self.player()
+    }
+
+    /// Spend movement points, failing without any change if there are too few
+    ///
+    /// Returns the points left afterwards.
+    pub fn try_spend_movement_points(
+        &mut self,
+        cost: u8,
+    ) -> Result<u8, crate::domain::DomainError> {
+        let player = Self::require(&mut self.player)?;
+        let available = player.movement_points();
+        if available < cost {
+            return Err(crate::domain::DomainError::InsufficientResources(format!(
+                "Not enough movement points. Need: {}, Have: {}",
+                cost, available
+            )));
+        }
+        player.subtract_movement_points(cost);
+        let remaining = player.movement_points();
+        self.record(PlayerChange::MovementPointsSpent { cost, remaining });
+        Ok(remaining)
+    }
+
+    /// Grant movement points up to the player's maximum
+    ///
+    /// Every grant counts against the day's soft cap: past it grants are
+    /// halved, past twice the cap they are lost. Only the points there is
+    /// room for count. Returns the points actually granted.
+    pub fn grant_movement_points(&mut self, points: u8, source: GrantSource) -> u8 {
+        let Some(player) = self.player.as_mut() else {
+            return 0;
+        };
+        let before = player.movement_points();
+        let requested = points.min(player.max_movement_points().saturating_sub(before));
+        let allowed = self
+            .governor
+            .govern(requested, player.derived_stats().endurance);
+        player.add_movement_points(allowed);
+        let total = player.movement_points();
+        let granted = total.saturating_sub(before);
+        if allowed < requested {
+            self.record(PlayerChange::MovementGrantReduced {
+                source,
+                requested,
+                granted,
+            });
+        }
+        if granted > 0 {
+            self.record(PlayerChange::MovementPointsGranted { granted, total });
+        }
+        granted
+    }
+
+    /// Give back movement points spent on a move that never happened
+    ///
+    /// Refunds are not rewards and do not count against the day's cap.
+    pub fn refund_movement_points(&mut self, points: u8) -> u8 {
+        let Some(player) = self.player.as_mut() else {
+            return 0;
+        };
+        let before = player.movement_points();
+        player.add_movement_points(points);
+        let total = player.movement_points();
+        let granted = total.saturating_sub(before);
+        if granted > 0 {
+            self.record(PlayerChange::MovementPointsGranted { granted, total });
+        }
+        granted
+    }
+
+    /// Set the difficulty the day's movement cap is scaled by
+    pub fn set_difficulty(&mut self, difficulty: crate::domain::entities::game::DifficultyLevel) {
+        self.governor = MovementGovernor::new(difficulty);
+    }
+
+    /// How worn out the day's grants have left the player
+    pub fn movement_fatigue(&self) -> Fatigue {
+        self.player.as_ref().map_or(Fatigue::Fresh, |player| {
+            self.governor.fatigue(player.derived_stats().endurance)
+        })
+    }
+
+    /// Take away movement points as a penalty, stopping at zero
+    ///
+    /// Returns the points actually lost.
+    pub fn lose_movement_points(&mut self, points: u8) -> u8 {
+        let Some(player) = self.player.as_mut() else {
+            return 0;
+        };
+        let before = player.movement_points();
+        player.subtract_movement_points(points);
+        let remaining = player.movement_points();
+        let lost = before - remaining;
+        if lost > 0 {
+            self.record(PlayerChange::MovementPointsLost { lost, remaining });
+        }
+        lost
+    }
+
+    /// Damage the player, stopping at zero health
+    ///
+    /// Returns the damage actually taken.
+    pub fn take_damage(&mut self, amount: u32) -> u32 {
+        let Some(player) = self.player.as_mut() else {
+            return 0;
+        };
+        let taken = player.take_damage(amount);
+        let remaining = player.health().current();
+        if taken > 0 {
+            self.record(PlayerChange::Damaged { taken, remaining });
+        }
+        taken
+    }
+
+    /// Heal the player, stopping at maximum health
+    ///
+    /// Returns the health actually gained.
+    pub fn heal(&mut self, amount: u32) -> u32 {
+        let Some(player) = self.player.as_mut() else {
+            return 0;
+        };
+        let healed = player.heal(amount);
+        let current = player.health().current();
+        if healed > 0 {
+            self.record(PlayerChange::Healed { healed, current });
+        }
+        healed
+    }
+
+    /// Refill movement and action points
+    pub fn restore_movement_points(&mut self) {
+        let Some(player) = self.player.as_mut() else {
+            return;
+        };
+        player.restore_points();
+        let total = player.movement_points();
+        self.governor.reset();
+        self.record(PlayerChange::MovementPointsRestored { total });
+    }
+
+    /// Add or remove an amount of one resource
+    ///
+    /// Removing more than the player holds or going past the resource cap
+    /// fails without any change. Returns the new total.
+    pub fn apply_resource_delta(
+        &mut self,
+        resource_type: ResourceType,
+        delta: i32,
+    ) -> Result<u32, crate::domain::DomainError> {
+        let player = Self::require(&mut self.player)?;
+        let current = player.resources().get_amount(resource_type) as i64;
+        let new_total = current + delta as i64;
+        if new_total < 0 {
+            return Err(crate::domain::DomainError::InsufficientResources(format!(
+                "Not enough {}. Need: {}, Have: {}",
+                resource_type, -delta, current
+            )));
+        }
+        if new_total > crate::domain::constants::MAX_RESOURCE_AMOUNT as i64 {
+            return Err(crate::domain::DomainError::InvalidResourceAmount(
+                new_total as i32,
+            ));
+        }
+        let new_total = new_total as u32;
+        player.resources_mut().set_amount(resource_type, new_total);
+        if delta != 0 {
+            self.record(PlayerChange::ResourceChanged {
+                resource_type,
+                delta,
+                new_total,
+            });
+        }
+        Ok(new_total)
+    }
+
+    /// Add every amount of a collection; amounts past the cap are dropped
+    pub fn add_resources(&mut self, resources: &ResourceCollection) {
+        for amount in resources.amounts() {
+            if let Err(e) = self.apply_resource_delta(amount.resource_type, amount.amount as i32) {
+                warn!("Failed to add {} to player: {}", amount.resource_type, e);
+            }
+        }
+    }
+
+    /// Cargo the player can carry, with the base's storage bonus
+    pub fn carrying_capacity(&self) -> u32 {
+        self.player.as_ref().map_or(0, |player| {
+            player.carrying_capacity() + self.facilities.cargo_capacity
+        })
+    }
+
+    /// Cargo the player can still carry before reaching their capacity
+    pub fn free_carrying_capacity(&self) -> u32 {
+        self.player.as_ref().map_or(0, |player| {
+            player.inventory().free_capacity(self.carrying_capacity())
+        })
+    }
+
+    /// Pick up a find in the field through the inventory, up to the carrying capacity
+    ///
+    /// Returns what did not fit and was left behind.
+    pub fn pick_up(&mut self, found: &ResourceCollection) -> ResourceCollection {
+        let capacity = self.carrying_capacity();
+        let Some(player) = self.player.as_mut() else {
+            return found.clone();
+        };
+        let dropped = player.inventory_mut().pick_up(found, capacity);
+        let picked: Vec<PlayerChange> = found
+            .amounts()
+            .iter()
+            .filter_map(|amount| {
+                let kept = amount.amount - dropped.get_amount(amount.resource_type);
+                (kept > 0).then(|| PlayerChange::ResourceChanged {
+                    resource_type: amount.resource_type,
+                    delta: kept as i32,
+                    new_total: player.resources().get_amount(amount.resource_type),
+                })
+            })
+            .collect();
+        for change in picked {
+            self.record(change);
+        }
+        dropped
+    }
+
+    /// Pick up an item stack through the inventory, up to the carrying capacity
+    ///
+    /// Returns how many units did not fit and were left behind.
+    pub fn pick_up_item(&mut self, item: InventoryItem) -> u32 {
+        let capacity = self.carrying_capacity();
+        let Some(player) = self.player.as_mut() else {
+            return item.count;
+        };
+        let (item_id, count) = (item.item_id.clone(), item.count);
+        let refused = player.inventory_mut().add_item(item, capacity);
+        if refused < count {
+            let remaining = player.inventory().item_count(&item_id);
+            self.record(PlayerChange::ItemChanged {
+                item_id,
+                delta: (count - refused) as i32,
+                remaining,
+            });
+        }
+        refused
+    }
+
+    /// Give up units of a carried item; returns how many are left
+    pub fn remove_item(
         &mut self,
-        new_position: Position3D,
-        movement_cost: u8,
+        item_id: &str,
+        count: u32,
+    ) -> Result<u32, crate::domain::DomainError> {
+        let player = Self::require(&mut self.player)?;
+        let remaining = player.inventory_mut().remove_item(item_id, count)?;
+        self.record(PlayerChange::ItemChanged {
+            item_id: item_id.to_string(),
+            delta: -(count as i32),
+            remaining,
+        });
+        Ok(remaining)
+    }
+
+    /// Pay a cost from the player's cargo; nothing is taken unless all of it can be paid
+    pub fn try_pay_resources(
+        &mut self,
+        cost: &ResourceCollection,
     ) -> Result<(), crate::domain::DomainError> {
-        if let Some(player) = &mut self.player {
-            player.move_to(new_position, movement_cost)
-        } else {
-            Err(crate::domain::DomainError::PlayerError(
-                "No player exists".to_string(),
-            ))
+        let player = Self::require(&mut self.player)?;
+        if !player.resources().can_afford(cost) {
+            return Err(crate::domain::DomainError::InsufficientResources(format!(
+                "Cannot afford {}",
+                cost
+            )));
         }
+        for amount in cost.amounts() {
+            self.apply_resource_delta(amount.resource_type, -(amount.amount as i32))?;
+        }
+        Ok(())
     }
 
-    /// Get reference to current player (convenience method)
-    pub fn get_player(&self) -> Option<&Player> {
-        self.player()
+    /// Craft one consumable from cargo; nothing is paid unless it can be crafted
+    ///
+    /// Returns how many of the kind are held afterwards.
+    pub fn craft_consumable(
+        &mut self,
+        kind: ConsumableKind,
+    ) -> Result<u32, crate::domain::DomainError> {
+        self.try_pay_resources(&kind.craft_cost()?)?;
+        self.add_consumables(kind, 1)
+    }
+
+    /// Add consumables found in the world; returns the new count
+    pub fn add_consumables(
+        &mut self,
+        kind: ConsumableKind,
+        amount: u32,
+    ) -> Result<u32, crate::domain::DomainError> {
+        let player = Self::require(&mut self.player)?;
+        let remaining = player.consumables_mut().add(kind, amount);
+        if amount > 0 {
+            self.record(PlayerChange::ConsumableChanged {
+                kind,
+                delta: amount as i32,
+                remaining,
+            });
+        }
+        Ok(remaining)
+    }
+
+    /// Use up one consumable; returns how many are left
+    pub fn use_consumable(
+        &mut self,
+        kind: ConsumableKind,
+    ) -> Result<u32, crate::domain::DomainError> {
+        let player = Self::require(&mut self.player)?;
+        let remaining = player.consumables_mut().consume(kind)?;
+        self.record(PlayerChange::ConsumableChanged {
+            kind,
+            delta: -1,
+            remaining,
+        });
+        Ok(remaining)
+    }
+
+    /// Grant experience; returns true on level up
+    pub fn grant_experience(&mut self, points: u32) -> Result<bool, crate::domain::DomainError> {
+        let player = Self::require(&mut self.player)?;
+        let leveled_up = player.add_experience(points)?;
+        self.record(PlayerChange::ExperienceGained { points, leveled_up });
+        Ok(leveled_up)
+    }
+
+    /// Stash a piece of found gear; returns its ID
+    pub fn stow_gear(&mut self, item: GearItem) -> Result<u32, crate::domain::DomainError> {
+        let player = Self::require(&mut self.player)?;
+        let name = item.name.clone();
+        let id = player.stow_gear(item);
+        self.record(PlayerChange::GearFound { id, name });
+        Ok(id)
+    }
+
+    /// Equip a stashed piece; returns the ID of the piece it replaced
+    pub fn equip_gear(&mut self, id: u32) -> Result<Option<u32>, crate::domain::DomainError> {
+        let player = Self::require(&mut self.player)?;
+        let was_equipped = player.gear().is_equipped(id);
+        let displaced = player.equip_gear(id)?;
+        let slot = player
+            .gear()
+            .items()
+            .into_iter()
+            .find(|item| item.id == id)
+            .map(|item| item.slot());
+        if let (false, Some(slot)) = (was_equipped, slot) {
+            self.record(PlayerChange::GearEquipped {
+                slot,
+                id,
+                displaced,
+            });
+        }
+        Ok(displaced)
     }
 
-    /// Get mutable reference to current player (convenience method)
-    pub fn get_player_mut(&mut self) -> Option<&mut Player> {
-        self.player_mut()
+    /// Take off the piece in a slot; returns its ID
+    pub fn unequip_gear(&mut self, slot: GearSlot) -> Option<u32> {
+        let id = self.player.as_mut()?.unequip_gear(slot)?;
+        self.record(PlayerChange::GearUnequipped { slot, id });
+        Some(id)
     }
 
-    /// Subtract movement points from player
-    pub fn subtract_movement_points(
+    /// Take a stashed piece apart for its salvage; returns what it gave
+    pub fn salvage_gear(
         &mut self,
-        points: u8,
+        id: u32,
+    ) -> Result<ResourceCollection, crate::domain::DomainError> {
+        let player = Self::require(&mut self.player)?;
+        let item = player.salvage_gear(id)?;
+        let salvage = item.salvage_value();
+        self.record(PlayerChange::GearSalvaged { name: item.name });
+        self.add_resources(&salvage);
+        Ok(salvage)
+    }
+
+    /// Place the player on a tile without spending movement points
+    pub fn set_position(&mut self, position: Position3D) {
+        let Some(player) = self.player.as_mut() else {
+            return;
+        };
+        let from = *player.position();
+        player.relocate(position);
+        if from != position {
+            self.record(PlayerChange::Relocated { from, to: position });
+        }
+    }
+
+    /// Put the player back as they were before a move: on `from`, with
+    /// `movement_points` and every resource change of the move reversed
+    ///
+    /// Fails without any change when a change can no longer be reversed.
+    pub fn take_back_move(
+        &mut self,
+        from: Position3D,
+        movement_points: u8,
+        resource_deltas: &[(ResourceType, i32)],
     ) -> Result<(), crate::domain::DomainError> {
-        if let Some(player) = &mut self.player {
-            player.subtract_movement_points(points);
-            Ok(())
+        let player = Self::require(&mut self.player)?;
+        for &(resource_type, delta) in resource_deltas {
+            let restored = player.resources().get_amount(resource_type) as i64 - delta as i64;
+            if !(0..=crate::domain::constants::MAX_RESOURCE_AMOUNT as i64).contains(&restored) {
+                return Err(crate::domain::DomainError::InvalidResourceAmount(
+                    restored as i32,
+                ));
+            }
+        }
+        for &(resource_type, delta) in resource_deltas {
+            self.apply_resource_delta(resource_type, -delta)?;
+        }
+        let current = self
+            .player
+            .as_ref()
+            .map_or(0, |player| player.movement_points());
+        if movement_points > current {
+            self.refund_movement_points(movement_points - current);
         } else {
-            Err(crate::domain::DomainError::PlayerError(
-                "No player exists".to_string(),
-            ))
+            self.lose_movement_points(current - movement_points);
         }
+        self.set_position(from);
+        Ok(())
+    }
+
+    /// Run a night of rest on the player
+    pub fn rest(
+        &mut self,
+        resting_service: &RestingService,
+        position: Position3D,
+        modifiers: &ModifierStack,
+    ) -> Result<RestCycleResult, crate::domain::DomainError> {
+        let player = Self::require(&mut self.player)?;
+        let result = resting_service.process_rest_cycle(
+            player,
+            position,
+            modifiers,
+            self.facilities.rest_roll_bonus,
+        )?;
+        let total = player.movement_points();
+        let health = player.health().current();
+        self.governor.reset();
+        let gained: Vec<PlayerChange> = result
+            .resources_gained
+            .amounts()
+            .iter()
+            .map(|amount| PlayerChange::ResourceChanged {
+                resource_type: amount.resource_type,
+                delta: amount.amount as i32,
+                new_total: player.resources().get_amount(amount.resource_type),
+            })
+            .collect();
+        self.record(PlayerChange::MovementPointsRestored { total });
+        if result.health_restored > 0 {
+            self.record(PlayerChange::Healed {
+                healed: result.health_restored,
+                current: health,
+            });
+        }
+        for change in gained {
+            self.record(change);
+        }
+        Ok(result)
+    }
+
+    /// Hand control to the party's waiting character
+    ///
+    /// The character in play and their open signal go to the bench and the
+    /// waiting character takes their place. Returns the new party slot.
+    pub fn switch_character(
+        &mut self,
+        party: &mut Party,
+        signal: &mut Option<DistressSignal>,
+    ) -> Result<usize, crate::domain::DomainError> {
+        let player = Self::require(&mut self.player)?;
+        let from = player.name().to_string();
+        let slot = party.take_over(player, signal)?;
+        let to = player.name().to_string();
+        self.record(PlayerChange::CharacterSwitched { from, to });
+        Ok(slot)
+    }
+
+    /// Exchange cargo with the party's waiting character, all or nothing
+    pub fn exchange_with(
+        &mut self,
+        party: &mut Party,
+        offer: &TransferOffer,
+    ) -> Result<(), crate::domain::DomainError> {
+        let player = Self::require(&mut self.player)?;
+        let before = player.resources().clone();
+        party.exchange(player, offer)?;
+        let changes: Vec<PlayerChange> = ResourceType::all()
+            .into_iter()
+            .filter_map(|resource_type| {
+                let new_total = player.resources().get_amount(resource_type);
+                let delta = new_total as i32 - before.get_amount(resource_type) as i32;
+                (delta != 0).then_some(PlayerChange::ResourceChanged {
+                    resource_type,
+                    delta,
+                    new_total,
+                })
+            })
+            .collect();
+        for change in changes {
+            self.record(change);
+        }
+        Ok(())
+    }
+
+    /// Check if changes were recorded since the last drain
+    pub fn has_changes(&self) -> bool {
+        !self.changes.is_empty()
+    }
+
+    /// Take the changes recorded since the last call, oldest first
+    pub fn drain_changes(&mut self) -> Vec<PlayerChange> {
+        std::mem::take(&mut self.changes)
+    }
+
+    fn require(player: &mut Option<Player>) -> Result<&mut Player, crate::domain::DomainError> {
+        player
+            .as_mut()
+            .ok_or_else(|| crate::domain::DomainError::PlayerError("No player exists".to_string()))
+    }
+
+    fn record(&mut self, change: PlayerChange) {
+        if self.changes.len() >= PLAYER_CHANGE_BACKLOG {
+            self.changes.remove(0);
+        }
+        self.changes.push(change);
     }
 }
 
@@ -109,6 +723,57 @@ impl Default for PlayerResource {
     }
 }
 
+/// Bevy resource wrapper for the pass-and-play party
+///
+/// Empty for single-player runs. While a party plays, the character in
+/// play lives in `PlayerResource` as usual and the other one waits here.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PartyResource {
+    party: Option<Party>,
+    /// Cargo exchange being put together, while its panel is open
+    pub offer: Option<TransferOffer>,
+}
+
+impl PartyResource {
+    /// Create a party resource for a single-player run
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start or end pass-and-play for the run
+    pub fn set_party(&mut self, party: Option<Party>) {
+        self.party = party;
+        self.offer = None;
+    }
+
+    /// The party, when the run has one
+    pub fn party(&self) -> Option<&Party> {
+        self.party.as_ref()
+    }
+
+    /// Mutable access to the party, e.g. to close a day
+    pub fn party_mut(&mut self) -> Option<&mut Party> {
+        self.party.as_mut()
+    }
+
+    /// Check if the run is played in pass-and-play turns
+    pub fn is_active(&self) -> bool {
+        self.party.is_some()
+    }
+
+    /// Check if the device is being passed on between days
+    pub fn is_handover(&self) -> bool {
+        self.party
+            .as_ref()
+            .is_some_and(|party| party.turns().is_handover())
+    }
+
+    /// Check if the handover or the exchange panel holds up movement
+    pub fn blocks_movement(&self) -> bool {
+        self.is_handover() || self.offer.is_some()
+    }
+}
+
 /// Bevy resource wrapper for the player's base
 #[derive(Resource, Debug, Clone)]
 pub struct BaseResource {
@@ -327,7 +992,8 @@ pub struct GameTimerResource {
     pub last_event_check: f32,
     pub last_resource_update: f32,
     pub paused_time: f32,
-    pub real_time: f32,
+    /// Seconds of play, fed from the sim clock so pauses do not count
+    pub active_time: f32,
 }
 
 impl GameTimerResource {
@@ -340,13 +1006,13 @@ impl GameTimerResource {
             last_event_check: 0.0,
             last_resource_update: 0.0,
             paused_time: 0.0,
-            real_time: 0.0,
+            active_time: 0.0,
         }
     }
 
-    /// Update game time
+    /// Update game time by `delta_time` seconds of play
     pub fn update(&mut self, delta_time: f32) {
-        self.real_time += delta_time;
+        self.active_time += delta_time;
         if self.phase != GamePhase::Paused {
             self.game_time = self.game_time.advance_by_seconds(delta_time as u32);
         }
@@ -366,29 +1032,29 @@ impl GameTimerResource {
 
     /// Check if enough time has passed for event check
     pub fn should_check_events(&self) -> bool {
-        self.real_time - self.last_event_check
+        self.active_time - self.last_event_check
             >= crate::domain::constants::EVENT_CHECK_INTERVAL as f32
     }
 
     /// Record event check time
     pub fn record_event_check(&mut self) {
-        self.last_event_check = self.real_time;
+        self.last_event_check = self.active_time;
     }
 
     /// Check if enough time has passed for resource update
     pub fn should_update_resources(&self) -> bool {
-        self.real_time - self.last_resource_update
+        self.active_time - self.last_resource_update
             >= crate::domain::constants::RESOURCE_GATHERING_TIME as f32
     }
 
     /// Record resource update time
     pub fn record_resource_update(&mut self) {
-        self.last_resource_update = self.real_time;
+        self.last_resource_update = self.active_time;
     }
 
     /// Pause timer
     pub fn pause(&mut self) {
-        self.paused_time = self.real_time;
+        self.paused_time = self.active_time;
         self.phase = GamePhase::Paused;
     }
 
@@ -407,7 +1073,7 @@ impl GameTimerResource {
         self.last_event_check = 0.0;
         self.last_resource_update = 0.0;
         self.paused_time = 0.0;
-        self.real_time = 0.0;
+        self.active_time = 0.0;
     }
 
     /// Get current game phase
@@ -442,8 +1108,28 @@ pub struct GameStatsResource {
     pub successful_rolls: u32,
     pub critical_successes: u32,
     pub critical_failures: u32,
+    pub assisted_rolls: u32,
     pub tiles_explored: u32,
+    /// All experience of the run, exploration included
     pub experience_gained: u32,
+    /// Part of `experience_gained` earned by charting new tiles
+    pub exploration_experience: u32,
+    /// Terrain types charted this run, for first discovery bonuses
+    pub discovered_terrains: DiscoveredTerrains,
+    pub nights_rested: u32,
+    /// Run played with the blitz countdown
+    pub blitz: bool,
+    /// Moves the blitz autopilot made after the countdown ran out
+    pub autopilot_moves: u32,
+    /// Moves taken back in a relaxed run
+    pub moves_undone: u32,
+    /// Difficulty and mutators of the run, fixed when it starts
+    pub modifiers: ModifierStack,
+    /// Scenario the run started with
+    pub scenario: Scenario,
+    /// Share of the score the run keeps, in percent; hardcore runs pay for
+    /// their Emergency Recall here
+    pub score_percent: u32,
     pub game_duration: f32,
 }
 
@@ -458,8 +1144,18 @@ impl GameStatsResource {
             successful_rolls: 0,
             critical_successes: 0,
             critical_failures: 0,
+            assisted_rolls: 0,
             tiles_explored: 0,
             experience_gained: 0,
+            exploration_experience: 0,
+            discovered_terrains: DiscoveredTerrains::new(),
+            nights_rested: 0,
+            blitz: false,
+            autopilot_moves: 0,
+            moves_undone: 0,
+            modifiers: ModifierStack::default(),
+            scenario: Scenario::standard_drop(),
+            score_percent: 100,
             game_duration: 0.0,
         }
     }
@@ -495,14 +1191,158 @@ impl GameStatsResource {
         }
     }
 
+    /// Record a roll that received a Fortune's Favor bonus
+    pub fn record_assisted_roll(&mut self) {
+        self.assisted_rolls += 1;
+    }
+
+    /// Transparency line for the end-of-run summary, if any roll was assisted
+    pub fn assist_summary(&self) -> Option<String> {
+        match self.assisted_rolls {
+            0 => None,
+            1 => Some("Fortune's Favor assisted 1 roll".to_string()),
+            n => Some(format!("Fortune's Favor assisted {} rolls", n)),
+        }
+    }
+
+    /// Record a move the blitz autopilot made
+    pub fn record_autopilot_move(&mut self) {
+        self.autopilot_moves += 1;
+    }
+
+    /// Run summary line for blitz runs
+    pub fn blitz_summary(&self) -> Option<String> {
+        if !self.blitz {
+            return None;
+        }
+        Some(match self.autopilot_moves {
+            0 => "Blitz run - every decision made in time".to_string(),
+            1 => "Blitz run - the autopilot moved once".to_string(),
+            n => format!("Blitz run - the autopilot moved {} times", n),
+        })
+    }
+
+    /// Record a move taken back
+    pub fn record_undo(&mut self) {
+        self.moves_undone += 1;
+    }
+
+    /// Transparency line for the end-of-run summary, if any move was undone
+    pub fn undo_summary(&self) -> Option<String> {
+        match self.moves_undone {
+            0 => None,
+            1 => Some("1 move undone".to_string()),
+            n => Some(format!("{} moves undone", n)),
+        }
+    }
+
+    /// Counters a pass-and-play party credits to its characters
+    pub fn run_tally(&self) -> RunTally {
+        let gathered: i32 = self.resources_gathered.values().sum();
+        RunTally {
+            tiles_explored: self.tiles_explored,
+            experience_gained: self.experience_gained,
+            resources_gathered: gathered.max(0) as u32,
+        }
+    }
+
     /// Record tile exploration
     pub fn record_tile_explored(&mut self) {
         self.tiles_explored += 1;
     }
 
-    /// Record experience gain
+    /// Record experience gain, as changed by the run's modifiers
     pub fn record_experience_gain(&mut self, amount: u32) {
-        self.experience_gained += amount;
+        self.experience_gained += self.modifiers.experience(amount);
+    }
+
+    /// Record exploring `tile`, the one way exploration experience is earned
+    ///
+    /// The grant comes back with its experience after the run's modifiers.
+    /// A tile that was explored already earns nothing.
+    pub fn record_exploration(
+        &mut self,
+        tile: &crate::domain::entities::MapTile,
+    ) -> Option<ExplorationGrant> {
+        let mut grant = self.discovered_terrains.explore(tile)?;
+        grant.experience = self.modifiers.experience(grant.experience);
+        self.experience_gained += grant.experience;
+        self.exploration_experience += grant.experience;
+        Some(grant)
+    }
+
+    /// Experience from everything but exploration: events, encounters, rescues
+    pub fn event_experience(&self) -> u32 {
+        self.experience_gained
+            .saturating_sub(self.exploration_experience)
+    }
+
+    /// Summary line splitting the run's experience by source
+    pub fn experience_summary(&self) -> String {
+        format!(
+            "Experience: {} ({} exploring, {} from events)",
+            self.experience_gained,
+            self.exploration_experience,
+            self.event_experience()
+        )
+    }
+
+    /// Record a night of rest, which ends the current day
+    pub fn record_rest(&mut self) {
+        self.nights_rested += 1;
+    }
+
+    /// Current day of the run, starting at day 1
+    pub fn current_day(&self) -> u32 {
+        self.nights_rested + 1
+    }
+
+    /// Run score: experience plus everything gathered, times the score share
+    pub fn run_score(&self) -> u32 {
+        let gathered: i32 = self.resources_gathered.values().sum();
+        (self.experience_gained + gathered.max(0) as u32) * self.score_percent / 100
+    }
+
+    /// Run summary line naming the run's mutators, if any
+    pub fn mutator_summary(&self) -> Option<String> {
+        let mutators = self.modifiers.mutators();
+        if mutators.is_empty() {
+            return None;
+        }
+        Some(format!("Mutators: {}", mutators.names()))
+    }
+
+    /// Run summary line naming the starting scenario, unless it was a
+    /// Standard Drop
+    pub fn scenario_summary(&self) -> Option<String> {
+        (self.scenario.id != STANDARD_DROP).then(|| format!("Start: {}", self.scenario.name))
+    }
+
+    /// Hash identifying the score together with the rules it was made under
+    ///
+    /// Two runs only share a hash if they reached the same score on the same
+    /// day with the same ruleset, so scores made with different mutators or
+    /// starting scenarios cannot be passed off as each other.
+    pub fn score_hash(&self) -> u64 {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&self.run_score().to_le_bytes());
+        bytes.extend_from_slice(&self.current_day().to_le_bytes());
+        bytes.push(self.blitz as u8);
+        bytes.push(self.modifiers.difficulty() as u8);
+        bytes.extend(
+            self.modifiers
+                .mutators()
+                .iter()
+                .map(|mutator| mutator.code()),
+        );
+        // Standard Drop runs hash as they did before scenarios existed
+        if self.scenario.id != STANDARD_DROP {
+            bytes.extend_from_slice(self.scenario.id.as_bytes());
+        }
+        if self.score_percent != 100 {
+            bytes.extend_from_slice(&self.score_percent.to_le_bytes());
+        }
+        fnv1a_64(&bytes)
     }
 
     /// Update game duration
@@ -519,8 +1359,18 @@ impl GameStatsResource {
         self.successful_rolls = 0;
         self.critical_successes = 0;
         self.critical_failures = 0;
+        self.assisted_rolls = 0;
         self.tiles_explored = 0;
         self.experience_gained = 0;
+        self.exploration_experience = 0;
+        self.discovered_terrains = DiscoveredTerrains::new();
+        self.nights_rested = 0;
+        self.blitz = false;
+        self.autopilot_moves = 0;
+        self.moves_undone = 0;
+        self.modifiers = ModifierStack::default();
+        self.scenario = Scenario::standard_drop();
+        self.score_percent = 100;
         self.game_duration = 0.0;
     }
 
@@ -562,10 +1412,25 @@ impl Default for GameStatsResource {
     }
 }
 
+/// Which map the player is currently on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ActiveMapHandle {
+    /// The procedurally generated surface
+    #[default]
+    Overworld,
+    /// A ruin interior, keyed by the overworld tile it is entered from
+    Interior(TileCoordinate),
+}
+
 /// Resource for managing current map state
 #[derive(Resource, Debug, Clone)]
 pub struct MapResource {
-    pub current_map: Option<Map>,
+    /// The overworld; interiors are stored separately
+    pub overworld: Option<Map>,
+    /// Interiors generated so far, kept so looted tiles stay looted
+    pub interiors: HashMap<TileCoordinate, Interior>,
+    /// Map that movement, rendering and exploration currently use
+    pub active: ActiveMapHandle,
     pub loaded_chunks: HashMap<(i32, i32), bool>, // Track which map chunks are loaded
     pub visible_area: (Position3D, Position3D),   // Min and max positions currently visible
 }
@@ -574,31 +1439,104 @@ impl MapResource {
     /// Create new map resource
     pub fn new() -> Self {
         Self {
-            current_map: None,
+            overworld: None,
+            interiors: HashMap::new(),
+            active: ActiveMapHandle::Overworld,
             loaded_chunks: HashMap::new(),
             visible_area: (Position3D::new(0, 0, 0), Position3D::new(0, 0, 0)),
         }
     }
 
-    /// Load a new map
+    /// Load a new overworld, discarding interiors of the previous one
     pub fn load_map(&mut self, map: Map) {
-        self.current_map = Some(map);
+        self.overworld = Some(map);
+        self.interiors.clear();
+        self.active = ActiveMapHandle::Overworld;
         self.loaded_chunks.clear();
     }
 
-    /// Get reference to current map
+    /// Get reference to the active map
     pub fn current_map(&self) -> Option<&Map> {
-        self.current_map.as_ref()
+        match self.active {
+            ActiveMapHandle::Overworld => self.overworld.as_ref(),
+            ActiveMapHandle::Interior(site) => self.interiors.get(&site).map(Interior::map),
+        }
     }
 
-    /// Get mutable reference to current map
+    /// Get mutable reference to the active map
     pub fn current_map_mut(&mut self) -> Option<&mut Map> {
-        self.current_map.as_mut()
+        match self.active {
+            ActiveMapHandle::Overworld => self.overworld.as_mut(),
+            ActiveMapHandle::Interior(site) => self.interiors.get_mut(&site).map(Interior::map_mut),
+        }
     }
 
     /// Check if map is loaded
     pub fn has_map(&self) -> bool {
-        self.current_map.is_some()
+        self.current_map().is_some()
+    }
+
+    /// Get the overworld regardless of the active map
+    pub fn overworld(&self) -> Option<&Map> {
+        self.overworld.as_ref()
+    }
+
+    /// Handle of the active map
+    pub fn active_map(&self) -> ActiveMapHandle {
+        self.active
+    }
+
+    /// Check if the player is inside an interior
+    pub fn is_in_interior(&self) -> bool {
+        matches!(self.active, ActiveMapHandle::Interior(_))
+    }
+
+    /// The interior the player is in, if any
+    pub fn active_interior(&self) -> Option<&Interior> {
+        match self.active {
+            ActiveMapHandle::Interior(site) => self.interiors.get(&site),
+            ActiveMapHandle::Overworld => None,
+        }
+    }
+
+    /// Mutable access to the interior the player is in, if any
+    pub fn active_interior_mut(&mut self) -> Option<&mut Interior> {
+        match self.active {
+            ActiveMapHandle::Interior(site) => self.interiors.get_mut(&site),
+            ActiveMapHandle::Overworld => None,
+        }
+    }
+
+    /// Make the interior behind `site` active, generating it on first visit
+    ///
+    /// Returns the interior's entrance, or `None` when already inside or
+    /// there is no overworld to derive the interior from.
+    pub fn enter_interior(&mut self, site: TileCoordinate) -> Option<Position3D> {
+        if self.is_in_interior() {
+            return None;
+        }
+        let seed = self.overworld.as_ref()?.seed();
+        if let Entry::Vacant(slot) = self.interiors.entry(site) {
+            match InteriorGenerator::new().generate(seed, site) {
+                Ok(interior) => {
+                    slot.insert(interior);
+                }
+                Err(e) => {
+                    error!("Failed to generate interior at {:?}: {}", site, e);
+                    return None;
+                }
+            }
+        }
+        self.active = ActiveMapHandle::Interior(site);
+        self.interiors.get(&site).map(Interior::entrance)
+    }
+
+    /// Return to the overworld, yielding the site the interior was entered from
+    pub fn exit_interior(&mut self) -> Option<TileCoordinate> {
+        match std::mem::take(&mut self.active) {
+            ActiveMapHandle::Interior(site) => Some(site),
+            ActiveMapHandle::Overworld => None,
+        }
     }
 
     /// Update visible area based on camera/player position
@@ -650,43 +1588,73 @@ impl MapResource {
     /// Get or create map around a given position
     /// This method ensures there's always a map available for gameplay
     pub fn get_or_create_map(&mut self, center_position: Position3D) -> &Map {
-        if self.current_map.is_none() {
-            // Create a new map with procedural generation
-            let map_id = EntityId::generate();
-            let map_name = format!(
-                "Sector {}-{}",
-                center_position.x / 100,
-                center_position.y / 100
-            );
+        if self.overworld.is_none() {
             let seed = ((center_position.x as u64) << 32) | (center_position.y as u64);
+            self.generate_overworld(center_position, seed);
+        }
 
-            let mut new_map = Map::new(map_id, map_name, seed).expect("Failed to create new map");
+        self.current_map().unwrap()
+    }
 
-            // Use MapService for generation
-            let map_service = crate::domain::services::MapService::new(seed);
-            if let Err(e) = map_service.generate_chunk(&mut new_map, center_position, 20) {
-                error!("Failed to generate map chunk: {}", e);
-                // Continue with empty map rather than failing
-            }
+    /// Replace the overworld with a new one generated from `seed`
+    pub fn generate_overworld(&mut self, center_position: Position3D, seed: u64) -> &Map {
+        // Create a new map with procedural generation
+        let map_id = EntityId::generate();
+        let map_name = format!(
+            "Sector {}-{}",
+            center_position.x / 100,
+            center_position.y / 100
+        );
+
+        let mut new_map = Map::new(map_id, map_name, seed).expect("Failed to create new map");
+
+        // Use MapService for generation
+        let map_service = crate::domain::services::MapService::new(seed);
+        if let Err(e) = map_service.generate_chunk(&mut new_map, center_position, 20) {
+            error!("Failed to generate map chunk: {}", e);
+            // Continue with empty map rather than failing
+        }
 
-            // Ensure the immediate area is also generated
-            self.ensure_area_generated(center_position);
+        self.load_map(new_map);
 
-            self.current_map = Some(new_map);
+        info!(
+            "🗺️ Generated new map sector at ({}, {}) from seed {}",
+            center_position.x, center_position.y, seed
+        );
 
+        self.current_map().unwrap()
+    }
+
+    /// Curate the spawn area of a new overworld for a fair first day
+    ///
+    /// Charts the tiles within `reveal_radius` of the spawn when a mutator
+    /// asks for it.
+    pub fn prepare_start_area(&mut self, spawn: Position3D, reveal_radius: u32) {
+        let Some(map) = self.overworld.as_mut() else {
+            return;
+        };
+        let map_service = crate::domain::services::MapService::new(map.seed());
+        match map_service.shape_spawn_area(map, spawn) {
+            Ok(report) => info!(
+                "🌱 Spawn area shaped: {} tiles softened, {} resource nodes added, path carved: {}",
+                report.tiles_softened, report.nodes_added, report.path_carved
+            ),
+            Err(e) => warn!("Failed to shape spawn area: {}", e),
+        }
+
+        if reveal_radius > 0 {
+            let charted = crate::domain::services::chart_start_area(map, spawn, reveal_radius);
             info!(
-                "🗺️ Generated new map sector at ({}, {})",
-                center_position.x, center_position.y
+                "🗺️ Charted {} tiles within {} of the spawn",
+                charted, reveal_radius
             );
         }
-
-        self.current_map.as_ref().unwrap()
     }
 
     /// Get or create map around a given position (mutable reference)
     /// This method ensures there's always a map available for gameplay
     pub fn get_or_create_map_mut(&mut self, center_position: Position3D) -> &mut Map {
-        if self.current_map.is_none() {
+        if self.overworld.is_none() {
             // Create a new map with procedural generation
             let map_id = EntityId::generate();
             let map_name = format!(
@@ -704,46 +1672,10 @@ impl MapResource {
                 .generate_chunk(&mut new_map, center_position, 8)
                 .expect("Failed to generate map chunk");
 
-            self.current_map = Some(new_map);
-        }
-
-        self.current_map.as_mut().unwrap()
-    }
-
-    /// Ensure the area around a position is generated
-    fn ensure_area_generated(&mut self, position: Position3D) {
-        let chunk_size = 20;
-        let chunk_x = position.x / chunk_size;
-        let chunk_y = position.y / chunk_size;
-
-        // Check if we need to generate surrounding chunks
-        for dx in -1..=1 {
-            for dy in -1..=1 {
-                let check_chunk_x = chunk_x + dx;
-                let check_chunk_y = chunk_y + dy;
-
-                if !self.is_chunk_loaded(check_chunk_x, check_chunk_y) {
-                    if let Some(map) = &mut self.current_map {
-                        let chunk_center = Position3D::new(
-                            check_chunk_x * chunk_size,
-                            check_chunk_y * chunk_size,
-                            position.z,
-                        );
-
-                        let map_service = crate::domain::services::MapService::new(map.seed());
-                        if let Err(e) = map_service.generate_chunk(map, chunk_center, chunk_size) {
-                            warn!(
-                                "Failed to generate chunk at ({}, {}): {}",
-                                check_chunk_x, check_chunk_y, e
-                            );
-                        } else {
-                            self.mark_chunk_loaded(check_chunk_x, check_chunk_y);
-                            debug!("Generated chunk at ({}, {})", check_chunk_x, check_chunk_y);
-                        }
-                    }
-                }
-            }
+            self.overworld = Some(new_map);
         }
+
+        self.current_map_mut().unwrap()
     }
 }
 
@@ -844,6 +1776,274 @@ mod tests {
         assert_eq!(resource.player_position(), Some(pos));
     }
 
+    fn resource_with_player() -> PlayerResource {
+        let mut resource = PlayerResource::new();
+        let stats = crate::domain::PlayerStats::new(10, 10, 10, 10, 10, 10).unwrap();
+        resource
+            .create_player(
+                "test_player_id".to_string(),
+                "Test Player".to_string(),
+                Position3D::origin(),
+                stats,
+            )
+            .unwrap();
+        resource
+    }
+
+    #[test]
+    fn pickups_stop_at_carrying_capacity() {
+        let mut resource = resource_with_player();
+        let free = resource.free_carrying_capacity();
+        let metal_before = resource
+            .player()
+            .unwrap()
+            .resources()
+            .get_amount(ResourceType::Metal);
+
+        let mut found = ResourceCollection::new();
+        found.set_amount(ResourceType::Metal, free + 12);
+        let dropped = resource.pick_up(&found);
+        assert_eq!(dropped.get_amount(ResourceType::Metal), 12);
+        assert_eq!(
+            resource
+                .player()
+                .unwrap()
+                .resources()
+                .get_amount(ResourceType::Metal),
+            metal_before + free
+        );
+        assert_eq!(resource.free_carrying_capacity(), 0);
+
+        // A full hold leaves the whole find behind
+        assert_eq!(resource.pick_up(&found), found);
+    }
+
+    fn movement_points(resource: &PlayerResource) -> u8 {
+        resource.player().unwrap().movement_points()
+    }
+
+    #[test]
+    fn spending_never_goes_negative() {
+        let mut resource = resource_with_player();
+        let start = movement_points(&resource);
+
+        assert_eq!(resource.try_spend_movement_points(1).unwrap(), start - 1);
+        assert!(resource.try_spend_movement_points(start).is_err());
+        assert_eq!(movement_points(&resource), start - 1);
+
+        assert_eq!(resource.lose_movement_points(u8::MAX), start - 1);
+        assert_eq!(movement_points(&resource), 0);
+        assert_eq!(resource.lose_movement_points(1), 0);
+    }
+
+    #[test]
+    fn grants_are_capped_at_maximum() {
+        let mut resource = resource_with_player();
+        let max = resource.player().unwrap().max_movement_points();
+        resource.try_spend_movement_points(2).unwrap();
+
+        assert_eq!(
+            resource.grant_movement_points(10, GrantSource::EventReward),
+            2
+        );
+        assert_eq!(movement_points(&resource), max);
+        assert_eq!(
+            resource.grant_movement_points(1, GrantSource::EventReward),
+            0
+        );
+    }
+
+    /// Spend `points`, grant them back from `source` and refund any shortfall
+    fn spend_and_grant(resource: &mut PlayerResource, points: u8, source: GrantSource) -> u8 {
+        resource.try_spend_movement_points(points).unwrap();
+        let granted = resource.grant_movement_points(points, source);
+        resource.refund_movement_points(points - granted);
+        granted
+    }
+
+    #[test]
+    fn every_grant_source_is_governed_until_rest() {
+        for source in GrantSource::all() {
+            let mut resource = resource_with_player();
+            // Endurance 10 on Normal: a soft cap of 8 points a day
+            for _ in 0..8 {
+                assert_eq!(spend_and_grant(&mut resource, 1, source), 1);
+            }
+            assert_eq!(spend_and_grant(&mut resource, 2, source), 1);
+            assert_eq!(resource.movement_fatigue(), Fatigue::Tired);
+            for _ in 0..3 {
+                spend_and_grant(&mut resource, 2, source);
+            }
+            assert_eq!(resource.movement_fatigue(), Fatigue::Exhausted);
+            resource.drain_changes();
+            assert_eq!(spend_and_grant(&mut resource, 2, source), 0);
+            assert_eq!(
+                resource.drain_changes()[1],
+                PlayerChange::MovementGrantReduced {
+                    source,
+                    requested: 2,
+                    granted: 0
+                }
+            );
+
+            resource.restore_movement_points();
+            assert_eq!(resource.movement_fatigue(), Fatigue::Fresh);
+            assert_eq!(spend_and_grant(&mut resource, 2, source), 2);
+        }
+    }
+
+    #[test]
+    fn refunds_do_not_count_against_the_day() {
+        let mut resource = resource_with_player();
+        for _ in 0..20 {
+            resource.try_spend_movement_points(1).unwrap();
+            assert_eq!(resource.refund_movement_points(1), 1);
+        }
+        assert_eq!(resource.movement_fatigue(), Fatigue::Fresh);
+    }
+
+    #[test]
+    fn resource_delta_rejects_overdraw_and_cap() {
+        let mut resource = resource_with_player();
+        let metal = resource
+            .player()
+            .unwrap()
+            .resources()
+            .get_amount(ResourceType::Metal);
+
+        assert_eq!(
+            resource
+                .apply_resource_delta(ResourceType::Metal, 5)
+                .unwrap(),
+            metal + 5
+        );
+        assert!(resource
+            .apply_resource_delta(ResourceType::Metal, -(metal as i32) - 6)
+            .is_err());
+        assert!(resource
+            .apply_resource_delta(
+                ResourceType::Metal,
+                crate::domain::constants::MAX_RESOURCE_AMOUNT as i32
+            )
+            .is_err());
+        assert_eq!(
+            resource
+                .player()
+                .unwrap()
+                .resources()
+                .get_amount(ResourceType::Metal),
+            metal + 5
+        );
+    }
+
+    #[test]
+    fn salvaging_gear_pays_metal_and_records_it() {
+        use crate::domain::services::gear::{GearBonus, GearRarity};
+
+        let mut resource = resource_with_player();
+        let metal = resource
+            .player()
+            .unwrap()
+            .resources()
+            .get_amount(ResourceType::Metal);
+        let id = resource
+            .stow_gear(GearItem::new(GearBonus::Endurance, GearRarity::Rare))
+            .unwrap();
+        resource.equip_gear(id).unwrap();
+        assert!(resource.salvage_gear(id).is_err());
+
+        assert_eq!(resource.unequip_gear(GearSlot::Suit), Some(id));
+        let salvage = resource.salvage_gear(id).unwrap();
+        assert_eq!(
+            resource
+                .player()
+                .unwrap()
+                .resources()
+                .get_amount(ResourceType::Metal),
+            metal + salvage.get_amount(ResourceType::Metal)
+        );
+
+        let changes = resource.drain_changes();
+        assert!(matches!(changes[0], PlayerChange::GearFound { .. }));
+        assert!(changes.contains(&PlayerChange::GearSalvaged {
+            name: "Tuned Padded Exosuit".to_string()
+        }));
+        assert!(matches!(
+            changes.last(),
+            Some(PlayerChange::ResourceChanged {
+                resource_type: ResourceType::Metal,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn paying_a_cost_is_all_or_nothing() {
+        let mut resource = resource_with_player();
+        let cargo = resource.player().unwrap().resources().clone();
+        let metal = cargo.get_amount(ResourceType::Metal);
+
+        let too_much = ResourceCollection::cost(&[
+            (ResourceType::Metal, 1),
+            (
+                ResourceType::ExoticMatter,
+                cargo.get_amount(ResourceType::ExoticMatter) + 1,
+            ),
+        ])
+        .unwrap();
+        assert!(resource.try_pay_resources(&too_much).is_err());
+        assert_eq!(resource.player().unwrap().resources(), &cargo);
+
+        let affordable = ResourceCollection::cost(&[(ResourceType::Metal, metal)]).unwrap();
+        resource.try_pay_resources(&affordable).unwrap();
+        assert_eq!(
+            resource
+                .player()
+                .unwrap()
+                .resources()
+                .get_amount(ResourceType::Metal),
+            0
+        );
+    }
+
+    #[test]
+    fn changes_are_recorded_once_per_effect() {
+        let mut resource = resource_with_player();
+        resource.try_spend_movement_points(1).unwrap();
+        resource.grant_movement_points(1, GrantSource::Exploration);
+        resource.set_position(Position3D::new(2, 0, 0));
+        // No-ops record nothing
+        resource.grant_movement_points(1, GrantSource::Exploration);
+        resource.set_position(Position3D::new(2, 0, 0));
+
+        let changes = resource.drain_changes();
+        assert_eq!(changes.len(), 3);
+        assert_eq!(
+            changes[2],
+            PlayerChange::Relocated {
+                from: Position3D::origin(),
+                to: Position3D::new(2, 0, 0)
+            }
+        );
+        assert!(!resource.has_changes());
+    }
+
+    #[test]
+    fn points_cannot_change_between_validation_and_spending() {
+        // Old shape: validate, let another system spend, then subtract
+        // saturating. The move went through with points it no longer had.
+        let mut resource = resource_with_player();
+        let cost = movement_points(&resource);
+        let validated = movement_points(&resource) >= cost;
+        resource.try_spend_movement_points(1).unwrap();
+        assert!(validated);
+
+        // New shape: the check and the spend are one call, so the stale
+        // validation cannot be acted on
+        assert!(resource.try_spend_movement_points(cost).is_err());
+        assert_eq!(movement_points(&resource), cost - 1);
+    }
+
     #[test]
     fn base_resource_creation() {
         let mut resource = BaseResource::new();
@@ -926,6 +2126,134 @@ mod tests {
         assert_eq!(stats.total_resources_gathered(), 0);
     }
 
+    #[test]
+    fn game_stats_counts_assisted_rolls() {
+        let mut stats = GameStatsResource::new();
+        assert_eq!(stats.assist_summary(), None);
+
+        stats.record_assisted_roll();
+        stats.record_assisted_roll();
+        assert_eq!(stats.assisted_rolls, 2);
+        assert_eq!(
+            stats.assist_summary().as_deref(),
+            Some("Fortune's Favor assisted 2 rolls")
+        );
+
+        stats.reset();
+        assert_eq!(stats.assisted_rolls, 0);
+
+        stats.record_undo();
+        assert_eq!(stats.undo_summary().as_deref(), Some("1 move undone"));
+    }
+
+    #[test]
+    fn game_stats_flags_blitz_runs() {
+        let mut stats = GameStatsResource::new();
+        stats.record_autopilot_move();
+        assert_eq!(stats.blitz_summary(), None);
+
+        stats.blitz = true;
+        stats.record_autopilot_move();
+        assert_eq!(
+            stats.blitz_summary().as_deref(),
+            Some("Blitz run - the autopilot moved 2 times")
+        );
+
+        stats.reset();
+        assert!(!stats.blitz);
+        assert_eq!(stats.autopilot_moves, 0);
+    }
+
+    #[test]
+    fn game_stats_apply_and_hash_the_run_mutators() {
+        use crate::domain::entities::game::DifficultyLevel;
+        use crate::domain::services::{EmergencyRecall, Mutator, Mutators, RunMode};
+
+        let mut plain = GameStatsResource::new();
+        plain.record_experience_gain(50);
+        assert_eq!(plain.mutator_summary(), None);
+
+        let mut charted = GameStatsResource::new();
+        charted.modifiers = ModifierStack::new(
+            DifficultyLevel::Normal,
+            Mutators::new([Mutator::NightOwl, Mutator::Cartographer]),
+        );
+        charted.record_experience_gain(50);
+        assert_eq!(charted.experience_gained, 40);
+        assert_eq!(
+            charted.mutator_summary().as_deref(),
+            Some("Mutators: Cartographer, Night Owl")
+        );
+
+        // Same score and day, different rules: different hash
+        charted.experience_gained = plain.experience_gained;
+        assert_eq!(charted.run_score(), plain.run_score());
+        assert_ne!(charted.score_hash(), plain.score_hash());
+        let mut replay = GameStatsResource::new();
+        replay.record_experience_gain(50);
+        assert_eq!(replay.score_hash(), plain.score_hash());
+
+        let mut crashed = GameStatsResource::new();
+        crashed.record_experience_gain(50);
+        crashed.scenario = Scenario::crash_survivor();
+        assert_eq!(
+            crashed.scenario_summary().as_deref(),
+            Some("Start: Crash Survivor")
+        );
+        assert_ne!(crashed.score_hash(), plain.score_hash());
+        assert_eq!(plain.scenario_summary(), None);
+
+        // A hardcore run with the Emergency Recall keeps 80% of its score
+        let mut recalled = GameStatsResource::new();
+        recalled.record_experience_gain(50);
+        recalled.score_percent = EmergencyRecall::for_run(RunMode::Hardcore, true).score_percent();
+        assert_eq!(recalled.run_score(), 40);
+        assert_ne!(recalled.score_hash(), plain.score_hash());
+        recalled.reset();
+        assert_eq!(recalled.score_percent, 100);
+
+        charted.reset();
+        assert_eq!(charted.mutator_summary(), None);
+    }
+
+    #[test]
+    fn game_stats_split_exploration_experience() {
+        use crate::domain::entities::game::DifficultyLevel;
+        use crate::domain::entities::MapTile;
+        use crate::domain::services::{Mutator, Mutators};
+        use crate::domain::value_objects::terrain::Elevation;
+
+        let mut stats = GameStatsResource::new();
+        stats.modifiers = ModifierStack::new(
+            DifficultyLevel::Normal,
+            Mutators::new([Mutator::Cartographer]),
+        );
+        let ridge = MapTile::new(TerrainType::Mountains, Elevation::sea_level(), false);
+        let plain = stats.modifiers.experience(25);
+        let grant = stats.record_exploration(&ridge).unwrap();
+        assert!(grant.first_discovery);
+        assert_eq!(grant.experience, plain);
+        assert_eq!(
+            stats.record_exploration(&ridge).unwrap().experience,
+            stats.modifiers.experience(5)
+        );
+        stats.record_experience_gain(50);
+
+        assert_eq!(
+            stats.exploration_experience,
+            plain + stats.modifiers.experience(5)
+        );
+        assert_eq!(stats.event_experience(), stats.modifiers.experience(50));
+        assert_eq!(
+            stats.experience_gained,
+            stats.exploration_experience + stats.event_experience()
+        );
+
+        stats.reset();
+        assert_eq!(stats.exploration_experience, 0);
+        assert!(stats.record_exploration(&ridge).unwrap().first_discovery);
+    }
+
     #[test]
     fn map_resource_functionality() {
         let mut map_resource = MapResource::new();
@@ -947,6 +2275,49 @@ mod tests {
         assert!(map_resource.is_chunk_loaded(0, 0));
     }
 
+    #[test]
+    fn map_handle_switches_between_overworld_and_interior() {
+        let mut map_resource = MapResource::new();
+        // A fixed id; generated ids only differ across milliseconds
+        let overworld = Map::new(EntityId::new(1), "Surface".to_string(), 77).unwrap();
+        let overworld_id = *overworld.id();
+        map_resource.load_map(overworld);
+        let site = TileCoordinate::new(4, -2, 0);
+
+        let entrance = map_resource.enter_interior(site).unwrap();
+        assert_eq!(map_resource.active_map(), ActiveMapHandle::Interior(site));
+        let interior_map = map_resource.current_map().unwrap();
+        assert_ne!(*interior_map.id(), overworld_id);
+        assert!(interior_map.is_passable(&entrance));
+
+        // Already inside: no nesting
+        assert!(map_resource.enter_interior(site).is_none());
+
+        assert_eq!(map_resource.exit_interior(), Some(site));
+        assert_eq!(map_resource.active_map(), ActiveMapHandle::Overworld);
+        assert_eq!(*map_resource.current_map().unwrap().id(), overworld_id);
+        assert_eq!(map_resource.exit_interior(), None);
+    }
+
+    #[test]
+    fn looted_interiors_stay_looted() {
+        let mut map_resource = MapResource::new();
+        map_resource.load_map(Map::new(EntityId::generate(), "Surface".to_string(), 77).unwrap());
+        let site = TileCoordinate::new(1, 1, 0);
+
+        map_resource.enter_interior(site);
+        let interior = map_resource.active_interior_mut().unwrap();
+        let loot = interior.remaining_loot()[0];
+        let remaining = interior.remaining_loot().len();
+        assert!(interior.take_loot(loot).is_some());
+        map_resource.exit_interior();
+
+        map_resource.enter_interior(site);
+        let interior = map_resource.active_interior().unwrap();
+        assert_eq!(interior.remaining_loot().len(), remaining - 1);
+        assert!(!interior.remaining_loot().contains(&loot));
+    }
+
     #[test]
     fn dice_ui_resource_functionality() {
         let mut dice_ui = DiceUIResource::new();
@@ -0,0 +1,408 @@
+//! Bug Reports - One versioned JSON bundle with everything needed to triage
+//!
+//! A bug report gathers the current session as a save, the settings, the
+//! recent recorded events, the tail of the game log, the world seed, the
+//! build and platform, a short performance summary, the startup asset
+//! check and the sizes of the last save written. It never carries file paths or the player's account name: known
+//! private strings are scrubbed from free text before the bundle is written. When a report grows past the
+//! size cap, the oldest recorded events are dropped first.
+
+use crate::infrastructure::assets::AssetIntegrityReport;
+use crate::infrastructure::build_info::BuildInfo;
+use crate::infrastructure::saves::{
+    active_packs, last_save_sizes, SaveData, SaveEnvelope, SaveSizes, SAVE_VERSION,
+};
+use crate::infrastructure::settings::SettingsFile;
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+/// Version written by this build
+pub const BUG_REPORT_VERSION: u32 = 3;
+
+/// Number of game log entries included in a report
+pub const BUG_REPORT_LOG_ENTRIES: usize = 200;
+
+/// Number of recorded events kept for the next report
+pub const BUG_REPORT_EVENT_CAPACITY: usize = 256;
+
+/// Largest report written, in bytes of JSON
+pub const BUG_REPORT_MAX_BYTES: usize = 512 * 1024;
+
+/// Stand-in for scrubbed private text
+const REDACTED: &str = "<redacted>";
+
+/// Shortest private string worth scrubbing; shorter ones match too much
+const MIN_PRIVATE_LEN: usize = 3;
+
+/// Kind of JSON value a report section holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SectionKind {
+    String,
+    Number,
+    Array,
+    Object,
+    OptionalNumber,
+    OptionalObject,
+}
+
+impl SectionKind {
+    fn accepts(&self, value: &Value) -> bool {
+        match self {
+            SectionKind::String => value.is_string(),
+            SectionKind::Number => value.is_u64(),
+            SectionKind::Array => value.is_array(),
+            SectionKind::Object => value.is_object(),
+            SectionKind::OptionalNumber => value.is_null() || value.is_u64(),
+            SectionKind::OptionalObject => value.is_null() || value.is_object(),
+        }
+    }
+}
+
+/// Sections of the current version, the kind of value each holds and the
+/// version that introduced it
+const BUG_REPORT_SECTIONS: [(&str, SectionKind, u32); 12] = [
+    ("version", SectionKind::Number, 1),
+    ("app_version", SectionKind::String, 1),
+    ("platform", SectionKind::String, 1),
+    ("world_seed", SectionKind::OptionalNumber, 1),
+    ("save", SectionKind::OptionalObject, 1),
+    ("settings", SectionKind::Object, 1),
+    ("events", SectionKind::Array, 1),
+    ("events_dropped", SectionKind::Number, 1),
+    ("log", SectionKind::Array, 1),
+    ("performance", SectionKind::Object, 1),
+    ("assets", SectionKind::OptionalObject, 2),
+    ("save_sizes", SectionKind::OptionalObject, 3),
+];
+
+/// An event captured by the recorder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Frame the event was seen on
+    pub frame: u32,
+    pub description: String,
+}
+
+/// A game log entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportLogEntry {
+    pub sequence: u64,
+    pub timestamp: DateTime<Utc>,
+    /// Game log category, e.g. `Movement`
+    pub kind: String,
+    pub message: String,
+}
+
+/// Frame timing at the moment of the report
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PerformanceSummary {
+    /// Frames run since startup
+    pub frames: u32,
+    /// Smoothed frames per second, if measured
+    pub fps: Option<f64>,
+    /// Smoothed frame time in milliseconds, if measured
+    pub frame_time_ms: Option<f64>,
+}
+
+/// The complete bug report document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BugReport {
+    pub version: u32,
+    /// Game version that wrote the report
+    pub app_version: String,
+    /// Browser user agent on the web, OS and architecture on native
+    pub platform: String,
+    pub world_seed: Option<u64>,
+    /// The running session, as it would be saved
+    pub save: Option<SaveEnvelope<SaveData>>,
+    pub settings: SettingsFile,
+    /// Recorded events, oldest first
+    pub events: Vec<RecordedEvent>,
+    /// Events dropped to keep the report under the size cap
+    pub events_dropped: u32,
+    /// Tail of the game log, oldest first
+    pub log: Vec<ReportLogEntry>,
+    pub performance: PerformanceSummary,
+    /// Startup asset check, if it ran
+    #[serde(default)]
+    pub assets: Option<AssetIntegrityReport>,
+    /// Compressed and uncompressed size of the last save written, if any
+    #[serde(default)]
+    pub save_sizes: Option<SaveSizes>,
+}
+
+impl BugReport {
+    /// Bundle the given parts as the current version for this build
+    pub fn new(
+        world_seed: Option<u64>,
+        save: Option<SaveData>,
+        settings: SettingsFile,
+        events: Vec<RecordedEvent>,
+        log: Vec<ReportLogEntry>,
+        performance: PerformanceSummary,
+        assets: Option<AssetIntegrityReport>,
+    ) -> Self {
+        Self {
+            version: BUG_REPORT_VERSION,
+            app_version: BuildInfo::current().version_string(),
+            platform: platform_string(),
+            world_seed,
+            save: save.map(|data| SaveEnvelope {
+                version: SAVE_VERSION,
+                created_with: BuildInfo::current().version_string(),
+                packs: active_packs(),
+                data,
+            }),
+            settings,
+            events,
+            events_dropped: 0,
+            log,
+            performance,
+            assets,
+            save_sizes: last_save_sizes(),
+        }
+    }
+
+    /// Replace every occurrence of the given private strings in free text
+    pub fn scrub(&mut self, private: &[String]) {
+        for event in &mut self.events {
+            event.description = scrub_text(&event.description, private);
+        }
+        for entry in &mut self.log {
+            entry.message = scrub_text(&entry.message, private);
+        }
+        if let Some(save) = &mut self.save {
+            save.data.player.name = scrub_text(&save.data.player.name, private);
+            save.data.base.name = scrub_text(&save.data.base.name, private);
+        }
+    }
+
+    /// Serialize, dropping the oldest events until the report fits `max_bytes`
+    ///
+    /// Only events are dropped; a report that is still too large once they
+    /// are all gone is returned as is.
+    pub fn to_json_capped(&mut self, max_bytes: usize) -> InfrastructureResult<String> {
+        loop {
+            let json = serde_json::to_string_pretty(self).map_err(|e| {
+                InfrastructureError::ExternalServiceError(format!(
+                    "failed to serialize bug report: {}",
+                    e
+                ))
+            })?;
+            if json.len() <= max_bytes || self.events.is_empty() {
+                return Ok(json);
+            }
+
+            let mut excess = json.len() - max_bytes;
+            while excess > 0 && !self.events.is_empty() {
+                let event = self.events.remove(0);
+                let size = serde_json::to_string_pretty(&event).map_or(1, |text| text.len());
+                excess = excess.saturating_sub(size);
+                self.events_dropped += 1;
+            }
+        }
+    }
+}
+
+/// Check that `value` is a bug report this build can read
+pub fn validate_bug_report(value: &Value) -> Result<(), String> {
+    let object = value
+        .as_object()
+        .ok_or_else(|| "a bug report must be a JSON object".to_string())?;
+    let version = object
+        .get("version")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| "missing section 'version'".to_string())?;
+    if version == 0 || version > BUG_REPORT_VERSION as u64 {
+        return Err(format!(
+            "unsupported bug report version {} (this build writes {})",
+            version, BUG_REPORT_VERSION
+        ));
+    }
+    // Sections added after the report was written are not expected
+    for (section, kind, since) in BUG_REPORT_SECTIONS {
+        if (since as u64) > version {
+            continue;
+        }
+        let entry = object
+            .get(section)
+            .ok_or_else(|| format!("missing section '{}'", section))?;
+        if !kind.accepts(entry) {
+            return Err(format!("section '{}' should be {:?}", section, kind));
+        }
+    }
+    Ok(())
+}
+
+/// Platform the game runs on, without anything identifying the player
+pub fn platform_string() -> String {
+    #[cfg(target_arch = "wasm32")]
+    {
+        crate::infrastructure::web::utils::get_user_agent()
+            .unwrap_or_else(|| "web (unknown browser)".to_string())
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        format!("{} {}", std::env::consts::OS, std::env::consts::ARCH)
+    }
+}
+
+/// Home directory, account name and working directory of this process
+///
+/// Empty on the web, where none of these are visible to the game.
+pub fn private_strings() -> Vec<String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        Vec::new()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let mut private: Vec<String> = ["HOME", "USERPROFILE", "USER", "USERNAME"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .collect();
+        if let Ok(dir) = std::env::current_dir() {
+            private.push(dir.display().to_string());
+        }
+        private
+    }
+}
+
+/// Replace the private strings in `text`, longest first
+pub fn scrub_text(text: &str, private: &[String]) -> String {
+    let mut private: Vec<&String> = private
+        .iter()
+        .filter(|value| value.len() >= MIN_PRIVATE_LEN)
+        .collect();
+    private.sort_by_key(|value| std::cmp::Reverse(value.len()));
+    private.into_iter().fold(text.to_string(), |text, value| {
+        text.replace(value.as_str(), REDACTED)
+    })
+}
+
+/// File name for a report written at `now`
+pub fn bug_report_file_name(now: DateTime<Utc>) -> String {
+    format!("bug_report_{}.json", now.format("%Y%m%d_%H%M%S"))
+}
+
+/// Write a serialized report to `path`
+pub fn write_bug_report(path: &Path, json: &str) -> InfrastructureResult<()> {
+    std::fs::write(path, json).map_err(|e| {
+        InfrastructureError::ExternalServiceError(format!("failed to write bug report: {}", e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_report(events: usize) -> BugReport {
+        let events = (0..events as u32)
+            .map(|frame| RecordedEvent {
+                frame,
+                description: format!("MovementPointsSpent {{ cost: 1, remaining: {} }}", frame),
+            })
+            .collect();
+        let log = vec![ReportLogEntry {
+            sequence: 7,
+            timestamp: Utc::now(),
+            kind: "System".to_string(),
+            message: "failed to read /home/ada/space-looter/settings.json".to_string(),
+        }];
+        BugReport::new(
+            Some(42),
+            Some(SaveData::default()),
+            SettingsFile::default(),
+            events,
+            log,
+            PerformanceSummary {
+                frames: 600,
+                fps: Some(59.8),
+                frame_time_ms: Some(16.7),
+            },
+            Some(AssetIntegrityReport::default()),
+        )
+    }
+
+    #[test]
+    fn every_section_is_present_and_private_text_is_scrubbed() {
+        let mut report = sample_report(3);
+        report.scrub(&["/home/ada/space-looter".to_string()]);
+        let json = report.to_json_capped(BUG_REPORT_MAX_BYTES).unwrap();
+        let value: Value = serde_json::from_str(&json).unwrap();
+
+        for (section, _, _) in BUG_REPORT_SECTIONS {
+            assert!(value.get(section).is_some(), "missing {}", section);
+        }
+        assert_eq!(validate_bug_report(&value), Ok(()));
+        assert_eq!(value["world_seed"], 42);
+        assert_eq!(value["save"]["version"], SAVE_VERSION);
+        assert_eq!(value["events"].as_array().unwrap().len(), 3);
+        assert!(!json.contains("/home/ada"));
+        assert_eq!(
+            report.log[0].message,
+            "failed to read <redacted>/settings.json"
+        );
+        let parsed: BugReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.events, report.events);
+    }
+
+    #[test]
+    fn validation_rejects_missing_sections_wrong_kinds_and_newer_versions() {
+        let json = sample_report(1)
+            .to_json_capped(BUG_REPORT_MAX_BYTES)
+            .unwrap();
+        let valid: Value = serde_json::from_str(&json).unwrap();
+
+        let mut missing = valid.clone();
+        missing.as_object_mut().unwrap().remove("settings");
+        assert_eq!(
+            validate_bug_report(&missing),
+            Err("missing section 'settings'".to_string())
+        );
+
+        let mut wrong_kind = valid.clone();
+        wrong_kind["log"] = Value::String("nothing".to_string());
+        assert!(validate_bug_report(&wrong_kind).is_err());
+
+        let mut newer = valid.clone();
+        newer["version"] = Value::from(BUG_REPORT_VERSION + 1);
+        assert!(validate_bug_report(&newer)
+            .unwrap_err()
+            .contains("unsupported bug report version"));
+
+        // Version 1 reports predate the asset check
+        let mut first_version = valid.clone();
+        first_version["version"] = Value::from(1);
+        first_version.as_object_mut().unwrap().remove("assets");
+        assert_eq!(validate_bug_report(&first_version), Ok(()));
+
+        let mut no_session = valid;
+        no_session["save"] = Value::Null;
+        no_session["world_seed"] = Value::Null;
+        assert_eq!(validate_bug_report(&no_session), Ok(()));
+    }
+
+    #[test]
+    fn size_cap_drops_the_oldest_events_first() {
+        let uncapped = sample_report(200)
+            .to_json_capped(BUG_REPORT_MAX_BYTES)
+            .unwrap();
+        let cap = uncapped.len() - 2_000;
+
+        let mut report = sample_report(200);
+        let json = report.to_json_capped(cap).unwrap();
+        assert!(json.len() <= cap);
+        assert!(report.events_dropped > 0);
+        assert_eq!(report.events.len() as u32 + report.events_dropped, 200);
+        assert_eq!(report.events[0].frame, report.events_dropped);
+        assert_eq!(report.events.last().unwrap().frame, 199);
+        assert_eq!(report.log.len(), 1);
+
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(validate_bug_report(&value), Ok(()));
+    }
+}
@@ -0,0 +1,170 @@
+//! Build Info - What build is running, fixed at compile time
+//!
+//! The crate version, git hash, build date, target and enabled features are
+//! baked in when the game is compiled. The git hash and build date come from
+//! the `SPACE_LOOTER_GIT_HASH` and `SPACE_LOOTER_BUILD_DATE` environment
+//! variables, which the build scripts export; a build without them reports
+//! `unknown`. The version string goes into saves and bug reports so every
+//! artifact can be traced back to the build that wrote it.
+
+/// Stand-in for build details that were not provided
+pub const UNKNOWN_BUILD_DETAIL: &str = "unknown";
+
+/// Cargo features compiled into this build
+const ENABLED_FEATURES: &[(&str, bool)] = &[("dev-tools", cfg!(feature = "dev-tools"))];
+
+/// Details of the running build
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    pub git_hash: &'static str,
+    pub build_date: &'static str,
+    /// Target architecture and OS, e.g. `wasm32-unknown`
+    pub target: String,
+    pub features: Vec<&'static str>,
+}
+
+/// The run being played, for the about screen and copied build info
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunInfo {
+    pub world_seed: Option<u64>,
+    /// Session start, in milliseconds since the epoch
+    pub session_id: Option<u64>,
+}
+
+impl BuildInfo {
+    /// The build this binary was compiled as
+    pub fn current() -> Self {
+        Self::from_parts(
+            env!("CARGO_PKG_VERSION"),
+            option_env!("SPACE_LOOTER_GIT_HASH"),
+            option_env!("SPACE_LOOTER_BUILD_DATE"),
+            format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS),
+            ENABLED_FEATURES
+                .iter()
+                .filter(|(_, enabled)| *enabled)
+                .map(|(name, _)| *name)
+                .collect(),
+        )
+    }
+
+    /// Assemble build info, filling in missing details
+    pub fn from_parts(
+        version: &'static str,
+        git_hash: Option<&'static str>,
+        build_date: Option<&'static str>,
+        target: String,
+        features: Vec<&'static str>,
+    ) -> Self {
+        let or_unknown = |detail: Option<&'static str>| {
+            detail
+                .map(str::trim)
+                .filter(|detail| !detail.is_empty())
+                .unwrap_or(UNKNOWN_BUILD_DETAIL)
+        };
+        Self {
+            version,
+            git_hash: or_unknown(git_hash),
+            build_date: or_unknown(build_date),
+            target,
+            features,
+        }
+    }
+
+    /// Version and git hash, e.g. `0.2.0 (1a2b3c4)`
+    pub fn version_string(&self) -> String {
+        format!("{} ({})", self.version, self.git_hash)
+    }
+
+    /// Enabled features, sorted and comma separated, or `none`
+    pub fn feature_list(&self) -> String {
+        if self.features.is_empty() {
+            return "none".to_string();
+        }
+        let mut features = self.features.clone();
+        features.sort_unstable();
+        features.join(", ")
+    }
+
+    /// Lines shown on the about screen
+    pub fn lines(&self, run: Option<&RunInfo>) -> Vec<String> {
+        let mut lines = vec![
+            format!("Space Looter {}", self.version_string()),
+            format!("Built: {}", self.build_date),
+            format!("Target: {}", self.target),
+            format!("Features: {}", self.feature_list()),
+        ];
+        if let Some(run) = run {
+            if let Some(seed) = run.world_seed {
+                lines.push(format!("World seed: {}", seed));
+            }
+            if let Some(session_id) = run.session_id {
+                lines.push(format!("Session: {}", session_id));
+            }
+        }
+        lines
+    }
+
+    /// Text put on the clipboard by "copy build info"
+    pub fn copy_text(&self, run: Option<&RunInfo>) -> String {
+        self.lines(run).join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(git_hash: Option<&'static str>, features: Vec<&'static str>) -> BuildInfo {
+        BuildInfo::from_parts(
+            "0.2.0",
+            git_hash,
+            Some("2026-10-16"),
+            "wasm32-unknown".to_string(),
+            features,
+        )
+    }
+
+    #[test]
+    fn missing_details_fall_back_to_unknown() {
+        let info = build(None, Vec::new());
+        assert_eq!(info.git_hash, UNKNOWN_BUILD_DETAIL);
+        assert_eq!(info.version_string(), "0.2.0 (unknown)");
+        assert_eq!(build(Some("  "), Vec::new()).git_hash, UNKNOWN_BUILD_DETAIL);
+        assert_eq!(
+            build(Some("1a2b3c4"), Vec::new()).version_string(),
+            "0.2.0 (1a2b3c4)"
+        );
+        assert_eq!(BuildInfo::current().version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn features_are_listed_sorted() {
+        assert_eq!(build(None, Vec::new()).feature_list(), "none");
+        assert_eq!(
+            build(None, vec!["dev-tools", "audio"]).feature_list(),
+            "audio, dev-tools"
+        );
+    }
+
+    #[test]
+    fn copied_text_includes_the_run_when_there_is_one() {
+        let info = build(Some("1a2b3c4"), vec!["dev-tools"]);
+        assert_eq!(
+            info.copy_text(None),
+            "Space Looter 0.2.0 (1a2b3c4)\nBuilt: 2026-10-16\nTarget: wasm32-unknown\nFeatures: dev-tools"
+        );
+
+        let run = RunInfo {
+            world_seed: Some(42),
+            session_id: Some(1_700_000_000_000),
+        };
+        let text = info.copy_text(Some(&run));
+        assert!(text.ends_with("\nWorld seed: 42\nSession: 1700000000000"));
+        let seedless = RunInfo {
+            world_seed: None,
+            ..run
+        };
+        assert!(!info.copy_text(Some(&seedless)).contains("World seed"));
+    }
+}
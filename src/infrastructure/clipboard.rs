@@ -0,0 +1,87 @@
+//! Clipboard - Putting text where the player can paste it, and reading it back
+//!
+//! On the web the text goes to the browser clipboard. Native builds have no
+//! clipboard dependency, so the text is written to a file next to the save
+//! instead and the caller tells the player where to find it.
+//!
+//! Reading the browser clipboard needs a promise the game loop cannot wait
+//! on, so pasting on the web asks for the text in a prompt the player pastes
+//! into. Native builds read the same fallback file copying writes, which the
+//! player can also fill with a text editor.
+
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
+
+/// Where copied text ended up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CopyOutcome {
+    /// On the system clipboard
+    Clipboard,
+    /// Written to the named file
+    File(String),
+}
+
+/// Copy `text`, falling back to `fallback_file` where there is no clipboard
+#[cfg(target_arch = "wasm32")]
+pub fn copy_text(text: &str, _fallback_file: &str) -> InfrastructureResult<CopyOutcome> {
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let window = web_sys::window()
+        .ok_or_else(|| InfrastructureError::WebError("No window object".to_string()))?;
+    let clipboard = js_sys::Reflect::get(&window.navigator(), &JsValue::from_str("clipboard"))
+        .ok()
+        .filter(|clipboard| !clipboard.is_undefined())
+        .ok_or_else(|| InfrastructureError::WebError("Clipboard not available".to_string()))?;
+    let write_text = js_sys::Reflect::get(&clipboard, &JsValue::from_str("writeText"))
+        .ok()
+        .and_then(|write_text| write_text.dyn_into::<js_sys::Function>().ok())
+        .ok_or_else(|| InfrastructureError::WebError("Clipboard cannot write text".to_string()))?;
+    // The returned promise is not awaited; a refusal only shows in the console
+    write_text
+        .call1(&clipboard, &JsValue::from_str(text))
+        .map_err(|_| InfrastructureError::WebError("Clipboard write failed".to_string()))?;
+    Ok(CopyOutcome::Clipboard)
+}
+
+/// Copy `text`, falling back to `fallback_file` where there is no clipboard
+#[cfg(not(target_arch = "wasm32"))]
+pub fn copy_text(text: &str, fallback_file: &str) -> InfrastructureResult<CopyOutcome> {
+    std::fs::write(fallback_file, text).map_err(|e| {
+        InfrastructureError::ExternalServiceError(format!(
+            "failed to write {}: {}",
+            fallback_file, e
+        ))
+    })?;
+    Ok(CopyOutcome::File(fallback_file.to_string()))
+}
+
+/// Text the player pasted, or `None` if there was nothing to paste
+#[cfg(target_arch = "wasm32")]
+pub fn paste_text(prompt: &str, _fallback_file: &str) -> InfrastructureResult<Option<String>> {
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let window = web_sys::window()
+        .ok_or_else(|| InfrastructureError::WebError("No window object".to_string()))?;
+    let ask = js_sys::Reflect::get(&window, &JsValue::from_str("prompt"))
+        .ok()
+        .and_then(|ask| ask.dyn_into::<js_sys::Function>().ok())
+        .ok_or_else(|| InfrastructureError::WebError("Prompt not available".to_string()))?;
+    // A cancelled prompt answers null
+    let answer = ask
+        .call1(&window, &JsValue::from_str(prompt))
+        .map_err(|_| InfrastructureError::WebError("Prompt failed".to_string()))?;
+    Ok(answer.as_string().filter(|text| !text.trim().is_empty()))
+}
+
+/// Text the player pasted, or `None` if there was nothing to paste
+#[cfg(not(target_arch = "wasm32"))]
+pub fn paste_text(_prompt: &str, fallback_file: &str) -> InfrastructureResult<Option<String>> {
+    match std::fs::read_to_string(fallback_file) {
+        Ok(text) if !text.trim().is_empty() => Ok(Some(text)),
+        Ok(_) => Ok(None),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(InfrastructureError::ExternalServiceError(format!(
+            "failed to read {}: {}",
+            fallback_file, e
+        ))),
+    }
+}
@@ -0,0 +1,255 @@
+//! Completion - Ranked prefix completion for typed commands
+//!
+//! A `CommandRegistry` declares every command with its arguments. Completing
+//! a line looks at the word under the cursor (the end of the line): the
+//! first word completes against command names, later words against the
+//! completer the command declares for that argument, if any. Completion only
+//! ever proposes text to put in place of that word; nothing is executed.
+//! `rank_prefix` is usable on its own for any list of names, such as data
+//! pack ids or asset paths.
+
+use crate::domain::value_objects::{ResourceType, TerrainType};
+
+/// Candidates starting with `prefix`, best first
+///
+/// Matching ignores case. An exact match ranks first, then candidates whose
+/// case also matches the prefix, then shorter ones, then alphabetical order.
+/// Duplicates are dropped.
+pub fn rank_prefix<I, S>(prefix: &str, candidates: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let lowered = prefix.to_lowercase();
+    let mut matches: Vec<(bool, bool, String)> = candidates
+        .into_iter()
+        .map(|candidate| candidate.as_ref().to_string())
+        .filter(|candidate| candidate.to_lowercase().starts_with(&lowered))
+        .map(|candidate| {
+            let inexact = candidate.to_lowercase() != lowered;
+            let case_differs = !candidate.starts_with(prefix);
+            (inexact, case_differs, candidate)
+        })
+        .collect();
+    matches.sort_by(|a, b| (a.0, a.1, a.2.len(), &a.2).cmp(&(b.0, b.1, b.2.len(), &b.2)));
+    let mut ranked: Vec<String> = Vec::with_capacity(matches.len());
+    for (_, _, candidate) in matches {
+        if !ranked.contains(&candidate) {
+            ranked.push(candidate);
+        }
+    }
+    ranked
+}
+
+/// Where the values of an argument come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgCompleter {
+    /// Resource type names
+    Resource,
+    /// Terrain type names
+    Terrain,
+    /// Points of interest on the current map
+    PoiId,
+    /// A fixed list of words
+    Choices(&'static [&'static str]),
+}
+
+impl ArgCompleter {
+    /// Every value this completer knows of
+    pub fn candidates(&self, context: &CompletionContext) -> Vec<String> {
+        match self {
+            ArgCompleter::Resource => ResourceType::all()
+                .into_iter()
+                .map(|resource| format!("{:?}", resource))
+                .collect(),
+            ArgCompleter::Terrain => TerrainType::all()
+                .into_iter()
+                .map(|terrain| format!("{:?}", terrain))
+                .collect(),
+            ArgCompleter::PoiId => context.poi_ids.clone(),
+            ArgCompleter::Choices(words) => words.iter().map(|word| word.to_string()).collect(),
+        }
+    }
+}
+
+/// Live game data completers may draw from, gathered by the caller
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompletionContext {
+    /// Ids of the points of interest currently on the map
+    pub poi_ids: Vec<String>,
+}
+
+/// One argument of a command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgSpec {
+    pub name: &'static str,
+    pub completer: Option<ArgCompleter>,
+}
+
+/// A command the registry can complete and hint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub args: Vec<ArgSpec>,
+}
+
+impl CommandSpec {
+    /// Command without arguments yet
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            args: Vec::new(),
+        }
+    }
+
+    /// Add an argument, completed by `completer` if given
+    pub fn arg(mut self, name: &'static str, completer: Option<ArgCompleter>) -> Self {
+        self.args.push(ArgSpec { name, completer });
+        self
+    }
+
+    /// Syntax line such as `give <resource> <amount>`
+    pub fn usage(&self) -> String {
+        std::iter::once(self.name.to_string())
+            .chain(self.args.iter().map(|arg| format!("<{}>", arg.name)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Suggestions for the word at the end of a line
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Completion {
+    /// Byte offset where the word being completed starts
+    pub start: usize,
+    /// Replacements for that word, best first
+    pub suggestions: Vec<String>,
+}
+
+impl Completion {
+    /// `line` with the word replaced by suggestion `index`, if there is one
+    pub fn apply(&self, line: &str, index: usize) -> Option<String> {
+        let suggestion = self.suggestions.get(index)?;
+        Some(format!("{}{}", &line[..self.start], suggestion))
+    }
+}
+
+/// Commands known to the console
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandRegistry {
+    commands: Vec<CommandSpec>,
+}
+
+impl CommandRegistry {
+    /// Add a command, replacing any with the same name
+    pub fn register(&mut self, spec: CommandSpec) {
+        self.commands.retain(|command| command.name != spec.name);
+        self.commands.push(spec);
+    }
+
+    /// Look up a command by its exact name
+    pub fn get(&self, name: &str) -> Option<&CommandSpec> {
+        self.commands.iter().find(|command| command.name == name)
+    }
+
+    /// Suggestions for the last word of `line`
+    pub fn complete(&self, line: &str, context: &CompletionContext) -> Completion {
+        let start = line
+            .rfind(char::is_whitespace)
+            .map(|index| index + 1)
+            .unwrap_or(0);
+        let partial = &line[start..];
+        let words: Vec<&str> = line[..start].split_whitespace().collect();
+
+        let suggestions = match words.split_first() {
+            None => rank_prefix(partial, self.commands.iter().map(|command| command.name)),
+            Some((name, typed_args)) => self
+                .get(name)
+                .and_then(|command| command.args.get(typed_args.len()))
+                .and_then(|arg| arg.completer)
+                .map(|completer| rank_prefix(partial, completer.candidates(context)))
+                .unwrap_or_default(),
+        };
+        Completion { start, suggestions }
+    }
+
+    /// Syntax of the command typed so far, if it is a known one
+    pub fn hint(&self, line: &str) -> Option<String> {
+        let name = line.split_whitespace().next()?;
+        self.get(name).map(CommandSpec::usage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_and_case_matching_names_rank_first() {
+        let names = ["inspect", "info", "Inventory", "in", "ping", "info"];
+        assert_eq!(
+            rank_prefix("in", names),
+            vec!["in", "info", "inspect", "Inventory"]
+        );
+        assert_eq!(
+            rank_prefix("In", names),
+            vec!["in", "Inventory", "info", "inspect"]
+        );
+        assert_eq!(rank_prefix("", ["b", "a"]), vec!["a", "b"]);
+        assert!(rank_prefix("x", names).is_empty());
+    }
+
+    #[test]
+    fn arguments_complete_with_the_completer_of_their_command() {
+        let mut registry = CommandRegistry::default();
+        registry.register(
+            CommandSpec::new("give")
+                .arg("resource", Some(ArgCompleter::Resource))
+                .arg("amount", None),
+        );
+        registry
+            .register(CommandSpec::new("terraform").arg("terrain", Some(ArgCompleter::Terrain)));
+        registry.register(CommandSpec::new("goto").arg("poi", Some(ArgCompleter::PoiId)));
+        let context = CompletionContext {
+            poi_ids: vec!["ruins-3".to_string(), "deposit-1".to_string()],
+        };
+
+        let names = registry.complete("te", &context);
+        assert_eq!(names.suggestions, vec!["terraform"]);
+        assert_eq!(names.apply("te", 0).as_deref(), Some("terraform"));
+
+        let resources = registry.complete("give ex", &context);
+        assert_eq!(resources.start, 5);
+        assert_eq!(resources.suggestions, vec!["ExoticMatter"]);
+        assert_eq!(
+            resources.apply("give ex", 0).as_deref(),
+            Some("give ExoticMatter")
+        );
+        assert_eq!(
+            registry.complete("terraform c", &context).suggestions,
+            vec!["Cave", "Crystal", "Constructed"]
+        );
+        assert_eq!(
+            registry.complete("goto r", &context).suggestions,
+            vec!["ruins-3"]
+        );
+
+        // Arguments without a completer, extra words and unknown commands
+        // have nothing to offer
+        assert!(registry
+            .complete("give Metal ", &context)
+            .suggestions
+            .is_empty());
+        assert!(registry
+            .complete("goto ruins-3 x", &context)
+            .suggestions
+            .is_empty());
+        assert!(registry.complete("warp n", &context).suggestions.is_empty());
+
+        assert_eq!(
+            registry.hint("give Me").as_deref(),
+            Some("give <resource> <amount>")
+        );
+        assert_eq!(registry.hint("warp"), None);
+    }
+}
@@ -0,0 +1,123 @@
+//! History - Commands entered at the console, kept across sessions
+//!
+//! The history lives in the settings file, so it survives restarts without a
+//! file of its own. Blank lines and repeats of the previous command are not
+//! recorded, and the oldest entries make room once the limit is reached.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Most commands the history keeps
+pub const CONSOLE_HISTORY_LIMIT: usize = 100;
+
+/// Entered commands, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommandHistory {
+    entries: VecDeque<String>,
+}
+
+impl CommandHistory {
+    /// Record an entered command
+    pub fn push(&mut self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() || self.entries.back().map(String::as_str) == Some(line) {
+            return;
+        }
+        self.entries.push_back(line.to_string());
+        while self.entries.len() > CONSOLE_HISTORY_LIMIT {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Number of recorded commands
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if nothing was recorded yet
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Command `back` steps before the newest, which is step 0
+    pub fn recent(&self, back: usize) -> Option<&str> {
+        let index = self.entries.len().checked_sub(back + 1)?;
+        self.entries.get(index).map(String::as_str)
+    }
+}
+
+/// Position while stepping through the history with the arrow keys
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HistoryBrowser {
+    /// Steps back from the newest entry; `None` while editing a new line
+    back: Option<usize>,
+}
+
+impl HistoryBrowser {
+    /// Step to an older command (up arrow)
+    pub fn older<'a>(&mut self, history: &'a CommandHistory) -> Option<&'a str> {
+        let back = self.back.map_or(0, |back| back + 1);
+        let line = history.recent(back)?;
+        self.back = Some(back);
+        Some(line)
+    }
+
+    /// Step to a newer command (down arrow); `None` means back to a blank line
+    pub fn newer<'a>(&mut self, history: &'a CommandHistory) -> Option<&'a str> {
+        match self.back {
+            Some(back) if back > 0 => {
+                self.back = Some(back - 1);
+                history.recent(back - 1)
+            }
+            _ => {
+                self.back = None;
+                None
+            }
+        }
+    }
+
+    /// Forget the position, e.g. after a command was entered
+    pub fn reset(&mut self) {
+        self.back = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oldest_commands_are_evicted_past_the_limit() {
+        let mut history = CommandHistory::default();
+        for n in 0..CONSOLE_HISTORY_LIMIT + 5 {
+            history.push(&format!("cmd {}", n));
+        }
+        assert_eq!(history.len(), CONSOLE_HISTORY_LIMIT);
+        assert_eq!(history.recent(0), Some("cmd 104"));
+        assert_eq!(history.recent(CONSOLE_HISTORY_LIMIT - 1), Some("cmd 5"));
+        assert_eq!(history.recent(CONSOLE_HISTORY_LIMIT), None);
+
+        // Blank lines and immediate repeats are not recorded
+        history.push("   ");
+        history.push("cmd 104");
+        assert_eq!(history.len(), CONSOLE_HISTORY_LIMIT);
+        assert_eq!(history.recent(1), Some("cmd 103"));
+    }
+
+    #[test]
+    fn arrows_walk_back_and_return_to_a_blank_line() {
+        let mut history = CommandHistory::default();
+        history.push("ping");
+        history.push("inspect 12v1");
+        let mut browser = HistoryBrowser::default();
+
+        assert_eq!(browser.older(&history), Some("inspect 12v1"));
+        assert_eq!(browser.older(&history), Some("ping"));
+        // The oldest entry stays put
+        assert_eq!(browser.older(&history), None);
+        assert_eq!(browser.newer(&history), Some("inspect 12v1"));
+        assert_eq!(browser.newer(&history), None);
+        assert_eq!(browser.older(&history), Some("inspect 12v1"));
+    }
+}
@@ -0,0 +1,13 @@
+//! Console - Text helpers for typed dev commands
+//!
+//! Completion and history are kept apart from any console window so the
+//! same engine serves every place that takes typed names. Completion only
+//! proposes text; running a command is always up to the caller.
+
+pub mod completion;
+pub mod history;
+
+pub use completion::{
+    rank_prefix, ArgCompleter, ArgSpec, CommandRegistry, CommandSpec, Completion, CompletionContext,
+};
+pub use history::{CommandHistory, HistoryBrowser, CONSOLE_HISTORY_LIMIT};
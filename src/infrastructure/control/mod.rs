@@ -0,0 +1,355 @@
+//! Control Channel - Scripted Game Control for Automated Playtesting
+//!
+//! Behind the `dev-tools` feature, native builds started with
+//! `--control-port <port>` accept newline-delimited JSON commands on
+//! localhost. Commands are executed by a Bevy system at frame boundaries,
+//! so the network thread never touches the world directly. While the
+//! server runs, a state snapshot is recorded at every rest for `diff`.
+//! `inspect` needs the whole world, so it is answered by an exclusive
+//! system right after the others.
+
+pub mod protocol;
+pub mod server;
+
+pub use protocol::{parse_command, ControlAction, ControlCommand, ControlResponse, QueryTarget};
+pub use server::{control_port_from_args, start_control_server, ControlQueue, ControlRequest};
+
+use crate::application::services::GameQueryService;
+use crate::domain::services::{GameLogService, MapRegion, MapService, ModifierStack, WorldHazards};
+use crate::domain::value_objects::position::Direction;
+use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource, PlayerResource};
+use crate::infrastructure::packs::ActivePacks;
+use crate::infrastructure::snapshots::{SnapshotReason, StateSnapshot, StateSnapshots};
+use crate::presentation::asset_integrity::AssetIntegrity;
+use crate::presentation::game_state::RpgGameSession;
+use crate::presentation::inspect::{component_dump, parse_entity};
+use crate::presentation::map_renderer::PlayerMarker;
+use crate::presentation::movement::{
+    begin_player_step, movement_cost_at, ExecuteRpgMovement, MovementConfig, MovementStarted,
+    SmoothMovement,
+};
+use crate::presentation::world_tick::{TickCursor, TickPhase, WorldTick, WorldTickSet};
+use crate::presentation::{GameAction, RpgAppState};
+use bevy::prelude::*;
+use std::sync::mpsc::Receiver;
+use std::sync::Mutex;
+
+/// Plugin wiring the control server into the app
+pub struct ControlPlugin {
+    pub port: u16,
+}
+
+impl Plugin for ControlPlugin {
+    fn build(&self, app: &mut App) {
+        match start_control_server(self.port) {
+            Ok(receiver) => {
+                app.insert_resource(ControlInbox(Mutex::new(receiver)))
+                    .init_resource::<StateSnapshots>()
+                    .init_resource::<PendingInspections>()
+                    .add_systems(
+                        PreUpdate,
+                        (execute_control_commands, answer_inspections).chain(),
+                    )
+                    .add_systems(
+                        Update,
+                        snapshot_on_rest_system.after(WorldTickSet::Objectives),
+                    );
+            }
+            Err(e) => warn!("🛠️ Control server not started: {}", e),
+        }
+    }
+}
+
+/// Receiving end of the control command queue
+#[derive(Resource)]
+pub struct ControlInbox(Mutex<Receiver<ControlRequest>>);
+
+/// `inspect` requests waiting for world access
+#[derive(Resource, Default)]
+pub struct PendingInspections(Vec<(String, ControlRequest)>);
+
+/// Execute all queued control commands for this frame
+#[allow(clippy::too_many_arguments)]
+fn execute_control_commands(
+    inbox: Res<ControlInbox>,
+    mut player_query: Query<(&mut SmoothMovement, Entity), With<PlayerMarker>>,
+    player_resource: Res<PlayerResource>,
+    map_resource: Res<MapResource>,
+    world_hazards: Option<Res<WorldHazards>>,
+    game_log: Res<GameLogService>,
+    session: Option<Res<RpgGameSession>>,
+    asset_integrity: Option<Res<AssetIntegrity>>,
+    packs: Option<Res<ActivePacks>>,
+    mut snapshots: ResMut<StateSnapshots>,
+    game_stats: Res<GameStatsResource>,
+    config: Res<MovementConfig>,
+    (current_state, mut next_state): (Res<State<RpgAppState>>, ResMut<NextState<RpgAppState>>),
+    mut inspections: ResMut<PendingInspections>,
+    mut movement_started_events: EventWriter<MovementStarted>,
+    mut execute_rpg_events: EventWriter<ExecuteRpgMovement>,
+) {
+    let Ok(receiver) = inbox.0.lock() else {
+        return;
+    };
+    let query_service = GameQueryService::new();
+
+    while let Ok(request) = receiver.try_recv() {
+        let response = match request.command.to_action() {
+            ControlAction::Ping => ControlResponse::ok(),
+            ControlAction::Game(action) => match state_after_action(&action, current_state.get()) {
+                Some(state) => {
+                    next_state.set(state.clone());
+                    ControlResponse::with_data(&format!("{:?}", state))
+                }
+                None => ControlResponse::error(format!(
+                    "{:?} has no effect in state {:?}",
+                    action,
+                    current_state.get()
+                )),
+            },
+            ControlAction::Move(direction) => {
+                if *current_state.get() != RpgAppState::Exploration {
+                    ControlResponse::error("moves are only accepted while exploring")
+                } else {
+                    start_player_move(
+                        direction,
+                        &mut player_query,
+                        &player_resource,
+                        &map_resource,
+                        world_hazards.as_deref(),
+                        &game_stats.modifiers,
+                        &config,
+                        &mut movement_started_events,
+                        &mut execute_rpg_events,
+                    )
+                }
+            }
+            ControlAction::Query {
+                target,
+                radius,
+                since,
+            } => match target {
+                QueryTarget::Player => match player_resource.get_player() {
+                    Some(player) => {
+                        ControlResponse::with_data(&query_service.player_snapshot(player))
+                    }
+                    None => ControlResponse::error("no active player"),
+                },
+                QueryTarget::Tiles => {
+                    match (
+                        map_resource.current_map(),
+                        player_resource.player_position(),
+                    ) {
+                        (Some(map), Some(center)) => ControlResponse::with_data(
+                            &query_service.tiles_around(map, center, radius),
+                        ),
+                        _ => ControlResponse::error("no map loaded"),
+                    }
+                }
+                QueryTarget::Log => {
+                    ControlResponse::with_data(&query_service.log_since(&game_log, since))
+                }
+                QueryTarget::State => {
+                    ControlResponse::with_data(&format!("{:?}", current_state.get()))
+                }
+                QueryTarget::MapStats => {
+                    match (
+                        map_resource.current_map(),
+                        player_resource.player_position(),
+                    ) {
+                        (Some(map), Some(center)) => {
+                            let analysis = MapService::new(map.seed())
+                                .analyze(map, MapRegion::new(center, radius));
+                            ControlResponse::with_data(&analysis.to_string())
+                        }
+                        _ => ControlResponse::error("no map loaded"),
+                    }
+                }
+                QueryTarget::Flags => match &session {
+                    Some(session) => ControlResponse::with_data(&session.flags.listing()),
+                    None => ControlResponse::error("no active session"),
+                },
+                QueryTarget::Assets => match &asset_integrity {
+                    Some(integrity) => ControlResponse::with_data(&integrity.lines()),
+                    None => ControlResponse::error("the asset check is not running"),
+                },
+                QueryTarget::Snapshots => ControlResponse::with_data(&snapshots.listing()),
+                QueryTarget::Packs => match &packs {
+                    Some(packs) => ControlResponse::with_data(&packs.lines()),
+                    None => ControlResponse::error("data packs are not loaded"),
+                },
+            },
+            ControlAction::Snapshot => match capture_snapshot(
+                session.as_deref(),
+                &player_resource,
+                &map_resource,
+                game_stats.current_day(),
+                SnapshotReason::Requested,
+            ) {
+                Some(snapshot) => ControlResponse::with_data(&snapshots.record(snapshot)),
+                None => ControlResponse::error("no active session"),
+            },
+            ControlAction::Diff { from, to } => match snapshots.diff(from, to) {
+                Ok(diff) => ControlResponse::with_data(&diff.lines()),
+                Err(error) => ControlResponse::error(error),
+            },
+            ControlAction::Inspect { entity } => {
+                inspections.0.push((entity, request));
+                continue;
+            }
+        };
+        request.respond(response);
+    }
+}
+
+/// Answer `inspect` requests with the components of the named entity
+fn answer_inspections(world: &mut World) {
+    let pending = std::mem::take(&mut world.resource_mut::<PendingInspections>().0);
+    for (entity, request) in pending {
+        let response = match parse_entity(&entity) {
+            Some(id) => match component_dump(world, id) {
+                Some(lines) => ControlResponse::with_data(&lines),
+                None => ControlResponse::error(format!("no entity {}", entity)),
+            },
+            None => ControlResponse::error(format!("not an entity id: {}", entity)),
+        };
+        request.respond(response);
+    }
+}
+
+/// Record a snapshot after every rest, once the world has caught up
+fn snapshot_on_rest_system(
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    mut snapshots: ResMut<StateSnapshots>,
+    session: Option<Res<RpgGameSession>>,
+    player_resource: Res<PlayerResource>,
+    map_resource: Res<MapResource>,
+    game_stats: Res<GameStatsResource>,
+) {
+    if cursor.take(ticks.read(), TickPhase::AfterRest).is_empty() {
+        return;
+    }
+    if let Some(snapshot) = capture_snapshot(
+        session.as_deref(),
+        &player_resource,
+        &map_resource,
+        game_stats.current_day(),
+        SnapshotReason::Rest,
+    ) {
+        snapshots.record(snapshot);
+    }
+}
+
+/// Snapshot the session with the live player and the overworld
+fn capture_snapshot(
+    session: Option<&RpgGameSession>,
+    player_resource: &PlayerResource,
+    map_resource: &MapResource,
+    day: u32,
+    reason: SnapshotReason,
+) -> Option<StateSnapshot> {
+    let mut session = session?.clone();
+    // The session's copy of the player only catches up on save
+    if let Some(player) = player_resource.get_player() {
+        session.player = player.clone();
+    }
+    Some(StateSnapshot::capture(
+        &session,
+        map_resource.overworld(),
+        day,
+        reason,
+    ))
+}
+
+/// Start a one-tile move the same way keyboard input does
+#[allow(clippy::too_many_arguments)]
+fn start_player_move(
+    direction: Direction,
+    player_query: &mut Query<(&mut SmoothMovement, Entity), With<PlayerMarker>>,
+    player_resource: &PlayerResource,
+    map_resource: &MapResource,
+    world_hazards: Option<&WorldHazards>,
+    modifiers: &ModifierStack,
+    config: &MovementConfig,
+    movement_started_events: &mut EventWriter<MovementStarted>,
+    execute_rpg_events: &mut EventWriter<ExecuteRpgMovement>,
+) -> ControlResponse {
+    let Some(player) = player_resource.get_player() else {
+        return ControlResponse::error("no active player");
+    };
+    let Ok((mut smooth_movement, entity)) = player_query.single_mut() else {
+        return ControlResponse::error("player entity not spawned");
+    };
+    if smooth_movement.is_moving {
+        return ControlResponse::error("movement in progress");
+    }
+
+    let to = smooth_movement.target_position.move_direction(direction, 1);
+    let movement_cost = movement_cost_at(map_resource, world_hazards, modifiers, to);
+    if player.movement_points() < movement_cost {
+        return ControlResponse::error(format!(
+            "not enough movement points: need {}, have {}",
+            movement_cost,
+            player.movement_points()
+        ));
+    }
+
+    begin_player_step(
+        &mut smooth_movement,
+        entity,
+        direction,
+        config,
+        movement_started_events,
+        execute_rpg_events,
+    );
+
+    ControlResponse::with_data(&to)
+}
+
+/// State a game action leads to, mirroring the keyboard state transitions
+fn state_after_action(action: &GameAction, state: &RpgAppState) -> Option<RpgAppState> {
+    match (action, state) {
+        (GameAction::Confirm, RpgAppState::MainMenu) => Some(RpgAppState::Exploration),
+        (GameAction::TogglePause, RpgAppState::Exploration) => Some(RpgAppState::Paused),
+        (GameAction::TogglePause, RpgAppState::Paused) => Some(RpgAppState::Exploration),
+        (GameAction::OpenBase, RpgAppState::Exploration) => Some(RpgAppState::BaseManagement),
+        (GameAction::OpenQuests, RpgAppState::Exploration) => Some(RpgAppState::QuestLog),
+        (GameAction::OpenInventory, RpgAppState::Exploration) => Some(RpgAppState::Inventory),
+        (
+            GameAction::Cancel,
+            RpgAppState::BaseManagement | RpgAppState::QuestLog | RpgAppState::Inventory,
+        ) => Some(RpgAppState::Exploration),
+        (GameAction::Cancel, RpgAppState::ExpeditionPlanning) => Some(RpgAppState::BaseManagement),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn game_actions_follow_keyboard_transitions() {
+        assert_eq!(
+            state_after_action(&GameAction::Confirm, &RpgAppState::MainMenu),
+            Some(RpgAppState::Exploration)
+        );
+        assert_eq!(
+            state_after_action(&GameAction::TogglePause, &RpgAppState::Exploration),
+            Some(RpgAppState::Paused)
+        );
+        assert_eq!(
+            state_after_action(&GameAction::TogglePause, &RpgAppState::Paused),
+            Some(RpgAppState::Exploration)
+        );
+        assert_eq!(
+            state_after_action(&GameAction::Cancel, &RpgAppState::Inventory),
+            Some(RpgAppState::Exploration)
+        );
+        assert_eq!(
+            state_after_action(&GameAction::Confirm, &RpgAppState::Exploration),
+            None
+        );
+    }
+}
@@ -0,0 +1,393 @@
+//! Control Protocol - Newline-Delimited JSON Commands and Responses
+//!
+//! Every request is a single JSON object on its own line, tagged by `cmd`:
+//!
+//! ```text
+//! {"cmd":"ping"}
+//! {"cmd":"move","dir":"north"}
+//! {"cmd":"query","what":"player"}
+//! {"cmd":"query","what":"tiles","radius":3}
+//! {"cmd":"query","what":"log","since":42}
+//! {"cmd":"query","what":"mapstats","radius":8}
+//! {"cmd":"query","what":"flags"}
+//! {"cmd":"query","what":"snapshots"}
+//! {"cmd":"query","what":"packs"}
+//! {"cmd":"snapshot"}
+//! {"cmd":"diff","from":3,"to":5}
+//! {"cmd":"inspect","entity":"12v1"}
+//! {"cmd":"action","action":"pause"}
+//! ```
+//!
+//! Every request receives exactly one response line:
+//! `{"ok":true,"data":...}` or `{"ok":false,"error":"..."}`.
+
+use crate::domain::value_objects::position::Direction;
+use crate::presentation::GameAction;
+use serde::{Deserialize, Serialize};
+
+/// Default radius for tile queries when none is given
+pub const DEFAULT_TILE_QUERY_RADIUS: u32 = 2;
+
+/// Largest radius a tile query may request
+pub const MAX_TILE_QUERY_RADIUS: u32 = 10;
+
+/// A command received from a control client
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum ControlCommand {
+    /// Liveness check
+    Ping,
+    /// Move the player one tile
+    Move { dir: ControlDirection },
+    /// Read part of the game state
+    Query {
+        what: QueryTarget,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        radius: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        since: Option<u64>,
+    },
+    /// Inject a game action as if the matching key was pressed
+    Action { action: ControlGameAction },
+    /// Record a state snapshot now
+    Snapshot,
+    /// Compare two recorded state snapshots
+    Diff { from: u64, to: u64 },
+    /// Dump the components of an entity, given as `index` or `indexvgeneration`
+    Inspect { entity: String },
+}
+
+/// Movement directions accepted by the `move` command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlDirection {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl From<ControlDirection> for Direction {
+    fn from(direction: ControlDirection) -> Self {
+        match direction {
+            ControlDirection::North => Direction::North,
+            ControlDirection::South => Direction::South,
+            ControlDirection::East => Direction::East,
+            ControlDirection::West => Direction::West,
+        }
+    }
+}
+
+/// Parts of the game state that can be queried
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryTarget {
+    Player,
+    Tiles,
+    Log,
+    State,
+    /// Map analysis report around the player, for generation tuning
+    #[serde(rename = "mapstats")]
+    MapStats,
+    /// Session flags set by earlier choices, one `key = value` line each
+    Flags,
+    /// Startup asset check: a summary line, then one line per problem
+    Assets,
+    /// Recorded state snapshots with their root hashes
+    Snapshots,
+    /// Loaded data packs, then their conflicts and skipped manifests
+    Packs,
+}
+
+/// Game actions that may be injected through the `action` command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlGameAction {
+    Pause,
+    Confirm,
+    Cancel,
+}
+
+impl From<ControlGameAction> for GameAction {
+    fn from(action: ControlGameAction) -> Self {
+        match action {
+            ControlGameAction::Pause => GameAction::TogglePause,
+            ControlGameAction::Confirm => GameAction::Confirm,
+            ControlGameAction::Cancel => GameAction::Cancel,
+        }
+    }
+}
+
+/// What the game loop should do for a command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlAction {
+    /// Reply immediately without touching the world
+    Ping,
+    /// Start a player move in the given direction
+    Move(Direction),
+    /// Inject a game action
+    Game(GameAction),
+    /// Answer a read-only query
+    Query {
+        target: QueryTarget,
+        radius: u32,
+        since: Option<u64>,
+    },
+    /// Record a state snapshot
+    Snapshot,
+    /// Compare two state snapshots by index
+    Diff { from: u64, to: u64 },
+    /// Dump an entity's components; answered with world access
+    Inspect { entity: String },
+}
+
+impl ControlCommand {
+    /// Map the wire command onto the action the game loop executes
+    pub fn to_action(&self) -> ControlAction {
+        match self {
+            ControlCommand::Ping => ControlAction::Ping,
+            ControlCommand::Move { dir } => ControlAction::Move((*dir).into()),
+            ControlCommand::Action { action } => ControlAction::Game((*action).into()),
+            ControlCommand::Snapshot => ControlAction::Snapshot,
+            ControlCommand::Diff { from, to } => ControlAction::Diff {
+                from: *from,
+                to: *to,
+            },
+            ControlCommand::Inspect { entity } => ControlAction::Inspect {
+                entity: entity.clone(),
+            },
+            ControlCommand::Query {
+                what,
+                radius,
+                since,
+            } => ControlAction::Query {
+                target: *what,
+                radius: radius
+                    .unwrap_or(DEFAULT_TILE_QUERY_RADIUS)
+                    .min(MAX_TILE_QUERY_RADIUS),
+                since: *since,
+            },
+        }
+    }
+}
+
+/// Parse a single request line
+pub fn parse_command(line: &str) -> Result<ControlCommand, String> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Err("empty command".to_string());
+    }
+    serde_json::from_str(line).map_err(|e| format!("invalid command: {}", e))
+}
+
+/// A single response line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ControlResponse {
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ControlResponse {
+    /// Successful response without a payload
+    pub fn ok() -> Self {
+        Self {
+            ok: true,
+            data: None,
+            error: None,
+        }
+    }
+
+    /// Successful response carrying a serialized payload
+    pub fn with_data<T: Serialize>(data: &T) -> Self {
+        match serde_json::to_value(data) {
+            Ok(value) => Self {
+                ok: true,
+                data: Some(value),
+                error: None,
+            },
+            Err(e) => Self::error(format!("failed to serialize response: {}", e)),
+        }
+    }
+
+    /// Failed response with a message
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            data: None,
+            error: Some(message.into()),
+        }
+    }
+
+    /// Serialize as a newline-terminated JSON line
+    pub fn to_line(&self) -> String {
+        let mut line = serde_json::to_string(self)
+            .unwrap_or_else(|_| r#"{"ok":false,"error":"serialization failed"}"#.to_string());
+        line.push('\n');
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_move_command() {
+        let command = parse_command(r#"{"cmd":"move","dir":"north"}"#).unwrap();
+        assert_eq!(
+            command,
+            ControlCommand::Move {
+                dir: ControlDirection::North
+            }
+        );
+    }
+
+    #[test]
+    fn parses_query_with_optional_fields() {
+        let command = parse_command(r#"{"cmd":"query","what":"player"}"#).unwrap();
+        assert_eq!(
+            command,
+            ControlCommand::Query {
+                what: QueryTarget::Player,
+                radius: None,
+                since: None
+            }
+        );
+
+        let command = parse_command(r#"{"cmd":"query","what":"log","since":7}"#).unwrap();
+        assert_eq!(
+            command.to_action(),
+            ControlAction::Query {
+                target: QueryTarget::Log,
+                radius: DEFAULT_TILE_QUERY_RADIUS,
+                since: Some(7)
+            }
+        );
+    }
+
+    #[test]
+    fn parses_mapstats_query() {
+        let command = parse_command(r#"{"cmd":"query","what":"mapstats","radius":6}"#).unwrap();
+        assert_eq!(
+            command.to_action(),
+            ControlAction::Query {
+                target: QueryTarget::MapStats,
+                radius: 6,
+                since: None
+            }
+        );
+    }
+
+    #[test]
+    fn parses_flags_query() {
+        let command = parse_command(r#"{"cmd":"query","what":"flags"}"#).unwrap();
+        assert_eq!(
+            command.to_action(),
+            ControlAction::Query {
+                target: QueryTarget::Flags,
+                radius: DEFAULT_TILE_QUERY_RADIUS,
+                since: None
+            }
+        );
+    }
+
+    #[test]
+    fn parses_assets_query() {
+        let command = parse_command(r#"{"cmd":"query","what":"assets"}"#).unwrap();
+        assert_eq!(
+            command.to_action(),
+            ControlAction::Query {
+                target: QueryTarget::Assets,
+                radius: DEFAULT_TILE_QUERY_RADIUS,
+                since: None
+            }
+        );
+    }
+
+    #[test]
+    fn parses_snapshot_commands() {
+        let command = parse_command(r#"{"cmd":"snapshot"}"#).unwrap();
+        assert_eq!(command.to_action(), ControlAction::Snapshot);
+
+        let command = parse_command(r#"{"cmd":"diff","from":3,"to":5}"#).unwrap();
+        assert_eq!(command.to_action(), ControlAction::Diff { from: 3, to: 5 });
+        assert!(parse_command(r#"{"cmd":"diff","from":3}"#).is_err());
+    }
+
+    #[test]
+    fn parses_inspect_command() {
+        let command = parse_command(r#"{"cmd":"inspect","entity":"12v1"}"#).unwrap();
+        assert_eq!(
+            command.to_action(),
+            ControlAction::Inspect {
+                entity: "12v1".to_string()
+            }
+        );
+        assert!(parse_command(r#"{"cmd":"inspect"}"#).is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_commands() {
+        assert!(parse_command("").is_err());
+        assert!(parse_command("not json").is_err());
+        assert!(parse_command(r#"{"cmd":"teleport"}"#).is_err());
+        assert!(parse_command(r#"{"cmd":"move","dir":"up"}"#).is_err());
+    }
+
+    #[test]
+    fn command_round_trips_through_json() {
+        let command = ControlCommand::Action {
+            action: ControlGameAction::Pause,
+        };
+        let json = serde_json::to_string(&command).unwrap();
+        assert_eq!(json, r#"{"cmd":"action","action":"pause"}"#);
+        assert_eq!(parse_command(&json).unwrap(), command);
+    }
+
+    #[test]
+    fn commands_map_to_actions() {
+        assert_eq!(ControlCommand::Ping.to_action(), ControlAction::Ping);
+        assert_eq!(
+            ControlCommand::Move {
+                dir: ControlDirection::West
+            }
+            .to_action(),
+            ControlAction::Move(Direction::West)
+        );
+        assert_eq!(
+            ControlCommand::Action {
+                action: ControlGameAction::Pause
+            }
+            .to_action(),
+            ControlAction::Game(GameAction::TogglePause)
+        );
+        assert_eq!(
+            ControlCommand::Query {
+                what: QueryTarget::Tiles,
+                radius: Some(100),
+                since: None
+            }
+            .to_action(),
+            ControlAction::Query {
+                target: QueryTarget::Tiles,
+                radius: MAX_TILE_QUERY_RADIUS,
+                since: None
+            }
+        );
+    }
+
+    #[test]
+    fn responses_serialize_as_single_lines() {
+        assert_eq!(ControlResponse::ok().to_line(), "{\"ok\":true}\n");
+        assert_eq!(
+            ControlResponse::error("nope").to_line(),
+            "{\"ok\":false,\"error\":\"nope\"}\n"
+        );
+
+        let line = ControlResponse::with_data(&vec![1, 2]).to_line();
+        assert_eq!(line, "{\"ok\":true,\"data\":[1,2]}\n");
+    }
+}
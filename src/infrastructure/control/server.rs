@@ -0,0 +1,283 @@
+//! Control Server - Background TCP Listener Feeding a Bounded Command Queue
+//!
+//! The listener thread never touches the ECS world. Each parsed command is
+//! pushed into a bounded queue together with a one-shot reply channel; a
+//! Bevy system drains the queue at frame boundaries and answers. When the
+//! queue is full the client immediately gets an error line instead of the
+//! command being dropped.
+
+use super::protocol::{ControlCommand, ControlResponse};
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender, TrySendError};
+use std::time::Duration;
+
+/// Number of commands that may wait for the game loop at once
+pub const CONTROL_COMMAND_BUFFER: usize = 32;
+
+/// Maximum number of simultaneously connected clients
+pub const CONTROL_MAX_CONNECTIONS: usize = 1;
+
+/// Connections without any traffic for this long are closed
+pub const CONTROL_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// How long a connection waits for the game loop to answer a command
+pub const CONTROL_REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A command waiting to be executed by the game loop
+#[derive(Debug)]
+pub struct ControlRequest {
+    pub command: ControlCommand,
+    pub reply: Sender<ControlResponse>,
+}
+
+impl ControlRequest {
+    /// Send the response back to the waiting connection
+    pub fn respond(self, response: ControlResponse) {
+        // The client may already have disconnected; nothing to do then
+        let _ = self.reply.send(response);
+    }
+}
+
+/// Sending half of the bounded command queue
+#[derive(Debug, Clone)]
+pub struct ControlQueue {
+    sender: SyncSender<ControlRequest>,
+}
+
+impl ControlQueue {
+    /// Create a queue holding at most `capacity` pending commands
+    pub fn bounded(capacity: usize) -> (Self, Receiver<ControlRequest>) {
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        (Self { sender }, receiver)
+    }
+
+    /// Enqueue a command without blocking
+    ///
+    /// Returns the channel the response will arrive on, or an error response
+    /// when the buffer is full or the game loop has gone away.
+    pub fn submit(
+        &self,
+        command: ControlCommand,
+    ) -> Result<Receiver<ControlResponse>, ControlResponse> {
+        let (reply, response) = mpsc::channel();
+        match self.sender.try_send(ControlRequest { command, reply }) {
+            Ok(()) => Ok(response),
+            Err(TrySendError::Full(_)) => Err(ControlResponse::error("command buffer full")),
+            Err(TrySendError::Disconnected(_)) => {
+                Err(ControlResponse::error("game loop is not running"))
+            }
+        }
+    }
+}
+
+/// Parse `--control-port <port>` from command line arguments
+pub fn control_port_from_args<I>(args: I) -> Option<u16>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--control-port" {
+            return args.next().and_then(|port| port.parse().ok());
+        }
+        if let Some(port) = arg.strip_prefix("--control-port=") {
+            return port.parse().ok();
+        }
+    }
+    None
+}
+
+/// Start the control server on localhost and return the command receiver
+///
+/// Only available in native builds with the `dev-tools` feature enabled.
+pub fn start_control_server(port: u16) -> InfrastructureResult<Receiver<ControlRequest>> {
+    #[cfg(all(feature = "dev-tools", not(target_arch = "wasm32")))]
+    {
+        listener::spawn(port)
+    }
+    #[cfg(not(all(feature = "dev-tools", not(target_arch = "wasm32"))))]
+    {
+        Err(InfrastructureError::ExternalServiceError(format!(
+            "control server on port {} requires a native build with the dev-tools feature",
+            port
+        )))
+    }
+}
+
+#[cfg(all(feature = "dev-tools", not(target_arch = "wasm32")))]
+mod listener {
+    use super::*;
+    use crate::infrastructure::control::protocol::parse_command;
+    use bevy::log::{info, warn};
+    use std::io::{BufRead, BufReader, ErrorKind, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    /// Decrements the active connection count when a connection ends
+    struct ConnectionSlot(Arc<AtomicUsize>);
+
+    impl Drop for ConnectionSlot {
+        fn drop(&mut self) {
+            self.0.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    pub(super) fn spawn(port: u16) -> InfrastructureResult<Receiver<ControlRequest>> {
+        let listener = TcpListener::bind(("127.0.0.1", port)).map_err(|e| {
+            InfrastructureError::ExternalServiceError(format!(
+                "failed to bind control port {}: {}",
+                port, e
+            ))
+        })?;
+        let (queue, receiver) = ControlQueue::bounded(CONTROL_COMMAND_BUFFER);
+        let active = Arc::new(AtomicUsize::new(0));
+
+        thread::Builder::new()
+            .name("control-server".to_string())
+            .spawn(move || {
+                info!("🛠️ Control server listening on 127.0.0.1:{}", port);
+                for stream in listener.incoming() {
+                    let Ok(mut stream) = stream else { continue };
+
+                    if active.fetch_add(1, Ordering::SeqCst) >= CONTROL_MAX_CONNECTIONS {
+                        active.fetch_sub(1, Ordering::SeqCst);
+                        let refusal = ControlResponse::error("too many connections");
+                        let _ = stream.write_all(refusal.to_line().as_bytes());
+                        continue;
+                    }
+
+                    let slot = ConnectionSlot(active.clone());
+                    let queue = queue.clone();
+                    let _ = thread::Builder::new()
+                        .name("control-connection".to_string())
+                        .spawn(move || {
+                            let _slot = slot;
+                            if let Err(e) = serve_connection(stream, queue) {
+                                warn!("Control connection closed: {}", e);
+                            }
+                        });
+                }
+            })
+            .map_err(|e| InfrastructureError::ExternalServiceError(e.to_string()))?;
+
+        Ok(receiver)
+    }
+
+    /// Read commands until the client disconnects or goes idle
+    ///
+    /// Commands are submitted as soon as they are read so a pipelining
+    /// client can fill the buffer; a writer thread answers them in order.
+    fn serve_connection(stream: TcpStream, queue: ControlQueue) -> std::io::Result<()> {
+        stream.set_read_timeout(Some(CONTROL_IDLE_TIMEOUT))?;
+        let mut writer = stream.try_clone()?;
+        let reader = BufReader::new(stream);
+
+        let (pending_tx, pending_rx) =
+            mpsc::channel::<Result<Receiver<ControlResponse>, ControlResponse>>();
+        let writer_thread = thread::spawn(move || {
+            for pending in pending_rx {
+                let response = match pending {
+                    Ok(reply) => reply
+                        .recv_timeout(CONTROL_REPLY_TIMEOUT)
+                        .unwrap_or_else(|_| ControlResponse::error("timed out waiting for game")),
+                    Err(response) => response,
+                };
+                if writer.write_all(response.to_line().as_bytes()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        for line in reader.lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                    let _ = pending_tx.send(Err(ControlResponse::error("idle timeout")));
+                    break;
+                }
+                Err(e) => return Err(e),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let pending = parse_command(&line)
+                .map_err(ControlResponse::error)
+                .and_then(|command| queue.submit(command));
+            if pending_tx.send(pending).is_err() {
+                break;
+            }
+        }
+
+        drop(pending_tx);
+        let _ = writer_thread.join();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_rejects_commands_beyond_capacity() {
+        let (queue, receiver) = ControlQueue::bounded(2);
+
+        assert!(queue.submit(ControlCommand::Ping).is_ok());
+        assert!(queue.submit(ControlCommand::Ping).is_ok());
+        let overflow = queue.submit(ControlCommand::Ping).unwrap_err();
+        assert!(!overflow.ok);
+        assert_eq!(overflow.error.as_deref(), Some("command buffer full"));
+
+        // Draining one slot makes room again
+        receiver.try_recv().unwrap().respond(ControlResponse::ok());
+        assert!(queue.submit(ControlCommand::Ping).is_ok());
+    }
+
+    #[test]
+    fn queued_command_receives_its_reply() {
+        let (queue, receiver) = ControlQueue::bounded(1);
+        let reply = queue.submit(ControlCommand::Ping).unwrap();
+
+        let request = receiver.try_recv().unwrap();
+        assert_eq!(request.command, ControlCommand::Ping);
+        request.respond(ControlResponse::ok());
+
+        assert_eq!(reply.recv().unwrap(), ControlResponse::ok());
+    }
+
+    #[test]
+    fn queue_reports_stopped_game_loop() {
+        let (queue, receiver) = ControlQueue::bounded(1);
+        drop(receiver);
+
+        let response = queue.submit(ControlCommand::Ping).unwrap_err();
+        assert_eq!(response.error.as_deref(), Some("game loop is not running"));
+    }
+
+    #[test]
+    fn parses_control_port_argument() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            control_port_from_args(args(&["game", "--control-port", "7777"])),
+            Some(7777)
+        );
+        assert_eq!(
+            control_port_from_args(args(&["game", "--control-port=9000"])),
+            Some(9000)
+        );
+        assert_eq!(control_port_from_args(args(&["game"])), None);
+        assert_eq!(
+            control_port_from_args(args(&["game", "--control-port", "nope"])),
+            None
+        );
+    }
+
+    #[cfg(not(all(feature = "dev-tools", not(target_arch = "wasm32"))))]
+    #[test]
+    fn refuses_to_start_without_dev_tools() {
+        assert!(start_control_server(7777).is_err());
+    }
+}
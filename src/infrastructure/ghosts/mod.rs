@@ -0,0 +1,160 @@
+//! Ghost Persistence - Breadcrumbs of previous runs, keyed by seed
+//!
+//! Finished runs are written to their own versioned JSON file next to the
+//! settings. A missing file is an empty ghost book; an unreadable one is
+//! ignored with a warning, since ghosts are a nice-to-have and must never
+//! keep the game from starting.
+
+use crate::domain::services::{GhostBook, GhostRun};
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Version written by this build
+pub const GHOSTS_VERSION: u32 = 1;
+
+/// Default ghost file location for native builds
+pub const GHOSTS_FILE_PATH: &str = "ghosts.json";
+
+/// The complete ghost document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GhostFile {
+    pub version: u32,
+    pub book: GhostBook,
+}
+
+impl Default for GhostFile {
+    fn default() -> Self {
+        Self {
+            version: GHOSTS_VERSION,
+            book: GhostBook::new(),
+        }
+    }
+}
+
+/// Load the ghost book from `path`; missing or unusable files yield an empty book
+pub fn load_ghosts(path: &Path) -> InfrastructureResult<GhostBook> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(GhostBook::new()),
+        Err(e) => {
+            return Err(InfrastructureError::ExternalServiceError(format!(
+                "failed to read ghosts: {}",
+                e
+            )))
+        }
+    };
+    let file: GhostFile = serde_json::from_str(&text).map_err(|e| {
+        InfrastructureError::ExternalServiceError(format!("invalid ghost file: {}", e))
+    })?;
+    if file.version > GHOSTS_VERSION {
+        return Err(InfrastructureError::ExternalServiceError(format!(
+            "unsupported ghost file version {} (this build writes {})",
+            file.version, GHOSTS_VERSION
+        )));
+    }
+    Ok(file.book)
+}
+
+/// Write the ghost book to `path` as the current version
+pub fn save_ghosts(path: &Path, book: &GhostBook) -> InfrastructureResult<()> {
+    let file = GhostFile {
+        version: GHOSTS_VERSION,
+        book: book.clone(),
+    };
+    let json = serde_json::to_string(&file).map_err(|e| {
+        InfrastructureError::ExternalServiceError(format!("failed to serialize ghosts: {}", e))
+    })?;
+    std::fs::write(path, json).map_err(|e| {
+        InfrastructureError::ExternalServiceError(format!("failed to write ghosts: {}", e))
+    })
+}
+
+/// Ghost book plus where to write it back
+#[derive(Resource, Debug)]
+pub struct GhostStore {
+    pub book: GhostBook,
+    /// File backing the store; `None` keeps ghosts in memory only
+    path: Option<PathBuf>,
+}
+
+impl GhostStore {
+    /// Store backed by a ghost file
+    pub fn from_file(path: PathBuf) -> Self {
+        let book = load_ghosts(&path).unwrap_or_else(|e| {
+            warn!("👻 Ignoring previous runs: {}", e);
+            GhostBook::new()
+        });
+        Self {
+            book,
+            path: Some(path),
+        }
+    }
+
+    /// Store that never touches the disk (web builds and tests)
+    pub fn in_memory(book: GhostBook) -> Self {
+        Self { book, path: None }
+    }
+
+    /// Remember a finished run and write the book if it was kept
+    pub fn record(&mut self, seed: u64, run: GhostRun) -> bool {
+        if !self.book.record(seed, run) {
+            return false;
+        }
+        if let Some(path) = &self.path {
+            match save_ghosts(path, &self.book) {
+                Ok(()) => debug!("👻 Ghosts saved to {}", path.display()),
+                Err(e) => warn!("👻 {}", e),
+            }
+        }
+        true
+    }
+}
+
+impl Default for GhostStore {
+    fn default() -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        return Self::from_file(PathBuf::from(GHOSTS_FILE_PATH));
+        #[cfg(target_arch = "wasm32")]
+        return Self::in_memory(GhostBook::new());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::Position3D;
+
+    fn temp_ghosts_path() -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("space-looter-ghosts-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("ghosts.json")
+    }
+
+    #[test]
+    fn recorded_runs_survive_a_reload() {
+        let path = temp_ghosts_path();
+        let samples = [Position3D::new(0, 0, 0), Position3D::new(3, -2, 0)];
+
+        let mut store = GhostStore::from_file(path.clone());
+        assert!(store.book.best(42).is_none());
+        assert!(store.record(42, GhostRun::new(3400, 12, &samples)));
+
+        let reloaded = GhostStore::from_file(path);
+        let best = reloaded.book.best(42).unwrap();
+        assert_eq!(best.score, 3400);
+        assert_eq!(best.days, 12);
+        assert_eq!(best.trail.expand(), samples);
+    }
+
+    #[test]
+    fn unreadable_file_yields_empty_book() {
+        let path = temp_ghosts_path();
+        std::fs::write(&path, "{ not json").unwrap();
+        assert!(load_ghosts(&path).is_err());
+        assert_eq!(GhostStore::from_file(path).book, GhostBook::new());
+    }
+}
@@ -0,0 +1,86 @@
+//! Map Export - Image files of the explored map
+//!
+//! Native builds encode the pixels as a PNG in the exports directory next to
+//! the save. The browser has no file system to write to, so there the pixels
+//! are drawn on a detached canvas and handed to the player as a download.
+
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
+
+/// Directory native exports are written to
+pub const MAP_EXPORT_DIR: &str = "exports";
+
+/// Save RGBA `pixels` of a `width` by `height` image as `file_name`
+///
+/// Returns where the image went: the file path, or the download name.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_png(
+    file_name: &str,
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+) -> InfrastructureResult<String> {
+    let failed = |e: &dyn std::fmt::Display| {
+        InfrastructureError::ExternalServiceError(format!("failed to export {}: {}", file_name, e))
+    };
+    std::fs::create_dir_all(MAP_EXPORT_DIR).map_err(|e| failed(&e))?;
+    let path = std::path::Path::new(MAP_EXPORT_DIR).join(file_name);
+    let file = std::fs::File::create(&path).map_err(|e| failed(&e))?;
+
+    let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| failed(&e))?;
+    writer.write_image_data(pixels).map_err(|e| failed(&e))?;
+    writer.finish().map_err(|e| failed(&e))?;
+    Ok(path.display().to_string())
+}
+
+/// Save RGBA `pixels` of a `width` by `height` image as `file_name`
+///
+/// Returns where the image went: the file path, or the download name.
+#[cfg(target_arch = "wasm32")]
+pub fn save_png(
+    file_name: &str,
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+) -> InfrastructureResult<String> {
+    use wasm_bindgen::{Clamped, JsCast};
+
+    let web_error = |message: &str| InfrastructureError::WebError(message.to_string());
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| web_error("No document object"))?;
+    let canvas = document
+        .create_element("canvas")
+        .ok()
+        .and_then(|element| element.dyn_into::<web_sys::HtmlCanvasElement>().ok())
+        .ok_or_else(|| web_error("Cannot create a canvas"))?;
+    canvas.set_width(width);
+    canvas.set_height(height);
+    let context = canvas
+        .get_context("2d")
+        .ok()
+        .flatten()
+        .and_then(|context| context.dyn_into::<web_sys::CanvasRenderingContext2d>().ok())
+        .ok_or_else(|| web_error("Canvas has no 2d context"))?;
+    let image =
+        web_sys::ImageData::new_with_u8_clamped_array_and_sh(Clamped(pixels), width, height)
+            .map_err(|_| web_error("Cannot build image data"))?;
+    context
+        .put_image_data(&image, 0.0, 0.0)
+        .map_err(|_| web_error("Cannot draw image data"))?;
+    let url = canvas
+        .to_data_url_with_type("image/png")
+        .map_err(|_| web_error("Cannot encode the image"))?;
+
+    let link = document
+        .create_element("a")
+        .ok()
+        .and_then(|element| element.dyn_into::<web_sys::HtmlAnchorElement>().ok())
+        .ok_or_else(|| web_error("Cannot create a download link"))?;
+    link.set_href(&url);
+    link.set_download(file_name);
+    link.click();
+    Ok(file_name.to_string())
+}
@@ -5,8 +5,20 @@
 //! libraries and frameworks to work with our domain model.
 //!
 //! ## Architecture
+//! - **Asset Integrity**: Manifest of bundled assets and the startup check
 //! - **Bevy Integration**: ECS components, systems, and resources
+//! - **Bug Reports**: Versioned triage bundles with a size cap
+//! - **Build Info**: Version, git hash and features fixed at compile time
+//! - **Clipboard**: Browser clipboard with a file fallback on native
+//! - **Console**: Ranked command completion and persistent command history
+//! - **Control Channel**: Scripted control server for playtesting (dev-tools)
+//! - **Ghosts**: Breadcrumbs of previous runs per world seed
+//! - **Map Export**: Image files of the explored map
+//! - **Profile**: Run counts and save slots buried by hardcore defeats
 //! - **Random Generation**: Platform-specific random number generation
+//! - **Save Games**: Versioned, compressed session saves with ordered migrations
+//! - **Settings**: Versioned persistence of player preferences
+//! - **Snapshots**: Per-subsystem state hashes for desync debugging
 //! - **Web Integration**: WebAssembly bindings and web-specific code
 //!
 //! ## Rules
@@ -15,14 +27,27 @@
 //! - Implements adapter patterns for external services
 //! - Handles platform-specific implementations
 
+pub mod assets;
 pub mod bevy;
+pub mod bug_report;
+pub mod build_info;
+pub mod clipboard;
+pub mod console;
+pub mod control;
+pub mod ghosts;
+pub mod map_export;
+pub mod packs;
+pub mod profile;
 pub mod random;
+pub mod saves;
+pub mod settings;
+pub mod snapshots;
 pub mod time;
 pub mod web;
 
 // Re-export common infrastructure types
 pub use bevy::{BevyGamePlugin, BevySystemsPlugin};
-pub use random::RandomNumberGenerator;
+pub use random::{DeterministicRng, RandomNumberGenerator, RngStreams};
 pub use time::TimeService;
 
 /// Infrastructure-specific error types
@@ -71,6 +96,7 @@ pub type InfrastructureResult<T> = Result<T, InfrastructureError>;
 
 /// Traits for infrastructure services
 pub mod traits {
+    use crate::domain::constants::{discovery_resource_at, DISCOVERY_WEIGHT_TOTAL};
     use crate::domain::{DiceRoll, DiceType, Position3D, ResourceType, TerrainType};
 
     /// Trait for random number generation adapted for RPG mechanics
@@ -98,9 +124,16 @@ pub mod traits {
         /// Perform a dice roll
         fn roll_dice(&self, dice_type: DiceType, count: u8) -> DiceRoll;
 
-        /// Generate random resource type for discovery
+        /// Generate a random resource type, every type equally likely
         fn random_resource_type(&self) -> ResourceType;
 
+        /// Pick the resource a discovery on `terrain` turns up, by the
+        /// odds of `constants::discovery_resource_weights`
+        fn random_resource_for_terrain(&self, terrain: TerrainType) -> ResourceType {
+            let roll = self.random_range_i32(0, DISCOVERY_WEIGHT_TOTAL as i32 - 1);
+            discovery_resource_at(terrain, roll as u32)
+        }
+
         /// Generate random terrain type for map generation
         fn random_terrain_type(&self) -> TerrainType;
 
@@ -0,0 +1,212 @@
+//! Data Pack Loading - Pack manifests found at startup
+//!
+//! Native builds read every `*.json` manifest in the `packs/` directory
+//! next to the game, in file name order. Web builds read the page's
+//! `spaceLooterPacks` global, set before the game script loads: one
+//! manifest or an array of them, as JSON text; a page that wants packs
+//! from a URL fetches them into the global first. A manifest that does not
+//! parse is skipped and reported, never keeping the game from starting.
+//!
+//! The loaded pack set is applied to the codex and the movement events at
+//! startup, and its fingerprint goes into every save written afterwards.
+
+use crate::domain::services::{DataPack, PackSet, TileMovementService};
+use crate::infrastructure::saves::set_active_packs;
+use crate::presentation::codex::CodexScreen;
+use bevy::prelude::*;
+use std::path::Path;
+
+/// Directory scanned for pack manifests on native builds
+pub const PACKS_DIR: &str = "packs";
+
+/// Plugin loading the data packs and applying them to the game data
+pub struct DataPacksPlugin;
+
+impl Plugin for DataPacksPlugin {
+    fn build(&self, app: &mut App) {
+        let packs = ActivePacks::load();
+        set_active_packs(&packs.set.fingerprint());
+        app.insert_resource(packs)
+            .add_systems(Startup, apply_data_packs_system);
+    }
+}
+
+/// The pack set in use and the manifests that could not be used
+#[derive(Resource, Debug, Default)]
+pub struct ActivePacks {
+    pub set: PackSet,
+    /// One line per manifest that was skipped
+    pub problems: Vec<String>,
+}
+
+impl ActivePacks {
+    /// Packs available to this build
+    pub fn load() -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let (packs, problems) = load_pack_dir(Path::new(PACKS_DIR));
+        #[cfg(target_arch = "wasm32")]
+        let (packs, problems) = parse_manifests(
+            web::inline_manifests()
+                .into_iter()
+                .enumerate()
+                .map(|(index, text)| (format!("inline pack {}", index + 1), text)),
+        );
+        Self::from_packs(packs, problems)
+    }
+
+    /// Order `packs` into a set, reporting every conflict and problem
+    pub fn from_packs(packs: Vec<DataPack>, problems: Vec<String>) -> Self {
+        let set = PackSet::new(packs);
+        for problem in &problems {
+            warn!("📦 {}", problem);
+        }
+        for conflict in set.conflicts() {
+            warn!("📦 Pack conflict {}", conflict.describe());
+        }
+        if !set.is_empty() {
+            info!(
+                "📦 {} data pack(s) loaded, set {}",
+                set.packs().len(),
+                set.fingerprint()
+            );
+        }
+        Self { set, problems }
+    }
+
+    /// Listing for the `packs` control query
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = self.set.listing();
+        lines.extend(
+            self.problems
+                .iter()
+                .map(|problem| format!("skipped {}", problem)),
+        );
+        lines
+    }
+}
+
+/// Read every manifest in `dir`, in file name order; a missing directory has none
+pub fn load_pack_dir(dir: &Path) -> (Vec<DataPack>, Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return (Vec::new(), Vec::new());
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .collect();
+    paths.sort();
+
+    let mut problems = Vec::new();
+    let manifests = paths.into_iter().filter_map(|path| {
+        let name = path.display().to_string();
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Some((name, text)),
+            Err(e) => {
+                problems.push(format!("{}: {}", name, e));
+                None
+            }
+        }
+    });
+    let manifests: Vec<_> = manifests.collect();
+    let (packs, mut invalid) = parse_manifests(manifests);
+    problems.append(&mut invalid);
+    (packs, problems)
+}
+
+/// Parse named manifest texts, keeping the ones that are valid
+fn parse_manifests(
+    manifests: impl IntoIterator<Item = (String, String)>,
+) -> (Vec<DataPack>, Vec<String>) {
+    let mut packs = Vec::new();
+    let mut problems = Vec::new();
+    for (name, text) in manifests {
+        match DataPack::parse(&text) {
+            Ok(pack) => packs.push(pack),
+            Err(e) => problems.push(format!("{}: {}", name, e)),
+        }
+    }
+    (packs, problems)
+}
+
+/// Apply the packs to the codex and the movement events
+fn apply_data_packs_system(
+    packs: Res<ActivePacks>,
+    codex: Option<ResMut<CodexScreen>>,
+    movement: Option<ResMut<TileMovementService>>,
+) {
+    if packs.set.is_empty() {
+        return;
+    }
+    if let Some(mut codex) = codex {
+        match packs.set.apply_codex(codex.codex()) {
+            Ok(merged) => codex.set_codex(merged),
+            Err(e) => warn!("📦 Pack codex entries not applied: {}", e),
+        }
+    }
+    if let Some(mut movement) = movement {
+        for event in packs.set.events() {
+            movement.add_event_template(
+                event.category,
+                event.event_type,
+                &event.title,
+                &event.description,
+            );
+        }
+    }
+}
+
+/// The page's pack global, read through `Reflect` like the save storage
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use wasm_bindgen::JsValue;
+
+    /// Name of the global holding inline manifests
+    const PACKS_GLOBAL: &str = "spaceLooterPacks";
+
+    pub fn inline_manifests() -> Vec<String> {
+        let Some(window) = web_sys::window() else {
+            return Vec::new();
+        };
+        let Some(text) = js_sys::Reflect::get(&window, &JsValue::from_str(PACKS_GLOBAL))
+            .ok()
+            .and_then(|value| value.as_string())
+        else {
+            return Vec::new();
+        };
+        match serde_json::from_str::<serde_json::Value>(&text) {
+            Ok(serde_json::Value::Array(manifests)) => manifests
+                .into_iter()
+                .map(|manifest| manifest.to_string())
+                .collect(),
+            _ => vec![text],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_directories_load_in_order_and_report_bad_manifests() {
+        let dir = std::env::temp_dir().join(format!("space-looter-packs-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("b.json"), r#"{"id": "second", "priority": 1}"#).unwrap();
+        std::fs::write(dir.join("a.json"), r#"{"id": "first", "priority": 1}"#).unwrap();
+        std::fs::write(dir.join("broken.json"), "{").unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a pack").unwrap();
+
+        let (packs, problems) = load_pack_dir(&dir);
+        let ids: Vec<&str> = packs.iter().map(|pack| pack.id.as_str()).collect();
+        assert_eq!(ids, ["first", "second"]);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("broken.json"));
+
+        let active = ActivePacks::from_packs(packs, problems);
+        assert_eq!(active.lines().len(), 3);
+        assert!(load_pack_dir(&dir.join("missing")).0.is_empty());
+    }
+}
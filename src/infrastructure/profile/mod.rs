@@ -0,0 +1,187 @@
+//! Profile Persistence - Run counts and buried save slots across runs
+//!
+//! The player profile outlives every run: it counts how runs were started
+//! and ended, and remembers which save slots a hardcore defeat buried so
+//! they can never be loaded again. It is a small versioned JSON file next
+//! to the settings. A missing file is a fresh profile; an unreadable one is
+//! replaced with a warning rather than keeping the game from starting.
+
+use crate::domain::services::{ProfileStats, RunEnd, RunMode, Tombstones};
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Version written by this build
+pub const PROFILE_VERSION: u32 = 1;
+
+/// Default profile file location for native builds
+pub const PROFILE_FILE_PATH: &str = "profile.json";
+
+/// The complete profile document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileFile {
+    pub version: u32,
+    pub stats: ProfileStats,
+    pub tombstones: Tombstones,
+}
+
+impl Default for ProfileFile {
+    fn default() -> Self {
+        Self {
+            version: PROFILE_VERSION,
+            stats: ProfileStats::default(),
+            tombstones: Tombstones::default(),
+        }
+    }
+}
+
+/// Load the profile from `path`; a missing file yields a fresh profile
+pub fn load_profile(path: &Path) -> InfrastructureResult<ProfileFile> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(ProfileFile::default()),
+        Err(e) => {
+            return Err(InfrastructureError::ExternalServiceError(format!(
+                "failed to read profile: {}",
+                e
+            )))
+        }
+    };
+    let file: ProfileFile = serde_json::from_str(&text).map_err(|e| {
+        InfrastructureError::ExternalServiceError(format!("invalid profile file: {}", e))
+    })?;
+    if file.version > PROFILE_VERSION {
+        return Err(InfrastructureError::ExternalServiceError(format!(
+            "unsupported profile version {} (this build writes {})",
+            file.version, PROFILE_VERSION
+        )));
+    }
+    Ok(file)
+}
+
+/// Write the profile to `path` as the current version
+pub fn save_profile(path: &Path, profile: &ProfileFile) -> InfrastructureResult<()> {
+    let file = ProfileFile {
+        version: PROFILE_VERSION,
+        ..profile.clone()
+    };
+    let json = serde_json::to_string_pretty(&file).map_err(|e| {
+        InfrastructureError::ExternalServiceError(format!("failed to serialize profile: {}", e))
+    })?;
+    std::fs::write(path, json).map_err(|e| {
+        InfrastructureError::ExternalServiceError(format!("failed to write profile: {}", e))
+    })
+}
+
+/// Player profile plus where to write it back
+#[derive(Resource, Debug)]
+pub struct ProfileStore {
+    profile: ProfileFile,
+    /// File backing the store; `None` keeps the profile in memory only
+    path: Option<PathBuf>,
+}
+
+impl ProfileStore {
+    /// Store backed by a profile file
+    pub fn from_file(path: PathBuf) -> Self {
+        let profile = load_profile(&path).unwrap_or_else(|e| {
+            warn!("🪪 Starting a fresh profile: {}", e);
+            ProfileFile::default()
+        });
+        Self {
+            profile,
+            path: Some(path),
+        }
+    }
+
+    /// Store that never touches the disk (web builds and tests)
+    pub fn in_memory() -> Self {
+        Self {
+            profile: ProfileFile::default(),
+            path: None,
+        }
+    }
+
+    /// Run counts so far
+    pub fn stats(&self) -> &ProfileStats {
+        &self.profile.stats
+    }
+
+    /// Save slots that may not be loaded
+    pub fn tombstones(&self) -> &Tombstones {
+        &self.profile.tombstones
+    }
+
+    /// Count a new run in `mode`
+    pub fn record_start(&mut self, mode: RunMode) {
+        self.profile.stats.record_start(mode);
+        self.write();
+    }
+
+    /// Count a run that ended
+    pub fn record_end(&mut self, end: RunEnd) {
+        self.profile.stats.record_end(end);
+        self.write();
+    }
+
+    /// Bury `slot` after a hardcore defeat
+    pub fn bury(&mut self, slot: &str) {
+        if self.profile.tombstones.bury(slot) {
+            self.write();
+        }
+    }
+
+    /// Free `slot` for a new run
+    pub fn lift(&mut self, slot: &str) {
+        if self.profile.tombstones.lift(slot) {
+            self.write();
+        }
+    }
+
+    fn write(&self) {
+        if let Some(path) = &self.path {
+            match save_profile(path, &self.profile) {
+                Ok(()) => debug!("🪪 Profile saved to {}", path.display()),
+                Err(e) => warn!("🪪 {}", e),
+            }
+        }
+    }
+}
+
+impl Default for ProfileStore {
+    fn default() -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        return Self::from_file(PathBuf::from(PROFILE_FILE_PATH));
+        #[cfg(target_arch = "wasm32")]
+        return Self::in_memory();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_and_tombstones_survive_a_reload() {
+        let dir =
+            std::env::temp_dir().join(format!("space-looter-profile-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("profile.json");
+
+        let mut store = ProfileStore::from_file(path.clone());
+        store.record_start(RunMode::Hardcore);
+        store.record_end(RunEnd::Defeated);
+        store.bury("savegame.json");
+        store.record_start(RunMode::Normal);
+        store.record_end(RunEnd::Abandoned);
+
+        let reloaded = ProfileStore::from_file(path);
+        assert_eq!(reloaded.stats().runs_started, 2);
+        assert_eq!(reloaded.stats().hardcore_runs_started, 1);
+        assert_eq!(reloaded.stats().runs_finished, 1);
+        assert_eq!(reloaded.stats().runs_abandoned, 1);
+        assert!(reloaded.tombstones().is_buried("savegame.json"));
+    }
+}
@@ -0,0 +1,554 @@
+//! Deterministic Gameplay Randomness
+//!
+//! The platform generators differ: native builds use fastrand, web builds use
+//! the linear congruential generator of [`WebRandomGenerator`]. Anything a
+//! replay, daily run or ghost depends on must instead draw from
+//! [`DeterministicRng`], which always runs that LCG, so a seed yields the
+//! same rolls on every platform. The game draws it through the named
+//! streams of [`super::RngStreams`], so cosmetic randomness such as ambient
+//! fauna goes to a volatile stream instead of shifting gameplay rolls.
+//!
+//! The LCG only uses wrapping integer arithmetic and plain `f32` conversions,
+//! divisions and additions, which IEEE 754 defines exactly, so debug and
+//! release builds agree as well. The tests pin the first 100 outputs of
+//! every method for seed `0xDEADBEEF` and the hash of a scripted headless
+//! run; a change to any of them breaks existing seeds.
+
+use super::WebRandomGenerator;
+use crate::domain::{DiceRoll, DiceType, Position3D, ResourceType, TerrainType};
+use crate::infrastructure::traits::RandomService;
+use rand::RngCore;
+
+/// Seeded generator for gameplay rolls, identical on every platform
+#[derive(Debug, Clone)]
+pub struct DeterministicRng {
+    inner: WebRandomGenerator,
+}
+
+impl DeterministicRng {
+    /// Create a generator for a seed
+    pub fn new(seed: u64) -> Self {
+        Self {
+            inner: WebRandomGenerator::new(seed),
+        }
+    }
+
+    /// Next raw output of the underlying LCG
+    pub fn next_raw(&self) -> u64 {
+        self.inner.next_u64()
+    }
+}
+
+impl RandomService for DeterministicRng {
+    fn random_f32(&self) -> f32 {
+        self.inner.random_f32()
+    }
+
+    fn random_range(&self, min: f32, max: f32) -> f32 {
+        self.inner.random_range(min, max)
+    }
+
+    fn random_range_i32(&self, min: i32, max: i32) -> i32 {
+        self.inner.random_range_i32(min, max)
+    }
+
+    fn random_position_3d(
+        &self,
+        min_x: i32,
+        max_x: i32,
+        min_y: i32,
+        max_y: i32,
+        min_z: i32,
+        max_z: i32,
+    ) -> Position3D {
+        self.inner
+            .random_position_3d(min_x, max_x, min_y, max_y, min_z, max_z)
+    }
+
+    fn roll_dice(&self, dice_type: DiceType, count: u8) -> DiceRoll {
+        self.inner.roll_dice(dice_type, count)
+    }
+
+    fn random_resource_type(&self) -> ResourceType {
+        self.inner.random_resource_type()
+    }
+
+    fn random_resource_for_terrain(&self, terrain: TerrainType) -> ResourceType {
+        self.inner.random_resource_for_terrain(terrain)
+    }
+
+    fn random_terrain_type(&self) -> TerrainType {
+        self.inner.random_terrain_type()
+    }
+
+    fn random_bool(&self, probability: f32) -> bool {
+        self.inner.random_bool(probability)
+    }
+}
+
+/// Lets domain services that take `impl Rng` draw from the canonical stream
+///
+/// 32-bit draws use the high half of an output, the better-mixed bits of an
+/// LCG. Note that `rand` samples `usize` ranges with 32-bit draws on wasm32
+/// and 64-bit draws elsewhere; gameplay code should sample fixed-width
+/// integer ranges to stay platform-identical.
+impl RngCore for DeterministicRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.inner.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.inner.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.inner.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::position::Direction;
+    use crate::infrastructure::random::create_gameplay_generator;
+
+    const SEED: u64 = 0xDEADBEEF;
+
+    /// Hash of [`scripted_run_hash`] for [`SEED`]
+    const SCRIPTED_RUN_HASH: u64 = 0xE745_4262_FDFF_6218;
+
+    const WEB_TERRAINS: [TerrainType; 7] = [
+        TerrainType::Plains,
+        TerrainType::Forest,
+        TerrainType::Mountains,
+        TerrainType::Desert,
+        TerrainType::Ocean,
+        TerrainType::Swamp,
+        TerrainType::Constructed,
+    ];
+
+    const GOLDEN_U64: [u64; 100] = [
+        6218547498573698,
+        2304349713340111097,
+        16211351022370123268,
+        6681583118989861267,
+        9458632668912761302,
+        497007576101824317,
+        404137237873835896,
+        17566480055199464567,
+        3416364248053526378,
+        9010900706112477121,
+        6358949372670283308,
+        17582257979586753435,
+        16449138111364584510,
+        15895046112449228421,
+        16220748859516882464,
+        6777485322640812799,
+        2950950886551878738,
+        6299466682404171657,
+        10832686379254395732,
+        9238491032684758691,
+        13810989400298601382,
+        17285249639354834637,
+        11382788992710652360,
+        9308823513525365383,
+        2499024776628495930,
+        3767928265441034321,
+        10044692359665384828,
+        10783651678267882155,
+        16595357620938903566,
+        15582911782051895317,
+        13371280268153211504,
+        3459933834438073103,
+        17525723691502505250,
+        10554784670577240601,
+        5451247531756653732,
+        5748868401141870515,
+        15814382359098945910,
+        4790071938636921437,
+        13642912364302497816,
+        12177530104126446743,
+        4399544955595690250,
+        16530879109850352865,
+        12646262954367615180,
+        16906495091002688955,
+        893656235055349726,
+        4596039716193363365,
+        7753092007632394944,
+        2447245626864373535,
+        17713723584346198514,
+        10509953910273886377,
+        609731746203236852,
+        10769401591724170435,
+        1036188165961997126,
+        8982800124913514989,
+        13182000097650414184,
+        12272906624870601383,
+        8323238275196628954,
+        13967646921887432049,
+        14472146329499179036,
+        15218133792734500043,
+        5869797880676197294,
+        15084971915753527093,
+        10673305820648816400,
+        17387446171518502703,
+        4191640972813208258,
+        16072760890200935225,
+        14923220012915096388,
+        816989472685411795,
+        5428907817936845078,
+        9372775082598129021,
+        13771230314410213560,
+        5763803666777066679,
+        9726402423518249642,
+        18164227380104368641,
+        6747247911421789036,
+        13188618694389131227,
+        16386431133254286206,
+        10240276116807806149,
+        16697596340950300512,
+        4827514708230226751,
+        6520744596255373202,
+        7313319791888970185,
+        12744912318904176788,
+        8324256148784759523,
+        11143226423644048102,
+        14690189303450101005,
+        13509699215696904968,
+        5425113368501553863,
+        2203302013155133818,
+        17200664706756430481,
+        3198626185330819772,
+        1742863364459724523,
+        12434975391340660558,
+        10651412921962063445,
+        8429805180420522928,
+        13354551932956164943,
+        13311341271934064738,
+        11940946854132087897,
+        6757833646945450468,
+        16769450824312437747,
+    ];
+    const GOLDEN_F32_BITS: [u32; 100] = [
+        0x39B0BDE3, 0x3DFFD58F, 0x3F60FA4A, 0x3EB97381, 0x3F0343D0, 0x3CDCB738, 0x3CB37916,
+        0x3F73C8AD, 0x3E3DA575, 0x3EFA1A4D, 0x3EB07F0E, 0x3F7400BA, 0x3F644714, 0x3F5C968C,
+        0x3F611BAD, 0x3EBC1CEE, 0x3E23CF89, 0x3EAED867, 0x3F16556F, 0x3F0035B7, 0x3F3FAA7E,
+        0x3F6FE18B, 0x3F1DF7CA, 0x3F012F96, 0x3E0AB947, 0x3E51297B, 0x3F0B65EA, 0x3F15A73A,
+        0x3F664E8E, 0x3F5841A0, 0x3F399055, 0x3E40109E, 0x3F7337E1, 0x3F127A21, 0x3E974D71,
+        0x3E9F902B, 0x3F5B77F9, 0x3E84F382, 0x3F3D555D, 0x3F28FF47, 0x3E74394B, 0x3F65697B,
+        0x3F2F808D, 0x3F6A9FF0, 0x3D466E7A, 0x3E7F21A7, 0x3ED73108, 0x3E07D974, 0x3F75D3CA,
+        0x3F11DADB, 0x3D07633D, 0x3F15749A, 0x3D66147A, 0x3EF952A2, 0x3F36EFDF, 0x3F2A5220,
+        0x3EE7042A, 0x3F41D70D, 0x3F48D764, 0x3F5331AC, 0x3EA2EB6C, 0x3F515896, 0x3F141F33,
+        0x3F714C9E, 0x3E68AECD, 0x3F5F0DEB, 0x3F4F19EE, 0x3D35687A, 0x3E96AEB6, 0x3F0212C9,
+        0x3F3F1D3D, 0x3E9FFA4A, 0x3F06FB1F, 0x3F7C144D, 0x3EBB4614, 0x3F370763, 0x3F63684C,
+        0x3F0E1CC5, 0x3F67B9C7, 0x3E85FD8E, 0x3EB4FCAD, 0x3ECAFC43, 0x3F30DF06, 0x3EE70B66,
+        0x3F1AA4B1, 0x3F4BDE09, 0x3F3B7C18, 0x3E9693C0, 0x3DF49D9C, 0x3F6EB509, 0x3E318F36,
+        0x3DC17F28, 0x3F2C91E8, 0x3F13D16C, 0x3EE9F95E, 0x3F3954E6, 0x3F38BB62, 0x3F25B6C4,
+        0x3EBB914C, 0x3F68B90F,
+    ];
+    const GOLDEN_RANGE_BITS: [u32; 100] = [
+        0xC09FE462, 0xC0700D44, 0x407271B8, 0xBFB05F3E, 0x3E029880, 0xC09760D8, 0xC098FD45,
+        0x4090BAD8, 0xC0497897, 0xBDEBE400, 0xBFC6C25C, 0x409100E8, 0x407AB1B0, 0x40677860,
+        0x4072C530, 0xBFA9B7AC, 0xC0599E4A, 0xBFCAE2FE, 0x3F5F5658, 0x3C064A00, 0x401F2A3C,
+        0x408BD9EE, 0x3F95D6F0, 0x3D3DBE00, 0xC0694C34, 0xC03D4613, 0x3EE3F640, 0x3F588840,
+        0x407FC464, 0x405CA410, 0x400FE8D4, 0xC047F59D, 0x409005DA, 0x3F38C548, 0xC002DF33,
+        0xBFF11794, 0x4064ABF0, 0xC019CF9E, 0x40195568, 0x3FCCFC64, 0xC0275C31, 0x407D87B4,
+        0x3FED82C0, 0x408547EC, 0xC0907F5E, 0xC0208AF8, 0xBF4C0AD8, 0xC06B1818, 0x409348BC,
+        0x3F328C90, 0xC0956C3F, 0x3F568E00, 0xC08E0666, 0xBE058B60, 0x400957AE, 0x3FD39AA0,
+        0xBEF9D660, 0x402499A0, 0x40361A7A, 0x404FFC30, 0xBFE8B372, 0x404B5D78, 0x3F493800,
+        0x408D9FC6, 0xC02E92C0, 0x406DA2CC, 0x4045C0D4, 0xC091D3D6, 0xC003A59C, 0x3DA5DEC0,
+        0x401DC918, 0xBFF00E48, 0x3E8B9E70, 0x409B1960, 0xBFABD0CE, 0x40099278, 0x407884C0,
+        0x3F0D1FB0, 0x4081A838, 0xC018830E, 0xBFBB8850, 0xBF848958, 0x3FF45B20, 0xBEF98E00,
+        0x3F853774, 0x403DAB16, 0x4014B63C, 0xC003C750, 0xC0738EBF, 0x408A624C, 0xC051067E,
+        0xC081C422, 0x3FDED988, 0x3F462E38, 0xBEDC4250, 0x400F5440, 0x400DD474, 0x3FBC91D4,
+        0xBFAB14C2, 0x4082E752,
+    ];
+    const GOLDEN_RANGE_I32: [i32; 100] = [
+        99, 98, 69, 68, 3, 18, 97, 68, 79, 22, 9, 36, 11, 22, 65, 100, 39, 58, 33, 92, 83, 38, 61,
+        84, 31, 22, 29, 56, 67, 18, 5, 4, 51, 2, 33, 16, 11, 38, 17, 44, 51, 66, 81, 56, 27, 66,
+        45, 36, 15, 78, 53, 36, 27, 90, 85, 84, 55, 50, 37, 44, 95, 94, 1, 4, 59, 26, 89, 96, 79,
+        22, 61, 80, 43, 42, 37, 28, 7, 50, 13, 52, 3, 86, 89, 24, 3, 6, 69, 64, 19, 82, 73, 24, 59,
+        46, 29, 44, 39, 98, 69, 48,
+    ];
+    const GOLDEN_POSITIONS: [(i32, i32, i32); 100] = [
+        (48, 4, 0),
+        (8, 8, 1),
+        (-12, 20, 2),
+        (10, -38, 3),
+        (-13, 44, 0),
+        (38, 48, 1),
+        (-3, -10, 2),
+        (8, -43, 3),
+        (13, 41, 0),
+        (2, 27, 1),
+        (-47, 30, 2),
+        (13, -7, 3),
+        (-44, -34, 0),
+        (36, 17, 1),
+        (-27, -13, 2),
+        (50, -44, 3),
+        (-22, -15, 0),
+        (6, 15, 1),
+        (36, -12, 2),
+        (15, 35, 3),
+        (15, -47, 0),
+        (50, 4, 1),
+        (-38, -37, 2),
+        (-11, -20, 3),
+        (-3, -16, 0),
+        (2, -23, 1),
+        (-14, -45, 2),
+        (-8, -34, 3),
+        (24, -49, 0),
+        (-24, 6, 1),
+        (-33, -44, 2),
+        (-14, 9, 3),
+        (-49, 21, 0),
+        (29, -15, 1),
+        (-45, 18, 2),
+        (43, -44, 3),
+        (-18, 26, 0),
+        (-35, -1, 1),
+        (43, -2, 2),
+        (-14, 4, 3),
+        (36, -19, 0),
+        (-21, -24, 1),
+        (23, -28, 2),
+        (-21, 44, 3),
+        (35, -12, 0),
+        (48, 21, 1),
+        (-19, 30, 2),
+        (-10, 38, 3),
+        (-47, -13, 0),
+        (-25, 44, 1),
+        (18, -15, 2),
+        (3, -2, 3),
+        (-25, -5, 0),
+        (2, 30, 1),
+        (17, -30, 2),
+        (-29, -36, 3),
+        (37, 18, 0),
+        (11, 29, 1),
+        (40, -5, 2),
+        (-11, -20, 3),
+        (-49, -5, 0),
+        (12, 6, 1),
+        (9, -30, 2),
+        (-37, 44, 3),
+        (41, 8, 0),
+        (2, 39, 1),
+        (17, -36, 2),
+        (39, 17, 3),
+        (-41, -15, 0),
+        (13, 46, 1),
+        (-10, -38, 2),
+        (-31, -30, 3),
+        (-29, -43, 0),
+        (-25, 11, 1),
+        (-18, -50, 2),
+        (7, -6, 3),
+        (-49, -17, 0),
+        (40, -16, 1),
+        (24, 50, 2),
+        (-15, -45, 3),
+        (-45, 41, 0),
+        (50, -26, 1),
+        (25, 49, 2),
+        (-44, -15, 3),
+        (10, -40, 0),
+        (45, 50, 1),
+        (-40, -41, 2),
+        (-44, 45, 3),
+        (-5, 0, 0),
+        (-24, -9, 1),
+        (-31, 47, 2),
+        (38, -9, 3),
+        (34, 2, 0),
+        (-5, 28, 1),
+        (-16, 11, 2),
+        (-48, -45, 3),
+        (-45, -29, 0),
+        (-35, 36, 1),
+        (43, 39, 2),
+        (-26, 29, 3),
+    ];
+    const GOLDEN_D20: [i32; 100] = [
+        19, 18, 9, 8, 3, 18, 17, 8, 19, 2, 9, 16, 11, 2, 5, 20, 19, 18, 13, 12, 3, 18, 1, 4, 11, 2,
+        9, 16, 7, 18, 5, 4, 11, 2, 13, 16, 11, 18, 17, 4, 11, 6, 1, 16, 7, 6, 5, 16, 15, 18, 13,
+        16, 7, 10, 5, 4, 15, 10, 17, 4, 15, 14, 1, 4, 19, 6, 9, 16, 19, 2, 1, 20, 3, 2, 17, 8, 7,
+        10, 13, 12, 3, 6, 9, 4, 3, 6, 9, 4, 19, 2, 13, 4, 19, 6, 9, 4, 19, 18, 9, 8,
+    ];
+    const GOLDEN_RESOURCE_INDEX: [usize; 100] = [
+        2, 1, 4, 3, 6, 5, 0, 7, 2, 1, 4, 3, 6, 5, 0, 7, 2, 1, 4, 3, 6, 5, 0, 7, 2, 1, 4, 3, 6, 5,
+        0, 7, 2, 1, 4, 3, 6, 5, 0, 7, 2, 1, 4, 3, 6, 5, 0, 7, 2, 1, 4, 3, 6, 5, 0, 7, 2, 1, 4, 3,
+        6, 5, 0, 7, 2, 1, 4, 3, 6, 5, 0, 7, 2, 1, 4, 3, 6, 5, 0, 7, 2, 1, 4, 3, 6, 5, 0, 7, 2, 1,
+        4, 3, 6, 5, 0, 7, 2, 1, 4, 3,
+    ];
+    const GOLDEN_TERRAIN_INDEX: [usize; 100] = [
+        1, 3, 5, 3, 3, 5, 3, 3, 0, 5, 3, 3, 3, 4, 0, 5, 3, 3, 6, 0, 4, 3, 4, 1, 3, 4, 1, 0, 4, 1,
+        2, 6, 2, 4, 0, 0, 1, 4, 1, 5, 0, 2, 6, 0, 2, 4, 3, 4, 0, 1, 6, 5, 1, 5, 0, 2, 2, 3, 2, 3,
+        1, 5, 6, 1, 1, 4, 2, 0, 4, 1, 6, 6, 5, 1, 6, 3, 4, 3, 2, 4, 5, 4, 6, 4, 5, 5, 6, 3, 4, 1,
+        4, 2, 1, 5, 1, 5, 1, 0, 0, 6,
+    ];
+    const GOLDEN_BOOL_0_3: [bool; 100] = [
+        true, true, false, false, false, true, true, false, true, false, false, false, false,
+        false, false, false, true, false, false, false, false, false, false, false, true, true,
+        false, false, false, false, false, true, false, false, true, false, false, true, false,
+        false, true, false, false, false, true, true, false, true, false, false, true, false, true,
+        false, false, false, false, false, false, false, false, false, false, false, true, false,
+        false, true, true, false, false, false, false, false, false, false, false, false, false,
+        true, false, false, false, false, false, false, false, true, true, false, true, true,
+        false, false, false, false, false, false, false, false,
+    ];
+
+    /// Collect the first 100 outputs of a method from a fresh generator
+    fn first_100<T>(
+        make: &dyn Fn() -> Box<dyn RandomService>,
+        draw: impl Fn(&dyn RandomService) -> T,
+    ) -> Vec<T> {
+        let rng = make();
+        (0..100).map(|_| draw(rng.as_ref())).collect()
+    }
+
+    fn assert_golden_vectors(make: &dyn Fn() -> Box<dyn RandomService>) {
+        assert_eq!(
+            first_100(make, |rng| rng.random_f32().to_bits()),
+            GOLDEN_F32_BITS
+        );
+        assert_eq!(
+            first_100(make, |rng| rng.random_range(-5.0, 5.0).to_bits()),
+            GOLDEN_RANGE_BITS
+        );
+        assert_eq!(
+            first_100(make, |rng| rng.random_range_i32(1, 100)),
+            GOLDEN_RANGE_I32
+        );
+        assert_eq!(
+            first_100(make, |rng| {
+                let position = rng.random_position_3d(-50, 50, -50, 50, 0, 3);
+                (position.x, position.y, position.z)
+            }),
+            GOLDEN_POSITIONS
+        );
+        assert_eq!(
+            first_100(make, |rng| rng.random_range_i32(1, 20)),
+            GOLDEN_D20
+        );
+        assert_eq!(
+            first_100(make, |rng| {
+                let resource_type = rng.random_resource_type();
+                ResourceType::all()
+                    .iter()
+                    .position(|candidate| *candidate == resource_type)
+                    .unwrap()
+            }),
+            GOLDEN_RESOURCE_INDEX
+        );
+        assert_eq!(
+            first_100(make, |rng| rng.random_terrain_type()),
+            GOLDEN_TERRAIN_INDEX.map(|index| WEB_TERRAINS[index])
+        );
+        assert_eq!(first_100(make, |rng| rng.random_bool(0.3)), GOLDEN_BOOL_0_3);
+
+        // A DiceRoll only keeps its specification, so check that rolling
+        // consumes exactly one draw per die
+        let rng = make();
+        for _ in 0..10 {
+            rng.roll_dice(DiceType::D20, 3);
+        }
+        assert_eq!(rng.random_range_i32(1, 20), GOLDEN_D20[30]);
+    }
+
+    /// Fifty turns of direction picks, movement rolls, gathering and event checks
+    fn scripted_run_hash(rng: &dyn RandomService) -> u64 {
+        let directions = Direction::horizontal();
+        let mut position = Position3D::origin();
+        let mut hash: u64 = 0xCBF2_9CE4_8422_2325;
+        for turn in 0..50 {
+            let direction = directions[rng.random_range_i32(0, 3) as usize];
+            let roll = rng.random_range_i32(1, 20);
+            if roll >= 8 {
+                position = position.move_direction(direction, 1);
+            }
+            let (resource, amount) = if roll >= 15 {
+                let resource_type = rng.random_resource_type();
+                let index = ResourceType::all()
+                    .iter()
+                    .position(|candidate| *candidate == resource_type)
+                    .unwrap() as i32;
+                (index, rng.random_range_i32(1, 6))
+            } else {
+                (-1, 0)
+            };
+            let event = rng.random_bool(0.1) as i32;
+
+            for value in [turn, position.x, position.y, roll, resource, amount, event] {
+                for byte in value.to_le_bytes() {
+                    hash ^= byte as u64;
+                    hash = hash.wrapping_mul(0x0100_0000_01B3);
+                }
+            }
+        }
+        hash
+    }
+
+    #[test]
+    fn web_generator_matches_golden_vectors() {
+        assert_golden_vectors(&|| Box::new(WebRandomGenerator::new(SEED)));
+    }
+
+    #[test]
+    fn deterministic_rng_matches_golden_vectors_on_every_platform() {
+        assert_golden_vectors(&|| Box::new(DeterministicRng::new(SEED)));
+
+        let rng = DeterministicRng::new(SEED);
+        let raw: Vec<u64> = (0..100).map(|_| rng.next_raw()).collect();
+        assert_eq!(raw, GOLDEN_U64);
+
+        let mut rng = DeterministicRng::new(SEED);
+        assert_eq!(RngCore::next_u64(&mut rng), GOLDEN_U64[0]);
+        assert_eq!(rng.next_u32(), (GOLDEN_U64[1] >> 32) as u32);
+        let mut bytes = [0u8; 12];
+        rng.fill_bytes(&mut bytes);
+        assert_eq!(bytes[..8], GOLDEN_U64[2].to_le_bytes());
+        assert_eq!(bytes[8..], GOLDEN_U64[3].to_le_bytes()[..4]);
+    }
+
+    #[test]
+    fn scripted_run_hash_is_stable() {
+        assert_eq!(
+            scripted_run_hash(&DeterministicRng::new(SEED)),
+            SCRIPTED_RUN_HASH
+        );
+        assert_eq!(
+            scripted_run_hash(create_gameplay_generator(SEED).as_ref()),
+            SCRIPTED_RUN_HASH
+        );
+        assert_ne!(
+            scripted_run_hash(&DeterministicRng::new(SEED + 1)),
+            SCRIPTED_RUN_HASH
+        );
+    }
+
+    /// The native generator is reproducible but intentionally not canonical
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn native_generator_is_seeded_but_platform_specific() {
+        use crate::infrastructure::random::NativeRandomGenerator;
+
+        let first = NativeRandomGenerator::new(SEED);
+        let second = NativeRandomGenerator::new(SEED);
+        let draws: Vec<i32> = (0..100).map(|_| first.random_range_i32(1, 100)).collect();
+        let again: Vec<i32> = (0..100).map(|_| second.random_range_i32(1, 100)).collect();
+        assert_eq!(draws, again);
+        assert_ne!(draws, GOLDEN_RANGE_I32);
+    }
+}
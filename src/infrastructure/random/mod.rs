@@ -1,13 +1,20 @@
 //! Random Number Generation Infrastructure
 //!
 //! This module provides random number generation services for the RPG game,
-//! with support for dice rolling and 3D coordinate generation.
+//! with support for dice rolling and 3D coordinate generation. Gameplay
+//! rolls use [`DeterministicRng`] so seeds replay identically everywhere,
+//! split into the named per-subsystem streams of [`RngStreams`].
 
+pub mod deterministic;
 pub mod generator;
+pub mod streams;
 
 // Re-export the main generator
+pub use deterministic::DeterministicRng;
 pub use generator::RandomNumberGenerator;
+pub use streams::{RngStreams, RngStreamsPlugin, StreamHandle};
 
+use crate::domain::constants::{discovery_resource_at, DISCOVERY_WEIGHT_TOTAL};
 use crate::domain::{DiceRoll, DiceType, Position3D, ResourceType, TerrainType};
 use crate::infrastructure::traits::{MouseButton, RandomService};
 use crate::infrastructure::InfrastructureResult;
@@ -130,6 +137,12 @@ impl RandomService for WebRandomGenerator {
         types[index]
     }
 
+    fn random_resource_for_terrain(&self, terrain: TerrainType) -> ResourceType {
+        // The high bits of the LCG are the well-mixed ones
+        let roll = (self.next_u64() >> 32) % DISCOVERY_WEIGHT_TOTAL as u64;
+        discovery_resource_at(terrain, roll as u32)
+    }
+
     fn random_terrain_type(&self) -> TerrainType {
         let types = [
             TerrainType::Plains,
@@ -264,6 +277,11 @@ impl RandomService for NativeRandomGenerator {
         types[index]
     }
 
+    fn random_resource_for_terrain(&self, terrain: TerrainType) -> ResourceType {
+        let roll = self.rng.lock().unwrap().u32(0..DISCOVERY_WEIGHT_TOTAL);
+        discovery_resource_at(terrain, roll)
+    }
+
     fn random_terrain_type(&self) -> TerrainType {
         let types = [
             TerrainType::Plains,
@@ -307,7 +325,18 @@ pub fn create_random_generator() -> Box<dyn RandomService> {
     }
 }
 
+/// Create the seeded generator for gameplay rolls
+///
+/// Unlike [`create_seeded_generator`], the output is the same on every
+/// platform, which replays, daily runs and ghosts rely on.
+pub fn create_gameplay_generator(seed: u64) -> Box<dyn RandomService> {
+    Box::new(DeterministicRng::new(seed))
+}
+
 /// Convenience function to create a seeded random generator
+///
+/// The sequence differs between native and web builds; use it only for
+/// cosmetic randomness.
 pub fn create_seeded_generator(seed: u64) -> Box<dyn RandomService> {
     #[cfg(target_arch = "wasm32")]
     {
@@ -451,6 +480,59 @@ mod tests {
         assert!(roll.rolls()[0] >= 1 && roll.rolls()[0] <= 20);
     }
 
+    /// Percent of `draws` discoveries on `terrain` that turned up each resource
+    fn discovery_shares(
+        rng: &dyn RandomService,
+        terrain: TerrainType,
+        draws: u32,
+    ) -> std::collections::HashMap<ResourceType, f32> {
+        let mut shares = std::collections::HashMap::new();
+        for _ in 0..draws {
+            *shares
+                .entry(rng.random_resource_for_terrain(terrain))
+                .or_insert(0.0) += 100.0 / draws as f32;
+        }
+        shares
+    }
+
+    #[test]
+    fn terrain_discoveries_follow_the_weights() {
+        use crate::domain::constants::discovery_resource_weights;
+
+        let mut generators: Vec<Box<dyn RandomService>> = vec![
+            Box::new(WebRandomGenerator::new(2024)),
+            Box::new(DeterministicRng::new(2024)),
+        ];
+        #[cfg(not(target_arch = "wasm32"))]
+        generators.push(Box::new(NativeRandomGenerator::new(2024)));
+
+        for generator in &generators {
+            for terrain in [
+                TerrainType::Mountains,
+                TerrainType::Forest,
+                TerrainType::Anomaly,
+                TerrainType::Constructed,
+            ] {
+                let weights = discovery_resource_weights(terrain);
+                let shares = discovery_shares(generator.as_ref(), terrain, 10_000);
+                for (resource_type, weight) in weights {
+                    let share = shares.get(resource_type).copied().unwrap_or(0.0);
+                    assert!(
+                        (share - *weight as f32).abs() < 2.5,
+                        "{:?} on {:?}: {:.1}% against {}%",
+                        resource_type,
+                        terrain,
+                        share,
+                        weight
+                    );
+                }
+                assert!(shares
+                    .keys()
+                    .all(|found| weights.iter().any(|(listed, _)| listed == found)));
+            }
+        }
+    }
+
     #[test]
     fn convenience_functions() {
         let _gen1 = create_random_generator();
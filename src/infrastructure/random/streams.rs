@@ -0,0 +1,474 @@
+//! RNG Streams - Named sub-streams of the world seed, one per subsystem
+//!
+//! A single generator shared by everything means a new consumer shifts
+//! every roll after it, so a replay or a daily from an older build stops
+//! matching. Instead each gameplay subsystem draws from its own stream,
+//! seeded from the world seed and the stream's name, which advances only
+//! when that subsystem rolls. Map generation gets a fresh stream per chunk,
+//! keyed by the chunk coordinate, so chunks come out the same whatever
+//! order they are generated in. Cosmetic randomness draws from the volatile
+//! stream, which gameplay never reads.
+//!
+//! [`RNG_STREAMS`] is the registry of streams and the consumers drawing
+//! from each. New streams and new volatile consumers are free; adding a
+//! consumer to a gameplay stream, reordering its draws or renaming a stream
+//! changes the rolls of existing seeds. That is a compatibility break:
+//! bump [`RNG_STREAMS_VERSION`] together with the share code version. The
+//! tests pin the registry fingerprint to the version to enforce it.
+
+use super::DeterministicRng;
+use crate::domain::services::hashing::{fnv1a_64, fnv1a_64_from, FNV64_OFFSET};
+use crate::domain::value_objects::position::ChunkCoordinate;
+use crate::infrastructure::bevy::resources::MapResource;
+use bevy::prelude::*;
+use rand::RngCore;
+use std::collections::BTreeMap;
+use std::ops::Deref;
+
+/// Movement d20s and their advantage dice
+pub const MOVEMENT_ROLLS: &str = "movement_rolls";
+
+/// Which event a move, a storm or the bulletin board produces
+pub const EVENT_GENERATION: &str = "event_generation";
+
+/// Everything rolled while the player rests
+pub const REST: &str = "rest";
+
+/// Salvage checks and gear handed out
+pub const LOOT: &str = "loot";
+
+/// Fights, flights and pursuits with hostiles
+pub const ENCOUNTERS: &str = "encounters";
+
+/// Trader offers and haggling
+pub const TRADE: &str = "trade";
+
+/// Rolls made meeting a mystery
+pub const MYSTERIES: &str = "mysteries";
+
+/// Per-chunk map generation, see [`RngStreams::chunk_stream`]
+pub const MAP_GEN: &str = "map_gen";
+
+/// Cosmetic randomness gameplay never reads
+pub const VOLATILE: &str = "volatile";
+
+/// Version of the gameplay stream layout in [`RNG_STREAMS`]
+pub const RNG_STREAMS_VERSION: u32 = 3;
+
+/// Whether a stream's rolls are part of a replay
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamKind {
+    /// Rolls a replay depends on; its consumers are fixed per version
+    Gameplay,
+    /// Rolls nothing depends on; consumers may be added freely
+    Volatile,
+}
+
+/// A registered stream and what draws from it, in draw order
+#[derive(Debug, Clone, Copy)]
+pub struct StreamSpec {
+    pub name: &'static str,
+    pub kind: StreamKind,
+    pub consumers: &'static [&'static str],
+}
+
+/// Every stream this build draws from
+pub const RNG_STREAMS: &[StreamSpec] = &[
+    StreamSpec {
+        name: MOVEMENT_ROLLS,
+        kind: StreamKind::Gameplay,
+        consumers: &["movement d20 and advantage dice"],
+    },
+    StreamSpec {
+        name: EVENT_GENERATION,
+        kind: StreamKind::Gameplay,
+        consumers: &[
+            "movement event template",
+            "storm hazard on a move",
+            "bulletin board postings",
+        ],
+    },
+    StreamSpec {
+        name: REST,
+        kind: StreamKind::Gameplay,
+        consumers: &["anomaly storm formation", "seasonal gear malfunction"],
+    },
+    StreamSpec {
+        name: LOOT,
+        kind: StreamKind::Gameplay,
+        consumers: &[
+            "wreck salvage check",
+            "trader bonus gear",
+            "resource found on a discovery",
+        ],
+    },
+    StreamSpec {
+        name: ENCOUNTERS,
+        kind: StreamKind::Gameplay,
+        consumers: &[
+            "hostile pursuit",
+            "combat rounds",
+            "flight from hostiles",
+            "rescue attempts and distress signals",
+        ],
+    },
+    StreamSpec {
+        name: TRADE,
+        kind: StreamKind::Gameplay,
+        consumers: &["trader offer", "haggle counter"],
+    },
+    StreamSpec {
+        name: MYSTERIES,
+        kind: StreamKind::Gameplay,
+        consumers: &["mystery encounter choices"],
+    },
+    // Terrain and resource nodes hash their position and draw nothing yet
+    StreamSpec {
+        name: MAP_GEN,
+        kind: StreamKind::Gameplay,
+        consumers: &[],
+    },
+    StreamSpec {
+        name: VOLATILE,
+        kind: StreamKind::Volatile,
+        consumers: &["ambient fauna"],
+    },
+];
+
+/// The registered stream called `name`
+pub fn stream_spec(name: &str) -> Option<&'static StreamSpec> {
+    RNG_STREAMS.iter().find(|spec| spec.name == name)
+}
+
+/// SplitMix64 finaliser, spreading close inputs over the whole range
+fn mix(mut value: u64) -> u64 {
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
+
+/// Seed of the stream `name` for a world seed
+pub fn stream_seed(world_seed: u64, name: &str) -> u64 {
+    mix(world_seed ^ fnv1a_64(name.as_bytes()))
+}
+
+/// Seed of the map generation stream of one chunk
+pub fn chunk_seed(world_seed: u64, chunk: ChunkCoordinate) -> u64 {
+    let mut seed = stream_seed(world_seed, MAP_GEN);
+    for axis in [chunk.x, chunk.y, chunk.z] {
+        seed = mix(seed ^ axis as u32 as u64);
+    }
+    seed
+}
+
+/// Hash of the gameplay streams and their consumers
+///
+/// Volatile streams are left out, so only changes that move gameplay rolls
+/// change the fingerprint.
+pub fn registry_fingerprint() -> u64 {
+    RNG_STREAMS
+        .iter()
+        .filter(|spec| spec.kind == StreamKind::Gameplay)
+        .fold(FNV64_OFFSET, |hash, spec| {
+            let hash = fnv1a_64_from(fnv1a_64_from(hash, spec.name.as_bytes()), &[0]);
+            let hash = spec.consumers.iter().fold(hash, |hash, consumer| {
+                fnv1a_64_from(fnv1a_64_from(hash, consumer.as_bytes()), &[0])
+            });
+            fnv1a_64_from(hash, &[0xFF])
+        })
+}
+
+/// The world's random streams
+///
+/// Drawing only needs `&self`, so a system can hold several streams at
+/// once; systems still take the resource as `ResMut` so the scheduler runs
+/// every system that draws in a fixed order.
+#[derive(Resource, Debug, Clone)]
+pub struct RngStreams {
+    seed: u64,
+    streams: BTreeMap<&'static str, DeterministicRng>,
+}
+
+impl Default for RngStreams {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl RngStreams {
+    /// Every registered stream of a world seed, none drawn from yet
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: RNG_STREAMS
+                .iter()
+                .filter(|spec| spec.name != MAP_GEN)
+                .map(|spec| {
+                    (
+                        spec.name,
+                        DeterministicRng::new(stream_seed(seed, spec.name)),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// World seed the streams derive from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Restart every stream from a new world seed
+    pub fn reseed(&mut self, seed: u64) {
+        *self = Self::new(seed);
+    }
+
+    /// The stream called `name`
+    ///
+    /// # Panics
+    ///
+    /// If `name` is not in [`RNG_STREAMS`], or is the chunk-keyed
+    /// [`MAP_GEN`]; an unregistered consumer would slip past the version.
+    pub fn stream(&self, name: &'static str) -> StreamHandle<'_> {
+        assert_ne!(name, MAP_GEN, "map generation draws from chunk_stream");
+        let rng = self
+            .streams
+            .get(name)
+            .unwrap_or_else(|| panic!("RNG stream {:?} is not registered in RNG_STREAMS", name));
+        StreamHandle { name, rng }
+    }
+
+    /// The volatile stream, for randomness no replay depends on
+    pub fn volatile(&self) -> StreamHandle<'_> {
+        self.stream(VOLATILE)
+    }
+
+    /// A fresh map generation stream for one chunk
+    ///
+    /// Each call starts the chunk's stream over, so generating a chunk
+    /// again, or other chunks first, yields the same draws.
+    pub fn chunk_stream(&self, chunk: ChunkCoordinate) -> DeterministicRng {
+        DeterministicRng::new(chunk_seed(self.seed, chunk))
+    }
+}
+
+/// Access to one named stream
+///
+/// Draws the same numbers as the stream's own [`DeterministicRng`].
+#[derive(Debug)]
+pub struct StreamHandle<'a> {
+    name: &'static str,
+    rng: &'a DeterministicRng,
+}
+
+impl StreamHandle<'_> {
+    /// Registered name of the stream
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl Deref for StreamHandle<'_> {
+    type Target = DeterministicRng;
+
+    fn deref(&self) -> &DeterministicRng {
+        self.rng
+    }
+}
+
+impl RngCore for StreamHandle<'_> {
+    fn next_u32(&mut self) -> u32 {
+        (self.rng.next_raw() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_raw()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.rng.next_raw().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// Plugin keeping the streams seeded from the current world
+pub struct RngStreamsPlugin;
+
+impl Plugin for RngStreamsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RngStreams>()
+            .add_systems(PreUpdate, reseed_rng_streams_system);
+    }
+}
+
+/// Restart the streams whenever a world with another seed is loaded
+fn reseed_rng_streams_system(
+    map_resource: Option<Res<MapResource>>,
+    mut streams: ResMut<RngStreams>,
+) {
+    let Some(seed) = map_resource
+        .as_ref()
+        .and_then(|map_resource| map_resource.overworld())
+        .map(|map| map.seed())
+    else {
+        return;
+    };
+    if seed != streams.seed() {
+        streams.reseed(seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::traits::RandomService;
+    use rand::Rng;
+
+    const SEED: u64 = 0xDEADBEEF;
+
+    /// Registry fingerprint of every [`RNG_STREAMS_VERSION`] so far
+    const FINGERPRINTS: [(u32, u64); 3] = [
+        (1, 0xBF8F_17E9_D401_EF66),
+        (2, 0x7BB3_9C26_D76E_25A6),
+        (3, 0x6E26_47AE_77D2_5E22),
+    ];
+
+    /// Hash of a scripted run drawing from the gameplay streams
+    ///
+    /// `cosmetic` draws from the volatile stream between every step, as a
+    /// new cosmetic consumer would.
+    fn scripted_run_hash(seed: u64, cosmetic: bool) -> u64 {
+        let streams = RngStreams::new(seed);
+        let mut hash: u64 = FNV64_OFFSET;
+        for turn in 0..50 {
+            let roll = streams.stream(MOVEMENT_ROLLS).random_range_i32(1, 20);
+            if cosmetic {
+                streams.volatile().gen_range(0..8u32);
+            }
+            let event = streams.stream(EVENT_GENERATION).random_bool(0.1) as i32;
+            let loot = if roll >= 15 {
+                streams.stream(LOOT).random_range_i32(1, 6)
+            } else {
+                0
+            };
+            if cosmetic {
+                streams.volatile().next_raw();
+            }
+            let rest = if turn % 10 == 9 {
+                streams.stream(REST).random_range_i32(1, 100)
+            } else {
+                0
+            };
+            for value in [turn, roll, event, loot, rest] {
+                hash = fnv1a_64_from(hash, &value.to_le_bytes());
+            }
+        }
+        hash
+    }
+
+    #[test]
+    fn draws_on_one_stream_leave_the_others_alone() {
+        let quiet = RngStreams::new(SEED);
+        let expected: Vec<u64> = (0..20)
+            .map(|_| quiet.stream(MOVEMENT_ROLLS).next_raw())
+            .collect();
+
+        let busy = RngStreams::new(SEED);
+        let mut drawn = Vec::new();
+        for draws in 0..20 {
+            for _ in 0..draws % 7 {
+                busy.stream(LOOT).next_raw();
+                busy.volatile().gen_range(0..100u32);
+            }
+            busy.stream(EVENT_GENERATION).random_bool(0.5);
+            drawn.push(busy.stream(MOVEMENT_ROLLS).next_raw());
+        }
+        assert_eq!(drawn, expected);
+
+        // Streams of one seed are not copies of one another
+        assert_ne!(
+            RngStreams::new(SEED).stream(LOOT).next_raw(),
+            RngStreams::new(SEED).stream(REST).next_raw()
+        );
+        assert_ne!(stream_seed(SEED, LOOT), stream_seed(SEED + 1, LOOT));
+    }
+
+    #[test]
+    fn chunks_generate_the_same_in_any_order() {
+        use crate::domain::entities::Map;
+        use crate::domain::services::MapService;
+        use crate::domain::value_objects::EntityId;
+        use crate::domain::value_objects::TileCoordinate;
+
+        let chunks: Vec<ChunkCoordinate> = (-1..=1)
+            .flat_map(|x| (-1..=1).map(move |y| ChunkCoordinate::new(x, y, 0)))
+            .collect();
+        let generate = |order: &[ChunkCoordinate]| {
+            let streams = RngStreams::new(SEED);
+            let service = MapService::new(SEED);
+            let mut map = Map::new(EntityId::generate(), "Order".to_string(), SEED).unwrap();
+            let mut scatter = BTreeMap::new();
+            for chunk in order {
+                let origin = chunk.to_world_origin(8);
+                service
+                    .generate_chunk(&mut map, origin, 8)
+                    .expect("chunk generates");
+                let mut rng = streams.chunk_stream(*chunk);
+                for _ in 0..4 {
+                    let tile = (
+                        origin.x + rng.gen_range(0..8i32),
+                        origin.y + rng.gen_range(0..8i32),
+                    );
+                    scatter.insert(tile, rng.next_raw());
+                }
+            }
+            let mut tiles: Vec<(TileCoordinate, String)> = map
+                .tiles()
+                .iter()
+                .map(|(coordinate, tile)| (*coordinate, format!("{:?}", tile)))
+                .collect();
+            tiles.sort_by_key(|(coordinate, _)| (coordinate.x, coordinate.y, coordinate.z));
+            (tiles, scatter)
+        };
+
+        let forward = generate(&chunks);
+        let mut shuffled = chunks.clone();
+        shuffled.reverse();
+        shuffled.swap(1, 5);
+        assert_eq!(generate(&shuffled), forward);
+        assert!(!forward.1.is_empty());
+    }
+
+    #[test]
+    fn new_volatile_consumers_keep_the_replay_hash() {
+        assert_eq!(
+            scripted_run_hash(SEED, true),
+            scripted_run_hash(SEED, false)
+        );
+        assert_ne!(
+            scripted_run_hash(SEED + 1, false),
+            scripted_run_hash(SEED, false)
+        );
+    }
+
+    /// Changing a gameplay stream without bumping the version fails here;
+    /// add the new fingerprint under the new version
+    #[test]
+    fn gameplay_registry_changes_bump_the_version() {
+        assert_eq!(
+            FINGERPRINTS.last(),
+            Some(&(RNG_STREAMS_VERSION, registry_fingerprint()))
+        );
+        let mut names: Vec<&str> = RNG_STREAMS.iter().map(|spec| spec.name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), RNG_STREAMS.len());
+    }
+}
@@ -0,0 +1,180 @@
+//! Save Compression - LZ4 blocks behind a small checked header
+//!
+//! A compressed save is `SLZ1`, the uncompressed length as a little-endian
+//! `u32`, an FNV-1a checksum of the uncompressed JSON as a little-endian
+//! `u64`, then one LZ4 block. Anything that does not start with the magic is
+//! taken for a save from before compression and read as plain JSON text.
+//! The sizes of the last save written are kept for the bug report.
+
+use super::SaveLoadError;
+use crate::domain::services::hashing::fnv1a_64;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// First bytes of every compressed save
+pub const COMPRESSED_SAVE_MAGIC: &[u8; 4] = b"SLZ1";
+
+/// Bytes of the header before the LZ4 block
+pub const COMPRESSED_SAVE_HEADER_LEN: usize = 16;
+
+/// Largest uncompressed save accepted, so a damaged header cannot ask for
+/// an absurd allocation
+const MAX_UNCOMPRESSED_BYTES: usize = 64 * 1024 * 1024;
+
+/// Sizes of the last save written, for the bug report
+static LAST_SAVE_SIZES: Mutex<Option<SaveSizes>> = Mutex::new(None);
+
+/// How big a save is before and after compression, in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveSizes {
+    pub uncompressed: usize,
+    /// Header included
+    pub compressed: usize,
+}
+
+impl SaveSizes {
+    /// Compressed size as a fraction of the uncompressed one
+    pub fn ratio(&self) -> f32 {
+        if self.uncompressed == 0 {
+            return 1.0;
+        }
+        self.compressed as f32 / self.uncompressed as f32
+    }
+
+    /// One line for the game log
+    pub fn describe(&self) -> String {
+        format!(
+            "{:.1} KB, {:.1} KB uncompressed ({:.0}%)",
+            self.compressed as f32 / 1024.0,
+            self.uncompressed as f32 / 1024.0,
+            self.ratio() * 100.0
+        )
+    }
+}
+
+/// Compress a serialized save
+pub fn compress_save(json: &str) -> (Vec<u8>, SaveSizes) {
+    let bytes = json.as_bytes();
+    let block = lz4_flex::block::compress(bytes);
+
+    let mut out = Vec::with_capacity(COMPRESSED_SAVE_HEADER_LEN + block.len());
+    out.extend_from_slice(COMPRESSED_SAVE_MAGIC);
+    out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    out.extend_from_slice(&fnv1a_64(bytes).to_le_bytes());
+    out.extend_from_slice(&block);
+
+    let sizes = SaveSizes {
+        uncompressed: bytes.len(),
+        compressed: out.len(),
+    };
+    (out, sizes)
+}
+
+/// Check if `bytes` carry the compressed save header
+pub fn is_compressed_save(bytes: &[u8]) -> bool {
+    bytes.starts_with(COMPRESSED_SAVE_MAGIC)
+}
+
+/// The save JSON in `bytes`, decompressing and checking it when compressed
+pub fn decompress_save(bytes: &[u8]) -> Result<String, SaveLoadError> {
+    if !is_compressed_save(bytes) {
+        // Saves from before compression are the JSON itself
+        return String::from_utf8(bytes.to_vec())
+            .map_err(|_| SaveLoadError::Unreadable("not a save file".to_string()));
+    }
+    if bytes.len() < COMPRESSED_SAVE_HEADER_LEN {
+        return Err(SaveLoadError::Corrupted("truncated header".to_string()));
+    }
+
+    let length = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as usize;
+    let mut checksum = [0u8; 8];
+    checksum.copy_from_slice(&bytes[8..COMPRESSED_SAVE_HEADER_LEN]);
+    let checksum = u64::from_le_bytes(checksum);
+    if length > MAX_UNCOMPRESSED_BYTES {
+        return Err(SaveLoadError::Corrupted(format!(
+            "header claims {} bytes",
+            length
+        )));
+    }
+
+    let json = lz4_flex::block::decompress(&bytes[COMPRESSED_SAVE_HEADER_LEN..], length)
+        .map_err(|e| SaveLoadError::Corrupted(format!("decompression failed: {}", e)))?;
+    if json.len() != length || fnv1a_64(&json) != checksum {
+        return Err(SaveLoadError::Corrupted("checksum mismatch".to_string()));
+    }
+    String::from_utf8(json).map_err(|_| SaveLoadError::Corrupted("not UTF-8".to_string()))
+}
+
+/// Remember the sizes of the save just written
+pub fn record_save_sizes(sizes: SaveSizes) {
+    if let Ok(mut last) = LAST_SAVE_SIZES.lock() {
+        *last = Some(sizes);
+    }
+}
+
+/// Sizes of the last save written this run, if any
+pub fn last_save_sizes() -> Option<SaveSizes> {
+    LAST_SAVE_SIZES.lock().ok().and_then(|last| *last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::saves::{parse_save, parse_save_bytes, save_to_json, SaveData};
+
+    fn sample_json() -> String {
+        let mut data = SaveData::default();
+        data.player.name = "Vex".to_string();
+        for flag in 0..200 {
+            data.flags.set_flag(&format!("explored_sector_{}", flag));
+        }
+        save_to_json(&data).unwrap()
+    }
+
+    #[test]
+    fn compressed_saves_round_trip_smaller() {
+        let json = sample_json();
+        let (bytes, sizes) = compress_save(&json);
+
+        assert!(is_compressed_save(&bytes));
+        assert_eq!(sizes.uncompressed, json.len());
+        assert_eq!(sizes.compressed, bytes.len());
+        assert!(sizes.ratio() < 0.5, "ratio {}", sizes.ratio());
+        assert_eq!(decompress_save(&bytes).unwrap(), json);
+        assert_eq!(
+            parse_save_bytes(&bytes).unwrap(),
+            parse_save(&json).unwrap()
+        );
+    }
+
+    #[test]
+    fn damaged_saves_fail_the_checksum() {
+        let (mut bytes, _) = compress_save(&sample_json());
+        // Flip a bit of the stored checksum: the data still decompresses
+        bytes[8] ^= 0x01;
+        assert_eq!(
+            decompress_save(&bytes),
+            Err(SaveLoadError::Corrupted("checksum mismatch".to_string()))
+        );
+
+        let (bytes, _) = compress_save(&sample_json());
+        assert!(matches!(
+            decompress_save(&bytes[..COMPRESSED_SAVE_HEADER_LEN - 1]),
+            Err(SaveLoadError::Corrupted(_))
+        ));
+        let truncated = &bytes[..bytes.len() - 8];
+        assert!(matches!(
+            parse_save_bytes(truncated),
+            Err(SaveLoadError::Corrupted(_))
+        ));
+    }
+
+    #[test]
+    fn uncompressed_saves_still_load() {
+        let legacy = include_str!("fixtures/save_v8.json");
+        assert!(!is_compressed_save(legacy.as_bytes()));
+        let loaded = parse_save_bytes(legacy.as_bytes()).unwrap();
+        assert_eq!(loaded, parse_save(legacy).unwrap());
+        assert_eq!(loaded.data.player.name, "Vex");
+    }
+}
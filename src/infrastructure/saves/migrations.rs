@@ -0,0 +1,449 @@
+//! Save Migrations - Ordered upgrades of old save payloads
+//!
+//! Each migration takes the `data` of a save at one version and returns it
+//! at the next. They work on plain JSON values so old fields can be renamed
+//! or defaulted without keeping the old Rust types around. To change the
+//! save schema: bump `SAVE_VERSION`, append a migration here, add a fixture
+//! for the new version and update `SAVE_SCHEMA_FINGERPRINT`.
+
+use serde_json::Value;
+
+/// Upgrade a save payload by one version
+pub type Migration = fn(Value) -> Result<Value, String>;
+
+/// A registered migration from `from` to `from + 1`
+#[derive(Debug, Clone, Copy)]
+pub struct SaveMigration {
+    pub from: u32,
+    pub description: &'static str,
+    pub apply: Migration,
+}
+
+/// Every migration, in version order starting at version 1
+pub const SAVE_MIGRATIONS: &[SaveMigration] = &[
+    SaveMigration {
+        from: 1,
+        description: "base storage renamed to stored_resources with the capacity rework",
+        apply: migrate_v1_to_v2,
+    },
+    SaveMigration {
+        from: 2,
+        description: "base buildings are saved; older saves start without any",
+        apply: migrate_v2_to_v3,
+    },
+    SaveMigration {
+        from: 3,
+        description: "faction reputation is saved; older saves start neutral with everyone",
+        apply: migrate_v3_to_v4,
+    },
+    SaveMigration {
+        from: 4,
+        description: "run mutators are saved; older runs were played without any",
+        apply: migrate_v4_to_v5,
+    },
+    SaveMigration {
+        from: 5,
+        description: "found gear is saved; older players start with none",
+        apply: migrate_v5_to_v6,
+    },
+    SaveMigration {
+        from: 6,
+        description: "pass-and-play parties are saved; older runs are single-player",
+        apply: migrate_v6_to_v7,
+    },
+    SaveMigration {
+        from: 7,
+        description: "session flags are saved; older runs remember no choices",
+        apply: migrate_v7_to_v8,
+    },
+    SaveMigration {
+        from: 8,
+        description: "wrecks are saved; older runs left none behind",
+        apply: migrate_v8_to_v9,
+    },
+    SaveMigration {
+        from: 9,
+        description: "the play heatmap is saved; older runs start with a blank one",
+        apply: migrate_v9_to_v10,
+    },
+    SaveMigration {
+        from: 10,
+        description: "the starting scenario is saved; older runs were Standard Drops",
+        apply: migrate_v10_to_v11,
+    },
+    SaveMigration {
+        from: 11,
+        description: "the Emergency Recall is saved; older runs still have theirs",
+        apply: migrate_v11_to_v12,
+    },
+    SaveMigration {
+        from: 12,
+        description: "the soundtrack is saved; older runs start the music afresh",
+        apply: migrate_v12_to_v13,
+    },
+    SaveMigration {
+        from: 13,
+        description: "tile decals are saved; older runs start with clean ground",
+        apply: migrate_v13_to_v14,
+    },
+    SaveMigration {
+        from: 14,
+        description: "the charted world is saved; older runs load into the world already open",
+        apply: migrate_v14_to_v15,
+    },
+    SaveMigration {
+        from: 15,
+        description: "player health is saved; older characters start unhurt",
+        apply: migrate_v15_to_v16,
+    },
+    SaveMigration {
+        from: 16,
+        description: "carried items are saved; older characters carry none",
+        apply: migrate_v16_to_v17,
+    },
+];
+
+/// Migrations needed to bring a save at `version` up to `target`
+pub fn migrations_between(
+    version: u32,
+    target: u32,
+) -> impl Iterator<Item = &'static SaveMigration> {
+    SAVE_MIGRATIONS
+        .iter()
+        .filter(move |migration| migration.from >= version && migration.from < target)
+}
+
+/// v1 kept base storage under `resources`; capped storage renamed it
+fn migrate_v1_to_v2(mut data: Value) -> Result<Value, String> {
+    let base = data
+        .get_mut("base")
+        .and_then(Value::as_object_mut)
+        .ok_or("missing base section")?;
+    let stored = base
+        .remove("resources")
+        .unwrap_or_else(|| serde_json::json!({ "resources": {} }));
+    base.insert("stored_resources".to_string(), stored);
+    Ok(data)
+}
+
+/// v2 did not save buildings
+fn migrate_v2_to_v3(mut data: Value) -> Result<Value, String> {
+    let base = data
+        .get_mut("base")
+        .and_then(Value::as_object_mut)
+        .ok_or("missing base section")?;
+    base.entry("buildings")
+        .or_insert_with(|| Value::Array(Vec::new()));
+    Ok(data)
+}
+
+/// v3 had no faction reputation
+fn migrate_v3_to_v4(mut data: Value) -> Result<Value, String> {
+    let data_object = data.as_object_mut().ok_or("save data is not an object")?;
+    data_object.entry("reputation").or_insert_with(|| {
+        serde_json::json!({
+            "scavenger_guild": 0,
+            "colonial_authority": 0,
+            "free_traders": 0
+        })
+    });
+    Ok(data)
+}
+
+/// v4 had no run mutators
+fn migrate_v4_to_v5(mut data: Value) -> Result<Value, String> {
+    let data_object = data.as_object_mut().ok_or("save data is not an object")?;
+    data_object
+        .entry("mutators")
+        .or_insert_with(|| Value::Array(Vec::new()));
+    Ok(data)
+}
+
+/// v5 had no gear
+fn migrate_v5_to_v6(mut data: Value) -> Result<Value, String> {
+    let player = data
+        .get_mut("player")
+        .and_then(Value::as_object_mut)
+        .ok_or("missing player section")?;
+    player.entry("gear").or_insert_with(|| {
+        serde_json::json!({
+            "equipped": {},
+            "stash": [],
+            "next_id": 1
+        })
+    });
+    Ok(data)
+}
+
+/// v6 runs had a single character
+fn migrate_v6_to_v7(mut data: Value) -> Result<Value, String> {
+    let data_object = data.as_object_mut().ok_or("save data is not an object")?;
+    data_object.entry("party").or_insert(Value::Null);
+    Ok(data)
+}
+
+/// v7 had no session flags
+fn migrate_v7_to_v8(mut data: Value) -> Result<Value, String> {
+    let data_object = data.as_object_mut().ok_or("save data is not an object")?;
+    data_object
+        .entry("flags")
+        .or_insert_with(|| Value::Object(serde_json::Map::new()));
+    Ok(data)
+}
+
+/// v8 had no wrecks
+fn migrate_v8_to_v9(mut data: Value) -> Result<Value, String> {
+    let data_object = data.as_object_mut().ok_or("save data is not an object")?;
+    data_object
+        .entry("wrecks")
+        .or_insert_with(|| serde_json::json!({ "wrecks": [], "debris": [] }));
+    Ok(data)
+}
+
+/// v9 kept no heatmap
+fn migrate_v9_to_v10(mut data: Value) -> Result<Value, String> {
+    let data_object = data.as_object_mut().ok_or("save data is not an object")?;
+    data_object
+        .entry("heatmap")
+        .or_insert_with(|| serde_json::json!({ "tiles": [] }));
+    Ok(data)
+}
+
+/// v10 had no starting scenarios; every run was a Standard Drop
+fn migrate_v10_to_v11(mut data: Value) -> Result<Value, String> {
+    let data_object = data.as_object_mut().ok_or("save data is not an object")?;
+    data_object
+        .entry("scenario")
+        .or_insert_with(|| Value::String("standard_drop".to_string()));
+    Ok(data)
+}
+
+/// v11 had no Emergency Recall; older runs keep an unused one
+fn migrate_v11_to_v12(mut data: Value) -> Result<Value, String> {
+    let data_object = data.as_object_mut().ok_or("save data is not an object")?;
+    data_object.entry("emergency_recall").or_insert_with(
+        || serde_json::json!({ "enabled": true, "score_penalty": false, "used_on_day": null }),
+    );
+    Ok(data)
+}
+
+/// v12 did not remember the soundtrack
+fn migrate_v12_to_v13(mut data: Value) -> Result<Value, String> {
+    let data_object = data.as_object_mut().ok_or("save data is not an object")?;
+    data_object.entry("audio").or_insert(Value::Null);
+    Ok(data)
+}
+
+/// v13 left no decals behind
+fn migrate_v13_to_v14(mut data: Value) -> Result<Value, String> {
+    let data_object = data.as_object_mut().ok_or("save data is not an object")?;
+    data_object
+        .entry("decals")
+        .or_insert_with(|| serde_json::json!({ "sites": [] }));
+    Ok(data)
+}
+
+/// v14 did not keep the world, so the one already generated is played on
+fn migrate_v14_to_v15(mut data: Value) -> Result<Value, String> {
+    let data_object = data.as_object_mut().ok_or("save data is not an object")?;
+    data_object.entry("world").or_insert(Value::Null);
+    Ok(data)
+}
+
+/// v15 had no health, so every character was unhurt at the base 100
+fn migrate_v15_to_v16(mut data: Value) -> Result<Value, String> {
+    let unhurt = serde_json::json!({ "current": 100, "max": 100 });
+    let player = data
+        .get_mut("player")
+        .and_then(Value::as_object_mut)
+        .ok_or("missing player section")?;
+    player.entry("health").or_insert_with(|| unhurt.clone());
+    if let Some(benched) = data
+        .pointer_mut("/party/benched")
+        .and_then(Value::as_object_mut)
+    {
+        benched.entry("health").or_insert(unhurt);
+    }
+    Ok(data)
+}
+
+/// v16 did not keep carried items, so every character carried none
+fn migrate_v16_to_v17(mut data: Value) -> Result<Value, String> {
+    let player = data
+        .get_mut("player")
+        .and_then(Value::as_object_mut)
+        .ok_or("missing player section")?;
+    player
+        .entry("items")
+        .or_insert_with(|| Value::Array(Vec::new()));
+    if let Some(benched) = data
+        .pointer_mut("/party/benched")
+        .and_then(Value::as_object_mut)
+    {
+        benched
+            .entry("items")
+            .or_insert_with(|| Value::Array(Vec::new()));
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn migrations_are_contiguous_from_version_one() {
+        for (index, migration) in SAVE_MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.from, index as u32 + 1);
+        }
+        assert_eq!(migrations_between(1, 4).count(), 3);
+        assert_eq!(migrations_between(2, 4).count(), 2);
+        assert_eq!(migrations_between(4, 4).count(), 0);
+        assert_eq!(migrations_between(1, 5).count(), 4);
+        assert_eq!(migrations_between(1, 6).count(), 5);
+        assert_eq!(migrations_between(1, 7).count(), 6);
+        assert_eq!(migrations_between(1, 8).count(), 7);
+        assert_eq!(migrations_between(1, 9).count(), 8);
+    }
+
+    #[test]
+    fn v1_base_resources_are_renamed() {
+        let v1 = json!({
+            "base": { "name": "Outpost", "resources": { "resources": { "Metal": 40 } } }
+        });
+
+        let v2 = migrate_v1_to_v2(v1).unwrap();
+
+        assert!(v2["base"].get("resources").is_none());
+        assert_eq!(v2["base"]["stored_resources"]["resources"]["Metal"], 40);
+        assert!(migrate_v1_to_v2(json!({})).is_err());
+    }
+
+    #[test]
+    fn v2_bases_start_without_buildings() {
+        let v3 = migrate_v2_to_v3(json!({ "base": { "name": "Outpost" } })).unwrap();
+        assert_eq!(v3["base"]["buildings"], json!([]));
+        assert!(migrate_v2_to_v3(json!({})).is_err());
+    }
+
+    #[test]
+    fn v3_saves_start_neutral_with_every_faction() {
+        let v4 = migrate_v3_to_v4(json!({ "base": {} })).unwrap();
+        assert_eq!(v4["reputation"]["scavenger_guild"], 0);
+        assert_eq!(v4["reputation"]["free_traders"], 0);
+        assert!(migrate_v3_to_v4(json!([])).is_err());
+    }
+
+    #[test]
+    fn v4_runs_have_no_mutators() {
+        let v5 = migrate_v4_to_v5(json!({ "base": {} })).unwrap();
+        assert_eq!(v5["mutators"], json!([]));
+        assert!(migrate_v4_to_v5(json!([])).is_err());
+    }
+
+    #[test]
+    fn v5_players_start_without_gear() {
+        let v6 = migrate_v5_to_v6(json!({ "player": { "name": "Vex" } })).unwrap();
+        assert_eq!(v6["player"]["gear"]["stash"], json!([]));
+        assert_eq!(v6["player"]["gear"]["next_id"], 1);
+        assert!(migrate_v5_to_v6(json!({})).is_err());
+    }
+
+    #[test]
+    fn v6_runs_have_no_party() {
+        let v7 = migrate_v6_to_v7(json!({ "player": { "name": "Vex" } })).unwrap();
+        assert_eq!(v7["party"], Value::Null);
+        assert!(v7.get("party").is_some());
+        assert!(migrate_v6_to_v7(json!([])).is_err());
+    }
+
+    #[test]
+    fn v7_runs_remember_no_choices() {
+        let v8 = migrate_v7_to_v8(json!({ "player": { "name": "Vex" } })).unwrap();
+        assert_eq!(v8["flags"], json!({}));
+        assert!(migrate_v7_to_v8(json!([])).is_err());
+    }
+
+    #[test]
+    fn v8_runs_have_no_wrecks() {
+        let v9 = migrate_v8_to_v9(json!({ "player": { "name": "Vex" } })).unwrap();
+        assert_eq!(v9["wrecks"], json!({ "wrecks": [], "debris": [] }));
+        assert!(migrate_v8_to_v9(json!([])).is_err());
+    }
+
+    #[test]
+    fn v9_runs_start_a_blank_heatmap() {
+        let v10 = migrate_v9_to_v10(json!({ "player": { "name": "Vex" } })).unwrap();
+        assert_eq!(v10["heatmap"], json!({ "tiles": [] }));
+        assert!(migrate_v9_to_v10(json!([])).is_err());
+    }
+
+    #[test]
+    fn v10_runs_were_standard_drops() {
+        let v11 = migrate_v10_to_v11(json!({ "player": { "name": "Vex" } })).unwrap();
+        assert_eq!(v11["scenario"], json!("standard_drop"));
+        assert!(migrate_v10_to_v11(json!([])).is_err());
+    }
+
+    #[test]
+    fn v11_runs_keep_an_unused_recall() {
+        let v12 = migrate_v11_to_v12(json!({ "player": { "name": "Vex" } })).unwrap();
+        assert_eq!(v12["emergency_recall"]["enabled"], json!(true));
+        assert_eq!(v12["emergency_recall"]["used_on_day"], json!(null));
+        assert!(migrate_v11_to_v12(json!([])).is_err());
+    }
+
+    #[test]
+    fn v12_runs_start_the_music_afresh() {
+        let v13 = migrate_v12_to_v13(json!({ "player": { "name": "Vex" } })).unwrap();
+        assert_eq!(v13["audio"], Value::Null);
+        assert!(v13.get("audio").is_some());
+        assert!(migrate_v12_to_v13(json!([])).is_err());
+    }
+
+    #[test]
+    fn v13_runs_start_with_clean_ground() {
+        let v14 = migrate_v13_to_v14(json!({ "player": { "name": "Vex" } })).unwrap();
+        assert_eq!(v14["decals"], json!({ "sites": [] }));
+        assert!(migrate_v13_to_v14(json!([])).is_err());
+    }
+
+    #[test]
+    fn v14_runs_keep_the_world_they_load_into() {
+        let v15 = migrate_v14_to_v15(json!({ "player": { "name": "Vex" } })).unwrap();
+        assert_eq!(v15["world"], Value::Null);
+        assert!(migrate_v14_to_v15(json!("v14")).is_err());
+    }
+
+    #[test]
+    fn v15_characters_start_unhurt() {
+        let v16 = migrate_v15_to_v16(json!({
+            "player": { "name": "Vex" },
+            "party": { "benched": { "name": "Rook" } }
+        }))
+        .unwrap();
+        assert_eq!(
+            v16["player"]["health"],
+            json!({ "current": 100, "max": 100 })
+        );
+        assert_eq!(v16["party"]["benched"]["health"]["current"], 100);
+        let solo = migrate_v15_to_v16(json!({ "player": {}, "party": null })).unwrap();
+        assert_eq!(solo["party"], Value::Null);
+        assert!(migrate_v15_to_v16(json!({})).is_err());
+    }
+
+    #[test]
+    fn v16_characters_carry_no_items() {
+        let v17 = migrate_v16_to_v17(json!({
+            "player": { "name": "Vex" },
+            "party": { "benched": { "name": "Rook" } }
+        }))
+        .unwrap();
+        assert_eq!(v17["player"]["items"], json!([]));
+        assert_eq!(v17["party"]["benched"]["items"], json!([]));
+        let solo = migrate_v16_to_v17(json!({ "player": {}, "party": null })).unwrap();
+        assert_eq!(solo["party"], Value::Null);
+        assert!(migrate_v16_to_v17(json!({})).is_err());
+    }
+}
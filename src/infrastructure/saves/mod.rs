@@ -0,0 +1,856 @@
+//! Save Games - Versioned envelope around the session with explicit migrations
+//!
+//! A save file is `{ version, created_with, data }`. Loading reads the
+//! envelope as plain JSON, runs every registered migration from the stored
+//! version up to `SAVE_VERSION`, and only then deserializes the payload into
+//! today's types. The one save that is refused is one written by a newer
+//! build; its error message is meant to be shown to the player as-is. A
+//! slot buried by a defeated hardcore run is refused the same way, and so
+//! is a save made with a different set of data packs than the one loaded.
+//!
+//! Saves are written compressed behind a checksummed header; files without
+//! the header are older plain JSON saves and load as before. Where they are
+//! kept is up to a `PersistenceService`: a file on native builds, the
+//! browser's localStorage on the web.
+
+pub mod compression;
+pub mod migrations;
+pub mod storage;
+pub mod store;
+pub mod world;
+
+pub use compression::{compress_save, decompress_save, last_save_sizes, SaveSizes};
+pub use migrations::{migrations_between, Migration, SaveMigration, SAVE_MIGRATIONS};
+pub use store::{platform_save_store, FileSaveStore, SaveGameService};
+pub use world::{ExploredTile, RunCounters, WorldSave};
+
+use crate::domain::constants::{BASE_PLAYER_HEALTH, PARTY_SIZE};
+use crate::domain::entities::game::DifficultyLevel;
+use crate::domain::entities::{Base, BaseBuilding, BuildingType, InventoryItem, Player};
+use crate::domain::services::hashing::fnv1a_64;
+use crate::domain::services::{
+    AudioMemory, Contribution, EmergencyRecall, ExpeditionPlan, Gear, HotSeat, ModifierStack,
+    Mutators, Party, PlayHeatmap, Reputation, SessionFlags, TileDecals, Tombstones, WreckField,
+};
+use crate::domain::value_objects::{EntityId, Health, PlayerStats, Position3D, ResourceCollection};
+use crate::infrastructure::build_info::BuildInfo;
+use crate::presentation::game_state::RpgGameSession;
+use bevy::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Version written by this build
+///
+/// - v1: player, base storage under `resources`, play time and expedition
+/// - v2: base storage renamed to `stored_resources`
+/// - v3: constructed base buildings
+/// - v4: standing with the spaceport factions
+/// - v5: run mutators
+/// - v6: found gear
+/// - v7: pass-and-play party
+/// - v8: session flags
+/// - v9: wrecks and debris of won fights
+/// - v10: per-tile play heatmap
+/// - v11: starting scenario
+/// - v12: Emergency Recall
+/// - v13: music track, ambient and adaptive volume
+/// - v14: tile decals
+/// - v15: charted world, run counters and game clock
+/// - v16: player health
+/// - v17: carried items
+pub const SAVE_VERSION: u32 = 17;
+
+/// Fingerprint of the `SAVE_VERSION` schema, see [`schema_fingerprint`]
+pub const SAVE_SCHEMA_FINGERPRINT: u64 = 0x3f28_7d94_9dc8_c7a0;
+
+/// Default save file location for native builds
+pub const SAVE_FILE_PATH: &str = "savegame.json";
+
+/// Fingerprint of the data packs in use, written into every save
+static ACTIVE_PACKS: Mutex<String> = Mutex::new(String::new());
+
+/// Why a save could not be loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SaveLoadError {
+    /// The file could not be read or is not a save envelope
+    Unreadable(String),
+    /// The save was written by a newer build than this one
+    NewerVersion { version: u32, created_with: String },
+    /// A migration rejected the stored payload
+    MigrationFailed { from: u32, reason: String },
+    /// The upgraded payload does not describe a valid session
+    InvalidData(String),
+    /// The compressed save failed its integrity check
+    Corrupted(String),
+    /// The slot belongs to a hardcore run that was defeated
+    RunEnded(String),
+    /// The save was made with other data packs than the ones loaded
+    PackMismatch { saved: String, active: String },
+}
+
+impl std::fmt::Display for SaveLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveLoadError::Unreadable(reason) => {
+                write!(f, "This save file could not be read ({})", reason)
+            }
+            SaveLoadError::NewerVersion {
+                version,
+                created_with,
+            } => write!(
+                f,
+                "This save was made with Space Looter {} (save format {}). \
+                 Update the game to {} or later to continue it; the file was left untouched.",
+                created_with, version, created_with
+            ),
+            SaveLoadError::MigrationFailed { from, reason } => write!(
+                f,
+                "This save could not be upgraded from format {} ({})",
+                from, reason
+            ),
+            SaveLoadError::InvalidData(reason) => {
+                write!(f, "This save is damaged ({})", reason)
+            }
+            SaveLoadError::Corrupted(reason) => write!(
+                f,
+                "This save is corrupted ({}) and cannot be loaded; the file was left untouched.",
+                reason
+            ),
+            SaveLoadError::RunEnded(slot) => write!(
+                f,
+                "The hardcore run saved in {} has ended and cannot be continued.",
+                slot
+            ),
+            SaveLoadError::PackMismatch { saved, active } => {
+                let describe = |packs: &String| {
+                    if packs.is_empty() {
+                        "no data packs".to_string()
+                    } else {
+                        format!("data pack set {}", packs)
+                    }
+                };
+                write!(
+                    f,
+                    "This save needs {} but the game runs with {}. \
+                     Load the same packs to continue it; the file was left untouched.",
+                    describe(saved),
+                    describe(active)
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SaveLoadError {}
+
+/// The on-disk save document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveEnvelope<T = Value> {
+    pub version: u32,
+    /// Game version that wrote the file
+    pub created_with: String,
+    /// Fingerprint of the data packs in use, empty for the shipped data
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub packs: String,
+    pub data: T,
+}
+
+/// Saved player progress
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSave {
+    pub name: String,
+    pub position: Position3D,
+    pub stats: PlayerStats,
+    /// Experience points; the level follows from them
+    pub experience: u32,
+    pub resources: ResourceCollection,
+    pub movement_points: u8,
+    pub health: Health,
+    pub gear: Gear,
+    /// Carried item stacks
+    pub items: Vec<InventoryItem>,
+}
+
+impl PlayerSave {
+    fn from_player(player: &Player) -> Self {
+        Self {
+            name: player.name().to_string(),
+            position: *player.position(),
+            stats: *player.stats(),
+            experience: player.experience().points(),
+            resources: player.resources().clone(),
+            movement_points: player.movement_points(),
+            health: *player.health(),
+            gear: player.gear().clone(),
+            items: player.inventory().items().to_vec(),
+        }
+    }
+
+    fn into_player(self, modifiers: &ModifierStack) -> Result<Player, SaveLoadError> {
+        let invalid = |e: crate::domain::DomainError| SaveLoadError::InvalidData(e.to_string());
+
+        let mut player = Player::new(EntityId::generate(), self.name, self.position, self.stats)
+            .map_err(invalid)?;
+        // Replaying the experience applies the same level-ups as in play
+        player.add_experience(self.experience).map_err(invalid)?;
+        player.set_max_movement_points(modifiers.max_movement(player.max_movement_points()));
+        player.set_gear(self.gear);
+        player.restore_points();
+        player.subtract_movement_points(
+            player
+                .max_movement_points()
+                .saturating_sub(self.movement_points),
+        );
+        *player.resources_mut() = self.resources;
+        for item in self.items {
+            // Saved stacks were already carried, so no capacity applies
+            player.inventory_mut().add_item(item, u32::MAX);
+        }
+        player.set_health(Health::new(self.health.current(), self.health.max()).map_err(invalid)?);
+        Ok(player)
+    }
+}
+
+impl Default for PlayerSave {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            position: Position3D::origin(),
+            stats: PlayerStats::starting_stats(),
+            experience: 0,
+            resources: ResourceCollection::new(),
+            movement_points: 0,
+            health: Health::full(BASE_PLAYER_HEALTH),
+            gear: Gear::new(),
+            items: Vec::new(),
+        }
+    }
+}
+
+/// A saved base building
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildingSave {
+    pub building_type: BuildingType,
+    pub name: String,
+    pub level: u8,
+    pub damaged: bool,
+    /// Keeps the building on the same tile of the base cluster
+    pub constructed_at: DateTime<Utc>,
+}
+
+impl From<&BaseBuilding> for BuildingSave {
+    fn from(building: &BaseBuilding) -> Self {
+        Self {
+            building_type: building.building_type,
+            name: building.name.clone(),
+            level: building.level,
+            damaged: building.damaged,
+            constructed_at: building.constructed_at,
+        }
+    }
+}
+
+impl BuildingSave {
+    /// Rebuild the domain building
+    pub fn to_building(&self) -> BaseBuilding {
+        let mut building = BaseBuilding::new(self.building_type, self.name.clone(), (0, 0));
+        building.level = self.level;
+        building.damaged = self.damaged;
+        building.constructed_at = self.constructed_at;
+        building
+    }
+}
+
+/// Saved base state
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BaseSave {
+    pub name: String,
+    pub position: Position3D,
+    pub stored_resources: ResourceCollection,
+    pub buildings: Vec<BuildingSave>,
+}
+
+/// Saved pass-and-play party
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartySave {
+    /// The character waiting for their turn
+    pub benched: PlayerSave,
+    pub turns: HotSeat,
+    pub contributions: [Contribution; PARTY_SIZE],
+}
+
+/// Payload of the current save version
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SaveData {
+    pub player: PlayerSave,
+    pub base: BaseSave,
+    /// Total play time in seconds
+    pub total_play_time: u32,
+    pub active_expedition: Option<ExpeditionPlan>,
+    pub reputation: Reputation,
+    pub mutators: Mutators,
+    pub party: Option<PartySave>,
+    pub flags: SessionFlags,
+    pub wrecks: WreckField,
+    pub heatmap: PlayHeatmap,
+    /// Id of the scenario the run started with
+    pub scenario: String,
+    pub emergency_recall: EmergencyRecall,
+    /// Soundtrack to pick up again on load
+    pub audio: Option<AudioMemory>,
+    pub decals: TileDecals,
+    /// Charted overworld and run counters; older saves play on in the
+    /// world already open
+    pub world: Option<WorldSave>,
+}
+
+impl SaveData {
+    /// Snapshot a running session
+    pub fn from_session(session: &RpgGameSession) -> Self {
+        let base = &session.base;
+        Self {
+            player: PlayerSave::from_player(&session.player),
+            base: BaseSave {
+                name: base.name().to_string(),
+                position: *base.position(),
+                stored_resources: base.resources().clone(),
+                buildings: base.buildings().iter().map(BuildingSave::from).collect(),
+            },
+            total_play_time: session.total_play_time,
+            active_expedition: session.active_expedition.clone(),
+            reputation: session.reputation,
+            mutators: session.mutators.clone(),
+            party: session.party.as_ref().map(|party| PartySave {
+                benched: PlayerSave::from_player(party.benched()),
+                turns: *party.turns(),
+                contributions: party.contributions().clone(),
+            }),
+            flags: session.flags.clone(),
+            wrecks: session.wrecks.clone(),
+            heatmap: session.heatmap.clone(),
+            scenario: session.scenario.clone(),
+            emergency_recall: session.emergency_recall,
+            audio: session.audio_memory,
+            decals: session.decals.clone(),
+            world: None,
+        }
+    }
+
+    /// Add the charted world and run counters to the snapshot
+    pub fn with_world(mut self, world: Option<WorldSave>) -> Self {
+        self.world = world;
+        self
+    }
+
+    /// Rebuild a session from this snapshot
+    pub fn into_session(self) -> Result<RpgGameSession, SaveLoadError> {
+        let invalid = |e: crate::domain::DomainError| SaveLoadError::InvalidData(e.to_string());
+
+        // Runs are played at Normal difficulty
+        let modifiers = ModifierStack::new(DifficultyLevel::Normal, self.mutators.clone());
+        let player = self.player.into_player(&modifiers)?;
+        let party = match self.party {
+            Some(saved) => Some(Party::restore(
+                saved.benched.into_player(&modifiers)?,
+                saved.turns,
+                saved.contributions,
+            )),
+            None => None,
+        };
+
+        let mut base =
+            Base::new(EntityId::generate(), self.base.name, self.base.position).map_err(invalid)?;
+        base.store_resources(&self.base.stored_resources)
+            .map_err(invalid)?;
+        for building in &self.base.buildings {
+            base.add_building(building.to_building());
+        }
+
+        let mut session = RpgGameSession::new(player, base);
+        session.total_play_time = self.total_play_time;
+        session.active_expedition = self.active_expedition;
+        session.reputation = self.reputation;
+        session.mutators = self.mutators;
+        session.party = party;
+        session.flags = self.flags;
+        session.wrecks = self.wrecks;
+        session.heatmap = self.heatmap;
+        session.scenario = self.scenario;
+        session.emergency_recall = self.emergency_recall;
+        session.audio_memory = self.audio;
+        session.decals = self.decals;
+        Ok(session)
+    }
+}
+
+/// A save that was read and brought up to the current version
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadedSave {
+    pub data: SaveData,
+    /// Version stored in the file
+    pub stored_version: u32,
+    pub created_with: String,
+}
+
+impl LoadedSave {
+    /// Check if the file was written by an older version
+    pub fn was_migrated(&self) -> bool {
+        self.stored_version < SAVE_VERSION
+    }
+}
+
+/// Remember the data packs in use for the saves written from now on
+pub fn set_active_packs(fingerprint: &str) {
+    if let Ok(mut active) = ACTIVE_PACKS.lock() {
+        *active = fingerprint.to_string();
+    }
+}
+
+/// Fingerprint of the data packs in use, empty for the shipped data
+pub fn active_packs() -> String {
+    ACTIVE_PACKS
+        .lock()
+        .map(|active| active.clone())
+        .unwrap_or_default()
+}
+
+/// Parse a save document, migrating older versions to the current one
+pub fn parse_save(text: &str) -> Result<LoadedSave, SaveLoadError> {
+    parse_save_with_packs(text, &active_packs())
+}
+
+/// Parse a save document for a game running the `active` pack set
+pub fn parse_save_with_packs(text: &str, active: &str) -> Result<LoadedSave, SaveLoadError> {
+    let envelope: SaveEnvelope = serde_json::from_str(text)
+        .map_err(|e| SaveLoadError::Unreadable(format!("invalid save file: {}", e)))?;
+    if envelope.packs != active {
+        return Err(SaveLoadError::PackMismatch {
+            saved: envelope.packs,
+            active: active.to_string(),
+        });
+    }
+    if envelope.version > SAVE_VERSION {
+        return Err(SaveLoadError::NewerVersion {
+            version: envelope.version,
+            created_with: envelope.created_with,
+        });
+    }
+    if envelope.version == 0 {
+        return Err(SaveLoadError::Unreadable(
+            "unsupported save version 0".to_string(),
+        ));
+    }
+
+    let mut data = envelope.data;
+    for migration in migrations_between(envelope.version, SAVE_VERSION) {
+        data = (migration.apply)(data).map_err(|reason| SaveLoadError::MigrationFailed {
+            from: migration.from,
+            reason,
+        })?;
+        info!(
+            "💾 Save migrated from version {} to {}: {}",
+            migration.from,
+            migration.from + 1,
+            migration.description
+        );
+    }
+
+    let data = serde_json::from_value(data)
+        .map_err(|e| SaveLoadError::InvalidData(format!("unexpected save data: {}", e)))?;
+    Ok(LoadedSave {
+        data,
+        stored_version: envelope.version,
+        created_with: envelope.created_with,
+    })
+}
+
+/// Parse a save file, compressed or plain JSON
+pub fn parse_save_bytes(bytes: &[u8]) -> Result<LoadedSave, SaveLoadError> {
+    parse_save(&decompress_save(bytes)?)
+}
+
+/// Serialize a save as the current version
+pub fn save_to_json(data: &SaveData) -> Result<String, String> {
+    serde_json::to_string_pretty(&current_envelope(data))
+        .map_err(|e| format!("failed to serialize save: {}", e))
+}
+
+/// Serialize and compress a save as the current version
+pub fn save_to_bytes(data: &SaveData) -> Result<(Vec<u8>, SaveSizes), String> {
+    let json = serde_json::to_string(&current_envelope(data))
+        .map_err(|e| format!("failed to serialize save: {}", e))?;
+    Ok(compress_save(&json))
+}
+
+fn current_envelope(data: &SaveData) -> SaveEnvelope<&SaveData> {
+    SaveEnvelope {
+        version: SAVE_VERSION,
+        created_with: BuildInfo::current().version_string(),
+        packs: active_packs(),
+        data,
+    }
+}
+
+/// Load and migrate the save at `path`
+pub fn load_save(path: &Path) -> Result<LoadedSave, SaveLoadError> {
+    let bytes = std::fs::read(path)
+        .map_err(|e| SaveLoadError::Unreadable(format!("failed to read save: {}", e)))?;
+    parse_save_bytes(&bytes)
+}
+
+/// Load the save at `path` unless a defeated hardcore run buried the slot
+pub fn load_save_slot(path: &Path, tombstones: &Tombstones) -> Result<LoadedSave, SaveLoadError> {
+    let slot = path.to_string_lossy();
+    if tombstones.is_buried(&slot) {
+        return Err(SaveLoadError::RunEnded(slot.into_owned()));
+    }
+    load_save(path)
+}
+
+/// Write a compressed save to `path` as the current version
+pub fn write_save(path: &Path, data: &SaveData) -> Result<SaveSizes, String> {
+    let (bytes, sizes) = save_to_bytes(data)?;
+    std::fs::write(path, bytes).map_err(|e| format!("failed to write save: {}", e))?;
+    compression::record_save_sizes(sizes);
+    info!("💾 Save written: {}", sizes.describe());
+    Ok(sizes)
+}
+
+/// Stable hash of the shape of the current save payload
+///
+/// Covers every field path and JSON kind of the serialized default payload,
+/// not the values, so tuning defaults does not count as a schema change.
+pub fn schema_fingerprint() -> u64 {
+    let value = serde_json::to_value(SaveData::default()).unwrap_or(Value::Null);
+    let mut shape = BTreeSet::new();
+    collect_shape(&value, "", &mut shape);
+    let text = shape.into_iter().collect::<Vec<_>>().join("\n");
+    fnv1a_64(text.as_bytes())
+}
+
+fn collect_shape(value: &Value, path: &str, shape: &mut BTreeSet<String>) {
+    let kind = match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    };
+    if !path.is_empty() {
+        shape.insert(format!("{}:{}", path, kind));
+    }
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                collect_shape(field, &child, shape);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_shape(item, &format!("{}[]", path), shape);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::constants::{
+        IRON_STOMACH_MOVEMENT_PENALTY, REPUTATION_RIVAL_COUPLING, RUIN_RELIC_ID, RUIN_RELIC_WEIGHT,
+    };
+    use crate::domain::services::{
+        layout_base, DecalKind, GearBonus, GearItem, GearRarity, HeatMetric, Mutator,
+        ReputationCause, RunTally, WreckOrigin, CRASH_SURVIVOR,
+    };
+    use crate::domain::value_objects::ResourceType;
+
+    /// One fixture per historical save version
+    const SAVE_FIXTURES: &[(u32, &str)] = &[
+        (1, include_str!("fixtures/save_v1.json")),
+        (2, include_str!("fixtures/save_v2.json")),
+        (3, include_str!("fixtures/save_v3.json")),
+        (4, include_str!("fixtures/save_v4.json")),
+        (5, include_str!("fixtures/save_v5.json")),
+        (6, include_str!("fixtures/save_v6.json")),
+        (7, include_str!("fixtures/save_v7.json")),
+        (8, include_str!("fixtures/save_v8.json")),
+        (9, include_str!("fixtures/save_v9.json")),
+        (10, include_str!("fixtures/save_v10.json")),
+        (11, include_str!("fixtures/save_v11.json")),
+        (12, include_str!("fixtures/save_v12.json")),
+        (13, include_str!("fixtures/save_v13.json")),
+        (14, include_str!("fixtures/save_v14.json")),
+        (15, include_str!("fixtures/save_v15.json")),
+        (16, include_str!("fixtures/save_v16.json")),
+        (17, include_str!("fixtures/save_v17.json")),
+    ];
+
+    #[test]
+    fn every_historical_version_loads_to_a_valid_session() {
+        let versions: Vec<u32> = SAVE_FIXTURES.iter().map(|(version, _)| *version).collect();
+        assert_eq!(versions, (1..=SAVE_VERSION).collect::<Vec<_>>());
+
+        for (version, text) in SAVE_FIXTURES {
+            let loaded = parse_save(text).unwrap();
+            assert_eq!(loaded.stored_version, *version);
+            assert_eq!(loaded.was_migrated(), *version < SAVE_VERSION);
+            // Saves from before v15 carry no world and play on in the open one
+            assert_eq!(loaded.data.world.is_some(), *version >= 15);
+
+            let session = loaded.data.into_session().unwrap();
+            assert!(session.player.is_valid(), "fixture v{}", version);
+            assert_eq!(session.player.name(), "Vex");
+            // Carried items were first saved in v17
+            assert_eq!(
+                session.player.inventory().item_count(RUIN_RELIC_ID),
+                if *version >= 17 { 2 } else { 0 }
+            );
+            assert_eq!(
+                session.base.resources().get_amount(ResourceType::Metal),
+                120
+            );
+        }
+    }
+
+    #[test]
+    fn schema_changes_require_a_version_bump() {
+        assert_eq!(
+            schema_fingerprint(),
+            SAVE_SCHEMA_FINGERPRINT,
+            "the save schema changed: bump SAVE_VERSION, add a migration and a fixture, \
+             then set SAVE_SCHEMA_FINGERPRINT to {:#018x}",
+            schema_fingerprint()
+        );
+    }
+
+    #[test]
+    fn newer_saves_are_refused_with_a_player_message() {
+        let newer = format!(
+            r#"{{"version": {}, "created_with": "9.0.0", "data": {{}}}}"#,
+            SAVE_VERSION + 1
+        );
+
+        let error = parse_save(&newer).unwrap_err();
+
+        assert_eq!(
+            error,
+            SaveLoadError::NewerVersion {
+                version: SAVE_VERSION + 1,
+                created_with: "9.0.0".to_string()
+            }
+        );
+        assert!(error.to_string().contains("9.0.0"));
+    }
+
+    #[test]
+    fn sessions_round_trip_through_the_current_version() {
+        let player =
+            Player::create_new_character("Vex".to_string(), Position3D::new(3, -2, 0)).unwrap();
+        let base = Base::new(
+            EntityId::generate(),
+            "Outpost".to_string(),
+            Position3D::origin(),
+        )
+        .unwrap();
+        let mut session = RpgGameSession::new(player, base);
+        session.player.add_experience(250).unwrap();
+        // As set up for a new run with Iron Stomach
+        session.mutators = Mutators::new([Mutator::IronStomach]);
+        let max_movement = session.player.max_movement_points();
+        session
+            .player
+            .set_max_movement_points(max_movement - IRON_STOMACH_MOVEMENT_PENALTY as u8);
+        let regulator = session
+            .player
+            .stow_gear(GearItem::new(GearBonus::RestMovement, GearRarity::Common));
+        session.player.equip_gear(regulator).unwrap();
+        session.player.restore_points();
+        session.player.subtract_movement_points(2);
+        session.player.inventory_mut().add_item(
+            InventoryItem::new(RUIN_RELIC_ID, 2, RUIN_RELIC_WEIGHT),
+            u32::MAX,
+        );
+        session.total_play_time = 900;
+        session
+            .reputation
+            .apply(ReputationCause::Bribe, REPUTATION_RIVAL_COUPLING);
+        let mut rook =
+            Player::create_new_character("Rook".to_string(), Position3D::new(3, -2, 0)).unwrap();
+        rook.set_max_movement_points(
+            rook.max_movement_points() - IRON_STOMACH_MOVEMENT_PENALTY as u8,
+        );
+        rook.restore_points();
+        let mut party = Party::new("Vex", rook);
+        party
+            .end_day(RunTally {
+                tiles_explored: 9,
+                ..RunTally::default()
+            })
+            .unwrap();
+        session.party = Some(party);
+        session.flags.set_flag("spared_scavenger");
+        session.flags.increment("bribes_paid", 2);
+        session.flags.set_tag("vault_alpha", "opened");
+        session.wrecks.leave(
+            Position3D::new(4, 1, 0),
+            WreckOrigin::Hostiles,
+            vec![(ResourceType::Metal, 20)],
+        );
+        session
+            .heatmap
+            .record(Position3D::new(4, 1, 0), HeatMetric::Damage, 7);
+        session.scenario = CRASH_SURVIVOR.to_string();
+        session.emergency_recall.spend(3);
+        session.audio_memory = Some(AudioMemory {
+            track_index: Some(1),
+            track_position: Some(20.5),
+            ambient: None,
+            music_volume: 0.3,
+            danger_level: 0.2,
+        });
+        session
+            .decals
+            .add(Position3D::new(4, 1, 0), DecalKind::Scorch);
+        session.decals.rest();
+        let saved = SaveData::from_session(&session);
+
+        let loaded = parse_save(&save_to_json(&saved).unwrap()).unwrap();
+
+        assert!(!loaded.was_migrated());
+        assert_eq!(loaded.created_with, BuildInfo::current().version_string());
+        let restored = loaded.data.into_session().unwrap();
+        assert_eq!(SaveData::from_session(&restored), saved);
+        assert_eq!(restored.player.level(), session.player.level());
+        assert_eq!(
+            restored.player.max_movement_points(),
+            session.player.max_movement_points()
+        );
+        assert!(restored.mutators.contains(Mutator::IronStomach));
+        let party = restored.party.unwrap();
+        assert_eq!(party.benched().name(), "Rook");
+        assert!(party.turns().is_handover());
+        assert_eq!(party.contributions()[0].tally.tiles_explored, 9);
+        assert_eq!(restored.flags, session.flags);
+        assert_eq!(restored.flags.counter("bribes_paid"), 2);
+        assert_eq!(restored.wrecks, session.wrecks);
+        assert_eq!(restored.heatmap, session.heatmap);
+        assert_eq!(restored.scenario, CRASH_SURVIVOR);
+        assert_eq!(restored.emergency_recall.used_on_day(), Some(3));
+        assert_eq!(restored.audio_memory, session.audio_memory);
+        assert_eq!(restored.decals, session.decals);
+    }
+
+    #[test]
+    fn building_slots_survive_save_and_load() {
+        let mut base = Base::new(
+            EntityId::generate(),
+            "Outpost".to_string(),
+            Position3D::new(2, 2, 0),
+        )
+        .unwrap();
+        let start = chrono::Utc::now();
+        for (minute, building_type) in [
+            BuildingType::PowerPlant,
+            BuildingType::Refinery,
+            BuildingType::Workshop,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let mut building =
+                BaseBuilding::new(building_type, format!("{:?}", building_type), (0, 0));
+            building.constructed_at = start + chrono::Duration::minutes(minute as i64);
+            base.add_building(building);
+        }
+        base.upgrade_building(BuildingType::Refinery).unwrap();
+        base.set_building_damaged(BuildingType::Workshop, true);
+        let player =
+            Player::create_new_character("Vex".to_string(), Position3D::new(2, 2, 0)).unwrap();
+        let session = RpgGameSession::new(player, base);
+
+        let mut saved = SaveData::from_session(&session);
+        // The stored order must not matter
+        saved.base.buildings.reverse();
+        let loaded = parse_save(&save_to_json(&saved).unwrap()).unwrap();
+        let restored = loaded.data.into_session().unwrap();
+
+        assert_eq!(layout_base(&restored.base), layout_base(&session.base));
+    }
+
+    #[test]
+    fn buried_slots_refuse_to_load() {
+        let dir = std::env::temp_dir().join(format!("space-looter-saves-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("savegame.json");
+        let player = Player::create_new_character("Vex".to_string(), Position3D::origin()).unwrap();
+        let base = Base::new(
+            EntityId::generate(),
+            "Outpost".to_string(),
+            Position3D::origin(),
+        )
+        .unwrap();
+        write_save(
+            &path,
+            &SaveData::from_session(&RpgGameSession::new(player, base)),
+        )
+        .unwrap();
+
+        let mut tombstones = Tombstones::default();
+        let loaded = load_save_slot(&path, &tombstones).unwrap();
+        assert_eq!(loaded.data.into_session().unwrap().player.name(), "Vex");
+
+        tombstones.bury(&path.to_string_lossy());
+        let error = load_save_slot(&path, &tombstones).unwrap_err();
+        assert!(matches!(error, SaveLoadError::RunEnded(_)));
+        assert!(error.to_string().contains("hardcore run"));
+    }
+
+    #[test]
+    fn saves_refuse_a_different_pack_set() {
+        let player = Player::create_new_character("Vex".to_string(), Position3D::origin()).unwrap();
+        let base = Base::new(
+            EntityId::generate(),
+            "Outpost".to_string(),
+            Position3D::origin(),
+        )
+        .unwrap();
+        let data = SaveData::from_session(&RpgGameSession::new(player, base));
+        let mut envelope = serde_json::to_value(SaveEnvelope {
+            version: SAVE_VERSION,
+            created_with: "test".to_string(),
+            packs: "00000000deadbeef".to_string(),
+            data: &data,
+        })
+        .unwrap();
+        let text = envelope.to_string();
+
+        assert!(parse_save_with_packs(&text, "00000000deadbeef").is_ok());
+        let error = parse_save_with_packs(&text, "").unwrap_err();
+        assert_eq!(
+            error,
+            SaveLoadError::PackMismatch {
+                saved: "00000000deadbeef".to_string(),
+                active: String::new(),
+            }
+        );
+        assert!(error.to_string().contains("runs with no data packs"));
+
+        // Saves from before packs existed belong to the shipped data
+        envelope.as_object_mut().unwrap().remove("packs");
+        let text = envelope.to_string();
+        assert!(parse_save_with_packs(&text, "").is_ok());
+        assert!(parse_save_with_packs(&text, "00000000deadbeef").is_err());
+    }
+}
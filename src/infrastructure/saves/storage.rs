@@ -0,0 +1,412 @@
+//! Web Save Storage - Fitting saves into the browser's localStorage
+//!
+//! localStorage only holds strings, so a compressed save is kept as base64
+//! under `SAVE_STORAGE_KEY`. Some browsers allow an origin about 5 MB of
+//! UTF-16 text in total, so before a save is written the projected usage is
+//! checked against the conservative `WEB_STORAGE_BUDGET_BYTES`. When it
+//! would not fit, the oldest ghost trails are dropped first and then the
+//! oldest snapshots; if it still does not fit nothing is removed, and the
+//! error is meant to be shown to the player as-is.
+
+use super::SaveLoadError;
+
+/// Storage the web build allows itself, well under the smallest browser quota
+pub const WEB_STORAGE_BUDGET_BYTES: usize = 4 * 1024 * 1024;
+
+/// Key of the save in localStorage
+pub const SAVE_STORAGE_KEY: &str = "space-looter/save";
+
+/// Key prefixes of prunable entries, followed by their write order
+pub const GHOST_STORAGE_PREFIX: &str = "space-looter/ghosts/";
+pub const SNAPSHOT_STORAGE_PREFIX: &str = "space-looter/snapshots/";
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// What a stored entry is, in the order entries are pruned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StoredKind {
+    GhostTrail,
+    Snapshot,
+    /// Never pruned to make room
+    Other,
+}
+
+/// An entry already in storage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredItem {
+    pub key: String,
+    /// Length of the stored value in characters
+    pub chars: usize,
+}
+
+impl StoredItem {
+    pub fn kind(&self) -> StoredKind {
+        if self.key.starts_with(GHOST_STORAGE_PREFIX) {
+            StoredKind::GhostTrail
+        } else if self.key.starts_with(SNAPSHOT_STORAGE_PREFIX) {
+            StoredKind::Snapshot
+        } else {
+            StoredKind::Other
+        }
+    }
+
+    /// Write order from the key suffix; entries without one count as oldest
+    fn written(&self) -> u64 {
+        self.key
+            .rsplit('/')
+            .next()
+            .and_then(|suffix| suffix.parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// Bytes the entry takes up
+    pub fn bytes(&self) -> usize {
+        storage_bytes(&self.key, self.chars)
+    }
+}
+
+/// Bytes `chars` characters take up under `key`; both are stored as UTF-16
+pub fn storage_bytes(key: &str, chars: usize) -> usize {
+    (key.len() + chars) * 2
+}
+
+/// What to remove before a write fits the budget
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoragePlan {
+    /// Keys to remove, in order
+    pub prune: Vec<String>,
+    /// Usage once the write is done
+    pub projected_bytes: usize,
+}
+
+/// Why a save could not be stored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SaveStorageError {
+    /// Even with every prunable entry gone the save would not fit
+    OverBudget { needed: usize, budget: usize },
+    /// The browser offers no localStorage
+    Unavailable(String),
+    /// Serializing or writing failed
+    WriteFailed(String),
+}
+
+impl std::fmt::Display for SaveStorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveStorageError::OverBudget { needed, budget } => write!(
+                f,
+                "The game could not be saved: it needs {} KB of browser storage and only {} KB \
+                 are available. Your previous save was left untouched.",
+                needed.div_ceil(1024),
+                budget / 1024
+            ),
+            SaveStorageError::Unavailable(reason) => {
+                write!(
+                    f,
+                    "The game could not be saved: no browser storage ({})",
+                    reason
+                )
+            }
+            SaveStorageError::WriteFailed(reason) => {
+                write!(f, "The game could not be saved ({})", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SaveStorageError {}
+
+/// Plan writing `chars` characters under `key` next to `existing` entries
+///
+/// An entry already stored under `key` is replaced, so it does not count.
+pub fn plan_save_write(
+    key: &str,
+    chars: usize,
+    existing: &[StoredItem],
+    budget: usize,
+) -> Result<StoragePlan, SaveStorageError> {
+    let others: Vec<&StoredItem> = existing.iter().filter(|item| item.key != key).collect();
+    let mut projected =
+        storage_bytes(key, chars) + others.iter().map(|item| item.bytes()).sum::<usize>();
+
+    let mut candidates: Vec<&StoredItem> = others
+        .into_iter()
+        .filter(|item| item.kind() != StoredKind::Other)
+        .collect();
+    candidates.sort_by_key(|item| (item.kind(), item.written()));
+
+    let mut prune = Vec::new();
+    for item in candidates {
+        if projected <= budget {
+            break;
+        }
+        projected -= item.bytes();
+        prune.push(item.key.clone());
+    }
+    if projected > budget {
+        return Err(SaveStorageError::OverBudget {
+            needed: projected,
+            budget,
+        });
+    }
+    Ok(StoragePlan {
+        prune,
+        projected_bytes: projected,
+    })
+}
+
+/// A compressed save as text for localStorage
+pub fn encode_stored_save(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let padded = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let group = ((padded[0] as u32) << 16) | ((padded[1] as u32) << 8) | padded[2] as u32;
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let digit = (group >> (18 - 6 * index)) & 63;
+                text.push(BASE64_ALPHABET[digit as usize] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+/// The save bytes of a localStorage entry; plain JSON is an uncompressed save
+pub fn decode_stored_save(text: &str) -> Result<Vec<u8>, SaveLoadError> {
+    if text.trim_start().starts_with('{') {
+        return Ok(text.as_bytes().to_vec());
+    }
+
+    let digits = text.trim_end().trim_end_matches('=');
+    let mut bytes = Vec::with_capacity(digits.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for digit in digits.bytes() {
+        let value = BASE64_ALPHABET
+            .iter()
+            .position(|&candidate| candidate == digit)
+            .ok_or_else(|| SaveLoadError::Unreadable("stored save is not base64".to_string()))?;
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Ok(bytes)
+}
+
+/// Compress `data` and store it, pruning old ghost trails and snapshots if needed
+#[cfg(target_arch = "wasm32")]
+pub fn write_web_save(
+    data: &super::SaveData,
+) -> Result<super::compression::SaveSizes, SaveStorageError> {
+    let (bytes, sizes) = super::save_to_bytes(data).map_err(SaveStorageError::WriteFailed)?;
+    store_web_save(&bytes, sizes)?;
+    Ok(sizes)
+}
+
+/// Store compressed save bytes, pruning old ghost trails and snapshots if needed
+#[cfg(target_arch = "wasm32")]
+pub fn store_web_save(
+    bytes: &[u8],
+    sizes: super::compression::SaveSizes,
+) -> Result<(), SaveStorageError> {
+    use bevy::prelude::info;
+
+    let text = encode_stored_save(bytes);
+    let storage = web::local_storage()?;
+    let plan = plan_save_write(
+        SAVE_STORAGE_KEY,
+        text.len(),
+        &web::stored_items(&storage),
+        WEB_STORAGE_BUDGET_BYTES,
+    )?;
+    for key in &plan.prune {
+        web::call(&storage, "removeItem", &[key.as_str()])?;
+        info!("💾 Dropped {} to make room for the save", key);
+    }
+    web::call(&storage, "setItem", &[SAVE_STORAGE_KEY, &text])?;
+
+    super::compression::record_save_sizes(sizes);
+    info!(
+        "💾 Save stored: {}, {} KB of browser storage in use",
+        sizes.describe(),
+        plan.projected_bytes / 1024
+    );
+    Ok(())
+}
+
+/// The stored save, migrated to the current version, if there is one
+#[cfg(target_arch = "wasm32")]
+pub fn read_web_save() -> Result<Option<super::LoadedSave>, SaveLoadError> {
+    read_web_save_text()?
+        .map(|text| super::parse_save(&text))
+        .transpose()
+}
+
+/// Remove the stored save, if there is one
+#[cfg(target_arch = "wasm32")]
+pub fn remove_web_save() -> Result<(), SaveStorageError> {
+    let storage = web::local_storage()?;
+    web::call(&storage, "removeItem", &[SAVE_STORAGE_KEY]).map(|_| ())
+}
+
+/// The stored save as text, `None` when nothing is stored
+#[cfg(target_arch = "wasm32")]
+pub fn read_web_save_text() -> Result<Option<String>, SaveLoadError> {
+    let storage = web::local_storage().map_err(|e| SaveLoadError::Unreadable(e.to_string()))?;
+    let Some(text) = web::call(&storage, "getItem", &[SAVE_STORAGE_KEY])
+        .map_err(|e| SaveLoadError::Unreadable(e.to_string()))?
+        .as_string()
+    else {
+        return Ok(None);
+    };
+    super::decompress_save(&decode_stored_save(&text)?).map(Some)
+}
+
+/// localStorage through `Reflect`, like the clipboard
+#[cfg(target_arch = "wasm32")]
+pub(crate) mod web {
+    use super::{SaveStorageError, StoredItem};
+    use wasm_bindgen::{JsCast, JsValue};
+
+    pub fn local_storage() -> Result<JsValue, SaveStorageError> {
+        let window = web_sys::window()
+            .ok_or_else(|| SaveStorageError::Unavailable("no window object".to_string()))?;
+        js_sys::Reflect::get(&window, &JsValue::from_str("localStorage"))
+            .ok()
+            .filter(|storage| !storage.is_undefined() && !storage.is_null())
+            .ok_or_else(|| SaveStorageError::Unavailable("localStorage is disabled".to_string()))
+    }
+
+    pub fn call(
+        storage: &JsValue,
+        method: &str,
+        args: &[&str],
+    ) -> Result<JsValue, SaveStorageError> {
+        let function = js_sys::Reflect::get(storage, &JsValue::from_str(method))
+            .ok()
+            .and_then(|function| function.dyn_into::<js_sys::Function>().ok())
+            .ok_or_else(|| SaveStorageError::Unavailable(format!("no {}", method)))?;
+        let args: js_sys::Array = args.iter().map(|arg| JsValue::from_str(arg)).collect();
+        function
+            .apply(storage, &args)
+            .map_err(|_| SaveStorageError::WriteFailed(format!("the browser refused {}", method)))
+    }
+
+    pub fn stored_items(storage: &JsValue) -> Vec<StoredItem> {
+        let length = js_sys::Reflect::get(storage, &JsValue::from_str("length"))
+            .ok()
+            .and_then(|length| length.as_f64())
+            .unwrap_or(0.0) as u32;
+        (0..length)
+            .filter_map(|index| {
+                let key = call(storage, "key", &[&index.to_string()])
+                    .ok()?
+                    .as_string()?;
+                let chars = call(storage, "getItem", &[&key])
+                    .ok()?
+                    .as_string()
+                    .map_or(0, |value| value.encode_utf16().count());
+                Some(StoredItem { key, chars })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::saves::compression::compress_save;
+
+    fn item(key: &str, chars: usize) -> StoredItem {
+        StoredItem {
+            key: key.to_string(),
+            chars,
+        }
+    }
+
+    #[test]
+    fn over_budget_writes_prune_ghosts_then_snapshots_oldest_first() {
+        let existing = vec![
+            item("space-looter/snapshots/7", 100),
+            item("space-looter/ghosts/12", 100),
+            item("space-looter/settings", 100),
+            item("space-looter/ghosts/3", 100),
+            item("space-looter/snapshots/2", 100),
+            item(SAVE_STORAGE_KEY, 900),
+        ];
+        let entry = |key: &str| {
+            existing
+                .iter()
+                .find(|item| item.key == key)
+                .unwrap()
+                .bytes()
+        };
+        let others: usize = existing[..5].iter().map(StoredItem::bytes).sum();
+        let save = storage_bytes(SAVE_STORAGE_KEY, 300);
+
+        // Everything fits: nothing goes, and the old save does not count
+        let plan = plan_save_write(SAVE_STORAGE_KEY, 300, &existing, others + save).unwrap();
+        assert!(plan.prune.is_empty());
+        assert_eq!(plan.projected_bytes, others + save);
+
+        let budget = others + save - entry("space-looter/ghosts/3") - 1;
+        let plan = plan_save_write(SAVE_STORAGE_KEY, 300, &existing, budget).unwrap();
+        assert_eq!(
+            plan.prune,
+            vec!["space-looter/ghosts/3", "space-looter/ghosts/12"]
+        );
+
+        let budget = entry("space-looter/settings") + save + entry("space-looter/snapshots/7");
+        let plan = plan_save_write(SAVE_STORAGE_KEY, 300, &existing, budget).unwrap();
+        assert_eq!(
+            plan.prune,
+            vec![
+                "space-looter/ghosts/3",
+                "space-looter/ghosts/12",
+                "space-looter/snapshots/2",
+            ]
+        );
+
+        // Other entries are never dropped to make room
+        let budget = entry("space-looter/settings") + save - 1;
+        assert_eq!(
+            plan_save_write(SAVE_STORAGE_KEY, 300, &existing, budget),
+            Err(SaveStorageError::OverBudget {
+                needed: entry("space-looter/settings") + save,
+                budget,
+            })
+        );
+    }
+
+    #[test]
+    fn stored_saves_survive_the_text_encoding() {
+        for length in 0..6 {
+            let bytes: Vec<u8> = (0..length).map(|byte| byte * 51 + 7).collect();
+            let text = encode_stored_save(&bytes);
+            assert_eq!(text.len(), length.div_ceil(3) as usize * 4);
+            assert_eq!(decode_stored_save(&text).unwrap(), bytes);
+        }
+        assert_eq!(encode_stored_save(b"Vex"), "VmV4");
+
+        let (compressed, _) = compress_save(r#"{"version": 8}"#);
+        let text = encode_stored_save(&compressed);
+        assert_eq!(decode_stored_save(&text).unwrap(), compressed);
+        // Saves stored before compression are the JSON itself
+        assert_eq!(
+            decode_stored_save(r#"{"version": 8}"#).unwrap(),
+            br#"{"version": 8}"#.to_vec()
+        );
+        assert!(decode_stored_save("not*base64").is_err());
+    }
+}
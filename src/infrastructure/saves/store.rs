@@ -0,0 +1,214 @@
+//! Save Stores - Where the save slot is kept
+//!
+//! A `PersistenceService` keeps one save document as text. Native builds
+//! keep it in a file, compressed like every save; web builds keep it in
+//! localStorage, compressed and in base64, making room by dropping old
+//! ghost trails and snapshots. `SaveGameService` puts the versioned
+//! envelope on top: it writes a `SaveData` and parses, migrates and checks
+//! what the store hands back.
+
+use super::compression::{compress_save, decompress_save, record_save_sizes};
+use super::{current_envelope, parse_save, LoadedSave, SaveData, SaveLoadError, SAVE_FILE_PATH};
+use crate::domain::services::Tombstones;
+use crate::infrastructure::traits::PersistenceService;
+use bevy::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// Save slot kept in a file
+#[derive(Debug, Clone)]
+pub struct FileSaveStore {
+    path: PathBuf,
+}
+
+impl FileSaveStore {
+    /// Store the save at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// File holding the save
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl PersistenceService for FileSaveStore {
+    fn save_game(&self, data: &str) -> Result<(), String> {
+        let (bytes, sizes) = compress_save(data);
+        std::fs::write(&self.path, bytes).map_err(|e| format!("failed to write save: {}", e))?;
+        record_save_sizes(sizes);
+        info!("💾 Save written: {}", sizes.describe());
+        Ok(())
+    }
+
+    fn load_game(&self) -> Result<String, String> {
+        let bytes = std::fs::read(&self.path).map_err(|e| format!("failed to read save: {}", e))?;
+        decompress_save(&bytes).map_err(|e| e.to_string())
+    }
+
+    fn has_save_data(&self) -> bool {
+        self.path.is_file()
+    }
+
+    fn delete_save_data(&self) -> Result<(), String> {
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("failed to delete save: {}", e)),
+        }
+    }
+}
+
+/// Save slot kept in the browser's localStorage
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug, Clone, Default)]
+pub struct LocalStorageSaveStore;
+
+#[cfg(target_arch = "wasm32")]
+impl PersistenceService for LocalStorageSaveStore {
+    fn save_game(&self, data: &str) -> Result<(), String> {
+        let (bytes, sizes) = compress_save(data);
+        super::storage::store_web_save(&bytes, sizes).map_err(|e| e.to_string())
+    }
+
+    fn load_game(&self) -> Result<String, String> {
+        super::storage::read_web_save_text()
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "no save stored".to_string())
+    }
+
+    fn has_save_data(&self) -> bool {
+        matches!(super::storage::read_web_save_text(), Ok(Some(_)))
+    }
+
+    fn delete_save_data(&self) -> Result<(), String> {
+        super::storage::remove_web_save().map_err(|e| e.to_string())
+    }
+}
+
+/// The save slot of the platform the game runs on
+pub fn platform_save_store() -> Box<dyn PersistenceService> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        Box::new(LocalStorageSaveStore)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        Box::new(FileSaveStore::new(SAVE_FILE_PATH))
+    }
+}
+
+/// Writes and reads back whole runs through a save slot
+#[derive(Resource)]
+pub struct SaveGameService {
+    store: Box<dyn PersistenceService>,
+    /// Name the slot is buried under when a hardcore run is lost
+    slot: String,
+}
+
+impl Default for SaveGameService {
+    fn default() -> Self {
+        Self::new(platform_save_store(), SAVE_FILE_PATH)
+    }
+}
+
+impl SaveGameService {
+    /// Save through `store`, known to the tombstones as `slot`
+    pub fn new(store: Box<dyn PersistenceService>, slot: impl Into<String>) -> Self {
+        Self {
+            store,
+            slot: slot.into(),
+        }
+    }
+
+    /// Save to the file at `path`
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        let store = FileSaveStore::new(path);
+        let slot = store.path().to_string_lossy().into_owned();
+        Self::new(Box::new(store), slot)
+    }
+
+    /// Write `data` as the current version, replacing the saved run
+    pub fn save(&self, data: &SaveData) -> Result<(), String> {
+        let json = serde_json::to_string(&current_envelope(data))
+            .map_err(|e| format!("failed to serialize save: {}", e))?;
+        self.store.save_game(&json)
+    }
+
+    /// Read back the saved run, migrated to the current version
+    pub fn load(&self, tombstones: &Tombstones) -> Result<LoadedSave, SaveLoadError> {
+        if tombstones.is_buried(&self.slot) {
+            return Err(SaveLoadError::RunEnded(self.slot.clone()));
+        }
+        let text = self.store.load_game().map_err(SaveLoadError::Unreadable)?;
+        parse_save(&text)
+    }
+
+    /// Check if the slot holds a save
+    pub fn has_save_data(&self) -> bool {
+        self.store.has_save_data()
+    }
+
+    /// Check if the slot holds a run that may still be played on
+    pub fn can_continue(&self, tombstones: &Tombstones) -> bool {
+        !tombstones.is_buried(&self.slot) && self.has_save_data()
+    }
+
+    /// Empty the slot
+    pub fn delete(&self) -> Result<(), String> {
+        self.store.delete_save_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Base, Player};
+    use crate::domain::value_objects::{EntityId, Position3D};
+    use crate::infrastructure::saves::SAVE_VERSION;
+    use crate::presentation::game_state::RpgGameSession;
+
+    #[test]
+    fn file_slots_round_trip_and_refuse_newer_saves() {
+        let dir = std::env::temp_dir().join(format!("space-looter-store-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("savegame.json");
+        let saves = SaveGameService::file(&path);
+        let tombstones = Tombstones::default();
+        assert!(!saves.has_save_data());
+        assert!(matches!(
+            saves.load(&tombstones),
+            Err(SaveLoadError::Unreadable(_))
+        ));
+
+        let player =
+            Player::create_new_character("Vex".to_string(), Position3D::new(2, 5, 0)).unwrap();
+        let base = Base::new(
+            EntityId::generate(),
+            "Outpost".to_string(),
+            Position3D::origin(),
+        )
+        .unwrap();
+        let data = SaveData::from_session(&RpgGameSession::new(player, base));
+        saves.save(&data).unwrap();
+        assert!(saves.can_continue(&tombstones));
+        assert_eq!(saves.load(&tombstones).unwrap().data, data);
+
+        // A save from a newer build stays where it is and is refused
+        let newer = format!(
+            r#"{{"version": {}, "created_with": "9.0.0", "data": {{}}}}"#,
+            SAVE_VERSION + 1
+        );
+        FileSaveStore::new(&path).save_game(&newer).unwrap();
+        assert!(matches!(
+            saves.load(&tombstones),
+            Err(SaveLoadError::NewerVersion { .. })
+        ));
+        assert!(saves.has_save_data());
+
+        saves.delete().unwrap();
+        assert!(!saves.has_save_data());
+        saves.delete().unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
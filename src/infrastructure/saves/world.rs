@@ -0,0 +1,217 @@
+//! World Saves - The charted overworld, the run's counters and its clock
+//!
+//! The overworld comes from its seed, so a save keeps the seed and only the
+//! tiles the player has charted, as they were seen: scenarios and the spawn
+//! area reshape the ground after generation, and a loaded run must show the
+//! map the player remembers. Everything else is generated again on load,
+//! resource nodes included. Interiors are not kept; a run is always loaded
+//! on the overworld.
+
+use crate::domain::entities::map::MapTile;
+use crate::domain::services::{DiscoveredTerrains, MapService};
+use crate::domain::value_objects::resources::ResourceRichness;
+use crate::domain::value_objects::terrain::Elevation;
+use crate::domain::value_objects::{
+    GameTime, Position3D, ResourceType, TerrainType, TileCoordinate,
+};
+use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A charted tile as the player saw it
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExploredTile {
+    pub position: TileCoordinate,
+    pub terrain: TerrainType,
+    pub elevation: Elevation,
+    pub last_visited_day: Option<u32>,
+}
+
+/// The counters of `GameStatsResource` that make up a run's progress
+///
+/// The run's rules, its scenario and score share follow from the session
+/// and are set up again from it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunCounters {
+    pub quests_completed: u32,
+    pub events_triggered: u32,
+    pub resources_gathered: HashMap<ResourceType, i32>,
+    pub dice_rolls_made: u32,
+    pub successful_rolls: u32,
+    pub critical_successes: u32,
+    pub critical_failures: u32,
+    pub assisted_rolls: u32,
+    pub tiles_explored: u32,
+    pub experience_gained: u32,
+    pub exploration_experience: u32,
+    pub nights_rested: u32,
+    pub autopilot_moves: u32,
+    pub moves_undone: u32,
+    pub game_duration: f32,
+}
+
+impl RunCounters {
+    /// Counters of the run being played
+    pub fn capture(stats: &GameStatsResource) -> Self {
+        Self {
+            quests_completed: stats.quests_completed,
+            events_triggered: stats.events_triggered,
+            resources_gathered: stats.resources_gathered.clone(),
+            dice_rolls_made: stats.dice_rolls_made,
+            successful_rolls: stats.successful_rolls,
+            critical_successes: stats.critical_successes,
+            critical_failures: stats.critical_failures,
+            assisted_rolls: stats.assisted_rolls,
+            tiles_explored: stats.tiles_explored,
+            experience_gained: stats.experience_gained,
+            exploration_experience: stats.exploration_experience,
+            nights_rested: stats.nights_rested,
+            autopilot_moves: stats.autopilot_moves,
+            moves_undone: stats.moves_undone,
+            game_duration: stats.game_duration,
+        }
+    }
+
+    /// Put the counters back, leaving the run's rules as they are
+    pub fn apply(&self, stats: &mut GameStatsResource) {
+        stats.quests_completed = self.quests_completed;
+        stats.events_triggered = self.events_triggered;
+        stats.resources_gathered = self.resources_gathered.clone();
+        stats.dice_rolls_made = self.dice_rolls_made;
+        stats.successful_rolls = self.successful_rolls;
+        stats.critical_successes = self.critical_successes;
+        stats.critical_failures = self.critical_failures;
+        stats.assisted_rolls = self.assisted_rolls;
+        stats.tiles_explored = self.tiles_explored;
+        stats.experience_gained = self.experience_gained;
+        stats.exploration_experience = self.exploration_experience;
+        stats.nights_rested = self.nights_rested;
+        stats.autopilot_moves = self.autopilot_moves;
+        stats.moves_undone = self.moves_undone;
+        stats.game_duration = self.game_duration;
+    }
+}
+
+/// Saved overworld, run counters and game clock
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldSave {
+    pub seed: u64,
+    /// Charted tiles, sorted so the same world always saves the same way
+    pub explored: Vec<ExploredTile>,
+    pub stats: RunCounters,
+    pub game_time: GameTime,
+}
+
+impl WorldSave {
+    /// Snapshot the overworld and counters; `None` before a world exists
+    pub fn capture(
+        map_resource: &MapResource,
+        stats: &GameStatsResource,
+        game_time: GameTime,
+    ) -> Option<Self> {
+        let map = map_resource.overworld()?;
+        let mut explored: Vec<ExploredTile> = map
+            .tiles()
+            .iter()
+            .filter(|(_, tile)| tile.is_explored())
+            .map(|(position, tile)| ExploredTile {
+                position: *position,
+                terrain: tile.terrain_type,
+                elevation: tile.elevation,
+                last_visited_day: tile.last_visited_day,
+            })
+            .collect();
+        explored.sort_by_key(|tile| (tile.position.x, tile.position.y, tile.position.z));
+        Some(Self {
+            seed: map.seed(),
+            explored,
+            stats: RunCounters::capture(stats),
+            game_time,
+        })
+    }
+
+    /// Generate the overworld again and chart the saved tiles on it
+    ///
+    /// Returns the game clock to carry on from.
+    pub fn restore(
+        &self,
+        map_resource: &mut MapResource,
+        stats: &mut GameStatsResource,
+    ) -> GameTime {
+        map_resource.generate_overworld(Position3D::origin(), self.seed);
+        if let Some(map) = map_resource.overworld.as_mut() {
+            // Generated first so charted tiles far out keep their resource nodes
+            let positions = self
+                .explored
+                .iter()
+                .map(|tile| Position3D::from(tile.position));
+            if let Err(e) = MapService::new(self.seed).generate_tiles_at(map, positions) {
+                warn!("Failed to generate the saved tiles: {}", e);
+            }
+            for tile in &self.explored {
+                // Richness follows from the seed, like the resource nodes
+                let richness = map
+                    .get_tile(&tile.position)
+                    .map(|generated| generated.richness)
+                    .unwrap_or(ResourceRichness::Average);
+                let mut charted =
+                    MapTile::new(tile.terrain, tile.elevation, true).with_richness(richness);
+                charted.last_visited_day = tile.last_visited_day;
+                map.set_tile(tile.position, charted);
+            }
+        }
+
+        self.stats.apply(stats);
+        stats.discovered_terrains = DiscoveredTerrains::new();
+        for tile in &self.explored {
+            stats.discovered_terrains.record(tile.terrain);
+        }
+        self.game_time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn charted_ground_survives_a_new_overworld() {
+        let mut map_resource = MapResource::new();
+        map_resource.generate_overworld(Position3D::origin(), 77);
+        let far = TileCoordinate::new(40, -35, 0);
+        {
+            let map = map_resource.overworld.as_mut().unwrap();
+            map.set_tile(
+                far,
+                MapTile::new(TerrainType::Crystal, Elevation::new(12).unwrap(), false),
+            );
+            let mut seen = map.get_tile(&far).unwrap().clone();
+            seen.visit(4);
+            map.set_tile(far, seen);
+        }
+        let mut stats = GameStatsResource::new();
+        stats.tiles_explored = 1;
+        stats.record_rest();
+        let saved = WorldSave::capture(&map_resource, &stats, GameTime::from_minutes(90)).unwrap();
+        assert!(saved.explored.iter().any(|tile| tile.position == far));
+
+        let json = serde_json::to_string(&saved).unwrap();
+        let loaded: WorldSave = serde_json::from_str(&json).unwrap();
+        let mut restored_map = MapResource::new();
+        let mut restored_stats = GameStatsResource::new();
+        let time = loaded.restore(&mut restored_map, &mut restored_stats);
+
+        let map = restored_map.overworld().unwrap();
+        assert_eq!(map.seed(), 77);
+        let tile = map.get_tile(&far).unwrap();
+        assert!(tile.is_explored());
+        assert_eq!(tile.terrain_type, TerrainType::Crystal);
+        assert_eq!(tile.last_visited_day, Some(4));
+        assert_eq!(restored_stats.current_day(), 2);
+        assert!(restored_stats
+            .discovered_terrains
+            .contains(TerrainType::Crystal));
+        assert_eq!(time, GameTime::from_minutes(90));
+    }
+}
@@ -0,0 +1,428 @@
+//! Settings Persistence - One Versioned File for All Player Preferences
+//!
+//! The settings file is loaded before any dependent resource is inserted;
+//! each subsystem resource is built from its section. Later changes to any
+//! of those resources are copied back into the store and written to disk by
+//! a debounced save system, so a burst of tweaks costs a single write. Web
+//! builds keep the same document in the browser's localStorage instead.
+
+pub mod store;
+
+pub use store::{
+    backup_path, load_settings, peek_display_settings, read_settings_text, save_settings,
+    AccessibilitySettings, AudioSettingsSection, BackgroundSettings, BlitzSettings, CodexSettings,
+    ConsoleSettings, InputSettingsSection, InventorySettings, KeyBinding, LowPointsGuardSettings,
+    MapLayerVisibility, MutatorSettings, PartySettings, RunSettings, SettingsFile, SettingsLoad,
+    StalenessSettings, TutorialFlags, SETTINGS_FILE_PATH, SETTINGS_STORAGE_KEY, SETTINGS_VERSION,
+};
+
+use crate::presentation::audio_integration::{AudioSettings, GlobalAudioSettings};
+use crate::presentation::input::InputMapper;
+use crate::presentation::movement::MovementConfig;
+use crate::presentation::rendering::DisplaySettings;
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// Minimum time between two settings writes
+pub const SETTINGS_SAVE_INTERVAL_SECONDS: f64 = 2.0;
+
+/// Coalesces settings changes into at most one write per interval
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveDebouncer {
+    interval: f64,
+    last_write: Option<f64>,
+    dirty: bool,
+}
+
+impl SaveDebouncer {
+    /// Create a debouncer allowing one write per `interval` seconds
+    pub fn new(interval: f64) -> Self {
+        Self {
+            interval,
+            last_write: None,
+            dirty: false,
+        }
+    }
+
+    /// Note that there are unsaved changes
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Check if there are unsaved changes
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Decide whether to write now; clears the dirty flag when it does
+    pub fn should_write(&mut self, now: f64) -> bool {
+        if !self.dirty {
+            return false;
+        }
+        if let Some(last) = self.last_write {
+            if now - last < self.interval {
+                return false;
+            }
+        }
+        self.dirty = false;
+        self.last_write = Some(now);
+        true
+    }
+
+    /// Write now regardless of the interval if anything is unsaved
+    pub fn flush(&mut self, now: f64) -> bool {
+        if !self.dirty {
+            return false;
+        }
+        self.dirty = false;
+        self.last_write = Some(now);
+        true
+    }
+}
+
+/// Where a settings store writes its document
+#[derive(Debug, Clone, PartialEq)]
+enum SettingsBacking {
+    /// Nothing is written
+    Memory,
+    File(PathBuf),
+    /// The browser's localStorage, under `SETTINGS_STORAGE_KEY`
+    #[cfg(target_arch = "wasm32")]
+    LocalStorage,
+}
+
+/// Loaded settings plus where and when to write them back
+#[derive(Resource, Debug)]
+pub struct SettingsStore {
+    pub settings: SettingsFile,
+    backing: SettingsBacking,
+    debouncer: SaveDebouncer,
+}
+
+impl SettingsStore {
+    fn loaded(settings: SettingsFile, load: &SettingsLoad, backing: SettingsBacking) -> Self {
+        let mut store = Self {
+            settings,
+            backing,
+            debouncer: SaveDebouncer::new(SETTINGS_SAVE_INTERVAL_SECONDS),
+        };
+        // Write the upgraded document once the game is running
+        if matches!(load, SettingsLoad::Migrated { .. }) {
+            store.debouncer.mark_dirty();
+        }
+        store
+    }
+
+    /// Store backed by a settings file
+    pub fn from_file(path: PathBuf) -> (Self, SettingsLoad) {
+        let (settings, load) = load_settings(&path);
+        let store = Self::loaded(settings, &load, SettingsBacking::File(path));
+        (store, load)
+    }
+
+    /// Store backed by the browser's localStorage
+    #[cfg(target_arch = "wasm32")]
+    pub fn from_local_storage() -> (Self, SettingsLoad) {
+        let (settings, load) = store::load_web_settings();
+        let store = Self::loaded(settings, &load, SettingsBacking::LocalStorage);
+        (store, load)
+    }
+
+    /// Store that never writes anywhere (tests)
+    pub fn in_memory(settings: SettingsFile) -> Self {
+        Self {
+            settings,
+            backing: SettingsBacking::Memory,
+            debouncer: SaveDebouncer::new(SETTINGS_SAVE_INTERVAL_SECONDS),
+        }
+    }
+
+    /// Check if there are changes not yet written
+    pub fn is_dirty(&self) -> bool {
+        self.debouncer.is_dirty()
+    }
+
+    /// Replace a section if it changed, marking the store dirty
+    fn update<T: PartialEq>(
+        &mut self,
+        section: impl FnOnce(&mut SettingsFile) -> &mut T,
+        value: T,
+    ) {
+        let slot = section(&mut self.settings);
+        if *slot != value {
+            *slot = value;
+            self.debouncer.mark_dirty();
+        }
+    }
+
+    fn write(&self) {
+        let result = match &self.backing {
+            SettingsBacking::Memory => return,
+            SettingsBacking::File(path) => save_settings(path, &self.settings)
+                .map(|_| debug!("⚙️ Settings saved to {}", path.display())),
+            #[cfg(target_arch = "wasm32")]
+            SettingsBacking::LocalStorage => store::save_web_settings(&self.settings)
+                .map(|_| debug!("⚙️ Settings saved to browser storage")),
+        };
+        if let Err(e) = result {
+            warn!("⚙️ {}", e);
+        }
+    }
+}
+
+/// Plugin loading settings and inserting the resources built from them
+///
+/// Must be added before the plugins that `init_resource` the same types so
+/// the loaded values win over the defaults.
+pub struct SettingsPlugin {
+    pub path: PathBuf,
+}
+
+impl Default for SettingsPlugin {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from(SETTINGS_FILE_PATH),
+        }
+    }
+}
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        #[cfg(not(target_arch = "wasm32"))]
+        let (store, load) = SettingsStore::from_file(self.path.clone());
+        #[cfg(target_arch = "wasm32")]
+        let (store, load) = SettingsStore::from_local_storage();
+        match &load {
+            SettingsLoad::Missing => info!("⚙️ No settings saved yet, using defaults"),
+            SettingsLoad::Loaded => info!("⚙️ Settings loaded"),
+            SettingsLoad::Migrated { from } => info!(
+                "⚙️ Settings upgraded from version {} to {}",
+                from, SETTINGS_VERSION
+            ),
+            SettingsLoad::Fallback { reason, backup } => warn!(
+                "⚙️ Settings reset to defaults ({}); previous file kept at {:?}",
+                reason, backup
+            ),
+        }
+
+        let settings = &store.settings;
+        app.insert_resource(settings.movement.clone())
+            .insert_resource(settings.display.clone())
+            .insert_resource(settings.audio.to_runtime())
+            .insert_resource(settings.volume.clone())
+            .insert_resource(settings.input.to_runtime())
+            .insert_resource(settings.tutorial.clone())
+            .insert_resource(settings.map_layers.clone())
+            .insert_resource(settings.low_points_guard.clone())
+            .insert_resource(settings.inventory.clone())
+            .insert_resource(settings.blitz.clone())
+            .insert_resource(settings.background.clone())
+            .insert_resource(settings.staleness.clone())
+            .insert_resource(settings.mutators.clone())
+            .insert_resource(settings.party.clone())
+            .insert_resource(settings.codex.clone())
+            .insert_resource(settings.run.clone())
+            .insert_resource(settings.console.clone())
+            .insert_resource(settings.accessibility.clone())
+            .insert_resource(store)
+            .add_systems(
+                Update,
+                (collect_settings_changes_system, save_settings_system).chain(),
+            );
+    }
+}
+
+/// Copy changed subsystem resources back into the settings store
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn collect_settings_changes_system(
+    mut store: ResMut<SettingsStore>,
+    movement: Res<MovementConfig>,
+    display: Res<DisplaySettings>,
+    audio: Res<GlobalAudioSettings>,
+    input: Res<InputMapper>,
+    tutorial: Res<TutorialFlags>,
+    map_layers: Res<MapLayerVisibility>,
+    low_points_guard: Res<LowPointsGuardSettings>,
+    inventory: Res<InventorySettings>,
+    blitz: Res<BlitzSettings>,
+    background: Res<BackgroundSettings>,
+    staleness: Res<StalenessSettings>,
+    mutators: Res<MutatorSettings>,
+    party: Res<PartySettings>,
+    (codex, run, console, accessibility, volume): (
+        Res<CodexSettings>,
+        Res<RunSettings>,
+        Res<ConsoleSettings>,
+        Res<AccessibilitySettings>,
+        Res<AudioSettings>,
+    ),
+) {
+    // Freshly inserted resources already match the store
+    if movement.is_changed() && !movement.is_added() {
+        store.update(|s| &mut s.movement, movement.clone());
+    }
+    if display.is_changed() && !display.is_added() {
+        store.update(|s| &mut s.display, display.clone());
+    }
+    if audio.is_changed() && !audio.is_added() {
+        store.update(|s| &mut s.audio, AudioSettingsSection::from(&*audio));
+    }
+    if input.is_changed() && !input.is_added() {
+        store.update(|s| &mut s.input, InputSettingsSection::from(&*input));
+    }
+    if tutorial.is_changed() && !tutorial.is_added() {
+        store.update(|s| &mut s.tutorial, tutorial.clone());
+    }
+    if map_layers.is_changed() && !map_layers.is_added() {
+        store.update(|s| &mut s.map_layers, map_layers.clone());
+    }
+    if low_points_guard.is_changed() && !low_points_guard.is_added() {
+        store.update(|s| &mut s.low_points_guard, low_points_guard.clone());
+    }
+    if inventory.is_changed() && !inventory.is_added() {
+        store.update(|s| &mut s.inventory, inventory.clone());
+    }
+    if blitz.is_changed() && !blitz.is_added() {
+        store.update(|s| &mut s.blitz, blitz.clone());
+    }
+    if background.is_changed() && !background.is_added() {
+        store.update(|s| &mut s.background, background.clone());
+    }
+    if staleness.is_changed() && !staleness.is_added() {
+        store.update(|s| &mut s.staleness, staleness.clone());
+    }
+    if mutators.is_changed() && !mutators.is_added() {
+        store.update(|s| &mut s.mutators, mutators.clone());
+    }
+    if party.is_changed() && !party.is_added() {
+        store.update(|s| &mut s.party, party.clone());
+    }
+    if codex.is_changed() && !codex.is_added() {
+        store.update(|s| &mut s.codex, codex.clone());
+    }
+    if run.is_changed() && !run.is_added() {
+        store.update(|s| &mut s.run, run.clone());
+    }
+    if console.is_changed() && !console.is_added() {
+        store.update(|s| &mut s.console, console.clone());
+    }
+    if accessibility.is_changed() && !accessibility.is_added() {
+        store.update(|s| &mut s.accessibility, accessibility.clone());
+    }
+    if volume.is_changed() && !volume.is_added() {
+        store.update(|s| &mut s.volume, volume.clone());
+    }
+}
+
+/// Write pending changes at most once per save interval, and on exit
+fn save_settings_system(
+    mut store: ResMut<SettingsStore>,
+    time: Res<Time<Real>>,
+    mut exit_events: EventReader<AppExit>,
+) {
+    let now = time.elapsed_secs_f64();
+    let exiting = exit_events.read().count() > 0;
+    let write = if exiting {
+        store.debouncer.flush(now)
+    } else {
+        store.debouncer.should_write(now)
+    };
+    if write {
+        store.write();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debouncer_coalesces_writes_within_interval() {
+        let mut debouncer = SaveDebouncer::new(2.0);
+        assert!(!debouncer.should_write(0.0));
+
+        debouncer.mark_dirty();
+        assert!(debouncer.should_write(0.1));
+
+        // A burst of changes right after a write waits for the interval
+        debouncer.mark_dirty();
+        assert!(!debouncer.should_write(0.5));
+        debouncer.mark_dirty();
+        assert!(!debouncer.should_write(1.9));
+        assert!(debouncer.should_write(2.1));
+
+        // Nothing left to write
+        assert!(!debouncer.should_write(10.0));
+    }
+
+    #[test]
+    fn debouncer_flush_ignores_interval() {
+        let mut debouncer = SaveDebouncer::new(2.0);
+        debouncer.mark_dirty();
+        assert!(debouncer.should_write(0.0));
+
+        debouncer.mark_dirty();
+        assert!(debouncer.flush(0.5));
+        assert!(!debouncer.flush(0.6));
+    }
+
+    #[test]
+    fn unchanged_sections_do_not_mark_dirty() {
+        let mut store = SettingsStore::in_memory(SettingsFile::default());
+
+        store.update(|s| &mut s.movement, MovementConfig::default());
+        assert!(!store.is_dirty());
+
+        let slower = MovementConfig {
+            base_duration_ms: 2000,
+            ..MovementConfig::default()
+        };
+        store.update(|s| &mut s.movement, slower);
+        assert!(store.is_dirty());
+        assert_eq!(store.settings.movement.base_duration_ms, 2000);
+    }
+
+    #[test]
+    fn resource_changes_update_the_store() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(SettingsStore::in_memory(SettingsFile::default()))
+            .init_resource::<MovementConfig>()
+            .init_resource::<DisplaySettings>()
+            .init_resource::<GlobalAudioSettings>()
+            .insert_resource(InputMapper::new())
+            .init_resource::<TutorialFlags>()
+            .init_resource::<MapLayerVisibility>()
+            .init_resource::<LowPointsGuardSettings>()
+            .init_resource::<InventorySettings>()
+            .init_resource::<BlitzSettings>()
+            .init_resource::<BackgroundSettings>()
+            .init_resource::<StalenessSettings>()
+            .init_resource::<MutatorSettings>()
+            .init_resource::<PartySettings>()
+            .init_resource::<CodexSettings>()
+            .init_resource::<RunSettings>()
+            .init_resource::<ConsoleSettings>()
+            .init_resource::<AccessibilitySettings>()
+            .init_resource::<AudioSettings>()
+            .add_systems(Update, collect_settings_changes_system);
+        app.update();
+        assert!(!app.world().resource::<SettingsStore>().is_dirty());
+
+        app.world_mut()
+            .resource_mut::<TutorialFlags>()
+            .complete("first_move");
+        app.world_mut().resource_mut::<DisplaySettings>().ui_scale = 1.5;
+        app.world_mut()
+            .resource_mut::<AudioSettings>()
+            .toggle_mute();
+        app.update();
+
+        let store = app.world().resource::<SettingsStore>();
+        assert!(store.is_dirty());
+        assert!(store.settings.tutorial.is_completed("first_move"));
+        assert_eq!(store.settings.display.ui_scale, 1.5);
+        assert!(store.settings.volume.muted);
+    }
+}
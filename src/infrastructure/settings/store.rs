@@ -0,0 +1,861 @@
+//! Settings Store - Versioned Settings File With Migration and Fallback
+//!
+//! All persisted preferences live in one JSON document with a `version`
+//! field and one section per subsystem. Every section fills missing fields
+//! with defaults, so files written by older versions load cleanly and are
+//! upgraded the next time they are saved. Files from a newer version or
+//! that fail to parse are moved aside as a backup and defaults are used.
+
+use crate::domain::constants::{
+    BLITZ_DEFAULT_DECISION_SECS, BLITZ_MAX_DECISION_SECS, BLITZ_MIN_DECISION_SECS,
+    DEFAULT_STALE_AFTER_DAYS, INVENTORY_DEFAULT_RESERVE_DAYS, LOW_POINTS_DEFAULT_THRESHOLD,
+    PARTY_DEFAULT_PARTNER_NAME,
+};
+use crate::domain::services::{
+    AnnouncementFilter, CodexUnlocks, FortuneFavor, InventorySortMode, LowPointsGuardMode,
+    Mutators, RunMode, STANDARD_DROP,
+};
+use crate::infrastructure::console::CommandHistory;
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
+use crate::presentation::audio_integration::{AudioSettings, GlobalAudioSettings};
+use crate::presentation::frame_limiter::BackgroundPolicy;
+use crate::presentation::input::{GameAction, InputMapper};
+use crate::presentation::movement::MovementConfig;
+use crate::presentation::rendering::DisplaySettings;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Version written by this build
+///
+/// - v1: movement and audio sections only
+/// - v2: adds display, input bindings, tutorial flags and map layers
+/// - v3: binds dice rolls and the base, quest and inventory screens
+pub const SETTINGS_VERSION: u32 = 3;
+
+/// Last version whose key bindings predate the rebindable actions
+const LEGACY_BINDINGS_VERSION: u32 = 2;
+
+/// Default settings file location for native builds
+pub const SETTINGS_FILE_PATH: &str = "settings.json";
+
+/// File name a broken or unreadable settings file is preserved under
+pub const SETTINGS_BACKUP_FILE_NAME: &str = "settings.bak";
+
+/// Key of the settings document in the browser's localStorage
+pub const SETTINGS_STORAGE_KEY: &str = "space-looter/settings";
+
+/// Key a broken settings document is preserved under in localStorage
+pub const SETTINGS_BACKUP_STORAGE_KEY: &str = "space-looter/settings.bak";
+
+/// Audio switches chosen by the player
+///
+/// Device availability is detected at runtime and never persisted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettingsSection {
+    pub music_enabled: bool,
+    pub ambient_enabled: bool,
+    pub sfx_enabled: bool,
+    pub ui_enabled: bool,
+}
+
+impl Default for AudioSettingsSection {
+    fn default() -> Self {
+        Self::from(&GlobalAudioSettings::default())
+    }
+}
+
+impl From<&GlobalAudioSettings> for AudioSettingsSection {
+    fn from(settings: &GlobalAudioSettings) -> Self {
+        Self {
+            music_enabled: settings.music_enabled,
+            ambient_enabled: settings.ambient_enabled,
+            sfx_enabled: settings.sfx_enabled,
+            ui_enabled: settings.ui_enabled,
+        }
+    }
+}
+
+impl AudioSettingsSection {
+    /// Build the runtime audio resource from this section
+    pub fn to_runtime(&self) -> GlobalAudioSettings {
+        GlobalAudioSettings {
+            music_enabled: self.music_enabled,
+            ambient_enabled: self.ambient_enabled,
+            sfx_enabled: self.sfx_enabled,
+            ui_enabled: self.ui_enabled,
+            ..GlobalAudioSettings::default()
+        }
+    }
+}
+
+/// A single persisted key binding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBinding {
+    pub key: KeyCode,
+    pub action: GameAction,
+}
+
+/// Persisted key bindings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputSettingsSection {
+    pub bindings: Vec<KeyBinding>,
+}
+
+impl Default for InputSettingsSection {
+    fn default() -> Self {
+        Self::from(&InputMapper::new())
+    }
+}
+
+impl From<&InputMapper> for InputSettingsSection {
+    fn from(mapper: &InputMapper) -> Self {
+        Self {
+            bindings: mapper
+                .bindings()
+                .into_iter()
+                .map(|(key, action)| KeyBinding { key, action })
+                .collect(),
+        }
+    }
+}
+
+impl InputSettingsSection {
+    /// Build the runtime input mapper from this section
+    pub fn to_runtime(&self) -> InputMapper {
+        InputMapper::from_bindings(
+            self.bindings
+                .iter()
+                .map(|binding| (binding.key, binding.action)),
+        )
+    }
+}
+
+/// Tutorial hints the player has already dismissed
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TutorialFlags {
+    pub completed: BTreeSet<String>,
+}
+
+impl TutorialFlags {
+    /// Check if a tutorial step has been completed
+    pub fn is_completed(&self, step: &str) -> bool {
+        self.completed.contains(step)
+    }
+
+    /// Mark a tutorial step as completed
+    pub fn complete(&mut self, step: &str) {
+        self.completed.insert(step.to_string());
+    }
+}
+
+/// Which optional map overlays are drawn
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MapLayerVisibility {
+    pub terrain_transitions: bool,
+    pub resource_nodes: bool,
+    pub tile_highlights: bool,
+    /// Faint trail of the best previous run on the same seed
+    pub ghost_trail: bool,
+}
+
+impl Default for MapLayerVisibility {
+    fn default() -> Self {
+        Self {
+            terrain_transitions: true,
+            resource_nodes: true,
+            tile_highlights: false,
+            ghost_trail: true,
+        }
+    }
+}
+
+/// Warn or pause when a move leaves few movement points
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LowPointsGuardSettings {
+    pub mode: LowPointsGuardMode,
+    /// Movement points at or below which the guard reacts
+    pub threshold: u8,
+}
+
+impl Default for LowPointsGuardSettings {
+    fn default() -> Self {
+        Self {
+            mode: LowPointsGuardMode::default(),
+            threshold: LOW_POINTS_DEFAULT_THRESHOLD,
+        }
+    }
+}
+
+/// Inventory list order and the supply reserve kept on deposit
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InventorySettings {
+    pub sort_mode: InventorySortMode,
+    /// Days of Food and Energy "Deposit all except reserve" leaves in the cargo
+    pub reserve_days: u32,
+}
+
+impl Default for InventorySettings {
+    fn default() -> Self {
+        Self {
+            sort_mode: InventorySortMode::default(),
+            reserve_days: INVENTORY_DEFAULT_RESERVE_DAYS,
+        }
+    }
+}
+
+/// Blitz ruleset for new runs
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BlitzSettings {
+    /// Start new runs with a countdown on every decision
+    pub enabled: bool,
+    /// Seconds per decision before the autopilot moves
+    pub decision_secs: u32,
+}
+
+impl BlitzSettings {
+    /// Decision time clamped to the supported range
+    pub fn decision_secs(&self) -> f32 {
+        self.decision_secs
+            .clamp(BLITZ_MIN_DECISION_SECS, BLITZ_MAX_DECISION_SECS) as f32
+    }
+}
+
+impl Default for BlitzSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            decision_secs: BLITZ_DEFAULT_DECISION_SECS,
+        }
+    }
+}
+
+/// What the web build does while its page is hidden
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackgroundSettings {
+    pub policy: BackgroundPolicy,
+}
+
+/// When explored ground turns stale
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StalenessSettings {
+    /// Days an explored tile can go unseen before it turns stale
+    pub stale_after_days: u32,
+}
+
+impl Default for StalenessSettings {
+    fn default() -> Self {
+        Self {
+            stale_after_days: DEFAULT_STALE_AFTER_DAYS,
+        }
+    }
+}
+
+/// Mutators picked for new runs
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MutatorSettings {
+    /// Mutators the next run starts with; a run in progress keeps its own
+    pub selected: Mutators,
+}
+
+/// Pass-and-play party for new runs
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PartySettings {
+    /// Start new runs with two characters taking turns on one device
+    pub hot_seat: bool,
+    /// Name of the second character
+    pub partner_name: String,
+}
+
+impl Default for PartySettings {
+    fn default() -> Self {
+        Self {
+            hot_seat: false,
+            partner_name: PARTY_DEFAULT_PARTNER_NAME.to_string(),
+        }
+    }
+}
+
+/// Codex entries unlocked over every run played
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CodexSettings {
+    pub unlocked: CodexUnlocks,
+}
+
+/// Rules picked for new runs
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunSettings {
+    /// Start new runs in hardcore mode, where defeat deletes the save
+    pub hardcore: bool,
+    /// Id of the starting scenario of new runs
+    pub scenario: String,
+    /// Give hardcore runs an Emergency Recall, at a cost in score
+    pub hardcore_recall: bool,
+    /// Start new runs in relaxed mode, where the last move can be undone
+    pub relaxed: bool,
+    /// Soften streaks of bad luck in new runs with Fortune's Favor
+    pub fortune_favor: bool,
+    /// Label the rolls Fortune's Favor helps instead of helping silently
+    pub show_assist: bool,
+}
+
+impl Default for RunSettings {
+    fn default() -> Self {
+        Self {
+            hardcore: false,
+            scenario: STANDARD_DROP.to_string(),
+            hardcore_recall: false,
+            relaxed: false,
+            fortune_favor: false,
+            show_assist: false,
+        }
+    }
+}
+
+impl RunSettings {
+    /// Mode new runs start in
+    pub fn mode(&self) -> RunMode {
+        if self.hardcore {
+            RunMode::Hardcore
+        } else if self.relaxed {
+            RunMode::Relaxed
+        } else {
+            RunMode::Normal
+        }
+    }
+
+    /// Switch new runs to the mode after the current one
+    pub fn cycle_mode(&mut self) {
+        let next = match self.mode() {
+            RunMode::Normal => RunMode::Hardcore,
+            RunMode::Hardcore => RunMode::Relaxed,
+            RunMode::Relaxed => RunMode::Normal,
+        };
+        self.hardcore = next == RunMode::Hardcore;
+        self.relaxed = next == RunMode::Relaxed;
+    }
+
+    /// Switch Fortune's Favor from off to on, to on and labelled, to off
+    pub fn cycle_fortune_favor(&mut self) {
+        (self.fortune_favor, self.show_assist) = match (self.fortune_favor, self.show_assist) {
+            (false, _) => (true, false),
+            (true, false) => (true, true),
+            (true, true) => (false, false),
+        };
+    }
+
+    /// Fortune's Favor for a new run; daily runs never get the assist
+    pub fn fortune_favor(&self, daily: bool) -> FortuneFavor {
+        let mut favor = if daily {
+            FortuneFavor::for_daily_run()
+        } else {
+            FortuneFavor::new()
+        };
+        favor.set_show_assist(self.show_assist);
+        // Only enabling is refused in daily mode, so this cannot fail
+        let _ = favor.set_enabled(self.fortune_favor && !daily);
+        favor
+    }
+}
+
+/// Dev console state kept between sessions
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConsoleSettings {
+    pub history: CommandHistory,
+}
+
+/// Screen reader and audio accessibility
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilitySettings {
+    /// Severities put into words for screen readers
+    pub announcements: AnnouncementFilter,
+    /// Leave out the sting played when an encounter begins
+    pub mute_stingers: bool,
+}
+
+/// The complete settings document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SettingsFile {
+    pub version: u32,
+    pub movement: MovementConfig,
+    pub display: DisplaySettings,
+    pub audio: AudioSettingsSection,
+    /// Volume levels and mute
+    pub volume: AudioSettings,
+    pub input: InputSettingsSection,
+    pub tutorial: TutorialFlags,
+    pub map_layers: MapLayerVisibility,
+    pub low_points_guard: LowPointsGuardSettings,
+    pub inventory: InventorySettings,
+    pub blitz: BlitzSettings,
+    pub background: BackgroundSettings,
+    pub staleness: StalenessSettings,
+    pub mutators: MutatorSettings,
+    pub party: PartySettings,
+    pub codex: CodexSettings,
+    pub run: RunSettings,
+    pub console: ConsoleSettings,
+    pub accessibility: AccessibilitySettings,
+}
+
+impl Default for SettingsFile {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            movement: MovementConfig::default(),
+            display: DisplaySettings::default(),
+            audio: AudioSettingsSection::default(),
+            volume: AudioSettings::default(),
+            input: InputSettingsSection::default(),
+            tutorial: TutorialFlags::default(),
+            map_layers: MapLayerVisibility::default(),
+            low_points_guard: LowPointsGuardSettings::default(),
+            inventory: InventorySettings::default(),
+            blitz: BlitzSettings::default(),
+            background: BackgroundSettings::default(),
+            staleness: StalenessSettings::default(),
+            mutators: MutatorSettings::default(),
+            party: PartySettings::default(),
+            codex: CodexSettings::default(),
+            run: RunSettings::default(),
+            console: ConsoleSettings::default(),
+            accessibility: AccessibilitySettings::default(),
+        }
+    }
+}
+
+impl SettingsFile {
+    /// Parse a settings document, upgrading older versions in memory
+    ///
+    /// Missing fields of older versions are filled with defaults. The
+    /// version field keeps the loaded value until the file is saved again.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut settings: SettingsFile =
+            serde_json::from_str(text).map_err(|e| format!("invalid settings file: {}", e))?;
+        if settings.version == 0 || settings.version > SETTINGS_VERSION {
+            return Err(format!(
+                "unsupported settings version {} (this build writes {})",
+                settings.version, SETTINGS_VERSION
+            ));
+        }
+        // Older bindings could not be changed in game and left the new
+        // actions unbound, so they start over from the defaults
+        if settings.version <= LEGACY_BINDINGS_VERSION {
+            settings.input = InputSettingsSection::default();
+        }
+        Ok(settings)
+    }
+
+    /// Serialize as the current version
+    pub fn to_json(&self) -> InfrastructureResult<String> {
+        let current = SettingsFile {
+            version: SETTINGS_VERSION,
+            ..self.clone()
+        };
+        serde_json::to_string_pretty(&current).map_err(|e| {
+            InfrastructureError::ExternalServiceError(format!(
+                "failed to serialize settings: {}",
+                e
+            ))
+        })
+    }
+
+    /// Check if this document was loaded from an older version
+    pub fn needs_upgrade(&self) -> bool {
+        self.version < SETTINGS_VERSION
+    }
+}
+
+/// How the settings were obtained at startup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingsLoad {
+    /// No settings file existed yet
+    Missing,
+    /// The file was loaded as-is
+    Loaded,
+    /// An older file was loaded; it is upgraded on the next save
+    Migrated { from: u32 },
+    /// The file was unusable; defaults are used and the file was backed up
+    Fallback {
+        reason: String,
+        backup: Option<PathBuf>,
+    },
+}
+
+/// Load settings from `path`, falling back to defaults when unusable
+///
+/// A file that cannot be parsed, or that was written by a newer version,
+/// is renamed to `settings.bak` next to it so the player's data is not lost
+/// when defaults are saved over it.
+pub fn load_settings(path: &Path) -> (SettingsFile, SettingsLoad) {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return (SettingsFile::default(), SettingsLoad::Missing);
+        }
+        Err(e) => {
+            return (
+                SettingsFile::default(),
+                SettingsLoad::Fallback {
+                    reason: format!("failed to read settings: {}", e),
+                    backup: None,
+                },
+            );
+        }
+    };
+
+    match read_settings_text(&text) {
+        Ok(loaded) => loaded,
+        Err(reason) => {
+            let backup = backup_path(path);
+            let backup = std::fs::rename(path, &backup).ok().map(|_| backup);
+            (
+                SettingsFile::default(),
+                SettingsLoad::Fallback { reason, backup },
+            )
+        }
+    }
+}
+
+/// Make sense of a settings document read at startup
+///
+/// The error is the reason the document cannot be used.
+pub fn read_settings_text(text: &str) -> Result<(SettingsFile, SettingsLoad), String> {
+    let settings = SettingsFile::parse(text)?;
+    let load = if settings.needs_upgrade() {
+        SettingsLoad::Migrated {
+            from: settings.version,
+        }
+    } else {
+        SettingsLoad::Loaded
+    };
+    Ok((settings, load))
+}
+
+/// Load settings from localStorage, falling back to defaults when unusable
+///
+/// An unusable document is copied to `SETTINGS_BACKUP_STORAGE_KEY` first,
+/// like a broken file is moved aside on native builds.
+#[cfg(target_arch = "wasm32")]
+pub fn load_web_settings() -> (SettingsFile, SettingsLoad) {
+    use crate::infrastructure::saves::storage::web;
+
+    let stored = web::local_storage()
+        .and_then(|storage| web::call(&storage, "getItem", &[SETTINGS_STORAGE_KEY]));
+    let text = match stored {
+        Ok(value) => match value.as_string() {
+            Some(text) => text,
+            None => return (SettingsFile::default(), SettingsLoad::Missing),
+        },
+        Err(e) => {
+            return (
+                SettingsFile::default(),
+                SettingsLoad::Fallback {
+                    reason: format!("failed to read settings: {}", e),
+                    backup: None,
+                },
+            );
+        }
+    };
+
+    read_settings_text(&text).unwrap_or_else(|reason| {
+        let _ = web::local_storage().and_then(|storage| {
+            web::call(&storage, "setItem", &[SETTINGS_BACKUP_STORAGE_KEY, &text])
+        });
+        (
+            SettingsFile::default(),
+            SettingsLoad::Fallback {
+                reason,
+                backup: None,
+            },
+        )
+    })
+}
+
+/// Write settings to localStorage as the current version
+#[cfg(target_arch = "wasm32")]
+pub fn save_web_settings(settings: &SettingsFile) -> InfrastructureResult<()> {
+    use crate::infrastructure::saves::storage::web;
+
+    let json = settings.to_json()?;
+    web::local_storage()
+        .and_then(|storage| web::call(&storage, "setItem", &[SETTINGS_STORAGE_KEY, &json]))
+        .map(|_| ())
+        .map_err(|e| {
+            InfrastructureError::ExternalServiceError(format!("failed to store settings: {}", e))
+        })
+}
+
+/// Display settings saved at `path`, without reporting or backing up
+///
+/// Read before the window is created so it opens at the saved size; any
+/// problem with the file is left for the full load to report.
+pub fn peek_display_settings(path: &Path) -> DisplaySettings {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|text| SettingsFile::parse(&text).ok())
+        .map(|settings| settings.display)
+        .unwrap_or_default()
+}
+
+/// Write settings to `path` as the current version
+pub fn save_settings(path: &Path, settings: &SettingsFile) -> InfrastructureResult<()> {
+    let json = settings.to_json()?;
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent).map_err(|e| {
+            InfrastructureError::ExternalServiceError(format!(
+                "failed to create settings directory: {}",
+                e
+            ))
+        })?;
+    }
+    std::fs::write(path, json).map_err(|e| {
+        InfrastructureError::ExternalServiceError(format!("failed to write settings: {}", e))
+    })
+}
+
+/// Location a broken settings file is moved to
+pub fn backup_path(path: &Path) -> PathBuf {
+    path.with_file_name(SETTINGS_BACKUP_FILE_NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presentation::audio_integration::VolumeChannel;
+    use crate::presentation::display_mode::ResolutionPreset;
+
+    fn temp_settings_path() -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("space-looter-settings-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("settings.json")
+    }
+
+    const V1_SETTINGS: &str = r#"{
+        "version": 1,
+        "movement": { "base_duration_ms": 750, "use_easing": false },
+        "audio": { "music_enabled": false }
+    }"#;
+
+    #[test]
+    fn v1_settings_migrate_to_current() {
+        let settings = SettingsFile::parse(V1_SETTINGS).unwrap();
+
+        assert_eq!(settings.version, 1);
+        assert!(settings.needs_upgrade());
+        assert_eq!(settings.movement.base_duration_ms, 750);
+        assert!(!settings.movement.use_easing);
+        // Fields missing from v1 come from defaults
+        assert_eq!(
+            settings.movement.camera_follow_speed,
+            MovementConfig::default().camera_follow_speed
+        );
+        assert!(!settings.audio.music_enabled);
+        assert!(settings.audio.sfx_enabled);
+        assert_eq!(settings.display, DisplaySettings::default());
+        assert_eq!(settings.input, InputSettingsSection::default());
+
+        // Saving writes the current version
+        let upgraded = SettingsFile::parse(&settings.to_json().unwrap()).unwrap();
+        assert_eq!(upgraded.version, SETTINGS_VERSION);
+        assert!(!upgraded.needs_upgrade());
+        assert_eq!(upgraded.movement.base_duration_ms, 750);
+    }
+
+    #[test]
+    fn migrated_file_is_reported_and_rewritten() {
+        let path = temp_settings_path();
+        std::fs::write(&path, V1_SETTINGS).unwrap();
+
+        let (settings, load) = load_settings(&path);
+        assert_eq!(load, SettingsLoad::Migrated { from: 1 });
+
+        save_settings(&path, &settings).unwrap();
+        let (reloaded, load) = load_settings(&path);
+        assert_eq!(load, SettingsLoad::Loaded);
+        assert_eq!(reloaded.version, SETTINGS_VERSION);
+        assert_eq!(reloaded.movement.base_duration_ms, 750);
+    }
+
+    #[test]
+    fn corrupted_file_falls_back_and_is_backed_up() {
+        let path = temp_settings_path();
+        std::fs::write(&path, "{ this is not json").unwrap();
+
+        let (settings, load) = load_settings(&path);
+
+        assert_eq!(settings, SettingsFile::default());
+        let SettingsLoad::Fallback { backup, .. } = load else {
+            panic!("expected fallback, got {:?}", load);
+        };
+        let backup = backup.unwrap();
+        assert_eq!(backup, backup_path(&path));
+        assert_eq!(
+            std::fs::read_to_string(&backup).unwrap(),
+            "{ this is not json"
+        );
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn newer_version_falls_back_to_defaults() {
+        let path = temp_settings_path();
+        let newer = format!(r#"{{"version": {}}}"#, SETTINGS_VERSION + 1);
+        std::fs::write(&path, &newer).unwrap();
+
+        let (settings, load) = load_settings(&path);
+
+        assert_eq!(settings, SettingsFile::default());
+        assert!(matches!(load, SettingsLoad::Fallback { .. }));
+        assert_eq!(std::fs::read_to_string(backup_path(&path)).unwrap(), newer);
+    }
+
+    #[test]
+    fn codex_unlocks_carry_over_to_the_next_session() {
+        let path = temp_settings_path();
+        let mut first = SettingsFile::default();
+        assert!(first.codex.unlocked.unlock("terrain/Swamp"));
+        save_settings(&path, &first).unwrap();
+
+        // A later session starts from the same profile
+        let (mut second, load) = load_settings(&path);
+        assert_eq!(load, SettingsLoad::Loaded);
+        assert!(second.codex.unlocked.is_unlocked("terrain/Swamp"));
+        assert!(!second.codex.unlocked.unlock("terrain/Swamp"));
+        assert!(second.codex.unlocked.unlock("event/Mystery"));
+
+        // Older files without the section start with nothing unlocked
+        let settings = SettingsFile::parse(V1_SETTINGS).unwrap();
+        assert_eq!(settings.codex, CodexSettings::default());
+    }
+
+    #[test]
+    fn console_history_survives_a_restart() {
+        let path = temp_settings_path();
+        let mut first = SettingsFile::default();
+        first.console.history.push("ping");
+        first.console.history.push("inspect 12v1");
+        save_settings(&path, &first).unwrap();
+
+        let (second, load) = load_settings(&path);
+        assert_eq!(load, SettingsLoad::Loaded);
+        assert_eq!(second.console, first.console);
+        assert_eq!(second.console.history.recent(0), Some("inspect 12v1"));
+
+        // Older files without the section start with an empty history
+        let settings = SettingsFile::parse(V1_SETTINGS).unwrap();
+        assert!(settings.console.history.is_empty());
+    }
+
+    #[test]
+    fn volume_levels_and_mute_survive_a_restart() {
+        let path = temp_settings_path();
+        let mut first = SettingsFile::default();
+        first.volume.adjust(VolumeChannel::Music, -0.4);
+        first.volume.toggle_mute();
+        save_settings(&path, &first).unwrap();
+
+        let (second, load) = load_settings(&path);
+        assert_eq!(load, SettingsLoad::Loaded);
+        assert_eq!(second.volume, first.volume);
+        assert!(second.volume.muted);
+        assert_eq!(second.volume.music_volume, 0.6);
+
+        // Older files without the section play at full levels
+        let settings = SettingsFile::parse(V1_SETTINGS).unwrap();
+        assert_eq!(settings.volume, AudioSettings::default());
+    }
+
+    #[test]
+    fn fortune_favor_is_opt_in_and_never_reaches_daily_runs() {
+        let path = temp_settings_path();
+        let mut first = SettingsFile::default();
+        assert!(!first.run.fortune_favor(false).is_enabled());
+
+        first.run.cycle_fortune_favor();
+        assert!(first.run.fortune_favor(false).is_enabled());
+        assert!(!first.run.fortune_favor(false).show_assist());
+        first.run.cycle_fortune_favor();
+        assert!(first.run.fortune_favor(false).show_assist());
+        assert!(!first.run.fortune_favor(true).is_enabled());
+        save_settings(&path, &first).unwrap();
+
+        let (second, _) = load_settings(&path);
+        assert_eq!(second.run, first.run);
+        let mut third = second.run.clone();
+        third.cycle_fortune_favor();
+        assert!(!third.fortune_favor && !third.show_assist);
+    }
+
+    #[test]
+    fn window_choices_are_restored_before_startup() {
+        let path = temp_settings_path();
+        assert_eq!(peek_display_settings(&path), DisplaySettings::default());
+
+        let mut settings = SettingsFile::default();
+        settings.display.resolution = ResolutionPreset::Hd1080;
+        settings.display.fullscreen = true;
+        save_settings(&path, &settings).unwrap();
+
+        let display = peek_display_settings(&path);
+        assert_eq!(display.resolution, ResolutionPreset::Hd1080);
+        assert!(display.fullscreen);
+        let (loaded, _) = load_settings(&path);
+        assert_eq!(loaded.display, display);
+
+        // Peeking at a broken file leaves it for the full load to back up
+        std::fs::write(&path, "{ broken").unwrap();
+        assert_eq!(peek_display_settings(&path), DisplaySettings::default());
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn missing_file_uses_defaults() {
+        let path = temp_settings_path();
+        let (settings, load) = load_settings(&path);
+        assert_eq!(settings, SettingsFile::default());
+        assert_eq!(load, SettingsLoad::Missing);
+    }
+
+    #[test]
+    fn sections_round_trip_to_runtime_resources() {
+        let mut mapper = InputMapper::new();
+        mapper.bind_key(KeyCode::KeyJ, GameAction::Confirm);
+        let section = InputSettingsSection::from(&mapper);
+        assert_eq!(
+            section.to_runtime().get_action(&KeyCode::KeyJ),
+            Some(&GameAction::Confirm)
+        );
+
+        let audio = GlobalAudioSettings {
+            device_available: false,
+            ambient_enabled: false,
+            ..GlobalAudioSettings::default()
+        };
+        let runtime = AudioSettingsSection::from(&audio).to_runtime();
+        assert!(!runtime.ambient_enabled);
+        // Device availability is probed at runtime, never restored
+        assert!(runtime.device_available);
+    }
+}
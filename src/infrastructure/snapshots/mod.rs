@@ -0,0 +1,651 @@
+//! State Snapshots - Per-subsystem hashes of the session for desync debugging
+//!
+//! When a replay or a pass-and-play turn diverges, knowing the turn is not
+//! enough; the question is what differed. A snapshot hashes each subsystem
+//! of the session on its own and keeps a few raw values beside each hash, so
+//! two snapshots can be compared subsystem by subsystem. Values are hashed
+//! as canonical JSON with object keys sorted, and map tiles are ordered by
+//! coordinate first, so a hash depends only on the state and is the same on
+//! every platform. Anything comparing runs, such as a replay check, should
+//! hash through [`StateSnapshot::capture`] so its reports name the same
+//! subsystems. The control channel records a snapshot at every rest and on
+//! request, keeping the last [`SNAPSHOT_CAPACITY`].
+
+use crate::domain::constants::MAP_CHUNK_SIZE;
+use crate::domain::entities::Map;
+use crate::domain::services::hashing::fnv1a_64;
+use crate::domain::services::{TileDecals, WreckField};
+use crate::domain::value_objects::TileCoordinate;
+use crate::infrastructure::saves::SaveData;
+use crate::presentation::game_state::RpgGameSession;
+use bevy::prelude::Resource;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, VecDeque};
+
+/// Snapshots kept in memory; older ones are forgotten
+pub const SNAPSHOT_CAPACITY: usize = 20;
+
+/// Raw values longer than this are shown by their hash instead
+const RAW_VALUE_MAX_LEN: usize = 48;
+
+/// Part of the session hashed on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Subsystem {
+    /// Position, stats, experience and movement points
+    Player,
+    /// Carried resources and gear
+    Inventory,
+    /// Base buildings and stored resources
+    Base,
+    /// Map chunks that play has changed since generation
+    Map,
+    /// Quests and the committed expedition
+    Quests,
+    /// Session flags
+    Flags,
+}
+
+impl Subsystem {
+    /// Every subsystem, in hashing order
+    pub fn all() -> [Subsystem; 6] {
+        [
+            Subsystem::Player,
+            Subsystem::Inventory,
+            Subsystem::Base,
+            Subsystem::Map,
+            Subsystem::Quests,
+            Subsystem::Flags,
+        ]
+    }
+
+    /// Name used in diff output
+    pub fn name(&self) -> &'static str {
+        match self {
+            Subsystem::Player => "player",
+            Subsystem::Inventory => "inventory",
+            Subsystem::Base => "base",
+            Subsystem::Map => "map",
+            Subsystem::Quests => "quests",
+            Subsystem::Flags => "flags",
+        }
+    }
+}
+
+/// Why a snapshot was taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotReason {
+    /// The player finished a rest
+    Rest,
+    /// Asked for through the control channel
+    Requested,
+}
+
+/// Write `value` as JSON with every object's keys in sorted order
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<(&String, &Value)> = object.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+/// `value` as canonical JSON text
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+/// Hash of `value` that ignores the order of object keys
+pub fn canonical_hash(value: &Value) -> u64 {
+    fnv1a_64(canonical_json(value).as_bytes())
+}
+
+/// Top-level fields of `value` as short text, long ones by their hash
+fn raw_values(value: &Value) -> BTreeMap<String, String> {
+    let Value::Object(object) = value else {
+        return BTreeMap::new();
+    };
+    object
+        .iter()
+        .map(|(key, value)| {
+            let text = canonical_json(value);
+            let shown = if text.len() > RAW_VALUE_MAX_LEN {
+                format!("#{:016x}", fnv1a_64(text.as_bytes()))
+            } else {
+                text
+            };
+            (key.clone(), shown)
+        })
+        .collect()
+}
+
+/// Hash and raw values of one subsystem
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubsystemState {
+    pub hash: u64,
+    /// Values shown when the subsystem differs, by name
+    pub values: BTreeMap<String, String>,
+}
+
+impl SubsystemState {
+    /// Hash `value` and keep its top-level fields as raw values
+    pub fn of_value(value: &Value) -> Self {
+        Self {
+            hash: canonical_hash(value),
+            values: raw_values(value),
+        }
+    }
+
+    /// Hash only the play-changed chunks of `map`, one raw value per chunk
+    ///
+    /// A chunk has changed once one of its tiles is explored, one of its
+    /// resource nodes is no longer full, a wreck or debris lies in it or one
+    /// of its tiles bears a decal; untouched chunks follow from the seed and
+    /// are left out.
+    pub fn of_map(map: &Map, wrecks: &WreckField, decals: &TileDecals) -> Self {
+        let chunk_of = |x: i32, y: i32| {
+            let size = MAP_CHUNK_SIZE as i32;
+            (x.div_euclid(size), y.div_euclid(size))
+        };
+
+        let mut tiles: Vec<(&TileCoordinate, _)> = map.tiles().iter().collect();
+        tiles.sort_by_key(|(coordinate, _)| (coordinate.x, coordinate.y, coordinate.z));
+        let mut nodes: Vec<_> = map.resource_nodes().iter().collect();
+        nodes.sort_by_key(|(position, _)| (position.x, position.y, position.z));
+
+        let mut chunks: BTreeMap<(i32, i32), (bool, String)> = BTreeMap::new();
+        for (coordinate, tile) in tiles {
+            let chunk = chunks
+                .entry(chunk_of(coordinate.x, coordinate.y))
+                .or_default();
+            chunk.0 |= tile.is_explored;
+            chunk.1.push_str(&format!(
+                "t{},{},{}:{:?}:{}:{}:{:?};",
+                coordinate.x,
+                coordinate.y,
+                coordinate.z,
+                tile.terrain_type,
+                tile.elevation.height,
+                tile.is_explored,
+                tile.last_visited_day
+            ));
+        }
+        for (position, node) in nodes {
+            let chunk = chunks.entry(chunk_of(position.x, position.y)).or_default();
+            chunk.0 |= !node.is_full();
+            chunk.1.push_str(&format!(
+                "n{},{},{}:{};",
+                position.x,
+                position.y,
+                position.z,
+                node.current_amount()
+            ));
+        }
+
+        for wreck in wrecks.wrecks() {
+            let position = wreck.position;
+            let chunk = chunks.entry(chunk_of(position.x, position.y)).or_default();
+            chunk.0 = true;
+            chunk.1.push_str(&format!(
+                "w{},{},{}:{:?}:{:?};",
+                position.x, position.y, position.z, wreck.origin, wreck.loot
+            ));
+        }
+        for debris in wrecks.debris() {
+            let position = debris.position;
+            let chunk = chunks.entry(chunk_of(position.x, position.y)).or_default();
+            chunk.0 = true;
+            chunk.1.push_str(&format!(
+                "d{},{},{}:{};",
+                position.x, position.y, position.z, debris.rests_left
+            ));
+        }
+        for site in decals.sites() {
+            let position = site.position;
+            let chunk = chunks.entry(chunk_of(position.x, position.y)).or_default();
+            chunk.0 = true;
+            for decal in &site.decals {
+                chunk.1.push_str(&format!(
+                    "m{},{},{}:{:?}:{};",
+                    position.x, position.y, position.z, decal.kind, decal.age
+                ));
+            }
+        }
+
+        let mut values = BTreeMap::new();
+        let mut tree = String::new();
+        for ((x, y), (_, contents)) in chunks.iter().filter(|(_, (dirty, _))| *dirty) {
+            let hash = fnv1a_64(contents.as_bytes());
+            tree.push_str(&format!("{},{}:{:016x};", x, y, hash));
+            values.insert(format!("chunk {},{}", x, y), format!("#{:016x}", hash));
+        }
+        values.insert(
+            "dirty_chunks".to_string(),
+            chunks
+                .values()
+                .filter(|(dirty, _)| *dirty)
+                .count()
+                .to_string(),
+        );
+        Self {
+            hash: fnv1a_64(tree.as_bytes()),
+            values,
+        }
+    }
+}
+
+/// Per-subsystem hashes of the session at one moment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateSnapshot {
+    /// Position in the recording, assigned by [`StateSnapshots::record`]
+    pub index: u64,
+    pub day: u32,
+    pub reason: SnapshotReason,
+    pub subsystems: BTreeMap<Subsystem, SubsystemState>,
+}
+
+impl StateSnapshot {
+    /// An empty snapshot to add subsystems to
+    pub fn new(day: u32, reason: SnapshotReason) -> Self {
+        Self {
+            index: 0,
+            day,
+            reason,
+            subsystems: BTreeMap::new(),
+        }
+    }
+
+    /// Add or replace one subsystem
+    pub fn with(mut self, subsystem: Subsystem, state: SubsystemState) -> Self {
+        self.subsystems.insert(subsystem, state);
+        self
+    }
+
+    /// Hash every subsystem of `session` and the changed chunks of `map`
+    pub fn capture(
+        session: &RpgGameSession,
+        map: Option<&Map>,
+        day: u32,
+        reason: SnapshotReason,
+    ) -> Self {
+        let save = SaveData::from_session(session);
+        let player = json!({
+            "position": save.player.position,
+            "stats": save.player.stats,
+            "experience": save.player.experience,
+            "level": session.player.level(),
+            "movement_points": save.player.movement_points,
+        });
+        let inventory = json!({
+            "resources": save.player.resources,
+            "gear": save.player.gear,
+        });
+        let quests = json!({
+            "offers": session
+                .quest_board
+                .offers()
+                .iter()
+                .map(|posted| quest_value(&posted.quest))
+                .collect::<Vec<_>>(),
+            "active": session
+                .quest_board
+                .active()
+                .iter()
+                .map(|posted| quest_value(&posted.quest))
+                .collect::<Vec<_>>(),
+            "completed": session.completed_quests.iter().map(quest_value).collect::<Vec<_>>(),
+            "expedition": save.active_expedition,
+        });
+        let flags = SubsystemState {
+            hash: canonical_hash(&json!(save.flags)),
+            values: session
+                .flags
+                .listing()
+                .iter()
+                .filter_map(|line| line.split_once(" = "))
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        };
+
+        Self::new(day, reason)
+            .with(Subsystem::Player, SubsystemState::of_value(&player))
+            .with(Subsystem::Inventory, SubsystemState::of_value(&inventory))
+            .with(Subsystem::Base, SubsystemState::of_value(&json!(save.base)))
+            .with(
+                Subsystem::Map,
+                map.map_or_else(
+                    || SubsystemState::of_value(&Value::Null),
+                    |map| SubsystemState::of_map(map, &session.wrecks, &session.decals),
+                ),
+            )
+            .with(Subsystem::Quests, SubsystemState::of_value(&quests))
+            .with(Subsystem::Flags, flags)
+    }
+
+    /// Hash over every subsystem hash, in a fixed order
+    pub fn root_hash(&self) -> u64 {
+        let bytes: Vec<u8> = self
+            .subsystems
+            .values()
+            .flat_map(|state| state.hash.to_le_bytes())
+            .collect();
+        fnv1a_64(&bytes)
+    }
+
+    /// Short description, e.g. `#3 (day 2, rest)`
+    pub fn label(&self) -> String {
+        let reason = match self.reason {
+            SnapshotReason::Rest => "rest",
+            SnapshotReason::Requested => "requested",
+        };
+        format!("#{} (day {}, {})", self.index, self.day, reason)
+    }
+
+    /// Subsystems whose hashes differ from `other`, with their raw changes
+    pub fn diff(&self, other: &StateSnapshot) -> SnapshotDiff {
+        let missing = SubsystemState {
+            hash: 0,
+            values: BTreeMap::new(),
+        };
+        let changes = Subsystem::all()
+            .into_iter()
+            .filter_map(|subsystem| {
+                let before = self.subsystems.get(&subsystem).unwrap_or(&missing);
+                let after = other.subsystems.get(&subsystem).unwrap_or(&missing);
+                if before.hash == after.hash {
+                    return None;
+                }
+                let mut keys: Vec<&String> =
+                    before.values.keys().chain(after.values.keys()).collect();
+                keys.sort();
+                keys.dedup();
+                let deltas = keys
+                    .into_iter()
+                    .filter_map(|key| {
+                        let old = before.values.get(key);
+                        let new = after.values.get(key);
+                        (old != new).then(|| ValueDelta {
+                            key: key.clone(),
+                            before: old.cloned(),
+                            after: new.cloned(),
+                        })
+                    })
+                    .collect();
+                Some(SubsystemChange { subsystem, deltas })
+            })
+            .collect();
+        SnapshotDiff {
+            from: self.label(),
+            to: other.label(),
+            changes,
+        }
+    }
+}
+
+/// Quest fields that change during play
+fn quest_value(quest: &crate::domain::entities::Quest) -> Value {
+    json!({
+        "title": quest.title(),
+        "status": format!("{:?}", quest.status()),
+        "progress": quest.completion_percentage(),
+    })
+}
+
+/// One raw value that differs between two snapshots
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueDelta {
+    pub key: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// A subsystem whose hash differs between two snapshots
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubsystemChange {
+    pub subsystem: Subsystem,
+    /// Raw values that differ; empty when only unrecorded state changed
+    pub deltas: Vec<ValueDelta>,
+}
+
+/// What differs between two snapshots
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotDiff {
+    pub from: String,
+    pub to: String,
+    pub changes: Vec<SubsystemChange>,
+}
+
+impl SnapshotDiff {
+    /// Whether every subsystem hash matched
+    pub fn is_identical(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// A summary line, then one line per differing subsystem
+    pub fn lines(&self) -> Vec<String> {
+        if self.is_identical() {
+            return vec![format!("{} vs {}: identical", self.from, self.to)];
+        }
+        let names: Vec<&str> = self
+            .changes
+            .iter()
+            .map(|change| change.subsystem.name())
+            .collect();
+        let verb = if names.len() == 1 {
+            "differs"
+        } else {
+            "differ"
+        };
+        let mut lines = vec![format!(
+            "{} vs {}: {} {}",
+            self.from,
+            self.to,
+            names.join(", "),
+            verb
+        )];
+        for change in &self.changes {
+            if change.deltas.is_empty() {
+                lines.push(format!(
+                    "{}: hash differs, no raw values changed",
+                    change.subsystem.name()
+                ));
+                continue;
+            }
+            let deltas: Vec<String> = change
+                .deltas
+                .iter()
+                .map(|delta| {
+                    format!(
+                        "{} {} -> {}",
+                        delta.key,
+                        delta.before.as_deref().unwrap_or("none"),
+                        delta.after.as_deref().unwrap_or("none")
+                    )
+                })
+                .collect();
+            lines.push(format!(
+                "{}: {}",
+                change.subsystem.name(),
+                deltas.join("; ")
+            ));
+        }
+        lines
+    }
+}
+
+/// The most recent snapshots, oldest first
+#[derive(Resource, Debug, Clone, Default)]
+pub struct StateSnapshots {
+    snapshots: VecDeque<StateSnapshot>,
+    next_index: u64,
+}
+
+impl StateSnapshots {
+    /// Keep `snapshot`, forgetting the oldest once full; returns its index
+    pub fn record(&mut self, mut snapshot: StateSnapshot) -> u64 {
+        snapshot.index = self.next_index;
+        self.next_index += 1;
+        if self.snapshots.len() >= SNAPSHOT_CAPACITY {
+            self.snapshots.pop_front();
+        }
+        let index = snapshot.index;
+        self.snapshots.push_back(snapshot);
+        index
+    }
+
+    /// The snapshot recorded as `index`, if it is still kept
+    pub fn get(&self, index: u64) -> Option<&StateSnapshot> {
+        self.snapshots
+            .iter()
+            .find(|snapshot| snapshot.index == index)
+    }
+
+    /// One line per kept snapshot with its root hash
+    pub fn listing(&self) -> Vec<String> {
+        self.snapshots
+            .iter()
+            .map(|snapshot| format!("{} {:016x}", snapshot.label(), snapshot.root_hash()))
+            .collect()
+    }
+
+    /// Compare two kept snapshots
+    pub fn diff(&self, from: u64, to: u64) -> Result<SnapshotDiff, String> {
+        let find = |index: u64| {
+            self.get(index).ok_or_else(|| match self.snapshots.front() {
+                Some(oldest) if index < oldest.index => format!(
+                    "snapshot #{} is no longer kept (oldest is #{})",
+                    index, oldest.index
+                ),
+                _ => format!("no snapshot #{}", index),
+            })
+        };
+        Ok(find(from)?.diff(find(to)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::MapTile;
+    use crate::domain::services::{DecalKind, WreckOrigin};
+    use crate::domain::value_objects::terrain::{Elevation, TerrainType};
+    use crate::domain::value_objects::{EntityId, Position3D, ResourceType};
+
+    #[test]
+    fn hashes_ignore_field_and_insertion_order() {
+        let a: Value =
+            serde_json::from_str(r#"{"x": 1, "stats": {"str": 12, "dex": 9}, "tags": [1, 2]}"#)
+                .unwrap();
+        let b: Value =
+            serde_json::from_str(r#"{"tags": [1, 2], "stats": {"dex": 9, "str": 12}, "x": 1}"#)
+                .unwrap();
+        assert_eq!(canonical_hash(&a), canonical_hash(&b));
+        let reordered: Value =
+            serde_json::from_str(r#"{"x": 1, "stats": {"str": 12, "dex": 9}, "tags": [2, 1]}"#)
+                .unwrap();
+        assert_ne!(canonical_hash(&a), canonical_hash(&reordered));
+
+        let tile =
+            |explored| MapTile::new(TerrainType::Plains, Elevation::new(3).unwrap(), explored);
+        let coordinates = [(0, 0), (17, 2), (-1, 5), (3, 3)];
+        let mut forward = Map::new(EntityId::generate(), "a".to_string(), 7).unwrap();
+        for &(x, y) in &coordinates {
+            forward.set_tile(TileCoordinate::new(x, y, 0), tile(x != 3));
+        }
+        let mut backward = Map::new(EntityId::generate(), "b".to_string(), 7).unwrap();
+        for &(x, y) in coordinates.iter().rev() {
+            backward.set_tile(TileCoordinate::new(x, y, 0), tile(x != 3));
+        }
+        let clean = TileDecals::new();
+        let state = SubsystemState::of_map(&forward, &WreckField::new(), &clean);
+        assert_eq!(
+            state,
+            SubsystemState::of_map(&backward, &WreckField::new(), &clean)
+        );
+        assert_eq!(state.values["dirty_chunks"], "3");
+
+        // A wreck marks its chunk as changed by play
+        let mut wrecks = WreckField::new();
+        wrecks.leave(
+            Position3D::new(100, 100, 0),
+            WreckOrigin::Hostiles,
+            vec![(ResourceType::Metal, 5)],
+        );
+        let wrecked = SubsystemState::of_map(&forward, &wrecks, &clean);
+        assert_eq!(wrecked.values["dirty_chunks"], "4");
+        assert_ne!(wrecked.hash, state.hash);
+
+        // So does a decal
+        let mut decals = TileDecals::new();
+        decals.add(Position3D::new(-40, 0, 0), DecalKind::SurveyStake);
+        let staked = SubsystemState::of_map(&forward, &wrecks, &decals);
+        assert_eq!(staked.values["dirty_chunks"], "5");
+    }
+
+    #[test]
+    fn diff_names_the_subsystem_and_its_changed_values() {
+        let player = |points: u8| {
+            SubsystemState::of_value(&json!({"movement_points": points, "experience": 40}))
+        };
+        let flags = SubsystemState::of_value(&json!({"spared_scavenger": true}));
+        let before = StateSnapshot::new(2, SnapshotReason::Rest)
+            .with(Subsystem::Player, player(4))
+            .with(Subsystem::Flags, flags.clone());
+        let after = StateSnapshot::new(2, SnapshotReason::Requested)
+            .with(Subsystem::Player, player(6))
+            .with(Subsystem::Flags, flags);
+
+        let mut snapshots = StateSnapshots::default();
+        let first = snapshots.record(before);
+        let second = snapshots.record(after);
+        let diff = snapshots.diff(first, second).unwrap();
+        assert_eq!(
+            diff.lines(),
+            vec![
+                "#0 (day 2, rest) vs #1 (day 2, requested): player differs".to_string(),
+                "player: movement_points 4 -> 6".to_string(),
+            ]
+        );
+        assert!(snapshots.diff(first, first).unwrap().is_identical());
+    }
+
+    #[test]
+    fn only_the_latest_snapshots_are_kept() {
+        let mut snapshots = StateSnapshots::default();
+        for day in 0..(SNAPSHOT_CAPACITY as u32 + 5) {
+            snapshots.record(StateSnapshot::new(day, SnapshotReason::Rest));
+        }
+
+        assert_eq!(snapshots.listing().len(), SNAPSHOT_CAPACITY);
+        assert!(snapshots.get(4).is_none());
+        assert_eq!(snapshots.get(5).map(|snapshot| snapshot.day), Some(5));
+        assert_eq!(
+            snapshots.diff(4, 6).unwrap_err(),
+            "snapshot #4 is no longer kept (oldest is #5)"
+        );
+        assert_eq!(snapshots.diff(5, 99).unwrap_err(), "no snapshot #99");
+    }
+}
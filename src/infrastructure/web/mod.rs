@@ -2,7 +2,10 @@
 //!
 //! This module provides web-specific infrastructure for running the game
 //! in web browsers using WebAssembly. It handles browser APIs, web-specific
-//! optimizations, and WASM bindings.
+//! optimizations, and WASM bindings. The service worker handshake for
+//! offline play lives in [`offline`].
+
+pub mod offline;
 
 use crate::infrastructure::{InfrastructureError, InfrastructureResult};
 #[cfg(target_arch = "wasm32")]
@@ -14,6 +17,14 @@ use wasm_bindgen::JsValue;
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen_futures;
 
+/// Id of the live region screen readers announce from
+pub const LIVE_REGION_ID: &str = "space-looter-announcements";
+
+/// Keeps the live region out of sight but in the accessibility tree
+#[cfg(target_arch = "wasm32")]
+const LIVE_REGION_STYLE: &str = "position:absolute;width:1px;height:1px;margin:-1px;padding:0;\
+    overflow:hidden;clip:rect(0 0 0 0);white-space:nowrap;border:0";
+
 /// Web-specific configuration and utilities
 pub struct WebInfrastructure {
     /// Canvas element ID
@@ -197,6 +208,55 @@ pub mod utils {
         Ok(())
     }
 
+    /// Speak `text` through a visually hidden ARIA live region
+    ///
+    /// The region is created next to the canvas on first use. Critical text
+    /// interrupts the screen reader; everything else waits its turn.
+    #[cfg(target_arch = "wasm32")]
+    pub fn announce(text: &str, critical: bool) -> InfrastructureResult<()> {
+        let window = web_sys::window()
+            .ok_or_else(|| InfrastructureError::WebError("No window object".to_string()))?;
+
+        let document = window
+            .document()
+            .ok_or_else(|| InfrastructureError::WebError("No document object".to_string()))?;
+
+        let region = match document.get_element_by_id(LIVE_REGION_ID) {
+            Some(region) => region,
+            None => {
+                let region = document.create_element("div").map_err(|_| {
+                    InfrastructureError::WebError("Failed to create live region".to_string())
+                })?;
+                let body = document
+                    .body()
+                    .ok_or_else(|| InfrastructureError::WebError("No body element".to_string()))?;
+                for (name, value) in [
+                    ("id", LIVE_REGION_ID),
+                    ("role", "status"),
+                    ("aria-atomic", "true"),
+                    ("style", LIVE_REGION_STYLE),
+                ] {
+                    region.set_attribute(name, value).map_err(|_| {
+                        InfrastructureError::WebError("Failed to set up live region".to_string())
+                    })?;
+                }
+                body.append_child(&region).map_err(|_| {
+                    InfrastructureError::WebError("Failed to attach live region".to_string())
+                })?;
+                region
+            }
+        };
+
+        let politeness = if critical { "assertive" } else { "polite" };
+        region.set_attribute("aria-live", politeness).map_err(|_| {
+            InfrastructureError::WebError("Failed to update live region".to_string())
+        })?;
+        // Clearing first makes a repeated line count as a change
+        region.set_text_content(None);
+        region.set_text_content(Some(text));
+        Ok(())
+    }
+
     /// Show/hide loading indicator
     #[cfg(target_arch = "wasm32")]
     pub fn set_loading_state(loading: bool) -> InfrastructureResult<()> {
@@ -0,0 +1,286 @@
+//! Offline Support - The game's side of the service worker handshake
+//!
+//! The service worker (`web/sw.js`) precaches the page, the WASM binary and
+//! every file listed by [`asset_manifest_json`], which is built from the same
+//! `ASSET_MANIFEST` the startup integrity check uses. Once it is installed
+//! the page relays what the worker has cached through
+//! [`report_offline_cache`]; the game shows that status with the load
+//! diagnostics and compares the cached build against its own to spot a
+//! stale version. Native builds have no service worker, so registering does
+//! nothing and the cache is reported as unsupported.
+
+use crate::infrastructure::assets::{AssetEntry, ASSET_MANIFEST};
+use crate::infrastructure::build_info::{BuildInfo, UNKNOWN_BUILD_DETAIL};
+use crate::infrastructure::InfrastructureResult;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Script of the service worker, next to the page
+pub const SERVICE_WORKER_SCRIPT: &str = "./sw.js";
+
+/// Folder the bundled assets are served from, relative to the page
+const ASSETS_URL_PREFIX: &str = "assets/";
+
+/// Cache status reported by the page, taken on the next frame
+static REPORTED_STATUS: Mutex<Option<OfflineCacheStatus>> = Mutex::new(None);
+
+/// What the service worker should precache for this build
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetManifestExport {
+    /// Git hash of the build, names the cache
+    pub build: String,
+    /// Version string shown to the player
+    pub version: String,
+    /// Asset URLs relative to the page
+    pub assets: Vec<String>,
+}
+
+impl AssetManifestExport {
+    /// Export `manifest` for `info`
+    pub fn new(manifest: &[AssetEntry], info: &BuildInfo) -> Self {
+        Self {
+            build: info.git_hash.to_string(),
+            version: info.version_string(),
+            assets: manifest
+                .iter()
+                .map(|entry| format!("{}{}", ASSETS_URL_PREFIX, entry.path))
+                .collect(),
+        }
+    }
+}
+
+/// The asset manifest of the running build, as JSON for the service worker
+pub fn asset_manifest_json() -> String {
+    let export = AssetManifestExport::new(ASSET_MANIFEST, &BuildInfo::current());
+    serde_json::to_string(&export).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// What the service worker has cached
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OfflineCacheStatus {
+    /// No service worker here: native builds and older browsers
+    Unsupported,
+    /// Registered, but the worker has not reported yet
+    Pending,
+    /// The worker holds a cache for `build`
+    Cached { build: String, assets: u32 },
+    /// Registering or precaching failed
+    Failed(String),
+}
+
+impl OfflineCacheStatus {
+    /// Status before anything has been registered
+    pub fn initial() -> Self {
+        if cfg!(target_arch = "wasm32") {
+            OfflineCacheStatus::Pending
+        } else {
+            OfflineCacheStatus::Unsupported
+        }
+    }
+
+    /// One line for the load diagnostics
+    pub fn describe(&self) -> String {
+        match self {
+            OfflineCacheStatus::Unsupported => "offline cache: unsupported".to_string(),
+            OfflineCacheStatus::Pending => "offline cache: pending".to_string(),
+            OfflineCacheStatus::Cached { build, assets } => {
+                format!("offline cache: {} assets of build {}", assets, build)
+            }
+            OfflineCacheStatus::Failed(reason) => format!("offline cache: failed: {}", reason),
+        }
+    }
+
+    /// Whether the cached build is a different one than `running`
+    pub fn version_skew(&self, running: &BuildInfo) -> VersionSkew {
+        match self {
+            OfflineCacheStatus::Cached { build, .. } => {
+                detect_version_skew(running.git_hash, build)
+            }
+            _ => VersionSkew::Unknown,
+        }
+    }
+}
+
+impl Default for OfflineCacheStatus {
+    fn default() -> Self {
+        Self::initial()
+    }
+}
+
+/// How the running build compares to the cached one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionSkew {
+    /// Either build is unknown, so nothing can be said
+    Unknown,
+    Current,
+    /// The page should be reloaded to run one build throughout
+    Stale {
+        running: String,
+        cached: String,
+    },
+}
+
+/// Compare the running build hash with the one the service worker cached
+pub fn detect_version_skew(running: &str, cached: &str) -> VersionSkew {
+    let known = |hash: &str| !hash.trim().is_empty() && hash != UNKNOWN_BUILD_DETAIL;
+    if !known(running) || !known(cached) {
+        return VersionSkew::Unknown;
+    }
+    if running.trim() == cached.trim() {
+        VersionSkew::Current
+    } else {
+        VersionSkew::Stale {
+            running: running.trim().to_string(),
+            cached: cached.trim().to_string(),
+        }
+    }
+}
+
+/// Record what the page heard from the service worker
+pub fn report_offline_cache(status: OfflineCacheStatus) {
+    if let Ok(mut reported) = REPORTED_STATUS.lock() {
+        *reported = Some(status);
+    }
+}
+
+/// Take the status reported since the last call, if any
+pub fn take_offline_cache_report() -> Option<OfflineCacheStatus> {
+    REPORTED_STATUS
+        .lock()
+        .ok()
+        .and_then(|mut reported| reported.take())
+}
+
+/// Register the service worker; `false` where there is none to register
+#[cfg(target_arch = "wasm32")]
+pub fn register_service_worker() -> InfrastructureResult<bool> {
+    use crate::infrastructure::InfrastructureError;
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let window = web_sys::window()
+        .ok_or_else(|| InfrastructureError::WebError("No window object".to_string()))?;
+    let Some(container) =
+        js_sys::Reflect::get(&window.navigator(), &JsValue::from_str("serviceWorker"))
+            .ok()
+            .filter(|container| !container.is_undefined())
+    else {
+        return Ok(false);
+    };
+    let register = js_sys::Reflect::get(&container, &JsValue::from_str("register"))
+        .ok()
+        .and_then(|register| register.dyn_into::<js_sys::Function>().ok())
+        .ok_or_else(|| {
+            InfrastructureError::WebError("Service worker cannot be registered".to_string())
+        })?;
+    // The page hears back from the worker and relays its cache status
+    register
+        .call1(&container, &JsValue::from_str(SERVICE_WORKER_SCRIPT))
+        .map_err(|_| {
+            InfrastructureError::WebError("Service worker registration failed".to_string())
+        })?;
+    Ok(true)
+}
+
+/// Register the service worker; `false` where there is none to register
+#[cfg(not(target_arch = "wasm32"))]
+pub fn register_service_worker() -> InfrastructureResult<bool> {
+    Ok(false)
+}
+
+/// Reload the page to pick up the newest build
+#[cfg(target_arch = "wasm32")]
+pub fn reload_page() -> InfrastructureResult<()> {
+    use crate::infrastructure::InfrastructureError;
+
+    web_sys::window()
+        .ok_or_else(|| InfrastructureError::WebError("No window object".to_string()))?
+        .location()
+        .reload()
+        .map_err(|_| InfrastructureError::WebError("Page reload failed".to_string()))
+}
+
+/// Reload the page to pick up the newest build
+#[cfg(not(target_arch = "wasm32"))]
+pub fn reload_page() -> InfrastructureResult<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::assets::AssetKind;
+
+    fn build(git_hash: &'static str) -> BuildInfo {
+        BuildInfo::from_parts(
+            "0.2.0",
+            Some(git_hash),
+            None,
+            "wasm32-unknown".to_string(),
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn manifest_export_lists_every_integrity_entry() {
+        let manifest = [
+            AssetEntry::required("fonts/FiraSans-Regular.ttf", AssetKind::Font),
+            AssetEntry::optional("icons/gear.png", AssetKind::Image),
+        ];
+        let export = AssetManifestExport::new(&manifest, &build("1a2b3c4"));
+        assert_eq!(
+            serde_json::to_value(&export).unwrap(),
+            serde_json::json!({
+                "build": "1a2b3c4",
+                "version": "0.2.0 (1a2b3c4)",
+                "assets": ["assets/fonts/FiraSans-Regular.ttf", "assets/icons/gear.png"],
+            })
+        );
+
+        let current: AssetManifestExport = serde_json::from_str(&asset_manifest_json()).unwrap();
+        assert_eq!(current.assets.len(), ASSET_MANIFEST.len());
+    }
+
+    #[test]
+    fn a_different_cached_build_is_stale() {
+        assert_eq!(
+            detect_version_skew("1a2b3c4", "1a2b3c4"),
+            VersionSkew::Current
+        );
+        assert_eq!(
+            detect_version_skew("1a2b3c4", "9f8e7d6"),
+            VersionSkew::Stale {
+                running: "1a2b3c4".to_string(),
+                cached: "9f8e7d6".to_string(),
+            }
+        );
+        assert_eq!(
+            detect_version_skew(UNKNOWN_BUILD_DETAIL, "9f8e7d6"),
+            VersionSkew::Unknown
+        );
+        assert_eq!(detect_version_skew("1a2b3c4", ""), VersionSkew::Unknown);
+
+        let cached = OfflineCacheStatus::Cached {
+            build: "9f8e7d6".to_string(),
+            assets: 30,
+        };
+        assert!(matches!(
+            cached.version_skew(&build("1a2b3c4")),
+            VersionSkew::Stale { .. }
+        ));
+        assert_eq!(
+            OfflineCacheStatus::Pending.version_skew(&build("1a2b3c4")),
+            VersionSkew::Unknown
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn native_builds_have_no_service_worker() {
+        assert!(!register_service_worker().unwrap());
+        assert!(reload_page().is_ok());
+        assert_eq!(
+            OfflineCacheStatus::initial(),
+            OfflineCacheStatus::Unsupported
+        );
+    }
+}
@@ -13,11 +13,18 @@ pub mod domain;
 pub mod infrastructure;
 pub mod presentation;
 
+pub use presentation::embedding::{
+    SpaceLooterCorePlugin, SpaceLooterMapPlugin, SpaceLooterMovementPlugin,
+};
+
 use crate::domain::services::audio_service::AudioService;
 use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::infrastructure::random::streams::{ENCOUNTERS, EVENT_GENERATION, LOOT, MOVEMENT_ROLLS};
 use crate::infrastructure::time::TimeService as InfraTimeService;
+use crate::presentation::delayed_audio::PlaySequenceExt;
 
 use bevy::asset::{AssetMetaCheck, AssetPlugin};
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::time::{Timer, TimerMode};
 
@@ -87,8 +94,10 @@ fn create_headless_app() -> App {
 fn configure_headless_rpg_app(app: &mut App) {
     web_sys::console::log_1(&"⚙️ Configuring RPG systems...".into());
 
-    // Initialize RPG state management
-    app.init_state::<presentation::RpgAppState>();
+    // Initialize RPG state management; there is no menu, so play starts at once
+    app.insert_state(presentation::RpgAppState::Exploration)
+        .add_plugins(presentation::clocks::ClocksPlugin)
+        .add_event::<presentation::page_api::DiceRollRequested>();
 
     // Add domain services as resources (no rendering required)
     app.insert_resource(domain::services::TileMovementService::new())
@@ -131,22 +140,23 @@ fn configure_headless_rpg_app(app: &mut App) {
 
 /// System that runs the headless game and logs status to console
 #[cfg(target_arch = "wasm32")]
-fn headless_game_tick_system(time: Res<Time>) {
-    // Log game status every 10 seconds
-    static mut LAST_LOG: f32 = 0.0;
-    let current_time = time.elapsed_secs();
-
-    unsafe {
-        if current_time - LAST_LOG >= 10.0 {
-            web_sys::console::log_1(
-                &format!(
-                    "🎮 Game Status: Running for {:.1}s - RPG engine active, dice systems ready",
-                    current_time
-                )
-                .into(),
-            );
-            LAST_LOG = current_time;
-        }
+fn headless_game_tick_system(
+    wall: Res<presentation::clocks::WallClock>,
+    config: Res<presentation::clocks::GameplayConfig>,
+    mut status_timer: ResMut<presentation::clocks::HeadlessStatusTimer>,
+) {
+    if presentation::clocks::tick_every(
+        &mut status_timer.0,
+        wall.delta(),
+        config.status_log_interval_secs,
+    ) {
+        web_sys::console::log_1(
+            &format!(
+                "🎮 Game Status: Running for {:.1}s - RPG engine active, dice systems ready",
+                wall.elapsed_secs()
+            )
+            .into(),
+        );
     }
 }
 
@@ -156,14 +166,16 @@ pub fn create_app() -> App {
 
     // Configure for native with RPG-appropriate resolution
     #[cfg(not(target_arch = "wasm32"))]
-    app.add_plugins(DefaultPlugins.set(WindowPlugin {
-        primary_window: Some(Window {
-            title: "Space Looter - 3D Isometric RPG".into(),
-            resolution: (1200.0, 800.0).into(), // Wider for RPG UI
+    {
+        // Open the window at the saved size and mode to avoid a resize flash
+        let display = infrastructure::settings::peek_display_settings(std::path::Path::new(
+            infrastructure::settings::SETTINGS_FILE_PATH,
+        ));
+        app.add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(presentation::display_mode::primary_window(&display)),
             ..default()
-        }),
-        ..default()
-    }));
+        }));
+    }
 
     // For WASM, we use the web-compatible version with proper canvas setup and disabled meta files
     #[cfg(target_arch = "wasm32")]
@@ -172,7 +184,7 @@ pub fn create_app() -> App {
             DefaultPlugins
                 .set(WindowPlugin {
                     primary_window: Some(Window {
-                        title: "Space Looter - 3D Isometric RPG".into(),
+                        title: presentation::display_mode::WINDOW_TITLE.into(),
                         canvas: Some("#bevy".into()),
                         fit_canvas_to_parent: true,
                         prevent_default_event_handling: false,
@@ -199,16 +211,84 @@ fn configure_rpg_app(app: &mut App) {
     // Set RPG-appropriate background color (dark space theme)
     app.insert_resource(ClearColor(Color::srgb(0.05, 0.05, 0.1)));
 
+    // Load persisted settings before the plugins that default the same resources
+    app.add_plugins(infrastructure::settings::SettingsPlugin::default());
+
     // Add core RPG systems
     app.add_plugins((
         infrastructure::bevy::font_service::FontPlugin,
         presentation::game_state::RpgStatePlugin,
         presentation::game_ui::GameUIPlugin,
         presentation::game_log_integration::GameLogIntegrationPlugin,
-        presentation::map_renderer::MapRendererPlugin,
+        SpaceLooterMapPlugin { render: true },
         presentation::rendering::RenderingPlugin,
         presentation::audio_integration::AudioEventIntegrationPlugin,
         presentation::game_event_logger::GameEventLoggerPlugin,
+        presentation::expedition::ExpeditionPlugin,
+        presentation::delving::DelvingPlugin,
+        presentation::ghost_trail::GhostTrailPlugin,
+        presentation::rescue::RescuePlugin,
+        presentation::fauna::FaunaPlugin,
+        presentation::world_tick::WorldTickPlugin,
+        (
+            presentation::low_points_guard::LowPointsGuardPlugin,
+            presentation::refinery::RefineryPlugin,
+            presentation::anomaly_storm::AnomalyStormPlugin,
+            presentation::inventory::InventoryPlugin,
+            presentation::camera_hints::CameraHintPlugin,
+            presentation::blitz::BlitzPlugin,
+            presentation::scout_probe::ScoutProbePlugin,
+            presentation::reputation::ReputationPlugin,
+            presentation::frame_limiter::FrameLimiterPlugin,
+            presentation::tile_staleness::TileStalenessPlugin,
+            presentation::bug_report::BugReportPlugin,
+            presentation::party::PartyPlugin,
+            presentation::asset_integrity::AssetIntegrityPlugin,
+            presentation::about::AboutPlugin,
+            (
+                presentation::offline::OfflinePlugin,
+                presentation::codex::CodexPlugin,
+                presentation::display_mode::DisplayModePlugin,
+                presentation::base_report::BaseReportPlugin,
+                presentation::day_night::DayNightPlugin,
+                presentation::run_end::RunEndPlugin,
+                presentation::odds_preview::OddsPreviewPlugin,
+                infrastructure::packs::DataPacksPlugin,
+                presentation::share_code::ShareCodePlugin,
+                presentation::clocks::ClocksPlugin,
+                presentation::wrecks::WrecksPlugin,
+                presentation::hud_layout::HudLayoutPlugin,
+                presentation::dawn_report::DawnReportPlugin,
+                presentation::transient_pool::TransientPoolPlugin,
+                (
+                    presentation::play_heatmap::PlayHeatmapPlugin,
+                    presentation::map_export::MapExportPlugin,
+                    presentation::field_trade::FieldTradePlugin,
+                    presentation::seasons::SeasonPlugin,
+                    presentation::scenarios::ScenarioPlugin,
+                    presentation::emergency_recall::EmergencyRecallPlugin,
+                    presentation::move_undo::MoveUndoPlugin,
+                    presentation::quest_board::QuestBoardPlugin,
+                    presentation::encounter_music::EncounterMusicPlugin,
+                    presentation::warning_layer::WarningLayerPlugin,
+                    presentation::decals::DecalsPlugin,
+                    (
+                        presentation::world_rebuild::WorldRebuildPlugin,
+                        presentation::quick_save::QuickSavePlugin,
+                        presentation::base_construction::BaseConstructionPlugin,
+                        presentation::encounter::EncounterPlugin,
+                        presentation::audio_settings::AudioSettingsPlugin,
+                        presentation::page_api::PageApiPlugin,
+                        presentation::minimap::MinimapPlugin,
+                        presentation::controls::ControlsPlugin,
+                    ),
+                    infrastructure::random::RngStreamsPlugin,
+                    SpaceLooterCorePlugin,
+                    // Steps are answered by the exploration system below
+                    SpaceLooterMovementPlugin { plain_steps: false },
+                ),
+            ),
+        ),
     ));
 
     // Register audio events
@@ -216,19 +296,11 @@ fn configure_rpg_app(app: &mut App) {
         .add_event::<presentation::game_event_logger::RestCompletedEvent>()
         .add_event::<presentation::game_event_logger::ResourceChangedEvent>()
         .add_event::<presentation::game_event_logger::DiscoveryEvent>()
-        .add_event::<presentation::game_event_logger::GameSystemEvent>();
+        .add_event::<presentation::game_event_logger::GameSystemEvent>()
+        .add_event::<presentation::game_event_logger::PlayerChangedEvent>();
 
-    // Add RPG-specific resources
-    app.insert_resource(infrastructure::bevy::resources::PlayerResource::new())
-        .insert_resource(infrastructure::bevy::resources::BaseResource::new())
-        .insert_resource(infrastructure::bevy::resources::MapResource::new())
-        .insert_resource(infrastructure::bevy::resources::GameStatsResource::new())
-        .insert_resource(infrastructure::bevy::resources::GameTimerResource::new())
-        .insert_resource(MusicControl::default());
-
-    // Add domain services as resources
-    app.insert_resource(domain::services::TileMovementService::new())
-        .insert_resource(domain::services::RestingService::new());
+    // RPG resources and domain services come with the core plugin
+    app.insert_resource(MusicControl::default());
 
     // Initialize empty RpgGameSession - will be populated when game starts
     let dummy_player = domain::Player::create_new_character(
@@ -245,6 +317,20 @@ fn configure_rpg_app(app: &mut App) {
     let rpg_session = presentation::game_state::RpgGameSession::new(dummy_player, dummy_base);
     app.insert_resource(rpg_session);
 
+    // Optional scripted control channel for automated playtesting
+    if let Some(port) = infrastructure::control::control_port_from_args(std::env::args()) {
+        app.add_plugins(infrastructure::control::ControlPlugin { port });
+    }
+
+    // Screen reader announcements, mirrored to stdout with --narrate
+    app.add_plugins(presentation::announcements::AnnouncementPlugin {
+        narrate: presentation::announcements::narrate_from_args(std::env::args()),
+    });
+
+    // Entity inspection gizmos for chasing rendering mismatches
+    #[cfg(feature = "dev-tools")]
+    app.add_plugins(presentation::inspect::InspectPlugin);
+
     // Add startup systems for RPG initialization
     app.add_systems(
         Startup,
@@ -254,14 +340,15 @@ fn configure_rpg_app(app: &mut App) {
     // Add core RPG update systems
     // Add systems individually to avoid complex tuple signature issues
     app.add_systems(Update, rpg_turn_management_system);
-    app.add_systems(Update, rpg_exploration_system);
+    // Moves and rests applied here are what the world tick reports
+    app.add_systems(
+        Update,
+        rpg_exploration_system.before(presentation::world_tick::WorldTickSet::Emit),
+    );
     app.add_systems(Update, rpg_dice_mechanics_system);
     app.add_systems(Update, handle_window_resize_system);
     app.add_systems(Update, rpg_state_transition_system);
 
-    // Add dice sound timer system
-    app.add_systems(Update, dice_sound_timer_system);
-
     // Add RPG-specific system sets for better organization
     app.configure_sets(
         Update,
@@ -290,32 +377,6 @@ pub enum RpgSystemSet {
     UI,
 }
 
-/// Component for delayed dice sound playback
-#[derive(Component)]
-struct DiceSoundTimer {
-    timer: Timer,
-    audio_handle: Handle<AudioSource>,
-}
-
-/// System to handle delayed dice sound playback
-fn dice_sound_timer_system(
-    mut commands: Commands,
-    time: Res<Time>,
-    mut query: Query<(Entity, &mut DiceSoundTimer)>,
-) {
-    for (entity, mut dice_timer) in query.iter_mut() {
-        dice_timer.timer.tick(time.delta());
-
-        if dice_timer.timer.just_finished() {
-            // Play the dice sound
-            commands.spawn(AudioPlayer::new(dice_timer.audio_handle.clone()));
-
-            // Remove the timer component
-            commands.entity(entity).despawn();
-        }
-    }
-}
-
 /// Setup cameras for RPG (2D tile view)
 fn setup_rpg_camera_system(_commands: Commands) {
     info!("Setting up 2D tile-based RPG camera");
@@ -326,66 +387,195 @@ fn setup_rpg_camera_system(_commands: Commands) {
     info!("🎲 Controls: WASD/Arrows=Move, SPACE=Roll Dice, B=Base, Q=Quests, I=Inventory");
 }
 
+/// Run settings and run state set up alongside the player, base and map
+#[derive(SystemParam)]
+struct RunStartParams<'w> {
+    base_events: EventWriter<'w, presentation::base_visuals::BaseChanged>,
+    blitz_settings: Option<Res<'w, infrastructure::settings::BlitzSettings>>,
+    mutator_settings: Option<Res<'w, infrastructure::settings::MutatorSettings>>,
+    rpg_session: Option<ResMut<'w, presentation::game_state::RpgGameSession>>,
+    party_settings: Option<Res<'w, infrastructure::settings::PartySettings>>,
+    party_resource: Option<ResMut<'w, infrastructure::bevy::resources::PartyResource>>,
+    run_settings: Option<Res<'w, infrastructure::settings::RunSettings>>,
+    scenarios: Option<Res<'w, domain::services::ScenarioTable>>,
+    starting_character: Option<Res<'w, presentation::scenarios::StartingCharacter>>,
+}
+
 /// Initialize the RPG world with starting state
 fn initialize_rpg_world_system(
     mut player_resource: ResMut<infrastructure::bevy::resources::PlayerResource>,
     mut base_resource: ResMut<infrastructure::bevy::resources::BaseResource>,
     mut map_resource: ResMut<infrastructure::bevy::resources::MapResource>,
     mut game_stats: ResMut<infrastructure::bevy::resources::GameStatsResource>,
+    run_start: RunStartParams,
 ) {
-    info!("Initializing RPG world state");
+    let RunStartParams {
+        mut base_events,
+        blitz_settings,
+        mutator_settings,
+        rpg_session,
+        party_settings,
+        party_resource,
+        run_settings,
+        scenarios,
+        starting_character,
+    } = run_start;
 
-    // Create starting player
-    let starting_position = domain::Position3D::origin();
-    let starting_stats = domain::PlayerStats::new(12, 10, 8, 14, 11, 9)
-        .expect("Failed to create starting player stats");
-
-    if let Err(e) = player_resource.create_player(
-        "player_001".to_string(),
-        "Space Looter".to_string(),
-        starting_position,
-        starting_stats,
-    ) {
-        error!("Failed to create starting player: {}", e);
-    }
+    info!("Initializing RPG world state");
 
-    // Create starting base
-    let base_position = domain::Position3D::new(0, 0, 0);
-    if let Err(e) = base_resource.create_base("Central Command".to_string(), base_position) {
-        error!("Failed to create starting base: {}", e);
+    // The run's rules are fixed here; later settings changes wait for the next run
+    let mutators = mutator_settings
+        .map(|settings| settings.selected.clone())
+        .unwrap_or_default();
+    let modifiers = domain::services::ModifierStack::new(
+        domain::entities::game::DifficultyLevel::Normal,
+        mutators.clone(),
+    );
+    if !mutators.is_empty() {
+        info!("🧬 Run mutators: {}", mutators.names());
     }
+    let scenario_id = run_settings
+        .map(|settings| settings.scenario.clone())
+        .unwrap_or_default();
+    let scenario = scenarios
+        .map(|table| table.resolve(&scenario_id))
+        .unwrap_or_else(domain::services::Scenario::standard_drop);
+    let character = starting_character
+        .map(|character| character.clone())
+        .unwrap_or_default();
 
     // Initialize game statistics
     game_stats.reset();
+    game_stats.blitz = blitz_settings.is_some_and(|settings| settings.enabled);
+    game_stats.modifiers = modifiers.clone();
+    game_stats.scenario = scenario.clone();
 
-    // Force initial map generation around starting position
-    if let Some(player_position) = player_resource.player_position() {
-        let _initial_map = map_resource.get_or_create_map(player_position);
-        info!(
-            "🗺️ Initial map generated for position: {:?}",
-            player_position
-        );
+    // Force initial map generation around the starting position
+    let starting_position = domain::Position3D::origin();
+    let _initial_map = map_resource.get_or_create_map(starting_position);
+    info!(
+        "🗺️ Initial map generated for position: {:?}",
+        starting_position
+    );
+
+    // Starting player, spawn area, scenario and base, then the mutators
+    if let Some(mut session) = rpg_session {
+        session.mutators = mutators;
+        match presentation::scenarios::set_up_run_start(
+            &character,
+            &scenario,
+            &modifiers,
+            &mut player_resource,
+            &mut base_resource,
+            &mut map_resource,
+            &mut session,
+        ) {
+            Ok(start) => {
+                if scenario.id != domain::services::STANDARD_DROP {
+                    info!(
+                        "🧭 Starting scenario: {} - base at {:?}",
+                        scenario.name, start.base_position
+                    );
+                }
+                base_events.write(presentation::base_visuals::BaseChanged::new(
+                    presentation::base_visuals::BaseChange::Founded,
+                ));
+            }
+            Err(e) => error!("{}", e),
+        }
+    }
+
+    // A hot-seat run adds a second character starting on the same tile
+    if let Some(mut party_resource) = party_resource {
+        let party = party_settings
+            .filter(|settings| settings.hot_seat)
+            .and_then(|settings| {
+                match domain::Player::new(
+                    domain::EntityId::generate(),
+                    settings.partner_name.clone(),
+                    starting_position,
+                    character.stats,
+                ) {
+                    Ok(mut partner) => {
+                        let max_movement = modifiers.max_movement(partner.max_movement_points());
+                        partner.set_max_movement_points(max_movement);
+                        Some(domain::services::Party::new(&character.name, partner))
+                    }
+                    Err(e) => {
+                        error!("Failed to create the second character: {}", e);
+                        None
+                    }
+                }
+            });
+        if let Some(party) = &party {
+            info!(
+                "👥 Pass-and-play run: {} and {} take turns",
+                character.name,
+                party.waiting_name()
+            );
+        }
+        party_resource.set_party(party);
     }
 
     info!("RPG world initialization complete");
 }
 
-/// Core RPG turn management system
+/// Core RPG turn management system; turns only pass while the run is played
 fn rpg_turn_management_system(
     mut game_timer: ResMut<infrastructure::bevy::resources::GameTimerResource>,
-    time: Res<Time>,
+    sim: Res<presentation::clocks::SimClock>,
 ) {
-    game_timer.update(time.delta_secs());
+    if sim.is_running() {
+        game_timer.resume();
+    } else if game_timer.phase() != domain::GamePhase::Paused {
+        game_timer.pause();
+    }
+    game_timer.update(sim.delta_secs());
 }
 
 /// RPG tile-based exploration with dice roll events
 /// RPG exploration and movement system with dice mechanics
+#[allow(clippy::type_complexity)]
 pub fn rpg_exploration_system(
     mut player_resource: ResMut<infrastructure::bevy::resources::PlayerResource>,
     mut map_resource: ResMut<infrastructure::bevy::resources::MapResource>,
     mut game_stats: ResMut<infrastructure::bevy::resources::GameStatsResource>,
-    tile_movement_service: Res<domain::services::TileMovementService>,
-    resting_service: Res<domain::services::RestingService>,
+    (
+        tile_movement_service,
+        resting_service,
+        audio_settings,
+        mut sfx,
+        timed_objective,
+        mut result_applied_events,
+        world_hazards,
+        mut hostile_contact,
+        party,
+        mut codex_unlocks,
+        mut trader_contact,
+        move_undo,
+        rng_streams,
+        config,
+        mut mystery_contact,
+        calendar,
+    ): (
+        Res<domain::services::TileMovementService>,
+        Res<domain::services::RestingService>,
+        Res<presentation::audio_integration::GlobalAudioSettings>,
+        ResMut<presentation::audio_integration::SfxArbiter>,
+        Res<domain::services::TimedObjective>,
+        EventWriter<presentation::movement::MovementResultApplied>,
+        Res<domain::services::WorldHazards>,
+        ResMut<presentation::reputation::HostileContact>,
+        Option<Res<infrastructure::bevy::resources::PartyResource>>,
+        EventWriter<presentation::codex::CodexUnlockEvent>,
+        ResMut<presentation::field_trade::TraderContact>,
+        Option<Res<domain::services::MoveUndo>>,
+        ResMut<infrastructure::RngStreams>,
+        Res<presentation::clocks::GameplayConfig>,
+        ResMut<presentation::encounter::MysteryContact>,
+        Res<domain::services::GameCalendar>,
+    ),
+    mut rpg_session: ResMut<presentation::game_state::RpgGameSession>,
     mut movement_events: EventReader<crate::presentation::movement::ExecuteRpgMovement>,
     mut movement_completed_events: EventReader<crate::presentation::movement::MovementCompleted>,
     mut resting_events: EventReader<crate::presentation::movement::RestingTriggered>,
@@ -394,12 +584,7 @@ pub fn rpg_exploration_system(
         With<crate::presentation::map_renderer::PlayerMarker>,
     >,
     mut pending_movement: Local<Option<domain::Position3D>>,
-    mut pending_rpg_results: Local<
-        Vec<(
-            domain::Position3D,
-            domain::services::tile_movement::MovementResult,
-        )>,
-    >,
+    mut pending_rpg_results: ResMut<presentation::movement::PendingRpgResults>,
     mut rest_timer: Local<Option<Timer>>,
     time: Res<Time>,
     mut game_log: ResMut<GameLogService>,
@@ -418,7 +603,14 @@ pub fn rpg_exploration_system(
             // Play rest complete audio
             if let Some(audio_assets) = &audio_assets {
                 if let Some(rest_handle) = &audio_assets.rest_complete {
-                    commands.spawn(AudioPlayer::new(rest_handle.clone()));
+                    presentation::audio_integration::play_audio(
+                        &mut sfx,
+                        &audio_settings,
+                        presentation::audio_integration::AudioCategory::Sfx,
+                        presentation::audio_integration::SfxPriority::Effect,
+                        rest_handle,
+                        PlaybackSettings::ONCE,
+                    );
                 }
             }
             *rest_timer = None;
@@ -430,16 +622,29 @@ pub fn rpg_exploration_system(
 
     // Handle resting events (when movement points reach zero)
     for resting_event in resting_events.read() {
-        if let Some(mut player) = player_resource.get_player_mut() {
-            info!(
-                "😴 Processing automatic rest at {:?}",
-                resting_event.player_position
-            );
+        if player_resource.has_player() {
+            let mut rest_position = resting_event.player_position;
+
+            // Ruins have no safe place to rest: climb back out to make camp
+            if let Some(site) = map_resource.exit_interior() {
+                rest_position = domain::Position3D::new(site.x, site.y, site.z);
+                player_resource.set_position(rest_position);
+                if let Ok((mut smooth_movement, _)) = player_query.single_mut() {
+                    smooth_movement.reset_to_position(rest_position);
+                }
+                game_log.log_message(
+                    "Too exhausted to go on, you climb out of the ruins to make camp".to_string(),
+                    GameLogType::Rest,
+                );
+            }
+
+            info!("😴 Processing automatic rest at {:?}", rest_position);
 
             // Process rest cycle using the resting service
-            match resting_service.process_rest_cycle(&mut player, resting_event.player_position) {
+            match player_resource.rest(&resting_service, rest_position, &game_stats.modifiers) {
                 Ok(rest_result) => {
                     info!("🌅 Rest completed: {}", rest_result.description);
+                    game_stats.record_rest();
                     info!(
                         "⚡ Movement points restored: {} (was {})",
                         rest_result.movement_points_restored,
@@ -461,11 +666,30 @@ pub fn rpg_exploration_system(
                             format!("Resources gained: {}", resource_types.join(", "));
                         game_log.log_message(resource_msg, crate::GameLogType::Resources);
                     }
+                    if rest_result.health_restored > 0 {
+                        if let Some(player) = player_resource.get_player() {
+                            game_log.log_message(
+                                format!(
+                                    "🔧 Overnight repairs mend {} hull - {}",
+                                    rest_result.health_restored,
+                                    player.health()
+                                ),
+                                crate::GameLogType::Rest,
+                            );
+                        }
+                    }
 
                     // Play rest complete audio
                     if let Some(audio_assets) = &audio_assets {
                         if let Some(rest_handle) = &audio_assets.rest_complete {
-                            commands.spawn(AudioPlayer::new(rest_handle.clone()));
+                            presentation::audio_integration::play_audio(
+                                &mut sfx,
+                                &audio_settings,
+                                presentation::audio_integration::AudioCategory::Sfx,
+                                presentation::audio_integration::SfxPriority::Effect,
+                                rest_handle,
+                                PlaybackSettings::ONCE,
+                            );
                         }
                     }
 
@@ -493,6 +717,11 @@ pub fn rpg_exploration_system(
     let current_position = player_resource.player_position().unwrap_or_default();
     let mut target_position = current_position;
 
+    // In a party the rest ends the day; the next character has no pending move
+    if party.is_some_and(|party| party.is_active()) {
+        *pending_movement = None;
+    }
+
     // Handle pending movement first (after resting)
     if let Some(pending_pos) = pending_movement.take() {
         target_position = pending_pos;
@@ -520,28 +749,67 @@ pub fn rpg_exploration_system(
     for completion_event in movement_completed_events.read() {
         // Find and apply the corresponding RPG movement result
         if let Some(index) = pending_rpg_results
+            .results
             .iter()
-            .position(|(pos, _)| *pos == completion_event.final_position)
+            .position(|pending| pending.target == completion_event.final_position)
         {
-            let (final_pos, movement_result) = pending_rpg_results.remove(index);
+            let presentation::movement::PendingRpgResult {
+                target: final_pos,
+                result: movement_result,
+                ..
+            } = pending_rpg_results.results.remove(index);
+
+            // Results of movements that never completed are stale: discard them
+            // together with any dice sound still waiting to play, and give back
+            // the points spent on them
+            for stale in pending_rpg_results.results.drain(..) {
+                info!(
+                    "🎮 RPG System: Discarding stale movement result to {:?}",
+                    stale.target
+                );
+                player_resource.refund_movement_points(stale.result.movement_cost);
+                if let Ok(mut sound) = commands.get_entity(stale.dice_sound) {
+                    sound.try_despawn();
+                }
+            }
 
             info!(
                 "🎮 RPG System: Applying delayed movement result to {:?}",
                 final_pos
             );
 
-            // Now actually update the player position
-            if let Err(e) = player_resource.move_player(final_pos, 1) {
-                warn!("Failed to update player position after animation: {:?}", e);
-            } else {
-                // Apply the movement result effects
-                apply_movement_result(
-                    &movement_result,
-                    &mut player_resource,
-                    &mut game_stats,
-                    &mut game_log,
+            // Points were spent when the move was validated; only the position changes now
+            player_resource.set_position(final_pos);
+            apply_movement_result(
+                &movement_result,
+                &mut player_resource,
+                &mut game_stats,
+                &mut game_log,
+                &mut hostile_contact,
+                &mut trader_contact,
+                &mut rpg_session.flags,
+                &mut mystery_contact,
+                &config.movement_economy,
+                &map_resource,
+                &rng_streams,
+            );
+            if let Some(event) = &movement_result.triggered_event {
+                codex_unlocks.write(presentation::codex::CodexUnlockEvent::new(
+                    domain::services::event_entry_id(event.event_type()),
+                ));
+                rpg_session.heatmap.record(
+                    presentation::play_heatmap::heat_position(&map_resource, final_pos),
+                    domain::services::HeatMetric::Events,
+                    1,
                 );
             }
+            result_applied_events.write(presentation::movement::MovementResultApplied {
+                final_position: final_pos,
+                event_title: movement_result
+                    .triggered_event
+                    .as_ref()
+                    .map(|event| event.title().to_string()),
+            });
         }
     }
 
@@ -550,29 +818,95 @@ pub fn rpg_exploration_system(
         if let Some(player) = player_resource.get_player() {
             let player_level = player.level();
 
+            // Anomaly storms only sweep the overworld
+            let storms_apply = !map_resource.is_in_interior();
+
             // Get or generate map around player position
             let map = map_resource.get_or_create_map_mut(current_position);
 
-            // Attempt tile movement with dice roll - but DON'T update player position yet
-            match tile_movement_service.attempt_movement(
-                &player,
+            // Fortune's Favor, unanswered distress signals, storms, standing and
+            // earlier choices, exactly as the odds preview sees them
+            let presentation::odds_preview::MoveInputs {
+                assist,
+                roll_modifier,
+                mut conditions,
+            } = presentation::odds_preview::MoveInputs::for_move(
+                &rpg_session,
+                &timed_objective,
+                &world_hazards,
+                &game_stats.modifiers,
+                storms_apply,
                 target_position,
-                map,
-                player_level,
-            ) {
-                Ok(movement_result) => {
-                    // Schedule dice roll sound with delay
-                    if let Some(audio_assets) = &audio_assets {
-                        if let Some(dice_handle) = &audio_assets.dice_roll {
-                            commands.spawn((DiceSoundTimer {
-                                timer: Timer::from_seconds(
-                                    crate::domain::constants::DICE_SOUND_DELAY_MS as f32 / 1000.0,
-                                    TimerMode::Once,
-                                ),
-                                audio_handle: dice_handle.clone(),
-                            },));
-                        }
+            );
+            // Night brings out hazards and raiders; the odds of an event stay
+            // the same, so the preview does not need it
+            conditions.phase = calendar.phase();
+
+            // Attempt tile movement with dice roll - but DON'T update player position yet.
+            // The cost is spent in the same step that validated it, so nothing can
+            // change the points in between
+            match tile_movement_service
+                .attempt_movement_with_rngs(
+                    player,
+                    target_position,
+                    map,
+                    player_level,
+                    &roll_modifier,
+                    conditions,
+                    &mut rng_streams.stream(MOVEMENT_ROLLS),
+                    &mut rng_streams.stream(EVENT_GENERATION),
+                )
+                .and_then(|movement_result| {
+                    player_resource.try_spend_movement_points(movement_result.movement_cost)?;
+                    Ok(movement_result)
+                }) {
+                Ok(mut movement_result) => {
+                    if !assist.is_zero() {
+                        game_stats.record_assisted_roll();
+                    }
+                    rpg_session
+                        .fortune_favor
+                        .record_outcome(movement_result.dice_result.roll_outcome());
+
+                    // A storm may strike when no other event did
+                    if storms_apply && movement_result.triggered_event.is_none() {
+                        use rand::Rng;
+                        movement_result.triggered_event = world_hazards.storm_hazard(
+                            target_position,
+                            rng_streams.stream(EVENT_GENERATION).gen_range(1..=100),
+                        );
+                    }
+
+                    // Opening moves of a new session never turn hostile
+                    if rpg_session.event_grace.is_active() {
+                        movement_result.triggered_event = rpg_session
+                            .event_grace
+                            .filter_event(movement_result.triggered_event.take());
+                        info!(
+                            "🛡️ New session grace: {} protected moves left",
+                            rpg_session.event_grace.moves_remaining()
+                        );
                     }
+
+                    // A tile whose move was taken back never rolls another event
+                    if move_undo
+                        .as_ref()
+                        .is_some_and(|undo| undo.is_event_consumed(target_position))
+                    {
+                        movement_result.triggered_event = None;
+                    }
+
+                    // Schedule dice roll sound with delay; dropped if the result is discarded
+                    let dice_sound = commands
+                        .play_sequence(&[presentation::delayed_audio::AudioStep::new(
+                            crate::domain::constants::DICE_SOUND_DELAY_MS as f32 / 1000.0,
+                            presentation::delayed_audio::AudioKey::DiceRoll,
+                            1.0,
+                        )])
+                        .insert(presentation::delayed_audio::AudioStateScope(
+                            presentation::RpgAppState::Exploration,
+                        ))
+                        .id();
                     // Log dice roll result - console only (debug)
                     info!("🎲 {}", movement_result.dice_result.description());
                     info!(
@@ -624,7 +958,13 @@ pub fn rpg_exploration_system(
 
                     // Store the movement result to be applied when animation completes
                     info!("🎮 RPG System: Storing movement result for delayed execution");
-                    pending_rpg_results.push((target_position, movement_result));
+                    pending_rpg_results
+                        .results
+                        .push(presentation::movement::PendingRpgResult {
+                            target: target_position,
+                            result: movement_result,
+                            dice_sound,
+                        });
 
                     // Don't update player position immediately - wait for animation to complete
                     info!("✅ Movement validation passed, waiting for animation to complete");
@@ -638,7 +978,14 @@ pub fn rpg_exploration_system(
                     // Play blocked movement audio
                     if let Some(audio_assets) = &audio_assets {
                         if let Some(ui_handle) = &audio_assets.ui_click {
-                            commands.spawn(AudioPlayer::new(ui_handle.clone()));
+                            presentation::audio_integration::play_audio(
+                                &mut sfx,
+                                &audio_settings,
+                                presentation::audio_integration::AudioCategory::Ui,
+                                presentation::audio_integration::SfxPriority::Ui,
+                                ui_handle,
+                                PlaybackSettings::ONCE,
+                            );
                         }
                     }
 
@@ -667,7 +1014,14 @@ pub fn rpg_exploration_system(
                             // Play exhausted audio for no movement points
                             if let Some(audio_assets) = &audio_assets {
                                 if let Some(ui_handle) = &audio_assets.ui_click {
-                                    commands.spawn(AudioPlayer::new(ui_handle.clone()));
+                                    presentation::audio_integration::play_audio(
+                                        &mut sfx,
+                                        &audio_settings,
+                                        presentation::audio_integration::AudioCategory::Ui,
+                                        presentation::audio_integration::SfxPriority::Ui,
+                                        ui_handle,
+                                        PlaybackSettings::ONCE,
+                                    );
                                 }
                             }
 
@@ -701,8 +1055,14 @@ pub fn rpg_exploration_system(
                                 ];
 
                                 let can_move_anywhere = adjacent_positions.iter().any(|pos| {
-                                    let movement_cost = map.movement_cost(pos);
-                                    player.movement_points() >= movement_cost
+                                    let mut conditions = if storms_apply {
+                                        world_hazards.conditions_at(*pos)
+                                    } else {
+                                        domain::services::MovementConditions::default()
+                                    };
+                                    conditions.open_terrain_surcharge =
+                                        game_stats.modifiers.open_terrain_surcharge();
+                                    player.movement_points() >= conditions.step_cost(map, pos)
                                 });
 
                                 if !can_move_anywhere {
@@ -716,13 +1076,15 @@ pub fn rpg_exploration_system(
                                     *pending_movement = Some(target_position);
 
                                     // Process rest cycle
-                                    if let Some(player_mut) = player_resource.get_player_mut() {
-                                        let current_pos = *player_mut.position();
-                                        match resting_service
-                                            .process_rest_cycle(player_mut, current_pos)
-                                        {
+                                    if let Some(current_pos) = player_resource.player_position() {
+                                        match player_resource.rest(
+                                            &resting_service,
+                                            current_pos,
+                                            &game_stats.modifiers,
+                                        ) {
                                             Ok(rest_result) => {
                                                 info!("🌅 Dawn breaks after a night of rest");
+                                                game_stats.record_rest();
                                                 game_log.log_message(
                                                     "Dawn breaks after a night of rest".to_string(),
                                                     GameLogType::System,
@@ -732,9 +1094,7 @@ pub fn rpg_exploration_system(
                                                 if let Some(audio_assets) = &audio_assets {
                                                     if let Some(ui_handle) = &audio_assets.ui_click
                                                     {
-                                                        commands.spawn(AudioPlayer::new(
-                                                            ui_handle.clone(),
-                                                        ));
+                                                        presentation::audio_integration::play_audio(&mut sfx, &audio_settings, presentation::audio_integration::AudioCategory::Ui, presentation::audio_integration::SfxPriority::Ui, ui_handle, PlaybackSettings::ONCE);
                                                     }
                                                 }
 
@@ -799,15 +1159,18 @@ pub fn rpg_exploration_system(
                                                     rest_duration,
                                                     TimerMode::Once,
                                                 ));
+                                                let restored_points = player_resource
+                                                    .get_player()
+                                                    .map_or(0, |player| player.movement_points());
 
                                                 info!(
                                                     "🏃 Movement points restored: {} - resting for {} seconds...",
-                                                    player_mut.movement_points(),
+                                                    restored_points,
                                                     rest_duration.as_secs()
                                                 );
                                                 game_log.log_message(
                                                     format!("Movement points restored: {} - resting for {} seconds",
-                                                        player_mut.movement_points(),
+                                                        restored_points,
                                                         rest_duration.as_secs()
                                                     ),
                                                     GameLogType::System
@@ -827,7 +1190,7 @@ pub fn rpg_exploration_system(
                                             Err(e) => {
                                                 warn!("❌ Rest cycle failed: {}", e);
                                                 // Fallback: just restore movement points with normal rest time
-                                                player_mut.restore_points();
+                                                player_resource.restore_movement_points();
                                                 *rest_timer = Some(Timer::new(
                                                     std::time::Duration::from_secs(4),
                                                     TimerMode::Once,
@@ -840,9 +1203,7 @@ pub fn rpg_exploration_system(
                                                 if let Some(audio_assets) = &audio_assets {
                                                     if let Some(ui_handle) = &audio_assets.ui_click
                                                     {
-                                                        commands.spawn(AudioPlayer::new(
-                                                            ui_handle.clone(),
-                                                        ));
+                                                        presentation::audio_integration::play_audio(&mut sfx, &audio_settings, presentation::audio_integration::AudioCategory::Ui, presentation::audio_integration::SfxPriority::Ui, ui_handle, PlaybackSettings::ONCE);
                                                     }
                                                 }
                                             }
@@ -883,71 +1244,174 @@ fn format_resource_summary(
     }
 }
 
+/// Movement points a hazard takes on `final_roll`, after the run's rules
+/// and suit shielding
+fn hazard_penalty(
+    final_roll: u8,
+    game_stats: &infrastructure::bevy::resources::GameStatsResource,
+    player_resource: &infrastructure::bevy::resources::PlayerResource,
+) -> u8 {
+    let penalty = match final_roll {
+        1..=3 => 2,  // Critical failure - lose movement points
+        4..=7 => 1,  // Failure - lose movement point
+        8..=12 => 0, // Neutral - no penalty
+        _ => 0,      // Success+ - no penalty
+    };
+    let penalty = game_stats.modifiers.hazard_damage(penalty);
+    // Suit shielding soaks part of the hit
+    penalty.saturating_sub(
+        player_resource
+            .get_player()
+            .map(|player| player.gear().hazard_reduction())
+            .unwrap_or(0),
+    )
+}
+
+/// Log hull damage the player just took, with the health left
+fn log_hull_damage(
+    taken: u32,
+    player_resource: &infrastructure::bevy::resources::PlayerResource,
+    game_log: &mut GameLogService,
+) {
+    let Some(health) = player_resource.get_player().map(|player| *player.health()) else {
+        return;
+    };
+    if taken > 0 {
+        game_log.log_message(
+            format!("🩸 The hull takes {} damage - {} left", taken, health),
+            GameLogType::Warning,
+        );
+    }
+}
+
+/// Terrain of the tile at `position`, Plains where no map is loaded
+fn terrain_at(
+    map_resource: &infrastructure::bevy::resources::MapResource,
+    position: domain::Position3D,
+) -> domain::value_objects::TerrainType {
+    map_resource
+        .current_map()
+        .and_then(|map| map.get_tile(&domain::value_objects::TileCoordinate::from(position)))
+        .map(|tile| tile.terrain_type)
+        .unwrap_or(domain::value_objects::TerrainType::Plains)
+}
+
+/// Roll what a resource discovery on `position` turns up and hand it over
+///
+/// The terrain picks the resource, drawn from the loot stream, and the
+/// richness of the ground scales the amount. Returns the amount found.
+fn discover_resources(
+    position: domain::Position3D,
+    reward: &domain::services::MovementReward,
+    map_resource: &infrastructure::bevy::resources::MapResource,
+    rng_streams: &infrastructure::RngStreams,
+    player_resource: &mut infrastructure::bevy::resources::PlayerResource,
+    game_stats: &mut infrastructure::bevy::resources::GameStatsResource,
+    game_log: &mut GameLogService,
+) -> u32 {
+    use domain::value_objects::resources::{ResourceCollection, ResourceRichness};
+    use infrastructure::traits::RandomService;
+
+    let map = map_resource.current_map();
+    let terrain = terrain_at(map_resource, position);
+    let richness = map
+        .map(|map| map.discovery_richness(&position))
+        .unwrap_or(ResourceRichness::Average);
+    let resource_type = rng_streams
+        .stream(LOOT)
+        .random_resource_for_terrain(terrain);
+    let found = game_stats
+        .modifiers
+        .event_reward(richness.scale(reward.discovered));
+    if found == 0 || !player_resource.has_player() {
+        return 0;
+    }
+
+    let mut resources = ResourceCollection::new();
+    resources.set_amount(resource_type, found);
+    presentation::inventory::pick_up_find(player_resource, &resources, game_log);
+    info!("💰 Found {} {}!", found, resource_type);
+    game_log.log_message(
+        format!(
+            "💰 Found {} {} ({} ground)",
+            found,
+            resource_type,
+            richness.to_string().to_lowercase()
+        ),
+        GameLogType::Resources,
+    );
+    game_stats.record_experience_gain(reward.experience);
+    found
+}
+
 /// Process events triggered by tile movement
-fn process_movement_event(
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn process_movement_event(
     event: &domain::entities::Event,
     dice_result: &domain::services::tile_movement::MovementDiceResult,
     player_resource: &mut infrastructure::bevy::resources::PlayerResource,
     game_stats: &mut infrastructure::bevy::resources::GameStatsResource,
     game_log: &mut ResMut<GameLogService>,
-    commands: &mut Commands,
+    sfx: &mut presentation::audio_integration::SfxArbiter,
     audio_assets: Option<&Res<presentation::audio_integration::AudioAssets>>,
+    audio_settings: &presentation::audio_integration::GlobalAudioSettings,
+    economy: &domain::services::MovementEconomyService,
+    position: domain::Position3D,
+    map_resource: &infrastructure::bevy::resources::MapResource,
+    rng_streams: &infrastructure::RngStreams,
 ) {
     use domain::entities::EventType;
-    use domain::value_objects::resources::ResourceCollection;
     use domain::value_objects::ResourceType;
 
     let final_roll = dice_result.final_result;
-
-    // Calculate movement point rewards based on dice roll success
-    let movement_reward = match final_roll {
-        20..=u8::MAX => 7, // Critical success - major reward
-        17..=19 => 5,      // Great success - good reward
-        13..=16 => 4,      // Success - moderate reward
-        10..=12 => 3,      // Neutral - small reward
-        7..=9 => 2,        // Mild failure - minimal reward
-        4..=6 => 1,        // Failure - tiny reward
-        _ => 0,            // Critical failure - no reward
-    };
+    let reward = economy.event_reward(
+        dice_result,
+        event.event_type(),
+        terrain_at(map_resource, position),
+    );
 
     // Apply movement point rewards for successful outcomes
-    if movement_reward > 0 {
-        if let Some(player) = player_resource.get_player_mut() {
-            player.add_movement_points(movement_reward);
-            info!(
-                "🏃 Gained {} movement points from successful exploration!",
-                movement_reward
-            );
-            game_log.log_message(
-                format!(
-                    "Gained {} movement points from successful exploration!",
-                    movement_reward
-                ),
-                GameLogType::Resources,
-            );
-        }
+    if reward.movement_points > 0 && player_resource.has_player() {
+        let granted = player_resource.grant_movement_points(
+            reward.movement_points,
+            domain::services::GrantSource::EventReward,
+        );
+        info!(
+            "🏃 Gained {} movement points from successful exploration!",
+            granted
+        );
+        game_log.log_message(
+            format!(
+                "Gained {} movement points from successful exploration!",
+                granted
+            ),
+            GameLogType::Resources,
+        );
     }
 
     match event.event_type() {
         EventType::ResourceDiscovery => {
-            let mut resources = ResourceCollection::new();
-            let amount = match final_roll {
-                20..=u8::MAX => 50, // Critical success - lots of resources
-                17..=19 => 30,      // Great success
-                13..=16 => 15,      // Success
-                _ => 5,             // Minimal find
-            };
-
-            resources.set_amount(ResourceType::Metal, amount);
-            if let Some(player) = player_resource.get_player_mut() {
-                player.add_resources(&resources);
-                info!("💰 Found {} metal!", amount);
-                game_stats.record_experience_gain(amount as u32);
-
+            let found = discover_resources(
+                position,
+                &reward,
+                map_resource,
+                rng_streams,
+                player_resource,
+                game_stats,
+                game_log,
+            );
+            if found > 0 {
                 // Play resource discovery audio
                 if let Some(audio_assets) = audio_assets {
                     if let Some(resource_handle) = &audio_assets.resource_collect {
-                        commands.spawn(AudioPlayer::new(resource_handle.clone()));
+                        presentation::audio_integration::play_audio(
+                            sfx,
+                            audio_settings,
+                            presentation::audio_integration::AudioCategory::Sfx,
+                            presentation::audio_integration::SfxPriority::Resource,
+                            resource_handle,
+                            PlaybackSettings::ONCE,
+                        );
                     }
                 }
 
@@ -966,53 +1430,74 @@ fn process_movement_event(
             };
 
             if damage > 0 {
-                info!("⚔️ Combat! Took {} damage", damage);
+                let taken = player_resource.take_damage(damage);
+                info!("⚔️ Combat! Took {} damage", taken);
+                log_hull_damage(taken, player_resource, game_log);
                 // Play damage/combat audio
                 if let Some(audio_assets) = audio_assets {
                     if let Some(ui_handle) = &audio_assets.ui_click {
-                        commands.spawn(AudioPlayer::new(ui_handle.clone()));
+                        presentation::audio_integration::play_audio(
+                            sfx,
+                            audio_settings,
+                            presentation::audio_integration::AudioCategory::Ui,
+                            presentation::audio_integration::SfxPriority::Ui,
+                            ui_handle,
+                            PlaybackSettings::ONCE,
+                        );
                     }
                 }
-                // TODO: Implement actual damage system
             } else {
                 info!("⚔️ Combat encounter successfully resolved!");
                 // Play victory audio
                 if let Some(audio_assets) = audio_assets {
                     if let Some(ui_handle) = &audio_assets.ui_click {
-                        commands.spawn(AudioPlayer::new(ui_handle.clone()));
-                    }
-                }
-                if movement_bonus > 0 {
-                    if let Some(player) = player_resource.get_player_mut() {
-                        player.add_movement_points(movement_bonus);
-                        info!(
-                            "🏃 Combat victory! Gained {} extra movement points!",
-                            movement_bonus
+                        presentation::audio_integration::play_audio(
+                            sfx,
+                            audio_settings,
+                            presentation::audio_integration::AudioCategory::Ui,
+                            presentation::audio_integration::SfxPriority::Ui,
+                            ui_handle,
+                            PlaybackSettings::ONCE,
                         );
                     }
                 }
+                if movement_bonus > 0 && player_resource.has_player() {
+                    player_resource.grant_movement_points(
+                        movement_bonus,
+                        domain::services::GrantSource::CombatBonus,
+                    );
+                    info!(
+                        "🏃 Combat victory! Gained {} extra movement points!",
+                        movement_bonus
+                    );
+                }
                 game_stats.record_experience_gain(20);
             }
         }
 
         EventType::Hazard => {
-            let penalty = match final_roll {
-                1..=3 => 2,  // Critical failure - lose movement points
-                4..=7 => 1,  // Failure - lose movement point
-                8..=12 => 0, // Neutral - no penalty
-                _ => 0,      // Success+ - no penalty (already got reward above)
-            };
+            let penalty = hazard_penalty(final_roll, game_stats, player_resource);
 
             if penalty > 0 {
-                if let Some(player) = player_resource.get_player_mut() {
-                    // Safely subtract movement points (won't go below 0)
-                    player.subtract_movement_points(penalty);
+                if player_resource.has_player() {
+                    // Penalties stop at zero movement points and zero health
+                    player_resource.lose_movement_points(penalty);
                     info!("⚠️ Environmental hazard! Lost {} movement points!", penalty);
+                    let taken = player_resource
+                        .take_damage(penalty as u32 * domain::constants::HAZARD_DAMAGE_PER_POINT);
+                    log_hull_damage(taken, player_resource, game_log);
 
                     // Play hazard audio
                     if let Some(audio_assets) = audio_assets {
                         if let Some(ui_handle) = &audio_assets.ui_click {
-                            commands.spawn(AudioPlayer::new(ui_handle.clone()));
+                            presentation::audio_integration::play_audio(
+                                sfx,
+                                audio_settings,
+                                presentation::audio_integration::AudioCategory::Ui,
+                                presentation::audio_integration::SfxPriority::Ui,
+                                ui_handle,
+                                PlaybackSettings::ONCE,
+                            );
                         }
                     }
                 }
@@ -1022,31 +1507,43 @@ fn process_movement_event(
                 // Play success audio
                 if let Some(audio_assets) = audio_assets {
                     if let Some(ui_handle) = &audio_assets.ui_click {
-                        commands.spawn(AudioPlayer::new(ui_handle.clone()));
+                        presentation::audio_integration::play_audio(
+                            sfx,
+                            audio_settings,
+                            presentation::audio_integration::AudioCategory::Ui,
+                            presentation::audio_integration::SfxPriority::Ui,
+                            ui_handle,
+                            PlaybackSettings::ONCE,
+                        );
                     }
                 }
             }
         }
 
         EventType::Trade => {
-            if final_roll >= 13 {
-                let mut resources = ResourceCollection::new();
-                let data_amount = match final_roll {
-                    20..=u8::MAX => 40, // Critical success
-                    17..=19 => 30,      // Great success
-                    _ => 20,            // Success
-                };
-
-                resources.set_amount(ResourceType::Data, data_amount);
-                if let Some(player) = player_resource.get_player_mut() {
-                    player.add_resources(&resources);
-                    info!("💾 Successful trade! Gained {} data!", data_amount);
-                    game_stats.record_experience_gain(data_amount as u32 / 2);
+            if let Some(offer) = application::use_cases::HaggleOffer::legacy(final_roll) {
+                if player_resource.has_player() {
+                    let data_gained = presentation::field_trade::close_deal(
+                        &offer,
+                        true,
+                        player_resource,
+                        game_stats,
+                    )
+                    .map(|goods| goods.get_amount(ResourceType::Data))
+                    .unwrap_or(0);
+                    info!("💾 Successful trade! Gained {} data!", data_gained);
 
                     // Play successful trade audio
                     if let Some(audio_assets) = audio_assets {
                         if let Some(ui_handle) = &audio_assets.ui_click {
-                            commands.spawn(AudioPlayer::new(ui_handle.clone()));
+                            presentation::audio_integration::play_audio(
+                                sfx,
+                                audio_settings,
+                                presentation::audio_integration::AudioCategory::Ui,
+                                presentation::audio_integration::SfxPriority::Ui,
+                                ui_handle,
+                                PlaybackSettings::ONCE,
+                            );
                         }
                     }
                 }
@@ -1055,7 +1552,14 @@ fn process_movement_event(
                 // Play trade failure audio
                 if let Some(audio_assets) = audio_assets {
                     if let Some(ui_handle) = &audio_assets.ui_click {
-                        commands.spawn(AudioPlayer::new(ui_handle.clone()));
+                        presentation::audio_integration::play_audio(
+                            sfx,
+                            audio_settings,
+                            presentation::audio_integration::AudioCategory::Ui,
+                            presentation::audio_integration::SfxPriority::Ui,
+                            ui_handle,
+                            PlaybackSettings::ONCE,
+                        );
                     }
                 }
             }
@@ -1069,9 +1573,10 @@ fn process_movement_event(
                 _ => (10, 0),             // Minor benefit
             };
 
-            if let Some(player) = player_resource.get_player_mut() {
+            if player_resource.has_player() {
                 if extra_movement > 0 {
-                    player.add_movement_points(extra_movement);
+                    player_resource
+                        .grant_movement_points(extra_movement, domain::services::GrantSource::Boon);
                     info!("✨ Fortune smiles upon you! Gained {} experience and {} extra movement points!", xp_gain, extra_movement);
                 } else {
                     info!("✨ Fortune smiles upon you! Gained {} experience", xp_gain);
@@ -1081,12 +1586,26 @@ fn process_movement_event(
         }
 
         EventType::Mystery => {
-            if final_roll >= 15 {
+            if final_roll >= domain::constants::MYSTERY_INSIGHT_ROLL {
                 let bonus_movement = if final_roll >= 18 { 2 } else { 1 };
-                if let Some(player) = player_resource.get_player_mut() {
-                    player.add_movement_points(bonus_movement);
+                if player_resource.has_player() {
+                    player_resource.grant_movement_points(
+                        bonus_movement,
+                        domain::services::GrantSource::Mystery,
+                    );
                     info!("🔮 Mysterious phenomenon understood! Gained knowledge and {} movement points!", bonus_movement);
                 }
+                // Critical insight turns up a piece of lost gear
+                if final_roll >= 20 {
+                    let item = domain::services::GearItem::roll(rand::random::<u64>());
+                    let description = format!("{} ({})", item.name, item.describe());
+                    if player_resource.stow_gear(item).is_ok() {
+                        game_log.log_message(
+                            format!("🧰 Found gear: {}", description),
+                            GameLogType::Discovery,
+                        );
+                    }
+                }
                 game_stats.record_experience_gain(40);
             } else {
                 info!("🔮 A mysterious phenomenon occurs, but its meaning eludes you");
@@ -1096,8 +1615,8 @@ fn process_movement_event(
         EventType::Malfunction => {
             if final_roll <= 7 {
                 // Equipment malfunction reduces movement points
-                if let Some(player) = player_resource.get_player_mut() {
-                    player.subtract_movement_points(1);
+                if player_resource.has_player() {
+                    player_resource.lose_movement_points(1);
                     info!("🔧 Equipment malfunction! Lost 1 movement point due to efficiency reduction");
                 }
             } else {
@@ -1119,30 +1638,41 @@ fn process_movement_event(
 }
 
 /// RPG dice mechanics and random events system
+#[allow(clippy::too_many_arguments)]
 fn rpg_dice_mechanics_system(
     keyboard_input: Option<Res<ButtonInput<KeyCode>>>,
+    mut page_rolls: EventReader<presentation::page_api::DiceRollRequested>,
     mut game_stats: ResMut<infrastructure::bevy::resources::GameStatsResource>,
-    mut commands: Commands,
+    mut sfx: Option<ResMut<presentation::audio_integration::SfxArbiter>>,
     audio_assets: Option<Res<presentation::audio_integration::AudioAssets>>,
-    time: Res<Time>,
+    audio_settings: Option<Res<presentation::audio_integration::GlobalAudioSettings>>,
+    sim: Res<presentation::clocks::SimClock>,
+    config: Res<presentation::clocks::GameplayConfig>,
+    mut auto_roll: ResMut<presentation::clocks::AutoRollTimer>,
+    input_mapper: Option<Res<presentation::InputMapper>>,
 ) {
-    // Auto-roll for headless mode or manual roll with spacebar
+    // No rolls, and no progress towards one, while the run is not played
+    if !sim.is_running() {
+        return;
+    }
+
+    // Auto-roll for headless mode or manual roll with the bound key
     let has_keyboard = keyboard_input.is_some();
-    let should_roll = if let Some(keyboard) = &keyboard_input {
-        keyboard.just_pressed(KeyCode::Space)
-    } else {
-        // Auto-roll every 5 seconds in headless mode
-        static mut LAST_ROLL_TIME: f32 = 0.0;
-        let current_time = time.elapsed_secs();
-        unsafe {
-            if current_time - LAST_ROLL_TIME >= 5.0 {
-                LAST_ROLL_TIME = current_time;
-                true
-            } else {
-                false
-            }
-        }
-    };
+    // The embedding page can ask for a roll too
+    let page_roll = page_rolls.read().count() > 0;
+    let should_roll = page_roll
+        || if let Some(keyboard) = &keyboard_input {
+            input_mapper.as_ref().is_some_and(|mapper| {
+                mapper.just_pressed(keyboard, presentation::GameAction::RollDice)
+            })
+        } else {
+            // Auto-roll on the configured cadence of play in headless mode
+            presentation::clocks::tick_every(
+                &mut auto_roll.0,
+                sim.delta(),
+                config.auto_roll_interval_secs,
+            )
+        };
 
     if should_roll {
         // Create a basic dice roll for demonstration
@@ -1156,9 +1686,18 @@ fn rpg_dice_mechanics_system(
 
             // Trigger dice roll audio
             info!("🎲 Playing dice roll audio for roll: {}", total);
-            if let Some(audio_assets) = &audio_assets {
+            if let (Some(sfx), Some(audio_assets), Some(audio_settings)) =
+                (&mut sfx, &audio_assets, &audio_settings)
+            {
                 if let Some(dice_handle) = &audio_assets.dice_roll {
-                    commands.spawn(AudioPlayer::new(dice_handle.clone()));
+                    presentation::audio_integration::play_audio(
+                        sfx,
+                        audio_settings,
+                        presentation::audio_integration::AudioCategory::Sfx,
+                        presentation::audio_integration::SfxPriority::Effect,
+                        dice_handle,
+                        PlaybackSettings::ONCE,
+                    );
                 }
             }
 
@@ -1232,11 +1771,29 @@ fn rpg_dice_mechanics_system(
 }
 
 /// Handle RPG state transitions
+///
+/// Screens asked for from exploration while the player is mid-move open
+/// once the move has landed, so its result is never left behind.
+#[allow(clippy::too_many_arguments)]
 fn rpg_state_transition_system(
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_mapper: Res<presentation::InputMapper>,
     current_state: Res<State<presentation::RpgAppState>>,
     mut next_state: ResMut<NextState<presentation::RpgAppState>>,
+    party: Option<Res<infrastructure::bevy::resources::PartyResource>>,
+    player_query: Query<
+        &presentation::movement::SmoothMovement,
+        With<presentation::map_renderer::PlayerMarker>,
+    >,
+    mut deferred: ResMut<presentation::movement::DeferredTransition>,
+    mut game_log: ResMut<GameLogService>,
+    run: Option<Res<presentation::run_end::ActiveRun>>,
 ) {
+    // Screens stay shut while the device is passed on or cargo is traded
+    if party.is_some_and(|party| party.blocks_movement()) {
+        return;
+    }
+
     match current_state.get() {
         presentation::RpgAppState::Loading => {
             // Auto-transition to main menu after initialization
@@ -1249,29 +1806,70 @@ fn rpg_state_transition_system(
             }
         }
         presentation::RpgAppState::Exploration => {
-            if keyboard_input.just_pressed(KeyCode::KeyB) {
-                next_state.set(presentation::RpgAppState::BaseManagement);
-                info!("Entering base management mode");
-            } else if keyboard_input.just_pressed(KeyCode::KeyQ) {
-                next_state.set(presentation::RpgAppState::QuestLog);
-                info!("Opening quest log");
-            } else if keyboard_input.just_pressed(KeyCode::KeyI) {
-                next_state.set(presentation::RpgAppState::Inventory);
-                info!("Opening inventory");
+            let pressed = |action| input_mapper.just_pressed(&keyboard_input, action);
+            let requested = if pressed(presentation::GameAction::OpenBase) {
+                Some(presentation::RpgAppState::BaseManagement)
+            } else if pressed(presentation::GameAction::OpenQuests) {
+                Some(presentation::RpgAppState::QuestLog)
+            } else if pressed(presentation::GameAction::OpenInventory) {
+                Some(presentation::RpgAppState::Inventory)
+            } else if pressed(presentation::GameAction::TogglePause) {
+                Some(presentation::RpgAppState::Paused)
+            } else {
+                None
+            };
+
+            let moving = player_query.iter().any(|movement| movement.is_moving);
+            let next = match requested {
+                Some(state) if moving => {
+                    if deferred.defer(state) {
+                        game_log
+                            .log_message("Finishing move...".to_string(), GameLogType::Movement);
+                    }
+                    None
+                }
+                Some(state) => Some(state),
+                None => deferred.take_when_settled(moving),
+            };
+            if let Some(state) = next {
+                info!("Leaving exploration for {:?}", state);
+                next_state.set(state);
+            }
+        }
+        presentation::RpgAppState::BaseManagement => {
+            if keyboard_input.just_pressed(KeyCode::KeyE) {
+                next_state.set(presentation::RpgAppState::ExpeditionPlanning);
+                info!("Opening expedition planner");
             } else if keyboard_input.just_pressed(KeyCode::Escape) {
-                next_state.set(presentation::RpgAppState::Paused);
+                next_state.set(presentation::RpgAppState::Exploration);
+                info!("Returning to exploration");
             }
         }
-        presentation::RpgAppState::BaseManagement
-        | presentation::RpgAppState::QuestLog
-        | presentation::RpgAppState::Inventory => {
+        presentation::RpgAppState::ExpeditionPlanning
+            if keyboard_input.just_pressed(KeyCode::Escape) =>
+        {
+            next_state.set(presentation::RpgAppState::BaseManagement);
+            info!("Closing expedition planner");
+        }
+        presentation::RpgAppState::QuestLog | presentation::RpgAppState::Inventory => {
             if keyboard_input.just_pressed(KeyCode::Escape) {
                 next_state.set(presentation::RpgAppState::Exploration);
                 info!("Returning to exploration");
             }
         }
+        // Resuming comes back here while the encounter still waits
+        presentation::RpgAppState::EventResolution
+            if input_mapper
+                .just_pressed(&keyboard_input, presentation::GameAction::TogglePause) =>
+        {
+            next_state.set(presentation::RpgAppState::Paused);
+        }
         presentation::RpgAppState::Paused => {
-            if keyboard_input.just_pressed(KeyCode::Escape) {
+            // Escape answers the abandon question first
+            let prompting = run.is_some_and(|run| run.is_prompting());
+            let resume =
+                input_mapper.just_pressed(&keyboard_input, presentation::GameAction::TogglePause);
+            if resume && !prompting {
                 next_state.set(presentation::RpgAppState::Exploration);
                 info!("Resuming game");
             }
@@ -1394,17 +1992,29 @@ pub fn stop_music() {
 }
 
 /// Apply movement result effects after animation completes
+#[allow(clippy::too_many_arguments)]
 fn apply_movement_result(
     movement_result: &domain::services::tile_movement::MovementResult,
     player_resource: &mut ResMut<infrastructure::bevy::resources::PlayerResource>,
     game_stats: &mut ResMut<infrastructure::bevy::resources::GameStatsResource>,
     game_log: &mut ResMut<GameLogService>,
+    hostile_contact: &mut presentation::reputation::HostileContact,
+    trader_contact: &mut presentation::field_trade::TraderContact,
+    flags: &mut domain::services::SessionFlags,
+    mystery_contact: &mut presentation::encounter::MysteryContact,
+    economy: &domain::services::MovementEconomyService,
+    map_resource: &infrastructure::bevy::resources::MapResource,
+    rng_streams: &infrastructure::RngStreams,
 ) {
     // Update game statistics
     game_stats.record_tile_explored();
 
     // Handle movement result based on what happened
     if let Some(event) = &movement_result.triggered_event {
+        // Follow-up events happen once; remember that this one did
+        if let Some(key) = movement_result.marks_flag {
+            flags.set_flag(key);
+        }
         info!(
             "🎭 Event Triggered: {} - {}",
             event.title(),
@@ -1420,32 +2030,255 @@ fn apply_movement_result(
             GameLogType::Event,
         );
 
-        // Add resources from event
-        if let Some(mut player) = player_resource.get_player_mut() {
-            // Add movement points from successful exploration
-            player.add_movement_points(2);
-        }
+        // Add movement points from successful exploration
+        let granted = player_resource.grant_movement_points(
+            economy.landing_points(true),
+            domain::services::GrantSource::EventReward,
+        );
 
-        info!("🏃 Gained 2 movement points from successful exploration!");
+        info!(
+            "🏃 Gained {} movement points from successful exploration!",
+            granted
+        );
 
         // Log event description
         info!("📖 {}", event.description());
         game_log.log_message(event.description().to_string(), GameLogType::Narrative);
+
+        // Hostile contact holds movement until the player fights, flees or pays
+        if event.event_type() == domain::entities::EventType::Combat {
+            let can_bribe = player_resource.get_player().is_some_and(|player| {
+                player
+                    .resources()
+                    .can_afford(&presentation::reputation::hostile_bribe_cost())
+            });
+            hostile_contact.raise(
+                movement_result.target_position,
+                Some(movement_result.origin_position),
+            );
+            let choices = if can_bribe {
+                "fight them (F), flee (H) or pay them off (G)"
+            } else {
+                "fight them (F) or flee (H)"
+            };
+            game_log.log_message(
+                format!("⚠️ Raiders lock on - {}", choices),
+                GameLogType::Warning,
+            );
+        }
+
+        // Hazards strike the hull as the move lands
+        if event.event_type() == domain::entities::EventType::Hazard {
+            let penalty = hazard_penalty(
+                movement_result.dice_result.final_result,
+                game_stats,
+                player_resource,
+            );
+            let taken = player_resource
+                .take_damage(penalty as u32 * domain::constants::HAZARD_DAMAGE_PER_POINT);
+            log_hull_damage(taken, player_resource, game_log);
+        }
+
+        // A trader holds movement until the player deals or walks away
+        if event.event_type() == domain::entities::EventType::Trade {
+            trader_contact.raise(
+                movement_result.target_position,
+                movement_result.faction,
+                movement_result.dice_result.final_result,
+            );
+        }
+
+        // A discovery hands over what the ground holds
+        if event.event_type() == domain::entities::EventType::ResourceDiscovery {
+            let reward = economy.event_reward(
+                &movement_result.dice_result,
+                event.event_type(),
+                terrain_at(map_resource, movement_result.target_position),
+            );
+            discover_resources(
+                movement_result.target_position,
+                &reward,
+                map_resource,
+                rng_streams,
+                player_resource,
+                game_stats,
+                game_log,
+            );
+        }
+
+        // A mystery holds movement until the player picks how to meet it
+        if event.event_type() == domain::entities::EventType::Mystery {
+            mystery_contact.raise(movement_result.target_position, event.clone());
+            game_log.log_message(
+                "🔮 Study it (1), attune to it (2) or leave it be (3)".to_string(),
+                GameLogType::Event,
+            );
+        }
     } else {
         info!("🚶 Safe movement - no events triggered");
 
         // Give small movement point recovery even for safe movement
-        if let Some(player) = player_resource.get_player_mut() {
-            player.add_movement_points(2);
-            info!("🏃 Safe exploration grants 2 movement points");
+        if player_resource.has_player() {
+            let granted = player_resource.grant_movement_points(
+                economy.landing_points(false),
+                domain::services::GrantSource::Exploration,
+            );
+            info!("🏃 Safe exploration grants {} movement points", granted);
             game_log.log_message(
-                "Safe exploration grants 2 movement points".to_string(),
+                format!("Safe exploration grants {} movement points", granted),
                 GameLogType::Resources,
             );
         }
     }
 }
 
+/// Threat of the hostiles met on `position`, and the ground they stand on
+///
+/// The opponent's threat is the danger level of the terrain they were met
+/// on, which also sets the odds of running.
+fn hostile_threat(
+    position: domain::Position3D,
+    map_resource: &infrastructure::bevy::resources::MapResource,
+) -> (u8, Option<domain::value_objects::terrain::TerrainType>) {
+    let terrain = map_resource
+        .current_map()
+        .and_then(|map| map.get_tile(&domain::value_objects::TileCoordinate::from(position)))
+        .map(|tile| tile.terrain_type);
+    let threat = terrain.map(|terrain| terrain.danger_level()).unwrap_or(1);
+    (threat, terrain)
+}
+
+/// Face the hostiles met on `position` through the encounter use case
+///
+/// Returns the outcome, applied through [`apply_encounter_outcome`], if
+/// there was a player to face them.
+#[allow(clippy::too_many_arguments)]
+fn resolve_hostile_contact(
+    position: domain::Position3D,
+    approach: application::use_cases::EncounterApproach,
+    disadvantage: bool,
+    player_resource: &mut ResMut<infrastructure::bevy::resources::PlayerResource>,
+    map_resource: &infrastructure::bevy::resources::MapResource,
+    game_stats: &mut ResMut<infrastructure::bevy::resources::GameStatsResource>,
+    game_log: &mut ResMut<GameLogService>,
+    session: &mut presentation::game_state::RpgGameSession,
+    rng_streams: &infrastructure::RngStreams,
+) -> Option<application::use_cases::EncounterOutcome> {
+    use application::use_cases::{EncounterContext, ResolveEncounterUseCase, RngDice};
+
+    let stats = player_resource
+        .get_player()
+        .map(|player| player.derived_stats())?;
+    let (threat, terrain) = hostile_threat(position, map_resource);
+
+    let mut dice = RngDice(rng_streams.stream(ENCOUNTERS));
+    let outcome = match ResolveEncounterUseCase::new().execute(EncounterContext {
+        stats,
+        threat,
+        approach,
+        terrain,
+        disadvantage,
+        dice: &mut dice,
+    }) {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            warn!("Failed to resolve encounter: {}", e);
+            return None;
+        }
+    };
+    apply_encounter_outcome(
+        &outcome,
+        position,
+        player_resource,
+        game_stats,
+        game_log,
+        session,
+    );
+    Some(outcome)
+}
+
+/// Apply how an encounter on `position` went to the player, the stats and the log
+///
+/// Damage hits the hull; the survival check ends the run if it gives out.
+/// How the contact went is left in the session flags for later events.
+/// A won fight leaves its loot in a wreck on the tile rather than handing
+/// it over; other approaches pay out at once.
+fn apply_encounter_outcome(
+    outcome: &application::use_cases::EncounterOutcome,
+    position: domain::Position3D,
+    player_resource: &mut ResMut<infrastructure::bevy::resources::PlayerResource>,
+    game_stats: &mut ResMut<infrastructure::bevy::resources::GameStatsResource>,
+    game_log: &mut ResMut<GameLogService>,
+    session: &mut presentation::game_state::RpgGameSession,
+) {
+    use application::use_cases::EncounterApproach;
+    use domain::constants::{FLAG_LAST_ENCOUNTER, FLAG_RAIDERS_DRIVEN_OFF, FLAG_SPARED_SCAVENGER};
+    use domain::value_objects::resources::ResourceCollection;
+
+    info!("⚔️ {:?} encounter: {:?}", outcome.approach, outcome.tier);
+    game_log.log_message(outcome.summary(), GameLogType::Combat);
+    session
+        .flags
+        .set_tag(FLAG_LAST_ENCOUNTER, outcome.approach.key());
+    if outcome.flags.spared {
+        session.flags.set_flag(FLAG_SPARED_SCAVENGER);
+    }
+    let won_fight = outcome.approach == EncounterApproach::Fight && outcome.tier.is_success();
+    if won_fight {
+        session.flags.increment(FLAG_RAIDERS_DRIVEN_OFF, 1);
+        session
+            .decals
+            .add(position, domain::services::DecalKind::Scorch);
+    }
+
+    if won_fight && !outcome.loot.is_empty() {
+        let origin = domain::services::WreckOrigin::of_fight(position, *session.base.position());
+        let evicted = session.wrecks.leave(position, origin, outcome.loot.clone());
+        game_log.log_message(
+            format!(
+                "💥 The {} is left on the tile - U to salvage it",
+                origin.name()
+            ),
+            GameLogType::Event,
+        );
+        if let Some(evicted) = evicted {
+            game_log.log_message(
+                format!(
+                    "The {} at ({}, {}) has been picked clean by others",
+                    evicted.origin.name(),
+                    evicted.position.x,
+                    evicted.position.y
+                ),
+                GameLogType::System,
+            );
+        }
+    } else if !outcome.loot.is_empty() {
+        let mut loot = ResourceCollection::new();
+        for &(resource_type, amount) in &outcome.loot {
+            let amount = game_stats.modifiers.resource_yield(resource_type, amount);
+            loot.set_amount(resource_type, amount);
+        }
+        game_log.log_message(
+            format!(
+                "Salvaged from the wreckage: {}",
+                format_resource_summary(&loot)
+            ),
+            GameLogType::Resources,
+        );
+        presentation::inventory::gather_find(player_resource, &loot, game_stats, game_log);
+    }
+
+    session.heatmap.record(
+        position,
+        domain::services::HeatMetric::Damage,
+        outcome.damage_taken,
+    );
+    let taken = player_resource.take_damage(outcome.damage_taken);
+    log_hull_damage(taken, player_resource, game_log);
+
+    game_stats.record_experience_gain(outcome.experience);
+}
+
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 pub fn toggle_audio(enabled: bool) {
@@ -1463,6 +2296,70 @@ pub fn toggle_audio(enabled: bool) {
     }
 }
 
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn set_background_policy(policy: &str) {
+    match presentation::frame_limiter::BackgroundPolicy::parse(policy) {
+        Some(policy) => presentation::frame_limiter::request_background_policy(policy),
+        None => web_sys::console::warn_1(
+            &format!(
+                "Unknown background policy '{}', expected pause, throttle or fullspeed",
+                policy
+            )
+            .into(),
+        ),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn request_bug_report() {
+    presentation::bug_report::request_bug_report();
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn take_bug_report() -> Option<String> {
+    presentation::bug_report::take_bug_report()
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn get_game_state_json() -> String {
+    presentation::page_api::game_state_json()
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn dispatch_command(json: &str) -> String {
+    presentation::page_api::dispatch_command(json)
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn get_asset_manifest() -> String {
+    infrastructure::web::offline::asset_manifest_json()
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn report_offline_cache(build: &str, assets: u32) {
+    infrastructure::web::offline::report_offline_cache(
+        infrastructure::web::offline::OfflineCacheStatus::Cached {
+            build: build.to_string(),
+            assets,
+        },
+    );
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn report_offline_cache_failure(reason: &str) {
+    infrastructure::web::offline::report_offline_cache(
+        infrastructure::web::offline::OfflineCacheStatus::Failed(reason.to_string()),
+    );
+}
+
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 pub fn get_music_state() -> bool {
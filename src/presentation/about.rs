@@ -0,0 +1,303 @@
+//! About - Build details, the current run and scrolling credits
+//!
+//! F1 opens the about screen from the main menu or while paused. It shows
+//! the build the player is running and, during a run, the world seed and
+//! session, above credits that scroll from `assets/credits.txt`. The copy
+//! button (or C) puts the same details on the clipboard for bug reports;
+//! native builds write them to `build_info.txt` instead.
+
+use crate::domain::constants::{
+    ENERGY_COLOR, HANDOVER_BACKGROUND, PANEL_BACKGROUND, PRIMARY_TEXT, SECONDARY_TEXT,
+};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::infrastructure::bevy::resources::MapResource;
+use crate::infrastructure::build_info::{BuildInfo, RunInfo};
+use crate::infrastructure::clipboard::{copy_text, CopyOutcome};
+use crate::presentation::game_state::{RpgAppState, RpgGameSession};
+use bevy::prelude::*;
+
+/// Key that opens and closes the about screen
+pub const ABOUT_KEY: KeyCode = KeyCode::F1;
+
+/// Key that copies the build info while the about screen is open
+pub const COPY_BUILD_INFO_KEY: KeyCode = KeyCode::KeyC;
+
+/// File the build info goes to where there is no clipboard
+pub const BUILD_INFO_FILE: &str = "build_info.txt";
+
+/// Credits shown on the about screen
+pub const CREDITS: &str = include_str!("../../assets/credits.txt");
+
+/// Credits scroll speed, in pixels per second
+const CREDITS_SCROLL_SPEED: f32 = 24.0;
+
+/// Height of the credits window, in pixels
+const CREDITS_VIEW_HEIGHT: f32 = 220.0;
+
+/// Plugin for the about screen
+pub struct AboutPlugin;
+
+impl Plugin for AboutPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AboutScreen>()
+            .add_systems(Startup, setup_about_screen)
+            .add_systems(
+                Update,
+                (
+                    toggle_about_system,
+                    copy_build_info_system,
+                    update_about_screen_system,
+                    scroll_credits_system,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Whether the about screen is open and how far the credits have scrolled
+#[derive(Resource, Debug, Default)]
+pub struct AboutScreen {
+    open: bool,
+    scroll: f32,
+}
+
+impl AboutScreen {
+    /// Whether the about screen is showing
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+}
+
+/// Marker for the about screen root
+#[derive(Component)]
+pub struct AboutRoot;
+
+/// Marker for the build and run details
+#[derive(Component)]
+pub struct AboutDetailsText;
+
+/// Marker for the scrolling credits
+#[derive(Component)]
+pub struct AboutCredits;
+
+/// Marker for the copy button
+#[derive(Component)]
+pub struct CopyBuildInfoButton;
+
+fn setup_about_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                top: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(16.0),
+                ..default()
+            },
+            BackgroundColor(HANDOVER_BACKGROUND),
+            GlobalZIndex(15),
+            Visibility::Hidden,
+            AboutRoot,
+            Name::new("AboutScreen"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Medium.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                TextLayout::new_with_justify(JustifyText::Center),
+                AboutDetailsText,
+            ));
+
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(480.0),
+                        height: Val::Px(CREDITS_VIEW_HEIGHT),
+                        overflow: Overflow::clip_y(),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    BackgroundColor(PANEL_BACKGROUND),
+                ))
+                .with_children(|view| {
+                    view.spawn((
+                        Text::new(CREDITS),
+                        TextFont {
+                            font_size: FontSize::Regular.to_pixels(),
+                            ..default()
+                        },
+                        TextColor(SECONDARY_TEXT),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                        Node {
+                            position_type: PositionType::Absolute,
+                            top: Val::Px(CREDITS_VIEW_HEIGHT),
+                            ..default()
+                        },
+                        AboutCredits,
+                    ));
+                });
+
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                        ..default()
+                    },
+                    BackgroundColor(PANEL_BACKGROUND),
+                    CopyBuildInfoButton,
+                ))
+                .with_children(|button| {
+                    button.spawn((
+                        Text::new("Copy build info (C)"),
+                        TextFont {
+                            font_size: FontSize::Regular.to_pixels(),
+                            ..default()
+                        },
+                        TextColor(ENERGY_COLOR),
+                    ));
+                });
+
+            parent.spawn((
+                Text::new("Press F1 to close"),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(SECONDARY_TEXT),
+            ));
+        });
+}
+
+/// Open or close on F1; only the main menu and the pause screen offer it
+fn toggle_about_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    mut about: ResMut<AboutScreen>,
+) {
+    let available = matches!(state.get(), RpgAppState::MainMenu | RpgAppState::Paused);
+    if !available {
+        if about.open {
+            about.open = false;
+        }
+        return;
+    }
+    if keyboard.just_pressed(ABOUT_KEY) {
+        about.open = !about.open;
+        about.scroll = 0.0;
+    }
+}
+
+/// The current run, if one has started
+fn run_info(map_resource: &MapResource, session: Option<&RpgGameSession>) -> Option<RunInfo> {
+    let world_seed = map_resource.overworld().map(|map| map.seed());
+    let session_id = session.map(|session| session.session_start);
+    if world_seed.is_none() && session_id.is_none() {
+        return None;
+    }
+    Some(RunInfo {
+        world_seed,
+        session_id,
+    })
+}
+
+/// Copy the build info on C or a click on the copy button
+fn copy_build_info_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    about: Res<AboutScreen>,
+    buttons: Query<&Interaction, (Changed<Interaction>, With<CopyBuildInfoButton>)>,
+    map_resource: Res<MapResource>,
+    session: Option<Res<RpgGameSession>>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    if !about.open {
+        return;
+    }
+    let clicked = buttons
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed);
+    if !clicked && !keyboard.just_pressed(COPY_BUILD_INFO_KEY) {
+        return;
+    }
+
+    let run = run_info(&map_resource, session.as_deref());
+    let text = BuildInfo::current().copy_text(run.as_ref());
+    match copy_text(&text, BUILD_INFO_FILE) {
+        Ok(CopyOutcome::Clipboard) => game_log.log_message(
+            "📋 Build info copied to the clipboard".to_string(),
+            GameLogType::System,
+        ),
+        Ok(CopyOutcome::File(file)) => game_log.log_message(
+            format!("📋 Build info saved as {}", file),
+            GameLogType::System,
+        ),
+        Err(error) => {
+            warn!("Build info could not be copied: {}", error);
+            game_log.log_message(
+                "📋 The build info could not be copied".to_string(),
+                GameLogType::Warning,
+            );
+        }
+    }
+}
+
+/// Show or hide the screen and fill in the details when it opens
+fn update_about_screen_system(
+    about: Res<AboutScreen>,
+    map_resource: Res<MapResource>,
+    session: Option<Res<RpgGameSession>>,
+    mut roots: Query<&mut Visibility, With<AboutRoot>>,
+    mut details: Query<&mut Text, With<AboutDetailsText>>,
+) {
+    let wanted = if about.open {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    for mut visibility in roots.iter_mut() {
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+    if !about.open || !about.is_changed() {
+        return;
+    }
+
+    let run = run_info(&map_resource, session.as_deref());
+    let lines = BuildInfo::current().lines(run.as_ref()).join("\n");
+    for mut text in details.iter_mut() {
+        text.0 = lines.clone();
+    }
+}
+
+/// Scroll the credits upwards, starting over once they have passed
+fn scroll_credits_system(
+    time: Res<Time>,
+    mut about: ResMut<AboutScreen>,
+    mut credits: Query<(&mut Node, &ComputedNode), With<AboutCredits>>,
+) {
+    if !about.open {
+        return;
+    }
+    let Ok((mut node, computed)) = credits.single_mut() else {
+        return;
+    };
+    let credits_height = computed.size().y * computed.inverse_scale_factor();
+    // Scrolling is not a change worth refreshing the details for
+    let about = about.bypass_change_detection();
+    about.scroll += CREDITS_SCROLL_SPEED * time.delta_secs();
+    if about.scroll > CREDITS_VIEW_HEIGHT + credits_height {
+        about.scroll = 0.0;
+    }
+    node.top = Val::Px(CREDITS_VIEW_HEIGHT - about.scroll);
+}
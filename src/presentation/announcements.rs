@@ -0,0 +1,297 @@
+//! Announcements - Critical events put into words for screen readers
+//!
+//! Blocked moves, event outcomes, low supplies, rests and screens opening
+//! are turned into short announcements straight from the events that feed
+//! the game log, never from the log's own text. The announcement service
+//! filters them by the severity chosen in the accessibility settings and
+//! merges bursts into one line. In the browser each line goes to a hidden
+//! ARIA live region; native builds started with `--narrate` print them to
+//! standard output instead.
+
+use crate::domain::constants::ANNOUNCEMENT_LOW_SUPPLY_UNITS;
+use crate::domain::services::{AnnouncementService, AnnouncementSeverity};
+use crate::domain::value_objects::ResourceType;
+use crate::infrastructure::bevy::resources::PlayerChange;
+use crate::infrastructure::settings::AccessibilitySettings;
+use crate::presentation::game_event_logger::{
+    GameSystemEvent, PlayerChangedEvent, SystemEventSeverity,
+};
+use crate::presentation::movement::{MovementResultApplied, RestingTriggered};
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+
+/// Plugin for the screen reader announcement channel
+pub struct AnnouncementPlugin {
+    /// Print announcements to standard output in native builds
+    pub narrate: bool,
+}
+
+impl Plugin for AnnouncementPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AccessibilitySettings>()
+            .init_resource::<AnnouncementService>()
+            .insert_resource(Narration {
+                stdout: self.narrate,
+            })
+            .add_event::<GameSystemEvent>()
+            .add_event::<PlayerChangedEvent>()
+            .add_event::<MovementResultApplied>()
+            .add_event::<RestingTriggered>()
+            .add_systems(
+                Update,
+                (
+                    sync_announcement_filter,
+                    announce_system_events,
+                    announce_player_changes,
+                    announce_turn_events,
+                    announce_screen_changes,
+                    deliver_announcements,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Where announcements go besides the browser's live region
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct Narration {
+    pub stdout: bool,
+}
+
+/// Check for `--narrate` among command line arguments
+pub fn narrate_from_args<I>(args: I) -> bool
+where
+    I: IntoIterator<Item = String>,
+{
+    args.into_iter().any(|arg| arg == "--narrate")
+}
+
+/// Follow the severity filter of the accessibility settings
+fn sync_announcement_filter(
+    settings: Res<AccessibilitySettings>,
+    mut service: ResMut<AnnouncementService>,
+) {
+    if settings.is_changed() {
+        service.set_filter(settings.announcements);
+    }
+}
+
+/// Blocked moves and other system messages
+fn announce_system_events(
+    time: Res<Time>,
+    mut system_events: EventReader<GameSystemEvent>,
+    mut service: ResMut<AnnouncementService>,
+) {
+    let now = time.elapsed_secs_f64();
+    for event in system_events.read() {
+        let severity = match event.severity {
+            SystemEventSeverity::Info => AnnouncementSeverity::Info,
+            SystemEventSeverity::Warning => AnnouncementSeverity::Important,
+            SystemEventSeverity::Critical => AnnouncementSeverity::Critical,
+        };
+        service.push(&event.message, severity, now);
+    }
+}
+
+/// Low supplies, level ups and the summary of a night of rest
+fn announce_player_changes(
+    time: Res<Time>,
+    mut player_changes: EventReader<PlayerChangedEvent>,
+    mut service: ResMut<AnnouncementService>,
+) {
+    let changes: Vec<PlayerChange> = player_changes
+        .read()
+        .map(|event| event.change.clone())
+        .collect();
+    let now = time.elapsed_secs_f64();
+    for (text, severity) in player_change_announcements(&changes) {
+        service.push(&text, severity, now);
+    }
+}
+
+/// Announcements for one batch of player changes
+///
+/// A batch holding a full restore of movement points is a night of rest;
+/// the resources it brought are summed up with it.
+fn player_change_announcements(changes: &[PlayerChange]) -> Vec<(String, AnnouncementSeverity)> {
+    let mut announcements = Vec::new();
+    let mut restored = None;
+    let mut gained = Vec::new();
+    for change in changes {
+        match change {
+            PlayerChange::MovementPointsRestored { total } => restored = Some(*total),
+            PlayerChange::ResourceChanged {
+                resource_type,
+                delta,
+                ..
+            } if *delta > 0 => gained.push(format!("{} {}", delta, resource_type)),
+            PlayerChange::ResourceChanged {
+                resource_type: resource_type @ (ResourceType::Food | ResourceType::Energy),
+                delta,
+                new_total,
+            } => {
+                let before = *new_total as i64 - *delta as i64;
+                if *new_total == 0 && before > 0 {
+                    announcements.push((
+                        format!("Out of {}", resource_type),
+                        AnnouncementSeverity::Critical,
+                    ));
+                } else if *new_total <= ANNOUNCEMENT_LOW_SUPPLY_UNITS
+                    && before > ANNOUNCEMENT_LOW_SUPPLY_UNITS as i64
+                {
+                    announcements.push((
+                        format!("{} is low: {} left", resource_type, new_total),
+                        AnnouncementSeverity::Critical,
+                    ));
+                }
+            }
+            PlayerChange::ExperienceGained {
+                leveled_up: true, ..
+            } => announcements.push((
+                "Level up! Your character grew stronger".to_string(),
+                AnnouncementSeverity::Important,
+            )),
+            _ => {}
+        }
+    }
+    if let Some(total) = restored {
+        let mut summary = format!("Rested. {} movement points", total);
+        if !gained.is_empty() {
+            summary.push_str(&format!(". Found {}", gained.join(", ")));
+        }
+        announcements.insert(0, (summary, AnnouncementSeverity::Important));
+    }
+    announcements
+}
+
+/// Forced rests and the events moves triggered
+fn announce_turn_events(
+    time: Res<Time>,
+    mut resting_events: EventReader<RestingTriggered>,
+    mut applied_events: EventReader<MovementResultApplied>,
+    mut service: ResMut<AnnouncementService>,
+) {
+    let now = time.elapsed_secs_f64();
+    for _ in resting_events.read() {
+        service.push(
+            "Out of movement points. Resting for the night",
+            AnnouncementSeverity::Important,
+            now,
+        );
+    }
+    for applied in applied_events.read() {
+        if let Some(title) = &applied.event_title {
+            service.push(
+                &format!("Event: {}", title),
+                AnnouncementSeverity::Important,
+                now,
+            );
+        }
+    }
+}
+
+/// Name a screen when it opens, with the keys it answers to
+fn announce_screen_changes(
+    time: Res<Time>,
+    state: Option<Res<State<RpgAppState>>>,
+    mut service: ResMut<AnnouncementService>,
+) {
+    let Some(state) = state.filter(|state| state.is_changed()) else {
+        return;
+    };
+    if let Some(text) = screen_announcement(state.get()) {
+        service.push(text, AnnouncementSeverity::Info, time.elapsed_secs_f64());
+    }
+}
+
+/// What opening a screen sounds like
+fn screen_announcement(state: &RpgAppState) -> Option<&'static str> {
+    match state {
+        RpgAppState::MainMenu => Some("Main menu. Enter: start exploring"),
+        RpgAppState::BaseManagement => Some(
+            "Base opened. Arrows: pick a building. Enter: build or upgrade. E: plan an expedition. Escape: back to exploration",
+        ),
+        RpgAppState::ExpeditionPlanning => Some(
+            "Expedition planner opened. Arrows: move the cursor. Escape: back to the base",
+        ),
+        RpgAppState::QuestLog => Some(
+            "Quest log opened. Tab: board or active quests. Enter: accept. Delete: abandon. Escape: close",
+        ),
+        RpgAppState::Inventory => Some(
+            "Inventory opened. Tab: sort order. C: craft a probe. 1, 2, 3: transfers at the base. Escape: close",
+        ),
+        RpgAppState::EventResolution => {
+            Some("Encounter. Make your choice to move on. Escape: pause")
+        }
+        RpgAppState::Paused => Some("Game paused. Escape: resume, F6: audio settings, K: controls"),
+        RpgAppState::GameOver => Some("Game over"),
+        _ => None,
+    }
+}
+
+/// Hand merged announcements to the screen reader
+fn deliver_announcements(
+    time: Res<Time>,
+    narration: Res<Narration>,
+    mut service: ResMut<AnnouncementService>,
+) {
+    let Some(announcement) = service.flush(time.elapsed_secs_f64()) else {
+        return;
+    };
+    #[cfg(target_arch = "wasm32")]
+    if let Err(e) = crate::infrastructure::web::WebInfrastructure::announce(
+        &announcement.text,
+        announcement.severity == AnnouncementSeverity::Critical,
+    ) {
+        warn!("Failed to announce: {:?}", e);
+    }
+    if narration.stdout {
+        println!("[narrate] {}", announcement.text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rest_and_supply_changes_become_announcements() {
+        let announcements = player_change_announcements(&[
+            PlayerChange::MovementPointsRestored { total: 10 },
+            PlayerChange::ResourceChanged {
+                resource_type: ResourceType::Metal,
+                delta: 3,
+                new_total: 20,
+            },
+            PlayerChange::ResourceChanged {
+                resource_type: ResourceType::Food,
+                delta: -2,
+                new_total: 4,
+            },
+        ]);
+        assert_eq!(announcements.len(), 2);
+        assert!(announcements[0]
+            .0
+            .starts_with("Rested. 10 movement points. Found 3 "));
+        assert_eq!(announcements[1].1, AnnouncementSeverity::Critical);
+
+        // Already low supplies are not announced again on every step
+        let again = player_change_announcements(&[PlayerChange::ResourceChanged {
+            resource_type: ResourceType::Food,
+            delta: -1,
+            new_total: 3,
+        }]);
+        assert!(again.is_empty());
+    }
+
+    #[test]
+    fn narrate_flag_is_read_from_arguments() {
+        let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert!(narrate_from_args(args(&["game", "--narrate"])));
+        assert!(!narrate_from_args(args(&[
+            "game",
+            "--control-port",
+            "7777"
+        ])));
+    }
+}
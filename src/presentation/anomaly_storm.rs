@@ -0,0 +1,287 @@
+//! Anomaly Storms - Forming, drifting and drawing roaming storms
+//!
+//! Each night of rest may brew a storm when the player is in the deep zone,
+//! and moves every active storm along. Storm footprints are drawn as
+//! shimmering translucent tiles over the overworld, recycled through the
+//! tile highlight pool, and summarised on the HUD. The player is never
+//! pushed around: a storm that rolls over or lifts off the player's tile
+//! only changes the cost and risk of the next step.
+
+use crate::domain::constants::{QUANTUM_STORM, WARNING_TEXT};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{NodeChange, WorldHazards};
+use crate::domain::value_objects::Position3D;
+use crate::infrastructure::bevy::resources::{MapResource, PlayerResource};
+use crate::infrastructure::random::streams::{RngStreams, REST};
+use crate::presentation::transient_pool::{TransientKind, TransientPools};
+use crate::presentation::world_tick::{TickCursor, TickPhase, WorldTick, WorldTickSet};
+use bevy::prelude::*;
+
+/// Resting opacity of the storm overlay
+const STORM_OVERLAY_ALPHA: f32 = 0.35;
+
+/// Plugin for roaming anomaly storms
+pub struct AnomalyStormPlugin;
+
+impl Plugin for AnomalyStormPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WorldHazards>()
+            .init_resource::<RngStreams>()
+            .add_systems(Startup, setup_storm_hud)
+            .add_systems(
+                Update,
+                (
+                    storm_rest_system.in_set(WorldTickSet::Hazards),
+                    update_storm_hud,
+                    update_storm_overlay,
+                    shimmer_storm_overlay,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Marker for the storm HUD line
+#[derive(Component)]
+pub struct StormHudText;
+
+/// Marker for a tile of a storm footprint
+#[derive(Component)]
+pub struct StormOverlayTile;
+
+fn setup_storm_hud(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: FontSize::Small.to_pixels(),
+            ..default()
+        },
+        TextColor(QUANTUM_STORM),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(15.0),
+            bottom: Val::Px(90.0),
+            ..default()
+        },
+        StormHudText,
+        Name::new("StormHud"),
+    ));
+}
+
+/// Form and move storms once per night of rest
+fn storm_rest_system(
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    player_resource: Res<PlayerResource>,
+    mut map_resource: ResMut<MapResource>,
+    mut hazards: ResMut<WorldHazards>,
+    mut game_log: ResMut<GameLogService>,
+    rng_streams: ResMut<RngStreams>,
+) {
+    let nights = cursor.take(ticks.read(), TickPhase::AfterRest).len();
+    if nights == 0 {
+        return;
+    }
+
+    // Interior coordinates are not overworld coordinates
+    let player_position = player_resource
+        .player_position()
+        .filter(|_| !map_resource.is_in_interior());
+    let Some(map) = map_resource.overworld.as_mut() else {
+        return;
+    };
+
+    let mut rng = rng_streams.stream(REST);
+    for _ in 0..nights {
+        let was_inside = player_position.is_some_and(|p| hazards.storm_at(p).is_some());
+
+        let report = hazards.tick_rest(map, &mut rng);
+        for center in &report.dissipated {
+            game_log.log_message(
+                format!(
+                    "🌀 The anomaly storm at ({}, {}) dissipates",
+                    center.x, center.y
+                ),
+                GameLogType::Narrative,
+            );
+        }
+        for (position, change) in &report.node_changes {
+            let message = match change {
+                NodeChange::Transmuted => format!(
+                    "✨ The storm transmuted the metal deposit at ({}, {}) into exotic matter",
+                    position.x, position.y
+                ),
+                NodeChange::Depleted => format!(
+                    "🌀 The storm stripped the deposit at ({}, {}) bare",
+                    position.x, position.y
+                ),
+            };
+            game_log.log_message(message, GameLogType::Discovery);
+        }
+
+        if let Some(position) = player_position {
+            if let Some(storm) = hazards.try_form_storm(map, position, &mut rng) {
+                game_log.log_message(
+                    format!(
+                        "🌀 An anomaly storm gathers {} tiles away",
+                        storm.center.manhattan_distance_2d(&position)
+                    ),
+                    GameLogType::Warning,
+                );
+            }
+        }
+
+        let is_inside = player_position.is_some_and(|p| hazards.storm_at(p).is_some());
+        match (was_inside, is_inside) {
+            (false, true) => game_log.log_message(
+                "⚠️ The storm rolls over your position. Every step will be a struggle".to_string(),
+                GameLogType::Warning,
+            ),
+            (true, false) => game_log.log_message(
+                "🌤️ The storm lifts from your position".to_string(),
+                GameLogType::Narrative,
+            ),
+            _ => {}
+        }
+    }
+}
+
+/// Summarise the active storms and warn when the player stands in one
+fn update_storm_hud(
+    hazards: Res<WorldHazards>,
+    player_resource: Res<PlayerResource>,
+    map_resource: Res<MapResource>,
+    mut hud_query: Query<(&mut Text, &mut TextColor), With<StormHudText>>,
+) {
+    let Ok((mut text, mut color)) = hud_query.single_mut() else {
+        return;
+    };
+
+    let in_storm = player_resource
+        .player_position()
+        .filter(|_| !map_resource.is_in_interior())
+        .and_then(|position| hazards.storm_at(position));
+    let (line, line_color) = match (in_storm, hazards.storms().len()) {
+        (Some(storm), _) => (
+            format!(
+                "IN ANOMALY STORM: x2 cost, disadvantage, {} rests left",
+                storm.rests_remaining
+            ),
+            WARNING_TEXT,
+        ),
+        (None, 0) => (String::new(), QUANTUM_STORM),
+        (None, count) => (format!("ANOMALY STORMS: {}", count), QUANTUM_STORM),
+    };
+
+    if **text != line {
+        **text = line;
+    }
+    if color.0 != line_color {
+        color.0 = line_color;
+    }
+}
+
+/// Rebuild the footprint tiles whenever the storms change
+#[allow(clippy::too_many_arguments)]
+fn update_storm_overlay(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    hazards: Res<WorldHazards>,
+    map_resource: Res<MapResource>,
+    mut pools: ResMut<TransientPools>,
+    tiles: Query<Entity, With<StormOverlayTile>>,
+    mut rendered: Local<Vec<Position3D>>,
+) {
+    let wanted = storm_footprints(&hazards, map_resource.is_in_interior());
+    if *rendered == wanted {
+        return;
+    }
+
+    for entity in tiles.iter() {
+        pools.release(&mut commands, entity);
+    }
+
+    if !wanted.is_empty() {
+        let mesh = meshes.add(Mesh::from(Plane3d::default().mesh().size(0.95, 0.95)));
+        let material = materials.add(StandardMaterial {
+            base_color: QUANTUM_STORM.with_alpha(STORM_OVERLAY_ALPHA),
+            emissive: LinearRgba::from(QUANTUM_STORM) * 0.4,
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        });
+        for position in &wanted {
+            let world = crate::presentation::movement::tile_to_world_position(*position);
+            pools.acquire(
+                &mut commands,
+                TransientKind::TileHighlight,
+                (
+                    Mesh3d(mesh.clone()),
+                    MeshMaterial3d(material.clone()),
+                    Transform::from_translation(Vec3::new(world.x, 0.6, world.z)),
+                    StormOverlayTile,
+                    Name::new("StormOverlayTile"),
+                ),
+            );
+        }
+    }
+    *rendered = wanted;
+}
+
+/// Tiles covered by any storm, in a stable order; nothing inside interiors
+fn storm_footprints(hazards: &WorldHazards, in_interior: bool) -> Vec<Position3D> {
+    if in_interior {
+        return Vec::new();
+    }
+    let mut tiles: Vec<Position3D> = hazards
+        .storms()
+        .iter()
+        .flat_map(|storm| storm.footprint())
+        .collect();
+    tiles.sort_by_key(|position| (position.x, position.y));
+    tiles.dedup();
+    tiles
+}
+
+/// Let the overlay shimmer; the tiles share a single material
+fn shimmer_storm_overlay(
+    time: Res<Time>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    tiles: Query<&MeshMaterial3d<StandardMaterial>, With<StormOverlayTile>>,
+) {
+    let Some(handle) = tiles.iter().next() else {
+        return;
+    };
+    let Some(material) = materials.get_mut(&handle.0) else {
+        return;
+    };
+    let alpha = STORM_OVERLAY_ALPHA + 0.15 * (time.elapsed_secs() * 2.5).sin();
+    material.base_color = QUANTUM_STORM.with_alpha(alpha);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::AnomalyStorm;
+    use crate::domain::value_objects::position::Direction;
+
+    #[test]
+    fn footprints_merge_overlapping_storms_and_hide_in_interiors() {
+        let mut hazards = WorldHazards::new();
+        for x in [30, 32] {
+            hazards.add_storm(AnomalyStorm {
+                center: Position3D::new(x, 0, 0),
+                heading: Direction::East,
+                rests_remaining: 5,
+            });
+        }
+
+        let tiles = storm_footprints(&hazards, false);
+        let single = hazards.storms()[0].footprint().len();
+        assert!(tiles.len() < single * 2);
+        assert!(tiles.contains(&Position3D::new(34, 0, 0)));
+        assert!(storm_footprints(&hazards, true).is_empty());
+    }
+}
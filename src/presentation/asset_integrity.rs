@@ -0,0 +1,225 @@
+//! Asset Integrity - Telling the player when the game was deployed incomplete
+//!
+//! At startup every manifest entry is loaded once more and followed until
+//! it settles or the check times out. The report then goes to the game log
+//! as warnings, to the dev console (`query assets`) and into bug reports.
+//! When a required asset is missing or broken, a details screen lists what
+//! failed with instructions to repair the deployment; Enter continues anyway.
+//! If the broken asset is the font, the screen also says that text is shown
+//! with the built-in font. On the web the offline cache status joins the
+//! report once the service worker has answered.
+
+use crate::domain::constants::{HANDOVER_BACKGROUND, PRIMARY_TEXT};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::infrastructure::assets::{
+    asset_file_size, AssetIntegrityReport, AssetKind, AssetSeverity, LoadObservation,
+    ASSET_CHECK_TIMEOUT_SECONDS, ASSET_MANIFEST, ASSET_REPAIR_INSTRUCTIONS,
+};
+use crate::infrastructure::bevy::font_service::BevyFontService;
+use crate::infrastructure::web::offline::OfflineCacheStatus;
+use bevy::prelude::*;
+
+/// Plugin for the startup asset check and its details screen
+pub struct AssetIntegrityPlugin;
+
+impl Plugin for AssetIntegrityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AssetIntegrity>()
+            .add_systems(Startup, (load_manifest_assets, setup_asset_report_screen))
+            .add_systems(
+                Update,
+                (check_asset_integrity_system, asset_report_screen_system).chain(),
+            );
+    }
+}
+
+/// Handles of the manifest assets and the latest report
+#[derive(Resource, Debug, Default)]
+pub struct AssetIntegrity {
+    handles: Vec<(&'static str, UntypedHandle)>,
+    report: AssetIntegrityReport,
+    settled: bool,
+    dismissed: bool,
+    offline_cache: OfflineCacheStatus,
+}
+
+impl AssetIntegrity {
+    /// Latest report; entries still loading are pending until it settles
+    pub fn report(&self) -> &AssetIntegrityReport {
+        &self.report
+    }
+
+    /// Whether every asset finished loading or the check timed out
+    pub fn is_settled(&self) -> bool {
+        self.settled
+    }
+
+    /// What the service worker has cached for offline play
+    pub fn offline_cache(&self) -> &OfflineCacheStatus {
+        &self.offline_cache
+    }
+
+    /// Record the latest offline cache status
+    pub fn record_offline_cache(&mut self, status: OfflineCacheStatus) {
+        self.offline_cache = status;
+    }
+
+    /// The report lines followed by the offline cache status
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = self.report.lines();
+        lines.push(self.offline_cache.describe());
+        lines
+    }
+}
+
+/// Marker for the asset report screen
+#[derive(Component)]
+pub struct AssetReportScreen;
+
+/// Marker for the asset report screen text
+#[derive(Component)]
+pub struct AssetReportText;
+
+fn load_manifest_assets(asset_server: Res<AssetServer>, mut integrity: ResMut<AssetIntegrity>) {
+    integrity.handles = ASSET_MANIFEST
+        .iter()
+        .map(|entry| {
+            let handle = match entry.kind {
+                AssetKind::Font => asset_server.load::<Font>(entry.path).untyped(),
+                AssetKind::Audio => asset_server.load::<AudioSource>(entry.path).untyped(),
+                AssetKind::Image => asset_server.load::<Image>(entry.path).untyped(),
+            };
+            (entry.path, handle)
+        })
+        .collect();
+}
+
+fn setup_asset_report_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                top: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                padding: UiRect::all(Val::Px(40.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(HANDOVER_BACKGROUND),
+            // Above everything, including the handover screen
+            GlobalZIndex(20),
+            Visibility::Hidden,
+            AssetReportScreen,
+            Name::new("AssetReportScreen"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Medium.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                AssetReportText,
+            ));
+        });
+}
+
+/// Follow the manifest assets until they settle, then report once
+fn check_asset_integrity_system(
+    mut integrity: ResMut<AssetIntegrity>,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    if integrity.settled || integrity.handles.is_empty() {
+        return;
+    }
+
+    let timed_out = time.elapsed_secs() >= ASSET_CHECK_TIMEOUT_SECONDS;
+    let observe = |path: &str| {
+        integrity
+            .handles
+            .iter()
+            .find(|(handle_path, _)| *handle_path == path)
+            .map_or(LoadObservation::Pending, |(_, handle)| {
+                LoadObservation::from_load_state(&asset_server.load_state(handle.id()))
+            })
+    };
+    let report = AssetIntegrityReport::build(ASSET_MANIFEST, timed_out, |entry| {
+        (observe(entry.path), None)
+    });
+    if !report.is_settled() {
+        integrity.report = report;
+        return;
+    }
+
+    // Sizes are only read once, when nothing is loading anymore
+    let report = AssetIntegrityReport::build(ASSET_MANIFEST, timed_out, |entry| {
+        (observe(entry.path), asset_file_size(entry.path))
+    });
+    info!("Asset check: {}", report.summary());
+    for problem in report.problems() {
+        if problem.severity() == AssetSeverity::Blocking {
+            error!("Asset check: {}", problem.describe());
+        } else {
+            warn!("Asset check: {}", problem.describe());
+        }
+        game_log.log_message(
+            format!("⚠️ Asset check: {}", problem.describe()),
+            GameLogType::Warning,
+        );
+    }
+    integrity.report = report;
+    integrity.settled = true;
+}
+
+/// Show the details screen while a required asset is broken
+fn asset_report_screen_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut integrity: ResMut<AssetIntegrity>,
+    font_service: Option<Res<BevyFontService>>,
+    mut screens: Query<&mut Visibility, With<AssetReportScreen>>,
+    mut texts: Query<&mut Text, With<AssetReportText>>,
+) {
+    let blocking = integrity.settled && integrity.report.is_blocking();
+    if blocking && !integrity.dismissed && keyboard.just_pressed(KeyCode::Enter) {
+        integrity.dismissed = true;
+    }
+    let visible = blocking && !integrity.dismissed;
+
+    for mut visibility in screens.iter_mut() {
+        let wanted = if visible {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+    let font_changed = font_service
+        .as_ref()
+        .is_some_and(|service| service.is_changed());
+    if !visible || !(integrity.is_changed() || font_changed) {
+        return;
+    }
+    let font_note = if font_service.is_some_and(|service| service.is_fallback_active()) {
+        "\n\nText is shown with the built-in font; symbols appear as plain words."
+    } else {
+        ""
+    };
+    let details = format!(
+        "⚠️ The game is missing files it needs\n\n{}\n\n{}{}\n\nPress Enter to continue anyway",
+        integrity.report.lines().join("\n"),
+        ASSET_REPAIR_INSTRUCTIONS,
+        font_note
+    );
+    for mut text in texts.iter_mut() {
+        text.0 = details.clone();
+    }
+}
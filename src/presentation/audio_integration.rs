@@ -4,11 +4,20 @@
 //! It handles loading audio assets and playing sounds directly without complex service layers.
 
 use crate::domain::constants::*;
+use crate::domain::services::{
+    AudioMemory, MusicOverrideStack, ResumeAction, ResumePoint, TrackRestore,
+};
 use crate::domain::value_objects::terrain::TerrainType;
+use crate::presentation::clocks::{SimClock, WallClock};
 use crate::presentation::game_event_logger::{
     DiscoveryEvent, GameSystemEvent, MovementAttemptEvent, ResourceChangedEvent, RestCompletedEvent,
 };
+use crate::presentation::game_state::RpgGameSession;
+use crate::presentation::transient_pool::{play_pooled_sfx, TransientPools};
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Event to trigger music adaptation to progression
 #[derive(Event)]
@@ -35,6 +44,500 @@ pub enum AreaType {
     Anomaly,
 }
 
+/// Layers of a stemmed music track, in descriptor order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MusicStem {
+    /// Always audible
+    Base,
+    /// Fades in as danger rises
+    Tension,
+    /// Fades in when danger is high
+    Percussion,
+}
+
+impl MusicStem {
+    /// Get all stems in descriptor order
+    pub fn all() -> [MusicStem; 3] {
+        [MusicStem::Base, MusicStem::Tension, MusicStem::Percussion]
+    }
+
+    /// Gain of this stem at a danger level, from 0.0 (silent) to 1.0
+    pub fn gain(&self, danger_level: f32) -> f32 {
+        let fade_in =
+            |threshold: f32| ((danger_level - threshold) / MUSIC_STEM_FADE_WIDTH).clamp(0.0, 1.0);
+        match self {
+            MusicStem::Base => 1.0,
+            MusicStem::Tension => fade_in(MUSIC_STEM_TENSION_THRESHOLD),
+            MusicStem::Percussion => fade_in(MUSIC_STEM_PERCUSSION_THRESHOLD),
+        }
+    }
+}
+
+/// Move `current` towards `target` by at most `max_step`
+pub fn ramp_towards(current: f32, target: f32, max_step: f32) -> f32 {
+    let difference = target - current;
+    if difference.abs() <= max_step {
+        target
+    } else {
+        current + max_step.copysign(difference)
+    }
+}
+
+/// Loaded audio of one playlist entry
+#[derive(Debug, Clone, Default)]
+pub struct MusicTrackAssets {
+    /// Handles in `MusicStem` order; a single handle is a plain track
+    pub layers: Vec<Handle<AudioSource>>,
+}
+
+impl MusicTrackAssets {
+    /// Check if the track is made of several stems
+    pub fn is_layered(&self) -> bool {
+        self.layers.len() > 1
+    }
+
+    /// Load state of the whole track: failed if any layer failed, loaded once all are
+    fn load_state(&self, asset_server: &AssetServer) -> bevy::asset::LoadState {
+        let states: Vec<_> = self
+            .layers
+            .iter()
+            .map(|handle| asset_server.load_state(handle.id()))
+            .collect();
+        states
+            .iter()
+            .find(|state| matches!(state, bevy::asset::LoadState::Failed(_)))
+            .or_else(|| {
+                states
+                    .iter()
+                    .find(|state| !matches!(state, bevy::asset::LoadState::Loaded))
+            })
+            .cloned()
+            .unwrap_or(bevy::asset::LoadState::Loaded)
+    }
+}
+
+/// One playing stem of a layered track
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MusicLayer {
+    pub stem: MusicStem,
+    pub entity: Entity,
+    /// Current gain, ramped towards the danger target
+    pub gain: f32,
+    /// Seconds this stem has played, counted from `Time` while its sink runs
+    pub played: f32,
+}
+
+/// Categories of sound that can be switched on and off independently
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioCategory {
+    Music,
+    Ambient,
+    Sfx,
+    Ui,
+}
+
+impl AudioCategory {
+    /// Get all audio categories
+    pub fn all() -> [AudioCategory; 4] {
+        [
+            AudioCategory::Music,
+            AudioCategory::Ambient,
+            AudioCategory::Sfx,
+            AudioCategory::Ui,
+        ]
+    }
+
+    /// Name shown in the audio settings
+    pub fn label(&self) -> &'static str {
+        match self {
+            AudioCategory::Music => "Music",
+            AudioCategory::Ambient => "Ambient",
+            AudioCategory::Sfx => "Effects",
+            AudioCategory::Ui => "Interface",
+        }
+    }
+}
+
+/// Categories the current runtime situation lets through
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AudioScope {
+    /// Every enabled category plays
+    #[default]
+    Full,
+    /// Only the ambient bed plays, e.g. while the page is hidden
+    AmbientOnly,
+    /// Nothing plays
+    Silent,
+}
+
+impl AudioScope {
+    /// Check whether the scope lets a category through
+    pub fn allows(&self, category: AudioCategory) -> bool {
+        match self {
+            AudioScope::Full => true,
+            AudioScope::AmbientOnly => category == AudioCategory::Ambient,
+            AudioScope::Silent => false,
+        }
+    }
+}
+
+/// Global audio switches consulted by every playback helper
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct GlobalAudioSettings {
+    /// False when no audio output device could be opened
+    pub device_available: bool,
+    /// Runtime limit on top of the user's switches; never persisted
+    pub scope: AudioScope,
+    pub music_enabled: bool,
+    pub ambient_enabled: bool,
+    pub sfx_enabled: bool,
+    pub ui_enabled: bool,
+}
+
+impl Default for GlobalAudioSettings {
+    fn default() -> Self {
+        Self {
+            device_available: true,
+            scope: AudioScope::Full,
+            music_enabled: true,
+            ambient_enabled: true,
+            sfx_enabled: true,
+            ui_enabled: true,
+        }
+    }
+}
+
+impl GlobalAudioSettings {
+    /// Check whether the user has enabled a category
+    pub fn is_category_enabled(&self, category: AudioCategory) -> bool {
+        match category {
+            AudioCategory::Music => self.music_enabled,
+            AudioCategory::Ambient => self.ambient_enabled,
+            AudioCategory::Sfx => self.sfx_enabled,
+            AudioCategory::Ui => self.ui_enabled,
+        }
+    }
+
+    /// Enable or disable a category
+    pub fn set_category_enabled(&mut self, category: AudioCategory, enabled: bool) {
+        match category {
+            AudioCategory::Music => self.music_enabled = enabled,
+            AudioCategory::Ambient => self.ambient_enabled = enabled,
+            AudioCategory::Sfx => self.sfx_enabled = enabled,
+            AudioCategory::Ui => self.ui_enabled = enabled,
+        }
+    }
+
+    /// Flip a category and return its new state
+    pub fn toggle_category(&mut self, category: AudioCategory) -> bool {
+        let enabled = !self.is_category_enabled(category);
+        self.set_category_enabled(category, enabled);
+        enabled
+    }
+
+    /// Check whether sounds of a category should actually be played
+    pub fn can_play(&self, category: AudioCategory) -> bool {
+        self.device_available && self.scope.allows(category) && self.is_category_enabled(category)
+    }
+}
+
+/// Volume levels the player can set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VolumeChannel {
+    Master,
+    Music,
+    Sfx,
+    Ambient,
+}
+
+impl VolumeChannel {
+    /// Every channel, in menu order
+    pub const ALL: [VolumeChannel; 4] = [
+        VolumeChannel::Master,
+        VolumeChannel::Music,
+        VolumeChannel::Sfx,
+        VolumeChannel::Ambient,
+    ];
+
+    /// Name shown in the audio settings
+    pub fn label(&self) -> &'static str {
+        match self {
+            VolumeChannel::Master => "Master",
+            VolumeChannel::Music => "Music",
+            VolumeChannel::Sfx => "Effects",
+            VolumeChannel::Ambient => "Ambient",
+        }
+    }
+}
+
+/// Volume levels chosen by the player, each from 0.0 to 1.0
+///
+/// Levels scale the volume the game picks for every sound rather than
+/// replacing it, so the mix stays balanced at any level. Muting keeps the
+/// levels, and unmuting brings back exactly what was set before.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub ambient_volume: f32,
+    pub muted: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+            ambient_volume: 1.0,
+            muted: false,
+        }
+    }
+}
+
+impl AudioSettings {
+    /// Level set for a channel
+    pub fn level(&self, channel: VolumeChannel) -> f32 {
+        match channel {
+            VolumeChannel::Master => self.master_volume,
+            VolumeChannel::Music => self.music_volume,
+            VolumeChannel::Sfx => self.sfx_volume,
+            VolumeChannel::Ambient => self.ambient_volume,
+        }
+    }
+
+    /// Move a channel's level by `delta`, unmuting so the change is heard
+    pub fn adjust(&mut self, channel: VolumeChannel, delta: f32) {
+        let slot = match channel {
+            VolumeChannel::Master => &mut self.master_volume,
+            VolumeChannel::Music => &mut self.music_volume,
+            VolumeChannel::Sfx => &mut self.sfx_volume,
+            VolumeChannel::Ambient => &mut self.ambient_volume,
+        };
+        // Rounded to whole percent so repeated steps land on the same levels
+        *slot = ((*slot + delta).clamp(0.0, 1.0) * 100.0).round() / 100.0;
+        self.muted = false;
+    }
+
+    /// Mute or unmute everything and return whether sound is now muted
+    pub fn toggle_mute(&mut self) -> bool {
+        self.muted = !self.muted;
+        self.muted
+    }
+
+    /// Factor applied to sounds of a category; UI sounds follow effects
+    pub fn gain(&self, category: AudioCategory) -> f32 {
+        if self.muted {
+            return 0.0;
+        }
+        let channel = match category {
+            AudioCategory::Music => VolumeChannel::Music,
+            AudioCategory::Ambient => VolumeChannel::Ambient,
+            AudioCategory::Sfx | AudioCategory::Ui => VolumeChannel::Sfx,
+        };
+        self.master_volume.clamp(0.0, 1.0) * self.level(channel).clamp(0.0, 1.0)
+    }
+}
+
+/// Priority of a one-shot sound when a frame requests more than the budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SfxPriority {
+    Ui,
+    Effect,
+    Resource,
+    Discovery,
+    /// Cue of a low health or starvation warning beginning
+    Warning,
+    /// Stinger of an encounter beginning
+    Encounter,
+}
+
+/// A one-shot sound waiting for the end-of-frame drain
+#[derive(Debug, Clone)]
+pub struct SfxRequest {
+    pub handle: Handle<AudioSource>,
+    pub playback: PlaybackSettings,
+    /// Category whose volume level the sound is played at
+    pub category: AudioCategory,
+    pub priority: SfxPriority,
+}
+
+impl SfxRequest {
+    fn key(&self) -> AssetId<AudioSource> {
+        self.handle.id()
+    }
+
+    fn loudness(&self) -> f32 {
+        self.playback.volume.to_linear()
+    }
+}
+
+/// Collects one-shot sound requests and decides once per frame which start
+///
+/// Several systems react to the same movement in the same frame; starting
+/// the same sample twice sample-aligned produces a loud doubled transient.
+/// Requests for the same sample within a frame collapse into the loudest,
+/// a sample does not restart within the retrigger interval, and at most
+/// `max_per_frame` new sounds start per frame, highest priority first.
+#[derive(Resource, Debug, Clone)]
+pub struct SfxArbiter {
+    max_per_frame: usize,
+    retrigger_interval: f32,
+    pending: Vec<SfxRequest>,
+    last_started: HashMap<AssetId<AudioSource>, f32>,
+}
+
+impl Default for SfxArbiter {
+    fn default() -> Self {
+        Self::new(SFX_MAX_NEW_PER_FRAME, SFX_RETRIGGER_INTERVAL_SECONDS)
+    }
+}
+
+impl SfxArbiter {
+    /// Create an arbiter with a per-frame budget and retrigger interval (seconds)
+    pub fn new(max_per_frame: usize, retrigger_interval: f32) -> Self {
+        Self {
+            max_per_frame,
+            retrigger_interval,
+            pending: Vec::new(),
+            last_started: HashMap::new(),
+        }
+    }
+
+    /// Register a sound for this frame, merging duplicates of the same sample
+    pub fn request(
+        &mut self,
+        handle: &Handle<AudioSource>,
+        playback: PlaybackSettings,
+        category: AudioCategory,
+        priority: SfxPriority,
+    ) {
+        let request = SfxRequest {
+            handle: handle.clone(),
+            playback,
+            category,
+            priority,
+        };
+        match self.pending.iter_mut().find(|p| p.key() == request.key()) {
+            Some(existing) => {
+                existing.priority = existing.priority.max(request.priority);
+                if request.loudness() > existing.loudness() {
+                    existing.playback = request.playback;
+                }
+            }
+            None => self.pending.push(request),
+        }
+    }
+
+    /// Number of distinct sounds requested this frame
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Take the sounds that should start now, `now` being elapsed seconds
+    pub fn drain(&mut self, now: f32) -> Vec<SfxRequest> {
+        let mut ready: Vec<SfxRequest> = self
+            .pending
+            .drain(..)
+            .filter(|request| {
+                self.last_started
+                    .get(&request.key())
+                    .is_none_or(|last| now - last >= self.retrigger_interval)
+            })
+            .collect();
+
+        // Stable sort keeps request order within a priority
+        ready.sort_by_key(|request| std::cmp::Reverse(request.priority));
+        ready.truncate(self.max_per_frame);
+
+        for request in &ready {
+            self.last_started.insert(request.key(), now);
+        }
+        ready
+    }
+}
+
+/// Request a one-shot sound if the category may play
+///
+/// The sound starts at the end of the frame unless the arbiter drops it.
+pub fn play_audio(
+    sfx: &mut SfxArbiter,
+    settings: &GlobalAudioSettings,
+    category: AudioCategory,
+    priority: SfxPriority,
+    handle: &Handle<AudioSource>,
+    playback: PlaybackSettings,
+) -> bool {
+    if !settings.can_play(category) {
+        return false;
+    }
+    sfx.request(handle, playback, category, priority);
+    true
+}
+
+/// Request a one-shot sound effect at the default effect volume
+pub fn spawn_world_sfx(
+    sfx: &mut SfxArbiter,
+    settings: &GlobalAudioSettings,
+    category: AudioCategory,
+    priority: SfxPriority,
+    handle: &Handle<AudioSource>,
+) -> bool {
+    play_audio(
+        sfx,
+        settings,
+        category,
+        priority,
+        handle,
+        PlaybackSettings::ONCE.with_volume(bevy::audio::Volume::Linear(
+            crate::domain::constants::DEFAULT_SFX_VOLUME,
+        )),
+    )
+}
+
+/// Start the sounds the arbiter accepted this frame, at the player's levels
+fn drain_sfx_requests(
+    mut commands: Commands,
+    mut sfx: ResMut<SfxArbiter>,
+    mut pools: ResMut<TransientPools>,
+    volumes: Res<AudioSettings>,
+    time: Res<Time>,
+) {
+    if sfx.pending_count() == 0 {
+        return;
+    }
+    for request in sfx.drain(time.elapsed_secs()) {
+        let volume = request.loudness() * volumes.gain(request.category);
+        if volume <= 0.0 {
+            continue;
+        }
+        let playback = request
+            .playback
+            .with_volume(bevy::audio::Volume::Linear(volume));
+        play_pooled_sfx(&mut commands, &mut pools, request.handle, playback);
+    }
+}
+
+/// Marker for the silent sink used to detect a working audio device
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Component)]
+struct AudioDeviceProbe;
+
+/// Progress of the audio device probe
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+enum AudioProbeState {
+    #[default]
+    WaitingForAsset,
+    Probing {
+        entity: Entity,
+        started: f32,
+    },
+    Done,
+}
+
 /// Plugin for audio event integration
 pub struct AudioEventIntegrationPlugin;
 
@@ -42,6 +545,10 @@ impl Plugin for AudioEventIntegrationPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<AudioAssets>()
             .init_resource::<MusicManager>()
+            .init_resource::<GlobalAudioSettings>()
+            .init_resource::<AudioSettings>()
+            .init_resource::<SfxArbiter>()
+            .init_resource::<PendingAudioRestore>()
             .add_event::<MusicProgressionEvent>()
             .add_event::<TerrainChangeEvent>()
             .add_systems(Startup, (setup_audio_assets, setup_initial_terrain))
@@ -54,12 +561,23 @@ impl Plugin for AudioEventIntegrationPlugin {
                     handle_rest_audio,
                     handle_system_audio,
                     monitor_audio_status,
-                    manage_music_playlist,
+                    (restore_audio_memory, manage_music_playlist).chain(),
+                    remember_audio_system,
                     handle_music_progression_events,
+                    (mix_encounter_music, ramp_music_stems, sync_music_stems).chain(),
                     handle_terrain_change_events,
                     retry_ambient_music_loading,
+                    apply_audio_settings_changes,
+                    apply_volume_settings,
+                    crate::presentation::delayed_audio::delayed_audio_system,
                 ),
-            );
+            )
+            // Runs after every Update system has registered its sounds
+            .add_systems(PostUpdate, drain_sfx_requests);
+
+        // Browsers always expose an output; only native builds can lack a device
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(Update, probe_audio_device);
     }
 }
 
@@ -73,8 +591,16 @@ pub struct AudioAssets {
     pub ui_click: Option<Handle<AudioSource>>,
     pub resource_collect: Option<Handle<AudioSource>>,
     pub rest_complete: Option<Handle<AudioSource>>,
-    // Random music playlist
-    pub music_tracks: Vec<Handle<AudioSource>>,
+    /// Short cue played when an encounter begins
+    pub encounter_stinger: Option<Handle<AudioSource>>,
+    /// Loop for long fights when the track has no percussion stem
+    pub combat_loop: Option<Handle<AudioSource>>,
+    /// Cue played when a danger warning begins
+    pub warning_cue: Option<Handle<AudioSource>>,
+    /// Loop playing while a danger warning lasts
+    pub warning_heartbeat: Option<Handle<AudioSource>>,
+    // Random music playlist, each entry a single file or a set of stems
+    pub music_tracks: Vec<MusicTrackAssets>,
     // Terrain-specific ambient sounds
     pub ambient_sounds: std::collections::HashMap<String, Handle<AudioSource>>,
 }
@@ -83,16 +609,37 @@ pub struct AudioAssets {
 #[derive(Resource)]
 pub struct MusicManager {
     pub current_ambient: Option<Entity>,
+    /// Single-file track, or the base stem of a layered one
     pub current_music: Option<Entity>,
+    /// Every stem of the current layered track; empty for single-file tracks
+    pub music_layers: Vec<MusicLayer>,
     pub last_track_index: Option<usize>,
     pub music_change_timer: Timer,
     pub ambient_volume: f32,
     pub music_volume: f32,
+    /// Area volume for stems; danger is expressed by the stem mix instead
+    pub layered_music_volume: f32,
+    /// Player level for music, from `AudioSettings`
+    pub music_gain: f32,
+    /// Player level for the ambient bed, from `AudioSettings`
+    pub ambient_gain: f32,
     pub danger_level: f32,
     pub current_area: AreaType,
     pub current_terrain: Option<crate::domain::value_objects::terrain::TerrainType>,
     pub last_logged_status: String,
     pub last_ambient_retry_status: String,
+    /// Encounters holding the music
+    pub overrides: MusicOverrideStack,
+    /// Encounter gain of the playlist track, ramped towards the override mix
+    pub track_gain: f32,
+    /// Rate `track_gain` currently ramps at
+    pub track_fade_per_second: f32,
+    pub combat_loop: Option<Entity>,
+    pub combat_loop_gain: f32,
+    /// Track faded out for the combat loop, to bring back afterwards
+    pub resume_point: Option<ResumePoint>,
+    /// Seconds the current track has played, counted from `Time` while its sink runs
+    pub track_played: f32,
 }
 
 impl Default for MusicManager {
@@ -102,6 +649,7 @@ impl Default for MusicManager {
         Self {
             current_ambient: None,
             current_music: None,
+            music_layers: Vec::new(),
             last_track_index: None,
             music_change_timer: Timer::from_seconds(
                 crate::domain::constants::MUSIC_CHANGE_INTERVAL_SECONDS,
@@ -109,15 +657,182 @@ impl Default for MusicManager {
             ),
             ambient_volume: crate::domain::constants::DEFAULT_AMBIENT_VOLUME,
             music_volume: crate::domain::constants::DEFAULT_MUSIC_VOLUME,
+            layered_music_volume: crate::domain::constants::DEFAULT_MUSIC_VOLUME,
+            music_gain: 1.0,
+            ambient_gain: 1.0,
             danger_level: 0.0,
             current_area: AreaType::Space,
             current_terrain: None,
             last_logged_status: String::new(),
             last_ambient_retry_status: String::new(),
+            overrides: MusicOverrideStack::default(),
+            track_gain: 1.0,
+            track_fade_per_second: MUSIC_ENCOUNTER_FADE_PER_SECOND,
+            combat_loop: None,
+            combat_loop_gain: 0.0,
+            resume_point: None,
+            track_played: 0.0,
+        }
+    }
+}
+
+impl MusicManager {
+    /// Release music and ambient players whose category may no longer play
+    ///
+    /// Returns the entities to despawn. With the slots cleared, the playlist
+    /// and ambient retry systems start fresh players once the category is
+    /// enabled again.
+    pub fn silence_disabled(&mut self, settings: &GlobalAudioSettings) -> Vec<Entity> {
+        let mut silenced = Vec::new();
+        if !settings.can_play(AudioCategory::Music) {
+            silenced.extend(self.take_music());
+            silenced.extend(self.combat_loop.take());
+            self.resume_point = None;
+        }
+        if !settings.can_play(AudioCategory::Ambient) {
+            silenced.extend(self.current_ambient.take());
+        }
+        silenced
+    }
+
+    /// Release the current track, returning every entity playing it
+    pub fn take_music(&mut self) -> Vec<Entity> {
+        let mut entities: Vec<Entity> = self.current_music.take().into_iter().collect();
+        for layer in self.music_layers.drain(..) {
+            if !entities.contains(&layer.entity) {
+                entities.push(layer.entity);
+            }
+        }
+        entities
+    }
+
+    /// Players to start for a track: one per stem, or just the file
+    ///
+    /// Stems start at the gain of the current danger level; a single-file
+    /// track keeps the plain music volume.
+    pub fn plan_track(
+        &self,
+        track: &MusicTrackAssets,
+    ) -> Vec<(MusicStem, Handle<AudioSource>, f32)> {
+        if !track.is_layered() {
+            return track
+                .layers
+                .first()
+                .map(|handle| (MusicStem::Base, handle.clone(), self.music_volume))
+                .into_iter()
+                .collect();
         }
+        MusicStem::all()
+            .into_iter()
+            .zip(track.layers.iter())
+            .map(|(stem, handle)| {
+                let gain = stem.gain(self.danger_level);
+                (stem, handle.clone(), self.layered_music_volume * gain)
+            })
+            .collect()
+    }
+
+    /// Check if the playlist waits for a fight to end before playing on
+    pub fn is_track_parked(&self) -> bool {
+        self.resume_point.is_some() || self.combat_loop.is_some()
+    }
+
+    /// Ramp the track and combat loop gains towards the override mix
+    ///
+    /// Returns true if any gain moved. Once the track is back at its target
+    /// the normal fade rate applies again.
+    pub fn step_encounter_gains(&mut self, delta: f32) -> bool {
+        let mix = self.overrides.mix();
+        let track_gain = ramp_towards(
+            self.track_gain,
+            mix.track,
+            self.track_fade_per_second * delta,
+        );
+        let loop_gain = ramp_towards(
+            self.combat_loop_gain,
+            mix.combat_loop,
+            MUSIC_ENCOUNTER_FADE_PER_SECOND * delta,
+        );
+        let changed = track_gain != self.track_gain || loop_gain != self.combat_loop_gain;
+        self.track_gain = track_gain;
+        self.combat_loop_gain = loop_gain;
+        if track_gain == mix.track {
+            self.track_fade_per_second = MUSIC_ENCOUNTER_FADE_PER_SECOND;
+        }
+        changed
+    }
+
+    /// Ramp every stem towards its danger target over `delta` seconds
+    ///
+    /// Returns true if any gain moved.
+    pub fn step_stem_gains(&mut self, delta: f32) -> bool {
+        let max_step = MUSIC_STEM_RAMP_PER_SECOND * delta;
+        let danger_level = self.danger_level;
+        let mut changed = false;
+        for layer in &mut self.music_layers {
+            let gain = ramp_towards(layer.gain, layer.stem.gain(danger_level), max_step);
+            changed |= gain != layer.gain;
+            layer.gain = gain;
+        }
+        changed
     }
 }
 
+/// Spawn the players of a track together so stems start in sync
+fn start_music_track(
+    commands: &mut Commands,
+    music_manager: &mut MusicManager,
+    track_index: usize,
+    track: &MusicTrackAssets,
+) {
+    let layered = track.is_layered();
+    let mut layers = Vec::new();
+    for (stem, handle, volume) in music_manager.plan_track(track) {
+        let entity = commands
+            .spawn((
+                AudioPlayer::new(handle),
+                PlaybackSettings::ONCE.with_volume(bevy::audio::Volume::Linear(
+                    volume * music_manager.track_gain * music_manager.music_gain,
+                )),
+            ))
+            .id();
+        if stem == MusicStem::Base {
+            music_manager.current_music = Some(entity);
+        }
+        if layered {
+            layers.push(MusicLayer {
+                stem,
+                entity,
+                gain: stem.gain(music_manager.danger_level),
+                played: 0.0,
+            });
+        }
+    }
+    music_manager.music_layers = layers;
+    music_manager.last_track_index = Some(track_index);
+    music_manager.track_played = 0.0;
+}
+
+/// Bring a track parked for the combat loop back by starting its players again
+///
+/// Sinks cannot seek, so the track plays from the top and fades in slowly
+/// instead of cutting in mid-phrase at full volume.
+fn resume_music_track(
+    commands: &mut Commands,
+    music_manager: &mut MusicManager,
+    resume: ResumePoint,
+    track: &MusicTrackAssets,
+) {
+    start_music_track(commands, music_manager, resume.track_index, track);
+    music_manager.music_change_timer.reset();
+    let (ResumeAction::Seek {
+        fade_per_second, ..
+    }
+    | ResumeAction::Restart { fade_per_second }) = resume.action(false);
+    info!("🎵 Bringing the track back from the top");
+    music_manager.track_fade_per_second = fade_per_second;
+}
+
 fn setup_audio_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
     info!("🎵 Loading audio assets...");
 
@@ -154,6 +869,10 @@ fn setup_audio_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
         ui_click: Some(asset_server.load(AUDIO_UI_CLICK)),
         resource_collect: Some(asset_server.load(AUDIO_RESOURCE_FOUND)),
         rest_complete: Some(asset_server.load(AUDIO_REST_COMPLETE)),
+        encounter_stinger: Some(asset_server.load(AUDIO_ENCOUNTER_STINGER)),
+        combat_loop: Some(asset_server.load(AUDIO_COMBAT_LOOP)),
+        warning_cue: Some(asset_server.load(AUDIO_UI_WARNING)),
+        warning_heartbeat: Some(asset_server.load(AUDIO_WARNING_HEARTBEAT)),
         // Load random music playlist using helper function
         music_tracks: load_music_playlist(&asset_server),
         // Terrain-specific ambient sounds
@@ -165,20 +884,42 @@ fn setup_audio_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
 }
 
 /// Helper function to load music playlist - easy to expand
-fn load_music_playlist(asset_server: &AssetServer) -> Vec<Handle<AudioSource>> {
+///
+/// Entries with stems in `AUDIO_MUSIC_STEMS` load up to one file per stem
+/// instead of the playlist file.
+fn load_music_playlist(asset_server: &AssetServer) -> Vec<MusicTrackAssets> {
     AUDIO_MUSIC_PLAYLIST
         .iter()
-        .map(|file| asset_server.load(*file))
+        .enumerate()
+        .map(|(index, file)| {
+            let stems = AUDIO_MUSIC_STEMS.get(index).copied().unwrap_or(&[]);
+            let layers = if stems.is_empty() {
+                vec![asset_server.load(*file)]
+            } else {
+                stems
+                    .iter()
+                    .take(MusicStem::all().len())
+                    .map(|stem| asset_server.load(*stem))
+                    .collect()
+            };
+            MusicTrackAssets { layers }
+        })
         .collect()
 }
 
 /// System to handle movement-related audio
 fn handle_movement_audio(
-    mut commands: Commands,
+    mut sfx: ResMut<SfxArbiter>,
     mut movement_events: EventReader<MovementAttemptEvent>,
     audio_assets: Res<AudioAssets>,
+    audio_settings: Res<GlobalAudioSettings>,
     asset_server: Res<AssetServer>,
 ) {
+    if !audio_settings.can_play(AudioCategory::Sfx) {
+        movement_events.clear();
+        return;
+    }
+
     for event in movement_events.read() {
         info!(
             "🎵 Processing movement audio event: success={}",
@@ -190,12 +931,13 @@ fn handle_movement_audio(
             if let Some(dice_handle) = &audio_assets.dice_roll {
                 let load_state = asset_server.load_state(dice_handle.id());
                 info!("🎲 Playing dice roll sound (state: {:?})", load_state);
-                commands.spawn((
-                    AudioPlayer::new(dice_handle.clone()),
-                    PlaybackSettings::ONCE.with_volume(bevy::audio::Volume::Linear(
-                        crate::domain::constants::DEFAULT_SFX_VOLUME,
-                    )),
-                ));
+                spawn_world_sfx(
+                    &mut sfx,
+                    &audio_settings,
+                    AudioCategory::Sfx,
+                    SfxPriority::Effect,
+                    dice_handle,
+                );
             } else {
                 warn!("🎲 No dice roll audio handle available!");
             }
@@ -206,12 +948,13 @@ fn handle_movement_audio(
             if let Some(step_handle) = &audio_assets.movement_step {
                 let load_state = asset_server.load_state(step_handle.id());
                 info!("👟 Playing footstep sound (state: {:?})", load_state);
-                commands.spawn((
-                    AudioPlayer::new(step_handle.clone()),
-                    PlaybackSettings::ONCE.with_volume(bevy::audio::Volume::Linear(
-                        crate::domain::constants::DEFAULT_SFX_VOLUME,
-                    )),
-                ));
+                spawn_world_sfx(
+                    &mut sfx,
+                    &audio_settings,
+                    AudioCategory::Sfx,
+                    SfxPriority::Effect,
+                    step_handle,
+                );
             } else {
                 warn!("👟 No footstep audio handle available!");
             }
@@ -221,11 +964,17 @@ fn handle_movement_audio(
 
 /// System to handle system event audio
 fn handle_system_audio(
-    mut commands: Commands,
+    mut sfx: ResMut<SfxArbiter>,
     mut system_events: EventReader<GameSystemEvent>,
     audio_assets: Res<AudioAssets>,
+    audio_settings: Res<GlobalAudioSettings>,
     asset_server: Res<AssetServer>,
 ) {
+    if !audio_settings.can_play(AudioCategory::Ui) {
+        system_events.clear();
+        return;
+    }
+
     for event in system_events.read() {
         info!("🔔 Processing system audio event: {:?}", event.event_type);
 
@@ -239,12 +988,13 @@ fn handle_system_audio(
         if let Some(handle) = audio_handle {
             let load_state = asset_server.load_state(handle.id());
             info!("🔔 Playing system audio (state: {:?})", load_state);
-            commands.spawn((
-                AudioPlayer::new(handle.clone()),
-                PlaybackSettings::ONCE.with_volume(bevy::audio::Volume::Linear(
-                    crate::domain::constants::DEFAULT_SFX_VOLUME,
-                )),
-            ));
+            spawn_world_sfx(
+                &mut sfx,
+                &audio_settings,
+                AudioCategory::Ui,
+                SfxPriority::Ui,
+                handle,
+            );
         } else {
             warn!("🔔 No audio handle available for system event!");
         }
@@ -253,11 +1003,17 @@ fn handle_system_audio(
 
 /// System to handle discovery-related audio
 fn handle_discovery_audio(
-    mut commands: Commands,
+    mut sfx: ResMut<SfxArbiter>,
     mut discovery_events: EventReader<DiscoveryEvent>,
     audio_assets: Res<AudioAssets>,
+    audio_settings: Res<GlobalAudioSettings>,
     asset_server: Res<AssetServer>,
 ) {
+    if !audio_settings.can_play(AudioCategory::Sfx) {
+        discovery_events.clear();
+        return;
+    }
+
     for event in discovery_events.read() {
         info!(
             "🔍 Processing discovery audio event: {:?}",
@@ -267,12 +1023,13 @@ fn handle_discovery_audio(
         if let Some(discovery_handle) = &audio_assets.discovery_chime {
             let load_state = asset_server.load_state(discovery_handle.id());
             info!("🔍 Playing discovery chime (state: {:?})", load_state);
-            commands.spawn((
-                AudioPlayer::new(discovery_handle.clone()),
-                PlaybackSettings::ONCE.with_volume(bevy::audio::Volume::Linear(
-                    crate::domain::constants::DEFAULT_SFX_VOLUME,
-                )),
-            ));
+            spawn_world_sfx(
+                &mut sfx,
+                &audio_settings,
+                AudioCategory::Sfx,
+                SfxPriority::Discovery,
+                discovery_handle,
+            );
         } else {
             warn!("🔍 No discovery chime audio handle available!");
         }
@@ -281,11 +1038,17 @@ fn handle_discovery_audio(
 
 /// System to handle resource-related audio
 fn handle_resource_audio(
-    mut commands: Commands,
+    mut sfx: ResMut<SfxArbiter>,
     mut resource_events: EventReader<ResourceChangedEvent>,
     audio_assets: Res<AudioAssets>,
+    audio_settings: Res<GlobalAudioSettings>,
     asset_server: Res<AssetServer>,
 ) {
+    if !audio_settings.can_play(AudioCategory::Sfx) {
+        resource_events.clear();
+        return;
+    }
+
     for event in resource_events.read() {
         info!(
             "💰 Processing resource audio event: {:?}",
@@ -298,12 +1061,13 @@ fn handle_resource_audio(
                 "💰 Playing resource collect sound (state: {:?})",
                 load_state
             );
-            commands.spawn((
-                AudioPlayer::new(resource_handle.clone()),
-                PlaybackSettings::ONCE.with_volume(bevy::audio::Volume::Linear(
-                    crate::domain::constants::DEFAULT_SFX_VOLUME,
-                )),
-            ));
+            spawn_world_sfx(
+                &mut sfx,
+                &audio_settings,
+                AudioCategory::Sfx,
+                SfxPriority::Resource,
+                resource_handle,
+            );
         } else {
             warn!("💰 No resource collect audio handle available!");
         }
@@ -312,11 +1076,17 @@ fn handle_resource_audio(
 
 /// System to handle rest-related audio
 fn handle_rest_audio(
-    mut commands: Commands,
+    mut sfx: ResMut<SfxArbiter>,
     mut rest_events: EventReader<RestCompletedEvent>,
     audio_assets: Res<AudioAssets>,
+    audio_settings: Res<GlobalAudioSettings>,
     asset_server: Res<AssetServer>,
 ) {
+    if !audio_settings.can_play(AudioCategory::Sfx) {
+        rest_events.clear();
+        return;
+    }
+
     for event in rest_events.read() {
         info!(
             "😴 Processing rest audio event: {:?}",
@@ -326,12 +1096,13 @@ fn handle_rest_audio(
         if let Some(rest_handle) = &audio_assets.rest_complete {
             let load_state = asset_server.load_state(rest_handle.id());
             info!("😴 Playing rest complete sound (state: {:?})", load_state);
-            commands.spawn((
-                AudioPlayer::new(rest_handle.clone()),
-                PlaybackSettings::ONCE.with_volume(bevy::audio::Volume::Linear(
-                    crate::domain::constants::DEFAULT_SFX_VOLUME,
-                )),
-            ));
+            spawn_world_sfx(
+                &mut sfx,
+                &audio_settings,
+                AudioCategory::Sfx,
+                SfxPriority::Effect,
+                rest_handle,
+            );
         } else {
             warn!("😴 No rest complete audio handle available!");
         }
@@ -344,11 +1115,11 @@ fn monitor_audio_status(
     asset_server: Res<AssetServer>,
     mut music_manager: ResMut<MusicManager>,
     mut last_check: Local<f32>,
-    time: Res<Time>,
+    wall: Res<WallClock>,
 ) {
-    // Check every few seconds to avoid spam
-    if time.elapsed_secs() - *last_check > AUDIO_STATUS_CHECK_INTERVAL_SECONDS as f32 {
-        *last_check = time.elapsed_secs();
+    // Check every few seconds to avoid spam; a diagnostic, so on the wall clock
+    if wall.elapsed_secs() - *last_check > AUDIO_STATUS_CHECK_INTERVAL_SECONDS as f32 {
+        *last_check = wall.elapsed_secs();
 
         let assets_to_check = [
             ("dice_roll", &audio_assets.dice_roll),
@@ -386,9 +1157,9 @@ fn monitor_audio_status(
 
         let track_names = AUDIO_TRACK_NAMES;
 
-        for (i, handle) in audio_assets.music_tracks.iter().enumerate() {
+        for (i, track) in audio_assets.music_tracks.iter().enumerate() {
             let track_name = track_names.get(i).unwrap_or(&"unknown");
-            match asset_server.load_state(handle.id()) {
+            match track.load_state(&asset_server) {
                 bevy::asset::LoadState::Loaded => music_loaded += 1,
                 bevy::asset::LoadState::Failed(error) => {
                     music_failed += 1;
@@ -447,19 +1218,23 @@ fn monitor_audio_status(
 fn retry_ambient_music_loading(
     mut commands: Commands,
     audio_assets: Res<AudioAssets>,
+    audio_settings: Res<GlobalAudioSettings>,
     mut music_manager: ResMut<MusicManager>,
     asset_server: Res<AssetServer>,
-    time: Res<Time>,
+    sim: Res<SimClock>,
+    mut last_retry: Local<Option<f32>>,
 ) {
     // Only try to start ambient music if we don't already have it playing
-    if music_manager.current_ambient.is_some() {
+    if music_manager.current_ambient.is_some() || !audio_settings.can_play(AudioCategory::Ambient) {
         return;
     }
 
-    // Only check periodically to avoid spam
-    if (time.elapsed_secs() as u64) % AMBIENT_RETRY_INTERVAL_SECONDS != 0 {
+    // Retry once per interval of play; paused runs wait with their ambience
+    let now = sim.elapsed_secs();
+    if last_retry.is_some_and(|last| now - last < AMBIENT_RETRY_INTERVAL_SECONDS as f32) {
         return;
     }
+    *last_retry = Some(now);
 
     // Try to load ambient for current terrain, fallback to space
     let (terrain_name, ambient_handle) = if let Some(terrain) = &music_manager.current_terrain {
@@ -478,8 +1253,9 @@ fn retry_ambient_music_loading(
                 let entity = commands
                     .spawn((
                         AudioPlayer::new(handle.clone()),
-                        PlaybackSettings::LOOP
-                            .with_volume(bevy::audio::Volume::Linear(music_manager.ambient_volume)),
+                        PlaybackSettings::LOOP.with_volume(bevy::audio::Volume::Linear(
+                            music_manager.ambient_volume * music_manager.ambient_gain,
+                        )),
                     ))
                     .id();
                 music_manager.current_ambient = Some(entity);
@@ -526,19 +1302,40 @@ fn retry_ambient_music_loading(
     }
 }
 
+/// Settings and restores that can keep the playlist from starting a track
+#[derive(SystemParam)]
+struct PlaylistHolds<'w> {
+    audio_settings: Res<'w, GlobalAudioSettings>,
+    restore: Res<'w, PendingAudioRestore>,
+}
+
+impl PlaylistHolds<'_> {
+    /// Whether music is switched off or waits for a loaded save's track
+    fn holds_music(&self) -> bool {
+        !self.audio_settings.can_play(AudioCategory::Music) || self.restore.holds_music()
+    }
+}
+
 /// Manage random music playlist
 fn manage_music_playlist(
     mut commands: Commands,
     audio_assets: Res<AudioAssets>,
     mut music_manager: ResMut<MusicManager>,
-    time: Res<Time>,
+    sim: Res<SimClock>,
     audio_sinks: Query<&AudioSink>,
     asset_server: Res<AssetServer>,
+    holds: PlaylistHolds,
 ) {
-    music_manager.music_change_timer.tick(time.delta());
-
-    // Early return if no music tracks are configured
-    if audio_assets.music_tracks.is_empty() {
+    // Tracks change with play time, so a paused run keeps its track
+    music_manager.music_change_timer.tick(sim.delta());
+
+    // Early return if no music tracks are configured or music may not play;
+    // a track parked for a fight comes back through the encounter mix, and
+    // a loaded save's track once its assets are ready
+    if audio_assets.music_tracks.is_empty()
+        || holds.holds_music()
+        || music_manager.is_track_parked()
+    {
         return;
     }
 
@@ -547,18 +1344,24 @@ fn manage_music_playlist(
         .music_tracks
         .iter()
         .enumerate()
-        .filter(|(_, handle)| {
+        .filter(|(_, track)| {
             matches!(
-                asset_server.load_state(handle.id()),
+                track.load_state(&asset_server),
                 bevy::asset::LoadState::Loaded
             )
         })
         .collect();
 
-    // Check if current music has finished or it's time to change
+    // Check if current music has finished or it's time to change; a layered
+    // track ends with its first stem so the next one starts all stems together
     let should_change_music = if let Some(current_entity) = music_manager.current_music {
         if let Ok(sink) = audio_sinks.get(current_entity) {
-            sink.empty() // Music finished
+            sink.empty()
+                || music_manager.music_layers.iter().any(|layer| {
+                    audio_sinks
+                        .get(layer.entity)
+                        .is_ok_and(|layer_sink| layer_sink.empty())
+                })
         } else {
             true // No music playing
         }
@@ -572,9 +1375,9 @@ fn manage_music_playlist(
             "🎵 Attempting to change music - {} loaded tracks available",
             loaded_tracks.len()
         );
-        // Stop current music if playing
-        if let Some(current_entity) = music_manager.current_music {
-            if let Ok(sink) = audio_sinks.get(current_entity) {
+        // Stop current music if playing, every stem at once
+        for entity in music_manager.take_music() {
+            if let Ok(sink) = audio_sinks.get(entity) {
                 sink.stop();
                 info!("🛑 Stopped previous music track");
             }
@@ -584,23 +1387,16 @@ fn manage_music_playlist(
         let selected_track =
             get_random_loaded_track(&loaded_tracks, music_manager.last_track_index);
 
-        if let Some((original_index, track_handle)) = selected_track {
+        if let Some((original_index, track)) = selected_track {
             let track_names = AUDIO_TRACK_NAMES;
             let track_name = track_names.get(original_index).unwrap_or(&"unknown");
             info!(
-                "🎵 Playing random music track {} ({})",
-                original_index, track_name
+                "🎵 Playing random music track {} ({}, {} layers)",
+                original_index,
+                track_name,
+                track.layers.len()
             );
-            let entity = commands
-                .spawn((
-                    AudioPlayer::new(track_handle.clone()),
-                    PlaybackSettings::ONCE
-                        .with_volume(bevy::audio::Volume::Linear(music_manager.music_volume)),
-                ))
-                .id();
-
-            music_manager.current_music = Some(entity);
-            music_manager.last_track_index = Some(original_index);
+            start_music_track(&mut commands, &mut music_manager, original_index, &track);
             music_manager.music_change_timer.reset();
         } else {
             // Only log occasionally to avoid spam when tracks are still loading
@@ -614,6 +1410,158 @@ fn manage_music_playlist(
     }
 }
 
+/// Soundtrack of a loaded save, waiting for its assets to play again
+#[derive(Resource, Debug, Default)]
+pub struct PendingAudioRestore {
+    memory: Option<AudioMemory>,
+    mix: bool,
+    track: bool,
+    ambient: bool,
+}
+
+impl PendingAudioRestore {
+    /// Restore `memory` once the assets it names are loaded
+    pub fn queue(&mut self, memory: AudioMemory) {
+        *self = Self {
+            memory: Some(memory),
+            mix: true,
+            track: true,
+            ambient: memory.ambient.is_some(),
+        };
+    }
+
+    /// Check if a save is still being restored
+    pub fn is_pending(&self) -> bool {
+        self.memory.is_some()
+    }
+
+    /// Check if the playlist should hold off for the saved track
+    pub fn holds_music(&self) -> bool {
+        self.memory.is_some() && self.track
+    }
+}
+
+/// Pick a loaded save's soundtrack back up once its assets are loaded
+///
+/// The saved track starts again through the same path as a track coming
+/// back after combat. A track the playlist no longer has leaves the choice
+/// to the random playlist.
+fn restore_audio_memory(
+    mut commands: Commands,
+    mut restore: ResMut<PendingAudioRestore>,
+    audio_assets: Res<AudioAssets>,
+    audio_settings: Res<GlobalAudioSettings>,
+    mut music_manager: ResMut<MusicManager>,
+    asset_server: Res<AssetServer>,
+    mut terrain_events: EventWriter<TerrainChangeEvent>,
+) {
+    let Some(memory) = restore.memory else {
+        return;
+    };
+    if restore.mix {
+        music_manager.music_volume = memory.music_volume;
+        music_manager.danger_level = memory.danger_level;
+        restore.mix = false;
+    }
+
+    if restore.track && !audio_settings.can_play(AudioCategory::Music) {
+        restore.track = false;
+    }
+    if restore.track {
+        let state = memory
+            .track_index
+            .and_then(|index| audio_assets.music_tracks.get(index))
+            .map(|track| track.load_state(&asset_server));
+        let failed = matches!(state, Some(bevy::asset::LoadState::Failed(_)));
+        let loaded = matches!(state, Some(bevy::asset::LoadState::Loaded));
+        match memory.track_restore(audio_assets.music_tracks.len(), loaded) {
+            TrackRestore::Waiting if !failed => {}
+            TrackRestore::Resume(resume) if music_manager.is_track_parked() => {
+                // Mid-fight: the saved track comes back when the fight ends
+                music_manager.resume_point = Some(resume);
+                restore.track = false;
+            }
+            TrackRestore::Resume(resume) => {
+                for entity in music_manager.take_music() {
+                    if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                        entity_commands.despawn();
+                    }
+                }
+                let track = &audio_assets.music_tracks[resume.track_index];
+                info!("🎵 Restoring saved music track {}", resume.track_index);
+                resume_music_track(&mut commands, &mut music_manager, resume, track);
+                restore.track = false;
+            }
+            TrackRestore::Waiting | TrackRestore::Missing => {
+                if let Some(index) = memory.track_index {
+                    info!(
+                        "🎵 Saved music track {} is unavailable - picking a random one",
+                        index
+                    );
+                }
+                restore.track = false;
+            }
+        }
+    }
+
+    if let Some(terrain) = memory.ambient.filter(|_| restore.ambient) {
+        let state = audio_assets
+            .ambient_sounds
+            .get(get_terrain_name(&terrain))
+            .map(|handle| asset_server.load_state(handle.id()));
+        let waiting = matches!(
+            state,
+            Some(bevy::asset::LoadState::Loading | bevy::asset::LoadState::NotLoaded)
+        );
+        if !waiting {
+            // Cleared so the terrain handler switches even to the same
+            // terrain; an ambient that failed goes through its fallback
+            music_manager.current_terrain = None;
+            terrain_events.write(TerrainChangeEvent {
+                new_terrain: terrain,
+                fade_duration: None,
+            });
+            restore.ambient = false;
+        }
+    }
+
+    if !restore.track && !restore.ambient {
+        restore.memory = None;
+    }
+}
+
+/// Keep the session's audio memory in step with what is playing
+fn remember_audio_system(
+    music_manager: Res<MusicManager>,
+    restore: Res<PendingAudioRestore>,
+    session: Option<ResMut<RpgGameSession>>,
+) {
+    // A save still being restored keeps what it remembered
+    let Some(mut session) = session.filter(|_| !restore.is_pending()) else {
+        return;
+    };
+    let track_position = match music_manager.resume_point {
+        Some(resume) => resume.offset,
+        None => music_manager
+            .current_music
+            .map(|_| music_manager.track_played),
+    };
+    let memory = AudioMemory {
+        track_index: music_manager
+            .resume_point
+            .map(|resume| resume.track_index)
+            .or(music_manager.last_track_index),
+        track_position,
+        ambient: music_manager.current_terrain,
+        music_volume: music_manager.music_volume,
+        danger_level: music_manager.danger_level,
+    };
+    // Written every frame; systems watching the session need not wake up
+    if session.audio_memory != Some(memory) {
+        session.bypass_change_detection().audio_memory = Some(memory);
+    }
+}
+
 /// Handle music progression events (triggered from anywhere in the game)
 fn handle_music_progression_events(
     mut events: EventReader<MusicProgressionEvent>,
@@ -645,6 +1593,7 @@ fn handle_music_progression_events(
 
             let tension_modifier = event.danger_level * 0.4;
             music_manager.music_volume = (base_music_volume + tension_modifier).clamp(0.0, 1.0);
+            music_manager.layered_music_volume = base_music_volume.clamp(0.0, 1.0);
 
             let base_ambient_volume = crate::domain::constants::DEFAULT_AMBIENT_VOLUME;
             let ambient_modifier = event.danger_level * 0.15;
@@ -653,13 +1602,20 @@ fn handle_music_progression_events(
             // Update current playing tracks
             if let Some(ambient_entity) = music_manager.current_ambient {
                 if let Ok(mut sink) = audio_sinks.get_mut(ambient_entity) {
-                    sink.set_volume(bevy::audio::Volume::Linear(music_manager.ambient_volume));
+                    sink.set_volume(bevy::audio::Volume::Linear(
+                        music_manager.ambient_volume * music_manager.ambient_gain,
+                    ));
                 }
             }
 
-            if let Some(music_entity) = music_manager.current_music {
-                if let Ok(mut sink) = audio_sinks.get_mut(music_entity) {
-                    sink.set_volume(bevy::audio::Volume::Linear(music_manager.music_volume));
+            // Stems follow the new danger level through the ramp instead
+            if music_manager.music_layers.is_empty() {
+                if let Some(music_entity) = music_manager.current_music {
+                    if let Ok(mut sink) = audio_sinks.get_mut(music_entity) {
+                        sink.set_volume(bevy::audio::Volume::Linear(
+                            music_manager.music_volume * music_manager.music_gain,
+                        ));
+                    }
                 }
             }
 
@@ -681,11 +1637,192 @@ fn handle_music_progression_events(
     }
 }
 
+/// Follow the encounter overrides: duck, crossfade into the combat loop and back
+///
+/// The combat loop is the percussion stem of a layered track, or the
+/// dedicated loop otherwise. The track is stopped once it faded out, with
+/// its offset kept, and started again once the loop faded out.
+fn mix_encounter_music(
+    mut commands: Commands,
+    time: Res<Time>,
+    audio_assets: Res<AudioAssets>,
+    audio_settings: Res<GlobalAudioSettings>,
+    mut music_manager: ResMut<MusicManager>,
+    mut audio_sinks: Query<&mut AudioSink>,
+) {
+    music_manager.step_encounter_gains(time.delta_secs());
+    let can_play = audio_settings.can_play(AudioCategory::Music);
+
+    if can_play
+        && music_manager.overrides.wants_combat_loop()
+        && music_manager.combat_loop.is_none()
+    {
+        let percussion = music_manager
+            .last_track_index
+            .and_then(|index| audio_assets.music_tracks.get(index))
+            .filter(|track| track.is_layered())
+            .and_then(|track| track.layers.get(2).cloned());
+        if let Some(handle) = percussion.or_else(|| audio_assets.combat_loop.clone()) {
+            info!("🥁 Combat drags on - crossfading into the combat loop");
+            let entity = commands
+                .spawn((
+                    AudioPlayer::new(handle),
+                    PlaybackSettings::LOOP.with_volume(bevy::audio::Volume::Linear(0.0)),
+                ))
+                .id();
+            music_manager.combat_loop = Some(entity);
+        }
+    }
+
+    // The track faded out under the loop: stop it and remember where it was
+    if music_manager.combat_loop.is_some()
+        && music_manager.track_gain == 0.0
+        && music_manager.current_music.is_some()
+    {
+        let offset = Some(music_manager.track_played);
+        music_manager.resume_point =
+            music_manager
+                .last_track_index
+                .map(|track_index| ResumePoint {
+                    track_index,
+                    offset,
+                });
+        for entity in music_manager.take_music() {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.despawn();
+            }
+        }
+    }
+
+    // The loop faded out after the fight: stop it and bring the track back
+    if !music_manager.overrides.wants_combat_loop() && music_manager.combat_loop_gain == 0.0 {
+        if let Some(entity) = music_manager.combat_loop.take() {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.despawn();
+            }
+        }
+        let resume = music_manager.resume_point.take();
+        let track = resume.and_then(|resume| audio_assets.music_tracks.get(resume.track_index));
+        if let (Some(resume), Some(track), true) = (resume, track, can_play) {
+            resume_music_track(&mut commands, &mut music_manager, resume, track);
+        }
+    }
+
+    if let Some(entity) = music_manager.combat_loop {
+        if let Ok(mut sink) = audio_sinks.get_mut(entity) {
+            sink.set_volume(bevy::audio::Volume::Linear(
+                music_manager.music_volume
+                    * music_manager.combat_loop_gain
+                    * music_manager.music_gain,
+            ));
+        }
+    }
+}
+
+/// Ramp stem volumes smoothly towards the mix for the current danger level
+///
+/// Every player of the track also carries the encounter gain, so ducking
+/// and crossfades reach single-file tracks too. The track's played time is
+/// counted here as well, while its base player runs.
+fn ramp_music_stems(
+    time: Res<Time>,
+    mut music_manager: ResMut<MusicManager>,
+    mut audio_sinks: Query<&mut AudioSink>,
+) {
+    if music_manager
+        .current_music
+        .and_then(|entity| audio_sinks.get(entity).ok())
+        .is_some_and(|sink| !sink.is_paused() && !sink.empty())
+    {
+        music_manager.track_played += time.delta_secs();
+    }
+    if music_manager.music_layers.is_empty() {
+        if let Some(entity) = music_manager.current_music {
+            if let Ok(mut sink) = audio_sinks.get_mut(entity) {
+                sink.set_volume(bevy::audio::Volume::Linear(
+                    music_manager.music_volume
+                        * music_manager.track_gain
+                        * music_manager.music_gain,
+                ));
+            }
+        }
+        return;
+    }
+    // Volumes are applied every frame so area volume changes reach the stems too
+    music_manager.step_stem_gains(time.delta_secs());
+    for layer in &music_manager.music_layers {
+        if let Ok(mut sink) = audio_sinks.get_mut(layer.entity) {
+            sink.set_volume(bevy::audio::Volume::Linear(
+                music_manager.layered_music_volume
+                    * layer.gain
+                    * music_manager.track_gain
+                    * music_manager.music_gain,
+            ));
+        }
+    }
+}
+
+/// Restart every stem together once they drift apart
+///
+/// The sinks do not report where they are, so each stem keeps its own
+/// offset: it only advances while the stem's sink exists and plays, and a
+/// stem whose sink came late or stalled falls behind the others.
+fn sync_music_stems(
+    time: Res<Time>,
+    mut commands: Commands,
+    audio_assets: Res<AudioAssets>,
+    mut music_manager: ResMut<MusicManager>,
+    audio_sinks: Query<&AudioSink>,
+) {
+    if music_manager.music_layers.is_empty() {
+        return;
+    }
+    let delta = time.delta_secs();
+    for layer in &mut music_manager.music_layers {
+        if audio_sinks
+            .get(layer.entity)
+            .is_ok_and(|sink| !sink.is_paused() && !sink.empty())
+        {
+            layer.played += delta;
+        }
+    }
+    let positions: Vec<f32> = music_manager
+        .music_layers
+        .iter()
+        .map(|layer| layer.played)
+        .collect();
+    if !stems_drifted(&positions, MUSIC_STEM_MAX_DRIFT_SECONDS) {
+        return;
+    }
+    let Some(track_index) = music_manager.last_track_index else {
+        return;
+    };
+    let Some(track) = audio_assets.music_tracks.get(track_index) else {
+        return;
+    };
+
+    warn!("🎵 Music stems drifted apart - restarting them together");
+    for entity in music_manager.take_music() {
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.despawn();
+        }
+    }
+    start_music_track(&mut commands, &mut music_manager, track_index, track);
+}
+
+/// Check if playback positions of stems differ by more than `tolerance` seconds
+fn stems_drifted(positions: &[f32], tolerance: f32) -> bool {
+    let earliest = positions.iter().copied().fold(f32::INFINITY, f32::min);
+    let latest = positions.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    latest - earliest > tolerance
+}
+
 /// Handle terrain change events for ambient sound switching
 fn handle_terrain_change_events(
     mut events: EventReader<TerrainChangeEvent>,
     mut commands: Commands,
     audio_assets: Res<AudioAssets>,
+    audio_settings: Res<GlobalAudioSettings>,
     mut music_manager: ResMut<MusicManager>,
     asset_server: Res<AssetServer>,
     audio_sinks: Query<&AudioSink>,
@@ -706,6 +1843,12 @@ fn handle_terrain_change_events(
             return; // Same terrain, no change needed
         }
 
+        if !audio_settings.can_play(AudioCategory::Ambient) {
+            // Remember the terrain so ambient resumes correctly when re-enabled
+            music_manager.current_terrain = Some(event.new_terrain);
+            continue;
+        }
+
         info!(
             "🌍 Terrain changed to: {} - switching ambient sound",
             terrain_name
@@ -751,8 +1894,9 @@ fn handle_terrain_change_events(
                     let entity = commands
                         .spawn((
                             AudioPlayer::new(handle.clone()),
-                            PlaybackSettings::LOOP
-                                .with_volume(bevy::audio::Volume::Linear(final_volume)),
+                            PlaybackSettings::LOOP.with_volume(bevy::audio::Volume::Linear(
+                                final_volume * music_manager.ambient_gain,
+                            )),
                         ))
                         .id();
 
@@ -793,7 +1937,8 @@ fn handle_terrain_change_events(
                                         AudioPlayer::new(space_handle.clone()),
                                         PlaybackSettings::LOOP.with_volume(
                                             bevy::audio::Volume::Linear(
-                                                crate::domain::constants::DEFAULT_MUSIC_VOLUME,
+                                                crate::domain::constants::DEFAULT_MUSIC_VOLUME
+                                                    * music_manager.ambient_gain,
                                             ),
                                         ),
                                     ))
@@ -829,11 +1974,105 @@ fn handle_terrain_change_events(
     }
 }
 
+/// Stop music and ambient players when their category gets disabled
+fn apply_audio_settings_changes(
+    mut commands: Commands,
+    audio_settings: Res<GlobalAudioSettings>,
+    mut music_manager: ResMut<MusicManager>,
+) {
+    if !audio_settings.is_changed() {
+        return;
+    }
+
+    for entity in music_manager.silence_disabled(&audio_settings) {
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.despawn();
+        }
+    }
+}
+
+/// Carry the player's volume levels to the music and the playing ambient
+///
+/// Music players pick the new level up on their next ramp; the ambient bed
+/// is only set when it starts, so its sink is updated here.
+fn apply_volume_settings(
+    volumes: Res<AudioSettings>,
+    mut music_manager: ResMut<MusicManager>,
+    mut audio_sinks: Query<&mut AudioSink>,
+) {
+    if !volumes.is_changed() {
+        return;
+    }
+    music_manager.music_gain = volumes.gain(AudioCategory::Music);
+    music_manager.ambient_gain = volumes.gain(AudioCategory::Ambient);
+    if let Some(entity) = music_manager.current_ambient {
+        if let Ok(mut sink) = audio_sinks.get_mut(entity) {
+            sink.set_volume(bevy::audio::Volume::Linear(
+                music_manager.ambient_volume * music_manager.ambient_gain,
+            ));
+        }
+    }
+}
+
+/// Detect whether audio output works by playing a silent probe sink
+///
+/// Bevy only attaches an `AudioSink` when an output stream could be opened,
+/// so a probe that never receives one means there is no usable device.
+#[cfg(not(target_arch = "wasm32"))]
+fn probe_audio_device(
+    mut commands: Commands,
+    audio_assets: Res<AudioAssets>,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+    mut audio_settings: ResMut<GlobalAudioSettings>,
+    probe_sinks: Query<(), (With<AudioDeviceProbe>, With<AudioSink>)>,
+    mut state: Local<AudioProbeState>,
+) {
+    match *state {
+        AudioProbeState::WaitingForAsset => {
+            let Some(handle) = &audio_assets.ui_click else {
+                return;
+            };
+            match asset_server.load_state(handle.id()) {
+                bevy::asset::LoadState::Loaded => {
+                    let entity = commands
+                        .spawn((
+                            AudioPlayer::new(handle.clone()),
+                            PlaybackSettings::LOOP.with_volume(bevy::audio::Volume::Linear(0.0)),
+                            AudioDeviceProbe,
+                        ))
+                        .id();
+                    *state = AudioProbeState::Probing {
+                        entity,
+                        started: time.elapsed_secs(),
+                    };
+                }
+                // Without the asset there is nothing to probe with
+                bevy::asset::LoadState::Failed(_) => *state = AudioProbeState::Done,
+                _ => {}
+            }
+        }
+        AudioProbeState::Probing { entity, started } => {
+            if probe_sinks.contains(entity) {
+                info!("🔊 Audio output device available");
+            } else if time.elapsed_secs() - started > AUDIO_PROBE_TIMEOUT_SECONDS {
+                audio_settings.device_available = false;
+                warn!("🔇 No audio output device found - running in silent mode");
+            } else {
+                return;
+            }
+            commands.entity(entity).despawn();
+            *state = AudioProbeState::Done;
+        }
+        AudioProbeState::Done => {}
+    }
+}
+
 /// Helper function to get random loaded track avoiding immediate repeats
 fn get_random_loaded_track(
-    loaded_tracks: &[(usize, &Handle<AudioSource>)],
+    loaded_tracks: &[(usize, &MusicTrackAssets)],
     last_index: Option<usize>,
-) -> Option<(usize, Handle<AudioSource>)> {
+) -> Option<(usize, MusicTrackAssets)> {
     if loaded_tracks.is_empty() {
         return None;
     }
@@ -935,3 +2174,339 @@ pub fn send_music_progression(
         area_type,
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::world::CommandQueue;
+
+    fn spawned_players(settings: &GlobalAudioSettings, category: AudioCategory) -> usize {
+        let mut world = World::new();
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let mut sfx = SfxArbiter::default();
+        let requested = spawn_world_sfx(
+            &mut sfx,
+            settings,
+            category,
+            SfxPriority::Effect,
+            &Handle::default(),
+        );
+        for request in sfx.drain(0.0) {
+            commands.spawn((AudioPlayer::new(request.handle), request.playback));
+        }
+        queue.apply(&mut world);
+
+        let count = world.query::<&AudioPlayer>().iter(&world).count();
+        assert_eq!(requested, count == 1);
+        count
+    }
+
+    fn sample(id: u128) -> Handle<AudioSource> {
+        Handle::Weak(AssetId::Uuid {
+            uuid: uuid::Uuid::from_u128(id),
+        })
+    }
+
+    fn at_volume(volume: f32) -> PlaybackSettings {
+        PlaybackSettings::ONCE.with_volume(bevy::audio::Volume::Linear(volume))
+    }
+
+    #[test]
+    fn duplicate_requests_in_a_frame_keep_the_loudest() {
+        let mut sfx = SfxArbiter::new(4, 0.08);
+        sfx.request(
+            &sample(1),
+            at_volume(0.4),
+            AudioCategory::Sfx,
+            SfxPriority::Ui,
+        );
+        sfx.request(
+            &sample(1),
+            at_volume(0.9),
+            AudioCategory::Sfx,
+            SfxPriority::Effect,
+        );
+        sfx.request(
+            &sample(1),
+            at_volume(0.6),
+            AudioCategory::Sfx,
+            SfxPriority::Ui,
+        );
+        assert_eq!(sfx.pending_count(), 1);
+
+        let started = sfx.drain(1.0);
+        assert_eq!(started.len(), 1);
+        assert_eq!(started[0].playback.volume.to_linear(), 0.9);
+        assert_eq!(started[0].priority, SfxPriority::Effect);
+        assert_eq!(sfx.pending_count(), 0);
+    }
+
+    #[test]
+    fn same_sample_waits_for_retrigger_interval() {
+        let mut sfx = SfxArbiter::new(4, 0.08);
+        sfx.request(
+            &sample(1),
+            at_volume(0.8),
+            AudioCategory::Sfx,
+            SfxPriority::Effect,
+        );
+        assert_eq!(sfx.drain(1.0).len(), 1);
+
+        // Next frame, 16ms later: too soon for the same sample, fine for another
+        sfx.request(
+            &sample(1),
+            at_volume(0.8),
+            AudioCategory::Sfx,
+            SfxPriority::Effect,
+        );
+        sfx.request(
+            &sample(2),
+            at_volume(0.8),
+            AudioCategory::Sfx,
+            SfxPriority::Effect,
+        );
+        let started = sfx.drain(1.016);
+        assert_eq!(started.len(), 1);
+        assert_eq!(started[0].handle, sample(2));
+
+        sfx.request(
+            &sample(1),
+            at_volume(0.8),
+            AudioCategory::Sfx,
+            SfxPriority::Effect,
+        );
+        assert_eq!(sfx.drain(1.1).len(), 1);
+    }
+
+    #[test]
+    fn over_budget_frames_keep_highest_priority() {
+        let mut sfx = SfxArbiter::new(2, 0.08);
+        sfx.request(
+            &sample(1),
+            at_volume(0.8),
+            AudioCategory::Sfx,
+            SfxPriority::Ui,
+        );
+        sfx.request(
+            &sample(2),
+            at_volume(0.8),
+            AudioCategory::Sfx,
+            SfxPriority::Resource,
+        );
+        sfx.request(
+            &sample(3),
+            at_volume(0.8),
+            AudioCategory::Sfx,
+            SfxPriority::Effect,
+        );
+        sfx.request(
+            &sample(4),
+            at_volume(0.8),
+            AudioCategory::Sfx,
+            SfxPriority::Discovery,
+        );
+
+        let started: Vec<_> = sfx.drain(1.0).into_iter().map(|r| r.handle).collect();
+        assert_eq!(started, vec![sample(4), sample(2)]);
+
+        // Dropped sounds did not start, so they are not held back next frame
+        sfx.request(
+            &sample(1),
+            at_volume(0.8),
+            AudioCategory::Sfx,
+            SfxPriority::Ui,
+        );
+        assert_eq!(sfx.drain(1.016).len(), 1);
+    }
+
+    #[test]
+    fn missing_device_spawns_nothing() {
+        let settings = GlobalAudioSettings {
+            device_available: false,
+            ..Default::default()
+        };
+
+        for category in AudioCategory::all() {
+            assert!(!settings.can_play(category));
+            assert_eq!(spawned_players(&settings, category), 0);
+        }
+    }
+
+    #[test]
+    fn category_gating_matrix() {
+        for disabled in AudioCategory::all() {
+            let mut settings = GlobalAudioSettings::default();
+            settings.set_category_enabled(disabled, false);
+
+            for category in AudioCategory::all() {
+                let expected = category != disabled;
+                assert_eq!(settings.can_play(category), expected);
+                assert_eq!(spawned_players(&settings, category), expected as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn reenabling_category_resumes_playback() {
+        let mut settings = GlobalAudioSettings::default();
+        let mut manager = MusicManager::default();
+        let music = Entity::from_raw(1);
+        let ambient = Entity::from_raw(2);
+        manager.current_music = Some(music);
+        manager.current_ambient = Some(ambient);
+
+        assert!(!settings.toggle_category(AudioCategory::Music));
+        assert_eq!(manager.silence_disabled(&settings), vec![music]);
+        assert_eq!(manager.current_music, None);
+        assert_eq!(manager.current_ambient, Some(ambient));
+        assert_eq!(spawned_players(&settings, AudioCategory::Music), 0);
+
+        assert!(settings.toggle_category(AudioCategory::Music));
+        assert!(manager.silence_disabled(&settings).is_empty());
+        assert_eq!(spawned_players(&settings, AudioCategory::Music), 1);
+    }
+
+    #[test]
+    fn unmuting_brings_back_the_levels_set_before() {
+        let mut volumes = AudioSettings::default();
+        volumes.adjust(VolumeChannel::Master, -0.5);
+        volumes.adjust(VolumeChannel::Sfx, -0.2);
+        volumes.adjust(VolumeChannel::Music, 0.3);
+        assert_eq!(volumes.music_volume, 1.0);
+        assert_eq!(volumes.gain(AudioCategory::Sfx), 0.4);
+        assert_eq!(volumes.gain(AudioCategory::Ui), 0.4);
+        assert_eq!(volumes.gain(AudioCategory::Ambient), 0.5);
+
+        let before = volumes.clone();
+        assert!(volumes.toggle_mute());
+        for category in AudioCategory::all() {
+            assert_eq!(volumes.gain(category), 0.0);
+        }
+        assert!(!volumes.toggle_mute());
+        assert_eq!(volumes, before);
+    }
+
+    #[test]
+    fn danger_maps_to_stem_gains() {
+        for danger in [0.0, 0.4, 0.7, 1.0] {
+            assert_eq!(MusicStem::Base.gain(danger), 1.0);
+        }
+
+        assert_eq!(MusicStem::Tension.gain(0.4), 0.0);
+        assert!(MusicStem::Tension.gain(0.45) > 0.0);
+        assert_eq!(MusicStem::Tension.gain(0.6), 1.0);
+        assert_eq!(MusicStem::Percussion.gain(0.6), 0.0);
+        assert_eq!(MusicStem::Percussion.gain(0.7), 0.0);
+        assert!(MusicStem::Percussion.gain(0.75) > 0.0);
+        assert_eq!(MusicStem::Percussion.gain(1.0), 1.0);
+
+        // The curve never decreases as danger rises
+        let mut last = [0.0; 3];
+        for step in 0..=100 {
+            let danger = step as f32 / 100.0;
+            for (stem, previous) in MusicStem::all().iter().zip(last.iter_mut()) {
+                let gain = stem.gain(danger);
+                assert!(gain >= *previous);
+                *previous = gain;
+            }
+        }
+    }
+
+    #[test]
+    fn single_file_tracks_fall_back_to_plain_playback() {
+        let mut manager = MusicManager {
+            danger_level: 0.75,
+            music_volume: 0.8,
+            layered_music_volume: 0.5,
+            ..Default::default()
+        };
+
+        let single = MusicTrackAssets {
+            layers: vec![sample(1)],
+        };
+        assert!(!single.is_layered());
+        assert_eq!(
+            manager.plan_track(&single),
+            vec![(MusicStem::Base, sample(1), 0.8)]
+        );
+
+        let layered = MusicTrackAssets {
+            layers: vec![sample(1), sample(2), sample(3)],
+        };
+        let plan = manager.plan_track(&layered);
+        assert_eq!(plan.len(), 3);
+        assert_eq!(plan[0], (MusicStem::Base, sample(1), 0.5));
+        assert_eq!(plan[1].0, MusicStem::Tension);
+        assert_eq!(plan[1].2, 0.5);
+        assert_eq!(plan[2].0, MusicStem::Percussion);
+        assert!(plan[2].2 > 0.0 && plan[2].2 < 0.5);
+
+        // Stopping a layered track releases every stem once
+        manager.current_music = Some(Entity::from_raw(1));
+        manager.music_layers = MusicStem::all()
+            .into_iter()
+            .enumerate()
+            .map(|(i, stem)| MusicLayer {
+                stem,
+                entity: Entity::from_raw(i as u32 + 1),
+                gain: 1.0,
+                played: 0.0,
+            })
+            .collect();
+        assert_eq!(manager.take_music().len(), 3);
+        assert!(manager.music_layers.is_empty());
+    }
+
+    #[test]
+    fn stem_gains_ramp_one_step_at_a_time() {
+        assert_eq!(ramp_towards(0.0, 1.0, 0.25), 0.25);
+        assert_eq!(ramp_towards(1.0, 0.0, 0.25), 0.75);
+        assert_eq!(ramp_towards(0.9, 1.0, 0.25), 1.0);
+        assert_eq!(ramp_towards(0.5, 0.5, 0.25), 0.5);
+
+        let mut manager = MusicManager {
+            music_layers: vec![MusicLayer {
+                stem: MusicStem::Tension,
+                entity: Entity::from_raw(1),
+                gain: 0.0,
+                played: 0.0,
+            }],
+            danger_level: 1.0,
+            ..Default::default()
+        };
+
+        // Half a second at the ramp rate, then the rest of the way
+        assert!(manager.step_stem_gains(0.5));
+        assert_eq!(
+            manager.music_layers[0].gain,
+            MUSIC_STEM_RAMP_PER_SECOND * 0.5
+        );
+        while manager.step_stem_gains(0.5) {}
+        assert_eq!(manager.music_layers[0].gain, 1.0);
+
+        assert!(!stems_drifted(
+            &[1.0, 1.02, 1.01],
+            MUSIC_STEM_MAX_DRIFT_SECONDS
+        ));
+        assert!(stems_drifted(&[1.0, 1.2], MUSIC_STEM_MAX_DRIFT_SECONDS));
+    }
+
+    #[test]
+    fn loaded_saves_hold_the_playlist_until_restored() {
+        let mut restore = PendingAudioRestore::default();
+        assert!(!restore.holds_music());
+        restore.queue(AudioMemory {
+            track_index: Some(1),
+            track_position: Some(9.0),
+            ambient: None,
+            music_volume: 0.5,
+            danger_level: 0.3,
+        });
+        assert!(restore.is_pending());
+        assert!(restore.holds_music());
+        assert!(!restore.ambient);
+        restore.track = false;
+        assert!(!restore.holds_music());
+    }
+}
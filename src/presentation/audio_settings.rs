@@ -0,0 +1,373 @@
+//! Audio Settings - Volume levels and mute, from the pause screen
+//!
+//! F6 opens the audio settings while paused. Up and Down pick a level,
+//! Left and Right (or - and +) move it a step, 1 to 4 switch music,
+//! ambient, effects and interface sounds on or off, and F7 mutes or
+//! unmutes all sound, here or anywhere else. Levels live in
+//! `AudioSettings` and the switches in `GlobalAudioSettings`, so both reach
+//! playing sounds at once and are saved with the other settings.
+
+use crate::domain::constants::{
+    ENERGY_COLOR, HANDOVER_BACKGROUND, PRIMARY_TEXT, SECONDARY_TEXT, VOLUME_STEP,
+};
+use crate::domain::services::font_service::FontSize;
+use crate::presentation::audio_integration::{
+    AudioCategory, AudioSettings, GlobalAudioSettings, VolumeChannel,
+};
+use crate::presentation::game_state::RpgAppState;
+use bevy::prelude::*;
+
+/// Key that opens and closes the audio settings while paused
+pub const AUDIO_SETTINGS_KEY: KeyCode = KeyCode::F6;
+
+/// Key that mutes or unmutes all sound
+pub const MUTE_KEY: KeyCode = KeyCode::F7;
+
+/// Keys that switch each sound category, in `AudioCategory::all` order
+pub const CATEGORY_KEYS: [KeyCode; 4] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+];
+
+/// Cells of a level's bar
+const VOLUME_BAR_CELLS: usize = 10;
+
+/// Plugin for the audio settings screen
+pub struct AudioSettingsPlugin;
+
+impl Plugin for AudioSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AudioSettingsScreen>()
+            .init_resource::<AudioSettings>()
+            .add_systems(Startup, setup_audio_settings_screen)
+            .add_systems(
+                Update,
+                (
+                    toggle_audio_settings_system,
+                    mute_system,
+                    adjust_volume_system,
+                    toggle_category_system,
+                    update_audio_settings_screen,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Whether the audio settings are open and which level is picked
+#[derive(Resource, Debug, Default)]
+pub struct AudioSettingsScreen {
+    open: bool,
+    selected: usize,
+}
+
+impl AudioSettingsScreen {
+    /// Whether the audio settings are showing
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Level the arrow keys move
+    pub fn selected(&self) -> VolumeChannel {
+        VolumeChannel::ALL[self.selected % VolumeChannel::ALL.len()]
+    }
+}
+
+/// Marker for the audio settings root
+#[derive(Component)]
+pub struct AudioSettingsRoot;
+
+/// Marker for the levels text
+#[derive(Component)]
+pub struct AudioSettingsText;
+
+fn setup_audio_settings_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                top: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(16.0),
+                ..default()
+            },
+            BackgroundColor(HANDOVER_BACKGROUND),
+            GlobalZIndex(15),
+            Visibility::Hidden,
+            AudioSettingsRoot,
+            Name::new("AudioSettingsScreen"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("AUDIO"),
+                TextFont {
+                    font_size: FontSize::Large.to_pixels(),
+                    ..default()
+                },
+                TextColor(ENERGY_COLOR),
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Medium.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                AudioSettingsText,
+            ));
+            parent.spawn((
+                Text::new(
+                    "Up/Down: pick  Left/Right or -/+: adjust  1-4: switch  F7: mute  F6: close",
+                ),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(SECONDARY_TEXT),
+            ));
+        });
+}
+
+/// Open or close on F6; only the pause screen offers it
+fn toggle_audio_settings_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    mut screen: ResMut<AudioSettingsScreen>,
+) {
+    if *state.get() != RpgAppState::Paused {
+        if screen.open {
+            screen.open = false;
+        }
+        return;
+    }
+    if keyboard.just_pressed(AUDIO_SETTINGS_KEY) {
+        screen.open = !screen.open;
+    }
+}
+
+/// Mute or unmute on F7, wherever the player is
+fn mute_system(keyboard: Res<ButtonInput<KeyCode>>, mut volumes: ResMut<AudioSettings>) {
+    if keyboard.just_pressed(MUTE_KEY) {
+        let muted = volumes.toggle_mute();
+        info!("🔇 Sound {}", if muted { "muted" } else { "unmuted" });
+    }
+}
+
+/// Pick a level with Up and Down and move it with Left and Right
+fn adjust_volume_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut screen: ResMut<AudioSettingsScreen>,
+    mut volumes: ResMut<AudioSettings>,
+) {
+    if !screen.open {
+        return;
+    }
+    let channels = VolumeChannel::ALL.len();
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        screen.selected = (screen.selected + 1) % channels;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        screen.selected = (screen.selected + channels - 1) % channels;
+    }
+
+    let pressed = |keys: [KeyCode; 3]| keys.iter().any(|key| keyboard.just_pressed(*key));
+    let delta = if pressed([KeyCode::ArrowRight, KeyCode::Equal, KeyCode::NumpadAdd]) {
+        VOLUME_STEP
+    } else if pressed([KeyCode::ArrowLeft, KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        -VOLUME_STEP
+    } else {
+        return;
+    };
+    volumes.adjust(screen.selected(), delta);
+}
+
+/// Switch a sound category on or off with 1 to 4
+fn toggle_category_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    screen: Res<AudioSettingsScreen>,
+    mut audio: ResMut<GlobalAudioSettings>,
+) {
+    if !screen.open {
+        return;
+    }
+    for (category, key) in AudioCategory::all().into_iter().zip(CATEGORY_KEYS) {
+        if keyboard.just_pressed(key) {
+            let enabled = audio.toggle_category(category);
+            info!(
+                "🔈 {} sounds {}",
+                category.label(),
+                if enabled { "on" } else { "off" }
+            );
+        }
+    }
+}
+
+/// Show or hide the screen and redraw the levels when they change
+fn update_audio_settings_screen(
+    screen: Res<AudioSettingsScreen>,
+    volumes: Res<AudioSettings>,
+    audio: Res<GlobalAudioSettings>,
+    mut roots: Query<&mut Visibility, With<AudioSettingsRoot>>,
+    mut texts: Query<&mut Text, With<AudioSettingsText>>,
+) {
+    let wanted = if screen.open {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    for mut visibility in roots.iter_mut() {
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+    if !screen.open || !(screen.is_changed() || volumes.is_changed() || audio.is_changed()) {
+        return;
+    }
+    for mut text in texts.iter_mut() {
+        text.0 = audio_settings_text(&volumes, &audio, screen.selected());
+    }
+}
+
+/// Text bar such as `[#######---] 70%`
+fn volume_bar(level: f32) -> String {
+    let filled =
+        ((level.clamp(0.0, 1.0) * VOLUME_BAR_CELLS as f32).round() as usize).min(VOLUME_BAR_CELLS);
+    format!(
+        "[{}{}] {:>3.0}%",
+        "#".repeat(filled),
+        "-".repeat(VOLUME_BAR_CELLS - filled),
+        level * 100.0
+    )
+}
+
+/// Every level with its bar, the picked one marked, then the switches
+fn audio_settings_text(
+    volumes: &AudioSettings,
+    audio: &GlobalAudioSettings,
+    selected: VolumeChannel,
+) -> String {
+    let mut lines: Vec<String> = VolumeChannel::ALL
+        .iter()
+        .map(|channel| {
+            let marker = if *channel == selected { ">" } else { " " };
+            format!(
+                "{} {:<8} {}",
+                marker,
+                channel.label(),
+                volume_bar(volumes.level(*channel))
+            )
+        })
+        .collect();
+    lines.push(String::new());
+    for (number, category) in AudioCategory::all().into_iter().enumerate() {
+        lines.push(format!(
+            "{} {:<9} {}",
+            number + 1,
+            category.label(),
+            if audio.is_category_enabled(category) {
+                "on"
+            } else {
+                "off"
+            }
+        ));
+    }
+    if volumes.muted {
+        lines.push(String::new());
+        lines.push("🔇 Muted - F7 brings the levels back".to_string());
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+
+    fn press(app: &mut App, key: KeyCode) {
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+        app.update();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .reset_all();
+    }
+
+    #[test]
+    fn levels_move_only_while_the_screen_is_open_in_pause() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_state(RpgAppState::Exploration)
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<AudioSettingsScreen>()
+            .init_resource::<AudioSettings>()
+            .init_resource::<GlobalAudioSettings>()
+            .add_systems(
+                Update,
+                (
+                    toggle_audio_settings_system,
+                    mute_system,
+                    adjust_volume_system,
+                    toggle_category_system,
+                )
+                    .chain(),
+            );
+
+        // Not offered while exploring, but mute works anywhere
+        press(&mut app, AUDIO_SETTINGS_KEY);
+        press(&mut app, KeyCode::ArrowLeft);
+        press(&mut app, KeyCode::Digit1);
+        assert!(app.world().resource::<GlobalAudioSettings>().music_enabled);
+        assert!(!app.world().resource::<AudioSettingsScreen>().is_open());
+        assert_eq!(
+            *app.world().resource::<AudioSettings>(),
+            AudioSettings::default()
+        );
+        press(&mut app, MUTE_KEY);
+        assert!(app.world().resource::<AudioSettings>().muted);
+
+        app.world_mut()
+            .resource_mut::<NextState<RpgAppState>>()
+            .set(RpgAppState::Paused);
+        app.update();
+        press(&mut app, AUDIO_SETTINGS_KEY);
+        press(&mut app, KeyCode::ArrowDown);
+        press(&mut app, KeyCode::Minus);
+        press(&mut app, KeyCode::Minus);
+
+        let volumes = app.world().resource::<AudioSettings>();
+        assert_eq!(volumes.music_volume, 0.8);
+        assert_eq!(volumes.master_volume, 1.0);
+        // Adjusting a level brings the sound back to hear it
+        assert!(!volumes.muted);
+
+        press(&mut app, KeyCode::Digit1);
+        let audio = app.world().resource::<GlobalAudioSettings>();
+        assert!(!audio.can_play(AudioCategory::Music));
+        assert!(audio.can_play(AudioCategory::Sfx));
+    }
+
+    #[test]
+    fn the_screen_shows_every_level_and_the_mute() {
+        let mut volumes = AudioSettings::default();
+        volumes.adjust(VolumeChannel::Ambient, -0.7);
+        let mut audio = GlobalAudioSettings::default();
+        audio.set_category_enabled(AudioCategory::Music, false);
+        let text = audio_settings_text(&volumes, &audio, VolumeChannel::Ambient);
+        assert!(text.contains("  Master   [##########] 100%"));
+        assert!(text.contains("> Ambient  [###-------]  30%"));
+        assert!(text.contains("1 Music     off"));
+        assert!(text.contains("3 Effects   on"));
+        assert!(!text.contains("Muted"));
+
+        volumes.toggle_mute();
+        assert!(audio_settings_text(&volumes, &audio, VolumeChannel::Master).contains("Muted"));
+    }
+}
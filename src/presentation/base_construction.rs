@@ -0,0 +1,260 @@
+//! Construction Panel - Building and upgrading in base management
+//!
+//! While managing the base, a panel lists every building type: the ones
+//! standing with their level, the others with what they cost to build.
+//! The arrow keys pick a building and Enter builds or upgrades it, paid
+//! from the player's cargo; a refused construction says why in the log.
+//! The bonuses of the standing buildings are handed to the player whenever
+//! the base changes.
+
+use crate::application::use_cases::{ConstructBuildingUseCase, Construction};
+use crate::application::ApplicationError;
+use crate::domain::constants::{MAX_BUILDING_LEVEL, PANEL_BACKGROUND, PRIMARY_TEXT};
+use crate::domain::entities::{Base, BuildingType};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::FacilityBonuses;
+use crate::infrastructure::bevy::resources::{BaseResource, PlayerResource};
+use crate::presentation::base_visuals::{BaseChange, BaseChanged};
+use crate::presentation::reputation::TradeBoard;
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+
+/// Plugin for the construction panel and the buildings' bonuses
+pub struct BaseConstructionPlugin;
+
+impl Plugin for BaseConstructionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConstructionSelection>()
+            .add_event::<BaseChanged>()
+            .add_systems(Startup, setup_construction_panel)
+            .add_systems(
+                Update,
+                (
+                    construction_input_system,
+                    facility_bonus_system,
+                    update_construction_panel,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Building type highlighted in the construction panel
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ConstructionSelection {
+    pub index: usize,
+}
+
+/// Marker for the construction panel
+#[derive(Component)]
+pub struct ConstructionPanel;
+
+/// Marker for the construction panel text
+#[derive(Component)]
+pub struct ConstructionPanelText;
+
+fn setup_construction_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(15.0),
+                top: Val::Px(80.0),
+                width: Val::Px(420.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(PANEL_BACKGROUND),
+            Visibility::Hidden,
+            ConstructionPanel,
+            Name::new("ConstructionPanel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                ConstructionPanelText,
+            ));
+        });
+}
+
+/// Pick a building and build or upgrade it from the base management screen
+#[allow(clippy::too_many_arguments)]
+fn construction_input_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_state: Res<State<RpgAppState>>,
+    mut base_resource: ResMut<BaseResource>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut selection: ResMut<ConstructionSelection>,
+    mut game_log: ResMut<GameLogService>,
+    mut base_events: EventWriter<BaseChanged>,
+    trade_board: Option<Res<TradeBoard>>,
+) {
+    if *current_state.get() != RpgAppState::BaseManagement {
+        return;
+    }
+    // The trade board covers the panel while it is open
+    if trade_board.is_some_and(|board| board.is_open()) {
+        return;
+    }
+    let building_types = BuildingType::all();
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        selection.index = selection.index.saturating_sub(1);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        selection.index = (selection.index + 1).min(building_types.len() - 1);
+    }
+    if !keyboard.just_pressed(KeyCode::Enter) {
+        return;
+    }
+
+    let Some(base) = base_resource.base_mut() else {
+        return;
+    };
+    let commander_level = player_resource
+        .get_player()
+        .map_or(0, |player| player.level());
+    let building_type = building_types[selection.index];
+    match ConstructBuildingUseCase::new().execute(base, building_type, commander_level, |cost| {
+        player_resource.try_pay_resources(cost)
+    }) {
+        Ok(Construction::Built(building_type)) => {
+            base_events.write(BaseChanged::new(BaseChange::Constructed(building_type)));
+            game_log.log_message(
+                format!("🏗️ {} constructed", building_type.name()),
+                GameLogType::Discovery,
+            );
+        }
+        Ok(Construction::Upgraded {
+            building_type,
+            level,
+        }) => {
+            base_events.write(BaseChanged::new(BaseChange::Upgraded(building_type)));
+            game_log.log_message(
+                format!("🏗️ {} upgraded to level {}", building_type.name(), level),
+                GameLogType::Discovery,
+            );
+        }
+        Err(ApplicationError::DomainError(e)) => {
+            game_log.log_message(format!("{}", e), GameLogType::Warning)
+        }
+        Err(e) => game_log.log_message(format!("{}", e), GameLogType::Warning),
+    }
+}
+
+/// Hand the bonuses of the base's buildings to the player
+fn facility_bonus_system(
+    base_resource: Res<BaseResource>,
+    mut player_resource: ResMut<PlayerResource>,
+) {
+    let bonuses = base_resource
+        .base()
+        .map(FacilityBonuses::of)
+        .unwrap_or_default();
+    if player_resource.facilities() != bonuses {
+        player_resource.set_facilities(bonuses);
+    }
+}
+
+/// Show the panel in base management, unless the trade board covers it
+fn update_construction_panel(
+    current_state: Res<State<RpgAppState>>,
+    base_resource: Res<BaseResource>,
+    player_resource: Res<PlayerResource>,
+    selection: Res<ConstructionSelection>,
+    trade_board: Option<Res<TradeBoard>>,
+    mut panel_query: Query<&mut Visibility, With<ConstructionPanel>>,
+    mut text_query: Query<&mut Text, With<ConstructionPanelText>>,
+) {
+    let Ok(mut visibility) = panel_query.single_mut() else {
+        return;
+    };
+    let covered = trade_board.is_some_and(|board| board.is_open());
+    let base = base_resource
+        .base()
+        .filter(|_| *current_state.get() == RpgAppState::BaseManagement && !covered);
+    let wanted = if base.is_some() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    if *visibility != wanted {
+        *visibility = wanted;
+    }
+
+    let Some(base) = base else {
+        return;
+    };
+    if let Ok(mut text) = text_query.single_mut() {
+        let content = construction_panel_text(base, selection.index, player_resource.facilities());
+        if **text != content {
+            **text = content;
+        }
+    }
+}
+
+fn construction_panel_text(base: &Base, selected: usize, bonuses: FacilityBonuses) -> String {
+    let mut lines = vec!["CONSTRUCTION".to_string()];
+    for (index, building_type) in BuildingType::all().into_iter().enumerate() {
+        let marker = if index == selected { ">" } else { " " };
+        let cost = ConstructBuildingUseCase::next_cost(base, building_type);
+        let status = match base.building(building_type) {
+            None => format!("build: {}", cost),
+            Some(building) if building.level >= MAX_BUILDING_LEVEL => {
+                format!("Lv {} - highest level", building.level)
+            }
+            Some(building) if building.damaged => {
+                format!("Lv {} - damaged", building.level)
+            }
+            Some(building) => format!("Lv {} - upgrade: {}", building.level, cost),
+        };
+        lines.push(format!("{} {} ({})", marker, building_type.name(), status));
+    }
+    lines.push(String::new());
+    lines.push(format!(
+        "BONUSES: +{} cargo | +{} view | +{} night roll",
+        bonuses.cargo_capacity, bonuses.view_radius, bonuses.rest_roll_bonus
+    ));
+    lines.push("UP/DOWN: Select | ENTER: Build or upgrade".to_string());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::BaseBuilding;
+    use crate::domain::value_objects::{EntityId, Position3D};
+
+    #[test]
+    fn panel_shows_costs_levels_and_damage() {
+        let mut base = Base::new(
+            EntityId::generate(),
+            "Home".to_string(),
+            Position3D::origin(),
+        )
+        .unwrap();
+        base.add_building(BaseBuilding::new(
+            BuildingType::LivingQuarters,
+            "Living Quarters".to_string(),
+            (0, 0),
+        ));
+        base.add_building(BaseBuilding::new(
+            BuildingType::Laboratory,
+            "Laboratory".to_string(),
+            (1, 0),
+        ));
+        base.set_building_damaged(BuildingType::Laboratory, true);
+
+        let text = construction_panel_text(&base, 1, FacilityBonuses::of(&base));
+        assert!(text.contains("> Workshop (build: "));
+        assert!(text.contains("  Living Quarters (Lv 1 - upgrade: "));
+        assert!(text.contains("  Laboratory (Lv 1 - damaged)"));
+        assert!(text.contains("BONUSES: +0 cargo | +0 view | +1 night roll"));
+    }
+}
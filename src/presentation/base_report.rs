@@ -0,0 +1,72 @@
+//! Base Report - One ordered digest of what the base did overnight
+//!
+//! Base systems that act on a rest add line items to the pending report
+//! from the economy set; once the objectives set runs, damaged buildings
+//! are noted and the report is written to the game log as a single entry.
+//! There is no night summary or run history to keep it in yet, so the last
+//! report only lives until the next rest.
+
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{BaseAutomationReport, BaseReportItem};
+use crate::infrastructure::bevy::resources::BaseResource;
+use crate::presentation::world_tick::{TickCursor, TickPhase, WorldTick, WorldTickSet};
+use bevy::prelude::*;
+
+/// Plugin for the overnight base report
+pub struct BaseReportPlugin;
+
+impl Plugin for BaseReportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BaseReports>().add_systems(
+            Update,
+            base_report_digest_system.in_set(WorldTickSet::Objectives),
+        );
+    }
+}
+
+/// The report being filled in this rest, and the last finished one
+#[derive(Resource, Debug, Default)]
+pub struct BaseReports {
+    pending: BaseAutomationReport,
+    last: Option<BaseAutomationReport>,
+}
+
+impl BaseReports {
+    /// Add a line item to this rest's report
+    pub fn add(&mut self, item: BaseReportItem) {
+        self.pending.add(item);
+    }
+
+    /// The report of the last rest, if there was one
+    pub fn last(&self) -> Option<&BaseAutomationReport> {
+        self.last.as_ref()
+    }
+
+    /// Close this rest's report and start an empty one
+    fn finish(&mut self) -> &BaseAutomationReport {
+        let report = std::mem::take(&mut self.pending);
+        self.last.insert(report)
+    }
+}
+
+/// Note damaged buildings and log one digest per rest
+pub fn base_report_digest_system(
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    base_resource: Res<BaseResource>,
+    mut reports: ResMut<BaseReports>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    for _ in cursor.take(ticks.read(), TickPhase::AfterRest) {
+        if let Some(base) = base_resource.base() {
+            for building in base.buildings().iter().filter(|building| building.damaged) {
+                reports.add(
+                    BaseReportItem::new(building, "stood idle")
+                        .with_incident("damaged, awaiting repair"),
+                );
+            }
+        }
+        let report = reports.finish();
+        game_log.log_message(format!("🏠 {}", report.digest()), GameLogType::Resources);
+    }
+}
@@ -0,0 +1,344 @@
+//! Base Visuals - Constructed buildings drawn around the base tile
+//!
+//! The base tile and its four neighbours show one model per constructed
+//! building, sized by building tier and swapped for a wrecked model while
+//! damaged. Nothing is polled: construction, upgrades and raid damage send a
+//! [`BaseChanged`] event and the cluster is rebuilt from the new layout. A
+//! working refinery puffs a cloud of smoke each rest.
+
+use crate::domain::constants::BUILDING_VISUAL_TIERS;
+use crate::domain::entities::BuildingType;
+use crate::domain::services::{layout_base, BuildingSlot, BuildingVariant, PlacedBuilding};
+use crate::domain::value_objects::Position3D;
+use crate::infrastructure::bevy::resources::BaseResource;
+use crate::presentation::movement::tile_to_world_position;
+use bevy::prelude::*;
+
+/// Seconds a refinery smoke puff stays visible
+const SMOKE_LIFETIME_SECS: f32 = 2.5;
+
+/// Height between two stacked building indicators on the base tile
+const STACK_STEP: f32 = 0.35;
+
+/// Plugin for the base building cluster
+pub struct BaseVisualPlugin;
+
+impl Plugin for BaseVisualPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BaseChanged>()
+            .init_resource::<BaseLayout>()
+            .add_systems(
+                Update,
+                (
+                    sync_base_layout_system,
+                    base_visual_system,
+                    spawn_refinery_smoke_system,
+                    animate_refinery_smoke_system,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// What happened to the base
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseChange {
+    /// The base was created or restored
+    Founded,
+    Constructed(BuildingType),
+    Upgraded(BuildingType),
+    Damaged(BuildingType),
+    /// The refinery processed a job overnight
+    RefineryWorked,
+}
+
+/// Sent whenever the base's buildings change
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BaseChanged {
+    pub change: BaseChange,
+}
+
+impl BaseChanged {
+    pub fn new(change: BaseChange) -> Self {
+        Self { change }
+    }
+
+    /// Check if the building models need to be rebuilt
+    pub fn changes_buildings(&self) -> bool {
+        self.change != BaseChange::RefineryWorked
+    }
+}
+
+/// Building placement the cluster is currently drawn from
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct BaseLayout {
+    pub buildings: Vec<PlacedBuilding>,
+}
+
+impl BaseLayout {
+    /// Tile a building type is drawn on, if it stands on a neighbour tile
+    pub fn tile_of(&self, building_type: BuildingType) -> Option<Position3D> {
+        self.buildings
+            .iter()
+            .find(|placed| placed.building_type == building_type)
+            .and_then(|placed| match placed.slot {
+                BuildingSlot::Neighbor(tile) => Some(tile),
+                BuildingSlot::Stacked(_) => None,
+            })
+    }
+}
+
+/// Marker for building models of the base cluster
+#[derive(Component)]
+pub struct BaseBuildingVisual;
+
+/// A rising puff of refinery smoke
+#[derive(Component)]
+pub struct RefinerySmoke {
+    pub age: f32,
+}
+
+/// Recompute the layout when a building change is reported
+fn sync_base_layout_system(
+    mut events: EventReader<BaseChanged>,
+    base_resource: Res<BaseResource>,
+    mut layout: ResMut<BaseLayout>,
+) {
+    let changed = events
+        .read()
+        .fold(false, |changed, event| changed | event.changes_buildings());
+    if !changed {
+        return;
+    }
+    let buildings = base_resource.base().map(layout_base).unwrap_or_default();
+    layout.set_if_neq(BaseLayout { buildings });
+}
+
+/// Rebuild the building models from the layout
+fn base_visual_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    layout: Res<BaseLayout>,
+    base_resource: Res<BaseResource>,
+    visuals: Query<Entity, With<BaseBuildingVisual>>,
+) {
+    if !layout.is_changed() {
+        return;
+    }
+    for entity in visuals.iter() {
+        commands.entity(entity).despawn();
+    }
+    let Some(base_tile) = base_resource.base_position() else {
+        return;
+    };
+
+    for placed in &layout.buildings {
+        let (translation, scale) = match placed.slot {
+            BuildingSlot::Neighbor(tile) => (tile_to_world_position(tile) + Vec3::Y * 0.4, 1.0),
+            BuildingSlot::Stacked(index) => (
+                tile_to_world_position(base_tile) + Vec3::Y * (0.9 + index as f32 * STACK_STEP),
+                0.3,
+            ),
+        };
+        let color = building_color(placed.building_type);
+        let (mesh, material, rotation) = match placed.variant {
+            BuildingVariant::Intact { tier } => {
+                let size = 0.6 + 0.2 * f32::from(tier - 1) / f32::from(BUILDING_VISUAL_TIERS);
+                (
+                    meshes.add(Cuboid::new(size, 0.5 + 0.25 * f32::from(tier), size)),
+                    materials.add(StandardMaterial {
+                        base_color: color,
+                        ..default()
+                    }),
+                    Quat::IDENTITY,
+                )
+            }
+            BuildingVariant::Damaged => (
+                meshes.add(Cuboid::new(0.6, 0.3, 0.6)),
+                materials.add(StandardMaterial {
+                    base_color: scorched(color),
+                    perceptual_roughness: 1.0,
+                    ..default()
+                }),
+                Quat::from_rotation_z(0.25),
+            ),
+        };
+
+        let mut building = commands.spawn((
+            Mesh3d(mesh),
+            MeshMaterial3d(material),
+            Transform::from_translation(translation)
+                .with_rotation(rotation)
+                .with_scale(Vec3::splat(scale)),
+            BaseBuildingVisual,
+            Name::new(format!("BaseBuilding({:?})", placed.building_type)),
+        ));
+
+        // Upgraded buildings get an antenna on the roof
+        if let BuildingVariant::Intact { tier } = placed.variant {
+            if tier > 1 && matches!(placed.slot, BuildingSlot::Neighbor(_)) {
+                let antenna = meshes.add(Cylinder::new(0.04, 0.2 * f32::from(tier)));
+                let antenna_material = materials.add(StandardMaterial {
+                    base_color: Color::srgb(0.8, 0.8, 0.85),
+                    ..default()
+                });
+                building.with_children(|parent| {
+                    parent.spawn((
+                        Mesh3d(antenna),
+                        MeshMaterial3d(antenna_material),
+                        Transform::from_xyz(0.15, 0.45 + 0.2 * f32::from(tier), 0.15),
+                    ));
+                });
+            }
+        }
+    }
+}
+
+/// Puff smoke from the refinery each rest it worked
+fn spawn_refinery_smoke_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut events: EventReader<BaseChanged>,
+    layout: Res<BaseLayout>,
+) {
+    let worked = events
+        .read()
+        .filter(|event| event.change == BaseChange::RefineryWorked)
+        .count()
+        > 0;
+    let Some(tile) = layout.tile_of(BuildingType::Refinery).filter(|_| worked) else {
+        return;
+    };
+    commands.spawn((
+        Mesh3d(meshes.add(Sphere::new(0.25))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::srgba(0.7, 0.7, 0.7, 0.6),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        })),
+        Transform::from_translation(tile_to_world_position(tile) + Vec3::Y * 1.3),
+        RefinerySmoke { age: 0.0 },
+        Name::new("RefinerySmoke"),
+    ));
+}
+
+/// Let smoke rise, grow and disappear
+fn animate_refinery_smoke_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut puffs: Query<(Entity, &mut RefinerySmoke, &mut Transform)>,
+) {
+    for (entity, mut smoke, mut transform) in puffs.iter_mut() {
+        smoke.age += time.delta_secs();
+        if smoke.age >= SMOKE_LIFETIME_SECS {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.translation.y += time.delta_secs() * 0.6;
+        transform.scale = Vec3::splat(1.0 + smoke.age / SMOKE_LIFETIME_SECS);
+    }
+}
+
+/// Signature color of each building type
+fn building_color(building_type: BuildingType) -> Color {
+    match building_type {
+        BuildingType::ResourceStorage => Color::srgb(0.55, 0.45, 0.3),
+        BuildingType::Workshop => Color::srgb(0.6, 0.6, 0.65),
+        BuildingType::Laboratory => Color::srgb(0.85, 0.9, 0.95),
+        BuildingType::PowerPlant => Color::srgb(0.95, 0.8, 0.2),
+        BuildingType::LivingQuarters => Color::srgb(0.4, 0.65, 0.45),
+        BuildingType::DefenseSystem => Color::srgb(0.7, 0.25, 0.25),
+        BuildingType::Refinery => Color::srgb(0.45, 0.4, 0.5),
+    }
+}
+
+/// Blackened tint for damaged buildings
+fn scorched(color: Color) -> Color {
+    let srgba = color.to_srgba();
+    Color::srgb(srgba.red * 0.4, srgba.green * 0.35, srgba.blue * 0.3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::BaseBuilding;
+
+    fn app_with_base() -> App {
+        let mut app = App::new();
+        let mut base_resource = BaseResource::new();
+        base_resource
+            .create_base("Central Command".to_string(), Position3D::origin())
+            .unwrap();
+        app.add_event::<BaseChanged>()
+            .init_resource::<BaseLayout>()
+            .insert_resource(base_resource)
+            .add_systems(Update, sync_base_layout_system);
+        app
+    }
+
+    fn construct(app: &mut App, building_type: BuildingType) {
+        app.world_mut()
+            .resource_mut::<BaseResource>()
+            .base_mut()
+            .unwrap()
+            .add_building(BaseBuilding::new(
+                building_type,
+                format!("{:?}", building_type),
+                (0, 0),
+            ));
+    }
+
+    #[test]
+    fn layout_refreshes_only_on_building_events() {
+        let mut app = app_with_base();
+        construct(&mut app, BuildingType::Refinery);
+        app.update();
+        assert!(app.world().resource::<BaseLayout>().buildings.is_empty());
+
+        // Smoke alone does not rebuild the models
+        app.world_mut()
+            .send_event(BaseChanged::new(BaseChange::RefineryWorked));
+        app.update();
+        assert!(app.world().resource::<BaseLayout>().buildings.is_empty());
+
+        app.world_mut()
+            .send_event(BaseChanged::new(BaseChange::Constructed(
+                BuildingType::Refinery,
+            )));
+        app.update();
+        let layout = app.world().resource::<BaseLayout>();
+        assert_eq!(layout.buildings.len(), 1);
+        assert_eq!(
+            layout.tile_of(BuildingType::Refinery),
+            Some(Position3D::new(1, 0, 0))
+        );
+    }
+
+    #[test]
+    fn damage_events_switch_to_the_damaged_variant() {
+        let mut app = app_with_base();
+        construct(&mut app, BuildingType::Workshop);
+        app.world_mut()
+            .send_event(BaseChanged::new(BaseChange::Founded));
+        app.update();
+
+        app.world_mut()
+            .resource_mut::<BaseResource>()
+            .base_mut()
+            .unwrap()
+            .set_building_damaged(BuildingType::Workshop, true);
+        app.world_mut()
+            .send_event(BaseChanged::new(BaseChange::Damaged(
+                BuildingType::Workshop,
+            )));
+        app.update();
+
+        assert_eq!(
+            app.world().resource::<BaseLayout>().buildings[0].variant,
+            BuildingVariant::Damaged
+        );
+    }
+}
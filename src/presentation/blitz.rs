@@ -0,0 +1,351 @@
+//! Blitz Mode - Countdown HUD and autopilot for time-boxed runs
+//!
+//! Runs started with blitz enabled in the settings show a countdown for
+//! every exploration decision. Any move starts a fresh decision. If the
+//! countdown runs out, the autopilot steps towards the nearest affordable
+//! unexplored tile through the same movement events as keyboard input, and
+//! the game log records that it acted.
+
+use crate::domain::constants::{BLITZ_CRITICAL_SECS, CRITICAL_TEXT, WARNING_TEXT};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{
+    autopilot_direction, BlitzClock, BlitzPause, LowPointsGuard, PathfindingService, WorldHazards,
+};
+use crate::infrastructure::bevy::resources::{
+    GameStatsResource, MapResource, PartyResource, PlayerResource,
+};
+use crate::infrastructure::settings::BlitzSettings;
+use crate::presentation::map_renderer::PlayerMarker;
+use crate::presentation::movement::{
+    begin_player_step, movement_cost_at, ExecuteRpgMovement, MovementConfig, MovementStarted,
+    SmoothMovement,
+};
+use crate::presentation::reputation::HostileContact;
+use crate::presentation::scout_probe::ScoutProbeLauncher;
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+
+/// Plugin for the blitz countdown and autopilot
+pub struct BlitzPlugin;
+
+impl Plugin for BlitzPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BlitzState>()
+            .init_resource::<BlitzSettings>()
+            .add_systems(Startup, setup_blitz_hud)
+            .add_systems(
+                Update,
+                (
+                    blitz_countdown_system,
+                    blitz_autopilot_system,
+                    update_blitz_hud,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Countdown of the current blitz run
+#[derive(Resource, Debug, Clone, Default)]
+pub struct BlitzState {
+    clock: Option<BlitzClock>,
+    paused: bool,
+    autopilot_due: bool,
+}
+
+impl BlitzState {
+    /// Check if the current run is a blitz run
+    pub fn is_active(&self) -> bool {
+        self.clock.is_some()
+    }
+
+    /// Countdown of the current decision
+    pub fn clock(&self) -> Option<&BlitzClock> {
+        self.clock.as_ref()
+    }
+
+    /// Check if the countdown is currently holding
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Begin timing decisions with `decision_secs` each
+    pub fn start(&mut self, decision_secs: f32) {
+        self.clock = Some(BlitzClock::new(decision_secs));
+        self.paused = false;
+        self.autopilot_due = false;
+    }
+
+    /// Stop timing decisions
+    pub fn stop(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Advance the countdown; `moved` starts a fresh decision first
+    pub fn advance(&mut self, delta_secs: f32, pause: Option<BlitzPause>, moved: bool) {
+        let Some(clock) = self.clock.as_mut() else {
+            return;
+        };
+        if moved {
+            clock.reset();
+            self.autopilot_due = false;
+        }
+        self.paused = pause.is_some();
+        if clock.tick(delta_secs, pause) {
+            self.autopilot_due = true;
+        }
+    }
+
+    /// Claim the pending autopilot move, if the countdown ran out
+    pub fn take_autopilot_turn(&mut self) -> bool {
+        std::mem::take(&mut self.autopilot_due)
+    }
+
+    /// Give the player a fresh decision without moving
+    pub fn restart_decision(&mut self) {
+        if let Some(clock) = self.clock.as_mut() {
+            clock.reset();
+        }
+    }
+}
+
+/// Why the countdown should hold this frame, if at all
+pub fn blitz_pause(
+    state: &RpgAppState,
+    moving: bool,
+    movement_points: u8,
+    guard_blocks: bool,
+) -> Option<BlitzPause> {
+    match state {
+        RpgAppState::Exploration => {}
+        RpgAppState::Combat | RpgAppState::EventResolution => return Some(BlitzPause::Encounter),
+        _ => return Some(BlitzPause::Menu),
+    }
+    if guard_blocks {
+        Some(BlitzPause::Menu)
+    } else if moving {
+        Some(BlitzPause::Animating)
+    } else if movement_points == 0 {
+        Some(BlitzPause::Resting)
+    } else {
+        None
+    }
+}
+
+/// Marker for the countdown HUD line
+#[derive(Component)]
+pub struct BlitzHudText;
+
+fn setup_blitz_hud(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: FontSize::Medium.to_pixels(),
+            ..default()
+        },
+        TextColor(WARNING_TEXT),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(15.0),
+            bottom: Val::Px(90.0),
+            ..default()
+        },
+        BlitzHudText,
+        Name::new("BlitzHud"),
+    ));
+}
+
+/// Run the countdown while the player could act
+#[allow(clippy::too_many_arguments)]
+fn blitz_countdown_system(
+    time: Res<Time>,
+    settings: Res<BlitzSettings>,
+    game_stats: Res<GameStatsResource>,
+    current_state: Res<State<RpgAppState>>,
+    player_resource: Res<PlayerResource>,
+    low_points_guard: Option<Res<LowPointsGuard>>,
+    probe_launcher: Option<Res<ScoutProbeLauncher>>,
+    hostile_contact: Option<Res<HostileContact>>,
+    party: Option<Res<PartyResource>>,
+    player_query: Query<&SmoothMovement, With<PlayerMarker>>,
+    mut movement_events: EventReader<MovementStarted>,
+    mut blitz: ResMut<BlitzState>,
+) {
+    let moved = movement_events.read().count() > 0;
+    if !game_stats.blitz {
+        if blitz.is_active() {
+            blitz.stop();
+        }
+        return;
+    }
+    if !blitz.is_active() {
+        blitz.start(settings.decision_secs());
+    }
+    let Some(player) = player_resource.get_player() else {
+        return;
+    };
+
+    let moving = player_query
+        .single()
+        .is_ok_and(|movement| movement.is_moving);
+    let guard_blocks = low_points_guard.is_some_and(|guard| guard.blocks_movement())
+        || probe_launcher.is_some_and(|launcher| launcher.is_choosing())
+        || hostile_contact.is_some_and(|contact| contact.is_pending())
+        || party.is_some_and(|party| party.blocks_movement());
+    let pause = blitz_pause(
+        current_state.get(),
+        moving,
+        player.movement_points(),
+        guard_blocks,
+    );
+    blitz.advance(time.delta_secs(), pause, moved);
+}
+
+/// Take one step for the player when the countdown ran out
+#[allow(clippy::too_many_arguments)]
+fn blitz_autopilot_system(
+    mut blitz: ResMut<BlitzState>,
+    mut player_query: Query<(&mut SmoothMovement, Entity), With<PlayerMarker>>,
+    player_resource: Res<PlayerResource>,
+    map_resource: Res<MapResource>,
+    world_hazards: Option<Res<WorldHazards>>,
+    config: Res<MovementConfig>,
+    mut game_stats: ResMut<GameStatsResource>,
+    mut game_log: ResMut<GameLogService>,
+    mut movement_started_events: EventWriter<MovementStarted>,
+    mut execute_rpg_events: EventWriter<ExecuteRpgMovement>,
+) {
+    if !blitz.take_autopilot_turn() {
+        return;
+    }
+    let (Some(player), Some(map)) = (player_resource.get_player(), map_resource.current_map())
+    else {
+        return;
+    };
+    let Ok((mut smooth_movement, entity)) = player_query.single_mut() else {
+        return;
+    };
+
+    let from = smooth_movement.target_position;
+    let step = autopilot_direction(&PathfindingService::new(), map, from).filter(|direction| {
+        let cost = movement_cost_at(
+            &map_resource,
+            world_hazards.as_deref(),
+            &game_stats.modifiers,
+            from.move_direction(*direction, 1),
+        );
+        cost <= player.movement_points()
+    });
+
+    match step {
+        Some(direction) => {
+            begin_player_step(
+                &mut smooth_movement,
+                entity,
+                direction,
+                &config,
+                &mut movement_started_events,
+                &mut execute_rpg_events,
+            );
+            game_stats.record_autopilot_move();
+            game_log.log_message(
+                format!(
+                    "🤖 Time's up! The autopilot moved {:?} towards unexplored ground",
+                    direction
+                ),
+                GameLogType::Movement,
+            );
+        }
+        None => {
+            blitz.restart_decision();
+            game_log.log_message(
+                "🤖 Time's up! The autopilot found no unexplored tile within reach".to_string(),
+                GameLogType::Warning,
+            );
+        }
+    }
+}
+
+/// Show the seconds left for the current decision
+fn update_blitz_hud(
+    blitz: Res<BlitzState>,
+    mut hud_query: Query<(&mut Text, &mut TextColor), With<BlitzHudText>>,
+) {
+    if !blitz.is_changed() {
+        return;
+    }
+    let Ok((mut text, mut color)) = hud_query.single_mut() else {
+        return;
+    };
+
+    let (line, line_color) = match blitz.clock() {
+        None => (String::new(), WARNING_TEXT),
+        Some(clock) if blitz.is_paused() => {
+            (format!("BLITZ ⏸ {:.0}s", clock.remaining()), WARNING_TEXT)
+        }
+        Some(clock) => (
+            format!("BLITZ ⏱ {:.1}s", clock.remaining()),
+            if clock.remaining() <= BLITZ_CRITICAL_SECS {
+                CRITICAL_TEXT
+            } else {
+                WARNING_TEXT
+            },
+        ),
+    };
+
+    if **text != line {
+        **text = line;
+    }
+    if color.0 != line_color {
+        color.0 = line_color;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn countdown_pauses_outside_of_free_exploration() {
+        let exploring = RpgAppState::Exploration;
+        assert_eq!(blitz_pause(&exploring, false, 5, false), None);
+        assert_eq!(
+            blitz_pause(&exploring, true, 5, false),
+            Some(BlitzPause::Animating)
+        );
+        assert_eq!(
+            blitz_pause(&exploring, false, 0, false),
+            Some(BlitzPause::Resting)
+        );
+        assert_eq!(
+            blitz_pause(&exploring, false, 5, true),
+            Some(BlitzPause::Menu)
+        );
+        assert_eq!(
+            blitz_pause(&RpgAppState::Combat, false, 5, false),
+            Some(BlitzPause::Encounter)
+        );
+        assert_eq!(
+            blitz_pause(&RpgAppState::Inventory, false, 5, false),
+            Some(BlitzPause::Menu)
+        );
+    }
+
+    #[test]
+    fn expiry_schedules_one_autopilot_turn_until_the_next_move() {
+        let mut blitz = BlitzState::default();
+        blitz.advance(20.0, None, false);
+        assert!(!blitz.take_autopilot_turn());
+
+        blitz.start(5.0);
+        blitz.advance(6.0, None, false);
+        assert!(blitz.take_autopilot_turn());
+        assert!(!blitz.take_autopilot_turn());
+
+        // The autopilot's move starts a fresh decision
+        blitz.advance(1.0, Some(BlitzPause::Animating), true);
+        assert!(blitz.is_paused());
+        assert_eq!(blitz.clock().unwrap().remaining(), 5.0);
+    }
+}
@@ -0,0 +1,219 @@
+//! Bug Report - One keypress exports everything needed to triage a bug
+//!
+//! Pressing F10 bundles the running session, the settings, the recorded
+//! player events, the tail of the game log, the world seed, a frame timing
+//! summary and the startup asset check into a single JSON report. Native builds write it next to
+//! the save; on the web the page picks it up through `take_bug_report`, and
+//! can ask for one with `request_bug_report`. The game log says where the
+//! report went.
+
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::infrastructure::bevy::resources::MapResource;
+use crate::infrastructure::bug_report::{
+    private_strings, BugReport, PerformanceSummary, RecordedEvent, ReportLogEntry,
+    BUG_REPORT_EVENT_CAPACITY, BUG_REPORT_LOG_ENTRIES, BUG_REPORT_MAX_BYTES,
+};
+use crate::infrastructure::saves::SaveData;
+use crate::infrastructure::settings::SettingsStore;
+use crate::presentation::asset_integrity::AssetIntegrity;
+use crate::presentation::game_event_logger::PlayerChangedEvent;
+use crate::presentation::game_state::RpgGameSession;
+use bevy::diagnostic::{DiagnosticPath, DiagnosticsStore, FrameCount, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Key that exports a bug report
+pub const BUG_REPORT_KEY: KeyCode = KeyCode::F10;
+
+/// Whether the page asked for a report, served on the next frame
+static REPORT_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Latest report waiting for the page to take it
+static LATEST_REPORT: Mutex<Option<String>> = Mutex::new(None);
+
+/// Ask for a report from outside the ECS, e.g. the page's controls
+pub fn request_bug_report() {
+    REPORT_REQUESTED.store(true, Ordering::Relaxed);
+}
+
+/// Take the latest exported report, if one is waiting
+pub fn take_bug_report() -> Option<String> {
+    LATEST_REPORT
+        .lock()
+        .ok()
+        .and_then(|mut latest| latest.take())
+}
+
+/// Plugin for the event recorder and the bug report export
+pub struct BugReportPlugin;
+
+impl Plugin for BugReportPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+        }
+        app.init_resource::<EventRecorder>().add_systems(
+            Update,
+            (record_player_events, export_bug_report_system).chain(),
+        );
+    }
+}
+
+/// Bounded buffer of the most recent player events
+#[derive(Resource, Debug, Clone, Default)]
+pub struct EventRecorder {
+    events: VecDeque<RecordedEvent>,
+}
+
+impl EventRecorder {
+    /// Record an event, forgetting the oldest once the buffer is full
+    pub fn record(&mut self, frame: u32, description: String) {
+        if self.events.len() >= BUG_REPORT_EVENT_CAPACITY {
+            self.events.pop_front();
+        }
+        self.events.push_back(RecordedEvent { frame, description });
+    }
+
+    /// Recorded events, oldest first
+    pub fn events(&self) -> Vec<RecordedEvent> {
+        self.events.iter().cloned().collect()
+    }
+}
+
+/// Record every change made to the player
+fn record_player_events(
+    mut player_events: EventReader<PlayerChangedEvent>,
+    mut recorder: ResMut<EventRecorder>,
+    frames: Res<FrameCount>,
+) {
+    for event in player_events.read() {
+        recorder.record(frames.0, format!("{:?}", event.change));
+    }
+}
+
+/// Export a report on F10 or when the page asks for one
+#[allow(clippy::too_many_arguments)]
+fn export_bug_report_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    recorder: Res<EventRecorder>,
+    mut game_log: ResMut<GameLogService>,
+    map_resource: Res<MapResource>,
+    session: Option<Res<RpgGameSession>>,
+    settings_store: Option<Res<SettingsStore>>,
+    diagnostics: Option<Res<DiagnosticsStore>>,
+    asset_integrity: Option<Res<AssetIntegrity>>,
+    frames: Res<FrameCount>,
+) {
+    let requested = REPORT_REQUESTED.swap(false, Ordering::Relaxed);
+    if !keyboard.just_pressed(BUG_REPORT_KEY) && !requested {
+        return;
+    }
+
+    let log = game_log
+        .get_recent_messages(BUG_REPORT_LOG_ENTRIES)
+        .into_iter()
+        .map(|message| ReportLogEntry {
+            sequence: message.sequence,
+            timestamp: message.timestamp,
+            kind: format!("{:?}", message.log_type),
+            message: message.display_text(),
+        })
+        .collect();
+    let measure = |path: &DiagnosticPath| {
+        diagnostics
+            .as_ref()
+            .and_then(|store| store.get(path))
+            .and_then(|diagnostic| diagnostic.smoothed())
+    };
+    let performance = PerformanceSummary {
+        frames: frames.0,
+        fps: measure(&FrameTimeDiagnosticsPlugin::FPS),
+        frame_time_ms: measure(&FrameTimeDiagnosticsPlugin::FRAME_TIME),
+    };
+
+    let mut report = BugReport::new(
+        map_resource.overworld().map(|map| map.seed()),
+        session.as_deref().map(SaveData::from_session),
+        settings_store
+            .as_deref()
+            .map(|store| store.settings.clone())
+            .unwrap_or_default(),
+        recorder.events(),
+        log,
+        performance,
+        asset_integrity.map(|integrity| integrity.report().clone()),
+    );
+    report.scrub(&private_strings());
+    let json = match report.to_json_capped(BUG_REPORT_MAX_BYTES) {
+        Ok(json) => json,
+        Err(error) => {
+            warn!("Bug report could not be exported: {}", error);
+            game_log.log_message(
+                "🐞 The bug report could not be created".to_string(),
+                GameLogType::Warning,
+            );
+            return;
+        }
+    };
+
+    deliver_report(json, &mut game_log);
+}
+
+/// Write the report next to the save and say where it went
+#[cfg(not(target_arch = "wasm32"))]
+fn deliver_report(json: String, game_log: &mut GameLogService) {
+    use crate::infrastructure::bug_report::{bug_report_file_name, write_bug_report};
+
+    let file_name = bug_report_file_name(chrono::Utc::now());
+    match write_bug_report(std::path::Path::new(&file_name), &json) {
+        Ok(()) => {
+            info!("Bug report written to {}", file_name);
+            game_log.log_message(
+                format!("🐞 Bug report saved as {}", file_name),
+                GameLogType::System,
+            );
+        }
+        Err(error) => {
+            warn!("Bug report could not be written: {}", error);
+            game_log.log_message(
+                "🐞 The bug report could not be saved".to_string(),
+                GameLogType::Warning,
+            );
+        }
+    }
+}
+
+/// Hand the report to the page
+#[cfg(target_arch = "wasm32")]
+fn deliver_report(json: String, game_log: &mut GameLogService) {
+    if let Ok(mut latest) = LATEST_REPORT.lock() {
+        *latest = Some(json);
+    }
+    game_log.log_message(
+        "🐞 Bug report ready to download from the page".to_string(),
+        GameLogType::System,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorder_keeps_only_the_newest_events() {
+        let mut recorder = EventRecorder::default();
+        for frame in 0..(BUG_REPORT_EVENT_CAPACITY as u32 + 10) {
+            recorder.record(frame, format!("event {}", frame));
+        }
+
+        let events = recorder.events();
+        assert_eq!(events.len(), BUG_REPORT_EVENT_CAPACITY);
+        assert_eq!(events[0].frame, 10);
+        assert_eq!(
+            events.last().unwrap().description,
+            format!("event {}", BUG_REPORT_EVENT_CAPACITY + 9)
+        );
+    }
+}
@@ -0,0 +1,367 @@
+//! Camera Hints - Briefly show where an off-screen event happened
+//!
+//! Systems that raise a notable event with a map position can send a
+//! [`CameraHintRequest`]. If the position is off-screen, the camera pans to
+//! frame it for a moment, a labeled marker appears, and the camera returns to
+//! the player along its usual follow path. Hints are queued, spaced out and
+//! deduplicated by position. Any movement input skips the current hint
+//! without consuming the key press, so the move still happens. With reduce
+//! motion enabled, hints are ignored.
+
+use crate::domain::constants::{
+    CAMERA_HINT_DURATION_SECS, CAMERA_HINT_MAX_QUEUED, CAMERA_HINT_MIN_INTERVAL_SECS,
+    CAMERA_HINT_SCREEN_MARGIN, PRIMARY_TEXT, WARNING_TEXT,
+};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::value_objects::Position3D;
+use crate::presentation::input::InputMapper;
+use crate::presentation::movement::{tile_to_world_position, MovementConfig};
+use crate::presentation::rendering::DisplaySettings;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Plugin for event-driven camera hints
+pub struct CameraHintPlugin;
+
+impl Plugin for CameraHintPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CameraHintRequest>()
+            .init_resource::<CameraHintQueue>()
+            .init_resource::<InputMapper>()
+            .add_systems(Startup, setup_camera_hint_label)
+            .add_systems(
+                Update,
+                (
+                    skip_camera_hint_on_movement,
+                    queue_camera_hints,
+                    advance_camera_hints,
+                    update_camera_hint_marker,
+                )
+                    .chain()
+                    .before(crate::presentation::movement::handle_player_movement_input),
+            );
+    }
+}
+
+/// Ask the camera to show where something happened
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct CameraHintRequest {
+    pub position: Position3D,
+    /// Short text shown next to the marker
+    pub label: String,
+}
+
+/// A hint being shown
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveCameraHint {
+    pub request: CameraHintRequest,
+    pub remaining_secs: f32,
+}
+
+/// Pending and active camera hints
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CameraHintQueue {
+    pending: VecDeque<CameraHintRequest>,
+    active: Option<ActiveCameraHint>,
+    last_started_secs: Option<f32>,
+}
+
+impl CameraHintQueue {
+    /// Queue a hint; returns false for a position already pending or shown
+    pub fn push(&mut self, request: CameraHintRequest) -> bool {
+        let duplicate = self
+            .active
+            .iter()
+            .map(|hint| &hint.request)
+            .chain(self.pending.iter())
+            .any(|queued| queued.position == request.position);
+        if duplicate {
+            return false;
+        }
+        if self.pending.len() >= CAMERA_HINT_MAX_QUEUED {
+            self.pending.pop_front();
+        }
+        self.pending.push_back(request);
+        true
+    }
+
+    /// Start the next hint if none is active and the last one is long enough ago
+    pub fn start_next(&mut self, now_secs: f32) -> Option<&ActiveCameraHint> {
+        if self.active.is_some() {
+            return None;
+        }
+        if self
+            .last_started_secs
+            .is_some_and(|last| now_secs - last < CAMERA_HINT_MIN_INTERVAL_SECS)
+        {
+            return None;
+        }
+        let request = self.pending.pop_front()?;
+        self.last_started_secs = Some(now_secs);
+        self.active = Some(ActiveCameraHint {
+            request,
+            remaining_secs: CAMERA_HINT_DURATION_SECS,
+        });
+        self.active.as_ref()
+    }
+
+    /// Count the active hint down; returns true when it just ended
+    pub fn tick(&mut self, delta_secs: f32) -> bool {
+        let Some(hint) = self.active.as_mut() else {
+            return false;
+        };
+        hint.remaining_secs -= delta_secs;
+        if hint.remaining_secs > 0.0 {
+            return false;
+        }
+        self.active = None;
+        true
+    }
+
+    /// End the active hint early
+    pub fn skip(&mut self) -> bool {
+        self.active.take().is_some()
+    }
+
+    /// Drop every hint, e.g. when reduce motion is switched on
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.active = None;
+    }
+
+    /// The hint being shown
+    pub fn active(&self) -> Option<&ActiveCameraHint> {
+        self.active.as_ref()
+    }
+
+    /// World position the camera should frame instead of the player
+    pub fn focus(&self) -> Option<Vec3> {
+        self.active
+            .as_ref()
+            .map(|hint| tile_to_world_position(hint.request.position))
+    }
+
+    /// Number of hints waiting
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+/// Check if a point in normalized device coordinates lies outside the view
+///
+/// `None` means the point could not be projected, e.g. it is behind the camera.
+pub fn is_off_screen(ndc: Option<Vec3>) -> bool {
+    match ndc {
+        Some(ndc) => {
+            ndc.x.abs() > CAMERA_HINT_SCREEN_MARGIN
+                || ndc.y.abs() > CAMERA_HINT_SCREEN_MARGIN
+                || !(0.0..=1.0).contains(&ndc.z)
+        }
+        None => true,
+    }
+}
+
+/// Marker for the hint marker in the world
+#[derive(Component)]
+pub struct CameraHintMarker;
+
+/// Marker for the hint label
+#[derive(Component)]
+pub struct CameraHintLabel;
+
+fn setup_camera_hint_label(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: FontSize::Regular.to_pixels(),
+            ..default()
+        },
+        TextColor(PRIMARY_TEXT),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(60.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        TextLayout::new_with_justify(JustifyText::Center),
+        CameraHintLabel,
+        Name::new("CameraHintLabel"),
+    ));
+}
+
+/// Any movement key ends the hint; the key press is left for the movement systems
+fn skip_camera_hint_on_movement(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    config: Res<MovementConfig>,
+    mut queue: ResMut<CameraHintQueue>,
+    input_mapper: Res<InputMapper>,
+) {
+    if queue.active().is_none() {
+        return;
+    }
+    let movement_key =
+        config.enable_keyboard_movement && input_mapper.just_pressed_movement(&keyboard).is_some();
+    let movement_click = config.enable_click_to_move && mouse.just_pressed(MouseButton::Left);
+    if movement_key || movement_click {
+        queue.skip();
+    }
+}
+
+/// Queue requests whose position is off-screen
+fn queue_camera_hints(
+    mut requests: EventReader<CameraHintRequest>,
+    display: Option<Res<DisplaySettings>>,
+    mut queue: ResMut<CameraHintQueue>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+) {
+    if display.is_some_and(|display| display.reduce_motion) {
+        requests.clear();
+        if queue.active().is_some() || queue.pending_len() > 0 {
+            queue.clear();
+        }
+        return;
+    }
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        requests.clear();
+        return;
+    };
+    for request in requests.read() {
+        let world = tile_to_world_position(request.position);
+        if is_off_screen(camera.world_to_ndc(camera_transform, world)) {
+            queue.push(request.clone());
+        }
+    }
+}
+
+/// Start queued hints when allowed and end them when their time is up
+fn advance_camera_hints(time: Res<Time>, mut queue: ResMut<CameraHintQueue>) {
+    queue.tick(time.delta_secs());
+    if let Some(hint) = queue.start_next(time.elapsed_secs()) {
+        info!("🎥 Camera hint: {}", hint.request.label);
+    }
+}
+
+/// Show the marker and label while a hint is active
+fn update_camera_hint_marker(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    queue: Res<CameraHintQueue>,
+    markers: Query<Entity, With<CameraHintMarker>>,
+    mut label_query: Query<&mut Text, With<CameraHintLabel>>,
+    mut shown: Local<Option<Position3D>>,
+) {
+    let wanted = queue.active().map(|hint| hint.request.position);
+    if *shown == wanted {
+        return;
+    }
+
+    for entity in markers.iter() {
+        commands.entity(entity).despawn();
+    }
+    if let (Some(position), Some(world)) = (wanted, queue.focus()) {
+        commands.spawn((
+            Mesh3d(meshes.add(Mesh::from(Torus::new(0.35, 0.45)))),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: WARNING_TEXT,
+                emissive: LinearRgba::from(WARNING_TEXT) * 0.8,
+                unlit: true,
+                ..default()
+            })),
+            Transform::from_translation(Vec3::new(world.x, 0.7, world.z)),
+            CameraHintMarker,
+            Name::new(format!("CameraHintMarker({}, {})", position.x, position.y)),
+        ));
+    }
+
+    if let Ok(mut text) = label_query.single_mut() {
+        **text = queue
+            .active()
+            .map(|hint| format!("📍 {}", hint.request.label))
+            .unwrap_or_default();
+    }
+    *shown = wanted;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(x: i32, y: i32) -> CameraHintRequest {
+        CameraHintRequest {
+            position: Position3D::new(x, y, 0),
+            label: format!("Event at {}, {}", x, y),
+        }
+    }
+
+    #[test]
+    fn off_screen_uses_the_margin_and_depth() {
+        assert!(!is_off_screen(Some(Vec3::new(0.0, 0.0, 0.5))));
+        assert!(!is_off_screen(Some(Vec3::new(-0.85, 0.85, 0.5))));
+        assert!(is_off_screen(Some(Vec3::new(0.95, 0.0, 0.5))));
+        assert!(is_off_screen(Some(Vec3::new(0.0, -1.5, 0.5))));
+        assert!(is_off_screen(Some(Vec3::new(0.0, 0.0, -0.1))));
+        assert!(is_off_screen(None));
+    }
+
+    #[test]
+    fn queue_is_rate_limited_and_deduplicated() {
+        let mut queue = CameraHintQueue::default();
+        assert!(queue.push(request(5, 5)));
+        assert!(!queue.push(request(5, 5)));
+        assert!(queue.push(request(9, 0)));
+
+        assert!(queue.start_next(10.0).is_some());
+        // Still a duplicate while it is being shown
+        assert!(!queue.push(request(5, 5)));
+
+        assert!(!queue.tick(1.0));
+        assert!(queue.tick(CAMERA_HINT_DURATION_SECS));
+        assert!(queue.start_next(12.0).is_none());
+        let next = queue
+            .start_next(10.0 + CAMERA_HINT_MIN_INTERVAL_SECS)
+            .unwrap();
+        assert_eq!(next.request.position, Position3D::new(9, 0, 0));
+        assert!(queue.skip());
+        assert!(queue.focus().is_none());
+
+        for x in 0..(CAMERA_HINT_MAX_QUEUED as i32 + 2) {
+            queue.push(request(x, 20));
+        }
+        assert_eq!(queue.pending_len(), CAMERA_HINT_MAX_QUEUED);
+    }
+
+    #[test]
+    fn skipping_a_hint_keeps_the_movement_key_press() {
+        #[derive(Resource, Default)]
+        struct MoveSeen(bool);
+
+        fn movement_probe(keyboard: Res<ButtonInput<KeyCode>>, mut seen: ResMut<MoveSeen>) {
+            seen.0 |= keyboard.just_pressed(KeyCode::KeyW);
+        }
+
+        let mut app = App::new();
+        let mut queue = CameraHintQueue::default();
+        queue.push(request(30, 30));
+        queue.start_next(0.0);
+        app.insert_resource(queue)
+            .init_resource::<MovementConfig>()
+            .init_resource::<InputMapper>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<MoveSeen>()
+            .add_systems(
+                Update,
+                (skip_camera_hint_on_movement, movement_probe).chain(),
+            );
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyW);
+        app.update();
+
+        assert!(app.world().resource::<CameraHintQueue>().active().is_none());
+        assert!(app.world().resource::<MoveSeen>().0);
+    }
+}
@@ -0,0 +1,376 @@
+//! Clocks - Wall time for the player, sim time for the game
+//!
+//! Two clocks replace reading `Time` directly for cadences:
+//!
+//! - `WallClock` follows real time and never stops. It is only for what the
+//!   player reads as "time played" and for diagnostics such as asset status
+//!   logs.
+//! - `SimClock` follows virtual time, and only while a run is being played.
+//!   It stops on the pause screen, in menus, and when the frame limiter
+//!   pauses a hidden window. Anything gameplay-visible runs on it: the dice
+//!   auto-roll, turn timing, music changes and ambient retries.
+//!
+//! A system that reads the wall clock has to be on `WALL_CLOCK_SYSTEMS`;
+//! the test below checks every system signature in the crate against it.
+//!
+//! Repeating cadences keep a timer resource of their own, with the interval
+//! taken from `GameplayConfig` on every tick so a changed interval applies
+//! at once. The auto-roll timer starts over whenever a new world is built.
+
+use crate::domain::constants::{AUTO_ROLL_INTERVAL_SECS, STATUS_LOG_INTERVAL_SECS};
+use crate::domain::services::MovementEconomyService;
+use crate::presentation::game_state::RpgAppState;
+use crate::presentation::world_rebuild::WorldRebuilt;
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Systems allowed to read the wall clock; everything else is gameplay
+pub const WALL_CLOCK_SYSTEMS: &[&str] = &[
+    "tick_clocks_system",
+    "update_game_session",
+    "monitor_audio_status",
+    "headless_game_tick_system",
+];
+
+/// Plugin ticking the wall and sim clocks
+pub struct ClocksPlugin;
+
+impl Plugin for ClocksPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WallClock>()
+            .init_resource::<SimClock>()
+            .init_resource::<GameplayConfig>()
+            .init_resource::<AutoRollTimer>()
+            .init_resource::<HeadlessStatusTimer>()
+            .add_event::<WorldRebuilt>()
+            .add_systems(PreUpdate, (tick_clocks_system, reset_session_timers_system));
+    }
+}
+
+/// Tunable gameplay settings: timer intervals, in seconds, and the
+/// movement reward tables
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct GameplayConfig {
+    /// Play time between automatic dice rolls when there is no keyboard
+    pub auto_roll_interval_secs: f32,
+    /// Real time between status lines of the headless web build
+    pub status_log_interval_secs: f32,
+    /// What moves and their events pay out
+    pub movement_economy: MovementEconomyService,
+}
+
+impl Default for GameplayConfig {
+    fn default() -> Self {
+        Self {
+            auto_roll_interval_secs: AUTO_ROLL_INTERVAL_SECS,
+            status_log_interval_secs: STATUS_LOG_INTERVAL_SECS,
+            movement_economy: MovementEconomyService::default(),
+        }
+    }
+}
+
+/// Advance a repeating `timer` by `delta`; returns true each time
+/// `interval_secs` has passed
+pub fn tick_every(timer: &mut Timer, delta: Duration, interval_secs: f32) -> bool {
+    let interval = Duration::from_secs_f32(interval_secs.max(0.0));
+    if timer.duration() != interval {
+        timer.set_duration(interval);
+    }
+    timer.tick(delta).just_finished()
+}
+
+/// Play time towards the next automatic dice roll
+#[derive(Resource, Debug, Clone)]
+pub struct AutoRollTimer(pub Timer);
+
+impl Default for AutoRollTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(
+            AUTO_ROLL_INTERVAL_SECS,
+            TimerMode::Repeating,
+        ))
+    }
+}
+
+/// Real time towards the next status line of the headless web build
+#[derive(Resource, Debug, Clone)]
+pub struct HeadlessStatusTimer(pub Timer);
+
+impl Default for HeadlessStatusTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(
+            STATUS_LOG_INTERVAL_SECS,
+            TimerMode::Repeating,
+        ))
+    }
+}
+
+/// Real time since the app started, for "time played" and diagnostics
+#[derive(Resource, Debug, Default, Clone)]
+pub struct WallClock {
+    elapsed: Duration,
+    delta: Duration,
+}
+
+impl WallClock {
+    /// Advance by one frame of real time
+    pub fn advance(&mut self, delta: Duration) {
+        self.delta = delta;
+        self.elapsed += delta;
+    }
+
+    /// Seconds since the app started
+    pub fn elapsed_secs(&self) -> f32 {
+        self.elapsed.as_secs_f32()
+    }
+
+    /// Real time of the last frame
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+}
+
+/// Time the run has been played, stopped whenever play is
+#[derive(Resource, Debug, Default, Clone)]
+pub struct SimClock {
+    elapsed: Duration,
+    delta: Duration,
+    running: bool,
+}
+
+impl SimClock {
+    /// Advance by `delta`, or stand still this frame when not `running`
+    pub fn advance(&mut self, delta: Duration, running: bool) {
+        self.running = running;
+        self.delta = if running { delta } else { Duration::ZERO };
+        self.elapsed += self.delta;
+    }
+
+    /// Seconds of play so far
+    pub fn elapsed_secs(&self) -> f32 {
+        self.elapsed.as_secs_f32()
+    }
+
+    /// Play time of the last frame, zero while stopped
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    /// Play time of the last frame in seconds
+    pub fn delta_secs(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    /// Check if the run was being played last frame
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+}
+
+/// Check if the sim clock runs in `state`
+pub fn sim_runs_in(state: &RpgAppState) -> bool {
+    !matches!(
+        state,
+        RpgAppState::Loading
+            | RpgAppState::MainMenu
+            | RpgAppState::CharacterCreation
+            | RpgAppState::Settings
+            | RpgAppState::Paused
+            | RpgAppState::GameOver
+    )
+}
+
+fn tick_clocks_system(
+    real_time: Res<Time<Real>>,
+    virtual_time: Res<Time<Virtual>>,
+    state: Option<Res<State<RpgAppState>>>,
+    mut wall: ResMut<WallClock>,
+    mut sim: ResMut<SimClock>,
+) {
+    wall.advance(real_time.delta());
+    let running = state.is_some_and(|state| sim_runs_in(state.get()));
+    sim.advance(virtual_time.delta(), running);
+}
+
+/// A new world starts the auto-roll from scratch
+fn reset_session_timers_system(
+    mut rebuilt: EventReader<WorldRebuilt>,
+    mut auto_roll: ResMut<AutoRollTimer>,
+) {
+    if rebuilt.read().count() > 0 {
+        auto_roll.0.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::bevy::resources::{GameStatsResource, GameTimerResource};
+    use crate::presentation::game_state::RpgGameSession;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::path::Path;
+
+    /// Names of the functions in `source` whose signature mentions `WallClock`
+    fn wall_clock_readers(source: &str) -> Vec<String> {
+        let mut readers = Vec::new();
+        for (index, _) in source.match_indices("fn ") {
+            let rest = &source[index + 3..];
+            let Some(name_end) = rest.find(|c: char| !(c.is_alphanumeric() || c == '_')) else {
+                continue;
+            };
+            let signature_end = rest.find('{').unwrap_or(rest.len());
+            if rest[..signature_end].contains("WallClock>") {
+                readers.push(rest[..name_end].to_string());
+            }
+        }
+        readers
+    }
+
+    fn collect_readers(dir: &Path, readers: &mut Vec<(String, String)>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                collect_readers(&path, readers);
+            } else if path.extension().is_some_and(|extension| extension == "rs") {
+                let source = std::fs::read_to_string(&path).unwrap();
+                for name in wall_clock_readers(&source) {
+                    readers.push((path.display().to_string(), name));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn only_allowlisted_systems_read_the_wall_clock() {
+        let mut readers = Vec::new();
+        collect_readers(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut readers,
+        );
+        assert!(readers.iter().any(|(_, name)| name == "tick_clocks_system"));
+
+        let gameplay: Vec<_> = readers
+            .iter()
+            .filter(|(_, name)| !WALL_CLOCK_SYSTEMS.contains(&name.as_str()))
+            .collect();
+        assert!(
+            gameplay.is_empty(),
+            "gameplay systems reading the wall clock: {:?}",
+            gameplay
+        );
+    }
+
+    /// Headless app auto-rolling on a sim clock that steps one second a frame
+    fn auto_roll_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, ClocksPlugin))
+            .insert_state(RpgAppState::Exploration)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(1)))
+            .insert_resource(GameStatsResource::new())
+            .add_event::<crate::presentation::page_api::DiceRollRequested>()
+            .add_systems(Update, crate::rpg_dice_mechanics_system);
+        app.world_mut()
+            .resource_mut::<Time<Virtual>>()
+            .set_max_delta(Duration::from_secs(5));
+        // The first frame has no time delta yet
+        app.update();
+        app
+    }
+
+    fn rolls_after(app: &mut App, frames: usize) -> u32 {
+        for _ in 0..frames {
+            app.update();
+        }
+        app.world().resource::<GameStatsResource>().dice_rolls_made
+    }
+
+    #[test]
+    fn auto_rolls_follow_the_configured_interval() {
+        let mut app = auto_roll_app();
+        assert_eq!(rolls_after(&mut app, 4), 0);
+        assert_eq!(rolls_after(&mut app, 1), 1);
+        assert_eq!(rolls_after(&mut app, 5), 2);
+
+        app.world_mut()
+            .resource_mut::<GameplayConfig>()
+            .auto_roll_interval_secs = 2.0;
+        assert_eq!(rolls_after(&mut app, 6), 5);
+
+        let mut status = Timer::from_seconds(STATUS_LOG_INTERVAL_SECS, TimerMode::Repeating);
+        let logged = (0..30)
+            .filter(|_| tick_every(&mut status, Duration::from_secs(1), 10.0))
+            .count();
+        assert_eq!(logged, 3);
+    }
+
+    #[test]
+    fn a_new_world_starts_the_auto_roll_over() {
+        let mut app = auto_roll_app();
+        assert_eq!(rolls_after(&mut app, 4), 0);
+        app.world_mut().send_event(WorldRebuilt {
+            seed: 1,
+            spawn: crate::domain::Position3D::origin(),
+        });
+        assert_eq!(rolls_after(&mut app, 4), 0);
+        assert_eq!(rolls_after(&mut app, 1), 1);
+
+        // A second app keeps its own timers
+        let mut other = auto_roll_app();
+        assert_eq!(rolls_after(&mut other, 4), 0);
+    }
+
+    #[test]
+    fn ten_minutes_paused_only_adds_time_played() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, ClocksPlugin))
+            .init_state::<RpgAppState>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(1)))
+            .insert_resource(GameStatsResource::new())
+            .insert_resource(GameTimerResource::new())
+            .add_event::<crate::presentation::page_api::DiceRollRequested>()
+            .insert_resource(RpgGameSession::new(
+                crate::domain::Player::create_new_character(
+                    "Pauser".to_string(),
+                    crate::domain::Position3D::origin(),
+                )
+                .unwrap(),
+                crate::domain::Base::new(
+                    crate::domain::EntityId::generate(),
+                    "Base".to_string(),
+                    crate::domain::Position3D::origin(),
+                )
+                .unwrap(),
+            ))
+            .add_systems(
+                Update,
+                (
+                    crate::rpg_turn_management_system,
+                    crate::rpg_dice_mechanics_system,
+                    crate::presentation::game_state::update_game_session,
+                ),
+            );
+        app.world_mut()
+            .resource_mut::<NextState<RpgAppState>>()
+            .set(RpgAppState::Paused);
+        app.update();
+
+        let sim_before = app.world().resource::<SimClock>().elapsed_secs();
+        let rolls_before = app.world().resource::<GameStatsResource>().dice_rolls_made;
+        let played_before = app.world().resource::<RpgGameSession>().total_play_time;
+        for _ in 0..600 {
+            app.update();
+        }
+
+        let world = app.world();
+        assert!(world.resource::<WallClock>().elapsed_secs() >= 600.0);
+        assert!(world.resource::<RpgGameSession>().total_play_time >= played_before + 599);
+        assert_eq!(world.resource::<SimClock>().elapsed_secs(), sim_before);
+        let stats = world.resource::<GameStatsResource>();
+        assert_eq!(stats.dice_rolls_made, rolls_before);
+        assert_eq!(stats.experience_gained, 0);
+        let timer = world.resource::<GameTimerResource>();
+        assert_eq!(timer.active_time, 0.0);
+        assert_eq!(timer.turn(), 1);
+    }
+}
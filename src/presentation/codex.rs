@@ -0,0 +1,400 @@
+//! Codex Screen - Browsing what the player has met, and unlocking it
+//!
+//! F2 opens the codex from the main menu or while paused; the left and
+//! right arrows switch between its categories. Entries load from
+//! `assets/codex.json`. Locked entries show only a silhouette and a hint.
+//! The moment something is first met its pathway sends a
+//! `CodexUnlockEvent`; a new unlock is saved with the settings, so it
+//! carries over to every later run, and shows a toast for a few seconds.
+//! Terrain, items and factions are picked up from the events already sent
+//! for them.
+
+use crate::domain::constants::{
+    ENERGY_COLOR, HANDOVER_BACKGROUND, PANEL_BACKGROUND, PRIMARY_TEXT, SECONDARY_TEXT,
+};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::{
+    consumable_entry_id, faction_entry_id, gear_entry_id, terrain_entry_id, Codex, CodexCategory,
+};
+use crate::infrastructure::bevy::resources::{PlayerChange, PlayerResource};
+use crate::infrastructure::settings::CodexSettings;
+use crate::presentation::audio_integration::TerrainChangeEvent;
+use crate::presentation::game_event_logger::PlayerChangedEvent;
+use crate::presentation::game_state::RpgAppState;
+use crate::presentation::reputation::ReputationChangedEvent;
+use bevy::prelude::*;
+
+/// Key that opens and closes the codex
+pub const CODEX_KEY: KeyCode = KeyCode::F2;
+
+/// Codex entries shipped with the game
+pub const CODEX_DATA: &str = include_str!("../../assets/codex.json");
+
+/// Seconds an unlock toast stays up
+const CODEX_TOAST_SECS: f32 = 3.0;
+
+/// Plugin for the codex screen and unlocks
+pub struct CodexPlugin;
+
+impl Plugin for CodexPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CodexUnlockEvent>()
+            .init_resource::<CodexSettings>()
+            .init_resource::<CodexScreen>()
+            .add_systems(Startup, (setup_codex_screen, setup_codex_toast))
+            .add_systems(
+                Update,
+                (
+                    codex_pathway_system,
+                    codex_unlock_system,
+                    codex_toast_system,
+                    toggle_codex_system,
+                    update_codex_screen_system,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Something with a codex entry was met
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct CodexUnlockEvent {
+    /// Id of the entry, e.g. `terrain/Plains`
+    pub id: String,
+}
+
+impl CodexUnlockEvent {
+    /// Unlock the entry with `id`
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+/// The loaded codex, whether its screen is open and the category shown
+#[derive(Resource, Debug)]
+pub struct CodexScreen {
+    codex: Codex,
+    open: bool,
+    category: usize,
+}
+
+impl Default for CodexScreen {
+    fn default() -> Self {
+        let codex = Codex::parse(CODEX_DATA).unwrap_or_else(|e| {
+            error!("📖 Codex not loaded: {}", e);
+            Codex::default()
+        });
+        Self {
+            codex,
+            open: false,
+            category: 0,
+        }
+    }
+}
+
+impl CodexScreen {
+    /// The loaded entries
+    pub fn codex(&self) -> &Codex {
+        &self.codex
+    }
+
+    /// Replace the loaded entries, e.g. with data packs applied
+    pub fn set_codex(&mut self, codex: Codex) {
+        self.codex = codex;
+    }
+
+    /// Whether the codex is showing
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Category currently shown
+    pub fn category(&self) -> CodexCategory {
+        let categories = CodexCategory::all();
+        categories[self.category % categories.len()]
+    }
+
+    /// Text of the current category, with locked entries as silhouettes
+    pub fn page(&self, settings: &CodexSettings) -> String {
+        let mut tabs = Vec::new();
+        for category in CodexCategory::all() {
+            let (unlocked, total) = settings.unlocked.progress(&self.codex, category);
+            let tab = format!("{} {}/{}", category.name(), unlocked, total);
+            if category == self.category() {
+                tabs.push(format!("[{}]", tab));
+            } else {
+                tabs.push(tab);
+            }
+        }
+
+        let mut lines = vec![tabs.join("   "), String::new()];
+        for entry in self.codex.in_category(self.category()) {
+            if settings.unlocked.is_unlocked(&entry.id) {
+                lines.push(entry.title.to_uppercase());
+                lines.push(entry.flavor.clone());
+                lines.push(format!("> {}", entry.notes));
+            } else {
+                lines.push("?".repeat(entry.title.chars().count()));
+                lines.push(format!("Hint: {}", entry.hint));
+            }
+            lines.push(String::new());
+        }
+        lines.join("\n")
+    }
+}
+
+/// Marker for the codex screen root
+#[derive(Component)]
+pub struct CodexRoot;
+
+/// Marker for the codex page text
+#[derive(Component)]
+pub struct CodexPageText;
+
+/// An unlock toast and the time it has left
+#[derive(Component, Debug, Default)]
+pub struct CodexToast {
+    remaining: f32,
+}
+
+fn setup_codex_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                top: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(Val::Px(30.0)),
+                row_gap: Val::Px(12.0),
+                overflow: Overflow::clip_y(),
+                ..default()
+            },
+            BackgroundColor(HANDOVER_BACKGROUND),
+            GlobalZIndex(15),
+            Visibility::Hidden,
+            CodexRoot,
+            Name::new("CodexScreen"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("CODEX"),
+                TextFont {
+                    font_size: FontSize::Large.to_pixels(),
+                    ..default()
+                },
+                TextColor(ENERGY_COLOR),
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Regular.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                Node {
+                    max_width: Val::Px(720.0),
+                    ..default()
+                },
+                CodexPageText,
+            ));
+            parent.spawn((
+                Text::new("Left/Right to switch category - Press F2 to close"),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(SECONDARY_TEXT),
+            ));
+        });
+}
+
+fn setup_codex_toast(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: FontSize::Regular.to_pixels(),
+            ..default()
+        },
+        TextColor(ENERGY_COLOR),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(90.0),
+            right: Val::Px(15.0),
+            padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+            ..default()
+        },
+        BackgroundColor(PANEL_BACKGROUND),
+        GlobalZIndex(12),
+        Visibility::Hidden,
+        CodexToast::default(),
+        Name::new("CodexToast"),
+    ));
+}
+
+/// Turn the terrain, item and faction events into codex unlocks
+fn codex_pathway_system(
+    player_resource: Res<PlayerResource>,
+    mut terrain_events: EventReader<TerrainChangeEvent>,
+    mut player_events: EventReader<PlayerChangedEvent>,
+    mut reputation_events: EventReader<ReputationChangedEvent>,
+    mut unlocks: EventWriter<CodexUnlockEvent>,
+) {
+    for event in terrain_events.read() {
+        unlocks.write(CodexUnlockEvent::new(terrain_entry_id(event.new_terrain)));
+    }
+    for event in player_events.read() {
+        match &event.change {
+            PlayerChange::GearFound { id, .. } => {
+                let slot = player_resource.player().and_then(|player| {
+                    player
+                        .gear()
+                        .items()
+                        .into_iter()
+                        .find(|item| item.id == *id)
+                        .map(|item| item.slot())
+                });
+                if let Some(slot) = slot {
+                    unlocks.write(CodexUnlockEvent::new(gear_entry_id(slot)));
+                }
+            }
+            PlayerChange::ConsumableChanged { kind, delta, .. } if *delta > 0 => {
+                unlocks.write(CodexUnlockEvent::new(consumable_entry_id(*kind)));
+            }
+            _ => {}
+        }
+    }
+    for event in reputation_events.read() {
+        unlocks.write(CodexUnlockEvent::new(faction_entry_id(
+            event.change.faction,
+        )));
+    }
+}
+
+/// Unlock entries met for the first time and show their toast
+fn codex_unlock_system(
+    screen: Res<CodexScreen>,
+    mut settings: ResMut<CodexSettings>,
+    mut events: EventReader<CodexUnlockEvent>,
+    mut toasts: Query<(&mut Text, &mut Visibility, &mut CodexToast)>,
+) {
+    for event in events.read() {
+        // Already unlocked entries must not mark the settings for saving
+        if settings.unlocked.is_unlocked(&event.id) {
+            continue;
+        }
+        let Some(entry) = screen.codex.entry(&event.id) else {
+            warn!("📖 No codex entry {}", event.id);
+            continue;
+        };
+        settings.unlocked.unlock(&event.id);
+        info!("📖 Codex entry unlocked: {}", entry.id);
+        for (mut text, mut visibility, mut toast) in toasts.iter_mut() {
+            text.0 = format!("📖 Codex: {} unlocked", entry.title);
+            *visibility = Visibility::Visible;
+            toast.remaining = CODEX_TOAST_SECS;
+        }
+    }
+}
+
+/// Hide the unlock toast once its time is up
+fn codex_toast_system(time: Res<Time>, mut toasts: Query<(&mut Visibility, &mut CodexToast)>) {
+    for (mut visibility, mut toast) in toasts.iter_mut() {
+        if toast.remaining <= 0.0 {
+            continue;
+        }
+        toast.remaining -= time.delta_secs();
+        if toast.remaining <= 0.0 {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
+
+/// Open or close on F2 and switch categories with the arrows; only the
+/// main menu and the pause screen offer it
+fn toggle_codex_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    mut screen: ResMut<CodexScreen>,
+) {
+    let available = matches!(state.get(), RpgAppState::MainMenu | RpgAppState::Paused);
+    if !available {
+        if screen.open {
+            screen.open = false;
+        }
+        return;
+    }
+    if keyboard.just_pressed(CODEX_KEY) {
+        screen.open = !screen.open;
+    }
+    if !screen.open {
+        return;
+    }
+    let count = CodexCategory::all().len();
+    if keyboard.just_pressed(KeyCode::ArrowRight) {
+        screen.category = (screen.category + 1) % count;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        screen.category = (screen.category + count - 1) % count;
+    }
+}
+
+/// Show or hide the screen and refresh its page when anything changed
+fn update_codex_screen_system(
+    screen: Res<CodexScreen>,
+    settings: Res<CodexSettings>,
+    mut roots: Query<&mut Visibility, With<CodexRoot>>,
+    mut pages: Query<&mut Text, With<CodexPageText>>,
+) {
+    let wanted = if screen.open {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    for mut visibility in roots.iter_mut() {
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+    if !screen.open || !(screen.is_changed() || settings.is_changed()) {
+        return;
+    }
+
+    let page = screen.page(&settings);
+    for mut text in pages.iter_mut() {
+        text.0 = page.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_codex_documents_everything() {
+        let codex = Codex::parse(CODEX_DATA).unwrap();
+        for category in CodexCategory::all() {
+            assert!(
+                codex.in_category(category).count() > 0,
+                "no {} entries",
+                category.name()
+            );
+        }
+    }
+
+    #[test]
+    fn locked_entries_show_as_silhouettes() {
+        let screen = CodexScreen::default();
+        let mut settings = CodexSettings::default();
+        settings.unlocked.unlock("terrain/Plains");
+
+        let page = screen.page(&settings);
+        assert!(page.starts_with("[Terrain 1/12]"), "{}", page);
+        assert!(page.contains("PLAINS"));
+        assert!(!page.contains("SWAMP"));
+        assert!(page.contains("?????\nHint: Wet, slow and alive."));
+    }
+}
@@ -0,0 +1,398 @@
+//! Controls - Rebinding the game's keys from the pause screen
+//!
+//! K opens the controls while paused. Up and Down pick an action and Enter
+//! listens for its new key; the key pressed next becomes the action's only
+//! key. A key already bound to another action is refused once with a
+//! warning, and pressing it again takes it over, leaving the other action
+//! without that key. Backspace puts every binding back to the defaults. The
+//! bindings live in `InputMapper`, so they apply at once and are saved with
+//! the other settings.
+//!
+//! While the screen is open it swallows every key press before the game
+//! reads the keyboard, so picking a key never also moves, rolls or resumes.
+
+use crate::domain::constants::{
+    ENERGY_COLOR, HANDOVER_BACKGROUND, PRIMARY_TEXT, SECONDARY_TEXT, WARNING_TEXT,
+};
+use crate::domain::services::font_service::FontSize;
+use crate::presentation::game_state::RpgAppState;
+use crate::presentation::input::{key_label, GameAction, InputMapper};
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+/// Key that opens and closes the controls while paused
+pub const CONTROLS_KEY: KeyCode = KeyCode::KeyK;
+
+/// Key that puts every binding back to the defaults
+pub const RESET_BINDINGS_KEY: KeyCode = KeyCode::Backspace;
+
+/// Plugin for the controls screen
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ControlsScreen>()
+            .init_resource::<InputMapper>()
+            .add_systems(Startup, setup_controls_screen)
+            .add_systems(PreUpdate, controls_input_system.after(InputSystem))
+            .add_systems(Update, update_controls_screen);
+    }
+}
+
+/// What the controls screen waits for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Capture {
+    /// Picking an action
+    #[default]
+    Idle,
+    /// Waiting for the picked action's new key
+    Listening,
+    /// The key is bound elsewhere; pressing it again takes it over
+    Confirming(KeyCode),
+}
+
+/// Whether the controls are open, which action is picked and what happened
+#[derive(Resource, Debug, Default)]
+pub struct ControlsScreen {
+    open: bool,
+    selected: usize,
+    capture: Capture,
+    notice: Option<String>,
+}
+
+impl ControlsScreen {
+    /// Whether the controls are showing
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Action Enter rebinds
+    pub fn selected(&self) -> GameAction {
+        GameAction::REBINDABLE[self.selected % GameAction::REBINDABLE.len()]
+    }
+
+    /// What the screen waits for
+    pub fn capture(&self) -> Capture {
+        self.capture
+    }
+
+    /// Offer `key` as the picked action's new key
+    ///
+    /// A key bound to another action is only taken over when offered twice
+    /// in a row.
+    pub fn offer_key(&mut self, mapper: &mut InputMapper, key: KeyCode) {
+        let action = self.selected();
+        let holder = mapper.get_action(&key).copied();
+        match holder {
+            Some(other) if other != action && self.capture != Capture::Confirming(key) => {
+                self.capture = Capture::Confirming(key);
+                self.notice = Some(format!(
+                    "⚠ {} is already bound to {}. Press it again to take it over",
+                    key_label(key),
+                    other.label()
+                ));
+                return;
+            }
+            _ => {}
+        }
+        self.capture = Capture::Idle;
+        self.notice = Some(match mapper.rebind(action, key) {
+            Some(other) => format!(
+                "{} is now {}; {} lost it",
+                action.label(),
+                key_label(key),
+                other.label()
+            ),
+            None => format!("{} is now {}", action.label(), key_label(key)),
+        });
+    }
+}
+
+/// Marker for the controls root
+#[derive(Component)]
+pub struct ControlsRoot;
+
+/// Marker for the bindings text
+#[derive(Component)]
+pub struct ControlsText;
+
+/// Marker for the line that reports rebinds and conflicts
+#[derive(Component)]
+pub struct ControlsNotice;
+
+fn setup_controls_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                top: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(16.0),
+                ..default()
+            },
+            BackgroundColor(HANDOVER_BACKGROUND),
+            GlobalZIndex(15),
+            Visibility::Hidden,
+            ControlsRoot,
+            Name::new("ControlsScreen"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("CONTROLS"),
+                TextFont {
+                    font_size: FontSize::Large.to_pixels(),
+                    ..default()
+                },
+                TextColor(ENERGY_COLOR),
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Medium.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                ControlsText,
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Regular.to_pixels(),
+                    ..default()
+                },
+                TextColor(WARNING_TEXT),
+                ControlsNotice,
+            ));
+            parent.spawn((
+                Text::new(
+                    "Up/Down: pick  Enter: rebind  Backspace: reset to defaults  Esc or K: close",
+                ),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(SECONDARY_TEXT),
+            ));
+        });
+}
+
+/// Open on K while paused, then take every key press for the screen
+fn controls_input_system(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    mut screen: ResMut<ControlsScreen>,
+    mut mapper: ResMut<InputMapper>,
+) {
+    if *state.get() != RpgAppState::Paused {
+        if screen.open {
+            *screen = ControlsScreen::default();
+        }
+        return;
+    }
+    if !screen.open {
+        if keyboard.just_pressed(CONTROLS_KEY) {
+            keyboard.clear_just_pressed(CONTROLS_KEY);
+            screen.open = true;
+        }
+        return;
+    }
+
+    let pressed: Vec<KeyCode> = keyboard.get_just_pressed().copied().collect();
+    for key in pressed {
+        keyboard.clear_just_pressed(key);
+        match (screen.capture, key) {
+            (Capture::Idle, KeyCode::Escape | CONTROLS_KEY) => {
+                *screen = ControlsScreen::default();
+                return;
+            }
+            (Capture::Idle, KeyCode::ArrowDown) => {
+                screen.selected = (screen.selected + 1) % GameAction::REBINDABLE.len();
+            }
+            (Capture::Idle, KeyCode::ArrowUp) => {
+                let rows = GameAction::REBINDABLE.len();
+                screen.selected = (screen.selected + rows - 1) % rows;
+            }
+            (Capture::Idle, KeyCode::Enter) => {
+                screen.capture = Capture::Listening;
+                screen.notice = Some(format!(
+                    "Press the new key for {}",
+                    screen.selected().label()
+                ));
+            }
+            (Capture::Idle, RESET_BINDINGS_KEY) => {
+                mapper.reset_to_defaults();
+                screen.notice = Some("Every key is back to its default".to_string());
+            }
+            (Capture::Idle, _) => {}
+            (_, KeyCode::Escape) => {
+                screen.capture = Capture::Idle;
+                screen.notice = None;
+            }
+            (_, key) => screen.offer_key(&mut mapper, key),
+        }
+    }
+}
+
+/// Show or hide the screen and redraw it when the bindings change
+fn update_controls_screen(
+    screen: Res<ControlsScreen>,
+    mapper: Res<InputMapper>,
+    mut roots: Query<&mut Visibility, With<ControlsRoot>>,
+    mut texts: Query<&mut Text, (With<ControlsText>, Without<ControlsNotice>)>,
+    mut notices: Query<&mut Text, (With<ControlsNotice>, Without<ControlsText>)>,
+) {
+    let wanted = if screen.open {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    for mut visibility in roots.iter_mut() {
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+    if !screen.open || !(screen.is_changed() || mapper.is_changed()) {
+        return;
+    }
+    for mut text in texts.iter_mut() {
+        text.0 = controls_text(&mapper, &screen);
+    }
+    for mut notice in notices.iter_mut() {
+        notice.0 = screen.notice.clone().unwrap_or_default();
+    }
+}
+
+/// Every rebindable action with its keys, the picked one marked
+fn controls_text(mapper: &InputMapper, screen: &ControlsScreen) -> String {
+    GameAction::REBINDABLE
+        .iter()
+        .map(|action| {
+            let marker = match screen.capture {
+                _ if *action != screen.selected() => " ",
+                Capture::Idle => ">",
+                Capture::Listening | Capture::Confirming(_) => "?",
+            };
+            let mut keys: Vec<String> = mapper
+                .get_keys_for_action(action)
+                .into_iter()
+                .map(key_label)
+                .collect();
+            keys.sort();
+            let keys = if keys.is_empty() {
+                "(unbound)".to_string()
+            } else {
+                keys.join(" / ")
+            };
+            format!("{} {:<12} {}", marker, action.label(), keys)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+
+    fn press(app: &mut App, key: KeyCode) {
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+        app.update();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .reset_all();
+    }
+
+    #[test]
+    fn a_taken_key_is_only_moved_over_when_pressed_twice() {
+        let mut mapper = InputMapper::new();
+        let mut screen = ControlsScreen::default();
+        assert_eq!(screen.selected(), GameAction::MoveUp);
+
+        screen.offer_key(&mut mapper, KeyCode::Space);
+        assert_eq!(screen.capture(), Capture::Confirming(KeyCode::Space));
+        assert_eq!(
+            mapper.get_action(&KeyCode::Space),
+            Some(&GameAction::RollDice)
+        );
+        assert!(controls_text(&mapper, &screen).contains("? Move up      ArrowUp / W"));
+
+        screen.offer_key(&mut mapper, KeyCode::Space);
+        assert_eq!(screen.capture(), Capture::Idle);
+        assert_eq!(
+            mapper.get_keys_for_action(&GameAction::MoveUp),
+            vec![KeyCode::Space]
+        );
+        assert!(mapper.get_keys_for_action(&GameAction::RollDice).is_empty());
+        assert!(controls_text(&mapper, &screen).contains("  Roll dice    (unbound)"));
+
+        // A free key is bound at once
+        screen.offer_key(&mut mapper, KeyCode::KeyT);
+        assert_eq!(
+            mapper.get_keys_for_action(&GameAction::MoveUp),
+            vec![KeyCode::KeyT]
+        );
+    }
+
+    #[test]
+    fn rebinding_from_the_pause_screen_applies_at_once() {
+        #[derive(Resource, Default)]
+        struct Rolls(u32);
+
+        fn count_rolls(
+            keyboard: Res<ButtonInput<KeyCode>>,
+            mapper: Res<InputMapper>,
+            mut rolls: ResMut<Rolls>,
+        ) {
+            if mapper.just_pressed(&keyboard, GameAction::RollDice) {
+                rolls.0 += 1;
+            }
+        }
+
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin)
+            .insert_state(RpgAppState::Paused)
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ControlsScreen>()
+            .init_resource::<InputMapper>()
+            .init_resource::<Rolls>()
+            .add_systems(PreUpdate, controls_input_system)
+            .add_systems(Update, count_rolls);
+        app.update();
+
+        press(&mut app, CONTROLS_KEY);
+        assert!(app.world().resource::<ControlsScreen>().is_open());
+        let roll_row = GameAction::REBINDABLE
+            .iter()
+            .position(|action| *action == GameAction::RollDice)
+            .unwrap();
+        for _ in 0..roll_row {
+            press(&mut app, KeyCode::ArrowDown);
+        }
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::KeyG);
+        // Keys pressed on the screen never reach the game
+        assert_eq!(app.world().resource::<Rolls>().0, 0);
+        press(&mut app, KeyCode::Escape);
+        assert!(!app.world().resource::<ControlsScreen>().is_open());
+
+        press(&mut app, KeyCode::KeyG);
+        press(&mut app, KeyCode::Space);
+        assert_eq!(app.world().resource::<Rolls>().0, 1);
+
+        press(&mut app, CONTROLS_KEY);
+        press(&mut app, RESET_BINDINGS_KEY);
+        assert_eq!(
+            app.world()
+                .resource::<InputMapper>()
+                .get_action(&KeyCode::Space),
+            Some(&GameAction::RollDice)
+        );
+    }
+}
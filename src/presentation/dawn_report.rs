@@ -0,0 +1,359 @@
+//! Dawn Report - The day's forecast after the overnight digests
+//!
+//! Once a rest's other reports are written, the dawn report closes the
+//! night in the game log: a headline and the advisor's suggestion. The full
+//! report stays available during the day on a panel that O opens and
+//! closes. Raids and timed status effects have nothing to report yet, so
+//! the report shows what does run out tonight: a fading distress signal,
+//! lifting storms and a season on its last day.
+
+use crate::domain::constants::{
+    ENERGY_COLOR, EXPEDITION_ENERGY_PER_DAY, EXPEDITION_FOOD_PER_DAY, PANEL_BACKGROUND,
+    PRIMARY_TEXT,
+};
+use crate::domain::entities::Map;
+use crate::domain::services::dawn_report::days_of;
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::season_days_left;
+use crate::domain::services::{
+    nearest_landmark, probe_sightings, Bearing, DawnReport, Landmark, ProbeSighting,
+    TimedObjective, WorldHazards, WreckField,
+};
+use crate::domain::value_objects::{Position3D, ResourceType};
+use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource, PlayerResource};
+use crate::presentation::base_report::base_report_digest_system;
+use crate::presentation::game_state::RpgGameSession;
+use crate::presentation::world_tick::{TickCursor, TickPhase, WorldTick, WorldTickSet};
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+
+/// Key that opens and closes the dawn report panel
+pub const DAWN_REPORT_KEY: KeyCode = KeyCode::KeyO;
+
+/// Plugin for the dawn report and its panel
+pub struct DawnReportPlugin;
+
+impl Plugin for DawnReportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DawnReports>()
+            .add_systems(Startup, setup_dawn_report_panel)
+            .add_systems(
+                Update,
+                (
+                    dawn_report_system
+                        .in_set(WorldTickSet::Objectives)
+                        .after(base_report_digest_system),
+                    toggle_dawn_report_system,
+                    update_dawn_report_panel,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// The last dawn report and whether its panel is open
+#[derive(Resource, Debug, Default)]
+pub struct DawnReports {
+    last: Option<DawnReport>,
+    open: bool,
+}
+
+impl DawnReports {
+    /// The report of the last rest, if there was one
+    pub fn last(&self) -> Option<&DawnReport> {
+        self.last.as_ref()
+    }
+
+    /// Check if the panel is showing
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+}
+
+/// Marker for the dawn report panel
+#[derive(Component)]
+pub struct DawnReportPanel;
+
+/// Marker for the dawn report panel text
+#[derive(Component)]
+pub struct DawnReportText;
+
+fn setup_dawn_report_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(30.0),
+                top: Val::Px(80.0),
+                width: Val::Px(420.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(PANEL_BACKGROUND),
+            Visibility::Hidden,
+            DawnReportPanel,
+            Name::new("DawnReportPanel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                DawnReportText,
+            ));
+        });
+}
+
+/// Points of interest on explored tiles, and the wrecks left lying around
+pub fn known_landmarks(map: &Map, wrecks: &WreckField) -> Vec<Landmark> {
+    let explored: Vec<_> = map
+        .tiles()
+        .iter()
+        .filter(|(_, tile)| tile.is_explored())
+        .map(|(coordinate, _)| *coordinate)
+        .collect();
+    let mut landmarks: Vec<Landmark> = probe_sightings(map, &explored)
+        .into_iter()
+        .map(|(coordinate, sighting)| Landmark {
+            name: match sighting {
+                ProbeSighting::ResourceNode(resource_type) => {
+                    format!("{} deposit", resource_type).to_lowercase()
+                }
+                ProbeSighting::Ruins => "ruins".to_string(),
+            },
+            position: Position3D::from(coordinate),
+        })
+        .collect();
+    landmarks.extend(wrecks.wrecks().iter().map(|wreck| Landmark {
+        name: wreck.origin.name().to_string(),
+        position: wreck.position,
+    }));
+    // Tiles come out of a map in no particular order; ties go to the same one
+    landmarks.sort_by_key(|landmark| (landmark.position.x, landmark.position.y));
+    landmarks
+}
+
+/// Forecast for the day starting at `position`
+pub fn forecast(
+    day: u32,
+    position: Position3D,
+    map: Option<&Map>,
+    session: &RpgGameSession,
+    hazards: Option<&WorldHazards>,
+    objective: Option<&TimedObjective>,
+) -> DawnReport {
+    let mut report = DawnReport {
+        day,
+        ..DawnReport::default()
+    };
+
+    if let Some(hazards) = hazards {
+        report.storm = hazards
+            .storms()
+            .iter()
+            .filter(|storm| storm.center.z == position.z)
+            .map(|storm| {
+                (
+                    Bearing::between(position, storm.center),
+                    storm.is_dissipating(),
+                )
+            })
+            .min_by_key(|(bearing, _)| bearing.distance);
+        if hazards.storms().iter().any(|storm| storm.is_dissipating()) {
+            report.expiring.push("an anomaly storm".to_string());
+        }
+    }
+    if let Some(map) = map {
+        let landmarks = known_landmarks(map, &session.wrecks);
+        report.nearest_poi = nearest_landmark(position, &landmarks)
+            .map(|(landmark, bearing)| (landmark.name.clone(), bearing));
+    }
+    if let Some(objective) = objective {
+        if let Some(signal) = objective.active() {
+            report.distress = Some((
+                Bearing::between(position, signal.target),
+                signal.rests_remaining,
+            ));
+            if signal.rests_remaining <= 1 {
+                report.expiring.push("the distress signal".to_string());
+            }
+        }
+        report.region_threat = objective.threat_at(position);
+    }
+    report
+}
+
+/// Write the dawn report after each rest, as the night's last entry
+#[allow(clippy::too_many_arguments)]
+fn dawn_report_system(
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    map_resource: Res<MapResource>,
+    player_resource: Res<PlayerResource>,
+    game_stats: Res<GameStatsResource>,
+    session: Res<RpgGameSession>,
+    hazards: Option<Res<WorldHazards>>,
+    objective: Option<Res<TimedObjective>>,
+    mut reports: ResMut<DawnReports>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    for tick in cursor.take(ticks.read(), TickPhase::AfterRest) {
+        let Some(position) = tick.position else {
+            continue;
+        };
+        let mut report = forecast(
+            tick.day,
+            position,
+            map_resource.overworld(),
+            &session,
+            hazards.as_deref(),
+            objective.as_deref(),
+        );
+        if let Some(season) = game_stats.modifiers.season() {
+            let days_left = season_days_left(tick.day);
+            report.season = Some((season, days_left));
+            if days_left == 1 {
+                report.expiring.push(format!("the {}", season.name()));
+            }
+        }
+        if let Some(player) = player_resource.get_player() {
+            let food_per_day = game_stats.modifiers.food_upkeep(EXPEDITION_FOOD_PER_DAY);
+            report.food_days = days_of(
+                player.resources().get_amount(ResourceType::Food),
+                food_per_day,
+            );
+            report.energy_days = days_of(
+                player.resources().get_amount(ResourceType::Energy),
+                EXPEDITION_ENERGY_PER_DAY,
+            );
+        }
+        game_log.log_message(
+            format!(
+                "🌅 Day {} dawns. {} (O for the full report)",
+                report.day,
+                report
+                    .advice()
+                    .map(|advice| format!("Advisor: {}", advice))
+                    .unwrap_or_else(|| "Nothing pressing ahead".to_string())
+            ),
+            GameLogType::Narrative,
+        );
+        reports.last = Some(report);
+    }
+}
+
+/// Open or close the panel on O; it closes on leaving exploration
+fn toggle_dawn_report_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    mut reports: ResMut<DawnReports>,
+) {
+    if *state.get() != RpgAppState::Exploration {
+        if reports.open {
+            reports.open = false;
+        }
+        return;
+    }
+    if keyboard.just_pressed(DAWN_REPORT_KEY) {
+        reports.open = !reports.open;
+    }
+}
+
+/// Show the last report on the panel
+fn update_dawn_report_panel(
+    reports: Res<DawnReports>,
+    mut panels: Query<&mut Visibility, With<DawnReportPanel>>,
+    mut texts: Query<(&mut Text, &mut TextColor), With<DawnReportText>>,
+) {
+    if !reports.is_changed() {
+        return;
+    }
+    for mut visibility in panels.iter_mut() {
+        *visibility = if reports.open {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+    let Ok((mut text, mut color)) = texts.single_mut() else {
+        return;
+    };
+    match &reports.last {
+        Some(report) => {
+            text.0 = format!("{}\n\nO to close", report.lines().join("\n"));
+            color.0 = PRIMARY_TEXT;
+        }
+        None => {
+            text.0 = "No dawn report yet - rest to get one\n\nO to close".to_string();
+            color.0 = ENERGY_COLOR;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::{AnomalyStorm, WreckOrigin};
+    use crate::domain::value_objects::position::Direction;
+    use crate::domain::{Base, EntityId, Player};
+
+    #[test]
+    fn the_forecast_gathers_what_each_subsystem_knows() {
+        let mut session = RpgGameSession::new(
+            Player::create_new_character("Dawn".to_string(), Position3D::origin()).unwrap(),
+            Base::new(
+                EntityId::generate(),
+                "Outpost".to_string(),
+                Position3D::origin(),
+            )
+            .unwrap(),
+        );
+        let position = Position3D::new(10, 0, 0);
+
+        // Nothing known at all
+        let empty = forecast(3, position, None, &session, None, None);
+        assert_eq!(empty.lines().len(), 3);
+
+        session.wrecks.leave(
+            Position3D::new(10, 4, 0),
+            WreckOrigin::Hostiles,
+            vec![(ResourceType::Metal, 5)],
+        );
+        let map = Map::new(EntityId::generate(), "Dawn".to_string(), 7).unwrap();
+        let mut hazards = WorldHazards::new();
+        hazards.add_storm(AnomalyStorm {
+            center: Position3D::new(14, 0, 0),
+            heading: Direction::West,
+            rests_remaining: 1,
+        });
+
+        let mut report = forecast(
+            3,
+            position,
+            Some(&map),
+            &session,
+            Some(&hazards),
+            Some(&TimedObjective::new()),
+        );
+        report.food_days = days_of(12, 5);
+        report.energy_days = days_of(2, EXPEDITION_ENERGY_PER_DAY);
+        assert_eq!(
+            report.storm,
+            Some((Bearing::between(position, Position3D::new(14, 0, 0)), true))
+        );
+        assert_eq!(report.nearest_poi.as_ref().unwrap().0, "hostile wreck");
+        assert_eq!(
+            report.nearest_poi.as_ref().unwrap().1.describe(),
+            "4 tiles north"
+        );
+        assert_eq!(report.food_days, Some(2));
+        assert_eq!(report.energy_days, Some(0));
+        assert_eq!(report.expiring, vec!["an anomaly storm".to_string()]);
+        assert!(report.advice().unwrap().starts_with("supplies are out"));
+    }
+}
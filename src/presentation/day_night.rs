@@ -0,0 +1,415 @@
+//! Day/Night - The world's light and colors follow the time of day
+//!
+//! The time of day is the run's `GameCalendar`, which advances on the world
+//! tick: each rest starts a day at dawn and each move spends part of the
+//! daylight, until night falls and runs on towards midnight. A loaded or
+//! restarted run brings its own day, counted from the nights rested. The
+//! clear color, the ambient light and the color and strength of the sun move
+//! through four keyframes, dawn, day, dusk and night. A move that jumps the
+//! clock by hours does not pop: the look eases towards its new target over a
+//! second of real time. Only the lights and the clear color change, never
+//! the tile materials, so the cost does not grow with the map. The
+//! high-contrast palette keeps plain daylight on the tiles and shifts only
+//! the clear color. At night the fogged view shrinks by a tile.
+
+use crate::domain::constants::CALENDAR_DAWN_HOUR;
+use crate::domain::services::GameCalendar;
+use crate::infrastructure::bevy::resources::GameStatsResource;
+use crate::presentation::map_renderer::{MapPalette, MapRenderConfig};
+use crate::presentation::world_tick::{TickCursor, TickPhase, WorldTick, WorldTickSet};
+use bevy::prelude::*;
+
+/// Hour each day starts at after a rest
+pub const DAWN_HOUR: f32 = CALENDAR_DAWN_HOUR as f32;
+
+/// Hour the clock wraps back to the start of the day
+pub const MIDNIGHT_HOUR: f32 = 24.0;
+
+/// Real seconds the look takes to reach a new time of day
+pub const DAY_NIGHT_EASE_SECS: f32 = 1.0;
+
+/// Tiles the fogged view shrinks by at night
+pub const NIGHT_VIEW_NARROWING: u32 = 1;
+
+/// Plugin for the day/night lighting
+pub struct DayNightPlugin;
+
+impl Plugin for DayNightPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DayNightCycle>()
+            .init_resource::<GameCalendar>()
+            .add_systems(
+                Update,
+                (
+                    advance_calendar_system.after(WorldTickSet::Emit),
+                    day_night_system,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// The four parts of the day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DayPhase {
+    Dawn,
+    Day,
+    Dusk,
+    Night,
+}
+
+impl DayPhase {
+    /// The part of the day `hour` falls in: the nearest keyframe
+    pub fn at(hour: f32) -> Self {
+        match hour.rem_euclid(24.0) {
+            h if (3.0..9.0).contains(&h) => DayPhase::Dawn,
+            h if (9.0..15.0).contains(&h) => DayPhase::Day,
+            h if (15.0..21.0).contains(&h) => DayPhase::Dusk,
+            _ => DayPhase::Night,
+        }
+    }
+
+    /// HUD clock icon
+    pub fn icon(&self) -> &'static str {
+        match self {
+            DayPhase::Dawn => "🌅",
+            DayPhase::Day => "☀️",
+            DayPhase::Dusk => "🌇",
+            DayPhase::Night => "🌙",
+        }
+    }
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            DayPhase::Dawn => "DAWN",
+            DayPhase::Day => "DAY",
+            DayPhase::Dusk => "DUSK",
+            DayPhase::Night => "NIGHT",
+        }
+    }
+}
+
+/// Lighting and background of one moment of the day
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DayNightLook {
+    /// Background color, in sRGB
+    pub clear_color: [f32; 3],
+    /// Color of the sun and the ambient light, in sRGB
+    pub light_tint: [f32; 3],
+    pub ambient_brightness: f32,
+    pub sun_illuminance: f32,
+}
+
+impl DayNightLook {
+    /// The look a fraction `t` of the way from `self` to `other`
+    pub fn lerp(&self, other: &DayNightLook, t: f32) -> DayNightLook {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        let mix3 = |a: [f32; 3], b: [f32; 3]| [mix(a[0], b[0]), mix(a[1], b[1]), mix(a[2], b[2])];
+        DayNightLook {
+            clear_color: mix3(self.clear_color, other.clear_color),
+            light_tint: mix3(self.light_tint, other.light_tint),
+            ambient_brightness: mix(self.ambient_brightness, other.ambient_brightness),
+            sun_illuminance: mix(self.sun_illuminance, other.sun_illuminance),
+        }
+    }
+
+    /// The look drawn with `palette`
+    ///
+    /// High contrast keeps plain daylight on the tiles, so only the clear
+    /// color follows the time of day.
+    pub fn for_palette(&self, palette: MapPalette) -> DayNightLook {
+        match palette {
+            MapPalette::Standard => *self,
+            MapPalette::HighContrast => DayNightLook {
+                clear_color: self.clear_color,
+                ..DAY_LOOK
+            },
+        }
+    }
+}
+
+const DAWN_LOOK: DayNightLook = DayNightLook {
+    clear_color: [0.12, 0.07, 0.1],
+    light_tint: [1.0, 0.85, 0.7],
+    ambient_brightness: 220.0,
+    sun_illuminance: 6000.0,
+};
+
+/// Plain daylight, as the map was lit before the cycle
+const DAY_LOOK: DayNightLook = DayNightLook {
+    clear_color: [0.05, 0.05, 0.1],
+    light_tint: [1.0, 1.0, 1.0],
+    ambient_brightness: 300.0,
+    sun_illuminance: 10000.0,
+};
+
+const DUSK_LOOK: DayNightLook = DayNightLook {
+    clear_color: [0.1, 0.05, 0.08],
+    light_tint: [1.0, 0.7, 0.5],
+    ambient_brightness: 200.0,
+    sun_illuminance: 5000.0,
+};
+
+const NIGHT_LOOK: DayNightLook = DayNightLook {
+    clear_color: [0.01, 0.01, 0.03],
+    light_tint: [0.55, 0.6, 0.9],
+    ambient_brightness: 90.0,
+    sun_illuminance: 1500.0,
+};
+
+/// Keyframes by hour, midnight at both ends so the cycle wraps
+const KEYFRAMES: [(f32, DayNightLook); 5] = [
+    (0.0, NIGHT_LOOK),
+    (6.0, DAWN_LOOK),
+    (12.0, DAY_LOOK),
+    (18.0, DUSK_LOOK),
+    (24.0, NIGHT_LOOK),
+];
+
+/// The look at `hour`, blended between the keyframes either side of it
+pub fn look_at(hour: f32) -> DayNightLook {
+    let hour = hour.rem_euclid(24.0);
+    for pair in KEYFRAMES.windows(2) {
+        let ((start, from), (end, to)) = (pair[0], pair[1]);
+        if hour <= end {
+            return from.lerp(&to, (hour - start) / (end - start));
+        }
+    }
+    NIGHT_LOOK
+}
+
+/// The time of day and the look easing towards it
+#[derive(Resource, Debug, Clone)]
+pub struct DayNightCycle {
+    hour: f32,
+    from: DayNightLook,
+    current: DayNightLook,
+    elapsed: f32,
+}
+
+impl Default for DayNightCycle {
+    fn default() -> Self {
+        let look = look_at(DAWN_HOUR);
+        Self {
+            hour: DAWN_HOUR,
+            from: look,
+            current: look,
+            elapsed: DAY_NIGHT_EASE_SECS,
+        }
+    }
+}
+
+impl DayNightCycle {
+    /// Hour of the day
+    pub fn hour(&self) -> f32 {
+        self.hour
+    }
+
+    /// Part of the day
+    pub fn phase(&self) -> DayPhase {
+        DayPhase::at(self.hour)
+    }
+
+    /// The look being shown
+    pub fn current(&self) -> DayNightLook {
+        self.current
+    }
+
+    /// Tiles the fogged view shrinks by at this time of day
+    pub fn view_narrowing(&self) -> u32 {
+        if self.phase() == DayPhase::Night {
+            NIGHT_VIEW_NARROWING
+        } else {
+            0
+        }
+    }
+
+    /// Move the clock to `hour`, easing from the look shown now
+    pub fn set_hour(&mut self, hour: f32) {
+        if hour == self.hour {
+            return;
+        }
+        self.hour = hour;
+        self.from = self.current;
+        self.elapsed = 0.0;
+    }
+
+    /// Ease the look towards the time of day by `delta_secs` of real time
+    pub fn advance(&mut self, delta_secs: f32) {
+        self.elapsed = (self.elapsed + delta_secs).min(DAY_NIGHT_EASE_SECS);
+        self.current = self
+            .from
+            .lerp(&look_at(self.hour), self.elapsed / DAY_NIGHT_EASE_SECS);
+    }
+
+    /// Check if the look has reached the time of day
+    pub fn is_settled(&self) -> bool {
+        self.elapsed >= DAY_NIGHT_EASE_SECS
+    }
+
+    /// HUD clock line, e.g. `CLOCK: 🌙 NIGHT 22:30`
+    pub fn clock_readout(&self) -> String {
+        let minutes = (self.hour * 60.0).round() as u32;
+        format!(
+            "CLOCK: {} {} {:02}:{:02}",
+            self.phase().icon(),
+            self.phase().name(),
+            (minutes / 60) % 24,
+            minutes % 60
+        )
+    }
+}
+
+/// Move the calendar on by the world's ticks
+///
+/// The day always matches the nights rested, so a loaded save or a new run
+/// starts from the dawn of its own day.
+fn advance_calendar_system(
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    game_stats: Res<GameStatsResource>,
+    mut calendar: ResMut<GameCalendar>,
+) {
+    for tick in ticks.read().filter(|tick| cursor.accept(tick)) {
+        match tick.phase {
+            TickPhase::AfterPlayerMove => calendar.record_move(),
+            TickPhase::AfterRest => *calendar = GameCalendar::at_dawn(tick.day),
+        }
+    }
+    let day = game_stats.current_day();
+    if calendar.day() != day {
+        *calendar = GameCalendar::at_dawn(day);
+    }
+}
+
+/// Follow the calendar and light the world to match
+fn day_night_system(
+    time: Res<Time>,
+    calendar: Res<GameCalendar>,
+    render_config: Res<MapRenderConfig>,
+    mut cycle: ResMut<DayNightCycle>,
+    mut clear_color: ResMut<ClearColor>,
+    mut ambient: ResMut<AmbientLight>,
+    mut suns: Query<&mut DirectionalLight>,
+) {
+    let hour = calendar.hour();
+    if cycle.hour() == hour && cycle.is_settled() && !render_config.is_changed() {
+        return;
+    }
+    cycle.set_hour(hour);
+    cycle.advance(time.delta_secs());
+
+    let look = cycle.current().for_palette(render_config.palette);
+    let [r, g, b] = look.clear_color;
+    clear_color.0 = Color::srgb(r, g, b);
+    let [r, g, b] = look.light_tint;
+    ambient.color = Color::srgb(r, g, b);
+    ambient.brightness = look.ambient_brightness;
+    for mut sun in suns.iter_mut() {
+        sun.color = Color::srgb(r, g, b);
+        sun.illuminance = look.sun_illuminance;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: DayNightLook, expected: DayNightLook) {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+        let same = (0..3).all(|i| {
+            close(actual.clear_color[i], expected.clear_color[i])
+                && close(actual.light_tint[i], expected.light_tint[i])
+        }) && close(actual.ambient_brightness, expected.ambient_brightness)
+            && close(actual.sun_illuminance, expected.sun_illuminance);
+        assert!(same, "{:?} != {:?}", actual, expected);
+    }
+
+    #[test]
+    fn keyframes_hold_at_their_hours_and_blend_between() {
+        assert_close(look_at(0.0), NIGHT_LOOK);
+        assert_close(look_at(24.0), NIGHT_LOOK);
+        assert_close(look_at(6.0), DAWN_LOOK);
+        assert_close(look_at(12.0), DAY_LOOK);
+        assert_close(look_at(18.0), DUSK_LOOK);
+        assert_close(look_at(9.0), DAWN_LOOK.lerp(&DAY_LOOK, 0.5));
+        assert_close(look_at(23.0), DUSK_LOOK.lerp(&NIGHT_LOOK, 5.0 / 6.0));
+
+        assert_eq!(DayPhase::at(6.0), DayPhase::Dawn);
+        assert_eq!(DayPhase::at(21.0), DayPhase::Night);
+    }
+
+    #[test]
+    fn a_jump_in_time_eases_over_a_second() {
+        let mut cycle = DayNightCycle::default();
+        cycle.advance(0.1);
+        assert_close(cycle.current(), DAWN_LOOK);
+
+        // A jump from dawn to midnight eases in
+        cycle.set_hour(MIDNIGHT_HOUR);
+        assert_close(cycle.current(), DAWN_LOOK);
+        cycle.advance(0.5);
+        assert_close(cycle.current(), DAWN_LOOK.lerp(&NIGHT_LOOK, 0.5));
+        assert!(!cycle.is_settled());
+        cycle.advance(0.6);
+        assert_close(cycle.current(), NIGHT_LOOK);
+        assert!(cycle.is_settled());
+        assert_eq!(cycle.view_narrowing(), NIGHT_VIEW_NARROWING);
+        assert_eq!(cycle.clock_readout(), "CLOCK: 🌙 NIGHT 00:00");
+    }
+
+    #[test]
+    fn the_calendar_follows_moves_rests_and_loaded_days() {
+        use crate::domain::constants::CALENDAR_TILES_PER_DAY;
+        use crate::domain::services::CalendarPhase;
+        use crate::presentation::world_tick::WorldClock;
+
+        let mut app = App::new();
+        app.add_event::<WorldTick>()
+            .init_resource::<GameStatsResource>()
+            .init_resource::<GameCalendar>()
+            .add_systems(Update, advance_calendar_system);
+        let mut clock = WorldClock::default();
+        let mut send = |app: &mut App, phase: TickPhase, day: u32| {
+            let tick = clock.tick(phase, day, None);
+            app.world_mut().send_event(tick);
+            app.update();
+        };
+
+        for _ in 0..CALENDAR_TILES_PER_DAY {
+            send(&mut app, TickPhase::AfterPlayerMove, 1);
+        }
+        let calendar = *app.world().resource::<GameCalendar>();
+        assert_eq!(calendar.phase(), CalendarPhase::Night);
+        assert_eq!(calendar.label(), "Day 1, Night");
+
+        app.world_mut()
+            .resource_mut::<GameStatsResource>()
+            .record_rest();
+        send(&mut app, TickPhase::AfterRest, 2);
+        assert_eq!(
+            *app.world().resource::<GameCalendar>(),
+            GameCalendar::at_dawn(2)
+        );
+
+        // A new run starts over at the first dawn
+        app.insert_resource(GameStatsResource::new());
+        app.update();
+        assert_eq!(
+            *app.world().resource::<GameCalendar>(),
+            GameCalendar::default()
+        );
+    }
+
+    #[test]
+    fn high_contrast_keeps_daylight_on_the_tiles() {
+        let night = NIGHT_LOOK.for_palette(MapPalette::HighContrast);
+        assert_eq!(night.clear_color, NIGHT_LOOK.clear_color);
+        assert_eq!(night.light_tint, DAY_LOOK.light_tint);
+        assert_eq!(night.ambient_brightness, DAY_LOOK.ambient_brightness);
+        assert_eq!(night.sun_illuminance, DAY_LOOK.sun_illuminance);
+        assert_eq!(NIGHT_LOOK.for_palette(MapPalette::Standard), NIGHT_LOOK);
+    }
+}
@@ -0,0 +1,242 @@
+//! Decals - Drawing the marks the player leaves on the ground
+//!
+//! Resources taken away from the base mark the tile as worked, won fights
+//! scorch it and completed Prospect quests leave a survey stake on the
+//! deposit. Decals are drawn as children of their tile entity, so they come
+//! and go with the tile, and age once per night of rest. They live in the
+//! session and are saved with the run; the reduce-clutter display setting
+//! hides them.
+
+use crate::domain::services::{Decal, DecalKind, DecalLook, TileDecals};
+use crate::domain::value_objects::Position3D;
+use crate::infrastructure::bevy::resources::{MapResource, PlayerChange, PlayerResource};
+use crate::presentation::game_event_logger::PlayerChangedEvent;
+use crate::presentation::game_state::RpgGameSession;
+use crate::presentation::map_renderer::TerrainTile;
+use crate::presentation::rendering::DisplaySettings;
+use crate::presentation::world_rebuild::WorldTeardown;
+use crate::presentation::world_tick::{TickCursor, TickPhase, WorldTick, WorldTickSet};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Plugin for leaving, aging and drawing tile decals
+pub struct DecalsPlugin;
+
+impl Plugin for DecalsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DecalAssets>()
+            .add_systems(
+                Update,
+                (
+                    age_decals_system.in_set(WorldTickSet::Economy),
+                    mark_worked_ground_system,
+                    sync_decal_markers,
+                )
+                    .chain(),
+            )
+            .add_systems(WorldTeardown, clear_decals_system);
+    }
+}
+
+/// A decal drawn on its tile
+#[derive(Component)]
+pub struct DecalMarker;
+
+/// Meshes and materials shared by every decal
+#[derive(Resource)]
+pub struct DecalAssets {
+    pub disc: Handle<Mesh>,
+    pub stump: Handle<Mesh>,
+    pub stake: Handle<Mesh>,
+    pub stump_material: Handle<StandardMaterial>,
+    pub pit_material: Handle<StandardMaterial>,
+    pub stake_material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for DecalAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let disc = meshes.add(Cylinder::new(0.35, 0.01));
+        let stump = meshes.add(Cylinder::new(0.12, 0.15));
+        let stake = meshes.add(Cuboid::new(0.05, 0.45, 0.05));
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        Self {
+            disc,
+            stump,
+            stake,
+            stump_material: materials.add(Color::srgb(0.45, 0.32, 0.2)),
+            pit_material: materials.add(Color::srgb(0.2, 0.17, 0.14)),
+            stake_material: materials.add(StandardMaterial {
+                base_color: Color::srgb(0.95, 0.55, 0.1),
+                emissive: LinearRgba::rgb(0.4, 0.2, 0.0),
+                ..default()
+            }),
+        }
+    }
+}
+
+/// Decals to draw, or none while in an interior or with reduced clutter
+pub fn shown_decals(
+    decals: &TileDecals,
+    in_interior: bool,
+    reduce_clutter: bool,
+) -> Vec<(Position3D, Decal)> {
+    if in_interior || reduce_clutter {
+        return Vec::new();
+    }
+    decals
+        .sites()
+        .iter()
+        .flat_map(|site| site.decals.iter().map(|decal| (site.position, *decal)))
+        .collect()
+}
+
+/// Clear the decals and their markers off a world being rebuilt
+fn clear_decals_system(
+    mut commands: Commands,
+    mut session: ResMut<RpgGameSession>,
+    markers: Query<Entity, With<DecalMarker>>,
+) {
+    session.decals = TileDecals::new();
+    for entity in markers.iter() {
+        commands.entity(entity).try_despawn();
+    }
+}
+
+/// Age every decal once per night of rest
+fn age_decals_system(
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    mut session: ResMut<RpgGameSession>,
+) {
+    for _ in cursor.take(ticks.read(), TickPhase::AfterRest) {
+        let faded = session.decals.rest();
+        if faded > 0 {
+            debug!("{} scorch mark(s) faded away", faded);
+        }
+    }
+}
+
+/// Mark the player's tile as worked when resources are taken from it
+fn mark_worked_ground_system(
+    mut player_events: EventReader<PlayerChangedEvent>,
+    map_resource: Res<MapResource>,
+    player_resource: Res<PlayerResource>,
+    mut session: ResMut<RpgGameSession>,
+) {
+    let gained = player_events.read().any(
+        |event| matches!(event.change, PlayerChange::ResourceChanged { delta, .. } if delta > 0),
+    );
+    if !gained || map_resource.is_in_interior() {
+        return;
+    }
+    let Some(position) = player_resource.player_position() else {
+        return;
+    };
+    if position != *session.base.position() {
+        session.decals.add(position, DecalKind::Worked);
+    }
+}
+
+/// Keep a child marker on every explored tile entity bearing a decal
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn sync_decal_markers(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    assets: Res<DecalAssets>,
+    session: Res<RpgGameSession>,
+    map_resource: Res<MapResource>,
+    display: Option<Res<DisplaySettings>>,
+    tiles: Query<(Entity, &TerrainTile)>,
+    markers: Query<Entity, With<DecalMarker>>,
+    mut rendered: Local<Option<Vec<(Entity, usize, DecalLook, u8)>>>,
+) {
+    let shown = shown_decals(
+        &session.decals,
+        map_resource.is_in_interior(),
+        display.is_some_and(|display| display.reduce_clutter),
+    );
+    let wanted: Vec<(Entity, usize, DecalLook, u8)> = if shown.is_empty() {
+        Vec::new()
+    } else {
+        let tiles: HashMap<Position3D, (Entity, &TerrainTile)> = tiles
+            .iter()
+            .filter(|(_, tile)| tile.is_explored)
+            .map(|(entity, tile)| (Position3D::from(tile.coordinate), (entity, tile)))
+            .collect();
+        let mut slots: HashMap<Position3D, usize> = HashMap::new();
+        shown
+            .into_iter()
+            .filter_map(|(position, decal)| {
+                let (entity, tile) = tiles.get(&position)?;
+                let slot = slots.entry(position).or_default();
+                *slot += 1;
+                let opacity = (decal.opacity() * 100.0).round() as u8;
+                Some((
+                    *entity,
+                    *slot - 1,
+                    decal.kind.look(tile.terrain_type),
+                    opacity,
+                ))
+            })
+            .collect()
+    };
+    if rendered.as_ref() == Some(&wanted) {
+        return;
+    }
+
+    for entity in markers.iter() {
+        commands.entity(entity).try_despawn();
+    }
+    for &(tile, slot, look, opacity) in &wanted {
+        // Two decals share a tile side by side, just above its surface
+        let offset = if slot == 0 { -0.35 } else { 0.35 };
+        let (mesh, material, height) = match look {
+            DecalLook::Scorch => (
+                assets.disc.clone(),
+                materials.add(StandardMaterial {
+                    base_color: Color::srgba(0.08, 0.07, 0.06, opacity as f32 / 100.0 * 0.85),
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    ..default()
+                }),
+                0.11,
+            ),
+            DecalLook::Pit => (assets.disc.clone(), assets.pit_material.clone(), 0.105),
+            DecalLook::Stump => (assets.stump.clone(), assets.stump_material.clone(), 0.175),
+            DecalLook::Stake => (assets.stake.clone(), assets.stake_material.clone(), 0.325),
+        };
+        commands.spawn((
+            Mesh3d(mesh),
+            MeshMaterial3d(material),
+            Transform::from_translation(Vec3::new(offset, height, offset * 0.5)),
+            DecalMarker,
+            Name::new(format!("Decal_{:?}", look)),
+            ChildOf(tile),
+        ));
+    }
+    *rendered = Some(wanted);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reduce_clutter_hides_every_decal() {
+        let mut decals = TileDecals::new();
+        decals.add(Position3D::new(2, 3, 0), DecalKind::Scorch);
+        decals.add(Position3D::new(2, 3, 0), DecalKind::Worked);
+        decals.add(Position3D::new(-5, 1, 0), DecalKind::SurveyStake);
+
+        let shown = shown_decals(&decals, false, false);
+        assert_eq!(shown.len(), 3);
+        assert_eq!(shown[0].0, Position3D::new(2, 3, 0));
+        assert_eq!(shown[2].1.kind, DecalKind::SurveyStake);
+
+        assert!(shown_decals(&decals, false, true).is_empty());
+        assert!(shown_decals(&decals, true, false).is_empty());
+        // Hiding them leaves the decals themselves alone
+        assert_eq!(decals.sites().len(), 2);
+    }
+}
@@ -0,0 +1,282 @@
+//! Delayed Audio - Timed sound sequences carried by an entity
+//!
+//! A sequence is a list of steps, each starting a sound some time after the
+//! sequence was spawned. The carrier entity is despawned once the last step
+//! has played, and despawning it early cancels whatever has not played yet.
+//! Sequences tagged with an `AudioStateScope` are dropped silently as soon
+//! as the game leaves that state. Every step goes through the global audio
+//! switches and, when present, the SFX arbiter.
+
+use crate::presentation::audio_integration::{
+    AudioAssets, AudioCategory, GlobalAudioSettings, SfxArbiter, SfxPriority,
+};
+use crate::presentation::RpgAppState;
+use bevy::audio::Volume;
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+
+/// Loaded one-shot sounds that can be scheduled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioKey {
+    DiceRoll,
+    MovementStep,
+    DiscoveryChime,
+    UiClick,
+    ResourceCollect,
+    RestComplete,
+}
+
+impl AudioKey {
+    /// Handle of the sound, if it was loaded
+    pub fn handle(self, assets: &AudioAssets) -> Option<&Handle<AudioSource>> {
+        match self {
+            AudioKey::DiceRoll => assets.dice_roll.as_ref(),
+            AudioKey::MovementStep => assets.movement_step.as_ref(),
+            AudioKey::DiscoveryChime => assets.discovery_chime.as_ref(),
+            AudioKey::UiClick => assets.ui_click.as_ref(),
+            AudioKey::ResourceCollect => assets.resource_collect.as_ref(),
+            AudioKey::RestComplete => assets.rest_complete.as_ref(),
+        }
+    }
+
+    /// Category switch the sound obeys
+    pub fn category(self) -> AudioCategory {
+        match self {
+            AudioKey::UiClick => AudioCategory::Ui,
+            _ => AudioCategory::Sfx,
+        }
+    }
+
+    /// Priority of the sound in the SFX arbiter
+    pub fn priority(self) -> SfxPriority {
+        match self {
+            AudioKey::UiClick => SfxPriority::Ui,
+            AudioKey::DiscoveryChime => SfxPriority::Discovery,
+            AudioKey::ResourceCollect => SfxPriority::Resource,
+            AudioKey::DiceRoll | AudioKey::MovementStep | AudioKey::RestComplete => {
+                SfxPriority::Effect
+            }
+        }
+    }
+}
+
+/// One sound of a sequence
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioStep {
+    /// Seconds after the sequence started
+    pub delay: f32,
+    pub key: AudioKey,
+    /// Linear volume
+    pub volume: f32,
+}
+
+impl AudioStep {
+    /// Create a step playing `key` at `volume`, `delay` seconds into the sequence
+    pub fn new(delay: f32, key: AudioKey, volume: f32) -> Self {
+        Self { delay, key, volume }
+    }
+}
+
+/// Sounds still to be played by this entity
+#[derive(Component, Debug, Clone)]
+pub struct DelayedAudio {
+    steps: Vec<AudioStep>,
+    elapsed: f32,
+    next: usize,
+}
+
+impl DelayedAudio {
+    /// Create a sequence; steps are played in order of their delay
+    pub fn new(steps: &[AudioStep]) -> Self {
+        let mut steps = steps.to_vec();
+        steps.sort_by(|a, b| a.delay.total_cmp(&b.delay));
+        Self {
+            steps,
+            elapsed: 0.0,
+            next: 0,
+        }
+    }
+
+    /// Advance the sequence clock and take the steps that are now due
+    pub fn advance(&mut self, delta: f32) -> Vec<AudioStep> {
+        self.elapsed += delta;
+        let due = self.steps[self.next..]
+            .iter()
+            .take_while(|step| step.delay <= self.elapsed)
+            .count();
+        let start = self.next;
+        self.next += due;
+        self.steps[start..self.next].to_vec()
+    }
+
+    /// Check if every step has played
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.steps.len()
+    }
+}
+
+/// State a sequence belongs to; leaving it drops the sequence unplayed
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct AudioStateScope(pub RpgAppState);
+
+/// Spawn audio sequences from systems
+pub trait PlaySequenceExt {
+    /// Spawn a carrier entity playing `steps`; despawn it to cancel
+    fn play_sequence(&mut self, steps: &[AudioStep]) -> EntityCommands<'_>;
+}
+
+impl PlaySequenceExt for Commands<'_, '_> {
+    fn play_sequence(&mut self, steps: &[AudioStep]) -> EntityCommands<'_> {
+        self.spawn((DelayedAudio::new(steps), Name::new("DelayedAudio")))
+    }
+}
+
+/// Play due steps of every sequence and despawn finished or out-of-scope ones
+pub fn delayed_audio_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    current_state: Res<State<RpgAppState>>,
+    settings: Res<GlobalAudioSettings>,
+    assets: Option<Res<AudioAssets>>,
+    mut sfx: Option<ResMut<SfxArbiter>>,
+    mut sequences: Query<(Entity, &mut DelayedAudio, Option<&AudioStateScope>)>,
+) {
+    for (entity, mut sequence, scope) in sequences.iter_mut() {
+        if scope.is_some_and(|scope| scope.0 != *current_state.get()) {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        for step in sequence.advance(time.delta_secs()) {
+            let Some(handle) = assets.as_ref().and_then(|assets| step.key.handle(assets)) else {
+                continue;
+            };
+            let category = step.key.category();
+            if !settings.can_play(category) {
+                continue;
+            }
+            let playback = PlaybackSettings::ONCE.with_volume(Volume::Linear(step.volume));
+            match sfx.as_mut() {
+                Some(sfx) => sfx.request(handle, playback, category, step.key.priority()),
+                None => {
+                    commands.spawn((AudioPlayer::new(handle.clone()), playback));
+                }
+            }
+        }
+
+        if sequence.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use std::time::Duration;
+
+    fn sample(id: u128) -> Handle<AudioSource> {
+        Handle::Weak(AssetId::Uuid {
+            uuid: uuid::Uuid::from_u128(id),
+        })
+    }
+
+    fn audio_world() -> World {
+        let mut world = World::new();
+        world.insert_resource(Time::<()>::default());
+        world.insert_resource(State::new(RpgAppState::Exploration));
+        world.insert_resource(GlobalAudioSettings::default());
+        world.insert_resource(SfxArbiter::new(8, 0.0));
+        world.insert_resource(AudioAssets {
+            dice_roll: Some(sample(1)),
+            rest_complete: Some(sample(2)),
+            discovery_chime: Some(sample(3)),
+            ..Default::default()
+        });
+        world
+    }
+
+    /// Advance time, run the system and return the sounds it requested
+    fn tick(world: &mut World, seconds: f32) -> Vec<Handle<AudioSource>> {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(seconds));
+        world.run_system_once(delayed_audio_system).unwrap();
+        let now = world.resource::<Time>().elapsed_secs();
+        world
+            .resource_mut::<SfxArbiter>()
+            .drain(now)
+            .into_iter()
+            .map(|request| request.handle)
+            .collect()
+    }
+
+    fn spawn_sequence(world: &mut World, steps: &[AudioStep]) -> Entity {
+        let entity = world.commands().play_sequence(steps).id();
+        world.flush();
+        entity
+    }
+
+    fn three_steps() -> [AudioStep; 3] {
+        [
+            AudioStep::new(0.5, AudioKey::DiceRoll, 1.0),
+            AudioStep::new(1.0, AudioKey::RestComplete, 0.8),
+            AudioStep::new(1.5, AudioKey::DiscoveryChime, 0.6),
+        ]
+    }
+
+    #[test]
+    fn steps_play_at_their_time_and_carrier_despawns() {
+        let mut world = audio_world();
+        let carrier = spawn_sequence(&mut world, &three_steps());
+
+        assert!(tick(&mut world, 0.25).is_empty());
+        assert_eq!(tick(&mut world, 0.25), vec![sample(1)]);
+        assert!(tick(&mut world, 0.25).is_empty());
+        assert_eq!(tick(&mut world, 0.25), vec![sample(2)]);
+        assert!(world.get_entity(carrier).is_ok());
+
+        assert_eq!(tick(&mut world, 0.5), vec![sample(3)]);
+        assert!(world.get_entity(carrier).is_err());
+    }
+
+    #[test]
+    fn despawning_mid_sequence_cancels_the_rest() {
+        let mut world = audio_world();
+        let carrier = spawn_sequence(&mut world, &three_steps());
+
+        assert_eq!(tick(&mut world, 0.6), vec![sample(1)]);
+        world.despawn(carrier);
+
+        assert!(tick(&mut world, 0.6).is_empty());
+        assert!(tick(&mut world, 1.0).is_empty());
+    }
+
+    #[test]
+    fn leaving_the_scoped_state_drops_the_sequence() {
+        let mut world = audio_world();
+        let scoped = world
+            .commands()
+            .play_sequence(&three_steps())
+            .insert(AudioStateScope(RpgAppState::Exploration))
+            .id();
+        world.flush();
+
+        assert_eq!(tick(&mut world, 0.5), vec![sample(1)]);
+        world.insert_resource(State::new(RpgAppState::Combat));
+
+        assert!(tick(&mut world, 2.0).is_empty());
+        assert!(world.get_entity(scoped).is_err());
+    }
+
+    #[test]
+    fn disabled_category_skips_steps_but_finishes() {
+        let mut world = audio_world();
+        world.resource_mut::<GlobalAudioSettings>().sfx_enabled = false;
+        let carrier = spawn_sequence(&mut world, &three_steps());
+
+        assert!(tick(&mut world, 2.0).is_empty());
+        assert!(world.get_entity(carrier).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+//! Ruin Delving - Entering, looting and leaving interior sub-maps
+//!
+//! Standing on a ruin in the overworld offers to delve. Delving switches
+//! the active map to the ruin's interior and places the player on its
+//! entrance; loot tiles pay out when stepped on, each with a relic to carry
+//! home, the last one adds a scout probe and a piece of gear from the
+//! ruin's vault, and the exit tile returns the player to the ruin on the
+//! surface.
+
+use crate::domain::constants::{RUIN_RELIC_ID, RUIN_RELIC_WEIGHT};
+use crate::domain::entities::InventoryItem;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{poi_entry_id, ConsumableKind, InteriorGenerator, PointOfInterest};
+use crate::domain::value_objects::{Position3D, TileCoordinate};
+use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource, PlayerResource};
+use crate::presentation::codex::CodexUnlockEvent;
+use crate::presentation::inventory::pick_up_find;
+use crate::presentation::map_renderer::PlayerMarker;
+use crate::presentation::movement::{run_modifiers, SmoothMovement};
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+
+/// Plugin for ruin interiors
+pub struct DelvingPlugin;
+
+impl Plugin for DelvingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, delving_system);
+    }
+}
+
+/// Offer, enter, loot and leave ruin interiors
+#[allow(clippy::too_many_arguments)]
+fn delving_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_state: Res<State<RpgAppState>>,
+    mut map_resource: ResMut<MapResource>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut player_query: Query<&mut SmoothMovement, With<PlayerMarker>>,
+    mut game_log: ResMut<GameLogService>,
+    mut codex_unlocks: EventWriter<CodexUnlockEvent>,
+    mut last_position: Local<Option<Position3D>>,
+    game_stats: Option<Res<GameStatsResource>>,
+) {
+    if *current_state.get() != RpgAppState::Exploration {
+        return;
+    }
+    let Some(position) = player_resource.player_position() else {
+        return;
+    };
+    if player_query
+        .single()
+        .is_ok_and(|movement| movement.is_moving)
+    {
+        return;
+    }
+    let arrived = *last_position != Some(position);
+    *last_position = Some(position);
+
+    if map_resource.is_in_interior() {
+        if !arrived {
+            return;
+        }
+        let Some(interior) = map_resource.active_interior_mut() else {
+            return;
+        };
+        if let Some(loot) = interior.take_loot(position) {
+            let loot = run_modifiers(game_stats.as_deref()).yields(&loot);
+            let found: Vec<String> = loot
+                .amounts()
+                .iter()
+                .map(|amount| format!("{} {}", amount.amount, amount.resource_type))
+                .collect();
+            game_log.log_message(
+                format!("💰 Salvaged {} from the ruins", found.join(", ")),
+                GameLogType::Resources,
+            );
+            pick_up_find(&mut player_resource, &loot, &mut game_log);
+            let relic = InventoryItem::new(RUIN_RELIC_ID, 1, RUIN_RELIC_WEIGHT);
+            if player_resource.pick_up_item(relic) == 0 {
+                game_log.log_message(
+                    "🏺 A relic lies in the rubble - hand it in at the base".to_string(),
+                    GameLogType::Discovery,
+                );
+            } else {
+                game_log.log_message(
+                    "🎒 Cargo hold full - left a relic behind".to_string(),
+                    GameLogType::Warning,
+                );
+            }
+            // The last room of a ruin always holds an intact probe and
+            // opens onto the ruin's vault
+            if interior.remaining_loot().is_empty() {
+                if player_resource
+                    .add_consumables(ConsumableKind::ScoutProbe, 1)
+                    .is_ok()
+                {
+                    game_log.log_message(
+                        "🛰️ Among the debris: an intact scout probe".to_string(),
+                        GameLogType::Discovery,
+                    );
+                }
+                let gear = interior.vault_gear();
+                let description = format!("{} ({})", gear.name, gear.describe());
+                if player_resource.stow_gear(gear).is_ok() {
+                    game_log.log_message(
+                        format!("🧰 The vault holds gear: {}", description),
+                        GameLogType::Discovery,
+                    );
+                }
+            }
+        }
+        if interior.is_exit(position) {
+            if let Some(site) = map_resource.exit_interior() {
+                let surface = Position3D::new(site.x, site.y, site.z);
+                relocate_player(&mut player_resource, &mut player_query, surface);
+                *last_position = Some(surface);
+                game_log.log_message(
+                    "🏚️ You climb back out of the ruins".to_string(),
+                    GameLogType::Movement,
+                );
+            }
+        }
+        return;
+    }
+
+    let site = TileCoordinate::from(position);
+    let is_site = map_resource
+        .overworld()
+        .and_then(|map| {
+            map.get_tile(&site).map(|tile| {
+                InteriorGenerator::new().is_delve_site(map.seed(), site, tile.terrain_type)
+            })
+        })
+        .unwrap_or(false);
+    if !is_site {
+        return;
+    }
+
+    if arrived {
+        game_log.log_message(
+            "🏚️ Ruins here. Press V to delve inside".to_string(),
+            GameLogType::Discovery,
+        );
+        codex_unlocks.write(CodexUnlockEvent::new(poi_entry_id(PointOfInterest::Ruins)));
+    }
+    if keyboard.just_pressed(KeyCode::KeyV) {
+        if let Some(entrance) = map_resource.enter_interior(site) {
+            relocate_player(&mut player_resource, &mut player_query, entrance);
+            *last_position = Some(entrance);
+            game_log.log_message(
+                "🔦 You delve into the ruins. Resting is impossible inside".to_string(),
+                GameLogType::Discovery,
+            );
+        }
+    }
+}
+
+/// Move the player and its model without an animation or movement cost
+fn relocate_player(
+    player_resource: &mut PlayerResource,
+    player_query: &mut Query<&mut SmoothMovement, With<PlayerMarker>>,
+    position: Position3D,
+) {
+    player_resource.set_position(position);
+    if let Ok(mut movement) = player_query.single_mut() {
+        movement.reset_to_position(position);
+    }
+}
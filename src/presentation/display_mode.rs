@@ -0,0 +1,272 @@
+//! Display Mode - Window size presets, fullscreen and the UI scale
+//!
+//! Native builds pick the window size from a short list of presets, or
+//! match the desktop, and F11 switches to borderless fullscreen and back;
+//! F9 steps through the presets. Both are display settings, so they persist
+//! and are read once more before the window is created, which spares the
+//! player a visible resize at startup. A preset larger than the desktop is
+//! shrunk to fit, keeping its aspect ratio. When the window size changes a
+//! lot, the game log suggests a matching UI scale. On the web the canvas
+//! follows the page, so only the UI scale applies.
+
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::presentation::rendering::DisplaySettings;
+use bevy::prelude::*;
+use bevy::window::{Monitor, MonitorSelection, PrimaryMonitor, PrimaryWindow, WindowMode};
+use serde::{Deserialize, Serialize};
+
+/// Key that toggles borderless fullscreen
+pub const FULLSCREEN_KEY: KeyCode = KeyCode::F11;
+
+/// Key that steps to the next window size preset
+pub const RESOLUTION_KEY: KeyCode = KeyCode::F9;
+
+/// Title of the game window
+pub const WINDOW_TITLE: &str = "Space Looter - 3D Isometric RPG";
+
+/// Window size used while the desktop size is unknown
+const FALLBACK_RESOLUTION: UVec2 = UVec2::new(1280, 720);
+
+/// Window height the UI was laid out for at a scale of 1
+const UI_REFERENCE_HEIGHT: f32 = 800.0;
+
+/// Height ratio past which a new window size earns a UI scale suggestion
+const DRASTIC_RESIZE_RATIO: f32 = 1.3;
+
+/// Window size presets offered on native builds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ResolutionPreset {
+    #[default]
+    Hd720,
+    Hd900,
+    Hd1080,
+    /// As large as the desktop the window is on
+    MatchDesktop,
+}
+
+impl ResolutionPreset {
+    /// Every preset, in display order
+    pub fn all() -> [ResolutionPreset; 4] {
+        [
+            ResolutionPreset::Hd720,
+            ResolutionPreset::Hd900,
+            ResolutionPreset::Hd1080,
+            ResolutionPreset::MatchDesktop,
+        ]
+    }
+
+    /// Display name
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResolutionPreset::Hd720 => "1280x720",
+            ResolutionPreset::Hd900 => "1600x900",
+            ResolutionPreset::Hd1080 => "1920x1080",
+            ResolutionPreset::MatchDesktop => "Match desktop",
+        }
+    }
+
+    /// The preset after this one, wrapping around
+    pub fn next(&self) -> Self {
+        let all = Self::all();
+        let index = all.iter().position(|preset| preset == self).unwrap_or(0);
+        all[(index + 1) % all.len()]
+    }
+
+    /// Window size for this preset on a desktop of `desktop`, if known
+    ///
+    /// Sizes never exceed the desktop: a larger preset is shrunk to fit.
+    pub fn window_resolution(&self, desktop: Option<UVec2>) -> UVec2 {
+        let requested = match self {
+            ResolutionPreset::Hd720 => UVec2::new(1280, 720),
+            ResolutionPreset::Hd900 => UVec2::new(1600, 900),
+            ResolutionPreset::Hd1080 => UVec2::new(1920, 1080),
+            ResolutionPreset::MatchDesktop => desktop.unwrap_or(FALLBACK_RESOLUTION),
+        };
+        match desktop {
+            Some(desktop) => fit_to_desktop(requested, desktop),
+            None => requested,
+        }
+    }
+}
+
+/// Shrink `requested` to fit on `desktop`, keeping its aspect ratio
+pub fn fit_to_desktop(requested: UVec2, desktop: UVec2) -> UVec2 {
+    if requested.x <= desktop.x && requested.y <= desktop.y {
+        return requested;
+    }
+    let scale = (desktop.x as f32 / requested.x as f32).min(desktop.y as f32 / requested.y as f32);
+    let fit = |side: u32, limit: u32| ((side as f32 * scale).round() as u32).clamp(1, limit);
+    UVec2::new(fit(requested.x, desktop.x), fit(requested.y, desktop.y))
+}
+
+/// UI scale that suits a window `height` pixels tall, in steps of 0.25
+pub fn suggested_ui_scale(height: u32) -> f32 {
+    let scale = height as f32 / UI_REFERENCE_HEIGHT;
+    ((scale * 4.0).round() / 4.0).clamp(0.75, 2.0)
+}
+
+/// Window mode for the fullscreen setting
+fn window_mode(fullscreen: bool) -> WindowMode {
+    if fullscreen {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    } else {
+        WindowMode::Windowed
+    }
+}
+
+/// The primary window as the persisted display settings want it
+///
+/// Used before the window exists, when the desktop size is not known yet;
+/// matching the desktop starts at the fallback size until it is.
+pub fn primary_window(display: &DisplaySettings) -> Window {
+    let size = display.resolution.window_resolution(None);
+    Window {
+        title: WINDOW_TITLE.into(),
+        resolution: (size.x as f32, size.y as f32).into(),
+        mode: window_mode(display.fullscreen),
+        ..default()
+    }
+}
+
+/// Plugin for window presets, fullscreen and the UI scale
+pub struct DisplayModePlugin;
+
+impl Plugin for DisplayModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DisplaySettings>().add_systems(
+            Update,
+            (display_mode_input_system, apply_display_mode_system).chain(),
+        );
+    }
+}
+
+/// Toggle fullscreen on F11 and step through the presets on F9
+fn display_mode_input_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut display: ResMut<DisplaySettings>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    if cfg!(target_arch = "wasm32") {
+        return;
+    }
+    if keyboard.just_pressed(FULLSCREEN_KEY) {
+        display.fullscreen = !display.fullscreen;
+    }
+    if keyboard.just_pressed(RESOLUTION_KEY) {
+        display.resolution = display.resolution.next();
+        game_log.log_message(
+            format!("🖥️ Window size: {}", display.resolution.label()),
+            GameLogType::System,
+        );
+    }
+}
+
+/// Bring the window and the UI scale in line with the display settings
+///
+/// Runs when the settings change and once the desktop size is first known.
+/// Open screens and menus are left as they are; the window is asked to keep
+/// the focus across a mode switch.
+fn apply_display_mode_system(
+    display: Res<DisplaySettings>,
+    mut ui_scale: ResMut<UiScale>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    monitors: Query<&Monitor, With<PrimaryMonitor>>,
+    mut game_log: ResMut<GameLogService>,
+    mut desktop_seen: Local<bool>,
+) {
+    if ui_scale.0 != display.ui_scale {
+        ui_scale.0 = display.ui_scale;
+    }
+    if cfg!(target_arch = "wasm32") {
+        return;
+    }
+
+    let desktop = monitors.single().ok().map(|monitor| {
+        let scale = monitor.scale_factor.max(1.0);
+        UVec2::new(
+            (monitor.physical_width as f64 / scale) as u32,
+            (monitor.physical_height as f64 / scale) as u32,
+        )
+    });
+    let desktop_appeared = desktop.is_some() && !*desktop_seen;
+    *desktop_seen |= desktop.is_some();
+    if !display.is_changed() && !desktop_appeared {
+        return;
+    }
+    let Ok(mut window) = windows.single_mut() else {
+        return;
+    };
+
+    let mode = window_mode(display.fullscreen);
+    if window.mode != mode {
+        window.mode = mode;
+        window.focused = true;
+    }
+
+    // Fullscreen covers the desktop; the preset applies once windowed again
+    if display.fullscreen {
+        return;
+    }
+    let size = display.resolution.window_resolution(desktop);
+    let previous_height = window.resolution.height();
+    if window.resolution.width() as u32 != size.x || previous_height as u32 != size.y {
+        window.resolution.set(size.x as f32, size.y as f32);
+        let ratio = size.y as f32 / previous_height.max(1.0);
+        let suggestion = suggested_ui_scale(size.y);
+        let drastic = !(1.0 / DRASTIC_RESIZE_RATIO..=DRASTIC_RESIZE_RATIO).contains(&ratio);
+        if drastic && suggestion != display.ui_scale {
+            game_log.log_message(
+                format!(
+                    "🖥️ Window is now {}x{}. A UI scale of {:.2} may read better",
+                    size.x, size.y, suggestion
+                ),
+                GameLogType::System,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_map_to_window_sizes() {
+        assert_eq!(
+            ResolutionPreset::Hd900.window_resolution(None),
+            UVec2::new(1600, 900)
+        );
+        assert_eq!(
+            ResolutionPreset::MatchDesktop.window_resolution(Some(UVec2::new(2560, 1440))),
+            UVec2::new(2560, 1440)
+        );
+        assert_eq!(
+            ResolutionPreset::MatchDesktop.window_resolution(None),
+            FALLBACK_RESOLUTION
+        );
+        assert_eq!(
+            ResolutionPreset::MatchDesktop.next(),
+            ResolutionPreset::Hd720
+        );
+        assert_eq!(suggested_ui_scale(1080), 1.25);
+        assert_eq!(suggested_ui_scale(720), 1.0);
+    }
+
+    #[test]
+    fn oversized_presets_shrink_to_the_desktop() {
+        let laptop = UVec2::new(1366, 768);
+        assert_eq!(
+            ResolutionPreset::Hd720.window_resolution(Some(laptop)),
+            UVec2::new(1280, 720)
+        );
+        // 1920x1080 keeps 16:9 on a 1366x768 desktop
+        assert_eq!(
+            ResolutionPreset::Hd1080.window_resolution(Some(laptop)),
+            UVec2::new(1365, 768)
+        );
+        assert_eq!(
+            fit_to_desktop(UVec2::new(1600, 900), UVec2::new(1280, 1024)),
+            UVec2::new(1280, 720)
+        );
+    }
+}
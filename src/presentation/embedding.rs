@@ -0,0 +1,213 @@
+//! Embedding - Space Looter's building blocks as plugins for other Bevy apps
+//!
+//! The game itself is assembled from these three plugins, and another app
+//! can take just the ones it wants:
+//! - [`SpaceLooterCorePlugin`]: the domain services (movement rules, rest)
+//!   and the player, base, stats, timer and game log resources
+//! - [`SpaceLooterMapPlugin`]: the map resource and, unless turned off, the
+//!   3D map renderer
+//! - [`SpaceLooterMovementPlugin`]: keyboard and click movement with smooth
+//!   tile-to-tile animation
+//!
+//! Each plugin inserts the resources it owns only when they are missing, so
+//! they may be added in any order, next to resources the app inserted
+//! itself. What a plugin needs from elsewhere is checked once every plugin
+//! is built, and a missing piece panics naming the plugin that provides it.
+//! `examples/embed_minimal.rs` shows the smallest app: Core and Movement on
+//! a hand-made map.
+
+use crate::domain::services::game_log_service::GameLogService;
+use crate::domain::services::{RestingService, TileMovementService};
+use crate::infrastructure::bevy::resources::{
+    BaseResource, GameStatsResource, GameTimerResource, MapResource, PlayerResource,
+};
+use crate::presentation::map_renderer::{MapRendererPlugin, PlayerMarker};
+use crate::presentation::movement::{
+    movement_cost_at, run_modifiers, MovementCompleted, RestingTriggered, SmoothMovementPlugin,
+};
+use bevy::input::touch::TouchInput;
+use bevy::prelude::*;
+
+/// Domain services and the resources every other piece works on
+///
+/// Inserts `TileMovementService`, `RestingService`, `PlayerResource`,
+/// `BaseResource`, `GameStatsResource`, `GameTimerResource` and
+/// `GameLogService`. No player exists until the app creates one.
+pub struct SpaceLooterCorePlugin;
+
+impl Plugin for SpaceLooterCorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TileMovementService>()
+            .init_resource::<RestingService>()
+            .init_resource::<PlayerResource>()
+            .init_resource::<BaseResource>()
+            .init_resource::<GameStatsResource>()
+            .init_resource::<GameTimerResource>()
+            .init_resource::<GameLogService>();
+    }
+}
+
+/// The map resource and, with `render`, the 3D map renderer
+///
+/// The renderer needs `DefaultPlugins` and [`SpaceLooterCorePlugin`].
+pub struct SpaceLooterMapPlugin {
+    pub render: bool,
+}
+
+impl Default for SpaceLooterMapPlugin {
+    fn default() -> Self {
+        Self { render: true }
+    }
+}
+
+impl Plugin for SpaceLooterMapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MapResource>();
+        if self.render && !app.is_plugin_added::<MapRendererPlugin>() {
+            app.add_plugins(MapRendererPlugin);
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        if self.render {
+            require::<PlayerResource>(app, "SpaceLooterMapPlugin", "SpaceLooterCorePlugin");
+            require::<GameStatsResource>(app, "SpaceLooterMapPlugin", "SpaceLooterCorePlugin");
+            require::<Assets<Mesh>>(app, "SpaceLooterMapPlugin", "DefaultPlugins");
+        }
+    }
+}
+
+/// Keyboard and click movement of the entity marked `PlayerMarker`
+///
+/// Needs `PlayerResource` and `GameLogService` from
+/// [`SpaceLooterCorePlugin`], and `Time` and the keyboard and mouse inputs
+/// from `DefaultPlugins` (or `MinimalPlugins` with `InputPlugin`). Inserts
+/// an empty `MapResource` when there is none, so movement works without
+/// [`SpaceLooterMapPlugin`] on a map loaded by the app.
+///
+/// Steps are only animated and reported as `ExecuteRpgMovement` and
+/// `MovementCompleted` events; the game answers them with its dice and
+/// events. With `plain_steps` the plugin answers them itself instead: a
+/// finished step moves the player and costs its movement points, and the
+/// points refill as soon as they run out.
+pub struct SpaceLooterMovementPlugin {
+    pub plain_steps: bool,
+}
+
+impl Default for SpaceLooterMovementPlugin {
+    fn default() -> Self {
+        Self { plain_steps: true }
+    }
+}
+
+impl Plugin for SpaceLooterMovementPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MapResource>().add_event::<TouchInput>();
+        if !app.is_plugin_added::<SmoothMovementPlugin>() {
+            app.add_plugins(SmoothMovementPlugin);
+        }
+        if self.plain_steps {
+            app.add_systems(
+                Update,
+                plain_step_system.after(crate::presentation::movement::update_movement_animations),
+            );
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        require::<PlayerResource>(app, "SpaceLooterMovementPlugin", "SpaceLooterCorePlugin");
+        require::<GameLogService>(app, "SpaceLooterMovementPlugin", "SpaceLooterCorePlugin");
+        require::<Time>(
+            app,
+            "SpaceLooterMovementPlugin",
+            "DefaultPlugins or MinimalPlugins",
+        );
+        require::<ButtonInput<KeyCode>>(
+            app,
+            "SpaceLooterMovementPlugin",
+            "DefaultPlugins or InputPlugin",
+        );
+        require::<ButtonInput<MouseButton>>(
+            app,
+            "SpaceLooterMovementPlugin",
+            "DefaultPlugins or InputPlugin",
+        );
+    }
+}
+
+/// Panic with the plugin to add unless resource `R` exists
+fn require<R: Resource>(app: &App, plugin: &str, provider: &str) {
+    if !app.world().contains_resource::<R>() {
+        panic!(
+            "{} needs the {} resource: add {} to the app, or insert it yourself",
+            plugin,
+            std::any::type_name::<R>(),
+            provider
+        );
+    }
+}
+
+/// Move the player for each finished step, without dice or events
+fn plain_step_system(
+    mut completed: EventReader<MovementCompleted>,
+    mut resting: EventReader<RestingTriggered>,
+    players: Query<(), With<PlayerMarker>>,
+    map_resource: Res<MapResource>,
+    game_stats: Option<Res<GameStatsResource>>,
+    mut player_resource: ResMut<PlayerResource>,
+) {
+    for step in completed.read() {
+        if !players.contains(step.entity) {
+            continue;
+        }
+        let cost = movement_cost_at(
+            &map_resource,
+            None,
+            &run_modifiers(game_stats.as_deref()),
+            step.final_position,
+        );
+        if let Err(e) = player_resource.try_spend_movement_points(cost) {
+            warn!("Step without the points for it: {}", e);
+        }
+        player_resource.set_position(step.final_position);
+    }
+    if resting.read().count() > 0 {
+        player_resource.restore_movement_points();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headless_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(ButtonInput::<KeyCode>::default())
+            .insert_resource(ButtonInput::<MouseButton>::default());
+        app
+    }
+
+    #[test]
+    fn plugins_may_be_added_in_any_order() {
+        let mut app = headless_app();
+        app.add_plugins((
+            SpaceLooterMovementPlugin::default(),
+            SpaceLooterMapPlugin { render: false },
+            SpaceLooterCorePlugin,
+        ));
+        app.finish();
+        app.cleanup();
+        app.update();
+        assert!(app.world().contains_resource::<MapResource>());
+        assert!(app.world().contains_resource::<PlayerResource>());
+    }
+
+    #[test]
+    #[should_panic(expected = "add SpaceLooterCorePlugin")]
+    fn movement_without_core_names_the_missing_plugin() {
+        let mut app = headless_app();
+        app.add_plugins(SpaceLooterMovementPlugin::default());
+        app.finish();
+    }
+}
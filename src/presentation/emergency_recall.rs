@@ -0,0 +1,323 @@
+//! Emergency Recall - Turning one defeat per run into a return to base
+//!
+//! After every turn, once hazards and the rest of the world had their say,
+//! the player's vitals are checked. A defeated player with the recall still
+//! available is pulled back to the base instead of losing the run: half of
+//! the cargo stays behind and the Injured layer joins the modifier stack
+//! for a few days, and the hull is patched up to half. Without a recall
+//! left the run ends as before.
+
+use crate::domain::constants::RECALL_HEALTH_PERCENT;
+use crate::domain::entities::{Map, Player};
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{recall_losses, Vitals};
+use crate::domain::value_objects::{Position3D, ResourceCollection, ResourceType, TileCoordinate};
+use crate::infrastructure::bevy::resources::{
+    BaseResource, GameStatsResource, MapResource, PlayerResource,
+};
+use crate::presentation::game_state::{RpgAppState, RpgGameSession};
+use crate::presentation::map_renderer::PlayerMarker;
+use crate::presentation::movement::SmoothMovement;
+use crate::presentation::world_tick::{TickCursor, WorldTick, WorldTickSet};
+use bevy::prelude::*;
+
+/// Plugin for the survival check and the Emergency Recall
+pub struct EmergencyRecallPlugin;
+
+impl Plugin for EmergencyRecallPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                injury_layer_system.in_set(WorldTickSet::Hazards),
+                survival_check_system.in_set(WorldTickSet::Survival),
+            ),
+        );
+    }
+}
+
+/// The player's vitals on `map`
+pub fn player_vitals(player: &Player, map: &Map) -> Vitals {
+    let position = *player.position();
+    let open_neighbours = [(1, 0), (-1, 0), (0, 1), (0, -1)]
+        .into_iter()
+        .map(|(dx, dy)| Position3D::new(position.x + dx, position.y + dy, position.z))
+        // Uncharted tiles may still be a way out; only known barriers block
+        .filter(|next| {
+            !matches!(
+                map.get_tile(&TileCoordinate::from(*next)),
+                Some(tile) if !tile.terrain_type.is_passable()
+            )
+        })
+        .count();
+    Vitals {
+        health: player.health().current(),
+        movement_points: player.movement_points(),
+        food: player.resources().get_amount(ResourceType::Food),
+        open_neighbours,
+    }
+}
+
+/// Pull the player back to `base_position`, leaving the recall's losses
+/// behind and patching the hull up; returns what was lost
+pub fn recall_to_base(
+    player_resource: &mut PlayerResource,
+    map_resource: &mut MapResource,
+    base_position: Position3D,
+) -> ResourceCollection {
+    let lost = player_resource
+        .player()
+        .map(|player| recall_losses(player.resources()))
+        .unwrap_or_default();
+    for resource in lost.resource_types() {
+        let amount = lost.get_amount(resource) as i32;
+        if let Err(e) = player_resource.apply_resource_delta(resource, -amount) {
+            warn!("Failed to take the recall losses: {}", e);
+        }
+    }
+    map_resource.exit_interior();
+    player_resource.set_position(base_position);
+    if let Some(health) = player_resource.player().map(|player| *player.health()) {
+        let patched = health.max() * RECALL_HEALTH_PERCENT / 100;
+        player_resource.heal(patched.saturating_sub(health.current()));
+    }
+    lost
+}
+
+/// Keep the Injured layer of the modifier stack in step with the recall
+fn injury_layer_system(session: Res<RpgGameSession>, mut game_stats: ResMut<GameStatsResource>) {
+    let injured = session
+        .emergency_recall
+        .is_injured(game_stats.current_day());
+    if game_stats.modifiers.is_injured() != injured {
+        game_stats.modifiers.set_injured(injured);
+    }
+}
+
+/// Check the player once per turn; recall or end the run on defeat
+#[allow(clippy::too_many_arguments)]
+fn survival_check_system(
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    state: Res<State<RpgAppState>>,
+    mut next_state: ResMut<NextState<RpgAppState>>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut map_resource: ResMut<MapResource>,
+    base_resource: Res<BaseResource>,
+    mut session: ResMut<RpgGameSession>,
+    game_stats: Res<GameStatsResource>,
+    mut game_log: ResMut<GameLogService>,
+    mut movement: Query<&mut SmoothMovement, With<PlayerMarker>>,
+) {
+    let new_ticks = ticks.read().filter(|tick| cursor.accept(tick)).count();
+    if new_ticks == 0 || *state.get() == RpgAppState::GameOver {
+        return;
+    }
+    let (Some(player), Some(map)) = (player_resource.player(), map_resource.current_map()) else {
+        return;
+    };
+    let Some(cause) = player_vitals(player, map).defeat_cause() else {
+        return;
+    };
+
+    let day = game_stats.current_day();
+    let base_position = base_resource.base_position();
+    let Some(base_position) = base_position.filter(|_| session.emergency_recall.spend(day)) else {
+        game_log.log_message(
+            format!("☠️ Defeated: {}", cause.describe()),
+            GameLogType::Event,
+        );
+        next_state.set(RpgAppState::GameOver);
+        return;
+    };
+
+    let lost = recall_to_base(&mut player_resource, &mut map_resource, base_position);
+    for mut smooth in movement.iter_mut() {
+        smooth.reset_to_position(base_position);
+    }
+    game_log.log_message(
+        format!(
+            "🚨 EMERGENCY RECALL - {}: the base pulls you home. Lost: {}. Injured for {} days",
+            cause.describe(),
+            lost,
+            session.emergency_recall.injury_days_left(day)
+        ),
+        GameLogType::Warning,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::constants::BASE_PLAYER_HEALTH;
+    use crate::domain::entities::map::MapTile;
+    use crate::domain::entities::Base;
+    use crate::domain::services::{EmergencyRecall, RunMode};
+    use crate::domain::value_objects::terrain::{Elevation, TerrainType};
+    use crate::domain::value_objects::EntityId;
+    use crate::presentation::movement::MovementResultApplied;
+    use crate::presentation::world_tick::WorldTickPlugin;
+    use bevy::state::app::StatesPlugin;
+
+    const TRAP: Position3D = Position3D { x: 5, y: 5, z: 0 };
+
+    /// A hazard that leaves the player with nothing, like a bad turn would
+    fn drain_system(
+        mut ticks: EventReader<WorldTick>,
+        mut player_resource: ResMut<PlayerResource>,
+    ) {
+        if ticks.read().count() == 0 {
+            return;
+        }
+        player_resource.lose_movement_points(u8::MAX);
+        let food = player_resource.player().map_or(0, |player| {
+            player.resources().get_amount(ResourceType::Food)
+        });
+        player_resource
+            .apply_resource_delta(ResourceType::Food, -(food as i32))
+            .unwrap();
+    }
+
+    fn recall_app(recall: EmergencyRecall) -> App {
+        let mut map = Map::new(EntityId::generate(), "Test".to_string(), 1950).unwrap();
+        for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+            map.set_tile(
+                TileCoordinate::new(TRAP.x + dx, TRAP.y + dy, 0),
+                MapTile::new(TerrainType::Ocean, Elevation::sea_level(), true),
+            );
+        }
+        let mut map_resource = MapResource::new();
+        map_resource.load_map(map);
+        let mut base_resource = BaseResource::new();
+        base_resource
+            .create_base("Outpost".to_string(), Position3D::origin())
+            .unwrap();
+        let player = Player::create_new_character("Vex".to_string(), TRAP).unwrap();
+        let mut session = RpgGameSession::new(
+            player.clone(),
+            Base::new(
+                EntityId::generate(),
+                "Outpost".to_string(),
+                Position3D::origin(),
+            )
+            .unwrap(),
+        );
+        session.emergency_recall = recall;
+        let mut player_resource = PlayerResource::new();
+        player_resource
+            .create_player("vex".to_string(), "Vex".to_string(), TRAP, *player.stats())
+            .unwrap();
+        player_resource
+            .apply_resource_delta(ResourceType::Metal, 10)
+            .unwrap();
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, WorldTickPlugin))
+            .insert_state(RpgAppState::Exploration)
+            .insert_resource(GameStatsResource::new())
+            .insert_resource(player_resource)
+            .insert_resource(map_resource)
+            .insert_resource(base_resource)
+            .insert_resource(session)
+            .insert_resource(GameLogService::new())
+            .add_plugins(EmergencyRecallPlugin)
+            .add_systems(Update, drain_system.in_set(WorldTickSet::Hazards));
+        app
+    }
+
+    fn end_turn(app: &mut App) {
+        app.world_mut().send_event(MovementResultApplied {
+            final_position: TRAP,
+            event_title: None,
+        });
+        app.update();
+        app.update();
+    }
+
+    fn state(app: &App) -> RpgAppState {
+        app.world().resource::<State<RpgAppState>>().get().clone()
+    }
+
+    #[test]
+    fn a_stranded_player_is_recalled_once_then_defeated() {
+        let mut app = recall_app(EmergencyRecall::for_run(RunMode::Normal, false));
+        let metal = app
+            .world()
+            .resource::<PlayerResource>()
+            .player()
+            .unwrap()
+            .resources()
+            .get_amount(ResourceType::Metal);
+
+        end_turn(&mut app);
+        assert_eq!(state(&app), RpgAppState::Exploration);
+        let player = app.world().resource::<PlayerResource>().player().unwrap();
+        assert_eq!(*player.position(), Position3D::origin());
+        assert_eq!(
+            player.resources().get_amount(ResourceType::Metal),
+            metal - metal.div_ceil(2)
+        );
+        let day = app.world().resource::<GameStatsResource>().current_day();
+        let session = app.world().resource::<RpgGameSession>();
+        assert_eq!(session.emergency_recall.used_on_day(), Some(day));
+        assert!(app
+            .world()
+            .resource::<GameStatsResource>()
+            .modifiers
+            .is_injured());
+
+        // Stranded again with the recall spent: the run is lost
+        app.world_mut()
+            .resource_mut::<PlayerResource>()
+            .set_position(TRAP);
+        end_turn(&mut app);
+        assert_eq!(state(&app), RpgAppState::GameOver);
+    }
+
+    #[test]
+    fn a_killing_blow_during_a_move_ends_the_run_once_the_move_lands() {
+        let mut app = recall_app(EmergencyRecall::for_run(RunMode::Hardcore, false));
+        // Room to move on, so only the hull can end the run
+        app.world_mut()
+            .resource_mut::<PlayerResource>()
+            .set_position(Position3D::new(20, 20, 0));
+
+        // The hit is applied while the move is still animating
+        let taken = app
+            .world_mut()
+            .resource_mut::<PlayerResource>()
+            .take_damage(u32::MAX);
+        assert_eq!(taken, BASE_PLAYER_HEALTH);
+        app.update();
+        assert_eq!(state(&app), RpgAppState::Exploration);
+
+        // MovementCompleted applies the result and the turn ends on it
+        end_turn(&mut app);
+        assert_eq!(state(&app), RpgAppState::GameOver);
+    }
+
+    #[test]
+    fn the_recall_patches_up_a_wrecked_hull() {
+        let mut app = recall_app(EmergencyRecall::for_run(RunMode::Normal, false));
+        app.world_mut()
+            .resource_mut::<PlayerResource>()
+            .take_damage(u32::MAX);
+        end_turn(&mut app);
+        assert_eq!(state(&app), RpgAppState::Exploration);
+        let player = app.world().resource::<PlayerResource>().player().unwrap();
+        assert_eq!(*player.position(), Position3D::origin());
+        assert_eq!(
+            player.health().current(),
+            BASE_PLAYER_HEALTH * RECALL_HEALTH_PERCENT / 100
+        );
+    }
+
+    #[test]
+    fn hardcore_runs_without_the_recall_are_defeated_at_once() {
+        let mut app = recall_app(EmergencyRecall::for_run(RunMode::Hardcore, false));
+        end_turn(&mut app);
+        assert_eq!(state(&app), RpgAppState::GameOver);
+        let player = app.world().resource::<PlayerResource>().player().unwrap();
+        assert_eq!(*player.position(), TRAP);
+    }
+}
@@ -0,0 +1,375 @@
+//! Encounter Resolution - Holding the game while an event waits on a choice
+//!
+//! Raiders, traders and mysteries met on a move all wait for the player to
+//! choose. While one does, the game sits in `RpgAppState::EventResolution`,
+//! so movement and the other exploration inputs stay shut until it is
+//! settled. Contacts are raised when a move's result applies, after its
+//! animation completed, and the state only switches once the ship stands
+//! still.
+//!
+//! Raiders and traders keep their own panels. A mystery opens the panel
+//! here: 1 studies it on Intelligence, 2 attunes to it on Luck and 3 leaves
+//! it be, each with its roll shown up front. The d20 comes from the
+//! mysteries stream and the roll pays out through the same event path as
+//! any other event roll.
+
+use crate::application::use_cases::MysteryApproach;
+use crate::domain::constants::{MYSTERY_INSIGHT_ROLL, PANEL_BACKGROUND, PRIMARY_TEXT};
+use crate::domain::entities::Event;
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::value_objects::{PlayerStats, Position3D};
+use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource, PlayerResource};
+use crate::infrastructure::random::streams::{RngStreams, MYSTERIES};
+use crate::infrastructure::traits::RandomService;
+use crate::presentation::audio_integration::{AudioAssets, GlobalAudioSettings, SfxArbiter};
+use crate::presentation::clocks::GameplayConfig;
+use crate::presentation::field_trade::TraderContact;
+use crate::presentation::map_renderer::PlayerMarker;
+use crate::presentation::movement::SmoothMovement;
+use crate::presentation::reputation::HostileContact;
+use crate::presentation::world_rebuild::WorldTeardown;
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+
+/// Keys choosing the mystery approaches, in menu order
+pub const MYSTERY_KEYS: [KeyCode; 3] = [KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3];
+
+/// Plugin for the event resolution state and the mystery panel
+pub struct EncounterPlugin;
+
+impl Plugin for EncounterPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MysteryContact>()
+            .init_resource::<HostileContact>()
+            .init_resource::<TraderContact>()
+            .init_resource::<RngStreams>()
+            .add_systems(Startup, setup_mystery_panel)
+            .add_systems(WorldTeardown, forget_mystery_system)
+            .add_systems(
+                Update,
+                (
+                    event_resolution_state_system,
+                    mystery_choice_system,
+                    update_mystery_panel,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// A mystery met on the old world is gone with it
+fn forget_mystery_system(mut contact: ResMut<MysteryContact>) {
+    contact.settle();
+}
+
+/// A mystery waiting for the player to choose an approach
+#[derive(Resource, Debug, Clone, Default)]
+pub struct MysteryContact {
+    pending: Option<PendingMystery>,
+}
+
+/// A mystery event waiting to be met
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingMystery {
+    /// Tile the mystery was met on
+    pub position: Position3D,
+    pub event: Event,
+}
+
+impl MysteryContact {
+    /// Hold movement until the mystery met on `position` is dealt with
+    pub fn raise(&mut self, position: Position3D, event: Event) {
+        self.pending = Some(PendingMystery { position, event });
+    }
+
+    /// Check if movement is held for the mystery
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// The mystery waiting to be met
+    pub fn pending(&self) -> Option<&PendingMystery> {
+        self.pending.as_ref()
+    }
+
+    /// The mystery is dealt with
+    pub fn settle(&mut self) {
+        self.pending = None;
+    }
+}
+
+/// Marker for the mystery panel
+#[derive(Component)]
+pub struct MysteryPanel;
+
+/// Marker for the mystery panel text
+#[derive(Component)]
+pub struct MysteryText;
+
+fn setup_mystery_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(30.0),
+                top: Val::Px(120.0),
+                width: Val::Px(420.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(PANEL_BACKGROUND),
+            Visibility::Hidden,
+            MysteryPanel,
+            Name::new("MysteryPanel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                MysteryText,
+            ));
+        });
+}
+
+/// Enter event resolution while a contact waits, and go back to exploring
+/// once every contact is settled
+///
+/// Either way the ship has to stand still first: a move lands before its
+/// contact opens, and a flight plays out before exploring resumes.
+fn event_resolution_state_system(
+    state: Res<State<RpgAppState>>,
+    mut next_state: ResMut<NextState<RpgAppState>>,
+    hostile: Res<HostileContact>,
+    trader: Res<TraderContact>,
+    mystery: Res<MysteryContact>,
+    player_query: Query<&SmoothMovement, With<PlayerMarker>>,
+) {
+    if player_query.iter().any(|movement| movement.is_moving) {
+        return;
+    }
+    let pending = hostile.is_pending() || trader.is_pending() || mystery.is_pending();
+    match state.get() {
+        RpgAppState::Exploration if pending => next_state.set(RpgAppState::EventResolution),
+        RpgAppState::EventResolution if !pending => next_state.set(RpgAppState::Exploration),
+        _ => {}
+    }
+}
+
+/// Meet the mystery with the approach whose key was pressed
+#[allow(clippy::too_many_arguments)]
+fn mystery_choice_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut contact: ResMut<MysteryContact>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut game_stats: ResMut<GameStatsResource>,
+    mut game_log: ResMut<GameLogService>,
+    mut sfx: ResMut<SfxArbiter>,
+    audio_assets: Option<Res<AudioAssets>>,
+    audio_settings: Res<GlobalAudioSettings>,
+    config: Res<GameplayConfig>,
+    rng_streams: ResMut<RngStreams>,
+    map_resource: Res<MapResource>,
+) {
+    let Some(pending) = contact.pending.clone() else {
+        return;
+    };
+    let Some(approach) = MYSTERY_KEYS
+        .iter()
+        .position(|key| keyboard.just_pressed(*key))
+        .map(|index| MysteryApproach::ALL[index])
+    else {
+        return;
+    };
+    let Some(stats) = player_resource
+        .get_player()
+        .map(|player| player.derived_stats())
+    else {
+        return;
+    };
+
+    contact.settle();
+    if approach.stat().is_none() {
+        game_log.log_message(
+            format!(
+                "🔮 {}: you leave {} behind",
+                approach.label(),
+                pending.event.title()
+            ),
+            GameLogType::Event,
+        );
+        return;
+    }
+    let face = rng_streams.stream(MYSTERIES).random_range_i32(1, 20) as u8;
+    let Some(dice_result) = approach.resolve(&stats, face) else {
+        return;
+    };
+
+    game_log.log_message(
+        format!(
+            "🔮 {}: {} - {}",
+            approach.label(),
+            dice_result.description(),
+            dice_result.outcome_category()
+        ),
+        GameLogType::Event,
+    );
+    crate::process_movement_event(
+        &pending.event,
+        &dice_result,
+        &mut player_resource,
+        &mut game_stats,
+        &mut game_log,
+        &mut sfx,
+        audio_assets.as_ref(),
+        &audio_settings,
+        &config.movement_economy,
+        pending.position,
+        &map_resource,
+        &rng_streams,
+    );
+    let verdict = if dice_result.final_result >= MYSTERY_INSIGHT_ROLL {
+        "🔮 The phenomenon gives up its meaning"
+    } else {
+        "🔮 Its meaning eludes you"
+    };
+    game_log.log_message(verdict.to_string(), GameLogType::Narrative);
+}
+
+/// Show the mystery and its approaches while one waits
+fn update_mystery_panel(
+    contact: Res<MysteryContact>,
+    player_resource: Res<PlayerResource>,
+    mut panels: Query<&mut Visibility, With<MysteryPanel>>,
+    mut texts: Query<&mut Text, With<MysteryText>>,
+) {
+    if !contact.is_changed() {
+        return;
+    }
+    for mut visibility in panels.iter_mut() {
+        *visibility = if contact.is_pending() {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+    let stats = player_resource
+        .get_player()
+        .map(|player| player.derived_stats());
+    if let (Some(pending), Some(stats), Ok(mut text)) =
+        (contact.pending(), stats, texts.single_mut())
+    {
+        text.0 = mystery_text(&pending.event, &stats);
+    }
+}
+
+/// Text of the mystery panel: the event and each approach with its roll
+fn mystery_text(event: &Event, stats: &PlayerStats) -> String {
+    let mut lines = vec![
+        format!("🔮 MYSTERY - {}", event.title()),
+        String::new(),
+        event.description().to_string(),
+        String::new(),
+    ];
+    for (index, approach) in MysteryApproach::ALL.iter().enumerate() {
+        lines.push(format!(
+            "{}: {} - {}",
+            index + 1,
+            approach.label(),
+            approach.preview(stats)
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::EventType;
+    use bevy::state::app::StatesPlugin;
+
+    fn mystery() -> Event {
+        Event::new(
+            EventType::Mystery,
+            "Humming Monolith".to_string(),
+            "A black slab hums beneath the dust".to_string(),
+            Some(Position3D::new(1, 0, 0)),
+        )
+        .unwrap()
+    }
+
+    fn resolution_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_state(RpgAppState::Exploration)
+            .init_resource::<HostileContact>()
+            .init_resource::<TraderContact>()
+            .init_resource::<MysteryContact>()
+            .add_systems(Update, event_resolution_state_system);
+        app.world_mut()
+            .spawn((SmoothMovement::new(Position3D::origin()), PlayerMarker));
+        app
+    }
+
+    fn state(app: &App) -> RpgAppState {
+        app.world().resource::<State<RpgAppState>>().get().clone()
+    }
+
+    fn set_moving(app: &mut App, moving: bool) {
+        let world = app.world_mut();
+        let mut query = world.query::<&mut SmoothMovement>();
+        query.single_mut(world).unwrap().is_moving = moving;
+    }
+
+    #[test]
+    fn a_contact_holds_exploration_once_the_move_has_landed() {
+        let mut app = resolution_app();
+        set_moving(&mut app, true);
+        app.world_mut()
+            .resource_mut::<MysteryContact>()
+            .raise(Position3D::new(1, 0, 0), mystery());
+        app.update();
+        app.update();
+        assert_eq!(state(&app), RpgAppState::Exploration);
+
+        set_moving(&mut app, false);
+        app.update();
+        app.update();
+        assert_eq!(state(&app), RpgAppState::EventResolution);
+
+        // Raiders keep it held after the mystery is dealt with
+        app.world_mut()
+            .resource_mut::<HostileContact>()
+            .raise(Position3D::new(1, 0, 0), None);
+        app.world_mut().resource_mut::<MysteryContact>().settle();
+        app.update();
+        app.update();
+        assert_eq!(state(&app), RpgAppState::EventResolution);
+
+        // A flight plays out before exploring resumes
+        app.world_mut().resource_mut::<HostileContact>().settle();
+        set_moving(&mut app, true);
+        app.update();
+        app.update();
+        assert_eq!(state(&app), RpgAppState::EventResolution);
+        set_moving(&mut app, false);
+        app.update();
+        app.update();
+        assert_eq!(state(&app), RpgAppState::Exploration);
+    }
+
+    #[test]
+    fn the_panel_previews_every_approach() {
+        let stats = PlayerStats::new(10, 10, 14, 10, 10, 10).unwrap();
+        let text = mystery_text(&mystery(), &stats);
+        assert!(text.contains("Humming Monolith"));
+        assert!(text.contains("1: Study it - d20 +2 (Intelligence)"));
+        assert!(text.contains("2: Attune to it - d20 +0 (Luck)"));
+        assert!(text.contains("3: Leave it be - no roll"));
+    }
+}
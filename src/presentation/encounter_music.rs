@@ -0,0 +1,136 @@
+//! Encounter Music - Telling the soundtrack what the player is going through
+//!
+//! Fights with hostiles, anomaly storms over the player's tile and raids on
+//! the base are read from the state that already tracks them and kept in
+//! the music manager's override stack. Each encounter that begins plays a
+//! short stinger, unless the accessibility settings mute stingers; the
+//! ducking and the combat loop follow from the stack in the audio module.
+//! Raids are instant, so a damaged base holds the music for a few seconds.
+
+use crate::domain::constants::MUSIC_RAID_SECONDS;
+use crate::domain::services::{EncounterKind, WorldHazards};
+use crate::infrastructure::bevy::resources::{MapResource, PlayerResource};
+use crate::infrastructure::settings::AccessibilitySettings;
+use crate::presentation::audio_integration::{
+    spawn_world_sfx, AudioAssets, AudioCategory, GlobalAudioSettings, MusicManager, SfxArbiter,
+    SfxPriority,
+};
+use crate::presentation::base_visuals::{BaseChange, BaseChanged};
+use crate::presentation::reputation::HostileContact;
+use bevy::prelude::*;
+
+/// Plugin for encounter-aware music
+pub struct EncounterMusicPlugin;
+
+impl Plugin for EncounterMusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AccessibilitySettings>()
+            .add_event::<BaseChanged>()
+            .add_systems(Update, track_encounters_system);
+    }
+}
+
+/// Encounters going on now, from the state that tracks them
+fn current_encounters(
+    in_combat: bool,
+    in_storm: bool,
+    raid_until: Option<f32>,
+    now: f32,
+) -> Vec<EncounterKind> {
+    let mut encounters = Vec::new();
+    if in_storm {
+        encounters.push(EncounterKind::Storm);
+    }
+    if raid_until.is_some_and(|until| now < until) {
+        encounters.push(EncounterKind::Raid);
+    }
+    if in_combat {
+        encounters.push(EncounterKind::Combat);
+    }
+    encounters
+}
+
+/// Keep the override stack in step with fights, storms and raids
+#[allow(clippy::too_many_arguments)]
+fn track_encounters_system(
+    time: Res<Time>,
+    contact: Option<Res<HostileContact>>,
+    hazards: Option<Res<WorldHazards>>,
+    player_resource: Res<PlayerResource>,
+    map_resource: Res<MapResource>,
+    mut base_changes: EventReader<BaseChanged>,
+    mut raid_until: Local<Option<f32>>,
+    mut music_manager: ResMut<MusicManager>,
+    (audio_assets, audio_settings, accessibility, mut sfx): (
+        Res<AudioAssets>,
+        Res<GlobalAudioSettings>,
+        Res<AccessibilitySettings>,
+        ResMut<SfxArbiter>,
+    ),
+) {
+    let now = time.elapsed_secs();
+    if base_changes
+        .read()
+        .any(|event| matches!(event.change, BaseChange::Damaged(_)))
+    {
+        *raid_until = Some(now + MUSIC_RAID_SECONDS);
+    }
+
+    let pending = contact.as_ref().and_then(|contact| contact.pending());
+    // Storm footprints are overworld tiles
+    let in_storm = hazards.is_some_and(|hazards| {
+        player_resource
+            .player_position()
+            .filter(|_| !map_resource.is_in_interior())
+            .is_some_and(|position| hazards.storm_at(position).is_some())
+    });
+    let encounters = current_encounters(pending.is_some(), in_storm, *raid_until, now);
+
+    let began = music_manager.overrides.sync(&encounters);
+    // The exchange is kept between rounds, so a second round has begun
+    if pending.is_some_and(|pending| pending.exchange.is_some()) {
+        music_manager.overrides.engage_combat_loop();
+    }
+    if began.is_empty() {
+        return;
+    }
+    info!(
+        "🎵 Encounter began: {}",
+        began
+            .iter()
+            .map(|kind| kind.name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    if accessibility.mute_stingers {
+        return;
+    }
+    if let Some(stinger) = &audio_assets.encounter_stinger {
+        spawn_world_sfx(
+            &mut sfx,
+            &audio_settings,
+            AudioCategory::Sfx,
+            SfxPriority::Encounter,
+            stinger,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raids_hold_the_music_for_a_while() {
+        assert!(current_encounters(false, false, None, 3.0).is_empty());
+        assert_eq!(
+            current_encounters(true, true, Some(5.0), 3.0),
+            vec![
+                EncounterKind::Storm,
+                EncounterKind::Raid,
+                EncounterKind::Combat
+            ]
+        );
+        assert!(current_encounters(false, false, Some(5.0), 5.0).is_empty());
+    }
+}
@@ -0,0 +1,489 @@
+//! Expedition Planner - Waypoint planning from the base and route tracking
+//!
+//! From base management the player opens the planner, moves a cursor over
+//! explored tiles and drops waypoints. The panel shows the projected cost of
+//! the route; committing the plan makes it the session's active expedition,
+//! which is then tracked during exploration: waypoint markers on the map, a
+//! "next waypoint" HUD line, progress messages and an abandon prompt when the
+//! player strays too far from the route.
+
+use crate::domain::constants::{
+    ENERGY_COLOR, EXPEDITION_DEVIATION_TILES, EXPEDITION_MAX_WAYPOINTS, PANEL_BACKGROUND,
+    PRIMARY_TEXT, WARNING_TEXT,
+};
+use crate::domain::entities::Map;
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{
+    ExpeditionEstimate, ExpeditionPlan, ExpeditionPlanner, RestingService,
+};
+use crate::domain::value_objects::Position3D;
+use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource, PlayerResource};
+use crate::presentation::game_state::RpgGameSession;
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+
+/// Plugin for the expedition planner and active expedition tracking
+pub struct ExpeditionPlugin;
+
+impl Plugin for ExpeditionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ExpeditionDraft>()
+            .init_resource::<ExpeditionTracking>()
+            .add_systems(Startup, setup_expedition_ui)
+            .add_systems(
+                Update,
+                (
+                    planner_input_system,
+                    update_planner_panel,
+                    expedition_progress_system,
+                    update_expedition_hud,
+                    update_waypoint_markers,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Waypoints being placed in the planner and the resulting projection
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ExpeditionDraft {
+    pub cursor: Position3D,
+    pub waypoints: Vec<Position3D>,
+    pub plan: Option<ExpeditionPlan>,
+    pub estimate: Option<ExpeditionEstimate>,
+    pub error: Option<String>,
+}
+
+impl ExpeditionDraft {
+    /// Start a fresh draft with the cursor on the player
+    pub fn reset(&mut self, cursor: Position3D) {
+        *self = Self {
+            cursor,
+            ..Self::default()
+        };
+    }
+
+    /// Add a waypoint under the cursor; returns false when the list is full
+    pub fn add_waypoint(&mut self) -> bool {
+        if self.waypoints.len() >= EXPEDITION_MAX_WAYPOINTS {
+            return false;
+        }
+        self.waypoints.push(self.cursor);
+        true
+    }
+
+    /// Remove the most recently added waypoint
+    pub fn remove_last_waypoint(&mut self) -> Option<Position3D> {
+        self.waypoints.pop()
+    }
+
+    /// Rebuild the plan and its estimate from the current waypoints
+    pub fn replan(
+        &mut self,
+        map: &Map,
+        start: Position3D,
+        current_movement_points: u8,
+        movement_per_rest: f32,
+    ) {
+        self.plan = None;
+        self.estimate = None;
+        self.error = None;
+        if self.waypoints.is_empty() {
+            return;
+        }
+
+        let planner = ExpeditionPlanner::new();
+        match planner.plan(map, start, &self.waypoints) {
+            Ok(plan) => {
+                self.estimate =
+                    Some(planner.estimate(map, &plan, current_movement_points, movement_per_rest));
+                self.plan = Some(plan);
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+}
+
+/// Tracking state for the active expedition
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ExpeditionTracking {
+    last_position: Option<Position3D>,
+    /// The abandon prompt is waiting for an answer
+    prompt_open: bool,
+    /// The player chose to keep the plan; stay quiet until back on course
+    prompt_dismissed: bool,
+}
+
+/// Marker for the planner panel
+#[derive(Component)]
+pub struct ExpeditionPlannerPanel;
+
+/// Marker for the planner panel text
+#[derive(Component)]
+pub struct ExpeditionPlannerText;
+
+/// Marker for the active expedition HUD line
+#[derive(Component)]
+pub struct ExpeditionHudText;
+
+/// Marker for waypoint and cursor meshes on the map
+#[derive(Component)]
+pub struct WaypointMarker;
+
+fn setup_expedition_ui(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(30.0),
+                top: Val::Px(80.0),
+                width: Val::Px(420.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(PANEL_BACKGROUND),
+            Visibility::Hidden,
+            ExpeditionPlannerPanel,
+            Name::new("ExpeditionPlannerPanel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                ExpeditionPlannerText,
+            ));
+        });
+
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: FontSize::Small.to_pixels(),
+            ..default()
+        },
+        TextColor(ENERGY_COLOR),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(15.0),
+            bottom: Val::Px(15.0),
+            ..default()
+        },
+        ExpeditionHudText,
+        Name::new("ExpeditionHud"),
+    ));
+}
+
+/// Cursor movement, waypoint placement and plan commit while planning
+#[allow(clippy::too_many_arguments)]
+fn planner_input_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_state: Res<State<RpgAppState>>,
+    mut next_state: ResMut<NextState<RpgAppState>>,
+    mut draft: ResMut<ExpeditionDraft>,
+    mut tracking: ResMut<ExpeditionTracking>,
+    mut session: ResMut<RpgGameSession>,
+    map_resource: Res<MapResource>,
+    player_resource: Res<PlayerResource>,
+    resting_service: Res<RestingService>,
+    game_stats: Res<GameStatsResource>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    if *current_state.get() != RpgAppState::ExpeditionPlanning {
+        return;
+    }
+    let (Some(map), Some(player)) = (map_resource.overworld(), player_resource.get_player()) else {
+        return;
+    };
+    let start = *player.position();
+
+    if current_state.is_changed() {
+        draft.reset(start);
+    }
+
+    // Same screen directions as player movement: up is south on the map
+    let mut changed = false;
+    let (mut dx, mut dy) = (0, 0);
+    if keyboard.just_pressed(KeyCode::KeyW) || keyboard.just_pressed(KeyCode::ArrowUp) {
+        dy -= 1;
+    }
+    if keyboard.just_pressed(KeyCode::KeyS) || keyboard.just_pressed(KeyCode::ArrowDown) {
+        dy += 1;
+    }
+    if keyboard.just_pressed(KeyCode::KeyA) || keyboard.just_pressed(KeyCode::ArrowLeft) {
+        dx -= 1;
+    }
+    if keyboard.just_pressed(KeyCode::KeyD) || keyboard.just_pressed(KeyCode::ArrowRight) {
+        dx += 1;
+    }
+    if dx != 0 || dy != 0 {
+        draft.cursor = draft.cursor.offset(dx, dy, 0);
+    }
+
+    if keyboard.just_pressed(KeyCode::Enter) {
+        changed |= draft.add_waypoint();
+    }
+    if keyboard.just_pressed(KeyCode::Backspace) {
+        changed |= draft.remove_last_waypoint().is_some();
+    }
+
+    if changed {
+        let movement_per_rest =
+            resting_service.average_movement_after_rest(player.max_movement_points());
+        draft.replan(map, start, player.movement_points(), movement_per_rest);
+        if let Some(estimate) = draft.estimate.as_mut() {
+            estimate.food_needed = game_stats.modifiers.food_upkeep(estimate.food_needed);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyC) {
+        if let Some(plan) = draft.plan.take() {
+            game_log.log_message(
+                format!(
+                    "🧭 Expedition set: {} waypoints, {} tiles",
+                    plan.waypoints().len(),
+                    plan.route().len()
+                ),
+                GameLogType::System,
+            );
+            session.active_expedition = Some(plan);
+            *tracking = ExpeditionTracking::default();
+            next_state.set(RpgAppState::Exploration);
+            info!("🧭 Expedition plan committed");
+        }
+    }
+}
+
+/// Show the planner panel while planning and describe the draft
+fn update_planner_panel(
+    current_state: Res<State<RpgAppState>>,
+    draft: Res<ExpeditionDraft>,
+    mut panel_query: Query<&mut Visibility, With<ExpeditionPlannerPanel>>,
+    mut text_query: Query<&mut Text, With<ExpeditionPlannerText>>,
+) {
+    let planning = *current_state.get() == RpgAppState::ExpeditionPlanning;
+    if let Ok(mut visibility) = panel_query.single_mut() {
+        *visibility = if planning {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+    if !planning || !draft.is_changed() {
+        return;
+    }
+    if let Ok(mut text) = text_query.single_mut() {
+        **text = planner_panel_text(&draft);
+    }
+}
+
+fn planner_panel_text(draft: &ExpeditionDraft) -> String {
+    let mut lines = vec![
+        "EXPEDITION PLANNER".to_string(),
+        format!(
+            "CURSOR: [{}, {}] | WAYPOINTS: {}/{}",
+            draft.cursor.x,
+            draft.cursor.y,
+            draft.waypoints.len(),
+            EXPEDITION_MAX_WAYPOINTS
+        ),
+    ];
+    if let Some(estimate) = &draft.estimate {
+        lines.push(format!(
+            "ROUTE: {} tiles | {} MP | ~{} days ({} rests)",
+            estimate.tiles, estimate.movement_points, estimate.days, estimate.rest_stops
+        ));
+        lines.push(format!("RISK: {}", estimate.cumulative_risk));
+        lines.push(format!(
+            "BRING: {} Food, {} Energy",
+            estimate.food_needed, estimate.energy_needed
+        ));
+    }
+    if let Some(error) = &draft.error {
+        lines.push(format!("⚠ {}", error));
+    }
+    lines.push("WASD: Cursor | ENTER: Add | BACKSPACE: Remove | C: Commit | ESC: Back".to_string());
+    lines.join("\n")
+}
+
+/// Track waypoint progress and prompt when the player strays off the route
+fn expedition_progress_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_state: Res<State<RpgAppState>>,
+    mut tracking: ResMut<ExpeditionTracking>,
+    mut session: ResMut<RpgGameSession>,
+    player_resource: Res<PlayerResource>,
+    map_resource: Res<MapResource>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    // Interior coordinates have nothing to do with the surface route
+    if *current_state.get() != RpgAppState::Exploration
+        || session.active_expedition.is_none()
+        || map_resource.is_in_interior()
+    {
+        return;
+    }
+    let Some(position) = player_resource.player_position() else {
+        return;
+    };
+
+    if tracking.prompt_open {
+        if keyboard.just_pressed(KeyCode::KeyY) {
+            session.active_expedition = None;
+            *tracking = ExpeditionTracking::default();
+            game_log.log_message("🧭 Expedition abandoned".to_string(), GameLogType::System);
+        } else if keyboard.just_pressed(KeyCode::KeyN) {
+            tracking.prompt_open = false;
+            tracking.prompt_dismissed = true;
+        }
+        return;
+    }
+
+    if tracking.last_position == Some(position) {
+        return;
+    }
+    tracking.last_position = Some(position);
+
+    let Some(plan) = session.active_expedition.as_mut() else {
+        return;
+    };
+    if let Some(progress) = plan.record_position(position) {
+        if progress.is_final() {
+            game_log.log_message(
+                "🏁 Expedition complete: final waypoint reached".to_string(),
+                GameLogType::Discovery,
+            );
+            session.active_expedition = None;
+            return;
+        }
+        game_log.log_message(
+            format!(
+                "🧭 Waypoint {}/{} reached",
+                progress.reached, progress.total
+            ),
+            GameLogType::Movement,
+        );
+    }
+
+    if plan.is_off_course(position, EXPEDITION_DEVIATION_TILES) {
+        if !tracking.prompt_dismissed {
+            tracking.prompt_open = true;
+            game_log.log_message(
+                "⚠️ Off the planned route. Abandon the expedition? (Y/N)".to_string(),
+                GameLogType::Warning,
+            );
+        }
+    } else {
+        tracking.prompt_dismissed = false;
+    }
+}
+
+/// Show the distance to the next waypoint, or the abandon prompt
+fn update_expedition_hud(
+    session: Res<RpgGameSession>,
+    tracking: Res<ExpeditionTracking>,
+    player_resource: Res<PlayerResource>,
+    map_resource: Res<MapResource>,
+    mut hud_query: Query<(&mut Text, &mut TextColor), With<ExpeditionHudText>>,
+) {
+    let Ok((mut text, mut color)) = hud_query.single_mut() else {
+        return;
+    };
+
+    let surface_position = player_resource
+        .player_position()
+        .filter(|_| !map_resource.is_in_interior());
+    let (line, line_color) = match (&session.active_expedition, surface_position) {
+        _ if tracking.prompt_open => (
+            "OFF ROUTE - ABANDON EXPEDITION? (Y/N)".to_string(),
+            WARNING_TEXT,
+        ),
+        (Some(plan), Some(position)) => match plan.tiles_to_next_waypoint(position) {
+            Some(tiles) => (format!("NEXT WAYPOINT: {} tiles", tiles), ENERGY_COLOR),
+            None => (String::new(), ENERGY_COLOR),
+        },
+        _ => (String::new(), ENERGY_COLOR),
+    };
+
+    if **text != line {
+        **text = line;
+    }
+    if color.0 != line_color {
+        color.0 = line_color;
+    }
+}
+
+/// Keep waypoint markers in sync with the draft or the active expedition
+#[allow(clippy::too_many_arguments)]
+fn update_waypoint_markers(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    current_state: Res<State<RpgAppState>>,
+    draft: Res<ExpeditionDraft>,
+    session: Res<RpgGameSession>,
+    markers: Query<Entity, With<WaypointMarker>>,
+    mut rendered: Local<Vec<Position3D>>,
+) {
+    let wanted: Vec<Position3D> = if *current_state.get() == RpgAppState::ExpeditionPlanning {
+        let mut positions = draft.waypoints.clone();
+        positions.push(draft.cursor);
+        positions
+    } else {
+        session
+            .active_expedition
+            .as_ref()
+            .map(|plan| plan.remaining_waypoints().to_vec())
+            .unwrap_or_default()
+    };
+    if *rendered == wanted {
+        return;
+    }
+
+    for entity in markers.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let mesh = meshes.add(Mesh::from(Cylinder::new(0.25, 2.0)));
+    let material = materials.add(StandardMaterial {
+        base_color: ENERGY_COLOR,
+        emissive: LinearRgba::from(ENERGY_COLOR) * 0.5,
+        ..default()
+    });
+    for position in &wanted {
+        let world = crate::presentation::movement::tile_to_world_position(*position);
+        commands.spawn((
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::from_translation(Vec3::new(world.x, 1.2, world.z)),
+            WaypointMarker,
+            Name::new("WaypointMarker"),
+        ));
+    }
+    *rendered = wanted;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draft_caps_and_removes_waypoints() {
+        let mut draft = ExpeditionDraft::default();
+        draft.reset(Position3D::new(2, 3, 0));
+
+        for _ in 0..EXPEDITION_MAX_WAYPOINTS {
+            assert!(draft.add_waypoint());
+        }
+        assert!(!draft.add_waypoint());
+        assert_eq!(draft.waypoints.len(), EXPEDITION_MAX_WAYPOINTS);
+
+        assert_eq!(draft.remove_last_waypoint(), Some(Position3D::new(2, 3, 0)));
+        draft.reset(Position3D::origin());
+        assert!(draft.waypoints.is_empty());
+    }
+}
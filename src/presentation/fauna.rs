@@ -0,0 +1,339 @@
+//! Ambient Fauna - Small creatures wandering the visible surface
+//!
+//! Creatures are spawned sparsely on explored tiles in view whenever the
+//! player changes tile, hop to a neighbouring tile of their habitat every
+//! few seconds and vanish once their tile leaves sight. They use a direct
+//! lerp instead of SmoothMovement to stay cheap. Walking onto a creature's
+//! tile scatters it with a puff of particles. Fauna is purely cosmetic and
+//! draws from the volatile stream, leaving gameplay rolls untouched.
+
+use crate::domain::constants::{FAUNA_STEP_SECS, FAUNA_WANDER_INTERVAL_SECS};
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{FaunaCensus, FaunaKind, FaunaService};
+use crate::domain::value_objects::resources::ResourceType;
+use crate::domain::value_objects::terrain::TerrainType;
+use crate::domain::value_objects::{Position3D, TileCoordinate};
+use crate::infrastructure::bevy::resources::{MapResource, PlayerResource};
+use crate::infrastructure::random::streams::RngStreams;
+use crate::presentation::movement::tile_to_world_position;
+use crate::presentation::world_tick::{TickCursor, TickPhase, WorldTick, WorldTickSet};
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Height of a creature above its tile
+const FAUNA_HEIGHT: f32 = 0.45;
+
+/// Plugin for decorative wandering creatures
+pub struct FaunaPlugin;
+
+impl Plugin for FaunaPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RngStreams>().add_systems(
+            Update,
+            (
+                despawn_out_of_range_fauna,
+                scatter_fauna_system.in_set(WorldTickSet::Fauna),
+                spawn_fauna_system.in_set(WorldTickSet::Fauna),
+                wander_fauna_system,
+                update_scatter_particles,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// A wandering creature and its current hop
+#[derive(Component, Debug)]
+pub struct Fauna {
+    pub kind: FaunaKind,
+    /// Tile the creature lives on, or is hopping to
+    pub tile: TileCoordinate,
+    /// Terrain the creature never leaves
+    pub habitat: TerrainType,
+    from: Vec3,
+    to: Vec3,
+    /// Hop progress from 0.0 to 1.0
+    progress: f32,
+    wander: Timer,
+}
+
+impl Fauna {
+    /// Create a creature resting on `tile`
+    pub fn new(kind: FaunaKind, tile: TileCoordinate, habitat: TerrainType) -> Self {
+        let rest = fauna_world_position(tile);
+        Self {
+            kind,
+            tile,
+            habitat,
+            from: rest,
+            to: rest,
+            progress: 1.0,
+            wander: Timer::from_seconds(FAUNA_WANDER_INTERVAL_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+/// A puff left behind by scattered fauna
+#[derive(Component, Debug)]
+pub struct FaunaScatterParticle {
+    velocity: Vec3,
+    remaining: f32,
+}
+
+fn fauna_world_position(tile: TileCoordinate) -> Vec3 {
+    tile_to_world_position(Position3D::from(tile)) + Vec3::Y * FAUNA_HEIGHT
+}
+
+/// Body color of each kind
+fn fauna_color(kind: FaunaKind) -> Color {
+    match kind {
+        FaunaKind::Grazer => Color::srgb(0.75, 0.65, 0.45),
+        FaunaKind::Hopper => Color::srgb(0.55, 0.75, 0.35),
+        FaunaKind::CanopyGlider => Color::srgb(0.35, 0.55, 0.3),
+        FaunaKind::MossCrawler => Color::srgb(0.3, 0.45, 0.2),
+        FaunaKind::DustMite => Color::srgb(0.85, 0.7, 0.5),
+        FaunaKind::SandSkitter => Color::srgb(0.7, 0.5, 0.3),
+        FaunaKind::FrostMoth => Color::srgb(0.85, 0.9, 1.0),
+        FaunaKind::SnowBurrower => Color::srgb(0.95, 0.95, 0.95),
+        FaunaKind::ShardBeetle => Color::srgb(0.6, 0.4, 0.9),
+        FaunaKind::PrismMoth => Color::srgb(0.8, 0.6, 1.0),
+        FaunaKind::BogCroaker => Color::srgb(0.35, 0.4, 0.2),
+        FaunaKind::MireFly => Color::srgb(0.7, 0.8, 0.3),
+        FaunaKind::GlowWisp => Color::srgb(0.5, 1.0, 0.9),
+        FaunaKind::PhaseFlicker => Color::srgb(1.0, 0.4, 0.8),
+    }
+}
+
+/// Remove creatures whose tile is out of sight, or all of them inside ruins
+fn despawn_out_of_range_fauna(
+    mut commands: Commands,
+    player_resource: Res<PlayerResource>,
+    map_resource: Res<MapResource>,
+    fauna_query: Query<(Entity, &Fauna)>,
+) {
+    let player = player_resource.player_position();
+    let service = FaunaService::new();
+    for (entity, fauna) in fauna_query.iter() {
+        let in_range = player.is_some_and(|position| service.is_in_range(position, fauna.tile));
+        if map_resource.is_in_interior() || !in_range {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Scatter the creatures on the tile the player just stepped onto
+#[allow(clippy::too_many_arguments)]
+fn scatter_fauna_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut game_log: ResMut<GameLogService>,
+    fauna_query: Query<(Entity, &Fauna, &Transform)>,
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    rng_streams: ResMut<RngStreams>,
+) {
+    let Some(position) = cursor
+        .take(ticks.read(), TickPhase::AfterPlayerMove)
+        .last()
+        .and_then(|tick| tick.position)
+    else {
+        return;
+    };
+
+    let tile = TileCoordinate::from(position);
+    let service = FaunaService::new();
+    let mut rng = rng_streams.volatile();
+    for (entity, fauna, transform) in fauna_query.iter().filter(|(_, f, _)| f.tile == tile) {
+        commands.entity(entity).despawn();
+
+        let mesh = meshes.add(Mesh::from(Sphere::new(0.06)));
+        let material = materials.add(StandardMaterial {
+            base_color: fauna_color(fauna.kind),
+            unlit: true,
+            ..default()
+        });
+        for _ in 0..6 {
+            let velocity = Vec3::new(
+                rng.gen_range(-1.5..1.5),
+                rng.gen_range(1.0..2.5),
+                rng.gen_range(-1.5..1.5),
+            );
+            commands.spawn((
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(material.clone()),
+                Transform::from_translation(transform.translation),
+                FaunaScatterParticle {
+                    velocity,
+                    remaining: 0.6,
+                },
+            ));
+        }
+
+        game_log.log_message(
+            format!("🐾 A group of {} scatters", fauna.kind.plural_name()),
+            GameLogType::Narrative,
+        );
+        if service.scatter_drops_organics(&mut rng)
+            && player_resource
+                .apply_resource_delta(ResourceType::Organics, 1)
+                .is_ok()
+        {
+            game_log.log_message(
+                "🌿 They left something behind: +1 Organics".to_string(),
+                GameLogType::Discovery,
+            );
+        }
+    }
+}
+
+/// Populate visible habitat whenever the player changes tile
+#[allow(clippy::too_many_arguments)]
+fn spawn_fauna_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    current_state: Res<State<RpgAppState>>,
+    player_resource: Res<PlayerResource>,
+    map_resource: Res<MapResource>,
+    fauna_query: Query<&Fauna>,
+    mut last_position: Local<Option<Position3D>>,
+    rng_streams: ResMut<RngStreams>,
+) {
+    if *current_state.get() != RpgAppState::Exploration || map_resource.is_in_interior() {
+        return;
+    }
+    let Some(position) = player_resource.player_position() else {
+        return;
+    };
+    if *last_position == Some(position) {
+        return;
+    }
+    *last_position = Some(position);
+    let Some(map) = map_resource.overworld() else {
+        return;
+    };
+
+    let mut census = FaunaCensus::new();
+    let mut occupied = HashSet::new();
+    for fauna in fauna_query.iter() {
+        census.add(fauna.habitat);
+        occupied.insert(fauna.tile);
+    }
+
+    let mut rng = rng_streams.volatile();
+    let spawns = FaunaService::new().plan_spawns(map, position, &occupied, &mut census, &mut rng);
+    for (tile, kind) in spawns {
+        let Some(habitat) = map.get_tile(&tile).map(|t| t.terrain_type) else {
+            continue;
+        };
+        let fauna = Fauna::new(kind, tile, habitat);
+        commands.spawn((
+            Mesh3d(meshes.add(Mesh::from(Sphere::new(0.15)))),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: fauna_color(kind),
+                ..default()
+            })),
+            Transform::from_translation(fauna.to),
+            fauna,
+            Name::new("Fauna"),
+        ));
+    }
+}
+
+/// Hop creatures to a neighbouring habitat tile every few seconds
+fn wander_fauna_system(
+    time: Res<Time>,
+    map_resource: Res<MapResource>,
+    mut fauna_query: Query<(&mut Fauna, &mut Transform)>,
+    rng_streams: ResMut<RngStreams>,
+) {
+    let Some(map) = map_resource.overworld() else {
+        return;
+    };
+    let service = FaunaService::new();
+    let mut rng = rng_streams.volatile();
+    for (mut fauna, mut transform) in fauna_query.iter_mut() {
+        if fauna.progress < 1.0 {
+            fauna.progress = (fauna.progress + time.delta_secs() / FAUNA_STEP_SECS).min(1.0);
+            // Small hop arc on top of the straight lerp
+            let arc = (fauna.progress * std::f32::consts::PI).sin() * 0.3;
+            transform.translation = fauna.from.lerp(fauna.to, fauna.progress) + Vec3::Y * arc;
+            continue;
+        }
+
+        fauna.wander.tick(time.delta());
+        if !fauna.wander.just_finished() {
+            continue;
+        }
+        if let Some(target) = service.wander_target(map, fauna.tile, fauna.habitat, &mut rng) {
+            fauna.from = transform.translation;
+            fauna.to = fauna_world_position(target);
+            fauna.tile = target;
+            fauna.progress = 0.0;
+        }
+    }
+}
+
+/// Move scatter particles and remove them once faded
+fn update_scatter_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut particles: Query<(Entity, &mut FaunaScatterParticle, &mut Transform)>,
+) {
+    let delta = time.delta_secs();
+    for (entity, mut particle, mut transform) in particles.iter_mut() {
+        particle.remaining -= delta;
+        if particle.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        particle.velocity.y -= 6.0 * delta;
+        transform.translation += particle.velocity * delta;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn fauna_out_of_sight_are_despawned() {
+        let mut world = World::new();
+        let mut player_resource = PlayerResource::new();
+        player_resource
+            .create_player(
+                "fauna_player".to_string(),
+                "Fauna Player".to_string(),
+                Position3D::origin(),
+                crate::domain::PlayerStats::new(10, 10, 10, 10, 10, 10).unwrap(),
+            )
+            .unwrap();
+        world.insert_resource(player_resource);
+        world.insert_resource(MapResource::new());
+
+        let near = world
+            .spawn(Fauna::new(
+                FaunaKind::Grazer,
+                TileCoordinate::new(1, 1, 0),
+                TerrainType::Plains,
+            ))
+            .id();
+        let far = world
+            .spawn(Fauna::new(
+                FaunaKind::Grazer,
+                TileCoordinate::new(9, 0, 0),
+                TerrainType::Plains,
+            ))
+            .id();
+
+        world.run_system_once(despawn_out_of_range_fauna).unwrap();
+
+        assert!(world.get_entity(near).is_ok());
+        assert!(world.get_entity(far).is_err());
+    }
+}
@@ -0,0 +1,499 @@
+//! Field Trade - Haggling with traders met while exploring
+//!
+//! A trade event holds movement until the trader is dealt with. The trader
+//! opens with a bundle built from the terrain and the player's cargo, and
+//! the trade panel shows it: 1 takes it, 2 counters, 3 walks away and 4
+//! settles on the event roll the way trade events used to. Blitz runs
+//! always settle on the roll. On a deal the asked cargo is paid in full
+//! before the goods come in, or nothing moves at all. A haggled deal with
+//! a faction's trader counts as trade with that faction.
+
+use crate::application::use_cases::{Haggle, HaggleOffer, HaggleState, RngDice};
+use crate::domain::constants::{PANEL_BACKGROUND, PRIMARY_TEXT};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{Faction, GearItem, ReputationCause, ReputationTier};
+use crate::domain::value_objects::{Position3D, ResourceCollection, TileCoordinate};
+use crate::domain::DomainError;
+use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource, PlayerResource};
+use crate::infrastructure::random::streams::{RngStreams, LOOT, TRADE};
+use crate::presentation::game_state::RpgGameSession;
+use crate::presentation::reputation::{shift_reputation, ReputationChangedEvent};
+use bevy::prelude::*;
+
+/// Key that takes the trader's offer
+pub const ACCEPT_KEY: KeyCode = KeyCode::Digit1;
+
+/// Key that counters the trader's offer
+pub const COUNTER_KEY: KeyCode = KeyCode::Digit2;
+
+/// Key that leaves the trader without a deal
+pub const WALK_AWAY_KEY: KeyCode = KeyCode::Digit3;
+
+/// Key that settles the trade on the event roll
+pub const AUTO_RESOLVE_KEY: KeyCode = KeyCode::Digit4;
+
+/// Plugin for trade events and their haggle
+pub struct FieldTradePlugin;
+
+impl Plugin for FieldTradePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TraderContact>()
+            .init_resource::<RngStreams>()
+            .add_systems(Startup, setup_trader_panel)
+            .add_systems(
+                Update,
+                (
+                    open_haggle_system,
+                    haggle_choice_system,
+                    update_trader_panel,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// A trader waiting for the player to deal
+#[derive(Resource, Debug, Clone, Default)]
+pub struct TraderContact {
+    pending: Option<PendingTrade>,
+}
+
+/// A trade event waiting to be settled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingTrade {
+    /// Tile the trader was met on
+    pub position: Position3D,
+    /// Faction the trader deals for, if any
+    pub faction: Option<Faction>,
+    /// Final roll of the trade event
+    pub roll: u8,
+    /// The haggle, once the trader has made an offer
+    pub haggle: Option<Haggle>,
+}
+
+impl TraderContact {
+    /// Hold movement until the trader met on `position` is dealt with
+    pub fn raise(&mut self, position: Position3D, faction: Option<Faction>, roll: u8) {
+        self.pending = Some(PendingTrade {
+            position,
+            faction,
+            roll,
+            haggle: None,
+        });
+    }
+
+    /// Check if movement is held for the trader
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// The trade waiting to be settled
+    pub fn pending(&self) -> Option<PendingTrade> {
+        self.pending
+    }
+
+    /// The trader is dealt with
+    pub fn settle(&mut self) {
+        self.pending = None;
+    }
+}
+
+/// Marker for the trader panel
+#[derive(Component)]
+pub struct TraderPanel;
+
+/// Marker for the trader panel text
+#[derive(Component)]
+pub struct TraderText;
+
+fn setup_trader_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(30.0),
+                top: Val::Px(120.0),
+                width: Val::Px(420.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(PANEL_BACKGROUND),
+            Visibility::Hidden,
+            TraderPanel,
+            Name::new("TraderPanel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                TraderText,
+            ));
+        });
+}
+
+/// Move the goods of a deal: the asked cargo is paid first, all of it or
+/// none, and only then do the goods come in
+///
+/// Payouts settled on the event roll are scaled by the event reward
+/// modifier, as trade events always were. Returns the goods received.
+pub fn close_deal(
+    offer: &HaggleOffer,
+    auto_resolved: bool,
+    player_resource: &mut PlayerResource,
+    game_stats: &mut GameStatsResource,
+) -> Result<ResourceCollection, DomainError> {
+    player_resource.try_pay_resources(&offer.cost())?;
+    let mut goods = offer.goods();
+    if auto_resolved {
+        let (resource_type, amount) = offer.offered;
+        goods.set_amount(resource_type, game_stats.modifiers.event_reward(amount));
+        game_stats.record_experience_gain(amount / 2);
+    }
+    player_resource.add_resources(&goods);
+    Ok(goods)
+}
+
+/// Have the trader make an offer; blitz runs settle on the roll at once
+fn open_haggle_system(
+    mut contact: ResMut<TraderContact>,
+    map_resource: Res<MapResource>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut game_stats: ResMut<GameStatsResource>,
+    session: Res<RpgGameSession>,
+    mut game_log: ResMut<GameLogService>,
+    rng_streams: ResMut<RngStreams>,
+) {
+    let Some(pending) = contact.pending.filter(|pending| pending.haggle.is_none()) else {
+        return;
+    };
+    let Some(player) = player_resource.get_player() else {
+        return;
+    };
+
+    let terrain = map_resource
+        .current_map()
+        .and_then(|map| map.get_tile(&TileCoordinate::from(pending.position)))
+        .map(|tile| tile.terrain_type);
+    let tier = pending
+        .faction
+        .map(|faction| session.reputation.tier(faction))
+        .unwrap_or(ReputationTier::Neutral);
+    let offer = HaggleOffer::generate(
+        terrain,
+        player.resources(),
+        tier,
+        &mut RngDice(rng_streams.stream(TRADE)),
+    );
+
+    match offer.filter(|_| !game_stats.blitz) {
+        Some(offer) => {
+            contact.pending = Some(PendingTrade {
+                haggle: Some(Haggle::new(&player.derived_stats(), pending.roll, offer)),
+                ..pending
+            });
+            game_log.log_message(
+                format!(
+                    "🤝 The trader offers {} - 1 to accept, 2 to counter, 3 to walk away",
+                    offer.describe()
+                ),
+                GameLogType::Event,
+            );
+        }
+        None => {
+            contact.settle();
+            settle_on_roll(
+                pending.roll,
+                &mut player_resource,
+                &mut game_stats,
+                &mut game_log,
+            );
+        }
+    }
+}
+
+/// Settle a trade on its event roll, as trade events paid before haggling
+fn settle_on_roll(
+    roll: u8,
+    player_resource: &mut PlayerResource,
+    game_stats: &mut GameStatsResource,
+    game_log: &mut GameLogService,
+) {
+    let Some(offer) = HaggleOffer::legacy(roll) else {
+        game_log.log_message(
+            "💼 The trader finds nothing worth a deal".to_string(),
+            GameLogType::Event,
+        );
+        return;
+    };
+    match close_deal(&offer, true, player_resource, game_stats) {
+        Ok(goods) => game_log.log_message(
+            format!("💾 Quick trade: {}", crate::format_resource_summary(&goods)),
+            GameLogType::Resources,
+        ),
+        Err(e) => game_log.log_message(format!("{}", e), GameLogType::Warning),
+    }
+}
+
+/// Take, counter, leave or skip the trader's offer
+#[allow(clippy::too_many_arguments)]
+fn haggle_choice_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut contact: ResMut<TraderContact>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut game_stats: ResMut<GameStatsResource>,
+    mut session: ResMut<RpgGameSession>,
+    mut game_log: ResMut<GameLogService>,
+    mut reputation_events: EventWriter<ReputationChangedEvent>,
+    rng_streams: ResMut<RngStreams>,
+) {
+    let Some(pending) = contact.pending else {
+        return;
+    };
+    let Some(mut haggle) = pending.haggle else {
+        return;
+    };
+
+    if keyboard.just_pressed(ACCEPT_KEY) {
+        let offer = *haggle.offer();
+        if let Err(e) = close_deal(&offer, false, &mut player_resource, &mut game_stats) {
+            game_log.log_message(format!("{}", e), GameLogType::Warning);
+            return;
+        }
+        contact.settle();
+        game_log.log_message(
+            format!("🤝 Deal: {}", offer.describe()),
+            GameLogType::Resources,
+        );
+        if offer.bonus_item {
+            let item = GearItem::roll(rng_streams.stream(LOOT).next_raw());
+            let description = format!("{} ({})", item.name, item.describe());
+            if player_resource.stow_gear(item).is_ok() {
+                game_log.log_message(
+                    format!("🧰 The trader throws in: {}", description),
+                    GameLogType::Discovery,
+                );
+            }
+        }
+        if let Some(faction) = pending.faction {
+            shift_reputation(
+                &mut session,
+                ReputationCause::Trade {
+                    faction,
+                    volume: offer.offered.1,
+                },
+                &mut reputation_events,
+            );
+        }
+    } else if keyboard.just_pressed(COUNTER_KEY) {
+        if haggle.counters_left() == 0 {
+            game_log.log_message(
+                "The trader takes no more counters - accept (1) or walk away (3)".to_string(),
+                GameLogType::Warning,
+            );
+            return;
+        }
+        let counter = match haggle.counter(&mut RngDice(rng_streams.stream(TRADE))) {
+            Ok(counter) => counter,
+            Err(e) => {
+                warn!("Failed to counter: {}", e);
+                return;
+            }
+        };
+        game_log.log_message(
+            format!("🗣️ Counter: {}", counter.describe()),
+            GameLogType::Event,
+        );
+        if haggle.state() == HaggleState::CalledOff {
+            contact.settle();
+            game_log.log_message(
+                "💼 The trader packs up and leaves".to_string(),
+                GameLogType::Event,
+            );
+        } else if let Some(pending) = contact.pending.as_mut() {
+            pending.haggle = Some(haggle);
+        }
+    } else if keyboard.just_pressed(WALK_AWAY_KEY) {
+        contact.settle();
+        game_log.log_message(
+            "💼 You leave the trader without a deal".to_string(),
+            GameLogType::Event,
+        );
+    } else if keyboard.just_pressed(AUTO_RESOLVE_KEY) {
+        contact.settle();
+        settle_on_roll(
+            pending.roll,
+            &mut player_resource,
+            &mut game_stats,
+            &mut game_log,
+        );
+    }
+}
+
+/// Show the trader's offer while a haggle is open
+fn update_trader_panel(
+    contact: Res<TraderContact>,
+    mut panels: Query<&mut Visibility, With<TraderPanel>>,
+    mut texts: Query<&mut Text, With<TraderText>>,
+) {
+    if !contact.is_changed() {
+        return;
+    }
+    let haggle = contact
+        .pending()
+        .and_then(|pending| pending.haggle.map(|haggle| (pending.faction, haggle)));
+    for mut visibility in panels.iter_mut() {
+        *visibility = if haggle.is_some() {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+    if let (Some((faction, haggle)), Ok(mut text)) = (haggle, texts.single_mut()) {
+        text.0 = trader_text(faction, &haggle);
+    }
+}
+
+/// Text of the trader panel: the offer, the counters so far and the keys
+fn trader_text(faction: Option<Faction>, haggle: &Haggle) -> String {
+    let mut lines = vec![
+        match faction {
+            Some(faction) => format!("🤝 TRADER - {}", faction.name()),
+            None => "🤝 TRADER".to_string(),
+        },
+        String::new(),
+        format!("Offer: {}", haggle.offer().describe()),
+    ];
+    for (index, counter) in haggle.counters().enumerate() {
+        lines.push(format!("Counter {}: {}", index + 1, counter.describe()));
+    }
+    lines.push(String::new());
+    lines.push("1: Accept".to_string());
+    if haggle.counters_left() > 0 {
+        lines.push(format!(
+            "2: Counter (Charisma DC {}, {} left)",
+            haggle.next_difficulty(),
+            haggle.counters_left()
+        ));
+    }
+    lines.push("3: Walk away".to_string());
+    lines.push("4: Settle on the event roll".to_string());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Base, Player};
+    use crate::domain::value_objects::{EntityId, ResourceType};
+    use crate::domain::PlayerStats;
+    use bevy::ecs::system::RunSystemOnce;
+
+    fn world_with_trader() -> World {
+        let mut world = World::new();
+        let mut player_resource = PlayerResource::new();
+        player_resource
+            .create_player(
+                "trader_player".to_string(),
+                "Trader Player".to_string(),
+                Position3D::origin(),
+                PlayerStats::new(10, 10, 10, 10, 10, 10).unwrap(),
+            )
+            .unwrap();
+        world.insert_resource(player_resource);
+        world.insert_resource(GameStatsResource::new());
+        world.insert_resource(GameLogService::new());
+        world.insert_resource(RpgGameSession::new(
+            Player::create_new_character("Trader Player".to_string(), Position3D::origin())
+                .unwrap(),
+            Base::new(
+                EntityId::generate(),
+                "Outpost".to_string(),
+                Position3D::origin(),
+            )
+            .unwrap(),
+        ));
+        world.init_resource::<Events<ReputationChangedEvent>>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<RngStreams>();
+
+        let offer = HaggleOffer {
+            wanted: Some((ResourceType::Metal, 10)),
+            offered: (ResourceType::Energy, 5),
+            bonus_item: false,
+        };
+        let mut contact = TraderContact::default();
+        contact.raise(Position3D::new(2, 1, 0), Some(Faction::FreeTraders), 15);
+        contact.pending.as_mut().unwrap().haggle =
+            Some(Haggle::new(&PlayerStats::starting_stats(), 15, offer));
+        world.insert_resource(contact);
+        world
+    }
+
+    fn cargo(world: &World) -> ResourceCollection {
+        world
+            .resource::<PlayerResource>()
+            .get_player()
+            .unwrap()
+            .resources()
+            .clone()
+    }
+
+    fn press(world: &mut World, key: KeyCode) {
+        let mut keyboard = world.resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.release_all();
+        keyboard.clear();
+        keyboard.press(key);
+        world.run_system_once(haggle_choice_system).unwrap();
+    }
+
+    #[test]
+    fn walking_away_or_failing_to_pay_leaves_the_cargo_untouched() {
+        let mut world = world_with_trader();
+        let before = cargo(&world);
+        press(&mut world, WALK_AWAY_KEY);
+        assert!(!world.resource::<TraderContact>().is_pending());
+        assert_eq!(cargo(&world), before);
+
+        // Short of the asked Metal, accepting moves nothing and keeps the trader
+        let mut world = world_with_trader();
+        let held = cargo(&world).get_amount(ResourceType::Metal);
+        let mut spend = ResourceCollection::new();
+        spend.set_amount(ResourceType::Metal, held.saturating_sub(5));
+        world
+            .resource_mut::<PlayerResource>()
+            .try_pay_resources(&spend)
+            .unwrap();
+        let before = cargo(&world);
+        press(&mut world, ACCEPT_KEY);
+        assert!(world.resource::<TraderContact>().is_pending());
+        assert_eq!(cargo(&world), before);
+
+        // With the Metal in the hold, both sides of the deal go through
+        let mut world = world_with_trader();
+        let before = cargo(&world);
+        press(&mut world, ACCEPT_KEY);
+        assert!(!world.resource::<TraderContact>().is_pending());
+        let after = cargo(&world);
+        assert_eq!(
+            after.get_amount(ResourceType::Metal) + 10,
+            before.get_amount(ResourceType::Metal)
+        );
+        assert_eq!(
+            after.get_amount(ResourceType::Energy),
+            before.get_amount(ResourceType::Energy) + 5
+        );
+        assert!(
+            world
+                .resource::<RpgGameSession>()
+                .reputation
+                .score(Faction::FreeTraders)
+                > 0
+        );
+    }
+}
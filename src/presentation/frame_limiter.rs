@@ -0,0 +1,342 @@
+//! Frame Limiter - Idle-safe update rate while the page is hidden
+//!
+//! Browsers report when the game's tab is hidden through the
+//! `visibilitychange` event. Instead of running flat out or freezing, the
+//! game follows a background policy from the settings: `Throttle` drops to a
+//! low reactive update rate and keeps only the ambient bed playing, `Pause`
+//! stops virtual time and all audio, and `FullSpeed` changes nothing. While
+//! throttled, frames keep their real delta so timers stay in step with the
+//! wall clock. The page can change the policy through
+//! `set_background_policy`.
+
+use crate::domain::constants::{
+    BACKGROUND_MAX_FRAME_DELTA_SECS, BACKGROUND_THROTTLED_UPDATES_PER_SEC,
+};
+use crate::infrastructure::settings::BackgroundSettings;
+use crate::infrastructure::web::events::WebEventListener;
+use crate::presentation::audio_integration::{AudioScope, GlobalAudioSettings};
+use bevy::prelude::*;
+use bevy::winit::{UpdateMode, WinitSettings};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Largest frame delta Bevy's virtual clock accepts by default
+const DEFAULT_MAX_FRAME_DELTA: Duration = Duration::from_millis(250);
+
+/// Whether the page was visible at the last `visibilitychange`
+static PAGE_VISIBLE: AtomicBool = AtomicBool::new(true);
+
+/// Policy requested from the page, applied on the next frame
+static REQUESTED_POLICY: Mutex<Option<BackgroundPolicy>> = Mutex::new(None);
+
+/// Record a visibility change reported by the browser
+pub fn report_page_visibility(visible: bool) {
+    PAGE_VISIBLE.store(visible, Ordering::Relaxed);
+}
+
+/// Queue a policy change from outside the ECS, e.g. the page's controls
+pub fn request_background_policy(policy: BackgroundPolicy) {
+    if let Ok(mut requested) = REQUESTED_POLICY.lock() {
+        *requested = Some(policy);
+    }
+}
+
+/// What the game does while its page is hidden
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BackgroundPolicy {
+    /// Stop virtual time and all audio
+    Pause,
+    /// Update at a low rate with only the ambient bed playing
+    #[default]
+    Throttle,
+    /// Keep running as if the page were visible
+    FullSpeed,
+}
+
+impl BackgroundPolicy {
+    /// Parse a policy name as passed from the page, ignoring case
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "pause" => Some(BackgroundPolicy::Pause),
+            "throttle" => Some(BackgroundPolicy::Throttle),
+            "fullspeed" | "full_speed" | "full-speed" => Some(BackgroundPolicy::FullSpeed),
+            _ => None,
+        }
+    }
+
+    /// Update pace for the page's visibility
+    pub fn pace(&self, visible: bool) -> FramePace {
+        if visible {
+            return FramePace::Continuous;
+        }
+        match self {
+            BackgroundPolicy::Pause => FramePace::Paused,
+            BackgroundPolicy::Throttle => FramePace::Throttled(throttled_interval()),
+            BackgroundPolicy::FullSpeed => FramePace::Continuous,
+        }
+    }
+
+    /// Audio allowed for the page's visibility
+    pub fn audio(&self, visible: bool) -> AudioScope {
+        match self.pace(visible) {
+            FramePace::Continuous => AudioScope::Full,
+            FramePace::Throttled(_) => AudioScope::AmbientOnly,
+            FramePace::Paused => AudioScope::Silent,
+        }
+    }
+}
+
+/// Time between updates while throttled
+pub fn throttled_interval() -> Duration {
+    Duration::from_secs(1) / BACKGROUND_THROTTLED_UPDATES_PER_SEC
+}
+
+/// How often the app updates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FramePace {
+    /// Every display frame
+    Continuous,
+    /// At most once per interval, or sooner on input
+    Throttled(Duration),
+    /// Only on window events, with virtual time stopped
+    Paused,
+}
+
+impl FramePace {
+    /// Largest frame delta virtual time accepts at this pace
+    pub fn max_frame_delta(&self) -> Duration {
+        match self {
+            FramePace::Throttled(_) => Duration::from_secs(BACKGROUND_MAX_FRAME_DELTA_SECS),
+            FramePace::Continuous | FramePace::Paused => DEFAULT_MAX_FRAME_DELTA,
+        }
+    }
+
+    /// Winit update mode for this pace
+    pub fn update_mode(&self) -> UpdateMode {
+        match self {
+            FramePace::Continuous => UpdateMode::Continuous,
+            FramePace::Throttled(interval) => UpdateMode::reactive_low_power(*interval),
+            FramePace::Paused => {
+                UpdateMode::reactive_low_power(Duration::from_secs(BACKGROUND_MAX_FRAME_DELTA_SECS))
+            }
+        }
+    }
+}
+
+/// Visibility and policy the app is currently running with
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct FrameLimiter {
+    visible: bool,
+    policy: BackgroundPolicy,
+}
+
+impl Default for FrameLimiter {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            policy: BackgroundPolicy::default(),
+        }
+    }
+}
+
+impl FrameLimiter {
+    /// Check if the page is visible
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Policy applied while hidden
+    pub fn policy(&self) -> BackgroundPolicy {
+        self.policy
+    }
+
+    /// Current update pace
+    pub fn pace(&self) -> FramePace {
+        self.policy.pace(self.visible)
+    }
+
+    /// Current audio scope
+    pub fn audio(&self) -> AudioScope {
+        self.policy.audio(self.visible)
+    }
+
+    /// Take on a new visibility and policy, returning whether either changed
+    pub fn update(&mut self, visible: bool, policy: BackgroundPolicy) -> bool {
+        let changed = self.visible != visible || self.policy != policy;
+        self.visible = visible;
+        self.policy = policy;
+        changed
+    }
+}
+
+/// Keeps the `visibilitychange` listener alive
+struct VisibilityListener {
+    _listener: WebEventListener,
+}
+
+/// Plugin switching update rate and audio with the page's visibility
+pub struct FrameLimiterPlugin;
+
+impl Plugin for FrameLimiterPlugin {
+    fn build(&self, app: &mut App) {
+        match WebEventListener::on_visibility_change(report_page_visibility) {
+            Ok(listener) => {
+                app.insert_non_send_resource(VisibilityListener {
+                    _listener: listener,
+                });
+            }
+            Err(error) => warn!("Page visibility is not tracked: {}", error),
+        }
+
+        app.init_resource::<FrameLimiter>()
+            .init_resource::<BackgroundSettings>()
+            .add_systems(PreUpdate, apply_frame_limiter);
+    }
+}
+
+/// Apply visibility and policy changes to the update mode, clock and audio
+fn apply_frame_limiter(
+    mut limiter: ResMut<FrameLimiter>,
+    mut settings: ResMut<BackgroundSettings>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut audio: ResMut<GlobalAudioSettings>,
+    winit: Option<ResMut<WinitSettings>>,
+) {
+    let requested = REQUESTED_POLICY
+        .lock()
+        .ok()
+        .and_then(|mut requested| requested.take());
+    if let Some(policy) = requested {
+        if settings.policy != policy {
+            settings.policy = policy;
+            info!("Background policy set to {:?}", policy);
+        }
+    }
+
+    let visible = PAGE_VISIBLE.load(Ordering::Relaxed);
+    let was_paused = limiter.pace() == FramePace::Paused;
+    if !limiter.update(visible, settings.policy) {
+        return;
+    }
+
+    let pace = limiter.pace();
+    virtual_time.set_max_delta(pace.max_frame_delta());
+    if pace == FramePace::Paused {
+        virtual_time.pause();
+    } else if was_paused {
+        virtual_time.unpause();
+    }
+
+    let scope = limiter.audio();
+    if audio.scope != scope {
+        audio.scope = scope;
+    }
+
+    if let Some(mut winit) = winit {
+        let mode = pace.update_mode();
+        winit.focused_mode = mode;
+        winit.unfocused_mode = mode;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presentation::audio_integration::AudioCategory;
+
+    #[test]
+    fn policies_switch_pace_and_audio_only_while_hidden() {
+        for policy in [
+            BackgroundPolicy::Pause,
+            BackgroundPolicy::Throttle,
+            BackgroundPolicy::FullSpeed,
+        ] {
+            assert_eq!(policy.pace(true), FramePace::Continuous);
+            assert_eq!(policy.audio(true), AudioScope::Full);
+        }
+
+        assert_eq!(BackgroundPolicy::Pause.pace(false), FramePace::Paused);
+        assert_eq!(BackgroundPolicy::Pause.audio(false), AudioScope::Silent);
+        assert_eq!(
+            BackgroundPolicy::Throttle.pace(false),
+            FramePace::Throttled(Duration::from_millis(500))
+        );
+        let hidden = BackgroundPolicy::Throttle.audio(false);
+        assert!(hidden.allows(AudioCategory::Ambient));
+        assert!(!hidden.allows(AudioCategory::Music));
+        assert!(!hidden.allows(AudioCategory::Sfx));
+        assert_eq!(
+            BackgroundPolicy::FullSpeed.pace(false),
+            FramePace::Continuous
+        );
+
+        let mut limiter = FrameLimiter::default();
+        assert!(!limiter.update(true, BackgroundPolicy::Throttle));
+        assert!(limiter.update(false, BackgroundPolicy::Throttle));
+        assert!(limiter.update(false, BackgroundPolicy::Pause));
+        assert_eq!(limiter.pace(), FramePace::Paused);
+        assert!(limiter.update(true, BackgroundPolicy::Pause));
+        assert_eq!(limiter.audio(), AudioScope::Full);
+
+        assert_eq!(
+            BackgroundPolicy::parse(" Full-Speed "),
+            Some(BackgroundPolicy::FullSpeed)
+        );
+        assert_eq!(BackgroundPolicy::parse("sleep"), None);
+    }
+
+    /// Wall clock feeding virtual time, the way the time plugin does
+    struct Frames {
+        real: Time<Real>,
+        current: Time,
+    }
+
+    impl Frames {
+        fn new() -> Self {
+            let mut real = Time::<Real>::default();
+            real.update_with_duration(Duration::ZERO);
+            Self {
+                real,
+                current: Time::default(),
+            }
+        }
+
+        fn advance(&mut self, time: &mut Time<Virtual>, raw_delta: Duration) {
+            self.real.update_with_duration(raw_delta);
+            bevy::time::update_virtual_time(&mut self.current, time, &self.real);
+        }
+    }
+
+    #[test]
+    fn throttled_frames_keep_timers_on_the_wall_clock() {
+        let pace = BackgroundPolicy::Throttle.pace(false);
+        let FramePace::Throttled(interval) = pace else {
+            panic!("hidden throttle policy should throttle");
+        };
+        let mut time = Time::<Virtual>::default();
+        time.set_max_delta(pace.max_frame_delta());
+        let mut timer = Timer::from_seconds(1.0, TimerMode::Repeating);
+
+        // Ten seconds of hidden frames, then the browser stalls the tab
+        let mut frames_clock = Frames::new();
+        let mut wall_clock = Duration::ZERO;
+        let mut finished = 0;
+        let frames = std::iter::repeat_n(interval, 20).chain([Duration::from_secs(30)]);
+        for raw_delta in frames {
+            frames_clock.advance(&mut time, raw_delta);
+            timer.tick(time.delta());
+            finished += timer.times_finished_this_tick();
+            wall_clock += raw_delta;
+        }
+        assert_eq!(time.elapsed(), wall_clock);
+        assert_eq!(finished, 40);
+
+        // At the default cap the same frames would lose most of the time
+        let mut capped = Time::<Virtual>::default();
+        capped.set_max_delta(FramePace::Continuous.max_frame_delta());
+        Frames::new().advance(&mut capped, interval);
+        assert!(capped.delta() < interval);
+    }
+}
@@ -4,11 +4,16 @@
 //! into properly formatted log messages for the UI display. It acts as a bridge
 //! between the domain services and the presentation layer.
 
+use crate::domain::constants::{SURVEY_REPORT_RADIUS, SURVEY_REPORT_TILES};
 use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::map_service::{MapAnalysis, MapRegion, MapService};
 use crate::domain::services::resting_service::{NightEventType, RestCycleResult, RestOutcome};
 use crate::domain::services::tile_movement::{MovementDiceResult, MovementResult};
 use crate::domain::value_objects::{Position3D, ResourceType};
-use crate::infrastructure::bevy::resources::{GameStatsResource, PlayerResource};
+use crate::infrastructure::bevy::resources::{
+    GameStatsResource, MapResource, PlayerChange, PlayerResource,
+};
+use crate::presentation::world_tick::WorldTickSet;
 use bevy::prelude::*;
 
 /// Plugin for game event logging functionality
@@ -22,14 +27,18 @@ impl Plugin for GameEventLoggerPlugin {
             .add_event::<ResourceChangedEvent>()
             .add_event::<DiscoveryEvent>()
             .add_event::<GameSystemEvent>()
+            .add_event::<PlayerChangedEvent>()
             .add_systems(
                 Update,
                 (
+                    forward_player_changes.after(WorldTickSet::Survival),
+                    log_reduced_grants,
                     log_movement_events,
                     log_rest_events,
                     log_resource_events,
                     log_discovery_events,
                     log_system_events,
+                    survey_report_system,
                 ),
             );
     }
@@ -69,6 +78,12 @@ pub struct ResourceChangedEvent {
     pub reason: String,
 }
 
+/// Event fired for each change made to the player through `PlayerResource`
+#[derive(Event, Debug, Clone)]
+pub struct PlayerChangedEvent {
+    pub change: PlayerChange,
+}
+
 /// Event fired when something is discovered
 #[derive(Event, Debug, Clone)]
 pub struct DiscoveryEvent {
@@ -114,6 +129,47 @@ pub enum SystemEventSeverity {
     Critical,
 }
 
+/// Turn changes recorded on the player resource into events
+///
+/// Runs once the world has answered the turn, so every change a move
+/// caused is sent together.
+pub fn forward_player_changes(
+    mut player_resource: ResMut<PlayerResource>,
+    mut player_events: EventWriter<PlayerChangedEvent>,
+) {
+    // Checked first so idle frames don't mark the resource as changed
+    if !player_resource.has_changes() {
+        return;
+    }
+    player_events.write_batch(
+        player_resource
+            .drain_changes()
+            .into_iter()
+            .map(|change| PlayerChangedEvent { change }),
+    );
+}
+
+/// Note grants cut down by the day's diminishing returns
+fn log_reduced_grants(
+    mut player_events: EventReader<PlayerChangedEvent>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    for event in player_events.read() {
+        if let PlayerChange::MovementGrantReduced {
+            requested, granted, ..
+        } = event.change
+        {
+            game_log.log_message(
+                format!(
+                    "😮‍💨 You're exhausted; the adrenaline barely helps. ({} of {} movement points)",
+                    granted, requested
+                ),
+                GameLogType::Resources,
+            );
+        }
+    }
+}
+
 /// System to handle movement event logging
 fn log_movement_events(
     mut movement_events: EventReader<MovementAttemptEvent>,
@@ -250,6 +306,62 @@ fn log_system_events(
     }
 }
 
+/// Send the player a survey of their region once they have explored enough
+fn survey_report_system(
+    game_stats: Res<GameStatsResource>,
+    map_resource: Res<MapResource>,
+    player_resource: Res<PlayerResource>,
+    mut game_log: ResMut<GameLogService>,
+    mut reported: Local<bool>,
+) {
+    if *reported || game_stats.tiles_explored < SURVEY_REPORT_TILES || map_resource.is_in_interior()
+    {
+        return;
+    }
+    let (Some(map), Some(center)) = (map_resource.overworld(), player_resource.player_position())
+    else {
+        return;
+    };
+    *reported = true;
+
+    let analysis =
+        MapService::new(map.seed()).analyze(map, MapRegion::new(center, SURVEY_REPORT_RADIUS));
+    info!("🗺️ Survey report\n{}", analysis);
+    game_log.log_message(survey_summary(&analysis), GameLogType::Discovery);
+}
+
+/// One-line survey of a region for the game log
+fn survey_summary(analysis: &MapAnalysis) -> String {
+    let terrain = match analysis.biomes.dominant() {
+        Some(biome) => format!(
+            "mostly {:?} ({:.0}%)",
+            biome,
+            analysis.biomes.percentage(biome)
+        ),
+        None => "uncharted".to_string(),
+    };
+    let landmasses = match analysis.components {
+        0 => "no solid ground".to_string(),
+        1 => "one connected landmass".to_string(),
+        n => format!("{} separate landmasses", n),
+    };
+    let resources = match analysis.richest_resource() {
+        Some(resource) => format!(
+            "richest in {} ({:.1} per 100 tiles)",
+            resource,
+            analysis.resource_density(resource)
+        ),
+        None => "no resource deposits".to_string(),
+    };
+    format!(
+        "📜 Survey report: {}, {}, {}, average terrain cost {:.1}",
+        terrain,
+        landmasses,
+        resources,
+        analysis.average_movement_cost()
+    )
+}
+
 /// Calculate rest duration based on rest outcome
 fn calculate_rest_duration(rest_outcome: &RestOutcome) -> u64 {
     match rest_outcome {
@@ -421,4 +533,30 @@ mod tests {
         assert_eq!(calculate_rest_duration(&RestOutcome::NormalRest), 6);
         assert_eq!(calculate_rest_duration(&RestOutcome::ExceptionalRest), 3);
     }
+
+    #[test]
+    fn survey_summary_describes_the_region() {
+        use crate::domain::entities::{Map, MapTile};
+        use crate::domain::value_objects::terrain::{Elevation, TerrainType};
+        use crate::domain::value_objects::TileCoordinate;
+
+        let mut map = Map::new(EntityId::generate(), "Survey".to_string(), 3).unwrap();
+        for (x, terrain) in [
+            (0, TerrainType::Plains),
+            (1, TerrainType::Forest),
+            (2, TerrainType::Ocean),
+            (3, TerrainType::Desert),
+        ] {
+            map.set_tile(
+                TileCoordinate::new(x, 0, 0),
+                MapTile::new(terrain, Elevation::sea_level(), true),
+            );
+        }
+        let analysis = MapService::new(3).analyze(&map, MapRegion::new(Position3D::origin(), 3));
+
+        let summary = survey_summary(&analysis);
+        assert!(summary.contains("mostly Temperate (50%)"));
+        assert!(summary.contains("2 separate landmasses"));
+        assert!(summary.contains("no resource deposits"));
+    }
 }
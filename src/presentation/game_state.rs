@@ -6,6 +6,10 @@
 
 use crate::domain::{
     entities::{Base, Player, Quest},
+    services::{
+        AudioMemory, EmergencyRecall, EventGrace, ExpeditionPlan, FortuneFavor, Mutators, Party,
+        PlayHeatmap, QuestBoard, Reputation, SessionFlags, TileDecals, WreckField, STANDARD_DROP,
+    },
     value_objects::{dice::DiceResult, Position3D, ResourceCollection},
 };
 use bevy::prelude::*;
@@ -20,7 +24,9 @@ pub enum RpgAppState {
     CharacterCreation,
     Exploration,
     Combat,
+    EventResolution,
     BaseManagement,
+    ExpeditionPlanning,
     QuestLog,
     Inventory,
     Settings,
@@ -72,8 +78,8 @@ pub struct RpgGameSession {
     pub player: Player,
     /// Player's base
     pub base: Base,
-    /// Active quests
-    pub active_quests: Vec<Quest>,
+    /// Bulletin board offers and the quests the player accepted
+    pub quest_board: QuestBoard,
     /// Completed quests
     pub completed_quests: Vec<Quest>,
     /// Current exploration position
@@ -85,6 +91,32 @@ pub struct RpgGameSession {
     pub total_play_time: u32,
     /// Last save timestamp (milliseconds since epoch)
     pub last_save: Option<u64>,
+    /// Opening moves during which hostile events are suppressed
+    pub event_grace: EventGrace,
+    /// Optional bad-luck assist; lives for this run only
+    pub fortune_favor: FortuneFavor,
+    /// Expedition the player committed to from the base, if any
+    pub active_expedition: Option<ExpeditionPlan>,
+    /// Standing with the spaceport factions
+    pub reputation: Reputation,
+    /// Mutators the run was started with; fixed until the next run
+    pub mutators: Mutators,
+    /// Second character of a pass-and-play run, with the turn order
+    pub party: Option<Party>,
+    /// Choices that later events remember
+    pub flags: SessionFlags,
+    /// Wrecks of won fights and the debris of salvaged ones
+    pub wrecks: WreckField,
+    /// Where the run spent its time and what happened there
+    pub heatmap: PlayHeatmap,
+    /// Id of the scenario the run started with
+    pub scenario: String,
+    /// Once-per-run rescue from a defeat
+    pub emergency_recall: EmergencyRecall,
+    /// Soundtrack kept in step by the audio systems, restored on load
+    pub audio_memory: Option<AudioMemory>,
+    /// Marks the player left on the ground
+    pub decals: TileDecals,
 }
 
 impl RpgGameSession {
@@ -94,11 +126,24 @@ impl RpgGameSession {
             current_position: *player.position(),
             player,
             base,
-            active_quests: Vec::new(),
+            quest_board: QuestBoard::new(),
             completed_quests: Vec::new(),
             session_start: crate::infrastructure::time::TimeService::now_millis().unwrap_or(0),
             total_play_time: 0,
             last_save: None,
+            event_grace: EventGrace::default(),
+            fortune_favor: FortuneFavor::new(),
+            active_expedition: None,
+            reputation: Reputation::new(),
+            mutators: Mutators::default(),
+            party: None,
+            flags: SessionFlags::new(),
+            wrecks: WreckField::new(),
+            heatmap: PlayHeatmap::new(),
+            scenario: STANDARD_DROP.to_string(),
+            emergency_recall: EmergencyRecall::default(),
+            audio_memory: None,
+            decals: TileDecals::new(),
         }
     }
 
@@ -114,7 +159,7 @@ impl RpgGameSession {
 
     /// Get active quest count
     pub fn active_quest_count(&self) -> usize {
-        self.active_quests.len()
+        self.quest_board.active().len()
     }
 
     /// Get completed quest count
@@ -129,16 +174,9 @@ impl RpgGameSession {
         self.player.resources().total_value() >= upgrade_cost
     }
 
-    /// Update play time
-    pub fn update_play_time(&mut self) {
-        use crate::infrastructure::time::TimeService;
-        let now = TimeService::now_millis().unwrap_or(0);
-        let elapsed = if now >= self.session_start {
-            ((now - self.session_start) / 1000) as u32 // Convert millis to seconds
-        } else {
-            0
-        };
-        self.total_play_time = elapsed;
+    /// Add whole seconds of wall-clock time to the play time
+    pub fn add_play_time(&mut self, seconds: u32) {
+        self.total_play_time = self.total_play_time.saturating_add(seconds);
     }
 
     /// Mark as saved
@@ -386,9 +424,18 @@ pub fn handle_rpg_state_transitions(
     }
 }
 
-/// Update game session data
-pub fn update_game_session(mut game_session: ResMut<RpgGameSession>, _time: Res<Time>) {
-    game_session.update_play_time();
+/// Update game session data; time played follows the wall clock
+pub fn update_game_session(
+    mut game_session: ResMut<RpgGameSession>,
+    wall: Res<crate::presentation::clocks::WallClock>,
+    mut carry: Local<f32>,
+) {
+    *carry += wall.delta().as_secs_f32();
+    if *carry >= 1.0 {
+        let seconds = carry.floor();
+        *carry -= seconds;
+        game_session.add_play_time(seconds as u32);
+    }
 }
 
 /// RPG state management plugin
@@ -5,16 +5,32 @@
 
 use crate::domain::constants::{
     get_terrain_scanner_color, CRITICAL_TEXT, ENERGY_COLOR, PANEL_BACKGROUND, PRIMARY_TEXT,
-    RESOURCE_COLOR, SCANNER_GRID, SECONDARY_TEXT, SHIP_SIGNATURE, SUCCESS_TEXT, UNEXPLORED_SPACE,
-    WARNING_TEXT,
+    REPUTATION_MAX, REPUTATION_MIN, RESOURCE_COLOR, SCANNER_GRID, SECONDARY_TEXT, SHIP_SIGNATURE,
+    SUCCESS_TEXT, UNEXPLORED_SPACE, WARNING_TEXT, WRECK_SIGNATURE,
 };
 use crate::domain::services::font_service::{FontService, FontSize, FontType};
 use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{Faction, Fatigue, GameCalendar, Reputation};
 
 use crate::infrastructure::bevy::font_service::{BevyFontService, RegularText};
-use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource, PlayerResource};
+use crate::infrastructure::bevy::resources::{
+    GameStatsResource, MapResource, PartyResource, PlayerResource,
+};
 use crate::infrastructure::time::TimeService;
+use crate::presentation::about::AboutScreen;
+use crate::presentation::codex::CodexScreen;
+use crate::presentation::day_night::DayNightCycle;
+use crate::presentation::game_state::RpgGameSession;
+use crate::presentation::hud_layout::{
+    slot_node, spawn_hud_bars, touch_target_size, HudLayout, HudSlot, LogHeader,
+};
+use crate::presentation::odds_preview::AdjacentOdds;
+use crate::presentation::quick_save::ContinueOffer;
+use crate::presentation::run_end::ActiveRun;
+use crate::presentation::share_code::SharePrompt;
+use crate::presentation::tile_staleness::{desaturate, TileStaleness};
 use crate::presentation::RpgAppState;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
 /// Plugin for space-themed game UI functionality
@@ -111,6 +127,8 @@ pub struct SpaceIcons {
 /// Setup comprehensive space-themed UI
 fn setup_space_ui(mut commands: Commands) {
     info!("🚀 Initializing Space Looter Command Interface");
+    let layout = HudLayout::default();
+    let touch = touch_target_size(1.0);
 
     // Root container - Full screen HUD overlay
     commands
@@ -127,16 +145,8 @@ fn setup_space_ui(mut commands: Commands) {
             // Left Panel - Sector Scanner
             parent
                 .spawn((
-                    Node {
-                        position_type: PositionType::Absolute,
-                        left: Val::Px(15.0),
-                        top: Val::Px(15.0),
-                        width: Val::Px(320.0),
-                        height: Val::Px(280.0),
-                        padding: UiRect::all(Val::Px(15.0)),
-                        flex_direction: FlexDirection::Column,
-                        ..default()
-                    },
+                    slot_node(HudSlot::MiniMap, &layout, touch),
+                    HudSlot::MiniMap,
                     Name::new("SectorScanner"),
                 ))
                 .with_children(|parent| {
@@ -224,6 +234,7 @@ fn setup_space_ui(mut commands: Commands) {
                                         },
                                         BackgroundColor(UNEXPLORED_SPACE),
                                         BorderColor(SCANNER_GRID),
+                                        Interaction::default(),
                                         SectorTile {
                                             grid_x: x - 3,
                                             grid_y: y - 3,
@@ -237,16 +248,9 @@ fn setup_space_ui(mut commands: Commands) {
             // Game Log Panel - Bottom Left
             parent
                 .spawn((
-                    Node {
-                        position_type: PositionType::Absolute,
-                        left: Val::Px(15.0),
-                        bottom: Val::Px(15.0),
-                        width: Val::Px(400.0),
-                        height: Val::Px(200.0),
-                        padding: UiRect::all(Val::Px(12.0)),
-                        flex_direction: FlexDirection::Column,
-                        ..default()
-                    },
+                    slot_node(HudSlot::Log, &layout, touch),
+                    HudSlot::Log,
+                    Interaction::default(),
                     GameLogPanel,
                     BackgroundColor(PANEL_BACKGROUND),
                     Name::new("GameLogPanel"),
@@ -265,6 +269,7 @@ fn setup_space_ui(mut commands: Commands) {
                             margin: UiRect::bottom(Val::Px(8.0)),
                             ..default()
                         },
+                        LogHeader,
                     ));
 
                     // Scrollable log area with working scroll
@@ -292,16 +297,31 @@ fn setup_space_ui(mut commands: Commands) {
                             ));
                         });
                 });
+
+            // Stat bar and action bar, placed by the HUD layout profile
+            spawn_hud_bars(parent);
         });
 
     info!("Space Command Interface initialized");
 }
 
+/// Run state shown alongside the scanner and status panels
+#[derive(SystemParam)]
+struct SpaceUiRunState<'w> {
+    game_stats: Res<'w, GameStatsResource>,
+    rpg_session: Option<Res<'w, RpgGameSession>>,
+    staleness: Option<Res<'w, TileStaleness>>,
+    party: Option<Res<'w, PartyResource>>,
+    day_night: Option<Res<'w, DayNightCycle>>,
+    calendar: Option<Res<'w, GameCalendar>>,
+    odds: Option<Res<'w, AdjacentOdds>>,
+}
+
 /// Update all space UI elements
 fn update_space_ui(
     map_resource: Res<MapResource>,
     player_resource: Res<PlayerResource>,
-    game_stats: Res<GameStatsResource>,
+    run_state: SpaceUiRunState,
     mut scanner_query: Query<
         &mut Text,
         (
@@ -326,16 +346,56 @@ fn update_space_ui(
             Without<ShipStatusPanel>,
         ),
     >,
-    mut tile_query: Query<(&mut BackgroundColor, &SectorTile)>,
+    mut tile_query: Query<(&mut BackgroundColor, &SectorTile, &Interaction)>,
 ) {
-    // Update scanner coordinates
+    let SpaceUiRunState {
+        game_stats,
+        rpg_session,
+        staleness,
+        party,
+        day_night,
+        calendar,
+        odds,
+    } = run_state;
+
+    // Update scanner coordinates, or describe the hovered scanner tile
     if let Ok(mut scanner_text) = scanner_query.single_mut() {
         if map_resource.has_map() && player_resource.has_player() {
             let player_pos = player_resource.player_position().unwrap_or_default();
-            **scanner_text = format!(
-                "COORDINATES: [{}, {}, {}] | SECTOR SCAN ACTIVE",
-                player_pos.x, player_pos.y, player_pos.z
-            );
+            let map = map_resource.current_map().unwrap();
+            let hovered = tile_query
+                .iter()
+                .find(|(_, _, interaction)| **interaction == Interaction::Hovered)
+                .map(|(_, tile_info, _)| {
+                    crate::domain::value_objects::TileCoordinate::new(
+                        player_pos.x + tile_info.grid_x,
+                        player_pos.y + tile_info.grid_y,
+                        player_pos.z,
+                    )
+                });
+            let readout = hovered.and_then(|coord| {
+                let tile = map.get_tile(&coord)?;
+                let staleness = staleness.as_ref()?;
+                let mut readout = format!(
+                    "SECTOR [{}, {}] | {}",
+                    coord.x,
+                    coord.y,
+                    staleness.describe(map, coord, tile)
+                );
+                // Tiles next to the player also show the odds of moving there
+                let target =
+                    crate::domain::value_objects::Position3D::new(coord.x, coord.y, coord.z);
+                if let Some(preview) = odds.as_ref().and_then(|odds| odds.at(target)) {
+                    readout.push_str(&format!(" | {}", preview.summary()));
+                }
+                Some(readout)
+            });
+            **scanner_text = readout.unwrap_or_else(|| {
+                format!(
+                    "COORDINATES: [{}, {}, {}] | SECTOR SCAN ACTIVE",
+                    player_pos.x, player_pos.y, player_pos.z
+                )
+            });
         } else {
             **scanner_text = "COORDINATES: [?, ?, ?] | INITIALIZING SENSORS...".to_string();
         }
@@ -346,7 +406,7 @@ fn update_space_ui(
         let player_pos = player_resource.player_position().unwrap_or_default();
         let map = map_resource.current_map().unwrap();
 
-        for (mut bg_color, tile_info) in tile_query.iter_mut() {
+        for (mut bg_color, tile_info, _) in tile_query.iter_mut() {
             let world_x = player_pos.x + tile_info.grid_x;
             let world_y = player_pos.y + tile_info.grid_y;
 
@@ -360,9 +420,26 @@ fn update_space_ui(
                     player_pos.z,
                 );
 
+                let wreck = rpg_session.as_ref().is_some_and(|session| {
+                    session
+                        .wrecks
+                        .at(crate::domain::value_objects::Position3D::new(
+                            world_x,
+                            world_y,
+                            player_pos.z,
+                        ))
+                        .is_some()
+                });
+
                 if let Some(tile) = map.get_tile(&tile_coord) {
-                    if tile.is_explored() {
-                        bg_color.0 = get_terrain_scanner_color(tile.terrain_type);
+                    if tile.is_explored() && wreck && !map_resource.is_in_interior() {
+                        bg_color.0 = WRECK_SIGNATURE;
+                    } else if tile.is_explored() {
+                        let color = get_terrain_scanner_color(tile.terrain_type);
+                        let stale = staleness
+                            .as_ref()
+                            .is_some_and(|staleness| staleness.is_stale(map, tile_coord, tile));
+                        bg_color.0 = if stale { desaturate(color) } else { color };
                     } else {
                         bg_color.0 = UNEXPLORED_SPACE;
                     }
@@ -377,7 +454,8 @@ fn update_space_ui(
     if let Ok(mut status_text) = status_query.single_mut() {
         if player_resource.has_player() {
             let player = player_resource.get_player().unwrap();
-            let health_percent = 100; // Player health is always 100 for now
+            let health = *player.health();
+            let health_percent = (health.fraction() * 100.0).round() as i32;
             let energy_percent = (player.movement_points() as f32
                 / player.max_movement_points() as f32
                 * 100.0) as i32;
@@ -389,18 +467,72 @@ fn update_space_ui(
                 _ => ("🔴 CRITICAL", CRITICAL_TEXT),
             };
 
+            // Diminishing movement rewards show as a quiet note on the thrust line
+            let fatigue = match player_resource.movement_fatigue() {
+                Fatigue::Fresh => "",
+                Fatigue::Tired => " · fatigued",
+                Fatigue::Exhausted => " · exhausted",
+            };
+
             **status_text = format!(
-                "HULL INTEGRITY: {}% - {}\nPOWER CORE: {}% CAPACITY\nPROPULSION: {}/{} THRUST\nPILOT LEVEL: {}\n\nMISSION PROGRESS\nSectors Mapped: {}\nQuantum Events: {}\nSuccess Rate: {:.0}%",
-                health_percent,
+                "HULL INTEGRITY: {} - {}\nPOWER CORE: {}% CAPACITY\nPROPULSION: {}/{} THRUST{}\nPILOT LEVEL: {}\n\nMISSION PROGRESS\nSectors Mapped: {}\nQuantum Events: {}\nSuccess Rate: {:.0}%",
+                health,
                 health_status.0,
                 energy_percent,
                 player.movement_points(),
                 player.max_movement_points(),
+                fatigue,
                 player.level(),
                 game_stats.tiles_explored,
                 game_stats.dice_rolls_made,
                 game_stats.success_rate() * 100.0
             );
+            if let Some(cycle) = &day_night {
+                let date = calendar
+                    .as_ref()
+                    .map(|calendar| format!("{} | ", calendar.label()))
+                    .unwrap_or_default();
+                status_text.push_str(&format!("\n{}{}", date, cycle.clock_readout()));
+            }
+            status_text.push_str(&format!("\n{}", game_stats.experience_summary()));
+            if let Some(blitz) = game_stats.blitz_summary() {
+                status_text.push_str(&format!("\n{}", blitz));
+            }
+            if let Some(undone) = game_stats.undo_summary() {
+                status_text.push_str(&format!("\n{}", undone));
+            }
+            if let Some(mutators) = game_stats.mutator_summary() {
+                status_text.push_str(&format!("\n{}", mutators));
+            }
+            if let Some(scenario) = game_stats.scenario_summary() {
+                status_text.push_str(&format!("\n{}", scenario));
+            }
+            status_text.push_str(&format!("\nRun Code: {:016x}", game_stats.score_hash()));
+            if let Some(party) = party.as_ref().and_then(|party| party.party()) {
+                status_text.push_str(&format!("\n\nPARTY - {} on duty", player.name()));
+                for contribution in party.contributions() {
+                    status_text.push_str(&format!("\n{}", contribution.summary()));
+                }
+                let waiting_at = party.benched().position();
+                status_text.push_str(&format!(
+                    "\n{} waits at [{}, {}]",
+                    party.waiting_name(),
+                    waiting_at.x,
+                    waiting_at.y
+                ));
+            }
+            if let Some(session) = &rpg_session {
+                status_text.push_str(&format!(
+                    "\n{}",
+                    session
+                        .emergency_recall
+                        .status_line(game_stats.current_day())
+                ));
+                status_text.push_str(&format!(
+                    "\n\nFACTION STANDING\n{}",
+                    reputation_bars(&session.reputation)
+                ));
+            }
         } else {
             **status_text =
                 "SHIP SYSTEMS: INITIALIZING...\nESTABLISHING QUANTUM LINK...".to_string();
@@ -409,10 +541,47 @@ fn update_space_ui(
 
     // Update mission control commands (can be dynamic based on state)
     if let Ok(mut control_text) = control_query.single_mut() {
-        **control_text = "WASD/ARROWS: Navigate Sectors | SPACE: Quantum Dice Roll | B: Base Operations | Q: Mission Database | I: Cargo Manifest | ESC: Command Menu".to_string();
+        let mut controls = "WASD/ARROWS: Navigate Sectors | SPACE: Quantum Dice Roll | B: Base Operations | Q: Mission Database | I: Cargo Manifest | L: Scout Probe | O: Dawn Report | K: Heatmap".to_string();
+        if party.is_some_and(|party| party.is_active()) {
+            controls.push_str(" | T: Trade Cargo");
+        }
+        let on_wreck = player_resource.player_position().is_some_and(|position| {
+            rpg_session
+                .as_ref()
+                .is_some_and(|session| session.wrecks.at(position).is_some())
+        });
+        if on_wreck && !map_resource.is_in_interior() {
+            controls.push_str(" | U: Salvage Wreck");
+        }
+        controls.push_str(" | ESC: Command Menu");
+        if **control_text != controls {
+            **control_text = controls;
+        }
     }
 }
 
+/// Width of a faction standing bar in characters
+const REPUTATION_BAR_WIDTH: i32 = 10;
+
+/// One bar with its tier per faction
+fn reputation_bars(reputation: &Reputation) -> String {
+    Faction::all()
+        .iter()
+        .map(|&faction| {
+            let filled = (reputation.score(faction) - REPUTATION_MIN) * REPUTATION_BAR_WIDTH
+                / (REPUTATION_MAX - REPUTATION_MIN);
+            format!(
+                "{:<18} [{}{}] {}",
+                faction.name(),
+                "█".repeat(filled as usize),
+                "░".repeat((REPUTATION_BAR_WIDTH - filled) as usize),
+                reputation.tier(faction).name()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Add a log message to the game log service
 pub fn add_game_log_message(
     mut game_log: ResMut<GameLogService>,
@@ -473,7 +642,7 @@ fn update_game_log_display(
                 let color = get_log_type_color(&message.log_type);
 
                 parent.spawn((
-                    Text::new(&message.message),
+                    Text::new(message.display_text()),
                     TextFont {
                         font_size: 11.0,
                         ..default()
@@ -510,6 +679,34 @@ fn get_log_type_color(log_type: &GameLogType) -> Color {
     }
 }
 
+/// Screens and prompts that keep the main menu open
+#[derive(SystemParam)]
+struct MenuHolds<'w> {
+    about: Option<Res<'w, AboutScreen>>,
+    codex: Option<Res<'w, CodexScreen>>,
+    run: Option<Res<'w, ActiveRun>>,
+    share: Option<Res<'w, SharePrompt>>,
+    continue_offer: Option<Res<'w, ContinueOffer>>,
+}
+
+impl MenuHolds<'_> {
+    /// Whether anything on the menu still waits for the player
+    fn holds_menu(&self) -> bool {
+        // Hold the menu while the player reads the about screen or the codex
+        self.about.as_ref().is_some_and(|about| about.is_open())
+            || self.codex.as_ref().is_some_and(|codex| codex.is_open())
+            // A pasted share code waits for Enter, or for another paste after a refusal
+            || self.share.as_ref().is_some_and(|share| share.holds_menu())
+            // After a run ends the next one starts when the player is ready
+            || self.run.as_ref().is_some_and(|run| run.has_ended())
+            // A saved run waits for the player to continue it or start a new one
+            || self
+                .continue_offer
+                .as_ref()
+                .is_some_and(|offer| offer.holds_menu())
+    }
+}
+
 /// Auto-start exploration mode with space theme
 fn auto_start_exploration_system(
     current_state: Res<State<RpgAppState>>,
@@ -518,7 +715,11 @@ fn auto_start_exploration_system(
     time: Res<Time>,
     map_resource: Res<MapResource>,
     player_resource: Res<PlayerResource>,
+    holds: MenuHolds,
 ) {
+    if holds.holds_menu() {
+        return;
+    }
     if *current_state == RpgAppState::MainMenu
         && map_resource.has_map()
         && player_resource.has_player()
@@ -578,6 +779,20 @@ mod tests {
         assert_ne!(SHIP_SIGNATURE, UNEXPLORED_SPACE);
     }
 
+    #[test]
+    fn reputation_bars_fill_with_standing() {
+        let mut reputation = Reputation::new();
+        reputation.adjust(Faction::ScavengerGuild, REPUTATION_MAX, 0.5);
+
+        let bars = reputation_bars(&reputation);
+        let lines: Vec<&str> = bars.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].ends_with("[██████████] Allied"));
+        assert!(lines[1].ends_with("[██░░░░░░░░] Hostile"));
+        assert!(lines[2].ends_with("[█████░░░░░] Neutral"));
+    }
+
     #[test]
     fn ui_components_have_proper_markers() {
         // This would test component spawning in integration tests
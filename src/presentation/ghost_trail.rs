@@ -0,0 +1,327 @@
+//! Ghost Trail - Where the best previous run on this seed went
+//!
+//! During a run the player's position is sampled at the end of every day.
+//! When the run ends (game over or closing the game) the samples are stored
+//! with the run's score in the ghost book. A new run on the same seed shows
+//! the best previous run as faint markers on tiles the player has already
+//! explored, plus a "previous best" banner. Both follow the ghost trail map
+//! layer and are display only.
+
+use crate::domain::constants::{ENERGY_COLOR, GHOST_TRAIL_ALPHA, SECONDARY_TEXT};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::GhostRun;
+use crate::domain::value_objects::Position3D;
+use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource, PlayerResource};
+use crate::infrastructure::ghosts::GhostStore;
+use crate::infrastructure::settings::MapLayerVisibility;
+use crate::presentation::RpgAppState;
+use bevy::app::AppExit;
+use bevy::prelude::*;
+
+/// Plugin recording runs and drawing the previous best run
+pub struct GhostTrailPlugin;
+
+impl Plugin for GhostTrailPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GhostStore>()
+            .init_resource::<MapLayerVisibility>()
+            .init_resource::<RunRecorder>()
+            .init_resource::<PreviousBest>()
+            .add_systems(Startup, setup_ghost_banner)
+            .add_systems(
+                Update,
+                (
+                    track_run_system,
+                    finish_run_system,
+                    update_ghost_banner,
+                    update_ghost_markers,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Per-day position samples of the current run
+#[derive(Resource, Debug, Clone, Default)]
+pub struct RunRecorder {
+    seed: Option<u64>,
+    samples: Vec<Position3D>,
+    nights_recorded: u32,
+    finished: bool,
+}
+
+impl RunRecorder {
+    /// Start recording a run on `seed` at the starting position
+    pub fn begin(&mut self, seed: u64, start: Position3D) {
+        *self = Self {
+            seed: Some(seed),
+            samples: vec![start],
+            ..Self::default()
+        };
+    }
+
+    /// Seed of the run being recorded
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Sample the position at the end of a day
+    pub fn end_day(&mut self, position: Position3D) {
+        self.samples.push(position);
+    }
+
+    /// Close the run; yields the ghost to store once per run
+    pub fn finish(
+        &mut self,
+        final_position: Position3D,
+        score: u32,
+        days: u32,
+    ) -> Option<(u64, GhostRun)> {
+        let seed = self.seed?;
+        if self.finished {
+            return None;
+        }
+        self.finished = true;
+        if self.samples.last() != Some(&final_position) {
+            self.samples.push(final_position);
+        }
+        Some((seed, GhostRun::new(score, days, &self.samples)))
+    }
+}
+
+/// Best previous run on the current seed, fixed when the run starts
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PreviousBest {
+    pub run: Option<GhostRun>,
+}
+
+/// Marker for the previous best banner
+#[derive(Component)]
+pub struct GhostBannerText;
+
+/// Marker for ghost trail meshes on the map
+#[derive(Component)]
+pub struct GhostTrailMarker;
+
+fn setup_ghost_banner(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: FontSize::Small.to_pixels(),
+            ..default()
+        },
+        TextColor(SECONDARY_TEXT),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Percent(42.0),
+            top: Val::Px(15.0),
+            ..default()
+        },
+        Visibility::Hidden,
+        GhostBannerText,
+        Name::new("GhostBanner"),
+    ));
+}
+
+/// Start the recording once the world exists and sample each new day
+fn track_run_system(
+    current_state: Res<State<RpgAppState>>,
+    map_resource: Res<MapResource>,
+    player_resource: Res<PlayerResource>,
+    game_stats: Res<GameStatsResource>,
+    store: Res<GhostStore>,
+    mut recorder: ResMut<RunRecorder>,
+    mut previous_best: ResMut<PreviousBest>,
+) {
+    if *current_state.get() != RpgAppState::Exploration {
+        return;
+    }
+    let Some(position) = player_resource.player_position() else {
+        return;
+    };
+
+    if recorder.seed().is_none() {
+        let Some(seed) = map_resource.overworld().map(|map| map.seed()) else {
+            return;
+        };
+        recorder.begin(seed, position);
+        previous_best.run = store.book.best(seed).cloned();
+        return;
+    }
+
+    if game_stats.nights_rested > recorder.nights_recorded {
+        recorder.nights_recorded = game_stats.nights_rested;
+        // Interior coordinates are not overworld positions
+        if !map_resource.is_in_interior() {
+            recorder.end_day(position);
+        }
+    }
+}
+
+/// Store the run's breadcrumb on game over or when the game closes
+fn finish_run_system(
+    current_state: Res<State<RpgAppState>>,
+    player_resource: Res<PlayerResource>,
+    map_resource: Res<MapResource>,
+    game_stats: Res<GameStatsResource>,
+    mut exit_events: EventReader<AppExit>,
+    mut recorder: ResMut<RunRecorder>,
+    mut store: ResMut<GhostStore>,
+) {
+    let exiting = exit_events.read().count() > 0;
+    let game_over = current_state.is_changed() && *current_state.get() == RpgAppState::GameOver;
+    if !exiting && !game_over {
+        return;
+    }
+    let Some(position) = player_resource.player_position() else {
+        return;
+    };
+    // Inside a ruin the last surface sample stands in for the position
+    let final_position = if map_resource.is_in_interior() {
+        recorder.samples.last().copied().unwrap_or(position)
+    } else {
+        position
+    };
+
+    if let Some((seed, run)) = recorder.finish(
+        final_position,
+        game_stats.run_score(),
+        game_stats.current_day(),
+    ) {
+        let (score, days) = (run.score, run.days);
+        if store.record(seed, run) {
+            info!(
+                "👻 Run saved as a ghost for seed {} (day {}, {} pts)",
+                seed, days, score
+            );
+        }
+    }
+}
+
+/// Show the previous best run while exploring
+fn update_ghost_banner(
+    current_state: Res<State<RpgAppState>>,
+    previous_best: Res<PreviousBest>,
+    layers: Res<MapLayerVisibility>,
+    mut banner_query: Query<(&mut Text, &mut Visibility), With<GhostBannerText>>,
+) {
+    let Ok((mut text, mut visibility)) = banner_query.single_mut() else {
+        return;
+    };
+
+    let best = previous_best
+        .run
+        .as_ref()
+        .filter(|_| layers.ghost_trail && *current_state.get() == RpgAppState::Exploration);
+    let wanted = match best {
+        Some(run) => {
+            let line = format!(
+                "PREVIOUS BEST: DAY {}, {} PTS",
+                run.days,
+                format_points(run.score)
+            );
+            if text.0 != line {
+                text.0 = line;
+            }
+            Visibility::Inherited
+        }
+        None => Visibility::Hidden,
+    };
+    if *visibility != wanted {
+        *visibility = wanted;
+    }
+}
+
+/// Keep ghost markers on explored tiles along the previous best run
+#[allow(clippy::too_many_arguments)]
+fn update_ghost_markers(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    previous_best: Res<PreviousBest>,
+    layers: Res<MapLayerVisibility>,
+    map_resource: Res<MapResource>,
+    markers: Query<Entity, With<GhostTrailMarker>>,
+    mut rendered: Local<Vec<Position3D>>,
+) {
+    let wanted: Vec<Position3D> = match (&previous_best.run, map_resource.overworld()) {
+        (Some(run), Some(map)) if layers.ghost_trail && !map_resource.is_in_interior() => {
+            run.visible_trail(map)
+        }
+        _ => Vec::new(),
+    };
+    if *rendered == wanted {
+        return;
+    }
+
+    for entity in markers.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let mesh = meshes.add(Mesh::from(Sphere::new(0.15)));
+    let material = materials.add(StandardMaterial {
+        base_color: ENERGY_COLOR.with_alpha(GHOST_TRAIL_ALPHA),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+    for position in &wanted {
+        let world = crate::presentation::movement::tile_to_world_position(*position);
+        commands.spawn((
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::from_translation(Vec3::new(world.x, 0.8, world.z)),
+            GhostTrailMarker,
+            Name::new("GhostTrailMarker"),
+        ));
+    }
+    *rendered = wanted;
+}
+
+/// Format points with thousands separators, e.g. 3,400
+fn format_points(points: u32) -> String {
+    let digits = points.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorder_finishes_a_run_once() {
+        let mut recorder = RunRecorder::default();
+        assert!(recorder.finish(Position3D::origin(), 10, 1).is_none());
+
+        recorder.begin(9, Position3D::new(0, 0, 0));
+        recorder.end_day(Position3D::new(4, 1, 0));
+        let (seed, run) = recorder.finish(Position3D::new(6, 1, 0), 3400, 2).unwrap();
+
+        assert_eq!(seed, 9);
+        assert_eq!(run.score, 3400);
+        assert_eq!(
+            run.trail.expand(),
+            vec![
+                Position3D::new(0, 0, 0),
+                Position3D::new(4, 1, 0),
+                Position3D::new(6, 1, 0)
+            ]
+        );
+        assert!(recorder.finish(Position3D::new(7, 1, 0), 3500, 2).is_none());
+    }
+
+    #[test]
+    fn points_use_thousands_separators() {
+        assert_eq!(format_points(0), "0");
+        assert_eq!(format_points(999), "999");
+        assert_eq!(format_points(3400), "3,400");
+        assert_eq!(format_points(1234567), "1,234,567");
+    }
+}
@@ -0,0 +1,718 @@
+//! HUD Layout - Landscape and portrait profiles for the HUD
+//!
+//! The HUD root picks a layout profile from the window's aspect ratio. A
+//! window has to get clearly taller than wide before the HUD turns portrait,
+//! and clearly wider than tall before it turns back, so a phone being
+//! rotated does not flap between the two.
+//!
+//! In portrait the stat bar runs along the top, the game log shrinks to a
+//! ticker of its last few lines that a tap expands, the action bar becomes a
+//! grid in the thumb zone at the bottom, and the sector scanner only shows
+//! when its map button is toggled. Action buttons are never smaller than
+//! `MIN_TOUCH_TARGET_PX` on screen, and grow with the UI scale.
+//!
+//! Switching profiles only lays out again the containers whose layout
+//! differs, and only the action bar has its buttons rebuilt. Whatever the
+//! player left open or scrolled is put back afterwards.
+
+use crate::domain::constants::{PANEL_BACKGROUND, PRIMARY_TEXT, SCANNER_GRID, SECONDARY_TEXT};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::GameCalendar;
+use crate::infrastructure::bevy::resources::PlayerResource;
+use crate::presentation::day_night::DayNightCycle;
+use crate::presentation::input::{GameAction, InputMapper};
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+use bevy::window::PrimaryWindow;
+use std::collections::HashMap;
+
+/// Width over height below which a landscape HUD turns portrait
+pub const PORTRAIT_BELOW_ASPECT: f32 = 0.9;
+
+/// Width over height above which a portrait HUD turns landscape again
+pub const LANDSCAPE_ABOVE_ASPECT: f32 = 1.1;
+
+/// Smallest on-screen size of a touch target, at a UI scale of 1
+pub const MIN_TOUCH_TARGET_PX: f32 = 44.0;
+
+/// Lines of the game log the portrait ticker shows
+pub const LOG_TICKER_LINES: usize = 3;
+
+/// Height of one game log line
+const LOG_LINE_HEIGHT: f32 = 14.0;
+
+/// Height of the stat bar
+const STAT_BAR_HEIGHT: f32 = 36.0;
+
+/// Gap between the HUD and the window edge
+const HUD_MARGIN: f32 = 15.0;
+
+/// Gap between the portrait HUD and the window edge
+const PORTRAIT_MARGIN: f32 = 8.0;
+
+/// Columns of the portrait action grid
+const PORTRAIT_ACTION_COLUMNS: u16 = 4;
+
+/// Height of an action button in the landscape action bar
+const LANDSCAPE_BUTTON_HEIGHT: f32 = 28.0;
+
+/// Buttons of the action bar, each standing in for a key
+const ACTIONS: [(&str, HudAction); 9] = [
+    ("ROLL", HudAction::Action(GameAction::RollDice)),
+    (
+        "UNDO",
+        HudAction::Chord(KeyCode::ControlLeft, KeyCode::KeyZ),
+    ),
+    ("BASE", HudAction::Action(GameAction::OpenBase)),
+    ("QUESTS", HudAction::Action(GameAction::OpenQuests)),
+    ("CARGO", HudAction::Action(GameAction::OpenInventory)),
+    ("PROBE", HudAction::Key(KeyCode::KeyL)),
+    ("SALVAGE", HudAction::Key(KeyCode::KeyU)),
+    ("MAP", HudAction::ToggleMiniMap),
+    ("MENU", HudAction::Key(KeyCode::Escape)),
+];
+
+/// Plugin for the landscape and portrait HUD profiles
+pub struct HudLayoutPlugin;
+
+impl Plugin for HudLayoutPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HudLayout>()
+            .init_resource::<InputMapper>()
+            .add_systems(
+                PreUpdate,
+                (hud_pointer_system, press_action_buttons_system)
+                    .chain()
+                    .after(UiSystem::Focus),
+            )
+            .add_systems(
+                Update,
+                (
+                    classify_layout_system,
+                    toggle_log_ticker_system,
+                    apply_hud_layout_system,
+                    update_stat_bar_system,
+                )
+                    .chain(),
+            )
+            .add_systems(Last, release_action_keys_system);
+    }
+}
+
+/// How the HUD is laid out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LayoutProfile {
+    /// Wider than tall: panels in the corners
+    #[default]
+    Landscape,
+    /// Taller than wide: stat bar on top, actions under the thumbs
+    Portrait,
+}
+
+impl LayoutProfile {
+    /// Profile for a window of `aspect` width over height, coming from `current`
+    ///
+    /// Between the two thresholds the current profile is kept.
+    pub fn classify(aspect: f32, current: LayoutProfile) -> LayoutProfile {
+        if aspect < PORTRAIT_BELOW_ASPECT {
+            LayoutProfile::Portrait
+        } else if aspect > LANDSCAPE_ABOVE_ASPECT {
+            LayoutProfile::Landscape
+        } else {
+            current
+        }
+    }
+}
+
+/// Size in UI pixels of a touch target at `ui_scale`
+///
+/// UI pixels are multiplied by the UI scale on screen, so a scale below 1
+/// gets larger targets to stay at the minimum, and a scale above 1 lets them
+/// grow with the rest of the HUD.
+pub fn touch_target_size(ui_scale: f32) -> f32 {
+    let ui_scale = ui_scale.max(0.1);
+    MIN_TOUCH_TARGET_PX * ui_scale.max(1.0) / ui_scale
+}
+
+/// The HUD's profile and what the player opened in it
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct HudLayout {
+    pub profile: LayoutProfile,
+    /// The portrait log shows in full instead of as a ticker
+    pub log_expanded: bool,
+    /// The portrait sector scanner is shown
+    pub minimap_open: bool,
+    /// The pointer is on the HUD, so clicks are not meant for the map
+    pointer_on_hud: bool,
+}
+
+impl HudLayout {
+    /// Check if the sector scanner is on screen
+    pub fn shows_minimap(&self) -> bool {
+        self.profile == LayoutProfile::Landscape || self.minimap_open
+    }
+
+    /// Check if the game log is cut down to a ticker
+    pub fn log_is_ticker(&self) -> bool {
+        self.profile == LayoutProfile::Portrait && !self.log_expanded
+    }
+
+    /// Check if a click or tap landed on the HUD rather than the map
+    pub fn captures_pointer(&self) -> bool {
+        self.pointer_on_hud
+    }
+}
+
+/// A HUD container laid out by the profile
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HudSlot {
+    StatBar,
+    Log,
+    ActionBar,
+    MiniMap,
+}
+
+/// What a button of the action bar does
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HudAction {
+    /// Press the same key the keyboard would
+    Key(KeyCode),
+    /// Press a key the action is bound to, wherever it was rebound
+    Action(GameAction),
+    /// Hold the modifier and press the key, as for Ctrl+Z
+    Chord(KeyCode, KeyCode),
+    /// Show or hide the portrait sector scanner
+    ToggleMiniMap,
+}
+
+/// Text of the stat bar
+#[derive(Component)]
+pub struct StatBarText;
+
+/// The game log's title, hidden while the log is a ticker
+#[derive(Component)]
+pub struct LogHeader;
+
+/// Layout of a HUD container for `layout`, with touch targets of `touch`
+pub fn slot_node(slot: HudSlot, layout: &HudLayout, touch: f32) -> Node {
+    let landscape = layout.profile == LayoutProfile::Landscape;
+    match (slot, landscape) {
+        (HudSlot::StatBar, true) => Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(HUD_MARGIN),
+            right: Val::Px(HUD_MARGIN),
+            height: Val::Px(STAT_BAR_HEIGHT),
+            padding: UiRect::horizontal(Val::Px(12.0)),
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        (HudSlot::StatBar, false) => Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(0.0),
+            left: Val::Px(0.0),
+            width: Val::Percent(100.0),
+            height: Val::Px(STAT_BAR_HEIGHT),
+            padding: UiRect::horizontal(Val::Px(PORTRAIT_MARGIN)),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        (HudSlot::Log, true) => Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(HUD_MARGIN),
+            bottom: Val::Px(HUD_MARGIN),
+            width: Val::Px(400.0),
+            height: Val::Px(200.0),
+            padding: UiRect::all(Val::Px(12.0)),
+            flex_direction: FlexDirection::Column,
+            ..default()
+        },
+        (HudSlot::Log, false) => {
+            let height = if layout.log_expanded {
+                Val::Percent(45.0)
+            } else {
+                Val::Px(LOG_TICKER_LINES as f32 * LOG_LINE_HEIGHT + 2.0 * PORTRAIT_MARGIN)
+            };
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(STAT_BAR_HEIGHT + PORTRAIT_MARGIN),
+                left: Val::Px(PORTRAIT_MARGIN),
+                right: Val::Px(PORTRAIT_MARGIN),
+                height,
+                padding: UiRect::all(Val::Px(PORTRAIT_MARGIN)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            }
+        }
+        (HudSlot::ActionBar, true) => Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(HUD_MARGIN),
+            bottom: Val::Px(HUD_MARGIN),
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(6.0),
+            ..default()
+        },
+        (HudSlot::ActionBar, false) => Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(0.0),
+            bottom: Val::Px(0.0),
+            width: Val::Percent(100.0),
+            padding: UiRect::all(Val::Px(PORTRAIT_MARGIN)),
+            display: Display::Grid,
+            grid_template_columns: RepeatedGridTrack::flex(PORTRAIT_ACTION_COLUMNS, 1.0),
+            grid_auto_rows: vec![GridTrack::px(touch)],
+            column_gap: Val::Px(PORTRAIT_MARGIN),
+            row_gap: Val::Px(PORTRAIT_MARGIN),
+            ..default()
+        },
+        (HudSlot::MiniMap, true) => Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(HUD_MARGIN),
+            top: Val::Px(HUD_MARGIN),
+            width: Val::Px(320.0),
+            height: Val::Px(280.0),
+            padding: UiRect::all(Val::Px(15.0)),
+            flex_direction: FlexDirection::Column,
+            ..default()
+        },
+        (HudSlot::MiniMap, false) => Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(PORTRAIT_MARGIN),
+            top: Val::Percent(30.0),
+            width: Val::Px(320.0),
+            height: Val::Px(280.0),
+            padding: UiRect::all(Val::Px(15.0)),
+            flex_direction: FlexDirection::Column,
+            display: if layout.shows_minimap() {
+                Display::Flex
+            } else {
+                Display::None
+            },
+            ..default()
+        },
+    }
+}
+
+/// Transient state of the HUD containers, kept across a rebuild
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TransientUiState {
+    visibility: HashMap<HudSlot, Visibility>,
+    scroll: HashMap<(HudSlot, usize), Vec2>,
+}
+
+impl TransientUiState {
+    /// Remember whether `slot` was left shown or hidden
+    pub fn record_visibility(&mut self, slot: HudSlot, visibility: Visibility) {
+        self.visibility.insert(slot, visibility);
+    }
+
+    /// Remember the scroll offset of the `index`th scroll area in `slot`
+    pub fn record_scroll(&mut self, slot: HudSlot, index: usize, offset: Vec2) {
+        self.scroll.insert((slot, index), offset);
+    }
+
+    /// Visibility `slot` was left at
+    pub fn visibility(&self, slot: HudSlot) -> Option<Visibility> {
+        self.visibility.get(&slot).copied()
+    }
+
+    /// Scroll offset the `index`th scroll area in `slot` was left at
+    pub fn scroll(&self, slot: HudSlot, index: usize) -> Option<Vec2> {
+        self.scroll.get(&(slot, index)).copied()
+    }
+}
+
+/// Track whether the pointer is on an interactive part of the HUD
+#[allow(clippy::type_complexity)]
+fn hud_pointer_system(
+    mut layout: ResMut<HudLayout>,
+    interactions: Query<&Interaction, Or<(With<HudSlot>, With<HudAction>)>>,
+) {
+    let on_hud = interactions
+        .iter()
+        .any(|interaction| *interaction != Interaction::None);
+    if layout.pointer_on_hud != on_hud {
+        layout.pointer_on_hud = on_hud;
+    }
+}
+
+/// Press the key of an action button when it is pressed
+///
+/// Runs after the UI has read the pointer and before the game reads the
+/// keyboard, so a button works exactly like its key.
+fn press_action_buttons_system(
+    buttons: Query<(&Interaction, &HudAction), Changed<Interaction>>,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut layout: ResMut<HudLayout>,
+    input_mapper: Res<InputMapper>,
+) {
+    for (interaction, action) in buttons.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            HudAction::Key(key) => keyboard.press(*key),
+            HudAction::Action(action) => {
+                let bound = input_mapper
+                    .bindings()
+                    .into_iter()
+                    .find(|(_, bound)| bound == action);
+                if let Some((key, _)) = bound {
+                    keyboard.press(key);
+                }
+            }
+            HudAction::Chord(modifier, key) => {
+                keyboard.press(*modifier);
+                keyboard.press(*key);
+            }
+            HudAction::ToggleMiniMap => layout.minimap_open = !layout.minimap_open,
+        }
+    }
+}
+
+/// Let go of keys the action bar pressed this frame
+fn release_action_keys_system(
+    buttons: Query<&HudAction>,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    input_mapper: Res<InputMapper>,
+) {
+    for action in buttons.iter() {
+        match action {
+            HudAction::Key(key) if keyboard.just_pressed(*key) => keyboard.release(*key),
+            HudAction::Action(action) => {
+                for key in input_mapper.get_keys_for_action(action) {
+                    if keyboard.just_pressed(key) {
+                        keyboard.release(key);
+                    }
+                }
+            }
+            HudAction::Chord(modifier, key) if keyboard.just_pressed(*key) => {
+                keyboard.release(*modifier);
+                keyboard.release(*key);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Pick the profile from the window's aspect ratio
+fn classify_layout_system(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut layout: ResMut<HudLayout>,
+) {
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    if window.height() <= 0.0 {
+        return;
+    }
+    let profile = LayoutProfile::classify(window.width() / window.height(), layout.profile);
+    if layout.profile != profile {
+        info!("📱 HUD layout: {:?}", profile);
+        layout.profile = profile;
+    }
+}
+
+/// Expand or collapse the portrait log ticker on a tap
+fn toggle_log_ticker_system(
+    logs: Query<(&HudSlot, &Interaction), Changed<Interaction>>,
+    mut layout: ResMut<HudLayout>,
+) {
+    if layout.profile != LayoutProfile::Portrait {
+        return;
+    }
+    let tapped = logs
+        .iter()
+        .any(|(slot, interaction)| *slot == HudSlot::Log && *interaction == Interaction::Pressed);
+    if tapped {
+        layout.log_expanded = !layout.log_expanded;
+    }
+}
+
+/// Lay the HUD containers out for the profile
+///
+/// Only containers whose layout changed are touched, and only the action
+/// bar's buttons are spawned again.
+fn apply_hud_layout_system(
+    mut commands: Commands,
+    layout: Res<HudLayout>,
+    ui_scale: Res<UiScale>,
+    mut containers: Query<(Entity, &HudSlot, &mut Node, &mut Visibility)>,
+    mut scroll_areas: Query<(&ChildOf, &mut ScrollPosition)>,
+    mut headers: Query<&mut Node, (With<LogHeader>, Without<HudSlot>)>,
+    mut built: Local<Option<(LayoutProfile, f32)>>,
+) {
+    if !layout.is_changed() && !ui_scale.is_changed() && built.is_some() {
+        return;
+    }
+    let touch = touch_target_size(ui_scale.0);
+
+    // Keep what the player left shown or scrolled
+    let mut transient = TransientUiState::default();
+    for (entity, slot, _, visibility) in containers.iter() {
+        transient.record_visibility(*slot, *visibility);
+        let scrolled = scroll_areas
+            .iter()
+            .filter(|(parent, _)| parent.parent() == entity);
+        for (index, (_, scroll)) in scrolled.enumerate() {
+            transient.record_scroll(*slot, index, Vec2::new(scroll.offset_x, scroll.offset_y));
+        }
+    }
+
+    let rebuild_actions = *built != Some((layout.profile, touch));
+    for (entity, slot, mut node, mut visibility) in containers.iter_mut() {
+        let wanted = slot_node(*slot, &layout, touch);
+        if *node != wanted {
+            *node = wanted;
+        }
+        if *slot == HudSlot::ActionBar && rebuild_actions {
+            commands.entity(entity).despawn_related::<Children>();
+            commands.entity(entity).with_children(|parent| {
+                spawn_action_buttons(parent, layout.profile, touch);
+            });
+        }
+        if let Some(kept) = transient.visibility(*slot) {
+            if *visibility != kept {
+                *visibility = kept;
+            }
+        }
+    }
+
+    for (entity, slot, _, _) in containers.iter() {
+        let scrolled = scroll_areas
+            .iter_mut()
+            .filter(|(parent, _)| parent.parent() == entity);
+        for (index, (_, mut scroll)) in scrolled.enumerate() {
+            let Some(offset) = transient.scroll(*slot, index) else {
+                continue;
+            };
+            if scroll.offset_x != offset.x || scroll.offset_y != offset.y {
+                scroll.offset_x = offset.x;
+                scroll.offset_y = offset.y;
+            }
+        }
+    }
+
+    let header_display = if layout.log_is_ticker() {
+        Display::None
+    } else {
+        Display::Flex
+    };
+    for mut header in headers.iter_mut() {
+        if header.display != header_display {
+            header.display = header_display;
+        }
+    }
+    *built = Some((layout.profile, touch));
+}
+
+/// Buttons of the action bar for `profile`
+fn spawn_action_buttons(parent: &mut ChildSpawnerCommands, profile: LayoutProfile, touch: f32) {
+    let (height, font_size) = match profile {
+        LayoutProfile::Landscape => (LANDSCAPE_BUTTON_HEIGHT, FontSize::Small.to_pixels()),
+        LayoutProfile::Portrait => (touch, FontSize::Medium.to_pixels()),
+    };
+    for (label, action) in ACTIONS {
+        // The scanner is always on screen in landscape
+        if profile == LayoutProfile::Landscape && action == HudAction::ToggleMiniMap {
+            continue;
+        }
+        parent
+            .spawn((
+                Button,
+                Node {
+                    min_width: Val::Px(height),
+                    min_height: Val::Px(height),
+                    padding: UiRect::horizontal(Val::Px(8.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BackgroundColor(PANEL_BACKGROUND),
+                BorderColor(SCANNER_GRID),
+                action,
+                Name::new(format!("Action_{}", label)),
+            ))
+            .with_children(|button| {
+                button.spawn((
+                    Text::new(label),
+                    TextFont {
+                        font_size,
+                        ..default()
+                    },
+                    TextColor(PRIMARY_TEXT),
+                ));
+            });
+    }
+}
+
+/// Keep the stat bar's readout current
+fn update_stat_bar_system(
+    player_resource: Res<PlayerResource>,
+    day_night: Option<Res<DayNightCycle>>,
+    calendar: Option<Res<GameCalendar>>,
+    mut texts: Query<&mut Text, With<StatBarText>>,
+) {
+    let Ok(mut text) = texts.single_mut() else {
+        return;
+    };
+    let readout = match player_resource.get_player() {
+        Some(player) => {
+            let mut readout = format!(
+                "THRUST {}/{} | PILOT LV {}",
+                player.movement_points(),
+                player.max_movement_points(),
+                player.level()
+            );
+            if let Some(calendar) = &calendar {
+                readout.push_str(&format!(" | {}", calendar.label()));
+            }
+            if let Some(cycle) = &day_night {
+                readout.push_str(&format!(" | {}", cycle.clock_readout()));
+            }
+            readout
+        }
+        None => "SHIP SYSTEMS OFFLINE".to_string(),
+    };
+    if text.0 != readout {
+        text.0 = readout;
+    }
+}
+
+/// Spawn the stat bar and the empty action bar under the HUD root
+///
+/// The action bar's buttons are added once the profile is known.
+pub fn spawn_hud_bars(parent: &mut ChildSpawnerCommands) {
+    let layout = HudLayout::default();
+    let touch = touch_target_size(1.0);
+    parent
+        .spawn((
+            slot_node(HudSlot::StatBar, &layout, touch),
+            BackgroundColor(PANEL_BACKGROUND),
+            HudSlot::StatBar,
+            Name::new("StatBar"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Regular.to_pixels(),
+                    ..default()
+                },
+                TextColor(SECONDARY_TEXT),
+                StatBarText,
+            ));
+        });
+    parent.spawn((
+        slot_node(HudSlot::ActionBar, &layout, touch),
+        HudSlot::ActionBar,
+        Name::new("ActionBar"),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_profile_only_flips_past_the_thresholds() {
+        use LayoutProfile::{Landscape, Portrait};
+
+        assert_eq!(LayoutProfile::classify(16.0 / 9.0, Portrait), Landscape);
+        assert_eq!(LayoutProfile::classify(9.0 / 16.0, Landscape), Portrait);
+        // A nearly square window keeps whichever profile it had
+        assert_eq!(LayoutProfile::classify(1.0, Landscape), Landscape);
+        assert_eq!(LayoutProfile::classify(1.0, Portrait), Portrait);
+
+        // Rotating through square does not flap
+        let mut profile = Landscape;
+        let mut flips = 0;
+        for aspect in [1.6, 1.05, 0.95, 1.05, 0.85, 0.95, 1.05, 0.95, 1.2] {
+            let next = LayoutProfile::classify(aspect, profile);
+            flips += (next != profile) as u32;
+            profile = next;
+        }
+        assert_eq!(flips, 2);
+        assert_eq!(profile, Landscape);
+    }
+
+    #[test]
+    fn transient_state_survives_a_rebuild() {
+        let mut world = World::new();
+        let mut layout = HudLayout {
+            minimap_open: true,
+            ..default()
+        };
+        let log = world
+            .spawn((slot_node(HudSlot::Log, &layout, 44.0), HudSlot::Log))
+            .id();
+        let log_area = world
+            .spawn((
+                ScrollPosition {
+                    offset_x: 0.0,
+                    offset_y: 120.0,
+                },
+                ChildOf(log),
+            ))
+            .id();
+        let minimap = world
+            .spawn((
+                slot_node(HudSlot::MiniMap, &layout, 44.0),
+                HudSlot::MiniMap,
+                Visibility::Hidden,
+            ))
+            .id();
+        world.insert_resource(UiScale(1.0));
+        world.insert_resource(layout.clone());
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(apply_hud_layout_system);
+        schedule.run(&mut world);
+
+        layout.profile = LayoutProfile::Portrait;
+        world.insert_resource(layout.clone());
+        schedule.run(&mut world);
+
+        // The log was laid out again, but what the player left is kept
+        let scroll = world.get::<ScrollPosition>(log_area).unwrap();
+        assert_eq!(scroll.offset_y, 120.0);
+        assert_eq!(world.get::<Visibility>(minimap), Some(&Visibility::Hidden));
+        assert_eq!(
+            world.get::<Node>(log),
+            Some(&slot_node(HudSlot::Log, &layout, 44.0))
+        );
+        let mut transient = TransientUiState::default();
+        transient.record_scroll(HudSlot::Log, 0, Vec2::new(0.0, 120.0));
+        transient.record_visibility(HudSlot::MiniMap, Visibility::Hidden);
+        assert_eq!(
+            transient.scroll(HudSlot::Log, 0),
+            Some(Vec2::new(0.0, 120.0))
+        );
+        assert_eq!(transient.scroll(HudSlot::Log, 1), None);
+        assert_eq!(
+            transient.visibility(HudSlot::MiniMap),
+            Some(Visibility::Hidden)
+        );
+        assert!(world.resource::<HudLayout>().minimap_open);
+    }
+
+    #[test]
+    fn touch_targets_never_drop_below_the_minimum() {
+        // On screen a target is its UI size times the UI scale
+        for ui_scale in [0.5, 0.75, 1.0] {
+            let on_screen = touch_target_size(ui_scale) * ui_scale;
+            assert!((on_screen - MIN_TOUCH_TARGET_PX).abs() < 1e-3);
+        }
+        assert_eq!(touch_target_size(0.5), 88.0);
+        assert_eq!(touch_target_size(2.0) * 2.0, 88.0);
+        assert_eq!(touch_target_size(1.5), MIN_TOUCH_TARGET_PX);
+
+        let portrait = HudLayout {
+            profile: LayoutProfile::Portrait,
+            ..default()
+        };
+        let grid = slot_node(HudSlot::ActionBar, &portrait, touch_target_size(0.75));
+        assert_eq!(grid.grid_auto_rows, vec![GridTrack::px(44.0 / 0.75)]);
+    }
+}
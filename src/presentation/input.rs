@@ -5,10 +5,11 @@
 //! raw input events and game logic.
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Game actions that can be triggered by user input
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum GameAction {
     /// Move player up
     MoveUp,
@@ -36,6 +37,74 @@ pub enum GameAction {
     Confirm,
     /// Cancel action (Escape)
     Cancel,
+    /// Roll the dice
+    RollDice,
+    /// Open the base
+    OpenBase,
+    /// Open the quest log
+    OpenQuests,
+    /// Open the inventory
+    OpenInventory,
+}
+
+impl GameAction {
+    /// Actions the controls screen lets the player rebind, in its order
+    pub const REBINDABLE: [GameAction; 9] = [
+        GameAction::MoveUp,
+        GameAction::MoveDown,
+        GameAction::MoveLeft,
+        GameAction::MoveRight,
+        GameAction::RollDice,
+        GameAction::OpenBase,
+        GameAction::OpenQuests,
+        GameAction::OpenInventory,
+        GameAction::TogglePause,
+    ];
+
+    /// Display name
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameAction::MoveUp => "Move up",
+            GameAction::MoveDown => "Move down",
+            GameAction::MoveLeft => "Move left",
+            GameAction::MoveRight => "Move right",
+            GameAction::TogglePause => "Pause",
+            GameAction::StartGame => "Start game",
+            GameAction::RestartGame => "Restart",
+            GameAction::ReturnToMenu => "Main menu",
+            GameAction::EnterSettings => "Settings",
+            GameAction::ExitSettings => "Leave settings",
+            GameAction::QuitApp => "Quit",
+            GameAction::Confirm => "Confirm",
+            GameAction::Cancel => "Cancel",
+            GameAction::RollDice => "Roll dice",
+            GameAction::OpenBase => "Base",
+            GameAction::OpenQuests => "Quests",
+            GameAction::OpenInventory => "Inventory",
+        }
+    }
+
+    /// Check if this action steps the player
+    pub fn is_movement(&self) -> bool {
+        matches!(
+            self,
+            GameAction::MoveUp
+                | GameAction::MoveDown
+                | GameAction::MoveLeft
+                | GameAction::MoveRight
+        )
+    }
+}
+
+/// Short name of a key, e.g. `W`, `7` or `ArrowUp`
+pub fn key_label(key: KeyCode) -> String {
+    let name = format!("{:?}", key);
+    ["Key", "Digit"]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .filter(|rest| rest.len() == 1)
+        .map(str::to_string)
+        .unwrap_or(name)
 }
 
 /// Input mapper for converting key presses to game actions
@@ -67,12 +136,13 @@ impl InputMapper {
         // Action keys
         key_bindings.insert(KeyCode::Escape, GameAction::TogglePause);
         key_bindings.insert(KeyCode::KeyP, GameAction::TogglePause);
-        key_bindings.insert(KeyCode::Space, GameAction::Confirm);
+        key_bindings.insert(KeyCode::Space, GameAction::RollDice);
         key_bindings.insert(KeyCode::Enter, GameAction::Confirm);
+        key_bindings.insert(KeyCode::KeyB, GameAction::OpenBase);
+        key_bindings.insert(KeyCode::KeyQ, GameAction::OpenQuests);
+        key_bindings.insert(KeyCode::KeyI, GameAction::OpenInventory);
         key_bindings.insert(KeyCode::KeyR, GameAction::RestartGame);
         key_bindings.insert(KeyCode::KeyM, GameAction::ReturnToMenu);
-        key_bindings.insert(KeyCode::KeyS, GameAction::EnterSettings);
-        key_bindings.insert(KeyCode::KeyQ, GameAction::QuitApp);
 
         Self {
             key_bindings,
@@ -81,6 +151,28 @@ impl InputMapper {
         }
     }
 
+    /// Create an input mapper from a list of key bindings
+    pub fn from_bindings<I>(bindings: I) -> Self
+    where
+        I: IntoIterator<Item = (KeyCode, GameAction)>,
+    {
+        Self {
+            key_bindings: bindings.into_iter().collect(),
+            ..Self::new()
+        }
+    }
+
+    /// All key bindings in a stable order
+    pub fn bindings(&self) -> Vec<(KeyCode, GameAction)> {
+        let mut bindings: Vec<(KeyCode, GameAction)> = self
+            .key_bindings
+            .iter()
+            .map(|(key, action)| (*key, *action))
+            .collect();
+        bindings.sort_by_key(|(key, _)| format!("{:?}", key));
+        bindings
+    }
+
     /// Get game action for a key code
     pub fn get_action(&self, key_code: &KeyCode) -> Option<&GameAction> {
         self.key_bindings.get(key_code)
@@ -99,6 +191,38 @@ impl InputMapper {
         self.key_bindings.insert(key_code, action);
     }
 
+    /// Make `key` the one key for `action`
+    ///
+    /// Returns the other action the key was taken from, if any.
+    pub fn rebind(&mut self, action: GameAction, key_code: KeyCode) -> Option<GameAction> {
+        self.clear_action_bindings(&action);
+        self.key_bindings
+            .insert(key_code, action)
+            .filter(|previous| *previous != action)
+    }
+
+    /// Check if a key bound to `action` was just pressed
+    pub fn just_pressed(&self, keyboard_input: &ButtonInput<KeyCode>, action: GameAction) -> bool {
+        self.input_enabled
+            && keyboard_input
+                .get_just_pressed()
+                .any(|key| self.key_bindings.get(key) == Some(&action))
+    }
+
+    /// Movement action of a key just pressed, if any
+    pub fn just_pressed_movement(
+        &self,
+        keyboard_input: &ButtonInput<KeyCode>,
+    ) -> Option<GameAction> {
+        if !self.input_enabled {
+            return None;
+        }
+        keyboard_input
+            .get_just_pressed()
+            .filter_map(|key| self.key_bindings.get(key).copied())
+            .find(GameAction::is_movement)
+    }
+
     /// Unbind a key
     pub fn unbind_key(&mut self, key_code: &KeyCode) {
         self.key_bindings.remove(key_code);
@@ -226,7 +350,7 @@ pub fn process_keyboard_input(
         if let Some(action) = input_mapper.get_action(key) {
             // Filter actions based on context
             if is_action_valid_in_context(action, &input_context) {
-                action_events.write(GameActionEvent::pressed(action.clone()));
+                action_events.write(GameActionEvent::pressed(*action));
             }
         }
     }
@@ -235,7 +359,7 @@ pub fn process_keyboard_input(
     for key in keyboard_input.get_just_released() {
         if let Some(action) = input_mapper.get_action(key) {
             if is_action_valid_in_context(action, &input_context) {
-                action_events.write(GameActionEvent::released(action.clone()));
+                action_events.write(GameActionEvent::released(*action));
             }
         }
     }
@@ -292,7 +416,9 @@ pub fn update_input_context(
         crate::presentation::RpgAppState::CharacterCreation => InputContext::MainMenu,
         crate::presentation::RpgAppState::Exploration => InputContext::InGame,
         crate::presentation::RpgAppState::Combat => InputContext::InGame,
+        crate::presentation::RpgAppState::EventResolution => InputContext::InGame,
         crate::presentation::RpgAppState::BaseManagement => InputContext::InGame,
+        crate::presentation::RpgAppState::ExpeditionPlanning => InputContext::Settings,
         crate::presentation::RpgAppState::QuestLog => InputContext::InGame,
         crate::presentation::RpgAppState::Inventory => InputContext::InGame,
         crate::presentation::RpgAppState::Settings => InputContext::Settings,
@@ -466,4 +592,38 @@ mod tests {
         assert_eq!(mapper.mouse_sensitivity, 1.0);
         assert!(mapper.input_enabled);
     }
+
+    #[test]
+    fn input_mapper_resolves_every_key_of_an_action() {
+        let mapper = InputMapper::new();
+        for key in [KeyCode::KeyW, KeyCode::ArrowUp] {
+            let mut keyboard = ButtonInput::<KeyCode>::default();
+            keyboard.press(key);
+            assert!(mapper.just_pressed(&keyboard, GameAction::MoveUp));
+            assert!(!mapper.just_pressed(&keyboard, GameAction::MoveDown));
+            assert_eq!(
+                mapper.just_pressed_movement(&keyboard),
+                Some(GameAction::MoveUp)
+            );
+        }
+    }
+
+    #[test]
+    fn input_mapper_rebind_takes_the_key_over() {
+        let mut mapper = InputMapper::new();
+        assert_eq!(
+            mapper.rebind(GameAction::RollDice, KeyCode::KeyW),
+            Some(GameAction::MoveUp)
+        );
+        assert_eq!(
+            mapper.get_keys_for_action(&GameAction::RollDice),
+            vec![KeyCode::KeyW]
+        );
+        assert_eq!(
+            mapper.get_keys_for_action(&GameAction::MoveUp),
+            vec![KeyCode::ArrowUp]
+        );
+        assert!(!mapper.is_key_bound(&KeyCode::Space));
+        assert_eq!(mapper.rebind(GameAction::RollDice, KeyCode::KeyW), None);
+    }
 }
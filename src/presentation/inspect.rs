@@ -0,0 +1,512 @@
+//! Entity Inspection - Gizmos for where entities are drawn vs where they are
+//!
+//! A dev-tools overlay for chasing "drawn on one tile, standing on another"
+//! bugs. F4 toggles it. For every entity that moves smoothly, every terrain
+//! tile and every creature, it outlines the tile the entity logically
+//! stands on and draws a line from its transform to that tile: green when
+//! they agree within `INSPECT_TOLERANCE`, yellow while the entity is still
+//! moving there, red when they disagree. Entities other than tiles, and
+//! tiles that disagree, also get a floating label with their id and key
+//! component values. Only the ground plane is compared, since tiles are
+//! raised or sunk by terrain.
+//!
+//! Logical positions are converted with `tile_to_world_position`, the same
+//! conversion the renderer places tiles with. The control channel's
+//! `inspect` command dumps every component of one entity.
+
+use crate::domain::constants::PRIMARY_TEXT;
+use crate::domain::services::font_service::FontSize;
+use crate::domain::value_objects::Position3D;
+use crate::presentation::fauna::Fauna;
+use crate::presentation::map_renderer::{IsometricCamera, PlayerMarker, TerrainTile};
+use crate::presentation::movement::{tile_to_world_position, SmoothMovement};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Key that toggles the inspection overlay
+pub const INSPECT_KEY: KeyCode = KeyCode::F4;
+
+/// Ground distance, in world units, a transform may stray from its tile
+pub const INSPECT_TOLERANCE: f32 = 0.25;
+
+/// Half the width of the outline drawn around a tile
+const OUTLINE_HALF_WIDTH: f32 = 1.0;
+
+/// Height the outlines and lines are drawn at above the tile
+const GIZMO_LIFT: f32 = 0.05;
+
+/// Height above the entity its label is anchored at
+const LABEL_LIFT: f32 = 1.5;
+
+/// Plugin for the inspection overlay; only added to dev-tools builds
+pub struct InspectPlugin;
+
+impl Plugin for InspectPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InspectOverlay>().add_systems(
+            Update,
+            (
+                toggle_inspect_overlay,
+                draw_inspection_gizmos.run_if(|overlay: Res<InspectOverlay>| overlay.enabled),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Whether the overlay is shown, and the labels it has up
+#[derive(Resource, Debug, Default)]
+pub struct InspectOverlay {
+    enabled: bool,
+    /// Label of each labelled entity
+    labels: HashMap<Entity, Entity>,
+}
+
+impl InspectOverlay {
+    /// Check if the overlay is drawn
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+/// Marker for an inspection label
+#[derive(Component)]
+pub struct InspectLabel;
+
+/// What kind of entity is inspected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InspectedKind {
+    Player,
+    /// Anything else moving smoothly
+    Mover,
+    Tile,
+    Creature,
+}
+
+impl InspectedKind {
+    /// Name shown on labels
+    pub fn name(&self) -> &'static str {
+        match self {
+            InspectedKind::Player => "Player",
+            InspectedKind::Mover => "Mover",
+            InspectedKind::Tile => "Tile",
+            InspectedKind::Creature => "Creature",
+        }
+    }
+
+    /// Color of the tile outline
+    fn color(&self) -> Color {
+        match self {
+            InspectedKind::Player => Color::srgb(0.2, 0.8, 1.0),
+            InspectedKind::Mover => Color::srgb(0.7, 0.5, 1.0),
+            InspectedKind::Tile => Color::srgba(1.0, 1.0, 1.0, 0.25),
+            InspectedKind::Creature => Color::srgb(0.5, 1.0, 0.4),
+        }
+    }
+}
+
+/// How a transform compares to its logical tile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Agreement {
+    Agrees,
+    /// Still moving towards the tile
+    InTransit,
+    Disagrees,
+}
+
+impl Agreement {
+    /// Color of the line from the transform to the tile
+    fn color(&self) -> Color {
+        match self {
+            Agreement::Agrees => Color::srgb(0.2, 0.9, 0.3),
+            Agreement::InTransit => Color::srgb(1.0, 0.85, 0.2),
+            Agreement::Disagrees => Color::srgb(1.0, 0.2, 0.2),
+        }
+    }
+}
+
+/// Ground distance between a transform and where its tile is drawn
+pub fn ground_drift(logical: Position3D, translation: Vec3) -> f32 {
+    let expected = tile_to_world_position(logical);
+    Vec2::new(translation.x, translation.z).distance(Vec2::new(expected.x, expected.z))
+}
+
+/// Compare a transform to its tile; entities on their way are in transit
+pub fn agreement(logical: Position3D, translation: Vec3, moving: bool) -> Agreement {
+    if ground_drift(logical, translation) <= INSPECT_TOLERANCE {
+        Agreement::Agrees
+    } else if moving {
+        Agreement::InTransit
+    } else {
+        Agreement::Disagrees
+    }
+}
+
+/// Entity id as labels show it and the `inspect` command takes it, e.g. `12v1`
+pub fn entity_id(entity: Entity) -> String {
+    format!("{}v{}", entity.index(), entity.generation())
+}
+
+/// Read an entity id like `12v1`; a bare index means the first generation
+pub fn parse_entity(text: &str) -> Option<Entity> {
+    let text = text.trim();
+    let (index, generation) = match text.split_once('v') {
+        Some((index, generation)) => (index.parse::<u32>().ok()?, generation.parse::<u32>().ok()?),
+        None => (text.parse::<u32>().ok()?, 1),
+    };
+    Entity::try_from_bits(((generation as u64) << 32) | index as u64).ok()
+}
+
+/// One inspected entity: where it logically is and where it is drawn
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inspection {
+    pub entity: Entity,
+    pub kind: InspectedKind,
+    pub name: Option<String>,
+    pub logical: Position3D,
+    pub translation: Vec3,
+    pub moving: bool,
+    /// Key component values, one `key: value` line each
+    pub details: Vec<String>,
+}
+
+impl Inspection {
+    /// Inspect an entity from its components; none if it has nothing to check
+    pub fn of(
+        entity: Entity,
+        transform: &GlobalTransform,
+        name: Option<&Name>,
+        player: bool,
+        movement: Option<&SmoothMovement>,
+        tile: Option<&TerrainTile>,
+        fauna: Option<&Fauna>,
+    ) -> Option<Self> {
+        let (kind, logical, moving, details) = if let Some(movement) = movement {
+            let kind = if player {
+                InspectedKind::Player
+            } else {
+                InspectedKind::Mover
+            };
+            let details = vec![
+                format!("start: {}", position_text(movement.start_position)),
+                format!("progress: {:.2}", movement.progress),
+            ];
+            (kind, movement.target_position, movement.is_moving, details)
+        } else if let Some(fauna) = fauna {
+            let details = vec![
+                format!("kind: {:?}", fauna.kind),
+                format!("habitat: {}", fauna.habitat),
+            ];
+            (InspectedKind::Creature, fauna.tile.into(), false, details)
+        } else if let Some(tile) = tile {
+            let details = vec![
+                format!("terrain: {}", tile.terrain_type),
+                format!("explored: {}", tile.is_explored),
+            ];
+            (InspectedKind::Tile, tile.coordinate.into(), false, details)
+        } else {
+            return None;
+        };
+        Some(Self {
+            entity,
+            kind,
+            name: name.map(|name| name.as_str().to_string()),
+            logical,
+            translation: transform.translation(),
+            moving,
+            details,
+        })
+    }
+
+    /// How the transform compares to the logical tile
+    pub fn agreement(&self) -> Agreement {
+        agreement(self.logical, self.translation, self.moving)
+    }
+
+    /// Label lines: id and kind, both positions, then the details
+    pub fn label(&self) -> String {
+        let mut lines = vec![match &self.name {
+            Some(name) => format!(
+                "{} {} \"{}\"",
+                entity_id(self.entity),
+                self.kind.name(),
+                name
+            ),
+            None => format!("{} {}", entity_id(self.entity), self.kind.name()),
+        }];
+        lines.push(format!("logical: {}", position_text(self.logical)));
+        lines.push(format!(
+            "transform: ({:.2}, {:.2}, {:.2}) drift {:.2}{}",
+            self.translation.x,
+            self.translation.y,
+            self.translation.z,
+            ground_drift(self.logical, self.translation),
+            match self.agreement() {
+                Agreement::Agrees => "",
+                Agreement::InTransit => " (moving)",
+                Agreement::Disagrees => " MISMATCH",
+            }
+        ));
+        lines.extend(self.details.iter().cloned());
+        lines.join("\n")
+    }
+}
+
+fn position_text(position: Position3D) -> String {
+    format!("({}, {}, {})", position.x, position.y, position.z)
+}
+
+/// Every component of an entity by type name, for the `inspect` command,
+/// followed by its label when it is an inspected kind
+pub fn component_dump(world: &World, entity: Entity) -> Option<Vec<String>> {
+    let entity_ref = world.get_entity(entity).ok()?;
+    let mut lines: Vec<String> = entity_ref
+        .archetype()
+        .components()
+        .filter_map(|id| world.components().get_info(id))
+        .map(|info| info.name().to_string())
+        .collect();
+    lines.sort();
+
+    let inspection = entity_ref.get::<GlobalTransform>().and_then(|transform| {
+        Inspection::of(
+            entity,
+            transform,
+            entity_ref.get::<Name>(),
+            entity_ref.contains::<PlayerMarker>(),
+            entity_ref.get::<SmoothMovement>(),
+            entity_ref.get::<TerrainTile>(),
+            entity_ref.get::<Fauna>(),
+        )
+    });
+    if let Some(inspection) = inspection {
+        lines.push(String::new());
+        lines.extend(inspection.label().lines().map(str::to_string));
+    }
+    Some(lines)
+}
+
+/// Switch the overlay on F4; switching it off takes the labels down
+fn toggle_inspect_overlay(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<InspectOverlay>,
+) {
+    if !keyboard.just_pressed(INSPECT_KEY) {
+        return;
+    }
+    overlay.enabled = !overlay.enabled;
+    info!(
+        "🔍 Inspection overlay {}",
+        if overlay.enabled { "on" } else { "off" }
+    );
+    if !overlay.enabled {
+        for (_, label) in overlay.labels.drain() {
+            if let Ok(mut label) = commands.get_entity(label) {
+                label.try_despawn();
+            }
+        }
+    }
+}
+
+/// Draw the outlines, lines and labels of every inspected entity
+#[allow(clippy::type_complexity)]
+fn draw_inspection_gizmos(
+    mut commands: Commands,
+    mut gizmos: Gizmos,
+    mut overlay: ResMut<InspectOverlay>,
+    inspected: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            Option<&Name>,
+            Has<PlayerMarker>,
+            Option<&SmoothMovement>,
+            Option<&TerrainTile>,
+            Option<&Fauna>,
+        ),
+        Or<(With<SmoothMovement>, With<TerrainTile>, With<Fauna>)>,
+    >,
+    cameras: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+    mut labels: Query<(&mut Text, &mut Node), With<InspectLabel>>,
+) {
+    let camera = cameras.single().ok();
+    let mut labelled = Vec::new();
+
+    for (entity, transform, name, player, movement, tile, fauna) in inspected.iter() {
+        let Some(inspection) =
+            Inspection::of(entity, transform, name, player, movement, tile, fauna)
+        else {
+            continue;
+        };
+        let center = tile_to_world_position(inspection.logical) + Vec3::Y * GIZMO_LIFT;
+        let corner = |x: f32, z: f32| center + Vec3::new(x, 0.0, z) * OUTLINE_HALF_WIDTH;
+        gizmos.linestrip(
+            [
+                corner(-1.0, -1.0),
+                corner(1.0, -1.0),
+                corner(1.0, 1.0),
+                corner(-1.0, 1.0),
+                corner(-1.0, -1.0),
+            ],
+            inspection.kind.color(),
+        );
+        let agreement = inspection.agreement();
+        gizmos.line(inspection.translation, center, agreement.color());
+
+        // Tiles that are where they should be would bury the map in labels
+        if inspection.kind == InspectedKind::Tile && agreement == Agreement::Agrees {
+            continue;
+        }
+        let Some(screen) = camera.and_then(|(camera, camera_transform)| {
+            camera
+                .world_to_viewport(
+                    camera_transform,
+                    inspection.translation + Vec3::Y * LABEL_LIFT,
+                )
+                .ok()
+        }) else {
+            continue;
+        };
+        labelled.push(entity);
+        let content = inspection.label();
+        match overlay
+            .labels
+            .get(&entity)
+            .and_then(|label| labels.get_mut(*label).ok())
+        {
+            Some((mut text, mut node)) => {
+                if text.0 != content {
+                    text.0 = content;
+                }
+                node.left = Val::Px(screen.x);
+                node.top = Val::Px(screen.y);
+            }
+            None => {
+                let label = commands
+                    .spawn((
+                        Text::new(content),
+                        TextFont {
+                            font_size: FontSize::Small.to_pixels(),
+                            ..default()
+                        },
+                        TextColor(PRIMARY_TEXT),
+                        Node {
+                            position_type: PositionType::Absolute,
+                            left: Val::Px(screen.x),
+                            top: Val::Px(screen.y),
+                            ..default()
+                        },
+                        InspectLabel,
+                        Name::new("InspectLabel"),
+                    ))
+                    .id();
+                overlay.labels.insert(entity, label);
+            }
+        }
+    }
+
+    // Labels of entities gone, off screen or back in agreement
+    let stale: Vec<Entity> = overlay
+        .labels
+        .keys()
+        .filter(|entity| !labelled.contains(entity))
+        .copied()
+        .collect();
+    for entity in stale {
+        if let Some(label) = overlay.labels.remove(&entity) {
+            if let Ok(mut label) = commands.get_entity(label) {
+                label.try_despawn();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::VisibilityLevel;
+    use crate::domain::value_objects::{TerrainType, TileCoordinate};
+
+    #[test]
+    fn transforms_agree_within_the_tolerance_on_the_ground_plane() {
+        let tile = Position3D::new(3, -2, 0);
+        let center = tile_to_world_position(tile);
+        assert_eq!(agreement(tile, center, false), Agreement::Agrees);
+
+        // Just inside and just outside the tolerance, diagonally too
+        let inside = Vec3::new(INSPECT_TOLERANCE * 0.9, 0.0, 0.0);
+        assert_eq!(agreement(tile, center + inside, false), Agreement::Agrees);
+        let outside = Vec3::new(INSPECT_TOLERANCE * 0.8, 0.0, INSPECT_TOLERANCE * 0.8);
+        assert!(ground_drift(tile, center + outside) > INSPECT_TOLERANCE);
+        assert_eq!(
+            agreement(tile, center + outside, false),
+            Agreement::Disagrees
+        );
+
+        // Terrain height does not count; a moving entity is only on its way
+        assert_eq!(
+            agreement(tile, center + Vec3::Y * 3.0, false),
+            Agreement::Agrees
+        );
+        let one_tile_back = tile_to_world_position(Position3D::new(2, -2, 0));
+        assert_eq!(agreement(tile, one_tile_back, true), Agreement::InTransit);
+        assert_eq!(agreement(tile, one_tile_back, false), Agreement::Disagrees);
+    }
+
+    #[test]
+    fn labels_name_the_entity_and_its_key_values() {
+        let mut world = World::new();
+        let logical = Position3D::new(4, 1, 0);
+        let drawn = tile_to_world_position(Position3D::new(5, 1, 0));
+        let player = world
+            .spawn((
+                GlobalTransform::from_translation(drawn),
+                Name::new("Pilot"),
+                PlayerMarker,
+                SmoothMovement {
+                    target_position: logical,
+                    start_position: Position3D::new(3, 1, 0),
+                    ..default()
+                },
+            ))
+            .id();
+        let tile = world
+            .spawn((
+                GlobalTransform::from_translation(tile_to_world_position(logical)),
+                TerrainTile {
+                    coordinate: TileCoordinate::new(4, 1, 0),
+                    terrain_type: TerrainType::Forest,
+                    is_explored: true,
+                    visibility_level: VisibilityLevel::FullyVisible,
+                    tile_size: 2.0,
+                },
+            ))
+            .id();
+
+        let dump = component_dump(&world, player).unwrap();
+        let label: Vec<&str> = dump
+            .iter()
+            .skip_while(|line| !line.is_empty())
+            .skip(1)
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            label,
+            vec![
+                format!("{} Player \"Pilot\"", entity_id(player)).as_str(),
+                "logical: (4, 1, 0)",
+                "transform: (11.00, 0.00, 2.20) drift 2.20 MISMATCH",
+                "start: (3, 1, 0)",
+                "progress: 1.00",
+            ]
+        );
+        assert!(dump.iter().any(|line| line.ends_with("PlayerMarker")));
+
+        let dump = component_dump(&world, tile).unwrap();
+        assert!(dump.contains(&format!("{} Tile", entity_id(tile))));
+        assert!(dump.contains(&"terrain: Forest".to_string()));
+        assert_eq!(parse_entity(&entity_id(tile)), Some(tile));
+        assert!(parse_entity("nope").is_none());
+    }
+}
@@ -0,0 +1,803 @@
+//! Inventory Panel - Cargo list, sort modes and bulk transfers
+//!
+//! The inventory screen lists the player's cargo in the chosen sort order
+//! and badges entries whose amount changed since the panel was opened. On
+//! the base tile it also offers bulk transfers to and from base storage.
+//! Each transfer is applied as a whole through the player's resource
+//! methods and reported with a single log entry, including anything that
+//! had to stay behind, and relics carried out of ruins can be handed in
+//! there for experience. Finds in the field go into the player's inventory up
+//! to its carrying capacity, with a warning for what the full hold leaves
+//! behind. Carried items and consumables are listed below the cargo, and
+//! consumables can be crafted from it anywhere. The character section shows
+//! the stats with gear applied next to every piece of found gear, which can
+//! be selected, equipped, taken off or salvaged for metal.
+//!
+//! The panel scrolls with the mouse wheel or Page Up/Down and is rebuilt
+//! as resource and player changes arrive rather than every frame.
+
+use crate::domain::constants::{
+    INVENTORY_MAX_RESERVE_DAYS, PANEL_BACKGROUND, PRIMARY_TEXT, RUIN_RELIC_EXPERIENCE,
+    RUIN_RELIC_ID,
+};
+use crate::domain::entities::{Base, Inventory};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{
+    stat_line, BulkTransfer, ConsumableKind, Consumables, Gear, InventoryEntry, TransferPlan,
+};
+use crate::domain::value_objects::resources::ResourceCollection;
+use crate::domain::{DomainResult, PlayerStats};
+use crate::infrastructure::bevy::resources::{BaseResource, GameStatsResource, PlayerResource};
+use crate::infrastructure::settings::InventorySettings;
+use crate::presentation::game_event_logger::{
+    forward_player_changes, PlayerChangedEvent, ResourceChangedEvent,
+};
+use crate::presentation::RpgAppState;
+use bevy::input::mouse::{AccumulatedMouseScroll, MouseScrollUnit};
+use bevy::prelude::*;
+
+/// Pixels the panel scrolls per mouse wheel line
+const PANEL_SCROLL_LINE: f32 = 18.0;
+
+/// Pixels the panel scrolls per Page Up/Down
+const PANEL_SCROLL_PAGE: f32 = 240.0;
+
+/// Plugin for the inventory panel
+pub struct InventoryPlugin;
+
+impl Plugin for InventoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InventorySettings>()
+            .init_resource::<InventorySnapshot>()
+            .init_resource::<GearCursor>()
+            .add_event::<ResourceChangedEvent>()
+            .add_event::<PlayerChangedEvent>()
+            .add_systems(Startup, setup_inventory_panel)
+            .add_systems(OnEnter(RpgAppState::Inventory), snapshot_inventory)
+            .add_systems(
+                Update,
+                (
+                    inventory_input_system.before(forward_player_changes),
+                    (scroll_inventory_panel, update_inventory_panel)
+                        .chain()
+                        .after(forward_player_changes),
+                ),
+            );
+    }
+}
+
+/// Cargo as it was when the panel was opened
+#[derive(Resource, Debug, Clone, Default)]
+pub struct InventorySnapshot {
+    pub opened_with: ResourceCollection,
+}
+
+/// Piece of gear selected in the character section
+#[derive(Resource, Debug, Clone, Default)]
+pub struct GearCursor {
+    pub selected: Option<u32>,
+}
+
+impl GearCursor {
+    /// Select the piece after the current one, wrapping around
+    pub fn next(&mut self, gear: &Gear) {
+        let ids: Vec<u32> = gear.items().iter().map(|item| item.id).collect();
+        let position = self
+            .selected
+            .and_then(|id| ids.iter().position(|candidate| *candidate == id));
+        self.selected = match position {
+            Some(index) => ids.get((index + 1) % ids.len()).copied(),
+            None => ids.first().copied(),
+        };
+    }
+
+    /// The selected piece, if it is still owned
+    pub fn current(&self, gear: &Gear) -> Option<u32> {
+        self.selected
+            .filter(|id| gear.items().iter().any(|item| item.id == *id))
+    }
+}
+
+/// Marker for the inventory panel
+#[derive(Component)]
+pub struct InventoryPanel;
+
+/// Marker for the inventory panel text
+#[derive(Component)]
+pub struct InventoryPanelText;
+
+fn setup_inventory_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(15.0),
+                top: Val::Px(80.0),
+                width: Val::Px(460.0),
+                max_height: Val::Percent(80.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                flex_direction: FlexDirection::Column,
+                overflow: Overflow::scroll_y(),
+                ..default()
+            },
+            ScrollPosition::default(),
+            BackgroundColor(PANEL_BACKGROUND),
+            Visibility::Hidden,
+            InventoryPanel,
+            Name::new("InventoryPanel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                InventoryPanelText,
+            ));
+        });
+}
+
+fn snapshot_inventory(
+    player_resource: Res<PlayerResource>,
+    mut snapshot: ResMut<InventorySnapshot>,
+) {
+    snapshot.opened_with = player_resource
+        .get_player()
+        .map(|player| player.resources().clone())
+        .unwrap_or_default();
+}
+
+/// Cycle the sort mode, adjust the reserve and run bulk transfers
+fn inventory_input_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_state: Res<State<RpgAppState>>,
+    mut settings: ResMut<InventorySettings>,
+    mut base_resource: ResMut<BaseResource>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut gear_cursor: ResMut<GearCursor>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    if *current_state.get() != RpgAppState::Inventory {
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::Tab) {
+        settings.sort_mode = settings.sort_mode.next();
+    }
+    if keyboard.just_pressed(KeyCode::BracketLeft) && settings.reserve_days > 0 {
+        settings.reserve_days -= 1;
+    }
+    if keyboard.just_pressed(KeyCode::BracketRight)
+        && settings.reserve_days < INVENTORY_MAX_RESERVE_DAYS
+    {
+        settings.reserve_days += 1;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyC) {
+        let kind = ConsumableKind::ScoutProbe;
+        match player_resource.craft_consumable(kind) {
+            Ok(count) => game_log.log_message(
+                format!("🔧 Crafted a {} ({} carried)", kind.name(), count),
+                GameLogType::Resources,
+            ),
+            Err(e) => game_log.log_message(
+                format!("Cannot craft a {}: {}", kind.name(), e),
+                GameLogType::Warning,
+            ),
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyG) {
+        if let Some(player) = player_resource.get_player() {
+            gear_cursor.next(player.gear());
+        }
+    }
+    if keyboard.just_pressed(KeyCode::KeyE) {
+        toggle_selected_gear(&mut player_resource, &gear_cursor, &mut game_log);
+    }
+    if keyboard.just_pressed(KeyCode::Delete) {
+        salvage_selected_gear(&mut player_resource, &mut gear_cursor, &mut game_log);
+    }
+
+    let on_base = player_resource.player_position().is_some()
+        && player_resource.player_position() == base_resource.base_position();
+    let Some(base) = base_resource.base_mut().filter(|_| on_base) else {
+        return;
+    };
+    if keyboard.just_pressed(KeyCode::Digit4) {
+        hand_in_relics(&mut player_resource, &mut game_log);
+        return;
+    }
+
+    let result = if keyboard.just_pressed(KeyCode::Digit1) {
+        deposit(&mut player_resource, base, &ResourceCollection::new())
+            .map(|plan| ("Deposited", plan))
+    } else if keyboard.just_pressed(KeyCode::Digit2) {
+        let reserve = BulkTransfer::supply_reserve(settings.reserve_days);
+        deposit(&mut player_resource, base, &reserve).map(|plan| ("Deposited", plan))
+    } else if keyboard.just_pressed(KeyCode::Digit3) {
+        withdraw(&mut player_resource, base).map(|plan| ("Withdrew", plan))
+    } else {
+        return;
+    };
+
+    match result {
+        Ok((verb, plan)) => {
+            game_log.log_message(transfer_summary(verb, &plan), GameLogType::Resources)
+        }
+        Err(e) => game_log.log_message(format!("{}", e), GameLogType::Warning),
+    }
+}
+
+/// Equip the selected piece, or take it off if it is worn
+fn toggle_selected_gear(
+    player_resource: &mut PlayerResource,
+    gear_cursor: &GearCursor,
+    game_log: &mut GameLogService,
+) {
+    let Some((worn, item)) = player_resource.get_player().and_then(|player| {
+        let id = gear_cursor.current(player.gear())?;
+        let item = player
+            .gear()
+            .items()
+            .into_iter()
+            .find(|item| item.id == id)?
+            .clone();
+        Some((player.gear().is_equipped(id), item))
+    }) else {
+        return;
+    };
+    if worn {
+        player_resource.unequip_gear(item.slot());
+        game_log.log_message(
+            format!("🧰 Took off the {}", item.name),
+            GameLogType::System,
+        );
+        return;
+    }
+    match player_resource.equip_gear(item.id) {
+        Ok(_) => game_log.log_message(
+            format!("🧰 Equipped the {} ({})", item.name, item.describe()),
+            GameLogType::System,
+        ),
+        Err(e) => game_log.log_message(format!("{}", e), GameLogType::Warning),
+    }
+}
+
+/// Take the selected piece apart for metal
+fn salvage_selected_gear(
+    player_resource: &mut PlayerResource,
+    gear_cursor: &mut GearCursor,
+    game_log: &mut GameLogService,
+) {
+    let Some(id) = player_resource
+        .get_player()
+        .and_then(|player| gear_cursor.current(player.gear()))
+    else {
+        return;
+    };
+    match player_resource.salvage_gear(id) {
+        Ok(salvage) => {
+            gear_cursor.selected = None;
+            game_log.log_message(
+                format!("🔧 Salvaged gear for {}", salvage),
+                GameLogType::Resources,
+            );
+        }
+        Err(e) => game_log.log_message(format!("{}", e), GameLogType::Warning),
+    }
+}
+
+/// Hand every carried relic in at the base for experience
+fn hand_in_relics(player_resource: &mut PlayerResource, game_log: &mut GameLogService) {
+    let count = player_resource
+        .get_player()
+        .map_or(0, |player| player.inventory().item_count(RUIN_RELIC_ID));
+    if count == 0 {
+        game_log.log_message(
+            "No relics to hand in - they are found in ruins".to_string(),
+            GameLogType::Warning,
+        );
+        return;
+    }
+    let points = count * RUIN_RELIC_EXPERIENCE;
+    let handed_in = player_resource
+        .remove_item(RUIN_RELIC_ID, count)
+        .and_then(|_| player_resource.grant_experience(points));
+    match handed_in {
+        Ok(_) => game_log.log_message(
+            format!("🏺 Handed in {} relics for {} XP", count, points),
+            GameLogType::Resources,
+        ),
+        Err(e) => game_log.log_message(format!("{}", e), GameLogType::Warning),
+    }
+}
+
+/// Move the cargo above `reserve` into base storage
+fn deposit(
+    player_resource: &mut PlayerResource,
+    base: &mut Base,
+    reserve: &ResourceCollection,
+) -> DomainResult<TransferPlan> {
+    let cargo = player_resource
+        .get_player()
+        .map(|player| player.resources().clone())
+        .unwrap_or_default();
+    let plan = BulkTransfer::plan_deposit(&cargo, reserve, base.free_capacity());
+    if plan.is_empty() {
+        return Ok(plan);
+    }
+    player_resource.try_pay_resources(&plan.moved)?;
+    if let Err(e) = base.store_resources(&plan.moved) {
+        player_resource.add_resources(&plan.moved);
+        return Err(e);
+    }
+    Ok(plan)
+}
+
+/// Fill the cargo up to carrying capacity from base storage
+fn withdraw(player_resource: &mut PlayerResource, base: &mut Base) -> DomainResult<TransferPlan> {
+    let plan =
+        BulkTransfer::plan_withdraw(base.resources(), player_resource.free_carrying_capacity());
+    if plan.is_empty() {
+        return Ok(plan);
+    }
+    base.withdraw_resources(&plan.moved)?;
+    player_resource.add_resources(&plan.moved);
+    Ok(plan)
+}
+
+/// Pick up a find in the field, warning about whatever the full hold
+/// leaves behind; returns what was left
+pub fn pick_up_find(
+    player_resource: &mut PlayerResource,
+    found: &ResourceCollection,
+    game_log: &mut GameLogService,
+) -> ResourceCollection {
+    let dropped = player_resource.pick_up(found);
+    if !dropped.is_empty() {
+        game_log.log_message(
+            format!("🎒 Cargo hold full - left behind: {}", dropped),
+            GameLogType::Warning,
+        );
+    }
+    dropped
+}
+
+/// Pick up a find and count only what was kept as gathered; returns what was left
+pub fn gather_find(
+    player_resource: &mut PlayerResource,
+    found: &ResourceCollection,
+    game_stats: &mut GameStatsResource,
+    game_log: &mut GameLogService,
+) -> ResourceCollection {
+    let dropped = pick_up_find(player_resource, found, game_log);
+    for amount in found.amounts() {
+        let kept = amount.amount - dropped.get_amount(amount.resource_type);
+        if kept > 0 {
+            game_stats.record_resource_gather(amount.resource_type, kept);
+        }
+    }
+    dropped
+}
+
+/// One log line for a whole transfer
+fn transfer_summary(verb: &str, plan: &TransferPlan) -> String {
+    if plan.is_empty() && !plan.is_partial() {
+        return "📦 Nothing to transfer".to_string();
+    }
+    let mut summary = format!("📦 {}: {}", verb, plan.moved);
+    if plan.is_partial() {
+        summary.push_str(&format!(" | No room for: {}", plan.left_behind));
+    }
+    summary
+}
+
+/// Scroll the panel with the mouse wheel or Page Up/Down
+fn scroll_inventory_panel(
+    current_state: Res<State<RpgAppState>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    mut panel_query: Query<&mut ScrollPosition, With<InventoryPanel>>,
+) {
+    if *current_state.get() != RpgAppState::Inventory {
+        return;
+    }
+    let Ok(mut scroll) = panel_query.single_mut() else {
+        return;
+    };
+    let mut delta = -mouse_scroll.delta.y;
+    if mouse_scroll.unit == MouseScrollUnit::Line {
+        delta *= PANEL_SCROLL_LINE;
+    }
+    if keyboard.just_pressed(KeyCode::PageDown) {
+        delta += PANEL_SCROLL_PAGE;
+    }
+    if keyboard.just_pressed(KeyCode::PageUp) {
+        delta -= PANEL_SCROLL_PAGE;
+    }
+    // Layout clamps the far end to the content height
+    if delta != 0.0 {
+        scroll.offset_y = (scroll.offset_y + delta).max(0.0);
+    }
+}
+
+/// Show the panel on the inventory screen
+///
+/// The text is rebuilt when resource or player changes arrive, or when the
+/// panel's own state changes, so an open panel follows pickups live.
+#[allow(clippy::too_many_arguments)]
+fn update_inventory_panel(
+    current_state: Res<State<RpgAppState>>,
+    settings: Res<InventorySettings>,
+    snapshot: Res<InventorySnapshot>,
+    player_resource: Res<PlayerResource>,
+    base_resource: Res<BaseResource>,
+    gear_cursor: Res<GearCursor>,
+    mut resource_events: EventReader<ResourceChangedEvent>,
+    mut player_events: EventReader<PlayerChangedEvent>,
+    mut panel_query: Query<(&mut Visibility, &mut ScrollPosition), With<InventoryPanel>>,
+    mut text_query: Query<&mut Text, With<InventoryPanelText>>,
+) {
+    let resources_changed = !resource_events.is_empty();
+    let player_changed = !player_events.is_empty();
+    resource_events.clear();
+    player_events.clear();
+
+    let Ok((mut visibility, mut scroll)) = panel_query.single_mut() else {
+        return;
+    };
+    if current_state.is_changed() {
+        *scroll = ScrollPosition::default();
+    }
+    let player = player_resource
+        .get_player()
+        .filter(|_| *current_state.get() == RpgAppState::Inventory);
+    let wanted = if player.is_some() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    if *visibility != wanted {
+        *visibility = wanted;
+    }
+
+    let Some(player) = player else {
+        return;
+    };
+    let refresh = resources_changed
+        || player_changed
+        || current_state.is_changed()
+        || settings.is_changed()
+        || snapshot.is_changed()
+        || gear_cursor.is_changed()
+        || base_resource.is_changed();
+    if !refresh {
+        return;
+    }
+    let base = base_resource
+        .base()
+        .filter(|base| base.position() == player.position());
+    if let Ok(mut text) = text_query.single_mut() {
+        let content = inventory_panel_text(
+            player.inventory(),
+            player_resource.carrying_capacity(),
+            &snapshot.opened_with,
+            player.consumables(),
+            &character_lines(
+                player.stats(),
+                &player.derived_stats(),
+                player.gear(),
+                gear_cursor.current(player.gear()),
+            ),
+            &settings,
+            base,
+        );
+        if **text != content {
+            **text = content;
+        }
+    }
+}
+
+fn inventory_panel_text(
+    inventory: &Inventory,
+    carrying_capacity: u32,
+    opened_with: &ResourceCollection,
+    consumables: &Consumables,
+    character: &[String],
+    settings: &InventorySettings,
+    base: Option<&Base>,
+) -> String {
+    let mut lines = vec![format!(
+        "INVENTORY - {}/{} carried | Sort: {}",
+        inventory.weight(),
+        carrying_capacity,
+        settings.sort_mode.label()
+    )];
+
+    let entries = InventoryEntry::list(inventory.resources(), opened_with, settings.sort_mode);
+    if entries.is_empty() {
+        lines.push("  Cargo empty".to_string());
+    }
+    for entry in entries {
+        let badge = if entry.recently_changed() {
+            format!("  [{:+}]", entry.change)
+        } else {
+            String::new()
+        };
+        lines.push(format!(
+            "  {} {:<13} {:>6}{}",
+            entry.resource_type.icon(),
+            entry.resource_type.to_string(),
+            entry.amount,
+            badge
+        ));
+    }
+
+    if !inventory.items().is_empty() {
+        lines.push(String::new());
+    }
+    for item in inventory.items() {
+        lines.push(format!(
+            "  {:<15} x{:<4} {:>4} wt",
+            item.item_id,
+            item.count,
+            item.weight()
+        ));
+    }
+
+    lines.push(String::new());
+    for kind in ConsumableKind::all() {
+        let cost: Vec<String> = kind
+            .craft_inputs()
+            .iter()
+            .map(|(resource_type, amount)| format!("{} {}", amount, resource_type))
+            .collect();
+        lines.push(format!(
+            "  {:<15} {:>4}  (C: Craft for {})",
+            kind.name(),
+            consumables.count(kind),
+            cost.join(", ")
+        ));
+    }
+
+    lines.push(String::new());
+    lines.extend(character.iter().cloned());
+
+    lines.push(String::new());
+    match base {
+        Some(base) => {
+            lines.push(format!(
+                "Base storage: {}/{}",
+                base.storage_capacity() - base.free_capacity(),
+                base.storage_capacity()
+            ));
+            lines.push("1: Deposit all | 3: Withdraw to capacity".to_string());
+            if inventory.has_item(RUIN_RELIC_ID, 1) {
+                lines.push("4: Hand in relics for experience".to_string());
+            }
+            lines.push(format!(
+                "2: Deposit all except {} days of Food/Energy ([ ] to change)",
+                settings.reserve_days
+            ));
+        }
+        None => lines.push("Bulk transfers are available on the base tile".to_string()),
+    }
+    lines.push("TAB: Sort | PGUP/PGDN: Scroll | ESC: Close".to_string());
+    lines.join("\n")
+}
+
+/// Stats with gear applied, and every piece of gear with the selection marked
+fn character_lines(
+    base: &PlayerStats,
+    derived: &PlayerStats,
+    gear: &Gear,
+    selected: Option<u32>,
+) -> Vec<String> {
+    let mut lines = vec![
+        "CHARACTER".to_string(),
+        format!(
+            "  STR {} | DEX {} | INT {}",
+            stat_line(base.strength, derived.strength),
+            stat_line(base.dexterity, derived.dexterity),
+            stat_line(base.intelligence, derived.intelligence)
+        ),
+        format!(
+            "  CHA {} | LCK {} | END {}",
+            stat_line(base.charisma, derived.charisma),
+            stat_line(base.luck, derived.luck),
+            stat_line(base.endurance, derived.endurance)
+        ),
+    ];
+
+    let items = gear.items();
+    if items.is_empty() {
+        lines.push("  No gear found yet".to_string());
+    }
+    for item in items {
+        let cursor = if selected == Some(item.id) { ">" } else { " " };
+        let worn = if gear.is_equipped(item.id) {
+            format!("[{}]", item.slot().name())
+        } else {
+            String::new()
+        };
+        lines.push(format!(
+            " {} {:<8} {} ({}, {})",
+            cursor,
+            worn,
+            item.name,
+            item.rarity.name(),
+            item.describe()
+        ));
+    }
+    lines.push("G: Select gear | E: Equip/Take off | DEL: Salvage".to_string());
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{EntityId, Position3D, ResourceType};
+    use crate::domain::PlayerStats;
+
+    fn player_with_cargo(amounts: &[(ResourceType, u32)]) -> PlayerResource {
+        let mut player_resource = PlayerResource::new();
+        player_resource
+            .create_player(
+                "player".to_string(),
+                "Scout".to_string(),
+                Position3D::origin(),
+                PlayerStats::new(10, 10, 10, 10, 10, 10).unwrap(),
+            )
+            .unwrap();
+        let current = player_resource.get_player().unwrap().resources().clone();
+        player_resource.try_pay_resources(&current).unwrap();
+        for &(resource_type, amount) in amounts {
+            player_resource
+                .apply_resource_delta(resource_type, amount as i32)
+                .unwrap();
+        }
+        player_resource
+    }
+
+    #[test]
+    fn bulk_transfers_report_what_was_left_behind() {
+        let mut player_resource =
+            player_with_cargo(&[(ResourceType::Metal, 60), (ResourceType::Food, 30)]);
+        let mut base = Base::new(
+            EntityId::generate(),
+            "Home".to_string(),
+            Position3D::origin(),
+        )
+        .unwrap();
+        base.store_resource(ResourceType::Data, base.storage_capacity() - 70);
+
+        let plan = deposit(&mut player_resource, &mut base, &ResourceCollection::new()).unwrap();
+        assert_eq!(plan.left_behind.get_amount(ResourceType::Food), 20);
+        assert_eq!(base.free_capacity(), 0);
+        let cargo = player_resource.get_player().unwrap().resources().clone();
+        assert_eq!(cargo.get_amount(ResourceType::Metal), 0);
+        assert_eq!(cargo.get_amount(ResourceType::Food), 20);
+        assert!(transfer_summary("Deposited", &plan).contains("No room for"));
+
+        let plan = withdraw(&mut player_resource, &mut base).unwrap();
+        assert_eq!(plan.moved.get_amount(ResourceType::Metal), 60);
+        assert!(plan.left_behind.get_amount(ResourceType::Data) > 0);
+        let player = player_resource.get_player().unwrap();
+        assert_eq!(
+            player.resources().storage_requirement(),
+            player.carrying_capacity()
+        );
+        assert_eq!(
+            base.resources().storage_requirement(),
+            plan.left_behind.storage_requirement()
+        );
+    }
+
+    #[test]
+    fn only_the_kept_part_of_a_find_counts_as_gathered() {
+        let mut player_resource = player_with_cargo(&[]);
+        let capacity = player_resource.carrying_capacity();
+        player_resource
+            .apply_resource_delta(ResourceType::Food, capacity as i32 - 10)
+            .unwrap();
+        let mut game_stats = GameStatsResource::new();
+        let mut game_log = GameLogService::new();
+
+        let mut found = ResourceCollection::new();
+        found.set_amount(ResourceType::Metal, 25);
+        let dropped = gather_find(&mut player_resource, &found, &mut game_stats, &mut game_log);
+
+        assert_eq!(dropped.get_amount(ResourceType::Metal), 15);
+        assert_eq!(
+            game_stats.resources_gathered_for_type(ResourceType::Metal),
+            10
+        );
+        assert_eq!(player_resource.free_carrying_capacity(), 0);
+    }
+
+    #[test]
+    fn carried_items_count_towards_the_panel_weight() {
+        use crate::domain::entities::InventoryItem;
+
+        let mut cargo = ResourceCollection::new();
+        cargo.set_amount(ResourceType::Metal, 20);
+        let mut inventory = Inventory::with_resources(cargo);
+        inventory.add_item(InventoryItem::new("hull_patch", 3, 5), 200);
+
+        let text = inventory_panel_text(
+            &inventory,
+            200,
+            &ResourceCollection::new(),
+            &Consumables::new(),
+            &[],
+            &InventorySettings::default(),
+            None,
+        );
+        assert!(text.starts_with("INVENTORY - 35/200 carried"));
+        assert!(text.contains("hull_patch      x3      15 wt"));
+    }
+
+    #[test]
+    fn relics_are_picked_up_to_capacity_and_handed_in_for_experience() {
+        use crate::domain::constants::RUIN_RELIC_WEIGHT;
+        use crate::domain::entities::InventoryItem;
+
+        let mut player_resource = player_with_cargo(&[]);
+        let capacity = player_resource.carrying_capacity();
+        player_resource
+            .apply_resource_delta(
+                ResourceType::Food,
+                (capacity - RUIN_RELIC_WEIGHT * 2) as i32,
+            )
+            .unwrap();
+        let relics = InventoryItem::new(RUIN_RELIC_ID, 3, RUIN_RELIC_WEIGHT);
+        assert_eq!(player_resource.pick_up_item(relics), 1);
+
+        let mut game_log = GameLogService::new();
+        let experience = player_resource.get_player().unwrap().experience().points();
+        hand_in_relics(&mut player_resource, &mut game_log);
+        let player = player_resource.get_player().unwrap();
+        assert!(!player.inventory().has_item(RUIN_RELIC_ID, 1));
+        assert_eq!(
+            player.experience().points(),
+            experience + 2 * RUIN_RELIC_EXPERIENCE
+        );
+    }
+
+    #[test]
+    fn gear_cursor_cycles_and_the_sheet_shows_bonuses() {
+        use crate::domain::services::{GearBonus, GearItem, GearRarity};
+
+        let mut player_resource = player_with_cargo(&[]);
+        let suit = player_resource
+            .stow_gear(GearItem::new(GearBonus::Endurance, GearRarity::Common))
+            .unwrap();
+        let mast = player_resource
+            .stow_gear(GearItem::new(GearBonus::ViewRadius, GearRarity::Rare))
+            .unwrap();
+        player_resource.equip_gear(suit).unwrap();
+
+        let player = player_resource.get_player().unwrap();
+        let mut cursor = GearCursor::default();
+        cursor.next(player.gear());
+        assert_eq!(cursor.current(player.gear()), Some(suit));
+        cursor.next(player.gear());
+        assert_eq!(cursor.current(player.gear()), Some(mast));
+        cursor.next(player.gear());
+        assert_eq!(cursor.current(player.gear()), Some(suit));
+
+        let lines = character_lines(
+            player.stats(),
+            &player.derived_stats(),
+            player.gear(),
+            Some(suit),
+        );
+        assert!(lines[2].contains("END 11 (+1)"));
+        assert!(lines[3].starts_with(" > [Suit]"));
+        assert!(lines[4].contains("Tuned Sensor Mast"));
+    }
+}
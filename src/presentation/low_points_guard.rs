@@ -0,0 +1,356 @@
+//! Low Movement Guard - Warning or pause before running out of points
+//!
+//! Once the delayed result of a move has been applied (so points granted by
+//! its event count), the remaining movement points are checked against the
+//! threshold from the settings. Warn mode pulses a HUD line and logs a
+//! warning; pause mode opens a panel listing the nearby options and holds
+//! movement input until the player answers. "Don't ask again today" lasts
+//! until the next rest.
+
+use crate::domain::constants::{
+    CRITICAL_TEXT, LOW_POINTS_WARNING_PULSE_SECS, PANEL_BACKGROUND, PRIMARY_TEXT, WARNING_TEXT,
+};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{LowPointsAlert, LowPointsGuard, RestingService, SafeOptions};
+use crate::domain::value_objects::TileCoordinate;
+use crate::infrastructure::bevy::resources::{
+    BaseResource, GameStatsResource, MapResource, PlayerResource,
+};
+use crate::infrastructure::settings::LowPointsGuardSettings;
+use crate::presentation::movement::MovementResultApplied;
+use bevy::prelude::*;
+
+/// Plugin for the low movement points warning and pause
+pub struct LowPointsGuardPlugin;
+
+impl Plugin for LowPointsGuardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LowPointsGuard>()
+            .init_resource::<LowPointsGuardSettings>()
+            .add_systems(Startup, setup_low_points_ui)
+            .add_systems(
+                Update,
+                (
+                    low_points_rest_reset_system,
+                    low_points_check_system,
+                    low_points_prompt_system,
+                    update_low_points_panel,
+                    pulse_low_points_warning,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// HUD warning line and how long it keeps pulsing
+#[derive(Component, Debug, Default)]
+pub struct LowPointsWarningText {
+    remaining: f32,
+}
+
+/// Marker for the confirmation panel
+#[derive(Component)]
+pub struct LowPointsPanel;
+
+/// Marker for the confirmation panel text
+#[derive(Component)]
+pub struct LowPointsPanelText;
+
+fn setup_low_points_ui(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: FontSize::Small.to_pixels(),
+            ..default()
+        },
+        TextColor(CRITICAL_TEXT),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(15.0),
+            bottom: Val::Px(65.0),
+            ..default()
+        },
+        LowPointsWarningText::default(),
+        Name::new("LowPointsWarning"),
+    ));
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(30.0),
+                top: Val::Px(120.0),
+                width: Val::Px(420.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(PANEL_BACKGROUND),
+            Visibility::Hidden,
+            LowPointsPanel,
+            Name::new("LowPointsPanel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                LowPointsPanelText,
+            ));
+        });
+}
+
+/// Start a new day for the guard after every rest
+fn low_points_rest_reset_system(
+    game_stats: Res<GameStatsResource>,
+    mut guard: ResMut<LowPointsGuard>,
+    mut nights_counted: Local<u32>,
+) {
+    if game_stats.nights_rested != *nights_counted {
+        *nights_counted = game_stats.nights_rested;
+        guard.start_new_day();
+    }
+}
+
+/// Check the points left once a move's result has been applied
+#[allow(clippy::too_many_arguments)]
+fn low_points_check_system(
+    mut applied_events: EventReader<MovementResultApplied>,
+    settings: Res<LowPointsGuardSettings>,
+    mut guard: ResMut<LowPointsGuard>,
+    player_resource: Res<PlayerResource>,
+    map_resource: Res<MapResource>,
+    base_resource: Res<BaseResource>,
+    resting_service: Res<RestingService>,
+    mut game_log: ResMut<GameLogService>,
+    mut warning_query: Query<&mut LowPointsWarningText>,
+    mut panel_text_query: Query<&mut Text, With<LowPointsPanelText>>,
+) {
+    let Some(applied) = applied_events.read().last() else {
+        return;
+    };
+    let Some(player) = player_resource.get_player() else {
+        return;
+    };
+    let points = player.movement_points();
+
+    match guard.evaluate(points, settings.threshold, settings.mode) {
+        LowPointsAlert::None => {}
+        LowPointsAlert::Warn => {
+            game_log.log_message(
+                format!(
+                    "⚠️ Only {} movement points left - a forced rest is close",
+                    points
+                ),
+                GameLogType::Warning,
+            );
+            if let Ok(mut warning) = warning_query.single_mut() {
+                warning.remaining = LOW_POINTS_WARNING_PULSE_SECS;
+            }
+        }
+        LowPointsAlert::Pause => {
+            // Base coordinates mean nothing from inside ruins
+            let base = base_resource
+                .base_position()
+                .filter(|_| !map_resource.is_in_interior());
+            let terrain = map_resource
+                .current_map()
+                .and_then(|map| map.get_tile(&TileCoordinate::from(applied.final_position)))
+                .map(|tile| tile.terrain_type);
+            let options = SafeOptions::assess(
+                applied.final_position,
+                base,
+                terrain,
+                resting_service.average_movement_after_rest(player.max_movement_points()),
+            );
+
+            let mut lines = vec![
+                format!("LOW MOVEMENT POINTS: {} left", points),
+                String::new(),
+            ];
+            lines.extend(options.lines());
+            lines.push(String::new());
+            lines.push("Y: Continue | X: Continue, don't ask again today".to_string());
+            if let Ok(mut text) = panel_text_query.single_mut() {
+                **text = lines.join("\n");
+            }
+            game_log.log_message(
+                format!(
+                    "⏸️ Only {} movement points left - confirm before moving on",
+                    points
+                ),
+                GameLogType::Warning,
+            );
+        }
+    }
+}
+
+/// Answer the confirmation panel
+fn low_points_prompt_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut guard: ResMut<LowPointsGuard>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    if !guard.blocks_movement() {
+        return;
+    }
+    if keyboard.just_pressed(KeyCode::KeyY) {
+        guard.confirm(false);
+    } else if keyboard.just_pressed(KeyCode::KeyX) {
+        guard.confirm(true);
+        game_log.log_message(
+            "Low movement warnings silenced until your next rest".to_string(),
+            GameLogType::System,
+        );
+    }
+}
+
+/// Show the panel while the guard holds movement
+fn update_low_points_panel(
+    guard: Res<LowPointsGuard>,
+    mut panel_query: Query<&mut Visibility, With<LowPointsPanel>>,
+) {
+    let Ok(mut visibility) = panel_query.single_mut() else {
+        return;
+    };
+    let wanted = if guard.blocks_movement() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    if *visibility != wanted {
+        *visibility = wanted;
+    }
+}
+
+/// Pulse the HUD warning between warning and critical colors
+fn pulse_low_points_warning(
+    time: Res<Time>,
+    player_resource: Res<PlayerResource>,
+    mut warning_query: Query<(&mut LowPointsWarningText, &mut Text, &mut TextColor)>,
+) {
+    let Ok((mut warning, mut text, mut color)) = warning_query.single_mut() else {
+        return;
+    };
+
+    warning.remaining = (warning.remaining - time.delta_secs()).max(0.0);
+    if warning.remaining <= 0.0 {
+        if !text.is_empty() {
+            text.clear();
+        }
+        return;
+    }
+
+    let points = player_resource
+        .get_player()
+        .map(|player| player.movement_points())
+        .unwrap_or(0);
+    let line = format!("LOW MOVEMENT POINTS: {}", points);
+    if **text != line {
+        **text = line;
+    }
+    color.0 = if (time.elapsed_secs() * 6.0).sin() > 0.0 {
+        CRITICAL_TEXT
+    } else {
+        WARNING_TEXT
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::{GrantSource, LowPointsGuardMode};
+    use crate::domain::value_objects::Position3D;
+    use crate::domain::PlayerStats;
+
+    fn guard_app(mode: LowPointsGuardMode, movement_points: u8) -> App {
+        let mut player_resource = PlayerResource::new();
+        player_resource
+            .create_player(
+                "guard_player".to_string(),
+                "Guard Player".to_string(),
+                Position3D::origin(),
+                PlayerStats::new(10, 10, 10, 10, 10, 10).unwrap(),
+            )
+            .unwrap();
+        let spend = player_resource.get_player().unwrap().movement_points() - movement_points;
+        player_resource.try_spend_movement_points(spend).unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_event::<MovementResultApplied>()
+            .insert_resource(player_resource)
+            .insert_resource(MapResource::new())
+            .insert_resource(BaseResource::new())
+            .insert_resource(RestingService::new())
+            .insert_resource(GameLogService::new())
+            .insert_resource(LowPointsGuardSettings { mode, threshold: 2 })
+            .init_resource::<LowPointsGuard>()
+            .add_systems(Update, low_points_check_system);
+        app
+    }
+
+    fn apply_move(app: &mut App) {
+        app.world_mut().send_event(MovementResultApplied {
+            final_position: Position3D::origin(),
+            event_title: None,
+        });
+        app.update();
+    }
+
+    #[test]
+    fn event_rewards_are_counted_before_the_check() {
+        let mut app = guard_app(LowPointsGuardMode::Pause, 1);
+
+        // The move's event granted points before the result was reported
+        app.world_mut()
+            .resource_mut::<PlayerResource>()
+            .grant_movement_points(2, GrantSource::EventReward);
+        apply_move(&mut app);
+        assert!(!app.world().resource::<LowPointsGuard>().blocks_movement());
+
+        // Without a reward the same move trips the guard
+        let mut app = guard_app(LowPointsGuardMode::Pause, 1);
+        apply_move(&mut app);
+        assert!(app.world().resource::<LowPointsGuard>().blocks_movement());
+    }
+
+    #[test]
+    fn resting_lifts_the_dont_ask_again_flag() {
+        let mut app = guard_app(LowPointsGuardMode::Pause, 1);
+        app.insert_resource(GameStatsResource::new())
+            .add_systems(Update, low_points_rest_reset_system);
+        apply_move(&mut app);
+        app.world_mut()
+            .resource_mut::<LowPointsGuard>()
+            .confirm(true);
+
+        apply_move(&mut app);
+        assert!(!app.world().resource::<LowPointsGuard>().blocks_movement());
+
+        app.world_mut()
+            .resource_mut::<GameStatsResource>()
+            .record_rest();
+        app.update();
+        assert!(!app.world().resource::<LowPointsGuard>().is_suppressed());
+        apply_move(&mut app);
+        assert!(app.world().resource::<LowPointsGuard>().blocks_movement());
+    }
+
+    #[test]
+    fn warn_mode_logs_without_holding_movement() {
+        let mut app = guard_app(LowPointsGuardMode::Warn, 2);
+        apply_move(&mut app);
+
+        assert!(!app.world().resource::<LowPointsGuard>().blocks_movement());
+        let log = app.world().resource::<GameLogService>();
+        assert!(log
+            .get_recent_messages(5)
+            .iter()
+            .any(|message| message.message.contains("2 movement points left")));
+    }
+}
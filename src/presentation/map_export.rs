@@ -0,0 +1,144 @@
+//! Map Export - Saving the explored world to share it
+//!
+//! While the full-screen heatmap is open, E saves the explored surface as a
+//! PNG and C copies it as text. Both show terrain in its own colours or
+//! legend characters, the player, the base, the waypoints left on the active
+//! expedition and the resource nodes not yet worked out, and end with the
+//! world seed and the day. Nothing unexplored is shown.
+//!
+//! Native builds write the image to the `exports` directory and the text to
+//! `map_ascii.txt`; web builds download the image and copy the text to the
+//! clipboard.
+
+use crate::domain::constants::MAP_EXPORT_MAX_CELLS;
+use crate::domain::entities::Map;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{export_footer, ExportMarker, MapSnapshot};
+use crate::domain::value_objects::Position3D;
+use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource, PlayerResource};
+use crate::infrastructure::clipboard::{copy_text, CopyOutcome};
+use crate::infrastructure::map_export::save_png;
+use crate::presentation::game_state::{RpgAppState, RpgGameSession};
+use crate::presentation::play_heatmap::{heat_position, HeatmapView};
+use bevy::prelude::*;
+
+/// Key that saves the open map as an image
+pub const MAP_EXPORT_IMAGE_KEY: KeyCode = KeyCode::KeyE;
+
+/// Key that copies the open map as text
+pub const MAP_EXPORT_TEXT_KEY: KeyCode = KeyCode::KeyC;
+
+/// File the text map goes to where there is no clipboard
+pub const MAP_ASCII_FILE: &str = "map_ascii.txt";
+
+/// Plugin for exporting the explored map
+pub struct MapExportPlugin;
+
+impl Plugin for MapExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, export_map_system);
+    }
+}
+
+/// Save or copy the map on E or C while the heatmap is open
+#[allow(clippy::too_many_arguments)]
+fn export_map_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    view: Option<Res<HeatmapView>>,
+    map_resource: Res<MapResource>,
+    player_resource: Res<PlayerResource>,
+    game_stats: Res<GameStatsResource>,
+    session: Res<RpgGameSession>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    if *state.get() != RpgAppState::Exploration || !view.is_some_and(|view| view.is_open()) {
+        return;
+    }
+    let as_image = keyboard.just_pressed(MAP_EXPORT_IMAGE_KEY);
+    if !as_image && !keyboard.just_pressed(MAP_EXPORT_TEXT_KEY) {
+        return;
+    }
+
+    let position = player_resource.player_position().unwrap_or_default();
+    let level = heat_position(&map_resource, position).z;
+    let Some(map) = map_resource.overworld() else {
+        return;
+    };
+    let day = game_stats.current_day();
+    let markers = export_markers(&session, map, heat_position(&map_resource, position));
+    let Some(snapshot) = MapSnapshot::capture(
+        map,
+        level,
+        &markers,
+        MAP_EXPORT_MAX_CELLS,
+        export_footer(map.seed(), day),
+    ) else {
+        game_log.log_message(
+            "🗺️ Nothing explored on this level to export".to_string(),
+            GameLogType::Warning,
+        );
+        return;
+    };
+
+    if as_image {
+        let (width, height, pixels) = snapshot.to_rgba();
+        let file_name = format!("map_seed{}_day{}.png", map.seed(), day);
+        match save_png(&file_name, width, height, &pixels) {
+            Ok(saved) => game_log.log_message(
+                format!("🗺️ Map image saved as {}", saved),
+                GameLogType::System,
+            ),
+            Err(error) => {
+                warn!("Map image could not be saved: {}", error);
+                game_log.log_message(
+                    "🗺️ The map image could not be saved".to_string(),
+                    GameLogType::Warning,
+                );
+            }
+        }
+        return;
+    }
+
+    match copy_text(&snapshot.to_ascii(), MAP_ASCII_FILE) {
+        Ok(CopyOutcome::Clipboard) => game_log.log_message(
+            "🗺️ Text map copied to the clipboard".to_string(),
+            GameLogType::System,
+        ),
+        Ok(CopyOutcome::File(file)) => game_log.log_message(
+            format!("🗺️ Text map saved as {}", file),
+            GameLogType::System,
+        ),
+        Err(error) => {
+            warn!("Text map could not be copied: {}", error);
+            game_log.log_message(
+                "🗺️ The text map could not be copied".to_string(),
+                GameLogType::Warning,
+            );
+        }
+    }
+}
+
+/// Player, base, remaining waypoints and resource nodes with something left
+fn export_markers(
+    session: &RpgGameSession,
+    map: &Map,
+    player: Position3D,
+) -> Vec<(Position3D, ExportMarker)> {
+    let mut markers: Vec<(Position3D, ExportMarker)> = map
+        .resource_nodes()
+        .iter()
+        .filter(|(_, node)| !node.is_depleted())
+        .map(|(position, _)| (*position, ExportMarker::PointOfInterest))
+        .collect();
+    if let Some(plan) = &session.active_expedition {
+        markers.extend(
+            plan.remaining_waypoints()
+                .iter()
+                .map(|waypoint| (*waypoint, ExportMarker::Waypoint)),
+        );
+    }
+    markers.push((*session.base.position(), ExportMarker::Base));
+    markers.push((player, ExportMarker::Player));
+    markers
+}
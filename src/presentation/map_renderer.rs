@@ -3,13 +3,30 @@
 //! This module provides both 3D isometric visualization and console-based debugging
 //! of the game world, showing the tile grid, terrain types, and player position.
 
-use crate::domain::constants::get_terrain_render_color;
+use crate::domain::constants::{get_terrain_render_color, SLOPE_SHADING_DEFAULT_INTENSITY};
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
 use crate::domain::services::{MapService, TileCacheService, VisibilityLevel, VisibilityService};
 use crate::domain::value_objects::terrain::TerrainType;
-use crate::domain::value_objects::TileCoordinate;
-use crate::infrastructure::bevy::resources::{MapResource, PlayerResource};
+use crate::domain::value_objects::{Position3D, TileCoordinate};
+use crate::infrastructure::bevy::resources::{
+    ActiveMapHandle, GameStatsResource, MapResource, PlayerResource,
+};
 use crate::presentation::audio_integration::TerrainChangeEvent;
+use crate::presentation::base_visuals::BaseVisualPlugin;
+use crate::presentation::day_night::DayNightCycle;
 use crate::presentation::movement::{CameraFollowsMovement, SmoothMovement, SmoothMovementPlugin};
+use crate::presentation::slope_shading::{
+    apply_slope_shading_system, CliffAssets, ShadedMaterials,
+};
+use crate::presentation::terrain_animation::{
+    animate_terrain_system, tag_animated_tiles_system, AnimatedTerrainMaterials,
+    TerrainAnimationClock,
+};
+use crate::presentation::terrain_transitions::{
+    refresh_pending_transitions_system, spawn_tile_transitions, TransitionAssets,
+};
+use crate::presentation::world_rebuild::WorldTeardown;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
 /// Plugin for 3D isometric map rendering functionality
@@ -17,7 +34,11 @@ pub struct MapRendererPlugin;
 
 impl Plugin for MapRendererPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(SmoothMovementPlugin)
+        // Movement may already have been added on its own by an embedding app
+        if !app.is_plugin_added::<SmoothMovementPlugin>() {
+            app.add_plugins(SmoothMovementPlugin);
+        }
+        app.add_plugins(BaseVisualPlugin)
             .add_systems(
                 Startup,
                 (setup_3d_camera_system, setup_terrain_materials_system),
@@ -32,15 +53,62 @@ impl Plugin for MapRendererPlugin {
                     render_on_map_generation_system,
                     force_initial_render_system,
                     initial_map_render_system,
+                    refresh_pending_transitions_system,
+                    apply_slope_shading_system,
+                    tag_animated_tiles_system,
+                    animate_terrain_system,
                     detect_player_terrain_changes,
                 )
                     .chain(),
             )
+            .add_systems(WorldTeardown, clear_rendered_tiles_system)
             .init_resource::<TerrainMaterials>()
+            .init_resource::<TransitionAssets>()
+            .init_resource::<CliffAssets>()
+            .init_resource::<ShadedMaterials>()
+            .init_resource::<AnimatedTerrainMaterials>()
+            .init_resource::<TerrainAnimationClock>()
+            .init_resource::<MapRenderConfig>()
             .init_resource::<RenderState>();
     }
 }
 
+/// Colors the map is drawn with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MapPalette {
+    #[default]
+    Standard,
+    /// Flat terrain colors, without shading, for the clearest tile reading
+    HighContrast,
+}
+
+/// How the map is drawn
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct MapRenderConfig {
+    pub palette: MapPalette,
+    /// Strength of slope and height shading, from 0 (flat) to 1
+    pub slope_shading: f32,
+}
+
+impl Default for MapRenderConfig {
+    fn default() -> Self {
+        Self {
+            palette: MapPalette::default(),
+            slope_shading: SLOPE_SHADING_DEFAULT_INTENSITY,
+        }
+    }
+}
+
+impl MapRenderConfig {
+    /// Shading strength in effect; the high-contrast palette draws flat
+    pub fn shading_intensity(&self) -> f32 {
+        match self.palette {
+            MapPalette::Standard => self.slope_shading.clamp(0.0, 1.0),
+            MapPalette::HighContrast => 0.0,
+        }
+    }
+}
+
 /// 3D Camera setup for isometric view
 #[derive(Component)]
 pub struct IsometricCamera;
@@ -50,12 +118,14 @@ pub struct IsometricCamera;
 pub struct PlayerMarker;
 
 /// Terrain tile representation in 3D world
-#[derive(Component)]
+#[derive(Component, Clone)]
 pub struct TerrainTile {
     pub coordinate: TileCoordinate,
     pub terrain_type: TerrainType,
     pub is_explored: bool,
     pub visibility_level: VisibilityLevel,
+    /// Width of the tile mesh in world units
+    pub tile_size: f32,
 }
 
 /// Materials for different terrain types
@@ -175,6 +245,26 @@ impl FromWorld for TerrainMaterials {
     }
 }
 
+impl TerrainMaterials {
+    /// Get the shared material for a terrain type
+    pub fn for_terrain(&self, terrain_type: TerrainType) -> Handle<StandardMaterial> {
+        match terrain_type {
+            TerrainType::Plains => self.plains.clone(),
+            TerrainType::Forest => self.forest.clone(),
+            TerrainType::Mountains => self.mountains.clone(),
+            TerrainType::Desert => self.desert.clone(),
+            TerrainType::Tundra => self.tundra.clone(),
+            TerrainType::Ocean => self.ocean.clone(),
+            TerrainType::Swamp => self.swamp.clone(),
+            TerrainType::Volcanic => self.volcanic.clone(),
+            TerrainType::Constructed => self.constructed.clone(),
+            TerrainType::Cave => self.cave.clone(),
+            TerrainType::Crystal => self.crystal.clone(),
+            TerrainType::Anomaly => self.anomaly.clone(),
+        }
+    }
+}
+
 /// Tracks rendering state to avoid unnecessary updates
 #[derive(Resource)]
 pub struct RenderState {
@@ -183,6 +273,12 @@ pub struct RenderState {
     pub tile_cache: TileCacheService,
     pub initial_exploration_done: bool,
     pub last_terrain_type: Option<TerrainType>,
+    /// Map the rendered tiles were taken from
+    pub last_active_map: ActiveMapHandle,
+    /// Extra view radius from gear the tiles were drawn with
+    pub last_view_bonus: u32,
+    /// View narrowing from the time of day the tiles were drawn with
+    pub last_view_narrowing: u32,
 }
 
 impl Default for RenderState {
@@ -193,10 +289,25 @@ impl Default for RenderState {
             tile_cache: TileCacheService::new(),
             initial_exploration_done: false,
             last_terrain_type: None,
+            last_active_map: ActiveMapHandle::Overworld,
+            last_view_bonus: 0,
+            last_view_narrowing: 0,
         }
     }
 }
 
+/// Despawn every tile of a world being rebuilt and forget what was drawn
+fn clear_rendered_tiles_system(
+    mut commands: Commands,
+    mut render_state: ResMut<RenderState>,
+    tiles: Query<Entity, With<TerrainTile>>,
+) {
+    for entity in tiles.iter() {
+        commands.entity(entity).despawn();
+    }
+    *render_state = RenderState::default();
+}
+
 /// Setup 3D isometric camera
 fn setup_3d_camera_system(mut commands: Commands) {
     info!("🎨 Setting up 3D isometric camera and lighting");
@@ -285,15 +396,28 @@ fn setup_terrain_materials_system() {
     info!("🎨 Terrain materials loaded");
 }
 
+/// Meshes and materials the map tiles are built from
+#[derive(SystemParam)]
+struct TileRenderAssets<'w> {
+    meshes: ResMut<'w, Assets<Mesh>>,
+    terrain_materials: Res<'w, TerrainMaterials>,
+    transition_assets: Res<'w, TransitionAssets>,
+}
+
 /// Initial map render system - renders map immediately when game starts
 fn initial_map_render_system(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    terrain_materials: Res<TerrainMaterials>,
+    assets: TileRenderAssets,
     mut render_state: ResMut<RenderState>,
     mut map_resource: ResMut<MapResource>,
     player_resource: Res<PlayerResource>,
+    game_stats: Res<GameStatsResource>,
 ) {
+    let TileRenderAssets {
+        mut meshes,
+        terrain_materials,
+        transition_assets,
+    } = assets;
     // Render map when both map and player exist and no tiles are rendered yet
     if map_resource.has_map()
         && player_resource.has_player()
@@ -310,7 +434,7 @@ fn initial_map_render_system(
                 if let Some(tile) = map.get_tile(&tile_coord) {
                     if !tile.is_explored() {
                         let mut explored_tile = tile.clone();
-                        explored_tile.explore();
+                        explored_tile.visit(game_stats.current_day());
                         map.set_tile(tile_coord, explored_tile);
                         info!(
                             "🔍 Initially explored tile at ({}, {})",
@@ -328,6 +452,7 @@ fn initial_map_render_system(
             &mut commands,
             &mut meshes,
             &terrain_materials,
+            &transition_assets,
             &map_resource,
             &mut render_state,
             player_position,
@@ -340,12 +465,16 @@ fn initial_map_render_system(
 /// Render map immediately when a new map is generated
 fn render_on_map_generation_system(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    terrain_materials: Res<TerrainMaterials>,
+    assets: TileRenderAssets,
     mut render_state: ResMut<RenderState>,
     map_resource: Res<MapResource>,
     player_resource: Res<PlayerResource>,
 ) {
+    let TileRenderAssets {
+        mut meshes,
+        terrain_materials,
+        transition_assets,
+    } = assets;
     if !map_resource.has_map() || !player_resource.has_player() {
         return;
     }
@@ -358,6 +487,7 @@ fn render_on_map_generation_system(
             &mut commands,
             &mut meshes,
             &terrain_materials,
+            &transition_assets,
             &map_resource,
             &mut render_state,
             player_position,
@@ -370,12 +500,16 @@ fn render_on_map_generation_system(
 /// Force initial render system - ensures map shows immediately when available
 fn force_initial_render_system(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    terrain_materials: Res<TerrainMaterials>,
+    assets: TileRenderAssets,
     mut render_state: ResMut<RenderState>,
     map_resource: Res<MapResource>,
     player_resource: Res<PlayerResource>,
 ) {
+    let TileRenderAssets {
+        mut meshes,
+        terrain_materials,
+        transition_assets,
+    } = assets;
     // Force render if map exists but nothing is rendered yet
     if map_resource.has_map()
         && player_resource.has_player()
@@ -388,6 +522,7 @@ fn force_initial_render_system(
             &mut commands,
             &mut meshes,
             &terrain_materials,
+            &transition_assets,
             &map_resource,
             &mut render_state,
             player_position,
@@ -400,23 +535,45 @@ fn force_initial_render_system(
 /// Update 3D map representation
 fn update_3d_map_system(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    terrain_materials: Res<TerrainMaterials>,
+    assets: TileRenderAssets,
     mut render_state: ResMut<RenderState>,
     mut map_resource: ResMut<MapResource>,
     player_resource: Res<PlayerResource>,
+    day_night: Option<Res<DayNightCycle>>,
     mut query: Query<(
         Entity,
         &mut MeshMaterial3d<StandardMaterial>,
         &mut TerrainTile,
     )>,
 ) {
+    let TileRenderAssets {
+        mut meshes,
+        terrain_materials,
+        transition_assets,
+    } = assets;
     if !map_resource.has_map() || !player_resource.has_player() {
         return;
     }
 
     let player_position = player_resource.player_position().unwrap_or_default();
 
+    // Entering or leaving an interior swaps the tile source: redraw everything
+    if render_state.last_active_map != map_resource.active_map() {
+        render_state.last_active_map = map_resource.active_map();
+        render_state.last_player_position = None;
+    }
+
+    // Equipping or removing a sensor, or nightfall, changes how far the player sees
+    let view_bonus = player_view_bonus(&player_resource);
+    let view_narrowing = day_night.as_ref().map_or(0, |cycle| cycle.view_narrowing());
+    if render_state.last_view_bonus != view_bonus
+        || render_state.last_view_narrowing != view_narrowing
+    {
+        render_state.last_view_bonus = view_bonus;
+        render_state.last_view_narrowing = view_narrowing;
+        render_state.last_player_position = None;
+    }
+
     // Only update if player moved or this is the first run
     if let Some(last_pos) = render_state.last_player_position {
         if last_pos == player_position {
@@ -455,7 +612,8 @@ fn update_3d_map_system(
         .update_player_position(player_position);
 
     // Get all visible tiles (both fully visible and fogged)
-    let visibility_service = VisibilityService::new();
+    let visibility_service =
+        VisibilityService::with_extra_radius(view_bonus).narrowed_by(view_narrowing);
     let all_visible_coords = visibility_service.get_all_visible_coordinates(player_position);
     let visible_set: std::collections::HashSet<_> = all_visible_coords.iter().collect();
 
@@ -521,24 +679,40 @@ fn update_3d_map_system(
         let world_pos = tile_to_world_position(coord.x, coord.y, coord.z);
         let height_offset = get_terrain_height_offset(tile.terrain_type);
 
-        commands.spawn((
-            Mesh3d(meshes.add(Cuboid::new(2.0, 0.2, 2.0))),
-            MeshMaterial3d(if tile.is_explored {
-                // Show terrain for all explored tiles (no fog on explored areas)
-                get_terrain_material(&terrain_materials, tile.terrain_type)
-            } else {
-                // Show fog for unexplored tiles
-                terrain_materials.fog_overlay.clone()
-            }),
-            Transform::from_translation(Vec3::new(world_pos.x, height_offset, world_pos.z)),
-            TerrainTile {
-                coordinate: coord,
-                terrain_type: tile.terrain_type,
-                is_explored: tile.is_explored,
-                visibility_level,
-            },
-            Name::new(format!("Tile_{}_{}_{}", coord.x, coord.y, coord.z)),
-        ));
+        let terrain_tile = TerrainTile {
+            coordinate: coord,
+            terrain_type: tile.terrain_type,
+            is_explored: tile.is_explored,
+            visibility_level,
+            tile_size: 2.0,
+        };
+        let tile_entity = commands
+            .spawn((
+                Mesh3d(meshes.add(Cuboid::new(2.0, 0.2, 2.0))),
+                MeshMaterial3d(if tile.is_explored {
+                    // Show terrain for all explored tiles (no fog on explored areas)
+                    get_terrain_material(&terrain_materials, tile.terrain_type)
+                } else {
+                    // Show fog for unexplored tiles
+                    terrain_materials.fog_overlay.clone()
+                }),
+                Transform::from_translation(Vec3::new(world_pos.x, height_offset, world_pos.z)),
+                terrain_tile.clone(),
+                Name::new(format!("Tile_{}_{}_{}", coord.x, coord.y, coord.z)),
+            ))
+            .id();
+
+        if tile.is_explored {
+            spawn_tile_transitions(
+                &mut commands,
+                tile_entity,
+                map,
+                &terrain_tile,
+                2.0,
+                &transition_assets,
+                &terrain_materials,
+            );
+        }
 
         render_state.rendered_tiles.insert(coord_tuple);
     }
@@ -574,6 +748,15 @@ fn update_3d_map_system(
     );
 }
 
+/// Extra view radius granted by the player's equipped gear and the base's
+/// Laboratory
+pub(crate) fn player_view_bonus(player_resource: &PlayerResource) -> u32 {
+    player_resource
+        .get_player()
+        .map(|player| player.gear().view_radius_bonus() + player_resource.facilities().view_radius)
+        .unwrap_or(0)
+}
+
 /// Update 3D player position
 fn update_player_position_system(
     mut commands: Commands,
@@ -625,6 +808,7 @@ fn render_initial_map_around_player(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     terrain_materials: &TerrainMaterials,
+    transition_assets: &TransitionAssets,
     map_resource: &MapResource,
     render_state: &mut RenderState,
     player_position: crate::domain::value_objects::Position3D,
@@ -692,18 +876,34 @@ fn render_initial_map_around_player(
                 terrain_materials.fog_overlay.clone()
             };
 
-            commands.spawn((
-                Mesh3d(cube_mesh.clone()),
-                MeshMaterial3d(material),
-                Transform::from_translation(final_position),
-                TerrainTile {
-                    coordinate: tile_coord,
-                    terrain_type: tile.terrain_type,
-                    is_explored: tile.is_explored,
-                    visibility_level,
-                },
-                Name::new(format!("Terrain_{}_{}", tile_coord.x, tile_coord.y)),
-            ));
+            let terrain_tile = TerrainTile {
+                coordinate: tile_coord,
+                terrain_type: tile.terrain_type,
+                is_explored: tile.is_explored,
+                visibility_level,
+                tile_size: 1.0,
+            };
+            let tile_entity = commands
+                .spawn((
+                    Mesh3d(cube_mesh.clone()),
+                    MeshMaterial3d(material),
+                    Transform::from_translation(final_position),
+                    terrain_tile.clone(),
+                    Name::new(format!("Terrain_{}_{}", tile_coord.x, tile_coord.y)),
+                ))
+                .id();
+
+            if is_explored {
+                spawn_tile_transitions(
+                    commands,
+                    tile_entity,
+                    map,
+                    &terrain_tile,
+                    1.0,
+                    transition_assets,
+                    terrain_materials,
+                );
+            }
 
             render_state
                 .rendered_tiles
@@ -722,6 +922,7 @@ fn render_new_tiles_around_player(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     terrain_materials: &TerrainMaterials,
+    transition_assets: &TransitionAssets,
     map_resource: &MapResource,
     render_state_mut: &mut RenderState,
     player_position: crate::domain::value_objects::Position3D,
@@ -759,18 +960,34 @@ fn render_new_tiles_around_player(
                 terrain_materials.fog_overlay.clone()
             };
 
-            commands.spawn((
-                Mesh3d(cube_mesh.clone()),
-                MeshMaterial3d(material),
-                Transform::from_translation(final_position),
-                TerrainTile {
-                    coordinate: tile_coord,
-                    terrain_type: tile.terrain_type,
-                    is_explored: tile.is_explored,
-                    visibility_level,
-                },
-                Name::new(format!("Terrain_{}_{}", tile_coord.x, tile_coord.y)),
-            ));
+            let terrain_tile = TerrainTile {
+                coordinate: tile_coord,
+                terrain_type: tile.terrain_type,
+                is_explored: tile.is_explored,
+                visibility_level,
+                tile_size: 1.0,
+            };
+            let tile_entity = commands
+                .spawn((
+                    Mesh3d(cube_mesh.clone()),
+                    MeshMaterial3d(material),
+                    Transform::from_translation(final_position),
+                    terrain_tile.clone(),
+                    Name::new(format!("Terrain_{}_{}", tile_coord.x, tile_coord.y)),
+                ))
+                .id();
+
+            if is_explored {
+                spawn_tile_transitions(
+                    commands,
+                    tile_entity,
+                    map,
+                    &terrain_tile,
+                    1.0,
+                    transition_assets,
+                    terrain_materials,
+                );
+            }
 
             render_state_mut
                 .rendered_tiles
@@ -780,13 +997,11 @@ fn render_new_tiles_around_player(
 }
 
 /// Convert tile coordinates to 3D world position
+///
+/// Goes through the movement conversion so tiles and the things standing
+/// on them are always placed the same way.
 fn tile_to_world_position(tile_x: i32, tile_y: i32, tile_z: i32) -> Vec3 {
-    // Convert tile grid to isometric-like 3D coordinates
-    let x = (tile_x as f32) * 2.2; // Proper spacing for 2.0 unit wide tiles
-    let z = (tile_y as f32) * 2.2;
-    let y = (tile_z as f32) * 0.3; // Layer height
-
-    Vec3::new(x, y, z)
+    crate::presentation::movement::tile_to_world_position(Position3D::new(tile_x, tile_y, tile_z))
 }
 
 /// Get material for terrain type
@@ -794,20 +1009,7 @@ fn get_terrain_material(
     materials: &TerrainMaterials,
     terrain_type: TerrainType,
 ) -> Handle<StandardMaterial> {
-    match terrain_type {
-        TerrainType::Plains => materials.plains.clone(),
-        TerrainType::Forest => materials.forest.clone(),
-        TerrainType::Mountains => materials.mountains.clone(),
-        TerrainType::Desert => materials.desert.clone(),
-        TerrainType::Tundra => materials.tundra.clone(),
-        TerrainType::Ocean => materials.ocean.clone(),
-        TerrainType::Swamp => materials.swamp.clone(),
-        TerrainType::Volcanic => materials.volcanic.clone(),
-        TerrainType::Constructed => materials.constructed.clone(),
-        TerrainType::Cave => materials.cave.clone(),
-        TerrainType::Crystal => materials.crystal.clone(),
-        TerrainType::Anomaly => materials.anomaly.clone(),
-    }
+    materials.for_terrain(terrain_type)
 }
 
 /// Get height offset for different terrain types
@@ -847,10 +1049,16 @@ fn get_terrain_symbol(terrain_type: TerrainType) -> &'static str {
 }
 
 /// System to mark tiles as explored when player visits them (plus pattern visibility)
+///
+/// Every visible tile also records the day it was last seen, which keeps it
+/// from going stale.
 pub fn mark_tiles_explored_system(
-    player_resource: Res<PlayerResource>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut game_stats: ResMut<GameStatsResource>,
     mut map_resource: ResMut<MapResource>,
     mut render_state: ResMut<RenderState>,
+    mut game_log: ResMut<GameLogService>,
+    day_night: Option<Res<DayNightCycle>>,
 ) {
     if !player_resource.has_player() || !map_resource.has_map() {
         return;
@@ -868,21 +1076,48 @@ pub fn mark_tiles_explored_system(
         return;
     }
 
-    let visibility_service = VisibilityService::new();
+    let view_narrowing = day_night.as_ref().map_or(0, |cycle| cycle.view_narrowing());
+    let visibility_service =
+        VisibilityService::with_extra_radius(player_view_bonus(&player_resource))
+            .narrowed_by(view_narrowing);
     let visible_coords = visibility_service.get_all_visible_coordinates(player_position);
+    let day = game_stats.current_day();
+    // The starting view is handed out, not explored
+    let rewarded = render_state.last_player_position.is_some();
+    let mut experience = 0;
 
     if let Some(map) = map_resource.current_map_mut() {
         for tile_coord in visible_coords {
             if let Some(tile) = map.get_tile(&tile_coord) {
-                if !tile.is_explored() {
-                    let mut explored_tile = tile.clone();
-                    explored_tile.explore();
-                    map.set_tile(tile_coord, explored_tile);
+                if tile.last_visited_day != Some(day) {
+                    if rewarded {
+                        if let Some(grant) = game_stats.record_exploration(tile) {
+                            experience += grant.experience;
+                            if grant.first_discovery {
+                                game_log.log_message(
+                                    format!(
+                                        "🌟 First {} charted this run! +{} XP",
+                                        grant.terrain, grant.experience
+                                    ),
+                                    GameLogType::Discovery,
+                                );
+                            }
+                        }
+                    }
+                    let mut visited_tile = tile.clone();
+                    visited_tile.visit(day);
+                    map.set_tile(tile_coord, visited_tile);
                 }
             }
         }
     }
 
+    if experience > 0 {
+        if let Err(e) = player_resource.grant_experience(experience) {
+            warn!("Failed to grant exploration experience: {:?}", e);
+        }
+    }
+
     // Note: last_player_position is managed by update_3d_map_system to prevent conflicts
 }
 
@@ -945,7 +1180,7 @@ fn detect_player_terrain_changes(
         TileCoordinate::new(current_position.x, current_position.y, current_position.z);
 
     // Get the current terrain type
-    if let Some(map) = &map_resource.current_map {
+    if let Some(map) = map_resource.current_map() {
         if let Some(tile) = map.get_tile(&tile_coord) {
             let current_terrain = tile.terrain_type;
 
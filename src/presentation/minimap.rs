@@ -0,0 +1,539 @@
+//! Minimap - The explored surface at a glance, in a corner of the HUD
+//!
+//! The minimap is a single texture with one pixel per tile, anchored on the
+//! tile the player stood on when it was last drawn in full, so a map that
+//! keeps growing around the player never has to fit in it. Tiles are only
+//! painted once explored: around the player when they move, where a
+//! `DiscoveryEvent` is reported and where a scout probe flies. The whole
+//! texture is only drawn again when it is anchored anew, which happens on
+//! opening it, on a new or rebuilt map, on entering or leaving a ruin and
+//! when the player nears its edge. The player and the base are painted on
+//! top of the terrain.
+//!
+//! M shows and hides it, and Shift+M switches between the near and far
+//! view. Zooming only changes which part of the texture the panel shows.
+
+use crate::domain::constants::{
+    get_terrain_scanner_color, MINIMAP_BASE_COLOR, MINIMAP_CANVAS_TILES, MINIMAP_PANEL_SIZE,
+    MINIMAP_PLAYER_COLOR, MINIMAP_ZOOM_SPANS, PANEL_BACKGROUND, SCANNER_GRID,
+};
+use crate::domain::entities::Map;
+use crate::domain::services::VisibilityService;
+use crate::domain::value_objects::{EntityId, Position3D, TileCoordinate};
+use crate::infrastructure::bevy::resources::{ActiveMapHandle, MapResource, PlayerResource};
+use crate::presentation::game_event_logger::DiscoveryEvent;
+use crate::presentation::game_state::RpgGameSession;
+use crate::presentation::map_renderer::{mark_tiles_explored_system, player_view_bonus};
+use crate::presentation::play_heatmap::paint_pixel;
+use crate::presentation::world_rebuild::WorldTeardown;
+use crate::presentation::RpgAppState;
+use bevy::asset::RenderAssetUsages;
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+/// Key that shows and hides the minimap; with Shift it switches the zoom
+pub const MINIMAP_KEY: KeyCode = KeyCode::KeyM;
+
+/// Gap between the minimap and the right edge of the window
+const PANEL_RIGHT: f32 = 15.0;
+
+/// Distance from the top of the window, clear of the stat bar
+const PANEL_TOP: f32 = 59.0;
+
+/// Plugin for the minimap panel and its texture
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Minimap>()
+            .add_event::<TilesRevealed>()
+            .add_systems(Startup, setup_minimap_panel)
+            .add_systems(
+                Update,
+                (
+                    toggle_minimap_system,
+                    paint_minimap_system.after(mark_tiles_explored_system),
+                    frame_minimap_system,
+                )
+                    .chain(),
+            )
+            .add_systems(WorldTeardown, forget_minimap_canvas_system);
+    }
+}
+
+/// Tiles explored outside the player's view, e.g. by a scout probe
+#[derive(Event, Debug, Clone, Default)]
+pub struct TilesRevealed(pub Vec<TileCoordinate>);
+
+/// How much of the surroundings the minimap shows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MinimapZoom {
+    #[default]
+    Near,
+    Far,
+}
+
+impl MinimapZoom {
+    /// Tiles along each side of the view
+    pub fn span(&self) -> i32 {
+        match self {
+            MinimapZoom::Near => MINIMAP_ZOOM_SPANS[0],
+            MinimapZoom::Far => MINIMAP_ZOOM_SPANS[1],
+        }
+    }
+
+    /// The other zoom level
+    pub fn toggled(&self) -> Self {
+        match self {
+            MinimapZoom::Near => MinimapZoom::Far,
+            MinimapZoom::Far => MinimapZoom::Near,
+        }
+    }
+}
+
+/// Which tiles of which map the minimap texture covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinimapCanvas {
+    map: EntityId,
+    centre: TileCoordinate,
+}
+
+impl MinimapCanvas {
+    /// Canvas of `map` anchored on `position`
+    pub fn centred_on(map: EntityId, position: Position3D) -> Self {
+        Self {
+            map,
+            centre: TileCoordinate::from(position),
+        }
+    }
+
+    /// Pixel of `coordinate`, north up, if the canvas covers it
+    pub fn pixel(&self, coordinate: TileCoordinate) -> Option<(u32, u32)> {
+        if coordinate.z != self.centre.z {
+            return None;
+        }
+        let half = MINIMAP_CANVAS_TILES / 2;
+        let x = coordinate.x - (self.centre.x - half);
+        let y = (self.centre.y + half - 1) - coordinate.y;
+        let covered = 0..MINIMAP_CANVAS_TILES;
+        (covered.contains(&x) && covered.contains(&y)).then_some((x as u32, y as u32))
+    }
+
+    /// Check if the canvas still holds the far view around `position` on `map`
+    pub fn frames(&self, map: EntityId, position: Position3D) -> bool {
+        let reach = (MINIMAP_CANVAS_TILES - MinimapZoom::Far.span()) / 2;
+        map == self.map
+            && position.z == self.centre.z
+            && (position.x - self.centre.x).abs() <= reach
+            && (position.y - self.centre.y).abs() <= reach
+    }
+
+    /// Part of the texture a view `span` tiles wide around `position` shows
+    pub fn view_rect(&self, position: Position3D, span: i32) -> Rect {
+        let half = MINIMAP_CANVAS_TILES / 2;
+        let (x, y) = self
+            .pixel(TileCoordinate::from(position))
+            .unwrap_or((half as u32, half as u32));
+        let span = span.min(MINIMAP_CANVAS_TILES) as f32;
+        let corner = (Vec2::new(x as f32, y as f32) + Vec2::splat(0.5 - span / 2.0))
+            .clamp(Vec2::ZERO, Vec2::splat(MINIMAP_CANVAS_TILES as f32 - span));
+        Rect::from_corners(corner, corner + Vec2::splat(span))
+    }
+}
+
+/// Whether the minimap is shown, how close and what its texture holds
+#[derive(Resource, Debug, Default)]
+pub struct Minimap {
+    visible: bool,
+    zoom: MinimapZoom,
+    image: Option<Handle<Image>>,
+    canvas: Option<MinimapCanvas>,
+    /// Draw the whole texture again on the next frame
+    redraw: bool,
+    /// Where the player was when the minimap was last painted
+    painted_at: Option<Position3D>,
+    /// Tiles painted over by the player and base markers
+    markers: Vec<TileCoordinate>,
+}
+
+impl Minimap {
+    /// Check if the minimap is showing
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// How much of the surroundings it shows
+    pub fn zoom(&self) -> MinimapZoom {
+        self.zoom
+    }
+}
+
+/// Marker for the minimap panel
+#[derive(Component)]
+pub struct MinimapPanel;
+
+/// Marker for the node the minimap texture is drawn on
+#[derive(Component)]
+pub struct MinimapImage;
+
+fn setup_minimap_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(PANEL_RIGHT),
+                top: Val::Px(PANEL_TOP),
+                width: Val::Px(MINIMAP_PANEL_SIZE),
+                height: Val::Px(MINIMAP_PANEL_SIZE),
+                padding: UiRect::all(Val::Px(4.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BackgroundColor(PANEL_BACKGROUND),
+            BorderColor(SCANNER_GRID),
+            Visibility::Hidden,
+            MinimapPanel,
+            Name::new("MinimapPanel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                ImageNode::default(),
+                MinimapImage,
+            ));
+        });
+}
+
+/// Check if `state` is part of a run the minimap belongs to
+fn in_run(state: &RpgAppState) -> bool {
+    !matches!(
+        state,
+        RpgAppState::Loading
+            | RpgAppState::MainMenu
+            | RpgAppState::CharacterCreation
+            | RpgAppState::GameOver
+    )
+}
+
+/// Show or hide the minimap on M and switch its zoom on Shift+M
+///
+/// M returns to the menu while paused, so the minimap is left alone there.
+fn toggle_minimap_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    mut minimap: ResMut<Minimap>,
+) {
+    if !in_run(state.get()) || *state.get() == RpgAppState::Paused {
+        return;
+    }
+    if !keyboard.just_pressed(MINIMAP_KEY) {
+        return;
+    }
+    if keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        minimap.zoom = minimap.zoom.toggled();
+    } else {
+        minimap.visible = !minimap.visible;
+        // Whatever was explored while it was hidden is drawn on opening
+        minimap.redraw = minimap.visible;
+    }
+}
+
+/// Colour of the tile at `coordinate`, or transparent until it is explored
+pub fn tile_color(map: &Map, coordinate: TileCoordinate) -> Color {
+    map.get_tile(&coordinate)
+        .filter(|tile| tile.is_explored())
+        .map_or(Color::NONE, |tile| {
+            get_terrain_scanner_color(tile.terrain_type)
+        })
+}
+
+/// Paint the tiles at `coordinates` the canvas covers
+pub fn paint_tiles(
+    image: &mut Image,
+    canvas: &MinimapCanvas,
+    map: &Map,
+    coordinates: impl IntoIterator<Item = TileCoordinate>,
+) {
+    for coordinate in coordinates {
+        if let Some((x, y)) = canvas.pixel(coordinate) {
+            paint_pixel(image, x, y, tile_color(map, coordinate));
+        }
+    }
+}
+
+/// Draw every explored tile of `map` the canvas covers
+pub fn canvas_image(map: &Map, canvas: &MinimapCanvas) -> Image {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: MINIMAP_CANVAS_TILES as u32,
+            height: MINIMAP_CANVAS_TILES as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.sampler = ImageSampler::nearest();
+    let explored = map
+        .tiles()
+        .iter()
+        .filter(|(_, tile)| tile.is_explored())
+        .map(|(coordinate, _)| *coordinate);
+    paint_tiles(&mut image, canvas, map, explored);
+    image
+}
+
+/// Paint the player, then the base, over the terrain
+fn paint_markers(image: &mut Image, canvas: &MinimapCanvas, markers: &[TileCoordinate]) {
+    let colors = [MINIMAP_PLAYER_COLOR, MINIMAP_BASE_COLOR];
+    for (coordinate, color) in markers.iter().zip(colors) {
+        if let Some((x, y)) = canvas.pixel(*coordinate) {
+            paint_pixel(image, x, y, color);
+        }
+    }
+}
+
+/// Paint what was explored since the last frame, or everything when the
+/// canvas has to be anchored again
+#[allow(clippy::too_many_arguments)]
+fn paint_minimap_system(
+    mut minimap: ResMut<Minimap>,
+    map_resource: Res<MapResource>,
+    player_resource: Res<PlayerResource>,
+    session: Option<Res<RpgGameSession>>,
+    mut discoveries: EventReader<DiscoveryEvent>,
+    mut reveals: EventReader<TilesRevealed>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let mut dirty: Vec<TileCoordinate> = discoveries
+        .read()
+        .map(|discovery| TileCoordinate::from(discovery.position))
+        .chain(reveals.read().flat_map(|reveal| reveal.0.iter().copied()))
+        .collect();
+    if !minimap.visible {
+        return;
+    }
+    let (Some(map), Some(position)) = (
+        map_resource.current_map(),
+        player_resource.player_position(),
+    ) else {
+        return;
+    };
+    let base = match map_resource.active_map() {
+        ActiveMapHandle::Overworld => session.as_ref().map(|session| *session.base.position()),
+        ActiveMapHandle::Interior(_) => None,
+    };
+    let markers: Vec<TileCoordinate> = std::iter::once(position)
+        .chain(base)
+        .map(TileCoordinate::from)
+        .collect();
+
+    let anchored = minimap
+        .canvas
+        .filter(|canvas| !minimap.redraw && canvas.frames(*map.id(), position));
+    let Some(canvas) = anchored else {
+        let canvas = MinimapCanvas::centred_on(*map.id(), position);
+        let mut image = canvas_image(map, &canvas);
+        paint_markers(&mut image, &canvas, &markers);
+        match minimap.image.as_ref() {
+            Some(handle) => {
+                images.insert(handle, image);
+            }
+            None => minimap.image = Some(images.add(image)),
+        }
+        minimap.canvas = Some(canvas);
+        minimap.redraw = false;
+        minimap.painted_at = Some(position);
+        minimap.markers = markers;
+        return;
+    };
+
+    if minimap.painted_at != Some(position) {
+        let view = VisibilityService::with_extra_radius(player_view_bonus(&player_resource));
+        dirty.extend(view.get_all_visible_coordinates(position));
+        minimap.painted_at = Some(position);
+    }
+    if dirty.is_empty() && minimap.markers == markers {
+        return;
+    }
+    let Some(image) = minimap
+        .image
+        .as_ref()
+        .and_then(|handle| images.get_mut(handle))
+    else {
+        return;
+    };
+    // Markers that moved off a tile give it back its terrain
+    dirty.extend(minimap.markers.iter().copied());
+    paint_tiles(image, &canvas, map, dirty);
+    paint_markers(image, &canvas, &markers);
+    minimap.markers = markers;
+}
+
+/// Show the panel during a run and frame the player at the chosen zoom
+fn frame_minimap_system(
+    minimap: Res<Minimap>,
+    state: Res<State<RpgAppState>>,
+    player_resource: Res<PlayerResource>,
+    mut panels: Query<&mut Visibility, With<MinimapPanel>>,
+    mut image_nodes: Query<&mut ImageNode, With<MinimapImage>>,
+) {
+    let shown = minimap.visible && in_run(state.get());
+    let wanted = if shown {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    for mut visibility in panels.iter_mut() {
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+    if !shown {
+        return;
+    }
+    let (Some(handle), Some(canvas), Some(position)) = (
+        minimap.image.as_ref(),
+        minimap.canvas,
+        player_resource.player_position(),
+    ) else {
+        return;
+    };
+    let rect = canvas.view_rect(position, minimap.zoom.span());
+    for mut node in image_nodes.iter_mut() {
+        if node.image != *handle {
+            node.image = handle.clone();
+        }
+        if node.rect != Some(rect) {
+            node.rect = Some(rect);
+        }
+    }
+}
+
+/// A rebuilt world is drawn from scratch
+fn forget_minimap_canvas_system(mut minimap: ResMut<Minimap>) {
+    minimap.canvas = None;
+    minimap.markers.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presentation::game_event_logger::DiscoveryType;
+
+    #[test]
+    fn the_canvas_is_north_up_and_anchored_again_near_its_edge() {
+        // Fixed ids; generated ids only differ across milliseconds
+        let map = EntityId::new(1);
+        let canvas = MinimapCanvas::centred_on(map, Position3D::new(10, 10, 0));
+        let half = MINIMAP_CANVAS_TILES as u32 / 2;
+        assert_eq!(
+            canvas.pixel(TileCoordinate::new(10, 10, 0)),
+            Some((half, half - 1))
+        );
+        // North is up: a tile further north sits on a higher row
+        assert_eq!(
+            canvas.pixel(TileCoordinate::new(10, 11, 0)),
+            Some((half, half - 2))
+        );
+        assert_eq!(canvas.pixel(TileCoordinate::new(10, 10, 1)), None);
+        assert_eq!(
+            canvas.pixel(TileCoordinate::new(10 + MINIMAP_CANVAS_TILES, 10, 0)),
+            None
+        );
+
+        let reach = (MINIMAP_CANVAS_TILES - MinimapZoom::Far.span()) / 2;
+        assert!(canvas.frames(map, Position3D::new(10 + reach, 10 - reach, 0)));
+        assert!(!canvas.frames(map, Position3D::new(11 + reach, 10, 0)));
+        assert!(!canvas.frames(EntityId::new(2), Position3D::new(10, 10, 0)));
+
+        // Both views fit inside the texture wherever the player stands on it
+        for zoom in [MinimapZoom::Near, MinimapZoom::Far] {
+            let rect = canvas.view_rect(Position3D::new(10 + reach, 10, 0), zoom.span());
+            assert_eq!(rect.width(), zoom.span() as f32);
+            assert!(rect.max.x <= MINIMAP_CANVAS_TILES as f32 && rect.min.y >= 0.0);
+        }
+    }
+
+    #[test]
+    fn explored_tiles_are_painted_as_they_are_discovered() {
+        let mut map_resource = MapResource::new();
+        map_resource.generate_overworld(Position3D::origin(), 7);
+        let near = TileCoordinate::new(1, 0, 0);
+        let far = TileCoordinate::new(9, 9, 0);
+        {
+            let map = map_resource.current_map_mut().unwrap();
+            for coordinate in [TileCoordinate::new(0, 0, 0), near, far] {
+                let mut tile = map.get_tile(&coordinate).unwrap().clone();
+                tile.is_explored = coordinate != far;
+                map.set_tile(coordinate, tile);
+            }
+        }
+        let mut player_resource = PlayerResource::new();
+        player_resource
+            .create_player(
+                "cartographer".to_string(),
+                "Cartographer".to_string(),
+                Position3D::origin(),
+                crate::domain::PlayerStats::new(10, 10, 10, 10, 10, 10).unwrap(),
+            )
+            .unwrap();
+
+        let mut app = App::new();
+        app.init_resource::<Assets<Image>>()
+            .add_event::<DiscoveryEvent>()
+            .add_event::<TilesRevealed>()
+            .insert_resource(map_resource)
+            .insert_resource(player_resource)
+            .insert_resource(Minimap {
+                visible: true,
+                ..default()
+            })
+            .add_systems(Update, paint_minimap_system);
+        app.update();
+
+        let pixel = |app: &App, coordinate: TileCoordinate| {
+            let minimap = app.world().resource::<Minimap>();
+            let (x, y) = minimap.canvas.unwrap().pixel(coordinate).unwrap();
+            let images = app.world().resource::<Assets<Image>>();
+            let image = images.get(minimap.image.as_ref().unwrap()).unwrap();
+            image.get_color_at(x, y).unwrap().to_srgba().to_u8_array()
+        };
+        let terrain = |app: &App, coordinate: TileCoordinate| {
+            let map_resource = app.world().resource::<MapResource>();
+            tile_color(map_resource.current_map().unwrap(), coordinate)
+                .to_srgba()
+                .to_u8_array()
+        };
+        assert_eq!(
+            pixel(&app, TileCoordinate::new(0, 0, 0)),
+            MINIMAP_PLAYER_COLOR.to_srgba().to_u8_array()
+        );
+        assert_eq!(pixel(&app, near), terrain(&app, near));
+        assert_eq!(pixel(&app, far)[3], 0);
+
+        // A discovery far from the player is painted into the same texture
+        {
+            let mut map_resource = app.world_mut().resource_mut::<MapResource>();
+            let map = map_resource.current_map_mut().unwrap();
+            let mut tile = map.get_tile(&far).unwrap().clone();
+            tile.explore();
+            map.set_tile(far, tile);
+        }
+        app.world_mut().send_event(DiscoveryEvent {
+            discovery_type: DiscoveryType::Location("Crater".to_string()),
+            description: "A crater".to_string(),
+            position: Position3D::new(far.x, far.y, far.z),
+        });
+        app.update();
+        assert_eq!(pixel(&app, far), terrain(&app, far));
+        assert_ne!(pixel(&app, far)[3], 0);
+        assert_eq!(app.world().resource::<Assets<Image>>().len(), 1);
+    }
+}
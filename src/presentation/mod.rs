@@ -16,16 +16,76 @@
 //! - Translates between user actions and application commands
 //! - Manages presentation logic (not business logic)
 
+pub mod about;
+pub mod announcements;
+pub mod anomaly_storm;
+pub mod asset_integrity;
 pub mod audio_integration;
+pub mod audio_settings;
+pub mod base_construction;
+pub mod base_report;
+pub mod base_visuals;
+pub mod blitz;
+pub mod bug_report;
+pub mod camera_hints;
+pub mod clocks;
+pub mod codex;
+pub mod controls;
+pub mod dawn_report;
+pub mod day_night;
+pub mod decals;
+pub mod delayed_audio;
+pub mod delving;
+pub mod display_mode;
+pub mod embedding;
+pub mod emergency_recall;
+pub mod encounter;
+pub mod encounter_music;
+pub mod expedition;
+pub mod fauna;
+pub mod field_trade;
+pub mod frame_limiter;
 pub mod game_event_logger;
 pub mod game_log_integration;
 pub mod game_state;
 pub mod game_ui;
+pub mod ghost_trail;
+pub mod hud_layout;
 pub mod input;
+pub mod inspect;
+pub mod inventory;
 pub mod log_interceptor;
+pub mod low_points_guard;
+pub mod map_export;
 pub mod map_renderer;
+pub mod minimap;
+pub mod move_undo;
 pub mod movement;
+pub mod odds_preview;
+pub mod offline;
+pub mod page_api;
+pub mod party;
+pub mod play_heatmap;
+pub mod quest_board;
+pub mod quick_save;
+pub mod refinery;
 pub mod rendering;
+pub mod reputation;
+pub mod rescue;
+pub mod run_end;
+pub mod scenarios;
+pub mod scout_probe;
+pub mod seasons;
+pub mod share_code;
+pub mod slope_shading;
+pub mod terrain_animation;
+pub mod terrain_transitions;
+pub mod tile_staleness;
+pub mod transient_pool;
+pub mod warning_layer;
+pub mod world_rebuild;
+pub mod world_tick;
+pub mod wrecks;
 
 // Re-export common presentation types
 pub use audio_integration::{AudioAssets, AudioEventIntegrationPlugin};
@@ -0,0 +1,258 @@
+//! Move Undo - Ctrl+Z takes back the last move of a relaxed run
+//!
+//! Moves are followed through the player ledger rather than by copying the
+//! world: the cost paid opens a move, the relocation lands it, and once the
+//! world has answered the turn it is sealed. Ctrl+Z, or UNDO on the action
+//! bar, then puts the player back on the tile they left with the points,
+//! cargo and explored count they had. Only relaxed runs can undo, only the
+//! most recent move, and only until the player does anything else: rest,
+//! craft, or face a contact. What the world did in answer stays done, and
+//! the tile's event stays consumed.
+
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::MoveUndo;
+use crate::infrastructure::bevy::resources::{GameStatsResource, PlayerChange, PlayerResource};
+use crate::presentation::encounter::MysteryContact;
+use crate::presentation::field_trade::TraderContact;
+use crate::presentation::game_event_logger::{forward_player_changes, PlayerChangedEvent};
+use crate::presentation::game_state::RpgAppState;
+use crate::presentation::map_renderer::PlayerMarker;
+use crate::presentation::movement::SmoothMovement;
+use crate::presentation::reputation::HostileContact;
+use crate::presentation::run_end::ActiveRun;
+use bevy::prelude::*;
+
+/// Key that, with Ctrl held, takes back the last move
+pub const UNDO_KEY: KeyCode = KeyCode::KeyZ;
+
+/// Plugin for taking back the last move
+pub struct MoveUndoPlugin;
+
+impl Plugin for MoveUndoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MoveUndo>()
+            .add_event::<PlayerChangedEvent>()
+            .add_systems(
+                Update,
+                (track_moves_system, undo_move_system)
+                    .chain()
+                    .after(forward_player_changes),
+            );
+    }
+}
+
+/// Follow the last move through the player ledger
+fn track_moves_system(
+    mut player_events: EventReader<PlayerChangedEvent>,
+    game_stats: Res<GameStatsResource>,
+    hostile_contact: Option<Res<HostileContact>>,
+    trader_contact: Option<Res<TraderContact>>,
+    mystery_contact: Option<Res<MysteryContact>>,
+    mut undo: ResMut<MoveUndo>,
+) {
+    let mut observed = false;
+    for event in player_events.read() {
+        observed = true;
+        match event.change {
+            PlayerChange::MovementPointsSpent { cost, remaining } => {
+                undo.begin(cost.saturating_add(remaining), game_stats.tiles_explored)
+            }
+            PlayerChange::Relocated { from, to } => undo.land(from, to),
+            PlayerChange::ResourceChanged {
+                resource_type,
+                delta,
+                ..
+            } => undo.record_resource(resource_type, delta),
+            PlayerChange::MovementPointsGranted { .. }
+            | PlayerChange::MovementGrantReduced { .. }
+            | PlayerChange::MovementPointsLost { .. } => undo.record_points(),
+            // Charting pays out whenever the view moves; it is not taken back
+            PlayerChange::ExperienceGained { .. } => {}
+            // Rests, crafting, gear and handovers are actions of their own
+            _ => undo.expire(),
+        }
+    }
+
+    // A contact is a choice the move cannot dodge
+    let contact = hostile_contact.is_some_and(|contact| contact.is_pending())
+        || trader_contact.is_some_and(|contact| contact.is_pending())
+        || mystery_contact.is_some_and(|contact| contact.is_pending());
+    if contact && undo.available().is_some() {
+        undo.expire();
+    } else if observed {
+        undo.seal();
+    }
+}
+
+/// Take the last move back on Ctrl+Z, in relaxed runs only
+#[allow(clippy::too_many_arguments)]
+fn undo_move_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    run: Res<ActiveRun>,
+    mut undo: ResMut<MoveUndo>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut game_stats: ResMut<GameStatsResource>,
+    mut game_log: ResMut<GameLogService>,
+    mut movement: Query<&mut SmoothMovement, With<PlayerMarker>>,
+) {
+    let ctrl = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    if !ctrl || !keyboard.just_pressed(UNDO_KEY) || *state.get() != RpgAppState::Exploration {
+        return;
+    }
+    if !run.mode().is_some_and(|mode| mode.allows_undo()) {
+        game_log.log_message(
+            "↩️ Undo is only available in Relaxed runs".to_string(),
+            GameLogType::System,
+        );
+        return;
+    }
+    let Some((from, snapshot)) = undo
+        .take_back()
+        .and_then(|snapshot| Some((snapshot.from?, snapshot)))
+    else {
+        game_log.log_message(
+            "↩️ Nothing to undo: only the last move, before anything else happens".to_string(),
+            GameLogType::System,
+        );
+        return;
+    };
+
+    if let Err(e) =
+        player_resource.take_back_move(from, snapshot.movement_points, &snapshot.resource_deltas)
+    {
+        warn!("Failed to undo the last move: {}", e);
+        return;
+    }
+    game_stats.tiles_explored = snapshot.tiles_explored;
+    game_stats.record_undo();
+    for mut smooth in movement.iter_mut() {
+        smooth.reset_to_position(from);
+    }
+    game_log.log_message(
+        format!(
+            "↩️ Move undone - back at [{}, {}]. That tile's event stays spent",
+            from.x, from.y
+        ),
+        GameLogType::System,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::{GrantSource, RunMode};
+    use crate::domain::value_objects::{Position3D, ResourceType};
+    use crate::domain::PlayerStats;
+    use bevy::state::app::StatesPlugin;
+
+    const TARGET: Position3D = Position3D { x: 1, y: 0, z: 0 };
+
+    fn undo_app(mode: RunMode) -> App {
+        let mut player_resource = PlayerResource::new();
+        player_resource
+            .create_player(
+                "vex".to_string(),
+                "Vex".to_string(),
+                Position3D::origin(),
+                PlayerStats::new(10, 10, 10, 10, 10, 10).unwrap(),
+            )
+            .unwrap();
+        let mut run = ActiveRun::default();
+        run.start(mode);
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_state(RpgAppState::Exploration)
+            .insert_resource(ButtonInput::<KeyCode>::default())
+            .insert_resource(player_resource)
+            .insert_resource(GameStatsResource::new())
+            .insert_resource(GameLogService::new())
+            .insert_resource(run)
+            .add_systems(Update, forward_player_changes)
+            .add_plugins(MoveUndoPlugin);
+        app.update();
+        app
+    }
+
+    fn player(app: &App) -> &crate::domain::Player {
+        app.world().resource::<PlayerResource>().player().unwrap()
+    }
+
+    /// Pay for a move one frame, land it and let the world answer the next
+    fn make_move(app: &mut App) {
+        app.world_mut()
+            .resource_mut::<PlayerResource>()
+            .try_spend_movement_points(3)
+            .unwrap();
+        app.update();
+        let world = app.world_mut();
+        let mut player_resource = world.resource_mut::<PlayerResource>();
+        player_resource.set_position(TARGET);
+        player_resource.grant_movement_points(2, GrantSource::Exploration);
+        player_resource
+            .apply_resource_delta(ResourceType::Metal, 4)
+            .unwrap();
+        world
+            .resource_mut::<GameStatsResource>()
+            .record_tile_explored();
+        app.update();
+    }
+
+    fn press_undo(app: &mut App) {
+        let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.release_all();
+        keyboard.clear();
+        keyboard.press(KeyCode::ControlLeft);
+        keyboard.press(UNDO_KEY);
+        app.update();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release_all();
+    }
+
+    #[test]
+    fn undo_restores_everything_the_move_changed() {
+        let mut app = undo_app(RunMode::Relaxed);
+        let before = player(&app).clone();
+        make_move(&mut app);
+        assert_eq!(*player(&app).position(), TARGET);
+
+        press_undo(&mut app);
+        let after = player(&app);
+        assert_eq!(after.position(), before.position());
+        assert_eq!(after.movement_points(), before.movement_points());
+        assert_eq!(after.resources(), before.resources());
+        let game_stats = app.world().resource::<GameStatsResource>();
+        assert_eq!(game_stats.tiles_explored, 0);
+        assert_eq!(game_stats.moves_undone, 1);
+        assert!(app.world().resource::<MoveUndo>().is_event_consumed(TARGET));
+
+        // No chaining: the move before cannot be taken back too
+        press_undo(&mut app);
+        assert_eq!(app.world().resource::<GameStatsResource>().moves_undone, 1);
+    }
+
+    #[test]
+    fn undo_needs_a_relaxed_run_and_an_untouched_move() {
+        let mut normal = undo_app(RunMode::Normal);
+        make_move(&mut normal);
+        press_undo(&mut normal);
+        assert_eq!(*player(&normal).position(), TARGET);
+        assert_eq!(
+            normal.world().resource::<GameStatsResource>().moves_undone,
+            0
+        );
+
+        // Resting after the move retires it
+        let mut relaxed = undo_app(RunMode::Relaxed);
+        make_move(&mut relaxed);
+        relaxed
+            .world_mut()
+            .resource_mut::<PlayerResource>()
+            .restore_movement_points();
+        relaxed.update();
+        press_undo(&mut relaxed);
+        assert_eq!(*player(&relaxed).position(), TARGET);
+    }
+}
@@ -0,0 +1,312 @@
+//! Key Queue - Movement keys pressed during a step wait their turn
+//!
+//! While a step animates, a movement key no longer goes to waste: up to
+//! `input_buffer_size` of them are kept in order and each is walked once the
+//! step before it has landed and its result applied, through the same
+//! `ExecuteRpgMovement` path as a fresh keypress. A size of zero drops keys
+//! pressed mid-step, as before. Keys that would ask for the odds preview are
+//! never buffered.
+//!
+//! Anything that stops a keypress, or leaving exploration, drops the queue
+//! quietly. A queued step that cannot be taken, because the last one did not
+//! go through or the points have run out, drops the rest with one warning.
+
+use super::{
+    begin_player_step, movement_cost_at, run_modifiers, ExecuteRpgMovement, MovementConfig,
+    MovementHolds, MovementStarted, PendingRpgResults, SmoothMovement,
+};
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::WorldHazards;
+use crate::domain::value_objects::position::Direction;
+use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource, PlayerResource};
+use crate::presentation::map_renderer::PlayerMarker;
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Keyboard steps pressed while the player was still moving
+#[derive(Resource, Debug, Default)]
+pub struct MovementQueue {
+    steps: VecDeque<Direction>,
+}
+
+impl MovementQueue {
+    /// Buffer a step; false when `capacity` steps already wait
+    pub fn push(&mut self, direction: Direction, capacity: usize) -> bool {
+        if self.steps.len() >= capacity {
+            return false;
+        }
+        self.steps.push_back(direction);
+        true
+    }
+
+    /// Drop every buffered step; returns how many there were
+    pub fn clear(&mut self) -> usize {
+        let dropped = self.steps.len();
+        self.steps.clear();
+        dropped
+    }
+
+    /// The next step to take
+    pub fn next(&self) -> Option<Direction> {
+        self.steps.front().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+/// Take the next buffered step once the last one has landed
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn follow_movement_queue_system(
+    mut queue: ResMut<MovementQueue>,
+    mut player_query: Query<(&mut SmoothMovement, Entity), With<PlayerMarker>>,
+    player_resource: Res<PlayerResource>,
+    map_resource: Res<MapResource>,
+    world_hazards: Option<Res<WorldHazards>>,
+    config: Res<MovementConfig>,
+    pending: Res<PendingRpgResults>,
+    app_state: Option<Res<State<RpgAppState>>>,
+    holds: MovementHolds,
+    mut movement_started_events: EventWriter<MovementStarted>,
+    mut execute_rpg_events: EventWriter<ExecuteRpgMovement>,
+    mut game_log: ResMut<GameLogService>,
+    game_stats: Option<Res<GameStatsResource>>,
+) {
+    let Some(direction) = queue.next() else {
+        return;
+    };
+
+    // Whatever would turn a keypress away drops what is still buffered
+    let blocked = app_state.is_some_and(|state| *state.get() != RpgAppState::Exploration)
+        || holds.blocks_movement();
+    if blocked {
+        queue.clear();
+        return;
+    }
+
+    let Ok((mut smooth_movement, entity)) = player_query.single_mut() else {
+        return;
+    };
+    // The previous step is still animating or its result still has to apply
+    if smooth_movement.is_moving || !pending.results.is_empty() {
+        return;
+    }
+
+    // A step the RPG system turned down leaves the player short of its tile
+    let from = smooth_movement.target_position;
+    if player_resource.player_position() != Some(from) {
+        drop_queued_steps(
+            &mut queue,
+            &mut game_log,
+            "the last step did not go through",
+        );
+        return;
+    }
+    let cost = movement_cost_at(
+        &map_resource,
+        world_hazards.as_deref(),
+        &run_modifiers(game_stats.as_deref()),
+        from.move_direction(direction, 1),
+    );
+    if player_resource
+        .get_player()
+        .is_none_or(|player| player.movement_points() < cost)
+    {
+        drop_queued_steps(&mut queue, &mut game_log, "not enough movement points");
+        return;
+    }
+
+    queue.steps.pop_front();
+    let to = begin_player_step(
+        &mut smooth_movement,
+        entity,
+        direction,
+        &config,
+        &mut movement_started_events,
+        &mut execute_rpg_events,
+    );
+    info!(
+        "🎮 Queued movement: Starting animation from {:?} to {:?}",
+        from, to
+    );
+}
+
+/// Empty the queue with a single warning
+fn drop_queued_steps(queue: &mut MovementQueue, game_log: &mut GameLogService, reason: &str) {
+    let dropped = queue.clear();
+    game_log.log_message(
+        format!(
+            "⏹️ {} queued move{} dropped: {}",
+            dropped,
+            if dropped == 1 { "" } else { "s" },
+            reason
+        ),
+        GameLogType::Warning,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::position::Position3D;
+    use crate::presentation::game_event_logger::GameSystemEvent;
+    use crate::presentation::input::InputMapper;
+    use crate::presentation::movement::{
+        handle_player_movement_input, update_movement_animations, MovementCompleted,
+    };
+    use std::time::Duration;
+
+    /// Stand-in for the RPG system: a landed step moves the player
+    fn land_steps(
+        mut completed: EventReader<MovementCompleted>,
+        mut player_resource: ResMut<PlayerResource>,
+    ) {
+        for event in completed.read() {
+            player_resource.set_position(event.final_position);
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct Visited(Vec<Position3D>);
+
+    fn record_steps(mut steps: EventReader<ExecuteRpgMovement>, mut visited: ResMut<Visited>) {
+        visited
+            .0
+            .extend(steps.read().map(|step| step.target_position));
+    }
+
+    fn queue_app(land: bool) -> App {
+        let mut player_resource = PlayerResource::new();
+        player_resource
+            .create_player(
+                "queued_player".to_string(),
+                "Queued Player".to_string(),
+                Position3D::origin(),
+                crate::domain::PlayerStats::new(10, 10, 10, 10, 10, 10).unwrap(),
+            )
+            .unwrap();
+
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<InputMapper>()
+            .init_resource::<MovementConfig>()
+            .init_resource::<PendingRpgResults>()
+            .init_resource::<MovementQueue>()
+            .init_resource::<Visited>()
+            .insert_resource(player_resource)
+            .insert_resource(MapResource::new())
+            .insert_resource(GameLogService::new())
+            .add_event::<MovementStarted>()
+            .add_event::<MovementCompleted>()
+            .add_event::<ExecuteRpgMovement>()
+            .add_event::<GameSystemEvent>()
+            .add_systems(
+                Update,
+                (
+                    handle_player_movement_input,
+                    follow_movement_queue_system,
+                    update_movement_animations,
+                    record_steps,
+                )
+                    .chain(),
+            );
+        if land {
+            app.add_systems(Update, land_steps.after(update_movement_animations));
+        }
+        app.world_mut().spawn((
+            SmoothMovement::new(Position3D::origin()),
+            Transform::default(),
+            PlayerMarker,
+        ));
+        app
+    }
+
+    fn press(app: &mut App, key: KeyCode) {
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+        app.update();
+        let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.release_all();
+        keyboard.clear();
+    }
+
+    #[test]
+    fn the_queue_keeps_order_and_its_size() {
+        let mut queue = MovementQueue::default();
+        assert!(queue.push(Direction::North, 2));
+        assert!(queue.push(Direction::East, 2));
+        assert!(!queue.push(Direction::South, 2));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.next(), Some(Direction::North));
+
+        assert!(!MovementQueue::default().push(Direction::West, 0));
+        assert_eq!(queue.clear(), 2);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn rapid_keys_visit_every_tile_in_order() {
+        let mut app = queue_app(true);
+
+        // The second key lands mid-step and waits its turn
+        press(&mut app, KeyCode::KeyW);
+        press(&mut app, KeyCode::KeyD);
+        assert_eq!(app.world().resource::<MovementQueue>().len(), 1);
+
+        for _ in 0..20 {
+            app.world_mut()
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(250));
+            app.update();
+        }
+
+        let first = Position3D::origin().move_direction(Direction::South, 1);
+        let second = first.move_direction(Direction::East, 1);
+        assert_eq!(app.world().resource::<Visited>().0, vec![first, second]);
+        assert_eq!(
+            app.world().resource::<PlayerResource>().player_position(),
+            Some(second)
+        );
+        assert!(app.world().resource::<MovementQueue>().is_empty());
+    }
+
+    #[test]
+    fn a_step_that_did_not_go_through_drops_the_rest() {
+        // Without the stand-in for the RPG system the first step never moves the player
+        let mut app = queue_app(false);
+        app.world_mut()
+            .resource_mut::<MovementConfig>()
+            .input_buffer_size = 3;
+
+        press(&mut app, KeyCode::KeyW);
+        press(&mut app, KeyCode::KeyD);
+        press(&mut app, KeyCode::KeyD);
+        assert_eq!(app.world().resource::<MovementQueue>().len(), 2);
+
+        for _ in 0..10 {
+            app.world_mut()
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(250));
+            app.update();
+        }
+
+        assert_eq!(app.world().resource::<Visited>().0.len(), 1);
+        assert!(app.world().resource::<MovementQueue>().is_empty());
+        let warnings = app
+            .world()
+            .resource::<GameLogService>()
+            .get_all_messages()
+            .into_iter()
+            .filter(|message| message.message.contains("queued moves dropped"))
+            .count();
+        assert_eq!(warnings, 1);
+    }
+}
@@ -65,10 +65,14 @@
 //! ### Keyboard (Desktop)
 //! - **WASD** or **Arrow Keys** for movement
 //! - Only cardinal directions (North, South, East, West)
-//! - Input is blocked during movement animations (prevents spam)
+//! - Keys pressed during a step are buffered, up to `input_buffer_size`,
+//!   and walked in order once it lands
 //!
 //! ### Click/Touch (Mobile)
-//! - **Left Click** or **Touch** on adjacent tiles
+//! - **Left Click** or **Touch** on adjacent tiles moves at once
+//! - A tap on a farther tile, or one costing more than
+//!   `tap_to_confirm_threshold`, marks it and moves on a second tap
+//! - Tapping the origin tile just after a move starts undoes it
 //! - Can be configured for cardinal-only or 8-direction movement
 //! - Visual feedback available (configure `show_tile_highlights: true`)
 //!
@@ -80,8 +84,15 @@
 //! 4. This ensures smooth visuals while maintaining game rules
 //!
 
+pub mod key_queue;
+pub mod tap_confirm;
+
 use crate::domain::value_objects::position::{Direction, Position3D};
+use crate::presentation::game_event_logger::GameSystemEvent;
+use crate::presentation::input::{GameAction, InputMapper};
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 /// Plugin for smooth movement system
@@ -94,6 +105,11 @@ impl Plugin for SmoothMovementPlugin {
             (
                 handle_player_movement_input,
                 handle_click_movement_input,
+                tap_confirm::drop_tap_target_system,
+                tap_confirm::tap_move_system,
+                tap_confirm::follow_click_route_system,
+                key_queue::follow_movement_queue_system,
+                tap_confirm::sync_tap_markers_system,
                 update_movement_animations,
                 start_movement_transitions,
                 update_camera_following,
@@ -102,10 +118,22 @@ impl Plugin for SmoothMovementPlugin {
         )
         .add_event::<MovementStarted>()
         .add_event::<MovementCompleted>()
+        .add_event::<MovementResultApplied>()
         .add_event::<ExecuteRpgMovement>()
         .add_event::<TileClickEvent>()
         .add_event::<RestingTriggered>()
+        .add_event::<GameSystemEvent>()
         .init_resource::<MovementConfig>()
+        .init_resource::<InputMapper>()
+        .init_resource::<PendingRpgResults>()
+        .init_resource::<DeferredTransition>()
+        .init_resource::<tap_confirm::TapConfirmation>()
+        .init_resource::<tap_confirm::ClickRoute>()
+        .init_resource::<key_queue::MovementQueue>()
+        .add_systems(
+            OnEnter(crate::presentation::RpgAppState::Exploration),
+            settle_movement_on_enter,
+        )
         .add_systems(
             Update,
             check_for_zero_movement_points
@@ -133,6 +161,10 @@ pub struct SmoothMovement {
     pub elapsed: Duration,
     /// Movement speed modifier
     pub speed_multiplier: f32,
+    /// Stepping back out of an encounter; its completion is not reported
+    pub retreating: bool,
+    /// Way the player faces, turned towards each step as it starts
+    pub facing: Quat,
 }
 
 impl Default for SmoothMovement {
@@ -146,6 +178,8 @@ impl Default for SmoothMovement {
             duration: Duration::from_millis(1000),
             elapsed: Duration::ZERO,
             speed_multiplier: 1.0,
+            retreating: false,
+            facing: Quat::IDENTITY,
         }
     }
 }
@@ -163,6 +197,8 @@ impl SmoothMovement {
             duration: Duration::from_millis(1000),
             elapsed: Duration::ZERO,
             speed_multiplier: 1.0,
+            retreating: false,
+            facing: Quat::IDENTITY,
         }
     }
 
@@ -174,6 +210,7 @@ impl SmoothMovement {
 
         self.start_position = self.target_position;
         self.target_position = target;
+        self.face_step();
         self.is_moving = true;
         self.progress = 0.0;
         self.elapsed = Duration::ZERO;
@@ -181,6 +218,13 @@ impl SmoothMovement {
             Duration::from_millis((config.base_duration_ms as f32 / self.speed_multiplier) as u64);
     }
 
+    /// Turn to face the current step; a step in place keeps the facing
+    fn face_step(&mut self) {
+        if let Some(facing) = step_facing(self.start_position, self.target_position) {
+            self.facing = facing;
+        }
+    }
+
     /// Check if movement is complete
     pub fn is_movement_complete(&self) -> bool {
         !self.is_moving || self.progress >= 1.0
@@ -229,12 +273,24 @@ impl SmoothMovement {
 
         self.start_position = self.target_position;
         self.target_position = target;
+        self.face_step();
         self.is_moving = true;
         self.progress = 0.0;
         self.elapsed = Duration::ZERO;
         self.duration = Duration::from_millis((duration_seconds * 1000.0) as u64);
     }
 
+    /// Step back to `target` after escaping an encounter
+    ///
+    /// Nothing is rolled for a retreat and its completion sends no
+    /// `MovementCompleted`, so the RPG logic never sees it and it cannot
+    /// trigger another event.
+    pub fn start_retreat(&mut self, target: Position3D, config: &MovementConfig) {
+        self.complete_movement();
+        self.start_movement(target, config);
+        self.retreating = true;
+    }
+
     /// Reset to a specific position (used when movement fails)
     pub fn reset_to_position(&mut self, position: Position3D) {
         self.target_position = position;
@@ -243,6 +299,7 @@ impl SmoothMovement {
         self.is_moving = false;
         self.progress = 1.0;
         self.elapsed = Duration::ZERO;
+        self.retreating = false;
     }
 
     /// Set movement speed multiplier
@@ -252,7 +309,8 @@ impl SmoothMovement {
 }
 
 /// Configuration for movement animations
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MovementConfig {
     /// Base duration for tile-to-tile movement in milliseconds
     pub base_duration_ms: u32,
@@ -270,6 +328,14 @@ pub struct MovementConfig {
     pub show_tile_highlights: bool,
     /// Allow diagonal movement via click (keyboard always cardinal only)
     pub allow_diagonal_click_movement: bool,
+    /// Preview every keyboard move's odds before making it, not just with Alt
+    pub odds_preview_always_on: bool,
+    /// Tapped moves costing more than this wait for a second tap
+    pub tap_to_confirm_threshold: u8,
+    /// Tapping the origin tile right after a tapped move starts undoes it
+    pub tap_undo_window: bool,
+    /// Movement keys kept while a step animates; zero drops them
+    pub input_buffer_size: usize,
 }
 
 impl Default for MovementConfig {
@@ -283,6 +349,10 @@ impl Default for MovementConfig {
             enable_keyboard_movement: true,
             show_tile_highlights: false, // Disabled by default for performance
             allow_diagonal_click_movement: false, // Keep consistent with keyboard
+            odds_preview_always_on: false,
+            tap_to_confirm_threshold: 2,
+            tap_undo_window: true,
+            input_buffer_size: 2,
         }
     }
 }
@@ -302,6 +372,168 @@ pub struct MovementCompleted {
     pub final_position: Position3D,
 }
 
+/// Event sent once the delayed RPG result of a completed move has been applied
+///
+/// Unlike [`MovementCompleted`], points granted by the move's event are
+/// already included when this is sent.
+#[derive(Event, Debug, Clone)]
+pub struct MovementResultApplied {
+    pub final_position: Position3D,
+    /// Title of the event the move triggered, if any
+    pub event_title: Option<String>,
+}
+
+/// RPG result of a validated move, waiting for its animation to complete
+#[derive(Debug, Clone)]
+pub struct PendingRpgResult {
+    pub target: Position3D,
+    pub result: crate::domain::services::tile_movement::MovementResult,
+    /// Dice roll sound scheduled for the move, dropped with a stale result
+    pub dice_sound: Entity,
+}
+
+/// Results of moves still animating, in the order they were validated
+#[derive(Resource, Debug, Default)]
+pub struct PendingRpgResults {
+    pub results: Vec<PendingRpgResult>,
+}
+
+impl PendingRpgResults {
+    /// Check if a result waits for the move to `target`
+    pub fn awaits(&self, target: Position3D) -> bool {
+        self.results.iter().any(|pending| pending.target == target)
+    }
+}
+
+/// A screen asked for while the player was still moving
+///
+/// Leaving exploration mid-move would strand the move's result, so the
+/// switch waits until the move has landed.
+#[derive(Resource, Debug, Default)]
+pub struct DeferredTransition {
+    requested: Option<crate::presentation::RpgAppState>,
+}
+
+impl DeferredTransition {
+    /// Hold `state` until the move settles; false if one was held already
+    pub fn defer(&mut self, state: crate::presentation::RpgAppState) -> bool {
+        self.requested.replace(state).is_none()
+    }
+
+    /// The held screen, once the player has stopped moving
+    ///
+    /// The landed move's result may still be on its way; returning to
+    /// exploration settles it if it was left behind.
+    pub fn take_when_settled(&mut self, moving: bool) -> Option<crate::presentation::RpgAppState> {
+        if moving {
+            return None;
+        }
+        self.requested.take()
+    }
+}
+
+/// What returning to exploration did about a move left unfinished
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveSettlement {
+    /// Visual and logical positions already agree
+    Settled,
+    /// The animation was snapped to its end; its result still has to apply
+    Finished(Position3D),
+    /// The animation was put back on the logical position
+    Resynced(Position3D),
+}
+
+/// Bring the animation in line with the logical position
+///
+/// A move whose result still waits is finished so the result can apply;
+/// anything else moves the visual back to where the player really is.
+pub fn settle_movement(
+    smooth_movement: &mut SmoothMovement,
+    logical: Position3D,
+    awaiting_result: bool,
+) -> MoveSettlement {
+    let target = smooth_movement.target_position;
+    if !smooth_movement.is_moving && target == logical {
+        return MoveSettlement::Settled;
+    }
+    if awaiting_result && !smooth_movement.retreating && target != logical {
+        smooth_movement.complete_movement();
+        return MoveSettlement::Finished(target);
+    }
+    smooth_movement.reset_to_position(logical);
+    MoveSettlement::Resynced(logical)
+}
+
+/// Reconcile a move that was in flight when exploration was left
+///
+/// A finished move is reported again so its result applies; results that
+/// can no longer apply are discarded and their movement points refunded.
+/// Runs before the exploration systems of the first frame back.
+fn settle_movement_on_enter(
+    mut player_query: Query<
+        (Entity, &mut SmoothMovement, &mut Transform),
+        With<crate::presentation::map_renderer::PlayerMarker>,
+    >,
+    mut player_resource: ResMut<crate::infrastructure::bevy::resources::PlayerResource>,
+    mut pending: ResMut<PendingRpgResults>,
+    mut movement_completed_events: EventWriter<MovementCompleted>,
+    mut game_log: ResMut<crate::domain::services::game_log_service::GameLogService>,
+    mut deferred: ResMut<DeferredTransition>,
+    mut commands: Commands,
+) {
+    // A screen held on an earlier visit must not open now
+    deferred.requested = None;
+
+    let Some(logical) = player_resource.player_position() else {
+        return;
+    };
+    let Ok((entity, mut smooth_movement, mut transform)) = player_query.single_mut() else {
+        return;
+    };
+
+    let awaiting = pending.awaits(smooth_movement.target_position);
+    match settle_movement(&mut smooth_movement, logical, awaiting) {
+        MoveSettlement::Settled => return,
+        MoveSettlement::Finished(final_position) => {
+            movement_completed_events.write(MovementCompleted {
+                entity,
+                final_position,
+            });
+            game_log.log_message(
+                "Finished the move that was under way".to_string(),
+                crate::domain::services::game_log_service::GameLogType::Movement,
+            );
+        }
+        MoveSettlement::Resynced(position) => {
+            drop_pending_results(&mut pending, &mut player_resource, &mut commands);
+            game_log.log_message(
+                format!(
+                    "Unfinished move dropped, back at ({}, {})",
+                    position.x, position.y
+                ),
+                crate::domain::services::game_log_service::GameLogType::Movement,
+            );
+        }
+    }
+    transform.translation = smooth_movement.current_position;
+}
+
+/// Discard results of moves that will not land, refunding their points
+///
+/// Dice sounds still waiting to play for them are dropped too.
+pub fn drop_pending_results(
+    pending: &mut PendingRpgResults,
+    player_resource: &mut crate::infrastructure::bevy::resources::PlayerResource,
+    commands: &mut Commands,
+) {
+    for stale in pending.results.drain(..) {
+        player_resource.refund_movement_points(stale.result.movement_cost);
+        if let Ok(mut sound) = commands.get_entity(stale.dice_sound) {
+            sound.try_despawn();
+        }
+    }
+}
+
 /// Component to mark entities as movement blockers during animation
 #[derive(Component, Debug)]
 pub struct MovementBlocked {
@@ -329,7 +561,8 @@ impl Default for CameraFollowsMovement {
 /// Event to execute movement through RPG system after animation
 #[derive(Event, Debug, Clone)]
 pub struct ExecuteRpgMovement {
-    pub direction: Direction,
+    /// Cardinal direction of the step; `None` for a diagonal one
+    pub direction: Option<Direction>,
     pub target_position: Position3D,
     pub entity: Entity,
 }
@@ -373,11 +606,16 @@ pub fn update_movement_animations(
         let was_moving = smooth_movement.is_moving;
         smooth_movement.update(time.delta());
 
-        // Update transform position
+        // Update transform position and facing
         transform.translation = smooth_movement.current_position;
+        transform.rotation = smooth_movement.facing;
 
         // Send completion event if movement just finished
         if was_moving && smooth_movement.is_movement_complete() {
+            if smooth_movement.retreating {
+                smooth_movement.retreating = false;
+                continue;
+            }
             info!(
                 "🎮 Smooth movement: Animation completed at {:?}",
                 smooth_movement.target_position
@@ -390,10 +628,66 @@ pub fn update_movement_animations(
     }
 }
 
+/// Map direction of a movement action; up on screen is south on the map
+pub fn screen_direction(action: GameAction) -> Option<Direction> {
+    match action {
+        GameAction::MoveUp => Some(Direction::South),
+        GameAction::MoveDown => Some(Direction::North),
+        GameAction::MoveLeft => Some(Direction::West),
+        GameAction::MoveRight => Some(Direction::East),
+        _ => None,
+    }
+}
+
+/// Prompts that hold the player in place until they answer them
+#[derive(SystemParam)]
+pub struct MovementHolds<'w> {
+    low_points_guard: Option<Res<'w, crate::domain::services::LowPointsGuard>>,
+    hostile_contact: Option<Res<'w, crate::presentation::reputation::HostileContact>>,
+    trader_contact: Option<Res<'w, crate::presentation::field_trade::TraderContact>>,
+    party: Option<Res<'w, crate::infrastructure::bevy::resources::PartyResource>>,
+}
+
+impl MovementHolds<'_> {
+    /// Whether the low movement guard, raiders, a trader or a handover are waiting
+    pub fn blocks_movement(&self) -> bool {
+        self.low_points_guard
+            .as_ref()
+            .is_some_and(|guard| guard.blocks_movement())
+            || self
+                .hostile_contact
+                .as_ref()
+                .is_some_and(|contact| contact.is_pending())
+            || self
+                .trader_contact
+                .as_ref()
+                .is_some_and(|contact| contact.is_pending())
+            || self
+                .party
+                .as_ref()
+                .is_some_and(|party| party.blocks_movement())
+    }
+}
+
+/// Everything a movement key is checked against besides the player and map
+#[derive(SystemParam)]
+pub struct MovementKeyParams<'w> {
+    keyboard_input: Res<'w, ButtonInput<KeyCode>>,
+    input_mapper: Res<'w, InputMapper>,
+    app_state: Option<Res<'w, State<crate::presentation::RpgAppState>>>,
+    holds: MovementHolds<'w>,
+    probe_launcher: Option<Res<'w, crate::presentation::scout_probe::ScoutProbeLauncher>>,
+    world_hazards: Option<Res<'w, crate::domain::services::WorldHazards>>,
+    game_stats: Option<Res<'w, crate::infrastructure::bevy::resources::GameStatsResource>>,
+    odds_preview: Option<ResMut<'w, crate::presentation::odds_preview::OddsPreviewFlow>>,
+    key_queue: ResMut<'w, key_queue::MovementQueue>,
+    system_events: EventWriter<'w, GameSystemEvent>,
+}
+
 /// System to handle player movement input with blocking during animations
 /// This system runs BEFORE the RPG exploration system to intercept and control movement
+#[allow(clippy::type_complexity)]
 pub fn handle_player_movement_input(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
     mut player_query: Query<
         (&mut SmoothMovement, Entity),
         With<crate::presentation::map_renderer::PlayerMarker>,
@@ -403,15 +697,64 @@ pub fn handle_player_movement_input(
     config: Res<MovementConfig>,
     mut movement_started_events: EventWriter<MovementStarted>,
     mut execute_rpg_events: EventWriter<ExecuteRpgMovement>,
+    keys: MovementKeyParams,
 ) {
+    let MovementKeyParams {
+        keyboard_input,
+        input_mapper,
+        app_state,
+        holds,
+        probe_launcher,
+        world_hazards,
+        game_stats,
+        odds_preview,
+        mut key_queue,
+        mut system_events,
+    } = keys;
+
     if !player_resource.has_player() || !config.enable_keyboard_movement {
         return;
     }
 
-    // Check if movement is blocked
+    // The low movement guard, raiders, a trader or a handover are waiting
+    if holds.blocks_movement() {
+        return;
+    }
+
+    // The scout probe panel uses the same keys to pick a heading
+    if probe_launcher.is_some_and(|launcher| launcher.is_choosing()) {
+        return;
+    }
+
+    // The expedition planner uses the same keys to move its cursor, and an
+    // encounter waits for its choice
+    if app_state.is_some_and(|state| {
+        matches!(
+            state.get(),
+            crate::presentation::RpgAppState::ExpeditionPlanning
+                | crate::presentation::RpgAppState::EventResolution
+        )
+    }) {
+        return;
+    }
+
+    // Check for movement input (only process one direction at a time for tile-based movement)
+    let movement_direction = input_mapper
+        .just_pressed_movement(&keyboard_input)
+        .and_then(screen_direction);
+
+    // Keys pressed mid-step, or behind buffered ones, wait their turn
     if let Ok((smooth_movement, _)) = player_query.single() {
-        if config.block_input_during_movement && smooth_movement.is_moving {
-            return; // Block input during movement
+        if config.block_input_during_movement
+            && (smooth_movement.is_moving || !key_queue.is_empty())
+        {
+            // Keys that would ask for the odds preview first are not buffered
+            let wants_preview = config.odds_preview_always_on
+                || keyboard_input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+            if let Some(direction) = movement_direction.filter(|_| !wants_preview) {
+                key_queue.push(direction, config.input_buffer_size);
+            }
+            return;
         }
     }
 
@@ -422,25 +765,6 @@ pub fn handle_player_movement_input(
         }
     }
 
-    let mut movement_direction: Option<Direction> = None;
-
-    // Check for movement input (only process one direction at a time for tile-based movement)
-    if keyboard_input.just_pressed(KeyCode::KeyW) || keyboard_input.just_pressed(KeyCode::ArrowUp) {
-        movement_direction = Some(Direction::South);
-    } else if keyboard_input.just_pressed(KeyCode::KeyS)
-        || keyboard_input.just_pressed(KeyCode::ArrowDown)
-    {
-        movement_direction = Some(Direction::North);
-    } else if keyboard_input.just_pressed(KeyCode::KeyA)
-        || keyboard_input.just_pressed(KeyCode::ArrowLeft)
-    {
-        movement_direction = Some(Direction::West);
-    } else if keyboard_input.just_pressed(KeyCode::KeyD)
-        || keyboard_input.just_pressed(KeyCode::ArrowRight)
-    {
-        movement_direction = Some(Direction::East);
-    }
-
     if let Some(direction) = movement_direction {
         if let Ok((mut smooth_movement, entity)) = player_query.single_mut() {
             // Calculate new target position
@@ -449,12 +773,12 @@ pub fn handle_player_movement_input(
 
             // Pre-validate movement before starting animation
             let current_position = player_resource.player_position().unwrap_or_default();
-            let map = map_resource.current_map();
-            let movement_cost = if let Some(map) = map {
-                map.movement_cost(&new_target)
-            } else {
-                crate::domain::constants::BASE_MOVEMENT_COST
-            };
+            let movement_cost = movement_cost_at(
+                &map_resource,
+                world_hazards.as_deref(),
+                &run_modifiers(game_stats.as_deref()),
+                new_target,
+            );
 
             // Check if player has enough movement points for this specific movement
             if let Some(player) = player_resource.get_player() {
@@ -464,6 +788,10 @@ pub fn handle_player_movement_input(
                         movement_cost,
                         player.movement_points()
                     );
+                    system_events.write(GameSystemEvent::insufficient_resources(
+                        movement_cost,
+                        player.movement_points(),
+                    ));
                     return;
                 }
             }
@@ -474,27 +802,30 @@ pub fn handle_player_movement_input(
                 return;
             }
 
+            // Show the odds first when asked to; the move waits for a second press
+            if let Some(mut flow) = odds_preview {
+                let alt_held = keyboard_input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+                if let crate::presentation::odds_preview::OddsDecision::Preview(_) =
+                    flow.request(new_target, alt_held, config.odds_preview_always_on)
+                {
+                    return;
+                }
+            }
+
             info!(
                 "🎮 Smooth movement: Starting animation from {:?} to {:?}",
                 current_tile, new_target
             );
 
-            // Start movement animation with default duration - will be updated after RPG validation
-            smooth_movement.start_movement(new_target, &config);
-
-            // Send movement started event
-            movement_started_events.write(MovementStarted {
+            // Start the animation and send the RPG movement event immediately
+            begin_player_step(
+                &mut smooth_movement,
                 entity,
-                from: current_tile,
-                to: new_target,
-            });
-
-            // Send RPG movement event immediately
-            execute_rpg_events.write(ExecuteRpgMovement {
                 direction,
-                target_position: new_target,
-                entity,
-            });
+                &config,
+                &mut movement_started_events,
+                &mut execute_rpg_events,
+            );
 
             info!(
                 "🎮 Smooth movement: Sent RPG movement event for {:?}",
@@ -504,42 +835,67 @@ pub fn handle_player_movement_input(
     }
 }
 
-/// System to handle click/touch movement input for mobile
-/// This system also runs BEFORE the RPG exploration system
+/// Start a one-tile step and hand it to the RPG movement logic
+///
+/// The animation starts with the default duration and is adjusted once the
+/// RPG system has validated the move. Returns the target tile.
+pub fn begin_player_step(
+    smooth_movement: &mut SmoothMovement,
+    entity: Entity,
+    direction: Direction,
+    config: &MovementConfig,
+    movement_started_events: &mut EventWriter<MovementStarted>,
+    execute_rpg_events: &mut EventWriter<ExecuteRpgMovement>,
+) -> Position3D {
+    let from = smooth_movement.target_position;
+    let to = from.move_direction(direction, 1);
+
+    smooth_movement.start_movement(to, config);
+    movement_started_events.write(MovementStarted { entity, from, to });
+    execute_rpg_events.write(ExecuteRpgMovement {
+        direction: Some(direction),
+        target_position: to,
+        entity,
+    });
+    to
+}
+
+/// Mouse and touch input, and the HUD that may capture it before the map
+#[derive(SystemParam)]
+pub struct PointerInput<'w, 's> {
+    mouse_button_input: ResMut<'w, ButtonInput<MouseButton>>,
+    touch_events: EventReader<'w, 's, bevy::input::touch::TouchInput>,
+    hud: Option<Res<'w, crate::presentation::hud_layout::HudLayout>>,
+}
+
+/// System to turn clicks and touches into tile clicks
+///
+/// What a click does is decided by the tap confirmation systems, which
+/// also apply the checks that keep the player from moving.
 pub fn handle_click_movement_input(
-    mut mouse_button_input: ResMut<ButtonInput<MouseButton>>,
-    mut touch_events: EventReader<bevy::input::touch::TouchInput>,
+    pointer: PointerInput,
     windows: Query<&Window>,
     camera_query: Query<
         (&Camera, &GlobalTransform),
         With<crate::presentation::map_renderer::IsometricCamera>,
     >,
-    mut player_query: Query<
-        (&mut SmoothMovement, Entity),
-        With<crate::presentation::map_renderer::PlayerMarker>,
-    >,
-    mut player_resource: ResMut<crate::infrastructure::bevy::resources::PlayerResource>,
-    map_resource: Res<crate::infrastructure::bevy::resources::MapResource>,
+    player_resource: Res<crate::infrastructure::bevy::resources::PlayerResource>,
     config: Res<MovementConfig>,
-    mut movement_started_events: EventWriter<MovementStarted>,
-    mut execute_rpg_events: EventWriter<ExecuteRpgMovement>,
+    mut tile_clicks: EventWriter<TileClickEvent>,
 ) {
+    let PointerInput {
+        mut mouse_button_input,
+        mut touch_events,
+        hud,
+    } = pointer;
+
     if !player_resource.has_player() || !config.enable_click_to_move {
         return;
     }
-
-    // Check if movement is blocked
-    if let Ok((smooth_movement, _)) = player_query.single() {
-        if config.block_input_during_movement && smooth_movement.is_moving {
-            return; // Block input during movement
-        }
-    }
-
-    // Check if player has movement points
-    if let Some(player) = player_resource.get_player() {
-        if !player.can_move() {
-            return; // No movement points available
-        }
+    // Clicks and taps on the HUD are not meant for the map
+    if hud.is_some_and(|hud| hud.captures_pointer()) {
+        touch_events.clear();
+        return;
     }
 
     let mut click_position: Option<Vec2> = None;
@@ -562,76 +918,20 @@ pub fn handle_click_movement_input(
         }
     }
 
-    if let Some(screen_pos) = click_position {
-        if let Ok((camera, camera_transform)) = camera_query.single() {
-            if let Ok((mut smooth_movement, entity)) = player_query.single_mut() {
-                // Convert screen position to world position and then to tile coordinates
-                if let Some(clicked_tile) =
-                    screen_to_tile_position(screen_pos, camera, camera_transform)
-                {
-                    let current_tile = smooth_movement.target_position;
-
-                    info!("📱 Click detected at tile: {:?}", clicked_tile);
-
-                    // Check if movement is allowed (adjacent for cardinal, or diagonal if enabled)
-                    if is_valid_click_movement(current_tile, clicked_tile, &config) {
-                        // Pre-validate movement before starting animation
-                        let current_position =
-                            player_resource.player_position().unwrap_or_default();
-                        let map = map_resource.current_map();
-                        let movement_cost = if let Some(map) = map {
-                            map.movement_cost(&clicked_tile)
-                        } else {
-                            crate::domain::constants::BASE_MOVEMENT_COST
-                        };
-
-                        // Check if player has enough movement points for this specific movement
-                        if let Some(player) = player_resource.get_player() {
-                            if player.movement_points() < movement_cost {
-                                info!(
-                                    "⚡ Click movement blocked! Need: {} points, Have: {}",
-                                    movement_cost,
-                                    player.movement_points()
-                                );
-                                return;
-                            }
-                        }
-
-                        let direction = calculate_direction(current_tile, clicked_tile)
-                            .unwrap_or(Direction::North);
-
-                        info!(
-                            "🖱️ Click movement: Starting animation from {:?} to {:?}",
-                            current_tile, clicked_tile
-                        );
-
-                        // Start movement animation with default duration - will be updated after RPG validation
-                        smooth_movement.start_movement(clicked_tile, &config);
-
-                        // Send movement started event
-                        movement_started_events.write(MovementStarted {
-                            entity,
-                            from: current_tile,
-                            to: clicked_tile,
-                        });
-
-                        // Send RPG movement event immediately
-                        execute_rpg_events.write(ExecuteRpgMovement {
-                            direction,
-                            target_position: clicked_tile,
-                            entity,
-                        });
-
-                        info!(
-                            "🖱️ Click movement: Sent RPG movement event for {:?}",
-                            clicked_tile
-                        );
-                    } else {
-                        info!("🖱️ Click movement blocked! Target not adjacent or not allowed");
-                    }
-                }
-            }
-        }
+    let Some(screen_position) = click_position else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+    // Convert screen position to world position and then to tile coordinates
+    if let Some(tile_position) = screen_to_tile_position(screen_position, camera, camera_transform)
+    {
+        info!("📱 Click detected at tile: {:?}", tile_position);
+        tile_clicks.write(TileClickEvent {
+            tile_position,
+            screen_position,
+        });
     }
 }
 
@@ -646,11 +946,20 @@ pub fn update_camera_following(
     time: Res<Time>,
     movement_query: Query<&SmoothMovement>,
     mut camera_query: Query<(&mut Transform, &CameraFollowsMovement), Without<SmoothMovement>>,
+    camera_hints: Option<Res<crate::presentation::camera_hints::CameraHintQueue>>,
 ) {
+    // An active camera hint takes the place of the follow target
+    let hint_focus = camera_hints.and_then(|hints| hints.focus());
     for (mut camera_transform, camera_follow) in camera_query.iter_mut() {
         if let Some(target_entity) = camera_follow.target_entity {
-            if let Ok(smooth_movement) = movement_query.get(target_entity) {
-                let target_position = smooth_movement.current_position + camera_follow.offset;
+            let focus = hint_focus.or_else(|| {
+                movement_query
+                    .get(target_entity)
+                    .ok()
+                    .map(|smooth_movement| smooth_movement.current_position)
+            });
+            if let Some(focus) = focus {
+                let target_position = focus + camera_follow.offset;
                 let follow_speed = camera_follow.follow_speed;
 
                 camera_transform.translation = camera_transform
@@ -661,9 +970,41 @@ pub fn update_camera_following(
     }
 }
 
+/// Movement points a step onto `target` costs, anomaly storms and the
+/// season included
+///
+/// Storms only cover the overworld; interiors use their own coordinates.
+pub fn movement_cost_at(
+    map_resource: &crate::infrastructure::bevy::resources::MapResource,
+    world_hazards: Option<&crate::domain::services::WorldHazards>,
+    modifiers: &crate::domain::services::ModifierStack,
+    target: Position3D,
+) -> u8 {
+    let mut conditions = match world_hazards {
+        Some(hazards) if !map_resource.is_in_interior() => hazards.conditions_at(target),
+        _ => crate::domain::services::MovementConditions::default(),
+    };
+    conditions.open_terrain_surcharge = modifiers.open_terrain_surcharge();
+    map_resource
+        .current_map()
+        .map(|map| conditions.step_cost(map, &target))
+        .unwrap_or(crate::domain::constants::BASE_MOVEMENT_COST)
+}
+
+/// Modifiers of the run, or a plain stack while there are no game stats
+pub fn run_modifiers(
+    game_stats: Option<&crate::infrastructure::bevy::resources::GameStatsResource>,
+) -> crate::domain::services::ModifierStack {
+    game_stats
+        .map(|stats| stats.modifiers.clone())
+        .unwrap_or_default()
+}
+
 /// Convert tile position to world position
+///
+/// The one tile-to-world conversion: the map renderer places tiles with it
+/// and the inspection overlay checks entities against it.
 pub fn tile_to_world_position(tile_pos: Position3D) -> Vec3 {
-    // Use the same coordinate conversion as the existing tile_to_world_position in map_renderer
     let x = (tile_pos.x as f32) * 2.2; // Proper spacing for 2.0 unit wide tiles
     let z = (tile_pos.y as f32) * 2.2;
     let y = (tile_pos.z as f32) * 0.3; // Layer height
@@ -784,7 +1125,22 @@ pub fn check_for_zero_movement_points(
     }
 }
 
-/// Calculate direction from one tile to adjacent tile
+/// Rotation that points the player's forward (-Z) along a step
+///
+/// Derived from the step's delta on the ground, so diagonal steps face
+/// diagonally. Returns `None` for a step without a ground component.
+pub fn step_facing(from: Position3D, to: Position3D) -> Option<Quat> {
+    let delta = tile_to_world_position(to) - tile_to_world_position(from);
+    if delta.x == 0.0 && delta.z == 0.0 {
+        return None;
+    }
+    Some(Quat::from_rotation_y(f32::atan2(-delta.x, -delta.z)))
+}
+
+/// Calculate the cardinal direction from one tile to an adjacent tile
+///
+/// Diagonal steps have no cardinal direction; their facing comes from
+/// [`step_facing`].
 fn calculate_direction(from: Position3D, to: Position3D) -> Option<Direction> {
     let dx = to.x - from.x;
     let dy = to.y - from.y;
@@ -797,12 +1153,6 @@ fn calculate_direction(from: Position3D, to: Position3D) -> Option<Direction> {
             (0, -1) => Some(Direction::South),
             (1, 0) => Some(Direction::East),
             (-1, 0) => Some(Direction::West),
-            // For diagonal movement, pick the primary direction
-            // This is a simplified approach - you might want more sophisticated logic
-            (1, 1) => Some(Direction::North),   // NE -> N
-            (1, -1) => Some(Direction::South),  // SE -> S
-            (-1, 1) => Some(Direction::North),  // NW -> N
-            (-1, -1) => Some(Direction::South), // SW -> S
             _ => None,
         }
     } else if dx == 0 && dy == 0 {
@@ -843,6 +1193,37 @@ mod tests {
         assert_eq!(smooth.target_position, target);
     }
 
+    #[test]
+    fn a_retreat_completes_without_reporting_a_move() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_event::<MovementCompleted>()
+            .add_systems(Update, update_movement_animations);
+        let config = MovementConfig::default();
+        let mut smooth = SmoothMovement::new(Position3D::new(1, 0, 0));
+        smooth.start_retreat(Position3D::origin(), &config);
+        let player = app.world_mut().spawn((smooth, Transform::default())).id();
+
+        let completed = |app: &mut App| {
+            app.world_mut()
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs(5));
+            app.update();
+            app.world().resource::<Events<MovementCompleted>>().len()
+        };
+        assert_eq!(completed(&mut app), 0);
+        let smooth = app.world().get::<SmoothMovement>(player).unwrap();
+        assert_eq!(smooth.target_position, Position3D::origin());
+        assert!(!smooth.is_moving && !smooth.retreating);
+
+        // The next ordinary move is reported again
+        app.world_mut()
+            .get_mut::<SmoothMovement>(player)
+            .unwrap()
+            .start_movement(Position3D::new(0, 1, 0), &config);
+        assert_eq!(completed(&mut app), 1);
+    }
+
     #[test]
     fn smooth_movement_update() {
         let mut smooth = SmoothMovement::new(Position3D::origin());
@@ -900,6 +1281,7 @@ mod tests {
         assert!(config.block_input_during_movement);
         assert!(config.enable_click_to_move);
         assert!(config.enable_keyboard_movement);
+        assert_eq!(config.input_buffer_size, 2);
     }
 
     #[test]
@@ -1099,7 +1481,129 @@ mod tests {
             Some(Direction::Down)
         );
 
-        // Test diagonal (should return None for now)
+        // Diagonal steps have no cardinal direction
         assert_eq!(calculate_direction(origin, Position3D::new(1, 1, 0)), None);
     }
+
+    #[test]
+    fn screen_switches_wait_for_the_move_to_land() {
+        use crate::presentation::RpgAppState;
+
+        let mut deferred = DeferredTransition::default();
+        assert!(deferred.defer(RpgAppState::BaseManagement));
+        // A second key press replaces the screen without a second hint
+        assert!(!deferred.defer(RpgAppState::Inventory));
+        assert_eq!(deferred.take_when_settled(true), None);
+        assert_eq!(
+            deferred.take_when_settled(false),
+            Some(RpgAppState::Inventory)
+        );
+        assert_eq!(deferred.take_when_settled(false), None);
+    }
+
+    #[test]
+    fn steps_face_the_way_they_go_including_diagonals() {
+        let origin = Position3D::origin();
+        let forward = |to| {
+            let facing = step_facing(origin, to).unwrap();
+            let heading = facing * Vec3::NEG_Z;
+            Vec2::new(heading.x, heading.z)
+        };
+        let close = |a: Vec2, b: Vec2| a.distance(b) < 1e-5;
+
+        assert!(close(forward(Position3D::new(1, 0, 0)), Vec2::X));
+        assert!(close(forward(Position3D::new(0, 1, 0)), Vec2::Y));
+        assert!(close(
+            forward(Position3D::new(1, 1, 0)),
+            Vec2::new(1.0, 1.0).normalize()
+        ));
+        assert!(close(
+            forward(Position3D::new(-1, -1, 0)),
+            Vec2::new(-1.0, -1.0).normalize()
+        ));
+        assert_eq!(step_facing(origin, Position3D::new(0, 0, 1)), None);
+
+        // The facing holds once the step has landed
+        let config = MovementConfig::default();
+        let mut smooth = SmoothMovement::new(origin);
+        smooth.start_movement(Position3D::new(-1, 1, 0), &config);
+        smooth.complete_movement();
+        assert!(close(
+            {
+                let heading = smooth.facing * Vec3::NEG_Z;
+                Vec2::new(heading.x, heading.z)
+            },
+            Vec2::new(-1.0, 1.0).normalize()
+        ));
+    }
+
+    #[test]
+    fn a_landed_move_awaiting_its_result_is_finished() {
+        let config = MovementConfig::default();
+        let target = Position3D::new(1, 0, 0);
+        let mut smooth = SmoothMovement::new(Position3D::origin());
+        smooth.start_movement(target, &config);
+
+        let mut logical = Position3D::origin();
+        assert_eq!(
+            settle_movement(&mut smooth, logical, true),
+            MoveSettlement::Finished(target)
+        );
+        assert!(!smooth.is_moving);
+        assert_eq!(smooth.current_position, tile_to_world_position(target));
+
+        // The exploration system applies the result on the resent completion
+        logical = target;
+        assert_eq!(
+            settle_movement(&mut smooth, logical, false),
+            MoveSettlement::Settled
+        );
+        assert_eq!(smooth.target_position, logical);
+    }
+
+    #[test]
+    fn returning_mid_move_without_a_result_resyncs_the_player() {
+        use crate::domain::services::game_log_service::GameLogService;
+        use crate::infrastructure::bevy::resources::PlayerResource;
+        use crate::presentation::map_renderer::PlayerMarker;
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        let mut player_resource = PlayerResource::new();
+        player_resource
+            .create_player(
+                "moving_player".to_string(),
+                "Moving Player".to_string(),
+                Position3D::origin(),
+                crate::domain::PlayerStats::new(10, 10, 10, 10, 10, 10).unwrap(),
+            )
+            .unwrap();
+        world.insert_resource(player_resource);
+        world.insert_resource(GameLogService::new());
+        world.init_resource::<PendingRpgResults>();
+        world.init_resource::<DeferredTransition>();
+        world.init_resource::<Events<MovementCompleted>>();
+
+        let mut smooth = SmoothMovement::new(Position3D::origin());
+        smooth.start_movement(Position3D::new(0, 1, 0), &MovementConfig::default());
+        smooth.update(Duration::from_millis(300));
+        let player = world
+            .spawn((smooth, Transform::default(), PlayerMarker))
+            .id();
+
+        world.run_system_once(settle_movement_on_enter).unwrap();
+
+        let logical = world
+            .resource::<PlayerResource>()
+            .player_position()
+            .unwrap();
+        let smooth = world.get::<SmoothMovement>(player).unwrap();
+        assert!(!smooth.is_moving);
+        assert_eq!(smooth.target_position, logical);
+        assert_eq!(
+            world.get::<Transform>(player).unwrap().translation,
+            tile_to_world_position(logical)
+        );
+        assert!(world.resource::<Events<MovementCompleted>>().is_empty());
+    }
 }
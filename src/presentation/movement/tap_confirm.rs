@@ -0,0 +1,534 @@
+//! Tap Confirmation - Second taps for costly moves and a mis-tap undo
+//!
+//! A tap on an adjacent tile that costs no more than the
+//! `tap_to_confirm_threshold` moves at once, as before. A tap on a farther
+//! tile, or on a costlier one, only marks it: a marker goes on the target
+//! and on every step of the cheapest known route, diagonal steps included
+//! when `allow_diagonal_click_movement` is on, and the log shows the
+//! projected cost. Tapping the same tile again within
+//! `TAP_CONFIRM_TIMEOUT_SECS` confirms and the route is walked one step at
+//! a time; a tap anywhere else, any key or leaving exploration drops it.
+//!
+//! With the undo window on, tapping the origin tile within
+//! `TAP_UNDO_WINDOW_MS` of a move starting puts the player back and refunds
+//! the move, the same way an unfinished move is dropped when exploration
+//! is left.
+
+use super::{
+    calculate_direction, drop_pending_results, is_valid_click_movement, movement_cost_at,
+    run_modifiers, ExecuteRpgMovement, MovementConfig, MovementHolds, MovementStarted,
+    PendingRpgResults, SmoothMovement, TileClickEvent,
+};
+use crate::domain::constants::{TAP_CONFIRM_TIMEOUT_SECS, TAP_UNDO_WINDOW_MS, WARNING_TEXT};
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{find_route, WorldHazards};
+use crate::domain::value_objects::position::Position3D;
+use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource, PlayerResource};
+use crate::presentation::map_renderer::PlayerMarker;
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// A tapped move: the tile and the steps leading there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TapPlan {
+    pub target: Position3D,
+    /// Tiles stepped onto, ending with the target
+    pub route: Vec<Position3D>,
+    /// Movement points the whole route is projected to cost
+    pub cost: u32,
+}
+
+impl TapPlan {
+    /// Log line asking for the second tap
+    pub fn describe(&self) -> String {
+        format!(
+            "🎯 Tap ({}, {}) again to move: {} tile(s), {} movement points",
+            self.target.x,
+            self.target.y,
+            self.route.len(),
+            self.cost
+        )
+    }
+}
+
+/// What a tap did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TapDecision {
+    /// Move right away
+    Move(TapPlan),
+    /// The target is marked and waits for a second tap
+    Mark,
+    /// The marked target was tapped again
+    Confirm(TapPlan),
+    /// The tap dropped the marked target
+    Cancel,
+}
+
+/// Check if a tapped move has to be confirmed
+pub fn needs_confirmation(adjacent: bool, cost: u32, config: &MovementConfig) -> bool {
+    !adjacent || cost > config.tap_to_confirm_threshold as u32
+}
+
+/// The tapped target waiting for its second tap
+#[derive(Resource, Debug, Default)]
+pub struct TapConfirmation {
+    marked: Option<(TapPlan, f32)>,
+}
+
+impl TapConfirmation {
+    /// The marked move, if any
+    pub fn marked(&self) -> Option<&TapPlan> {
+        self.marked.as_ref().map(|(plan, _)| plan)
+    }
+
+    /// Decide what a tap planned as `plan` does at `now` seconds
+    pub fn tap(&mut self, plan: TapPlan, needs_confirmation: bool, now: f32) -> TapDecision {
+        self.expire(now);
+        if let Some((marked, _)) = self.marked.take() {
+            return if marked.target == plan.target {
+                TapDecision::Confirm(plan)
+            } else {
+                TapDecision::Cancel
+            };
+        }
+        if needs_confirmation {
+            self.marked = Some((plan, now));
+            TapDecision::Mark
+        } else {
+            TapDecision::Move(plan)
+        }
+    }
+
+    /// Drop a target marked too long ago; true if one was dropped
+    pub fn expire(&mut self, now: f32) -> bool {
+        let expired = self
+            .marked
+            .as_ref()
+            .is_some_and(|(_, placed)| now - placed > TAP_CONFIRM_TIMEOUT_SECS);
+        if expired {
+            self.marked = None;
+        }
+        expired
+    }
+
+    /// Drop the marked target; true if there was one
+    pub fn cancel(&mut self) -> bool {
+        self.marked.take().is_some()
+    }
+}
+
+/// Steps of a confirmed tapped move still to walk
+#[derive(Resource, Debug, Default)]
+pub struct ClickRoute {
+    steps: VecDeque<Position3D>,
+}
+
+impl ClickRoute {
+    /// Walk `route` from where the player stands
+    pub fn start(&mut self, route: Vec<Position3D>) {
+        self.steps = route.into();
+    }
+
+    /// Stop walking
+    pub fn clear(&mut self) {
+        self.steps.clear();
+    }
+
+    /// The next step to take
+    pub fn next(&self) -> Option<Position3D> {
+        self.steps.front().copied()
+    }
+}
+
+/// Marks the target and the route of a tapped move
+#[derive(Component)]
+pub struct TapMarker;
+
+/// Drop the marked target on any key, on timeout or outside exploration
+pub fn drop_tap_target_system(
+    time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    app_state: Option<Res<State<RpgAppState>>>,
+    mut tap: ResMut<TapConfirmation>,
+    mut route: ResMut<ClickRoute>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    let exploring = app_state.is_none_or(|state| *state.get() == RpgAppState::Exploration);
+    if !exploring {
+        tap.cancel();
+        route.clear();
+        return;
+    }
+    if keyboard.get_just_pressed().next().is_some() {
+        route.clear();
+        if tap.cancel() {
+            game_log.log_message("🎯 Move target dropped".to_string(), GameLogType::Movement);
+        }
+    }
+    if tap.expire(time.elapsed_secs()) {
+        game_log.log_message("🎯 Move target expired".to_string(), GameLogType::Movement);
+    }
+}
+
+/// Decide what a tile click does: move, mark, confirm, cancel or undo
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn tap_move_system(
+    mut tile_clicks: EventReader<TileClickEvent>,
+    time: Res<Time>,
+    config: Res<MovementConfig>,
+    app_state: Option<Res<State<RpgAppState>>>,
+    mut tap: ResMut<TapConfirmation>,
+    mut route: ResMut<ClickRoute>,
+    mut player_query: Query<&mut SmoothMovement, With<PlayerMarker>>,
+    mut player_resource: ResMut<PlayerResource>,
+    map_resource: Res<MapResource>,
+    world_hazards: Option<Res<WorldHazards>>,
+    mut pending: ResMut<PendingRpgResults>,
+    holds: MovementHolds,
+    mut commands: Commands,
+    mut game_log: ResMut<GameLogService>,
+    game_stats: Option<Res<GameStatsResource>>,
+) {
+    let Some(click) = tile_clicks.read().last().cloned() else {
+        return;
+    };
+    if app_state.is_some_and(|state| *state.get() != RpgAppState::Exploration) {
+        return;
+    }
+    let Ok(mut smooth_movement) = player_query.single_mut() else {
+        return;
+    };
+
+    if smooth_movement.is_moving {
+        // A tap back on the origin right after starting takes the move back
+        let undo = config.tap_undo_window
+            && !smooth_movement.retreating
+            && click.tile_position == smooth_movement.start_position
+            && smooth_movement.elapsed < Duration::from_millis(TAP_UNDO_WINDOW_MS);
+        if undo {
+            let logical = player_resource
+                .player_position()
+                .unwrap_or(smooth_movement.start_position);
+            smooth_movement.reset_to_position(logical);
+            drop_pending_results(&mut pending, &mut player_resource, &mut commands);
+            route.clear();
+            game_log.log_message("↩️ Move undone".to_string(), GameLogType::Movement);
+        }
+        // Other taps wait for the move to land
+        return;
+    }
+
+    // The low movement guard, raiders, a trader or a handover are waiting for the player
+    let blocked = holds.blocks_movement();
+    if blocked
+        || !player_resource
+            .get_player()
+            .is_some_and(|player| player.can_move())
+    {
+        return;
+    }
+
+    let from = smooth_movement.target_position;
+    let target = click.tile_position;
+    let adjacent = is_valid_click_movement(from, target, &config);
+    let steps = if adjacent {
+        Some(vec![target])
+    } else if target != from {
+        map_resource.current_map().and_then(|map| {
+            find_route(map, from, target, config.allow_diagonal_click_movement).ok()
+        })
+    } else {
+        None
+    };
+    let Some(steps) = steps else {
+        if tap.cancel() {
+            game_log.log_message("🎯 Move target dropped".to_string(), GameLogType::Movement);
+        } else if target != from {
+            info!("🖱️ Click movement blocked! No known route to {:?}", target);
+        }
+        return;
+    };
+    let modifiers = run_modifiers(game_stats.as_deref());
+    let cost = steps
+        .iter()
+        .map(|&step| {
+            movement_cost_at(&map_resource, world_hazards.as_deref(), &modifiers, step) as u32
+        })
+        .sum();
+    let plan = TapPlan {
+        target,
+        route: steps,
+        cost,
+    };
+
+    match tap.tap(
+        plan,
+        needs_confirmation(adjacent, cost, &config),
+        time.elapsed_secs(),
+    ) {
+        TapDecision::Move(plan) | TapDecision::Confirm(plan) => {
+            info!("🖱️ Click movement: walking {} step(s)", plan.route.len());
+            route.start(plan.route);
+        }
+        TapDecision::Mark => {
+            if let Some(plan) = tap.marked() {
+                game_log.log_message(plan.describe(), GameLogType::Movement);
+            }
+        }
+        TapDecision::Cancel => {
+            game_log.log_message("🎯 Move target dropped".to_string(), GameLogType::Movement)
+        }
+    }
+}
+
+/// Take the next step of a tapped move once the last one has landed
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn follow_click_route_system(
+    mut route: ResMut<ClickRoute>,
+    mut player_query: Query<(&mut SmoothMovement, Entity), With<PlayerMarker>>,
+    player_resource: Res<PlayerResource>,
+    map_resource: Res<MapResource>,
+    world_hazards: Option<Res<WorldHazards>>,
+    config: Res<MovementConfig>,
+    pending: Res<PendingRpgResults>,
+    holds: MovementHolds,
+    mut movement_started_events: EventWriter<MovementStarted>,
+    mut execute_rpg_events: EventWriter<ExecuteRpgMovement>,
+    mut game_log: ResMut<GameLogService>,
+    game_stats: Option<Res<GameStatsResource>>,
+) {
+    let Some(next) = route.next() else {
+        return;
+    };
+    let Ok((mut smooth_movement, entity)) = player_query.single_mut() else {
+        return;
+    };
+    // The previous step is still animating or its result still has to apply
+    if smooth_movement.is_moving || !pending.results.is_empty() {
+        return;
+    }
+
+    // Anything that stops a move, or a step that no longer follows on, ends the route
+    let blocked = holds.blocks_movement();
+    let from = smooth_movement.target_position;
+    let on_route = player_resource.player_position() == Some(from)
+        && is_valid_click_movement(from, next, &config);
+    if blocked || !on_route {
+        route.clear();
+        return;
+    }
+    let cost = movement_cost_at(
+        &map_resource,
+        world_hazards.as_deref(),
+        &run_modifiers(game_stats.as_deref()),
+        next,
+    );
+    if player_resource
+        .get_player()
+        .is_none_or(|player| player.movement_points() < cost)
+    {
+        route.clear();
+        game_log.log_message(
+            "⚡ Not enough movement points to go on".to_string(),
+            GameLogType::Movement,
+        );
+        return;
+    }
+
+    route.steps.pop_front();
+    let direction = calculate_direction(from, next);
+    info!(
+        "🖱️ Click movement: Starting animation from {:?} to {:?}",
+        from, next
+    );
+    smooth_movement.start_movement(next, &config);
+    movement_started_events.write(MovementStarted {
+        entity,
+        from,
+        to: next,
+    });
+    execute_rpg_events.write(ExecuteRpgMovement {
+        direction,
+        target_position: next,
+        entity,
+    });
+}
+
+/// Keep the markers on the marked target and its route, and only there
+///
+/// Apps without a 3D renderer have no mesh assets and get no markers.
+pub fn sync_tap_markers_system(
+    mut commands: Commands,
+    tap: Res<TapConfirmation>,
+    markers: Query<Entity, With<TapMarker>>,
+    meshes: Option<ResMut<Assets<Mesh>>>,
+    materials: Option<ResMut<Assets<StandardMaterial>>>,
+    mut shown: Local<Option<TapPlan>>,
+) {
+    let (Some(mut meshes), Some(mut materials)) = (meshes, materials) else {
+        return;
+    };
+    let wanted = tap.marked();
+    if shown.as_ref() == wanted {
+        return;
+    }
+    for entity in markers.iter() {
+        commands.entity(entity).despawn();
+    }
+    *shown = wanted.cloned();
+    let Some(plan) = wanted else {
+        return;
+    };
+
+    let material = materials.add(StandardMaterial {
+        base_color: WARNING_TEXT,
+        emissive: LinearRgba::from(WARNING_TEXT) * 0.5,
+        ..default()
+    });
+    let target_mesh = meshes.add(Mesh::from(Cylinder::new(0.35, 1.6)));
+    let step_mesh = meshes.add(Mesh::from(Sphere::new(0.15)));
+    for &step in &plan.route {
+        let world = super::tile_to_world_position(step);
+        let (mesh, height) = if step == plan.target {
+            (target_mesh.clone(), 1.0)
+        } else {
+            (step_mesh.clone(), 0.6)
+        };
+        commands.spawn((
+            Mesh3d(mesh),
+            MeshMaterial3d(material.clone()),
+            Transform::from_translation(Vec3::new(world.x, height, world.z)),
+            TapMarker,
+            Name::new("TapMarker"),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+
+    fn plan(x: i32, cost: u32) -> TapPlan {
+        let target = Position3D::new(x, 0, 0);
+        TapPlan {
+            target,
+            route: (1..=x).map(|step| Position3D::new(step, 0, 0)).collect(),
+            cost,
+        }
+    }
+
+    #[test]
+    fn second_taps_confirm_within_the_timeout() {
+        let mut tap = TapConfirmation::default();
+        // Cheap adjacent moves go at once
+        assert_eq!(
+            tap.tap(plan(1, 1), false, 0.0),
+            TapDecision::Move(plan(1, 1))
+        );
+
+        assert_eq!(tap.tap(plan(3, 5), true, 0.0), TapDecision::Mark);
+        assert_eq!(tap.marked(), Some(&plan(3, 5)));
+        assert_eq!(
+            tap.tap(plan(3, 5), true, 1.5),
+            TapDecision::Confirm(plan(3, 5))
+        );
+        assert_eq!(tap.marked(), None);
+
+        // Another tile drops the target without moving
+        assert_eq!(tap.tap(plan(3, 5), true, 0.0), TapDecision::Mark);
+        assert_eq!(tap.tap(plan(2, 3), true, 0.5), TapDecision::Cancel);
+        assert_eq!(tap.marked(), None);
+
+        // Too late: the second tap marks afresh
+        assert_eq!(tap.tap(plan(3, 5), true, 0.0), TapDecision::Mark);
+        assert!(!tap.expire(TAP_CONFIRM_TIMEOUT_SECS));
+        assert_eq!(
+            tap.tap(plan(3, 5), true, TAP_CONFIRM_TIMEOUT_SECS + 0.1),
+            TapDecision::Mark
+        );
+        assert!(tap.expire(2.0 * TAP_CONFIRM_TIMEOUT_SECS + 0.2));
+        assert!(!tap.cancel());
+    }
+
+    #[test]
+    fn only_far_or_costly_taps_need_a_second_tap() {
+        let config = MovementConfig::default();
+        let threshold = config.tap_to_confirm_threshold as u32;
+        assert!(!needs_confirmation(true, threshold, &config));
+        assert!(needs_confirmation(true, threshold + 1, &config));
+        assert!(needs_confirmation(false, 1, &config));
+
+        let always = MovementConfig {
+            tap_to_confirm_threshold: 0,
+            ..MovementConfig::default()
+        };
+        assert!(needs_confirmation(true, 1, &always));
+    }
+
+    #[test]
+    fn markers_clear_on_every_way_out() {
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin)
+            .insert_state(RpgAppState::Exploration)
+            .init_resource::<Time>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<StandardMaterial>>()
+            .init_resource::<TapConfirmation>()
+            .init_resource::<ClickRoute>()
+            .insert_resource(GameLogService::new())
+            .add_systems(
+                Update,
+                (drop_tap_target_system, sync_tap_markers_system).chain(),
+            );
+        let markers = |app: &mut App| {
+            app.update();
+            app.world_mut()
+                .query_filtered::<(), With<TapMarker>>()
+                .iter(app.world())
+                .count()
+        };
+        let tap = |app: &mut App, target: i32| {
+            let now = app.world().resource::<Time>().elapsed_secs();
+            app.world_mut()
+                .resource_mut::<TapConfirmation>()
+                .tap(plan(target, 4), true, now)
+        };
+
+        // Confirmation
+        tap(&mut app, 3);
+        assert_eq!(markers(&mut app), 3);
+        assert!(matches!(tap(&mut app, 3), TapDecision::Confirm(_)));
+        assert_eq!(markers(&mut app), 0);
+
+        // Cancellation by any key
+        tap(&mut app, 2);
+        assert_eq!(markers(&mut app), 2);
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyW);
+        assert_eq!(markers(&mut app), 0);
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .clear();
+
+        // Timeout
+        tap(&mut app, 2);
+        assert_eq!(markers(&mut app), 2);
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(TAP_CONFIRM_TIMEOUT_SECS + 0.5));
+        assert_eq!(markers(&mut app), 0);
+
+        // Leaving exploration
+        tap(&mut app, 2);
+        assert_eq!(markers(&mut app), 2);
+        app.world_mut()
+            .resource_mut::<NextState<RpgAppState>>()
+            .set(RpgAppState::Paused);
+        assert_eq!(markers(&mut app), 0);
+    }
+}
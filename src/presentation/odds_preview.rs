@@ -0,0 +1,367 @@
+//! Odds Preview - Cost and event chance of a move before it is made
+//!
+//! Holding Alt while pressing a direction previews the move instead of
+//! making it: an overlay above the target tile shows the movement cost, the
+//! chance of an event, the threat tier and the roll modifier. Pressing the
+//! direction again without Alt makes the move. With the always-on setting
+//! (F3) every first press previews and the second press moves. The scanner
+//! tooltip shows the same line for the tiles next to the player.
+//!
+//! Previews come from `TileMovementService::preview_movement`, fed with the
+//! same assist, threat and storm conditions as the move itself; they never
+//! roll, so looking cannot change what a move will do.
+
+use crate::domain::constants::{PANEL_BACKGROUND, PRIMARY_TEXT};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{
+    ModifierStack, MovementConditions, MovementPreview, TileMovementService, TimedObjective,
+    WorldHazards,
+};
+use crate::domain::value_objects::{DiceModifier, Position3D};
+use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource, PlayerResource};
+use crate::presentation::game_state::{RpgAppState, RpgGameSession};
+use crate::presentation::map_renderer::IsometricCamera;
+use crate::presentation::movement::{tile_to_world_position, MovementConfig};
+use bevy::prelude::*;
+
+/// Key that switches the always-on odds preview
+pub const ODDS_PREVIEW_KEY: KeyCode = KeyCode::F3;
+
+/// Height above the target tile the overlay is anchored at
+const OVERLAY_LIFT: f32 = 1.2;
+
+/// Plugin for the odds preview overlay and tooltip
+pub struct OddsPreviewPlugin;
+
+impl Plugin for OddsPreviewPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<OddsPreviewFlow>()
+            .init_resource::<AdjacentOdds>()
+            .add_systems(Startup, setup_odds_overlay)
+            .add_systems(
+                Update,
+                (
+                    toggle_always_on_system,
+                    adjacent_odds_system,
+                    update_odds_overlay_system,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// What a direction press should do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OddsDecision {
+    /// Show the odds of the move and wait
+    Preview(Position3D),
+    /// Make the move
+    Execute(Position3D),
+}
+
+/// Which move, if any, is being previewed
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct OddsPreviewFlow {
+    previewing: Option<Position3D>,
+}
+
+impl OddsPreviewFlow {
+    /// Tile whose odds are on screen
+    pub fn previewing(&self) -> Option<Position3D> {
+        self.previewing
+    }
+
+    /// Decide what a press towards `target` does
+    ///
+    /// Alt always previews. Without it, the move goes ahead, unless the
+    /// preview is always on and `target` was not the tile being previewed.
+    pub fn request(&mut self, target: Position3D, alt_held: bool, always_on: bool) -> OddsDecision {
+        let confirmed = self.previewing == Some(target);
+        if alt_held || (always_on && !confirmed) {
+            self.previewing = Some(target);
+            return OddsDecision::Preview(target);
+        }
+        self.previewing = None;
+        OddsDecision::Execute(target)
+    }
+
+    /// Drop the preview
+    pub fn clear(&mut self) {
+        self.previewing = None;
+    }
+}
+
+/// Assist and conditions a move into a tile rolls with
+pub struct MoveInputs {
+    /// Fortune's Favor bonus alone
+    pub assist: DiceModifier,
+    /// Assist plus the unanswered distress penalty
+    pub roll_modifier: DiceModifier,
+    pub conditions: MovementConditions,
+}
+
+impl MoveInputs {
+    /// Inputs of a move into `target`; storms only sweep the overworld
+    pub fn for_move(
+        session: &RpgGameSession,
+        timed_objective: &TimedObjective,
+        world_hazards: &WorldHazards,
+        modifiers: &ModifierStack,
+        storms_apply: bool,
+        target: Position3D,
+    ) -> Self {
+        // Fortune's Favor bonus for this roll (zero unless enabled and on a bad streak)
+        let assist = session
+            .fortune_favor
+            .modifier()
+            .unwrap_or_else(|_| DiceModifier::none());
+
+        // Regions where distress signals went unanswered roll worse, and so
+        // does every region in a Surge
+        let threat = timed_objective
+            .threat_at(target)
+            .saturating_add(modifiers.threat());
+        let roll_modifier = DiceModifier::situational(-(threat as i8))
+            .and_then(|penalty| assist.add(&penalty))
+            .unwrap_or_else(|_| assist.clone());
+
+        // Anomaly storms double the cost and roll with disadvantage
+        let mut conditions = if storms_apply {
+            world_hazards.conditions_at(target)
+        } else {
+            MovementConditions::default()
+        };
+        // Faction standing decides which flavour of an event turns up
+        conditions.reputation = session.reputation;
+        // Earlier choices decide which follow-up events can turn up
+        conditions.flags = session.flags.clone();
+        // The season may slow open ground and stir up events
+        conditions.open_terrain_surcharge = modifiers.open_terrain_surcharge();
+        conditions.event_pressure = modifiers.event_pressure();
+
+        Self {
+            assist,
+            roll_modifier,
+            conditions,
+        }
+    }
+}
+
+/// Previews of the moves into the tiles next to the player
+#[derive(Resource, Debug, Default)]
+pub struct AdjacentOdds {
+    origin: Option<Position3D>,
+    previews: Vec<MovementPreview>,
+}
+
+impl AdjacentOdds {
+    /// Preview of the move into `target`, if it is next to the player
+    pub fn at(&self, target: Position3D) -> Option<&MovementPreview> {
+        self.previews
+            .iter()
+            .find(|preview| preview.target_position == target)
+    }
+}
+
+#[derive(Component)]
+struct OddsOverlay;
+
+fn setup_odds_overlay(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: FontSize::Small.to_pixels(),
+            ..default()
+        },
+        TextColor(PRIMARY_TEXT),
+        Node {
+            position_type: PositionType::Absolute,
+            padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+            ..default()
+        },
+        BackgroundColor(PANEL_BACKGROUND),
+        GlobalZIndex(11),
+        Visibility::Hidden,
+        OddsOverlay,
+        Name::new("OddsOverlay"),
+    ));
+}
+
+/// Switch the always-on preview on F3
+fn toggle_always_on_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut config: ResMut<MovementConfig>,
+    mut flow: ResMut<OddsPreviewFlow>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    if !keyboard.just_pressed(ODDS_PREVIEW_KEY) {
+        return;
+    }
+    config.odds_preview_always_on = !config.odds_preview_always_on;
+    flow.clear();
+    game_log.log_message(
+        format!(
+            "🎯 Odds preview: {}",
+            if config.odds_preview_always_on {
+                "always on"
+            } else {
+                "hold Alt"
+            }
+        ),
+        GameLogType::System,
+    );
+}
+
+/// Work out the odds of the four moves next to the player
+#[allow(clippy::too_many_arguments)]
+fn adjacent_odds_system(
+    service: Res<TileMovementService>,
+    player_resource: Res<PlayerResource>,
+    map_resource: Res<MapResource>,
+    session: Res<RpgGameSession>,
+    timed_objective: Res<TimedObjective>,
+    world_hazards: Res<WorldHazards>,
+    game_stats: Res<GameStatsResource>,
+    mut odds: ResMut<AdjacentOdds>,
+) {
+    let (Some(player), Some(map)) = (player_resource.get_player(), map_resource.current_map())
+    else {
+        return;
+    };
+    let origin = *player.position();
+    let unchanged = odds.origin == Some(origin)
+        && !player_resource.is_changed()
+        && !map_resource.is_changed()
+        && !session.is_changed()
+        && !timed_objective.is_changed()
+        && !world_hazards.is_changed()
+        && !game_stats.is_changed();
+    if unchanged {
+        return;
+    }
+
+    let storms_apply = !map_resource.is_in_interior();
+    let neighbours = [(1, 0), (-1, 0), (0, 1), (0, -1)]
+        .map(|(dx, dy)| Position3D::new(origin.x + dx, origin.y + dy, origin.z));
+    let previews = neighbours
+        .into_iter()
+        .filter_map(|target| {
+            let inputs = MoveInputs::for_move(
+                &session,
+                &timed_objective,
+                &world_hazards,
+                &game_stats.modifiers,
+                storms_apply,
+                target,
+            );
+            service
+                .preview_movement(
+                    player,
+                    target,
+                    map,
+                    player.level(),
+                    &inputs.roll_modifier,
+                    &inputs.conditions,
+                )
+                .ok()
+        })
+        .collect();
+    *odds = AdjacentOdds {
+        origin: Some(origin),
+        previews,
+    };
+}
+
+/// Show the previewed move's odds above its tile
+fn update_odds_overlay_system(
+    state: Res<State<RpgAppState>>,
+    mut flow: ResMut<OddsPreviewFlow>,
+    odds: Res<AdjacentOdds>,
+    cameras: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+    mut overlays: Query<(&mut Text, &mut Node, &mut Visibility), With<OddsOverlay>>,
+) {
+    let Ok((mut text, mut node, mut visibility)) = overlays.single_mut() else {
+        return;
+    };
+    let hide = |visibility: &mut Visibility| {
+        if *visibility != Visibility::Hidden {
+            *visibility = Visibility::Hidden;
+        }
+    };
+    if *state.get() != RpgAppState::Exploration {
+        hide(&mut visibility);
+        return;
+    }
+    // A preview lasts while the player stands where it was asked for
+    let Some(preview) = flow.previewing().and_then(|target| odds.at(target)) else {
+        if flow.previewing().is_some() {
+            flow.clear();
+        }
+        hide(&mut visibility);
+        return;
+    };
+
+    let anchor = tile_to_world_position(preview.target_position) + Vec3::Y * OVERLAY_LIFT;
+    let screen = cameras
+        .single()
+        .ok()
+        .and_then(|(camera, transform)| camera.world_to_viewport(transform, anchor).ok());
+    let Some(screen) = screen else {
+        hide(&mut visibility);
+        return;
+    };
+    node.left = Val::Px(screen.x);
+    node.top = Val::Px(screen.y);
+    let line = preview.summary();
+    if text.0 != line {
+        text.0 = line;
+    }
+    if *visibility != Visibility::Visible {
+        *visibility = Visibility::Visible;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn previews_wait_for_a_second_press() {
+        let east = Position3D::new(1, 0, 0);
+        let north = Position3D::new(0, 1, 0);
+
+        // Hold Alt to look, release it and press again to go
+        let mut flow = OddsPreviewFlow::default();
+        assert_eq!(flow.request(east, true, false), OddsDecision::Preview(east));
+        assert_eq!(flow.request(east, true, false), OddsDecision::Preview(east));
+        assert_eq!(
+            flow.request(east, false, false),
+            OddsDecision::Execute(east)
+        );
+        assert_eq!(flow.previewing(), None);
+        // Without Alt nothing is in the way
+        assert_eq!(
+            flow.request(north, false, false),
+            OddsDecision::Execute(north)
+        );
+
+        // Always on: the first press looks, the same press again goes
+        let mut flow = OddsPreviewFlow::default();
+        assert_eq!(flow.request(east, false, true), OddsDecision::Preview(east));
+        assert_eq!(
+            flow.request(north, false, true),
+            OddsDecision::Preview(north)
+        );
+        assert_eq!(
+            flow.request(north, false, true),
+            OddsDecision::Execute(north)
+        );
+        assert_eq!(
+            flow.request(north, false, true),
+            OddsDecision::Preview(north)
+        );
+        flow.clear();
+        assert_eq!(flow.previewing(), None);
+    }
+}
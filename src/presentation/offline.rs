@@ -0,0 +1,124 @@
+//! Offline Play - Registering the service worker and spotting stale builds
+//!
+//! At startup the web build registers its service worker. When the page
+//! relays what the worker has cached, the status goes to the asset check
+//! and the cached build is compared with the running one; a mismatch shows
+//! a toast offering to reload into the new version. Native builds register
+//! nothing and never see a report.
+
+use crate::domain::constants::{ENERGY_COLOR, PANEL_BACKGROUND};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::infrastructure::build_info::BuildInfo;
+use crate::infrastructure::web::offline::{
+    register_service_worker, reload_page, take_offline_cache_report, OfflineCacheStatus,
+    VersionSkew,
+};
+use crate::presentation::asset_integrity::AssetIntegrity;
+use bevy::prelude::*;
+
+/// Plugin for the service worker handshake and the new version toast
+pub struct OfflinePlugin;
+
+impl Plugin for OfflinePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Startup,
+            (register_service_worker_system, setup_version_toast),
+        )
+        .add_systems(
+            Update,
+            (offline_cache_report_system, version_toast_click_system).chain(),
+        );
+    }
+}
+
+/// Marker for the new version toast
+#[derive(Component)]
+pub struct VersionToast;
+
+fn setup_version_toast(mut commands: Commands) {
+    commands
+        .spawn((
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(15.0),
+                left: Val::Percent(50.0),
+                margin: UiRect::left(Val::Px(-160.0)),
+                width: Val::Px(320.0),
+                padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BackgroundColor(PANEL_BACKGROUND),
+            GlobalZIndex(12),
+            Visibility::Hidden,
+            VersionToast,
+            Name::new("VersionToast"),
+        ))
+        .with_children(|toast| {
+            toast.spawn((
+                Text::new("✨ New version available — click to reload"),
+                TextFont {
+                    font_size: FontSize::Regular.to_pixels(),
+                    ..default()
+                },
+                TextColor(ENERGY_COLOR),
+            ));
+        });
+}
+
+fn register_service_worker_system(mut integrity: ResMut<AssetIntegrity>) {
+    match register_service_worker() {
+        Ok(true) => info!("Service worker registered"),
+        Ok(false) => integrity.record_offline_cache(OfflineCacheStatus::Unsupported),
+        Err(error) => {
+            warn!("Service worker not registered: {}", error);
+            integrity.record_offline_cache(OfflineCacheStatus::Failed(error.to_string()));
+        }
+    }
+}
+
+/// Take the cache status the page relayed and check it for a stale build
+fn offline_cache_report_system(
+    mut integrity: ResMut<AssetIntegrity>,
+    mut game_log: ResMut<GameLogService>,
+    mut toasts: Query<&mut Visibility, With<VersionToast>>,
+) {
+    let Some(status) = take_offline_cache_report() else {
+        return;
+    };
+    info!("Asset check: {}", status.describe());
+
+    if let VersionSkew::Stale { running, cached } = status.version_skew(&BuildInfo::current()) {
+        warn!(
+            "Running build {} differs from cached build {}",
+            running, cached
+        );
+        game_log.log_message(
+            "✨ A new version of the game is available. Reload to play it".to_string(),
+            GameLogType::System,
+        );
+        for mut visibility in toasts.iter_mut() {
+            *visibility = Visibility::Visible;
+        }
+    }
+    integrity.record_offline_cache(status);
+}
+
+/// Reload when the toast is clicked
+#[allow(clippy::type_complexity)]
+fn version_toast_click_system(
+    toasts: Query<(&Interaction, &Visibility), (Changed<Interaction>, With<VersionToast>)>,
+) {
+    let clicked = toasts.iter().any(|(interaction, visibility)| {
+        *interaction == Interaction::Pressed && *visibility == Visibility::Visible
+    });
+    if !clicked {
+        return;
+    }
+    if let Err(error) = reload_page() {
+        warn!("Reload failed: {}", error);
+    }
+}
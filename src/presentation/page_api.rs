@@ -0,0 +1,364 @@
+//! Page API - The run's state and a few safe commands for the embedding page
+//!
+//! A page that embeds the web build can read a JSON snapshot of the run
+//! through `game_state_json` and queue moves and dice rolls through
+//! `dispatch_command`, e.g. `{"cmd":"move","dir":"north"}` or
+//! `{"cmd":"roll_dice"}`. Like the music controls, both sides meet in
+//! queues outside the ECS: a command is only read and queued at the
+//! boundary, and a system drains the queue each frame, checks the move the
+//! way a keypress is checked and turns it into the game's own events. A
+//! command that cannot be read comes back as a JSON error instead of a
+//! panic. Directions are the map's compass; the default camera shows
+//! north at the bottom of the screen.
+
+use crate::domain::constants::PAGE_COMMAND_QUEUE_LIMIT;
+use crate::domain::entities::Player;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{CalendarPhase, GameCalendar, LowPointsGuard, WorldHazards};
+use crate::domain::value_objects::position::{Direction, Position3D};
+use crate::domain::value_objects::ResourceType;
+use crate::infrastructure::bevy::resources::{
+    GameStatsResource, MapResource, PartyResource, PlayerResource,
+};
+use crate::presentation::map_renderer::PlayerMarker;
+use crate::presentation::movement::{
+    begin_player_step, movement_cost_at, run_modifiers, ExecuteRpgMovement, MovementConfig,
+    MovementStarted, PendingRpgResults, SmoothMovement,
+};
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+
+/// Commands queued by the page, run on the next frame
+static PAGE_COMMANDS: Mutex<VecDeque<PageCommand>> = Mutex::new(VecDeque::new());
+
+/// Latest snapshot of the run, as JSON
+static LATEST_STATE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Read a command from the page and queue it; the answer is JSON either way
+pub fn dispatch_command(json: &str) -> String {
+    match parse_command(json).and_then(queue_command) {
+        Ok(()) => serde_json::json!({ "ok": true }).to_string(),
+        Err(error) => error.to_json(),
+    }
+}
+
+/// Latest snapshot of the run, or `null` before the first frame
+pub fn game_state_json() -> String {
+    LATEST_STATE
+        .lock()
+        .ok()
+        .and_then(|latest| latest.clone())
+        .unwrap_or_else(|| "null".to_string())
+}
+
+/// Plugin for the page's command queue and state snapshot
+pub struct PageApiPlugin;
+
+impl Plugin for PageApiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DiceRollRequested>().add_systems(
+            Update,
+            (run_page_commands_system, publish_game_state_system),
+        );
+    }
+}
+
+/// A dice roll asked for outside the keyboard
+#[derive(Event, Debug, Clone, Copy, Default)]
+pub struct DiceRollRequested;
+
+/// Something the page asked the game to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageCommand {
+    Move(Direction),
+    RollDice,
+}
+
+/// Why a command from the page was turned down
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageCommandError {
+    /// Stable code the page can match on
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl PageCommandError {
+    fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// The answer handed back to the page
+    pub fn to_json(&self) -> String {
+        serde_json::json!({ "ok": false, "error": self.code, "message": self.message }).to_string()
+    }
+}
+
+/// Read a command such as `{"cmd":"move","dir":"north"}`
+pub fn parse_command(json: &str) -> Result<PageCommand, PageCommandError> {
+    let value: serde_json::Value = serde_json::from_str(json)
+        .map_err(|error| PageCommandError::new("invalid_json", error.to_string()))?;
+    let Some(cmd) = value.get("cmd").and_then(|cmd| cmd.as_str()) else {
+        return Err(PageCommandError::new(
+            "missing_command",
+            "expected an object with a \"cmd\" string",
+        ));
+    };
+    match cmd {
+        "move" => {
+            let dir = value.get("dir").and_then(|dir| dir.as_str()).unwrap_or("");
+            let direction = match dir {
+                "north" => Direction::North,
+                "south" => Direction::South,
+                "east" => Direction::East,
+                "west" => Direction::West,
+                _ => {
+                    return Err(PageCommandError::new(
+                        "invalid_direction",
+                        format!("'{}' is not north, south, east or west", dir),
+                    ))
+                }
+            };
+            Ok(PageCommand::Move(direction))
+        }
+        "roll_dice" => Ok(PageCommand::RollDice),
+        other => Err(PageCommandError::new(
+            "unknown_command",
+            format!("'{}' is not move or roll_dice", other),
+        )),
+    }
+}
+
+fn queue_command(command: PageCommand) -> Result<(), PageCommandError> {
+    let mut queue = PAGE_COMMANDS
+        .lock()
+        .map_err(|_| PageCommandError::new("unavailable", "the command queue is unavailable"))?;
+    if queue.len() >= PAGE_COMMAND_QUEUE_LIMIT {
+        return Err(PageCommandError::new(
+            "queue_full",
+            "too many commands are waiting; try again next frame",
+        ));
+    }
+    queue.push_back(command);
+    Ok(())
+}
+
+fn take_page_commands() -> Vec<PageCommand> {
+    PAGE_COMMANDS
+        .lock()
+        .map(|mut queue| queue.drain(..).collect())
+        .unwrap_or_default()
+}
+
+/// The run as the page sees it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameStateSnapshot {
+    pub state: String,
+    pub day: u32,
+    pub phase: &'static str,
+    pub player: Option<PlayerSnapshot>,
+}
+
+/// The player as the page sees it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlayerSnapshot {
+    pub position: Position3D,
+    pub level: u32,
+    pub movement_points: u8,
+    pub max_movement_points: u8,
+    pub resources: BTreeMap<String, u32>,
+}
+
+impl GameStateSnapshot {
+    /// Snapshot of the run in `state` on `day`
+    pub fn capture(
+        state: &RpgAppState,
+        day: u32,
+        phase: CalendarPhase,
+        player: Option<&Player>,
+    ) -> Self {
+        Self {
+            state: format!("{:?}", state),
+            day,
+            phase: phase.name(),
+            player: player.map(|player| PlayerSnapshot {
+                position: *player.position(),
+                level: player.level(),
+                movement_points: player.movement_points(),
+                max_movement_points: player.max_movement_points(),
+                resources: ResourceType::all()
+                    .into_iter()
+                    .map(|kind| (kind.to_string(), player.resources().get_amount(kind)))
+                    .collect(),
+            }),
+        }
+    }
+}
+
+/// Run the page's commands, each only when a keypress could do the same
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn run_page_commands_system(
+    state: Res<State<RpgAppState>>,
+    mut player_query: Query<(&mut SmoothMovement, Entity), With<PlayerMarker>>,
+    player_resource: Res<PlayerResource>,
+    map_resource: Res<MapResource>,
+    config: Res<MovementConfig>,
+    pending: Res<PendingRpgResults>,
+    (world_hazards, game_stats, low_points_guard, party): (
+        Option<Res<WorldHazards>>,
+        Option<Res<GameStatsResource>>,
+        Option<Res<LowPointsGuard>>,
+        Option<Res<PartyResource>>,
+    ),
+    mut movement_started_events: EventWriter<MovementStarted>,
+    mut execute_rpg_events: EventWriter<ExecuteRpgMovement>,
+    mut dice_rolls: EventWriter<DiceRollRequested>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    for command in take_page_commands() {
+        let direction = match command {
+            PageCommand::RollDice => {
+                dice_rolls.write(DiceRollRequested);
+                continue;
+            }
+            PageCommand::Move(direction) => direction,
+        };
+        let Ok((mut smooth_movement, entity)) = player_query.single_mut() else {
+            continue;
+        };
+        let held = *state.get() != RpgAppState::Exploration
+            || smooth_movement.is_moving
+            || !pending.results.is_empty()
+            || low_points_guard
+                .as_ref()
+                .is_some_and(|guard| guard.blocks_movement())
+            || party.as_ref().is_some_and(|party| party.blocks_movement());
+        if held {
+            continue;
+        }
+        let target = smooth_movement.target_position.move_direction(direction, 1);
+        let cost = movement_cost_at(
+            &map_resource,
+            world_hazards.as_deref(),
+            &run_modifiers(game_stats.as_deref()),
+            target,
+        );
+        if player_resource
+            .get_player()
+            .is_none_or(|player| player.movement_points() < cost)
+        {
+            game_log.log_message(
+                "⚡ Not enough movement points for that move".to_string(),
+                GameLogType::Movement,
+            );
+            continue;
+        }
+        begin_player_step(
+            &mut smooth_movement,
+            entity,
+            direction,
+            &config,
+            &mut movement_started_events,
+            &mut execute_rpg_events,
+        );
+    }
+}
+
+/// Refresh the page's snapshot whenever the run changes
+fn publish_game_state_system(
+    state: Res<State<RpgAppState>>,
+    player_resource: Res<PlayerResource>,
+    game_stats: Res<GameStatsResource>,
+    calendar: Option<Res<GameCalendar>>,
+) {
+    let calendar_changed = calendar
+        .as_ref()
+        .is_some_and(|calendar| calendar.is_changed());
+    if !(state.is_changed()
+        || player_resource.is_changed()
+        || game_stats.is_changed()
+        || calendar_changed)
+    {
+        return;
+    }
+    let phase = calendar
+        .as_ref()
+        .map(|calendar| calendar.phase())
+        .unwrap_or_default();
+    let snapshot = GameStateSnapshot::capture(
+        state.get(),
+        game_stats.current_day(),
+        phase,
+        player_resource.get_player(),
+    );
+    match serde_json::to_string(&snapshot) {
+        Ok(json) => {
+            if let Ok(mut latest) = LATEST_STATE.lock() {
+                *latest = Some(json);
+            }
+        }
+        Err(error) => warn!("Game state snapshot could not be written: {}", error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_are_read_or_turned_down_with_a_reason() {
+        assert_eq!(
+            parse_command(r#"{"cmd":"move","dir":"north"}"#),
+            Ok(PageCommand::Move(Direction::North))
+        );
+        assert_eq!(
+            parse_command(r#"{"cmd":"roll_dice"}"#),
+            Ok(PageCommand::RollDice)
+        );
+
+        let code = |json: &str| parse_command(json).unwrap_err().code;
+        assert_eq!(code("{not json"), "invalid_json");
+        assert_eq!(code(r#"["move"]"#), "missing_command");
+        assert_eq!(code(r#"{"cmd":"teleport"}"#), "unknown_command");
+        assert_eq!(code(r#"{"cmd":"move","dir":"up"}"#), "invalid_direction");
+        assert_eq!(code(r#"{"cmd":"move"}"#), "invalid_direction");
+
+        let answer: serde_json::Value =
+            serde_json::from_str(&dispatch_command(r#"{"cmd":"fly"}"#)).unwrap();
+        assert_eq!(answer["ok"], false);
+        assert_eq!(answer["error"], "unknown_command");
+    }
+
+    #[test]
+    fn the_snapshot_carries_what_a_hud_shows() {
+        let player =
+            Player::create_new_character("Pilot".to_string(), Position3D::new(2, -1, 0)).unwrap();
+        let snapshot = GameStateSnapshot::capture(
+            &RpgAppState::Exploration,
+            3,
+            CalendarPhase::Night,
+            Some(&player),
+        );
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["state"], "Exploration");
+        assert_eq!(json["day"], 3);
+        assert_eq!(json["phase"], "Night");
+        assert_eq!(json["player"]["position"]["x"], 2);
+        assert_eq!(json["player"]["level"], player.level());
+        assert_eq!(
+            json["player"]["resources"]["Metal"],
+            player.resources().get_amount(ResourceType::Metal)
+        );
+
+        let menu = GameStateSnapshot::capture(&RpgAppState::MainMenu, 1, CalendarPhase::Day, None);
+        assert_eq!(
+            serde_json::to_value(&menu).unwrap()["player"],
+            serde_json::Value::Null
+        );
+    }
+}
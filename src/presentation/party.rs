@@ -0,0 +1,550 @@
+//! Pass-and-Play Party - Two characters taking turns on one device
+//!
+//! In a hot-seat run a rest ends the active character's day. A full-screen
+//! handover then hides the map and the outgoing character's plans until the
+//! next player presses Enter, and the waiting character is swapped into
+//! play; the HUD, movement and camera follow them from there. While both
+//! characters stand on the same tile, T opens a panel to exchange cargo.
+//! The exchange only goes through when both sides can hand over and carry
+//! what was agreed.
+
+use crate::domain::constants::{
+    HANDOVER_BACKGROUND, PANEL_BACKGROUND, PARTY_TRANSFER_STEP, PRIMARY_TEXT,
+};
+use crate::domain::entities::Player;
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{TimedObjective, TransferOffer};
+use crate::domain::value_objects::ResourceType;
+use crate::infrastructure::bevy::resources::{GameStatsResource, PartyResource, PlayerResource};
+use crate::presentation::map_renderer::PlayerMarker;
+use crate::presentation::movement::SmoothMovement;
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+
+/// Plugin for hot-seat turns and the cargo exchange panel
+pub struct PartyPlugin;
+
+impl Plugin for PartyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PartyResource>()
+            .init_resource::<TransferCursor>()
+            .add_systems(Startup, setup_party_ui)
+            .add_systems(
+                Update,
+                (
+                    party_rest_system,
+                    handover_input_system,
+                    transfer_input_system,
+                    update_party_panels,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Line highlighted in the exchange panel
+#[derive(Resource, Debug, Clone, Default)]
+pub struct TransferCursor {
+    /// Index into `ResourceType::all()`
+    pub resource: usize,
+    /// Whether the line edits what the active character receives
+    pub taking: bool,
+}
+
+impl TransferCursor {
+    /// Resource type of the highlighted line
+    pub fn resource_type(&self) -> ResourceType {
+        let all = ResourceType::all();
+        all[self.resource % all.len()]
+    }
+}
+
+/// Marker for the handover screen
+#[derive(Component)]
+pub struct HandoverScreen;
+
+/// Marker for the handover screen text
+#[derive(Component)]
+pub struct HandoverText;
+
+/// Marker for the exchange panel
+#[derive(Component)]
+pub struct TransferPanel;
+
+/// Marker for the exchange panel text
+#[derive(Component)]
+pub struct TransferPanelText;
+
+fn setup_party_ui(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                top: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(HANDOVER_BACKGROUND),
+            // Above the map and every other panel
+            GlobalZIndex(10),
+            Visibility::Hidden,
+            HandoverScreen,
+            Name::new("HandoverScreen"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Large.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                TextLayout::new_with_justify(JustifyText::Center),
+                HandoverText,
+            ));
+        });
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(15.0),
+                top: Val::Px(80.0),
+                width: Val::Px(420.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(PANEL_BACKGROUND),
+            Visibility::Hidden,
+            TransferPanel,
+            Name::new("TransferPanel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                TransferPanelText,
+            ));
+        });
+}
+
+/// End the active character's day once per night of rest
+fn party_rest_system(
+    game_stats: Res<GameStatsResource>,
+    mut party_resource: ResMut<PartyResource>,
+    mut game_log: ResMut<GameLogService>,
+    mut nights_counted: Local<u32>,
+) {
+    if game_stats.nights_rested < *nights_counted {
+        *nights_counted = game_stats.nights_rested;
+    }
+    while *nights_counted < game_stats.nights_rested {
+        *nights_counted += 1;
+        // The exchange cannot stay open across the handover
+        party_resource.offer = None;
+        let Some(party) = party_resource.party_mut() else {
+            continue;
+        };
+        match party.end_day(game_stats.run_tally()) {
+            Ok(()) => game_log.log_message(
+                format!(
+                    "👥 Day {} is over - pass the device to {}",
+                    *nights_counted,
+                    party.waiting_name()
+                ),
+                GameLogType::System,
+            ),
+            Err(e) => warn!("Failed to end the party's day: {}", e),
+        }
+    }
+}
+
+/// Swap the waiting character in once the next player takes the device
+fn handover_input_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut party_resource: ResMut<PartyResource>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut timed_objective: ResMut<TimedObjective>,
+    mut game_log: ResMut<GameLogService>,
+    mut player_query: Query<&mut SmoothMovement, With<PlayerMarker>>,
+) {
+    if !party_resource.is_handover() || !keyboard.just_pressed(KeyCode::Enter) {
+        return;
+    }
+    let Some(party) = party_resource.party_mut() else {
+        return;
+    };
+    if let Err(e) = player_resource.switch_character(party, timed_objective.signal_mut()) {
+        warn!("Failed to hand over to the next character: {}", e);
+        return;
+    }
+
+    // The camera follows the marker to wherever this character stands
+    if let Some(position) = player_resource.player_position() {
+        if let Ok(mut movement) = player_query.single_mut() {
+            movement.reset_to_position(position);
+        }
+    }
+    if let Some(player) = player_resource.get_player() {
+        game_log.log_message(
+            format!(
+                "👥 {}'s turn - {} waits",
+                player.name(),
+                party.waiting_name()
+            ),
+            GameLogType::System,
+        );
+    }
+}
+
+/// Open, edit and confirm the cargo exchange between the two characters
+fn transfer_input_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_state: Res<State<RpgAppState>>,
+    mut party_resource: ResMut<PartyResource>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut cursor: ResMut<TransferCursor>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    if *current_state.get() != RpgAppState::Exploration || party_resource.is_handover() {
+        return;
+    }
+    let Some(party) = party_resource.party() else {
+        return;
+    };
+    let Some(player) = player_resource.get_player() else {
+        return;
+    };
+
+    if keyboard.just_pressed(KeyCode::KeyT) {
+        let same_tile = player.position() == party.benched().position();
+        let partner = party.waiting_name().to_string();
+        if party_resource.offer.take().is_some() {
+            game_log.log_message("🤝 Exchange called off".to_string(), GameLogType::System);
+        } else if same_tile {
+            party_resource.offer = Some(TransferOffer::default());
+        } else {
+            game_log.log_message(
+                format!("🤝 {} is not on this tile", partner),
+                GameLogType::Warning,
+            );
+        }
+        return;
+    }
+    if party_resource.offer.is_none() {
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::Tab) {
+        cursor.resource = (cursor.resource + 1) % ResourceType::all().len();
+    }
+    if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        cursor.taking = false;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowRight) {
+        cursor.taking = true;
+    }
+
+    let resource_type = cursor.resource_type();
+    let available = if cursor.taking {
+        party.benched().resources().get_amount(resource_type)
+    } else {
+        player.resources().get_amount(resource_type)
+    };
+    let raise = keyboard.just_pressed(KeyCode::ArrowUp);
+    let lower = keyboard.just_pressed(KeyCode::ArrowDown);
+    if let Some(offer) = party_resource.offer.as_mut() {
+        if raise || lower {
+            adjust_offer(offer, &cursor, available, raise);
+        }
+    }
+
+    if !keyboard.just_pressed(KeyCode::Enter) {
+        return;
+    }
+    let Some(offer) = party_resource.offer.clone() else {
+        return;
+    };
+    if offer.is_empty() {
+        game_log.log_message(
+            "🤝 Nothing to exchange yet".to_string(),
+            GameLogType::Warning,
+        );
+        return;
+    }
+    let Some(party) = party_resource.party_mut() else {
+        return;
+    };
+    let partner = party.waiting_name().to_string();
+    match player_resource.exchange_with(party, &offer) {
+        Ok(()) => {
+            party_resource.offer = None;
+            game_log.log_message(
+                format!(
+                    "🤝 Exchanged with {}: {}",
+                    partner,
+                    exchange_summary(&offer)
+                ),
+                GameLogType::Resources,
+            );
+        }
+        // The offer stays open so it can be trimmed and tried again
+        Err(e) => game_log.log_message(format!("{}", e), GameLogType::Warning),
+    }
+}
+
+/// Raise or lower the highlighted line, never past what its side carries
+fn adjust_offer(offer: &mut TransferOffer, cursor: &TransferCursor, available: u32, raise: bool) {
+    let resource_type = cursor.resource_type();
+    let side = if cursor.taking {
+        &mut offer.take
+    } else {
+        &mut offer.give
+    };
+    let current = side.get_amount(resource_type);
+    let amount = if raise {
+        current.saturating_add(PARTY_TRANSFER_STEP).min(available)
+    } else {
+        current.saturating_sub(PARTY_TRANSFER_STEP)
+    };
+    side.set_amount(resource_type, amount);
+}
+
+fn exchange_summary(offer: &TransferOffer) -> String {
+    let side = |cargo: &crate::domain::value_objects::ResourceCollection| {
+        if cargo.is_empty() {
+            "nothing".to_string()
+        } else {
+            cargo.to_string()
+        }
+    };
+    format!("gave {}, received {}", side(&offer.give), side(&offer.take))
+}
+
+/// Show the handover screen and the exchange panel
+#[allow(clippy::too_many_arguments)]
+fn update_party_panels(
+    party_resource: Res<PartyResource>,
+    player_resource: Res<PlayerResource>,
+    game_stats: Res<GameStatsResource>,
+    cursor: Res<TransferCursor>,
+    mut handover_query: Query<&mut Visibility, (With<HandoverScreen>, Without<TransferPanel>)>,
+    mut transfer_query: Query<&mut Visibility, (With<TransferPanel>, Without<HandoverScreen>)>,
+    mut handover_text: Query<&mut Text, (With<HandoverText>, Without<TransferPanelText>)>,
+    mut transfer_text: Query<&mut Text, (With<TransferPanelText>, Without<HandoverText>)>,
+) {
+    let party = party_resource.party();
+
+    if let Ok(mut visibility) = handover_query.single_mut() {
+        let wanted = if party_resource.is_handover() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+    if let Some(party) = party.filter(|_| party_resource.is_handover()) {
+        if let Ok(mut text) = handover_text.single_mut() {
+            let content = format!(
+                "Day {} is over\n\nPass the device to {}\n\nPress ENTER to take over",
+                game_stats.nights_rested,
+                party.waiting_name()
+            );
+            if **text != content {
+                **text = content;
+            }
+        }
+    }
+
+    let exchange = party
+        .zip(party_resource.offer.as_ref())
+        .zip(player_resource.get_player());
+    if let Ok(mut visibility) = transfer_query.single_mut() {
+        let wanted = if exchange.is_some() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+    let Some(((party, offer), player)) = exchange else {
+        return;
+    };
+    if let Ok(mut text) = transfer_text.single_mut() {
+        let content = transfer_panel_text(player, party.benched(), offer, &cursor);
+        if **text != content {
+            **text = content;
+        }
+    }
+}
+
+fn transfer_panel_text(
+    active: &Player,
+    benched: &Player,
+    offer: &TransferOffer,
+    cursor: &TransferCursor,
+) -> String {
+    let mut lines = vec![format!(
+        "CARGO EXCHANGE - {} <> {}",
+        active.name(),
+        benched.name()
+    )];
+    for (index, resource_type) in ResourceType::all().into_iter().enumerate() {
+        let selected = index == cursor.resource;
+        let mark = |taking: bool| {
+            if selected && cursor.taking == taking {
+                ">"
+            } else {
+                " "
+            }
+        };
+        lines.push(format!(
+            "{:<12} {}give {:>3} / {}take {:>3}   ({} | {})",
+            resource_type.to_string(),
+            mark(false),
+            offer.give.get_amount(resource_type),
+            mark(true),
+            offer.take.get_amount(resource_type),
+            active.resources().get_amount(resource_type),
+            benched.resources().get_amount(resource_type)
+        ));
+    }
+    lines.push(String::new());
+    lines.push(format!(
+        "TAB: Resource | LEFT/RIGHT: Give/Take | UP/DOWN: +/-{} | ENTER: Exchange | T: Cancel",
+        PARTY_TRANSFER_STEP
+    ));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::Party;
+    use crate::domain::value_objects::{Position3D, ResourceCollection};
+    use crate::domain::PlayerStats;
+    use crate::infrastructure::bevy::resources::BaseResource;
+
+    fn party_app() -> App {
+        let mut player_resource = PlayerResource::new();
+        player_resource
+            .create_player(
+                "player".to_string(),
+                "Vex".to_string(),
+                Position3D::origin(),
+                PlayerStats::new(10, 10, 10, 10, 10, 10).unwrap(),
+            )
+            .unwrap();
+        *player_resource.player_mut().unwrap().resources_mut() =
+            ResourceCollection::cost(&[(ResourceType::Metal, 20)]).unwrap();
+        let mut rook =
+            Player::create_new_character("Rook".to_string(), Position3D::origin()).unwrap();
+        *rook.resources_mut() = ResourceCollection::cost(&[(ResourceType::Energy, 10)]).unwrap();
+
+        let mut base_resource = BaseResource::new();
+        base_resource
+            .create_base("Home".to_string(), Position3D::origin())
+            .unwrap();
+        base_resource
+            .base_mut()
+            .unwrap()
+            .store_resources(&ResourceCollection::cost(&[(ResourceType::Food, 30)]).unwrap())
+            .unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(State::new(RpgAppState::Exploration))
+            .insert_resource(ButtonInput::<KeyCode>::default())
+            .insert_resource(player_resource)
+            .insert_resource(base_resource)
+            .insert_resource(GameStatsResource::new())
+            .insert_resource(GameLogService::new())
+            .init_resource::<TimedObjective>()
+            .init_resource::<TransferCursor>()
+            .insert_resource(PartyResource::default())
+            .add_systems(
+                Update,
+                (
+                    party_rest_system,
+                    handover_input_system,
+                    transfer_input_system,
+                )
+                    .chain(),
+            );
+        app.world_mut()
+            .resource_mut::<PartyResource>()
+            .set_party(Some(Party::new("Vex", rook)));
+        app
+    }
+
+    fn press(app: &mut App, key: KeyCode) {
+        let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.release_all();
+        keyboard.clear();
+        keyboard.press(key);
+        app.update();
+    }
+
+    fn metal(player: &Player) -> u32 {
+        player.resources().get_amount(ResourceType::Metal)
+    }
+
+    #[test]
+    fn handover_swaps_the_character_but_not_the_base() {
+        let mut app = party_app();
+        app.world_mut()
+            .resource_mut::<GameStatsResource>()
+            .record_rest();
+        app.update();
+        assert!(app.world().resource::<PartyResource>().is_handover());
+
+        press(&mut app, KeyCode::Enter);
+        let world = app.world();
+        assert!(!world.resource::<PartyResource>().is_handover());
+        let player_resource = world.resource::<PlayerResource>();
+        assert_eq!(player_resource.get_player().unwrap().name(), "Rook");
+        assert_eq!(metal(player_resource.get_player().unwrap()), 0);
+        let party = world.resource::<PartyResource>().party().unwrap();
+        assert_eq!(metal(party.benched()), 20);
+        assert_eq!(party.contributions()[0].days_played, 1);
+
+        // The base and its storage belong to both characters
+        let base = world.resource::<BaseResource>().base().unwrap();
+        assert_eq!(base.resources().get_amount(ResourceType::Food), 30);
+    }
+
+    #[test]
+    fn exchange_panel_moves_cargo_only_on_confirm() {
+        let mut app = party_app();
+        press(&mut app, KeyCode::KeyT);
+        assert!(app.world().resource::<PartyResource>().offer.is_some());
+        // Metal is the first resource line; offer 10 of the 20 carried
+        press(&mut app, KeyCode::ArrowUp);
+        press(&mut app, KeyCode::ArrowUp);
+        // Nothing changes hands before the exchange is confirmed
+        let player_resource = app.world().resource::<PlayerResource>();
+        assert_eq!(metal(player_resource.get_player().unwrap()), 20);
+
+        press(&mut app, KeyCode::Enter);
+        let world = app.world();
+        assert!(world.resource::<PartyResource>().offer.is_none());
+        let player_resource = world.resource::<PlayerResource>();
+        assert_eq!(metal(player_resource.get_player().unwrap()), 10);
+        let party = world.resource::<PartyResource>().party().unwrap();
+        assert_eq!(metal(party.benched()), 10);
+    }
+}
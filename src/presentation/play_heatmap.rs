@@ -0,0 +1,468 @@
+//! Play Heatmap - Counting what happens where, and the full-screen map of it
+//!
+//! Moves ended on a tile are counted from the world tick, resources gained
+//! from the player's change ledger; events and damage are counted where
+//! they are resolved. Heat inside ruins is put on the ruin's tile, and
+//! gains at the base are transfers rather than finds, so they are skipped.
+//!
+//! K opens a full-screen map of the explored surface coloured by one of the
+//! counters, graded against the highest one, with a legend; H cycles the
+//! counters while it is open, and E and C export the explored map. The map
+//! texture is only built while the view is open, and again only when the
+//! counters, metric or level change.
+
+use crate::domain::constants::{
+    HEATMAP_EXPLORED_COLOR, HEATMAP_GRADE_COLORS, PANEL_BACKGROUND, PRIMARY_TEXT, SECONDARY_TEXT,
+};
+use crate::domain::entities::Map;
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::{grade_range, heat_grade, HeatMetric, PlayHeatmap};
+use crate::domain::value_objects::Position3D;
+use crate::infrastructure::bevy::resources::{
+    ActiveMapHandle, MapResource, PlayerChange, PlayerResource,
+};
+use crate::presentation::game_event_logger::PlayerChangedEvent;
+use crate::presentation::game_state::RpgGameSession;
+use crate::presentation::reputation::HostileContact;
+use crate::presentation::world_tick::{TickCursor, TickPhase, WorldTick, WorldTickSet};
+use crate::presentation::RpgAppState;
+use bevy::asset::RenderAssetUsages;
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+/// Key that opens and closes the heatmap
+pub const HEATMAP_KEY: KeyCode = KeyCode::KeyK;
+
+/// Key that cycles the counter the open heatmap shows
+pub const HEATMAP_METRIC_KEY: KeyCode = KeyCode::KeyH;
+
+/// Plugin for the play heatmap and its view
+pub struct PlayHeatmapPlugin;
+
+impl Plugin for PlayHeatmapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HeatmapView>()
+            .add_systems(Startup, setup_heatmap_view)
+            .add_systems(
+                Update,
+                (
+                    record_heat_system.in_set(WorldTickSet::Economy),
+                    toggle_heatmap_system,
+                    rebuild_heatmap_system,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Whether the heatmap is open, what it shows and what was last drawn
+#[derive(Resource, Debug, Default)]
+pub struct HeatmapView {
+    open: bool,
+    metric: HeatMetric,
+    image: Option<Handle<Image>>,
+    /// Metric, heatmap revision and level the image was drawn for
+    drawn: Option<(HeatMetric, u64, i32)>,
+}
+
+impl HeatmapView {
+    /// Check if the view is showing
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Counter the view shows
+    pub fn metric(&self) -> HeatMetric {
+        self.metric
+    }
+}
+
+/// Marker for the full-screen heatmap panel
+#[derive(Component)]
+pub struct HeatmapPanel;
+
+/// Marker for the heatmap title line
+#[derive(Component)]
+pub struct HeatmapTitle;
+
+/// Marker for the node the heatmap image is drawn on
+#[derive(Component)]
+pub struct HeatmapImage;
+
+/// Label of one grade of the legend
+#[derive(Component)]
+pub struct HeatLegendLabel(pub u32);
+
+fn setup_heatmap_view(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                top: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                padding: UiRect::all(Val::Px(16.0)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            BackgroundColor(PANEL_BACKGROUND),
+            GlobalZIndex(20),
+            Visibility::Hidden,
+            HeatmapPanel,
+            Name::new("HeatmapPanel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Medium.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                HeatmapTitle,
+            ));
+            parent.spawn((
+                Node {
+                    width: Val::Percent(100.0),
+                    flex_grow: 1.0,
+                    ..default()
+                },
+                ImageNode::default(),
+                HeatmapImage,
+            ));
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(12.0),
+                    ..default()
+                })
+                .with_children(|legend| {
+                    let swatches = std::iter::once((0, HEATMAP_EXPLORED_COLOR)).chain(
+                        HEATMAP_GRADE_COLORS
+                            .iter()
+                            .enumerate()
+                            .map(|(index, color)| (index as u32 + 1, *color)),
+                    );
+                    for (grade, color) in swatches {
+                        legend.spawn((
+                            Node {
+                                width: Val::Px(18.0),
+                                height: Val::Px(18.0),
+                                ..default()
+                            },
+                            BackgroundColor(color),
+                        ));
+                        legend.spawn((
+                            Text::new(""),
+                            TextFont {
+                                font_size: FontSize::Small.to_pixels(),
+                                ..default()
+                            },
+                            TextColor(SECONDARY_TEXT),
+                            HeatLegendLabel(grade),
+                        ));
+                    }
+                });
+        });
+}
+
+/// Tile heat is counted on; the ruin's tile while inside one
+pub fn heat_position(map_resource: &MapResource, position: Position3D) -> Position3D {
+    match map_resource.active_map() {
+        ActiveMapHandle::Interior(site) => Position3D::from(site),
+        ActiveMapHandle::Overworld => position,
+    }
+}
+
+/// Count moves ended on a tile and resources gained away from the base
+fn record_heat_system(
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    mut player_events: EventReader<PlayerChangedEvent>,
+    map_resource: Res<MapResource>,
+    player_resource: Res<PlayerResource>,
+    mut session: ResMut<RpgGameSession>,
+) {
+    for tick in cursor.take(ticks.read(), TickPhase::AfterPlayerMove) {
+        if let Some(position) = tick.position {
+            let position = heat_position(&map_resource, position);
+            session.heatmap.record(position, HeatMetric::Visits, 1);
+        }
+    }
+
+    let gained: u32 = player_events
+        .read()
+        .filter_map(|event| match event.change {
+            PlayerChange::ResourceChanged { delta, .. } if delta > 0 => Some(delta as u32),
+            _ => None,
+        })
+        .sum();
+    let Some(position) = player_resource.player_position() else {
+        return;
+    };
+    if gained > 0 && position != *session.base.position() {
+        let position = heat_position(&map_resource, position);
+        session
+            .heatmap
+            .record(position, HeatMetric::Extracted, gained);
+    }
+}
+
+/// Open or close the view on K and cycle its counter on H
+///
+/// H also flees a hostile contact, so the view stays shut while one is
+/// pending; it closes on leaving exploration.
+fn toggle_heatmap_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    contact: Option<Res<HostileContact>>,
+    mut view: ResMut<HeatmapView>,
+) {
+    let blocked = *state.get() != RpgAppState::Exploration
+        || contact.is_some_and(|contact| contact.is_pending());
+    if blocked {
+        if view.open {
+            view.open = false;
+        }
+        return;
+    }
+    if keyboard.just_pressed(HEATMAP_KEY) {
+        view.open = !view.open;
+    } else if view.open && keyboard.just_pressed(HEATMAP_METRIC_KEY) {
+        view.metric = view.metric.next();
+    }
+}
+
+/// Draw `metric` over the explored tiles of level `z`, north up
+///
+/// Unexplored tiles stay transparent. Returns the image and the highest
+/// counter it was graded against, or `None` when nothing is explored there.
+pub fn heat_image(
+    map: &Map,
+    heatmap: &PlayHeatmap,
+    metric: HeatMetric,
+    z: i32,
+) -> Option<(Image, u32)> {
+    let explored: Vec<Position3D> = map
+        .tiles()
+        .iter()
+        .filter(|(coordinate, tile)| coordinate.z == z && tile.is_explored())
+        .map(|(coordinate, _)| Position3D::from(*coordinate))
+        .collect();
+    let min_x = explored.iter().map(|p| p.x).min()?;
+    let max_x = explored.iter().map(|p| p.x).max()?;
+    let min_y = explored.iter().map(|p| p.y).min()?;
+    let max_y = explored.iter().map(|p| p.y).max()?;
+
+    let max = heatmap.max(metric, z);
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: (max_x - min_x + 1) as u32,
+            height: (max_y - min_y + 1) as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.sampler = ImageSampler::nearest();
+    for position in explored {
+        let color = match heat_grade(heatmap.at(position).get(metric), max) {
+            0 => HEATMAP_EXPLORED_COLOR,
+            grade => HEATMAP_GRADE_COLORS[grade as usize - 1],
+        };
+        let pixel = ((position.x - min_x) as u32, (max_y - position.y) as u32);
+        paint_pixel(&mut image, pixel.0, pixel.1, color);
+    }
+    Some((image, max))
+}
+
+/// Write `color` into one pixel of an `Rgba8UnormSrgb` image
+///
+/// Rounds to the nearest sRGB byte, where `Image::set_color_at` truncates.
+pub fn paint_pixel(image: &mut Image, x: u32, y: u32, color: Color) {
+    if let Some(bytes) = image.pixel_bytes_mut(UVec3::new(x, y, 0)) {
+        bytes.copy_from_slice(&color.to_srgba().to_u8_array());
+    }
+}
+
+/// Redraw the open view when what it shows has changed
+#[allow(clippy::too_many_arguments)]
+fn rebuild_heatmap_system(
+    mut view: ResMut<HeatmapView>,
+    session: Res<RpgGameSession>,
+    map_resource: Res<MapResource>,
+    player_resource: Res<PlayerResource>,
+    mut images: ResMut<Assets<Image>>,
+    mut panels: Query<&mut Visibility, With<HeatmapPanel>>,
+    mut image_nodes: Query<&mut ImageNode, With<HeatmapImage>>,
+    mut titles: Query<&mut Text, (With<HeatmapTitle>, Without<HeatLegendLabel>)>,
+    mut labels: Query<(&mut Text, &HeatLegendLabel), Without<HeatmapTitle>>,
+) {
+    if view.is_changed() {
+        let wanted = if view.open {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+        for mut visibility in panels.iter_mut() {
+            if *visibility != wanted {
+                *visibility = wanted;
+            }
+        }
+    }
+    if !view.open {
+        return;
+    }
+    let position = player_resource.player_position().unwrap_or_default();
+    let level = heat_position(&map_resource, position).z;
+    let wanted = (view.metric, session.heatmap.revision(), level);
+    if view.drawn == Some(wanted) {
+        return;
+    }
+    view.drawn = Some(wanted);
+
+    let drawn = map_resource
+        .overworld()
+        .and_then(|map| heat_image(map, &session.heatmap, view.metric, level));
+    let max = drawn.as_ref().map_or(0, |(_, max)| *max);
+    if let Some((image, _)) = drawn {
+        match view.image.as_ref() {
+            Some(handle) => {
+                images.insert(handle, image);
+            }
+            None => {
+                let handle = images.add(image);
+                for mut node in image_nodes.iter_mut() {
+                    node.image = handle.clone();
+                }
+                view.image = Some(handle);
+            }
+        }
+    }
+
+    for mut title in titles.iter_mut() {
+        title.0 = format!(
+            "HEATMAP - {} (highest {})   H: next counter - E: export PNG - C: copy ASCII - K: close",
+            view.metric.name(),
+            max
+        );
+    }
+    for (mut text, label) in labels.iter_mut() {
+        text.0 = match label.0 {
+            0 => "none".to_string(),
+            grade => match grade_range(grade, max) {
+                Some((low, high)) if low == high => low.to_string(),
+                Some((low, high)) => format!("{}-{}", low, high),
+                None => "-".to_string(),
+            },
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::constants::HEATMAP_GRADES;
+    use crate::domain::value_objects::TileCoordinate;
+    use crate::domain::{Base, EntityId, Player};
+    use bevy::state::app::StatesPlugin;
+
+    #[test]
+    fn the_map_is_only_drawn_once_opened_and_then_on_change() {
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin)
+            .insert_state(RpgAppState::Exploration)
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<Assets<Image>>()
+            .insert_resource(PlayerResource::new())
+            .insert_resource(RpgGameSession::new(
+                Player::create_new_character("Heat".to_string(), Position3D::origin()).unwrap(),
+                Base::new(
+                    EntityId::generate(),
+                    "Outpost".to_string(),
+                    Position3D::new(20, 20, 0),
+                )
+                .unwrap(),
+            ))
+            .init_resource::<HeatmapView>()
+            .add_systems(Startup, setup_heatmap_view)
+            .add_systems(
+                Update,
+                (toggle_heatmap_system, rebuild_heatmap_system).chain(),
+            );
+        let mut map_resource = MapResource::new();
+        map_resource.generate_overworld(Position3D::origin(), 7);
+        map_resource.prepare_start_area(Position3D::origin(), 2);
+        app.insert_resource(map_resource);
+        app.update();
+        assert!(app.world().resource::<Assets<Image>>().is_empty());
+
+        let hot = Position3D::new(1, 0, 0);
+        {
+            let mut session = app.world_mut().resource_mut::<RpgGameSession>();
+            session.heatmap.record(hot, HeatMetric::Visits, 4);
+            session
+                .heatmap
+                .record(Position3D::origin(), HeatMetric::Visits, 1);
+        }
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(HEATMAP_KEY);
+        app.update();
+
+        let view = app.world().resource::<HeatmapView>();
+        assert!(view.is_open());
+        let handle = view.image.clone().unwrap();
+        let images = app.world().resource::<Assets<Image>>();
+        let image = images.get(&handle).unwrap();
+        let map_resource = app.world().resource::<MapResource>();
+        let map = map_resource.overworld().unwrap();
+        let explored: Vec<TileCoordinate> = map
+            .tiles()
+            .iter()
+            .filter(|(_, tile)| tile.is_explored())
+            .map(|(coordinate, _)| *coordinate)
+            .collect();
+        let min_x = explored.iter().map(|c| c.x).min().unwrap();
+        let max_y = explored.iter().map(|c| c.y).max().unwrap();
+        let pixel = |p: Position3D| ((p.x - min_x) as u32, (max_y - p.y) as u32);
+        let at = |p: Position3D| {
+            let color = image.get_color_at(pixel(p).0, pixel(p).1).unwrap();
+            color.to_srgba().to_u8_array()
+        };
+        let grade = |grade: u32| {
+            HEATMAP_GRADE_COLORS[grade as usize - 1]
+                .to_srgba()
+                .to_u8_array()
+        };
+        assert_eq!(at(hot), grade(HEATMAP_GRADES));
+        assert_eq!(at(Position3D::origin()), grade(1));
+
+        // Nothing changed: the image is left alone
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(HEATMAP_KEY);
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .clear();
+        let drawn = app.world().resource::<HeatmapView>().drawn;
+        app.update();
+        assert_eq!(app.world().resource::<HeatmapView>().drawn, drawn);
+        assert_eq!(app.world().resource::<Assets<Image>>().len(), 1);
+
+        // The next counter is drawn into the same image
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(HEATMAP_METRIC_KEY);
+        app.update();
+        let view = app.world().resource::<HeatmapView>();
+        assert_eq!(view.metric(), HeatMetric::Events);
+        assert_eq!(view.drawn.unwrap().0, HeatMetric::Events);
+        assert_eq!(app.world().resource::<Assets<Image>>().len(), 1);
+    }
+}
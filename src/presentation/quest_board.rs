@@ -0,0 +1,466 @@
+//! Quest Log - The base's bulletin board and the player's active quests
+//!
+//! The quest log has two tabs. The board tab lists the offers posted at the
+//! base; they can be read anywhere but only accepted on the base tile, and
+//! only while a quest slot is free. The active tab lists the accepted quests
+//! with a progress bar each, and any of them can be abandoned at a
+//! reputation cost. The board is refreshed on the rest tick, and every tick
+//! settles the quests whose target disappeared and pays out the ones just
+//! completed.
+
+use crate::domain::constants::{PANEL_BACKGROUND, PRIMARY_TEXT};
+use crate::domain::entities::BuildingType;
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{
+    DecalKind, PostedQuest, QuestBoard, QuestCounters, QuestTemplate, ReputationCause,
+};
+use crate::domain::value_objects::resources::ResourceCollection;
+use crate::domain::value_objects::ResourceType;
+use crate::infrastructure::bevy::resources::{
+    BaseResource, GameStatsResource, MapResource, PlayerResource,
+};
+use crate::infrastructure::random::streams::{RngStreams, EVENT_GENERATION};
+use crate::presentation::game_state::RpgGameSession;
+use crate::presentation::reputation::{shift_reputation, ReputationChangedEvent};
+use crate::presentation::world_tick::{TickCursor, TickPhase, WorldTick, WorldTickSet};
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+
+/// Plugin for the bulletin board and the quest log screen
+pub struct QuestBoardPlugin;
+
+impl Plugin for QuestBoardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<QuestLogView>()
+            .init_resource::<RngStreams>()
+            .add_systems(Startup, setup_quest_log_panel)
+            .add_systems(
+                Update,
+                (
+                    quest_board_tick_system.in_set(WorldTickSet::Objectives),
+                    quest_log_input_system,
+                    update_quest_log_panel,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Tabs of the quest log
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuestLogTab {
+    #[default]
+    Board,
+    Active,
+}
+
+/// Tab and entry selected in the quest log
+#[derive(Resource, Debug, Clone, Default)]
+pub struct QuestLogView {
+    pub tab: QuestLogTab,
+    pub selected: usize,
+}
+
+/// Marker for the quest log panel
+#[derive(Component)]
+pub struct QuestLogPanel;
+
+/// Marker for the quest log panel text
+#[derive(Component)]
+pub struct QuestLogText;
+
+fn setup_quest_log_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(30.0),
+                top: Val::Px(80.0),
+                width: Val::Px(420.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(PANEL_BACKGROUND),
+            Visibility::Hidden,
+            QuestLogPanel,
+            Name::new("QuestLogPanel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                QuestLogText,
+            ));
+        });
+}
+
+/// Refresh the board on rests, settle lost targets and pay completed quests
+#[allow(clippy::too_many_arguments)]
+fn quest_board_tick_system(
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    map_resource: Res<MapResource>,
+    base_resource: Res<BaseResource>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut game_stats: ResMut<GameStatsResource>,
+    mut session: ResMut<RpgGameSession>,
+    mut game_log: ResMut<GameLogService>,
+    mut reputation_events: EventWriter<ReputationChangedEvent>,
+    rng_streams: ResMut<RngStreams>,
+) {
+    let new_ticks: Vec<WorldTick> = ticks
+        .read()
+        .filter(|tick| cursor.accept(tick))
+        .copied()
+        .collect();
+    let (Some(map), Some(base)) = (map_resource.overworld(), base_resource.base_position()) else {
+        return;
+    };
+
+    let mut rng = rng_streams.stream(EVENT_GENERATION);
+    for tick in new_ticks {
+        let due = match tick.phase {
+            TickPhase::AfterRest => session.quest_board.record_rest(),
+            TickPhase::AfterPlayerMove => false,
+        };
+        if due || !session.quest_board.has_posted() {
+            let posting = session.quest_board.post(map, base, tick.day, &mut rng);
+            game_log.log_message(
+                format!(
+                    "📌 The bulletin board at the base has {} new offers ({} expired)",
+                    posting.posted, posting.expired
+                ),
+                GameLogType::System,
+            );
+        }
+
+        for compensation in session.quest_board.settle_lost_targets(map) {
+            let mut token = ResourceCollection::new();
+            for &(resource_type, amount) in &compensation.resources {
+                token.set_amount(resource_type, amount);
+            }
+            pay_out(
+                compensation.experience,
+                &token,
+                &mut player_resource,
+                &mut game_stats,
+            );
+            game_log.log_message(
+                format!(
+                    "📜 The target of '{}' is gone; the issuer pays {} XP and {} instead",
+                    compensation.title, compensation.experience, token
+                ),
+                GameLogType::Warning,
+            );
+        }
+
+        let position = match tick.phase {
+            TickPhase::AfterPlayerMove => tick.position,
+            TickPhase::AfterRest => None,
+        };
+        for posted in session
+            .quest_board
+            .advance(position, quest_counters(&game_stats))
+        {
+            if let (QuestTemplate::Prospect, Some(target)) = (posted.template, posted.target) {
+                session.decals.add(target, DecalKind::SurveyStake);
+            }
+            let rewards = posted.quest.rewards().clone();
+            pay_out(
+                rewards.experience,
+                &rewards.resources,
+                &mut player_resource,
+                &mut game_stats,
+            );
+            game_stats.record_quest_completion();
+            session.flags.record_quest(&posted.quest);
+            shift_reputation(
+                &mut session,
+                ReputationCause::QuestCompleted,
+                &mut reputation_events,
+            );
+            game_log.log_message(
+                format!(
+                    "🏆 Quest complete: {} (+{} XP, {})",
+                    posted.quest.title(),
+                    rewards.experience,
+                    rewards.resources
+                ),
+                GameLogType::Discovery,
+            );
+            session.completed_quests.push(posted.quest);
+        }
+    }
+}
+
+/// Run counters the quests measure their progress against
+fn quest_counters(game_stats: &GameStatsResource) -> QuestCounters {
+    QuestCounters {
+        tiles_explored: game_stats.tiles_explored,
+        metal_gathered: game_stats
+            .resources_gathered_for_type(ResourceType::Metal)
+            .max(0) as u32,
+        nights_rested: game_stats.nights_rested,
+    }
+}
+
+/// Grant experience and resources for a quest
+fn pay_out(
+    experience: u32,
+    resources: &ResourceCollection,
+    player_resource: &mut PlayerResource,
+    game_stats: &mut GameStatsResource,
+) {
+    if let Err(e) = player_resource.grant_experience(experience) {
+        warn!("Failed to grant quest experience: {:?}", e);
+        return;
+    }
+    game_stats.record_experience_gain(experience);
+    for resource_type in resources.resource_types() {
+        game_stats.record_resource_gather(resource_type, resources.get_amount(resource_type));
+    }
+    player_resource.add_resources(resources);
+}
+
+/// Switch tabs, move the selection, accept offers and abandon quests
+#[allow(clippy::too_many_arguments)]
+fn quest_log_input_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_state: Res<State<RpgAppState>>,
+    player_resource: Res<PlayerResource>,
+    base_resource: Res<BaseResource>,
+    game_stats: Res<GameStatsResource>,
+    mut view: ResMut<QuestLogView>,
+    mut session: ResMut<RpgGameSession>,
+    mut game_log: ResMut<GameLogService>,
+    mut reputation_events: EventWriter<ReputationChangedEvent>,
+) {
+    if *current_state.get() != RpgAppState::QuestLog {
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::Tab) {
+        view.tab = match view.tab {
+            QuestLogTab::Board => QuestLogTab::Active,
+            QuestLogTab::Active => QuestLogTab::Board,
+        };
+        view.selected = 0;
+    }
+    let entries = match view.tab {
+        QuestLogTab::Board => session.quest_board.offers().len(),
+        QuestLogTab::Active => session.quest_board.active().len(),
+    };
+    if keyboard.any_just_pressed([KeyCode::KeyW, KeyCode::ArrowUp]) {
+        view.selected = view.selected.saturating_sub(1);
+    }
+    if keyboard.any_just_pressed([KeyCode::KeyS, KeyCode::ArrowDown]) {
+        view.selected += 1;
+    }
+    if view.selected >= entries {
+        view.selected = entries.saturating_sub(1);
+    }
+    if entries == 0 {
+        return;
+    }
+
+    match view.tab {
+        QuestLogTab::Board if keyboard.just_pressed(KeyCode::Enter) => {
+            let on_base = player_resource.player_position().is_some()
+                && player_resource.player_position() == base_resource.base_position();
+            if !on_base {
+                game_log.log_message(
+                    "Offers can only be taken at the base's bulletin board".to_string(),
+                    GameLogType::Warning,
+                );
+                return;
+            }
+            let quarters = base_resource
+                .base()
+                .and_then(|base| base.building(BuildingType::LivingQuarters))
+                .map(|building| building.level);
+            let slots = QuestBoard::active_slots(quarters);
+            match session
+                .quest_board
+                .accept(view.selected, slots, quest_counters(&game_stats))
+            {
+                Ok(posted) => game_log.log_message(
+                    format!("📜 Accepted quest: {}", posted.quest.title()),
+                    GameLogType::System,
+                ),
+                Err(e) => game_log.log_message(format!("{}", e), GameLogType::Warning),
+            }
+        }
+        QuestLogTab::Active if keyboard.just_pressed(KeyCode::Delete) => {
+            match session
+                .quest_board
+                .abandon(view.selected, game_stats.current_day())
+            {
+                Ok(abandoned) => {
+                    shift_reputation(
+                        &mut session,
+                        ReputationCause::QuestAbandoned,
+                        &mut reputation_events,
+                    );
+                    game_log.log_message(
+                        format!(
+                            "📜 Abandoned quest: {}. The Colonial Authority takes note",
+                            abandoned.quest.title()
+                        ),
+                        GameLogType::Warning,
+                    );
+                }
+                Err(e) => game_log.log_message(format!("{}", e), GameLogType::Warning),
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Show the quest log while it is open
+fn update_quest_log_panel(
+    current_state: Res<State<RpgAppState>>,
+    view: Res<QuestLogView>,
+    session: Res<RpgGameSession>,
+    base_resource: Res<BaseResource>,
+    mut panel_query: Query<&mut Visibility, With<QuestLogPanel>>,
+    mut text_query: Query<&mut Text, With<QuestLogText>>,
+) {
+    let open = *current_state.get() == RpgAppState::QuestLog;
+    if let Ok(mut visibility) = panel_query.single_mut() {
+        *visibility = if open {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+    if !open || !(view.is_changed() || session.is_changed() || current_state.is_changed()) {
+        return;
+    }
+    let quarters = base_resource
+        .base()
+        .and_then(|base| base.building(BuildingType::LivingQuarters))
+        .map(|building| building.level);
+    if let Ok(mut text) = text_query.single_mut() {
+        **text = quest_log_text(
+            &view,
+            &session.quest_board,
+            QuestBoard::active_slots(quarters),
+        );
+    }
+}
+
+fn quest_log_text(view: &QuestLogView, board: &QuestBoard, slots: usize) -> String {
+    let (board_tab, active_tab) = match view.tab {
+        QuestLogTab::Board => ("[BOARD]", "active"),
+        QuestLogTab::Active => ("board", "[ACTIVE]"),
+    };
+    let mut lines = vec![
+        format!("QUEST LOG  {} | {}", board_tab, active_tab),
+        format!(
+            "SLOTS: {}/{} | NEW OFFERS IN {} RESTS",
+            board.active().len(),
+            slots,
+            board.rests_until_posting()
+        ),
+        String::new(),
+    ];
+
+    let (entries, empty, hint) = match view.tab {
+        QuestLogTab::Board => (
+            board.offers(),
+            "The board is empty. Check back after a rest",
+            "ENTER: Accept (at base) | TAB: Active quests | ESC: Close",
+        ),
+        QuestLogTab::Active => (
+            board.active(),
+            "No active quests",
+            "DEL: Abandon | TAB: Board | ESC: Close",
+        ),
+    };
+    if entries.is_empty() {
+        lines.push(empty.to_string());
+    }
+    for (index, posted) in entries.iter().enumerate() {
+        let marker = if index == view.selected { ">" } else { " " };
+        lines.push(format!("{} {}", marker, quest_line(posted)));
+    }
+    lines.push(String::new());
+    lines.push(hint.to_string());
+    lines.join("\n")
+}
+
+fn quest_line(posted: &PostedQuest) -> String {
+    let rewards = posted.quest.rewards();
+    let progress = posted
+        .quest
+        .objectives()
+        .first()
+        .map(|objective| progress_bar(objective.current_progress, objective.target_amount))
+        .unwrap_or_default();
+    let target = posted
+        .target
+        .map(|target| format!(" @ [{}, {}]", target.x, target.y))
+        .unwrap_or_default();
+    format!(
+        "{}{} ({}) - {} XP, {}",
+        posted.quest.title(),
+        target,
+        progress,
+        rewards.experience,
+        rewards.resources
+    )
+}
+
+/// Cells of a quest's progress bar
+const PROGRESS_BAR_CELLS: u32 = 10;
+
+/// Text progress bar such as `[####------] 8/20`
+fn progress_bar(current: u32, target: u32) -> String {
+    let current = current.min(target);
+    let filled = (current * PROGRESS_BAR_CELLS)
+        .checked_div(target)
+        .unwrap_or(PROGRESS_BAR_CELLS);
+    format!(
+        "[{}{}] {}/{}",
+        "#".repeat(filled as usize),
+        "-".repeat((PROGRESS_BAR_CELLS - filled) as usize),
+        current,
+        target
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_board_tells_when_offers_arrive() {
+        let board = QuestBoard::new();
+        let text = quest_log_text(&QuestLogView::default(), &board, 3);
+        assert!(text.contains("[BOARD]"));
+        assert!(text.contains("SLOTS: 0/3 | NEW OFFERS IN 2 RESTS"));
+        assert!(text.contains("The board is empty"));
+
+        let view = QuestLogView {
+            tab: QuestLogTab::Active,
+            selected: 0,
+        };
+        let text = quest_log_text(&view, &board, 4);
+        assert!(text.contains("[ACTIVE]"));
+        assert!(text.contains("No active quests"));
+    }
+
+    #[test]
+    fn progress_bars_fill_with_the_objective() {
+        assert_eq!(progress_bar(0, 20), "[----------] 0/20");
+        assert_eq!(progress_bar(8, 20), "[####------] 8/20");
+        assert_eq!(progress_bar(45, 40), "[##########] 40/40");
+        assert_eq!(progress_bar(0, 0), "[##########] 0/0");
+    }
+}
@@ -0,0 +1,420 @@
+//! Quick Saves - Saving on demand and continuing a saved run
+//!
+//! F5 saves the run while exploring and F8 loads the save slot back; the
+//! slot is the one rests autosave to, so F8 also returns to the last rest
+//! when that came later. A move under way is saved on the tile it is
+//! heading for. On the main menu C continues the run in the slot, when
+//! there is one that a hardcore defeat has not buried; the menu then waits
+//! instead of starting a new run by itself, and Enter still starts one.
+//! Hardcore runs may save but not load. A save that cannot be loaded, one
+//! written by a newer build among them, changes nothing and says why in
+//! the game log.
+
+use crate::domain::constants::PRIMARY_TEXT;
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::RunMode;
+use crate::infrastructure::bevy::resources::{
+    BaseResource, GameStatsResource, GameTimerResource, MapResource, PlayerResource,
+};
+use crate::infrastructure::profile::ProfileStore;
+use crate::infrastructure::saves::{SaveData, SaveGameService, WorldSave};
+use crate::infrastructure::settings::RunSettings;
+use crate::presentation::about::AboutScreen;
+use crate::presentation::audio_integration::PendingAudioRestore;
+use crate::presentation::game_state::{RpgAppState, RpgGameSession};
+use crate::presentation::map_renderer::{PlayerMarker, RenderState};
+use crate::presentation::movement::SmoothMovement;
+use crate::presentation::run_end::{current_session, restore_run, ActiveRun, SavedRun};
+use bevy::prelude::*;
+
+/// Key that saves the run while exploring
+pub const QUICK_SAVE_KEY: KeyCode = KeyCode::F5;
+
+/// Key that loads the saved run back while exploring
+pub const QUICK_LOAD_KEY: KeyCode = KeyCode::F8;
+
+/// Key that continues the saved run from the main menu
+pub const CONTINUE_KEY: KeyCode = KeyCode::KeyC;
+
+/// Plugin for quick saves, quick loads and continuing a run
+pub struct QuickSavePlugin;
+
+impl Plugin for QuickSavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveGameService>()
+            .init_resource::<ProfileStore>()
+            .init_resource::<ContinueOffer>()
+            .init_resource::<PendingAudioRestore>()
+            .add_systems(Startup, setup_continue_line)
+            .add_systems(OnEnter(RpgAppState::MainMenu), offer_continue_system)
+            .add_systems(
+                Update,
+                (
+                    quick_save_system,
+                    quick_load_system,
+                    update_continue_line_system,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Whether the main menu offers to continue the saved run
+#[derive(Resource, Debug, Default)]
+pub struct ContinueOffer {
+    available: bool,
+}
+
+impl ContinueOffer {
+    /// Check if the menu should wait for the player to continue or start
+    pub fn holds_menu(&self) -> bool {
+        self.available
+    }
+
+    /// Line shown on the main menu, if any
+    pub fn line(&self) -> Option<String> {
+        self.available
+            .then(|| "Saved run found - C to continue, Enter for a new run".to_string())
+    }
+}
+
+#[derive(Component)]
+struct ContinueLine;
+
+fn setup_continue_line(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: FontSize::Regular.to_pixels(),
+            ..default()
+        },
+        TextColor(PRIMARY_TEXT),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Percent(10.0),
+            width: Val::Percent(80.0),
+            bottom: Val::Px(80.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        GlobalZIndex(14),
+        Visibility::Hidden,
+        ContinueLine,
+        Name::new("ContinueLine"),
+    ));
+}
+
+/// Look for a run to continue whenever the main menu opens
+fn offer_continue_system(
+    saves: Res<SaveGameService>,
+    profile: Res<ProfileStore>,
+    mut offer: ResMut<ContinueOffer>,
+) {
+    offer.available = saves.can_continue(profile.tombstones());
+}
+
+/// Save the run on F5 while exploring
+#[allow(clippy::too_many_arguments)]
+fn quick_save_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    saves: Res<SaveGameService>,
+    run: Res<ActiveRun>,
+    session: Res<RpgGameSession>,
+    player_resource: Res<PlayerResource>,
+    base_resource: Res<BaseResource>,
+    (map_resource, game_stats, game_timer): (
+        Res<MapResource>,
+        Res<GameStatsResource>,
+        Res<GameTimerResource>,
+    ),
+    player_query: Query<&SmoothMovement, With<PlayerMarker>>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    if *state.get() != RpgAppState::Exploration || !keyboard.just_pressed(QUICK_SAVE_KEY) {
+        return;
+    }
+    if run.mode().is_none() {
+        return;
+    }
+    // Only the overworld is saved; a ruin is entered again from its site
+    if map_resource.is_in_interior() {
+        game_log.log_message(
+            "💾 Leave the ruin to save the run".to_string(),
+            GameLogType::Warning,
+        );
+        return;
+    }
+    let Some(mut snapshot) = current_session(&session, &player_resource, &base_resource) else {
+        return;
+    };
+    if let Some(movement) = player_query.iter().find(|movement| movement.is_moving) {
+        snapshot.player.relocate(movement.target_position);
+    }
+    let world = WorldSave::capture(&map_resource, &game_stats, game_timer.game_time);
+    match saves.save(&SaveData::from_session(&snapshot).with_world(world)) {
+        Ok(()) => game_log.log_message(
+            "💾 Run saved - F8 to load it".to_string(),
+            GameLogType::System,
+        ),
+        Err(e) => {
+            warn!("💾 Quick save failed: {}", e);
+            game_log.log_message(
+                format!("💾 The run was not saved: {}", e),
+                GameLogType::Warning,
+            );
+        }
+    }
+}
+
+/// Load the saved run on F8 while exploring, or continue it from the menu
+#[allow(clippy::too_many_arguments)]
+fn quick_load_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    mut next_state: ResMut<NextState<RpgAppState>>,
+    (saves, profile, settings, about): (
+        Res<SaveGameService>,
+        Res<ProfileStore>,
+        Res<RunSettings>,
+        Option<Res<AboutScreen>>,
+    ),
+    mut run: ResMut<ActiveRun>,
+    mut offer: ResMut<ContinueOffer>,
+    mut game_log: ResMut<GameLogService>,
+    (mut session, mut player_resource, mut base_resource, mut audio_restore): (
+        ResMut<RpgGameSession>,
+        ResMut<PlayerResource>,
+        ResMut<BaseResource>,
+        ResMut<PendingAudioRestore>,
+    ),
+    (mut map_resource, mut game_stats, mut game_timer, mut render_state): (
+        ResMut<MapResource>,
+        ResMut<GameStatsResource>,
+        ResMut<GameTimerResource>,
+        Option<ResMut<RenderState>>,
+    ),
+    mut player_query: Query<(&mut SmoothMovement, &mut Transform), With<PlayerMarker>>,
+) {
+    let continuing = match state.get() {
+        RpgAppState::MainMenu => {
+            // C copies the build info while the about screen is open
+            if !offer.available
+                || about.is_some_and(|about| about.is_open())
+                || !keyboard.just_pressed(CONTINUE_KEY)
+            {
+                return;
+            }
+            true
+        }
+        RpgAppState::Exploration if keyboard.just_pressed(QUICK_LOAD_KEY) => false,
+        _ => {
+            if offer.available {
+                offer.available = false;
+            }
+            return;
+        }
+    };
+    if !continuing {
+        if run.mode() == Some(RunMode::Hardcore) {
+            game_log.log_message(
+                "💀 Hardcore runs cannot load a save".to_string(),
+                GameLogType::Warning,
+            );
+            return;
+        }
+        if player_query.iter().any(|(movement, _)| movement.is_moving) {
+            game_log.log_message(
+                "💾 Finish the move before loading".to_string(),
+                GameLogType::Movement,
+            );
+            return;
+        }
+    }
+
+    let saved = match saves
+        .load(profile.tombstones())
+        .and_then(SavedRun::from_loaded)
+    {
+        Ok(saved) => saved,
+        Err(e) => {
+            warn!("💾 Save not loaded: {}", e);
+            game_log.log_message(format!("💾 {}", e), GameLogType::Warning);
+            offer.available = false;
+            return;
+        }
+    };
+    restore_run(
+        saved,
+        &mut session,
+        &mut player_resource,
+        &mut base_resource,
+        &mut audio_restore,
+        (
+            &mut map_resource,
+            &mut game_stats,
+            &mut game_timer,
+            render_state.as_deref_mut(),
+        ),
+    );
+
+    if continuing {
+        // The saved run goes on in the mode picked for it; the menu never
+        // saw it start, so it is not counted again
+        run.resume(settings.mode());
+        offer.available = false;
+        game_log.log_message("💾 Saved run continued".to_string(), GameLogType::System);
+        next_state.set(RpgAppState::Exploration);
+    } else {
+        let position = *session.player.position();
+        for (mut movement, mut transform) in player_query.iter_mut() {
+            movement.reset_to_position(position);
+            transform.translation = movement.current_position;
+        }
+        game_log.log_message("💾 Saved run loaded".to_string(), GameLogType::System);
+    }
+}
+
+/// Show the continue line on the main menu while it is offered
+fn update_continue_line_system(
+    state: Res<State<RpgAppState>>,
+    offer: Res<ContinueOffer>,
+    mut lines: Query<(&mut Text, &mut Visibility), With<ContinueLine>>,
+) {
+    let Ok((mut text, mut visibility)) = lines.single_mut() else {
+        return;
+    };
+    let line = offer
+        .line()
+        .filter(|_| *state.get() == RpgAppState::MainMenu);
+    let wanted = if line.is_some() {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    if *visibility != wanted {
+        *visibility = wanted;
+    }
+    if let Some(line) = line {
+        if text.0 != line {
+            text.0 = line;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Base, Player};
+    use crate::domain::value_objects::{EntityId, Position3D};
+    use bevy::state::app::StatesPlugin;
+
+    #[test]
+    fn the_menu_continues_the_saved_run_or_warns_about_a_newer_one() {
+        let dir = std::env::temp_dir().join(format!("space-looter-quick-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("savegame.json");
+        let saves = SaveGameService::file(&path);
+        let player =
+            Player::create_new_character("Vex".to_string(), Position3D::new(6, -2, 0)).unwrap();
+        let base = Base::new(
+            EntityId::generate(),
+            "Outpost".to_string(),
+            Position3D::origin(),
+        )
+        .unwrap();
+        let mut map_resource = MapResource::new();
+        map_resource.generate_overworld(Position3D::origin(), 31);
+        let mut game_stats = GameStatsResource::new();
+        game_stats.record_rest();
+        let world = WorldSave::capture(&map_resource, &game_stats, Default::default());
+        saves
+            .save(&SaveData::from_session(&RpgGameSession::new(player, base)).with_world(world))
+            .unwrap();
+
+        let mut player_resource = PlayerResource::new();
+        player_resource
+            .create_player(
+                "player_001".to_string(),
+                "Fresh".to_string(),
+                Position3D::origin(),
+                crate::domain::PlayerStats::starting_stats(),
+            )
+            .unwrap();
+        let mut base_resource = BaseResource::new();
+        base_resource
+            .create_base("Central Command".to_string(), Position3D::origin())
+            .unwrap();
+        let fresh = RpgGameSession::new(
+            player_resource.player().unwrap().clone(),
+            base_resource.base().unwrap().clone(),
+        );
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_state(RpgAppState::MainMenu)
+            .insert_resource(ButtonInput::<KeyCode>::default())
+            .insert_resource(ProfileStore::in_memory())
+            .insert_resource(GameLogService::new())
+            .insert_resource(saves)
+            .insert_resource(fresh)
+            .insert_resource(player_resource)
+            .insert_resource(base_resource)
+            .insert_resource(MapResource::new())
+            .insert_resource(GameStatsResource::new())
+            .insert_resource(GameTimerResource::new())
+            .init_resource::<RunSettings>()
+            .init_resource::<ActiveRun>()
+            .init_resource::<ContinueOffer>()
+            .init_resource::<PendingAudioRestore>()
+            .add_systems(OnEnter(RpgAppState::MainMenu), offer_continue_system)
+            .add_systems(Update, quick_load_system);
+        app.update();
+        assert!(app.world().resource::<ContinueOffer>().holds_menu());
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(CONTINUE_KEY);
+        app.update();
+        app.update();
+
+        assert_eq!(
+            *app.world().resource::<State<RpgAppState>>().get(),
+            RpgAppState::Exploration
+        );
+        let world = app.world();
+        assert_eq!(
+            world.resource::<PlayerResource>().player_position(),
+            Some(Position3D::new(6, -2, 0))
+        );
+        assert_eq!(
+            world.resource::<MapResource>().overworld().unwrap().seed(),
+            31
+        );
+        assert_eq!(world.resource::<GameStatsResource>().current_day(), 2);
+        assert!(world.resource::<ActiveRun>().mode().is_some());
+
+        // A save from a newer build is refused with a warning, not a panic
+        std::fs::write(
+            &path,
+            r#"{"version": 999, "created_with": "9.0.0", "data": {}}"#,
+        )
+        .unwrap();
+        let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.release_all();
+        keyboard.clear();
+        keyboard.press(QUICK_LOAD_KEY);
+        app.update();
+
+        let world = app.world();
+        let warning = world.resource::<GameLogService>().get_recent_messages(1)[0].clone();
+        assert_eq!(warning.log_type, GameLogType::Warning);
+        assert!(warning.message.contains("9.0.0"));
+        assert_eq!(
+            world.resource::<PlayerResource>().player_position(),
+            Some(Position3D::new(6, -2, 0))
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,382 @@
+//! Refinery Panel - Base refinery queue in base management
+//!
+//! While managing the base, a panel shows the refinery's work queue with a
+//! progress bar per job. The player can build the refinery, queue jobs paid
+//! from their cargo, cancel unfinished jobs for a partial refund and clear
+//! finished ones. Every rest advances the queue wherever the player is;
+//! finished output goes into base storage.
+
+use crate::domain::constants::{PANEL_BACKGROUND, PRIMARY_TEXT, REFINERY_CANCEL_REFUND_PERCENT};
+use crate::domain::entities::{Base, BaseBuilding, BuildingType, RefineryQueue, RefineryRecipe};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::BaseReportItem;
+use crate::domain::value_objects::resources::ResourceCollection;
+use crate::infrastructure::bevy::resources::{BaseResource, PlayerResource};
+use crate::presentation::base_report::BaseReports;
+use crate::presentation::base_visuals::{BaseChange, BaseChanged};
+use crate::presentation::reputation::TradeBoard;
+use crate::presentation::world_tick::{TickCursor, TickPhase, WorldTick, WorldTickSet};
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+
+/// Width of a job progress bar in characters
+const PROGRESS_BAR_WIDTH: usize = 10;
+
+/// Plugin for the base refinery queue and its panel
+pub struct RefineryPlugin;
+
+impl Plugin for RefineryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RefinerySelection>()
+            .add_event::<BaseChanged>()
+            .add_systems(Startup, setup_refinery_panel)
+            .add_systems(
+                Update,
+                (
+                    refinery_rest_system.in_set(WorldTickSet::Economy),
+                    refinery_input_system,
+                    update_refinery_panel,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Job highlighted in the refinery panel
+#[derive(Resource, Debug, Clone, Default)]
+pub struct RefinerySelection {
+    pub index: usize,
+}
+
+/// Marker for the refinery panel
+#[derive(Component)]
+pub struct RefineryPanel;
+
+/// Marker for the refinery panel text
+#[derive(Component)]
+pub struct RefineryPanelText;
+
+fn setup_refinery_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(15.0),
+                top: Val::Px(80.0),
+                width: Val::Px(460.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(PANEL_BACKGROUND),
+            Visibility::Hidden,
+            RefineryPanel,
+            Name::new("RefineryPanel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                RefineryPanelText,
+            ));
+        });
+}
+
+/// Advance the refinery once per night of rest, wherever the player is,
+/// and add what it did to the base report
+fn refinery_rest_system(
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    mut base_resource: ResMut<BaseResource>,
+    mut reports: ResMut<BaseReports>,
+    mut base_events: EventWriter<BaseChanged>,
+) {
+    for _ in cursor.take(ticks.read(), TickPhase::AfterRest) {
+        let Some(base) = base_resource.base_mut() else {
+            continue;
+        };
+        let Some(recipe) = base
+            .refinery()
+            .jobs()
+            .iter()
+            .find(|job| !job.is_complete())
+            .map(|job| job.recipe)
+        else {
+            continue;
+        };
+        base_events.write(BaseChanged::new(BaseChange::RefineryWorked));
+        let refinery = base.building(BuildingType::Refinery).cloned();
+        let delivery = base.advance_refinery();
+
+        // Jobs restored without their building still run, unreported
+        let Some(refinery) = refinery else {
+            continue;
+        };
+        let item = match delivery {
+            Some(delivery) if delivery.overflow > 0 => BaseReportItem::new(&refinery, "produced")
+                .with_output(delivery.resource_type, delivery.deposited)
+                .with_incident(format!(
+                    "storage full, {} {} lost",
+                    delivery.overflow, delivery.resource_type
+                )),
+            Some(delivery) => BaseReportItem::new(&refinery, "produced")
+                .with_output(delivery.resource_type, delivery.deposited),
+            None => BaseReportItem::new(&refinery, format!("worked on {}", recipe.name())),
+        };
+        reports.add(item);
+    }
+}
+
+/// Build, queue, cancel and collect from the base management screen
+#[allow(clippy::too_many_arguments)]
+fn refinery_input_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_state: Res<State<RpgAppState>>,
+    mut base_resource: ResMut<BaseResource>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut selection: ResMut<RefinerySelection>,
+    mut game_log: ResMut<GameLogService>,
+    mut base_events: EventWriter<BaseChanged>,
+    trade_board: Option<Res<TradeBoard>>,
+) {
+    if *current_state.get() != RpgAppState::BaseManagement {
+        return;
+    }
+    // The trade board takes the number keys while it is open
+    if trade_board.is_some_and(|board| board.is_open()) {
+        return;
+    }
+    let Some(base) = base_resource.base_mut() else {
+        return;
+    };
+
+    if base.building(BuildingType::Refinery).is_none() {
+        if keyboard.just_pressed(KeyCode::KeyR) {
+            let cost = building_cost(BuildingType::Refinery);
+            match player_resource.try_pay_resources(&cost) {
+                Ok(()) => {
+                    base.add_building(BaseBuilding::new(
+                        BuildingType::Refinery,
+                        "Refinery".to_string(),
+                        (1, 0),
+                    ));
+                    base_events.write(BaseChanged::new(BaseChange::Constructed(
+                        BuildingType::Refinery,
+                    )));
+                    game_log.log_message(
+                        "🏭 Refinery constructed".to_string(),
+                        GameLogType::Discovery,
+                    );
+                }
+                Err(e) => game_log.log_message(format!("{}", e), GameLogType::Warning),
+            }
+        }
+        return;
+    }
+
+    let recipe = if keyboard.just_pressed(KeyCode::Digit1) {
+        Some(RefineryRecipe::AlloySmelting)
+    } else if keyboard.just_pressed(KeyCode::Digit2) {
+        Some(RefineryRecipe::TechnologyAssembly)
+    } else {
+        None
+    };
+    if let Some(recipe) = recipe {
+        match base.enqueue_refinery_job(recipe, |cost| player_resource.try_pay_resources(cost)) {
+            Ok(()) => {
+                game_log.log_message(format!("🏭 {} queued", recipe.name()), GameLogType::System)
+            }
+            Err(e) => game_log.log_message(format!("{}", e), GameLogType::Warning),
+        }
+    }
+
+    let job_count = base.refinery().jobs().len();
+    if keyboard.just_pressed(KeyCode::Tab) && job_count > 0 {
+        selection.index = (selection.index + 1) % job_count;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyC) {
+        match base.cancel_refinery_job(selection.index) {
+            Ok(refund) => {
+                player_resource.add_resources(&refund);
+                game_log.log_message(
+                    format!("🏭 Job cancelled, refunded: {}", refund),
+                    GameLogType::Resources,
+                );
+            }
+            Err(e) => game_log.log_message(format!("{}", e), GameLogType::Warning),
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyF) {
+        let collected = base.collect_refinery_jobs();
+        if collected > 0 {
+            game_log.log_message(
+                format!("🏭 Cleared {} finished refinery jobs", collected),
+                GameLogType::System,
+            );
+        }
+    }
+
+    selection.index = selection
+        .index
+        .min(base.refinery().jobs().len().saturating_sub(1));
+}
+
+/// Show the panel in base management
+fn update_refinery_panel(
+    current_state: Res<State<RpgAppState>>,
+    base_resource: Res<BaseResource>,
+    selection: Res<RefinerySelection>,
+    mut panel_query: Query<&mut Visibility, With<RefineryPanel>>,
+    mut text_query: Query<&mut Text, With<RefineryPanelText>>,
+) {
+    let Ok(mut visibility) = panel_query.single_mut() else {
+        return;
+    };
+    let base = base_resource
+        .base()
+        .filter(|_| *current_state.get() == RpgAppState::BaseManagement);
+    let wanted = if base.is_some() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    if *visibility != wanted {
+        *visibility = wanted;
+    }
+
+    let Some(base) = base else {
+        return;
+    };
+    if let Ok(mut text) = text_query.single_mut() {
+        let content = refinery_panel_text(base, selection.index);
+        if **text != content {
+            **text = content;
+        }
+    }
+}
+
+fn building_cost(building_type: BuildingType) -> ResourceCollection {
+    let mut cost = ResourceCollection::new();
+    for (resource_type, amount) in building_type.build_cost() {
+        cost.set_amount(resource_type, amount);
+    }
+    cost
+}
+
+/// Text progress bar such as `[#####.....]`
+fn progress_bar(progress: f32, width: usize) -> String {
+    let filled = ((progress.clamp(0.0, 1.0) * width as f32).round() as usize).min(width);
+    format!("[{}{}]", "#".repeat(filled), ".".repeat(width - filled))
+}
+
+fn refinery_panel_text(base: &Base, selected: usize) -> String {
+    let mut lines = Vec::new();
+    let Some(refinery) = base.building(BuildingType::Refinery) else {
+        lines.push("REFINERY - not built".to_string());
+        lines.push(format!(
+            "R: Build refinery ({})",
+            building_cost(BuildingType::Refinery)
+        ));
+        return lines.join("\n");
+    };
+
+    let queue = base.refinery();
+    lines.push(format!(
+        "REFINERY (Level {}) - {}/{} jobs",
+        refinery.level,
+        queue.pending_count(),
+        RefineryQueue::capacity(refinery.level)
+    ));
+    if queue.jobs().is_empty() {
+        lines.push("  Queue empty".to_string());
+    }
+    for (index, job) in queue.jobs().iter().enumerate() {
+        let marker = if index == selected { ">" } else { " " };
+        let status = if job.is_complete() {
+            "DONE".to_string()
+        } else {
+            format!("{}/{} rests", job.rests_done, job.recipe.rests_required())
+        };
+        lines.push(format!(
+            "{} {}. {} {} {}",
+            marker,
+            index + 1,
+            job.recipe.name(),
+            progress_bar(job.progress(), PROGRESS_BAR_WIDTH),
+            status
+        ));
+    }
+
+    lines.push(String::new());
+    for (index, recipe) in RefineryRecipe::all().iter().enumerate() {
+        let (output, amount) = recipe.output();
+        lines.push(format!(
+            "{}: {} ({} -> {} {}, {} rests)",
+            index + 1,
+            recipe.name(),
+            recipe
+                .input_cost()
+                .map(|cost| cost.to_string())
+                .unwrap_or_default(),
+            amount,
+            output,
+            recipe.rests_required()
+        ));
+    }
+    lines.push(format!(
+        "TAB: Select | C: Cancel ({}% refund) | F: Clear finished",
+        REFINERY_CANCEL_REFUND_PERCENT
+    ));
+    lines.push(format!(
+        "Base storage: {}/{}",
+        base.storage_capacity() - base.free_capacity(),
+        base.storage_capacity()
+    ));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{EntityId, Position3D};
+
+    #[test]
+    fn progress_bars_fill_with_progress() {
+        assert_eq!(progress_bar(0.0, 4), "[....]");
+        assert_eq!(progress_bar(0.5, 4), "[##..]");
+        assert_eq!(progress_bar(1.5, 4), "[####]");
+    }
+
+    #[test]
+    fn panel_lists_jobs_with_selection() {
+        let mut base = Base::new(
+            EntityId::generate(),
+            "Home".to_string(),
+            Position3D::origin(),
+        )
+        .unwrap();
+        assert!(refinery_panel_text(&base, 0).contains("not built"));
+
+        base.add_building(BaseBuilding::new(
+            BuildingType::Refinery,
+            "Refinery".to_string(),
+            (1, 0),
+        ));
+        base.enqueue_refinery_job(RefineryRecipe::TechnologyAssembly, |_| Ok(()))
+            .unwrap();
+        base.enqueue_refinery_job(RefineryRecipe::AlloySmelting, |_| Ok(()))
+            .unwrap();
+        base.advance_refinery();
+
+        let text = refinery_panel_text(&base, 1);
+        assert!(text.contains("  1. Technology Assembly [#####.....] 1/2 rests"));
+        assert!(text.contains("> 2. Alloy Smelting [..........] 0/1 rests"));
+    }
+}
@@ -5,8 +5,10 @@
 //! It acts as a bridge between the game logic and visual output.
 
 use crate::domain::{Position3D, Score};
+use crate::presentation::display_mode::ResolutionPreset;
 use crate::presentation::map_renderer::PlayerMarker;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 /// Rendering configuration and settings
@@ -39,6 +41,43 @@ impl Default for RenderingConfig {
     }
 }
 
+/// Player-facing display preferences persisted in the settings file
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplaySettings {
+    /// UI scale factor
+    pub ui_scale: f32,
+    /// VSync enabled
+    pub vsync: bool,
+    /// Show the frame rate counter
+    pub show_fps: bool,
+    /// Show debug information
+    pub show_debug: bool,
+    /// Avoid camera motion the player did not ask for
+    pub reduce_motion: bool,
+    /// Hide purely decorative marks such as tile decals
+    pub reduce_clutter: bool,
+    /// Window size on native builds
+    pub resolution: ResolutionPreset,
+    /// Borderless fullscreen on native builds
+    pub fullscreen: bool,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            ui_scale: 1.0,
+            vsync: true,
+            show_fps: false,
+            show_debug: false,
+            reduce_motion: false,
+            reduce_clutter: false,
+            resolution: ResolutionPreset::default(),
+            fullscreen: false,
+        }
+    }
+}
+
 impl RenderingConfig {
     /// Create configuration optimized for web
     pub fn web_optimized() -> Self {
@@ -400,6 +439,7 @@ impl Plugin for RenderingPlugin {
         app
             // Add resources
             .init_resource::<RenderingConfig>()
+            .init_resource::<DisplaySettings>()
             .init_resource::<UIRenderState>()
             // Add systems
             .add_systems(
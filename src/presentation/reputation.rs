@@ -0,0 +1,798 @@
+//! Faction Reputation - Hostile contact choice, trade board and the log
+//!
+//! When hostiles hail the player, movement holds until the player picks:
+//! fight them (F), flee back to the tile they came from (H), or, with enough
+//! Metal in the cargo, pay them off through the Scavenger Guild (G), which
+//! the Colonial Authority frowns upon. A fight is a best of three: every F
+//! plays a round, Shift+F plays the rest, and blitz runs play it out at
+//! once. Running is only possible before the first round or right after
+//! losing it. A failed escape costs a free hit and the next roll is made at
+//! disadvantage; a clean one leaves the hostiles a tile away, where they
+//! may catch up on the next world tick. At the base, T opens the trade board with the faction offers of
+//! the day; a number key accepts one, paid from the player's cargo.
+//! Every standing that moves is sent as a `ReputationChangedEvent`, which
+//! the game log reports. Paying off hostiles is also counted in the
+//! session flags, and the raiders may come back for it.
+
+use crate::application::use_cases::{CombatExchange, EncounterApproach, ExchangeState, RngDice};
+use crate::domain::constants::{
+    FLAG_BRIBES_PAID, FLAG_SPARED_SCAVENGER, FLEE_EXTRA_MOVEMENT_COST, HOSTILE_BRIBE_METAL,
+    HOSTILE_PURSUIT_DIFFICULTY, HOSTILE_PURSUIT_RANGE, PANEL_BACKGROUND, PRIMARY_TEXT,
+    REPUTATION_RIVAL_COUPLING,
+};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{
+    poi_entry_id, PointOfInterest, ReputationCause, ReputationChange, ReputationTier, RollOutcome,
+    TradeService,
+};
+use crate::domain::value_objects::resources::{ResourceCollection, ResourceType};
+use crate::domain::value_objects::Position3D;
+use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource, PlayerResource};
+use crate::infrastructure::random::streams::{RngStreams, ENCOUNTERS};
+use crate::presentation::codex::CodexUnlockEvent;
+use crate::presentation::game_state::RpgGameSession;
+use crate::presentation::map_renderer::PlayerMarker;
+use crate::presentation::movement::{MovementConfig, SmoothMovement};
+use crate::presentation::world_rebuild::WorldTeardown;
+use crate::presentation::world_tick::{TickCursor, WorldTick, WorldTickSet};
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+use rand::Rng;
+
+/// Keys accepting the offers on the trade board, in order
+const OFFER_KEYS: [KeyCode; 6] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+];
+
+/// Plugin for faction reputation, the hostile contact choice and trading
+pub struct ReputationPlugin;
+
+impl Plugin for ReputationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ReputationChangedEvent>()
+            .init_resource::<HostileContact>()
+            .init_resource::<TradeBoard>()
+            .init_resource::<RngStreams>()
+            .add_systems(Startup, setup_reputation_panels)
+            .add_systems(WorldTeardown, forget_hostiles_system)
+            .add_systems(
+                Update,
+                (
+                    hostile_pursuit_system.in_set(WorldTickSet::Hostiles),
+                    hostile_contact_choice_system,
+                    trade_board_input_system,
+                    reputation_log_system,
+                    update_reputation_panels,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Hostiles met on the old world have nowhere to stand on the new one
+fn forget_hostiles_system(mut contact: ResMut<HostileContact>) {
+    *contact = HostileContact::default();
+}
+
+/// A faction standing moved
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ReputationChangedEvent {
+    pub change: ReputationChange,
+    pub cause: ReputationCause,
+}
+
+/// Hostiles waiting for the player to fight, flee or pay
+#[derive(Resource, Debug, Clone, Default)]
+pub struct HostileContact {
+    pending: Option<PendingContact>,
+    /// Tile of hostiles the player fled from, until their one chance to catch up
+    pursuer: Option<Position3D>,
+}
+
+/// A contact waiting for the player's choice
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingContact {
+    /// Tile the hostiles were met on
+    pub position: Position3D,
+    /// Tile to fall back to when fleeing; none when they caught up
+    pub retreat: Option<Position3D>,
+    /// A failed escape puts the next roll at disadvantage
+    pub disadvantage: bool,
+    /// The fight, once the first round was played
+    pub exchange: Option<CombatExchange>,
+}
+
+impl HostileContact {
+    /// Hold movement until the contact met on `position` is settled
+    pub fn raise(&mut self, position: Position3D, retreat: Option<Position3D>) {
+        self.pending = Some(PendingContact {
+            position,
+            retreat,
+            disadvantage: false,
+            exchange: None,
+        });
+        self.pursuer = None;
+    }
+
+    /// Check if movement is held for a choice
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// The contact waiting for a choice
+    pub fn pending(&self) -> Option<PendingContact> {
+        self.pending
+    }
+
+    /// Tile of the hostiles that may still give chase
+    pub fn pursuer(&self) -> Option<Position3D> {
+        self.pursuer
+    }
+
+    /// The hostiles are dealt with
+    pub fn settle(&mut self) {
+        self.pending = None;
+    }
+
+    /// The escape failed: the fight goes on, at disadvantage
+    pub fn escape_failed(&mut self) {
+        if let Some(pending) = self.pending.as_mut() {
+            pending.disadvantage = true;
+            if let Some(exchange) = pending.exchange.as_mut() {
+                exchange.hamper_next_round();
+            }
+        }
+    }
+
+    /// Keep the fight going between rounds
+    pub fn continue_exchange(&mut self, exchange: CombatExchange) {
+        if let Some(pending) = self.pending.as_mut() {
+            pending.exchange = Some(exchange);
+            pending.disadvantage = false;
+        }
+    }
+
+    /// Check if the player may still run from the contact
+    pub fn can_flee(&self) -> bool {
+        self.pending
+            .is_some_and(|pending| pending.exchange.is_none_or(|exchange| exchange.can_flee()))
+    }
+
+    /// The escape worked; hostiles left next to the player may give chase
+    pub fn escaped(&mut self) {
+        if let Some(pending) = self.pending.take() {
+            self.pursuer = pending.retreat.map(|_| pending.position);
+        }
+    }
+
+    /// Give fled hostiles their one chance to catch the player at `player`
+    ///
+    /// Within a tile they always do; within `HOSTILE_PURSUIT_RANGE` tiles
+    /// they need a d20 `roll` of `HOSTILE_PURSUIT_DIFFICULTY`. Returns
+    /// whether they caught up, which raises the contact where the player is.
+    pub fn pursue(&mut self, player: Position3D, roll: u8) -> bool {
+        let Some(hostiles) = self.pursuer.take() else {
+            return false;
+        };
+        let distance = hostiles.manhattan_distance_2d(&player);
+        let caught = distance <= 1
+            || (distance <= HOSTILE_PURSUIT_RANGE && roll >= HOSTILE_PURSUIT_DIFFICULTY);
+        if caught {
+            self.raise(player, None);
+        }
+        caught
+    }
+}
+
+/// Whether the trade board is open
+#[derive(Resource, Debug, Clone, Default)]
+pub struct TradeBoard {
+    open: bool,
+}
+
+impl TradeBoard {
+    /// Check if the board takes the number keys
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+}
+
+/// Metal it takes to make hostiles look the other way
+pub fn hostile_bribe_cost() -> ResourceCollection {
+    let mut cost = ResourceCollection::new();
+    cost.set_amount(ResourceType::Metal, HOSTILE_BRIBE_METAL);
+    cost
+}
+
+/// Apply a cause to the session's standings and report what moved
+pub fn shift_reputation(
+    session: &mut RpgGameSession,
+    cause: ReputationCause,
+    events: &mut EventWriter<ReputationChangedEvent>,
+) {
+    for change in session.reputation.apply(cause, REPUTATION_RIVAL_COUPLING) {
+        events.write(ReputationChangedEvent { change, cause });
+    }
+}
+
+/// Marker for the hostile contact panel
+#[derive(Component)]
+pub struct HostileContactPanel;
+
+/// Marker for the hostile contact text
+#[derive(Component)]
+pub struct HostileContactText;
+
+/// Marker for the trade board panel
+#[derive(Component)]
+pub struct TradeBoardPanel;
+
+/// Marker for the trade board text
+#[derive(Component)]
+pub struct TradeBoardText;
+
+fn setup_reputation_panels(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(30.0),
+                top: Val::Px(120.0),
+                width: Val::Px(420.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(PANEL_BACKGROUND),
+            Visibility::Hidden,
+            HostileContactPanel,
+            Name::new("HostileContactPanel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                HostileContactText,
+            ));
+        });
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(15.0),
+                top: Val::Px(80.0),
+                width: Val::Px(420.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(PANEL_BACKGROUND),
+            Visibility::Hidden,
+            TradeBoardPanel,
+            Name::new("TradeBoardPanel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                TradeBoardText,
+            ));
+        });
+}
+
+/// Fled hostiles still close to the player may catch up on the next tick
+fn hostile_pursuit_system(
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    mut contact: ResMut<HostileContact>,
+    mut game_log: ResMut<GameLogService>,
+    rng_streams: ResMut<RngStreams>,
+) {
+    let Some(player) = ticks
+        .read()
+        .filter(|tick| cursor.accept(tick))
+        .last()
+        .and_then(|tick| tick.position)
+    else {
+        return;
+    };
+    if contact.pursuer().is_none() {
+        return;
+    }
+
+    let roll = rng_streams.stream(ENCOUNTERS).gen_range(1..=20);
+    if contact.pursue(player, roll) {
+        game_log.log_message(
+            "⚠️ The raiders you fled catch up - fight them (F) or flee again (H)".to_string(),
+            GameLogType::Warning,
+        );
+    } else {
+        game_log.log_message(
+            "The raiders lose your trail".to_string(),
+            GameLogType::Event,
+        );
+    }
+}
+
+/// Fight, flee or pay off the hostiles holding the player
+#[allow(clippy::too_many_arguments)]
+fn hostile_contact_choice_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut contact: ResMut<HostileContact>,
+    mut player_resource: ResMut<PlayerResource>,
+    map_resource: Res<MapResource>,
+    mut game_stats: ResMut<GameStatsResource>,
+    mut game_log: ResMut<GameLogService>,
+    mut session: ResMut<RpgGameSession>,
+    mut reputation_events: EventWriter<ReputationChangedEvent>,
+    config: Res<MovementConfig>,
+    mut player_query: Query<&mut SmoothMovement, With<PlayerMarker>>,
+    rng_streams: ResMut<RngStreams>,
+) {
+    let Some(pending) = contact.pending else {
+        return;
+    };
+
+    if keyboard.just_pressed(KeyCode::KeyF) {
+        let rest =
+            game_stats.blitz || keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        let exchange = match pending.exchange {
+            Some(exchange) => Some(exchange),
+            None => player_resource.get_player().and_then(|player| {
+                let (threat, _) = crate::hostile_threat(pending.position, &map_resource);
+                let assist = session.fortune_favor.pending_bonus() as i32;
+                CombatExchange::new(
+                    &player.derived_stats(),
+                    threat,
+                    assist,
+                    pending.disadvantage,
+                )
+                .map_err(|e| warn!("Failed to start the fight: {}", e))
+                .ok()
+            }),
+        };
+        let Some(mut exchange) = exchange else {
+            return;
+        };
+
+        let mut dice = RngDice(rng_streams.stream(ENCOUNTERS));
+        while exchange.state() == ExchangeState::Ongoing {
+            let round = match exchange.play_round(&mut dice) {
+                Ok(round) => round,
+                Err(e) => {
+                    warn!("Failed to play a round: {}", e);
+                    return;
+                }
+            };
+            // Rounds count towards Fortune's Favor like any other roll
+            session.fortune_favor.record_outcome(if round.won() {
+                RollOutcome::Success
+            } else {
+                RollOutcome::Failure
+            });
+            game_log.log_message(
+                format!(
+                    "⚔️ Round {} {} - {}",
+                    exchange.rounds().count(),
+                    exchange.pips(),
+                    round.describe()
+                ),
+                GameLogType::Combat,
+            );
+            if !rest {
+                break;
+            }
+        }
+
+        match exchange.outcome() {
+            Some(outcome) => {
+                contact.settle();
+                crate::apply_encounter_outcome(
+                    &outcome,
+                    pending.position,
+                    &mut player_resource,
+                    &mut game_stats,
+                    &mut game_log,
+                    &mut session,
+                );
+            }
+            None => contact.continue_exchange(exchange),
+        }
+    } else if keyboard.just_pressed(KeyCode::KeyH) {
+        if !contact.can_flee() {
+            game_log.log_message(
+                "⚔️ They have you pinned - the fight goes on (F)".to_string(),
+                GameLogType::Warning,
+            );
+            return;
+        }
+        let Some(outcome) = crate::resolve_hostile_contact(
+            pending.position,
+            EncounterApproach::Flee,
+            pending.disadvantage,
+            &mut player_resource,
+            &map_resource,
+            &mut game_stats,
+            &mut game_log,
+            &mut session,
+            &rng_streams,
+        ) else {
+            return;
+        };
+        if !outcome.fled {
+            contact.escape_failed();
+            let retry = if contact.can_flee() {
+                " or try again (H)"
+            } else {
+                ""
+            };
+            game_log.log_message(
+                format!(
+                    "⚔️ No way out - fight on (F){}, now at a disadvantage",
+                    retry
+                ),
+                GameLogType::Warning,
+            );
+            return;
+        }
+
+        contact.escaped();
+        let Some(retreat) = pending.retreat else {
+            return;
+        };
+        // The step back is not a move: nothing is rolled and no event can trigger
+        player_resource.set_position(retreat);
+        player_resource.lose_movement_points(FLEE_EXTRA_MOVEMENT_COST);
+        if let Ok(mut movement) = player_query.single_mut() {
+            movement.start_retreat(retreat, &config);
+        }
+        game_log.log_message(
+            format!(
+                "🏃 You fall back to ({}, {}) for {} extra movement point",
+                retreat.x, retreat.y, FLEE_EXTRA_MOVEMENT_COST
+            ),
+            GameLogType::Movement,
+        );
+    } else if keyboard.just_pressed(KeyCode::KeyG) {
+        match player_resource.try_pay_resources(&hostile_bribe_cost()) {
+            Ok(()) => {
+                contact.settle();
+                game_log.log_message(
+                    format!(
+                        "💰 {} Metal changes hands; the raiders peel off with a Guild salute",
+                        HOSTILE_BRIBE_METAL
+                    ),
+                    GameLogType::Event,
+                );
+                shift_reputation(&mut session, ReputationCause::Bribe, &mut reputation_events);
+                session.flags.set_flag(FLAG_SPARED_SCAVENGER);
+                session.flags.increment(FLAG_BRIBES_PAID, 1);
+            }
+            Err(e) => game_log.log_message(format!("{}", e), GameLogType::Warning),
+        }
+    }
+}
+
+/// Open the trade board at the base and accept its offers
+#[allow(clippy::too_many_arguments)]
+fn trade_board_input_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_state: Res<State<RpgAppState>>,
+    game_stats: Res<GameStatsResource>,
+    mut board: ResMut<TradeBoard>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut session: ResMut<RpgGameSession>,
+    mut game_log: ResMut<GameLogService>,
+    mut reputation_events: EventWriter<ReputationChangedEvent>,
+    mut codex_unlocks: EventWriter<CodexUnlockEvent>,
+) {
+    if *current_state.get() != RpgAppState::BaseManagement {
+        board.open = false;
+        return;
+    }
+    if keyboard.just_pressed(KeyCode::KeyT) {
+        board.open = !board.open;
+        if board.open {
+            codex_unlocks.write(CodexUnlockEvent::new(poi_entry_id(
+                PointOfInterest::Spaceport,
+            )));
+        }
+    }
+    if !board.open {
+        return;
+    }
+
+    let offers = TradeService::new().offers(&session.reputation, game_stats.nights_rested);
+    let Some(offer) = OFFER_KEYS
+        .iter()
+        .position(|key| keyboard.just_pressed(*key))
+        .and_then(|index| offers.get(index))
+    else {
+        return;
+    };
+
+    match player_resource.try_pay_resources(&offer.cost()) {
+        Ok(()) => {
+            player_resource.add_resources(&offer.goods());
+            game_log.log_message(
+                format!(
+                    "🤝 Traded {} {} for {} {} with the {}",
+                    offer.give.1,
+                    offer.give.0,
+                    offer.receive.1,
+                    offer.receive.0,
+                    offer.faction.name()
+                ),
+                GameLogType::Resources,
+            );
+            shift_reputation(
+                &mut session,
+                ReputationCause::Trade {
+                    faction: offer.faction,
+                    volume: offer.receive.1,
+                },
+                &mut reputation_events,
+            );
+        }
+        Err(e) => game_log.log_message(format!("{}", e), GameLogType::Warning),
+    }
+}
+
+/// Report standing changes in the game log
+fn reputation_log_system(
+    mut reputation_events: EventReader<ReputationChangedEvent>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    for event in reputation_events.read() {
+        let change = event.change;
+        game_log.log_message(
+            format!(
+                "{} standing {:+} ({})",
+                change.faction.name(),
+                change.current - change.previous,
+                change.current
+            ),
+            GameLogType::System,
+        );
+        if change.tier_changed() {
+            game_log.log_message(
+                format!(
+                    "📣 The {} now regards you as {}",
+                    change.faction.name(),
+                    ReputationTier::from_score(change.current).name()
+                ),
+                GameLogType::Event,
+            );
+        }
+    }
+}
+
+/// Show the contact choice and the trade board while they are up
+#[allow(clippy::too_many_arguments)]
+fn update_reputation_panels(
+    contact: Res<HostileContact>,
+    board: Res<TradeBoard>,
+    game_stats: Res<GameStatsResource>,
+    session: Res<RpgGameSession>,
+    mut contact_query: Query<
+        &mut Visibility,
+        (With<HostileContactPanel>, Without<TradeBoardPanel>),
+    >,
+    mut board_query: Query<&mut Visibility, With<TradeBoardPanel>>,
+    mut text_query: Query<&mut Text, (With<TradeBoardText>, Without<HostileContactText>)>,
+    mut contact_text_query: Query<&mut Text, With<HostileContactText>>,
+) {
+    if let Ok(mut visibility) = contact_query.single_mut() {
+        show_panel(&mut visibility, contact.is_pending());
+    }
+    if let (Some(pending), Ok(mut text)) = (contact.pending(), contact_text_query.single_mut()) {
+        let content = hostile_contact_text(&pending);
+        if **text != content {
+            **text = content;
+        }
+    }
+    if let Ok(mut visibility) = board_query.single_mut() {
+        show_panel(&mut visibility, board.is_open());
+    }
+
+    if !board.is_open() {
+        return;
+    }
+    if let Ok(mut text) = text_query.single_mut() {
+        let content = trade_board_text(&session, game_stats.nights_rested);
+        if **text != content {
+            **text = content;
+        }
+    }
+}
+
+fn show_panel(visibility: &mut Visibility, shown: bool) {
+    let wanted = if shown {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    if *visibility != wanted {
+        *visibility = wanted;
+    }
+}
+
+/// Text of the contact panel: the choices, or the fight so far
+fn hostile_contact_text(pending: &PendingContact) -> String {
+    let Some(exchange) = pending.exchange else {
+        return format!(
+            "⚠️ HOSTILE CONTACT\n\nRaiders lock on to your ship.\n\n\
+             F: Fight (best of three rounds)\nH: Flee the way you came (easier on open ground)\n\
+             G: Pay {} Metal through the Scavenger Guild\n   \
+             (the Colonial Authority will hear of it)",
+            HOSTILE_BRIBE_METAL
+        );
+    };
+
+    let mut lines = vec![format!("⚔️ FIGHT {}", exchange.pips()), String::new()];
+    for (index, round) in exchange.rounds().enumerate() {
+        lines.push(format!("Round {}: {}", index + 1, round.describe()));
+    }
+    lines.push(format!("Damage taken: {}", exchange.damage_taken()));
+    lines.push(String::new());
+    lines.push("F: Next round   Shift+F: Play it out".to_string());
+    if exchange.can_flee() {
+        lines.push("H: Flee the way you came".to_string());
+    }
+    lines.push(format!("G: Pay {} Metal", HOSTILE_BRIBE_METAL));
+    lines.join("\n")
+}
+
+/// Text of the trade board
+fn trade_board_text(session: &RpgGameSession, rotation: u32) -> String {
+    let offers = TradeService::new().offers(&session.reputation, rotation);
+    let mut lines = vec!["SPACEPORT TRADE BOARD".to_string(), String::new()];
+    if offers.is_empty() {
+        lines.push("No faction will deal with you today".to_string());
+    }
+    for (index, offer) in offers.iter().enumerate() {
+        lines.push(format!(
+            "{}: {}{} - {} {} for {} {}",
+            index + 1,
+            offer.faction.name(),
+            if offer.exclusive { " ★" } else { "" },
+            offer.give.1,
+            offer.give.0,
+            offer.receive.1,
+            offer.receive.0
+        ));
+    }
+    lines.push(String::new());
+    lines.push("Offers change after every rest. T: Close".to_string());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Base, Player};
+    use crate::domain::services::Faction;
+    use crate::domain::value_objects::EntityId;
+
+    #[test]
+    fn fled_hostiles_get_one_chance_to_catch_up() {
+        let met = Position3D::new(3, 0, 0);
+        let retreat = Position3D::new(2, 0, 0);
+        let fled = || {
+            let mut contact = HostileContact::default();
+            contact.raise(met, Some(retreat));
+            contact.escaped();
+            contact
+        };
+
+        // Resting next to them never works
+        let mut contact = fled();
+        assert_eq!(contact.pursuer(), Some(met));
+        assert!(contact.pursue(retreat, 1));
+        assert_eq!(
+            contact.pending(),
+            Some(PendingContact {
+                position: retreat,
+                retreat: None,
+                disadvantage: false,
+                exchange: None,
+            })
+        );
+
+        // A tile further away they need the roll, and only get one
+        let away = Position3D::new(1, 0, 0);
+        let mut contact = fled();
+        assert!(!contact.pursue(away, HOSTILE_PURSUIT_DIFFICULTY - 1));
+        assert!(!contact.is_pending());
+        assert!(!contact.pursue(away, 20));
+        assert!(fled().pursue(away, HOSTILE_PURSUIT_DIFFICULTY));
+        assert!(!fled().pursue(Position3D::new(0, 0, 0), 20));
+
+        // Hostiles that caught up once cannot be fled into another chase
+        let mut contact = fled();
+        contact.pursue(retreat, 20);
+        contact.escaped();
+        assert_eq!(contact.pursuer(), None);
+    }
+
+    #[test]
+    fn a_failed_escape_keeps_the_fight_at_disadvantage() {
+        let mut contact = HostileContact::default();
+        contact.raise(Position3D::new(3, 0, 0), Some(Position3D::new(2, 0, 0)));
+        contact.escape_failed();
+        let pending = contact.pending().unwrap();
+        assert!(pending.disadvantage);
+        assert_eq!(pending.retreat, Some(Position3D::new(2, 0, 0)));
+    }
+
+    #[test]
+    fn running_closes_once_the_first_round_is_won() {
+        use crate::application::use_cases::FixedRoll;
+        use crate::domain::value_objects::PlayerStats;
+
+        let mut contact = HostileContact::default();
+        contact.raise(Position3D::new(3, 0, 0), Some(Position3D::new(2, 0, 0)));
+        assert!(contact.can_flee());
+
+        let fight = |roll| {
+            let mut exchange =
+                CombatExchange::new(&PlayerStats::starting_stats(), 4, 0, false).unwrap();
+            exchange.play_round(&mut FixedRoll(roll)).unwrap();
+            exchange
+        };
+        contact.continue_exchange(fight(5));
+        assert!(contact.can_flee());
+        assert!(hostile_contact_text(&contact.pending().unwrap()).contains("FIGHT ○··"));
+        contact.escape_failed();
+        assert_eq!(
+            contact
+                .pending()
+                .unwrap()
+                .exchange
+                .unwrap()
+                .rounds()
+                .count(),
+            1
+        );
+
+        contact.continue_exchange(fight(15));
+        assert!(!contact.can_flee());
+        assert!(!hostile_contact_text(&contact.pending().unwrap()).contains("H: Flee"));
+    }
+
+    #[test]
+    fn trade_board_lists_exclusive_offers_and_hides_hostile_factions() {
+        let player = Player::create_new_character("Vex".to_string(), Position3D::origin()).unwrap();
+        let base = Base::new(
+            EntityId::generate(),
+            "Outpost".to_string(),
+            Position3D::origin(),
+        )
+        .unwrap();
+        let mut session = RpgGameSession::new(player, base);
+        assert_eq!(trade_board_text(&session, 0).matches(" for ").count(), 3);
+
+        session
+            .reputation
+            .adjust(Faction::ColonialAuthority, 80, 1.0);
+        let text = trade_board_text(&session, 0);
+        assert!(text.contains("Colonial Authority ★"));
+        assert!(!text.contains("Scavenger Guild"));
+        assert!(text.contains("3: Free Traders"));
+    }
+}
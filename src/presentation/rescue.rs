@@ -0,0 +1,282 @@
+//! Rescue Missions - Distress signals, their countdown and resolution
+//!
+//! Every step on the surface may pick up a distress signal. An open signal
+//! is shown as a pulsing beacon on its objective tile and as a HUD line with
+//! the distance and the rests left. Each night of rest counts the signal
+//! down; reaching the objective first resolves the rescue with a Charisma
+//! or Strength check. Signals live for the session only.
+
+use crate::domain::constants::{CRITICAL_TEXT, WARNING_TEXT};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{ReputationCause, RescueOutcome, TimedObjective};
+use crate::domain::value_objects::{Position3D, ResourceCollection, StatType};
+use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource, PlayerResource};
+use crate::infrastructure::random::streams::{RngStreams, ENCOUNTERS};
+use crate::presentation::camera_hints::CameraHintRequest;
+use crate::presentation::game_state::RpgGameSession;
+use crate::presentation::reputation::{shift_reputation, ReputationChangedEvent};
+use crate::presentation::world_tick::{TickCursor, TickPhase, WorldTick, WorldTickSet};
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+use rand::Rng;
+
+/// Plugin for distress signals and rescue objectives
+pub struct RescuePlugin;
+
+impl Plugin for RescuePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TimedObjective>()
+            .init_resource::<RngStreams>()
+            .add_event::<CameraHintRequest>()
+            .add_systems(Startup, setup_rescue_hud)
+            .add_systems(
+                Update,
+                (
+                    rescue_countdown_system.in_set(WorldTickSet::Objectives),
+                    rescue_mission_system.in_set(WorldTickSet::Objectives),
+                    update_rescue_hud,
+                    update_rescue_marker,
+                    pulse_rescue_marker,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Marker for the distress signal HUD line
+#[derive(Component)]
+pub struct RescueHudText;
+
+/// Marker for the beacon on the rescue objective
+#[derive(Component)]
+pub struct RescueBeacon;
+
+fn setup_rescue_hud(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: FontSize::Small.to_pixels(),
+            ..default()
+        },
+        TextColor(WARNING_TEXT),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(15.0),
+            bottom: Val::Px(40.0),
+            ..default()
+        },
+        RescueHudText,
+        Name::new("RescueHud"),
+    ));
+}
+
+/// Count the open signal down once per night of rest
+fn rescue_countdown_system(
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    mut objective: ResMut<TimedObjective>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    for _ in cursor.take(ticks.read(), TickPhase::AfterRest) {
+        if objective.tick_rest().is_some() {
+            game_log.log_message(
+                "📡 The distress signal falls silent. Whoever sent it is beyond help now"
+                    .to_string(),
+                GameLogType::Narrative,
+            );
+            game_log.log_message(
+                "⚠️ The region feels more dangerous than before".to_string(),
+                GameLogType::Warning,
+            );
+        }
+    }
+}
+
+/// Pick up new signals and resolve the open one on arrival, once per move
+#[allow(clippy::too_many_arguments)]
+fn rescue_mission_system(
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    current_state: Res<State<RpgAppState>>,
+    map_resource: Res<MapResource>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut game_stats: ResMut<GameStatsResource>,
+    mut objective: ResMut<TimedObjective>,
+    mut game_log: ResMut<GameLogService>,
+    mut camera_hints: EventWriter<CameraHintRequest>,
+    mut session: ResMut<RpgGameSession>,
+    mut reputation_events: EventWriter<ReputationChangedEvent>,
+    rng_streams: ResMut<RngStreams>,
+) {
+    // Only the latest move matters; the player stands where it ended
+    let Some(position) = cursor
+        .take(ticks.read(), TickPhase::AfterPlayerMove)
+        .last()
+        .and_then(|tick| tick.position)
+    else {
+        return;
+    };
+    // Signals point at surface tiles; ruins have their own coordinates
+    if *current_state.get() != RpgAppState::Exploration || map_resource.is_in_interior() {
+        return;
+    }
+
+    let mut rng = rng_streams.stream(ENCOUNTERS);
+    if objective.active().is_some() {
+        let Some(player) = player_resource.get_player() else {
+            return;
+        };
+        let charisma = player.get_stat_modifier(StatType::Charisma);
+        let strength = player.get_stat_modifier(StatType::Strength);
+        if let Some(outcome) =
+            objective.try_resolve(position, rng.gen_range(1..=20), charisma, strength)
+        {
+            apply_rescue_outcome(&outcome, &mut player_resource, &mut game_stats);
+            game_log.log_message(
+                format!("🆘 {}", outcome.summary()),
+                if outcome.success {
+                    GameLogType::Discovery
+                } else {
+                    GameLogType::Event
+                },
+            );
+            // The Colonial Authority credits rescues that bring the cargo home
+            if outcome.success {
+                game_stats.record_quest_completion();
+                shift_reputation(
+                    &mut session,
+                    ReputationCause::QuestCompleted,
+                    &mut reputation_events,
+                );
+            }
+        }
+        return;
+    }
+
+    let Some(map) = map_resource.overworld() else {
+        return;
+    };
+    if let Some(signal) =
+        objective.try_raise_signal(map, position, rng.gen_range(1..=100), rng.gen())
+    {
+        game_log.log_message(
+            format!(
+                "📡 Distress signal received! Survivors {} tiles away can hold out for {} nights",
+                signal.distance_from(position),
+                signal.rests_remaining
+            ),
+            GameLogType::Event,
+        );
+        camera_hints.write(CameraHintRequest {
+            position: signal.target,
+            label: "Distress signal".to_string(),
+        });
+    }
+}
+
+/// Pay out the rescue reward
+fn apply_rescue_outcome(
+    outcome: &RescueOutcome,
+    player_resource: &mut PlayerResource,
+    game_stats: &mut GameStatsResource,
+) {
+    if let Err(e) = player_resource.grant_experience(outcome.experience) {
+        warn!("Failed to grant rescue experience: {:?}", e);
+        return;
+    }
+    game_stats.record_experience_gain(outcome.experience);
+
+    let mut reward = ResourceCollection::new();
+    for &(resource_type, amount) in &outcome.resources {
+        reward.set_amount(resource_type, amount);
+        game_stats.record_resource_gather(resource_type, amount);
+    }
+    player_resource.add_resources(&reward);
+}
+
+/// Show the distance to the objective and the rests left
+fn update_rescue_hud(
+    objective: Res<TimedObjective>,
+    player_resource: Res<PlayerResource>,
+    map_resource: Res<MapResource>,
+    mut hud_query: Query<(&mut Text, &mut TextColor), With<RescueHudText>>,
+) {
+    let Ok((mut text, mut color)) = hud_query.single_mut() else {
+        return;
+    };
+
+    let (line, line_color) = match (objective.active(), player_resource.player_position()) {
+        (Some(signal), _) if map_resource.is_in_interior() => (
+            format!("DISTRESS SIGNAL: {} rests left", signal.rests_remaining),
+            WARNING_TEXT,
+        ),
+        (Some(signal), Some(position)) => (
+            format!(
+                "DISTRESS SIGNAL: {} tiles, {} rests left",
+                signal.distance_from(position),
+                signal.rests_remaining
+            ),
+            if signal.rests_remaining <= 1 {
+                CRITICAL_TEXT
+            } else {
+                WARNING_TEXT
+            },
+        ),
+        _ => (String::new(), WARNING_TEXT),
+    };
+
+    if **text != line {
+        **text = line;
+    }
+    if color.0 != line_color {
+        color.0 = line_color;
+    }
+}
+
+/// Keep the beacon on the open signal's objective
+fn update_rescue_marker(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    objective: Res<TimedObjective>,
+    map_resource: Res<MapResource>,
+    beacons: Query<Entity, With<RescueBeacon>>,
+    mut rendered: Local<Option<Position3D>>,
+) {
+    let wanted = objective
+        .active()
+        .map(|signal| signal.target)
+        .filter(|_| !map_resource.is_in_interior());
+    if *rendered == wanted {
+        return;
+    }
+
+    for entity in beacons.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    if let Some(target) = wanted {
+        let world = crate::presentation::movement::tile_to_world_position(target);
+        commands.spawn((
+            Mesh3d(meshes.add(Mesh::from(Sphere::new(0.3)))),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: CRITICAL_TEXT,
+                emissive: LinearRgba::from(CRITICAL_TEXT) * 0.8,
+                ..default()
+            })),
+            Transform::from_translation(Vec3::new(world.x, 1.4, world.z)),
+            RescueBeacon,
+            Name::new("RescueBeacon"),
+        ));
+    }
+    *rendered = wanted;
+}
+
+/// Pulse the beacon so it stands out from waypoint markers
+fn pulse_rescue_marker(time: Res<Time>, mut beacons: Query<&mut Transform, With<RescueBeacon>>) {
+    let scale = 1.0 + 0.35 * (time.elapsed_secs() * 4.0).sin();
+    for mut transform in beacons.iter_mut() {
+        transform.scale = Vec3::splat(scale);
+    }
+}
@@ -0,0 +1,744 @@
+//! Run End - Hardcore runs, defeat follow-ups and abandoning a run
+//!
+//! H on the main menu cycles the next run through normal, hardcore and
+//! relaxed; the choice is a setting, and a run keeps the mode it was started
+//! in. Relaxed runs may take back their last move. Normal and relaxed runs
+//! carry an Emergency Recall; E gives hardcore runs one too, at the cost of
+//! part of their score. Every rest autosaves the run and its charted world
+//! on native builds, hardcore included, so a crash never costs more than a
+//! day. On defeat the
+//! summary screen follows the mode: a hardcore defeat buries the save slot
+//! at once, so its autosave can no longer be loaded, and deletes the file
+//! once the summary is dismissed; a normal defeat offers to reload the last
+//! autosave. A paused run can be abandoned with A after a confirmation.
+//! Both ways a run ends are counted in the player profile.
+
+use crate::domain::constants::{
+    CRITICAL_TEXT, HANDOVER_BACKGROUND, PRIMARY_TEXT, SECONDARY_TEXT, WARNING_TEXT,
+};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{
+    DefeatFollowUp, EmergencyRecall, ModifierStack, MoveUndo, RunEnd, RunMode, ScenarioTable,
+    Tombstones,
+};
+use crate::infrastructure::bevy::resources::{
+    BaseResource, GameStatsResource, GameTimerResource, MapResource, PlayerResource,
+};
+use crate::infrastructure::profile::ProfileStore;
+use crate::infrastructure::saves::{
+    load_save_slot, write_save, LoadedSave, SaveData, SaveLoadError, WorldSave, SAVE_FILE_PATH,
+};
+use crate::infrastructure::settings::RunSettings;
+use crate::presentation::audio_integration::PendingAudioRestore;
+use crate::presentation::game_state::{RpgAppState, RpgGameSession};
+use crate::presentation::map_renderer::RenderState;
+use crate::presentation::share_code::SharePrompt;
+use crate::presentation::world_tick::{TickCursor, TickPhase, WorldTick, WorldTickSet};
+use bevy::prelude::*;
+use std::path::Path;
+
+/// Key that switches the next run between normal, hardcore and relaxed
+pub const HARDCORE_KEY: KeyCode = KeyCode::KeyH;
+
+/// Key that gives the next hardcore run an Emergency Recall, or takes it away
+pub const RECALL_KEY: KeyCode = KeyCode::KeyE;
+
+/// Key that switches Fortune's Favor for the next run
+pub const FORTUNE_KEY: KeyCode = KeyCode::KeyF;
+
+/// Key that asks to abandon the run from the pause screen
+pub const ABANDON_KEY: KeyCode = KeyCode::KeyA;
+
+/// Key that reloads the last autosave after a normal defeat
+pub const RELOAD_KEY: KeyCode = KeyCode::KeyR;
+
+/// Plugin for run modes and the ways a run ends
+pub struct RunEndPlugin;
+
+impl Plugin for RunEndPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunSettings>()
+            .init_resource::<ProfileStore>()
+            .init_resource::<ActiveRun>()
+            .init_resource::<PendingAudioRestore>()
+            .add_systems(Startup, setup_run_panel)
+            .add_systems(OnEnter(RpgAppState::Exploration), start_run_system)
+            .add_systems(OnEnter(RpgAppState::GameOver), defeat_system)
+            .add_systems(Update, autosave_system.in_set(WorldTickSet::Objectives))
+            .add_systems(
+                Update,
+                (
+                    hardcore_toggle_system,
+                    abandon_run_system,
+                    defeat_input_system,
+                    update_run_panel_system,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Question or screen the run is showing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RunPrompt {
+    #[default]
+    Hidden,
+    /// Abandon the run? Y/N
+    ConfirmAbandon,
+    /// Defeat summary with what happens to the saves
+    Defeat(DefeatFollowUp),
+    /// The hardcore save was deleted
+    SaveDeleted,
+}
+
+/// The run being played, if any, and how it ended
+#[derive(Resource, Debug, Default)]
+pub struct ActiveRun {
+    mode: Option<RunMode>,
+    ended: bool,
+    autosaved: bool,
+    prompt: RunPrompt,
+}
+
+impl ActiveRun {
+    /// Mode of the run in progress
+    pub fn mode(&self) -> Option<RunMode> {
+        self.mode
+    }
+
+    /// Check if the last run ended and no new one was started
+    pub fn has_ended(&self) -> bool {
+        self.ended
+    }
+
+    /// Check if a question or screen is waiting for the player
+    pub fn is_prompting(&self) -> bool {
+        self.prompt != RunPrompt::Hidden
+    }
+
+    /// What the run is showing
+    pub fn prompt(&self) -> RunPrompt {
+        self.prompt
+    }
+
+    /// Start a run in `mode`
+    pub fn start(&mut self, mode: RunMode) {
+        *self = Self {
+            mode: Some(mode),
+            ..Self::default()
+        };
+    }
+
+    /// Pick a saved run back up in `mode`; its save is there to reload on defeat
+    pub fn resume(&mut self, mode: RunMode) {
+        self.start(mode);
+        self.autosaved = true;
+    }
+
+    /// End the run in progress, returning the mode it was played in
+    fn end(&mut self) -> Option<RunMode> {
+        let mode = self.mode.take()?;
+        self.ended = true;
+        Some(mode)
+    }
+}
+
+#[derive(Component)]
+struct RunPanel;
+
+#[derive(Component)]
+struct RunPanelText;
+
+/// A saved run read back, ready to be played on
+pub struct SavedRun {
+    pub session: RpgGameSession,
+    /// Charted world and run counters, missing from older saves
+    pub world: Option<WorldSave>,
+}
+
+impl SavedRun {
+    /// Rebuild the run of a loaded save
+    pub fn from_loaded(loaded: LoadedSave) -> Result<Self, SaveLoadError> {
+        let world = loaded.data.world.clone();
+        Ok(Self {
+            session: loaded.data.into_session()?,
+            world,
+        })
+    }
+}
+
+/// The session with the player and base as they are in play
+pub fn current_session(
+    session: &RpgGameSession,
+    player_resource: &PlayerResource,
+    base_resource: &BaseResource,
+) -> Option<RpgGameSession> {
+    let (Some(player), Some(base)) = (player_resource.player(), base_resource.base()) else {
+        return None;
+    };
+    let mut snapshot = session.clone();
+    snapshot.player = player.clone();
+    snapshot.base = base.clone();
+    Some(snapshot)
+}
+
+/// Write the run as the autosave at `path`
+pub fn autosave(
+    path: &Path,
+    session: &RpgGameSession,
+    world: Option<WorldSave>,
+) -> Result<(), String> {
+    write_save(path, &SaveData::from_session(session).with_world(world)).map(|_| ())
+}
+
+/// Read back the autosave at `path`, unless a hardcore defeat buried it
+pub fn reload_autosave(path: &Path, tombstones: &Tombstones) -> Result<SavedRun, SaveLoadError> {
+    SavedRun::from_loaded(load_save_slot(path, tombstones)?)
+}
+
+/// Put a saved run back in play
+///
+/// The player and base move into their resources and the soundtrack is
+/// queued to pick up where it was. A saved world replaces the one open and
+/// is drawn again from scratch; older saves play on in the open world.
+pub fn restore_run(
+    saved: SavedRun,
+    session: &mut RpgGameSession,
+    player_resource: &mut PlayerResource,
+    base_resource: &mut BaseResource,
+    audio_restore: &mut PendingAudioRestore,
+    (map_resource, game_stats, game_timer, render_state): (
+        &mut MapResource,
+        &mut GameStatsResource,
+        &mut GameTimerResource,
+        Option<&mut RenderState>,
+    ),
+) {
+    let SavedRun {
+        session: restored,
+        world,
+    } = saved;
+    if let Some(player) = player_resource.player_mut() {
+        *player = restored.player.clone();
+    }
+    if let Some(base) = base_resource.base_mut() {
+        *base = restored.base.clone();
+    }
+    if let Some(memory) = restored.audio_memory {
+        audio_restore.queue(memory);
+    }
+    if let Some(world) = world {
+        game_timer.game_time = world.restore(map_resource, game_stats);
+        if let Some(render_state) = render_state {
+            render_state.last_player_position = None;
+        }
+    }
+    game_stats.modifiers =
+        ModifierStack::new(game_stats.modifiers.difficulty(), restored.mutators.clone());
+    game_stats.score_percent = restored.emergency_recall.score_percent();
+    *session = restored;
+}
+
+fn setup_run_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(25.0),
+                top: Val::Percent(30.0),
+                width: Val::Percent(50.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(Val::Px(24.0)),
+                ..default()
+            },
+            BackgroundColor(HANDOVER_BACKGROUND),
+            GlobalZIndex(14),
+            Visibility::Hidden,
+            RunPanel,
+            Name::new("RunPanel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Regular.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                TextLayout::new_with_justify(JustifyText::Center),
+                RunPanelText,
+            ));
+        });
+}
+
+/// Start a run in the chosen mode when exploration begins without one
+fn start_run_system(
+    (settings, share_prompt): (Res<RunSettings>, Option<Res<SharePrompt>>),
+    mut run: ResMut<ActiveRun>,
+    mut profile: ResMut<ProfileStore>,
+    mut session: ResMut<RpgGameSession>,
+    mut game_stats: ResMut<GameStatsResource>,
+    mut game_log: ResMut<GameLogService>,
+    move_undo: Option<ResMut<MoveUndo>>,
+) {
+    if run.mode().is_some() {
+        return;
+    }
+    let mode = settings.mode();
+    run.start(mode);
+    if let Some(mut move_undo) = move_undo {
+        *move_undo = MoveUndo::default();
+    }
+    profile.record_start(mode);
+    session.emergency_recall = EmergencyRecall::for_run(mode, settings.hardcore_recall);
+    game_stats.score_percent = session.emergency_recall.score_percent();
+    let daily = share_prompt.is_some_and(|prompt| prompt.is_daily_run());
+    session.fortune_favor = settings.fortune_favor(daily);
+    // The new run writes its autosaves to the slot again
+    profile.lift(SAVE_FILE_PATH);
+    if mode == RunMode::Relaxed {
+        game_log.log_message(
+            "↩️ Relaxed run: Ctrl+Z takes back the last move".to_string(),
+            GameLogType::System,
+        );
+    }
+    if session.fortune_favor.is_enabled() {
+        game_log.log_message(
+            "🍀 Fortune's Favor on: long streaks of bad luck are softened".to_string(),
+            GameLogType::System,
+        );
+    } else if daily && settings.fortune_favor {
+        game_log.log_message(
+            "🍀 Fortune's Favor is off in daily runs".to_string(),
+            GameLogType::System,
+        );
+    }
+    if mode == RunMode::Hardcore {
+        game_log.log_message(
+            "💀 Hardcore run: defeat deletes the save".to_string(),
+            GameLogType::System,
+        );
+        if session.emergency_recall.is_enabled() {
+            game_log.log_message(
+                format!(
+                    "🚨 Emergency Recall on: the run keeps {}% of its score",
+                    session.emergency_recall.score_percent()
+                ),
+                GameLogType::System,
+            );
+        }
+    }
+}
+
+/// Autosave after every rest for crash protection
+fn autosave_system(
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    mut run: ResMut<ActiveRun>,
+    session: Res<RpgGameSession>,
+    player_resource: Res<PlayerResource>,
+    base_resource: Res<BaseResource>,
+    (map_resource, game_stats, game_timer): (
+        Res<MapResource>,
+        Res<GameStatsResource>,
+        Res<GameTimerResource>,
+    ),
+) {
+    if cursor.take(ticks.read(), TickPhase::AfterRest).is_empty() {
+        return;
+    }
+    if cfg!(target_arch = "wasm32") || run.mode().is_none() {
+        return;
+    }
+    let Some(snapshot) = current_session(&session, &player_resource, &base_resource) else {
+        return;
+    };
+    let world = WorldSave::capture(&map_resource, &game_stats, game_timer.game_time);
+    match autosave(Path::new(SAVE_FILE_PATH), &snapshot, world) {
+        Ok(()) => run.autosaved = true,
+        Err(e) => warn!("💾 Autosave failed: {}", e),
+    }
+}
+
+/// Count the defeat and decide what happens to the saves
+fn defeat_system(
+    mut run: ResMut<ActiveRun>,
+    mut profile: ResMut<ProfileStore>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    let has_autosave = run.autosaved;
+    let Some(mode) = run.end() else {
+        return;
+    };
+    profile.record_end(RunEnd::Defeated);
+    let follow_up = DefeatFollowUp::after_defeat(mode, has_autosave);
+    if follow_up == DefeatFollowUp::DeleteSave {
+        // Buried before the file goes, so a crash cannot bring it back
+        profile.bury(SAVE_FILE_PATH);
+    }
+    run.prompt = RunPrompt::Defeat(follow_up);
+    game_log.log_message(format!("☠️ {} run lost", mode.name()), GameLogType::Event);
+}
+
+/// Pick normal, hardcore or relaxed, the hardcore recall and Fortune's
+/// Favor for the next run from the main menu
+fn hardcore_toggle_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    run: Res<ActiveRun>,
+    mut settings: ResMut<RunSettings>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    if *state.get() != RpgAppState::MainMenu || run.mode().is_some() {
+        return;
+    }
+    if keyboard.just_pressed(HARDCORE_KEY) {
+        settings.cycle_mode();
+        game_log.log_message(
+            format!("💀 Next run: {}", settings.mode().name()),
+            GameLogType::System,
+        );
+    }
+    if keyboard.just_pressed(RECALL_KEY) && settings.hardcore {
+        settings.hardcore_recall = !settings.hardcore_recall;
+        game_log.log_message(
+            format!(
+                "🚨 Hardcore Emergency Recall: {}",
+                if settings.hardcore_recall {
+                    "on"
+                } else {
+                    "off"
+                }
+            ),
+            GameLogType::System,
+        );
+    }
+    if keyboard.just_pressed(FORTUNE_KEY) {
+        settings.cycle_fortune_favor();
+        game_log.log_message(
+            format!("🍀 Fortune's Favor: {}", fortune_label(&settings)),
+            GameLogType::System,
+        );
+    }
+}
+
+/// How Fortune's Favor is set for the next run
+fn fortune_label(settings: &RunSettings) -> &'static str {
+    match (settings.fortune_favor, settings.show_assist) {
+        (false, _) => "off",
+        (true, false) => "on",
+        (true, true) => "on, labelled",
+    }
+}
+
+/// Ask to abandon the run while paused, and end it once confirmed
+fn abandon_run_system(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    mut next_state: ResMut<NextState<RpgAppState>>,
+    mut run: ResMut<ActiveRun>,
+    mut profile: ResMut<ProfileStore>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    if *state.get() != RpgAppState::Paused {
+        if run.prompt == RunPrompt::ConfirmAbandon {
+            run.prompt = RunPrompt::Hidden;
+        }
+        return;
+    }
+    match run.prompt {
+        RunPrompt::Hidden if keyboard.just_pressed(ABANDON_KEY) && run.mode().is_some() => {
+            run.prompt = RunPrompt::ConfirmAbandon;
+        }
+        RunPrompt::ConfirmAbandon if keyboard.just_pressed(KeyCode::KeyY) => {
+            if let Some(mode) = run.end() {
+                profile.record_end(RunEnd::Abandoned);
+                game_log.log_message(
+                    format!("🏳️ {} run abandoned", mode.name()),
+                    GameLogType::System,
+                );
+            }
+            run.prompt = RunPrompt::Hidden;
+            next_state.set(RpgAppState::MainMenu);
+        }
+        RunPrompt::ConfirmAbandon
+            if keyboard.just_pressed(KeyCode::KeyN) || keyboard.just_pressed(KeyCode::Escape) =>
+        {
+            // Escape only closes the question, it does not resume too
+            keyboard.clear_just_pressed(KeyCode::Escape);
+            run.prompt = RunPrompt::Hidden;
+        }
+        _ => {}
+    }
+}
+
+/// Follow up a defeat: delete the hardcore save, or reload the autosave
+#[allow(clippy::too_many_arguments)]
+fn defeat_input_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    mut next_state: ResMut<NextState<RpgAppState>>,
+    mut run: ResMut<ActiveRun>,
+    settings: Res<RunSettings>,
+    profile: Res<ProfileStore>,
+    mut session: ResMut<RpgGameSession>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut base_resource: ResMut<BaseResource>,
+    mut game_log: ResMut<GameLogService>,
+    mut audio_restore: ResMut<PendingAudioRestore>,
+    (mut map_resource, mut game_stats, mut game_timer, mut render_state): (
+        ResMut<MapResource>,
+        ResMut<GameStatsResource>,
+        ResMut<GameTimerResource>,
+        Option<ResMut<RenderState>>,
+    ),
+) {
+    if *state.get() != RpgAppState::GameOver {
+        return;
+    }
+    let confirm = keyboard.just_pressed(KeyCode::Enter);
+    match run.prompt {
+        RunPrompt::Defeat(DefeatFollowUp::DeleteSave) if confirm => {
+            match std::fs::remove_file(SAVE_FILE_PATH) {
+                Ok(()) => info!("💾 Hardcore save deleted"),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => warn!("💾 Failed to delete the hardcore save: {}", e),
+            }
+            run.prompt = RunPrompt::SaveDeleted;
+        }
+        RunPrompt::Defeat(DefeatFollowUp::OfferReload) if keyboard.just_pressed(RELOAD_KEY) => {
+            match reload_autosave(Path::new(SAVE_FILE_PATH), profile.tombstones()) {
+                Ok(saved) => {
+                    restore_run(
+                        saved,
+                        &mut session,
+                        &mut player_resource,
+                        &mut base_resource,
+                        &mut audio_restore,
+                        (
+                            &mut map_resource,
+                            &mut game_stats,
+                            &mut game_timer,
+                            render_state.as_deref_mut(),
+                        ),
+                    );
+                    // The reloaded run carries on where it was saved, in the
+                    // mode it was started in; the menu cannot change it meanwhile
+                    run.resume(settings.mode());
+                    game_log
+                        .log_message("💾 Last autosave reloaded".to_string(), GameLogType::System);
+                    next_state.set(RpgAppState::Exploration);
+                }
+                Err(e) => {
+                    game_log.log_message(format!("💾 {}", e), GameLogType::System);
+                    run.prompt = RunPrompt::Defeat(DefeatFollowUp::SummaryOnly);
+                }
+            }
+        }
+        RunPrompt::Defeat(_) | RunPrompt::SaveDeleted if confirm => {
+            run.prompt = RunPrompt::Hidden;
+            next_state.set(RpgAppState::MainMenu);
+        }
+        _ => {}
+    }
+}
+
+/// Show the pause options, the abandon question or the defeat summary
+#[allow(clippy::too_many_arguments)]
+fn update_run_panel_system(
+    state: Res<State<RpgAppState>>,
+    run: Res<ActiveRun>,
+    settings: Res<RunSettings>,
+    profile: Res<ProfileStore>,
+    game_stats: Res<GameStatsResource>,
+    session: Option<Res<RpgGameSession>>,
+    scenarios: Option<Res<ScenarioTable>>,
+    mut panels: Query<&mut Visibility, With<RunPanel>>,
+    mut texts: Query<(&mut Text, &mut TextColor), With<RunPanelText>>,
+) {
+    let shown = match (state.get(), run.prompt) {
+        (RpgAppState::Paused, RunPrompt::Hidden) if run.mode().is_some() => Some((
+            "PAUSED\n\nEsc to resume - A to abandon the run - X to copy a share code".to_string(),
+            PRIMARY_TEXT,
+        )),
+        (RpgAppState::Paused, RunPrompt::ConfirmAbandon) => Some((
+            "ABANDON THIS RUN?\n\nIt counts as abandoned in your profile.\n\nY to abandon - N to keep playing".to_string(),
+            WARNING_TEXT,
+        )),
+        (RpgAppState::GameOver, RunPrompt::Defeat(follow_up)) => {
+            let heat = session
+                .as_ref()
+                .map(|session| session.heatmap.summary_lines().join("\n") + "\n")
+                .unwrap_or_default();
+            let start = game_stats
+                .scenario_summary()
+                .map(|line| line + "\n")
+                .unwrap_or_default();
+            let recall = session
+                .as_ref()
+                .map(|session| {
+                    session
+                        .emergency_recall
+                        .status_line(game_stats.current_day())
+                        + "\n"
+                })
+                .unwrap_or_default();
+            let undone = game_stats
+                .undo_summary()
+                .map(|line| line + "\n")
+                .unwrap_or_default();
+            let assisted = game_stats
+                .assist_summary()
+                .map(|line| line + "\n")
+                .unwrap_or_default();
+            let summary = format!(
+                "DEFEATED\n\nDay {} - {} pts\n{}{}{}{}{}{}\n\n",
+                game_stats.current_day(),
+                game_stats.run_score(),
+                start,
+                recall,
+                undone,
+                assisted,
+                heat,
+                profile.stats().summary()
+            );
+            let options = match follow_up {
+                DefeatFollowUp::DeleteSave => {
+                    "Hardcore: this run's save will be deleted.\nEnter to delete it"
+                }
+                DefeatFollowUp::OfferReload => "R to reload the last autosave - Enter for the menu",
+                DefeatFollowUp::SummaryOnly => "No autosave yet. Enter for the menu",
+            };
+            Some((summary + options, CRITICAL_TEXT))
+        }
+        (RpgAppState::GameOver, RunPrompt::SaveDeleted) => Some((
+            "Save deleted. The run is over.\n\nEnter for the menu".to_string(),
+            SECONDARY_TEXT,
+        )),
+        (RpgAppState::MainMenu, _) if run.has_ended() => Some((
+            format!(
+                "Next run: {}, {}, Fortune's Favor {} - H to switch, N for the start, F for the assist, Enter to start\n{}",
+                match (settings.mode(), settings.hardcore_recall) {
+                    (RunMode::Normal, _) => "Normal",
+                    (RunMode::Hardcore, false) => "Hardcore (E for a recall)",
+                    (RunMode::Hardcore, true) => "Hardcore with recall (E to drop it)",
+                    (RunMode::Relaxed, _) => "Relaxed (Ctrl+Z undoes a move)",
+                },
+                scenarios
+                    .map_or_else(ScenarioTable::default, |table| table.clone())
+                    .resolve(&settings.scenario)
+                    .name,
+                fortune_label(&settings),
+                profile.stats().summary()
+            ),
+            PRIMARY_TEXT,
+        )),
+        _ => None,
+    };
+
+    let wanted = if shown.is_some() {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    for mut visibility in panels.iter_mut() {
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+    let Some((line, color)) = shown else {
+        return;
+    };
+    for (mut text, mut text_color) in texts.iter_mut() {
+        if text.0 != line {
+            text.0 = line.clone();
+        }
+        if text_color.0 != color {
+            text_color.0 = color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Base, Player};
+    use crate::domain::value_objects::{EntityId, Position3D};
+    use bevy::state::app::StatesPlugin;
+
+    fn press(app: &mut App, key: KeyCode) {
+        let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.release_all();
+        keyboard.clear();
+        keyboard.press(key);
+        app.update();
+    }
+
+    #[test]
+    fn abandoning_returns_to_the_menu_and_counts_the_run() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_state(RpgAppState::Paused)
+            .insert_resource(ButtonInput::<KeyCode>::default())
+            .insert_resource(ProfileStore::in_memory())
+            .insert_resource(GameLogService::new())
+            .init_resource::<ActiveRun>()
+            .add_systems(Update, abandon_run_system);
+        app.world_mut()
+            .resource_mut::<ActiveRun>()
+            .start(RunMode::Normal);
+
+        press(&mut app, ABANDON_KEY);
+        assert_eq!(
+            app.world().resource::<ActiveRun>().prompt(),
+            RunPrompt::ConfirmAbandon
+        );
+        press(&mut app, KeyCode::KeyN);
+        assert!(!app.world().resource::<ActiveRun>().is_prompting());
+
+        press(&mut app, ABANDON_KEY);
+        press(&mut app, KeyCode::KeyY);
+        app.update();
+
+        assert_eq!(
+            *app.world().resource::<State<RpgAppState>>().get(),
+            RpgAppState::MainMenu
+        );
+        let run = app.world().resource::<ActiveRun>();
+        assert!(run.has_ended());
+        assert_eq!(run.mode(), None);
+        let stats = app.world().resource::<ProfileStore>().stats();
+        assert_eq!(stats.runs_abandoned, 1);
+        assert_eq!(stats.runs_finished, 0);
+    }
+
+    #[test]
+    fn normal_defeats_reload_the_last_autosave() {
+        let dir = std::env::temp_dir().join(format!("space-looter-run-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("savegame.json");
+        let player =
+            Player::create_new_character("Vex".to_string(), Position3D::new(4, 1, 0)).unwrap();
+        let base = Base::new(
+            EntityId::generate(),
+            "Outpost".to_string(),
+            Position3D::origin(),
+        )
+        .unwrap();
+        let mut session = RpgGameSession::new(player, base);
+        session.player.add_experience(250).unwrap();
+        autosave(&path, &session, None).unwrap();
+
+        assert_eq!(
+            DefeatFollowUp::after_defeat(RunMode::Normal, true),
+            DefeatFollowUp::OfferReload
+        );
+        let restored = reload_autosave(&path, &Tombstones::default())
+            .unwrap()
+            .session;
+        assert_eq!(restored.player.level(), session.player.level());
+        assert_eq!(restored.player.position(), &Position3D::new(4, 1, 0));
+    }
+}
@@ -0,0 +1,364 @@
+//! Scenarios - Picking the starting scenario and setting a run up with it
+//!
+//! N on the main menu steps through the scenarios of the `ScenarioTable`
+//! and sets the next run up again with the one picked, the way a pasted
+//! share code does; the choice is kept as a setting. Every new run goes
+//! through `set_up_run_start`: the spawn area is shaped, then the scenario
+//! is applied, then the run's mutators, so a mutator always has the last
+//! word. The random parts of a scenario are drawn from the world seed, so a
+//! shared run starts the same way for everyone.
+
+use crate::application::use_cases::StartScenarioUseCase;
+use crate::domain::constants::SECONDARY_TEXT;
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::hashing::fnv1a_64;
+use crate::domain::services::{
+    chart_start_area, ModifierStack, Scenario, ScenarioStart, ScenarioTable, WreckField,
+};
+use crate::domain::value_objects::{PlayerStats, Position3D};
+use crate::infrastructure::bevy::resources::{
+    BaseResource, GameStatsResource, MapResource, PlayerResource,
+};
+use crate::infrastructure::settings::RunSettings;
+use crate::infrastructure::DeterministicRng;
+use crate::presentation::base_visuals::{BaseChange, BaseChanged};
+use crate::presentation::game_state::{RpgAppState, RpgGameSession};
+use crate::presentation::map_renderer::RenderState;
+use crate::presentation::run_end::ActiveRun;
+use bevy::prelude::*;
+
+/// Key that picks the next starting scenario on the main menu
+pub const SCENARIO_KEY: KeyCode = KeyCode::KeyN;
+
+/// Plugin for starting scenarios
+pub struct ScenarioPlugin;
+
+impl Plugin for ScenarioPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScenarioTable>()
+            .init_resource::<StartingCharacter>()
+            .add_systems(Startup, setup_scenario_line)
+            .add_systems(
+                Update,
+                (scenario_select_system, update_scenario_line_system).chain(),
+            );
+    }
+}
+
+/// The character new runs start with, before their scenario
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct StartingCharacter {
+    pub name: String,
+    pub stats: PlayerStats,
+}
+
+impl Default for StartingCharacter {
+    fn default() -> Self {
+        Self {
+            name: "Space Looter".to_string(),
+            stats: PlayerStats {
+                strength: 12,
+                dexterity: 10,
+                intelligence: 8,
+                charisma: 14,
+                luck: 11,
+                endurance: 9,
+            },
+        }
+    }
+}
+
+/// Set a new run up: character, spawn area, scenario, base and mutators
+///
+/// The overworld must already be generated. Creates the character on the
+/// origin, shapes the spawn area, applies `scenario` with its random parts
+/// drawn from the world seed, founds the base where the scenario puts it
+/// and only then applies the mutators in `modifiers`. Wrecks left from an
+/// earlier set-up are cleared first, so picking another scenario on the
+/// menu does not leave the last one's behind.
+pub fn set_up_run_start(
+    character: &StartingCharacter,
+    scenario: &Scenario,
+    modifiers: &ModifierStack,
+    player_resource: &mut PlayerResource,
+    base_resource: &mut BaseResource,
+    map_resource: &mut MapResource,
+    session: &mut RpgGameSession,
+) -> Result<ScenarioStart, String> {
+    let spawn = Position3D::origin();
+    player_resource
+        .create_player(
+            "player_001".to_string(),
+            character.name.clone(),
+            spawn,
+            character.stats,
+        )
+        .map_err(|e| format!("failed to create the starting player: {}", e))?;
+    map_resource.prepare_start_area(spawn, 0);
+
+    let seed = map_resource.overworld().map_or(0, |map| map.seed());
+    let mut rng = DeterministicRng::new(seed ^ scenario_salt(&scenario.id));
+    let start = scenario.roll(spawn, &mut rng);
+    session.wrecks = WreckField::new();
+    if let (Some(player), Some(map)) = (
+        player_resource.player_mut(),
+        map_resource.overworld.as_mut(),
+    ) {
+        StartScenarioUseCase::new()
+            .execute(scenario, &start, player, map, &mut session.wrecks)
+            .map_err(|e| format!("failed to apply the {} scenario: {}", scenario.name, e))?;
+    }
+
+    base_resource
+        .create_base("Central Command".to_string(), start.base_position)
+        .map_err(|e| format!("failed to create the starting base: {}", e))?;
+    if let Some(base) = base_resource.base() {
+        session.base = base.clone();
+    }
+
+    // Mutators come last, on top of whatever the scenario changed
+    if let Some(player) = player_resource.player_mut() {
+        let max_movement = modifiers.max_movement(player.max_movement_points());
+        player.set_max_movement_points(max_movement);
+    }
+    player_resource.set_difficulty(modifiers.difficulty());
+    let reveal_radius = modifiers.start_reveal_radius();
+    if let Some(map) = map_resource.overworld.as_mut() {
+        if reveal_radius > 0 {
+            let charted = chart_start_area(map, spawn, reveal_radius);
+            info!(
+                "🗺️ Charted {} tiles within {} of the spawn",
+                charted, reveal_radius
+            );
+        }
+    }
+
+    session.scenario = scenario.id.clone();
+    Ok(start)
+}
+
+/// Salt mixed into the world seed so scenarios draw apart from each other
+fn scenario_salt(id: &str) -> u64 {
+    fnv1a_64(id.as_bytes())
+}
+
+#[derive(Component)]
+struct ScenarioLine;
+
+fn setup_scenario_line(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: FontSize::Regular.to_pixels(),
+            ..default()
+        },
+        TextColor(SECONDARY_TEXT),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Percent(10.0),
+            width: Val::Percent(80.0),
+            bottom: Val::Px(76.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        GlobalZIndex(14),
+        Visibility::Hidden,
+        ScenarioLine,
+        Name::new("ScenarioLine"),
+    ));
+}
+
+/// Pick the next scenario on N and set the next run up with it
+#[allow(clippy::too_many_arguments)]
+fn scenario_select_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    run: Res<ActiveRun>,
+    mut settings: ResMut<RunSettings>,
+    table: Res<ScenarioTable>,
+    character: Res<StartingCharacter>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut base_resource: ResMut<BaseResource>,
+    mut map_resource: ResMut<MapResource>,
+    mut game_stats: ResMut<GameStatsResource>,
+    mut session: ResMut<RpgGameSession>,
+    mut base_events: EventWriter<BaseChanged>,
+    render_state: Option<ResMut<RenderState>>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    if *state.get() != RpgAppState::MainMenu
+        || run.mode().is_some()
+        || !keyboard.just_pressed(SCENARIO_KEY)
+    {
+        return;
+    }
+    let scenario = table.next_after(&settings.scenario);
+    settings.scenario = scenario.id.clone();
+
+    // Start over from a fresh overworld of the same seed
+    let seed = map_resource.overworld().map_or(0, |map| map.seed());
+    map_resource.generate_overworld(Position3D::origin(), seed);
+    let modifiers = game_stats.modifiers.clone();
+    match set_up_run_start(
+        &character,
+        &scenario,
+        &modifiers,
+        &mut player_resource,
+        &mut base_resource,
+        &mut map_resource,
+        &mut session,
+    ) {
+        Ok(_) => {
+            base_events.write(BaseChanged::new(BaseChange::Founded));
+            game_stats.scenario = scenario.clone();
+        }
+        Err(e) => error!("{}", e),
+    }
+    if let Some(mut render_state) = render_state {
+        render_state.last_player_position = None;
+    }
+
+    game_log.log_message(
+        format!("🧭 Next run: {} - {}", scenario.name, scenario.description),
+        GameLogType::System,
+    );
+}
+
+/// Show the picked scenario on the main menu only
+fn update_scenario_line_system(
+    state: Res<State<RpgAppState>>,
+    settings: Res<RunSettings>,
+    table: Res<ScenarioTable>,
+    mut lines: Query<(&mut Text, &mut Visibility), With<ScenarioLine>>,
+) {
+    let Ok((mut text, mut visibility)) = lines.single_mut() else {
+        return;
+    };
+    let wanted = if *state.get() == RpgAppState::MainMenu {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    if *visibility != wanted {
+        *visibility = wanted;
+    }
+    if !settings.is_changed() && !state.is_changed() {
+        return;
+    }
+    let scenario = table.resolve(&settings.scenario);
+    text.0 = format!(
+        "Start: {} ({}) - N to change",
+        scenario.name, scenario.description
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::constants::{
+        CARTOGRAPHER_REVEAL_RADIUS, CRASH_SURVIVOR_BASE_DISTANCE, IRON_STOMACH_MOVEMENT_PENALTY,
+        VETERAN_SCOUT_REVEAL_RADIUS,
+    };
+    use crate::domain::entities::game::DifficultyLevel;
+    use crate::domain::entities::Base;
+    use crate::domain::services::{Mutator, Mutators, WreckOrigin};
+    use crate::domain::value_objects::{EntityId, TileCoordinate};
+
+    struct Run {
+        player: PlayerResource,
+        base: BaseResource,
+        map: MapResource,
+        session: RpgGameSession,
+    }
+
+    fn set_up(scenario: Scenario, mutators: Mutators) -> (Run, ScenarioStart) {
+        let character = StartingCharacter::default();
+        let mut run = Run {
+            player: PlayerResource::new(),
+            base: BaseResource::default(),
+            map: MapResource::new(),
+            session: RpgGameSession::new(
+                crate::domain::Player::create_new_character(
+                    character.name.clone(),
+                    Position3D::origin(),
+                )
+                .unwrap(),
+                Base::new(
+                    EntityId::generate(),
+                    "Dummy".to_string(),
+                    Position3D::origin(),
+                )
+                .unwrap(),
+            ),
+        };
+        run.map.generate_overworld(Position3D::origin(), 1948);
+        let modifiers = ModifierStack::new(DifficultyLevel::Normal, mutators);
+        let start = set_up_run_start(
+            &character,
+            &scenario,
+            &modifiers,
+            &mut run.player,
+            &mut run.base,
+            &mut run.map,
+            &mut run.session,
+        )
+        .unwrap();
+        (run, start)
+    }
+
+    #[test]
+    fn the_base_is_founded_where_the_scenario_puts_it() {
+        let (run, start) = set_up(Scenario::crash_survivor(), Mutators::default());
+        let base = run.base.base_position().unwrap();
+        assert_eq!(base, start.base_position);
+        assert_eq!(
+            base.manhattan_distance_2d(&Position3D::origin()),
+            CRASH_SURVIVOR_BASE_DISTANCE
+        );
+        // The session and its fight logic see the same base
+        assert_eq!(*run.session.base.position(), base);
+        assert_eq!(
+            WreckOrigin::of_fight(base, *run.session.base.position()),
+            WreckOrigin::Raid
+        );
+        assert_eq!(run.session.wrecks.wrecks().len(), 1);
+        assert_eq!(run.session.scenario, "crash_survivor");
+
+        // The same world always puts it in the same place
+        let (_, again) = set_up(Scenario::crash_survivor(), Mutators::default());
+        assert_eq!(again, start);
+        let (standard, _) = set_up(Scenario::standard_drop(), Mutators::default());
+        assert_eq!(standard.base.base_position(), Some(Position3D::origin()));
+    }
+
+    #[test]
+    fn mutators_apply_after_the_scenario() {
+        let (plain, _) = set_up(Scenario::veteran_scout(), Mutators::default());
+        let (run, _) = set_up(
+            Scenario::veteran_scout(),
+            Mutators::new([Mutator::IronStomach, Mutator::Cartographer]),
+        );
+        let leveled_max = plain.player.player().unwrap().max_movement_points();
+        let player = run.player.player().unwrap();
+        // Iron Stomach takes its points off the leveled-up maximum
+        assert_eq!(
+            player.max_movement_points(),
+            leveled_max - IRON_STOMACH_MOVEMENT_PENALTY as u8
+        );
+        assert!(player.movement_points() <= player.max_movement_points());
+
+        // Cartographer charts further than the scenario does
+        let charted = |run: &Run, x: i32| {
+            run.map
+                .overworld()
+                .and_then(|map| map.get_tile(&TileCoordinate::new(x, 0, 0)))
+                .is_some_and(|tile| tile.is_explored())
+        };
+        let beyond_scout = VETERAN_SCOUT_REVEAL_RADIUS as i32 + 1;
+        assert!(beyond_scout <= CARTOGRAPHER_REVEAL_RADIUS as i32);
+        assert!(!charted(&plain, beyond_scout));
+        assert!(charted(&run, beyond_scout));
+    }
+}
@@ -0,0 +1,522 @@
+//! Scout Probes - Launch panel, probe flight and point of interest beacons
+//!
+//! Pressing L during exploration opens a small panel asking for a heading.
+//! Choosing one spends a probe from the player's consumables right away and
+//! sends a marker flying tile by tile along the planned route. Each tile it
+//! reaches reveals its corridor slice on the overworld; resource nodes and
+//! ruins it passes are logged and get a beacon. Flights keep running while
+//! menus are open. Probes cannot be launched inside ruins or from inside an
+//! anomaly storm.
+
+use crate::domain::constants::{
+    ENERGY_COLOR, PANEL_BACKGROUND, PRIMARY_TEXT, RESOURCE_COLOR, SCOUT_PROBE_CORRIDOR_HALF_WIDTH,
+    SCOUT_PROBE_RANGE,
+};
+use crate::domain::entities::Map;
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{
+    poi_entry_id, probe_sightings, reveal_probe_slice, ConsumableKind, MapService, PointOfInterest,
+    ProbeFlight, ProbeRoute, ProbeSighting, ProbeStop, VisibilityService, WorldHazards,
+};
+use crate::domain::value_objects::position::Direction;
+use crate::domain::value_objects::{Position3D, TileCoordinate};
+use crate::domain::{DomainError, DomainResult};
+use crate::infrastructure::bevy::resources::{MapResource, PlayerResource};
+use crate::presentation::codex::CodexUnlockEvent;
+use crate::presentation::input::InputMapper;
+use crate::presentation::map_renderer::PlayerMarker;
+use crate::presentation::minimap::TilesRevealed;
+use crate::presentation::movement::{screen_direction, tile_to_world_position, SmoothMovement};
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+/// Height the probe marker flies at
+const PROBE_FLIGHT_HEIGHT: f32 = 1.6;
+
+/// Plugin for scout probe launches and flights
+pub struct ScoutProbePlugin;
+
+impl Plugin for ScoutProbePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScoutProbeLauncher>()
+            .init_resource::<InputMapper>()
+            .add_event::<TilesRevealed>()
+            .add_systems(Startup, setup_launch_panel)
+            .add_systems(
+                Update,
+                (
+                    probe_launch_input_system,
+                    probe_flight_system,
+                    update_launch_panel,
+                    hide_probes_in_interiors,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Launch panel state and the points of interest already marked
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ScoutProbeLauncher {
+    choosing: bool,
+    marked: HashSet<TileCoordinate>,
+}
+
+impl ScoutProbeLauncher {
+    /// Check if the launch panel is waiting for a heading
+    pub fn is_choosing(&self) -> bool {
+        self.choosing
+    }
+
+    /// Remember a point of interest; false if it already has a beacon
+    pub fn mark(&mut self, coordinate: TileCoordinate) -> bool {
+        self.marked.insert(coordinate)
+    }
+}
+
+/// A probe in flight
+#[derive(Component, Debug)]
+pub struct ScoutProbe {
+    pub flight: ProbeFlight,
+    /// Tiles explored by this probe so far
+    pub revealed: usize,
+}
+
+/// Beacon over a point of interest found by a probe
+#[derive(Component)]
+pub struct ProbeBeacon;
+
+/// Marker for the launch panel
+#[derive(Component)]
+pub struct ScoutProbePanel;
+
+/// Marker for the launch panel text
+#[derive(Component)]
+pub struct ScoutProbePanelText;
+
+/// Spend a probe and start its flight from the player's tile
+///
+/// The probe is consumed here, at launch; a flight cut short by terrain
+/// does not give it back. A heading with no room to fly is refused before
+/// anything is spent.
+pub fn launch_probe(
+    player_resource: &mut PlayerResource,
+    map: &Map,
+    heading: Direction,
+) -> DomainResult<ProbeFlight> {
+    let origin = player_resource
+        .player_position()
+        .ok_or_else(|| DomainError::PlayerError("No player exists".to_string()))?;
+    let route = ProbeRoute::plan(map, origin, heading);
+    if route.is_empty() {
+        let next = origin.move_direction(heading, 1);
+        return Err(DomainError::TileNotAccessible(next.x, next.y, next.z));
+    }
+    player_resource.use_consumable(ConsumableKind::ScoutProbe)?;
+    Ok(ProbeFlight::launch(route))
+}
+
+/// Tiles a probe could fly over or reveal towards `heading`
+fn corridor_area(origin: Position3D, heading: Direction) -> Vec<Position3D> {
+    let visibility = VisibilityService::new();
+    (1..=SCOUT_PROBE_RANGE)
+        .flat_map(|distance| {
+            visibility
+                .get_probe_corridor_coordinates(origin.move_direction(heading, distance), heading)
+        })
+        .map(Position3D::from)
+        .collect()
+}
+
+fn setup_launch_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(30.0),
+                top: Val::Px(120.0),
+                width: Val::Px(420.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(PANEL_BACKGROUND),
+            Visibility::Hidden,
+            ScoutProbePanel,
+            Name::new("ScoutProbePanel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Small.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                ScoutProbePanelText,
+            ));
+        });
+}
+
+/// Open the launch panel and launch on the chosen heading
+#[allow(clippy::too_many_arguments)]
+fn probe_launch_input_system(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_state: Res<State<RpgAppState>>,
+    mut launcher: ResMut<ScoutProbeLauncher>,
+    mut map_resource: ResMut<MapResource>,
+    mut player_resource: ResMut<PlayerResource>,
+    world_hazards: Option<Res<WorldHazards>>,
+    player_query: Query<&SmoothMovement, With<PlayerMarker>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut game_log: ResMut<GameLogService>,
+    input_mapper: Res<InputMapper>,
+) {
+    if *current_state.get() != RpgAppState::Exploration {
+        return;
+    }
+    let Some(origin) = player_resource.player_position() else {
+        return;
+    };
+
+    if !launcher.choosing {
+        if !keyboard.just_pressed(KeyCode::KeyL)
+            || player_query
+                .single()
+                .is_ok_and(|movement| movement.is_moving)
+        {
+            return;
+        }
+        let probes = player_resource
+            .get_player()
+            .map(|player| player.consumables().count(ConsumableKind::ScoutProbe))
+            .unwrap_or(0);
+        let refusal = if probes == 0 {
+            Some("🛰️ No scout probes left. Craft one from the inventory (C)")
+        } else if map_resource.is_in_interior() {
+            Some("🛰️ Probes need open sky; they cannot be launched inside the ruins")
+        } else if world_hazards.is_some_and(|hazards| hazards.storm_at(origin).is_some()) {
+            Some("🛰️ The anomaly storm would scramble a probe. Launch once you are clear of it")
+        } else {
+            None
+        };
+        match refusal {
+            Some(message) => game_log.log_message(message.to_string(), GameLogType::Warning),
+            None => launcher.choosing = true,
+        }
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyL) || keyboard.just_pressed(KeyCode::KeyX) {
+        launcher.choosing = false;
+        return;
+    }
+    // Same keys and screen directions as player movement
+    let Some(heading) = input_mapper
+        .just_pressed_movement(&keyboard)
+        .and_then(screen_direction)
+    else {
+        return;
+    };
+    launcher.choosing = false;
+
+    let Some(map) = map_resource.overworld.as_mut() else {
+        return;
+    };
+    // The probe flies further than tiles are generated around the player
+    if let Err(e) =
+        MapService::new(map.seed()).generate_tiles_at(map, corridor_area(origin, heading))
+    {
+        error!("Failed to generate tiles for a scout probe: {:?}", e);
+    }
+
+    match launch_probe(&mut player_resource, map, heading) {
+        Ok(flight) => {
+            let left = player_resource
+                .get_player()
+                .map(|player| player.consumables().count(ConsumableKind::ScoutProbe))
+                .unwrap_or(0);
+            game_log.log_message(
+                format!(
+                    "🛰️ Scout probe launched {} ({} left)",
+                    heading.to_string().to_lowercase(),
+                    left
+                ),
+                GameLogType::Movement,
+            );
+            let world = tile_to_world_position(origin);
+            commands.spawn((
+                Mesh3d(meshes.add(Mesh::from(Sphere::new(0.25)))),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: ENERGY_COLOR,
+                    emissive: LinearRgba::from(ENERGY_COLOR),
+                    ..default()
+                })),
+                Transform::from_translation(Vec3::new(world.x, PROBE_FLIGHT_HEIGHT, world.z)),
+                ScoutProbe {
+                    flight,
+                    revealed: 0,
+                },
+                Name::new("ScoutProbe"),
+            ));
+        }
+        Err(DomainError::TileNotAccessible(..)) => game_log.log_message(
+            format!(
+                "🛰️ No room to launch {}; the probe stays in its rack",
+                heading.to_string().to_lowercase()
+            ),
+            GameLogType::Warning,
+        ),
+        Err(e) => game_log.log_message(format!("🛰️ {}", e), GameLogType::Warning),
+    }
+}
+
+/// Fly every probe, reveal what it passes and land it at the end of its route
+///
+/// Runs in every app state so flights continue behind menus.
+#[allow(clippy::too_many_arguments)]
+fn probe_flight_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut probes: Query<(Entity, &mut ScoutProbe, &mut Transform)>,
+    mut map_resource: ResMut<MapResource>,
+    mut launcher: ResMut<ScoutProbeLauncher>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut game_log: ResMut<GameLogService>,
+    mut codex_unlocks: EventWriter<CodexUnlockEvent>,
+    mut reveals: EventWriter<TilesRevealed>,
+) {
+    if probes.is_empty() {
+        return;
+    }
+    let Some(map) = map_resource.overworld.as_mut() else {
+        return;
+    };
+    let visibility = VisibilityService::new();
+
+    for (entity, mut probe, mut transform) in probes.iter_mut() {
+        let heading = probe.flight.route().heading;
+        for position in probe.flight.advance(time.delta_secs()) {
+            let revealed = reveal_probe_slice(map, &visibility, position, heading);
+            probe.revealed += revealed.len();
+            if !revealed.is_empty() {
+                reveals.write(TilesRevealed(revealed));
+            }
+
+            let slice = visibility.get_probe_corridor_coordinates(position, heading);
+            for (coordinate, sighting) in probe_sightings(map, &slice) {
+                if !launcher.mark(coordinate) {
+                    continue;
+                }
+                let (message, color, poi) = match sighting {
+                    ProbeSighting::ResourceNode(resource_type) => (
+                        format!(
+                            "📡 Probe spotted a {} deposit at ({}, {})",
+                            resource_type, coordinate.x, coordinate.y
+                        ),
+                        RESOURCE_COLOR,
+                        PointOfInterest::Deposit,
+                    ),
+                    ProbeSighting::Ruins => (
+                        format!(
+                            "📡 Probe spotted ruins at ({}, {})",
+                            coordinate.x, coordinate.y
+                        ),
+                        ENERGY_COLOR,
+                        PointOfInterest::Ruins,
+                    ),
+                };
+                game_log.log_message(message, GameLogType::Discovery);
+                codex_unlocks.write(CodexUnlockEvent::new(poi_entry_id(poi)));
+                let world = tile_to_world_position(Position3D::from(coordinate));
+                commands.spawn((
+                    Mesh3d(meshes.add(Mesh::from(Cylinder::new(0.15, 1.6)))),
+                    MeshMaterial3d(materials.add(StandardMaterial {
+                        base_color: color,
+                        emissive: LinearRgba::from(color) * 0.5,
+                        ..default()
+                    })),
+                    Transform::from_translation(Vec3::new(world.x, 1.0, world.z)),
+                    ProbeBeacon,
+                    Name::new("ProbeBeacon"),
+                ));
+            }
+        }
+
+        let (from, to, progress) = probe.flight.segment();
+        let position = tile_to_world_position(from).lerp(tile_to_world_position(to), progress);
+        transform.translation = Vec3::new(position.x, PROBE_FLIGHT_HEIGHT, position.z);
+
+        if probe.flight.is_landed() {
+            let route = probe.flight.route();
+            let ending = match route.stop {
+                ProbeStop::MaxRange => "burned out at full range".to_string(),
+                ProbeStop::Impassable(terrain) => {
+                    format!("turned back at {}", terrain.to_string().to_lowercase())
+                }
+                ProbeStop::WorldEdge => "lost signal at the edge of the charted world".to_string(),
+            };
+            game_log.log_message(
+                format!(
+                    "🛰️ Probe {} after {} tiles, revealing {} new tiles",
+                    ending,
+                    route.path.len(),
+                    probe.revealed
+                ),
+                GameLogType::Discovery,
+            );
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Show the launch panel while it waits for a heading
+fn update_launch_panel(
+    launcher: Res<ScoutProbeLauncher>,
+    player_resource: Res<PlayerResource>,
+    mut panel_query: Query<&mut Visibility, With<ScoutProbePanel>>,
+    mut text_query: Query<&mut Text, With<ScoutProbePanelText>>,
+) {
+    let Ok(mut visibility) = panel_query.single_mut() else {
+        return;
+    };
+    let wanted = if launcher.is_choosing() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    if *visibility != wanted {
+        *visibility = wanted;
+    }
+    if !launcher.is_choosing() {
+        return;
+    }
+
+    let probes = player_resource
+        .get_player()
+        .map(|player| player.consumables().count(ConsumableKind::ScoutProbe))
+        .unwrap_or(0);
+    if let Ok(mut text) = text_query.single_mut() {
+        let content = format!(
+            "LAUNCH SCOUT PROBE ({} carried)\n\
+             Flies up to {} tiles and reveals a {}-wide corridor\n\
+             WASD / Arrows: Choose direction | L or X: Cancel",
+            probes,
+            SCOUT_PROBE_RANGE,
+            2 * SCOUT_PROBE_CORRIDOR_HALF_WIDTH + 1
+        );
+        if **text != content {
+            **text = content;
+        }
+    }
+}
+
+/// Overworld probes and beacons are not drawn over ruin interiors
+#[allow(clippy::type_complexity)]
+fn hide_probes_in_interiors(
+    map_resource: Res<MapResource>,
+    mut query: Query<&mut Visibility, Or<(With<ScoutProbe>, With<ProbeBeacon>)>>,
+) {
+    let wanted = if map_resource.is_in_interior() {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+    for mut visibility in query.iter_mut() {
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::MapTile;
+    use crate::domain::value_objects::terrain::{Elevation, TerrainType};
+    use crate::domain::value_objects::EntityId;
+    use crate::domain::PlayerStats;
+
+    fn player_with_probes(probes: u32) -> PlayerResource {
+        let mut player_resource = PlayerResource::new();
+        player_resource
+            .create_player(
+                "player".to_string(),
+                "Scout".to_string(),
+                Position3D::origin(),
+                PlayerStats::new(10, 10, 10, 10, 10, 10).unwrap(),
+            )
+            .unwrap();
+        player_resource
+            .add_consumables(ConsumableKind::ScoutProbe, probes)
+            .unwrap();
+        player_resource
+    }
+
+    fn probes_left(player_resource: &PlayerResource) -> u32 {
+        player_resource
+            .get_player()
+            .unwrap()
+            .consumables()
+            .count(ConsumableKind::ScoutProbe)
+    }
+
+    #[test]
+    fn probe_is_spent_on_launch_not_on_landing() {
+        let mut map = Map::new(EntityId::generate(), "Test".to_string(), 1).unwrap();
+        for y in -1..=3 {
+            map.set_tile(
+                TileCoordinate::new(0, y, 0),
+                MapTile::new(TerrainType::Plains, Elevation::sea_level(), false),
+            );
+        }
+        map.set_tile(
+            TileCoordinate::new(0, 4, 0),
+            MapTile::new(TerrainType::Ocean, Elevation::sea_level(), false),
+        );
+        let mut player_resource = player_with_probes(1);
+
+        let mut flight = launch_probe(&mut player_resource, &map, Direction::North).unwrap();
+        assert_eq!(probes_left(&player_resource), 0);
+        assert!(!flight.is_landed());
+
+        // Cut short by the ocean, and nothing comes back on landing
+        flight.advance(10.0);
+        assert!(flight.is_landed());
+        assert_eq!(
+            flight.route().stop,
+            ProbeStop::Impassable(TerrainType::Ocean)
+        );
+        assert_eq!(probes_left(&player_resource), 0);
+
+        // Without probes nothing launches
+        assert!(launch_probe(&mut player_resource, &map, Direction::North).is_err());
+    }
+
+    #[test]
+    fn blocked_heading_keeps_the_probe() {
+        let mut map = Map::new(EntityId::generate(), "Test".to_string(), 1).unwrap();
+        map.set_tile(
+            TileCoordinate::new(0, 0, 0),
+            MapTile::new(TerrainType::Plains, Elevation::sea_level(), true),
+        );
+        map.set_tile(
+            TileCoordinate::new(1, 0, 0),
+            MapTile::new(TerrainType::Ocean, Elevation::sea_level(), false),
+        );
+        let mut player_resource = player_with_probes(1);
+
+        for heading in [Direction::East, Direction::West] {
+            assert!(matches!(
+                launch_probe(&mut player_resource, &map, heading),
+                Err(DomainError::TileNotAccessible(..))
+            ));
+        }
+        assert_eq!(probes_left(&player_resource), 1);
+    }
+}
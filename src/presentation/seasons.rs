@@ -0,0 +1,215 @@
+//! Seasons - Keeping the season layer current and announcing changes
+//!
+//! The season of the current day is worked out from the world seed every
+//! frame and swapped into the run's modifier stack when it differs, which
+//! also covers loaded games and new runs. A rest that dawns on a new season
+//! announces it in the log with a short sting. Nights in a Flare may knock
+//! a piece of equipped gear back into the stash. The HUD shows the season
+//! and the days it has left.
+
+use crate::domain::constants::SECONDARY_TEXT;
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{season_change, season_days_left, GearSlot, Season};
+use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource, PlayerResource};
+use crate::infrastructure::random::streams::{RngStreams, REST};
+use crate::presentation::delayed_audio::{AudioKey, AudioStep, PlaySequenceExt};
+use crate::presentation::world_tick::{TickCursor, TickPhase, WorldTick, WorldTickSet};
+use bevy::prelude::*;
+use rand::Rng;
+
+/// Plugin for the seasons of the world
+pub struct SeasonPlugin;
+
+impl Plugin for SeasonPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RngStreams>()
+            .add_systems(Startup, setup_season_hud)
+            .add_systems(
+                Update,
+                (
+                    season_system.in_set(WorldTickSet::Hazards),
+                    update_season_hud,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Marker for the season HUD line
+#[derive(Component)]
+pub struct SeasonHudText;
+
+fn setup_season_hud(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: FontSize::Small.to_pixels(),
+            ..default()
+        },
+        TextColor(SECONDARY_TEXT),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(15.0),
+            bottom: Val::Px(115.0),
+            ..default()
+        },
+        SeasonHudText,
+        Name::new("SeasonHud"),
+    ));
+}
+
+/// Log line announcing the season `day` dawns on, if it starts one
+pub fn season_announcement(seed: u64, day: u32) -> Option<String> {
+    season_change(seed, day).map(|(ended, begun)| {
+        format!(
+            "{} The {} is over - a {} begins for {} days: {}",
+            begun.icon(),
+            ended.name(),
+            begun.name(),
+            season_days_left(day),
+            begun.effects()
+        )
+    })
+}
+
+/// Equipped slot a malfunction hits, if the d100 `roll` is under `chance`
+///
+/// `pick` chooses among the occupied slots.
+pub fn malfunctioning_slot(
+    equipped: &[GearSlot],
+    chance: u8,
+    roll: u8,
+    pick: usize,
+) -> Option<GearSlot> {
+    if equipped.is_empty() || roll > chance {
+        return None;
+    }
+    Some(equipped[pick % equipped.len()])
+}
+
+/// Keep the season layer current; announce new seasons and roll
+/// malfunctions once per rest
+#[allow(clippy::too_many_arguments)]
+fn season_system(
+    mut commands: Commands,
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    map_resource: Res<MapResource>,
+    mut game_stats: ResMut<GameStatsResource>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut game_log: ResMut<GameLogService>,
+    rng_streams: ResMut<RngStreams>,
+) {
+    let rests = cursor.take(ticks.read(), TickPhase::AfterRest);
+    let Some(seed) = map_resource.overworld().map(|map| map.seed()) else {
+        return;
+    };
+    let season = Season::on_day(seed, game_stats.current_day());
+    if game_stats.modifiers.season() != Some(season) {
+        game_stats.modifiers.set_season(season);
+    }
+
+    for tick in rests {
+        if let Some(announcement) = season_announcement(seed, tick.day) {
+            game_log.log_message(announcement, GameLogType::Narrative);
+            commands.play_sequence(&[
+                AudioStep::new(0.0, AudioKey::DiscoveryChime, 0.5),
+                AudioStep::new(0.4, AudioKey::RestComplete, 0.35),
+            ]);
+        }
+
+        let equipped: Vec<GearSlot> = player_resource
+            .get_player()
+            .map(|player| {
+                GearSlot::all()
+                    .into_iter()
+                    .filter(|slot| player.gear().equipped(*slot).is_some())
+                    .collect()
+            })
+            .unwrap_or_default();
+        // Fixed-width ranges draw the same on every platform
+        let mut rng = rng_streams.stream(REST);
+        let Some(slot) = malfunctioning_slot(
+            &equipped,
+            game_stats.modifiers.malfunction_chance(),
+            rng.gen_range(1..=100),
+            rng.gen_range(0..GearSlot::all().len() as u32) as usize,
+        ) else {
+            continue;
+        };
+        let name = player_resource
+            .get_player()
+            .and_then(|player| player.gear().equipped(slot))
+            .map(|item| item.describe());
+        if player_resource.unequip_gear(slot).is_some() {
+            game_log.log_message(
+                format!(
+                    "☀️ Flare interference: your {} ({}) shorts out and goes back to the stash",
+                    slot.name(),
+                    name.unwrap_or_default()
+                ),
+                GameLogType::Warning,
+            );
+        }
+    }
+}
+
+/// Show the season and the days it has left
+fn update_season_hud(
+    game_stats: Res<GameStatsResource>,
+    mut hud_query: Query<&mut Text, With<SeasonHudText>>,
+) {
+    if !game_stats.is_changed() {
+        return;
+    }
+    let Ok(mut text) = hud_query.single_mut() else {
+        return;
+    };
+    let line = match game_stats.modifiers.season() {
+        Some(season) => format!(
+            "SEASON: {} {} - {} day(s) left",
+            season.icon(),
+            season.name().to_uppercase(),
+            season_days_left(game_stats.current_day())
+        ),
+        None => String::new(),
+    };
+    if **text != line {
+        **text = line;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::constants::SEASON_LENGTH_DAYS;
+
+    #[test]
+    fn new_seasons_are_announced_once_and_only_on_their_first_day() {
+        let seed = 42;
+        let announced: Vec<u32> = (1..=4 * SEASON_LENGTH_DAYS)
+            .filter(|&day| season_announcement(seed, day).is_some())
+            .collect();
+        assert_eq!(announced, vec![16, 31, 46]);
+
+        let line = season_announcement(seed, 16).unwrap();
+        let begun = Season::on_day(seed, 16);
+        assert!(line.contains("The Calm is over"));
+        assert!(line.contains(begun.name()));
+        assert!(line.contains(&format!("for {} days", SEASON_LENGTH_DAYS)));
+    }
+
+    #[test]
+    fn malfunctions_need_equipped_gear_and_a_low_roll() {
+        let equipped = [GearSlot::Suit, GearSlot::Module];
+        assert_eq!(
+            malfunctioning_slot(&equipped, 15, 15, 1),
+            Some(GearSlot::Module)
+        );
+        assert_eq!(malfunctioning_slot(&equipped, 15, 16, 1), None);
+        assert_eq!(malfunctioning_slot(&[], 15, 1, 0), None);
+        // A Calm has no chance at all
+        assert_eq!(malfunctioning_slot(&equipped, 0, 1, 0), None);
+    }
+}
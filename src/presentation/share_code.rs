@@ -0,0 +1,326 @@
+//! Share Codes - Passing a run's start between players
+//!
+//! X on the pause screen copies a share code of the run: its world seed,
+//! difficulty, mutators, starting scenario and the character's name and
+//! stats as they were before the scenario changed them. On the main menu V
+//! pastes one and sets the next run up from it before it starts; the menu
+//! then waits for Enter. Web builds copy to the browser clipboard
+//! and paste through a prompt, native builds go through `share_code.txt`.
+//!
+//! A code that cannot be used leaves the run as it was and says why under
+//! the menu: a bad checksum, an unsupported version, an illegal build or a
+//! scenario this build does not have.
+
+use crate::application::services::{ShareCode, ShareCodeError, SharedCharacter};
+use crate::domain::constants::{PRIMARY_TEXT, SECONDARY_TEXT, WARNING_TEXT};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{ModifierStack, ScenarioTable};
+use crate::domain::value_objects::Position3D;
+use crate::infrastructure::bevy::resources::{
+    BaseResource, GameStatsResource, MapResource, PlayerResource,
+};
+use crate::infrastructure::clipboard::{copy_text, paste_text, CopyOutcome};
+use crate::infrastructure::settings::{MutatorSettings, RunSettings};
+use crate::presentation::base_visuals::{BaseChange, BaseChanged};
+use crate::presentation::game_state::{RpgAppState, RpgGameSession};
+use crate::presentation::map_renderer::RenderState;
+use crate::presentation::scenarios::{set_up_run_start, StartingCharacter};
+use bevy::prelude::*;
+
+/// Key that copies the run's share code from the pause screen
+pub const SHARE_EXPORT_KEY: KeyCode = KeyCode::KeyX;
+
+/// Key that pastes a share code on the main menu
+pub const SHARE_IMPORT_KEY: KeyCode = KeyCode::KeyV;
+
+/// File share codes go through where there is no clipboard
+pub const SHARE_CODE_FILE: &str = "share_code.txt";
+
+/// Plugin for copying and pasting share codes
+pub struct ShareCodePlugin;
+
+impl Plugin for ShareCodePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SharePrompt>()
+            .add_systems(Startup, setup_share_line)
+            .add_systems(
+                Update,
+                (
+                    export_share_code_system,
+                    import_share_code_system,
+                    update_share_line_system,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// The last share code pasted on the main menu, or why it was refused
+#[derive(Resource, Debug, Default)]
+pub struct SharePrompt {
+    pasted: Option<Result<ShareCode, ShareCodeError>>,
+}
+
+impl SharePrompt {
+    /// Check if the menu should wait for the player to read the result
+    pub fn holds_menu(&self) -> bool {
+        self.pasted.is_some()
+    }
+
+    /// Check if the next run was set up from a share code
+    ///
+    /// Everyone pasting the code plays the same start, so such a run is
+    /// played as a daily run.
+    pub fn is_daily_run(&self) -> bool {
+        matches!(self.pasted, Some(Ok(_)))
+    }
+
+    /// Line shown under the main menu
+    pub fn line(&self) -> (String, Color) {
+        match &self.pasted {
+            None => ("V to paste a share code".to_string(), SECONDARY_TEXT),
+            Some(Ok(code)) => (
+                format!("Share code loaded: {} - Enter to start", code.summary()),
+                PRIMARY_TEXT,
+            ),
+            Some(Err(e)) => (format!("Share code refused: {}", e), WARNING_TEXT),
+        }
+    }
+}
+
+#[derive(Component)]
+struct ShareLine;
+
+fn setup_share_line(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: FontSize::Regular.to_pixels(),
+            ..default()
+        },
+        TextColor(SECONDARY_TEXT),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Percent(10.0),
+            width: Val::Percent(80.0),
+            bottom: Val::Px(48.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        GlobalZIndex(14),
+        Visibility::Hidden,
+        ShareLine,
+        Name::new("ShareLine"),
+    ));
+}
+
+/// The share code of the run being played
+///
+/// The character is shared as the run was set up from it, so the scenario
+/// changes it the same way for whoever pastes the code.
+fn current_share_code(
+    map_resource: &MapResource,
+    starting_character: &StartingCharacter,
+    game_stats: &GameStatsResource,
+    session: &RpgGameSession,
+) -> Option<ShareCode> {
+    let seed = map_resource.overworld()?.seed();
+    let character = Some(SharedCharacter {
+        name: starting_character.name.clone(),
+        stats: starting_character.stats,
+    });
+    Some(ShareCode {
+        seed,
+        difficulty: game_stats.modifiers.difficulty(),
+        mutators: session.mutators.clone(),
+        scenario: session.scenario.clone(),
+        character,
+    })
+}
+
+/// Copy the run's share code on X while paused
+fn export_share_code_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    map_resource: Res<MapResource>,
+    starting_character: Res<StartingCharacter>,
+    game_stats: Res<GameStatsResource>,
+    session: Res<RpgGameSession>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    if *state.get() != RpgAppState::Paused || !keyboard.just_pressed(SHARE_EXPORT_KEY) {
+        return;
+    }
+    let Some(code) = current_share_code(&map_resource, &starting_character, &game_stats, &session)
+    else {
+        return;
+    };
+    match copy_text(&code.encode(), SHARE_CODE_FILE) {
+        Ok(CopyOutcome::Clipboard) => game_log.log_message(
+            "🔗 Share code copied to the clipboard".to_string(),
+            GameLogType::System,
+        ),
+        Ok(CopyOutcome::File(file)) => game_log.log_message(
+            format!("🔗 Share code saved as {}", file),
+            GameLogType::System,
+        ),
+        Err(error) => {
+            warn!("Share code could not be copied: {}", error);
+            game_log.log_message(
+                "🔗 The share code could not be copied".to_string(),
+                GameLogType::Warning,
+            );
+        }
+    }
+}
+
+/// Set the next run up from a share code pasted on the main menu
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn import_share_code_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<RpgAppState>>,
+    mut prompt: ResMut<SharePrompt>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut base_resource: ResMut<BaseResource>,
+    mut map_resource: ResMut<MapResource>,
+    mut game_stats: ResMut<GameStatsResource>,
+    mut session: ResMut<RpgGameSession>,
+    (scenarios, mut starting_character, run_settings, mutator_settings): (
+        Res<ScenarioTable>,
+        ResMut<StartingCharacter>,
+        Option<ResMut<RunSettings>>,
+        Option<ResMut<MutatorSettings>>,
+    ),
+    mut base_events: EventWriter<BaseChanged>,
+    render_state: Option<ResMut<RenderState>>,
+) {
+    if *state.get() != RpgAppState::MainMenu {
+        if prompt.holds_menu() {
+            prompt.pasted = None;
+        }
+        return;
+    }
+    if !keyboard.just_pressed(SHARE_IMPORT_KEY) {
+        return;
+    }
+    let text = match paste_text("Paste a share code", SHARE_CODE_FILE) {
+        Ok(Some(text)) => text,
+        Ok(None) => return,
+        Err(error) => {
+            warn!("Share code could not be pasted: {}", error);
+            return;
+        }
+    };
+    let code = match ShareCode::decode(&text) {
+        Ok(code) => code,
+        Err(e) => {
+            prompt.pasted = Some(Err(e));
+            return;
+        }
+    };
+    let Some(scenario) = scenarios.get(&code.scenario).cloned() else {
+        prompt.pasted = Some(Err(ShareCodeError::UnknownScenario(code.scenario)));
+        return;
+    };
+
+    // Rebuild the run exactly as a new one would start with these choices
+    let modifiers = ModifierStack::new(code.difficulty, code.mutators.clone());
+    let character = code
+        .character
+        .clone()
+        .map(|character| StartingCharacter {
+            name: character.name,
+            stats: character.stats,
+        })
+        .unwrap_or_else(|| starting_character.clone());
+    map_resource.generate_overworld(Position3D::origin(), code.seed);
+    if let Err(e) = set_up_run_start(
+        &character,
+        &scenario,
+        &modifiers,
+        &mut player_resource,
+        &mut base_resource,
+        &mut map_resource,
+        &mut session,
+    ) {
+        prompt.pasted = Some(Err(ShareCodeError::InvalidStats(e)));
+        return;
+    }
+    base_events.write(BaseChanged::new(BaseChange::Founded));
+    if let Some(mut render_state) = render_state {
+        render_state.last_player_position = None;
+    }
+
+    *starting_character = character;
+    session.mutators = code.mutators.clone();
+    if let Some(mut settings) = mutator_settings {
+        settings.selected = code.mutators.clone();
+    }
+    if let Some(mut settings) = run_settings {
+        settings.scenario = scenario.id.clone();
+    }
+    game_stats.modifiers = modifiers;
+    game_stats.scenario = scenario;
+
+    info!("🔗 Next run set up from a share code: {}", code.summary());
+    prompt.pasted = Some(Ok(code));
+}
+
+/// Show the share line on the main menu only
+fn update_share_line_system(
+    state: Res<State<RpgAppState>>,
+    prompt: Res<SharePrompt>,
+    mut lines: Query<(&mut Text, &mut TextColor, &mut Visibility), With<ShareLine>>,
+) {
+    let Ok((mut text, mut color, mut visibility)) = lines.single_mut() else {
+        return;
+    };
+    let wanted = if *state.get() == RpgAppState::MainMenu {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    if *visibility != wanted {
+        *visibility = wanted;
+    }
+    if !prompt.is_changed() && !state.is_changed() {
+        return;
+    }
+    let (line, line_color) = prompt.line();
+    text.0 = line;
+    color.0 = line_color;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::game::DifficultyLevel;
+    use crate::domain::services::{Mutators, STANDARD_DROP};
+
+    #[test]
+    fn the_line_names_what_the_pasted_code_did() {
+        let mut prompt = SharePrompt::default();
+        assert!(!prompt.holds_menu());
+        assert_eq!(prompt.line().1, SECONDARY_TEXT);
+
+        prompt.pasted = Some(Err(ShareCodeError::UnsupportedVersion(9)));
+        assert!(prompt.holds_menu());
+        let (line, color) = prompt.line();
+        assert!(line.contains("unsupported share code version 9"));
+        assert_eq!(color, WARNING_TEXT);
+
+        prompt.pasted = Some(Ok(ShareCode {
+            seed: 42,
+            difficulty: DifficultyLevel::Hard,
+            mutators: Mutators::default(),
+            scenario: STANDARD_DROP.to_string(),
+            character: None,
+        }));
+        assert_eq!(
+            prompt.line().0,
+            "Share code loaded: seed 42, Hard - Enter to start"
+        );
+    }
+}
@@ -0,0 +1,275 @@
+//! Slope Shading - Height and steepness read off the tile colors
+//!
+//! Explored tiles are lightened with height and darkened with slope, so
+//! ridges and basins stand out instead of reading as one flat sheet. A rise
+//! of `CLIFF_MIN_SLOPE` or more also gets a dark strip along the edge of the
+//! lower tile, showing where the ground drops away. Tiles are shaded once
+//! when drawn and again whenever the map's relief or the render settings
+//! change; shaded materials are shared per terrain and shade step. Animated
+//! terrain keeps its keyframe materials and only gets the cliff strips.
+
+use crate::domain::constants::{
+    CLIFF_EDGE_COLOR, CLIFF_MIN_SLOPE, SLOPE_SHADING_HEIGHT_SPAN, SLOPE_SHADING_MAX_SLOPE,
+};
+use crate::domain::entities::Map;
+use crate::domain::value_objects::terrain::TerrainType;
+use crate::domain::value_objects::TileCoordinate;
+use crate::infrastructure::bevy::resources::MapResource;
+use crate::presentation::map_renderer::{MapRenderConfig, TerrainMaterials, TerrainTile};
+use crate::presentation::terrain_animation::terrain_motion;
+use crate::presentation::terrain_transitions::{TileEdge, TransitionAssets};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Shade steps per unit of brightness; shaded materials are shared per step
+const SHADE_STEPS: f32 = 20.0;
+
+/// Lift cliff strips above the terrain transition strips
+const CLIFF_LIFT: f32 = 0.005;
+
+/// Brightness factor applied to a tile's base color
+///
+/// Height lightens by up to half the intensity and slope darkens by up to
+/// the full intensity, so the factor stays within
+/// `1 - intensity ..= 1 + intensity / 2`.
+pub fn shade_factor(height: i32, slope: u32, intensity: f32) -> f32 {
+    let intensity = intensity.clamp(0.0, 1.0);
+    let band = (height as f32 / SLOPE_SHADING_HEIGHT_SPAN).clamp(-1.0, 1.0);
+    let steepness = slope.min(SLOPE_SHADING_MAX_SLOPE) as f32 / SLOPE_SHADING_MAX_SLOPE as f32;
+    1.0 + intensity * 0.5 * (band - steepness)
+}
+
+/// Edges of a tile that lie at the foot of a cliff
+///
+/// The strip belongs to the low side, so only edges whose neighbor rises by
+/// `CLIFF_MIN_SLOPE` or more are returned. Neighbors that are not loaded
+/// are skipped.
+pub fn cliff_edges(map: &Map, coord: TileCoordinate) -> Vec<TileEdge> {
+    let Some(tile) = map.get_tile(&coord) else {
+        return Vec::new();
+    };
+    TileEdge::ALL
+        .into_iter()
+        .filter(|edge| {
+            map.get_tile(&edge.neighbor(coord)).is_some_and(|neighbor| {
+                neighbor.elevation.height - tile.elevation.height >= CLIFF_MIN_SLOPE as i32
+            })
+        })
+        .collect()
+}
+
+/// Marker for tiles whose shading is up to date
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SlopeShaded;
+
+/// Dark strip along the low side of a cliff edge
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CliffEdge {
+    pub edge: TileEdge,
+}
+
+/// Material shared by every cliff strip
+#[derive(Resource)]
+pub struct CliffAssets {
+    pub material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for CliffAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        Self {
+            material: materials.add(StandardMaterial {
+                base_color: CLIFF_EDGE_COLOR,
+                perceptual_roughness: 1.0,
+                ..default()
+            }),
+        }
+    }
+}
+
+/// Shaded copies of the terrain materials, per terrain and shade step
+#[derive(Resource, Debug, Default)]
+pub struct ShadedMaterials {
+    materials: HashMap<(TerrainType, i32), Handle<StandardMaterial>>,
+}
+
+impl ShadedMaterials {
+    /// Material for a terrain at a brightness factor
+    fn material(
+        &mut self,
+        terrain_type: TerrainType,
+        factor: f32,
+        terrain_materials: &TerrainMaterials,
+        assets: &mut Assets<StandardMaterial>,
+    ) -> Handle<StandardMaterial> {
+        let base = terrain_materials.for_terrain(terrain_type);
+        let step = (factor * SHADE_STEPS).round() as i32;
+        if step == SHADE_STEPS as i32 {
+            return base;
+        }
+        self.materials
+            .entry((terrain_type, step))
+            .or_insert_with(|| {
+                let mut shaded = assets.get(&base).cloned().unwrap_or_default();
+                shaded.base_color = scale_brightness(shaded.base_color, step as f32 / SHADE_STEPS);
+                assets.add(shaded)
+            })
+            .clone()
+    }
+}
+
+fn scale_brightness(color: Color, factor: f32) -> Color {
+    let color = color.to_srgba();
+    Color::srgba(
+        (color.red * factor).min(1.0),
+        (color.green * factor).min(1.0),
+        (color.blue * factor).min(1.0),
+        color.alpha,
+    )
+}
+
+fn cliff_strip_bundle(
+    edge: TileEdge,
+    tile_size: f32,
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+) -> impl Bundle {
+    let mut transform = edge.strip_transform(tile_size);
+    transform.translation.y += CLIFF_LIFT;
+    (
+        Mesh3d(mesh),
+        MeshMaterial3d(material),
+        transform,
+        CliffEdge { edge },
+        Name::new(format!("Cliff_{:?}", edge)),
+    )
+}
+
+/// Shade newly drawn tiles, and every tile once the relief or settings change
+#[allow(clippy::too_many_arguments)]
+pub fn apply_slope_shading_system(
+    mut commands: Commands,
+    map_resource: Res<MapResource>,
+    config: Res<MapRenderConfig>,
+    terrain_materials: Res<TerrainMaterials>,
+    transition_assets: Res<TransitionAssets>,
+    cliff_assets: Res<CliffAssets>,
+    mut shaded_materials: ResMut<ShadedMaterials>,
+    mut assets: ResMut<Assets<StandardMaterial>>,
+    mut tiles: Query<(
+        Entity,
+        &TerrainTile,
+        &mut MeshMaterial3d<StandardMaterial>,
+        Has<SlopeShaded>,
+    )>,
+    cliffs: Query<Entity, With<CliffEdge>>,
+    mut shaded_relief: Local<Option<u64>>,
+) {
+    let Some(map) = map_resource.current_map() else {
+        return;
+    };
+    let relief = map.relief_version();
+    let reshade_all = config.is_changed() || *shaded_relief != Some(relief);
+    *shaded_relief = Some(relief);
+    if reshade_all {
+        for cliff in cliffs.iter() {
+            commands.entity(cliff).despawn();
+        }
+    }
+
+    let intensity = config.shading_intensity();
+    for (entity, tile, mut material, shaded) in tiles.iter_mut() {
+        if shaded && !reshade_all {
+            continue;
+        }
+        commands.entity(entity).insert(SlopeShaded);
+        // Fogged tiles keep the fog overlay
+        if !tile.is_explored {
+            continue;
+        }
+        let Some(map_tile) = map.get_tile(&tile.coordinate) else {
+            continue;
+        };
+
+        if terrain_motion(tile.terrain_type).is_none() {
+            let slope = map.slope_at(&tile.coordinate).unwrap_or(0);
+            let factor = shade_factor(map_tile.elevation.height, slope, intensity);
+            let shaded_material = shaded_materials.material(
+                tile.terrain_type,
+                factor,
+                &terrain_materials,
+                &mut assets,
+            );
+            if material.0 != shaded_material {
+                material.0 = shaded_material;
+            }
+        }
+
+        let edges = cliff_edges(map, tile.coordinate);
+        if !edges.is_empty() {
+            commands.entity(entity).with_children(|parent| {
+                for edge in edges {
+                    parent.spawn(cliff_strip_bundle(
+                        edge,
+                        tile.tile_size,
+                        transition_assets.strip_mesh.clone(),
+                        cliff_assets.material.clone(),
+                    ));
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::MapTile;
+    use crate::domain::value_objects::terrain::Elevation;
+    use crate::domain::value_objects::EntityId;
+
+    #[test]
+    fn shading_stays_within_its_bounds() {
+        for intensity in [0.0_f32, 0.35, 1.0, 3.0] {
+            let clamped = intensity.clamp(0.0, 1.0);
+            for height in [-100, -20, 0, 7, 20, 100] {
+                for slope in [0, 1, 2, 4, 50] {
+                    let factor = shade_factor(height, slope, intensity);
+                    assert!(factor >= 1.0 - clamped - f32::EPSILON, "{}", factor);
+                    assert!(factor <= 1.0 + clamped / 2.0 + f32::EPSILON, "{}", factor);
+                }
+            }
+        }
+        assert_eq!(shade_factor(60, 3, 0.0), 1.0);
+        // Higher ground is lighter, steeper ground darker
+        assert!(shade_factor(15, 0, 0.5) > shade_factor(2, 0, 0.5));
+        assert!(shade_factor(5, 3, 0.5) < shade_factor(5, 1, 0.5));
+    }
+
+    #[test]
+    fn cliff_strips_go_on_the_low_side() {
+        let mut map = Map::new(EntityId::generate(), "Test".to_string(), 1).unwrap();
+        let tile_at = |height| {
+            MapTile::new(
+                TerrainType::Mountains,
+                Elevation::new(height).unwrap(),
+                true,
+            )
+        };
+        let low = TileCoordinate::new(0, 0, 0);
+        map.set_tile(low, tile_at(1));
+        map.set_tile(TileEdge::North.neighbor(low), tile_at(4));
+        map.set_tile(TileEdge::East.neighbor(low), tile_at(2));
+        map.set_tile(TileEdge::South.neighbor(low), tile_at(3));
+
+        // A rise of one is no cliff; West is not loaded
+        assert_eq!(
+            cliff_edges(&map, low),
+            vec![TileEdge::North, TileEdge::South]
+        );
+        // The high side of the drop draws nothing
+        let high = TileEdge::North.neighbor(low);
+        assert!(cliff_edges(&map, high).is_empty());
+        assert!(cliff_edges(&map, TileCoordinate::new(9, 9, 0)).is_empty());
+    }
+}
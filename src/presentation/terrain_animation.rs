@@ -0,0 +1,349 @@
+//! Terrain Animation - Moving water, swamp and lava without shaders
+//!
+//! Ocean, Swamp and Volcanic tiles cycle through a few keyframe materials
+//! on a slow timer: the ocean shifts its hue a little, the swamp sways in
+//! brightness and lava pulses its glow. Keyframes are shared per terrain,
+//! so animating a tile only swaps which handle it points at and the number
+//! of materials never grows with the map. Each tile starts the cycle at a
+//! phase hashed from its coordinate, so neighbours do not blink in sync.
+//! Only explored tiles the camera can see are touched, and reduce-motion
+//! holds every tile on its first keyframe, the plain terrain material.
+
+use crate::domain::constants::{TERRAIN_ANIMATION_KEYFRAMES, TERRAIN_ANIMATION_STEP_SECS};
+use crate::domain::value_objects::terrain::TerrainType;
+use crate::domain::value_objects::TileCoordinate;
+use crate::presentation::map_renderer::{TerrainMaterials, TerrainTile};
+use crate::presentation::rendering::DisplaySettings;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Offset of each keyframe from the base look, from -1 to 1
+const KEYFRAME_WAVE: [f32; TERRAIN_ANIMATION_KEYFRAMES] = [0.0, 1.0, 0.0, -1.0];
+
+/// How an animated terrain changes between keyframes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TerrainMotion {
+    /// Hue rotated by up to this many degrees
+    HueShift(f32),
+    /// Brightness scaled by up to this share
+    Brightness(f32),
+    /// Glow scaled by up to this share
+    EmissivePulse(f32),
+}
+
+/// The motion of a terrain, if it is animated
+pub fn terrain_motion(terrain_type: TerrainType) -> Option<TerrainMotion> {
+    match terrain_type {
+        TerrainType::Ocean => Some(TerrainMotion::HueShift(8.0)),
+        TerrainType::Swamp => Some(TerrainMotion::Brightness(0.06)),
+        TerrainType::Volcanic => Some(TerrainMotion::EmissivePulse(0.6)),
+        _ => None,
+    }
+}
+
+/// Keyframe a tile's cycle starts at, hashed from its coordinate
+pub fn tile_phase(coordinate: TileCoordinate) -> usize {
+    let mut hash = (coordinate.x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    hash ^= (coordinate.y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+    hash ^= (coordinate.z as u64).wrapping_mul(0x1656_67B1_9E37_79F9);
+    hash ^= hash >> 29;
+    hash = hash.wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    hash ^= hash >> 32;
+    (hash % TERRAIN_ANIMATION_KEYFRAMES as u64) as usize
+}
+
+/// Keyframe shown by a tile with `phase` at animation `step`
+pub fn keyframe_at(step: u64, phase: usize, reduce_motion: bool) -> usize {
+    if reduce_motion {
+        return 0;
+    }
+    ((step % TERRAIN_ANIMATION_KEYFRAMES as u64) as usize + phase) % TERRAIN_ANIMATION_KEYFRAMES
+}
+
+/// The base material moved `wave` of the way along `motion`
+fn keyframe_material(
+    base: &StandardMaterial,
+    motion: TerrainMotion,
+    wave: f32,
+) -> StandardMaterial {
+    let mut material = base.clone();
+    match motion {
+        TerrainMotion::HueShift(degrees) => {
+            let mut color = Hsla::from(base.base_color);
+            color.hue = (color.hue + degrees * wave).rem_euclid(360.0);
+            material.base_color = color.into();
+        }
+        TerrainMotion::Brightness(share) => {
+            let color = base.base_color.to_srgba();
+            let factor = 1.0 + share * wave;
+            material.base_color = Color::srgba(
+                (color.red * factor).min(1.0),
+                (color.green * factor).min(1.0),
+                (color.blue * factor).min(1.0),
+                color.alpha,
+            );
+        }
+        TerrainMotion::EmissivePulse(share) => {
+            let glow = base.emissive;
+            let factor = 1.0 + share * wave;
+            material.emissive = LinearRgba::new(
+                glow.red * factor,
+                glow.green * factor,
+                glow.blue * factor,
+                glow.alpha,
+            );
+        }
+    }
+    material
+}
+
+/// Keyframe materials shared by every tile of an animated terrain
+///
+/// The first keyframe is the terrain's own material, so a frozen or freshly
+/// drawn tile looks exactly as it did before animation existed.
+#[derive(Resource, Debug)]
+pub struct AnimatedTerrainMaterials {
+    keyframes: HashMap<TerrainType, Vec<Handle<StandardMaterial>>>,
+}
+
+impl AnimatedTerrainMaterials {
+    /// Build the keyframes from the terrain materials
+    pub fn build(
+        terrain_materials: &TerrainMaterials,
+        assets: &mut Assets<StandardMaterial>,
+    ) -> Self {
+        let mut keyframes = HashMap::new();
+        for terrain_type in TerrainType::all() {
+            let Some(motion) = terrain_motion(terrain_type) else {
+                continue;
+            };
+            let base_handle = terrain_materials.for_terrain(terrain_type);
+            let base = assets.get(&base_handle).cloned().unwrap_or_default();
+            let mut handles = vec![base_handle];
+            for wave in &KEYFRAME_WAVE[1..] {
+                handles.push(assets.add(keyframe_material(&base, motion, *wave)));
+            }
+            keyframes.insert(terrain_type, handles);
+        }
+        Self { keyframes }
+    }
+
+    /// Material of keyframe `index` of a terrain, if the terrain is animated
+    pub fn keyframe(
+        &self,
+        terrain_type: TerrainType,
+        index: usize,
+    ) -> Option<&Handle<StandardMaterial>> {
+        self.keyframes.get(&terrain_type)?.get(index)
+    }
+}
+
+impl FromWorld for AnimatedTerrainMaterials {
+    fn from_world(world: &mut World) -> Self {
+        world.resource_scope(|world, mut assets: Mut<Assets<StandardMaterial>>| {
+            Self::build(world.resource::<TerrainMaterials>(), &mut assets)
+        })
+    }
+}
+
+/// Slow clock driving the keyframes
+#[derive(Resource, Debug, Default)]
+pub struct TerrainAnimationClock {
+    elapsed: f32,
+    step: u64,
+}
+
+impl TerrainAnimationClock {
+    /// Let `secs` pass; reduce-motion stops the clock
+    pub fn advance(&mut self, secs: f32, reduce_motion: bool) {
+        if reduce_motion {
+            return;
+        }
+        self.elapsed += secs;
+        while self.elapsed >= TERRAIN_ANIMATION_STEP_SECS {
+            self.elapsed -= TERRAIN_ANIMATION_STEP_SECS;
+            self.step = self.step.wrapping_add(1);
+        }
+    }
+
+    /// Keyframe steps taken so far
+    pub fn step(&self) -> u64 {
+        self.step
+    }
+}
+
+/// Phase of an animated tile, set when the tile is drawn
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnimatedTerrain {
+    pub phase: usize,
+}
+
+/// Tag newly drawn tiles whose terrain is animated
+pub fn tag_animated_tiles_system(
+    mut commands: Commands,
+    tiles: Query<(Entity, &TerrainTile), Added<TerrainTile>>,
+) {
+    for (entity, tile) in tiles.iter() {
+        if terrain_motion(tile.terrain_type).is_some() {
+            commands.entity(entity).insert(AnimatedTerrain {
+                phase: tile_phase(tile.coordinate),
+            });
+        }
+    }
+}
+
+/// Point visible animated tiles at their current keyframe
+///
+/// Runs once per keyframe step, over animated tiles only. Turning
+/// reduce-motion on or off also runs it once, over every animated tile
+/// whether seen or not, so none is left frozen mid-cycle.
+pub fn animate_terrain_system(
+    time: Res<Time>,
+    display: Option<Res<DisplaySettings>>,
+    animated_materials: Res<AnimatedTerrainMaterials>,
+    mut clock: ResMut<TerrainAnimationClock>,
+    mut tiles: Query<(
+        &TerrainTile,
+        &AnimatedTerrain,
+        &ViewVisibility,
+        &mut MeshMaterial3d<StandardMaterial>,
+    )>,
+    mut applied_step: Local<Option<u64>>,
+) {
+    let reduce_motion = display
+        .as_ref()
+        .is_some_and(|display| display.reduce_motion);
+    let motion_changed = display.as_ref().is_some_and(|display| display.is_changed());
+    clock.advance(time.delta_secs(), reduce_motion);
+    if *applied_step == Some(clock.step()) && !motion_changed {
+        return;
+    }
+    *applied_step = Some(clock.step());
+
+    for (tile, animated, visibility, mut material) in tiles.iter_mut() {
+        // Fogged tiles keep the fog overlay
+        if !tile.is_explored || (!visibility.get() && !motion_changed) {
+            continue;
+        }
+        let keyframe = keyframe_at(clock.step(), animated.phase, reduce_motion);
+        let Some(handle) = animated_materials.keyframe(tile.terrain_type, keyframe) else {
+            continue;
+        };
+        if material.0 != *handle {
+            material.0 = handle.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::VisibilityLevel;
+    use std::collections::HashSet;
+
+    #[test]
+    fn phases_spread_evenly_and_neighbours_rarely_match() {
+        let mut counts = [0; TERRAIN_ANIMATION_KEYFRAMES];
+        let mut matching_neighbours = 0;
+        for x in -16..16 {
+            for y in -16..16 {
+                let phase = tile_phase(TileCoordinate::new(x, y, 0));
+                counts[phase] += 1;
+                if phase == tile_phase(TileCoordinate::new(x + 1, y, 0)) {
+                    matching_neighbours += 1;
+                }
+            }
+        }
+        // 1024 tiles over 4 phases: about 256 each
+        for count in counts {
+            assert!((192..=320).contains(&count), "{:?}", counts);
+        }
+        assert!(
+            matching_neighbours < 1024 * 35 / 100,
+            "{}",
+            matching_neighbours
+        );
+    }
+
+    #[test]
+    fn reduce_motion_freezes_on_the_first_keyframe() {
+        let mut clock = TerrainAnimationClock::default();
+        clock.advance(TERRAIN_ANIMATION_STEP_SECS * 3.5, true);
+        assert_eq!(clock.step(), 0);
+        for step in 0..8 {
+            for phase in 0..TERRAIN_ANIMATION_KEYFRAMES {
+                assert_eq!(keyframe_at(step, phase, true), 0);
+            }
+        }
+
+        clock.advance(TERRAIN_ANIMATION_STEP_SECS * 3.5, false);
+        assert_eq!(clock.step(), 3);
+        let shown: HashSet<usize> = (0..TERRAIN_ANIMATION_KEYFRAMES as u64)
+            .map(|step| keyframe_at(step, 1, false))
+            .collect();
+        assert_eq!(shown.len(), TERRAIN_ANIMATION_KEYFRAMES);
+    }
+
+    fn animate(tiles_per_terrain: i32) -> (usize, usize) {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<Assets<StandardMaterial>>()
+            .init_resource::<TerrainMaterials>()
+            .init_resource::<AnimatedTerrainMaterials>()
+            .init_resource::<TerrainAnimationClock>()
+            .add_systems(
+                Update,
+                (tag_animated_tiles_system, animate_terrain_system).chain(),
+            );
+        let mut visible = ViewVisibility::HIDDEN;
+        visible.set();
+        for (row, terrain_type) in [
+            TerrainType::Ocean,
+            TerrainType::Volcanic,
+            TerrainType::Plains,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let base = app
+                .world()
+                .resource::<TerrainMaterials>()
+                .for_terrain(terrain_type);
+            for x in 0..tiles_per_terrain {
+                app.world_mut().spawn((
+                    TerrainTile {
+                        coordinate: TileCoordinate::new(x, row as i32, 0),
+                        terrain_type,
+                        is_explored: true,
+                        visibility_level: VisibilityLevel::FullyVisible,
+                        tile_size: 2.0,
+                    },
+                    visible,
+                    MeshMaterial3d(base.clone()),
+                ));
+            }
+        }
+
+        let mut handles = HashSet::new();
+        for _ in 0..6 {
+            app.world_mut()
+                .resource_mut::<TerrainAnimationClock>()
+                .advance(TERRAIN_ANIMATION_STEP_SECS, false);
+            app.update();
+            let mut materials = app.world_mut().query::<&MeshMaterial3d<StandardMaterial>>();
+            handles.extend(materials.iter(app.world()).map(|material| material.0.id()));
+        }
+        let assets = app.world().resource::<Assets<StandardMaterial>>().len();
+        (handles.len(), assets)
+    }
+
+    #[test]
+    fn keyframes_are_shared_however_many_tiles_animate() {
+        let (few_handles, few_assets) = animate(10);
+        let (many_handles, many_assets) = animate(500);
+        // Ocean and Volcanic cycle through their keyframes, Plains stays put
+        assert_eq!(few_handles, 2 * TERRAIN_ANIMATION_KEYFRAMES + 1);
+        assert_eq!(many_handles, few_handles);
+        assert_eq!(many_assets, few_assets);
+    }
+}
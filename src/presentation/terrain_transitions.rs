@@ -0,0 +1,362 @@
+//! Terrain Transitions - Edge blending between adjacent terrain tiles
+//!
+//! Where two explored tiles of different terrain meet, the lower-priority
+//! tile gets a thin strip along the shared edge in the neighbor's material.
+//! Strips are children of their tile so they are despawned with it. Edges
+//! whose neighbor is not generated or explored yet are remembered and
+//! filled in once the map changes.
+
+use crate::domain::entities::Map;
+use crate::domain::value_objects::terrain::TerrainType;
+use crate::domain::value_objects::TileCoordinate;
+use crate::infrastructure::bevy::resources::MapResource;
+use crate::presentation::map_renderer::{TerrainMaterials, TerrainTile};
+use bevy::prelude::*;
+
+/// Width of a transition strip as a fraction of the tile size
+const STRIP_WIDTH_FRACTION: f32 = 0.15;
+
+/// Height of the tile top surface above the tile origin
+const TILE_TOP_OFFSET: f32 = 0.1;
+
+/// Lift strips slightly above the tile surface to avoid z-fighting
+const STRIP_LIFT: f32 = 0.01;
+
+/// Edge of a tile shared with one cardinal neighbor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TileEdge {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl TileEdge {
+    /// All four edges
+    pub const ALL: [TileEdge; 4] = [
+        TileEdge::North,
+        TileEdge::South,
+        TileEdge::East,
+        TileEdge::West,
+    ];
+
+    /// Coordinate of the neighbor across this edge
+    pub fn neighbor(&self, coord: TileCoordinate) -> TileCoordinate {
+        match self {
+            TileEdge::North => TileCoordinate::new(coord.x, coord.y + 1, coord.z),
+            TileEdge::South => TileCoordinate::new(coord.x, coord.y - 1, coord.z),
+            TileEdge::East => TileCoordinate::new(coord.x + 1, coord.y, coord.z),
+            TileEdge::West => TileCoordinate::new(coord.x - 1, coord.y, coord.z),
+        }
+    }
+
+    /// Local transform of a strip along this edge, relative to its tile
+    pub(crate) fn strip_transform(&self, tile_size: f32) -> Transform {
+        let width = tile_size * STRIP_WIDTH_FRACTION;
+        let inset = (tile_size - width) * 0.5;
+        let y = TILE_TOP_OFFSET + STRIP_LIFT;
+        // Map y grows toward world +z, map x toward world +x
+        let (translation, scale) = match self {
+            TileEdge::North => (Vec3::new(0.0, y, inset), Vec3::new(tile_size, 1.0, width)),
+            TileEdge::South => (Vec3::new(0.0, y, -inset), Vec3::new(tile_size, 1.0, width)),
+            TileEdge::East => (Vec3::new(inset, y, 0.0), Vec3::new(width, 1.0, tile_size)),
+            TileEdge::West => (Vec3::new(-inset, y, 0.0), Vec3::new(width, 1.0, tile_size)),
+        };
+        Transform::from_translation(translation).with_scale(scale)
+    }
+}
+
+/// Transition work for a single tile
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TileTransitions {
+    /// Edges to draw now, with the terrain bleeding in
+    pub strips: Vec<(TileEdge, TerrainType)>,
+    /// Edges whose neighbor is not available yet
+    pub deferred: Vec<TileEdge>,
+}
+
+/// Resolve transitions for the given edges of a tile
+///
+/// A neighbor that is missing (e.g. in a chunk that has not been generated)
+/// or still unexplored defers the edge instead of drawing anything.
+pub fn resolve_transitions(
+    map: &Map,
+    coord: TileCoordinate,
+    terrain: TerrainType,
+    edges: &[TileEdge],
+) -> TileTransitions {
+    let mut transitions = TileTransitions::default();
+    for &edge in edges {
+        match map.get_tile(&edge.neighbor(coord)) {
+            Some(neighbor) if neighbor.is_explored() => {
+                if let Some(bleed) = terrain.transition_from(neighbor.terrain_type) {
+                    transitions.strips.push((edge, bleed));
+                }
+            }
+            _ => transitions.deferred.push(edge),
+        }
+    }
+    transitions
+}
+
+/// Transition strip drawn along one edge of its parent tile
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitionStrip {
+    pub edge: TileEdge,
+    pub terrain: TerrainType,
+}
+
+/// Edges of a tile still waiting for their neighbor to appear
+#[derive(Component, Debug, Clone)]
+pub struct PendingTransitions {
+    pub edges: Vec<TileEdge>,
+    pub tile_size: f32,
+}
+
+/// Shared mesh used by every transition strip
+#[derive(Resource)]
+pub struct TransitionAssets {
+    pub strip_mesh: Handle<Mesh>,
+}
+
+impl FromWorld for TransitionAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        Self {
+            strip_mesh: meshes.add(Cuboid::new(1.0, 0.02, 1.0)),
+        }
+    }
+}
+
+/// Build the bundle for one strip; meshes and materials are shared handles
+pub fn transition_strip_bundle(
+    edge: TileEdge,
+    terrain: TerrainType,
+    tile_size: f32,
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+) -> impl Bundle {
+    (
+        Mesh3d(mesh),
+        MeshMaterial3d(material),
+        edge.strip_transform(tile_size),
+        TransitionStrip { edge, terrain },
+        Name::new(format!("Transition_{:?}", edge)),
+    )
+}
+
+/// Spawn transition strips for a freshly spawned, explored tile
+pub fn spawn_tile_transitions(
+    commands: &mut Commands,
+    tile_entity: Entity,
+    map: &Map,
+    tile: &TerrainTile,
+    tile_size: f32,
+    assets: &TransitionAssets,
+    terrain_materials: &TerrainMaterials,
+) {
+    let transitions = resolve_transitions(map, tile.coordinate, tile.terrain_type, &TileEdge::ALL);
+    attach_transitions(
+        commands,
+        tile_entity,
+        transitions,
+        tile_size,
+        assets,
+        terrain_materials,
+    );
+}
+
+fn attach_transitions(
+    commands: &mut Commands,
+    tile_entity: Entity,
+    transitions: TileTransitions,
+    tile_size: f32,
+    assets: &TransitionAssets,
+    terrain_materials: &TerrainMaterials,
+) {
+    let mut tile = commands.entity(tile_entity);
+    if !transitions.strips.is_empty() {
+        tile.with_children(|parent| {
+            for (edge, bleed) in transitions.strips {
+                parent.spawn(transition_strip_bundle(
+                    edge,
+                    bleed,
+                    tile_size,
+                    assets.strip_mesh.clone(),
+                    terrain_materials.for_terrain(bleed),
+                ));
+            }
+        });
+    }
+    if transitions.deferred.is_empty() {
+        tile.remove::<PendingTransitions>();
+    } else {
+        tile.insert(PendingTransitions {
+            edges: transitions.deferred,
+            tile_size,
+        });
+    }
+}
+
+/// Fill in deferred edges once their neighbors have been generated or explored
+pub fn refresh_pending_transitions_system(
+    mut commands: Commands,
+    map_resource: Res<MapResource>,
+    assets: Res<TransitionAssets>,
+    terrain_materials: Res<TerrainMaterials>,
+    pending_query: Query<(Entity, &TerrainTile, &PendingTransitions)>,
+) {
+    if !map_resource.is_changed() {
+        return;
+    }
+    let Some(map) = map_resource.current_map() else {
+        return;
+    };
+
+    for (entity, tile, pending) in pending_query.iter() {
+        let transitions =
+            resolve_transitions(map, tile.coordinate, tile.terrain_type, &pending.edges);
+        if transitions.deferred.len() == pending.edges.len() {
+            continue; // Nothing new for this tile
+        }
+        attach_transitions(
+            &mut commands,
+            entity,
+            transitions,
+            pending.tile_size,
+            &assets,
+            &terrain_materials,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::MapTile;
+    use crate::domain::value_objects::terrain::Elevation;
+    use crate::domain::value_objects::EntityId;
+
+    fn explored(terrain: TerrainType) -> MapTile {
+        MapTile::new(terrain, Elevation::sea_level(), true)
+    }
+
+    #[test]
+    fn strips_follow_priority() {
+        let mut map = Map::new(EntityId::generate(), "Test".to_string(), 1).unwrap();
+        let center = TileCoordinate::new(0, 0, 0);
+        map.set_tile(center, explored(TerrainType::Plains));
+        map.set_tile(
+            TileEdge::North.neighbor(center),
+            explored(TerrainType::Ocean),
+        );
+        map.set_tile(
+            TileEdge::South.neighbor(center),
+            explored(TerrainType::Plains),
+        );
+        map.set_tile(
+            TileEdge::East.neighbor(center),
+            explored(TerrainType::Mountains),
+        );
+        map.set_tile(
+            TileEdge::West.neighbor(center),
+            explored(TerrainType::Plains),
+        );
+
+        let transitions = resolve_transitions(&map, center, TerrainType::Plains, &TileEdge::ALL);
+
+        assert_eq!(
+            transitions.strips,
+            vec![
+                (TileEdge::North, TerrainType::Ocean),
+                (TileEdge::East, TerrainType::Mountains)
+            ]
+        );
+        assert!(transitions.deferred.is_empty());
+
+        // The ocean tile outranks plains, so it draws nothing on its side
+        let ocean = TileEdge::North.neighbor(center);
+        let transitions = resolve_transitions(&map, ocean, TerrainType::Ocean, &[TileEdge::South]);
+        assert!(transitions.strips.is_empty());
+    }
+
+    #[test]
+    fn missing_neighbors_are_deferred_until_generated() {
+        let mut map = Map::new(EntityId::generate(), "Test".to_string(), 1).unwrap();
+        // Last tile of one chunk; its east neighbor lives in the next chunk
+        let border = TileCoordinate::new(15, 3, 0);
+        map.set_tile(border, explored(TerrainType::Desert));
+
+        let transitions = resolve_transitions(&map, border, TerrainType::Desert, &TileEdge::ALL);
+        assert!(transitions.strips.is_empty());
+        assert_eq!(transitions.deferred.len(), 4);
+
+        // The neighboring chunk appears later
+        map.set_tile(
+            TileEdge::East.neighbor(border),
+            explored(TerrainType::Forest),
+        );
+        let transitions =
+            resolve_transitions(&map, border, TerrainType::Desert, &transitions.deferred);
+
+        assert_eq!(
+            transitions.strips,
+            vec![(TileEdge::East, TerrainType::Forest)]
+        );
+        assert_eq!(transitions.deferred.len(), 3);
+        assert!(!transitions.deferred.contains(&TileEdge::East));
+    }
+
+    #[test]
+    fn unexplored_neighbors_are_deferred() {
+        let mut map = Map::new(EntityId::generate(), "Test".to_string(), 1).unwrap();
+        let center = TileCoordinate::new(0, 0, 0);
+        map.set_tile(center, explored(TerrainType::Plains));
+        map.set_tile(
+            TileEdge::North.neighbor(center),
+            MapTile::new(TerrainType::Ocean, Elevation::sea_level(), false),
+        );
+
+        let transitions =
+            resolve_transitions(&map, center, TerrainType::Plains, &[TileEdge::North]);
+        assert!(transitions.strips.is_empty());
+        assert_eq!(transitions.deferred, vec![TileEdge::North]);
+    }
+
+    #[test]
+    fn strips_are_despawned_with_their_tile() {
+        let mut world = World::new();
+        let tile = world
+            .spawn(Name::new("Tile"))
+            .with_children(|parent| {
+                for edge in TileEdge::ALL {
+                    parent.spawn(transition_strip_bundle(
+                        edge,
+                        TerrainType::Ocean,
+                        2.0,
+                        Handle::default(),
+                        Handle::default(),
+                    ));
+                }
+            })
+            .id();
+
+        let mut strips = world.query::<&TransitionStrip>();
+        assert_eq!(strips.iter(&world).count(), 4);
+
+        world.entity_mut(tile).despawn();
+        assert_eq!(strips.iter(&world).count(), 0);
+    }
+
+    #[test]
+    fn strips_sit_on_their_edge() {
+        let north = TileEdge::North.strip_transform(2.0);
+        let west = TileEdge::West.strip_transform(2.0);
+
+        assert!(north.translation.z > 0.0);
+        assert!(west.translation.x < 0.0);
+        assert!(north.translation.y > TILE_TOP_OFFSET);
+        // Strips stay within the tile footprint
+        assert!(north.translation.z + north.scale.z / 2.0 <= 1.0 + f32::EPSILON);
+    }
+}
@@ -0,0 +1,217 @@
+//! Tile Staleness - Fading map memory and refreshes on return
+//!
+//! Keeps the staleness rules in step with the settings, the base and the
+//! current day, and runs the refresh pass whenever the player steps into
+//! another chunk of the surface. Rescue objectives and expedition waypoints
+//! are anchored so the refresh never moves ground a quest points at. The
+//! scanner uses the same rules to fade stale tiles and to show when a tile
+//! was last seen.
+
+use crate::domain::constants::STALE_DESATURATION;
+use crate::domain::entities::map::{Map, MapTile};
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{
+    chunk_of, StaleRefresh, StalenessRules, TileStalenessService, TimedObjective,
+};
+use crate::domain::value_objects::position::ChunkCoordinate;
+use crate::domain::value_objects::{Position3D, TileCoordinate};
+use crate::infrastructure::bevy::resources::{
+    BaseResource, GameStatsResource, MapResource, PlayerResource,
+};
+use crate::infrastructure::settings::StalenessSettings;
+use crate::presentation::game_state::RpgGameSession;
+use crate::presentation::map_renderer::mark_tiles_explored_system;
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+/// Plugin for stale tiles and their refresh
+pub struct TileStalenessPlugin;
+
+impl Plugin for TileStalenessPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StalenessSettings>()
+            .init_resource::<TileStaleness>()
+            .add_systems(
+                Update,
+                (update_staleness_rules, refresh_stale_chunk_system)
+                    .chain()
+                    .before(mark_tiles_explored_system),
+            );
+    }
+}
+
+/// Staleness rules for the current day
+#[derive(Resource, Debug, Clone)]
+pub struct TileStaleness {
+    service: TileStalenessService,
+    day: u32,
+    last_chunk: Option<ChunkCoordinate>,
+}
+
+impl Default for TileStaleness {
+    fn default() -> Self {
+        Self {
+            service: TileStalenessService::new(StalenessRules::new(
+                StalenessSettings::default().stale_after_days,
+                None,
+            )),
+            day: 1,
+            last_chunk: None,
+        }
+    }
+}
+
+impl TileStaleness {
+    /// Current day of the run
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// Check if a tile of `map` is stale today; interiors never are
+    pub fn is_stale(&self, map: &Map, coordinate: TileCoordinate, tile: &MapTile) -> bool {
+        !map.has_fixed_layout() && self.service.is_stale(coordinate, tile, self.day)
+    }
+
+    /// Scanner readout for a tile
+    pub fn describe(&self, map: &Map, coordinate: TileCoordinate, tile: &MapTile) -> String {
+        if !tile.is_explored() {
+            return "uncharted".to_string();
+        }
+        match tile.last_visited_day {
+            Some(day) if self.is_stale(map, coordinate, tile) => {
+                format!("last visited day {} (stale)", day)
+            }
+            Some(day) => format!("last visited day {}", day),
+            None => "charted from afar".to_string(),
+        }
+    }
+}
+
+/// Fade a scanner colour towards grey for a stale tile
+pub fn desaturate(color: Color) -> Color {
+    let linear = color.to_linear();
+    let grey = 0.2126 * linear.red + 0.7152 * linear.green + 0.0722 * linear.blue;
+    let fade = |channel: f32| channel + (grey - channel) * STALE_DESATURATION;
+    Color::LinearRgba(LinearRgba::new(
+        fade(linear.red),
+        fade(linear.green),
+        fade(linear.blue),
+        linear.alpha,
+    ))
+}
+
+/// Positions the refresh must not touch: quest objectives and waypoints
+pub fn staleness_anchors(
+    objective: Option<&TimedObjective>,
+    session: Option<&RpgGameSession>,
+) -> HashSet<Position3D> {
+    let mut anchors: HashSet<Position3D> = objective
+        .and_then(|objective| objective.active())
+        .map(|signal| signal.target)
+        .into_iter()
+        .collect();
+    if let Some(plan) = session.and_then(|session| session.active_expedition.as_ref()) {
+        anchors.extend(plan.waypoints().iter().copied());
+    }
+    anchors
+}
+
+/// Follow the settings, the base and the day
+fn update_staleness_rules(
+    settings: Res<StalenessSettings>,
+    base_resource: Res<BaseResource>,
+    game_stats: Res<GameStatsResource>,
+    mut staleness: ResMut<TileStaleness>,
+) {
+    let rules = StalenessRules::new(settings.stale_after_days, base_resource.base_position());
+    let day = game_stats.current_day();
+    if *staleness.service.rules() != rules || staleness.day != day {
+        staleness.service = TileStalenessService::new(rules);
+        staleness.day = day;
+    }
+}
+
+/// Refresh the stale tiles of a chunk when the player enters it
+fn refresh_stale_chunk_system(
+    mut staleness: ResMut<TileStaleness>,
+    mut map_resource: ResMut<MapResource>,
+    player_resource: Res<PlayerResource>,
+    objective: Option<Res<TimedObjective>>,
+    session: Option<Res<RpgGameSession>>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    if map_resource.is_in_interior() {
+        return;
+    }
+    let Some(position) = player_resource.player_position() else {
+        return;
+    };
+    let chunk = chunk_of(TileCoordinate::from(position));
+    if staleness.last_chunk == Some(chunk) {
+        return;
+    }
+    staleness.last_chunk = Some(chunk);
+    let Some(map) = map_resource.current_map_mut() else {
+        return;
+    };
+    if map.has_fixed_layout() {
+        return;
+    }
+
+    let anchors = staleness_anchors(objective.as_deref(), session.as_deref());
+    let report = staleness
+        .service
+        .refresh_chunk(map, chunk, staleness.day, &anchors);
+    if report.changed_anything() {
+        game_log.log_message(refresh_message(&report), GameLogType::Discovery);
+    }
+}
+
+/// Game log line for a refresh that changed something
+fn refresh_message(report: &StaleRefresh) -> String {
+    let mut changes = Vec::new();
+    if report.tiles_rerolled > 0 {
+        changes.push(format!("{} tiles look different", report.tiles_rerolled));
+    }
+    if report.nodes_regrown > 0 {
+        changes.push(format!("{} deposits grew back", report.nodes_regrown));
+    }
+    if report.nodes_shifted > 0 {
+        changes.push(format!("{} deposits shifted", report.nodes_shifted));
+    }
+    format!(
+        "🍂 This region changed since your last visit: {}",
+        changes.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::terrain::{Elevation, TerrainType};
+    use crate::domain::value_objects::EntityId;
+
+    #[test]
+    fn scanner_readout_names_the_last_visit_and_fades_stale_tiles() {
+        let map = Map::new(EntityId::generate(), "Scan".to_string(), 7).unwrap();
+        let coordinate = TileCoordinate::new(40, 0, 0);
+        let mut tile = MapTile::new(TerrainType::Forest, Elevation::sea_level(), false);
+        let mut staleness = TileStaleness::default();
+        assert_eq!(staleness.describe(&map, coordinate, &tile), "uncharted");
+
+        tile.visit(4);
+        staleness.day = 10;
+        assert_eq!(
+            staleness.describe(&map, coordinate, &tile),
+            "last visited day 4"
+        );
+        staleness.day = 30;
+        assert_eq!(
+            staleness.describe(&map, coordinate, &tile),
+            "last visited day 4 (stale)"
+        );
+
+        let faded = desaturate(Color::srgb(0.2, 0.8, 0.2)).to_srgba();
+        assert!(faded.green < 0.8 && faded.red > 0.2);
+    }
+}
@@ -0,0 +1,333 @@
+//! Transient Pool - Recycling short-lived entities
+//!
+//! One-shot sound effects and tile highlights come and go
+//! many times a minute. Rather than spawning and despawning each of them,
+//! their entities are parked in a pool per category once they are done and
+//! handed out again on the next request. Parking an entity resets it: every
+//! component but the pool tag is stripped and visual ones are hidden, so
+//! nothing of its last use leaks into the next one. Each category keeps at
+//! most a set number of entities; past that, acquiring spawns a plain
+//! entity and releasing despawns it as before. Parked entities are dropped
+//! whenever the app state changes.
+
+use crate::presentation::RpgAppState;
+use bevy::audio::PlaybackMode;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Plugin for the transient entity pools
+pub struct TransientPoolPlugin;
+
+impl Plugin for TransientPoolPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TransientPools>()
+            .add_systems(
+                Update,
+                clear_pools_on_state_exit.run_if(state_changed::<RpgAppState>),
+            )
+            // Sinks are created in PostUpdate, after the sounds were queued
+            .add_systems(Last, release_finished_sfx);
+    }
+}
+
+/// Categories of pooled entities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransientKind {
+    /// One-shot sound effect players
+    Sfx,
+    /// Translucent overlays on map tiles
+    TileHighlight,
+}
+
+impl TransientKind {
+    /// Get all categories
+    pub fn all() -> [TransientKind; 2] {
+        [TransientKind::Sfx, TransientKind::TileHighlight]
+    }
+
+    /// Entities a category keeps by default, in use and parked together
+    pub fn default_limit(&self) -> usize {
+        match self {
+            TransientKind::Sfx => 24,
+            TransientKind::TileHighlight => 128,
+        }
+    }
+
+    /// Whether entities of this category are drawn
+    fn is_visual(&self) -> bool {
+        !matches!(self, TransientKind::Sfx)
+    }
+
+    /// Strip an entity back to the pool tag, ready for its next use
+    ///
+    /// Sound players lose their player, settings and sink; visual entities
+    /// lose everything they were given and are hidden.
+    pub fn reset(&self, entity: &mut EntityCommands) {
+        match self {
+            TransientKind::Sfx => {
+                entity.retain::<Pooled>();
+            }
+            TransientKind::TileHighlight => {
+                entity.retain::<Pooled>().insert(Visibility::Hidden);
+            }
+        }
+    }
+}
+
+/// Tag of an entity owned by a pool, in use or parked
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pooled(pub TransientKind);
+
+/// Pools of recyclable entities, one per category
+#[derive(Resource, Debug)]
+pub struct TransientPools {
+    limits: HashMap<TransientKind, usize>,
+    parked: HashMap<TransientKind, Vec<Entity>>,
+    in_use: HashMap<Entity, TransientKind>,
+}
+
+impl Default for TransientPools {
+    fn default() -> Self {
+        Self {
+            limits: TransientKind::all()
+                .into_iter()
+                .map(|kind| (kind, kind.default_limit()))
+                .collect(),
+            parked: HashMap::new(),
+            in_use: HashMap::new(),
+        }
+    }
+}
+
+impl TransientPools {
+    /// Change how many entities a category keeps
+    pub fn set_limit(&mut self, kind: TransientKind, limit: usize) {
+        self.limits.insert(kind, limit);
+    }
+
+    /// Entities of a category waiting to be reused
+    pub fn parked(&self, kind: TransientKind) -> usize {
+        self.parked.get(&kind).map_or(0, Vec::len)
+    }
+
+    /// Entities of a category handed out and not yet released
+    pub fn in_use(&self, kind: TransientKind) -> usize {
+        self.in_use.values().filter(|&&used| used == kind).count()
+    }
+
+    /// Get an entity carrying `bundle`, reused from the pool when possible
+    ///
+    /// Once a category holds as many entities as its limit, a plain entity
+    /// is spawned instead; releasing that one despawns it.
+    pub fn acquire(
+        &mut self,
+        commands: &mut Commands,
+        kind: TransientKind,
+        bundle: impl Bundle,
+    ) -> Entity {
+        let entity = match self.parked.get_mut(&kind).and_then(Vec::pop) {
+            Some(entity) => entity,
+            None => {
+                let limit = self.limits.get(&kind).copied().unwrap_or(0);
+                if self.in_use(kind) >= limit {
+                    return commands.spawn(bundle).id();
+                }
+                commands.spawn(Pooled(kind)).id()
+            }
+        };
+        let mut entity_commands = commands.entity(entity);
+        if kind.is_visual() {
+            entity_commands.insert(Visibility::Inherited);
+        }
+        entity_commands.insert(bundle);
+        self.in_use.insert(entity, kind);
+        entity
+    }
+
+    /// Hand an entity back: reset and parked, or despawned past the limit
+    pub fn release(&mut self, commands: &mut Commands, entity: Entity) {
+        if self.parked.values().any(|parked| parked.contains(&entity)) {
+            return;
+        }
+        let Ok(mut entity_commands) = commands.get_entity(entity) else {
+            self.in_use.remove(&entity);
+            return;
+        };
+        let Some(kind) = self.in_use.remove(&entity) else {
+            entity_commands.try_despawn();
+            return;
+        };
+        let limit = self.limits.get(&kind).copied().unwrap_or(0);
+        if self.parked(kind) + self.in_use(kind) >= limit {
+            entity_commands.try_despawn();
+            return;
+        }
+        kind.reset(&mut entity_commands);
+        self.parked.entry(kind).or_default().push(entity);
+    }
+
+    /// Despawn every parked entity; those in use are still released later
+    pub fn clear(&mut self, commands: &mut Commands) {
+        for (_, parked) in self.parked.drain() {
+            for entity in parked {
+                if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                    entity_commands.try_despawn();
+                }
+            }
+        }
+    }
+}
+
+/// Queue a one-shot sound on a pooled player
+///
+/// Pooled players always play once and stay, so that they can be released
+/// when their sink runs dry.
+pub fn play_pooled_sfx(
+    commands: &mut Commands,
+    pools: &mut TransientPools,
+    handle: Handle<AudioSource>,
+    playback: PlaybackSettings,
+) -> Entity {
+    let playback = PlaybackSettings {
+        mode: PlaybackMode::Once,
+        ..playback
+    };
+    pools.acquire(
+        commands,
+        TransientKind::Sfx,
+        (AudioPlayer::new(handle), playback),
+    )
+}
+
+/// Park sound players whose sound has finished
+fn release_finished_sfx(
+    mut commands: Commands,
+    mut pools: ResMut<TransientPools>,
+    players: Query<(Entity, &AudioSink, &Pooled)>,
+) {
+    for (entity, sink, pooled) in players.iter() {
+        if pooled.0 == TransientKind::Sfx && sink.empty() {
+            pools.release(&mut commands, entity);
+        }
+    }
+}
+
+/// Drop everything parked when the app state changes
+fn clear_pools_on_state_exit(mut commands: Commands, mut pools: ResMut<TransientPools>) {
+    pools.clear(&mut commands);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::state::app::StatesPlugin;
+
+    #[derive(Component)]
+    struct Marker;
+
+    fn highlight(world: &mut World) -> Entity {
+        world
+            .run_system_once(
+                |mut commands: Commands, mut pools: ResMut<TransientPools>| {
+                    pools.acquire(
+                        &mut commands,
+                        TransientKind::TileHighlight,
+                        (Transform::default(), Marker),
+                    )
+                },
+            )
+            .unwrap()
+    }
+
+    fn release(world: &mut World, entity: Entity) {
+        world
+            .run_system_once(
+                move |mut commands: Commands, mut pools: ResMut<TransientPools>| {
+                    pools.release(&mut commands, entity);
+                },
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn released_entities_are_reset_and_handed_out_again() {
+        let mut world = World::new();
+        world.init_resource::<TransientPools>();
+
+        let first = highlight(&mut world);
+        release(&mut world, first);
+        let parked = world.entity(first);
+        assert!(parked.contains::<Pooled>());
+        assert!(!parked.contains::<Marker>());
+        assert!(!parked.contains::<Transform>());
+        assert_eq!(parked.get::<Visibility>(), Some(&Visibility::Hidden));
+        assert_eq!(
+            world
+                .resource::<TransientPools>()
+                .parked(TransientKind::TileHighlight),
+            1
+        );
+
+        let second = highlight(&mut world);
+        assert_eq!(second, first);
+        let reused = world.entity(second);
+        assert!(reused.contains::<Marker>());
+        assert_eq!(reused.get::<Visibility>(), Some(&Visibility::Inherited));
+        assert_eq!(
+            world
+                .resource::<TransientPools>()
+                .parked(TransientKind::TileHighlight),
+            0
+        );
+    }
+
+    #[test]
+    fn past_the_limit_entities_are_spawned_and_despawned_as_usual() {
+        let mut world = World::new();
+        let mut pools = TransientPools::default();
+        pools.set_limit(TransientKind::TileHighlight, 1);
+        world.insert_resource(pools);
+
+        let pooled = highlight(&mut world);
+        let overflow = highlight(&mut world);
+        assert!(world.entity(pooled).contains::<Pooled>());
+        assert!(!world.entity(overflow).contains::<Pooled>());
+
+        release(&mut world, overflow);
+        assert!(world.get_entity(overflow).is_err());
+        release(&mut world, pooled);
+        assert!(world.get_entity(pooled).is_ok());
+        assert_eq!(
+            world
+                .resource::<TransientPools>()
+                .parked(TransientKind::TileHighlight),
+            1
+        );
+    }
+
+    #[test]
+    fn parked_entities_are_dropped_when_the_state_changes() {
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin)
+            .insert_state(RpgAppState::Exploration)
+            .add_plugins(TransientPoolPlugin);
+        app.update();
+
+        let parked = highlight(app.world_mut());
+        let in_use = highlight(app.world_mut());
+        release(app.world_mut(), parked);
+        app.update();
+        assert!(app.world().get_entity(parked).is_ok());
+
+        app.world_mut()
+            .resource_mut::<NextState<RpgAppState>>()
+            .set(RpgAppState::Paused);
+        app.update();
+        assert!(app.world().get_entity(parked).is_err());
+        assert!(app.world().get_entity(in_use).is_ok());
+        let pools = app.world().resource::<TransientPools>();
+        assert_eq!(pools.parked(TransientKind::TileHighlight), 0);
+        assert_eq!(pools.in_use(TransientKind::TileHighlight), 1);
+    }
+}
@@ -0,0 +1,242 @@
+//! Warning Layer - Heartbeat and screen-edge vignette when in danger
+//!
+//! Whenever the player changes, the warning state service rates how close
+//! they are to dying or starving. While a warning is active a looping
+//! heartbeat plays in the ambient category, louder the worse it gets, and
+//! the screen edges glow red for low health or amber for an empty Food
+//! hold, pulsing slowly. Both stop the moment the warning clears. A warning
+//! beginning also requests a one-shot cue through the sound arbiter.
+//!
+//! Reduce motion holds the vignette still; the high contrast palette draws
+//! a solid border instead of the translucent band.
+
+use crate::application::services::{VignetteStyle, WarningStateService, WarningTone};
+use crate::domain::constants::{
+    CRITICAL_TEXT, WARNING_BORDER_WIDTH, WARNING_HEARTBEAT_VOLUME, WARNING_PULSE_SECONDS,
+    WARNING_TEXT, WARNING_VIGNETTE_ALPHA, WARNING_VIGNETTE_WIDTH,
+};
+use crate::infrastructure::bevy::resources::PlayerResource;
+use crate::presentation::audio_integration::{
+    spawn_world_sfx, AudioAssets, AudioCategory, AudioSettings, GlobalAudioSettings, SfxArbiter,
+    SfxPriority,
+};
+use crate::presentation::map_renderer::{MapPalette, MapRenderConfig};
+use crate::presentation::rendering::DisplaySettings;
+use bevy::prelude::*;
+
+/// Plugin for the low health and starvation warnings
+pub struct WarningLayerPlugin;
+
+impl Plugin for WarningLayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WarningLayer>()
+            .add_systems(Startup, setup_warning_vignette)
+            .add_systems(
+                Update,
+                (
+                    update_warning_state,
+                    drive_warning_heartbeat,
+                    drive_warning_vignette,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// The warning state and the heartbeat playing for it
+#[derive(Resource, Debug, Default)]
+pub struct WarningLayer {
+    pub service: WarningStateService,
+    heartbeat: Option<Entity>,
+}
+
+/// Marker for the vignette node
+#[derive(Component)]
+struct WarningVignette;
+
+fn setup_warning_vignette(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(0.0),
+            top: Val::Px(0.0),
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BorderColor(Color::NONE),
+        Visibility::Hidden,
+        GlobalZIndex(5),
+        WarningVignette,
+        Name::new("WarningVignette"),
+    ));
+}
+
+/// Rate the warning again whenever the player changes
+fn update_warning_state(
+    player_resource: Res<PlayerResource>,
+    mut layer: ResMut<WarningLayer>,
+    audio_assets: Option<Res<AudioAssets>>,
+    audio_settings: Res<GlobalAudioSettings>,
+    mut sfx: ResMut<SfxArbiter>,
+) {
+    if !player_resource.is_changed() {
+        return;
+    }
+    let before = layer.service.current();
+    let after = match player_resource.get_player() {
+        Some(player) => layer
+            .service
+            .update_from_player(player, Some(player.health().fraction())),
+        None => {
+            layer.service = WarningStateService::new();
+            None
+        }
+    };
+
+    let began = after.is_some_and(|after| before.is_none_or(|before| before.tone != after.tone));
+    if !began {
+        return;
+    }
+    if let Some(cue) = audio_assets
+        .as_ref()
+        .and_then(|assets| assets.warning_cue.as_ref())
+    {
+        spawn_world_sfx(
+            &mut sfx,
+            &audio_settings,
+            AudioCategory::Sfx,
+            SfxPriority::Warning,
+            cue,
+        );
+    }
+}
+
+/// Keep one looping heartbeat playing at the warning's severity
+fn drive_warning_heartbeat(
+    mut commands: Commands,
+    mut layer: ResMut<WarningLayer>,
+    audio_assets: Option<Res<AudioAssets>>,
+    audio_settings: Res<GlobalAudioSettings>,
+    volumes: Res<AudioSettings>,
+    mut audio_sinks: Query<&mut AudioSink>,
+) {
+    let volume = layer
+        .service
+        .current()
+        .filter(|_| audio_settings.can_play(AudioCategory::Ambient))
+        .map(|warning| {
+            warning.severity * WARNING_HEARTBEAT_VOLUME * volumes.gain(AudioCategory::Ambient)
+        });
+
+    match (volume, layer.heartbeat) {
+        (Some(volume), Some(entity)) => {
+            if let Ok(mut sink) = audio_sinks.get_mut(entity) {
+                sink.set_volume(bevy::audio::Volume::Linear(volume));
+            }
+        }
+        (Some(volume), None) => {
+            let Some(handle) = audio_assets
+                .as_ref()
+                .and_then(|assets| assets.warning_heartbeat.clone())
+            else {
+                return;
+            };
+            let entity = commands
+                .spawn((
+                    AudioPlayer::new(handle),
+                    PlaybackSettings::LOOP.with_volume(bevy::audio::Volume::Linear(volume)),
+                    Name::new("WarningHeartbeat"),
+                ))
+                .id();
+            layer.heartbeat = Some(entity);
+        }
+        (None, Some(entity)) => {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.despawn();
+            }
+            layer.heartbeat = None;
+        }
+        (None, None) => {}
+    }
+}
+
+/// Colour of a warning's vignette
+fn tone_color(tone: WarningTone) -> Color {
+    match tone {
+        WarningTone::Amber => WARNING_TEXT,
+        WarningTone::Red => CRITICAL_TEXT,
+    }
+}
+
+/// Opacity of the vignette `elapsed` seconds in
+fn vignette_alpha(style: VignetteStyle, severity: f32, elapsed: f32) -> f32 {
+    let base = if style.border {
+        1.0
+    } else {
+        WARNING_VIGNETTE_ALPHA * severity
+    };
+    if !style.pulsing {
+        return base;
+    }
+    let phase = elapsed / WARNING_PULSE_SECONDS * std::f32::consts::TAU;
+    base * (0.7 + 0.3 * phase.sin())
+}
+
+/// Show, colour and pulse the vignette for the current warning
+fn drive_warning_vignette(
+    time: Res<Time>,
+    layer: Res<WarningLayer>,
+    display: Option<Res<DisplaySettings>>,
+    render_config: Option<Res<MapRenderConfig>>,
+    mut vignettes: Query<(&mut Node, &mut BorderColor, &mut Visibility), With<WarningVignette>>,
+) {
+    let Ok((mut node, mut border_color, mut visibility)) = vignettes.single_mut() else {
+        return;
+    };
+    let Some(warning) = layer.service.current() else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+
+    let style = VignetteStyle::select(
+        display.is_some_and(|display| display.reduce_motion),
+        render_config.is_some_and(|config| config.palette == MapPalette::HighContrast),
+    );
+    let width = if style.border {
+        WARNING_BORDER_WIDTH
+    } else {
+        WARNING_VIGNETTE_WIDTH
+    };
+    let border = UiRect::all(Val::Px(width));
+    if node.border != border {
+        node.border = border;
+    }
+    let alpha = vignette_alpha(style, warning.severity, time.elapsed_secs());
+    border_color.0 = tone_color(warning.tone).with_alpha(alpha);
+    visibility.set_if_neq(Visibility::Inherited);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reduce_motion_holds_the_vignette_still() {
+        let pulsing = VignetteStyle::select(false, false);
+        let still = VignetteStyle::select(true, false);
+        let samples: Vec<f32> = (0..8)
+            .map(|step| vignette_alpha(still, 0.5, step as f32 * 0.3))
+            .collect();
+        assert!(samples.iter().all(|alpha| *alpha == samples[0]));
+        assert_ne!(
+            vignette_alpha(pulsing, 0.5, 0.0),
+            vignette_alpha(pulsing, 0.5, WARNING_PULSE_SECONDS / 4.0)
+        );
+
+        // The high contrast border is opaque whatever the severity
+        let border = VignetteStyle::select(true, true);
+        assert_eq!(vignette_alpha(border, 0.25, 1.0), 1.0);
+        assert!(vignette_alpha(still, 0.25, 1.0) < vignette_alpha(still, 1.0, 1.0));
+    }
+}
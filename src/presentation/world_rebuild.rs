@@ -0,0 +1,643 @@
+//! World Rebuild - Regenerating the world without restarting the app
+//!
+//! A [`WorldRebuildRequest`] tears down everything drawn from the map and
+//! builds it again, one stage per frame so the app never hitches: input is
+//! frozen, the [`WorldTeardown`] schedule runs every plugin's cleanup system,
+//! the map and what depends on it are reset, a new overworld is generated
+//! around the player or the requested spawn, and input comes back. The
+//! request says what survives; [`WorldRebuilt`] is sent once it is done.
+//!
+//! A request arriving before the new world is generated is folded into the
+//! running rebuild. One arriving later waits and runs right after it, so
+//! two rebuilds never interleave.
+
+use crate::domain::constants::{HANDOVER_BACKGROUND, PRIMARY_TEXT};
+use crate::domain::services::font_service::FontSize;
+use crate::domain::value_objects::Position3D;
+use crate::infrastructure::bevy::resources::{
+    BaseResource, GameStatsResource, MapResource, PlayerResource,
+};
+use crate::presentation::base_visuals::{BaseChange, BaseChanged};
+use crate::presentation::game_state::RpgGameSession;
+use crate::presentation::scenarios::StartingCharacter;
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+/// Plugin for rebuilding the world on request
+pub struct WorldRebuildPlugin;
+
+impl Plugin for WorldRebuildPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WorldRebuild>()
+            .init_schedule(WorldTeardown)
+            .add_event::<WorldRebuildRequest>()
+            .add_event::<WorldRebuilt>()
+            .add_event::<BaseChanged>()
+            .add_systems(Startup, setup_rebuild_overlay)
+            .add_systems(PreUpdate, freeze_input_system.after(InputSystem))
+            .add_systems(
+                Update,
+                (
+                    receive_rebuild_requests,
+                    world_teardown_system,
+                    advance_world_rebuild_system,
+                    update_rebuild_overlay_system,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Schedule of the cleanup systems plugins register for a rebuild
+///
+/// It runs once per rebuild, after input is frozen and before anything is
+/// reset; commands queued in it are applied before the map is.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WorldTeardown;
+
+/// What a rebuild keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RebuildPreserve {
+    /// Keep the character, moved to the spawn; otherwise a new one starts there
+    pub player: bool,
+    /// Keep the base; otherwise a new one is founded on the spawn
+    pub base: bool,
+    /// Keep the run statistics
+    pub stats: bool,
+}
+
+impl Default for RebuildPreserve {
+    fn default() -> Self {
+        Self {
+            player: true,
+            base: true,
+            stats: true,
+        }
+    }
+}
+
+impl RebuildPreserve {
+    /// Keep only what both sides keep
+    fn and(self, other: Self) -> Self {
+        Self {
+            player: self.player && other.player,
+            base: self.base && other.base,
+            stats: self.stats && other.stats,
+        }
+    }
+}
+
+/// Ask for the world to be torn down and generated again
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WorldRebuildRequest {
+    /// Seed of the new overworld; the current one when unset
+    pub seed: Option<u64>,
+    /// Tile to rebuild around; the player's when unset
+    pub spawn: Option<Position3D>,
+    pub preserve: RebuildPreserve,
+}
+
+impl WorldRebuildRequest {
+    /// Regenerate the current world around the player, keeping everything
+    pub fn regenerate() -> Self {
+        Self::default()
+    }
+
+    /// Generate from another seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Rebuild around another tile
+    pub fn at(mut self, spawn: Position3D) -> Self {
+        self.spawn = Some(spawn);
+        self
+    }
+
+    /// Keep only what `preserve` names
+    pub fn preserving(mut self, preserve: RebuildPreserve) -> Self {
+        self.preserve = preserve;
+        self
+    }
+
+    /// One request doing the work of both; the later one's choices win
+    fn merge(self, later: Self) -> Self {
+        Self {
+            seed: later.seed.or(self.seed),
+            spawn: later.spawn.or(self.spawn),
+            preserve: self.preserve.and(later.preserve),
+        }
+    }
+}
+
+/// Sent once a rebuild has finished and input is back
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorldRebuilt {
+    pub seed: u64,
+    pub spawn: Position3D,
+}
+
+/// Step of a rebuild; each takes one frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RebuildStage {
+    #[default]
+    Idle,
+    Freeze,
+    Teardown,
+    Reset,
+    Regenerate,
+    Resume,
+}
+
+impl RebuildStage {
+    /// Stage after this one
+    fn next(self) -> Self {
+        match self {
+            RebuildStage::Idle => RebuildStage::Idle,
+            RebuildStage::Freeze => RebuildStage::Teardown,
+            RebuildStage::Teardown => RebuildStage::Reset,
+            RebuildStage::Reset => RebuildStage::Regenerate,
+            RebuildStage::Regenerate => RebuildStage::Resume,
+            RebuildStage::Resume => RebuildStage::Idle,
+        }
+    }
+
+    /// Share of the rebuild done once the stage is over, from 0 to 1
+    pub fn progress(self) -> f32 {
+        match self {
+            RebuildStage::Idle => 0.0,
+            RebuildStage::Freeze => 0.2,
+            RebuildStage::Teardown => 0.4,
+            RebuildStage::Reset => 0.6,
+            RebuildStage::Regenerate => 0.8,
+            RebuildStage::Resume => 1.0,
+        }
+    }
+
+    /// What the overlay says during the stage
+    pub fn label(self) -> &'static str {
+        match self {
+            RebuildStage::Idle => "",
+            RebuildStage::Freeze => "Holding controls",
+            RebuildStage::Teardown => "Clearing the old world",
+            RebuildStage::Reset => "Resetting the map",
+            RebuildStage::Regenerate => "Generating terrain",
+            RebuildStage::Resume => "Handing back control",
+        }
+    }
+}
+
+/// The rebuild under way and the one waiting behind it
+#[derive(Resource, Debug, Default)]
+pub struct WorldRebuild {
+    stage: RebuildStage,
+    request: Option<WorldRebuildRequest>,
+    queued: Option<WorldRebuildRequest>,
+}
+
+impl WorldRebuild {
+    /// Take a request: start, fold into the running rebuild or queue it
+    pub fn request(&mut self, request: WorldRebuildRequest) {
+        match self.stage {
+            RebuildStage::Idle => {
+                self.stage = RebuildStage::Freeze;
+                self.request = Some(request);
+            }
+            RebuildStage::Freeze | RebuildStage::Teardown | RebuildStage::Reset => {
+                self.request = Some(match self.request {
+                    Some(running) => running.merge(request),
+                    None => request,
+                });
+            }
+            RebuildStage::Regenerate | RebuildStage::Resume => {
+                self.queued = Some(match self.queued {
+                    Some(queued) => queued.merge(request),
+                    None => request,
+                });
+            }
+        }
+    }
+
+    /// Move to the next stage, starting the queued rebuild after the last
+    pub fn advance(&mut self) -> RebuildStage {
+        self.stage = self.stage.next();
+        if self.stage == RebuildStage::Idle {
+            self.request = self.queued.take();
+            if self.request.is_some() {
+                self.stage = RebuildStage::Freeze;
+            }
+        }
+        self.stage
+    }
+
+    /// Stage the rebuild is in
+    pub fn stage(&self) -> RebuildStage {
+        self.stage
+    }
+
+    /// The running request
+    pub fn current(&self) -> Option<WorldRebuildRequest> {
+        self.request
+    }
+
+    /// Check if a rebuild is waiting behind the running one
+    pub fn has_queued(&self) -> bool {
+        self.queued.is_some()
+    }
+
+    /// Check if input is held for a rebuild
+    pub fn is_frozen(&self) -> bool {
+        self.stage != RebuildStage::Idle
+    }
+}
+
+/// Marker for the rebuild overlay
+#[derive(Component)]
+pub struct RebuildOverlay;
+
+/// Marker for the rebuild progress text
+#[derive(Component)]
+pub struct RebuildOverlayText;
+
+fn setup_rebuild_overlay(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(HANDOVER_BACKGROUND.with_alpha(0.85)),
+            GlobalZIndex(30),
+            Visibility::Hidden,
+            RebuildOverlay,
+            Name::new("RebuildOverlay"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Large.to_pixels(),
+                    ..default()
+                },
+                TextColor(PRIMARY_TEXT),
+                TextLayout::new_with_justify(JustifyText::Center),
+                RebuildOverlayText,
+            ));
+        });
+}
+
+/// Swallow keys and clicks while a rebuild is running
+fn freeze_input_system(
+    rebuild: Res<WorldRebuild>,
+    keyboard: Option<ResMut<ButtonInput<KeyCode>>>,
+    mouse: Option<ResMut<ButtonInput<MouseButton>>>,
+) {
+    if !rebuild.is_frozen() {
+        return;
+    }
+    if let Some(mut keyboard) = keyboard {
+        keyboard.reset_all();
+    }
+    if let Some(mut mouse) = mouse {
+        mouse.reset_all();
+    }
+}
+
+fn receive_rebuild_requests(
+    mut requests: EventReader<WorldRebuildRequest>,
+    mut rebuild: ResMut<WorldRebuild>,
+) {
+    for request in requests.read() {
+        if rebuild.is_frozen() {
+            info!("🌍 World rebuild requested again - folding it in");
+        }
+        rebuild.request(*request);
+    }
+}
+
+/// Run the cleanup systems during the teardown stage
+fn world_teardown_system(world: &mut World) {
+    if world.resource::<WorldRebuild>().stage() != RebuildStage::Teardown {
+        return;
+    }
+    // A world without cleanup systems has nothing to tear down
+    let _ = world.try_run_schedule(WorldTeardown);
+}
+
+/// Do the current stage's work and move on to the next
+#[allow(clippy::too_many_arguments)]
+fn advance_world_rebuild_system(
+    mut rebuild: ResMut<WorldRebuild>,
+    mut map_resource: ResMut<MapResource>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut base_resource: ResMut<BaseResource>,
+    mut game_stats: ResMut<GameStatsResource>,
+    mut session: ResMut<RpgGameSession>,
+    character: Option<Res<StartingCharacter>>,
+    mut base_events: EventWriter<BaseChanged>,
+    mut rebuilt: EventWriter<WorldRebuilt>,
+) {
+    let Some(mut request) = rebuild.current() else {
+        return;
+    };
+    match rebuild.stage() {
+        RebuildStage::Idle | RebuildStage::Freeze | RebuildStage::Teardown => {}
+        RebuildStage::Reset => {
+            // Pin the seed and spawn before the map they come from is gone
+            let seed = request
+                .seed
+                .or_else(|| map_resource.overworld().map(|map| map.seed()))
+                .unwrap_or(0);
+            let spawn = request
+                .spawn
+                .or_else(|| player_resource.player_position())
+                .unwrap_or_else(Position3D::origin);
+            request = request.with_seed(seed).at(spawn);
+            rebuild.request = Some(request);
+
+            *map_resource = MapResource::new();
+            if !request.preserve.stats {
+                game_stats.reset();
+            }
+        }
+        RebuildStage::Regenerate => {
+            let (seed, spawn) = (request.seed.unwrap_or(0), request.spawn.unwrap_or_default());
+            map_resource.generate_overworld(spawn, seed);
+            map_resource.prepare_start_area(spawn, 0);
+
+            if request.preserve.player {
+                player_resource.set_position(spawn);
+            } else {
+                let character = character.as_deref().cloned().unwrap_or_default();
+                if let Err(e) = player_resource.create_player(
+                    "player_001".to_string(),
+                    character.name,
+                    spawn,
+                    character.stats,
+                ) {
+                    error!("Failed to create a player for the rebuilt world: {}", e);
+                }
+            }
+            session.current_position = spawn;
+
+            if !request.preserve.base {
+                match base_resource.create_base("Central Command".to_string(), spawn) {
+                    Ok(()) => {
+                        if let Some(base) = base_resource.base() {
+                            session.base = base.clone();
+                        }
+                        base_events.write(BaseChanged::new(BaseChange::Founded));
+                    }
+                    Err(e) => error!("Failed to found a base for the rebuilt world: {}", e),
+                }
+            }
+        }
+        RebuildStage::Resume => {
+            let (seed, spawn) = (request.seed.unwrap_or(0), request.spawn.unwrap_or_default());
+            info!(
+                "🌍 World rebuilt around ({}, {}) from seed {}",
+                spawn.x, spawn.y, seed
+            );
+            rebuilt.write(WorldRebuilt { seed, spawn });
+        }
+    }
+    rebuild.advance();
+}
+
+/// Show the overlay with the stage and its progress during a rebuild
+fn update_rebuild_overlay_system(
+    rebuild: Res<WorldRebuild>,
+    mut overlays: Query<&mut Visibility, With<RebuildOverlay>>,
+    mut texts: Query<&mut Text, With<RebuildOverlayText>>,
+) {
+    if !rebuild.is_changed() {
+        return;
+    }
+    let stage = rebuild.stage();
+    for mut visibility in overlays.iter_mut() {
+        *visibility = if rebuild.is_frozen() {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+    for mut text in texts.iter_mut() {
+        text.0 = format!(
+            "REBUILDING THE WORLD\n\n{}... {:.0}%",
+            stage.label(),
+            stage.progress() * 100.0
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Base, EntityId, Player, PlayerStats};
+
+    /// What the test systems saw, in order
+    #[derive(Resource, Default)]
+    struct Probe(Vec<String>);
+
+    fn rebuild_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(WorldRebuildPlugin)
+            .init_resource::<Probe>();
+
+        let mut map_resource = MapResource::new();
+        map_resource.generate_overworld(Position3D::origin(), 7);
+        let mut player_resource = PlayerResource::new();
+        player_resource
+            .create_player(
+                "rook".to_string(),
+                "Rook".to_string(),
+                Position3D::new(3, 4, 0),
+                PlayerStats::new(10, 10, 10, 10, 10, 10).unwrap(),
+            )
+            .unwrap();
+        let mut base_resource = BaseResource::new();
+        base_resource
+            .create_base("Outpost".to_string(), Position3D::origin())
+            .unwrap();
+        let mut game_stats = GameStatsResource::new();
+        game_stats.record_quest_completion();
+        app.insert_resource(map_resource)
+            .insert_resource(player_resource)
+            .insert_resource(base_resource)
+            .insert_resource(game_stats)
+            .insert_resource(RpgGameSession::new(
+                Player::create_new_character("Rook".to_string(), Position3D::origin()).unwrap(),
+                Base::new(
+                    EntityId::generate(),
+                    "Outpost".to_string(),
+                    Position3D::origin(),
+                )
+                .unwrap(),
+            ));
+
+        app.add_systems(
+            WorldTeardown,
+            |map_resource: Res<MapResource>, mut probe: ResMut<Probe>| {
+                let seed = map_resource.overworld().map(|map| map.seed());
+                probe.0.push(format!("teardown {:?}", seed));
+            },
+        );
+        app.add_systems(
+            Update,
+            (|mut rebuilt: EventReader<WorldRebuilt>, mut probe: ResMut<Probe>| {
+                for done in rebuilt.read() {
+                    probe.0.push(format!("rebuilt {}", done.seed));
+                }
+            })
+            .after(advance_world_rebuild_system),
+        );
+        app.update();
+        app
+    }
+
+    fn seed(app: &App) -> Option<u64> {
+        app.world()
+            .resource::<MapResource>()
+            .overworld()
+            .map(|map| map.seed())
+    }
+
+    fn stage(app: &App) -> RebuildStage {
+        app.world().resource::<WorldRebuild>().stage()
+    }
+
+    #[test]
+    fn teardown_completes_before_regeneration_begins() {
+        let mut app = rebuild_app();
+        app.world_mut()
+            .send_event(WorldRebuildRequest::regenerate().with_seed(11));
+
+        app.update();
+        assert_eq!(stage(&app), RebuildStage::Teardown);
+        assert!(app.world().resource::<WorldRebuild>().is_frozen());
+        assert!(app.world().resource::<Probe>().0.is_empty());
+
+        // The old world is still whole while it is torn down
+        app.update();
+        assert_eq!(app.world().resource::<Probe>().0, vec!["teardown Some(7)"]);
+        assert_eq!(seed(&app), Some(7));
+
+        app.update();
+        assert_eq!(seed(&app), None);
+        app.update();
+        assert_eq!(seed(&app), Some(11));
+        assert_eq!(stage(&app), RebuildStage::Resume);
+
+        app.update();
+        assert_eq!(stage(&app), RebuildStage::Idle);
+        assert_eq!(
+            app.world().resource::<Probe>().0,
+            vec!["teardown Some(7)", "rebuilt 11"]
+        );
+        assert!(!app.world().resource::<WorldRebuild>().is_frozen());
+    }
+
+    #[test]
+    fn preserve_flags_choose_what_survives() {
+        let spawn = Position3D::new(-6, 2, 0);
+        let mut kept = rebuild_app();
+        kept.world_mut()
+            .send_event(WorldRebuildRequest::regenerate().at(spawn));
+        for _ in 0..5 {
+            kept.update();
+        }
+        let player = kept.world().resource::<PlayerResource>();
+        assert_eq!(player.get_player().unwrap().name(), "Rook");
+        assert_eq!(player.player_position(), Some(spawn));
+        let base = kept.world().resource::<BaseResource>().base().unwrap();
+        assert_eq!(base.name(), "Outpost");
+        assert_eq!(
+            kept.world()
+                .resource::<GameStatsResource>()
+                .quests_completed,
+            1
+        );
+        // The seed of the old world carries over
+        assert_eq!(seed(&kept), Some(7));
+
+        let mut fresh = rebuild_app();
+        fresh
+            .world_mut()
+            .send_event(
+                WorldRebuildRequest::regenerate()
+                    .at(spawn)
+                    .preserving(RebuildPreserve {
+                        player: false,
+                        base: false,
+                        stats: false,
+                    }),
+            );
+        for _ in 0..5 {
+            fresh.update();
+        }
+        let player = fresh.world().resource::<PlayerResource>();
+        assert_eq!(
+            player.get_player().unwrap().name(),
+            StartingCharacter::default().name
+        );
+        assert_eq!(player.player_position(), Some(spawn));
+        let base = fresh.world().resource::<BaseResource>().base().unwrap();
+        assert_eq!(base.name(), "Central Command");
+        assert_eq!(*base.position(), spawn);
+        assert_eq!(
+            fresh.world().resource::<RpgGameSession>().base.name(),
+            "Central Command"
+        );
+        assert_eq!(
+            fresh
+                .world()
+                .resource::<GameStatsResource>()
+                .quests_completed,
+            0
+        );
+    }
+
+    #[test]
+    fn overlapping_requests_coalesce() {
+        let mut app = rebuild_app();
+        app.world_mut()
+            .send_event(WorldRebuildRequest::regenerate().with_seed(11));
+        app.update();
+        // Before regeneration: folded into the running rebuild
+        app.world_mut()
+            .send_event(WorldRebuildRequest::regenerate().with_seed(12));
+        app.update();
+        app.update();
+        assert_eq!(stage(&app), RebuildStage::Regenerate);
+
+        // After it began: both wait and run as one more rebuild
+        app.world_mut()
+            .send_event(WorldRebuildRequest::regenerate().with_seed(13));
+        app.world_mut()
+            .send_event(WorldRebuildRequest::regenerate().with_seed(14));
+        app.update();
+        assert_eq!(seed(&app), Some(12));
+        assert!(app.world().resource::<WorldRebuild>().has_queued());
+
+        for _ in 0..8 {
+            app.update();
+        }
+        assert_eq!(stage(&app), RebuildStage::Idle);
+        assert_eq!(
+            app.world().resource::<Probe>().0,
+            vec![
+                "teardown Some(7)",
+                "rebuilt 12",
+                "teardown Some(12)",
+                "rebuilt 14"
+            ]
+        );
+    }
+}
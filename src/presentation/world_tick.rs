@@ -0,0 +1,259 @@
+//! World Tick - One ordered update of the world per player turn
+//!
+//! Everything simulated outside the player advances on [`WorldTick`] instead
+//! of polling the player position or counting rests on its own. A tick is
+//! sent after every applied move and after every night of rest, and only
+//! [`emit_world_ticks_system`] sends them. Consumers run in the fixed
+//! [`WorldTickSet`] order: hazards, hostiles, fauna, economy, objectives,
+//! and last the survival check, once every penalty of the turn is in.
+//!
+//! Each tick carries a turn number that never repeats, so a consumer that
+//! reads through a [`TickCursor`] acts on a delivered-twice tick only once.
+
+use crate::domain::value_objects::Position3D;
+use crate::infrastructure::bevy::resources::{GameStatsResource, PlayerResource};
+use crate::presentation::movement::MovementResultApplied;
+use bevy::prelude::*;
+
+/// Plugin for the world tick and the order of its consumers
+pub struct WorldTickPlugin;
+
+impl Plugin for WorldTickPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WorldClock>()
+            .add_event::<WorldTick>()
+            .add_event::<MovementResultApplied>()
+            .configure_sets(
+                Update,
+                (
+                    WorldTickSet::Emit,
+                    WorldTickSet::Hazards,
+                    WorldTickSet::Hostiles,
+                    WorldTickSet::Fauna,
+                    WorldTickSet::Economy,
+                    WorldTickSet::Objectives,
+                    WorldTickSet::Survival,
+                )
+                    .chain(),
+            )
+            .add_systems(Update, emit_world_ticks_system.in_set(WorldTickSet::Emit));
+    }
+}
+
+/// What ended the turn a tick belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TickPhase {
+    AfterPlayerMove,
+    AfterRest,
+}
+
+/// The world advancing by one player turn
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorldTick {
+    pub phase: TickPhase,
+    /// Turn number, counting up from 1 for the whole app run
+    pub turn: u64,
+    /// Day of the run the turn ended on
+    pub day: u32,
+    /// Where the player ended the turn, if there is a player
+    pub position: Option<Position3D>,
+}
+
+/// Order in which tick consumers run, after the tick is sent
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WorldTickSet {
+    Emit,
+    Hazards,
+    Hostiles,
+    Fauna,
+    Economy,
+    Objectives,
+    Survival,
+}
+
+/// Hands out turn numbers and notices finished rests
+#[derive(Resource, Debug, Default)]
+pub struct WorldClock {
+    turn: u64,
+    nights_seen: Option<u32>,
+}
+
+impl WorldClock {
+    /// Turn number of the last tick
+    pub fn turn(&self) -> u64 {
+        self.turn
+    }
+
+    /// The next tick
+    pub fn tick(&mut self, phase: TickPhase, day: u32, position: Option<Position3D>) -> WorldTick {
+        self.turn += 1;
+        WorldTick {
+            phase,
+            turn: self.turn,
+            day,
+            position,
+        }
+    }
+
+    /// Nights rested since the last call; the first call only takes a count
+    pub fn new_rests(&mut self, nights_rested: u32) -> u32 {
+        let seen = self.nights_seen.replace(nights_rested);
+        match seen {
+            // A reset run starts counting from zero again
+            Some(seen) if nights_rested > seen => nights_rested - seen,
+            _ => 0,
+        }
+    }
+}
+
+/// The last turn a consumer acted on
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TickCursor {
+    last_turn: u64,
+}
+
+impl TickCursor {
+    /// Whether `tick` is new to this consumer, moving the cursor past it
+    pub fn accept(&mut self, tick: &WorldTick) -> bool {
+        if tick.turn <= self.last_turn {
+            return false;
+        }
+        self.last_turn = tick.turn;
+        true
+    }
+
+    /// The new ticks of `phase`, in turn order
+    pub fn take<'a>(
+        &mut self,
+        ticks: impl IntoIterator<Item = &'a WorldTick>,
+        phase: TickPhase,
+    ) -> Vec<WorldTick> {
+        ticks
+            .into_iter()
+            .filter(|tick| tick.phase == phase)
+            .filter(|tick| self.accept(tick))
+            .copied()
+            .collect()
+    }
+}
+
+/// Send one tick per night rested and per applied move, rests first
+fn emit_world_ticks_system(
+    mut applied_events: EventReader<MovementResultApplied>,
+    game_stats: Res<GameStatsResource>,
+    player_resource: Res<PlayerResource>,
+    mut clock: ResMut<WorldClock>,
+    mut ticks: EventWriter<WorldTick>,
+) {
+    let day = game_stats.current_day();
+    let rests = clock.new_rests(game_stats.nights_rested);
+    for _ in 0..rests {
+        let tick = clock.tick(TickPhase::AfterRest, day, player_resource.player_position());
+        ticks.write(tick);
+    }
+    for applied in applied_events.read() {
+        let tick = clock.tick(
+            TickPhase::AfterPlayerMove,
+            day,
+            Some(applied.final_position),
+        );
+        ticks.write(tick);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ticks, or consumer names, in the order they were seen
+    #[derive(Resource, Default)]
+    struct Probe(Vec<String>);
+
+    fn tick_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(WorldTickPlugin)
+            .insert_resource(GameStatsResource::new())
+            .insert_resource(PlayerResource::new())
+            .init_resource::<Probe>();
+        app
+    }
+
+    fn record_ticks(mut ticks: EventReader<WorldTick>, mut probe: ResMut<Probe>) {
+        for tick in ticks.read() {
+            probe.0.push(format!("{:?} {}", tick.phase, tick.turn));
+        }
+    }
+
+    #[test]
+    fn one_tick_per_move_and_per_rest() {
+        let mut app = tick_app();
+        app.add_systems(Update, record_ticks.after(WorldTickSet::Emit));
+        app.update();
+
+        app.world_mut().send_event(MovementResultApplied {
+            final_position: Position3D::origin(),
+            event_title: None,
+        });
+        app.update();
+        app.world_mut()
+            .resource_mut::<GameStatsResource>()
+            .record_rest();
+        app.update();
+        app.update();
+
+        assert_eq!(
+            app.world().resource::<Probe>().0,
+            vec!["AfterPlayerMove 1", "AfterRest 2"]
+        );
+    }
+
+    #[test]
+    fn consumers_run_in_set_order() {
+        let mut app = tick_app();
+        for (set, name) in [
+            (WorldTickSet::Objectives, "objectives"),
+            (WorldTickSet::Economy, "economy"),
+            (WorldTickSet::Fauna, "fauna"),
+            (WorldTickSet::Hostiles, "hostiles"),
+            (WorldTickSet::Hazards, "hazards"),
+        ] {
+            app.add_systems(
+                Update,
+                (move |mut ticks: EventReader<WorldTick>, mut probe: ResMut<Probe>| {
+                    if ticks.read().count() > 0 {
+                        probe.0.push(name.to_string());
+                    }
+                })
+                .in_set(set),
+            );
+        }
+
+        app.world_mut().send_event(MovementResultApplied {
+            final_position: Position3D::origin(),
+            event_title: None,
+        });
+        app.update();
+
+        assert_eq!(
+            app.world().resource::<Probe>().0,
+            vec!["hazards", "hostiles", "fauna", "economy", "objectives"]
+        );
+    }
+
+    #[test]
+    fn a_duplicated_tick_is_acted_on_once() {
+        let mut clock = WorldClock::default();
+        let rest = clock.tick(TickPhase::AfterRest, 2, None);
+        let step = clock.tick(TickPhase::AfterPlayerMove, 2, Some(Position3D::origin()));
+
+        let mut cursor = TickCursor::default();
+        assert_eq!(
+            cursor.take(&[rest, rest, step], TickPhase::AfterRest),
+            vec![rest]
+        );
+        assert!(cursor.take(&[rest], TickPhase::AfterRest).is_empty());
+        assert!(cursor.accept(&step));
+        assert!(!cursor.accept(&step));
+    }
+}
@@ -0,0 +1,333 @@
+//! Wrecks - Salvaging what won fights leave behind
+//!
+//! A won fight leaves a wreck on its tile, drawn as a smouldering hull and
+//! marked orange on the sector scanner. Standing on it, U spends a movement
+//! point to salvage it with an Intelligence check; what is left is debris,
+//! drawn as a low pile of scrap until enough rests have passed. Wrecks live
+//! in the session, so they are saved with the run.
+
+use crate::domain::constants::{WRECK_SALVAGE_COST, WRECK_SIGNATURE};
+use crate::domain::services::game_log_service::{GameLogService, GameLogType};
+use crate::domain::services::{SalvageYield, WreckField};
+use crate::domain::value_objects::{Position3D, ResourceCollection, StatType};
+use crate::infrastructure::bevy::resources::{GameStatsResource, MapResource, PlayerResource};
+use crate::infrastructure::random::streams::{RngStreams, LOOT};
+use crate::presentation::field_trade::TraderContact;
+use crate::presentation::game_state::RpgGameSession;
+use crate::presentation::inventory::gather_find;
+use crate::presentation::reputation::HostileContact;
+use crate::presentation::world_rebuild::WorldTeardown;
+use crate::presentation::world_tick::{TickCursor, TickPhase, WorldTick, WorldTickSet};
+use crate::presentation::RpgAppState;
+use bevy::prelude::*;
+use rand::Rng;
+
+/// Key that salvages the wreck under the player
+pub const SALVAGE_KEY: KeyCode = KeyCode::KeyU;
+
+/// Plugin for leaving, salvaging and clearing wrecks
+pub struct WrecksPlugin;
+
+impl Plugin for WrecksPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RngStreams>()
+            .add_systems(
+                Update,
+                (
+                    clear_debris_system.in_set(WorldTickSet::Economy),
+                    wreck_arrival_system.in_set(WorldTickSet::Objectives),
+                    salvage_wreck_system,
+                    sync_wreck_markers,
+                )
+                    .chain(),
+            )
+            .add_systems(WorldTeardown, clear_wrecks_system);
+    }
+}
+
+/// A wreck or pile of debris drawn on the map
+#[derive(Component)]
+pub struct WreckMarker;
+
+/// Clear the wrecks and their markers off a world being rebuilt
+fn clear_wrecks_system(
+    mut commands: Commands,
+    mut session: ResMut<RpgGameSession>,
+    markers: Query<Entity, With<WreckMarker>>,
+) {
+    session.wrecks = WreckField::new();
+    for entity in markers.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Count the debris down once per night of rest
+fn clear_debris_system(
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    mut session: ResMut<RpgGameSession>,
+) {
+    for _ in cursor.take(ticks.read(), TickPhase::AfterRest) {
+        let cleared = session.wrecks.rest();
+        if cleared > 0 {
+            info!("🧹 {} pile(s) of wreck debris cleared away", cleared);
+        }
+    }
+}
+
+/// Point out a wreck the player has just stepped onto
+fn wreck_arrival_system(
+    mut ticks: EventReader<WorldTick>,
+    mut cursor: Local<TickCursor>,
+    session: Res<RpgGameSession>,
+    mut game_log: ResMut<GameLogService>,
+) {
+    let Some(position) = cursor
+        .take(ticks.read(), TickPhase::AfterPlayerMove)
+        .last()
+        .and_then(|tick| tick.position)
+    else {
+        return;
+    };
+    if let Some(wreck) = session.wrecks.at(position) {
+        game_log.log_message(
+            format!(
+                "🛰️ A {} lies here - U to salvage it for {} movement point",
+                wreck.origin.name(),
+                WRECK_SALVAGE_COST
+            ),
+            GameLogType::Discovery,
+        );
+    }
+}
+
+/// Salvage the wreck under the player on U
+#[allow(clippy::too_many_arguments)]
+fn salvage_wreck_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_state: Res<State<RpgAppState>>,
+    map_resource: Res<MapResource>,
+    contact: Res<HostileContact>,
+    trader: Res<TraderContact>,
+    mut player_resource: ResMut<PlayerResource>,
+    mut game_stats: ResMut<GameStatsResource>,
+    mut session: ResMut<RpgGameSession>,
+    mut game_log: ResMut<GameLogService>,
+    rng_streams: ResMut<RngStreams>,
+) {
+    if !keyboard.just_pressed(SALVAGE_KEY)
+        || *current_state.get() != RpgAppState::Exploration
+        || map_resource.is_in_interior()
+        || contact.is_pending()
+        || trader.is_pending()
+    {
+        return;
+    }
+    let Some(position) = player_resource.player_position() else {
+        return;
+    };
+    if session.wrecks.at(position).is_none() {
+        game_log.log_message(
+            "There is nothing here to salvage".to_string(),
+            GameLogType::Warning,
+        );
+        return;
+    }
+    if let Err(e) = player_resource.try_spend_movement_points(WRECK_SALVAGE_COST) {
+        game_log.log_message(format!("{}", e), GameLogType::Warning);
+        return;
+    }
+
+    let intelligence = player_resource
+        .get_player()
+        .map(|player| player.get_stat_modifier(StatType::Intelligence))
+        .unwrap_or(0);
+    let roll = rng_streams.stream(LOOT).gen_range(1..=20);
+    let salvage_yield = SalvageYield::from_check(roll, intelligence);
+    let Some(loot) = session.wrecks.salvage(position, salvage_yield) else {
+        return;
+    };
+
+    let mut salvaged = ResourceCollection::new();
+    for &(resource_type, amount) in &loot {
+        let amount = game_stats.modifiers.resource_yield(resource_type, amount);
+        salvaged.set_amount(resource_type, amount);
+    }
+    game_log.log_message(
+        format!(
+            "🔧 Salvage check {} ({:+}): {} - {}",
+            roll,
+            intelligence,
+            salvage_yield.describe(),
+            crate::format_resource_summary(&salvaged)
+        ),
+        GameLogType::Resources,
+    );
+    gather_find(
+        &mut player_resource,
+        &salvaged,
+        &mut game_stats,
+        &mut game_log,
+    );
+}
+
+/// Draw every wreck and pile of debris on the surface
+fn sync_wreck_markers(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    session: Res<RpgGameSession>,
+    map_resource: Res<MapResource>,
+    markers: Query<Entity, With<WreckMarker>>,
+    mut rendered: Local<Option<Vec<(Position3D, bool)>>>,
+) {
+    let wanted: Vec<(Position3D, bool)> = if map_resource.is_in_interior() {
+        Vec::new()
+    } else {
+        let wrecks = session.wrecks.wrecks().iter().map(|w| (w.position, true));
+        let debris = session.wrecks.debris().iter().map(|d| (d.position, false));
+        wrecks.chain(debris).collect()
+    };
+    if rendered.as_ref() == Some(&wanted) {
+        return;
+    }
+
+    for entity in markers.iter() {
+        commands.entity(entity).despawn();
+    }
+    for &(position, is_wreck) in &wanted {
+        let world = crate::presentation::movement::tile_to_world_position(position);
+        let (mesh, material, height, name) = if is_wreck {
+            (
+                Cuboid::new(0.9, 0.3, 0.5),
+                StandardMaterial {
+                    base_color: Color::srgb(0.25, 0.25, 0.28),
+                    emissive: LinearRgba::from(WRECK_SIGNATURE) * 0.6,
+                    metallic: 0.8,
+                    ..default()
+                },
+                0.75,
+                "Wreck",
+            )
+        } else {
+            (
+                Cuboid::new(0.6, 0.08, 0.45),
+                StandardMaterial {
+                    base_color: Color::srgb(0.4, 0.38, 0.36),
+                    metallic: 0.5,
+                    ..default()
+                },
+                0.64,
+                "WreckDebris",
+            )
+        };
+        commands.spawn((
+            Mesh3d(meshes.add(Mesh::from(mesh))),
+            MeshMaterial3d(materials.add(material)),
+            Transform::from_translation(Vec3::new(world.x, height, world.z))
+                .with_rotation(Quat::from_rotation_y(0.6)),
+            WreckMarker,
+            Name::new(name),
+        ));
+    }
+    *rendered = Some(wanted);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::use_cases::{
+        EncounterApproach, EncounterFlags, EncounterOutcome, RollTier,
+    };
+    use crate::domain::value_objects::ResourceType;
+    use crate::domain::{Base, EntityId, Player};
+    use bevy::ecs::system::RunSystemOnce;
+
+    fn fight(tier: RollTier) -> EncounterOutcome {
+        EncounterOutcome {
+            approach: EncounterApproach::Fight,
+            tier,
+            roll: 15,
+            total: 17,
+            difficulty: 12,
+            damage_dealt: 6,
+            damage_taken: 0,
+            loot: if tier.is_success() {
+                vec![(ResourceType::Metal, 14)]
+            } else {
+                Vec::new()
+            },
+            fled: false,
+            flags: EncounterFlags::default(),
+            experience: 0,
+        }
+    }
+
+    #[test]
+    fn won_fights_leave_a_wreck_instead_of_paying_out() {
+        let mut world = World::new();
+        let mut player_resource = PlayerResource::new();
+        player_resource
+            .create_player(
+                "wreck_player".to_string(),
+                "Wreck Player".to_string(),
+                Position3D::origin(),
+                crate::domain::PlayerStats::new(10, 10, 10, 10, 10, 10).unwrap(),
+            )
+            .unwrap();
+        world.insert_resource(player_resource);
+        world.insert_resource(GameStatsResource::new());
+        world.insert_resource(GameLogService::new());
+        world.insert_resource(RpgGameSession::new(
+            Player::create_new_character("Wreck Player".to_string(), Position3D::origin()).unwrap(),
+            Base::new(
+                EntityId::generate(),
+                "Outpost".to_string(),
+                Position3D::origin(),
+            )
+            .unwrap(),
+        ));
+
+        let field = Position3D::new(3, 2, 0);
+        world
+            .run_system_once(
+                move |mut player_resource: ResMut<PlayerResource>,
+                      mut game_stats: ResMut<GameStatsResource>,
+                      mut game_log: ResMut<GameLogService>,
+                      mut session: ResMut<RpgGameSession>| {
+                    for (tier, position) in [
+                        (RollTier::Success, field),
+                        (RollTier::Failure, Position3D::new(5, 5, 0)),
+                        (RollTier::CriticalSuccess, Position3D::origin()),
+                    ] {
+                        crate::apply_encounter_outcome(
+                            &fight(tier),
+                            position,
+                            &mut player_resource,
+                            &mut game_stats,
+                            &mut game_log,
+                            &mut session,
+                        );
+                    }
+                },
+            )
+            .unwrap();
+
+        let session = world.resource::<RpgGameSession>();
+        let wrecks = session.wrecks.wrecks();
+        assert_eq!(wrecks.len(), 2);
+        assert_eq!(wrecks[0].position, field);
+        assert_eq!(
+            wrecks[0].origin,
+            crate::domain::services::WreckOrigin::Hostiles
+        );
+        assert_eq!(wrecks[0].loot, vec![(ResourceType::Metal, 14)]);
+        // Driving raiders off at the base leaves a raider wreck there
+        assert_eq!(wrecks[1].origin, crate::domain::services::WreckOrigin::Raid);
+        // Nothing was handed over yet
+        let game_stats = world.resource::<GameStatsResource>();
+        assert!(!game_stats
+            .resources_gathered
+            .contains_key(&ResourceType::Metal));
+    }
+}
@@ -0,0 +1,60 @@
+// Space Looter service worker
+//
+// Precaches the game for offline play. The page hands over the asset
+// manifest exported by the game (get_asset_manifest) together with the
+// build it belongs to; each build gets its own cache and older ones are
+// dropped once the new one is complete. Requests are answered from the
+// cache first, ignoring the ?v= cache busting query.
+
+const CACHE_PREFIX = 'space-looter-';
+const CORE_FILES = ['./', './index.html', './space_looter.js', './space_looter_bg.wasm'];
+
+self.addEventListener('install', () => self.skipWaiting());
+self.addEventListener('activate', (event) => event.waitUntil(self.clients.claim()));
+
+async function currentCache() {
+    const names = (await caches.keys()).filter((name) => name.startsWith(CACHE_PREFIX));
+    if (names.length === 0) return null;
+    const name = names[names.length - 1];
+    const cache = await caches.open(name);
+    return { build: name.slice(CACHE_PREFIX.length), assets: (await cache.keys()).length };
+}
+
+async function precache(manifest) {
+    const name = CACHE_PREFIX + manifest.build;
+    const cache = await caches.open(name);
+    // Optional assets may be missing from a deployment; cache what exists
+    await Promise.all(
+        CORE_FILES.concat(manifest.assets).map((url) => cache.add(url).catch(() => undefined))
+    );
+    for (const old of await caches.keys()) {
+        if (old.startsWith(CACHE_PREFIX) && old !== name) await caches.delete(old);
+    }
+    return { build: manifest.build, assets: (await cache.keys()).length };
+}
+
+self.addEventListener('message', (event) => {
+    const data = event.data || {};
+    const reply = (message) => event.source && event.source.postMessage(message);
+    if (data.type === 'status') {
+        event.waitUntil(
+            currentCache().then((status) => reply({ type: 'cache-status', ...(status || {}) }))
+        );
+    } else if (data.type === 'precache' && data.manifest) {
+        event.waitUntil(
+            precache(data.manifest)
+                .then((status) => reply({ type: 'precached', ...status }))
+                .catch((error) => reply({ type: 'cache-failed', reason: String(error) }))
+        );
+    }
+});
+
+self.addEventListener('fetch', (event) => {
+    const request = event.request;
+    if (request.method !== 'GET' || new URL(request.url).origin !== self.location.origin) return;
+    event.respondWith(
+        caches
+            .match(request, { ignoreSearch: true })
+            .then((cached) => cached || fetch(request))
+    );
+});